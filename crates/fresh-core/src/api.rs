@@ -272,6 +272,9 @@ pub struct BufferInfo {
     pub modified: bool,
     /// Length of buffer in bytes
     pub length: usize,
+    /// Revision of the buffer contents, advanced by every change
+    #[ts(type = "number")]
+    pub revision: u64,
 }
 
 fn serialize_path<S: serde::Serializer>(path: &Option<PathBuf>, s: S) -> Result<S::Ok, S::Error> {
@@ -729,6 +732,18 @@ pub enum MenuPosition {
     After(String),
 }
 
+/// Something a sandboxed plugin needs permission for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub enum PermissionRequest {
+    /// Read a file or directory
+    Read(PathBuf),
+    /// Write a file
+    Write(PathBuf),
+    /// Run a process
+    Subprocess(String),
+}
+
 /// Plugin command - allows plugins to send commands to the editor
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -819,6 +834,16 @@ pub enum PluginCommand {
     /// Kill a background process by ID
     KillBackgroundProcess { process_id: u64 },
 
+    /// Ask the user whether a plugin may do something its permissions don't
+    /// cover. `then` runs if it's allowed; if it's denied, `callback_id` (if
+    /// any) is rejected.
+    RequestPermission {
+        plugin_name: String,
+        request: PermissionRequest,
+        callback_id: Option<JsCallbackId>,
+        then: Option<Box<PluginCommand>>,
+    },
+
     /// Wait for a process to complete and get its result
     /// Used with processes started via SpawnProcess
     SpawnProcessWait {
@@ -1266,6 +1291,16 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Apply text edits to several files, all or none (async)
+    /// Resolves with the number of edits applied
+    ApplyEdits {
+        files: Vec<JsFileEdits>,
+        /// Description of the undo step of each buffer
+        description: String,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
     /// Get byte offset of the start of a line (async)
     /// Line is 0-indexed (0 = first line)
     GetLineStartPosition {
@@ -1649,6 +1684,33 @@ pub struct JsDiagnostic {
     pub source: Option<String>,
 }
 
+/// Text edit of a document, positioned like an LSP text edit
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct JsTextEdit {
+    /// Range replaced; characters are UTF-16 code units
+    pub range: JsRange,
+    /// Text replacing the range
+    pub new_text: String,
+    /// Text the range must hold; if it doesn't, no edit is applied
+    #[serde(default)]
+    #[ts(optional)]
+    pub old_text: Option<String>,
+}
+
+/// Text edits of a file, for applyEdits
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct JsFileEdits {
+    /// Path of the file, opened if it isn't already
+    #[ts(type = "string")]
+    pub path: PathBuf,
+    /// Edits positioned relative to the file's contents before any is applied
+    pub edits: Vec<JsTextEdit>,
+}
+
 /// Options for createVirtualBuffer
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -1800,6 +1862,16 @@ mod fromjs_impls {
         }
     }
 
+    impl<'js> FromJs<'js> for JsFileEdits {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "JsFileEdits",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
     impl<'js> FromJs<'js> for CreateVirtualBufferOptions {
         fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
             rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
@@ -2495,6 +2567,7 @@ mod tests {
                 path: Some(std::path::PathBuf::from("/test/file.txt")),
                 modified: true,
                 length: 100,
+                revision: 0,
             };
             snapshot.buffers.insert(BufferId(1), buffer_info);
         }
@@ -2534,6 +2607,7 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file1.txt")),
                     modified: false,
                     length: 50,
+                    revision: 0,
                 },
             );
             snapshot.buffers.insert(
@@ -2543,6 +2617,7 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file2.txt")),
                     modified: true,
                     length: 100,
+                    revision: 0,
                 },
             );
             snapshot.buffers.insert(
@@ -2552,6 +2627,7 @@ mod tests {
                     path: None,
                     modified: false,
                     length: 0,
+                    revision: 0,
                 },
             );
         }
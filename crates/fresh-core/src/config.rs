@@ -22,6 +22,47 @@ pub struct PluginConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(extend("readOnly" = true))]
     pub path: Option<PathBuf>,
+
+    /// What the plugin may do beyond its own directory.
+    /// Only enforced when `editor.plugin_sandbox` is on.
+    #[serde(default, skip_serializing_if = "PluginPermissions::is_default")]
+    pub permissions: PluginPermissions,
+}
+
+/// Whether a plugin may do something
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionMode {
+    /// Ask the first time the plugin tries
+    #[default]
+    Ask,
+    /// Always allow
+    Allow,
+    /// Never allow
+    Deny,
+}
+
+/// The permissions granted to a plugin
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PluginPermissions {
+    /// Running processes (`spawnProcess`, `spawnBackgroundProcess`).
+    /// Plugins reach the network through processes, so this covers it too.
+    #[serde(default)]
+    pub subprocess: PermissionMode,
+
+    /// Directories the plugin may read, besides its own
+    #[serde(default)]
+    pub read: Vec<PathBuf>,
+
+    /// Directories the plugin may write to, besides its own
+    #[serde(default)]
+    pub write: Vec<PathBuf>,
+}
+
+impl PluginPermissions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Default for PluginConfig {
@@ -29,6 +70,7 @@ impl Default for PluginConfig {
         Self {
             enabled: true,
             path: None,
+            permissions: PluginPermissions::default(),
         }
     }
 }
@@ -38,6 +80,7 @@ impl PluginConfig {
         Self {
             enabled: true,
             path: Some(path),
+            permissions: PluginPermissions::default(),
         }
     }
 }
@@ -76,6 +76,17 @@ pub enum HookArgs {
         lines_removed: usize,
     },
 
+    /// A buffer's contents changed (fired at most once per buffer per
+    /// editor tick, for all the changes since the last time)
+    BufferChanged {
+        buffer_id: BufferId,
+        /// The buffer revision after the changes
+        revision: u64,
+        /// The edits in the order they were applied, or None if they aren't
+        /// known and the contents should be read again
+        edits: Option<Vec<EditInfo>>,
+    },
+
     /// Cursor moved to a new position
     CursorMoved {
         buffer_id: BufferId,
@@ -271,6 +282,17 @@ pub struct LineInfo {
     pub content: String,
 }
 
+/// A single edit for the BufferChanged hook
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EditInfo {
+    /// Whether bytes were inserted, rather than deleted
+    pub inserted: bool,
+    /// Byte offset of the edit
+    pub offset: usize,
+    /// Number of bytes inserted or deleted
+    pub length: usize,
+}
+
 /// Location information for LSP references
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LspLocation {
@@ -379,6 +401,29 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
         HookArgs::BufferClosed { buffer_id } => {
             serde_json::json!({ "buffer_id": buffer_id.0 })
         }
+        HookArgs::BufferChanged {
+            buffer_id,
+            revision,
+            edits,
+        } => {
+            let edits_json: Vec<serde_json::Value> = edits
+                .iter()
+                .flatten()
+                .map(|edit| {
+                    serde_json::json!({
+                        "kind": if edit.inserted { "insert" } else { "delete" },
+                        "offset": edit.offset,
+                        "length": edit.length,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "buffer_id": buffer_id.0,
+                "revision": revision,
+                "edits": edits_json,
+                "reset": edits.is_none(),
+            })
+        }
         HookArgs::CursorMoved {
             buffer_id,
             cursor_id,
@@ -1,4 +1,25 @@
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::api::PermissionRequest;
+
+/// Whether a plugin may do something
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionDecision {
+    Allow,
+    Deny,
+    /// Not decided yet: the user has to be asked
+    Ask,
+}
+
+/// Resource limits for the plugin runtime
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PluginLimits {
+    /// Heap size limit in bytes, shared by all plugins
+    pub memory_limit: Option<usize>,
+    /// How long a plugin may run before it's interrupted
+    pub time_slice: Option<Duration>,
+}
 
 /// Trait for the editor to provide services to the plugin runtime
 /// without the runtime depending directly on UI or complex system logic.
@@ -52,6 +73,20 @@ pub trait PluginServiceBridge: Send + Sync + 'static {
 
     /// Get the config directory path
     fn config_dir(&self) -> std::path::PathBuf;
+
+    /// Whether a plugin may read or write a path, or run a process
+    fn check_permission(
+        &self,
+        _plugin_name: &str,
+        _request: &PermissionRequest,
+    ) -> PermissionDecision {
+        PermissionDecision::Allow
+    }
+
+    /// Resource limits for the plugin runtime
+    fn plugin_limits(&self) -> PluginLimits {
+        PluginLimits::default()
+    }
 }
 
 /// A no-op implementation of the service bridge for testing
@@ -177,7 +177,8 @@ fn collect_theme_files(
     Ok(())
 }
 
-/// Generate a Rust file with the list of available locales from the locales directory
+/// Generate Rust files with the list of available locales from the locales
+/// directory, and with the translation files embedded
 fn generate_locale_options() -> Result<(), Box<dyn std::error::Error>> {
     let locales_dir = Path::new("locales");
 
@@ -221,6 +222,29 @@ pub const GENERATED_LOCALE_OPTIONS: &[Option<&str>] = &[
     // but it doesn't hurt to use it for consistency
     fs::write(&dest_path, content)?;
 
+    // Embed the files themselves for i18n::EmbeddedTranslations
+    let embedded_entries: Vec<String> = locales
+        .iter()
+        .map(|l| {
+            format!(
+                r#"    ("{0}", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/{0}.json")))"#,
+                l
+            )
+        })
+        .collect();
+    let content = format!(
+        r#"// Auto-generated by build.rs from locales/*.json files
+// DO NOT EDIT MANUALLY
+
+/// Every locale and the JSON of its translation file
+pub const EMBEDDED_LOCALES: &[(&str, &str)] = &[
+{}
+];
+"#,
+        embedded_entries.join(",\n")
+    );
+    fs::write(Path::new(&out_dir).join("embedded_locales.rs"), content)?;
+
     println!(
         "cargo::warning=Generated locale options with {} locales",
         locales.len()
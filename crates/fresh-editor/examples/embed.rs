@@ -0,0 +1,164 @@
+//! A ratatui application hosting a Fresh editor pane
+//!
+//! The left column belongs to the host: it lists the files given on the
+//! command line. The editor fills the rest of the screen. Tab on the list
+//! (or F2 anywhere) switches focus; Enter opens the selected file in the
+//! editor. Quitting the editor quits the application.
+//!
+//! Run with: `cargo run --example embed -- src/main.rs src/lib.rs`
+
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event, KeyCode, KeyEventKind,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use fresh::embed::{Config, DirectoryContext, EditorPane};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, List, ListState};
+use ratatui::{Frame, Terminal};
+
+/// Which side of the screen gets key presses
+#[derive(PartialEq)]
+enum Focus {
+    Files,
+    Editor,
+}
+
+struct App {
+    files: Vec<PathBuf>,
+    selected: ListState,
+    focus: Focus,
+    pane: EditorPane,
+}
+
+impl App {
+    fn draw(&mut self, frame: &mut Frame) {
+        let [files_area, editor_area] =
+            Layout::horizontal([Constraint::Length(30), Constraint::Min(20)]).areas(frame.area());
+
+        let border = if self.focus == Focus::Files {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        };
+        let names = self
+            .files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        let list = List::new(names)
+            .block(
+                Block::default()
+                    .title(" Files (F2: focus) ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(border)),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, files_area, &mut self.selected);
+
+        self.pane.render(frame, editor_area);
+    }
+
+    /// Handle an event. Returns whether the screen needs drawing.
+    fn handle_event(&mut self, event: Event) -> AnyhowResult<bool> {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press && key.code == KeyCode::F(2) => {
+                self.focus = match self.focus {
+                    Focus::Files => Focus::Editor,
+                    Focus::Editor => Focus::Files,
+                };
+            }
+            Event::Key(key) if self.focus == Focus::Editor => self.pane.handle_key(key)?,
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Up => self.selected.select_previous(),
+                KeyCode::Down => self.selected.select_next(),
+                KeyCode::Tab => self.focus = Focus::Editor,
+                KeyCode::Enter => {
+                    if let Some(path) = self.selected.selected().and_then(|i| self.files.get(i)) {
+                        self.pane.open_file(path)?;
+                        self.focus = Focus::Editor;
+                    }
+                }
+                _ => return Ok(false),
+            },
+            Event::Mouse(mouse) => {
+                // Clicking the editor gives it focus
+                if self.pane.handle_mouse(mouse)? {
+                    self.focus = Focus::Editor;
+                    return Ok(true);
+                }
+                return Ok(false);
+            }
+            Event::Paste(text) if self.focus == Focus::Editor => self.pane.paste(text),
+            Event::Resize(..) => {}
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: App) -> AnyhowResult<()> {
+    let mut needs_draw = true;
+    loop {
+        if app.pane.update() {
+            needs_draw = true;
+        }
+        if app.pane.should_quit() {
+            return Ok(());
+        }
+        if needs_draw {
+            terminal.draw(|frame| app.draw(frame))?;
+            needs_draw = false;
+        }
+        if event::poll(Duration::from_millis(16))? && app.handle_event(event::read()?)? {
+            needs_draw = true;
+        }
+    }
+}
+
+fn main() -> AnyhowResult<()> {
+    let files: Vec<PathBuf> = std::env::args().skip(1).map(PathBuf::from).collect();
+
+    let mut pane = EditorPane::new(Config::default(), DirectoryContext::from_system()?)?;
+    if let Some(first) = files.first() {
+        pane.open_file(first)?;
+    }
+    let app = App {
+        selected: ListState::default().with_selected((!files.is_empty()).then_some(0)),
+        files,
+        focus: Focus::Editor,
+        pane,
+    };
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run(&mut terminal, app);
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+    result
+}
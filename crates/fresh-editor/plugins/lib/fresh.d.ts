@@ -250,6 +250,10 @@ type BufferInfo = {
 	* Length of buffer in bytes
 	*/
 	length: number;
+	/**
+	* Revision of the buffer contents, advanced by every change
+	*/
+	revision: number;
 };
 type JsDiagnostic = {
 	/**
@@ -293,6 +297,30 @@ type JsPosition = {
 	*/
 	character: number;
 };
+type JsTextEdit = {
+	/**
+	* Range replaced; characters are UTF-16 code units
+	*/
+	range: JsRange;
+	/**
+	* Text replacing the range
+	*/
+	newText: string;
+	/**
+	* Text the range must hold; if it doesn't, no edit is applied
+	*/
+	oldText?: string;
+};
+type JsFileEdits = {
+	/**
+	* Path of the file, opened if it isn't already
+	*/
+	path: string;
+	/**
+	* Edits positioned relative to the file's contents before any is applied
+	*/
+	edits: Array<JsTextEdit>;
+};
 type ActionSpec = {
 	/**
 	* Action name (e.g., "move_word_right", "delete_line")
@@ -702,6 +730,10 @@ interface EditorAPI {
 	*/
 	isBufferModified(bufferId: number): boolean;
 	/**
+	* Get the revision of a buffer's contents, advanced by every change
+	*/
+	getBufferRevision(bufferId: number): number;
+	/**
 	* Save a buffer to a specific file path
 	* Used by :w filename to save unnamed buffers or save-as
 	*/
@@ -1148,6 +1180,14 @@ interface EditorAPI {
 	*/
 	getBufferText(bufferId: number, start: number, end: number): Promise<string>;
 	/**
+	* Apply text edits to several files, all or none (async, returns request_id)
+	* 
+	* Each file's edits are a single undo step named `description`. Rejects
+	* without changing anything if the plugin may not write a file, a file
+	* is read-only, edits overlap, or an edit's `oldText` doesn't match.
+	*/
+	applyEdits(files: JsFileEdits[], description: string): Promise<number>;
+	/**
 	* Delay/sleep (async, returns request_id)
 	*/
 	delay(durationMs: number): Promise<void>;
@@ -0,0 +1,329 @@
+/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+
+/**
+ * Search and Edit Plugin
+ *
+ * Searches the git-tracked files of the project into an editable buffer with
+ * one line per match, like wgrep. Edit the text of the lines, then Ctrl+S
+ * writes the changed lines back to their files.
+ *
+ * - A line is only written if its file still holds the text it had when
+ *   searching; otherwise nothing is written and the search must be run again
+ * - Files open with unsaved changes are edited in their buffer but not saved
+ * - Lines can't be added or removed: each line stays the line it was found on
+ */
+
+import { parseGrepLine } from "./lib/finder.ts";
+
+interface SearchMatch {
+  /** Path relative to the working directory, as git grep prints it */
+  file: string;
+  line: number;
+  column: number;
+  /** The line's text, without a carriage return */
+  content: string;
+}
+
+// Maximum number of matches in the buffer
+const MAX_RESULTS = 1000;
+
+// Lines before the first match in the buffer
+const HEADER_LINES = 2;
+
+// State
+let resultsBufferId: number | null = null;
+let sourceSplitId: number | null = null;
+let matches: SearchMatch[] = [];
+let searchPattern = "";
+
+// Editable, with Ctrl+S writing the changes back and Enter opening a match
+editor.defineMode(
+  "search-edit",
+  "normal",
+  [
+    ["C-s", "search_edit_write"],
+    ["Return", "search_edit_open"],
+  ],
+  false // editable
+);
+
+/** The text before a match's content on its line */
+function prefix(match: SearchMatch): string {
+  return `${match.file}:${match.line}: `;
+}
+
+function buildEntries(): TextPropertyEntry[] {
+  const limited = matches.length >= MAX_RESULTS
+    ? " " + editor.t("panel.limited", { max: String(MAX_RESULTS) })
+    : "";
+  const entries: TextPropertyEntry[] = [
+    {
+      text: editor.t("panel.header", {
+        pattern: searchPattern,
+        count: String(matches.length),
+      }) + limited + "\n",
+      properties: { type: "header" },
+    },
+    { text: "\n", properties: { type: "spacer" } },
+  ];
+  matches.forEach((match, index) => {
+    entries.push({
+      text: prefix(match) + match.content + "\n",
+      properties: {
+        type: "result",
+        index,
+        location: { file: match.file, line: match.line, column: match.column },
+      },
+    });
+  });
+  return entries;
+}
+
+async function search(pattern: string): Promise<SearchMatch[] | null> {
+  try {
+    const result = await editor.spawnProcess(
+      "git",
+      ["grep", "-n", "--column", "-I", "-F", "--", pattern],
+      editor.getCwd()
+    );
+    // git grep exits with 1 when nothing matched
+    if (result.exit_code !== 0 && result.exit_code !== 1) {
+      editor.setStatus(editor.t("status.search_error", { error: result.stderr.trim() }));
+      return null;
+    }
+    const found: SearchMatch[] = [];
+    for (const line of result.stdout.split("\n")) {
+      const match = parseGrepLine(line);
+      if (!match) continue;
+      found.push({ ...match, content: match.content.replace(/\r$/, "") });
+      if (found.length >= MAX_RESULTS) break;
+    }
+    return found;
+  } catch (e) {
+    editor.setStatus(editor.t("status.search_error", { error: String(e) }));
+    return null;
+  }
+}
+
+async function showResults(): Promise<void> {
+  if (resultsBufferId !== null) {
+    editor.setVirtualBufferContent(resultsBufferId, buildEntries());
+    return;
+  }
+
+  sourceSplitId = editor.getActiveSplitId();
+  try {
+    const result = await editor.createVirtualBufferInSplit({
+      name: "*Search Edit*",
+      mode: "search-edit",
+      readOnly: false,
+      editingDisabled: false,
+      entries: buildEntries(),
+      ratio: 0.6,
+      panelId: "search-edit-panel",
+      showLineNumbers: false,
+      showCursors: true,
+    });
+    resultsBufferId = result.bufferId;
+  } catch (e) {
+    editor.setStatus(editor.t("status.failed_open_panel"));
+    editor.debug(`search_edit: createVirtualBufferInSplit failed: ${String(e)}`);
+  }
+}
+
+/**
+ * The new content of each match's line, or an error message if lines were
+ * added or removed or a line's location was edited
+ */
+function editedContents(text: string): string[] | string {
+  const lines = text.split("\n");
+  if (lines[lines.length - 1] === "") {
+    lines.pop();
+  }
+  if (lines.length - HEADER_LINES !== matches.length) {
+    return editor.t("status.lines_changed");
+  }
+  const contents: string[] = [];
+  for (let i = 0; i < matches.length; i++) {
+    const line = lines[HEADER_LINES + i];
+    const expected = prefix(matches[i]);
+    if (!line.startsWith(expected)) {
+      return editor.t("status.prefix_changed", { location: expected.trimEnd() });
+    }
+    contents.push(line.slice(expected.length));
+  }
+  return contents;
+}
+
+/** Write the changed lines back to their files, all or none */
+globalThis.search_edit_write = async function (): Promise<void> {
+  if (resultsBufferId === null) return;
+
+  const text = await editor.getBufferText(
+    resultsBufferId,
+    0,
+    editor.getBufferLength(resultsBufferId)
+  );
+  const contents = editedContents(text);
+  if (typeof contents === "string") {
+    editor.setStatus(contents);
+    return;
+  }
+
+  // Changed lines by file
+  const changes = new Map<string, Array<{ match: SearchMatch; content: string }>>();
+  matches.forEach((match, i) => {
+    if (contents[i] === match.content) return;
+    if (!changes.has(match.file)) changes.set(match.file, []);
+    changes.get(match.file)!.push({ match, content: contents[i] });
+  });
+  if (changes.size === 0) {
+    editor.setStatus(editor.t("status.nothing_changed"));
+    return;
+  }
+
+  const cwd = editor.getCwd();
+  // Files open in a buffer are edited there, the others on disk
+  const bufferEdits: JsFileEdits[] = [];
+  const saveBuffers: Array<{ bufferId: number; path: string }> = [];
+  const fileWrites: Array<{ path: string; content: string }> = [];
+  let unsaved = 0;
+  let lineCount = 0;
+
+  for (const [file, fileChanges] of changes) {
+    const path = editor.pathJoin(cwd, file);
+    lineCount += fileChanges.length;
+    const bufferId = editor.findBufferByPath(path);
+
+    if (bufferId !== 0) {
+      if (editor.isBufferModified(bufferId)) {
+        unsaved++;
+      } else {
+        saveBuffers.push({ bufferId, path });
+      }
+      bufferEdits.push({
+        path,
+        edits: fileChanges.map(({ match, content }) => ({
+          range: {
+            start: { line: match.line - 1, character: 0 },
+            end: { line: match.line - 1, character: match.content.length },
+          },
+          newText: content,
+          oldText: match.content,
+        })),
+      });
+      continue;
+    }
+
+    // Checked now, so a conflict in any file stops all writes
+    const current = editor.readFile(path);
+    const lines = current === null ? [] : current.split("\n");
+    for (const { match, content } of fileChanges) {
+      const index = match.line - 1;
+      const old = index < lines.length ? lines[index] : undefined;
+      if (old === undefined || old.replace(/\r$/, "") !== match.content) {
+        editor.setStatus(editor.t("status.conflict", { file }));
+        return;
+      }
+      lines[index] = content + (old.endsWith("\r") ? "\r" : "");
+    }
+    fileWrites.push({ path, content: lines.join("\n") });
+  }
+
+  try {
+    if (bufferEdits.length > 0) {
+      await editor.applyEdits(bufferEdits, "Search and Edit");
+    }
+  } catch (e) {
+    const message = e instanceof Error ? e.message : String(e);
+    editor.setStatus(editor.t("status.write_failed", { error: message }));
+    return;
+  }
+  for (const { bufferId, path } of saveBuffers) {
+    editor.saveBufferToPath(bufferId, path);
+  }
+  for (const { path, content } of fileWrites) {
+    if (!editor.writeFile(path, content)) {
+      editor.setStatus(editor.t("status.write_failed", { error: path }));
+      return;
+    }
+  }
+
+  // The buffer now matches the files again
+  matches.forEach((match, i) => {
+    match.content = contents[i];
+  });
+  editor.setVirtualBufferContent(resultsBufferId, buildEntries());
+
+  const counts = { count: String(lineCount), files: String(changes.size) };
+  editor.setStatus(
+    unsaved > 0
+      ? editor.t("status.written_unsaved", { ...counts, unsaved: String(unsaved) })
+      : editor.t("status.written", counts)
+  );
+};
+
+/** Open the file of the match under the cursor in the split searched from */
+globalThis.search_edit_open = function (): void {
+  if (resultsBufferId === null || sourceSplitId === null) return;
+  const props = editor.getTextPropertiesAtCursor(resultsBufferId);
+  const location = props.length > 0
+    ? props[0].location as { file: string; line: number; column: number } | undefined
+    : undefined;
+  if (location) {
+    editor.openFileInSplit(sourceSplitId, location.file, location.line, location.column);
+  }
+};
+
+globalThis.start_search_edit = function (): void {
+  editor.startPrompt(editor.t("prompt.search"), "search-edit");
+};
+
+globalThis.onSearchEditConfirmed = async function (args: {
+  prompt_type: string;
+  selected_index: number | null;
+  input: string;
+}): Promise<boolean> {
+  if (args.prompt_type !== "search-edit") {
+    return true;
+  }
+
+  const pattern = args.input.trim();
+  if (!pattern) {
+    editor.setStatus(editor.t("status.cancelled_empty"));
+    return true;
+  }
+
+  const found = await search(pattern);
+  if (found === null) return true;
+  if (found.length === 0) {
+    editor.setStatus(editor.t("status.no_matches", { pattern }));
+    return true;
+  }
+  searchPattern = pattern;
+  matches = found;
+  await showResults();
+  editor.setStatus(editor.t("status.found_matches", { count: String(found.length) }));
+  return true;
+};
+
+globalThis.onSearchEditBufferClosed = function (data: { buffer_id: number }): void {
+  if (data.buffer_id !== resultsBufferId) return;
+  resultsBufferId = null;
+  sourceSplitId = null;
+  matches = [];
+};
+
+editor.on("prompt_confirmed", "onSearchEditConfirmed");
+editor.on("buffer_closed", "onSearchEditBufferClosed");
+
+editor.registerCommand(
+  "%cmd.search_edit",
+  "%cmd.search_edit_desc",
+  "start_search_edit",
+  null
+);
+
+editor.debug("Search and Edit plugin loaded");
@@ -258,6 +258,7 @@ async function executeReplacements(): Promise<void> {
   }
 
   // Group by file
+  const cwd = editor.getCwd();
   const fileGroups: Map<string, SearchResult[]> = new Map();
   for (const result of selectedResults) {
     if (!fileGroups.has(result.file)) {
@@ -266,57 +267,65 @@ async function executeReplacements(): Promise<void> {
     fileGroups.get(result.file)!.push(result);
   }
 
-  let filesModified = 0;
+  // Each changed line is replaced as a whole, and only if it still holds
+  // the text it had when searching, so a file changed since is left alone
+  const fileEdits: JsFileEdits[] = [];
   let replacementsCount = 0;
-  const errors: string[] = [];
 
-  for (const [filePath, results] of fileGroups) {
-    try {
-      // Read file
+  try {
+    for (const [file, results] of fileGroups) {
+      const filePath = editor.pathJoin(cwd, file);
       const content = await editor.readFile(filePath);
       const lines = content.split("\n");
+      const edits: JsTextEdit[] = [];
 
-      // Sort results by line (descending) to avoid offset issues
-      const sortedResults = [...results].sort((a, b) => {
-        if (a.line !== b.line) return b.line - a.line;
-        return b.column - a.column;
-      });
-
-      // Apply replacements
-      for (const result of sortedResults) {
-        const lineIndex = result.line - 1;
-        if (lineIndex >= 0 && lineIndex < lines.length) {
-          let line = lines[lineIndex];
-
-          if (searchRegex) {
-            // Regex replacement
-            const regex = new RegExp(searchPattern, "g");
-            lines[lineIndex] = line.replace(regex, replaceText);
-          } else {
-            // Simple string replacement (all occurrences in line)
-            lines[lineIndex] = line.split(searchPattern).join(replaceText);
-          }
-          replacementsCount++;
+      for (const lineNumber of new Set(results.map(r => r.line))) {
+        const lineIndex = lineNumber - 1;
+        if (lineIndex < 0 || lineIndex >= lines.length) {
+          continue;
         }
+        const line = lines[lineIndex].replace(/\r$/, "");
+        const replaced = searchRegex
+          ? line.replace(new RegExp(searchPattern, "g"), replaceText)
+          : line.split(searchPattern).join(replaceText);
+        replacementsCount += results.filter(r => r.line === lineNumber).length;
+        if (replaced === line) {
+          continue;
+        }
+        edits.push({
+          range: {
+            start: { line: lineIndex, character: 0 },
+            end: { line: lineIndex, character: line.length },
+          },
+          newText: replaced,
+          oldText: line,
+        });
       }
 
-      // Write back
-      const newContent = lines.join("\n");
-      await editor.writeFile(filePath, newContent);
-      filesModified++;
-
-    } catch (e) {
-      const errorMessage = e instanceof Error ? e.message : String(e);
-      errors.push(`${filePath}: ${errorMessage}`);
+      if (edits.length > 0) {
+        fileEdits.push({ path: filePath, edits });
+      }
     }
-  }
 
-  // Report results
-  if (errors.length > 0) {
-    editor.setStatus(editor.t("status.replaced_with_errors", { files: String(filesModified), errors: String(errors.length) }));
-    editor.debug(`Replacement errors: ${errors.join(", ")}`);
-  } else {
-    editor.setStatus(editor.t("status.replaced", { count: String(replacementsCount), files: String(filesModified) }));
+    // Files without unsaved changes are saved once replaced; the others
+    // keep them for the user to review and save
+    const savePaths = fileEdits
+      .map(({ path }) => path)
+      .filter(path => {
+        const bufferId = editor.findBufferByPath(path);
+        return bufferId === 0 || !editor.isBufferModified(bufferId);
+      });
+
+    // All files are changed, or none
+    await editor.applyEdits(fileEdits, "Search and Replace");
+    for (const path of savePaths) {
+      editor.saveBufferToPath(editor.findBufferByPath(path), path);
+    }
+    editor.setStatus(editor.t("status.replaced", { count: String(replacementsCount), files: String(fileEdits.length) }));
+  } catch (e) {
+    const errorMessage = e instanceof Error ? e.message : String(e);
+    editor.setStatus(editor.t("status.replace_failed", { error: errorMessage }));
+    editor.debug(`Replacement failed: ${errorMessage}`);
   }
 
   // Close panel after replacement
@@ -0,0 +1,212 @@
+/// <reference path="./lib/fresh.d.ts" />
+
+/**
+ * TODO Panel Plugin
+ *
+ * Collects TODO, FIXME and HACK comments from the whole workspace into a
+ * panel grouped by file.
+ *
+ * - The workspace is scanned with ripgrep, so .gitignore'd files are skipped
+ * - Counts per tag are shown in the status bar after each scan
+ * - Saving a file rescans just that file and updates the panel
+ * - Enter jumps to the comment; showing the panel again rescans the workspace
+ */
+
+import {
+  Finder,
+  createLiveProvider,
+  parseGrepOutput,
+  type FinderProvider,
+} from "./lib/finder.ts";
+
+const editor = getEditor();
+
+const TAGS = ["TODO", "FIXME", "HACK"];
+
+/** A tag after a comment marker (or at the start of a line, as in Markdown) */
+const PATTERN = `(//|#|<!--|;|/\\*|--|^\\s*\\*|^)\\s*(${TAGS.join("|")})\\b`;
+
+/** Upper bound on the comments collected, to keep huge trees responsive */
+const MAX_ITEMS = 5000;
+
+interface TodoItem {
+  file: string;
+  line: number;
+  column: number;
+  tag: string;
+  text: string;
+}
+
+// State
+let items: TodoItem[] = [];
+let scanned = false;
+
+/** Turn ripgrep output into items that point at the tag */
+function parseItems(stdout: string): TodoItem[] {
+  const tagPattern = new RegExp(`\\b(${TAGS.join("|")})\\b`);
+  return parseGrepOutput(stdout, MAX_ITEMS).flatMap((match) => {
+    const found = tagPattern.exec(match.content);
+    if (!found) return [];
+    const text = match.content
+      .slice(found.index + found[1].length)
+      .replace(/^[\s:(\-]*/, "")
+      .replace(/\s*(\*\/|-->)\s*$/, "");
+    return [
+      {
+        file: match.file,
+        line: match.line,
+        column: found.index + 1,
+        tag: found[1],
+        text,
+      },
+    ];
+  });
+}
+
+/** Run ripgrep over `paths` (the workspace if empty); null on failure */
+async function runScan(paths: string[]): Promise<TodoItem[] | null> {
+  const args = [
+    "--line-number",
+    "--column",
+    "--no-heading",
+    "--with-filename",
+    "--color=never",
+    "-g",
+    "!.git",
+    "-e",
+    PATTERN,
+    "--",
+    ...paths,
+  ];
+  try {
+    const result = await editor.spawnProcess("rg", args, editor.getCwd());
+    // ripgrep exits with 1 when nothing matched
+    if (result.exit_code === 0 || result.exit_code === 1) {
+      return parseItems(result.stdout);
+    }
+    editor.setStatus(
+      editor.t("status.scan_failed", { error: result.stderr.trim() })
+    );
+  } catch (e) {
+    editor.setStatus(editor.t("status.scan_failed", { error: String(e) }));
+  }
+  return null;
+}
+
+function sortItems(): void {
+  items.sort((a, b) => {
+    if (a.file !== b.file) return a.file < b.file ? -1 : 1;
+    return a.line - b.line;
+  });
+}
+
+/** Show how many comments of each tag were found */
+function showCounts(): void {
+  const counts = TAGS.map(
+    (tag) => `${tag} ${items.filter((item) => item.tag === tag).length}`
+  );
+  editor.setStatus(editor.t("status.counts", { counts: counts.join(" | ") }));
+}
+
+const provider = createLiveProvider(() => items);
+
+const finder = new Finder<TodoItem>(editor, {
+  id: "todo",
+  format: (item) => ({
+    label: `${item.line}: ${item.tag}`,
+    description: item.text,
+    location: {
+      file: item.file,
+      line: item.line,
+      column: item.column,
+    },
+  }),
+  groupBy: "file",
+});
+
+function getTitle(): string {
+  return editor.t("panel.header", { count: String(items.length) });
+}
+
+/** Scan the whole workspace and refresh the panel */
+async function rescan(): Promise<void> {
+  editor.setStatus(editor.t("status.scanning"));
+  const found = await runScan([]);
+  if (found === null) return;
+  items = found;
+  scanned = true;
+  sortItems();
+  if (finder.isOpen) {
+    finder.updateTitle(getTitle());
+    provider.notify();
+  }
+  showCounts();
+}
+
+// Commands
+globalThis.show_todo_panel = async function (): Promise<void> {
+  await rescan();
+  if (finder.isOpen || !scanned) return;
+
+  await finder.livePanel({
+    title: getTitle(),
+    provider: provider as FinderProvider<TodoItem>,
+    ratio: 0.3,
+  });
+  showCounts();
+};
+
+globalThis.todo_close = function (): void {
+  finder.close();
+  editor.setStatus(editor.t("status.closed"));
+};
+
+globalThis.toggle_todo_panel = function (): void {
+  if (finder.isOpen) {
+    globalThis.todo_close();
+  } else {
+    globalThis.show_todo_panel();
+  }
+};
+
+// Event Handlers
+
+// Rescan a saved file and replace its items
+globalThis.on_todo_after_file_save = async function (data: {
+  buffer_id: number;
+  path: string;
+}): Promise<void> {
+  if (!scanned) return;
+  const cwd = editor.getCwd();
+  if (!data.path.startsWith(cwd + "/")) return;
+  const file = data.path.slice(cwd.length + 1);
+
+  const found = await runScan([file]);
+  if (found === null) return;
+  items = items.filter((item) => item.file !== file).concat(found);
+  sortItems();
+  if (finder.isOpen) {
+    finder.updateTitle(getTitle());
+    provider.notify();
+  }
+  showCounts();
+};
+
+editor.on("after_file_save", "on_todo_after_file_save");
+
+// Command Registration
+editor.registerCommand(
+  "%cmd.show_todo_panel",
+  "%cmd.show_todo_panel_desc",
+  "show_todo_panel",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.toggle_todo_panel",
+  "%cmd.toggle_todo_panel_desc",
+  "toggle_todo_panel",
+  null
+);
+
+editor.debug("TODO Panel plugin initialized (using Finder abstraction)");
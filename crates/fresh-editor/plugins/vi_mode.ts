@@ -584,6 +584,19 @@ globalThis.vi_redo = function (): void {
   editor.executeAction("redo");
 };
 
+// Increment/decrement the number under or after the cursor (Ctrl-a / Ctrl-x)
+globalThis.vi_increment = function (): void {
+  const count = consumeCount();
+  state.lastChange = { type: "simple", action: "increment_number", count };
+  executeWithCount("increment_number", count);
+};
+
+globalThis.vi_decrement = function (): void {
+  const count = consumeCount();
+  state.lastChange = { type: "simple", action: "decrement_number", count };
+  executeWithCount("decrement_number", count);
+};
+
 // Repeat last change (. command)
 globalThis.vi_repeat = async function (): Promise<void> {
   if (!state.lastChange) {
@@ -1019,6 +1032,11 @@ async function applyTextObject(objectType: string): Promise<void> {
     return;
   }
 
+  if (EDITOR_TEXT_OBJECTS.includes(objectType)) {
+    applyEditorTextObject(operator, isInner, objectType, bufferId, cursorPos);
+    return;
+  }
+
   // Get text around cursor to find the text object boundaries
   const windowSize = 1000;
   const startOffset = Math.max(0, cursorPos - windowSize);
@@ -1220,6 +1238,34 @@ async function applyTextObject(objectType: string): Promise<void> {
   switchMode("normal");
 }
 
+// Text objects the editor finds itself (functions and arguments from the syntax tree)
+const EDITOR_TEXT_OBJECTS = ["tag", "function", "argument"];
+
+// Select with the editor's text object command, then apply the operator to the
+// selection. With no such object the selection is empty, so nothing changes.
+function applyEditorTextObject(operator: string, isInner: boolean, objectType: string, bufferId: number, cursorPos: number): void {
+  editor.executeAction(`select_${isInner ? "inside" : "around"}_${objectType}`);
+  switch (operator) {
+    case "d": {
+      editor.executeAction("delete_backward");
+      state.lastYankWasLinewise = false;
+      break;
+    }
+    case "c": {
+      editor.executeAction("delete_backward");
+      switchMode("insert");
+      return;
+    }
+    case "y": {
+      editor.executeAction("copy");
+      state.lastYankWasLinewise = false;
+      editor.setBufferCursor(bufferId, cursorPos);
+      break;
+    }
+  }
+  switchMode("normal");
+}
+
 // Helper to find matching bracket pair containing the cursor
 function findMatchingPair(text: string, pos: number, openChar: string, closeChar: string): { start: number; end: number } | null {
   let depth = 0;
@@ -1264,6 +1310,9 @@ globalThis.vi_to_paren = async function (): Promise<void> { await applyTextObjec
 globalThis.vi_to_brace = async function (): Promise<void> { await applyTextObject("{"); };
 globalThis.vi_to_bracket = async function (): Promise<void> { await applyTextObject("["); };
 globalThis.vi_to_angle = async function (): Promise<void> { await applyTextObject("<"); };
+globalThis.vi_to_tag = async function (): Promise<void> { await applyTextObject("tag"); };
+globalThis.vi_to_function = async function (): Promise<void> { await applyTextObject("function"); };
+globalThis.vi_to_argument = async function (): Promise<void> { await applyTextObject("argument"); };
 
 // Cancel text object mode
 globalThis.vi_to_cancel = function (): void {
@@ -1548,6 +1597,10 @@ editor.defineMode("vi-normal", null, [
   ["u", "vi_undo"],
   ["C-r", "vi_redo"],
 
+  // Numbers
+  ["C-a", "vi_increment"],
+  ["C-x", "vi_decrement"],
+
   // Repeat last change
   [".", "vi_repeat"],
 
@@ -1562,6 +1615,18 @@ editor.defineMode("vi-normal", null, [
   // Command mode
   [":", "vi_command_mode"],
 
+  // Jump list
+  ["C-o", "jump_back"],
+  ["C-i", "jump_forward"],
+
+  // Tags
+  ["C-]", "goto_tag"],
+  ["C-t", "pop_tag"],
+
+  // Change list
+  ["g ;", "goto_last_change"],
+  ["g ,", "goto_next_change"],
+
   // Pass through to standard editor shortcuts
   ["C-p", "command_palette"],
   ["C-q", "quit"],
@@ -1732,6 +1797,11 @@ editor.defineMode("vi-text-object", null, [
   ["<", "vi_to_angle"],
   [">", "vi_to_angle"],
 
+  // Tag, function and argument objects
+  ["t", "vi_to_tag"],
+  ["f", "vi_to_function"],
+  ["a", "vi_to_argument"],
+
   // Cancel
   ["Escape", "vi_to_cancel"],
 ], true);
@@ -1932,6 +2002,8 @@ const opCommands = [
   ["vi_paste_before", "paste_before"],
   ["vi_undo", "undo"],
   ["vi_redo", "redo"],
+  ["vi_increment", "increment"],
+  ["vi_decrement", "decrement"],
   ["vi_join", "join_lines"],
 ];
 
@@ -0,0 +1,37 @@
+#!/bin/bash
+# Build the browser playground in web/
+#
+# Usage: ./scripts/build-web.sh [--debug]
+#
+# Compiles the editor core for wasm32 and generates its JavaScript bindings
+# into web/pkg, which web/index.html loads. Serve web/ with any static file
+# server, e.g. `python3 -m http.server -d web`.
+#
+# Requirements:
+#   - rustup target add wasm32-unknown-unknown
+#   - wasm-bindgen-cli, the same version as the wasm-bindgen crate
+#     (cargo install wasm-bindgen-cli)
+
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+PROFILE=release
+CARGO_PROFILE=(--release)
+if [[ "${1:-}" == "--debug" ]]; then
+    PROFILE=debug
+    CARGO_PROFILE=()
+fi
+
+# The library is built as an rlib by default; the bindings need a cdylib
+cargo rustc --lib "${CARGO_PROFILE[@]}" \
+    --target wasm32-unknown-unknown \
+    --no-default-features --features wasm \
+    --crate-type cdylib
+
+TARGET_DIR=$(cargo metadata --format-version 1 --no-deps | sed -n 's/.*"target_directory":"\([^"]*\)".*/\1/p')
+wasm-bindgen --target web --no-typescript \
+    --out-dir web/pkg \
+    "$TARGET_DIR/wasm32-unknown-unknown/$PROFILE/fresh.wasm"
+
+echo "Built web/pkg; serve web/ to try the playground"
@@ -12,7 +12,6 @@ use crate::model::event::BufferId;
 use crate::services::async_bridge::{
     LspMessageType, LspProgressValue, LspSemanticTokensResponse, LspServerStatus,
 };
-use crate::services::lsp::manager::detect_language;
 use crate::state::{SemanticTokenSpan, SemanticTokenStore};
 use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{
@@ -227,10 +226,7 @@ impl Editor {
         let Some(metadata) = self.buffer_metadata.get(&buffer_id) else {
             return;
         };
-        let Some(path) = metadata.file_path() else {
-            return;
-        };
-        let Some(language) = detect_language(path, &self.config.languages) else {
+        let Some(language) = metadata.language(&self.config.languages) else {
             return;
         };
 
@@ -250,7 +246,7 @@ impl Editor {
             return;
         };
 
-        let current_version = state.buffer.version();
+        let current_version = state.buffer.revision();
         if current_version != target_version {
             // Stale response - ignore; next render will request fresh tokens.
             return;
@@ -528,7 +524,7 @@ impl Editor {
         );
 
         // Skip if inlay hints are disabled
-        if !self.config.editor.enable_inlay_hints {
+        if !self.inlay_hints_shown() {
             return;
         }
 
@@ -635,6 +631,7 @@ impl Editor {
             message: message.clone(),
             timestamp: Instant::now(),
         });
+        self.handle_lsp_log_message(language.clone(), message_type, message.clone());
 
         // Keep only last 100 messages
         if self.lsp_window_messages.len() > 100 {
@@ -681,10 +678,6 @@ impl Editor {
 
         // Update server status
         self.lsp_server_statuses.insert(language.clone(), status);
-        self.update_lsp_status_from_server_statuses();
-
-        // Update warning domain for LSP status indicator
-        self.update_lsp_warning_domain();
 
         // Handle server crash - trigger auto-restart
         if status == LspServerStatus::Error {
@@ -696,11 +689,26 @@ impl Editor {
             if was_running {
                 if let Some(lsp) = self.lsp.as_mut() {
                     let message = lsp.handle_server_crash(&language);
-                    self.status_message = Some(message);
+                    self.status_message = Some(message.clone());
+                    self.handle_lsp_log_message(language.clone(), LspMessageType::Error, message);
                 }
             }
+        } else if old_status != Some(status) {
+            // Keep a record of the server's lifecycle in the LSP Log
+            let label = self.lsp_status_label(&language, status);
+            self.handle_lsp_log_message(
+                language.clone(),
+                LspMessageType::Info,
+                t!("lsp_log.status_changed", status = label).to_string(),
+            );
         }
 
+        // Shown after the crash handling, which decides whether it's restarting
+        self.update_lsp_status_from_server_statuses();
+
+        // Update warning domain for LSP status indicator
+        self.update_lsp_warning_domain();
+
         // Emit control event
         let status_str = match status {
             LspServerStatus::Starting => "starting",
@@ -810,6 +818,32 @@ impl Editor {
 // =============================================================================
 
 impl Editor {
+    /// Handle a file open forwarded by another `fresh` process
+    pub(super) fn handle_open_file_request(
+        &mut self,
+        request: crate::services::instance_server::OpenRequest,
+    ) {
+        tracing::info!(
+            "Opening file forwarded from another instance: {:?}",
+            request
+        );
+        match self.open_file(&request.path) {
+            Ok(_) => {
+                if let Some(line) = request.line {
+                    self.goto_line_col(line, request.column);
+                }
+                self.set_status_message(
+                    t!("file.opened", path = request.path.display().to_string()).to_string(),
+                );
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
     /// Handle file changed externally notification (from AsyncMessage)
     ///
     /// Includes debounce logic to prevent rapid auto-reverts from overwhelming the editor.
@@ -882,11 +916,14 @@ impl Editor {
     pub(super) fn handle_file_explorer_initialized(&mut self, mut view: FileTreeView) {
         tracing::info!("File explorer initialized");
 
-        // Load root .gitignore
-        let root_id = view.tree().root_id();
-        let root_path = view.tree().get_node(root_id).map(|n| n.entry.path.clone());
+        // Load the .gitignore of each root
+        let root_paths: Vec<_> = view
+            .tree()
+            .root_ids()
+            .filter_map(|id| view.tree().get_node(id).map(|n| n.entry.path.clone()))
+            .collect();
 
-        if let Some(root_path) = root_path {
+        for root_path in root_paths {
             if let Err(e) = view.load_gitignore_for_dir(&root_path) {
                 tracing::warn!("Failed to load root .gitignore from {:?}: {}", root_path, e);
             } else {
@@ -1061,6 +1098,12 @@ impl Editor {
 
         for (language, success, message) in restart_results {
             self.status_message = Some(message.clone());
+            let message_type = if success {
+                LspMessageType::Info
+            } else {
+                LspMessageType::Error
+            };
+            self.handle_lsp_log_message(language.clone(), message_type, message);
 
             if success {
                 self.resend_did_open_for_language(&language);
@@ -1068,23 +1111,26 @@ impl Editor {
         }
     }
 
+    /// Shut down language servers that have had no open documents for
+    /// `lsp_idle_timeout_secs` (0 keeps them running)
+    pub(super) fn release_idle_lsp_servers(&mut self) {
+        let idle_timeout = self.config.editor.lsp_idle_timeout_secs;
+        if idle_timeout == 0 {
+            return;
+        }
+        if let Some(lsp) = self.lsp.as_mut() {
+            lsp.release_idle_servers(Duration::from_secs(idle_timeout));
+        }
+    }
+
     /// Re-send didOpen notifications for all buffers of a given language
     pub(super) fn resend_did_open_for_language(&mut self, language: &str) {
         // Find all open buffers for this language
         let buffers_for_language: Vec<_> = self
             .buffer_metadata
             .iter()
-            .filter_map(|(buf_id, meta)| {
-                meta.file_path().and_then(|path| {
-                    if crate::services::lsp::manager::detect_language(path, &self.config.languages)
-                        == Some(language.to_string())
-                    {
-                        Some((*buf_id, path.clone()))
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter(|(_, meta)| meta.language(&self.config.languages).as_deref() == Some(language))
+            .filter_map(|(buf_id, meta)| Some((*buf_id, meta.file_path()?.clone())))
             .collect();
 
         // Re-send didOpen for each buffer
@@ -1099,15 +1145,10 @@ impl Editor {
                     .and_then(|u| u.as_str().parse::<lsp_types::Uri>().ok());
 
                 if let Some(uri) = uri {
-                    if let Some(lang_id) = crate::services::lsp::manager::detect_language(
-                        &path,
-                        &self.config.languages,
-                    ) {
-                        if let Some(lsp) = self.lsp.as_mut() {
-                            // LSP should already be running since we just restarted it
-                            if let Some(handle) = lsp.get_handle_mut(&lang_id) {
-                                let _ = handle.did_open(uri, content, lang_id);
-                            }
+                    if let Some(lsp) = self.lsp.as_mut() {
+                        // LSP should already be running since we just restarted it
+                        if let Some(handle) = lsp.get_handle_mut(language) {
+                            let _ = handle.did_open(uri, content, language.to_string());
                         }
                     }
                 }
@@ -1120,15 +1161,8 @@ impl Editor {
         let buffer_ids: Vec<_> = self
             .buffer_metadata
             .iter()
-            .filter_map(|(buffer_id, meta)| {
-                meta.file_path().and_then(|path| {
-                    if detect_language(path, &self.config.languages).as_deref() == Some(language) {
-                        Some(*buffer_id)
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter(|(_, meta)| meta.language(&self.config.languages).as_deref() == Some(language))
+            .map(|(buffer_id, _)| *buffer_id)
             .collect();
 
         for buffer_id in buffer_ids {
@@ -0,0 +1,225 @@
+//! Auto-save of modified files to disk for the Editor.
+//!
+//! Unlike recovery auto-save (see `recovery_actions`), which writes snapshots
+//! to the recovery directory, this writes the actual file:
+//! - after a buffer has been idle for `file_auto_save_delay_ms`
+//! - when the terminal loses focus (`file_auto_save_on_focus_loss`)
+//!
+//! Files can be excluded by glob (`file_auto_save_exclude`) or by language
+//! (`file_auto_save_exclude_languages`). A buffer saved to disk no longer has
+//! pending recovery data, so the recovery pass that runs after this one skips it.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::Result as AnyhowResult;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::model::event::BufferId;
+
+use super::Editor;
+
+/// Auto-save bookkeeping for one buffer
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FileAutoSaveState {
+    /// Buffer version last seen
+    version: u64,
+    /// When the buffer was first seen at this version, or `None` if saving
+    /// this version failed (don't retry until the next edit)
+    idle_since: Option<Instant>,
+}
+
+impl Editor {
+    /// Whether modified content of this buffer is auto-saved to disk
+    pub(crate) fn is_file_auto_save_active(&self, buffer_id: BufferId) -> bool {
+        self.config.editor.file_auto_save
+            && self
+                .auto_save_path(buffer_id, &self.file_auto_save_excludes())
+                .is_some()
+    }
+
+    /// Save modified files that have been idle for the configured delay
+    ///
+    /// Called every tick, before `auto_save_dirty_buffers`. Returns the number
+    /// of files saved.
+    pub fn auto_save_files(&mut self) -> usize {
+        if !self.config.editor.file_auto_save {
+            self.file_auto_save_states.clear();
+            return 0;
+        }
+
+        let delay = Duration::from_millis(self.config.editor.file_auto_save_delay_ms);
+        let now = self.time_source.now();
+        let mut due = Vec::new();
+
+        self.file_auto_save_states
+            .retain(|id, _| self.buffers.contains_key(id));
+        for (buffer_id, state) in &self.buffers {
+            if !state.buffer.is_modified() {
+                self.file_auto_save_states.remove(buffer_id);
+                continue;
+            }
+
+            let version = state.buffer.revision();
+            match self.file_auto_save_states.get(buffer_id) {
+                Some(entry) if entry.version == version => {
+                    if entry
+                        .idle_since
+                        .is_some_and(|since| now.saturating_duration_since(since) >= delay)
+                    {
+                        due.push(*buffer_id);
+                    }
+                }
+                _ => {
+                    self.file_auto_save_states.insert(
+                        *buffer_id,
+                        FileAutoSaveState {
+                            version,
+                            idle_since: Some(now),
+                        },
+                    );
+                }
+            }
+        }
+
+        self.auto_save_buffers_to_disk(due)
+    }
+
+    /// Save all modified files right away (the terminal lost focus)
+    pub fn handle_terminal_focus_lost(&mut self) -> usize {
+        if !self.config.editor.file_auto_save || !self.config.editor.file_auto_save_on_focus_loss {
+            return 0;
+        }
+
+        let modified: Vec<_> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified())
+            .map(|(id, _)| *id)
+            .collect();
+        self.auto_save_buffers_to_disk(modified)
+    }
+
+    /// Save the given buffers if they are eligible for auto-save
+    fn auto_save_buffers_to_disk(&mut self, buffer_ids: Vec<BufferId>) -> usize {
+        if buffer_ids.is_empty() {
+            return 0;
+        }
+
+        let excludes = self.file_auto_save_excludes();
+        let mut saved = 0;
+        for buffer_id in buffer_ids {
+            let Some(path) = self.auto_save_path(buffer_id, &excludes) else {
+                continue;
+            };
+            match self.write_buffer_to_disk(buffer_id, path.clone()) {
+                Ok(()) => {
+                    tracing::debug!("Auto-saved {:?}", path);
+                    self.file_auto_save_states.remove(&buffer_id);
+                    saved += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to auto-save {:?}: {}", path, e);
+                    if let Some(entry) = self.file_auto_save_states.get_mut(&buffer_id) {
+                        entry.idle_since = None;
+                    }
+                }
+            }
+        }
+        saved
+    }
+
+    /// Write one buffer to its file, without running on-save actions. Also
+    /// saves buffers other than the active one for plugins.
+    pub(super) fn write_buffer_to_disk(
+        &mut self,
+        buffer_id: BufferId,
+        path: PathBuf,
+    ) -> AnyhowResult<()> {
+        if let Err(e) = self.backup_before_save(&path) {
+            tracing::warn!("Failed to back up {:?}: {}", path, e);
+        }
+
+        let state = self
+            .buffers
+            .get_mut(&buffer_id)
+            .ok_or_else(|| anyhow::anyhow!("buffer {:?} not found", buffer_id))?;
+        state
+            .buffer
+            .set_save_strategy(self.config.editor.save_strategy);
+        state.buffer.save()?;
+        // The file on disk is now the recovery point
+        state.buffer.mark_recovery_saved();
+
+        if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+            event_log.mark_saved();
+        }
+        self.persist_undo_history(buffer_id);
+        self.forget_disk_merge(buffer_id);
+        if let Ok(metadata) = self.filesystem.metadata(&path) {
+            if let Some(mtime) = metadata.modified {
+                self.file_mod_times.insert(path.clone(), mtime);
+            }
+        }
+
+        self.notify_lsp_save_buffer(buffer_id);
+        let _ = self.delete_buffer_recovery(buffer_id);
+
+        self.emit_event(
+            crate::model::control_event::events::FILE_SAVED.name,
+            serde_json::json!({
+                "path": path.display().to_string()
+            }),
+        );
+        self.plugin_manager.run_hook(
+            "after_file_save",
+            crate::services::plugins::hooks::HookArgs::AfterFileSave { buffer_id, path },
+        );
+        Ok(())
+    }
+
+    /// File path to auto-save a buffer to, or `None` if it must not be auto-saved
+    fn auto_save_path(&self, buffer_id: BufferId, excludes: &Gitignore) -> Option<PathBuf> {
+        let state = self.buffers.get(&buffer_id)?;
+        let meta = self.buffer_metadata.get(&buffer_id)?;
+        if state.is_composite_buffer
+            || meta.is_virtual()
+            || meta.hidden_from_tabs
+            || meta.read_only
+            || meta.binary
+        {
+            return None;
+        }
+
+        let path = state.buffer.file_path()?;
+        if path.as_os_str().is_empty() {
+            return None;
+        }
+        if self
+            .config
+            .editor
+            .file_auto_save_exclude_languages
+            .contains(&state.language)
+        {
+            return None;
+        }
+        if excludes.matched(path, false).is_ignore() {
+            return None;
+        }
+        Some(path.to_path_buf())
+    }
+
+    /// Matcher for `file_auto_save_exclude`, relative to the working directory
+    fn file_auto_save_excludes(&self) -> Gitignore {
+        let mut builder = GitignoreBuilder::new(&self.working_dir);
+        for pattern in &self.config.editor.file_auto_save_exclude {
+            if let Err(e) = builder.add_line(None, pattern) {
+                tracing::warn!("Invalid auto-save exclude pattern {:?}: {}", pattern, e);
+            }
+        }
+        builder.build().unwrap_or_else(|e| {
+            tracing::warn!("Failed to build auto-save exclude patterns: {}", e);
+            Gitignore::empty()
+        })
+    }
+}
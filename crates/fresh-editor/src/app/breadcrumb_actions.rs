@@ -0,0 +1,190 @@
+//! Breadcrumb bar dropdowns.
+//!
+//! Clicking a crumb in the breadcrumb bar (see `view::ui::breadcrumbs`) shows
+//! its siblings in a dropdown under it: the definitions at the same level for
+//! a symbol, the entries of the same directory for a path. Picking a
+//! definition moves the cursor to it, picking a file opens it and picking a
+//! directory lists its own entries in the dropdown's place.
+
+use std::path::Path;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{
+    BufferId, PopupContentData, PopupData, PopupListItemData, PopupPositionData, SplitId,
+};
+use crate::primitives::outline::{self, OutlineSymbol};
+use crate::view::ui::breadcrumbs::{CrumbHitArea, CrumbTarget};
+
+const SYMBOL_PREFIX: &str = "symbol:";
+const FILE_PREFIX: &str = "file:";
+const DIR_PREFIX: &str = "dir:";
+
+/// Where an open breadcrumb dropdown was shown
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BreadcrumbDropdown {
+    buffer_id: BufferId,
+    x: u16,
+    y: u16,
+}
+
+impl Editor {
+    /// Show the siblings of a clicked crumb in a dropdown under it
+    pub(super) fn show_breadcrumb_dropdown(&mut self, split_id: SplitId, crumb: &CrumbHitArea) {
+        let Some(buffer_id) = self.split_manager.get_buffer_id(split_id) else {
+            return;
+        };
+        self.focus_split(split_id, buffer_id);
+
+        let (items, selected) = match &crumb.target {
+            CrumbTarget::Symbol(depth) => self.breadcrumb_symbol_items(*depth),
+            CrumbTarget::Path(path) => match path.parent() {
+                Some(dir) => self.breadcrumb_dir_items(dir, Some(path)),
+                None => return,
+            },
+            CrumbTarget::None => return,
+        };
+        let dropdown = BreadcrumbDropdown {
+            buffer_id,
+            x: crumb.area.x,
+            y: crumb.area.y + 1,
+        };
+        self.show_breadcrumb_popup(dropdown, items, selected);
+    }
+
+    /// Handle the choice made in a breadcrumb dropdown
+    pub(super) fn handle_breadcrumb_choice(&mut self, dropdown: BreadcrumbDropdown, choice: &str) {
+        if let Some(offset) = choice.strip_prefix(SYMBOL_PREFIX) {
+            let Ok(offset) = offset.parse::<usize>() else {
+                return;
+            };
+            if dropdown.buffer_id == self.active_buffer() {
+                self.record_jump();
+                self.go_to_location(dropdown.buffer_id, offset);
+            }
+        } else if let Some(dir) = choice.strip_prefix(DIR_PREFIX) {
+            let (items, selected) = self.breadcrumb_dir_items(Path::new(dir), None);
+            self.show_breadcrumb_popup(dropdown, items, selected);
+        } else if let Some(path) = choice.strip_prefix(FILE_PREFIX) {
+            self.record_jump();
+            if let Err(e) = self.open_file(Path::new(path)) {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    fn show_breadcrumb_popup(
+        &mut self,
+        dropdown: BreadcrumbDropdown,
+        items: Vec<PopupListItemData>,
+        selected: usize,
+    ) {
+        if items.is_empty() {
+            self.set_status_message(t!("breadcrumbs.nothing_to_show").to_string());
+            return;
+        }
+        let width = items
+            .iter()
+            .map(|item| item.text.chars().count())
+            .max()
+            .unwrap_or(0)
+            .clamp(16, 56) as u16
+            + 4;
+        let popup = PopupData {
+            title: None,
+            description: None,
+            transient: false,
+            content: PopupContentData::List { items, selected },
+            position: PopupPositionData::Fixed {
+                x: dropdown.x,
+                y: dropdown.y,
+            },
+            width,
+            max_height: 15,
+            bordered: true,
+        };
+        self.breadcrumb_dropdown = Some(dropdown);
+        self.show_popup(popup);
+    }
+
+    /// The definitions at the level of the one at `depth` around the cursor,
+    /// and which of them that is
+    fn breadcrumb_symbol_items(&mut self, depth: usize) -> (Vec<PopupListItemData>, usize) {
+        let state = self.active_state_mut();
+        let position = state.cursors.primary().position;
+        let symbols = state.outline();
+        let path = outline::symbol_path(symbols, position);
+        let level: &[OutlineSymbol] = match depth.checked_sub(1) {
+            None => symbols,
+            Some(parent) => match path.get(parent) {
+                Some(parent) => &parent.children,
+                None => return (Vec::new(), 0),
+            },
+        };
+        let selected = path
+            .get(depth)
+            .and_then(|current| {
+                level
+                    .iter()
+                    .position(|symbol| std::ptr::eq(symbol, *current))
+            })
+            .unwrap_or(0);
+        let items = level
+            .iter()
+            .map(|symbol| PopupListItemData {
+                text: symbol.name.clone(),
+                detail: None,
+                icon: None,
+                data: Some(format!("{}{}", SYMBOL_PREFIX, symbol.name_start)),
+            })
+            .collect();
+        (items, selected)
+    }
+
+    /// The entries of `dir`, directories first, and which of them is
+    /// `current`. Hidden entries are left out unless current.
+    fn breadcrumb_dir_items(
+        &self,
+        dir: &Path,
+        current: Option<&Path>,
+    ) -> (Vec<PopupListItemData>, usize) {
+        let mut entries = match self.filesystem.read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to list {:?} for breadcrumbs: {}", dir, e);
+                return (Vec::new(), 0);
+            }
+        };
+        entries.retain(|entry| !entry.name.starts_with('.') || Some(&*entry.path) == current);
+        entries.sort_by(|a, b| {
+            b.is_dir()
+                .cmp(&a.is_dir())
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        let selected = entries
+            .iter()
+            .position(|entry| Some(&*entry.path) == current)
+            .unwrap_or(0);
+        let items = entries
+            .into_iter()
+            .map(|entry| {
+                let (text, prefix) = if entry.is_dir() {
+                    (format!("{}/", entry.name), DIR_PREFIX)
+                } else {
+                    (entry.name, FILE_PREFIX)
+                };
+                PopupListItemData {
+                    text,
+                    detail: None,
+                    icon: None,
+                    data: Some(format!("{}{}", prefix, entry.path.display())),
+                }
+            })
+            .collect();
+        (items, selected)
+    }
+}
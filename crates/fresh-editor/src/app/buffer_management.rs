@@ -14,15 +14,25 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::app::warning_domains::WarningDomain;
+use crate::config::BufferConfig;
 use crate::model::event::{BufferId, Event, SplitId};
+use crate::primitives::indent_detection::Indentation;
+use crate::primitives::modeline::Modeline;
+use crate::services::encryption::{self, EncryptionScheme};
 use crate::services::lsp::manager::detect_language;
+use crate::services::spell::BufferSpellState;
 use crate::state::EditorState;
 use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
+use crate::view::visual_guides::VisualGuides;
 
 use super::help;
+use super::types::{BufferMetadata, ClosedBuffer};
 use super::Editor;
 
+/// How many closed buffers are remembered for Reopen Closed Buffer
+const MAX_CLOSED_BUFFERS: usize = 20;
+
 impl Editor {
     /// Open a file and return its buffer ID
     ///
@@ -68,6 +78,8 @@ impl Editor {
         // Show appropriate status message for binary vs regular files
         if is_binary {
             self.status_message = Some(t!("buffer.opened_binary", name = display_name).to_string());
+        } else if self.is_read_only_file(buffer_id) {
+            // Keep the message saying why the file is read-only
         } else {
             self.status_message = Some(t!("buffer.opened", name = display_name).to_string());
         }
@@ -177,7 +189,9 @@ impl Editor {
             self.grammar_registry.available_syntaxes().len(),
             self.grammar_registry.user_extensions_debug()
         );
-        let mut state = if file_exists {
+        // Encrypted files are decrypted once the user gives the key
+        let encryption_scheme = EncryptionScheme::for_path(path);
+        let mut state = if file_exists && encryption_scheme.is_none() {
             EditorState::from_file_with_languages(
                 path,
                 self.terminal_width,
@@ -188,7 +202,7 @@ impl Editor {
                 Arc::clone(&self.filesystem),
             )?
         } else {
-            // File doesn't exist - create empty buffer with the file path set
+            // File doesn't exist (or is still encrypted) - create empty buffer with the file path set
             let mut new_state = EditorState::new(
                 self.terminal_width,
                 self.terminal_height,
@@ -199,6 +213,14 @@ impl Editor {
             new_state.buffer.set_file_path(path.to_path_buf());
             new_state
         };
+        // Pick the language of the decrypted text (`notes.md.gpg` is Markdown)
+        let language_path = match encryption_scheme.and_then(|_| encryption::plaintext_name(path)) {
+            Some(name) => {
+                state.set_language_from_name(name, &self.grammar_registry);
+                Path::new(name)
+            }
+            None => path,
+        };
         // Note: line_wrap_enabled is set on SplitViewState.viewport when the split is created
 
         // Check if the buffer contains binary content
@@ -209,20 +231,59 @@ impl Editor {
             tracing::info!("Detected binary file: {}", path.display());
         }
 
-        // Set show_whitespace_tabs, use_tabs, and tab_size based on language config
-        // with fallback to global editor config for tab_size
-        if let Some(language) = detect_language(path, &self.config.languages) {
-            if let Some(lang_config) = self.config.languages.get(&language) {
-                state.show_whitespace_tabs = lang_config.show_whitespace_tabs;
-                state.use_tabs = lang_config.use_tabs;
-                // Use language-specific tab_size if set, otherwise fall back to global
-                state.tab_size = lang_config.tab_size.unwrap_or(self.config.editor.tab_size);
-            } else {
-                state.tab_size = self.config.editor.tab_size;
-            }
+        // Settings given in a modeline win over the file name's and the
+        // config's. They're off for files that can't be read as text.
+        let modeline = if self.config.editor.modelines && !is_binary && encryption_scheme.is_none()
+        {
+            Self::read_modeline(&mut state)
         } else {
-            state.tab_size = self.config.editor.tab_size;
+            Modeline::default()
+        };
+        let modeline_language = modeline
+            .language
+            .as_deref()
+            .and_then(|name| self.find_syntax_name(name))
+            .map(|syntax_name| {
+                state.set_syntax(&syntax_name, &self.grammar_registry);
+                let language = self.language_for_syntax(&syntax_name);
+                state.language = language.clone();
+                language
+            });
+
+        // Set show_whitespace_tabs, use_tabs, and tab_size from the language's
+        // config section, with fallback to the global editor config
+        let language = modeline_language
+            .clone()
+            .or_else(|| detect_language(language_path, &self.config.languages));
+        let buffer_config = BufferConfig::resolve(&self.config, language.as_deref());
+        state.show_whitespace_tabs = buffer_config.show_whitespace_tabs;
+        state.guides = VisualGuides::from_config(&self.config.editor, &buffer_config);
+        state.bidi_text = self.config.editor.bidi_text;
+        state.use_tabs = buffer_config.use_tabs;
+        state.tab_size = buffer_config.tab_size;
+        if self.config.editor.detect_indentation && !is_binary {
+            state.detect_indentation();
         }
+        if modeline.tab_size.is_some() || modeline.use_tabs.is_some() {
+            state.tab_size = modeline.tab_size.unwrap_or(state.tab_size);
+            state.use_tabs = modeline.use_tabs.unwrap_or(state.use_tabs);
+            state.detected_indentation = Some(if state.use_tabs {
+                Indentation::Tabs
+            } else {
+                Indentation::Spaces(state.tab_size)
+            });
+        }
+        state
+            .concealer
+            .set_rules(&buffer_config.conceal, state.highlighter.language());
+        state
+            .indent_calculator
+            .get_mut()
+            .set_rules(buffer_config.indent_patterns.as_ref());
+        state.spell = BufferSpellState::new(
+            self.config.editor.spell_check,
+            &self.config.editor.spell_language,
+        );
 
         // Apply line_numbers default from config
         state
@@ -244,8 +305,15 @@ impl Editor {
             metadata.disable_lsp(t!("buffer.binary_file").to_string());
         }
 
-        // Notify LSP about the newly opened file (skip for binary files)
-        if !is_binary {
+        metadata.language_override = modeline_language;
+
+        // Decrypted text is not shared with language servers
+        if encryption_scheme.is_some() {
+            metadata.disable_lsp(t!("encryption.lsp_disabled").to_string());
+        }
+
+        // Notify LSP about the newly opened file (skip for binary and encrypted files)
+        if !is_binary && encryption_scheme.is_none() {
             self.notify_lsp_file_opened(path, buffer_id, &mut metadata);
         }
 
@@ -257,12 +325,14 @@ impl Editor {
         if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
             view_state.add_buffer(buffer_id);
             // Apply line_wrap default from config (per-view setting, applies to split)
-            view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+            view_state.viewport.line_wrap_enabled =
+                modeline.line_wrap.unwrap_or(self.config.editor.line_wrap);
         }
 
         // Restore global file state (scroll/cursor position) if available
         // This persists file positions across projects and editor instances
         self.restore_global_file_state(buffer_id, path, active_split);
+        self.restore_undo_history(buffer_id, path);
 
         // Emit control event
         self.emit_event(
@@ -273,6 +343,14 @@ impl Editor {
             }),
         );
 
+        // Read-only buffers can't be modified, so other editors' locks only
+        // matter once they are made editable
+        if let Some(scheme) = encryption_scheme {
+            self.lock_encrypted_buffer(buffer_id, scheme);
+        } else if !is_binary && !self.mark_unwritable_read_only(buffer_id, path) {
+            self.check_file_lock(buffer_id, path);
+        }
+
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
 
@@ -366,7 +444,7 @@ impl Editor {
         use crate::session::PersistedFileSession;
 
         // Load the per-file session for this path (lazy load from disk)
-        let file_state = match PersistedFileSession::load(path) {
+        let file_state = match PersistedFileSession::load(path, self.recovery_service.codec()) {
             Some(state) => state,
             None => return, // No saved state for this file
         };
@@ -444,10 +522,95 @@ impl Editor {
         };
 
         // Save to disk
-        PersistedFileSession::save(&abs_path, file_state);
+        PersistedFileSession::save(&abs_path, file_state, self.recovery_service.codec());
         tracing::debug!("Saved file state on close for {:?}", abs_path);
     }
 
+    /// Push a closing file buffer onto the closed-buffers stack
+    fn remember_closed_buffer(&mut self, buffer_id: BufferId) {
+        if self.terminal_buffers.contains_key(&buffer_id) {
+            return;
+        }
+        let path = match self.buffer_metadata.get(&buffer_id) {
+            Some(metadata) if !metadata.is_virtual() => match metadata.file_path() {
+                Some(path) if !path.as_os_str().is_empty() => path.clone(),
+                _ => return, // Unnamed buffer
+            },
+            _ => return,
+        };
+        let cursor = self
+            .buffers
+            .get(&buffer_id)
+            .map(|state| state.cursors.primary().position)
+            .unwrap_or(0);
+
+        // Prefer the active split's viewport, then any split showing the buffer
+        let active_split = self.split_manager.active_split();
+        let view_state = self
+            .split_view_states
+            .get(&active_split)
+            .filter(|vs| vs.has_buffer(buffer_id))
+            .or_else(|| {
+                self.split_view_states
+                    .values()
+                    .find(|vs| vs.has_buffer(buffer_id))
+            });
+        let (top_byte, left_column) = view_state
+            .map(|vs| (vs.viewport.top_byte, vs.viewport.left_column))
+            .unwrap_or((0, 0));
+
+        self.closed_buffers.retain(|closed| closed.path != path);
+        self.closed_buffers.push(ClosedBuffer {
+            path,
+            cursor,
+            top_byte,
+            left_column,
+        });
+        if self.closed_buffers.len() > MAX_CLOSED_BUFFERS {
+            self.closed_buffers.remove(0);
+        }
+    }
+
+    /// Reopen the most recently closed file buffer, restoring its cursor and scroll
+    ///
+    /// Entries whose file no longer exists are skipped.
+    pub fn reopen_closed_buffer(&mut self) {
+        while let Some(closed) = self.closed_buffers.pop() {
+            if !closed.path.exists() {
+                continue;
+            }
+            let buffer_id = match self.open_file(&closed.path) {
+                Ok(id) => id,
+                Err(e) => {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                    return;
+                }
+            };
+
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                let max_pos = state.buffer.len();
+                let cursor = state.cursors.primary_mut();
+                cursor.position = closed.cursor.min(max_pos);
+                cursor.anchor = None;
+                let top_byte = closed.top_byte.min(max_pos);
+                let active_split = self.split_manager.active_split();
+                if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+                    view_state.viewport.top_byte = top_byte;
+                    view_state.viewport.left_column = closed.left_column;
+                }
+            }
+
+            // Reopening from the file explorer moves focus to the file
+            self.key_context = crate::input::keybindings::KeyContext::Normal;
+            let name = self.get_buffer_display_name(buffer_id);
+            self.set_status_message(t!("buffer.reopened", name = name).to_string());
+            return;
+        }
+        self.set_status_message(t!("buffer.no_closed_buffers").to_string());
+    }
+
     /// Navigate to a specific line and column in the active buffer.
     ///
     /// Line and column are 1-indexed (matching typical editor conventions).
@@ -457,6 +620,7 @@ impl Editor {
         if line == 0 {
             return; // Line numbers are 1-indexed
         }
+        self.record_jump();
 
         let buffer_id = self.active_buffer();
         let estimated_line_length = self.config.editor.estimated_line_length;
@@ -510,6 +674,8 @@ impl Editor {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
                 state.apply(&event);
             }
+            // Keep the split's cursors in step, so the session saves the new line
+            self.sync_editor_state_to_split_view_state();
         }
     }
 
@@ -539,6 +705,15 @@ impl Editor {
         state
             .margins
             .set_line_numbers(self.config.editor.line_numbers);
+        state.guides = VisualGuides::from_config(
+            &self.config.editor,
+            &BufferConfig::resolve(&self.config, None),
+        );
+        state.bidi_text = self.config.editor.bidi_text;
+        state.spell = BufferSpellState::new(
+            self.config.editor.spell_check,
+            &self.config.editor.spell_language,
+        );
         // Set default line ending for new buffers from config
         state
             .buffer
@@ -663,7 +838,9 @@ impl Editor {
             last_known_size: file_size,
             complete,
             thread_handle,
+            detect_language: true,
         });
+        self.detect_stdin_language();
 
         // Status will be updated by poll_stdin_streaming
         self.status_message = Some(t!("stdin.streaming").to_string());
@@ -735,6 +912,9 @@ impl Editor {
             changed = true;
         }
 
+        if changed {
+            self.detect_stdin_language();
+        }
         changed
     }
 
@@ -763,6 +943,214 @@ impl Editor {
             self.status_message =
                 Some(t!("stdin.read_complete", bytes = stream_state.last_known_size).to_string());
         }
+        self.detect_stdin_language();
+    }
+
+    /// Guess the stdin buffer's language from its first line (a shebang
+    /// such as `#!/usr/bin/env python`, `<?xml`, ...) once the line is in
+    fn detect_stdin_language(&mut self) {
+        let Some(stream_state) = self.stdin_streaming.as_mut() else {
+            return;
+        };
+        if !stream_state.detect_language {
+            return;
+        }
+        let (buffer_id, complete) = (stream_state.buffer_id, stream_state.complete);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        const MAX_FIRST_LINE: usize = 1024;
+        let head = state
+            .buffer
+            .get_text_range_mut(0, MAX_FIRST_LINE)
+            .unwrap_or_default();
+        let first_line = match head.iter().position(|&b| b == b'\n') {
+            Some(end) => &head[..end],
+            None if complete || head.len() >= MAX_FIRST_LINE => &head[..],
+            // Wait for the rest of the line
+            None => return,
+        };
+        if let Some(stream_state) = self.stdin_streaming.as_mut() {
+            stream_state.detect_language = false;
+        }
+        let syntax_name = self
+            .grammar_registry
+            .find_syntax_by_first_line(&String::from_utf8_lossy(first_line))
+            .map(|syntax| syntax.name.clone());
+        if let Some(name) = syntax_name {
+            tracing::info!("Detected stdin language from first line: {}", name);
+            self.apply_buffer_language(buffer_id, &name);
+        }
+    }
+
+    /// Set the language of a buffer by grammar name (case-insensitive, e.g.
+    /// "json") or file extension (e.g. "rs").
+    /// Returns the language's name, or None if no grammar matches.
+    pub fn set_buffer_language(&mut self, buffer_id: BufferId, name: &str) -> Option<String> {
+        let syntax_name = self.find_syntax_name(name)?;
+        self.apply_buffer_language(buffer_id, &syntax_name);
+        Some(syntax_name)
+    }
+
+    /// Highlight a buffer with the grammar named `syntax_name`, and make the
+    /// grammar's language the buffer's language
+    fn apply_buffer_language(&mut self, buffer_id: BufferId, syntax_name: &str) {
+        // A language chosen for the stdin buffer is not replaced by a guess
+        if let Some(stream_state) = self
+            .stdin_streaming
+            .as_mut()
+            .filter(|stream_state| stream_state.buffer_id == buffer_id)
+        {
+            stream_state.detect_language = false;
+        }
+        let language = self.language_for_syntax(syntax_name);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.set_syntax(syntax_name, &self.grammar_registry);
+        }
+        self.override_buffer_language(buffer_id, language);
+    }
+
+    /// The key of `config.languages` for a grammar: the language named like
+    /// it, or configured for one of its extensions. The grammar's name when
+    /// no language is configured for it.
+    fn language_for_syntax(&self, syntax_name: &str) -> String {
+        let languages = &self.config.languages;
+        if let Some(name) = languages
+            .keys()
+            .find(|name| name.eq_ignore_ascii_case(syntax_name))
+        {
+            return name.clone();
+        }
+        self.grammar_registry
+            .find_syntax_by_name(syntax_name)
+            .and_then(|syntax| {
+                syntax.file_extensions.iter().find_map(|extension| {
+                    languages
+                        .iter()
+                        .find(|(_, config)| config.extensions.contains(extension))
+                        .map(|(name, _)| name.clone())
+                })
+            })
+            .unwrap_or_else(|| syntax_name.to_string())
+    }
+
+    /// Make `language` the buffer's language, whatever its file is named:
+    /// the language's settings from the config apply to it, and it moves
+    /// to the language's server
+    pub(crate) fn override_buffer_language(&mut self, buffer_id: BufferId, language: String) {
+        let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) else {
+            return;
+        };
+        let previous = metadata.language(&self.config.languages);
+        metadata.language_override = Some(language.clone());
+
+        let buffer_config = self.configured_buffer_settings(buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.language = language.clone();
+            state.show_whitespace_tabs = buffer_config.show_whitespace_tabs;
+            state.guides = VisualGuides::from_config(&self.config.editor, &buffer_config);
+            // Indentation detected in the file still wins
+            match state.detected_indentation {
+                Some(Indentation::Tabs) => state.use_tabs = true,
+                Some(Indentation::Spaces(width)) => {
+                    state.use_tabs = false;
+                    state.tab_size = width;
+                }
+                None => {
+                    state.use_tabs = buffer_config.use_tabs;
+                    state.tab_size = buffer_config.tab_size;
+                }
+            }
+            state
+                .concealer
+                .set_rules(&buffer_config.conceal, state.highlighter.language());
+            state
+                .indent_calculator
+                .get_mut()
+                .set_rules(buffer_config.indent_patterns.as_ref());
+        }
+
+        if previous.as_deref() != Some(language.as_str()) {
+            self.move_to_language_server(buffer_id, previous.as_deref());
+        }
+    }
+
+    /// Close a buffer's document on the server of its `previous` language
+    /// and open it on the server of its current one
+    fn move_to_language_server(&mut self, buffer_id: BufferId, previous: Option<&str>) {
+        let Some(mut metadata) = self.buffer_metadata.remove(&buffer_id) else {
+            return;
+        };
+        if let Some(language) = previous {
+            self.close_lsp_document(&mut metadata, language);
+        }
+        if metadata.lsp_enabled && metadata.file_uri().is_some() {
+            if let Some(path) = metadata.file_path().cloned() {
+                self.notify_lsp_file_opened(&path, buffer_id, &mut metadata);
+            }
+        }
+        self.buffer_metadata.insert(buffer_id, metadata);
+    }
+
+    /// Send didClose for a buffer's document to the server of `language` if
+    /// it was opened there. When no other buffer has a document open on that
+    /// server, it's marked idle and shut down once `lsp_idle_timeout_secs`
+    /// pass without one of its files being opened again.
+    ///
+    /// `metadata` must already be out of `buffer_metadata`.
+    fn close_lsp_document(&mut self, metadata: &mut BufferMetadata, language: &str) {
+        let (Some(uri), Some(lsp)) = (metadata.file_uri().cloned(), self.lsp.as_mut()) else {
+            return;
+        };
+        let Some(handle) = lsp.get_handle_mut(language) else {
+            return;
+        };
+        let handle_id = handle.id();
+        if !metadata.lsp_opened_with.remove(&handle_id) {
+            return;
+        }
+        if let Err(e) = handle.did_close(uri) {
+            tracing::warn!("Failed to send didClose to LSP: {}", e);
+        }
+        if !self
+            .buffer_metadata
+            .values()
+            .any(|other| other.lsp_opened_with.contains(&handle_id))
+        {
+            lsp.mark_idle(language);
+        }
+    }
+
+    /// The settings of the buffer's modelines (see `primitives::modeline`)
+    fn read_modeline(state: &mut EditorState) -> Modeline {
+        const MODELINE_BYTES: usize = 4096;
+        let len = state.buffer.len();
+        let head = state
+            .buffer
+            .get_text_range_mut(0, MODELINE_BYTES)
+            .unwrap_or_default();
+        let tail_start = len.saturating_sub(MODELINE_BYTES);
+        let tail = state
+            .buffer
+            .get_text_range_mut(tail_start, len - tail_start)
+            .unwrap_or_default();
+        Modeline::parse(
+            &String::from_utf8_lossy(&head),
+            &String::from_utf8_lossy(&tail),
+        )
+    }
+
+    /// The grammar a language name given by the user is for: the grammar
+    /// of that name (case-insensitive) or for that file extension
+    fn find_syntax_name(&self, name: &str) -> Option<String> {
+        self.grammar_registry
+            .find_syntax_by_name(name)
+            .or_else(|| {
+                self.grammar_registry
+                    .syntax_set()
+                    .find_syntax_by_extension(name)
+            })
+            .map(|syntax| syntax.name.clone())
     }
 
     /// Check if stdin streaming is active (not complete).
@@ -1013,8 +1401,7 @@ impl Editor {
             .unwrap_or_else(|| {
                 self.buffer_metadata
                     .get(&self.active_buffer())
-                    .and_then(|m| m.file_path())
-                    .and_then(|path| detect_language(path, &self.config.languages))
+                    .and_then(|m| m.language(&self.config.languages))
                     .unwrap_or_else(|| "unknown".to_string())
             });
 
@@ -1088,6 +1475,7 @@ impl Editor {
     fn close_buffer_internal(&mut self, id: BufferId) -> anyhow::Result<()> {
         // Save file state before closing (for per-file session persistence)
         self.save_file_state_on_close(id);
+        self.remember_closed_buffer(id);
 
         // If closing a terminal buffer, clean up terminal-related data structures
         if let Some(terminal_id) = self.terminal_buffers.remove(&id) {
@@ -1177,7 +1565,11 @@ impl Editor {
         self.buffers.remove(&id);
         self.event_logs.remove(&id);
         self.seen_byte_ranges.remove(&id);
-        self.buffer_metadata.remove(&id);
+        if let Some(mut metadata) = self.buffer_metadata.remove(&id) {
+            if let Some(language) = metadata.language(&self.config.languages) {
+                self.close_lsp_document(&mut metadata, &language);
+            }
+        }
         if let Some((request_id, _, _)) = self.semantic_tokens_in_flight.remove(&id) {
             self.pending_semantic_token_requests.remove(&request_id);
         }
@@ -1198,6 +1590,12 @@ impl Editor {
             view_state.remove_buffer(id);
             view_state.remove_from_history(id);
         }
+        self.edit_locations.remove_buffer(id);
+        self.cursor_history
+            .retain(|(_, buffer_id), _| *buffer_id != id);
+        self.disk_merge_bases.remove(&id);
+        self.dir_diffs.remove(&id);
+        self.stop_task(id);
 
         // If this was the last visible buffer, focus file explorer
         if is_last_visible_buffer {
@@ -1433,17 +1831,17 @@ impl Editor {
 
     /// Close all other tabs in a split, keeping only the specified buffer
     pub fn close_other_tabs_in_split(&mut self, keep_buffer_id: BufferId, split_id: SplitId) {
-        // Get the split's open buffers
-        let split_tabs = self
+        // Get the split's open buffers; pinned tabs are left open
+        let (split_tabs, pinned) = self
             .split_view_states
             .get(&split_id)
-            .map(|vs| vs.open_buffers.clone())
+            .map(|vs| (vs.open_buffers.clone(), vs.pinned_buffers.clone()))
             .unwrap_or_default();
 
         // Close all tabs except the one we want to keep
         let tabs_to_close: Vec<_> = split_tabs
             .iter()
-            .filter(|&&id| id != keep_buffer_id)
+            .filter(|&&id| id != keep_buffer_id && !pinned.contains(&id))
             .copied()
             .collect();
 
@@ -1467,11 +1865,11 @@ impl Editor {
 
     /// Close tabs to the right of the specified buffer in a split
     pub fn close_tabs_to_right_in_split(&mut self, buffer_id: BufferId, split_id: SplitId) {
-        // Get the split's open buffers
-        let split_tabs = self
+        // Get the split's open buffers; pinned tabs are left open
+        let (split_tabs, pinned) = self
             .split_view_states
             .get(&split_id)
-            .map(|vs| vs.open_buffers.clone())
+            .map(|vs| (vs.open_buffers.clone(), vs.pinned_buffers.clone()))
             .unwrap_or_default();
 
         // Find the index of the target buffer
@@ -1480,7 +1878,12 @@ impl Editor {
         };
 
         // Close all tabs after the target
-        let tabs_to_close: Vec<_> = split_tabs.iter().skip(target_idx + 1).copied().collect();
+        let tabs_to_close: Vec<_> = split_tabs
+            .iter()
+            .skip(target_idx + 1)
+            .filter(|id| !pinned.contains(id))
+            .copied()
+            .collect();
 
         let mut closed = 0;
         let mut skipped_modified = 0;
@@ -1497,11 +1900,11 @@ impl Editor {
 
     /// Close tabs to the left of the specified buffer in a split
     pub fn close_tabs_to_left_in_split(&mut self, buffer_id: BufferId, split_id: SplitId) {
-        // Get the split's open buffers
-        let split_tabs = self
+        // Get the split's open buffers; pinned tabs are left open
+        let (split_tabs, pinned) = self
             .split_view_states
             .get(&split_id)
-            .map(|vs| vs.open_buffers.clone())
+            .map(|vs| (vs.open_buffers.clone(), vs.pinned_buffers.clone()))
             .unwrap_or_default();
 
         // Find the index of the target buffer
@@ -1510,7 +1913,12 @@ impl Editor {
         };
 
         // Close all tabs before the target
-        let tabs_to_close: Vec<_> = split_tabs.iter().take(target_idx).copied().collect();
+        let tabs_to_close: Vec<_> = split_tabs
+            .iter()
+            .take(target_idx)
+            .filter(|id| !pinned.contains(id))
+            .copied()
+            .collect();
 
         let mut closed = 0;
         let mut skipped_modified = 0;
@@ -0,0 +1,313 @@
+//! Buffer Switcher
+//!
+//! A popup listing the open buffers, most recently used first. While it's
+//! open the highlighted buffer is previewed in the active split; the switch
+//! is kept when the popup is closed with Enter or by releasing the modifier
+//! that opened it, and undone with Esc.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, ModifierKeyCode};
+
+use crate::model::event::BufferId;
+
+/// Modifiers that can be held down to keep the switcher open
+const HOLD_MODIFIERS: KeyModifiers = KeyModifiers::CONTROL
+    .union(KeyModifiers::ALT)
+    .union(KeyModifiers::SUPER);
+
+/// A buffer listed in the switcher
+#[derive(Debug, Clone)]
+pub struct BufferSwitcherEntry {
+    pub buffer_id: BufferId,
+    /// Name shown for the buffer
+    pub name: String,
+    /// Directory of the buffer's file, if it has one
+    pub detail: Option<String>,
+    /// Whether the buffer has unsaved changes
+    pub modified: bool,
+}
+
+/// What to do after an event is handled by the switcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferSwitcherAction {
+    /// Keep the switcher open
+    Continue,
+    /// Show a buffer in the active split, keeping the switcher open
+    Preview(BufferId),
+    /// Close a buffer, keeping the switcher open
+    CloseBuffer(BufferId),
+    /// Close the switcher, keeping the buffer shown
+    Commit(BufferId),
+    /// Close the switcher and go back to the buffer it was opened from
+    Cancel,
+    /// Close the switcher, keeping the buffer shown, then handle the key as
+    /// if the switcher wasn't open
+    CommitAndForward(BufferId),
+}
+
+/// State of the buffer switcher popup
+#[derive(Debug)]
+pub struct BufferSwitcher {
+    /// Buffers, most recently used first
+    entries: Vec<BufferSwitcherEntry>,
+    /// Index of the highlighted entry
+    selected: usize,
+    /// Modifiers seen held down since the switcher opened
+    held: KeyModifiers,
+    /// Buffer shown when the switcher was opened
+    pub(crate) original: BufferId,
+    /// Tabs of the active split when the switcher was opened
+    pub(crate) tabs: Vec<BufferId>,
+    /// Focus history of the active split when the switcher was opened
+    pub(crate) focus_history: Vec<BufferId>,
+}
+
+impl BufferSwitcher {
+    /// Create a switcher opened from the `original` buffer, listing `entries`
+    /// most recently used first (`original` first), with the entry `step`
+    /// away from the original highlighted
+    pub fn new(
+        original: BufferId,
+        entries: Vec<BufferSwitcherEntry>,
+        step: isize,
+        tabs: Vec<BufferId>,
+        focus_history: Vec<BufferId>,
+    ) -> Self {
+        let mut switcher = Self {
+            entries,
+            selected: 0,
+            held: KeyModifiers::NONE,
+            original,
+            tabs,
+            focus_history,
+        };
+        switcher.move_selection(step);
+        switcher
+    }
+
+    pub fn entries(&self) -> &[BufferSwitcherEntry] {
+        &self.entries
+    }
+
+    /// Index of the highlighted entry
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The highlighted buffer
+    pub fn selected_buffer(&self) -> Option<BufferId> {
+        self.entries.get(self.selected).map(|entry| entry.buffer_id)
+    }
+
+    /// Move the highlight `step` entries down, wrapping around
+    pub fn move_selection(&mut self, step: isize) -> BufferSwitcherAction {
+        if self.entries.is_empty() {
+            return BufferSwitcherAction::Continue;
+        }
+        let len = self.entries.len() as isize;
+        self.selected = (self.selected as isize + step).rem_euclid(len) as usize;
+        self.preview()
+    }
+
+    /// Move the highlight `step` entries down for a press of the key that
+    /// opens the switcher
+    pub fn cycle(&mut self, event: KeyEvent, step: isize) -> BufferSwitcherAction {
+        self.held |= event.modifiers & HOLD_MODIFIERS;
+        self.move_selection(step)
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, event: KeyEvent) -> BufferSwitcherAction {
+        self.held |= event.modifiers & HOLD_MODIFIERS;
+        let Some(selected) = self.selected_buffer() else {
+            return BufferSwitcherAction::Cancel;
+        };
+        match event.code {
+            KeyCode::Esc => BufferSwitcherAction::Cancel,
+            KeyCode::Enter => BufferSwitcherAction::Commit(selected),
+            KeyCode::Down | KeyCode::Tab => self.move_selection(1),
+            KeyCode::Up | KeyCode::BackTab => self.move_selection(-1),
+            KeyCode::Home => self.move_selection(-(self.selected as isize)),
+            KeyCode::End => {
+                self.move_selection(self.entries.len() as isize - 1 - self.selected as isize)
+            }
+            KeyCode::Delete => BufferSwitcherAction::CloseBuffer(selected),
+            KeyCode::Char('w') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                BufferSwitcherAction::CloseBuffer(selected)
+            }
+            // Modifiers pressed on their own (with the kitty keyboard protocol)
+            KeyCode::Modifier(_) => BufferSwitcherAction::Continue,
+            _ => BufferSwitcherAction::CommitAndForward(selected),
+        }
+    }
+
+    /// Handle a key release: letting go of the modifier held down since the
+    /// switcher opened keeps the highlighted buffer
+    pub fn handle_key_release(&mut self, event: KeyEvent) -> BufferSwitcherAction {
+        if event.kind != KeyEventKind::Release {
+            return BufferSwitcherAction::Continue;
+        }
+        let released_modifier = match event.code {
+            KeyCode::Modifier(ModifierKeyCode::LeftControl | ModifierKeyCode::RightControl) => {
+                KeyModifiers::CONTROL
+            }
+            KeyCode::Modifier(ModifierKeyCode::LeftAlt | ModifierKeyCode::RightAlt) => {
+                KeyModifiers::ALT
+            }
+            KeyCode::Modifier(ModifierKeyCode::LeftSuper | ModifierKeyCode::RightSuper) => {
+                KeyModifiers::SUPER
+            }
+            _ => KeyModifiers::NONE,
+        };
+        let still_held = (event.modifiers & HOLD_MODIFIERS) - released_modifier;
+        if !self.held.is_empty() && !still_held.contains(self.held) {
+            if let Some(selected) = self.selected_buffer() {
+                return BufferSwitcherAction::Commit(selected);
+            }
+        }
+        // A release with a modifier held means the switcher was opened with it
+        self.held |= still_held;
+        BufferSwitcherAction::Continue
+    }
+
+    /// Highlight the entry at `index` and keep it (e.g. when it's clicked)
+    pub fn choose(&mut self, index: usize) -> BufferSwitcherAction {
+        match self.entries.get(index) {
+            Some(entry) => {
+                self.selected = index;
+                BufferSwitcherAction::Commit(entry.buffer_id)
+            }
+            None => BufferSwitcherAction::Continue,
+        }
+    }
+
+    /// Take a closed buffer off the list and preview the one that took its
+    /// place
+    pub fn remove(&mut self, buffer_id: BufferId) -> BufferSwitcherAction {
+        self.entries.retain(|entry| entry.buffer_id != buffer_id);
+        self.tabs.retain(|&id| id != buffer_id);
+        self.focus_history.retain(|&id| id != buffer_id);
+        if self.entries.is_empty() {
+            return BufferSwitcherAction::Cancel;
+        }
+        self.selected = self.selected.min(self.entries.len() - 1);
+        if self.original == buffer_id {
+            self.original = self.entries[0].buffer_id;
+        }
+        self.preview()
+    }
+
+    fn preview(&self) -> BufferSwitcherAction {
+        match self.selected_buffer() {
+            Some(buffer_id) => BufferSwitcherAction::Preview(buffer_id),
+            None => BufferSwitcherAction::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn switcher(step: isize) -> BufferSwitcher {
+        let entries = (1..=3)
+            .map(|n| BufferSwitcherEntry {
+                buffer_id: BufferId(n),
+                name: format!("file{}.txt", n),
+                detail: None,
+                modified: false,
+            })
+            .collect();
+        BufferSwitcher::new(BufferId(1), entries, step, Vec::new(), Vec::new())
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn release(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Release)
+    }
+
+    #[test]
+    fn test_opens_on_previous_buffer_and_wraps() {
+        let mut s = switcher(1);
+        assert_eq!(s.selected_buffer(), Some(BufferId(2)));
+        assert_eq!(
+            s.handle_key(key(KeyCode::Down, KeyModifiers::NONE)),
+            BufferSwitcherAction::Preview(BufferId(3))
+        );
+        assert_eq!(
+            s.handle_key(key(KeyCode::Down, KeyModifiers::NONE)),
+            BufferSwitcherAction::Preview(BufferId(1))
+        );
+
+        let s = switcher(-1);
+        assert_eq!(s.selected_buffer(), Some(BufferId(3)));
+    }
+
+    #[test]
+    fn test_enter_esc_and_other_keys() {
+        let mut s = switcher(1);
+        assert_eq!(
+            s.handle_key(key(KeyCode::Enter, KeyModifiers::NONE)),
+            BufferSwitcherAction::Commit(BufferId(2))
+        );
+        assert_eq!(
+            s.handle_key(key(KeyCode::Esc, KeyModifiers::NONE)),
+            BufferSwitcherAction::Cancel
+        );
+        assert_eq!(
+            s.handle_key(key(KeyCode::Char('x'), KeyModifiers::NONE)),
+            BufferSwitcherAction::CommitAndForward(BufferId(2))
+        );
+    }
+
+    #[test]
+    fn test_releasing_held_modifier_commits() {
+        let mut s = switcher(1);
+        // Tab is let go first, with Ctrl still down
+        assert_eq!(
+            s.handle_key_release(release(KeyCode::Tab, KeyModifiers::CONTROL)),
+            BufferSwitcherAction::Continue
+        );
+        assert_eq!(
+            s.handle_key_release(release(
+                KeyCode::Modifier(ModifierKeyCode::LeftControl),
+                KeyModifiers::CONTROL
+            )),
+            BufferSwitcherAction::Commit(BufferId(2))
+        );
+    }
+
+    #[test]
+    fn test_release_without_held_modifier_is_ignored() {
+        // Opened from the command palette: the release of Enter doesn't commit
+        let mut s = switcher(1);
+        assert_eq!(
+            s.handle_key_release(release(KeyCode::Enter, KeyModifiers::NONE)),
+            BufferSwitcherAction::Continue
+        );
+    }
+
+    #[test]
+    fn test_remove_closed_buffer() {
+        let mut s = switcher(2);
+        assert_eq!(
+            s.handle_key(key(KeyCode::Char('w'), KeyModifiers::CONTROL)),
+            BufferSwitcherAction::CloseBuffer(BufferId(3))
+        );
+        // The last entry was closed: the one before it is shown
+        assert_eq!(
+            s.remove(BufferId(3)),
+            BufferSwitcherAction::Preview(BufferId(2))
+        );
+        // Closing the buffer the switcher was opened from
+        s.handle_key(key(KeyCode::Home, KeyModifiers::NONE));
+        assert_eq!(
+            s.remove(BufferId(1)),
+            BufferSwitcherAction::Preview(BufferId(2))
+        );
+        assert_eq!(s.original, BufferId(2));
+        assert_eq!(s.remove(BufferId(2)), BufferSwitcherAction::Cancel);
+    }
+}
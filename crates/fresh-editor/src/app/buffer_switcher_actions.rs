@@ -0,0 +1,260 @@
+//! Buffer switcher action handling
+//!
+//! Opens the switcher with the buffers in most recently used order, previews
+//! the highlighted buffer in the active split, and routes keys and mouse
+//! events to it while it's open.
+
+use super::buffer_switcher::{BufferSwitcher, BufferSwitcherAction, BufferSwitcherEntry};
+use super::Editor;
+use crate::input::handler::InputResult;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::BufferId;
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use rust_i18n::t;
+
+impl Editor {
+    /// Open the buffer switcher with the buffer `step` away from the current
+    /// one highlighted, or move the highlight if it's open
+    pub fn open_buffer_switcher(&mut self, step: isize) {
+        if let Some(switcher) = self.buffer_switcher.as_mut() {
+            let action = switcher.move_selection(step);
+            self.apply_buffer_switcher_action(action);
+            return;
+        }
+        let entries = self.buffer_switcher_entries();
+        if entries.len() < 2 {
+            self.set_status_message(t!("buffer_switcher.no_other_buffers").to_string());
+            return;
+        }
+        let active_split = self.split_manager.active_split();
+        let (tabs, focus_history) = self
+            .split_view_states
+            .get(&active_split)
+            .map(|vs| (vs.open_buffers.clone(), vs.focus_history.clone()))
+            .unwrap_or_default();
+        let switcher =
+            BufferSwitcher::new(self.active_buffer(), entries, step, tabs, focus_history);
+        let action = switcher.selected_buffer().map_or(
+            BufferSwitcherAction::Continue,
+            BufferSwitcherAction::Preview,
+        );
+        self.buffer_switcher = Some(switcher);
+        self.apply_buffer_switcher_action(action);
+    }
+
+    /// Check if the buffer switcher is open
+    pub fn is_buffer_switcher_open(&self) -> bool {
+        self.buffer_switcher.is_some()
+    }
+
+    /// Handle a key press while the buffer switcher is open. Keys the
+    /// switcher doesn't use close it and are ignored, to be handled as usual.
+    pub fn handle_buffer_switcher_input(&mut self, event: &KeyEvent) -> InputResult {
+        let Some(switcher) = self.buffer_switcher.as_mut() else {
+            return InputResult::Ignored;
+        };
+        // The keys that open the switcher move the highlight
+        let action = match self.keybindings.resolve(event, KeyContext::Normal) {
+            Action::SwitchBufferMru => switcher.cycle(*event, 1),
+            Action::SwitchBufferMruReverse => switcher.cycle(*event, -1),
+            _ => switcher.handle_key(*event),
+        };
+        let forward = matches!(action, BufferSwitcherAction::CommitAndForward(_));
+        self.apply_buffer_switcher_action(action);
+        if forward {
+            InputResult::Ignored
+        } else {
+            InputResult::Consumed
+        }
+    }
+
+    /// Handle a key release. Releasing the modifier held down since the
+    /// buffer switcher opened closes it on the highlighted buffer. Returns
+    /// whether to render.
+    pub fn handle_key_release(&mut self, event: &KeyEvent) -> bool {
+        let Some(switcher) = self.buffer_switcher.as_mut() else {
+            return false;
+        };
+        let action = switcher.handle_key_release(*event);
+        if action == BufferSwitcherAction::Continue {
+            return false;
+        }
+        self.apply_buffer_switcher_action(action);
+        true
+    }
+
+    /// Handle a mouse event while the buffer switcher is open. Returns
+    /// whether to render.
+    pub(super) fn handle_buffer_switcher_mouse(
+        &mut self,
+        mouse_event: MouseEvent,
+    ) -> AnyhowResult<bool> {
+        let Some(layout) = self.cached_layout.buffer_switcher_layout.clone() else {
+            return Ok(false);
+        };
+        let Some(switcher) = self.buffer_switcher.as_mut() else {
+            return Ok(false);
+        };
+        let (col, row) = (mouse_event.column, mouse_event.row);
+        let action = match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => match layout.entry_at(col, row) {
+                Some(index) => switcher.choose(index),
+                // Clicking outside the popup goes back, like Esc
+                None if !layout.contains(col, row) => BufferSwitcherAction::Cancel,
+                None => BufferSwitcherAction::Continue,
+            },
+            MouseEventKind::ScrollUp => switcher.move_selection(-1),
+            MouseEventKind::ScrollDown => switcher.move_selection(1),
+            _ => return Ok(false),
+        };
+        self.apply_buffer_switcher_action(action);
+        Ok(true)
+    }
+
+    fn apply_buffer_switcher_action(&mut self, mut action: BufferSwitcherAction) {
+        loop {
+            action = match action {
+                BufferSwitcherAction::Continue => return,
+                BufferSwitcherAction::Preview(buffer_id) => {
+                    self.show_buffer_quietly(buffer_id);
+                    return;
+                }
+                BufferSwitcherAction::CloseBuffer(buffer_id) => {
+                    let name = self.get_buffer_display_name(buffer_id);
+                    // Closing the shown buffer switches to another one, which
+                    // is only a preview
+                    self.in_navigation = true;
+                    let result = self.close_buffer(buffer_id);
+                    self.in_navigation = false;
+                    match result {
+                        Ok(()) => {
+                            self.set_status_message(t!("buffer.closed").to_string());
+                            match self.buffer_switcher.as_mut() {
+                                Some(switcher) => switcher.remove(buffer_id),
+                                None => return,
+                            }
+                        }
+                        Err(_) => {
+                            self.set_status_message(
+                                t!("buffer_switcher.modified", name = name).to_string(),
+                            );
+                            return;
+                        }
+                    }
+                }
+                BufferSwitcherAction::Commit(buffer_id)
+                | BufferSwitcherAction::CommitAndForward(buffer_id) => {
+                    self.close_buffer_switcher(Some(buffer_id));
+                    return;
+                }
+                BufferSwitcherAction::Cancel => {
+                    self.close_buffer_switcher(None);
+                    return;
+                }
+            };
+        }
+    }
+
+    /// Close the switcher, going back to the buffer it was opened from as if
+    /// nothing had been previewed, then switching to `target` (if any) the
+    /// usual way, so it's recorded in the history once
+    fn close_buffer_switcher(&mut self, target: Option<BufferId>) {
+        let Some(switcher) = self.buffer_switcher.take() else {
+            return;
+        };
+        self.show_buffer_quietly(switcher.original);
+        let active_split = self.split_manager.active_split();
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            view_state.open_buffers = switcher
+                .tabs
+                .into_iter()
+                .filter(|id| self.buffers.contains_key(id))
+                .collect();
+            view_state.add_buffer(switcher.original);
+            view_state.focus_history = switcher.focus_history;
+        }
+
+        let Some(target) = target.filter(|id| self.buffers.contains_key(id)) else {
+            return;
+        };
+        if target != self.active_buffer() {
+            // Save the current position before switching, like switching tabs
+            self.position_history.commit_pending_movement();
+            let current_state = self.active_state();
+            let position = current_state.cursors.primary().position;
+            let anchor = current_state.cursors.primary().anchor;
+            self.position_history
+                .record_movement(self.active_buffer(), position, anchor);
+            self.position_history.commit_pending_movement();
+
+            self.set_active_buffer(target);
+        }
+    }
+
+    /// Show a buffer in the active split without recording a jump
+    fn show_buffer_quietly(&mut self, buffer_id: BufferId) {
+        if !self.buffers.contains_key(&buffer_id) {
+            return;
+        }
+        self.in_navigation = true;
+        self.set_active_buffer(buffer_id);
+        self.in_navigation = false;
+    }
+
+    /// Open buffers, most recently used first: the current buffer, the ones
+    /// previously focused in the active split, its other tabs, then every
+    /// other buffer. Buffers hidden from tabs are left out.
+    pub(super) fn buffers_by_recency(&self) -> Vec<BufferId> {
+        let current = self.active_buffer();
+        let active_split = self.split_manager.active_split();
+        let mut order = vec![current];
+        if let Some(view_state) = self.split_view_states.get(&active_split) {
+            order.extend(view_state.focus_history.iter().rev());
+            order.extend(&view_state.open_buffers);
+        }
+        let mut others: Vec<BufferId> = self.buffers.keys().copied().collect();
+        others.sort_by_key(|id| id.0);
+        order.extend(others);
+
+        let mut seen = std::collections::HashSet::new();
+        order
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .filter(|id| {
+                *id == current
+                    || (self.buffers.contains_key(id)
+                        && !self
+                            .buffer_metadata
+                            .get(id)
+                            .is_some_and(|m| m.hidden_from_tabs))
+            })
+            .collect()
+    }
+
+    /// The buffers to list, in the order of `buffers_by_recency`
+    fn buffer_switcher_entries(&self) -> Vec<BufferSwitcherEntry> {
+        self.buffers_by_recency()
+            .into_iter()
+            .map(|buffer_id| {
+                let detail = self
+                    .buffer_metadata
+                    .get(&buffer_id)
+                    .and_then(|m| m.file_path())
+                    .and_then(|path| path.parent())
+                    .map(|dir| dir.strip_prefix(&self.working_dir).unwrap_or(dir))
+                    .map(|dir| dir.display().to_string())
+                    .filter(|dir| !dir.is_empty());
+                BufferSwitcherEntry {
+                    buffer_id,
+                    name: self.get_buffer_display_name(buffer_id),
+                    detail,
+                    modified: self
+                        .buffers
+                        .get(&buffer_id)
+                        .is_some_and(|state| state.buffer.is_modified()),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,253 @@
+//! Closing many buffers at once
+//!
+//! Close All, Close Saved and Close Others close the buffers without unsaved
+//! changes straight away. The ones with unsaved changes are listed in a
+//! prompt offering to save them all, discard them all, or go through them one
+//! at a time with the usual close prompt.
+
+use rust_i18n::t;
+
+use crate::input::commands::Suggestion;
+use crate::model::event::BufferId;
+use crate::view::prompt::{Prompt, PromptType};
+
+use super::Editor;
+
+impl Editor {
+    /// Close every buffer
+    pub fn close_all_buffers(&mut self) {
+        let buffers = self.buffers_to_close(|_, _| true);
+        self.close_buffers(buffers);
+    }
+
+    /// Close every buffer without unsaved changes
+    pub fn close_saved_buffers(&mut self) {
+        let buffers = self.buffers_to_close(|editor, id| !editor.has_unsaved_changes(id));
+        self.close_buffers(buffers);
+    }
+
+    /// Close every buffer but the active one and the ones pinned in a split
+    pub fn close_other_buffers(&mut self) {
+        let active = self.active_buffer();
+        let buffers = self.buffers_to_close(|editor, id| {
+            id != active
+                && !editor
+                    .split_view_states
+                    .values()
+                    .any(|view_state| view_state.is_pinned(id))
+        });
+        self.close_buffers(buffers);
+    }
+
+    /// Whether closing a buffer would lose changes. A buffer edited back to
+    /// its saved content has none.
+    pub(super) fn has_unsaved_changes(&self, buffer_id: BufferId) -> bool {
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        if !state.buffer.is_modified() {
+            return false;
+        }
+        // Comparing with the saved content is too slow in large file mode
+        state.buffer.line_count().is_none() || !state.buffer.diff_since_saved().equal
+    }
+
+    /// The buffers shown in tabs that `filter` accepts, oldest first
+    fn buffers_to_close(&self, filter: impl Fn(&Self, BufferId) -> bool) -> Vec<BufferId> {
+        let mut buffers: Vec<BufferId> = self
+            .buffers
+            .keys()
+            .copied()
+            .filter(|id| {
+                !self
+                    .buffer_metadata
+                    .get(id)
+                    .is_some_and(|m| m.hidden_from_tabs)
+            })
+            .filter(|&id| filter(self, id))
+            .collect();
+        buffers.sort_by_key(|id| id.0);
+        buffers
+    }
+
+    /// Close the buffers without unsaved changes, then ask what to do with
+    /// the rest
+    fn close_buffers(&mut self, buffers: Vec<BufferId>) {
+        let (unsaved, saved): (Vec<_>, Vec<_>) = buffers
+            .into_iter()
+            .partition(|&id| self.has_unsaved_changes(id));
+
+        let mut closed = 0;
+        for buffer_id in saved {
+            // Forced, as a buffer edited back to its saved content is still
+            // marked modified
+            match self.force_close_buffer(buffer_id) {
+                Ok(()) => closed += 1,
+                Err(e) => tracing::warn!("Failed to close buffer {:?}: {}", buffer_id, e),
+            }
+        }
+
+        if unsaved.is_empty() {
+            let message = match closed {
+                0 => t!("buffer.no_buffers_to_close"),
+                n => t!("buffer.closed_buffers", count = n),
+            };
+            self.set_status_message(message.to_string());
+        } else {
+            self.show_unsaved_buffers_prompt(unsaved);
+        }
+    }
+
+    /// Ask whether to save, discard or review the buffers with unsaved changes
+    fn show_unsaved_buffers_prompt(&mut self, buffers: Vec<BufferId>) {
+        let names: Vec<String> = buffers
+            .iter()
+            .map(|&id| self.get_buffer_display_name(id))
+            .collect();
+        let option = |text: String, description: Option<String>, value: &str| Suggestion {
+            text,
+            description,
+            value: Some(value.to_string()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        };
+        let suggestions = vec![
+            option(
+                t!("close_unsaved.save_all").to_string(),
+                Some(t!("close_unsaved.save_all_desc").to_string()),
+                "save",
+            ),
+            option(
+                t!("close_unsaved.discard_all").to_string(),
+                Some(t!("close_unsaved.discard_all_desc").to_string()),
+                "discard",
+            ),
+            option(
+                t!("close_unsaved.review").to_string(),
+                Some(t!("close_unsaved.review_desc").to_string()),
+                "review",
+            ),
+            option(t!("close_unsaved.cancel").to_string(), None, "cancel"),
+        ];
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!(
+                "close_unsaved.prompt",
+                count = buffers.len(),
+                names = names.join(", ")
+            )
+            .to_string(),
+            PromptType::CloseUnsavedBuffers { buffers },
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Handle CloseUnsavedBuffers prompt confirmation
+    pub(super) fn handle_close_unsaved_buffers(&mut self, buffers: Vec<BufferId>, action: &str) {
+        let buffers: Vec<BufferId> = buffers
+            .into_iter()
+            .filter(|id| self.buffers.contains_key(id))
+            .collect();
+        match action.trim() {
+            "save" => self.save_and_close_buffers(buffers),
+            "discard" => {
+                let mut closed = 0;
+                for buffer_id in buffers {
+                    match self.force_close_buffer(buffer_id) {
+                        Ok(()) => closed += 1,
+                        Err(e) => tracing::warn!("Failed to close buffer {:?}: {}", buffer_id, e),
+                    }
+                }
+                self.set_status_message(t!("close_unsaved.discarded", count = closed).to_string());
+            }
+            "review" => {
+                self.close_review_queue = buffers.into();
+                self.review_next_unsaved_buffer();
+            }
+            _ => self.set_status_message(t!("buffer.close_cancelled").to_string()),
+        }
+    }
+
+    /// Save and close each buffer. Buffers without a file are reviewed one
+    /// at a time afterwards, as they need a name to be saved.
+    fn save_and_close_buffers(&mut self, buffers: Vec<BufferId>) {
+        let mut closed = 0;
+        let mut failed = 0;
+        let mut unnamed = Vec::new();
+        for buffer_id in buffers {
+            let has_path = self
+                .buffers
+                .get(&buffer_id)
+                .is_some_and(|state| state.buffer.file_path().is_some());
+            if !has_path {
+                unnamed.push(buffer_id);
+                continue;
+            }
+
+            self.set_active_buffer(buffer_id);
+            if let Err(e) = self.save() {
+                tracing::warn!("Failed to save buffer {:?}: {}", buffer_id, e);
+            }
+            // A failed save leaves the buffer modified, and open
+            if !self.has_unsaved_changes(buffer_id) && self.force_close_buffer(buffer_id).is_ok() {
+                closed += 1;
+            } else {
+                failed += 1;
+            }
+            // Saving asked something (e.g. to save with sudo): leave the rest
+            if self.prompt.is_some() {
+                return;
+            }
+        }
+
+        let message = if failed == 0 {
+            t!("close_unsaved.saved", count = closed)
+        } else {
+            t!("close_unsaved.saved_some", closed = closed, failed = failed)
+        };
+        self.set_status_message(message.to_string());
+
+        self.close_review_queue = unnamed.into();
+        self.review_next_unsaved_buffer();
+    }
+
+    /// Ask about the next buffer waiting to be reviewed, with the usual
+    /// save/discard/cancel prompt for closing a modified buffer
+    pub(super) fn review_next_unsaved_buffer(&mut self) {
+        // Still answering something about the previous one (e.g. Save As)
+        if self.prompt.is_some() {
+            return;
+        }
+        while let Some(buffer_id) = self.close_review_queue.pop_front() {
+            if !self.buffers.contains_key(&buffer_id) {
+                continue;
+            }
+            if !self.has_unsaved_changes(buffer_id) {
+                let _ = self.force_close_buffer(buffer_id);
+                continue;
+            }
+
+            self.set_active_buffer(buffer_id);
+            let name = self.get_buffer_display_name(buffer_id);
+            let save_key = t!("prompt.key.save").to_string();
+            let discard_key = t!("prompt.key.discard").to_string();
+            let cancel_key = t!("prompt.key.cancel").to_string();
+            self.start_prompt(
+                t!(
+                    "prompt.buffer_modified",
+                    name = name,
+                    save_key = save_key,
+                    discard_key = discard_key,
+                    cancel_key = cancel_key
+                )
+                .to_string(),
+                PromptType::ConfirmCloseBuffer { buffer_id },
+            );
+            return;
+        }
+    }
+}
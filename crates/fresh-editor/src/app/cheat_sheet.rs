@@ -0,0 +1,337 @@
+//! Keybinding Cheat Sheet
+//!
+//! A full-screen overlay listing the commands of the active keymap with their
+//! keys, grouped by category. Typing filters the list, and Enter (or a click)
+//! runs the selected command.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::input::fuzzy::fuzzy_match;
+use crate::input::keybindings::Action;
+
+/// Entries moved by PageUp/PageDown
+const PAGE_SIZE: isize = 10;
+
+/// A command shown in the cheat sheet
+#[derive(Debug, Clone)]
+pub struct CheatSheetEntry {
+    /// Menu or plugin the command belongs to
+    pub category: String,
+    /// Localized command name
+    pub name: String,
+    /// Key the command is bound to, if any
+    pub keys: Option<String>,
+    /// Action run when the entry is chosen
+    pub action: Action,
+}
+
+/// A line of the cheat sheet list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheatSheetRow<'a> {
+    /// Heading of a category
+    Category(&'a str),
+    /// An entry, by index
+    Entry(usize),
+}
+
+/// What to do after a key is handled by the cheat sheet
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheatSheetAction {
+    /// Keep the cheat sheet open
+    Continue,
+    /// Close the cheat sheet
+    Close,
+    /// Close the cheat sheet and run an action
+    Execute(Action),
+}
+
+/// State of the cheat sheet overlay
+#[derive(Debug)]
+pub struct CheatSheet {
+    /// Entries, in display order (grouped by category)
+    entries: Vec<CheatSheetEntry>,
+    /// Filter typed by the user
+    pub query: String,
+    /// Index of the selected entry
+    selected: Option<usize>,
+    /// First row shown
+    pub scroll: usize,
+    /// Whether the next render should scroll the selection into view
+    follow_selection: bool,
+}
+
+impl CheatSheet {
+    /// Create a cheat sheet of `entries`, which must be grouped by category
+    pub fn new(entries: Vec<CheatSheetEntry>) -> Self {
+        let selected = (!entries.is_empty()).then_some(0);
+        Self {
+            entries,
+            query: String::new(),
+            selected,
+            scroll: 0,
+            follow_selection: true,
+        }
+    }
+
+    /// All entries, matching the query or not
+    pub fn entries(&self) -> &[CheatSheetEntry] {
+        &self.entries
+    }
+
+    /// Index of the selected entry
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Whether an entry matches the query: by name, or by its keys
+    fn matches(&self, entry: &CheatSheetEntry) -> bool {
+        if self.query.is_empty() {
+            return true;
+        }
+        fuzzy_match(&self.query, &entry.name).matched
+            || entry.keys.as_ref().is_some_and(|keys| {
+                keys.to_lowercase()
+                    .contains(&self.query.trim().to_lowercase())
+            })
+    }
+
+    /// Indices of the entries matching the query
+    fn matching(&self) -> Vec<usize> {
+        (0..self.entries.len())
+            .filter(|&i| self.matches(&self.entries[i]))
+            .collect()
+    }
+
+    /// The lines of the list: each category with entries matching the query,
+    /// followed by those entries
+    pub fn rows(&self) -> Vec<CheatSheetRow<'_>> {
+        let mut rows = Vec::new();
+        let mut category = None;
+        for i in self.matching() {
+            let entry_category = self.entries[i].category.as_str();
+            if category != Some(entry_category) {
+                rows.push(CheatSheetRow::Category(entry_category));
+                category = Some(entry_category);
+            }
+            rows.push(CheatSheetRow::Entry(i));
+        }
+        rows
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, event: KeyEvent) -> CheatSheetAction {
+        match event.code {
+            KeyCode::Esc if !self.query.is_empty() => {
+                self.query.clear();
+                self.select_first_match();
+            }
+            KeyCode::Esc => return CheatSheetAction::Close,
+            KeyCode::Enter => {
+                if let Some(i) = self.selected {
+                    return CheatSheetAction::Execute(self.entries[i].action.clone());
+                }
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-PAGE_SIZE),
+            KeyCode::PageDown => self.move_selection(PAGE_SIZE),
+            KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::End => self.move_selection(isize::MAX),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.select_first_match();
+            }
+            KeyCode::Char(c)
+                if !event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.select_first_match();
+            }
+            _ => {}
+        }
+        CheatSheetAction::Continue
+    }
+
+    /// Run the entry at `index` (e.g. when it's clicked)
+    pub fn choose(&mut self, index: usize) -> CheatSheetAction {
+        match self.entries.get(index) {
+            Some(entry) => {
+                self.selected = Some(index);
+                CheatSheetAction::Execute(entry.action.clone())
+            }
+            None => CheatSheetAction::Continue,
+        }
+    }
+
+    /// Scroll the list by `delta` rows, without moving the selection
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.rows().len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+        self.follow_selection = false;
+    }
+
+    /// Keep the scroll position within the list, and the selection in view
+    /// after it's moved, for a list `height` rows tall
+    pub fn update_scroll(&mut self, height: usize) {
+        let rows = self.rows();
+        let row_count = rows.len();
+        let selected_rows = self.selected.and_then(|selected| {
+            let row = rows
+                .iter()
+                .position(|&r| r == CheatSheetRow::Entry(selected))?;
+            // Show the category heading above the first entry too
+            let top = if row > 0 && matches!(rows[row - 1], CheatSheetRow::Category(_)) {
+                row - 1
+            } else {
+                row
+            };
+            Some((top, row))
+        });
+        if self.follow_selection {
+            if let Some((top, row)) = selected_rows {
+                if top < self.scroll {
+                    self.scroll = top;
+                } else if row >= self.scroll + height {
+                    self.scroll = row + 1 - height;
+                }
+            }
+            self.follow_selection = false;
+        }
+        self.scroll = self.scroll.min(row_count.saturating_sub(height));
+    }
+
+    fn select_first_match(&mut self) {
+        self.selected = self.matching().first().copied();
+        self.scroll = 0;
+        self.follow_selection = true;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let matching = self.matching();
+        if matching.is_empty() {
+            return;
+        }
+        let current = self
+            .selected
+            .and_then(|selected| matching.iter().position(|&i| i == selected))
+            .unwrap_or(0);
+        let new = current.saturating_add_signed(delta).min(matching.len() - 1);
+        self.selected = Some(matching[new]);
+        self.follow_selection = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(category: &str, name: &str, keys: Option<&str>) -> CheatSheetEntry {
+        CheatSheetEntry {
+            category: category.to_string(),
+            name: name.to_string(),
+            keys: keys.map(str::to_string),
+            action: Action::None,
+        }
+    }
+
+    fn cheat_sheet() -> CheatSheet {
+        CheatSheet::new(vec![
+            entry("File", "Save", Some("Ctrl+S")),
+            entry("File", "Open File", Some("Ctrl+O")),
+            entry("Edit", "Undo", Some("Ctrl+Z")),
+            entry("my-plugin", "Do Thing", None),
+        ])
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(sheet: &mut CheatSheet, text: &str) {
+        for c in text.chars() {
+            sheet.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    #[test]
+    fn test_rows_are_grouped_by_category() {
+        let sheet = cheat_sheet();
+        assert_eq!(
+            sheet.rows(),
+            vec![
+                CheatSheetRow::Category("File"),
+                CheatSheetRow::Entry(0),
+                CheatSheetRow::Entry(1),
+                CheatSheetRow::Category("Edit"),
+                CheatSheetRow::Entry(2),
+                CheatSheetRow::Category("my-plugin"),
+                CheatSheetRow::Entry(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_by_name_or_keys() {
+        let mut sheet = cheat_sheet();
+        type_text(&mut sheet, "undo");
+        assert_eq!(
+            sheet.rows(),
+            vec![CheatSheetRow::Category("Edit"), CheatSheetRow::Entry(2)]
+        );
+        assert_eq!(sheet.selected(), Some(2));
+
+        sheet.handle_key(key(KeyCode::Esc));
+        assert!(sheet.query.is_empty());
+        type_text(&mut sheet, "ctrl+o");
+        assert_eq!(sheet.selected(), Some(1));
+
+        // Esc clears the query first, then closes
+        assert_eq!(
+            sheet.handle_key(key(KeyCode::Esc)),
+            CheatSheetAction::Continue
+        );
+        assert_eq!(sheet.handle_key(key(KeyCode::Esc)), CheatSheetAction::Close);
+    }
+
+    #[test]
+    fn test_navigate_and_execute() {
+        let mut sheet = cheat_sheet();
+        sheet.handle_key(key(KeyCode::Down));
+        sheet.handle_key(key(KeyCode::Down));
+        assert_eq!(sheet.selected(), Some(2));
+        sheet.handle_key(key(KeyCode::End));
+        assert_eq!(sheet.selected(), Some(3));
+        sheet.handle_key(key(KeyCode::Down));
+        assert_eq!(sheet.selected(), Some(3));
+        sheet.handle_key(key(KeyCode::Home));
+        assert_eq!(sheet.selected(), Some(0));
+        assert_eq!(
+            sheet.handle_key(key(KeyCode::Enter)),
+            CheatSheetAction::Execute(Action::None)
+        );
+    }
+
+    #[test]
+    fn test_scroll_follows_selection() {
+        let mut sheet = cheat_sheet();
+        sheet.update_scroll(3);
+        assert_eq!(sheet.scroll, 0);
+
+        sheet.handle_key(key(KeyCode::End));
+        sheet.update_scroll(3);
+        assert_eq!(sheet.scroll, 4, "the last entry is at the bottom");
+
+        // Scrolling doesn't move the selection back into view
+        sheet.scroll_by(-10);
+        sheet.update_scroll(3);
+        assert_eq!(sheet.scroll, 0);
+
+        // Moving up to the first entry of a category shows its heading
+        sheet.handle_key(key(KeyCode::Up));
+        sheet.scroll = 4;
+        sheet.update_scroll(3);
+        assert_eq!(sheet.scroll, 3);
+    }
+}
@@ -0,0 +1,185 @@
+//! Keybinding cheat sheet action handling
+//!
+//! Builds the cheat sheet from the command registry and the active keymap,
+//! and routes keys and mouse events to it while it's open.
+
+use super::cheat_sheet::{CheatSheet, CheatSheetAction, CheatSheetEntry};
+use super::Editor;
+use crate::config::MenuItem;
+use crate::input::commands::CommandSource;
+use crate::input::handler::InputResult;
+use crate::input::keybindings::{Action, KeyContext};
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use rust_i18n::t;
+
+/// Rows scrolled per mouse wheel step
+const WHEEL_ROWS: isize = 3;
+
+impl Editor {
+    /// Open the cheat sheet, or close it if it's open
+    pub fn toggle_cheat_sheet(&mut self) {
+        if self.cheat_sheet.take().is_none() {
+            self.cheat_sheet = Some(CheatSheet::new(self.cheat_sheet_entries()));
+        }
+    }
+
+    /// Check if the cheat sheet is open
+    pub fn is_cheat_sheet_open(&self) -> bool {
+        self.cheat_sheet.is_some()
+    }
+
+    /// Handle input while the cheat sheet is open
+    pub fn handle_cheat_sheet_input(&mut self, event: &KeyEvent) -> InputResult {
+        // The key that opens the cheat sheet closes it too
+        if self.keybindings.resolve(event, KeyContext::Normal) == Action::ToggleCheatSheet {
+            self.cheat_sheet = None;
+            return InputResult::Consumed;
+        }
+        let Some(sheet) = self.cheat_sheet.as_mut() else {
+            return InputResult::Ignored;
+        };
+        let action = sheet.handle_key(*event);
+        self.apply_cheat_sheet_action(action);
+        InputResult::Consumed
+    }
+
+    /// Handle a mouse event while the cheat sheet is open. Returns whether to
+    /// render.
+    pub(super) fn handle_cheat_sheet_mouse(
+        &mut self,
+        mouse_event: MouseEvent,
+    ) -> AnyhowResult<bool> {
+        let Some(layout) = self.cached_layout.cheat_sheet_layout.clone() else {
+            return Ok(false);
+        };
+        let Some(sheet) = self.cheat_sheet.as_mut() else {
+            return Ok(false);
+        };
+        let (col, row) = (mouse_event.column, mouse_event.row);
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let action = match layout.entry_at(col, row) {
+                    Some(index) => sheet.choose(index),
+                    // Clicking outside the overlay closes it
+                    None if !layout.contains(col, row) => CheatSheetAction::Close,
+                    None => CheatSheetAction::Continue,
+                };
+                self.apply_cheat_sheet_action(action);
+                Ok(true)
+            }
+            MouseEventKind::ScrollUp => {
+                sheet.scroll_by(-WHEEL_ROWS);
+                Ok(true)
+            }
+            MouseEventKind::ScrollDown => {
+                sheet.scroll_by(WHEEL_ROWS);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn apply_cheat_sheet_action(&mut self, action: CheatSheetAction) {
+        match action {
+            CheatSheetAction::Continue => {}
+            CheatSheetAction::Close => self.cheat_sheet = None,
+            CheatSheetAction::Execute(action) => {
+                self.cheat_sheet = None;
+                if let Err(e) = self.handle_action(action) {
+                    tracing::warn!("Failed to run command from the cheat sheet: {}", e);
+                }
+            }
+        }
+    }
+
+    /// The commands to list: every command with a key in the active keymap,
+    /// and every plugin command. Each is put under the menu it appears in,
+    /// under its plugin, or under "Other".
+    fn cheat_sheet_entries(&self) -> Vec<CheatSheetEntry> {
+        let menus: Vec<_> = self
+            .menus
+            .menus
+            .iter()
+            .chain(self.menu_state.plugin_menus.iter())
+            .collect();
+        let mut menu_actions = Vec::new();
+        for (rank, menu) in menus.iter().enumerate() {
+            collect_menu_actions(&menu.items, rank, &menu.label, &mut menu_actions);
+        }
+
+        let active_buffer_mode = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|m| m.virtual_mode());
+        let commands = self.command_registry.read().unwrap().get_all();
+        let mut entries: Vec<(usize, CheatSheetEntry)> = commands
+            .iter()
+            .filter(|cmd| {
+                cmd.custom_contexts.iter().all(|ctx| {
+                    self.active_custom_contexts.contains(ctx)
+                        || active_buffer_mode == Some(ctx.as_str())
+                })
+            })
+            .filter_map(|cmd| {
+                let keys = self
+                    .keybindings
+                    .get_keybinding_for_action(&cmd.action, KeyContext::Normal);
+                let plugin = match &cmd.source {
+                    CommandSource::Plugin(name) => Some(name),
+                    CommandSource::Builtin => None,
+                };
+                if keys.is_none() && plugin.is_none() {
+                    return None;
+                }
+                let (rank, category) = menu_actions
+                    .iter()
+                    .find(|(action, _, _)| *action == cmd.action)
+                    .map(|(_, rank, label)| (*rank, label.clone()))
+                    .or_else(|| plugin.map(|name| (menus.len(), name.clone())))
+                    .unwrap_or_else(|| (menus.len() + 1, t!("cheat_sheet.other").to_string()));
+                Some((
+                    rank,
+                    CheatSheetEntry {
+                        category,
+                        name: cmd.get_localized_name(),
+                        keys,
+                        action: cmd.action.clone(),
+                    },
+                ))
+            })
+            .collect();
+        entries.sort_by(|(rank_a, a), (rank_b, b)| {
+            (rank_a, &a.category, a.name.to_lowercase()).cmp(&(
+                rank_b,
+                &b.category,
+                b.name.to_lowercase(),
+            ))
+        });
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+}
+
+/// Collect the actions of a menu's items, with the menu's rank and label
+fn collect_menu_actions(
+    items: &[MenuItem],
+    rank: usize,
+    label: &str,
+    actions: &mut Vec<(Action, usize, String)>,
+) {
+    for item in items {
+        match item {
+            MenuItem::Action { action, args, .. } => {
+                let action = Action::from_str(action, args)
+                    .unwrap_or_else(|| Action::PluginAction(action.clone()));
+                actions.push((action, rank, label.to_string()));
+            }
+            MenuItem::Submenu { items, .. } => {
+                collect_menu_actions(items, rank, label, actions);
+            }
+            MenuItem::Separator { .. }
+            | MenuItem::DynamicSubmenu { .. }
+            | MenuItem::Label { .. } => {}
+        }
+    }
+}
@@ -2,28 +2,24 @@
 //!
 //! This module contains clipboard operations and multi-cursor actions:
 //! - Copy/cut/paste operations
-//! - Copy with formatting (HTML with syntax highlighting)
+//! - Copy with formatting (HTML or ANSI escapes with syntax highlighting)
 //! - Multi-cursor add above/below/at next match
 
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use rust_i18n::t;
 
+use crate::input::keybindings::KeyContext;
 use crate::input::multi_cursor::{
     add_cursor_above, add_cursor_at_next_match, add_cursor_below, AddCursorResult,
 };
-use crate::model::buffer::Buffer;
-use crate::model::cursor::Position2D;
 use crate::model::event::{CursorId, Event};
+use crate::primitives::display_width::line_byte_offset_at_visual_column;
 use crate::primitives::word_navigation::{find_word_start_left, find_word_start_right};
 
 use super::Editor;
 
-/// Convert byte offset to 2D position (line, column)
-fn byte_to_2d(buffer: &Buffer, byte_pos: usize) -> Position2D {
-    let line = buffer.get_line_number(byte_pos);
-    let line_start = buffer.line_start_offset(line).unwrap_or(0);
-    let column = byte_pos.saturating_sub(line_start);
-    Position2D { line, column }
-}
+/// Shortest burst of typed keys without a line break that counts as a paste
+const MIN_TYPED_PASTE_CHARS: usize = 16;
 
 // These are the clipboard and multi-cursor operations on Editor.
 //
@@ -153,15 +149,18 @@ impl Editor {
         let mut result = String::new();
 
         for (block_anchor, anchor_byte, cursor_byte) in block_infos {
-            // Get current cursor position as 2D
-            let cursor_2d = {
+            // Get current cursor position as (line, visual column)
+            let (tab_size, cursor_col) = {
                 let state = self.active_state();
-                byte_to_2d(&state.buffer, cursor_byte)
+                let (_, column) = state
+                    .buffer
+                    .position_to_line_visual_col(cursor_byte, state.tab_size);
+                (state.tab_size, column)
             };
 
-            // Calculate column bounds (min and max columns for the rectangle)
-            let min_col = block_anchor.column.min(cursor_2d.column);
-            let max_col = block_anchor.column.max(cursor_2d.column);
+            // Calculate visual column bounds (min and max columns for the rectangle)
+            let min_col = block_anchor.column.min(cursor_col);
+            let max_col = block_anchor.column.max(cursor_col);
 
             // Calculate line bounds using byte positions
             let start_byte = anchor_byte.min(cursor_byte);
@@ -187,16 +186,20 @@ impl Editor {
                     // Extract the column range from this line
                     // Remove trailing newline for column calculation
                     let content_without_newline = line_content.trim_end_matches(&['\n', '\r'][..]);
-                    let chars: Vec<char> = content_without_newline.chars().collect();
 
-                    // Extract characters from min_col to max_col (exclusive)
-                    let extracted: String = chars
-                        .iter()
-                        .skip(min_col)
-                        .take(max_col.saturating_sub(min_col))
-                        .collect();
+                    // Extract the text from visual column min_col to max_col (exclusive)
+                    let start = line_byte_offset_at_visual_column(
+                        content_without_newline,
+                        min_col,
+                        tab_size,
+                    );
+                    let end = line_byte_offset_at_visual_column(
+                        content_without_newline,
+                        max_col,
+                        tab_size,
+                    );
 
-                    lines_text.push(extracted);
+                    lines_text.push(content_without_newline[start..end].to_string());
 
                     // If this line extends past end_byte, we're done
                     if line_start + line_content.len() > end_byte {
@@ -222,17 +225,7 @@ impl Editor {
     /// If theme_name is empty, opens a prompt to select a theme.
     /// Otherwise, copies the selected text as HTML with inline CSS styles.
     pub fn copy_selection_with_theme(&mut self, theme_name: &str) {
-        // Check if there's a selection first
-        let has_selection = {
-            let state = self.active_state();
-            state
-                .cursors
-                .iter()
-                .any(|(_, cursor)| cursor.selection_range().is_some())
-        };
-
-        if !has_selection {
-            self.status_message = Some(t!("clipboard.no_selection").to_string());
+        if !self.has_selection_to_copy() {
             return;
         }
 
@@ -252,86 +245,12 @@ impl Editor {
             }
         };
 
-        // Collect ranges and their byte offsets
-        let ranges: Vec<_> = {
-            let state = self.active_state();
-            state
-                .cursors
-                .iter()
-                .filter_map(|(_, cursor)| cursor.selection_range())
-                .collect()
-        };
-
-        if ranges.is_empty() {
-            self.status_message = Some(t!("clipboard.no_selection").to_string());
+        let Some((text, spans)) = self.highlighted_selection(&theme) else {
             return;
-        }
-
-        // Get the overall range for highlighting
-        let min_offset = ranges.iter().map(|r| r.start).min().unwrap_or(0);
-        let max_offset = ranges.iter().map(|r| r.end).max().unwrap_or(0);
-
-        // Collect text and highlight spans from state
-        let (text, highlight_spans) = {
-            let state = self.active_state_mut();
-
-            // Collect text from all ranges
-            let mut text = String::new();
-            for range in &ranges {
-                if !text.is_empty() {
-                    text.push('\n');
-                }
-                let range_text = state.get_text_range(range.start, range.end);
-                text.push_str(&range_text);
-            }
-
-            if text.is_empty() {
-                (text, Vec::new())
-            } else {
-                // Get highlight spans for the selected region
-                let highlight_spans = state.highlighter.highlight_viewport(
-                    &state.buffer,
-                    min_offset,
-                    max_offset,
-                    &theme,
-                    0, // No context needed since we're copying exact selection
-                );
-                (text, highlight_spans)
-            }
-        };
-
-        if text.is_empty() {
-            self.status_message = Some(t!("clipboard.no_text").to_string());
-            return;
-        }
-
-        // Adjust highlight spans to be relative to the copied text
-        let adjusted_spans: Vec<_> = if ranges.len() == 1 {
-            let base_offset = ranges[0].start;
-            highlight_spans
-                .into_iter()
-                .filter_map(|span| {
-                    if span.range.end <= base_offset || span.range.start >= ranges[0].end {
-                        return None;
-                    }
-                    let start = span.range.start.saturating_sub(base_offset);
-                    let end = (span.range.end - base_offset).min(text.len());
-                    if start < end {
-                        Some(crate::primitives::highlighter::HighlightSpan {
-                            range: start..end,
-                            color: span.color,
-                        })
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            Vec::new()
         };
 
         // Render the styled text to HTML
-        let html = render_styled_html(&text, &adjusted_spans, &theme);
+        let html = render_styled_html(&text, &spans, &theme);
 
         // Copy the HTML to clipboard (with plain text fallback)
         if self.clipboard.copy_html(&html, &text) {
@@ -343,6 +262,97 @@ impl Editor {
         }
     }
 
+    /// Copy selection as text with ANSI color escapes from the active theme,
+    /// for pasting into terminals and chat clients that show them
+    pub fn copy_selection_as_ansi(&mut self) {
+        use crate::services::styled_html::render_styled_ansi;
+
+        if !self.has_selection_to_copy() {
+            return;
+        }
+        let theme = self.theme.clone();
+        let Some((text, spans)) = self.highlighted_selection(&theme) else {
+            return;
+        };
+
+        self.clipboard.copy(render_styled_ansi(&text, &spans));
+        self.status_message = Some(t!("clipboard.copied_as_ansi").to_string());
+    }
+
+    /// Whether any cursor has a selection, setting a status message if not
+    fn has_selection_to_copy(&mut self) -> bool {
+        let has_selection = self
+            .active_state()
+            .cursors
+            .iter()
+            .any(|(_, cursor)| cursor.selection_range().is_some());
+        if !has_selection {
+            self.status_message = Some(t!("clipboard.no_selection").to_string());
+        }
+        has_selection
+    }
+
+    /// The selected text, with selections joined by newlines, and its syntax
+    /// highlighting in `theme` relative to the start of that text
+    ///
+    /// Returns None (with a status message) if nothing is selected.
+    fn highlighted_selection(
+        &mut self,
+        theme: &crate::view::theme::Theme,
+    ) -> Option<(String, Vec<crate::primitives::highlighter::HighlightSpan>)> {
+        use crate::primitives::highlighter::HighlightSpan;
+
+        let mut ranges: Vec<_> = self
+            .active_state()
+            .cursors
+            .iter()
+            .filter_map(|(_, cursor)| cursor.selection_range())
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+
+        let (Some(min_offset), Some(max_offset)) = (
+            ranges.first().map(|r| r.start),
+            ranges.iter().map(|r| r.end).max(),
+        ) else {
+            self.status_message = Some(t!("clipboard.no_selection").to_string());
+            return None;
+        };
+
+        let state = self.active_state_mut();
+        // No context needed since we're copying exact selection
+        let highlight_spans =
+            state
+                .highlighter
+                .highlight_viewport(&state.buffer, min_offset, max_offset, theme, 0);
+
+        // Join the selections, moving each one's spans to where it lands
+        let mut text = String::new();
+        let mut spans = Vec::new();
+        for range in &ranges {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            let text_start = text.len();
+            text.push_str(&state.get_text_range(range.start, range.end));
+            for span in &highlight_spans {
+                let start = span.range.start.max(range.start);
+                let end = span.range.end.min(range.end);
+                if start < end {
+                    spans.push(HighlightSpan {
+                        range: text_start + start - range.start..text_start + end - range.start,
+                        color: span.color,
+                    });
+                }
+            }
+        }
+
+        if text.is_empty() {
+            self.status_message = Some(t!("clipboard.no_text").to_string());
+            return None;
+        }
+        Some((text, spans))
+    }
+
     /// Start the theme selection prompt for copy with formatting
     fn start_copy_with_formatting_prompt(&mut self) {
         use crate::view::prompt::PromptType;
@@ -528,6 +538,52 @@ impl Editor {
         self.paste_text(text);
     }
 
+    /// The character a typed key press adds when it is part of pasted text.
+    ///
+    /// None for other events, and for every key while keys may run commands
+    /// rather than insert text: while a chord is pending, outside the Normal
+    /// context, in a global editor mode (like vi's) or when the active buffer
+    /// has a mode.
+    pub fn typed_paste_char(&self, event: &CrosstermEvent) -> Option<char> {
+        if self.is_event_debug_active()
+            || !self.chord_state.is_empty()
+            || self.get_key_context() != KeyContext::Normal
+            || self.editor_mode.is_some()
+            || self.active_buffer_mode().is_some()
+        {
+            return None;
+        }
+        match event {
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => {
+                pasted_char(&self.key_translator().translate(*key))
+            }
+            _ => None,
+        }
+    }
+
+    /// Turn a burst of typed keys into a paste.
+    ///
+    /// Terminals without bracketed paste deliver pasted text as key presses,
+    /// and handling those one at a time auto-indents every pasted line, makes
+    /// an undo step per key and crawls through large pastes. Keys that were
+    /// already queued up together weren't typed by hand, so a burst with a
+    /// line break, or a long one, becomes a single paste. Any other burst is
+    /// returned as is, to handle key by key.
+    pub fn coalesce_typed_paste(&self, burst: Vec<CrosstermEvent>) -> Vec<CrosstermEvent> {
+        let Some(text) = burst
+            .iter()
+            .map(|event| self.typed_paste_char(event))
+            .collect::<Option<String>>()
+        else {
+            return burst;
+        };
+        if (text.contains('\n') && burst.len() > 1) || burst.len() >= MIN_TYPED_PASTE_CHARS {
+            tracing::debug!("Pasting {} typed characters at once", burst.len());
+            return vec![CrosstermEvent::Paste(text)];
+        }
+        burst
+    }
+
     /// Paste text directly into the editor
     ///
     /// Handles:
@@ -537,6 +593,7 @@ impl Editor {
     /// - Selection replacement (deletes selection before inserting)
     /// - Atomic undo (single undo step for entire operation)
     /// - Routing to prompt if one is open
+    /// - Routing to the terminal in terminal mode
     pub fn paste_text(&mut self, paste_text: String) {
         if paste_text.is_empty() {
             return;
@@ -554,6 +611,12 @@ impl Editor {
             return;
         }
 
+        // In terminal mode the paste goes to the program running in the terminal
+        if self.terminal_mode && self.is_terminal_buffer(self.active_buffer()) {
+            self.send_terminal_paste(&normalized);
+            return;
+        }
+
         // Convert to buffer's line ending format
         let buffer_line_ending = self.active_state().buffer.line_ending();
         let paste_text = match buffer_line_ending {
@@ -945,3 +1008,45 @@ impl Editor {
         }
     }
 }
+
+/// The character a key press adds when it is part of pasted text
+fn pasted_char(key: &KeyEvent) -> Option<char> {
+    match key.code {
+        KeyCode::Char(c) if key.modifiers.difference(KeyModifiers::SHIFT).is_empty() => Some(c),
+        KeyCode::Enter if key.modifiers.is_empty() => Some('\n'),
+        KeyCode::Tab if key.modifiers.is_empty() => Some('\t'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pasted_char() {
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        assert_eq!(
+            pasted_char(&key(KeyCode::Char('a'), KeyModifiers::NONE)),
+            Some('a')
+        );
+        assert_eq!(
+            pasted_char(&key(KeyCode::Char('A'), KeyModifiers::SHIFT)),
+            Some('A')
+        );
+        assert_eq!(
+            pasted_char(&key(KeyCode::Enter, KeyModifiers::NONE)),
+            Some('\n')
+        );
+        assert_eq!(
+            pasted_char(&key(KeyCode::Tab, KeyModifiers::NONE)),
+            Some('\t')
+        );
+        assert_eq!(
+            pasted_char(&key(KeyCode::Char('v'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(pasted_char(&key(KeyCode::Enter, KeyModifiers::ALT)), None);
+        assert_eq!(pasted_char(&key(KeyCode::Left, KeyModifiers::NONE)), None);
+    }
+}
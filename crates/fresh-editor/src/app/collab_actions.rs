@@ -0,0 +1,599 @@
+//! Collaborative editing sessions.
+//!
+//! Collab Host shares the active buffer: other editors run Collab Join with
+//! the host's join code (the session token and address, as `token@address`)
+//! and get a copy of it. From then on, edits made by any
+//! peer are sent to the others as CRDT operations and merged, and every other
+//! peer's cursor and selection is drawn in the shared buffer in its own color.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::services::collab::crdt::{self, Document, Op, TextEdit};
+use crate::services::collab::{
+    Caret, CollabSession, Message, SessionEvent, HOST_SITE, MAX_SHARED_CHARS,
+};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace, Priority};
+use crate::view::prompt::PromptType;
+
+/// Address suggested when hosting (this machine only; other machines can
+/// join once the host enters an address they can reach)
+const DEFAULT_HOST_ADDRESS: &str = "127.0.0.1:7070";
+
+/// Colors of other peers' cursors, picked by site id
+const PEER_COLORS: [Color; 6] = [
+    Color::Rgb(255, 127, 80),  // Coral
+    Color::Rgb(50, 205, 50),   // Lime Green
+    Color::Rgb(30, 144, 255),  // Dodger Blue
+    Color::Rgb(255, 215, 0),   // Gold
+    Color::Rgb(218, 112, 214), // Orchid
+    Color::Rgb(64, 224, 208),  // Turquoise
+];
+
+/// Other peers' cursors and selections are drawn over this editor's own
+/// highlights (references, brackets, diagnostics), or they'd be hidden
+/// wherever our cursor is
+const REMOTE_CURSOR_PRIORITY: Priority = 20;
+
+fn collab_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("collab-cursors".to_string())
+}
+
+/// The name other peers see for this user
+fn collab_user_name() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "anonymous".to_string())
+}
+
+/// `token@address` of a hosted session
+fn join_code(session: &CollabSession) -> Option<String> {
+    Some(format!("{}@{}", session.token()?, session.local_addr()?))
+}
+
+fn peer_color(site: u32) -> Color {
+    PEER_COLORS[site as usize % PEER_COLORS.len()]
+}
+
+/// `color` mixed into `background`, for selections
+fn tint(color: Color, background: Color) -> Color {
+    match (color, background) {
+        (Color::Rgb(r, g, b), Color::Rgb(br, bg, bb)) => {
+            let mix = |c: u8, base: u8| ((c as u16 + 2 * base as u16) / 3) as u8;
+            Color::Rgb(mix(r, br), mix(g, bg), mix(b, bb))
+        }
+        _ => color,
+    }
+}
+
+/// Another peer's cursor in the shared buffer
+struct RemoteCursor {
+    user: String,
+    position: Caret,
+    anchor: Option<Caret>,
+}
+
+/// A collaboration session and the state of the shared buffer
+pub(super) struct CollabState {
+    session: CollabSession,
+    user: String,
+    /// The shared buffer; `None` while a guest waits for the host's welcome
+    buffer_id: Option<BufferId>,
+    doc: Document,
+    /// Buffer version the document last matched
+    synced_version: u64,
+    /// Remote operations waiting for ones they depend on
+    pending: Vec<Op>,
+    /// Other peers' cursors, by site
+    cursors: HashMap<u32, RemoteCursor>,
+    /// Names of the guests, by site (host only)
+    guests: HashMap<u32, String>,
+    /// Our cursor as last sent to the other peers
+    sent_cursor: Option<(Caret, Option<Caret>)>,
+    /// Whether the remote cursors need drawing again
+    cursors_changed: bool,
+}
+
+impl CollabState {
+    fn new(session: CollabSession, buffer_id: Option<BufferId>, doc: Document) -> Self {
+        Self {
+            session,
+            user: collab_user_name(),
+            buffer_id,
+            doc,
+            synced_version: 0,
+            pending: Vec::new(),
+            cursors: HashMap::new(),
+            guests: HashMap::new(),
+            sent_cursor: None,
+            cursors_changed: false,
+        }
+    }
+}
+
+impl Editor {
+    /// Start the prompt for the address to share the active buffer on
+    pub(super) fn start_collab_host_prompt(&mut self) {
+        if self.collab.is_some() {
+            self.set_status_message(t!("collab.already_active").to_string());
+            return;
+        }
+        self.start_prompt_with_initial_text(
+            t!("collab.host_prompt").to_string(),
+            PromptType::CollabHost,
+            DEFAULT_HOST_ADDRESS.to_string(),
+        );
+    }
+
+    /// Start the prompt for the join code of a session
+    pub(super) fn start_collab_join_prompt(&mut self) {
+        if self.collab.is_some() {
+            self.set_status_message(t!("collab.already_active").to_string());
+            return;
+        }
+        self.start_prompt(t!("collab.join_prompt").to_string(), PromptType::CollabJoin);
+    }
+
+    /// Handle CollabHost prompt confirmation: share the active buffer.
+    pub(super) fn handle_collab_host(&mut self, input: &str) {
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        // Decrypted text never leaves the editor
+        if state.buffer.encryption().is_some() {
+            let name = self.get_buffer_display_name(buffer_id);
+            self.set_status_message(t!("collab.encrypted", name = name).to_string());
+            return;
+        }
+        let Some(text) = state
+            .buffer
+            .to_string()
+            .filter(|text| text.chars().count() <= MAX_SHARED_CHARS)
+        else {
+            self.set_status_message(t!("collab.too_large").to_string());
+            return;
+        };
+        let version = state.buffer.revision();
+        match CollabSession::host(input.trim()) {
+            Ok(session) => {
+                let code = join_code(&session).unwrap_or_default();
+                let doc = Document::from_text(HOST_SITE, &text);
+                let mut collab = CollabState::new(session, Some(buffer_id), doc);
+                collab.synced_version = version;
+                self.collab = Some(collab);
+                let name = self.get_buffer_display_name(buffer_id);
+                self.set_status_message(t!("collab.hosting", name = name, code = code).to_string());
+            }
+            Err(e) => {
+                self.set_status_message(t!("collab.error", error = e.to_string()).to_string());
+            }
+        }
+    }
+
+    /// Handle CollabJoin prompt confirmation: connect to a host with the
+    /// join code it showed.
+    ///
+    /// The shared buffer opens once the host has sent it.
+    pub(super) fn handle_collab_join(&mut self, input: &str) {
+        let Some((token, address)) = input
+            .trim()
+            .split_once('@')
+            .filter(|(token, address)| !token.is_empty() && !address.is_empty())
+        else {
+            self.set_status_message(t!("collab.missing_token").to_string());
+            return;
+        };
+        match CollabSession::join(address) {
+            Ok(session) => {
+                let collab = CollabState::new(session, None, Document::new(HOST_SITE));
+                collab.session.send_to(
+                    HOST_SITE,
+                    &Message::Hello {
+                        user: collab.user.clone(),
+                        token: token.to_string(),
+                    },
+                );
+                self.collab = Some(collab);
+                self.set_status_message(t!("collab.connecting", address = address).to_string());
+            }
+            Err(e) => {
+                self.set_status_message(t!("collab.error", error = e.to_string()).to_string());
+            }
+        }
+    }
+
+    /// Leave the collaboration session; when hosting, this ends it for everyone
+    pub fn collab_leave(&mut self) {
+        match self.collab.take() {
+            Some(collab) => {
+                self.clear_remote_cursors(&collab);
+                self.set_status_message(t!("collab.ended").to_string());
+            }
+            None => self.set_status_message(t!("collab.not_active").to_string()),
+        }
+    }
+
+    /// Address the collaboration session is hosted on, when hosting
+    pub fn collab_address(&self) -> Option<SocketAddr> {
+        self.collab
+            .as_ref()
+            .and_then(|collab| collab.session.local_addr())
+    }
+
+    /// Code guests join the collaboration session with, when hosting
+    pub fn collab_join_code(&self) -> Option<String> {
+        self.collab
+            .as_ref()
+            .and_then(|collab| join_code(&collab.session))
+    }
+
+    /// The shared buffer of the collaboration session, once there is one
+    pub fn collab_buffer(&self) -> Option<BufferId> {
+        self.collab.as_ref().and_then(|collab| collab.buffer_id)
+    }
+
+    /// Exchange edits and cursors with the other peers of the collaboration
+    /// session. Called once per frame.
+    ///
+    /// Returns true if anything on screen changed.
+    pub fn sync_collab(&mut self) -> bool {
+        let Some(mut collab) = self.collab.take() else {
+            return false;
+        };
+        if collab
+            .buffer_id
+            .is_some_and(|id| !self.buffers.contains_key(&id))
+        {
+            // The shared buffer was closed
+            self.set_status_message(t!("collab.ended").to_string());
+            return true;
+        }
+
+        self.send_local_changes(&mut collab);
+        let mut changed = false;
+        for event in collab.session.poll() {
+            match event {
+                SessionEvent::Joined { site } => self.welcome_guest(&mut collab, site),
+                SessionEvent::Message { from, message } => {
+                    changed |= self.handle_collab_message(&mut collab, from, message);
+                }
+                SessionEvent::Left { site } if site == HOST_SITE => {
+                    self.clear_remote_cursors(&collab);
+                    // A host that drops us before its welcome refused the token
+                    let message = if collab.buffer_id.is_some() {
+                        t!("collab.host_left")
+                    } else {
+                        t!("collab.rejected")
+                    };
+                    self.set_status_message(message.to_string());
+                    return true;
+                }
+                SessionEvent::Left { site } => {
+                    collab.session.broadcast(&Message::Leave { site }, None);
+                    collab.cursors.remove(&site);
+                    collab.cursors_changed = true;
+                    if let Some(user) = collab.guests.remove(&site) {
+                        self.set_status_message(t!("collab.peer_left", user = user).to_string());
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        self.send_local_cursor(&mut collab);
+        if collab.cursors_changed {
+            self.draw_remote_cursors(&mut collab);
+            changed = true;
+        }
+        self.collab = Some(collab);
+        changed
+    }
+
+    /// Send a new guest the document and everyone's cursors
+    fn welcome_guest(&mut self, collab: &mut CollabState, site: u32) {
+        let Some(buffer_id) = collab.buffer_id else {
+            return;
+        };
+        let language = self
+            .buffers
+            .get(&buffer_id)
+            .map(|state| state.language.clone())
+            .unwrap_or_default();
+        collab.session.send_to(
+            site,
+            &Message::Welcome {
+                site,
+                name: self.get_buffer_display_name(buffer_id),
+                language,
+                items: collab.doc.items().to_vec(),
+            },
+        );
+        for (&other, cursor) in &collab.cursors {
+            collab.session.send_to(
+                site,
+                &Message::Cursor {
+                    site: other,
+                    user: cursor.user.clone(),
+                    position: cursor.position,
+                    anchor: cursor.anchor,
+                },
+            );
+        }
+        // Resend our own cursor to everyone, the new guest included
+        collab.sent_cursor = None;
+    }
+
+    /// Handle a message from a peer. Returns true if the screen changed.
+    fn handle_collab_message(
+        &mut self,
+        collab: &mut CollabState,
+        from: u32,
+        message: Message,
+    ) -> bool {
+        // The host passes everything between guests on
+        let relay = |collab: &CollabState, message: &Message| {
+            if collab.session.is_host() {
+                collab.session.broadcast(message, Some(from));
+            }
+        };
+        match message {
+            Message::Hello { user, .. } => {
+                if collab.session.is_host() {
+                    self.set_status_message(t!("collab.peer_joined", user = user).to_string());
+                    collab.guests.insert(from, user);
+                    return true;
+                }
+                false
+            }
+            Message::Welcome {
+                site,
+                name,
+                language,
+                items,
+            } => {
+                if collab.buffer_id.is_some() {
+                    return false;
+                }
+                self.open_collab_buffer(collab, site, &name, &language, items);
+                self.apply_pending_ops(collab);
+                true
+            }
+            Message::Op { op } => {
+                relay(collab, &Message::Op { op: op.clone() });
+                collab.pending.push(op);
+                self.apply_pending_ops(collab)
+            }
+            Message::Cursor {
+                site,
+                user,
+                position,
+                anchor,
+            } => {
+                let message = Message::Cursor {
+                    site,
+                    user: user.clone(),
+                    position,
+                    anchor,
+                };
+                relay(collab, &message);
+                collab.cursors.insert(
+                    site,
+                    RemoteCursor {
+                        user,
+                        position,
+                        anchor,
+                    },
+                );
+                collab.cursors_changed = true;
+                false
+            }
+            Message::Leave { site } => {
+                collab.cursors.remove(&site);
+                collab.cursors_changed = true;
+                false
+            }
+        }
+    }
+
+    /// Open the buffer a guest edits, filled with the host's document
+    fn open_collab_buffer(
+        &mut self,
+        collab: &mut CollabState,
+        site: u32,
+        name: &str,
+        language: &str,
+        items: Vec<crdt::Item>,
+    ) {
+        collab.doc = Document::from_items(site, items);
+        let buffer_id = self.new_buffer();
+        if !language.is_empty() {
+            self.handle_set_language(language);
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.display_name = format!("*collab: {}*", name);
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &collab.doc.text());
+            state.buffer.clear_modified();
+            collab.synced_version = state.buffer.revision();
+        }
+        collab.buffer_id = Some(buffer_id);
+        self.set_status_message(t!("collab.joined", name = name).to_string());
+    }
+
+    /// Send the edits made to the shared buffer since the last sync
+    fn send_local_changes(&mut self, collab: &mut CollabState) {
+        let Some(state) = collab.buffer_id.and_then(|id| self.buffers.get(&id)) else {
+            return;
+        };
+        let version = state.buffer.revision();
+        if version == collab.synced_version {
+            return;
+        }
+        collab.synced_version = version;
+        let Some(text) = state.buffer.to_string() else {
+            return;
+        };
+        let Some((range, replacement)) = crdt::diff(&collab.doc.text(), &text) else {
+            return;
+        };
+        let position = range.start;
+        if !range.is_empty() {
+            let op = collab.doc.local_delete(range);
+            collab.session.broadcast(&Message::Op { op }, None);
+        }
+        if !replacement.is_empty() {
+            let op = collab.doc.local_insert(position, &replacement);
+            collab.session.broadcast(&Message::Op { op }, None);
+        }
+        collab.cursors_changed = true;
+    }
+
+    /// Apply every pending remote operation whose dependencies have arrived
+    ///
+    /// Returns true if the shared buffer changed.
+    fn apply_pending_ops(&mut self, collab: &mut CollabState) -> bool {
+        let Some(buffer_id) = collab.buffer_id else {
+            return false;
+        };
+        let mut changed = false;
+        loop {
+            let Some(index) = collab
+                .pending
+                .iter()
+                .position(|op| collab.doc.can_apply(op))
+            else {
+                break;
+            };
+            let op = collab.pending.remove(index);
+            for edit in collab.doc.apply(&op) {
+                self.apply_remote_edit(buffer_id, edit);
+                changed = true;
+            }
+        }
+        if changed {
+            if let Some(state) = self.buffers.get(&buffer_id) {
+                collab.synced_version = state.buffer.revision();
+            }
+            collab.cursors_changed = true;
+        }
+        changed
+    }
+
+    /// Apply another peer's edit to the shared buffer without moving any cursor
+    fn apply_remote_edit(&mut self, buffer_id: BufferId, edit: TextEdit) {
+        let event = match edit {
+            TextEdit::Insert { position, text } => Event::Insert {
+                position,
+                text,
+                cursor_id: CursorId::UNDO_SENTINEL,
+            },
+            TextEdit::Delete { range } => {
+                let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                    return;
+                };
+                Event::Delete {
+                    deleted_text: state.get_text_range(range.start, range.end),
+                    range,
+                    cursor_id: CursorId::UNDO_SENTINEL,
+                }
+            }
+        };
+        if buffer_id == self.active_buffer() {
+            self.active_event_log_mut().append(event.clone());
+            self.apply_event_to_active_buffer(&event);
+        } else if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.apply(&event);
+            if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+                log.append(event);
+            }
+            self.invalidate_layouts_for_buffer(buffer_id);
+        }
+    }
+
+    /// Tell the other peers where our cursor is, if it moved
+    fn send_local_cursor(&mut self, collab: &mut CollabState) {
+        let Some(state) = collab.buffer_id.and_then(|id| self.buffers.get(&id)) else {
+            return;
+        };
+        let primary = state.cursors.primary();
+        let position = Caret {
+            after: collab.doc.anchor_at(primary.position),
+        };
+        let anchor = primary.anchor.map(|anchor| Caret {
+            after: collab.doc.anchor_at(anchor),
+        });
+        if collab.sent_cursor == Some((position, anchor)) {
+            return;
+        }
+        collab.sent_cursor = Some((position, anchor));
+        collab.session.broadcast(
+            &Message::Cursor {
+                site: collab.doc.site(),
+                user: collab.user.clone(),
+                position,
+                anchor,
+            },
+            None,
+        );
+    }
+
+    /// Draw the other peers' cursors and selections in the shared buffer
+    fn draw_remote_cursors(&mut self, collab: &mut CollabState) {
+        collab.cursors_changed = false;
+        self.clear_remote_cursors(collab);
+        let background = self.theme.editor_bg;
+        let Some(state) = collab.buffer_id.and_then(|id| self.buffers.get_mut(&id)) else {
+            return;
+        };
+        let ns = collab_namespace();
+        let len = state.buffer.len();
+        for (&site, cursor) in &collab.cursors {
+            let Some(position) = collab.doc.position_after(cursor.position.after) else {
+                continue;
+            };
+            let color = peer_color(site);
+            let anchor = cursor
+                .anchor
+                .and_then(|anchor| collab.doc.position_after(anchor.after))
+                .filter(|&anchor| anchor != position);
+            if let Some(anchor) = anchor {
+                let face = OverlayFace::Style {
+                    style: Style::default().bg(tint(color, background)),
+                };
+                let range = anchor.min(position)..anchor.max(position);
+                let overlay =
+                    Overlay::with_namespace(&mut state.marker_list, range, face, ns.clone())
+                        .with_priority_value(REMOTE_CURSOR_PRIORITY)
+                        .with_message(cursor.user.clone());
+                state.overlays.add(overlay);
+            }
+            if position < len {
+                let face = OverlayFace::Style {
+                    style: Style::default().bg(color).fg(Color::Black),
+                };
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    position..position + 1,
+                    face,
+                    ns.clone(),
+                )
+                .with_priority_value(REMOTE_CURSOR_PRIORITY)
+                .with_message(cursor.user.clone());
+                state.overlays.add(overlay);
+            }
+        }
+    }
+
+    fn clear_remote_cursors(&mut self, collab: &CollabState) {
+        if let Some(state) = collab.buffer_id.and_then(|id| self.buffers.get_mut(&id)) {
+            state
+                .overlays
+                .clear_namespace(&collab_namespace(), &mut state.marker_list);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Color swatches and the color adjuster.
+//!
+//! This module shows a swatch before each color literal in the visible part
+//! of each buffer and handles the popup that adjusts the color at the cursor,
+//! rewriting the literal in the notation it was written in.
+
+use std::ops::Range;
+
+use ratatui::style::{Color, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{
+    BufferId, Event, PopupContentData, PopupData, PopupListItemData, PopupPositionData,
+};
+use crate::primitives::color_literals::{find_color_literals, ColorAdjustment};
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
+
+/// Text of a color swatch, drawn in the literal's color (the renderer adds
+/// a space after it)
+const SWATCH: &str = "■";
+
+/// Languages where `#123` is more likely an issue number than a color, so
+/// only the six and eight digit hex forms are recognized
+const PROSE_LANGUAGES: &[&str] = &["text", "markdown", "git-commit"];
+
+/// Namespace for color swatch virtual texts
+fn color_swatch_namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string("color-swatches".to_string())
+}
+
+impl Editor {
+    /// Add a swatch before each color literal in lines `start_line..=end_line`
+    /// of a buffer.
+    ///
+    /// Called on every render; skipped when neither the buffer nor the visible
+    /// range changed since the last one.
+    pub(super) fn update_color_swatches(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let enabled = self.config.editor.color_swatches;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let ns = color_swatch_namespace();
+        if !enabled {
+            if state.color_swatches_shown.take().is_some() {
+                state
+                    .virtual_texts
+                    .clear_namespace(&mut state.marker_list, &ns);
+            }
+            return;
+        }
+
+        let visible_start = state.buffer.line_start_offset(start_line).unwrap_or(0);
+        let visible_end = state
+            .buffer
+            .line_start_offset(end_line + 1)
+            .unwrap_or(state.buffer.len());
+        let shown = Some((state.buffer.revision(), visible_start..visible_end));
+        if state.color_swatches_shown == shown {
+            return;
+        }
+        state.color_swatches_shown = shown;
+
+        let bytes = state.buffer.slice_bytes(visible_start..visible_end);
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        let short_hex = !PROSE_LANGUAGES.contains(&state.language.to_lowercase().as_str());
+
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &ns);
+        for literal in find_color_literals(text, short_hex) {
+            let (r, g, b) = literal.color.to_rgb8();
+            state.virtual_texts.add_inline(
+                &mut state.marker_list,
+                visible_start + literal.range.start,
+                SWATCH.to_string(),
+                Style::default().fg(Color::Rgb(r, g, b)),
+                VirtualTextPosition::BeforeChar,
+                ns.clone(),
+                0,
+            );
+        }
+    }
+
+    /// Show the color adjuster for the color literal at the cursor
+    pub fn show_color_adjuster(&mut self) {
+        self.show_color_adjuster_with_selection(0);
+    }
+
+    fn show_color_adjuster_with_selection(&mut self, selected: usize) {
+        let Some(range) = self.color_literal_at_cursor() else {
+            self.set_status_message(t!("color.no_color").to_string());
+            return;
+        };
+        let literal_text = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+
+        let items = ColorAdjustment::ALL
+            .into_iter()
+            .map(|adjustment| PopupListItemData {
+                text: adjustment_label(adjustment),
+                detail: None,
+                icon: None,
+                data: Some(adjustment.id().to_string()),
+            })
+            .collect();
+        let popup = PopupData {
+            title: Some(t!("color.adjuster_title", color = literal_text).to_string()),
+            description: None,
+            transient: false,
+            content: PopupContentData::List { items, selected },
+            position: PopupPositionData::BelowCursor,
+            width: 32,
+            max_height: 12,
+            bordered: true,
+        };
+        self.pending_color_adjustment = Some((self.active_buffer(), range));
+        self.show_popup(popup);
+    }
+
+    /// Handle the choice made in the color adjuster: rewrite the literal and
+    /// show the adjuster again so the change can be repeated
+    pub(super) fn handle_color_adjustment_response(
+        &mut self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        choice: &str,
+    ) {
+        if buffer_id != self.active_buffer() {
+            return;
+        }
+        let Some(adjustment) = ColorAdjustment::from_id(choice) else {
+            return;
+        };
+        let text = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        let Some(literal) = find_color_literals(&text, true)
+            .into_iter()
+            .find(|literal| literal.range == (0..text.len()))
+        else {
+            return;
+        };
+        let replacement = adjustment.apply(&literal);
+        if replacement != text {
+            let cursor_id = self.active_state().cursors.primary_id();
+            let batch = Event::Batch {
+                events: vec![
+                    Event::Delete {
+                        range: range.clone(),
+                        deleted_text: text,
+                        cursor_id,
+                    },
+                    Event::Insert {
+                        position: range.start,
+                        text: replacement,
+                        cursor_id,
+                    },
+                ],
+                description: t!("color.adjust_color").to_string(),
+            };
+            self.active_event_log_mut().append(batch.clone());
+            self.apply_event_to_active_buffer(&batch);
+        }
+
+        let selected = ColorAdjustment::ALL
+            .iter()
+            .position(|a| *a == adjustment)
+            .unwrap_or(0);
+        self.show_color_adjuster_with_selection(selected);
+    }
+
+    /// Byte range of the color literal under (or just after) the primary cursor
+    fn color_literal_at_cursor(&mut self) -> Option<Range<usize>> {
+        let state = self.active_state_mut();
+        let cursor = state.cursors.primary().position;
+        let line = state.buffer.get_line_number(cursor);
+        let line_start = state.buffer.line_start_offset(line)?;
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or(state.buffer.len());
+        let text = state.get_text_range(line_start, line_end);
+        find_color_literals(&text, true)
+            .into_iter()
+            .find(|literal| {
+                (line_start + literal.range.start..=line_start + literal.range.end)
+                    .contains(&cursor)
+            })
+            .map(|literal| line_start + literal.range.start..line_start + literal.range.end)
+    }
+}
+
+fn adjustment_label(adjustment: ColorAdjustment) -> String {
+    match adjustment {
+        ColorAdjustment::Lighten => t!("color.lighten"),
+        ColorAdjustment::Darken => t!("color.darken"),
+        ColorAdjustment::Saturate => t!("color.saturate"),
+        ColorAdjustment::Desaturate => t!("color.desaturate"),
+        ColorAdjustment::RotateHue => t!("color.rotate_hue"),
+        ColorAdjustment::ToHex => t!("color.to_hex"),
+        ColorAdjustment::ToRgb => t!("color.to_rgb"),
+        ColorAdjustment::ToHsl => t!("color.to_hsl"),
+    }
+    .to_string()
+}
@@ -0,0 +1,75 @@
+//! Cursor Undo and Cursor Redo.
+//!
+//! The cursors of each split and buffer are looked at before every action and
+//! mouse event, and kept in a [`CursorHistory`] when they changed. Cursor Undo
+//! goes back to the previous cursors (e.g. the 50 cursors collapsed by an
+//! accidental Esc) without touching the text or its undo history.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::cursor_history::CursorHistory;
+use crate::model::cursor::Cursors;
+
+impl Editor {
+    /// Keep the active cursors in the cursor history if they changed
+    pub(super) fn observe_cursors(&mut self) {
+        let key = (self.split_manager.active_split(), self.active_buffer());
+        let Some(state) = self.buffers.get(&key.1) else {
+            return;
+        };
+        self.cursor_history
+            .entry(key)
+            .or_default()
+            .observe(&state.cursors);
+    }
+
+    /// Go back to the cursors from before the last cursor change
+    pub fn cursor_undo(&mut self) {
+        self.restore_cursors(CursorHistory::undo, t!("cursor_history.nothing_to_undo"));
+    }
+
+    /// Go forward to the cursors from before the last Cursor Undo
+    pub fn cursor_redo(&mut self) {
+        self.restore_cursors(CursorHistory::redo, t!("cursor_history.nothing_to_redo"));
+    }
+
+    fn restore_cursors(
+        &mut self,
+        step: fn(&mut CursorHistory, &Cursors) -> Option<Cursors>,
+        nothing_message: impl ToString,
+    ) {
+        let key = (self.split_manager.active_split(), self.active_buffer());
+        let Some(state) = self.buffers.get_mut(&key.1) else {
+            return;
+        };
+        let history = self.cursor_history.entry(key).or_default();
+        let Some(mut cursors) = step(history, &state.cursors) else {
+            self.set_status_message(nothing_message.to_string());
+            return;
+        };
+
+        // The text may have been edited since: keep the cursors inside it
+        let buffer = &state.buffer;
+        let len = buffer.len();
+        cursors.map(|cursor| {
+            cursor.position = buffer.snap_to_char_boundary(cursor.position.min(len));
+            cursor.anchor = cursor
+                .anchor
+                .map(|anchor| buffer.snap_to_char_boundary(anchor.min(len)));
+        });
+        cursors.normalize();
+        let count = cursors.count();
+        state.cursors = cursors;
+        self.sync_editor_state_to_split_view_state();
+
+        if let Some(view_state) = self.split_view_states.get_mut(&key.0) {
+            let state = self.buffers.get_mut(&key.1).unwrap();
+            let primary = *state.cursors.primary();
+            view_state
+                .viewport
+                .ensure_visible(&mut state.buffer, &primary);
+        }
+        self.set_status_message(t!("cursor_history.restored", count = count).to_string());
+    }
+}
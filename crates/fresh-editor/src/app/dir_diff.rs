@@ -0,0 +1,292 @@
+//! Directory diff view, as opened by `fresh --diff OLD_DIR NEW_DIR` or the
+//! Compare Folders command: the merged tree of two directories, each entry
+//! marked as added, removed or modified. Enter compares a file that's on both
+//! sides in the side-by-side diff view, and `>` and `<` copy an entry from one
+//! side to the other.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::dir_diff::{compare_dirs, DirDiffEntry, DirDiffStatus};
+use crate::model::event::BufferId;
+use crate::model::filesystem::{EntryType, FileSystem};
+use crate::primitives::path_utils::expand_tilde;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+
+/// Mode name for directory diff views
+const DIR_DIFF_MODE: &str = "dir-diff";
+
+fn dir_diff_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("dir-diff".to_string())
+}
+
+/// The directories compared in a directory diff buffer
+pub(super) struct DirDiff {
+    left: PathBuf,
+    right: PathBuf,
+    entries: Vec<DirDiffEntry>,
+}
+
+impl Editor {
+    /// Compare two directories in a new buffer listing their merged tree
+    pub fn open_dir_diff(&mut self, left: &Path, right: &Path) -> anyhow::Result<BufferId> {
+        let [left, right] = [left, right].map(|dir| {
+            self.filesystem
+                .canonicalize(dir)
+                .unwrap_or_else(|_| dir.to_path_buf())
+        });
+        for dir in [&left, &right] {
+            if !self.filesystem.is_dir(dir).unwrap_or(false) {
+                anyhow::bail!(
+                    "{}",
+                    t!("dir_diff.not_a_folder", path = dir.display().to_string())
+                );
+            }
+        }
+        let entries = compare_dirs(self.filesystem.as_ref(), &left, &right)?;
+
+        let name = |dir: &Path| {
+            dir.file_name()
+                .map_or_else(|| dir.display().to_string(), |n| n.to_string_lossy().into())
+        };
+        let buffer_id = self.create_virtual_buffer(
+            t!("dir_diff.title", left = name(&left), right = name(&right)).to_string(),
+            DIR_DIFF_MODE.to_string(),
+            true,
+        );
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.dir_diffs.insert(
+            buffer_id,
+            DirDiff {
+                left,
+                right,
+                entries,
+            },
+        );
+        let first_entry = self.write_dir_diff(buffer_id);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.cursors.primary_mut().position = first_entry;
+        }
+        self.set_active_buffer(buffer_id);
+        Ok(buffer_id)
+    }
+
+    /// The folder typed at a Compare Folders prompt, relative to the working
+    /// directory. None if it isn't a folder.
+    pub(super) fn compare_folders_prompt_path(&mut self, input: &str) -> Option<PathBuf> {
+        let path = expand_tilde(input.trim());
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.working_dir.join(path)
+        };
+        if self.filesystem.is_dir(&path).unwrap_or(false) {
+            Some(path)
+        } else {
+            self.set_status_message(
+                t!("dir_diff.not_a_folder", path = path.display().to_string()).to_string(),
+            );
+            None
+        }
+    }
+
+    /// Compare the directories of the active directory diff buffer again
+    pub(super) fn refresh_dir_diff(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(diff) = self.dir_diffs.get(&buffer_id) else {
+            return;
+        };
+        match compare_dirs(self.filesystem.as_ref(), &diff.left, &diff.right) {
+            Ok(entries) => {
+                if let Some(diff) = self.dir_diffs.get_mut(&buffer_id) {
+                    diff.entries = entries;
+                }
+                self.write_dir_diff(buffer_id);
+            }
+            Err(e) => self.set_status_message(
+                t!("dir_diff.compare_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Open the entry under the cursor: a file on both sides in the
+    /// side-by-side diff view, a file on one side on its own
+    pub(super) fn dir_diff_open_entry(&mut self) {
+        let Some((left, right, entry)) = self.dir_diff_entry_at_cursor() else {
+            return;
+        };
+        if entry.is_dir {
+            self.set_status_message(t!("dir_diff.is_folder").to_string());
+            return;
+        }
+        let left = left.join(&entry.path);
+        let right = right.join(&entry.path);
+        let result = match (entry.on_left(), entry.on_right()) {
+            (true, true) => self.open_file_diff(&left, &right).map(|_| ()),
+            (true, false) => self.open_file(&left).map(|_| ()),
+            _ => self.open_file(&right).map(|_| ()),
+        };
+        if let Err(e) = result {
+            self.set_status_message(t!("dir_diff.open_failed", error = e.to_string()).to_string());
+        }
+    }
+
+    /// Copy the entry under the cursor to the other side, replacing what's
+    /// there
+    pub(super) fn dir_diff_copy_entry(&mut self, to_right: bool) {
+        let Some((left, right, entry)) = self.dir_diff_entry_at_cursor() else {
+            return;
+        };
+        let on_source = if to_right {
+            entry.on_left()
+        } else {
+            entry.on_right()
+        };
+        if !on_source {
+            self.set_status_message(t!("dir_diff.nothing_to_copy").to_string());
+            return;
+        }
+        if entry.status == DirDiffStatus::Same {
+            self.set_status_message(t!("dir_diff.already_same").to_string());
+            return;
+        }
+        let (from, to) = if to_right {
+            (left.join(&entry.path), right.join(&entry.path))
+        } else {
+            (right.join(&entry.path), left.join(&entry.path))
+        };
+        if let Err(e) = copy_path(self.filesystem.as_ref(), &from, &to) {
+            self.set_status_message(t!("dir_diff.copy_failed", error = e.to_string()).to_string());
+            return;
+        }
+        self.refresh_dir_diff();
+        let path = entry.path.display().to_string();
+        self.set_status_message(if to_right {
+            t!("dir_diff.copied_right", path = path).to_string()
+        } else {
+            t!("dir_diff.copied_left", path = path).to_string()
+        });
+    }
+
+    /// The compared directories and the entry under the cursor, if the
+    /// active buffer is a directory diff
+    fn dir_diff_entry_at_cursor(&self) -> Option<(PathBuf, PathBuf, DirDiffEntry)> {
+        let diff = self.dir_diffs.get(&self.active_buffer())?;
+        let index = self
+            .get_text_properties_at_cursor()?
+            .iter()
+            .find_map(|prop| prop.get("index").and_then(|v| v.as_u64()))?;
+        let entry = diff.entries.get(index as usize)?;
+        Some((diff.left.clone(), diff.right.clone(), entry.clone()))
+    }
+
+    /// Write the merged tree to its buffer, highlighting the entries that
+    /// differ. Returns the position of the first entry.
+    fn write_dir_diff(&mut self, buffer_id: BufferId) -> usize {
+        let Some(diff) = self.dir_diffs.get(&buffer_id) else {
+            return 0;
+        };
+        let mut content = vec![TextPropertyEntry::text(format!(
+            "{}\n{}\n{}\n\n",
+            t!("dir_diff.left", path = diff.left.display().to_string()),
+            t!("dir_diff.right", path = diff.right.display().to_string()),
+            t!("dir_diff.hint"),
+        ))];
+        let first_entry = content[0].text.len();
+        let mut offset = first_entry;
+        let mut highlights = Vec::new();
+        for (index, entry) in diff.entries.iter().enumerate() {
+            let line = format_entry(entry);
+            let color = match entry.status {
+                DirDiffStatus::Same => None,
+                DirDiffStatus::Modified => Some(self.theme.diff_modify_bg),
+                DirDiffStatus::Added => Some(self.theme.diff_add_bg),
+                DirDiffStatus::Removed => Some(self.theme.diff_remove_bg),
+            };
+            if let Some(color) = color {
+                highlights.push((offset..offset + line.len(), color));
+            }
+            offset += line.len() + 1;
+            content.push(
+                TextPropertyEntry::text(line + "\n")
+                    .with_property("index", serde_json::json!(index)),
+            );
+        }
+        if diff.entries.is_empty() {
+            content.push(TextPropertyEntry::text(format!(
+                "{}\n",
+                t!("dir_diff.empty")
+            )));
+        }
+
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, content) {
+            tracing::warn!("Failed to update the directory diff: {}", e);
+            return 0;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let ns = dir_diff_namespace();
+            state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            for (range, color) in highlights {
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    range,
+                    OverlayFace::Background { color },
+                    ns.clone(),
+                )
+                .with_extend_to_line_end(true);
+                state.overlays.add(overlay);
+            }
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+        first_entry
+    }
+}
+
+/// An entry's line: its status marker, then its name indented by its depth
+fn format_entry(entry: &DirDiffEntry) -> String {
+    let marker = match entry.status {
+        DirDiffStatus::Same => ' ',
+        DirDiffStatus::Modified => 'M',
+        DirDiffStatus::Added => '+',
+        DirDiffStatus::Removed => '-',
+    };
+    let name = entry
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    format!(
+        "{} {}{}{}",
+        marker,
+        "  ".repeat(entry.depth),
+        name,
+        if entry.is_dir { "/" } else { "" }
+    )
+}
+
+/// Copy a file, or a directory with everything in it, creating the parent
+/// directories of the destination. Symbolic links to directories aren't
+/// followed, as they aren't compared.
+fn copy_path(fs: &dyn FileSystem, from: &Path, to: &Path) -> io::Result<()> {
+    if !fs.is_dir(from)? {
+        if let Some(parent) = to.parent() {
+            fs.create_dir_all(parent)?;
+        }
+        return fs.copy(from, to).map(|_| ());
+    }
+    fs.create_dir_all(to)?;
+    for entry in fs.read_dir(from)? {
+        if entry.entry_type == EntryType::Symlink && entry.symlink_target_is_dir {
+            continue;
+        }
+        copy_path(fs, &entry.path, &to.join(&entry.name))?;
+    }
+    Ok(())
+}
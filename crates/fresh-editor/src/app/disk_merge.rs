@@ -0,0 +1,199 @@
+//! Merging changes made on disk into buffers with unsaved changes
+//!
+//! When a file changes on disk while its buffer has unsaved changes, the
+//! changes made on disk are merged into the buffer (see `model::merge`)
+//! rather than only warned about. Lines changed both in the buffer and on disk
+//! are kept in both versions between conflict markers, highlighted until the
+//! buffer is saved. The merge is a single undo step.
+//!
+//! Reloading a buffer without unsaved changes compares the old and new text
+//! too, so cursors and selections stay with the text around them.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::buffer::Buffer;
+use crate::model::cursor::Cursors;
+use crate::model::event::{BufferId, Event};
+use crate::model::merge::{self, Replacement};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+
+fn conflict_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("merge-conflicts".to_string())
+}
+
+impl Editor {
+    /// Merge the file's new content on disk into its buffer, which has
+    /// unsaved changes. Returns false if it couldn't be merged, e.g. because
+    /// the file is too large to compare.
+    pub(super) fn merge_disk_changes(&mut self, buffer_id: BufferId, path: &Path) -> bool {
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        if state.buffer.encryption().is_some() {
+            return false;
+        }
+        let Some(ours) = state
+            .buffer
+            .get_all_text()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+        else {
+            return false;
+        };
+        // The disk content the buffer last saw
+        let Some(base) = self
+            .disk_merge_bases
+            .get(&buffer_id)
+            .cloned()
+            .or_else(|| state.buffer.saved_text())
+        else {
+            return false;
+        };
+        let Some(theirs) = Buffer::load_from_file(
+            path,
+            self.config.editor.large_file_threshold_bytes as usize,
+            Arc::clone(&self.filesystem),
+        )
+        .ok()
+        .and_then(|buffer| buffer.to_string()) else {
+            return false;
+        };
+        let Some(replacements) = merge::merge3(&base, &ours, &theirs) else {
+            return false;
+        };
+        self.disk_merge_bases.insert(buffer_id, theirs);
+        if replacements.is_empty() {
+            return true;
+        }
+
+        let cursor_id = state.cursors.primary_id();
+        let mut events = Vec::new();
+        for replacement in replacements.iter().rev() {
+            let range = replacement.range.clone();
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    deleted_text: ours[range.clone()].to_string(),
+                    range: range.clone(),
+                    cursor_id,
+                });
+            }
+            if !replacement.text.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: replacement.text.clone(),
+                    cursor_id,
+                });
+            }
+        }
+        let old_cursors = state.cursors.clone();
+        if let Err(e) = self.apply_events_to_buffer_as_bulk_edit(
+            buffer_id,
+            events,
+            "Merge changes from disk".to_string(),
+        ) {
+            tracing::warn!("Failed to merge changes to {:?}: {}", path, e);
+            return false;
+        }
+
+        let conflicts = self.mark_conflicts(buffer_id, &replacements);
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.cursors = moved_cursors(old_cursors, &replacements, &state.buffer);
+        }
+        if buffer_id == self.active_buffer() {
+            self.sync_editor_state_to_split_view_state();
+        }
+
+        let name = self.get_buffer_display_name(buffer_id);
+        let message = if conflicts == 0 {
+            t!("merge.merged", name = name)
+        } else {
+            t!(
+                "merge.merged_with_conflicts",
+                name = name,
+                count = conflicts
+            )
+        };
+        self.set_status_message(message.to_string());
+        true
+    }
+
+    /// Highlight the conflicts of merged replacements. Returns how many
+    /// there are.
+    fn mark_conflicts(&mut self, buffer_id: BufferId, replacements: &[Replacement]) -> usize {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return 0;
+        };
+        let ns = conflict_namespace();
+        let faces = [self.theme.diff_modify_bg, self.theme.diff_add_bg];
+        let mut conflicts = 0;
+        let mut shift: isize = 0;
+        for replacement in replacements {
+            let start = (replacement.range.start as isize + shift) as usize;
+            shift += replacement.text.len() as isize - replacement.range.len() as isize;
+            let Some((ours, theirs)) = &replacement.conflict else {
+                continue;
+            };
+            conflicts += 1;
+            for (section, color) in [ours, theirs].into_iter().zip(faces) {
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    start + section.start..start + section.end,
+                    OverlayFace::Background { color },
+                    ns.clone(),
+                )
+                .with_message(t!("merge.conflict").to_string())
+                .with_extend_to_line_end(true);
+                state.overlays.add(overlay);
+            }
+        }
+        conflicts
+    }
+
+    /// Forget what was merged into a buffer, once it's saved or reloaded
+    pub(super) fn forget_disk_merge(&mut self, buffer_id: BufferId) {
+        if self.disk_merge_bases.remove(&buffer_id).is_none() {
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .overlays
+                .clear_namespace(&conflict_namespace(), &mut state.marker_list);
+        }
+    }
+}
+
+/// The cursors of a buffer reloaded from disk. They move with the text
+/// around them if the old and new text can be compared; otherwise they're
+/// kept inside the new text, without selections.
+pub(super) fn reloaded_cursors(old: &Buffer, mut cursors: Cursors, new: &Buffer) -> Cursors {
+    let replacements = old
+        .to_string()
+        .zip(new.to_string())
+        .and_then(|(old, new)| merge::diff(&old, &new));
+    match replacements {
+        Some(replacements) => moved_cursors(cursors, &replacements, new),
+        None => {
+            let len = new.len();
+            cursors.map(|cursor| {
+                cursor.position = cursor.position.min(len);
+                cursor.clear_selection();
+            });
+            cursors
+        }
+    }
+}
+
+/// `cursors` moved through the replacements made to get `buffer`
+fn moved_cursors(mut cursors: Cursors, replacements: &[Replacement], buffer: &Buffer) -> Cursors {
+    let len = buffer.len();
+    cursors.map(|cursor| {
+        cursor.position = merge::map_offset(replacements, cursor.position).min(len);
+        cursor.anchor = cursor
+            .anchor
+            .map(|anchor| merge::map_offset(replacements, anchor).min(len));
+    });
+    cursors
+}
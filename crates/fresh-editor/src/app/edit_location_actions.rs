@@ -0,0 +1,86 @@
+//! Go to last change.
+//!
+//! Every edit to a file buffer records where it happened in the editor-wide
+//! [`EditLocations`](crate::input::edit_locations::EditLocations) ring. Go to
+//! Last Change walks back through those locations across buffers, and Go to
+//! Next Change walks forward again.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::edit_locations::EditLocation;
+use crate::model::event::{BufferId, Event};
+
+impl Editor {
+    /// Record the location of an edit just applied to the active buffer
+    pub(super) fn record_edit_location(&mut self, event: &Event) {
+        let buffer_id = self.active_buffer();
+        if self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.is_virtual())
+        {
+            return;
+        }
+        self.adjust_edit_locations(buffer_id, event);
+
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let position = state.cursors.primary().position;
+        let line = state.buffer.get_line_number(position);
+        // Further edits on the same line update the newest location
+        let merge = self.edit_locations.newest().is_some_and(|newest| {
+            newest.buffer_id == buffer_id
+                && state
+                    .buffer
+                    .get_line_number(newest.position.min(state.buffer.len()))
+                    == line
+        });
+        self.edit_locations
+            .record(EditLocation::new(buffer_id, position), merge);
+    }
+
+    /// Shift recorded locations in `buffer_id` for the inserts and deletes of an event
+    fn adjust_edit_locations(&mut self, buffer_id: BufferId, event: &Event) {
+        match event {
+            Event::Insert { position, text, .. } => {
+                self.edit_locations
+                    .adjust_for_insert(buffer_id, *position, text.len());
+            }
+            Event::Delete { range, .. } => {
+                self.edit_locations
+                    .adjust_for_delete(buffer_id, range.clone());
+            }
+            Event::Batch { events, .. } => {
+                for event in events {
+                    self.adjust_edit_locations(buffer_id, event);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Go back to the previous edit location
+    pub fn goto_last_change(&mut self) {
+        let current = self.current_edit_location();
+        match self.edit_locations.older(current) {
+            Some(location) => self.go_to_location(location.buffer_id, location.position),
+            None => self.set_status_message(t!("change.at_oldest").to_string()),
+        }
+    }
+
+    /// Go forward to the next edit location after going back
+    pub fn goto_next_change(&mut self) {
+        let current = self.current_edit_location();
+        match self.edit_locations.newer(current) {
+            Some(location) => self.go_to_location(location.buffer_id, location.position),
+            None => self.set_status_message(t!("change.at_newest").to_string()),
+        }
+    }
+
+    fn current_edit_location(&self) -> EditLocation {
+        let position = self.active_state().cursors.primary().position;
+        EditLocation::new(self.active_buffer(), position)
+    }
+}
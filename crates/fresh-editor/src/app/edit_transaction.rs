@@ -0,0 +1,286 @@
+//! Edits of several buffers, applied all or none
+//!
+//! An edit transaction holds text edits for any number of files, which are
+//! opened if they aren't already. LSP workspace edits (renames,
+//! refactorings) and plugins (project search and replace) apply their edits
+//! with one.
+//!
+//! Every edit is checked before any is applied: if a buffer is read-only, two
+//! edits overlap, or the text an edit expects to replace isn't there, nothing
+//! is changed and files opened for the transaction are closed again. Each
+//! buffer's edits are a single undo step. Should applying the edits of a
+//! buffer still fail, the buffers already edited are rolled back.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use fresh_core::api::{JsCallbackId, JsFileEdits, JsTextEdit};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+
+/// A single edit of a transaction, positioned like an LSP text edit (lines
+/// and UTF-16 code units)
+#[derive(Debug, Clone)]
+pub(crate) struct TransactionEdit {
+    pub range: lsp_types::Range,
+    pub new_text: String,
+    /// The text the range must hold for the transaction to be applied
+    pub expected: Option<String>,
+}
+
+impl From<lsp_types::TextEdit> for TransactionEdit {
+    fn from(edit: lsp_types::TextEdit) -> Self {
+        Self {
+            range: edit.range,
+            new_text: edit.new_text,
+            expected: None,
+        }
+    }
+}
+
+impl From<JsTextEdit> for TransactionEdit {
+    fn from(edit: JsTextEdit) -> Self {
+        let position = |position: fresh_core::api::JsPosition| lsp_types::Position {
+            line: position.line,
+            character: position.character,
+        };
+        Self {
+            range: lsp_types::Range {
+                start: position(edit.range.start),
+                end: position(edit.range.end),
+            },
+            new_text: edit.new_text,
+            expected: edit.old_text,
+        }
+    }
+}
+
+/// Edits of several buffers, applied together by `Editor::apply_edit_transaction`
+#[derive(Debug, Clone)]
+pub(crate) struct EditTransaction {
+    /// Description of the undo step of each buffer
+    description: String,
+    edits: Vec<(PathBuf, Vec<TransactionEdit>)>,
+}
+
+impl EditTransaction {
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            edits: Vec::new(),
+        }
+    }
+
+    /// Add edits of a file. Edits of the same file are positioned relative to
+    /// its contents before the transaction, like the edits of an LSP
+    /// workspace edit.
+    pub fn edit(
+        &mut self,
+        path: PathBuf,
+        edits: impl IntoIterator<Item = impl Into<TransactionEdit>>,
+    ) {
+        self.edits
+            .push((path, edits.into_iter().map(Into::into).collect()));
+    }
+
+    /// The number of edits
+    pub fn len(&self) -> usize {
+        self.edits.iter().map(|(_, edits)| edits.len()).sum()
+    }
+}
+
+impl Editor {
+    /// Apply the edits of a transaction, all or none. Returns the number of
+    /// edits applied.
+    pub(crate) fn apply_edit_transaction(
+        &mut self,
+        transaction: EditTransaction,
+    ) -> AnyhowResult<usize> {
+        let count = transaction.len();
+        let existing: HashSet<BufferId> = self.buffers.keys().copied().collect();
+        let mut opened = Vec::new();
+
+        let prepared = self.prepare_transaction(transaction.edits, &existing, &mut opened);
+        let buffer_events = match prepared {
+            Ok(buffer_events) => buffer_events,
+            Err(e) => {
+                self.close_transaction_buffers(&opened);
+                return Err(e);
+            }
+        };
+
+        let mut applied = Vec::new();
+        for (buffer_id, events) in buffer_events {
+            if let Err(e) = self.apply_events_to_buffer_as_bulk_edit(
+                buffer_id,
+                events,
+                transaction.description.clone(),
+            ) {
+                self.roll_back_transaction(&applied);
+                self.close_transaction_buffers(&opened);
+                return Err(e);
+            }
+            applied.push(buffer_id);
+        }
+        Ok(count)
+    }
+
+    /// Apply the edits of a plugin's `applyEdits` call, resolving it with the
+    /// number of edits applied
+    pub(super) fn handle_apply_edits(
+        &mut self,
+        files: Vec<JsFileEdits>,
+        description: String,
+        request_id: u64,
+    ) {
+        let mut transaction = EditTransaction::new(description);
+        for file in files {
+            transaction.edit(file.path, file.edits);
+        }
+        let callback_id = JsCallbackId::from(request_id);
+        match self.apply_edit_transaction(transaction) {
+            Ok(count) => {
+                // The plugin can look up the buffers the transaction opened
+                #[cfg(feature = "plugins")]
+                self.update_plugin_state_snapshot();
+                self.plugin_manager
+                    .resolve_callback(callback_id, count.to_string());
+            }
+            Err(e) => self
+                .plugin_manager
+                .reject_callback(callback_id, e.to_string()),
+        }
+    }
+
+    /// Open the files of a transaction and check its edits, returning the
+    /// events to apply to each buffer
+    fn prepare_transaction(
+        &mut self,
+        edits: Vec<(PathBuf, Vec<TransactionEdit>)>,
+        existing: &HashSet<BufferId>,
+        opened: &mut Vec<BufferId>,
+    ) -> AnyhowResult<Vec<(BufferId, Vec<Event>)>> {
+        // Edits of the same buffer are checked and applied together
+        let mut buffer_edits: Vec<(BufferId, Vec<TransactionEdit>)> = Vec::new();
+        for (path, edits) in edits {
+            let buffer_id = self.open_file_no_focus(&path)?;
+            if !existing.contains(&buffer_id) && !opened.contains(&buffer_id) {
+                opened.push(buffer_id);
+            }
+            match buffer_edits.iter_mut().find(|(id, _)| *id == buffer_id) {
+                Some((_, all)) => all.extend(edits),
+                None => buffer_edits.push((buffer_id, edits)),
+            }
+        }
+
+        buffer_edits
+            .into_iter()
+            .filter(|(_, edits)| !edits.is_empty())
+            .map(|(buffer_id, edits)| {
+                let events = self.transaction_events(buffer_id, edits)?;
+                Ok((buffer_id, events))
+            })
+            .collect()
+    }
+
+    /// The events applying `edits` to a buffer, or an error if they can't
+    /// all be applied
+    fn transaction_events(
+        &mut self,
+        buffer_id: BufferId,
+        edits: Vec<TransactionEdit>,
+    ) -> AnyhowResult<Vec<Event>> {
+        let name = self.get_buffer_display_name(buffer_id);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            anyhow::bail!(t!("edit_transaction.not_found", name = name).to_string());
+        };
+        if state.editing_disabled {
+            anyhow::bail!(t!("edit_transaction.read_only", name = name).to_string());
+        }
+
+        let mut ranges = Vec::with_capacity(edits.len());
+        for (index, edit) in edits.iter().enumerate() {
+            let start = state.buffer.lsp_position_to_byte(
+                edit.range.start.line as usize,
+                edit.range.start.character as usize,
+            );
+            let end = state.buffer.lsp_position_to_byte(
+                edit.range.end.line as usize,
+                edit.range.end.character as usize,
+            );
+            if start > end {
+                anyhow::bail!(t!("edit_transaction.invalid_range", name = name).to_string());
+            }
+            if let Some(expected) = &edit.expected {
+                if state.get_text_range(start, end) != *expected {
+                    anyhow::bail!(t!("edit_transaction.changed", name = name).to_string());
+                }
+            }
+            ranges.push((start, end, index));
+        }
+
+        // Inserts at the same position apply in the order they were given
+        ranges.sort();
+        if ranges.windows(2).any(|pair| pair[0].1 > pair[1].0) {
+            anyhow::bail!(t!("edit_transaction.overlapping", name = name).to_string());
+        }
+
+        // Applied from the end of the buffer, so the positions of the edits
+        // before stay valid
+        let cursor_id = state.cursors.primary_id();
+        let mut events = Vec::new();
+        for &(start, end, index) in ranges.iter().rev() {
+            if start < end {
+                events.push(Event::Delete {
+                    range: start..end,
+                    deleted_text: state.get_text_range(start, end),
+                    cursor_id,
+                });
+            }
+            let new_text = &edits[index].new_text;
+            if !new_text.is_empty() {
+                events.push(Event::Insert {
+                    position: start,
+                    text: new_text.clone(),
+                    cursor_id,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    /// Undo the edits a transaction made to buffers before it failed
+    fn roll_back_transaction(&mut self, applied: &[BufferId]) {
+        for &buffer_id in applied.iter().rev() {
+            let Some(log) = self.event_logs.get_mut(&buffer_id) else {
+                continue;
+            };
+            let events = log.undo();
+            let at_saved = log.is_at_saved_position();
+            let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                continue;
+            };
+            for event in &events {
+                state.apply(event);
+            }
+            state.buffer.set_modified(!at_saved);
+            let path = state.buffer.file_path().map(|path| path.to_path_buf());
+            self.invalidate_layouts_for_buffer(buffer_id);
+            if let Some(path) = path {
+                self.notify_lsp_file_changed(&path);
+            }
+        }
+    }
+
+    /// Close the buffers of files opened for a transaction that failed
+    fn close_transaction_buffers(&mut self, opened: &[BufferId]) {
+        for &buffer_id in opened {
+            if let Err(e) = self.close_buffer(buffer_id) {
+                tracing::warn!("Failed to close buffer {:?}: {}", buffer_id, e);
+            }
+        }
+    }
+}
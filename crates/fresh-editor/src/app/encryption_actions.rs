@@ -0,0 +1,160 @@
+//! Encrypted files (`.age`, `.gpg`)
+//!
+//! Encrypted files open locked: the buffer is empty and read-only until the
+//! user gives the passphrase or identity, then the file is decrypted into
+//! memory. Saving re-encrypts it (see `TextBuffer::save_to_file`), and
+//! reverting decrypts it again with the same key.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::buffer::Buffer;
+use crate::model::event::{BufferId, EventLog};
+use crate::services::encryption::{EncryptionScheme, FileEncryption};
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Mark a newly opened encrypted file as locked and ask for its key
+    pub(super) fn lock_encrypted_buffer(&mut self, buffer_id: BufferId, scheme: EncryptionScheme) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state
+                .buffer
+                .set_encryption(Some(FileEncryption::locked(scheme)));
+            state.editing_disabled = true;
+        }
+        self.start_decrypt_prompt(buffer_id);
+    }
+
+    /// Ask for the passphrase or identity that unlocks an encrypted buffer
+    pub(super) fn start_decrypt_prompt(&mut self, buffer_id: BufferId) {
+        let Some(scheme) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.encryption())
+            .map(|encryption| encryption.scheme())
+        else {
+            return;
+        };
+        let name = self.get_buffer_display_name(buffer_id);
+        if scheme.key_is_passphrase() {
+            self.start_prompt(
+                t!("encryption.passphrase_prompt", name = name).to_string(),
+                PromptType::DecryptPassphrase { buffer_id },
+            );
+        } else {
+            self.start_prompt(
+                t!("encryption.identity_prompt", name = name).to_string(),
+                PromptType::DecryptIdentity { buffer_id },
+            );
+        }
+    }
+
+    /// Handle DecryptPassphrase and DecryptIdentity prompt confirmation.
+    pub(super) fn handle_decrypt(&mut self, buffer_id: BufferId, key: &str) {
+        let Some(scheme) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.encryption())
+            .map(|encryption| encryption.scheme())
+        else {
+            return;
+        };
+        let name = self.get_buffer_display_name(buffer_id);
+        match self.decrypt_buffer(buffer_id, FileEncryption::with_key(scheme, key)) {
+            Ok(()) => {
+                self.set_status_message(t!("encryption.decrypted", name = name).to_string());
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!(
+                        "encryption.decrypt_failed",
+                        name = name,
+                        error = e.to_string()
+                    )
+                    .to_string(),
+                );
+            }
+        }
+    }
+
+    /// Revert an encrypted buffer: decrypt the file again, or ask for the key
+    /// if it is still locked
+    pub(super) fn revert_encrypted_file(&mut self, buffer_id: BufferId) -> anyhow::Result<bool> {
+        let Some(encryption) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.encryption())
+            .cloned()
+        else {
+            return Ok(false);
+        };
+        if encryption.is_locked() {
+            self.start_decrypt_prompt(buffer_id);
+            return Ok(false);
+        }
+        self.decrypt_buffer(buffer_id, encryption)?;
+        self.status_message = Some(t!("status.reverted").to_string());
+        Ok(true)
+    }
+
+    /// Replace the buffer's contents with its file decrypted with `encryption`
+    fn decrypt_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        encryption: FileEncryption,
+    ) -> anyhow::Result<()> {
+        let path = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("buffer has no file"))?;
+
+        // A new file has nothing to decrypt, but needs the key to be saved
+        let plaintext = if self.filesystem.exists(&path) {
+            encryption.decrypt(&self.filesystem.read_file(&path)?)?
+        } else {
+            Vec::new()
+        };
+        let mut buffer = Buffer::from_bytes(plaintext, Arc::clone(&self.filesystem));
+        buffer.set_file_path(path.clone());
+        buffer.set_encryption(Some(encryption));
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return Ok(());
+        };
+        let new_len = buffer.len();
+        state.marker_list.adjust_for_delete(0, state.buffer.len());
+        state.marker_list.adjust_for_insert(0, new_len);
+        buffer.continue_revisions_from(&state.buffer);
+        state.buffer = buffer;
+        state.cursors.map(|cursor| {
+            cursor.position = 0;
+            cursor.clear_selection();
+        });
+        state.editing_disabled = false;
+        for split_id in self.split_manager.splits_for_buffer(buffer_id) {
+            if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                view_state.cursors.map(|cursor| {
+                    cursor.position = 0;
+                    cursor.clear_selection();
+                });
+                view_state.viewport.top_byte = 0;
+            }
+        }
+
+        // The locked or previous contents can't be undone back to
+        self.event_logs.insert(buffer_id, EventLog::new());
+        self.seen_byte_ranges.remove(&buffer_id);
+        self.invalidate_layouts_for_buffer(buffer_id);
+        if let Ok(metadata) = self.filesystem.metadata(&path) {
+            if let Some(mtime) = metadata.modified {
+                self.file_mod_times.insert(path, mtime);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+//! Extract Variable and Extract Function.
+//!
+//! The name is asked for first. With a language server, its extract
+//! refactoring is requested for the selection and applied with the name the
+//! server made up replaced by the chosen one, wherever it occurs. Without a
+//! server, or when it has no such refactoring, the syntax tree is used (see
+//! [`crate::primitives::extract`]). Either way the extraction is one undo
+//! step.
+
+use std::ops::Range;
+
+use lsp_types::{CodeActionOrCommand, WorkspaceEdit};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::config::LARGE_FILE_THRESHOLD_BYTES;
+use crate::model::event::{BufferId, Event};
+use crate::primitives::extract::{self, ExtractKind};
+use crate::primitives::text_objects;
+use crate::view::prompt::PromptType;
+
+/// An extraction waiting for the LSP's code actions
+pub(super) struct PendingExtraction {
+    request_id: u64,
+    buffer_id: BufferId,
+    kind: ExtractKind,
+    name: String,
+    range: Range<usize>,
+    /// The selected text, to tell whether the buffer changed meanwhile
+    text: String,
+}
+
+impl Editor {
+    /// Start the prompt for the name to extract the selection into
+    pub(super) fn start_extract_prompt(&mut self, kind: ExtractKind) {
+        if self.is_editing_disabled() {
+            self.set_status_message(self.editing_disabled_message());
+            return;
+        }
+        if self.extract_selection().is_none() {
+            self.set_status_message(t!("extract.no_selection").to_string());
+            return;
+        }
+        let message = match kind {
+            ExtractKind::Variable => t!("extract.variable_prompt"),
+            ExtractKind::Function => t!("extract.function_prompt"),
+        };
+        self.start_prompt(message.to_string(), PromptType::ExtractName { kind });
+    }
+
+    /// Handle ExtractName prompt confirmation.
+    pub(super) fn handle_extract_name(&mut self, kind: ExtractKind, input: &str) {
+        let name = input.trim();
+        if !extract::is_identifier(name) {
+            self.set_status_message(t!("extract.invalid_name", name = name).to_string());
+            return;
+        }
+        let Some(range) = self.extract_selection() else {
+            self.set_status_message(t!("extract.no_selection").to_string());
+            return;
+        };
+
+        let buffer_id = self.active_buffer();
+        let request_id = self.next_lsp_request_id;
+        let state = self.active_state();
+        let (start_line, start_char) = state.buffer.position_to_lsp_position(range.start);
+        let (end_line, end_char) = state.buffer.position_to_lsp_position(range.end);
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, |handle, uri, _language| {
+                handle
+                    .code_actions(
+                        request_id,
+                        uri.clone(),
+                        start_line as u32,
+                        start_char as u32,
+                        end_line as u32,
+                        end_char as u32,
+                        Vec::new(),
+                    )
+                    .is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            self.next_lsp_request_id += 1;
+            let text = self
+                .active_state_mut()
+                .get_text_range(range.start, range.end);
+            self.pending_extraction = Some(PendingExtraction {
+                request_id,
+                buffer_id,
+                kind,
+                name: name.to_string(),
+                range,
+                text,
+            });
+            self.lsp_status = "LSP: extract...".to_string();
+        } else {
+            self.extract_with_syntax(kind, name, range);
+        }
+    }
+
+    /// Apply the LSP's extract refactoring from a code actions response, or
+    /// fall back to the syntax tree if it has none
+    ///
+    /// Returns false if the response isn't for a pending extraction.
+    pub(super) fn handle_extract_code_actions(
+        &mut self,
+        request_id: u64,
+        actions: &[CodeActionOrCommand],
+    ) -> bool {
+        if self
+            .pending_extraction
+            .as_ref()
+            .is_none_or(|pending| pending.request_id != request_id)
+        {
+            return false;
+        }
+        let Some(pending) = self.pending_extraction.take() else {
+            return false;
+        };
+        self.lsp_status.clear();
+
+        // The edits are for the text the request was made with
+        let unchanged = self.active_buffer() == pending.buffer_id && {
+            let state = self.active_state_mut();
+            pending.range.end <= state.buffer.len()
+                && state.get_text_range(pending.range.start, pending.range.end) == pending.text
+        };
+        if !unchanged {
+            self.set_status_message(t!("extract.changed").to_string());
+            return true;
+        }
+
+        let Some(mut edit) = extraction_edit(actions, pending.kind) else {
+            self.extract_with_syntax(pending.kind, &pending.name, pending.range);
+            return true;
+        };
+        let document = {
+            let state = self.active_state_mut();
+            let len = state.buffer.len();
+            state.get_text_range(0, len)
+        };
+        let mut texts = edit_texts(&mut edit);
+        let inserted = texts
+            .iter()
+            .map(|text| text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if let Some(placeholder) = extract::new_identifier(&document, &inserted) {
+            let placeholder = placeholder.to_string();
+            for text in texts.iter_mut() {
+                **text = extract::rename_identifier(text, &placeholder, &pending.name);
+            }
+        }
+
+        match self.apply_workspace_edit(edit) {
+            Ok(_) => self.set_status_message(t!("extract.done", name = &pending.name).to_string()),
+            Err(e) => {
+                self.set_status_message(t!("extract.lsp_failed", error = e.to_string()).to_string())
+            }
+        }
+        true
+    }
+
+    /// The primary cursor's selection, if it selects anything
+    fn extract_selection(&self) -> Option<Range<usize>> {
+        self.active_state()
+            .cursors
+            .primary()
+            .selection_range()
+            .filter(|range| !range.is_empty())
+    }
+
+    /// Extract the selection using the syntax tree of the active buffer
+    fn extract_with_syntax(&mut self, kind: ExtractKind, name: &str, range: Range<usize>) {
+        let state = self.active_state_mut();
+        let language = state
+            .highlighter
+            .language()
+            .copied()
+            .filter(|language| extract::supports(language, kind));
+        let Some(language) = language else {
+            self.set_status_message(t!("extract.unsupported").to_string());
+            return;
+        };
+        let len = state.buffer.len();
+        let edits = if len as u64 <= LARGE_FILE_THRESHOLD_BYTES {
+            let text = state.get_text_range(0, len);
+            language
+                .ts_language()
+                .and_then(|ts_language| text_objects::parse(&ts_language, &text))
+                .and_then(|tree| extract::extract(&language, &text, &tree, range, kind, name))
+                .map(|edits| (text, edits))
+        } else {
+            None
+        };
+        let Some((text, edits)) = edits else {
+            self.set_status_message(t!("extract.failed").to_string());
+            return;
+        };
+
+        // Edit from the end of the buffer, except that the selection's
+        // replacement goes last: the cursor ends up after it
+        let cursor_id = state.cursors.primary_id();
+        let replacement = edits.iter().rposition(|(range, _)| !range.is_empty());
+        let mut ordered: Vec<&extract::Edit> = edits
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(i, _)| Some(*i) != replacement)
+            .map(|(_, edit)| edit)
+            .collect();
+        ordered.extend(replacement.map(|i| &edits[i]));
+        let mut events = Vec::new();
+        for (range, new_text) in ordered {
+            if !range.is_empty() {
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text: text[range.clone()].to_string(),
+                    cursor_id,
+                });
+            }
+            if !new_text.is_empty() {
+                events.push(Event::Insert {
+                    position: range.start,
+                    text: new_text.clone(),
+                    cursor_id,
+                });
+            }
+        }
+
+        let description = match kind {
+            ExtractKind::Variable => "Extract variable",
+            ExtractKind::Function => "Extract function",
+        };
+        if let Some(bulk_edit) = self.apply_events_as_bulk_edit(events, description.to_string()) {
+            self.active_event_log_mut().append(bulk_edit);
+        }
+        self.set_status_message(t!("extract.done", name = name).to_string());
+    }
+}
+
+/// The edit of the LSP code action that extracts into `kind`
+///
+/// Code actions without an edit need resolving or run a server command,
+/// neither of which is supported, so they are skipped.
+fn extraction_edit(actions: &[CodeActionOrCommand], kind: ExtractKind) -> Option<WorkspaceEdit> {
+    // In order of preference: rust-analyzer offers both "Extract into
+    // variable" and "Extract into constant"
+    let targets: &[&str] = match kind {
+        ExtractKind::Variable => &["variable", "constant"],
+        ExtractKind::Function => &["function", "method"],
+    };
+    let candidates: Vec<_> = actions
+        .iter()
+        .filter_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action)
+                if action.disabled.is_none() && action.edit.is_some() =>
+            {
+                Some(action)
+            }
+            _ => None,
+        })
+        .map(|action| {
+            let kind = action
+                .kind
+                .as_ref()
+                .map_or(String::new(), |kind| kind.as_str().to_string());
+            let title = action.title.to_lowercase();
+            (kind, title, action)
+        })
+        .filter(|(kind, title, _)| {
+            kind.starts_with("refactor.extract") || title.starts_with("extract")
+        })
+        .collect();
+    let index = targets.iter().find_map(|target| {
+        candidates
+            .iter()
+            .position(|(kind, title, _)| kind.contains(target) || title.contains(target))
+    })?;
+    candidates[index].2.edit.clone()
+}
+
+/// The new text of every text edit in `edit`
+fn edit_texts(edit: &mut WorkspaceEdit) -> Vec<&mut String> {
+    use lsp_types::{DocumentChangeOperation, DocumentChanges, OneOf};
+
+    let mut texts: Vec<&mut String> = edit
+        .changes
+        .iter_mut()
+        .flat_map(|changes| changes.values_mut())
+        .flatten()
+        .map(|text_edit| &mut text_edit.new_text)
+        .collect();
+    let documents: Vec<_> = match &mut edit.document_changes {
+        Some(DocumentChanges::Edits(edits)) => edits.iter_mut().collect(),
+        Some(DocumentChanges::Operations(operations)) => operations
+            .iter_mut()
+            .filter_map(|operation| match operation {
+                DocumentChangeOperation::Edit(edit) => Some(edit),
+                _ => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    for document in documents {
+        texts.extend(document.edits.iter_mut().map(|edit| match edit {
+            OneOf::Left(text_edit) => &mut text_edit.new_text,
+            OneOf::Right(annotated) => &mut annotated.text_edit.new_text,
+        }));
+    }
+    texts
+}
@@ -0,0 +1,68 @@
+//! Side-by-side diff of two files, as opened by `fresh --diff OLD NEW`
+
+use std::path::Path;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::composite_buffer::{
+    CompositeLayout, DiffHunk, LineAlignment, PaneStyle, SourcePane,
+};
+use crate::model::event::BufferId;
+use crate::model::line_diff::diff_hunks;
+
+/// Mode name for file diff views
+const FILE_DIFF_MODE: &str = "file-diff";
+
+impl Editor {
+    /// Open two files and show them side by side with their differences
+    /// highlighted. The files are also opened as ordinary tabs for editing.
+    pub fn open_file_diff(&mut self, old: &Path, new: &Path) -> anyhow::Result<BufferId> {
+        let old_content = self.filesystem.read_file(old)?;
+        let new_content = self.filesystem.read_file(new)?;
+        let old_id = self.open_file_no_focus(old)?;
+        let new_id = self.open_file_no_focus(new)?;
+        let old_name = self.get_buffer_display_name(old_id);
+        let new_name = self.get_buffer_display_name(new_id);
+
+        let composite_id = self.create_composite_buffer(
+            t!("diff.title", old = &old_name, new = &new_name).to_string(),
+            FILE_DIFF_MODE.to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![
+                SourcePane::new(old_id, old_name, false).with_style(PaneStyle::old_diff()),
+                SourcePane::new(new_id, new_name, false).with_style(PaneStyle::new_diff()),
+            ],
+        );
+        self.align_diff_panes(composite_id, (old_id, &old_content), (new_id, &new_content));
+        self.set_active_buffer(composite_id);
+        Ok(composite_id)
+    }
+
+    /// Line up the old and new panes of a side-by-side diff on the hunks
+    /// between their contents
+    pub(super) fn align_diff_panes(
+        &mut self,
+        composite_id: BufferId,
+        (old_id, old_content): (BufferId, &[u8]),
+        (new_id, new_content): (BufferId, &[u8]),
+    ) {
+        let hunks: Vec<DiffHunk> = diff_hunks(old_content, new_content)
+            .into_iter()
+            .map(|h| DiffHunk::new(h.old.start, h.old.len(), h.new.start, h.new.len()))
+            .collect();
+        let line_count = |editor: &Self, id: BufferId| {
+            editor
+                .buffers
+                .get(&id)
+                .and_then(|s| s.buffer.line_count())
+                .unwrap_or(0)
+        };
+        let alignment =
+            LineAlignment::from_hunks(&hunks, line_count(self, old_id), line_count(self, new_id));
+        self.set_composite_alignment(composite_id, alignment);
+    }
+}
@@ -43,6 +43,48 @@ fn get_parent_node_id(
     }
 }
 
+/// How long a file explorer deletion can be undone
+const TRASH_UNDO_WINDOW: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Restore the most recently trashed item that lived at `original` from the OS trash.
+#[cfg(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn restore_from_os_trash(original: &std::path::Path) -> std::io::Result<()> {
+    let item = trash::os_limited::list()
+        .map_err(std::io::Error::other)?
+        .into_iter()
+        .filter(|item| item.original_path() == original)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "item not found in trash")
+        })?;
+    trash::os_limited::restore_all([item]).map_err(std::io::Error::other)
+}
+
+/// The OS trash can't be listed on this platform, so restoring is unsupported.
+#[cfg(not(any(
+    target_os = "windows",
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+fn restore_from_os_trash(_original: &std::path::Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "restoring from the trash is not supported on this platform",
+    ))
+}
+
 impl Editor {
     pub fn file_explorer_visible(&self) -> bool {
         self.file_explorer_visible
@@ -150,6 +192,12 @@ impl Editor {
         } else {
             self.working_dir.clone()
         };
+        // Workspace folders outside the root are shown as roots of their own
+        let extra_roots = if self.filesystem.remote_connection_info().is_some() {
+            Vec::new()
+        } else {
+            self.file_explorer_extra_roots(&root_path)
+        };
 
         if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
             let fs_manager = Arc::clone(&self.fs_manager);
@@ -162,6 +210,15 @@ impl Editor {
                         if let Err(e) = tree.expand_node(root_id).await {
                             tracing::warn!("Failed to expand root directory: {}", e);
                         }
+                        for path in extra_roots {
+                            let result = match tree.add_root(path.clone()).await {
+                                Ok(id) => tree.expand_node(id).await,
+                                Err(e) => Err(e),
+                            };
+                            if let Err(e) = result {
+                                tracing::warn!("Failed to add workspace folder {:?}: {}", path, e);
+                            }
+                        }
 
                         let view = FileTreeView::new(tree);
                         let _ = sender.send(AsyncMessage::FileExplorerInitialized(view));
@@ -502,8 +559,8 @@ impl Editor {
     pub fn file_explorer_delete(&mut self) {
         if let Some(explorer) = &self.file_explorer {
             if let Some(selected_id) = explorer.get_selected() {
-                // Don't allow deleting the root directory
-                if selected_id == explorer.tree().root_id() {
+                // Don't allow deleting a root directory
+                if explorer.tree().is_root(selected_id) {
                     self.set_status_message(t!("explorer.cannot_delete_root").to_string());
                     return;
                 }
@@ -536,13 +593,21 @@ impl Editor {
         // For remote files, move to remote trash directory
         // For local files, use system trash
         let delete_result = if self.filesystem.remote_connection_info().is_some() {
-            self.move_to_remote_trash(&path)
+            self.move_to_remote_trash(&path).map(Some)
         } else {
-            trash::delete(&path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+            trash::delete(&path)
+                .map(|_| None)
+                .map_err(std::io::Error::other)
         };
 
         match delete_result {
-            Ok(_) => {
+            Ok(remote_trash_path) => {
+                self.last_trashed = Some(TrashedPath {
+                    original: path.clone(),
+                    remote_trash_path,
+                    trashed_at: self.time_source.now(),
+                });
+
                 // Refresh the parent directory in the file explorer
                 if let Some(explorer) = &mut self.file_explorer {
                     if let Some(runtime) = &self.tokio_runtime {
@@ -573,7 +638,14 @@ impl Editor {
                         }
                     }
                 }
-                self.set_status_message(t!("explorer.moved_to_trash", name = &name).to_string());
+                let message = match self.keybindings.find_keybinding_for_action(
+                    "file_explorer_undo_delete",
+                    KeyContext::FileExplorer,
+                ) {
+                    Some(key) => t!("explorer.moved_to_trash_undo", name = &name, key = key),
+                    None => t!("explorer.moved_to_trash", name = &name),
+                };
+                self.set_status_message(message.to_string());
 
                 // Ensure focus remains on file explorer
                 self.key_context = KeyContext::FileExplorer;
@@ -586,8 +658,58 @@ impl Editor {
         }
     }
 
+    /// Restore the last path deleted from the file explorer, if it was deleted
+    /// within the undo window
+    pub fn file_explorer_undo_delete(&mut self) {
+        let Some(trashed) = self.last_trashed.take() else {
+            self.set_status_message(t!("explorer.nothing_to_restore").to_string());
+            return;
+        };
+        if self.time_source.elapsed_since(trashed.trashed_at) > TRASH_UNDO_WINDOW {
+            self.set_status_message(t!("explorer.restore_expired").to_string());
+            return;
+        }
+        let name = trashed
+            .original
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let restore_result = match &trashed.remote_trash_path {
+            Some(trash_path) => self.filesystem.rename(trash_path, &trashed.original),
+            None => restore_from_os_trash(&trashed.original),
+        };
+
+        match restore_result {
+            Ok(()) => {
+                if let (Some(explorer), Some(runtime)) =
+                    (&mut self.file_explorer, &self.tokio_runtime)
+                {
+                    let parent_id = trashed
+                        .original
+                        .parent()
+                        .and_then(|parent| explorer.tree().get_node_by_path(parent))
+                        .map(|node| node.id);
+                    if let Some(parent_id) = parent_id {
+                        let _ = runtime.block_on(explorer.tree_mut().refresh_node(parent_id));
+                        if let Some(node) = explorer.tree().get_node_by_path(&trashed.original) {
+                            explorer.set_selected(Some(node.id));
+                        }
+                    }
+                }
+                self.set_status_message(t!("explorer.restored", name = &name).to_string());
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("explorer.error_restore", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
     /// Move a file/directory to the remote trash directory (~/.local/share/fresh/trash/)
-    fn move_to_remote_trash(&self, path: &std::path::Path) -> std::io::Result<()> {
+    /// and return its path inside the trash
+    fn move_to_remote_trash(&self, path: &std::path::Path) -> std::io::Result<PathBuf> {
         // Get remote home directory
         let home = self.filesystem.home_dir()?;
         let trash_dir = home.join(".local/share/fresh/trash");
@@ -609,14 +731,15 @@ impl Editor {
         let trash_path = trash_dir.join(trash_name);
 
         // Move to trash
-        self.filesystem.rename(path, &trash_path)
+        self.filesystem.rename(path, &trash_path)?;
+        Ok(trash_path)
     }
 
     pub fn file_explorer_rename(&mut self) {
         if let Some(explorer) = &self.file_explorer {
             if let Some(selected_id) = explorer.get_selected() {
-                // Don't allow renaming the root directory
-                if selected_id == explorer.tree().root_id() {
+                // Don't allow renaming a root directory
+                if explorer.tree().is_root(selected_id) {
                     self.set_status_message(t!("explorer.cannot_rename_root").to_string());
                     return;
                 }
@@ -660,6 +783,32 @@ impl Editor {
             .map(|p| p.join(&new_name))
             .unwrap_or_else(|| original_path.clone());
 
+        // Language servers may have edits to make first, such as updated
+        // imports; the rename then waits for them (see `lsp_file_rename`).
+        // Nothing refers to a file that was only just created.
+        if !is_new_file
+            && self.request_will_rename_files(&original_path, &original_name, &new_path, &new_name)
+        {
+            return;
+        }
+        self.rename_explorer_entry(
+            original_path,
+            original_name,
+            new_path,
+            new_name,
+            is_new_file,
+        );
+    }
+
+    /// Rename a file or directory on disk, and the buffers of the files in it
+    pub(super) fn rename_explorer_entry(
+        &mut self,
+        original_path: std::path::PathBuf,
+        original_name: String,
+        new_path: std::path::PathBuf,
+        new_name: String,
+        is_new_file: bool,
+    ) {
         if let Some(runtime) = &self.tokio_runtime {
             let result = self.filesystem.rename(&original_path, &new_path);
 
@@ -676,14 +825,22 @@ impl Editor {
                         explorer.navigate_to_path(&new_path);
                     }
 
-                    // Update buffer metadata if this file is open in a buffer
-                    let buffer_to_update = self
+                    // Update buffer metadata of the files open in buffers, the
+                    // renamed file itself or ones inside a renamed directory
+                    let buffers_to_update: Vec<_> = self
                         .buffers
                         .iter()
-                        .find(|(_, state)| state.buffer.file_path() == Some(&original_path))
-                        .map(|(id, _)| *id);
+                        .filter_map(|(id, state)| {
+                            let relative = state.buffer.file_path()?.strip_prefix(&original_path);
+                            let new_path = match relative.ok()? {
+                                relative if relative.as_os_str().is_empty() => new_path.clone(),
+                                relative => new_path.join(relative),
+                            };
+                            Some((*id, new_path))
+                        })
+                        .collect();
 
-                    if let Some(buffer_id) = buffer_to_update {
+                    for (buffer_id, new_path) in buffers_to_update {
                         // Update the buffer's file path
                         if let Some(state) = self.buffers.get_mut(&buffer_id) {
                             state.buffer.set_file_path(new_path.clone());
@@ -716,9 +873,23 @@ impl Editor {
                         }
                     }
 
+                    self.notify_did_rename_files(&original_path, &new_path);
                     self.set_status_message(
                         t!("explorer.renamed", old = &original_name, new = &new_name).to_string(),
                     );
+
+                    // A file just created in the explorer is named now, so its
+                    // templates are known
+                    if is_new_file {
+                        let buffer_id = self
+                            .buffers
+                            .iter()
+                            .find(|(_, state)| state.buffer.file_path() == Some(new_path.as_path()))
+                            .map(|(id, _)| *id);
+                        if let Some(buffer_id) = buffer_id {
+                            self.offer_file_templates(buffer_id);
+                        }
+                    }
                 }
                 Err(e) => {
                     self.set_status_message(
@@ -0,0 +1,100 @@
+//! Lock files for files with unsaved changes
+//!
+//! A modified file is locked (see `services::file_lock`) so another Fresh
+//! editing the same file can warn its user. Opening or modifying a file that
+//! someone else has locked makes the buffer read-only and asks whether to
+//! keep it that way or edit anyway. Locks are released when the buffer is
+//! closed and when the editor exits.
+
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::file_lock::LockOwner;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Lock the files of modified buffers, and release the locks of files
+    /// that are no longer open. Called once per frame.
+    pub fn update_file_locks(&mut self) {
+        if !self.config.editor.file_locks {
+            return;
+        }
+        let to_lock: Vec<(BufferId, PathBuf)> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified())
+            .filter_map(|(id, state)| Some((*id, state.buffer.file_path()?.to_path_buf())))
+            .filter(|(_, path)| !self.file_locks.is_held(path) && !self.file_locks.is_skipped(path))
+            .collect();
+        for (buffer_id, path) in to_lock {
+            match self.file_locks.owner(&path) {
+                Some(owner) => self.confirm_edit_locked_file(buffer_id, &path, owner),
+                None => {
+                    if let Err(e) = self.file_locks.acquire(&path) {
+                        tracing::warn!("Failed to lock {:?}: {}", path, e);
+                        self.file_locks.skip(&path);
+                    }
+                }
+            }
+        }
+
+        let stale: Vec<PathBuf> = self
+            .file_locks
+            .paths()
+            .filter(|path| {
+                !self
+                    .buffers
+                    .values()
+                    .any(|state| state.buffer.file_path() == Some(path))
+            })
+            .cloned()
+            .collect();
+        for path in stale {
+            self.file_locks.release(&path);
+        }
+    }
+
+    /// Check a newly opened file against other editors' locks
+    pub(super) fn check_file_lock(&mut self, buffer_id: BufferId, path: &Path) {
+        if !self.config.editor.file_locks {
+            return;
+        }
+        if let Some(owner) = self.file_locks.owner(path) {
+            self.confirm_edit_locked_file(buffer_id, path, owner);
+        }
+    }
+
+    /// Make a buffer whose file another editor has locked read-only, and ask
+    /// whether to edit it anyway
+    fn confirm_edit_locked_file(&mut self, buffer_id: BufferId, path: &Path, owner: LockOwner) {
+        self.file_locks.skip(path);
+        self.set_buffer_read_only(buffer_id, true);
+        let name = self.get_buffer_display_name(buffer_id);
+        if self.is_prompting() {
+            // Don't replace the prompt the user is answering
+            self.set_status_message(
+                t!("file_lock.opened_read_only", name = name, pid = owner.pid).to_string(),
+            );
+            return;
+        }
+        self.start_prompt(
+            t!("file_lock.locked_prompt", name = name, pid = owner.pid).to_string(),
+            PromptType::ConfirmEditLockedFile { buffer_id },
+        );
+    }
+
+    /// Handle ConfirmEditLockedFile prompt confirmation.
+    pub(super) fn handle_confirm_edit_locked_file(&mut self, buffer_id: BufferId, input: &str) {
+        let name = self.get_buffer_display_name(buffer_id);
+        let input_lower = input.trim().to_lowercase();
+        if input_lower == "e" || input_lower == "edit" {
+            self.set_buffer_read_only(buffer_id, false);
+            self.set_status_message(t!("file_lock.editing_anyway", name = name).to_string());
+        } else {
+            self.set_status_message(t!("file_lock.read_only", name = name).to_string());
+        }
+    }
+}
@@ -18,18 +18,27 @@ impl Editor {
             .map(|p| {
                 matches!(
                     p.prompt_type,
-                    PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                    PromptType::OpenFile
+                        | PromptType::SwitchProject
+                        | PromptType::AddWorkspaceFolder
+                        | PromptType::SaveFileAs
                 )
             })
             .unwrap_or(false)
             && self.file_open_state.is_some()
     }
 
-    /// Check if we're in folder-only selection mode (Switch Project)
+    /// Check if we're in folder-only selection mode (Switch Project, Add
+    /// Folder to Workspace)
     fn is_folder_open_mode(&self) -> bool {
         self.prompt
             .as_ref()
-            .map(|p| p.prompt_type == PromptType::SwitchProject)
+            .map(|p| {
+                matches!(
+                    p.prompt_type,
+                    PromptType::SwitchProject | PromptType::AddWorkspaceFolder
+                )
+            })
             .unwrap_or(false)
     }
 
@@ -261,14 +270,19 @@ impl Editor {
         // In folder mode, selecting a file does nothing
     }
 
-    /// Select a folder as the new project root (for SwitchProject mode)
+    /// Select a folder as the new project root (for SwitchProject mode) or
+    /// add it to the workspace (for AddWorkspaceFolder mode)
     fn file_open_select_folder(&mut self, path: std::path::PathBuf) {
         // Close the file browser
         self.file_open_state = None;
-        self.prompt = None;
+        let prompt = self.prompt.take();
 
-        // Change the working directory
-        self.change_working_dir(path);
+        if prompt.is_some_and(|p| p.prompt_type == PromptType::AddWorkspaceFolder) {
+            self.add_workspace_folder(path);
+        } else {
+            // Change the working directory
+            self.change_working_dir(path);
+        }
     }
 
     /// Navigate to a directory in the file browser
@@ -315,12 +329,18 @@ impl Editor {
         self.key_context = crate::input::keybindings::KeyContext::Normal;
 
         // Open the file - this will create an unsaved buffer with the path set
-        if let Err(e) = self.open_file(&path) {
-            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
-        } else {
-            self.set_status_message(
-                t!("file.created_new", path = path.display().to_string()).to_string(),
-            );
+        match self.open_file(&path) {
+            Ok(buffer_id) => {
+                self.set_status_message(
+                    t!("file.created_new", path = path.display().to_string()).to_string(),
+                );
+                self.offer_file_templates(buffer_id);
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+            }
         }
     }
 
@@ -16,10 +16,12 @@ use lsp_types::TextDocumentContentChangeEvent;
 use rust_i18n::t;
 
 use crate::model::event::{BufferId, EventLog};
-use crate::services::lsp::manager::{detect_language, LspSpawnResult};
+use crate::primitives::path_utils::expand_tilde;
+use crate::services::backup::BackupConfig;
+use crate::services::lsp::manager::LspSpawnResult;
 use crate::state::EditorState;
 
-use super::{BufferMetadata, Editor};
+use super::{disk_merge, BufferMetadata, Editor};
 
 impl Editor {
     /// Save the active buffer
@@ -30,7 +32,16 @@ impl Editor {
             .file_path()
             .map(|p| p.to_path_buf());
 
-        match self.active_state_mut().buffer.save() {
+        if let Some(ref p) = path {
+            if let Err(e) = self.backup_before_save(p) {
+                tracing::warn!("Failed to back up {:?}: {}", p, e);
+            }
+        }
+
+        let save_strategy = self.config.editor.save_strategy;
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_save_strategy(save_strategy);
+        match buffer.save() {
             Ok(()) => self.finalize_save(path),
             Err(e) => {
                 if let Some(sudo_info) = e.downcast_ref::<SudoSaveRequired>() {
@@ -47,6 +58,26 @@ impl Editor {
         }
     }
 
+    /// Back up the on-disk version of `path` before it is first overwritten
+    /// in this session, if backups are enabled
+    pub(crate) fn backup_before_save(&mut self, path: &Path) -> std::io::Result<()> {
+        if !self.config.editor.backup_enabled || self.backed_up_files.contains(path) {
+            return Ok(());
+        }
+
+        let directory = &self.config.editor.backup_directory;
+        let config = BackupConfig {
+            numbered: self.config.editor.backup_numbered,
+            directory: (!directory.is_empty()).then(|| expand_tilde(directory)),
+            keep_versions: self.config.editor.backup_keep_versions,
+        };
+        if let Some(backup) = config.make_backup(self.filesystem.as_ref(), path)? {
+            tracing::debug!("Backed up {:?} to {:?}", path, backup);
+        }
+        self.backed_up_files.insert(path.to_path_buf());
+        Ok(())
+    }
+
     /// Internal helper to finalize save state (mark as saved, notify LSP, etc.)
     pub(crate) fn finalize_save(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
         // Auto-detect language if it's currently "text" and we have a path
@@ -122,12 +153,21 @@ impl Editor {
             }
         }
 
+        // After on-save actions, which may have changed the buffer again
+        self.persist_undo_history(self.active_buffer());
+        self.forget_disk_merge(self.active_buffer());
+
         Ok(())
     }
 
     /// Revert the active buffer to the last saved version on disk
     /// Returns Ok(true) if reverted, Ok(false) if no file path, Err on failure
     pub fn revert_file(&mut self) -> anyhow::Result<bool> {
+        // Encrypted files are decrypted again rather than loaded as they are
+        if self.active_state().buffer.encryption().is_some() {
+            return self.revert_encrypted_file(self.active_buffer());
+        }
+
         let path = match self.active_state().buffer.file_path() {
             Some(p) => p.to_path_buf(),
             None => {
@@ -162,19 +202,25 @@ impl Editor {
             std::sync::Arc::clone(&self.filesystem),
         )?;
 
-        // Restore cursor positions (clamped to valid range for new file size)
+        // Restore cursor positions, moved with the text around them
         let new_file_size = new_state.buffer.len();
-        let mut restored_cursors = old_cursors;
-        restored_cursors.map(|cursor| {
-            cursor.position = cursor.position.min(new_file_size);
-            // Clear selection since the content may have changed
-            cursor.clear_selection();
-        });
-        new_state.cursors = restored_cursors;
+        new_state.cursors = disk_merge::reloaded_cursors(
+            &self.active_state().buffer,
+            old_cursors,
+            &new_state.buffer,
+        );
 
-        // Replace the current buffer with the new state
+        // Keep read-only buffers read-only
         let buffer_id = self.active_buffer();
+        self.forget_disk_merge(buffer_id);
+        new_state.editing_disabled = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| metadata.read_only);
+
+        // Replace the current buffer with the new state
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            new_state.buffer.continue_revisions_from(&state.buffer);
             *state = new_state;
             // Note: line_wrap_enabled is now in SplitViewState.viewport
         }
@@ -243,6 +289,25 @@ impl Editor {
         }
         self.last_auto_revert_poll = self.time_source.now();
 
+        self.check_open_files_changed()
+    }
+
+    /// Check open files for external changes right away (the terminal
+    /// regained focus), rather than waiting for the next poll
+    ///
+    /// Returns true if any file was changed (requires re-render).
+    pub fn handle_terminal_focus_gained(&mut self) -> bool {
+        if !self.auto_revert_enabled || !self.config.editor.auto_revert_on_focus_gain {
+            return false;
+        }
+        // Checked just now, so the next poll can wait a full interval
+        self.last_auto_revert_poll = self.time_source.now();
+        self.check_open_files_changed()
+    }
+
+    /// Compare the modification times of open files with the ones last seen,
+    /// reverting the buffers of files that changed
+    fn check_open_files_changed(&mut self) -> bool {
         // Collect paths of open files that need checking
         let files_to_check: Vec<PathBuf> = self
             .buffers
@@ -363,7 +428,7 @@ impl Editor {
         metadata: &mut BufferMetadata,
     ) {
         // Early return checks that don't need mutable lsp borrow
-        let Some(language) = detect_language(path, &self.config.languages) else {
+        let Some(language) = metadata.language(&self.config.languages) else {
             tracing::debug!("No language detected for file: {}", path.display());
             return;
         };
@@ -407,7 +472,7 @@ impl Editor {
             }
         };
 
-        let enable_inlay_hints = self.config.editor.enable_inlay_hints;
+        let enable_inlay_hints = self.inlay_hints_shown();
         let previous_result_id = self.diagnostic_result_ids.get(uri.as_str()).cloned();
 
         // Get buffer line count for inlay hints
@@ -524,16 +589,22 @@ impl Editor {
         let Ok(lsp_uri) = uri.as_str().parse::<lsp_types::Uri>() else {
             return;
         };
-        let Some(language) = detect_language(path, &self.config.languages) else {
-            return;
-        };
-
         // Find the buffer ID for this path
-        let Some((buffer_id, content)) = self
+        let Some((buffer_id, content, revision)) = self
             .buffers
             .iter()
             .find(|(_, s)| s.buffer.file_path() == Some(path))
-            .and_then(|(id, state)| state.buffer.to_string().map(|t| (*id, t)))
+            .and_then(|(id, state)| {
+                let text = state.buffer.to_string()?;
+                Some((*id, text, state.buffer.revision()))
+            })
+        else {
+            return;
+        };
+        let Some(language) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|metadata| metadata.language(&self.config.languages))
         else {
             return;
         };
@@ -602,7 +673,7 @@ impl Editor {
                     range_length: None,
                     text: content,
                 };
-                if let Err(e) = client.did_change(lsp_uri, vec![content_change]) {
+                if let Err(e) = client.did_change(lsp_uri, vec![content_change], revision) {
                     tracing::warn!("Failed to notify LSP of file change: {}", e);
                 }
             }
@@ -619,6 +690,14 @@ impl Editor {
         buffer_id: BufferId,
         path: &Path,
     ) -> anyhow::Result<()> {
+        if self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.buffer.encryption().is_some())
+        {
+            return self.revert_encrypted_file(buffer_id).map(|_| ());
+        }
+
         // Load the file content fresh from disk
         let new_state = EditorState::from_file_with_languages(
             path,
@@ -630,27 +709,15 @@ impl Editor {
             std::sync::Arc::clone(&self.filesystem),
         )?;
 
-        // Get the new file size for clamping
-        let new_file_size = new_state.buffer.len();
-
-        // Get old cursors before replacing the buffer
-        let old_cursors = self
-            .buffers
-            .get(&buffer_id)
-            .map(|s| s.cursors.clone())
-            .unwrap_or_default();
-
-        // Replace the buffer content
+        // Replace the buffer content, moving the cursors with the text
+        // around them
+        self.forget_disk_merge(buffer_id);
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let old_cursors = state.cursors.clone();
+            let cursors =
+                disk_merge::reloaded_cursors(&state.buffer, old_cursors, &new_state.buffer);
             *state = new_state;
-
-            // Restore cursor positions (clamped to valid range for new file size)
-            let mut restored_cursors = old_cursors;
-            restored_cursors.map(|cursor| {
-                cursor.position = cursor.position.min(new_file_size);
-                cursor.clear_selection();
-            });
-            state.cursors = restored_cursors;
+            state.cursors = cursors;
         }
 
         // Clear the undo/redo history for this buffer
@@ -725,8 +792,16 @@ impl Editor {
                 continue;
             }
 
-            // If buffer has local modifications, show a warning (don't auto-revert)
+            // If buffer has local modifications, merge the changes on disk
+            // into it, or show a warning if they can't be (don't auto-revert)
             if state.buffer.is_modified() {
+                if self.auto_revert_enabled
+                    && self.config.editor.auto_revert_merge
+                    && self.merge_disk_changes(buffer_id, &path)
+                {
+                    self.watch_file(&path);
+                    continue;
+                }
                 self.status_message = Some(format!(
                     "File {} changed on disk (buffer has unsaved changes)",
                     path.display()
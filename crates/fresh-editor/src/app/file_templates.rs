@@ -0,0 +1,250 @@
+//! Templates for new files (`editor.file_templates`).
+//!
+//! When a file that doesn't exist yet is opened, created in the file explorer
+//! or started with New File, the templates for its path or language are
+//! offered in a prompt. The chosen template's content is a snippet: its
+//! variables (`${FILENAME}`, `${DATE}`, `${AUTHOR}`...) are replaced, then it's
+//! inserted into the empty buffer with the cursor at `$0`.
+
+use std::path::Path;
+
+use ignore::gitignore::GitignoreBuilder;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::config::FileTemplate;
+use crate::input::commands::Suggestion;
+use crate::model::event::{BufferId, Event};
+use crate::primitives::snippet::{expand_snippet, substitute_variables};
+use crate::services::lsp::manager::detect_language;
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Value of the suggestion that leaves the new file empty
+const EMPTY_FILE: &str = "empty";
+
+impl Editor {
+    /// Offer the templates for a new, empty buffer. Those matching its file
+    /// are offered, or every template for a buffer without a file.
+    pub(super) fn offer_file_templates(&mut self, buffer_id: BufferId) {
+        if self.config.editor.file_templates.is_empty() {
+            return;
+        }
+        // Another prompt isn't replaced, but the offer for a previous file is
+        if self
+            .prompt
+            .as_ref()
+            .is_some_and(|p| !matches!(p.prompt_type, PromptType::SelectFileTemplate { .. }))
+        {
+            return;
+        }
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        if !state.buffer.is_empty() || state.buffer.is_modified() {
+            return;
+        }
+
+        let path = state.buffer.file_path().map(Path::to_path_buf);
+        let templates = &self.config.editor.file_templates;
+        let matching: Vec<usize> = match &path {
+            Some(path) => self.matching_file_templates(path),
+            None => (0..templates.len()).collect(),
+        };
+        if matching.is_empty() {
+            return;
+        }
+
+        let mut suggestions: Vec<Suggestion> = matching
+            .iter()
+            .map(|&index| {
+                let template = &templates[index];
+                Suggestion {
+                    text: template.name.clone(),
+                    description: template.language.clone(),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        let empty = Suggestion {
+            text: t!("file_templates.empty_file").to_string(),
+            description: None,
+            value: Some(EMPTY_FILE.to_string()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        };
+        // A file's own templates come first; a buffer without a file stays
+        // empty unless a template is picked
+        if path.is_some() {
+            suggestions.push(empty);
+        } else {
+            suggestions.insert(0, empty);
+        }
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("file_templates.prompt").to_string(),
+            PromptType::SelectFileTemplate { buffer_id },
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Insert the template chosen at the file template prompt
+    pub(super) fn handle_select_file_template(&mut self, buffer_id: BufferId, input: &str) {
+        let input = input.trim();
+        if input == EMPTY_FILE {
+            return;
+        }
+        let Some(template) = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| self.config.editor.file_templates.get(index))
+            .cloned()
+        else {
+            self.set_status_message(t!("file_templates.unknown", name = input).to_string());
+            return;
+        };
+        // The buffer must still be the empty one the templates were offered for
+        if self.active_buffer() != buffer_id || !self.active_state().buffer.is_empty() {
+            return;
+        }
+
+        let path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(Path::to_path_buf);
+        let snippet = substitute_variables(&template.content, |name| {
+            self.file_template_variable(path.as_deref(), name)
+        });
+        let expanded = expand_snippet(&snippet);
+
+        let cursor_id = self.active_state().cursors.primary_id();
+        let insert_event = Event::Insert {
+            position: 0,
+            text: expanded.text.clone(),
+            cursor_id,
+        };
+        self.active_event_log_mut().append(insert_event.clone());
+        self.apply_event_to_active_buffer(&insert_event);
+
+        let current_pos = self.active_state().cursors.primary().position;
+        if current_pos != expanded.cursor_offset {
+            let move_event = Event::MoveCursor {
+                cursor_id,
+                old_position: current_pos,
+                new_position: expanded.cursor_offset,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            };
+            self.active_state_mut().apply(&move_event);
+        }
+
+        // A buffer without a file takes the template's language
+        if path.is_none() {
+            if let Some(language) = &template.language {
+                self.set_buffer_language(buffer_id, language);
+            }
+        }
+        self.set_status_message(
+            t!("file_templates.inserted", name = template.name.as_str()).to_string(),
+        );
+    }
+
+    /// Indexes of the templates for a file: those with a pattern matching its
+    /// path or for its language, and those for every file
+    fn matching_file_templates(&self, path: &Path) -> Vec<usize> {
+        let language = detect_language(path, &self.config.languages);
+        self.config
+            .editor
+            .file_templates
+            .iter()
+            .enumerate()
+            .filter(|(_, template)| self.file_template_matches(template, path, language.as_deref()))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn file_template_matches(
+        &self,
+        template: &FileTemplate,
+        path: &Path,
+        language: Option<&str>,
+    ) -> bool {
+        if template.patterns.is_empty() && template.language.is_none() {
+            return true;
+        }
+        if template.language.is_some() && template.language.as_deref() == language {
+            return true;
+        }
+        if template.patterns.is_empty() {
+            return false;
+        }
+        // Patterns are relative to the working directory, like .gitignore's
+        let mut builder = GitignoreBuilder::new(&self.working_dir);
+        for pattern in &template.patterns {
+            if let Err(e) = builder.add_line(None, pattern) {
+                tracing::warn!("Invalid file template pattern {:?}: {}", pattern, e);
+            }
+        }
+        match builder.build() {
+            Ok(patterns) => patterns.matched(path, false).is_ignore(),
+            Err(e) => {
+                tracing::warn!("Failed to build the patterns of {:?}: {}", template.name, e);
+                false
+            }
+        }
+    }
+
+    /// The value of a template variable for the file at `path`
+    fn file_template_variable(&self, path: Option<&Path>, name: &str) -> Option<String> {
+        let file_name = |path: Option<&Path>| {
+            path.and_then(Path::file_name)
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        };
+        let value = match name {
+            "FILENAME" => file_name(path),
+            "FILENAME_BASE" => path
+                .and_then(Path::file_stem)
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "DIRECTORY" => file_name(Some(
+                path.and_then(Path::parent)
+                    .unwrap_or(self.working_dir.as_path()),
+            )),
+            "DATE" => self.time_source.today_date().to_string(),
+            "YEAR" => self.time_source.today_date().format("%Y").to_string(),
+            "AUTHOR" => self.file_template_author(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// `editor.file_template_author`, or else git's user.name or the user's
+    /// login name
+    fn file_template_author(&self) -> String {
+        let configured = self.config.editor.file_template_author.trim();
+        if !configured.is_empty() {
+            return configured.to_string();
+        }
+        std::process::Command::new("git")
+            .args(["config", "user.name"])
+            .current_dir(&self.working_dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|name| !name.is_empty())
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_default()
+    }
+}
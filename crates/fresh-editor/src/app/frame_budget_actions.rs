@@ -0,0 +1,101 @@
+//! Turning decorations off while frames take too long
+//!
+//! Every frame drawn is checked against `editor.frame_budget_ms` (see
+//! `services::frame_budget`). When frames keep going over it, semantic
+//! highlighting, inlay hints and the background fade are turned off in that
+//! order, and turned back on once frames are fast again. The status bar
+//! shows when any of them is off.
+
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::services::frame_budget::{BudgetChange, Layer};
+
+impl Editor {
+    /// Whether decorations are turned off because frames take too long
+    pub fn is_frame_budget_degraded(&self) -> bool {
+        self.frame_budget.is_degraded()
+    }
+
+    /// Whether inlay hints are enabled and not turned off under load
+    pub(super) fn inlay_hints_shown(&self) -> bool {
+        self.config.editor.enable_inlay_hints && !self.frame_budget.is_disabled(Layer::InlayHints)
+    }
+
+    /// Whether semantic highlighting is turned off under load
+    pub(super) fn semantic_highlight_degraded(&self) -> bool {
+        self.frame_budget.is_disabled(Layer::SemanticHighlight)
+    }
+
+    /// Whether the background fade is turned off under load
+    pub(super) fn background_fade_degraded(&self) -> bool {
+        self.frame_budget.is_disabled(Layer::BackgroundFade)
+    }
+
+    /// Check a frame's time against the budget, turning a layer off or back
+    /// on if it's time to
+    pub(super) fn check_frame_budget(&mut self, elapsed: Duration) {
+        let Some(change) = self.frame_budget.record(elapsed) else {
+            return;
+        };
+        match change {
+            BudgetChange::Disabled(layer) => {
+                tracing::info!("Frames over budget, turning off {:?}", layer);
+                self.disable_layer(layer);
+                self.set_status_message(
+                    t!("frame_budget.disabled", layer = layer_name(layer)).to_string(),
+                );
+            }
+            BudgetChange::Restored(layer) => {
+                tracing::info!("Frames within budget, turning on {:?}", layer);
+                self.restore_layer(layer);
+                self.set_status_message(
+                    t!("frame_budget.restored", layer = layer_name(layer)).to_string(),
+                );
+            }
+        }
+    }
+
+    fn disable_layer(&mut self, layer: Layer) {
+        match layer {
+            Layer::SemanticHighlight => {
+                // Rendering stops updating them (and requesting semantic
+                // tokens); clear the ones shown
+                for state in self.buffers.values_mut() {
+                    state
+                        .reference_highlight_overlay
+                        .clear(&mut state.overlays, &mut state.marker_list);
+                }
+            }
+            Layer::InlayHints => {
+                // Drop hints that are on their way, and the ones shown
+                self.pending_inlay_hints_request = None;
+                let ns = super::lsp_requests::inlay_hint_namespace();
+                for state in self.buffers.values_mut() {
+                    state
+                        .virtual_texts
+                        .clear_namespace(&mut state.marker_list, &ns);
+                }
+            }
+            // Rendering leaves the background out
+            Layer::BackgroundFade => {}
+        }
+    }
+
+    fn restore_layer(&mut self, layer: Layer) {
+        if layer == Layer::InlayHints {
+            self.request_inlay_hints_for_active_buffer();
+        }
+    }
+}
+
+fn layer_name(layer: Layer) -> String {
+    match layer {
+        Layer::SemanticHighlight => t!("frame_budget.semantic_highlight"),
+        Layer::InlayHints => t!("frame_budget.inlay_hints"),
+        Layer::BackgroundFade => t!("frame_budget.background_fade"),
+    }
+    .to_string()
+}
@@ -0,0 +1,353 @@
+//! Go to Anything prompt.
+//!
+//! One prompt for every kind of jump, routed by the first character like
+//! Quick Open:
+//! - `:123` goes to a line in the current buffer
+//! - `@name` filters the current buffer's symbols (`textDocument/documentSymbol`)
+//! - `#name` searches the workspace's symbols (`workspace/symbol`)
+//! - anything else fuzzy-matches files in the project
+//!
+//! Lines and files reuse the Quick Open pickers; symbols come from the active
+//! buffer's language server and arrive asynchronously.
+
+use lsp_types::SymbolKind;
+use rust_i18n::t;
+
+use super::prompt_actions::PromptResult;
+use super::{uri_to_path, Editor};
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::fuzzy_match;
+use crate::services::async_bridge::LspSymbol;
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Symbols fetched for the Go to Anything prompt, reset each time it opens
+#[derive(Debug, Default)]
+pub(super) struct GotoSymbols {
+    /// Symbols of the active buffer, `None` until requested and received
+    document: Option<Vec<LspSymbol>>,
+    document_request: Option<u64>,
+    /// Symbols returned for `workspace_query`
+    workspace: Vec<LspSymbol>,
+    workspace_request: Option<u64>,
+    workspace_query: Option<String>,
+    /// The active buffer has no language server to ask
+    unavailable: bool,
+}
+
+impl Editor {
+    /// Open the Go to Anything prompt
+    pub(super) fn start_goto_anything(&mut self) {
+        self.on_editor_focus_lost();
+        // The hints line replaces the status message while the prompt is open
+        self.status_message = None;
+        self.goto_symbols = GotoSymbols::default();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            String::new(),
+            PromptType::GotoAnything,
+            vec![],
+        ));
+        self.update_goto_anything_suggestions("");
+    }
+
+    /// Update Go to Anything suggestions based on the prefix of the input
+    pub(super) fn update_goto_anything_suggestions(&mut self, input: &str) {
+        let suggestions = if let Some(line) = input.strip_prefix(':') {
+            self.get_goto_line_suggestions(line)
+        } else if let Some(query) = input.strip_prefix('@') {
+            self.request_document_symbols();
+            self.document_symbol_suggestions(query)
+        } else if let Some(query) = input.strip_prefix('#') {
+            self.request_workspace_symbols(query);
+            self.workspace_symbol_suggestions(query)
+        } else {
+            self.get_file_suggestions(input)
+        };
+
+        if let Some(prompt) = &mut self.prompt {
+            prompt.suggestions = suggestions;
+            prompt.selected_suggestion = if prompt.suggestions.is_empty() {
+                None
+            } else {
+                Some(0)
+            };
+        }
+    }
+
+    /// Handle Go to Anything confirmation based on the prefix of the input
+    pub(super) fn handle_goto_anything_confirm(
+        &mut self,
+        input: &str,
+        selected_index: Option<usize>,
+    ) -> PromptResult {
+        let suggestions = if input.starts_with(':') {
+            return self.handle_quick_open_confirm(input, selected_index);
+        } else if let Some(query) = input.strip_prefix('@') {
+            self.document_symbol_suggestions(query)
+        } else if let Some(query) = input.strip_prefix('#') {
+            self.workspace_symbol_suggestions(query)
+        } else {
+            return self.handle_quick_open_file(input, selected_index);
+        };
+
+        // Symbol suggestions store the symbol's index in their value
+        let symbols: &[LspSymbol] = if input.starts_with('@') {
+            self.goto_symbols.document.as_deref().unwrap_or_default()
+        } else {
+            &self.goto_symbols.workspace
+        };
+        let symbol = selected_index
+            .and_then(|idx| suggestions.get(idx))
+            .filter(|suggestion| !suggestion.disabled)
+            .and_then(|suggestion| suggestion.value.as_ref()?.parse::<usize>().ok())
+            .and_then(|index| symbols.get(index))
+            .cloned();
+
+        match symbol {
+            Some(symbol) => self.go_to_symbol(&symbol),
+            None => self.set_status_message(t!("status.no_selection").to_string()),
+        }
+        PromptResult::Done
+    }
+
+    /// Handle the document symbols for the Go to Anything prompt
+    pub(super) fn handle_document_symbols_response(
+        &mut self,
+        request_id: u64,
+        symbols: Vec<LspSymbol>,
+    ) {
+        if self.goto_symbols.document_request != Some(request_id) {
+            return;
+        }
+        self.goto_symbols.document_request = None;
+        self.goto_symbols.document = Some(symbols);
+        self.refresh_goto_anything_suggestions();
+    }
+
+    /// Handle the workspace symbols for the Go to Anything prompt
+    pub(super) fn handle_workspace_symbols_response(
+        &mut self,
+        request_id: u64,
+        symbols: Vec<LspSymbol>,
+    ) {
+        if self.goto_symbols.workspace_request != Some(request_id) {
+            tracing::trace!(
+                "Ignoring outdated workspace symbols response {}",
+                request_id
+            );
+            return;
+        }
+        self.goto_symbols.workspace_request = None;
+        self.goto_symbols.workspace = symbols;
+        self.refresh_goto_anything_suggestions();
+    }
+
+    fn refresh_goto_anything_suggestions(&mut self) {
+        let input = match &self.prompt {
+            Some(prompt) if prompt.prompt_type == PromptType::GotoAnything => prompt.input.clone(),
+            _ => return,
+        };
+        self.update_goto_anything_suggestions(&input);
+    }
+
+    /// Ask the language server for the active buffer's symbols, once per prompt
+    fn request_document_symbols(&mut self) {
+        let symbols = &self.goto_symbols;
+        if symbols.document.is_some() || symbols.document_request.is_some() || symbols.unavailable {
+            return;
+        }
+
+        let request_id = self.next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(self.active_buffer(), |handle, uri, _language| {
+                handle.document_symbols(request_id, uri.clone()).is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.goto_symbols.document_request = Some(request_id);
+        } else {
+            self.goto_symbols.unavailable = true;
+        }
+    }
+
+    /// Ask the language server for workspace symbols whenever the query changes
+    fn request_workspace_symbols(&mut self, query: &str) {
+        if query.is_empty()
+            || self.goto_symbols.unavailable
+            || self.goto_symbols.workspace_query.as_deref() == Some(query)
+        {
+            return;
+        }
+
+        let request_id = self.next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(self.active_buffer(), |handle, _uri, _language| {
+                handle
+                    .workspace_symbols(request_id, query.to_string())
+                    .is_ok()
+            })
+            .unwrap_or(false);
+
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.goto_symbols.workspace_request = Some(request_id);
+            self.goto_symbols.workspace_query = Some(query.to_string());
+        } else {
+            self.goto_symbols.unavailable = true;
+        }
+    }
+
+    /// Suggestions for `@query`, in document order until a query is typed
+    fn document_symbol_suggestions(&self, query: &str) -> Vec<Suggestion> {
+        if self.goto_symbols.unavailable {
+            return vec![disabled_suggestion(t!("goto_anything.no_language_server"))];
+        }
+        let Some(symbols) = &self.goto_symbols.document else {
+            return vec![disabled_suggestion(t!("goto_anything.loading_symbols"))];
+        };
+
+        symbol_suggestions(symbols, query, |symbol| match &symbol.container {
+            Some(container) => format!("{} in {}", symbol_kind_label(symbol.kind), container),
+            None => symbol_kind_label(symbol.kind).to_string(),
+        })
+    }
+
+    /// Suggestions for `#query`, with the file and line of each symbol
+    fn workspace_symbol_suggestions(&self, query: &str) -> Vec<Suggestion> {
+        if self.goto_symbols.unavailable {
+            return vec![disabled_suggestion(t!("goto_anything.no_language_server"))];
+        }
+        if query.is_empty() {
+            return vec![disabled_suggestion(t!("goto_anything.workspace_hint"))];
+        }
+        if self.goto_symbols.workspace.is_empty() && self.goto_symbols.workspace_request.is_some() {
+            return vec![disabled_suggestion(t!("goto_anything.loading_symbols"))];
+        }
+
+        symbol_suggestions(&self.goto_symbols.workspace, query, |symbol| {
+            let path = uri_to_path(&symbol.location.uri)
+                .map(|path| {
+                    path.strip_prefix(&self.working_dir)
+                        .unwrap_or(&path)
+                        .display()
+                        .to_string()
+                })
+                .unwrap_or_else(|_| symbol.location.uri.as_str().to_string());
+            format!(
+                "{}  {}:{}",
+                symbol_kind_label(symbol.kind),
+                path,
+                symbol.location.range.start.line + 1
+            )
+        })
+    }
+
+    /// Open the file containing a symbol and move the cursor to it
+    fn go_to_symbol(&mut self, symbol: &LspSymbol) {
+        let Ok(path) = uri_to_path(&symbol.location.uri) else {
+            self.set_status_message(t!("goto_anything.cannot_open").to_string());
+            return;
+        };
+
+        self.record_jump();
+        let buffer_id = match self.open_file(&path) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        let start = symbol.location.range.start;
+        if let Some(state) = self.buffers.get(&buffer_id) {
+            let position = state
+                .buffer
+                .line_col_to_position(start.line as usize, start.character as usize);
+            self.go_to_location(buffer_id, position);
+        }
+        self.set_status_message(
+            t!(
+                "goto_anything.jumped_to_symbol",
+                name = &symbol.name,
+                line = start.line + 1
+            )
+            .to_string(),
+        );
+    }
+}
+
+/// Fuzzy-filter symbols by name, best match first
+///
+/// The suggestion value is the symbol's index in `symbols`.
+fn symbol_suggestions(
+    symbols: &[LspSymbol],
+    query: &str,
+    describe: impl Fn(&LspSymbol) -> String,
+) -> Vec<Suggestion> {
+    let mut matches: Vec<(usize, i32)> = symbols
+        .iter()
+        .enumerate()
+        .filter_map(|(index, symbol)| {
+            let result = fuzzy_match(query, &symbol.name);
+            result.matched.then_some((index, result.score))
+        })
+        .collect();
+    if matches.is_empty() {
+        return vec![disabled_suggestion(t!("goto_anything.no_symbols"))];
+    }
+    // Stable sort keeps document order among equal scores
+    matches.sort_by(|a, b| b.1.cmp(&a.1));
+
+    matches
+        .into_iter()
+        .map(|(index, _)| {
+            let symbol = &symbols[index];
+            Suggestion {
+                text: symbol.name.clone(),
+                description: Some(describe(symbol)),
+                value: Some(index.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            }
+        })
+        .collect()
+}
+
+fn disabled_suggestion(text: impl Into<String>) -> Suggestion {
+    Suggestion {
+        text: text.into(),
+        description: None,
+        value: None,
+        disabled: true,
+        keybinding: None,
+        source: None,
+    }
+}
+
+fn symbol_kind_label(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::FILE => "file",
+        SymbolKind::MODULE => "module",
+        SymbolKind::NAMESPACE => "namespace",
+        SymbolKind::PACKAGE => "package",
+        SymbolKind::CLASS => "class",
+        SymbolKind::METHOD => "method",
+        SymbolKind::PROPERTY => "property",
+        SymbolKind::FIELD => "field",
+        SymbolKind::CONSTRUCTOR => "constructor",
+        SymbolKind::ENUM => "enum",
+        SymbolKind::INTERFACE => "interface",
+        SymbolKind::FUNCTION => "function",
+        SymbolKind::VARIABLE => "variable",
+        SymbolKind::CONSTANT => "constant",
+        SymbolKind::ENUM_MEMBER => "variant",
+        SymbolKind::STRUCT => "struct",
+        SymbolKind::TYPE_PARAMETER => "type parameter",
+        _ => "symbol",
+    }
+}
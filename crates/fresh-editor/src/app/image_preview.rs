@@ -0,0 +1,203 @@
+//! Image previews over the splits showing image files, and thumbnails of the
+//! image hovered in the file explorer (see `services::image_preview`)
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{BufferId, SplitId};
+use crate::services::image_preview::{is_image_path, ImageStatus};
+
+/// Size of the file explorer thumbnail, borders included
+const THUMBNAIL_WIDTH: u16 = 26;
+const THUMBNAIL_HEIGHT: u16 = 14;
+
+impl Editor {
+    /// The image file previewed in place of `buffer_id`'s text, if any.
+    /// Binary image files are previewed unless toggled off; SVG files are
+    /// text, so only when toggled on.
+    pub fn image_preview_path(&self, buffer_id: BufferId) -> Option<PathBuf> {
+        self.image_previews.protocol()?;
+        let path = self.buffer_metadata.get(&buffer_id)?.file_path()?;
+        if !is_image_path(path) {
+            return None;
+        }
+        let binary = self.buffers.get(&buffer_id)?.buffer.is_binary();
+        (binary != self.image_preview_toggled.contains(&buffer_id)).then(|| path.clone())
+    }
+
+    /// Switch the active buffer between its image preview and its text
+    pub fn toggle_image_preview(&mut self) {
+        if self.image_previews.protocol().is_none() {
+            self.set_status_message(t!("image_preview.unsupported").to_string());
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let is_image = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|metadata| metadata.file_path())
+            .is_some_and(|path| is_image_path(path));
+        if !is_image {
+            self.set_status_message(t!("image_preview.not_an_image").to_string());
+            return;
+        }
+        if !self.image_preview_toggled.remove(&buffer_id) {
+            self.image_preview_toggled.insert(buffer_id);
+        }
+        let status = if self.image_preview_path(buffer_id).is_some() {
+            t!("image_preview.shown")
+        } else {
+            t!("image_preview.hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
+
+    /// Track the image file under the mouse in the file explorer.
+    /// Returns true if the thumbnail changed.
+    pub(super) fn update_hovered_explorer_image(&mut self, col: u16, row: u16) -> bool {
+        let in_explorer = self
+            .cached_layout
+            .file_explorer_area
+            .is_some_and(|area| col > area.x && col < area.right().saturating_sub(1));
+        let hovered = if in_explorer && self.image_previews.protocol().is_some() {
+            self.file_explorer_path_at_row(row)
+                .filter(|path| is_image_path(path))
+        } else {
+            None
+        };
+        if hovered == self.hovered_explorer_image {
+            return false;
+        }
+        self.hovered_explorer_image = hovered;
+        true
+    }
+
+    /// Pick up images decoded in the background. Returns true if any finished.
+    pub(super) fn poll_image_previews(&mut self) -> bool {
+        self.image_previews.poll()
+    }
+
+    /// Send the images placed in the frame just drawn to the terminal.
+    /// `cleared` says the screen was cleared before drawing it.
+    pub fn write_image_previews(&mut self, out: &mut impl Write, cleared: bool) -> io::Result<()> {
+        if self.image_previews.write(out, cleared)? {
+            // Sixel images can only be removed by redrawing the screen
+            self.request_full_redraw();
+        }
+        Ok(())
+    }
+
+    /// Draw image previews over the splits showing image files, and the
+    /// thumbnail of the image hovered in the file explorer
+    pub(super) fn render_image_previews(
+        &mut self,
+        frame: &mut Frame,
+        split_areas: &[(SplitId, BufferId, Rect, Rect, usize, usize)],
+    ) {
+        self.image_previews.begin_frame();
+        if self.image_previews.protocol().is_none() {
+            return;
+        }
+        // Images are drawn above the text, so they would hide menus, popups
+        // and dialogs; leave them out while one is open
+        let place = !self.image_previews_covered();
+
+        for (_split_id, buffer_id, content_rect, _, _, _) in split_areas {
+            if let Some(path) = self.image_preview_path(*buffer_id) {
+                self.render_image(frame, &path, *content_rect, place);
+            }
+        }
+
+        if let Some(path) = self.hovered_explorer_image.clone() {
+            self.render_explorer_thumbnail(frame, &path, place);
+        }
+    }
+
+    /// Draw the thumbnail box next to the hovered file explorer row
+    fn render_explorer_thumbnail(&mut self, frame: &mut Frame, path: &Path, place: bool) {
+        let (Some(explorer_area), Some((_, row))) = (
+            self.cached_layout.file_explorer_area,
+            self.mouse_state.last_position,
+        ) else {
+            return;
+        };
+        let screen = frame.area();
+        let x = explorer_area.right();
+        let width = THUMBNAIL_WIDTH.min(screen.right().saturating_sub(x));
+        let height = THUMBNAIL_HEIGHT.min(screen.height);
+        if width < 3 || height < 3 {
+            return;
+        }
+        let y = row.min(screen.bottom().saturating_sub(height));
+        let area = Rect::new(x, y, width, height);
+
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(name)
+            .border_style(Style::default().fg(self.theme.popup_border_fg))
+            .style(Style::default().bg(self.theme.popup_bg));
+        let inner = block.inner(area);
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+        self.render_image(frame, path, inner, place);
+    }
+
+    /// Fill `area` with the image at `path`, or a placeholder while it loads
+    fn render_image(&mut self, frame: &mut Frame, path: &Path, area: Rect, place: bool) {
+        let style = Style::default()
+            .fg(self.theme.line_number_fg)
+            .bg(self.theme.editor_bg);
+        frame.render_widget(Clear, area);
+        frame.render_widget(Block::default().style(style), area);
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let message = match self.image_previews.get(path, area.width, area.height) {
+            ImageStatus::Ready(image) => {
+                if place {
+                    // Center the image in the area
+                    let x = area.x + area.width.saturating_sub(image.cols) / 2;
+                    let y = area.y + area.height.saturating_sub(image.rows) / 2;
+                    self.image_previews.place(x, y, image);
+                }
+                return;
+            }
+            ImageStatus::Loading => t!("image_preview.loading").to_string(),
+            ImageStatus::Failed(error) => t!("image_preview.failed", error = error).to_string(),
+        };
+        let half = area.height / 2;
+        let text_area = Rect::new(area.x, area.y + half, area.width, area.height - half);
+        frame.render_widget(
+            Paragraph::new(message)
+                .style(style)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            text_area,
+        );
+    }
+
+    /// Whether something is drawn over the editor that images would hide
+    fn image_previews_covered(&self) -> bool {
+        self.menu_state.active_menu.is_some()
+            || self.active_state().popups.is_visible()
+            || self.is_settings_open()
+            || self.calibration_wizard.is_some()
+            || self.event_debug.is_some()
+            || self.cheat_sheet.is_some()
+            || self.buffer_switcher.is_some()
+            || self.tab_context_menu.is_some()
+            || self.prompt.is_some()
+    }
+}
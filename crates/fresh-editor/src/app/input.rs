@@ -1,5 +1,9 @@
 use super::*;
+use crate::config::RenderWhitespace;
+use crate::primitives::extract::ExtractKind;
+use crate::primitives::indent_detection::Indentation;
 use crate::services::plugins::hooks::HookArgs;
+use crate::view::ui::status_bar::indentation_label;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 impl Editor {
@@ -226,6 +230,12 @@ impl Editor {
         // Record action to macro if recording
         self.record_macro_action(&action);
 
+        // Check off Tutorial lessons done by running an action
+        self.observe_tutorial_action(&action);
+
+        // Let Cursor Undo go back to the cursors from before the action
+        self.observe_cursors();
+
         match action {
             Action::Quit => self.quit(),
             Action::ForceQuit => {
@@ -283,12 +293,32 @@ impl Editor {
                 );
                 self.init_folder_open_state();
             }
+            Action::AddWorkspaceFolder => {
+                self.start_prompt(
+                    t!("workspace.add_prompt").to_string(),
+                    PromptType::AddWorkspaceFolder,
+                );
+                self.init_folder_open_state();
+            }
+            Action::RemoveWorkspaceFolder => self.start_remove_workspace_folder_prompt(),
+            Action::CompareFolders => self.start_prompt(
+                t!("dir_diff.left_prompt").to_string(),
+                PromptType::CompareFoldersLeft,
+            ),
+            Action::DirDiffOpen => self.dir_diff_open_entry(),
+            Action::DirDiffCopyToRight => self.dir_diff_copy_entry(true),
+            Action::DirDiffCopyToLeft => self.dir_diff_copy_entry(false),
+            Action::DirDiffRefresh => self.refresh_dir_diff(),
+            Action::RunTask(name) => self.run_task(&name),
+            Action::TaskOutputOpen => self.task_output_open(),
+            Action::TaskOutputRerun => self.task_output_rerun(),
             Action::GotoLine => self.start_prompt(
                 t!("file.goto_line_prompt").to_string(),
                 PromptType::GotoLine,
             ),
             Action::New => {
-                self.new_buffer();
+                let buffer_id = self.new_buffer();
+                self.offer_file_templates(buffer_id);
             }
             Action::Close | Action::CloseTab => {
                 // Both Close and CloseTab use close_tab() which handles:
@@ -297,6 +327,11 @@ impl Editor {
                 // - Properly closing the buffer
                 self.close_tab();
             }
+            Action::TogglePinTab => self.toggle_active_tab_pinned(),
+            Action::CloseAllBuffers => self.close_all_buffers(),
+            Action::CloseSavedBuffers => self.close_saved_buffers(),
+            Action::CloseOtherBuffers => self.close_other_buffers(),
+            Action::ReopenClosedBuffer => self.reopen_closed_buffer(),
             Action::Revert => {
                 // Check if buffer has unsaved changes - prompt for confirmation
                 if self.active_state().buffer.is_modified() {
@@ -323,6 +358,12 @@ impl Editor {
             Action::ToggleAutoRevert => {
                 self.toggle_auto_revert();
             }
+            Action::ToggleReadOnly => {
+                self.toggle_read_only();
+            }
+            Action::RecoverFiles => {
+                self.recover_files();
+            }
             Action::FormatBuffer => {
                 if let Err(e) = self.format_buffer() {
                     self.set_status_message(
@@ -356,6 +397,7 @@ impl Editor {
                     );
                 }
             },
+            Action::ReindentLines => self.reindent_lines(),
             Action::Copy => {
                 // Check if there's an active popup with text selection
                 let state = self.active_state();
@@ -378,16 +420,17 @@ impl Editor {
                 self.copy_selection()
             }
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
+            Action::CopyAsAnsi => self.copy_selection_as_ansi(),
             Action::Cut => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 self.cut_selection()
             }
             Action::Paste => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 self.paste()
@@ -417,6 +460,25 @@ impl Editor {
             Action::ShowLspStatus => {
                 self.show_lsp_status_popup();
             }
+            Action::ShowLspLog => {
+                self.show_lsp_log();
+            }
+            Action::ShowProjectEnv => {
+                self.show_project_env();
+            }
+            Action::ReloadProjectEnv => self.reload_project_env(),
+            Action::ShowTerminalInfo => {
+                self.show_terminal_info();
+            }
+            Action::ShowProfiler => {
+                self.show_profiler();
+            }
+            Action::ShowTutorial => {
+                self.show_tutorial();
+            }
+            Action::ToggleCheatSheet => {
+                self.toggle_cheat_sheet();
+            }
             Action::ClearWarnings => {
                 self.clear_warnings();
             }
@@ -429,6 +491,10 @@ impl Editor {
                     }
                 }
 
+                // The project's tasks may have changed since the palette was
+                // last opened
+                self.refresh_task_commands();
+
                 // Use the current context for filtering commands
                 let active_buffer_mode = self
                     .buffer_metadata
@@ -460,6 +526,8 @@ impl Editor {
                 // Start Quick Open with file suggestions (default mode)
                 self.start_quick_open();
             }
+            Action::GotoAnything => self.start_goto_anything(),
+            Action::OpenLinkAtCursor => self.open_link_at_cursor(),
             Action::ToggleLineWrap => {
                 self.config.editor.line_wrap = !self.config.editor.line_wrap;
 
@@ -475,9 +543,41 @@ impl Editor {
                 };
                 self.set_status_message(t!("view.line_wrap_state", state = state).to_string());
             }
+            Action::ToggleConceal => {
+                let concealer = &mut self.active_state_mut().concealer;
+                concealer.enabled = !concealer.enabled;
+
+                let state = if concealer.enabled {
+                    t!("view.state_enabled").to_string()
+                } else {
+                    t!("view.state_disabled").to_string()
+                };
+                self.set_status_message(t!("view.conceal_state", state = state).to_string());
+            }
             Action::ToggleComposeMode => {
                 self.handle_toggle_compose_mode();
             }
+            Action::ToggleMarkdownPreview => {
+                self.handle_toggle_markdown_preview();
+            }
+            Action::OpenMarkdownPreviewToSide => {
+                self.open_markdown_preview_to_side();
+            }
+            Action::ToggleSpellCheck => {
+                self.toggle_spell_check();
+            }
+            Action::SpellCheckSuggestions => {
+                self.show_spell_suggestions();
+            }
+            Action::SpellCheckAddWord => {
+                self.add_spell_word_at_cursor();
+            }
+            Action::SetSpellLanguage => {
+                self.start_set_spell_language_prompt();
+            }
+            Action::AdjustColor => {
+                self.show_color_adjuster();
+            }
             Action::SetComposeWidth => {
                 let active_split = self.split_manager.active_split();
                 let current = self
@@ -517,10 +617,12 @@ impl Editor {
                 );
             }
             Action::LspCompletion => {
-                self.request_completion()?;
+                if !self.request_completion()? && self.config.editor.word_completion {
+                    self.show_word_completions();
+                }
             }
             Action::LspGotoDefinition => {
-                self.request_goto_definition()?;
+                self.goto_definition()?;
             }
             Action::LspRename => {
                 self.start_rename()?;
@@ -537,6 +639,8 @@ impl Editor {
             Action::LspCodeActions => {
                 self.request_code_actions()?;
             }
+            Action::ExtractVariable => self.start_extract_prompt(ExtractKind::Variable),
+            Action::ExtractFunction => self.start_extract_prompt(ExtractKind::Function),
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
@@ -549,9 +653,18 @@ impl Editor {
             Action::DumpConfig => {
                 self.dump_config();
             }
+            Action::ShowConfigSource => {
+                self.start_show_config_source_prompt();
+            }
+            Action::TrustWorkspace => {
+                self.start_trust_workspace_prompt();
+            }
             Action::SelectTheme => {
                 self.start_select_theme_prompt();
             }
+            Action::ToggleLightDarkTheme => {
+                self.toggle_light_dark_theme();
+            }
             Action::SelectKeybindingMap => {
                 self.start_select_keybinding_map_prompt();
             }
@@ -619,12 +732,18 @@ impl Editor {
                 self.find_selection_previous();
             }
             Action::AddCursorNextMatch => self.add_cursor_at_next_match(),
+            Action::SelectRegexInSelection => self.start_regex_select_prompt(false),
+            Action::SplitSelectionOnRegex => self.start_regex_select_prompt(true),
+            Action::CursorUndo => self.cursor_undo(),
+            Action::CursorRedo => self.cursor_redo(),
             Action::AddCursorAbove => self.add_cursor_above(),
             Action::AddCursorBelow => self.add_cursor_below(),
             Action::NextBuffer => self.next_buffer(),
             Action::PrevBuffer => self.prev_buffer(),
             Action::SwitchToPreviousTab => self.switch_to_previous_tab(),
             Action::SwitchToTabByName => self.start_switch_to_tab_prompt(),
+            Action::SwitchBufferMru => self.open_buffer_switcher(1),
+            Action::SwitchBufferMruReverse => self.open_buffer_switcher(-1),
 
             // Tab scrolling (manual scroll - don't auto-adjust)
             Action::ScrollTabsLeft => {
@@ -643,6 +762,15 @@ impl Editor {
             }
             Action::NavigateBack => self.navigate_back(),
             Action::NavigateForward => self.navigate_forward(),
+            Action::JumpBack => self.jump_back(),
+            Action::JumpForward => self.jump_forward(),
+            Action::ShowJumpList => self.start_jump_list_prompt(),
+            Action::GotoTag => self.goto_tag(),
+            Action::SelectTag => self.start_tag_prompt(),
+            Action::PopTag => self.pop_tag(),
+            Action::RegenerateTags => self.regenerate_tags(),
+            Action::GotoLastChange => self.goto_last_change(),
+            Action::GotoNextChange => self.goto_next_change(),
             Action::SplitHorizontal => self.split_pane_horizontal(),
             Action::SplitVertical => self.split_pane_vertical(),
             Action::CloseSplit => self.close_active_split(),
@@ -654,10 +782,12 @@ impl Editor {
             Action::ToggleFileExplorer => self.toggle_file_explorer(),
             Action::ToggleMenuBar => self.toggle_menu_bar(),
             Action::ToggleTabBar => self.toggle_tab_bar(),
+            Action::ToggleBreadcrumbs => self.toggle_breadcrumbs(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
             Action::ToggleDebugHighlights => self.toggle_debug_highlights(),
+            Action::ToggleImagePreview => self.toggle_image_preview(),
             // Buffer settings
             Action::SetTabSize => {
                 let current = self
@@ -671,9 +801,13 @@ impl Editor {
                     current,
                 );
             }
+            Action::SetIndentation => {
+                self.start_set_indentation_prompt();
+            }
             Action::SetLineEnding => {
                 self.start_set_line_ending_prompt();
             }
+            Action::InsertSequence => self.start_insert_sequence_prompt(),
             Action::SetEncoding => {
                 self.start_set_encoding_prompt();
             }
@@ -702,6 +836,27 @@ impl Editor {
                     self.set_status_message(status.to_string());
                 }
             }
+            Action::ToggleWhitespace => {
+                // Turning it on uses the configured mode, or shows all
+                // whitespace when the config doesn't render any
+                let configured = match self.config.editor.render_whitespace {
+                    RenderWhitespace::None => RenderWhitespace::All,
+                    mode => mode,
+                };
+                let guides = &mut self.active_state_mut().guides;
+                guides.render_whitespace = match guides.render_whitespace {
+                    RenderWhitespace::None => configured,
+                    _ => RenderWhitespace::None,
+                };
+
+                let state = if guides.render_whitespace == RenderWhitespace::None {
+                    t!("view.state_disabled").to_string()
+                } else {
+                    t!("view.state_enabled").to_string()
+                };
+                self.set_status_message(t!("view.whitespace_state", state = state).to_string());
+            }
+            Action::ToggleLineLengthWarnings => self.toggle_line_length_warnings(),
             Action::ResetBufferSettings => self.reset_buffer_settings(),
             Action::FocusFileExplorer => self.focus_file_explorer(),
             Action::FocusEditor => self.focus_editor(),
@@ -716,6 +871,7 @@ impl Editor {
             Action::FileExplorerNewFile => self.file_explorer_new_file(),
             Action::FileExplorerNewDirectory => self.file_explorer_new_directory(),
             Action::FileExplorerDelete => self.file_explorer_delete(),
+            Action::FileExplorerUndoDelete => self.file_explorer_undo_delete(),
             Action::FileExplorerRename => self.file_explorer_rename(),
             Action::FileExplorerToggleHidden => self.file_explorer_toggle_hidden(),
             Action::FileExplorerToggleGitignored => self.file_explorer_toggle_gitignored(),
@@ -960,7 +1116,7 @@ impl Editor {
             Action::None => {}
             Action::DeleteBackward => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.set_status_message(self.editing_disabled_message());
                     return Ok(());
                 }
                 // Normal backspace handling
@@ -1064,6 +1220,15 @@ impl Editor {
                 // Run shell command on buffer/selection, replace content
                 self.start_shell_command_prompt(true);
             }
+            Action::NewScratchBuffer => self.start_new_scratch_buffer_prompt(),
+            Action::SendToRepl => self.send_to_repl(false),
+            Action::SendToReplInsert => self.send_to_repl(true),
+            Action::CollabHost => self.start_collab_host_prompt(),
+            Action::CollabJoin => self.start_collab_join_prompt(),
+            Action::CollabLeave => self.collab_leave(),
+            Action::StartPresenting => self.start_presenting_prompt(),
+            Action::FollowPresentation => self.start_follow_presentation_prompt(),
+            Action::StopPresenting => self.stop_presentation(),
             Action::OpenSettings => {
                 self.open_settings();
             }
@@ -1175,7 +1340,8 @@ impl Editor {
             Action::PromptCopy => {
                 if let Some(prompt) = &self.prompt {
                     let text = prompt.selected_text().unwrap_or_else(|| prompt.get_text());
-                    if !text.is_empty() {
+                    // Secrets never go to the clipboard
+                    if !text.is_empty() && !prompt.is_secret() {
                         self.clipboard.copy(text);
                         self.set_status_message(t!("clipboard.copied").to_string());
                     }
@@ -1184,7 +1350,7 @@ impl Editor {
             Action::PromptCut => {
                 if let Some(prompt) = &self.prompt {
                     let text = prompt.selected_text().unwrap_or_else(|| prompt.get_text());
-                    if !text.is_empty() {
+                    if !text.is_empty() && !prompt.is_secret() {
                         self.clipboard.copy(text);
                     }
                 }
@@ -1233,6 +1399,27 @@ impl Editor {
         // (Bug #248: Mouse wheel stopped working properly after keyboard use)
         self.sync_editor_state_to_split_view_state();
 
+        // Scrolling over a tab bar switches to the previous or next tab
+        let tab_bar_split = self
+            .cached_layout
+            .tab_layouts
+            .iter()
+            .find(|(_, tab_layout)| tab_layout.hit_test(col, row).is_some())
+            .map(|(split_id, _)| *split_id);
+        if let Some(split_id) = tab_bar_split {
+            if split_id != self.split_manager.active_split() {
+                if let Some(buffer_id) = self.split_manager.buffer_for_split(split_id) {
+                    self.focus_split(split_id, buffer_id);
+                }
+            }
+            if delta < 0 {
+                self.prev_buffer();
+            } else {
+                self.next_buffer();
+            }
+            return Ok(());
+        }
+
         // Check if scroll is over the file explorer
         if let Some(explorer_area) = self.cached_layout.file_explorer_area {
             if col >= explorer_area.x
@@ -1269,11 +1456,60 @@ impl Editor {
             }
         }
 
-        // Otherwise, scroll the editor in the active split
-        // Use SplitViewState's viewport (View events go to SplitViewState, not EditorState)
-        let active_split = self.split_manager.active_split();
-        let buffer_id = self.active_buffer();
+        // Otherwise, scroll the split under the pointer, falling back to the
+        // active split when the pointer isn't over one
+        let (split_id, buffer_id) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, _, _, _, _)| (*split_id, *buffer_id))
+            .unwrap_or_else(|| (self.split_manager.active_split(), self.active_buffer()));
 
+        if self.config.editor.smooth_scrolling && !self.config.editor.reduced_motion {
+            self.queue_smooth_scroll(split_id, buffer_id, delta);
+        } else {
+            self.scroll_split_lines(split_id, buffer_id, delta);
+        }
+
+        Ok(())
+    }
+
+    /// Handle horizontal mouse wheel scroll (or Shift+wheel) over a split
+    pub(super) fn handle_mouse_horizontal_scroll(&mut self, col: u16, row: u16, delta: i32) {
+        let Some((split_id, buffer_id)) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, _, _, _, _)| (*split_id, *buffer_id))
+        else {
+            return;
+        };
+
+        let buffer = self.buffers.get_mut(&buffer_id).map(|s| &mut s.buffer);
+        let view_state = self.split_view_states.get_mut(&split_id);
+        if let (Some(buffer), Some(view_state)) = (buffer, view_state) {
+            view_state
+                .viewport
+                .scroll_horizontal(buffer, delta as isize);
+            // Skip ensure_visible so the cursor doesn't pull the view back
+            view_state.viewport.set_skip_ensure_visible();
+        }
+    }
+
+    /// Scroll a split's view of a buffer by `delta` lines
+    fn scroll_split_lines(&mut self, split_id: SplitId, buffer_id: BufferId, delta: i32) {
         // Check if this is a composite buffer - if so, use composite scroll
         if self.is_composite_buffer(buffer_id) {
             let max_row = self
@@ -1281,30 +1517,28 @@ impl Editor {
                 .get(&buffer_id)
                 .map(|c| c.row_count().saturating_sub(1))
                 .unwrap_or(0);
-            if let Some(view_state) = self
-                .composite_view_states
-                .get_mut(&(active_split, buffer_id))
-            {
+            if let Some(view_state) = self.composite_view_states.get_mut(&(split_id, buffer_id)) {
                 view_state.scroll(delta as isize, max_row);
                 tracing::trace!(
-                    "handle_mouse_scroll (composite): delta={}, scroll_row={}",
+                    "scroll_split_lines (composite): delta={}, scroll_row={}",
                     delta,
                     view_state.scroll_row
                 );
             }
-            return Ok(());
+            return;
         }
 
         // Get view_transform tokens from SplitViewState (if any)
         let view_transform_tokens = self
             .split_view_states
-            .get(&active_split)
+            .get(&split_id)
             .and_then(|vs| vs.view_transform.as_ref())
             .map(|vt| vt.tokens.clone());
 
         // Get mutable references to both buffer and view state
+        // Use SplitViewState's viewport (View events go to SplitViewState, not EditorState)
         let buffer = self.buffers.get_mut(&buffer_id).map(|s| &mut s.buffer);
-        let view_state = self.split_view_states.get_mut(&active_split);
+        let view_state = self.split_view_states.get_mut(&split_id);
 
         if let (Some(buffer), Some(view_state)) = (buffer, view_state) {
             let top_byte_before = view_state.viewport.top_byte;
@@ -1332,14 +1566,68 @@ impl Editor {
             // Skip ensure_visible so the scroll position isn't undone during render
             view_state.viewport.set_skip_ensure_visible();
             tracing::trace!(
-                "handle_mouse_scroll: delta={}, top_byte {} -> {}",
+                "scroll_split_lines: delta={}, top_byte {} -> {}",
                 delta,
                 top_byte_before,
                 view_state.viewport.top_byte
             );
         }
+    }
 
-        Ok(())
+    /// Add a wheel scroll to the smooth scroll in progress
+    ///
+    /// Wheel events in the same direction over the same split add up, so a
+    /// fast flick keeps gliding; reversing direction or moving to another
+    /// split starts over.
+    fn queue_smooth_scroll(&mut self, split_id: SplitId, buffer_id: BufferId, delta: i32) {
+        match &mut self.smooth_scroll {
+            Some(scroll)
+                if scroll.split_id == split_id
+                    && scroll.buffer_id == buffer_id
+                    && scroll.pending.signum() == delta.signum() =>
+            {
+                scroll.pending += delta;
+            }
+            _ => {
+                self.smooth_scroll = Some(SmoothScroll {
+                    split_id,
+                    buffer_id,
+                    pending: delta,
+                    last_step: None,
+                });
+            }
+        }
+    }
+
+    /// Advance the smooth scroll in progress by one frame
+    ///
+    /// Each frame scrolls a third of the remaining distance (at least one
+    /// line), so the view decelerates to a stop. Returns true while
+    /// scrolling is in progress and the screen needs redrawing.
+    pub fn step_smooth_scroll(&mut self) -> bool {
+        const FRAME: std::time::Duration = std::time::Duration::from_millis(16);
+
+        let Some(scroll) = &mut self.smooth_scroll else {
+            return false;
+        };
+        let now = self.time_source.now();
+        if scroll
+            .last_step
+            .is_some_and(|last_step| now.duration_since(last_step) < FRAME)
+        {
+            return true;
+        }
+        scroll.last_step = Some(now);
+
+        let step = (scroll.pending.abs() + 2) / 3 * scroll.pending.signum();
+        scroll.pending -= step;
+        let (split_id, buffer_id) = (scroll.split_id, scroll.buffer_id);
+        if scroll.pending == 0 {
+            self.smooth_scroll = None;
+        }
+
+        self.scroll_split_lines(split_id, buffer_id, step);
+        true
     }
 
     /// Handle scrollbar drag with relative movement (when dragging from thumb)
@@ -1827,6 +2115,7 @@ impl Editor {
             .unwrap_or(0);
 
         // Calculate clicked position in buffer
+        let mut clicked_position = None;
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let gutter_width = state.margins.left_total_width() as u16;
 
@@ -1875,10 +2164,9 @@ impl Editor {
             let old_position = primary_cursor.position;
             let old_anchor = primary_cursor.anchor;
 
-            // For shift+click or ctrl+click: extend selection from current anchor (or position if no anchor) to click
-            // Both modifiers supported since some terminals intercept shift+click
-            let extend_selection = modifiers.contains(KeyModifiers::SHIFT)
-                || modifiers.contains(KeyModifiers::CONTROL);
+            // For shift+click: extend selection from current anchor (or position if no anchor) to click
+            // Ctrl+click is handled below, after the cursor has moved
+            let extend_selection = modifiers.contains(KeyModifiers::SHIFT);
             let new_anchor = if extend_selection {
                 // If already selecting, keep the existing anchor; otherwise anchor at current position
                 Some(old_anchor.unwrap_or(old_position))
@@ -1913,6 +2201,17 @@ impl Editor {
             self.mouse_state.drag_selection_split = Some(split_id);
             // For shift+click, anchor stays at selection start; otherwise anchor at click position
             self.mouse_state.drag_selection_anchor = Some(new_anchor.unwrap_or(target_position));
+
+            clicked_position = Some(target_position);
+        }
+
+        // Ctrl+click opens the link under the pointer or goes to the definition
+        if let Some(position) = clicked_position {
+            if modifiers.contains(KeyModifiers::CONTROL) && !modifiers.contains(KeyModifiers::SHIFT)
+            {
+                self.mouse_state.dragging_text_selection = false;
+                return self.handle_ctrl_click(buffer_id, position);
+            }
         }
 
         Ok(())
@@ -2029,6 +2328,88 @@ impl Editor {
         }
     }
 
+    /// Start the indentation selection prompt: tabs or spaces of a common
+    /// width, detected from the buffer's contents, or from the config
+    fn start_set_indentation_prompt(&mut self) {
+        let (use_tabs, tab_size, detected) = {
+            let state = self.active_state();
+            (state.use_tabs, state.tab_size, state.detected_indentation)
+        };
+        let detected_label = detected.map(|indentation| match indentation {
+            Indentation::Tabs => indentation_label(true, tab_size),
+            Indentation::Spaces(width) => indentation_label(false, width),
+        });
+
+        let mut options: Vec<(String, String, bool, bool)> = [2, 4, 8]
+            .into_iter()
+            .map(|width| {
+                (
+                    t!("indentation.spaces", size = width).to_string(),
+                    format!("spaces:{}", width),
+                    !use_tabs && tab_size == width,
+                    detected == Some(Indentation::Spaces(width)),
+                )
+            })
+            .collect();
+        options.push((
+            t!("indentation.tabs").to_string(),
+            "tabs".to_string(),
+            use_tabs,
+            detected == Some(Indentation::Tabs),
+        ));
+        options.push((
+            t!("indentation.detect").to_string(),
+            "detect".to_string(),
+            false,
+            false,
+        ));
+        options.push((
+            t!("indentation.default").to_string(),
+            "default".to_string(),
+            false,
+            false,
+        ));
+
+        let current_index = options.iter().position(|(_, _, is_current, _)| *is_current);
+        let suggestions: Vec<crate::input::commands::Suggestion> = options
+            .iter()
+            .map(|(text, value, is_current, is_detected)| {
+                let description = match (*is_current, *is_detected) {
+                    (true, true) => Some(format!(
+                        "{}, {}",
+                        t!("indentation.current"),
+                        t!("indentation.detected")
+                    )),
+                    (true, false) => Some(t!("indentation.current").to_string()),
+                    (false, true) => Some(t!("indentation.detected").to_string()),
+                    // The detect option says what would be detected
+                    (false, false) if value == "detect" => detected_label.clone(),
+                    (false, false) => None,
+                };
+                crate::input::commands::Suggestion {
+                    text: text.clone(),
+                    description,
+                    value: Some(value.clone()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("indentation.prompt").to_string(),
+            PromptType::SetIndentation,
+            suggestions,
+        ));
+
+        if let (Some(prompt), Some(index)) = (self.prompt.as_mut(), current_index) {
+            prompt.selected_suggestion = Some(index);
+            prompt.input = options[index].0.clone();
+            prompt.cursor_pos = prompt.input.len();
+        }
+    }
+
     /// Start the encoding selection prompt
     fn start_set_encoding_prompt(&mut self) {
         use crate::model::buffer::Encoding;
@@ -2077,8 +2458,39 @@ impl Editor {
 
     /// Start the language selection prompt
     fn start_set_language_prompt(&mut self) {
-        let current_language = self.active_state().language.clone();
+        let state = self.active_state();
+        let current_language = state
+            .highlighter
+            .syntax_name()
+            .map_or_else(|| state.language.clone(), str::to_string);
+        let suggestions = self.language_suggestions(&current_language);
 
+        // Find current language index
+        let current_index = suggestions
+            .iter()
+            .position(|s| s.value.as_deref() == Some(&current_language))
+            .unwrap_or(0);
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            "Language: ".to_string(),
+            PromptType::SetLanguage,
+            suggestions,
+        ));
+
+        if let Some(prompt) = self.prompt.as_mut() {
+            if !prompt.suggestions.is_empty() {
+                prompt.selected_suggestion = Some(current_index);
+                // Don't set input - keep it empty so typing filters the list
+                // The selected suggestion shows the current language
+            }
+        }
+    }
+
+    /// Suggestions for every available language, with `current_language` marked
+    pub(super) fn language_suggestions(
+        &self,
+        current_language: &str,
+    ) -> Vec<crate::input::commands::Suggestion> {
         // Build suggestions from all available syntect syntaxes + Plain Text option
         let mut suggestions: Vec<crate::input::commands::Suggestion> = vec![
             // Plain Text option (no syntax highlighting)
@@ -2120,26 +2532,7 @@ impl Editor {
                 source: None,
             });
         }
-
-        // Find current language index
-        let current_index = suggestions
-            .iter()
-            .position(|s| s.value.as_deref() == Some(&current_language))
-            .unwrap_or(0);
-
-        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
-            "Language: ".to_string(),
-            PromptType::SetLanguage,
-            suggestions,
-        ));
-
-        if let Some(prompt) = self.prompt.as_mut() {
-            if !prompt.suggestions.is_empty() {
-                prompt.selected_suggestion = Some(current_index);
-                // Don't set input - keep it empty so typing filters the list
-                // The selected suggestion shows the current language
-            }
-        }
+        suggestions
     }
 
     /// Start the theme selection prompt with available themes
@@ -2195,14 +2588,24 @@ impl Editor {
     /// Apply a theme by name and persist it to config
     pub(super) fn apply_theme(&mut self, theme_name: &str) {
         if !theme_name.is_empty() {
-            if let Some(theme) = self.theme_registry.get_cloned(theme_name) {
+            if let Some(theme) = self.load_theme(theme_name) {
                 self.theme = theme;
 
                 // Set terminal cursor color to match theme
                 self.theme.set_terminal_cursor_color();
 
-                // Update the config in memory
-                self.config.theme = self.theme.name.clone().into();
+                // Update the config in memory. With a light/dark appearance
+                // active, the pick replaces the theme for that appearance.
+                let name: crate::config::ThemeName = self.theme.name.clone().into();
+                match self.current_appearance() {
+                    Some(crate::view::theme::Appearance::Light) => {
+                        self.config.appearance.light_theme = name
+                    }
+                    Some(crate::view::theme::Appearance::Dark) => {
+                        self.config.appearance.dark_theme = name
+                    }
+                    None => self.config.theme = name,
+                }
 
                 // Persist to config file
                 self.save_theme_to_config();
@@ -2220,7 +2623,7 @@ impl Editor {
     /// Used for live preview when navigating theme selection
     pub(super) fn preview_theme(&mut self, theme_name: &str) {
         if !theme_name.is_empty() && theme_name != self.theme.name {
-            if let Some(theme) = self.theme_registry.get_cloned(theme_name) {
+            if let Some(theme) = self.load_theme(theme_name) {
                 self.theme = theme;
                 self.theme.set_terminal_cursor_color();
             }
@@ -2405,7 +2808,12 @@ impl Editor {
 
             // Apply the cursor style to the terminal
             use std::io::stdout;
-            let _ = crossterm::execute!(stdout(), style.to_crossterm_style());
+            let shown = if self.config.editor.reduced_motion {
+                style.steady()
+            } else {
+                style
+            };
+            let _ = crossterm::execute!(stdout(), shown.to_crossterm_style());
 
             // Persist to config file
             self.save_cursor_style_to_config();
@@ -2713,7 +3121,7 @@ impl Editor {
     fn handle_insert_char_editor(&mut self, c: char) -> AnyhowResult<()> {
         // Check if editing is disabled (show_cursors = false)
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return Ok(());
         }
 
@@ -2776,10 +3184,15 @@ impl Editor {
                 | Action::DeleteLine
                 | Action::DedentSelection
                 | Action::ToggleComment
+                | Action::IncrementNumber
+                | Action::DecrementNumber
+                | Action::SwapArgumentNext
+                | Action::SwapArgumentPrevious
+                | Action::AddArgument
         );
 
         if is_editing_action && self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.set_status_message(self.editing_disabled_message());
             return Ok(());
         }
 
@@ -25,7 +25,9 @@ impl Editor {
             || self.active_state().popups.is_visible()
             || self.menu_state.active_menu.is_some()
             || self.settings_state.as_ref().is_some_and(|s| s.visible)
-            || self.calibration_wizard.is_some();
+            || self.calibration_wizard.is_some()
+            || self.cheat_sheet.is_some()
+            || self.buffer_switcher.is_some();
 
         if in_modal {
             return None;
@@ -72,6 +74,19 @@ impl Editor {
             return Some(result);
         }
 
+        // Cheat sheet overlay is next (modal, blocks all other input)
+        if self.cheat_sheet.is_some() {
+            return Some(self.handle_cheat_sheet_input(event));
+        }
+
+        // Buffer switcher is next. Keys it doesn't use close it and are then
+        // handled as usual.
+        if self.buffer_switcher.is_some()
+            && self.handle_buffer_switcher_input(event) == InputResult::Consumed
+        {
+            return Some(InputResult::Consumed);
+        }
+
         // Menu is next
         if self.menu_state.active_menu.is_some() {
             let all_menus: Vec<crate::config::Menu> = self
@@ -0,0 +1,181 @@
+//! Jump list navigation.
+//!
+//! Significant cursor jumps (go to definition, search, go to line, buffer
+//! switches) record the location they start from in the active split's
+//! [`JumpList`]. Jump Back / Jump Forward walk that list, and the Jump List
+//! picker shows every entry with a preview of its line.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::jump_list::{JumpEntry, JumpList};
+use crate::model::event::{BufferId, Event};
+use crate::view::prompt::{Prompt, PromptType};
+
+impl Editor {
+    /// Record the cursor location before a jump in the active split's jump list.
+    ///
+    /// Does nothing while navigating history, so walking the jump list doesn't
+    /// record new jumps.
+    pub(super) fn record_jump(&mut self) {
+        if self.in_navigation {
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let current = JumpEntry::new(buffer_id, state.cursors.primary().position);
+        if let Some(jump_list) = self.active_jump_list_mut() {
+            jump_list.record(current);
+        }
+    }
+
+    /// Go back to the location before the last jump (Ctrl+O in vi mode)
+    pub fn jump_back(&mut self) {
+        let current = self.current_jump_location();
+        match self
+            .active_jump_list_mut()
+            .and_then(|jump_list| jump_list.back(current))
+        {
+            Some(entry) => self.go_to_location(entry.buffer_id, entry.position),
+            None => self.set_status_message(t!("jump.at_oldest").to_string()),
+        }
+    }
+
+    /// Go forward again after jumping back (Ctrl+I in vi mode)
+    pub fn jump_forward(&mut self) {
+        match self
+            .active_jump_list_mut()
+            .and_then(|jump_list| jump_list.forward())
+        {
+            Some(entry) => self.go_to_location(entry.buffer_id, entry.position),
+            None => self.set_status_message(t!("jump.at_newest").to_string()),
+        }
+    }
+
+    /// Open the jump list picker, newest entry first
+    pub(super) fn start_jump_list_prompt(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let Some(view_state) = self.split_view_states.get(&active_split) else {
+            return;
+        };
+        if view_state.jump_list.is_empty() {
+            self.set_status_message(t!("jump.empty").to_string());
+            return;
+        }
+        let entries = view_state.jump_list.entries().to_vec();
+        let current_index = view_state.jump_list.index();
+
+        let mut suggestions = Vec::new();
+        let mut selected = 0;
+        for (index, entry) in entries.iter().enumerate().rev() {
+            let Some((line, preview)) = self.jump_line_preview(entry) else {
+                continue;
+            };
+            let name = self
+                .buffer_metadata
+                .get(&entry.buffer_id)
+                .map(|metadata| metadata.display_name.clone())
+                .unwrap_or_default();
+            if index == current_index {
+                selected = suggestions.len();
+            }
+            suggestions.push(Suggestion {
+                text: format!("{}:{}", name, line + 1),
+                description: Some(preview),
+                value: Some(index.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            });
+        }
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("jump.prompt").to_string(),
+            PromptType::JumpList,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            if !prompt.suggestions.is_empty() {
+                prompt.selected_suggestion = Some(selected);
+            }
+        }
+    }
+
+    /// Handle JumpList prompt confirmation
+    pub(super) fn handle_jump_list_selection(&mut self, input: &str) {
+        let Ok(index) = input.trim().parse::<usize>() else {
+            return;
+        };
+        let current = self.current_jump_location();
+        if let Some(entry) = self
+            .active_jump_list_mut()
+            .and_then(|jump_list| jump_list.select(index, current))
+        {
+            self.go_to_location(entry.buffer_id, entry.position);
+        }
+    }
+
+    /// The line number and trimmed line text of a jump entry, for the picker
+    fn jump_line_preview(&mut self, entry: &JumpEntry) -> Option<(usize, String)> {
+        let state = self.buffers.get_mut(&entry.buffer_id)?;
+        let position = entry.position.min(state.buffer.len());
+        let line = state.buffer.get_line_number(position);
+        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or(state.buffer.len());
+        let text = state.get_text_range(line_start, line_end);
+        Some((line, text.trim().to_string()))
+    }
+
+    fn current_jump_location(&self) -> JumpEntry {
+        let position = self.active_state().cursors.primary().position;
+        JumpEntry::new(self.active_buffer(), position)
+    }
+
+    fn active_jump_list_mut(&mut self) -> Option<&mut JumpList> {
+        let active_split = self.split_manager.active_split();
+        self.split_view_states
+            .get_mut(&active_split)
+            .map(|view_state| &mut view_state.jump_list)
+    }
+
+    /// Move the cursor to a location in a buffer without recording a jump
+    pub(super) fn go_to_location(&mut self, buffer_id: BufferId, position: usize) {
+        if !self.buffers.contains_key(&buffer_id) {
+            return;
+        }
+        self.in_navigation = true;
+        self.set_active_buffer(buffer_id);
+
+        let active_split = self.split_manager.active_split();
+        let state = self.active_state_mut();
+        let cursor_id = state.cursors.primary_id();
+        let old_position = state.cursors.primary().position;
+        let old_anchor = state.cursors.primary().anchor;
+        let old_sticky_column = state.cursors.primary().sticky_column;
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position,
+            new_position: position.min(state.buffer.len()),
+            old_anchor,
+            new_anchor: None,
+            old_sticky_column,
+            new_sticky_column: 0,
+        };
+        state.apply(&event);
+
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                view_state
+                    .viewport
+                    .ensure_visible(&mut state.buffer, state.cursors.primary());
+            }
+        }
+        self.in_navigation = false;
+    }
+}
@@ -0,0 +1,208 @@
+//! Line length warnings
+//!
+//! Characters past `max_line_length` are highlighted while rendering (see
+//! `VisualGuides`). With `line_length_diagnostics` on, overlong lines are also
+//! listed as warnings in the diagnostics panel, next to the language servers'
+//! diagnostics. They're looked for again whenever a buffer changes.
+
+use std::collections::HashSet;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use rust_i18n::t;
+
+use crate::services::plugins::hooks::HookArgs;
+use crate::view::visual_guides::VisualGuides;
+
+use super::Editor;
+
+/// Source shown for overlong line diagnostics
+const DIAGNOSTIC_SOURCE: &str = "line-length";
+
+/// The overlong lines of one buffer, as diagnostics
+#[derive(Debug, Clone)]
+pub(crate) struct LineLengthDiagnostics {
+    /// Buffer version they were found at
+    version: u64,
+    /// Line length limit they were found with
+    limit: usize,
+    /// URI of the buffer's file
+    pub(super) uri: String,
+    pub(super) diagnostics: Vec<Diagnostic>,
+}
+
+impl Editor {
+    /// Turn the line length warnings of the active buffer off, or back on
+    pub fn toggle_line_length_warnings(&mut self) {
+        let guides = &mut self.active_state_mut().guides;
+        if guides.max_line_length.is_none() {
+            self.set_status_message(t!("view.line_length_no_limit").to_string());
+            return;
+        }
+        guides.line_length_warnings = !guides.line_length_warnings;
+
+        let state = if guides.line_length_warnings {
+            t!("view.state_enabled").to_string()
+        } else {
+            t!("view.state_disabled").to_string()
+        };
+        self.set_status_message(t!("view.line_length_state", state = state).to_string());
+    }
+
+    /// Find the overlong lines of buffers that changed since the last call,
+    /// and tell plugins about the ones that differ (called from main loop)
+    pub(super) fn update_line_length_diagnostics(&mut self) {
+        let mut checked = HashSet::new();
+        let mut changed_uris = Vec::new();
+
+        for (buffer_id, state) in &self.buffers {
+            let guides = &state.guides;
+            let Some(limit) = guides
+                .line_length_limit()
+                .filter(|_| guides.line_length_diagnostics)
+            else {
+                continue;
+            };
+            let Some(uri) = self
+                .buffer_metadata
+                .get(buffer_id)
+                .and_then(|m| m.file_uri())
+                .map(|uri| uri.as_str().to_string())
+            else {
+                continue;
+            };
+            // Too slow to scan every edit in large file mode
+            if state.buffer.line_count().is_none() {
+                continue;
+            }
+            checked.insert(*buffer_id);
+
+            let version = state.buffer.revision();
+            if self
+                .line_length_diagnostics
+                .get(buffer_id)
+                .is_some_and(|entry| {
+                    entry.version == version && entry.limit == limit && entry.uri == uri
+                })
+            {
+                continue;
+            }
+            let Some(text) = state.buffer.to_string() else {
+                continue;
+            };
+
+            let diagnostics = overlong_line_diagnostics(&text, guides, state.tab_size, limit);
+            // Most edits don't change which lines are too long
+            let unchanged = self
+                .line_length_diagnostics
+                .get(buffer_id)
+                .is_some_and(|old| old.uri == uri && old.diagnostics == diagnostics);
+            let entry = LineLengthDiagnostics {
+                version,
+                limit,
+                uri: uri.clone(),
+                diagnostics,
+            };
+            if let Some(old) = self.line_length_diagnostics.insert(*buffer_id, entry) {
+                if old.uri != uri {
+                    changed_uris.push(old.uri);
+                }
+            }
+            if !unchanged {
+                changed_uris.push(uri);
+            }
+        }
+
+        // Buffers closed, or no longer checked
+        self.line_length_diagnostics.retain(|buffer_id, entry| {
+            let keep = checked.contains(buffer_id);
+            if !keep && !entry.diagnostics.is_empty() {
+                changed_uris.push(entry.uri.clone());
+            }
+            keep
+        });
+
+        changed_uris.sort();
+        changed_uris.dedup();
+        for uri in changed_uris {
+            let count = self.stored_diagnostics.get(&uri).map_or(0, Vec::len)
+                + self
+                    .line_length_diagnostics
+                    .values()
+                    .filter(|entry| entry.uri == uri)
+                    .map(|entry| entry.diagnostics.len())
+                    .sum::<usize>();
+            self.plugin_manager.run_hook(
+                "diagnostics_updated",
+                HookArgs::DiagnosticsUpdated { uri, count },
+            );
+        }
+    }
+}
+
+/// A warning for each line of `text` longer than `limit` columns
+fn overlong_line_diagnostics(
+    text: &str,
+    guides: &VisualGuides,
+    tab_size: usize,
+    limit: usize,
+) -> Vec<Diagnostic> {
+    text.split_inclusive('\n')
+        .enumerate()
+        .filter_map(|(line_number, line)| {
+            let overlong = guides.overlong(line, tab_size)?;
+            // LSP positions count UTF-16 code units
+            let position = |byte: usize| {
+                Position::new(
+                    line_number as u32,
+                    line[..byte].encode_utf16().count() as u32,
+                )
+            };
+            Some(Diagnostic {
+                range: Range::new(position(overlong.start), position(overlong.end)),
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: None,
+                code_description: None,
+                source: Some(DIAGNOSTIC_SOURCE.to_string()),
+                message: t!(
+                    "diagnostics.line_too_long",
+                    width = overlong.width,
+                    limit = limit
+                )
+                .to_string(),
+                related_information: None,
+                tags: None,
+                data: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlong_line_diagnostics() {
+        let guides = VisualGuides {
+            max_line_length: Some(4),
+            line_length_warnings: true,
+            ..Default::default()
+        };
+        let diagnostics = overlong_line_diagnostics("short\nok\n日本語abc\n", &guides, 4, 4);
+        assert_eq!(diagnostics.len(), 2);
+
+        assert_eq!(
+            diagnostics[0].range,
+            Range::new(Position::new(0, 4), Position::new(0, 5))
+        );
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].source.as_deref(), Some(DIAGNOSTIC_SOURCE));
+
+        // Wide characters take two columns, but one UTF-16 code unit
+        assert_eq!(
+            diagnostics[1].range,
+            Range::new(Position::new(2, 2), Position::new(2, 6))
+        );
+        assert!(diagnostics[1].message.contains('9'));
+    }
+}
@@ -0,0 +1,305 @@
+//! Links in buffers.
+//!
+//! URLs and `path:line` references to existing files in the visible lines are
+//! underlined, and so is any link under the mouse pointer, including bare
+//! paths. Ctrl+Click or Open Link at Cursor opens a link: URLs in the
+//! browser, files in the editor at their `:line:column` if one follows the
+//! path. Ctrl+Click anywhere else goes to the definition of the symbol under
+//! the pointer.
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+use anyhow::Result as AnyhowResult;
+use ratatui::style::{Modifier, Style};
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::link_detection::{find_links, link_at, LinkTarget};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+
+fn link_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("link-hover".to_string())
+}
+
+fn visible_link_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("links".to_string())
+}
+
+fn underline_face() -> OverlayFace {
+    OverlayFace::Style {
+        style: Style::default().add_modifier(Modifier::UNDERLINED),
+    }
+}
+
+/// A link in a buffer, with its path resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BufferLink {
+    Url(String),
+    File {
+        path: PathBuf,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+}
+
+impl Editor {
+    /// Underline the link under the mouse pointer, if any
+    ///
+    /// Returns true if the underlined link changed.
+    pub(super) fn update_hovered_link(&mut self, col: u16, row: u16) -> bool {
+        let link = self
+            .buffer_position_at(col, row)
+            .and_then(|(buffer_id, byte_pos)| {
+                let (range, _) = self.link_at_position(buffer_id, byte_pos)?;
+                Some((buffer_id, range))
+            });
+        if link == self.mouse_state.hovered_link {
+            return false;
+        }
+
+        let ns = link_namespace();
+        if let Some((buffer_id, _)) = self.mouse_state.hovered_link.take() {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+        }
+        if let Some((buffer_id, range)) = &link {
+            if let Some(state) = self.buffers.get_mut(buffer_id) {
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    range.clone(),
+                    underline_face(),
+                    ns,
+                );
+                state.overlays.add(overlay);
+            }
+        }
+        self.mouse_state.hovered_link = link;
+        true
+    }
+
+    /// Underline the URLs and `path:line` references to existing files in
+    /// lines `start_line..=end_line` of a buffer.
+    ///
+    /// Bare paths are too common in ordinary text to underline everywhere;
+    /// they are only underlined under the mouse pointer. Called on every
+    /// render; skipped when neither the buffer nor the visible range changed
+    /// since the last one.
+    pub(super) fn update_link_overlays(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let enabled = self.config.editor.underline_links;
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let ns = visible_link_namespace();
+        if !enabled {
+            if state.links_underlined.take().is_some() {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+            return;
+        }
+
+        let visible_start = state.buffer.line_start_offset(start_line).unwrap_or(0);
+        let visible_end = state
+            .buffer
+            .line_start_offset(end_line + 1)
+            .unwrap_or(state.buffer.len());
+        let underlined = Some((state.buffer.revision(), visible_start..visible_end));
+        if state.links_underlined == underlined {
+            return;
+        }
+        state.links_underlined = underlined;
+
+        let bytes = state.buffer.slice_bytes(visible_start..visible_end);
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        let mut links = Vec::new();
+        let mut line_start = visible_start;
+        for line in text.split_inclusive('\n') {
+            for link in find_links(line) {
+                let range = line_start + link.range.start..line_start + link.range.end;
+                links.push((range, link.target));
+            }
+            line_start += line.len();
+        }
+
+        let ranges: Vec<Range<usize>> = links
+            .into_iter()
+            .filter(|(_, target)| match target {
+                LinkTarget::Url(_) => true,
+                LinkTarget::Path { path, line, .. } => {
+                    line.is_some() && self.resolve_link_path(buffer_id, path).is_some()
+                }
+            })
+            .map(|(range, _)| range)
+            .collect();
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        state.overlays.clear_namespace(&ns, &mut state.marker_list);
+        for range in ranges {
+            let overlay = Overlay::with_namespace(
+                &mut state.marker_list,
+                range,
+                underline_face(),
+                ns.clone(),
+            );
+            state.overlays.add(overlay);
+        }
+    }
+
+    /// Open the link under (or just after) the primary cursor
+    pub fn open_link_at_cursor(&mut self) {
+        let buffer_id = self.active_buffer();
+        let cursor = self.active_state().cursors.primary().position;
+        let link = self
+            .link_at_position(buffer_id, cursor)
+            .or_else(|| self.link_at_position(buffer_id, cursor.checked_sub(1)?));
+        match link {
+            Some((_, link)) => self.open_link(link),
+            None => self.set_status_message(t!("link.no_link").to_string()),
+        }
+    }
+
+    /// Handle Ctrl+Click at a buffer position: open the link there, or go
+    /// to the definition of the symbol there
+    ///
+    /// The cursor has already been moved to `position`.
+    pub(super) fn handle_ctrl_click(
+        &mut self,
+        buffer_id: BufferId,
+        position: usize,
+    ) -> AnyhowResult<()> {
+        match self.link_at_position(buffer_id, position) {
+            Some((_, link)) => {
+                self.open_link(link);
+                Ok(())
+            }
+            None => self.goto_definition(),
+        }
+    }
+
+    /// The buffer and byte under a screen cell of a split's text area
+    fn buffer_position_at(&self, col: u16, row: u16) -> Option<(BufferId, usize)> {
+        let (split_id, buffer_id, content_rect) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, content_rect, _, _, _)| {
+                (*split_id, *buffer_id, *content_rect)
+            })?;
+        let gutter_width = self.buffers.get(&buffer_id)?.margins.left_total_width() as u16;
+        let text_col = col.checked_sub(content_rect.x + gutter_width)?;
+        let mapping = self
+            .cached_layout
+            .view_line_mappings
+            .get(&split_id)?
+            .get((row - content_rect.y) as usize)?;
+        let byte_pos = mapping.source_byte_at_visual_col(text_col as usize)?;
+        Some((buffer_id, byte_pos))
+    }
+
+    /// The link covering a byte of a buffer, with its byte range
+    ///
+    /// Paths only count as links if the file exists.
+    fn link_at_position(
+        &self,
+        buffer_id: BufferId,
+        byte_pos: usize,
+    ) -> Option<(Range<usize>, BufferLink)> {
+        let state = self.buffers.get(&buffer_id)?;
+        let line_number = state.buffer.get_line_number(byte_pos);
+        let line_start = state.buffer.line_start_offset(line_number)?;
+        let bytes = state.buffer.get_line(line_number)?;
+        let text = std::str::from_utf8(&bytes).ok()?;
+        let link = link_at(text, byte_pos.checked_sub(line_start)?)?;
+
+        let target = match link.target {
+            LinkTarget::Url(url) => BufferLink::Url(url),
+            LinkTarget::Path { path, line, column } => BufferLink::File {
+                path: self.resolve_link_path(buffer_id, &path)?,
+                line,
+                column,
+            },
+        };
+        let range = line_start + link.range.start..line_start + link.range.end;
+        Some((range, target))
+    }
+
+    /// Find the file a path refers to: relative paths are tried against the
+    /// buffer's directory, then the working directory
+    fn resolve_link_path(&self, buffer_id: BufferId, path: &str) -> Option<PathBuf> {
+        let path = match path.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()?.join(rest),
+            None => PathBuf::from(path),
+        };
+        let buffer_dir = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            .and_then(|file| file.parent())
+            .map(|dir| dir.to_path_buf());
+        let candidates = if path.is_absolute() {
+            vec![path]
+        } else {
+            buffer_dir
+                .into_iter()
+                .chain(std::iter::once(self.working_dir.clone()))
+                .map(|dir| dir.join(&path))
+                .collect()
+        };
+        candidates
+            .into_iter()
+            .find(|candidate| self.filesystem.is_file(candidate).unwrap_or(false))
+    }
+
+    fn open_link(&mut self, link: BufferLink) {
+        match link {
+            BufferLink::Url(url) => {
+                #[cfg(feature = "runtime")]
+                if let Err(e) = open::that(&url) {
+                    self.set_status_message(
+                        t!("link.failed_to_open", url = &url, error = e.to_string()).to_string(),
+                    );
+                    return;
+                }
+                self.set_status_message(t!("link.opening", url = &url).to_string());
+            }
+            BufferLink::File { path, line, column } => {
+                self.record_jump();
+                let buffer_id = match self.open_file(&path) {
+                    Ok(buffer_id) => buffer_id,
+                    Err(e) => {
+                        self.set_status_message(
+                            t!("file.error_opening", error = e.to_string()).to_string(),
+                        );
+                        return;
+                    }
+                };
+                if let Some(line) = line {
+                    if let Some(state) = self.buffers.get(&buffer_id) {
+                        let position = state
+                            .buffer
+                            .line_col_to_position(line - 1, column.unwrap_or(1) - 1);
+                        self.go_to_location(buffer_id, position);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -19,14 +19,12 @@ impl Editor {
             return;
         };
 
-        let Some(path) = metadata.file_path() else {
+        if metadata.file_path().is_none() {
             self.set_status_message(t!("lsp.buffer_has_no_file").to_string());
             return;
-        };
+        }
 
-        let Some(language) =
-            crate::services::lsp::manager::detect_language(path, &self.config.languages)
-        else {
+        let Some(language) = metadata.language(&self.config.languages) else {
             self.set_status_message(t!("lsp.no_server_configured").to_string());
             return;
         };
@@ -58,9 +56,7 @@ impl Editor {
             .iter()
             .filter_map(|(buf_id, meta)| {
                 let path = meta.file_path()?;
-                let detected =
-                    crate::services::lsp::manager::detect_language(path, &self.config.languages)?;
-                if detected == language {
+                if meta.language(&self.config.languages)? == language {
                     Some((*buf_id, path.clone()))
                 } else {
                     None
@@ -84,18 +80,12 @@ impl Editor {
                 continue;
             };
 
-            let Some(lang_id) =
-                crate::services::lsp::manager::detect_language(&buf_path, &self.config.languages)
-            else {
-                continue;
-            };
-
             if let Some(lsp) = self.lsp.as_mut() {
                 // Respect auto_start setting for this user action
                 use crate::services::lsp::manager::LspSpawnResult;
-                if lsp.try_spawn(&lang_id) == LspSpawnResult::Spawned {
-                    if let Some(handle) = lsp.get_handle_mut(&lang_id) {
-                        let _ = handle.did_open(uri, content, lang_id);
+                if lsp.try_spawn(language) == LspSpawnResult::Spawned {
+                    if let Some(handle) = lsp.get_handle_mut(language) {
+                        let _ = handle.did_open(uri, content, language.to_string());
                     }
                 }
             }
@@ -0,0 +1,252 @@
+//! Updating references when a file is renamed in the file explorer
+//!
+//! Before renaming, running language servers are asked for the edits that go
+//! with it (`workspace/willRenameFiles`), such as updated import paths. If
+//! any come back they're previewed in a popup: the edits can be applied
+//! before the rename, skipped, or the rename cancelled. Servers are told
+//! about the rename afterwards (`workspace/didRenameFiles`).
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use super::{uri_to_path, Editor};
+use crate::model::event::{PopupContentData, PopupData, PopupListItemData, PopupPositionData};
+use crate::services::lsp::async_handler::LspHandle;
+
+/// A rename waiting for language servers' edits, or for the user to decide
+/// what to do with them
+pub(super) struct PendingFileRename {
+    original_path: PathBuf,
+    original_name: String,
+    new_path: PathBuf,
+    new_name: String,
+    /// Requests not answered yet
+    waiting: HashSet<u64>,
+    edits: Vec<lsp_types::WorkspaceEdit>,
+}
+
+impl PendingFileRename {
+    /// Whether every server has answered and the edits are being previewed
+    pub(super) fn is_previewed(&self) -> bool {
+        self.waiting.is_empty()
+    }
+}
+
+impl Editor {
+    /// Ask running language servers for the edits that go with renaming
+    /// `original_path`. Returns false if none could be asked, in which case
+    /// the caller renames right away.
+    pub(super) fn request_will_rename_files(
+        &mut self,
+        original_path: &Path,
+        original_name: &str,
+        new_path: &Path,
+        new_name: &str,
+    ) -> bool {
+        if self.pending_file_rename.is_some() {
+            return false;
+        }
+        let is_dir = self.filesystem.is_dir(original_path).unwrap_or(false);
+
+        let mut request_id = self.next_lsp_request_id;
+        let mut waiting = HashSet::new();
+        for handle in self.running_lsp_handles() {
+            if handle
+                .will_rename_files(
+                    request_id,
+                    original_path.to_path_buf(),
+                    new_path.to_path_buf(),
+                    is_dir,
+                )
+                .is_ok()
+            {
+                waiting.insert(request_id);
+                request_id += 1;
+            }
+        }
+        self.next_lsp_request_id = request_id;
+        if waiting.is_empty() {
+            return false;
+        }
+
+        self.pending_file_rename = Some(PendingFileRename {
+            original_path: original_path.to_path_buf(),
+            original_name: original_name.to_string(),
+            new_path: new_path.to_path_buf(),
+            new_name: new_name.to_string(),
+            waiting,
+            edits: Vec::new(),
+        });
+        self.set_status_message(t!("explorer.rename_checking_references").to_string());
+        true
+    }
+
+    /// Handle a language server's edits for a pending rename. Once every
+    /// server has answered, the edits are previewed, or the file is renamed
+    /// if there are none.
+    pub(super) fn handle_will_rename_files_response(
+        &mut self,
+        request_id: u64,
+        edit: Option<lsp_types::WorkspaceEdit>,
+    ) {
+        let Some(pending) = self.pending_file_rename.as_mut() else {
+            return;
+        };
+        if !pending.waiting.remove(&request_id) {
+            return;
+        }
+        pending.edits.extend(edit);
+        if !pending.waiting.is_empty() {
+            return;
+        }
+
+        let changes: Vec<(PathBuf, usize)> = pending.edits.iter().flat_map(edited_files).collect();
+        if changes.is_empty() {
+            if let Some(pending) = self.pending_file_rename.take() {
+                self.finish_file_rename(pending, false);
+            }
+            return;
+        }
+        self.show_file_rename_preview(&changes);
+    }
+
+    /// Show the files the edits of a pending rename change, and what to do
+    fn show_file_rename_preview(&mut self, changes: &[(PathBuf, usize)]) {
+        let Some(pending) = &self.pending_file_rename else {
+            return;
+        };
+        let edit_count: usize = changes.iter().map(|(_, count)| count).sum();
+        let files = changes
+            .iter()
+            .map(|(path, count)| {
+                let path = path.strip_prefix(&self.working_dir).unwrap_or(path);
+                format!("{} ({})", path.display(), count)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let item = |text: String, data: &str| PopupListItemData {
+            text,
+            detail: None,
+            icon: None,
+            data: Some(data.to_string()),
+        };
+        let popup = PopupData {
+            title: Some(
+                t!(
+                    "explorer.rename_update_title",
+                    old = &pending.original_name,
+                    new = &pending.new_name
+                )
+                .to_string(),
+            ),
+            description: Some(
+                t!(
+                    "explorer.rename_update_description",
+                    count = edit_count,
+                    files = files
+                )
+                .to_string(),
+            ),
+            transient: false,
+            content: PopupContentData::List {
+                items: vec![
+                    item(t!("explorer.rename_update_apply").to_string(), "apply"),
+                    item(t!("explorer.rename_update_skip").to_string(), "skip"),
+                    item(t!("explorer.rename_update_cancel").to_string(), "cancel"),
+                ],
+                selected: 0,
+            },
+            position: PopupPositionData::Centered,
+            width: 60,
+            max_height: 16,
+            bordered: true,
+        };
+        self.show_popup(popup);
+    }
+
+    /// Handle the choice made in the rename preview popup
+    pub(super) fn handle_file_rename_choice(&mut self, pending: PendingFileRename, choice: &str) {
+        match choice {
+            "apply" => self.finish_file_rename(pending, true),
+            "skip" => self.finish_file_rename(pending, false),
+            _ => self.set_status_message(t!("explorer.rename_cancelled").to_string()),
+        }
+    }
+
+    /// Rename the file, after applying the edits that go with it if asked to
+    fn finish_file_rename(&mut self, mut pending: PendingFileRename, apply_edits: bool) {
+        if apply_edits {
+            // The edits refer to files by their old paths, so they're made
+            // first; renaming then moves the open buffers along
+            for edit in std::mem::take(&mut pending.edits) {
+                if let Err(e) = self.apply_workspace_edit(edit) {
+                    tracing::warn!("Failed to apply edits for rename: {}", e);
+                }
+            }
+        }
+        self.rename_explorer_entry(
+            pending.original_path,
+            pending.original_name,
+            pending.new_path,
+            pending.new_name,
+            false,
+        );
+    }
+
+    /// Tell running language servers that a file was renamed
+    pub(super) fn notify_did_rename_files(&self, old_path: &Path, new_path: &Path) {
+        let is_dir = self.filesystem.is_dir(new_path).unwrap_or(false);
+        for handle in self.running_lsp_handles() {
+            if let Err(e) =
+                handle.did_rename_files(old_path.to_path_buf(), new_path.to_path_buf(), is_dir)
+            {
+                tracing::warn!("Failed to send didRenameFiles: {}", e);
+            }
+        }
+    }
+
+    fn running_lsp_handles(&self) -> Vec<&LspHandle> {
+        let Some(lsp) = &self.lsp else {
+            return Vec::new();
+        };
+        lsp.running_servers()
+            .iter()
+            .filter_map(|language| lsp.get_handle(language))
+            .collect()
+    }
+}
+
+/// The files a workspace edit changes, and how many edits each gets
+fn edited_files(edit: &lsp_types::WorkspaceEdit) -> Vec<(PathBuf, usize)> {
+    use lsp_types::{DocumentChangeOperation, DocumentChanges};
+
+    let mut files = Vec::new();
+    if let Some(changes) = &edit.changes {
+        files.extend(
+            changes
+                .iter()
+                .filter_map(|(uri, edits)| Some((uri_to_path(uri).ok()?, edits.len()))),
+        );
+    }
+    let document_edits: Vec<&lsp_types::TextDocumentEdit> = match &edit.document_changes {
+        Some(DocumentChanges::Edits(edits)) => edits.iter().collect(),
+        Some(DocumentChanges::Operations(operations)) => operations
+            .iter()
+            .filter_map(|operation| match operation {
+                DocumentChangeOperation::Edit(edit) => Some(edit),
+                DocumentChangeOperation::Op(_) => None,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    files.extend(
+        document_edits.into_iter().filter_map(|edit| {
+            Some((uri_to_path(&edit.text_document.uri).ok()?, edit.edits.len()))
+        }),
+    );
+    files.retain(|(_, count)| *count > 0);
+    files
+}
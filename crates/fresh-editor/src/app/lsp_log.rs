@@ -0,0 +1,204 @@
+//! The LSP Log buffer: for each language server, the messages it sent
+//! (`window/logMessage` and `window/showMessage`), the editor's record of it
+//! starting, crashing and restarting, and the end of what it wrote to stderr.
+//! It's updated while it's shown, and follows new output when the cursor is
+//! at its end.
+
+use std::collections::BTreeSet;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::types::LspMessageEntry;
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::{LspMessageType, LspServerStatus};
+use crate::services::log_dirs;
+
+/// Mode name for the LSP Log buffer
+const LSP_LOG_MODE: &str = "lsp-log";
+
+/// How often the LSP Log buffer is updated while it's shown
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How much of the end of a server's stderr log is shown
+const STDERR_TAIL_BYTES: u64 = 16 * 1024;
+
+impl Editor {
+    /// Show the LSP Log buffer
+    pub fn show_lsp_log(&mut self) -> BufferId {
+        let buffer_id = self.lsp_log_buffer().unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                t!("lsp_log.title").to_string(),
+                LSP_LOG_MODE.to_string(),
+                true,
+            )
+        });
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+            // Start by following the output
+            state.cursors.primary_mut().position = state.buffer.len();
+        }
+        self.set_active_buffer(buffer_id);
+        self.write_lsp_log(buffer_id);
+        buffer_id
+    }
+
+    /// Update the LSP Log buffer if it's shown and its text changed since it
+    /// was last updated. Returns whether it was.
+    pub(super) fn update_lsp_log(&mut self) -> bool {
+        let Some(buffer_id) = self.lsp_log_buffer() else {
+            return false;
+        };
+        if self.split_manager.splits_for_buffer(buffer_id).is_empty() {
+            return false;
+        }
+        let now = self.time_source.now();
+        if self
+            .lsp_log_updated_at
+            .is_some_and(|updated_at| now.duration_since(updated_at) < UPDATE_INTERVAL)
+        {
+            return false;
+        }
+        self.write_lsp_log(buffer_id)
+    }
+
+    fn lsp_log_buffer(&self) -> Option<BufferId> {
+        self.buffer_metadata.iter().find_map(|(id, metadata)| {
+            (metadata.virtual_mode() == Some(LSP_LOG_MODE)).then_some(*id)
+        })
+    }
+
+    /// Write the log to its buffer if it changed, keeping the cursor at the
+    /// end if it was there. Returns whether the text changed.
+    fn write_lsp_log(&mut self, buffer_id: BufferId) -> bool {
+        self.lsp_log_updated_at = Some(self.time_source.now());
+        let text = self.lsp_log_text();
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        if state.buffer.to_string().as_deref() == Some(text.as_str()) {
+            return false;
+        }
+        let following = state.cursors.primary().position >= state.buffer.len();
+
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(text)])
+        {
+            tracing::warn!("Failed to update the LSP log: {}", e);
+            return false;
+        }
+        if following {
+            if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                state.cursors.primary_mut().position = state.buffer.len();
+            }
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+        true
+    }
+
+    fn lsp_log_text(&self) -> String {
+        let mut text = format!("{}\n", t!("lsp_log.hint"));
+
+        let languages: BTreeSet<&str> = self
+            .lsp_server_statuses
+            .keys()
+            .map(String::as_str)
+            .chain(
+                self.lsp_log_messages
+                    .iter()
+                    .map(|entry| entry.language.as_str()),
+            )
+            .collect();
+        if languages.is_empty() {
+            text.push_str(&format!("\n{}\n", t!("lsp_log.none")));
+            return text;
+        }
+
+        for language in languages {
+            let status = match self.lsp_server_statuses.get(language) {
+                Some(status) => self.lsp_status_label(language, *status),
+                None => "-",
+            };
+            text.push_str(&format!("\n== {} ({}) ==\n", language, status));
+
+            text.push_str(&format!("-- {} --\n", t!("lsp_log.messages")));
+            let mut any = false;
+            for entry in self
+                .lsp_log_messages
+                .iter()
+                .filter(|entry| entry.language == language)
+            {
+                text.push_str(&format_entry(entry));
+                any = true;
+            }
+            if !any {
+                text.push_str(&format!("{}\n", t!("lsp_log.no_output")));
+            }
+
+            let stderr_path = log_dirs::lsp_log_path(language);
+            text.push_str(&format!(
+                "-- {} --\n",
+                t!("lsp_log.stderr", path = stderr_path.display().to_string())
+            ));
+            match read_tail(&stderr_path) {
+                Some(stderr) if !stderr.is_empty() => {
+                    text.push_str(&stderr);
+                    if !stderr.ends_with('\n') {
+                        text.push('\n');
+                    }
+                }
+                _ => text.push_str(&format!("{}\n", t!("lsp_log.no_output"))),
+            }
+        }
+        text
+    }
+
+    /// The status of a language server as the status bar shows it
+    pub(super) fn lsp_status_label(&self, language: &str, status: LspServerStatus) -> &'static str {
+        match status {
+            LspServerStatus::Starting => "starting",
+            LspServerStatus::Initializing => "initializing",
+            LspServerStatus::Running => "ready",
+            LspServerStatus::Error => match self.lsp.as_ref() {
+                Some(lsp) if lsp.has_pending_restart(language) => "restarting",
+                Some(lsp) if lsp.is_in_cooldown(language) => "crashed",
+                _ => "error",
+            },
+            LspServerStatus::Shutdown => "shutdown",
+        }
+    }
+}
+
+fn format_entry(entry: &LspMessageEntry) -> String {
+    let level = match entry.message_type {
+        LspMessageType::Error => "error",
+        LspMessageType::Warning => "warn",
+        LspMessageType::Info => "info",
+        LspMessageType::Log => "log",
+    };
+    format!("[{}] {}\n", level, entry.message.trim_end())
+}
+
+/// The end of a log file, starting at a line boundary
+fn read_tail(path: &Path) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(STDERR_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    if start == 0 {
+        return Some(text.into_owned());
+    }
+    // Drop the line cut off by the start of the tail
+    Some(
+        text.split_once('\n')
+            .map_or(String::new(), |(_, rest)| rest.to_string()),
+    )
+}
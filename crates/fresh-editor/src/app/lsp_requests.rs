@@ -19,15 +19,24 @@ use lsp_types::TextDocumentContentChangeEvent;
 
 use crate::model::event::{BufferId, Event};
 use crate::primitives::word_navigation::{find_word_end, find_word_start};
-use crate::services::lsp::manager::detect_language;
+use crate::services::profiler::LspRequestKind;
 use crate::view::prompt::{Prompt, PromptType};
+use crate::view::virtual_text::VirtualTextNamespace;
 
+use super::edit_transaction::EditTransaction;
+use super::types::InFlightLspRequest;
 use super::{uri_to_path, Editor, SemanticTokenRangeRequest};
 
 const SEMANTIC_TOKENS_FULL_DEBOUNCE_MS: u64 = 500;
 const SEMANTIC_TOKENS_RANGE_DEBOUNCE_MS: u64 = 50;
 const SEMANTIC_TOKENS_RANGE_PADDING_LINES: usize = 10;
 
+/// Namespace of the inlay hint virtual texts, so that replacing them leaves
+/// other virtual text (color swatches, plugins) alone
+pub(super) fn inlay_hint_namespace() -> VirtualTextNamespace {
+    VirtualTextNamespace::from_string("inlay-hints".to_string())
+}
+
 impl Editor {
     /// Handle LSP completion response
     pub(crate) fn handle_completion_response(
@@ -45,11 +54,18 @@ impl Editor {
         }
 
         self.pending_completion_request = None;
+        self.finish_lsp_request(request_id);
         self.lsp_status.clear();
 
+        self.show_completion_popup(items);
+        Ok(())
+    }
+
+    /// Show the completion items matching the word being typed, if any
+    pub(super) fn show_completion_popup(&mut self, items: Vec<lsp_types::CompletionItem>) {
         if items.is_empty() {
             tracing::debug!("No completion items received");
-            return Ok(());
+            return;
         }
 
         // Get the partial word at cursor to filter completions
@@ -89,7 +105,7 @@ impl Editor {
 
         if filtered_items.is_empty() {
             tracing::debug!("No completion items match prefix '{}'", prefix);
-            return Ok(());
+            return;
         }
 
         // Convert CompletionItem to PopupListItem
@@ -166,8 +182,6 @@ impl Editor {
             "Showing completion popup with {} items",
             self.completion_items.as_ref().map_or(0, |i| i.len())
         );
-
-        Ok(())
     }
 
     /// Handle LSP go-to-definition response
@@ -186,6 +200,7 @@ impl Editor {
         }
 
         self.pending_goto_definition_request = None;
+        self.finish_lsp_request(request_id);
 
         if locations.is_empty() {
             self.status_message = Some(t!("lsp.no_definition").to_string());
@@ -197,6 +212,8 @@ impl Editor {
 
         // Convert URI to file path
         if let Ok(path) = uri_to_path(&location.uri) {
+            self.record_jump();
+
             // Open the file
             let buffer_id = self.open_file(&path)?;
 
@@ -263,48 +280,92 @@ impl Editor {
 
     /// Check if there are any pending LSP requests
     pub fn has_pending_lsp_requests(&self) -> bool {
-        self.pending_completion_request.is_some() || self.pending_goto_definition_request.is_some()
+        self.pending_completion_request.is_some()
+            || self.pending_goto_definition_request.is_some()
+            || self.pending_hover_request.is_some()
+            || self.pending_references_request.is_some()
     }
 
     /// Cancel any pending LSP requests
     /// This should be called when the user performs an action that would make
     /// the pending request's results stale (e.g., cursor movement, text editing)
     pub(crate) fn cancel_pending_lsp_requests(&mut self) {
-        if let Some(request_id) = self.pending_completion_request.take() {
-            tracing::debug!("Canceling pending LSP completion request {}", request_id);
-            // Send cancellation to the LSP server
-            self.send_lsp_cancel_request(request_id);
+        let pending = [
+            self.pending_completion_request.take(),
+            self.pending_goto_definition_request.take(),
+            self.pending_hover_request.take(),
+            self.pending_references_request.take(),
+        ];
+        for request_id in pending.into_iter().flatten() {
+            self.cancel_lsp_request(request_id);
             self.lsp_status.clear();
         }
-        if let Some(request_id) = self.pending_goto_definition_request.take() {
-            tracing::debug!(
-                "Canceling pending LSP goto-definition request {}",
-                request_id
-            );
-            // Send cancellation to the LSP server
-            self.send_lsp_cancel_request(request_id);
-            self.lsp_status.clear();
+    }
+
+    /// The pending request of a kind
+    fn pending_lsp_request(&mut self, kind: LspRequestKind) -> &mut Option<u64> {
+        match kind {
+            LspRequestKind::Completion => &mut self.pending_completion_request,
+            LspRequestKind::Hover => &mut self.pending_hover_request,
+            LspRequestKind::Definition => &mut self.pending_goto_definition_request,
+            LspRequestKind::References => &mut self.pending_references_request,
         }
     }
 
-    /// Send a cancel request to the LSP server for a specific request ID
-    fn send_lsp_cancel_request(&mut self, request_id: u64) {
-        // Get the current file path to determine language
-        let metadata = self.buffer_metadata.get(&self.active_buffer());
-        let file_path = metadata.and_then(|meta| meta.file_path());
-
-        if let Some(path) = file_path {
-            if let Some(language) = detect_language(path, &self.config.languages) {
-                if let Some(lsp) = self.lsp.as_mut() {
-                    // Only send cancel if LSP is already running (no need to spawn just to cancel)
-                    if let Some(handle) = lsp.get_handle_mut(&language) {
-                        if let Err(e) = handle.cancel_request(request_id) {
-                            tracing::warn!("Failed to send LSP cancel request: {}", e);
-                        } else {
-                            tracing::debug!("Sent $/cancelRequest for request_id={}", request_id);
-                        }
-                    }
-                }
+    /// Make a request just sent for a buffer the pending one of its kind,
+    /// cancelling the one it replaces
+    fn start_lsp_request(&mut self, buffer_id: BufferId, request_id: u64, kind: LspRequestKind) {
+        if let Some(replaced) = self.pending_lsp_request(kind).replace(request_id) {
+            tracing::debug!("LSP {:?} request {} replaced", kind, replaced);
+            self.cancel_lsp_request(replaced);
+        }
+        let Some(language) = self
+            .buffer_metadata
+            .get(&buffer_id)
+            .and_then(|meta| meta.language(&self.config.languages))
+        else {
+            return;
+        };
+        self.lsp_requests_in_flight.insert(
+            request_id,
+            InFlightLspRequest {
+                kind,
+                language,
+                sent_at: self.time_source.now(),
+            },
+        );
+    }
+
+    /// Record the response to a request for the Profiler
+    fn finish_lsp_request(&mut self, request_id: u64) {
+        if let Some(request) = self.lsp_requests_in_flight.remove(&request_id) {
+            let elapsed = self.time_source.now().duration_since(request.sent_at);
+            self.profiler.record_lsp_response(request.kind, elapsed);
+        }
+    }
+
+    /// Send $/cancelRequest for a request to the server it was sent to
+    pub(crate) fn cancel_lsp_request(&mut self, request_id: u64) {
+        let Some(request) = self.lsp_requests_in_flight.remove(&request_id) else {
+            return;
+        };
+        self.profiler.record_lsp_cancelled(request.kind);
+        tracing::debug!(
+            "Canceling pending LSP {:?} request {}",
+            request.kind,
+            request_id
+        );
+
+        // Only send cancel if LSP is still running (no need to spawn just to cancel)
+        if let Some(handle) = self
+            .lsp
+            .as_mut()
+            .and_then(|lsp| lsp.get_handle_mut(&request.language))
+        {
+            if let Err(e) = handle.cancel_request(request_id) {
+                tracing::warn!("Failed to send LSP cancel request: {}", e);
+            } else {
+                tracing::debug!("Sent $/cancelRequest for request_id={}", request_id);
             }
         }
     }
@@ -334,7 +395,7 @@ impl Editor {
             }
             let uri = metadata.file_uri()?.clone();
             let path = metadata.file_path()?.to_path_buf();
-            let language = detect_language(&path, &self.config.languages)?;
+            let language = metadata.language(&self.config.languages)?;
             (uri, path, language)
         };
 
@@ -384,8 +445,9 @@ impl Editor {
         Some(f(handle, &uri, &language))
     }
 
-    /// Request LSP completion at current cursor position
-    pub(crate) fn request_completion(&mut self) -> AnyhowResult<()> {
+    /// Request LSP completion at current cursor position. Returns whether a
+    /// language server was asked.
+    pub(crate) fn request_completion(&mut self) -> AnyhowResult<bool> {
         // Get the current buffer and cursor position
         let state = self.active_state();
         let cursor_pos = state.cursors.primary().position;
@@ -414,11 +476,11 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_completion_request = Some(request_id);
+            self.start_lsp_request(buffer_id, request_id, LspRequestKind::Completion);
             self.lsp_status = "LSP: completion...".to_string();
         }
 
-        Ok(())
+        Ok(sent)
     }
 
     /// Check if the inserted character should trigger completion
@@ -431,13 +493,15 @@ impl Editor {
     /// This provides VS Code-like behavior where suggestions appear while typing,
     /// with debouncing to avoid spamming the LSP server.
     pub(crate) fn maybe_trigger_completion(&mut self, c: char) {
-        // Get the active buffer's file path and detect its language
-        let path = match self.active_state().buffer.file_path() {
-            Some(p) => p,
-            None => return, // No path, no language detection
-        };
-
-        let language = match detect_language(path, &self.config.languages) {
+        // Get the active buffer's language
+        if self.active_state().buffer.file_path().is_none() {
+            return; // No path, no language server
+        }
+        let language = match self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|meta| meta.language(&self.config.languages))
+        {
             Some(lang) => lang,
             None => return, // Unknown language
         };
@@ -485,8 +549,9 @@ impl Editor {
         }
     }
 
-    /// Request LSP go-to-definition at current cursor position
-    pub(crate) fn request_goto_definition(&mut self) -> AnyhowResult<()> {
+    /// Request LSP go-to-definition at current cursor position. Returns
+    /// whether a language server was asked.
+    pub(crate) fn request_goto_definition(&mut self) -> AnyhowResult<bool> {
         // Get the current buffer and cursor position
         let state = self.active_state();
         let cursor_pos = state.cursors.primary().position;
@@ -515,10 +580,10 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_goto_definition_request = Some(request_id);
+            self.start_lsp_request(buffer_id, request_id, LspRequestKind::Definition);
         }
 
-        Ok(())
+        Ok(sent)
     }
 
     /// Request LSP hover documentation at current cursor position
@@ -563,7 +628,7 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_hover_request = Some(request_id);
+            self.start_lsp_request(buffer_id, request_id, LspRequestKind::Hover);
             self.lsp_status = "LSP: hover...".to_string();
         }
 
@@ -612,7 +677,7 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_hover_request = Some(request_id);
+            self.start_lsp_request(buffer_id, request_id, LspRequestKind::Hover);
             self.lsp_status = "LSP: hover...".to_string();
         }
 
@@ -634,6 +699,7 @@ impl Editor {
         }
 
         self.pending_hover_request = None;
+        self.finish_lsp_request(request_id);
         self.lsp_status.clear();
 
         if contents.is_empty() {
@@ -764,7 +830,10 @@ impl Editor {
         use ratatui::style::{Color, Style};
 
         // Clear existing inlay hints
-        state.virtual_texts.clear(&mut state.marker_list);
+        let ns = inlay_hint_namespace();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &ns);
 
         if hints.is_empty() {
             return;
@@ -810,12 +879,13 @@ impl Editor {
             // Use the hint text as-is - spacing is handled during rendering
             let display_text = text;
 
-            state.virtual_texts.add(
+            state.virtual_texts.add_inline(
                 &mut state.marker_list,
                 byte_offset,
                 display_text,
                 hint_style,
                 position,
+                ns.clone(),
                 0, // Default priority
             );
         }
@@ -914,7 +984,7 @@ impl Editor {
 
         if sent {
             self.next_lsp_request_id += 1;
-            self.pending_references_request = Some(request_id);
+            self.start_lsp_request(buffer_id, request_id, LspRequestKind::References);
             self.pending_references_symbol = symbol;
             self.lsp_status = "LSP: finding references...".to_string();
         }
@@ -1143,6 +1213,11 @@ impl Editor {
         request_id: u64,
         actions: Vec<lsp_types::CodeActionOrCommand>,
     ) {
+        // Extract Variable/Function asks for code actions too
+        if self.handle_extract_code_actions(request_id, &actions) {
+            return;
+        }
+
         // Check if this response is for the current pending request
         if self.pending_code_actions_request != Some(request_id) {
             tracing::debug!("Ignoring stale code actions response: {}", request_id);
@@ -1217,6 +1292,7 @@ impl Editor {
         }
 
         self.pending_references_request = None;
+        self.finish_lsp_request(request_id);
         self.lsp_status.clear();
 
         if locations.is_empty() {
@@ -1269,11 +1345,13 @@ impl Editor {
     }
 
     /// Apply LSP text edits to a buffer and return the number of changes made.
-    /// Edits are sorted in reverse order and applied as a batch.
+    /// Edits are sorted in reverse order and applied as a batch, undone as
+    /// one step with the given description.
     pub(crate) fn apply_lsp_text_edits(
         &mut self,
         buffer_id: BufferId,
         mut edits: Vec<lsp_types::TextEdit>,
+        description: String,
     ) -> AnyhowResult<usize> {
         if edits.is_empty() {
             return Ok(0);
@@ -1356,16 +1434,92 @@ impl Editor {
 
         // Apply all rename changes using bulk edit for O(n) performance
         if !batch_events.is_empty() {
-            self.apply_events_to_buffer_as_bulk_edit(
-                buffer_id,
-                batch_events,
-                "LSP Rename".to_string(),
-            )?;
+            self.apply_events_to_buffer_as_bulk_edit(buffer_id, batch_events, description)?;
         }
 
         Ok(changes)
     }
 
+    /// Apply a workspace edit, opening the files it changes
+    ///
+    /// The edits are applied all or none. Returns the number of text edits
+    /// applied.
+    pub(crate) fn apply_workspace_edit(
+        &mut self,
+        workspace_edit: lsp_types::WorkspaceEdit,
+    ) -> AnyhowResult<usize> {
+        let mut transaction = EditTransaction::new("LSP Rename");
+
+        // Handle changes (map of URI -> Vec<TextEdit>)
+        if let Some(changes) = workspace_edit.changes {
+            for (uri, edits) in changes {
+                if let Ok(path) = uri_to_path(&uri) {
+                    transaction.edit(path, edits);
+                }
+            }
+        }
+
+        // Handle document_changes (TextDocumentEdit[])
+        // This is what rust-analyzer sends instead of changes
+        if let Some(document_changes) = workspace_edit.document_changes {
+            use lsp_types::DocumentChanges;
+
+            let text_edits = match document_changes {
+                DocumentChanges::Edits(edits) => edits,
+                DocumentChanges::Operations(ops) => {
+                    // Extract TextDocumentEdit from operations
+                    ops.into_iter()
+                        .filter_map(|op| {
+                            if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
+                                Some(edit)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                }
+            };
+
+            for text_doc_edit in text_edits {
+                let uri = text_doc_edit.text_document.uri;
+
+                if let Ok(path) = uri_to_path(&uri) {
+                    // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
+                    let edits: Vec<lsp_types::TextEdit> = text_doc_edit
+                        .edits
+                        .into_iter()
+                        .map(|one_of| match one_of {
+                            lsp_types::OneOf::Left(text_edit) => text_edit,
+                            lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+                        })
+                        .collect();
+
+                    // Log the edits for debugging
+                    tracing::info!(
+                        "Applying {} edits from rust-analyzer for {:?}:",
+                        edits.len(),
+                        path
+                    );
+                    for (i, edit) in edits.iter().enumerate() {
+                        tracing::info!(
+                            "  Edit {}: line {}:{}-{}:{} -> {:?}",
+                            i,
+                            edit.range.start.line,
+                            edit.range.start.character,
+                            edit.range.end.line,
+                            edit.range.end.character,
+                            edit.new_text
+                        );
+                    }
+
+                    transaction.edit(path, edits);
+                }
+            }
+        }
+
+        self.apply_edit_transaction(transaction)
+    }
+
     /// Handle rename response from LSP
     pub fn handle_rename_response(
         &mut self,
@@ -1387,79 +1541,7 @@ impl Editor {
                     })
                 );
 
-                // Apply the workspace edit
-                let mut total_changes = 0;
-
-                // Handle changes (map of URI -> Vec<TextEdit>)
-                if let Some(changes) = workspace_edit.changes {
-                    for (uri, edits) in changes {
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
-
-                // Handle document_changes (TextDocumentEdit[])
-                // This is what rust-analyzer sends instead of changes
-                if let Some(document_changes) = workspace_edit.document_changes {
-                    use lsp_types::DocumentChanges;
-
-                    let text_edits = match document_changes {
-                        DocumentChanges::Edits(edits) => edits,
-                        DocumentChanges::Operations(ops) => {
-                            // Extract TextDocumentEdit from operations
-                            ops.into_iter()
-                                .filter_map(|op| {
-                                    if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
-                                        Some(edit)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        }
-                    };
-
-                    for text_doc_edit in text_edits {
-                        let uri = text_doc_edit.text_document.uri;
-
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-
-                            // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
-                            let edits: Vec<lsp_types::TextEdit> = text_doc_edit
-                                .edits
-                                .into_iter()
-                                .map(|one_of| match one_of {
-                                    lsp_types::OneOf::Left(text_edit) => text_edit,
-                                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
-                                })
-                                .collect();
-
-                            // Log the edits for debugging
-                            tracing::info!(
-                                "Applying {} edits from rust-analyzer for {:?}:",
-                                edits.len(),
-                                path
-                            );
-                            for (i, edit) in edits.iter().enumerate() {
-                                tracing::info!(
-                                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
-                                    i,
-                                    edit.range.start.line,
-                                    edit.range.start.character,
-                                    edit.range.end.line,
-                                    edit.range.end.character,
-                                    edit.new_text
-                                );
-                            }
-
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
-
+                let total_changes = self.apply_workspace_edit(workspace_edit)?;
                 self.status_message = Some(t!("lsp.renamed", count = total_changes).to_string());
             }
             Err(error) => {
@@ -1657,21 +1739,12 @@ impl Editor {
             }
         };
 
-        // Get the file path for language detection
-        let path = match metadata.file_path() {
-            Some(p) => p,
-            None => {
-                tracing::debug!("send_lsp_changes_for_buffer: no file path for buffer");
-                return;
-            }
-        };
-
-        let language = match detect_language(path, &self.config.languages) {
+        let language = match metadata.language(&self.config.languages) {
             Some(l) => l,
             None => {
                 tracing::debug!(
-                    "send_lsp_changes_for_buffer: no language detected for {:?}",
-                    path
+                    "send_lsp_changes_for_buffer: no language detected for {}",
+                    uri.as_str()
                 );
                 return;
             }
@@ -1750,11 +1823,15 @@ impl Editor {
         }
 
         // Now send didChange
+        let revision = self
+            .buffers
+            .get(&buffer_id)
+            .map_or(0, |state| state.buffer.revision());
         let Some(lsp) = self.lsp.as_mut() else { return };
         let Some(client) = lsp.get_handle_mut(&language) else {
             return;
         };
-        if let Err(e) = client.did_change(uri, changes) {
+        if let Err(e) = client.did_change(uri, changes, revision) {
             tracing::warn!("Failed to send didChange to LSP: {}", e);
         } else {
             tracing::trace!("Successfully sent batched didChange to LSP");
@@ -1886,7 +1963,7 @@ impl Editor {
 
     /// Request inlay hints for the active buffer (if enabled and LSP available)
     pub(crate) fn request_inlay_hints_for_active_buffer(&mut self) {
-        if !self.config.editor.enable_inlay_hints {
+        if !self.inlay_hints_shown() {
             return;
         }
 
@@ -1944,10 +2021,7 @@ impl Editor {
         let Some(uri) = metadata.file_uri().cloned() else {
             return;
         };
-        let Some(path) = metadata.file_path() else {
-            return;
-        };
-        let Some(language) = detect_language(path, &self.config.languages) else {
+        let Some(language) = metadata.language(&self.config.languages) else {
             return;
         };
 
@@ -1971,7 +2045,7 @@ impl Editor {
         let Some(state) = self.buffers.get(&buffer_id) else {
             return;
         };
-        let buffer_version = state.buffer.version();
+        let buffer_version = state.buffer.revision();
         if let Some(store) = state.semantic_tokens.as_ref() {
             if store.version == buffer_version {
                 return; // Already up to date
@@ -2068,10 +2142,7 @@ impl Editor {
         let Some(uri) = metadata.file_uri().cloned() else {
             return;
         };
-        let Some(path) = metadata.file_path() else {
-            return;
-        };
-        let Some(language) = detect_language(path, &self.config.languages) else {
+        let Some(language) = metadata.language(&self.config.languages) else {
             return;
         };
 
@@ -2101,7 +2172,7 @@ impl Editor {
             return;
         };
 
-        let buffer_version = state.buffer.version();
+        let buffer_version = state.buffer.revision();
         let mut padded_start = start_line.saturating_sub(SEMANTIC_TOKENS_RANGE_PADDING_LINES);
         let mut padded_end = end_line.saturating_add(SEMANTIC_TOKENS_RANGE_PADDING_LINES);
 
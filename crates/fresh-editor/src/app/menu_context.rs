@@ -6,7 +6,6 @@
 //! whether that feature is available.
 
 use super::Editor;
-use crate::services::lsp::manager::detect_language;
 use crate::view::ui::context_keys;
 
 impl Editor {
@@ -106,11 +105,11 @@ impl Editor {
                 if !metadata.lsp_enabled {
                     return None;
                 }
-                metadata.file_path().and_then(|path| {
-                    detect_language(path, &self.config.languages).and_then(|language| {
+                metadata
+                    .language(&self.config.languages)
+                    .and_then(|language| {
                         self.lsp.as_ref().map(|lsp| lsp.is_server_ready(&language))
                     })
-                })
             })
             .unwrap_or(false)
     }
@@ -120,15 +119,15 @@ impl Editor {
         self.buffer_metadata
             .get(&self.active_buffer())
             .and_then(|metadata| {
-                metadata.file_path().and_then(|path| {
-                    detect_language(path, &self.config.languages).and_then(|language| {
+                metadata
+                    .language(&self.config.languages)
+                    .and_then(|language| {
                         self.config
                             .languages
                             .get(&language)
                             .and_then(|lc| lc.formatter.as_ref())
                             .map(|_| true)
                     })
-                })
             })
             .unwrap_or(false)
     }
@@ -1,42 +1,89 @@
 mod async_messages;
+mod auto_save;
+mod breadcrumb_actions;
 mod buffer_management;
+pub mod buffer_switcher;
+mod buffer_switcher_actions;
+mod bulk_close_actions;
 mod calibration_actions;
 pub mod calibration_wizard;
+pub mod cheat_sheet;
+mod cheat_sheet_actions;
 mod clipboard;
+mod collab_actions;
+mod color_actions;
 mod composite_buffer_actions;
+mod cursor_undo_actions;
+mod dir_diff;
+mod disk_merge;
+mod edit_location_actions;
+mod edit_transaction;
+mod encryption_actions;
 pub mod event_debug;
 mod event_debug_actions;
+mod extract_actions;
+mod file_diff;
 mod file_explorer;
+mod file_locks;
 pub mod file_open;
 mod file_open_input;
 mod file_operations;
+mod file_templates;
+mod frame_budget_actions;
+mod goto_anything;
 mod help;
+mod image_preview;
 mod input;
 mod input_dispatch;
+mod jump_actions;
+mod line_length_actions;
+mod link_actions;
 mod lsp_actions;
+mod lsp_file_rename;
+mod lsp_log;
 mod lsp_requests;
 mod menu_actions;
 mod menu_context;
 mod mouse_input;
+mod number_actions;
 mod on_save_actions;
+mod persistent_undo;
 mod plugin_commands;
+mod plugin_permission_actions;
 mod popup_actions;
+mod presentation_actions;
+mod profiler;
+mod project_env;
 mod prompt_actions;
+mod read_only;
 mod recovery_actions;
+mod regex_select_actions;
+mod reindent;
 mod render;
+mod repl_actions;
 pub mod session;
 mod settings_actions;
 mod shell_command;
+mod shell_integration;
+mod spell_actions;
 mod split_actions;
+mod tab_actions;
 mod tab_drag;
+mod tags;
+mod tasks;
 mod terminal;
+mod terminal_info;
 mod terminal_input;
 mod terminal_mouse;
 mod toggle_actions;
+mod tutorial;
 pub mod types;
 mod undo_actions;
 mod view_actions;
 pub mod warning_domains;
+mod word_completion;
+mod workspace_folders;
+mod workspace_trust_actions;
 
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -75,8 +122,9 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
 }
 
 use self::types::{
-    Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
-    LspProgressInfo, MacroRecordingState, MouseState, SearchState, TabContextMenu,
+    Bookmark, CachedLayout, ClosedBuffer, EventLineInfo, InFlightLspRequest,
+    InteractiveReplaceState, LspMessageEntry, LspProgressInfo, MacroRecordingState, MouseState,
+    RecoveryPreview, SearchState, SmoothScroll, TabContextMenu, TrashedPath,
     DEFAULT_BACKGROUND_FILE,
 };
 use crate::config::Config;
@@ -85,19 +133,25 @@ use crate::input::actions::action_to_events as convert_action_to_events;
 use crate::input::buffer_mode::ModeRegistry;
 use crate::input::command_registry::CommandRegistry;
 use crate::input::commands::Suggestion;
+use crate::input::cursor_history::CursorHistory;
+use crate::input::edit_locations::EditLocations;
 use crate::input::keybindings::{Action, KeyContext, KeybindingResolver};
 use crate::input::position_history::PositionHistory;
 use crate::input::quick_open::{
     FileProvider, GotoLineProvider, QuickOpenContext, QuickOpenProvider, QuickOpenRegistry,
 };
+use crate::model::edit::{BufferChanges, ChangeSubscription, EditKind};
 use crate::model::event::{Event, EventLog, SplitDirection, SplitId};
 use crate::model::filesystem::FileSystem;
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
+use crate::services::file_lock::FileLocks;
 use crate::services::fs::FsManager;
-use crate::services::lsp::manager::{detect_language, LspManager};
-use crate::services::plugins::PluginManager;
+use crate::services::lsp::manager::LspManager;
+use crate::services::plugins::hooks::EditInfo;
+use crate::services::plugins::{PluginManager, PluginSandbox};
 use crate::services::recovery::{RecoveryConfig, RecoveryService};
 use crate::services::time_source::{RealTimeSource, SharedTimeSource};
+use crate::services::undo_history::UndoStore;
 use crate::state::EditorState;
 use crate::types::LspServerConfig;
 use crate::view::file_tree::{FileTree, FileTreeView};
@@ -330,6 +384,9 @@ pub struct Editor {
     /// Whether tab bar is visible
     tab_bar_visible: bool,
 
+    /// Whether the breadcrumb bar is visible above each split's content
+    breadcrumbs_visible: bool,
+
     /// Whether mouse capture is enabled
     mouse_enabled: bool,
 
@@ -353,12 +410,25 @@ pub struct Editor {
     /// Working directory for file explorer (set at initialization)
     working_dir: PathBuf,
 
+    /// Folders opened alongside the working directory, such as the other
+    /// projects of a monorepo
+    workspace_folders: Vec<PathBuf>,
+
     /// Position history for back/forward navigation
     pub position_history: PositionHistory,
 
     /// Flag to prevent recording movements during navigation
     in_navigation: bool,
 
+    /// Recent edit locations across buffers for "go to last change"
+    edit_locations: EditLocations,
+
+    /// Past cursors of each split and buffer for "cursor undo"
+    cursor_history: HashMap<(SplitId, BufferId), CursorHistory>,
+
+    /// Symbols fetched for the Go to Anything prompt
+    goto_symbols: goto_anything::GotoSymbols,
+
     /// Next LSP request ID
     next_lsp_request_id: u64,
 
@@ -385,12 +455,19 @@ pub struct Editor {
     /// Symbol name for pending references request
     pending_references_symbol: String,
 
+    /// The pending completion, go-to-definition, hover and references
+    /// requests, by request ID
+    lsp_requests_in_flight: HashMap<u64, InFlightLspRequest>,
+
     /// Pending LSP signature help request ID (if any)
     pending_signature_help_request: Option<u64>,
 
     /// Pending LSP code actions request ID (if any)
     pending_code_actions_request: Option<u64>,
 
+    /// Extract Variable/Function waiting for the LSP's code actions
+    pending_extraction: Option<extract_actions::PendingExtraction>,
+
     /// Pending LSP inlay hints request ID (if any)
     pending_inlay_hints_request: Option<u64>,
 
@@ -467,11 +544,21 @@ pub struct Editor {
     /// Plugin manager (handles both enabled and disabled cases)
     plugin_manager: PluginManager,
 
+    /// What plugins may do, shared with the plugin thread (see
+    /// `services::plugins::sandbox`)
+    plugin_sandbox: Arc<RwLock<PluginSandbox>>,
+
+    /// Plugin permission requests waiting for the user; the first is being asked
+    permission_requests: Vec<plugin_permission_actions::PermissionPrompt>,
+
     /// Track which byte ranges have been seen per buffer (for lines_changed optimization)
     /// Maps buffer_id -> set of (byte_start, byte_end) ranges that have been processed
     /// Using byte ranges instead of line numbers makes this agnostic to line number shifts
     seen_byte_ranges: HashMap<BufferId, std::collections::HashSet<(usize, usize)>>,
 
+    /// The buffer revisions plugins were last told about with buffer_changed
+    plugin_change_subscriptions: HashMap<BufferId, ChangeSubscription>,
+
     /// Named panel IDs mapping (for idempotent panel operations)
     /// Maps panel ID (e.g., "diagnostics") to buffer ID
     panel_ids: HashMap<String, BufferId>,
@@ -499,9 +586,13 @@ pub struct Editor {
     /// LSP window messages (recent messages from window/showMessage)
     lsp_window_messages: Vec<LspMessageEntry>,
 
-    /// LSP log messages (recent messages from window/logMessage)
+    /// LSP log messages (recent messages from window/logMessage and
+    /// window/showMessage, and server starts, crashes and restarts)
     lsp_log_messages: Vec<LspMessageEntry>,
 
+    /// When the LSP Log buffer was last updated
+    lsp_log_updated_at: Option<std::time::Instant>,
+
     /// Diagnostic result IDs per URI (for incremental pull diagnostics)
     /// Maps URI string to last result_id received from server
     diagnostic_result_ids: HashMap<String, String>,
@@ -554,10 +645,60 @@ pub struct Editor {
     /// When Some, a confirmation popup is shown asking user to approve LSP spawn
     pending_lsp_confirmation: Option<String>,
 
+    /// Spell checker with the loaded dictionaries and personal word list
+    spell_checker: crate::services::spell::SpellChecker,
+
+    /// Misspelled word the spell suggestions popup is for (buffer and byte range)
+    /// When Some, confirming the popup replaces the word or adds it to the dictionary
+    pending_spell_suggestion: Option<(BufferId, std::ops::Range<usize>)>,
+
+    /// Color literal the color adjuster popup is for (buffer and byte range)
+    /// When Some, confirming the popup rewrites the literal
+    pending_color_adjustment: Option<(BufferId, std::ops::Range<usize>)>,
+
+    /// Where the open breadcrumb dropdown was shown
+    /// When Some, confirming the popup goes to the chosen symbol or file
+    breadcrumb_dropdown: Option<breadcrumb_actions::BreadcrumbDropdown>,
+
+    /// File explorer rename waiting for language servers' edits, or for the
+    /// user to confirm them in the preview popup
+    pending_file_rename: Option<lsp_file_rename::PendingFileRename>,
+
+    /// Lines waiting for a language server to format them, for Re-indent Lines
+    pending_reindent: Option<reindent::PendingReindent>,
+
+    /// The working directory's tags file, for tag navigation and word completion
+    tags_cache: Option<tags::TagsCache>,
+
+    /// The tags offered by the open SelectTag prompt
+    tag_choices: Vec<crate::primitives::ctags::Tag>,
+
+    /// Where each tag jump was made from, for Pop Tag
+    tag_stack: Vec<crate::input::jump_list::JumpEntry>,
+
+    /// Recently closed file buffers, most recent last (for Reopen Closed Buffer)
+    closed_buffers: Vec<ClosedBuffer>,
+
+    /// The last path deleted from the file explorer, restorable for a short time
+    last_trashed: Option<TrashedPath>,
+
+    /// REPL output buffer for each language that code was sent from
+    repl_buffers: HashMap<String, BufferId>,
+
+    /// Collaborative editing session, when hosting or joined
+    collab: Option<collab_actions::CollabState>,
+
+    /// Presentation being given or followed
+    presentation: Option<presentation_actions::PresentationState>,
+
     /// Pending close buffer - buffer to close after SaveFileAs completes
     /// Used when closing a modified buffer that needs to be saved first
     pending_close_buffer: Option<BufferId>,
 
+    /// Buffers with unsaved changes still to be asked about, after choosing
+    /// to review them one at a time when closing many buffers
+    close_review_queue: std::collections::VecDeque<BufferId>,
+
     /// Whether auto-revert mode is enabled (automatically reload files when changed on disk)
     auto_revert_enabled: bool,
 
@@ -571,6 +712,73 @@ pub struct Editor {
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Disk content last merged into buffers with unsaved changes, the base
+    /// for merging the next change on disk (see `disk_merge`)
+    disk_merge_bases: HashMap<BufferId, String>,
+
+    /// The directories compared in each directory diff buffer
+    dir_diffs: HashMap<BufferId, dir_diff::DirDiff>,
+
+    /// The project task run shown in each task output buffer
+    task_runs: HashMap<BufferId, tasks::TaskRun>,
+
+    /// Id of the next task run, to tell its messages from an earlier run's
+    next_task_run_id: u64,
+
+    /// Names of the palette commands registered for the project's tasks
+    task_commands: Vec<String>,
+
+    /// The project's environment (direnv, `.env`), for the processes the
+    /// editor starts
+    project_env: crate::services::project_env::ProjectEnv,
+
+    /// Last time we polled the config files for changes (for hot-reload)
+    last_config_poll: std::time::Instant,
+
+    /// Last known modification times of the watched config files
+    /// (`None` = file doesn't exist)
+    config_mod_times: HashMap<PathBuf, Option<std::time::SystemTime>>,
+
+    /// Last known modification times of the user theme files (for hot-reload)
+    theme_mod_times: HashMap<PathBuf, std::time::SystemTime>,
+
+    /// Detected terminal background, used by `appearance.mode = "auto"`
+    terminal_appearance: Option<crate::view::theme::Appearance>,
+
+    /// What the terminal supports (shown by Terminal Info)
+    terminal_capabilities: crate::services::terminal_capabilities::TerminalCapabilities,
+
+    /// Frame times and input latency (shown by the Profiler buffer)
+    profiler: crate::services::profiler::Profiler,
+
+    /// When the Profiler buffer was last updated
+    profiler_updated_at: Option<std::time::Instant>,
+
+    /// Which decorations are turned off because frames take too long
+    frame_budget: crate::services::frame_budget::FrameBudget,
+
+    /// Progress through the Tutorial, while it's open
+    tutorial: Option<tutorial::TutorialProgress>,
+
+    /// Writes input events to the event log (`--event-log`)
+    input_recorder: Option<crate::services::event_replay::InputRecorder>,
+
+    /// Decoded images and the ones on screen (see `services::image_preview`)
+    image_previews: crate::services::image_preview::ImagePreviews,
+
+    /// Buffers whose image preview was toggled from the default (shown for
+    /// binary image files, hidden for SVG)
+    image_preview_toggled: HashSet<BufferId>,
+
+    /// Image file under the mouse in the file explorer, shown as a thumbnail
+    hovered_explorer_image: Option<PathBuf>,
+
+    /// Light/dark appearance chosen with the toggle command (overrides the config)
+    appearance_override: Option<crate::view::theme::Appearance>,
+
+    /// Appearance the active theme was last chosen for (to detect schedule changes)
+    applied_appearance: Option<crate::view::theme::Appearance>,
+
     /// Last known modification times for expanded directories (for file tree refresh)
     /// Maps directory path to last known modification time
     dir_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -588,6 +796,39 @@ pub struct Editor {
     /// Recovery service for auto-save and crash recovery
     recovery_service: RecoveryService,
 
+    /// Files already backed up this session (see `services::backup`)
+    backed_up_files: HashSet<PathBuf>,
+
+    /// Idle tracking for auto-saving modified files to disk
+    file_auto_save_states: HashMap<BufferId, auto_save::FileAutoSaveState>,
+
+    /// Overlong lines listed in the diagnostics panel, per buffer
+    line_length_diagnostics: HashMap<BufferId, line_length_actions::LineLengthDiagnostics>,
+
+    /// Lock files of modified files (see `services::file_lock`)
+    file_locks: FileLocks,
+
+    /// Undo histories kept across restarts (see `services::undo_history`)
+    undo_store: UndoStore,
+
+    /// Whether the user trusts the working directory, for good or for this
+    /// session; otherwise it's in safe mode (see `services::workspace_trust`)
+    workspace_trusted: bool,
+
+    /// Working directory last reported to the terminal with OSC 7
+    reported_working_dir: Option<PathBuf>,
+
+    /// Window title and progress last sent to the terminal
+    reported_title: Option<String>,
+    reported_progress: crate::services::shell_integration::TerminalProgress,
+
+    /// Socket listener receiving "open file" requests from other `fresh` processes
+    #[cfg(unix)]
+    instance_server: Option<crate::services::instance_server::InstanceServer>,
+
+    /// Diff preview of the recovery entry currently selected in the recovery picker
+    recovery_preview: Option<RecoveryPreview>,
+
     /// Request a full terminal clear and redraw on the next frame
     full_redraw_requested: bool,
 
@@ -650,6 +891,13 @@ pub struct Editor {
     /// Double-click is only detected if both clicks are at the same position
     previous_click_position: Option<(u16, u16)>,
 
+    /// Number of consecutive clicks at the same position: 2 for a double-click,
+    /// 3 for a triple-click
+    click_count: u8,
+
+    /// Mouse wheel scroll still gliding when `smooth_scrolling` is enabled
+    smooth_scroll: Option<SmoothScroll>,
+
     /// Settings UI state (when settings modal is open)
     pub(crate) settings_state: Option<crate::view::settings::SettingsState>,
 
@@ -659,6 +907,12 @@ pub struct Editor {
     /// Event debug dialog state (when event debug modal is open)
     pub(crate) event_debug: Option<event_debug::EventDebug>,
 
+    /// Keybinding cheat sheet state (when the cheat sheet overlay is open)
+    pub(crate) cheat_sheet: Option<cheat_sheet::CheatSheet>,
+
+    /// Buffer switcher state (while the Ctrl+Tab popup is open)
+    pub(crate) buffer_switcher: Option<buffer_switcher::BufferSwitcher>,
+
     /// Key translator for input calibration (loaded from config)
     pub(crate) key_translator: crate::input::key_translator::KeyTranslator,
 
@@ -697,6 +951,8 @@ pub struct StdinStreamingState {
     pub complete: bool,
     /// Background thread handle (for checking completion)
     pub thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
+    /// Whether the language is still to be guessed from the first line
+    pub detect_language: bool,
 }
 
 impl Editor {
@@ -749,8 +1005,36 @@ impl Editor {
         )
     }
 
+    /// Create a new editor that reads the time from `time_source`, so
+    /// `--replay` can control how much time passes
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_time_source(
+        config: Config,
+        width: u16,
+        height: u16,
+        working_dir: Option<PathBuf>,
+        dir_context: DirectoryContext,
+        plugins_enabled: bool,
+        color_capability: crate::view::color_support::ColorCapability,
+        filesystem: Arc<dyn FileSystem + Send + Sync>,
+        time_source: SharedTimeSource,
+    ) -> AnyhowResult<Self> {
+        Self::with_options(
+            config,
+            width,
+            height,
+            working_dir,
+            filesystem,
+            plugins_enabled,
+            dir_context,
+            Some(time_source),
+            color_capability,
+            crate::primitives::grammar::GrammarRegistry::for_editor(),
+        )
+    }
+
     /// Create a new editor for testing with custom backends
-    /// Uses empty grammar registry for fast initialization
+    /// Pass `GrammarRegistry::empty()` for fast initialization
     #[allow(clippy::too_many_arguments)]
     pub fn for_test(
         config: Config,
@@ -761,6 +1045,7 @@ impl Editor {
         color_capability: crate::view::color_support::ColorCapability,
         filesystem: Arc<dyn FileSystem + Send + Sync>,
         time_source: Option<SharedTimeSource>,
+        grammar_registry: Arc<crate::primitives::grammar::GrammarRegistry>,
     ) -> AnyhowResult<Self> {
         Self::with_options(
             config,
@@ -772,7 +1057,7 @@ impl Editor {
             dir_context,
             time_source,
             color_capability,
-            crate::primitives::grammar::GrammarRegistry::empty(),
+            grammar_registry,
         )
     }
 
@@ -805,11 +1090,13 @@ impl Editor {
         let working_dir = working_dir.canonicalize().unwrap_or(working_dir);
 
         // Load all themes into registry
-        let theme_loader = crate::view::theme::ThemeLoader::new();
+        let theme_loader =
+            crate::view::theme::ThemeLoader::with_user_dir(Some(dir_context.themes_dir()));
         let theme_registry = theme_loader.load_all();
+        let theme_mod_times = theme_loader.user_theme_mod_times();
 
         // Get active theme from registry, falling back to default if not found
-        let theme = theme_registry.get_cloned(&config.theme).unwrap_or_else(|| {
+        let mut theme = theme_registry.get_cloned(&config.theme).unwrap_or_else(|| {
             tracing::warn!(
                 "Theme '{}' not found, falling back to default theme",
                 config.theme.0
@@ -820,6 +1107,9 @@ impl Editor {
                 ))
                 .expect("Default theme must exist")
         });
+        if config.editor.high_contrast {
+            theme.enforce_min_contrast(crate::view::theme::HIGH_CONTRAST_RATIO);
+        }
 
         // Set terminal cursor color to match theme
         theme.set_terminal_cursor_color();
@@ -844,6 +1134,10 @@ impl Editor {
         );
         // Apply line_numbers default from config (fixes #539)
         state.margins.set_line_numbers(config.editor.line_numbers);
+        state.spell = crate::services::spell::BufferSpellState::new(
+            config.editor.spell_check,
+            &config.editor.spell_language,
+        );
         // Note: line_wrap_enabled is now stored in SplitViewState.viewport
         tracing::info!("EditorState created for buffer {:?}", buffer_id);
         buffers.insert(buffer_id, state);
@@ -912,10 +1206,12 @@ impl Editor {
         // We'll handle commands and buffers inline since they need App state
 
         // Initialize plugin manager (handles both enabled and disabled cases internally)
+        let plugin_sandbox = Arc::new(RwLock::new(PluginSandbox::new(&config)));
         let plugin_manager = PluginManager::new(
             enable_plugins,
             Arc::clone(&command_registry),
             dir_context.clone(),
+            Arc::clone(&plugin_sandbox),
         );
 
         // Update the plugin state snapshot with working_dir BEFORE loading plugins
@@ -926,20 +1222,27 @@ impl Editor {
             snapshot.working_dir = working_dir.clone();
         }
 
+        // A project's plugins and config commands only run once it's trusted
+        let workspace_trusted =
+            crate::services::workspace_trust::is_trusted(&dir_context, &working_dir);
+
         // Load TypeScript plugins from multiple directories:
         // 1. Next to the executable (for cargo-dist installations)
-        // 2. In the working directory (for development/local usage)
+        // 2. In the working directory (for development/local usage), if trusted
         // 3. From embedded plugins (for cargo-binstall, when embed-plugins feature is enabled)
         // 4. User plugins directory (~/.config/fresh/plugins)
         // 5. Package manager installed plugins (~/.config/fresh/plugins/packages/*)
         if plugin_manager.is_active() {
             let mut plugin_dirs: Vec<std::path::PathBuf> = vec![];
+            // The plugins that ship with Fresh, which the sandbox doesn't check
+            let mut bundled_plugin_dirs: Vec<std::path::PathBuf> = vec![];
 
             // Check next to executable first (for cargo-dist installations)
             if let Ok(exe_path) = std::env::current_exe() {
                 if let Some(exe_dir) = exe_path.parent() {
                     let exe_plugin_dir = exe_dir.join("plugins");
                     if exe_plugin_dir.exists() {
+                        bundled_plugin_dirs.push(exe_plugin_dir.clone());
                         plugin_dirs.push(exe_plugin_dir);
                     }
                 }
@@ -947,7 +1250,10 @@ impl Editor {
 
             // Then check working directory (for development)
             let working_plugin_dir = working_dir.join("plugins");
-            if working_plugin_dir.exists() && !plugin_dirs.contains(&working_plugin_dir) {
+            if workspace_trusted
+                && working_plugin_dir.exists()
+                && !plugin_dirs.contains(&working_plugin_dir)
+            {
                 plugin_dirs.push(working_plugin_dir);
             }
 
@@ -958,6 +1264,7 @@ impl Editor {
                     crate::services::plugins::embedded::get_embedded_plugins_dir()
                 {
                     tracing::info!("Using embedded plugins from: {:?}", embedded_dir);
+                    bundled_plugin_dirs.push(embedded_dir.clone());
                     plugin_dirs.push(embedded_dir.clone());
                 }
             }
@@ -998,6 +1305,10 @@ impl Editor {
             // Load from all found plugin directories, respecting config
             for plugin_dir in plugin_dirs {
                 tracing::info!("Loading TypeScript plugins from: {:?}", plugin_dir);
+                plugin_sandbox
+                    .write()
+                    .unwrap()
+                    .add_plugin_dir(&plugin_dir, bundled_plugin_dirs.contains(&plugin_dir));
                 let (errors, discovered_plugins) =
                     plugin_manager.load_plugins_from_dir_with_config(&plugin_dir, &config.plugins);
 
@@ -1026,9 +1337,18 @@ impl Editor {
         let file_explorer_width = config.file_explorer.width;
         let recovery_enabled = config.editor.recovery_enabled;
         let auto_save_interval_secs = config.editor.auto_save_interval_secs;
+        let recovery_codec = crate::services::recovery::DataCodec::from_settings(
+            config.editor.recovery_compression,
+            config.editor.recovery_encryption,
+            &config.editor.recovery_passphrase_command,
+            &dir_context.data_dir.join("encryption.salt"),
+        );
+        // Never fall back to writing plaintext when encryption was requested
+        let recovery_enabled = recovery_enabled && recovery_codec.is_usable();
         let check_for_updates = config.check_for_updates;
         let show_menu_bar = config.editor.show_menu_bar;
         let show_tab_bar = config.editor.show_tab_bar;
+        let show_breadcrumbs = config.editor.show_breadcrumbs;
 
         // Start periodic update checker if enabled (also sends daily telemetry)
         let update_checker = if check_for_updates {
@@ -1047,6 +1367,12 @@ impl Editor {
 
         // Cache raw user config at startup (to avoid re-reading file every frame)
         let user_config_raw = Config::read_user_config_raw(&working_dir);
+        let config_mod_times = Self::config_file_mod_times(&ConfigResolver::new(
+            dir_context.clone(),
+            working_dir.clone(),
+        ));
+        let frame_budget =
+            crate::services::frame_budget::FrameBudget::new(config.editor.frame_budget_ms);
 
         let mut editor = Editor {
             buffers,
@@ -1097,6 +1423,7 @@ impl Editor {
                 crate::view::file_tree::FileExplorerDecorationCache::default(),
             menu_bar_auto_shown: false,
             tab_bar_visible: show_tab_bar,
+            breadcrumbs_visible: show_breadcrumbs,
             mouse_enabled: true,
             mouse_cursor_position: None,
             gpm_active: false,
@@ -1104,8 +1431,12 @@ impl Editor {
             menu_state: crate::view::ui::MenuState::new(),
             menus: crate::config::MenuConfig::translated(),
             working_dir,
+            workspace_folders: Vec::new(),
             position_history: PositionHistory::new(),
             in_navigation: false,
+            edit_locations: EditLocations::new(),
+            cursor_history: HashMap::new(),
+            goto_symbols: goto_anything::GotoSymbols::default(),
             next_lsp_request_id: 0,
             pending_completion_request: None,
             completion_items: None,
@@ -1113,9 +1444,11 @@ impl Editor {
             pending_goto_definition_request: None,
             pending_hover_request: None,
             pending_references_request: None,
+            lsp_requests_in_flight: HashMap::new(),
             pending_references_symbol: String::new(),
             pending_signature_help_request: None,
             pending_code_actions_request: None,
+            pending_extraction: None,
             pending_inlay_hints_request: None,
             pending_semantic_token_requests: HashMap::new(),
             semantic_tokens_in_flight: HashMap::new(),
@@ -1144,7 +1477,10 @@ impl Editor {
             quick_open_registry,
             file_provider,
             plugin_manager,
+            plugin_sandbox,
+            permission_requests: Vec::new(),
             seen_byte_ranges: HashMap::new(),
+            plugin_change_subscriptions: HashMap::new(),
             panel_ids: HashMap::new(),
             background_process_handles: HashMap::new(),
             prompt_histories: {
@@ -1166,6 +1502,7 @@ impl Editor {
             lsp_server_statuses: std::collections::HashMap::new(),
             lsp_window_messages: Vec::new(),
             lsp_log_messages: Vec::new(),
+            lsp_log_updated_at: None,
             diagnostic_result_ids: HashMap::new(),
             stored_diagnostics: HashMap::new(),
             event_broadcaster: crate::model::control_event::EventBroadcaster::default(),
@@ -1184,11 +1521,52 @@ impl Editor {
             plugin_render_requested: false,
             chord_state: Vec::new(),
             pending_lsp_confirmation: None,
+            spell_checker: crate::services::spell::SpellChecker::new(
+                dir_context.dictionaries_dir(),
+            ),
+            pending_spell_suggestion: None,
+            pending_color_adjustment: None,
+            breadcrumb_dropdown: None,
+            pending_file_rename: None,
+            pending_reindent: None,
+            tags_cache: None,
+            tag_choices: Vec::new(),
+            tag_stack: Vec::new(),
+            closed_buffers: Vec::new(),
+            last_trashed: None,
+            repl_buffers: HashMap::new(),
+            collab: None,
+            presentation: None,
             pending_close_buffer: None,
+            close_review_queue: std::collections::VecDeque::new(),
             auto_revert_enabled: true,
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
             file_mod_times: HashMap::new(),
+            disk_merge_bases: HashMap::new(),
+            dir_diffs: HashMap::new(),
+            task_runs: HashMap::new(),
+            next_task_run_id: 0,
+            task_commands: Vec::new(),
+            project_env: Default::default(),
+            last_config_poll: time_source.now(),
+            config_mod_times,
+            theme_mod_times,
+            terminal_appearance: None,
+            terminal_capabilities:
+                crate::services::terminal_capabilities::TerminalCapabilities::from_env(
+                    color_capability,
+                ),
+            profiler: crate::services::profiler::Profiler::new(),
+            profiler_updated_at: None,
+            frame_budget,
+            tutorial: None,
+            input_recorder: None,
+            image_previews: Default::default(),
+            image_preview_toggled: HashSet::new(),
+            hovered_explorer_image: None,
+            appearance_override: None,
+            applied_appearance: None,
             dir_mod_times: HashMap::new(),
             file_rapid_change_counts: HashMap::new(),
             file_open_state: None,
@@ -1197,10 +1575,23 @@ impl Editor {
                 let recovery_config = RecoveryConfig {
                     enabled: recovery_enabled,
                     auto_save_interval_secs,
+                    codec: recovery_codec,
                     ..RecoveryConfig::default()
                 };
                 RecoveryService::with_config_and_dir(recovery_config, dir_context.recovery_dir())
             },
+            backed_up_files: HashSet::new(),
+            file_auto_save_states: HashMap::new(),
+            line_length_diagnostics: HashMap::new(),
+            file_locks: FileLocks::new(dir_context.locks_dir()),
+            undo_store: UndoStore::new(dir_context.undo_dir()),
+            workspace_trusted,
+            reported_working_dir: None,
+            reported_title: None,
+            reported_progress: Default::default(),
+            #[cfg(unix)]
+            instance_server: None,
+            recovery_preview: None,
             full_redraw_requested: false,
             time_source: time_source.clone(),
             last_auto_save: time_source.now(),
@@ -1219,9 +1610,13 @@ impl Editor {
             terminal_mode_resume: std::collections::HashSet::new(),
             previous_click_time: None,
             previous_click_position: None,
+            click_count: 0,
+            smooth_scroll: None,
             settings_state: None,
             calibration_wizard: None,
             event_debug: None,
+            cheat_sheet: None,
+            buffer_switcher: None,
             key_translator: crate::input::key_translator::KeyTranslator::load_default()
                 .unwrap_or_default(),
             color_capability,
@@ -1232,6 +1627,16 @@ impl Editor {
             composite_view_states: HashMap::new(),
         };
 
+        // Use the light or dark theme if the appearance schedule selects one
+        // (main sets the detected terminal background afterwards)
+        editor.set_terminal_appearance(None);
+
+        // A palette command for each of the project's tasks
+        editor.refresh_task_commands();
+
+        // Language servers, terminals and tasks get the project's environment
+        editor.load_project_env();
+
         #[cfg(feature = "plugins")]
         {
             editor.update_plugin_state_snapshot();
@@ -1493,8 +1898,15 @@ impl Editor {
         }
     }
 
-    /// Enable event log streaming to a file
+    /// Enable event log streaming to a file, along with the input events
+    /// `--replay` needs
     pub fn enable_event_streaming<P: AsRef<Path>>(&mut self, path: P) -> AnyhowResult<()> {
+        self.input_recorder = Some(crate::services::event_replay::InputRecorder::create(
+            path.as_ref(),
+            self.terminal_width,
+            self.terminal_height,
+            self.time_source.now(),
+        )?);
         // Enable streaming for all existing event logs
         for event_log in self.event_logs.values_mut() {
             event_log.enable_streaming(&path)?;
@@ -1502,6 +1914,14 @@ impl Editor {
         Ok(())
     }
 
+    /// Write an input event to the event log, if streaming is enabled
+    pub fn record_input(&mut self, event: &crossterm::event::Event) {
+        let now = self.time_source.now();
+        if let Some(recorder) = &mut self.input_recorder {
+            recorder.record(event, now);
+        }
+    }
+
     /// Log keystroke for debugging
     pub fn log_keystroke(&mut self, key_code: &str, modifiers: &str) {
         if let Some(event_log) = self.event_logs.get_mut(&self.active_buffer()) {
@@ -1797,6 +2217,9 @@ impl Editor {
             return; // No change
         }
 
+        // Remember where we came from for Jump Back
+        self.record_jump();
+
         // Dismiss transient popups and clear hover state when switching buffers
         self.on_editor_focus_lost();
 
@@ -2003,11 +2426,12 @@ impl Editor {
         self.sync_editor_state_to_split_view_state();
 
         // 1c. Invalidate layouts for all views of this buffer after content changes
-        // Note: recovery_pending is set automatically by the buffer on edits
+        // Note: recovery is pending automatically once the buffer revision changes
         match event {
             Event::Insert { .. } | Event::Delete { .. } | Event::BulkEdit { .. } => {
                 self.invalidate_layouts_for_buffer(self.active_buffer());
                 self.schedule_semantic_tokens_full_refresh(self.active_buffer());
+                self.record_edit_location(event);
             }
             Event::Batch { events, .. } => {
                 let has_edits = events
@@ -2016,6 +2440,7 @@ impl Editor {
                 if has_edits {
                     self.invalidate_layouts_for_buffer(self.active_buffer());
                     self.schedule_semantic_tokens_full_refresh(self.active_buffer());
+                    self.record_edit_location(event);
                 }
             }
             _ => {}
@@ -2239,6 +2664,7 @@ impl Editor {
         self.sync_editor_state_to_split_view_state();
         self.invalidate_layouts_for_buffer(self.active_buffer());
         self.adjust_other_split_cursors_for_event(&bulk_edit);
+        self.record_edit_location(&bulk_edit);
         // Note: Do NOT clear search overlays - markers track through edits for F3/Shift+F3
 
         Some(bulk_edit)
@@ -2626,6 +3052,70 @@ impl Editor {
         }
     }
 
+    /// Let the editor do the work that doesn't wait for input: async messages,
+    /// timers, auto-save and the like. Returns whether a frame needs drawing.
+    pub fn update(&mut self) -> bool {
+        let mut needs_render = false;
+
+        // Process async messages and poll for file changes (auto-revert, file tree)
+        if self.process_async_messages() {
+            needs_render = true;
+        }
+
+        // Check mouse hover timer for LSP hover requests
+        if self.check_mouse_hover_timer() {
+            needs_render = true;
+        }
+
+        // Exchange edits with collaboration peers
+        if self.sync_collab() {
+            needs_render = true;
+        }
+
+        // Broadcast to or follow a presentation
+        if self.sync_presentation() {
+            needs_render = true;
+        }
+
+        // Advance smooth mouse wheel scrolling by one frame
+        if self.step_smooth_scroll() {
+            needs_render = true;
+        }
+
+        // Check semantic highlight debounce timer
+        if self.check_semantic_highlight_timer() {
+            needs_render = true;
+        }
+
+        // Check completion trigger timer (debounced quick suggestions)
+        if self.check_completion_trigger_timer() {
+            needs_render = true;
+        }
+
+        // Check for warnings and open warning log if any occurred
+        if self.check_warning_log() {
+            needs_render = true;
+        }
+
+        // Poll stdin streaming progress (if active)
+        if self.poll_stdin_streaming() {
+            needs_render = true;
+        }
+
+        // Save files to disk first so recovery doesn't snapshot them as well
+        if self.auto_save_files() > 0 {
+            needs_render = true;
+        }
+
+        if let Err(e) = self.auto_save_dirty_buffers() {
+            tracing::debug!("Auto-save error: {}", e);
+        }
+
+        self.update_file_locks();
+
+        needs_render
+    }
+
     /// Check if the editor should quit
     pub fn should_quit(&self) -> bool {
         self.should_quit
@@ -2819,6 +3309,7 @@ impl Editor {
             prompt_type,
             PromptType::OpenFile
                 | PromptType::SwitchProject
+                | PromptType::AddWorkspaceFolder
                 | PromptType::SaveFileAs
                 | PromptType::Command
         );
@@ -2862,6 +3353,10 @@ impl Editor {
         prompt.cursor_pos = 1;
         self.prompt = Some(prompt);
 
+        // The project's tasks may have changed since the palette was last
+        // opened
+        self.refresh_task_commands();
+
         // Load initial command suggestions
         self.update_quick_open_suggestions(">");
     }
@@ -3241,6 +3736,24 @@ impl Editor {
             None
         };
 
+        // Recovery prompts chain into each other, so handle them after the reset below
+        let recovery_prompt = self
+            .prompt
+            .as_ref()
+            .map(|prompt| prompt.prompt_type.clone())
+            .filter(|prompt_type| {
+                matches!(
+                    prompt_type,
+                    PromptType::RecoverySelect | PromptType::RecoveryAction { .. }
+                )
+            });
+
+        // Cancelling a plugin permission prompt denies the permission
+        let permission_prompt = matches!(
+            self.prompt.as_ref().map(|prompt| &prompt.prompt_type),
+            Some(PromptType::PluginPermission)
+        );
+
         // Determine prompt type and reset appropriate history navigation
         if let Some(ref prompt) = self.prompt {
             // Reset history navigation for this prompt type
@@ -3271,7 +3784,10 @@ impl Editor {
                     };
                     self.apply_event_to_active_buffer(&remove_overlay_event);
                 }
-                PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs => {
+                PromptType::OpenFile
+                | PromptType::SwitchProject
+                | PromptType::AddWorkspaceFolder
+                | PromptType::SaveFileAs => {
                     // Clear file browser state
                     self.file_open_state = None;
                     self.file_browser_layout = None;
@@ -3287,6 +3803,18 @@ impl Editor {
             }
         }
 
+        // Escaping a close prompt stops reviewing buffers with unsaved changes
+        if matches!(
+            self.prompt.as_ref().map(|prompt| &prompt.prompt_type),
+            Some(
+                PromptType::ConfirmCloseBuffer { .. }
+                    | PromptType::SaveFileAs
+                    | PromptType::ConfirmOverwriteFile { .. }
+            )
+        ) {
+            self.close_review_queue.clear();
+        }
+
         self.prompt = None;
         self.pending_search_range = None;
         self.status_message = Some(t!("search.cancelled").to_string());
@@ -3295,6 +3823,13 @@ impl Editor {
         if let Some(original_theme) = theme_to_restore {
             self.preview_theme(&original_theme);
         }
+
+        if let Some(prompt_type) = recovery_prompt {
+            self.handle_recovery_prompt_cancelled(&prompt_type);
+        }
+        if permission_prompt {
+            self.handle_plugin_permission("");
+        }
     }
 
     /// Handle mouse wheel scroll in prompt with suggestions.
@@ -3346,14 +3881,27 @@ impl Editor {
                 PromptType::Command
                     | PromptType::OpenFile
                     | PromptType::SwitchProject
+                    | PromptType::AddWorkspaceFolder
                     | PromptType::SaveFileAs
                     | PromptType::StopLspServer
+                    | PromptType::RemoveWorkspaceFolder
+                    | PromptType::SelectFileTemplate { .. }
+                    | PromptType::RunTask
                     | PromptType::SelectTheme { .. }
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
                     | PromptType::SetLanguage
+                    | PromptType::NewScratchBuffer
+                    | PromptType::JumpList
+                    | PromptType::SelectTag
+                    | PromptType::SetSpellLanguage
                     | PromptType::SetEncoding
+                    | PromptType::SetIndentation
                     | PromptType::SetLineEnding
+                    | PromptType::RecoverySelect
+                    | PromptType::RecoveryAction { .. }
+                    | PromptType::CloseUnsavedBuffers { .. }
+                    | PromptType::ShowConfigSource
                     | PromptType::Plugin { .. }
             ) {
                 // Use the selected suggestion if any
@@ -3559,6 +4107,9 @@ impl Editor {
                 // Update Quick Open suggestions based on prefix
                 self.update_quick_open_suggestions(&input);
             }
+            PromptType::GotoAnything => {
+                self.update_goto_anything_suggestions(&input);
+            }
             PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
                 // Update incremental search highlights as user types
                 self.update_search_highlights(&input);
@@ -3579,7 +4130,10 @@ impl Editor {
                     history.reset_navigation();
                 }
             }
-            PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs => {
+            PromptType::OpenFile
+            | PromptType::SwitchProject
+            | PromptType::AddWorkspaceFolder
+            | PromptType::SaveFileAs => {
                 // For OpenFile/SwitchProject/SaveFileAs, update the file browser filter (native implementation)
                 self.update_file_open_filter();
             }
@@ -3609,9 +4163,21 @@ impl Editor {
             PromptType::SwitchToTab
             | PromptType::SelectTheme { .. }
             | PromptType::StopLspServer
+            | PromptType::RemoveWorkspaceFolder
+            | PromptType::SelectFileTemplate { .. }
+            | PromptType::RunTask
             | PromptType::SetLanguage
+            | PromptType::NewScratchBuffer
+            | PromptType::JumpList
+            | PromptType::SelectTag
+            | PromptType::SetSpellLanguage
             | PromptType::SetEncoding
-            | PromptType::SetLineEnding => {
+            | PromptType::SetIndentation
+            | PromptType::SetLineEnding
+            | PromptType::RecoverySelect
+            | PromptType::RecoveryAction { .. }
+            | PromptType::CloseUnsavedBuffers { .. }
+            | PromptType::ShowConfigSource => {
                 if let Some(prompt) = &mut self.prompt {
                     prompt.filter_suggestions(false);
                 }
@@ -3657,6 +4223,7 @@ impl Editor {
                     semantic_tokens_full,
                     semantic_tokens_full_delta,
                     semantic_tokens_range,
+                    workspace_folder_changes,
                 } => {
                     tracing::info!("LSP server initialized for language: {}", language);
                     tracing::debug!(
@@ -3679,6 +4246,10 @@ impl Editor {
                             semantic_tokens_full_delta,
                             semantic_tokens_range,
                         );
+                        lsp.set_workspace_folder_changes_support(
+                            &language,
+                            workspace_folder_changes,
+                        );
                     }
 
                     // Send didOpen for all open buffers of this language
@@ -3692,6 +4263,11 @@ impl Editor {
                 } => {
                     tracing::error!("LSP error for {}: {}", language, error);
                     self.status_message = Some(format!("LSP error ({}): {}", language, error));
+                    self.handle_lsp_log_message(
+                        language.clone(),
+                        crate::services::async_bridge::LspMessageType::Error,
+                        error.clone(),
+                    );
 
                     // Get server command from config for the hook
                     let server_command = self
@@ -3786,6 +4362,12 @@ impl Editor {
                         tracing::error!("Error handling references response: {}", e);
                     }
                 }
+                AsyncMessage::LspWillRenameFiles { request_id, edit } => {
+                    self.handle_will_rename_files_response(request_id, edit);
+                }
+                AsyncMessage::LspRangeFormatting { request_id, edits } => {
+                    self.handle_range_formatting_response(request_id, edits);
+                }
                 AsyncMessage::LspSignatureHelp {
                     request_id,
                     signature_help,
@@ -3798,6 +4380,18 @@ impl Editor {
                 } => {
                     self.handle_code_actions_response(request_id, actions);
                 }
+                AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols,
+                } => {
+                    self.handle_document_symbols_response(request_id, symbols);
+                }
+                AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols,
+                } => {
+                    self.handle_workspace_symbols_response(request_id, symbols);
+                }
                 AsyncMessage::LspPulledDiagnostics {
                     request_id: _,
                     uri,
@@ -3827,6 +4421,9 @@ impl Editor {
                 AsyncMessage::FileChanged { path } => {
                     self.handle_async_file_changed(path);
                 }
+                AsyncMessage::OpenFileRequest(request) => {
+                    self.handle_open_file_request(request);
+                }
                 AsyncMessage::GitStatusChanged { status } => {
                     tracing::info!("Git status changed: {}", status);
                     // TODO: Handle git status changes
@@ -4049,23 +4646,61 @@ impl Editor {
                         exit_code,
                     );
                 }
+                AsyncMessage::ShellFilterFinished {
+                    buffer_id,
+                    selection,
+                    version,
+                    command,
+                    result,
+                } => {
+                    self.handle_shell_filter_finished(
+                        buffer_id, selection, version, command, result,
+                    );
+                }
+                AsyncMessage::TagsRegenerated { result } => {
+                    self.handle_tags_regenerated(result);
+                }
+                AsyncMessage::TaskOutput {
+                    buffer_id,
+                    run_id,
+                    line,
+                } => {
+                    self.handle_task_output(buffer_id, run_id, line);
+                }
+                AsyncMessage::TaskFinished {
+                    buffer_id,
+                    run_id,
+                    result,
+                } => {
+                    self.handle_task_finished(buffer_id, run_id, result);
+                }
             }
         }
 
+        // Find overlong lines for the diagnostics panel
+        self.update_line_length_diagnostics();
+
         // Update plugin state snapshot BEFORE processing commands
         // This ensures plugins have access to current editor state (cursor positions, etc.)
         #[cfg(feature = "plugins")]
         self.update_plugin_state_snapshot();
 
+        // Tell plugins about the buffers changed since the last tick
+        self.notify_plugins_of_buffer_changes();
+
         // Process TypeScript plugin commands
         let processed_any_commands = self.process_plugin_commands();
 
+        // Ask for plugin permissions that were waiting for another prompt to close
+        let permission_prompt = self.show_permission_prompt();
+
         // Process pending plugin action completions
         #[cfg(feature = "plugins")]
         self.process_pending_plugin_actions();
 
         // Process pending LSP server restarts (with exponential backoff)
         self.process_pending_lsp_restarts();
+        self.release_idle_lsp_servers();
 
         // Check and clear the plugin render request flag
         #[cfg(feature = "plugins")]
@@ -4086,9 +4721,36 @@ impl Editor {
         // Poll for file changes (auto-revert) and file tree changes
         let file_changes = self.poll_file_changes();
         let tree_changes = self.poll_file_tree_changes();
+        let config_changes = self.poll_config_changes();
+
+        // Pick up images decoded in the background
+        let images_decoded = self.poll_image_previews();
+
+        // Keep the Profiler buffer up to date while it's shown
+        let profiler_updated = self.update_profiler();
+
+        // Follow language server output in the LSP Log buffer while it's shown
+        let lsp_log_updated = self.update_lsp_log();
+
+        // Follow the output of running tasks
+        let task_output_updated = self.update_task_outputs();
+
+        // Check off Tutorial lessons as they're done
+        let tutorial_updated = self.update_tutorial();
 
         // Trigger render if any async messages, plugin commands were processed, or plugin requested render
-        needs_render || processed_any_commands || plugin_render || file_changes || tree_changes
+        needs_render
+            || processed_any_commands
+            || permission_prompt
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || config_changes
+            || images_decoded
+            || profiler_updated
+            || lsp_log_updated
+            || task_output_updated
+            || tutorial_updated
     }
 
     /// Update LSP status bar string from active progress operations
@@ -4134,21 +4796,64 @@ impl Editor {
         // Build status string
         let status_parts: Vec<String> = statuses
             .iter()
-            .map(|(lang, status)| {
-                let status_str = match status {
-                    LspServerStatus::Starting => "starting",
-                    LspServerStatus::Initializing => "initializing",
-                    LspServerStatus::Running => "ready",
-                    LspServerStatus::Error => "error",
-                    LspServerStatus::Shutdown => "shutdown",
-                };
-                format!("{}: {}", lang, status_str)
-            })
+            .map(|(lang, status)| format!("{}: {}", lang, self.lsp_status_label(lang, *status)))
             .collect();
 
         self.lsp_status = format!("LSP [{}]", status_parts.join(", "));
     }
 
+    /// Run the buffer_changed hook for each buffer whose revision changed
+    /// since the last call, with the edits made since
+    fn notify_plugins_of_buffer_changes(&mut self) {
+        let buffers = &self.buffers;
+        self.plugin_change_subscriptions
+            .retain(|buffer_id, _| buffers.contains_key(buffer_id));
+        // Subscriptions are kept up to date without handlers, so that a
+        // plugin registering one isn't told that every buffer was reset
+        let has_handlers = self.plugin_manager.has_hook_handlers("buffer_changed");
+        for (buffer_id, state) in &self.buffers {
+            let Some(changes) = self
+                .plugin_change_subscriptions
+                .entry(*buffer_id)
+                .or_default()
+                .poll(&state.buffer)
+            else {
+                continue;
+            };
+            if !has_handlers {
+                continue;
+            }
+            let edits = match changes {
+                BufferChanges::Edits(edits) => Some(
+                    edits
+                        .iter()
+                        .map(|edit| match edit.kind {
+                            EditKind::Insert { offset, len } => EditInfo {
+                                inserted: true,
+                                offset,
+                                length: len,
+                            },
+                            EditKind::Delete { offset, len } => EditInfo {
+                                inserted: false,
+                                offset,
+                                length: len,
+                            },
+                        })
+                        .collect(),
+                ),
+                BufferChanges::Reset => None,
+            };
+            self.plugin_manager.run_hook(
+                "buffer_changed",
+                crate::services::plugins::hooks::HookArgs::BufferChanged {
+                    buffer_id: *buffer_id,
+                    revision: state.buffer.revision(),
+                    edits,
+                },
+            );
+        }
+    }
+
     /// Update the plugin state snapshot with current editor state
     #[cfg(feature = "plugins")]
     fn update_plugin_state_snapshot(&mut self) {
@@ -4175,6 +4880,7 @@ impl Editor {
                     path: state.buffer.file_path().map(|p| p.to_path_buf()),
                     modified: state.buffer.is_modified(),
                     length: state.buffer.len(),
+                    revision: state.buffer.revision(),
                 };
                 snapshot.buffers.insert(*buffer_id, buffer_info);
 
@@ -4262,8 +4968,15 @@ impl Editor {
             // Update working directory (for spawning processes in correct directory)
             snapshot.working_dir = self.working_dir.clone();
 
-            // Update LSP diagnostics
+            // Update LSP diagnostics, plus overlong lines
             snapshot.diagnostics = self.stored_diagnostics.clone();
+            for entry in self.line_length_diagnostics.values() {
+                snapshot
+                    .diagnostics
+                    .entry(entry.uri.clone())
+                    .or_default()
+                    .extend(entry.diagnostics.iter().cloned());
+            }
 
             // Update config (serialize the runtime config for plugins)
             snapshot.config = serde_json::to_value(&self.config).unwrap_or(serde_json::Value::Null);
@@ -4646,6 +5359,15 @@ impl Editor {
                 }
             }
 
+            PluginCommand::RequestPermission {
+                plugin_name,
+                request,
+                callback_id,
+                then,
+            } => {
+                self.handle_request_permission(plugin_name, request, callback_id, then.map(|c| *c));
+            }
+
             PluginCommand::SpawnProcessWait {
                 process_id,
                 callback_id,
@@ -5181,6 +5903,13 @@ impl Editor {
             } => {
                 self.handle_get_buffer_text(buffer_id, start, end, request_id);
             }
+            PluginCommand::ApplyEdits {
+                files,
+                description,
+                request_id,
+            } => {
+                self.handle_apply_edits(files, description, request_id);
+            }
             PluginCommand::GetLineStartPosition {
                 buffer_id,
                 line,
@@ -5403,8 +6132,30 @@ impl Editor {
 
     /// Save a buffer to a specific file path (for :w filename)
     fn handle_save_buffer_to_path(&mut self, buffer_id: BufferId, path: std::path::PathBuf) {
+        // A buffer in the background saved to its own file is written without
+        // the save handling of the active buffer
+        let is_own_file = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.file_path())
+            == Some(path.as_path());
+        if is_own_file && buffer_id != self.active_buffer() {
+            if let Err(e) = self.write_buffer_to_disk(buffer_id, path) {
+                self.handle_set_status(format!("Error saving: {}", e));
+                tracing::error!("Failed to save buffer to path: {}", e);
+            }
+            return;
+        }
+        if self.buffers.contains_key(&buffer_id) {
+            if let Err(e) = self.backup_before_save(&path) {
+                tracing::warn!("Failed to back up {:?}: {}", path, e);
+            }
+        }
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             // Save to the specified path
+            state
+                .buffer
+                .set_save_strategy(self.config.editor.save_strategy);
             match state.buffer.save_to_file(&path) {
                 Ok(()) => {
                     // Update the buffer's file path so future saves go to the same file
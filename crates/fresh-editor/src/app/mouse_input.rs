@@ -1,15 +1,18 @@
 //! Mouse input handling.
 //!
 //! This module contains all mouse event handling logic including:
-//! - Click, double-click, and drag handling
+//! - Click, double-click, triple-click, and drag handling
 //! - Scrollbar interaction
 //! - Hover target computation
 //! - Split separator dragging
 //! - Text selection via mouse
 
+use super::types::SelectionGranularity;
 use super::*;
 use crate::input::keybindings::Action;
+use crate::model::buffer::Buffer;
 use crate::model::event::{SplitDirection, SplitId};
+use crate::primitives::word_navigation::word_range_at;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::popup_mouse::{popup_areas_to_layout_info, PopupHitTester};
 use crate::view::prompt::PromptType;
@@ -24,16 +27,15 @@ impl Editor {
         &mut self,
         mouse_event: crossterm::event::MouseEvent,
     ) -> AnyhowResult<bool> {
-        use crossterm::event::{MouseButton, MouseEventKind};
+        use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
 
         let col = mouse_event.column;
         let row = mouse_event.row;
 
-        // Detect double-click for left button down events (used by all handlers)
-        let is_double_click = if matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left))
-        {
+        // Count consecutive clicks for left button down events (used by all handlers)
+        let click_count = if matches!(mouse_event.kind, MouseEventKind::Down(MouseButton::Left)) {
             let now = self.time_source.now();
-            let is_double = if let (Some(previous_time), Some(previous_pos)) =
+            let is_repeat = if let (Some(previous_time), Some(previous_pos)) =
                 (self.previous_click_time, self.previous_click_position)
             {
                 let double_click_threshold =
@@ -45,18 +47,24 @@ impl Editor {
                 false
             };
 
-            // Update click tracking
-            if is_double {
-                self.previous_click_time = None;
-                self.previous_click_position = None;
+            // Update click tracking; a click after a triple-click starts over
+            self.click_count = if is_repeat && self.click_count < 3 {
+                self.click_count + 1
             } else {
-                self.previous_click_time = Some(now);
-                self.previous_click_position = Some((col, row));
-            }
-            is_double
+                1
+            };
+            self.previous_click_time = Some(now);
+            self.previous_click_position = Some((col, row));
+            self.click_count
         } else {
-            false
+            0
         };
+        let is_double_click = click_count == 2;
+
+        // Let Cursor Undo go back to the cursors from before a click
+        if matches!(mouse_event.kind, MouseEventKind::Down(_)) {
+            self.observe_cursors();
+        }
 
         // When settings modal is open, capture all mouse events
         if self.settings_state.as_ref().is_some_and(|s| s.visible) {
@@ -68,6 +76,16 @@ impl Editor {
             return Ok(false);
         }
 
+        // When the cheat sheet is open, capture all mouse events
+        if self.cheat_sheet.is_some() {
+            return self.handle_cheat_sheet_mouse(mouse_event);
+        }
+
+        // When the buffer switcher is open, capture all mouse events
+        if self.buffer_switcher.is_some() {
+            return self.handle_buffer_switcher_mouse(mouse_event);
+        }
+
         // Cancel LSP rename prompt on any mouse interaction
         let mut needs_render = false;
         if let Some(ref prompt) = self.prompt {
@@ -98,7 +116,20 @@ impl Editor {
             return result;
         }
 
-        match mouse_event.kind {
+        // Shift+wheel scrolls horizontally, for mice without a horizontal wheel
+        let kind = match mouse_event.kind {
+            MouseEventKind::ScrollUp if mouse_event.modifiers.contains(KeyModifiers::SHIFT) => {
+                MouseEventKind::ScrollLeft
+            }
+            MouseEventKind::ScrollDown if mouse_event.modifiers.contains(KeyModifiers::SHIFT) => {
+                MouseEventKind::ScrollRight
+            }
+            kind => kind,
+        };
+        let scroll_lines = self.config.editor.mouse_scroll_lines as i32;
+        let scroll_columns = self.config.editor.mouse_scroll_columns as i32;
+
+        match kind {
             MouseEventKind::Down(MouseButton::Left) => {
                 if is_double_click {
                     // Double click detected - both clicks within time threshold AND at same position
@@ -106,6 +137,11 @@ impl Editor {
                     needs_render = true;
                     return Ok(needs_render);
                 }
+                if click_count == 3 && self.handle_mouse_triple_click(col, row)? {
+                    // Triple click on editor text - otherwise it counts as a single click
+                    needs_render = true;
+                    return Ok(needs_render);
+                }
                 self.handle_mouse_click(col, row, mouse_event.modifiers)?;
                 needs_render = true;
             }
@@ -143,6 +179,8 @@ impl Editor {
                 self.mouse_state.dragging_text_selection = false;
                 self.mouse_state.drag_selection_split = None;
                 self.mouse_state.drag_selection_anchor = None;
+                self.mouse_state.drag_selection_granularity = SelectionGranularity::Character;
+                self.mouse_state.drag_selection_origin = None;
                 // Clear popup scrollbar drag state
                 self.mouse_state.dragging_popup_scrollbar = None;
                 self.mouse_state.drag_start_popup_scroll = None;
@@ -192,17 +230,22 @@ impl Editor {
 
                 // Track LSP hover state for mouse-triggered hover popups
                 self.update_lsp_hover_state(col, row);
+
+                // Underline URLs and file paths under the pointer
+                let link_changed = self.update_hovered_link(col, row);
+                needs_render = needs_render || link_changed;
             }
             MouseEventKind::ScrollUp => {
                 // Check if prompt with suggestions is active and should handle scroll
-                if self.handle_prompt_scroll(-3) {
+                if self.handle_prompt_scroll(-scroll_lines) {
                     needs_render = true;
-                } else if self.is_file_open_active() && self.handle_file_open_scroll(-3) {
+                } else if self.is_file_open_active() && self.handle_file_open_scroll(-scroll_lines)
+                {
                     // Check if file browser is active and should handle scroll
                     needs_render = true;
                 } else if self.is_mouse_over_any_popup(col, row) {
                     // Scroll the popup content (works for all popups including completion)
-                    self.scroll_popup(-3);
+                    self.scroll_popup(-scroll_lines);
                     needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
@@ -213,7 +256,7 @@ impl Editor {
                     }
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, -3)?;
+                    self.handle_mouse_scroll(col, row, -scroll_lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     self.sync_split_view_state_to_editor_state();
                     needs_render = true;
@@ -221,13 +264,13 @@ impl Editor {
             }
             MouseEventKind::ScrollDown => {
                 // Check if prompt with suggestions is active and should handle scroll
-                if self.handle_prompt_scroll(3) {
+                if self.handle_prompt_scroll(scroll_lines) {
                     needs_render = true;
-                } else if self.is_file_open_active() && self.handle_file_open_scroll(3) {
+                } else if self.is_file_open_active() && self.handle_file_open_scroll(scroll_lines) {
                     needs_render = true;
                 } else if self.is_mouse_over_any_popup(col, row) {
                     // Scroll the popup content (works for all popups including completion)
-                    self.scroll_popup(3);
+                    self.scroll_popup(scroll_lines);
                     needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
@@ -238,12 +281,22 @@ impl Editor {
                     }
                     // Dismiss hover/signature help popups on scroll
                     self.dismiss_transient_popups();
-                    self.handle_mouse_scroll(col, row, 3)?;
+                    self.handle_mouse_scroll(col, row, scroll_lines)?;
                     // Sync viewport from SplitViewState to EditorState so rendering sees the scroll
                     self.sync_split_view_state_to_editor_state();
                     needs_render = true;
                 }
             }
+            MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight => {
+                let delta = if kind == MouseEventKind::ScrollLeft {
+                    -scroll_columns
+                } else {
+                    scroll_columns
+                };
+                self.dismiss_transient_popups();
+                self.handle_mouse_horizontal_scroll(col, row, delta);
+                needs_render = true;
+            }
             MouseEventKind::Down(MouseButton::Right) => {
                 // Handle right-click for context menus
                 self.handle_right_click(col, row)?;
@@ -258,12 +311,30 @@ impl Editor {
         Ok(needs_render)
     }
 
+    /// The path of the file explorer entry on screen row `row`, if any
+    pub(super) fn file_explorer_path_at_row(&self, row: u16) -> Option<std::path::PathBuf> {
+        let explorer_area = self.cached_layout.file_explorer_area?;
+        let content_start_y = explorer_area.y + 1; // +1 for title bar
+        let content_end_y = explorer_area.y + explorer_area.height.saturating_sub(1); // -1 for bottom border
+        if row < content_start_y || row >= content_end_y {
+            return None;
+        }
+        let explorer = self.file_explorer.as_ref()?;
+        let item_index = (row - content_start_y) as usize + explorer.get_scroll_offset();
+        let (node_id, _indent) = *explorer.get_display_nodes().get(item_index)?;
+        explorer
+            .tree()
+            .get_node(node_id)
+            .map(|node| node.entry.path.clone())
+    }
+
     /// Update the current hover target based on mouse position
     /// Returns true if the hover target changed (requiring a re-render)
     pub(super) fn update_hover_target(&mut self, col: u16, row: u16) -> bool {
         let old_target = self.mouse_state.hover_target.clone();
         let new_target = self.compute_hover_target(col, row);
-        let changed = old_target != new_target;
+        let thumbnail_changed = self.update_hovered_explorer_image(col, row);
+        let changed = old_target != new_target || thumbnail_changed;
         self.mouse_state.hover_target = new_target.clone();
 
         // If a menu is currently open and we're hovering over a different menu bar item,
@@ -595,6 +666,118 @@ impl Editor {
         hit_tester.is_over_popup(col, row)
     }
 
+    /// Whether the file explorer's resize border is at the given position
+    fn is_on_file_explorer_border(&self, col: u16, row: u16) -> bool {
+        self.cached_layout.file_explorer_area.is_some_and(|area| {
+            col == area.x + area.width && row >= area.y && row < area.y + area.height
+        })
+    }
+
+    /// The split separator at the given position
+    fn separator_at(&self, col: u16, row: u16) -> Option<(SplitId, SplitDirection)> {
+        self.cached_layout
+            .separator_areas
+            .iter()
+            .find(|(_, direction, sep_x, sep_y, sep_length)| match direction {
+                // Horizontal separator: spans full width at a specific y
+                SplitDirection::Horizontal => {
+                    row == *sep_y && col >= *sep_x && col < sep_x + sep_length
+                }
+                // Vertical separator: spans full height at a specific x
+                SplitDirection::Vertical => {
+                    col == *sep_x && row >= *sep_y && row < sep_y + sep_length
+                }
+            })
+            .map(|(split_id, direction, _, _, _)| (*split_id, *direction))
+    }
+
+    /// The split scrollbar at the given position, and whether it is on the thumb
+    fn scrollbar_at(
+        &self,
+        col: u16,
+        row: u16,
+    ) -> Option<(SplitId, BufferId, ratatui::layout::Rect, bool)> {
+        self.cached_layout.split_areas.iter().find_map(
+            |(split_id, buffer_id, _content_rect, scrollbar_rect, thumb_start, thumb_end)| {
+                if col >= scrollbar_rect.x
+                    && col < scrollbar_rect.x + scrollbar_rect.width
+                    && row >= scrollbar_rect.y
+                    && row < scrollbar_rect.y + scrollbar_rect.height
+                {
+                    let relative_row = row.saturating_sub(scrollbar_rect.y) as usize;
+                    let is_on_thumb = relative_row >= *thumb_start && relative_row < *thumb_end;
+                    Some((*split_id, *buffer_id, *scrollbar_rect, is_on_thumb))
+                } else {
+                    None
+                }
+            },
+        )
+    }
+
+    /// The split whose close button is at the given position
+    fn close_split_button_at(&self, col: u16, row: u16) -> Option<SplitId> {
+        self.cached_layout
+            .close_split_areas
+            .iter()
+            .find(|(_, btn_row, start_col, end_col)| {
+                row == *btn_row && col >= *start_col && col < *end_col
+            })
+            .map(|(split_id, _, _, _)| *split_id)
+    }
+
+    /// The split whose maximize button is at the given position
+    fn maximize_split_button_at(&self, col: u16, row: u16) -> Option<SplitId> {
+        self.cached_layout
+            .maximize_split_areas
+            .iter()
+            .find(|(_, btn_row, start_col, end_col)| {
+                row == *btn_row && col >= *start_col && col < *end_col
+            })
+            .map(|(split_id, _, _, _)| *split_id)
+    }
+
+    /// The position to hit test thin controls (split separators, scrollbars,
+    /// the file explorer border and the split buttons) at.
+    ///
+    /// With `large_hit_targets`, a text cell right next to one of them counts
+    /// as part of it, so this returns the control's cell instead. Other
+    /// controls are never shadowed, since only text cells are widened over.
+    fn thin_target_position(&self, col: u16, row: u16) -> (u16, u16) {
+        if !self.config.editor.large_hit_targets {
+            return (col, row);
+        }
+        let in_text = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .any(|(_, _, rect, ..)| {
+                col >= rect.x
+                    && col < rect.x + rect.width
+                    && row >= rect.y
+                    && row < rect.y + rect.height
+            });
+        if !in_text {
+            return (col, row);
+        }
+        let neighbors = [
+            (col.checked_add(1), Some(row)),
+            (col.checked_sub(1), Some(row)),
+            (Some(col), row.checked_sub(1)),
+            (Some(col), row.checked_add(1)),
+        ];
+        neighbors
+            .into_iter()
+            .filter_map(|(c, r)| Some((c?, r?)))
+            .find(|&(c, r)| {
+                self.is_on_file_explorer_border(c, r)
+                    || self.separator_at(c, r).is_some()
+                    || self.scrollbar_at(c, r).is_some()
+                    || self.close_split_button_at(c, r).is_some()
+                    || self.maximize_split_button_at(c, r).is_some()
+            })
+            .unwrap_or((col, row))
+    }
+
     /// Compute what hover target is at the given position
     fn compute_hover_target(&self, col: u16, row: u16) -> Option<HoverTarget> {
         // Check tab context menu first (it's rendered on top)
@@ -693,71 +876,37 @@ impl Editor {
 
             // Check if hovering over a status indicator in the file explorer content area
             // Status indicators are in the rightmost 2 characters of each row (before border)
-            let content_start_y = explorer_area.y + 1; // +1 for title bar
-            let content_end_y = explorer_area.y + explorer_area.height.saturating_sub(1); // -1 for bottom border
             let status_indicator_x = explorer_area.x + explorer_area.width.saturating_sub(3); // 2 chars + 1 border
 
-            if row >= content_start_y
-                && row < content_end_y
-                && col >= status_indicator_x
+            if col >= status_indicator_x
                 && col < explorer_area.x + explorer_area.width.saturating_sub(1)
             {
-                // Determine which item is at this row
-                if let Some(ref explorer) = self.file_explorer {
-                    let relative_row = row.saturating_sub(content_start_y) as usize;
-                    let scroll_offset = explorer.get_scroll_offset();
-                    let item_index = relative_row + scroll_offset;
-                    let display_nodes = explorer.get_display_nodes();
-
-                    if item_index < display_nodes.len() {
-                        let (node_id, _indent) = display_nodes[item_index];
-                        if let Some(node) = explorer.tree().get_node(node_id) {
-                            return Some(HoverTarget::FileExplorerStatusIndicator(
-                                node.entry.path.clone(),
-                            ));
-                        }
-                    }
+                if let Some(path) = self.file_explorer_path_at_row(row) {
+                    return Some(HoverTarget::FileExplorerStatusIndicator(path));
                 }
             }
+        }
 
-            // The border is at the right edge of the file explorer area
-            let border_x = explorer_area.x + explorer_area.width;
-            if col == border_x
-                && row >= explorer_area.y
-                && row < explorer_area.y + explorer_area.height
-            {
-                return Some(HoverTarget::FileExplorerBorder);
-            }
+        let (thin_col, thin_row) = self.thin_target_position(col, row);
+
+        // The border is at the right edge of the file explorer area
+        if self.is_on_file_explorer_border(thin_col, thin_row) {
+            return Some(HoverTarget::FileExplorerBorder);
         }
 
         // Check split separators
-        for (split_id, direction, sep_x, sep_y, sep_length) in &self.cached_layout.separator_areas {
-            let is_on_separator = match direction {
-                SplitDirection::Horizontal => {
-                    row == *sep_y && col >= *sep_x && col < sep_x + sep_length
-                }
-                SplitDirection::Vertical => {
-                    col == *sep_x && row >= *sep_y && row < sep_y + sep_length
-                }
-            };
-
-            if is_on_separator {
-                return Some(HoverTarget::SplitSeparator(*split_id, *direction));
-            }
+        if let Some((split_id, direction)) = self.separator_at(thin_col, thin_row) {
+            return Some(HoverTarget::SplitSeparator(split_id, direction));
         }
 
         // Check tab areas using cached hit regions (computed during rendering)
         // Check split control buttons first (they're on top of the tab row)
-        for (split_id, btn_row, start_col, end_col) in &self.cached_layout.close_split_areas {
-            if row == *btn_row && col >= *start_col && col < *end_col {
-                return Some(HoverTarget::CloseSplitButton(*split_id));
-            }
+        if let Some(split_id) = self.close_split_button_at(thin_col, thin_row) {
+            return Some(HoverTarget::CloseSplitButton(split_id));
         }
 
-        for (split_id, btn_row, start_col, end_col) in &self.cached_layout.maximize_split_areas {
-            if row == *btn_row && col >= *start_col && col < *end_col {
-                return Some(HoverTarget::MaximizeSplitButton(*split_id));
-            }
+        if let Some(split_id) = self.maximize_split_button_at(thin_col, thin_row) {
+            return Some(HoverTarget::MaximizeSplitButton(split_id));
         }
 
         for (split_id, tab_layout) in &self.cached_layout.tab_layouts {
@@ -776,28 +925,28 @@ impl Editor {
         }
 
         // Check scrollbars
-        for (split_id, _buffer_id, _content_rect, scrollbar_rect, thumb_start, thumb_end) in
-            &self.cached_layout.split_areas
+        if let Some((split_id, _buffer_id, _scrollbar_rect, is_on_thumb)) =
+            self.scrollbar_at(thin_col, thin_row)
         {
-            if col >= scrollbar_rect.x
-                && col < scrollbar_rect.x + scrollbar_rect.width
-                && row >= scrollbar_rect.y
-                && row < scrollbar_rect.y + scrollbar_rect.height
-            {
-                let relative_row = row.saturating_sub(scrollbar_rect.y) as usize;
-                let is_on_thumb = relative_row >= *thumb_start && relative_row < *thumb_end;
-
-                if is_on_thumb {
-                    return Some(HoverTarget::ScrollbarThumb(*split_id));
-                } else {
-                    return Some(HoverTarget::ScrollbarTrack(*split_id));
-                }
+            if is_on_thumb {
+                return Some(HoverTarget::ScrollbarThumb(split_id));
+            } else {
+                return Some(HoverTarget::ScrollbarTrack(split_id));
             }
         }
 
         // Check status bar indicators
         if let Some((status_row, _status_x, _status_width)) = self.cached_layout.status_bar_area {
             if row == status_row {
+                // Check indentation indicator area
+                if let Some((ind_row, ind_start, ind_end)) =
+                    self.cached_layout.status_bar_indentation_area
+                {
+                    if row == ind_row && col >= ind_start && col < ind_end {
+                        return Some(HoverTarget::StatusBarIndentationIndicator);
+                    }
+                }
+
                 // Check line ending indicator area
                 if let Some((le_row, le_start, le_end)) =
                     self.cached_layout.status_bar_line_ending_area
@@ -825,6 +974,15 @@ impl Editor {
                     }
                 }
 
+                // Check cursor position area
+                if let Some((pos_row, pos_start, pos_end)) =
+                    self.cached_layout.status_bar_position_area
+                {
+                    if row == pos_row && col >= pos_start && col < pos_end {
+                        return Some(HoverTarget::StatusBarPositionIndicator);
+                    }
+                }
+
                 // Check LSP indicator area
                 if let Some((lsp_row, lsp_start, lsp_end)) = self.cached_layout.status_bar_lsp_area
                 {
@@ -914,7 +1072,14 @@ impl Editor {
                 self.key_context = crate::input::keybindings::KeyContext::Normal;
 
                 // Position cursor at click location and select word
-                self.handle_editor_double_click(col, row, *split_id, *buffer_id, *content_rect)?;
+                self.handle_editor_multi_click(
+                    col,
+                    row,
+                    *split_id,
+                    *buffer_id,
+                    *content_rect,
+                    SelectionGranularity::Word,
+                )?;
                 return Ok(());
             }
         }
@@ -922,14 +1087,57 @@ impl Editor {
         Ok(())
     }
 
-    /// Handle double-click in editor content area - selects the word under cursor
-    fn handle_editor_double_click(
+    /// Handle mouse triple click (down event)
+    /// Triple-click in editor area selects the line under the cursor.
+    /// Returns false if the click isn't on editor text, so it can be handled as a single click.
+    pub(super) fn handle_mouse_triple_click(&mut self, col: u16, row: u16) -> AnyhowResult<bool> {
+        if self.is_mouse_over_any_popup(col, row) {
+            return Ok(false);
+        }
+
+        let split_info = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(_, _, content_rect, _, _, _)| {
+                col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height
+            })
+            .map(|(split_id, buffer_id, content_rect, _, _, _)| {
+                (*split_id, *buffer_id, *content_rect)
+            });
+        let Some((split_id, buffer_id, content_rect)) = split_info else {
+            return Ok(false);
+        };
+        if self.is_terminal_buffer(buffer_id) || self.is_composite_buffer(buffer_id) {
+            return Ok(false);
+        }
+
+        self.key_context = crate::input::keybindings::KeyContext::Normal;
+        self.handle_editor_multi_click(
+            col,
+            row,
+            split_id,
+            buffer_id,
+            content_rect,
+            SelectionGranularity::Line,
+        )?;
+        Ok(true)
+    }
+
+    /// Handle double- or triple-click in editor content area - selects the
+    /// word or line under the cursor, and starts a drag that extends the
+    /// selection by words or lines
+    fn handle_editor_multi_click(
         &mut self,
         col: u16,
         row: u16,
         split_id: crate::model::event::SplitId,
         buffer_id: BufferId,
         content_rect: ratatui::layout::Rect,
+        granularity: SelectionGranularity,
     ) -> AnyhowResult<()> {
         use crate::model::event::Event;
 
@@ -966,15 +1174,16 @@ impl Editor {
                 return Ok(());
             };
 
-            // Move cursor to clicked position first
-            let primary_cursor_id = state.cursors.primary_id();
+            // Select the word or line at the clicked position
+            let range = selection_unit_range(&mut state.buffer, granularity, target_position);
+            let primary_cursor = state.cursors.primary();
             let event = Event::MoveCursor {
-                cursor_id: primary_cursor_id,
-                old_position: 0,
-                new_position: target_position,
-                old_anchor: None,
-                new_anchor: None,
-                old_sticky_column: 0,
+                cursor_id: state.cursors.primary_id(),
+                old_position: primary_cursor.position,
+                new_position: range.end,
+                old_anchor: primary_cursor.anchor,
+                new_anchor: (!range.is_empty()).then_some(range.start),
+                old_sticky_column: primary_cursor.sticky_column,
                 new_sticky_column: 0,
             };
 
@@ -982,10 +1191,14 @@ impl Editor {
                 event_log.append(event.clone());
             }
             state.apply(&event);
-        }
 
-        // Now select the word under cursor
-        self.handle_action(Action::SelectWord)?;
+            // Dragging from here extends the selection by whole words or lines
+            self.mouse_state.dragging_text_selection = true;
+            self.mouse_state.drag_selection_split = Some(split_id);
+            self.mouse_state.drag_selection_anchor = Some(range.start);
+            self.mouse_state.drag_selection_granularity = granularity;
+            self.mouse_state.drag_selection_origin = Some(range);
+        }
 
         Ok(())
     }
@@ -1237,24 +1450,12 @@ impl Editor {
             }
         }
 
-        // Check if click is on a scrollbar
-        let scrollbar_hit = self.cached_layout.split_areas.iter().find_map(
-            |(split_id, buffer_id, _content_rect, scrollbar_rect, thumb_start, thumb_end)| {
-                if col >= scrollbar_rect.x
-                    && col < scrollbar_rect.x + scrollbar_rect.width
-                    && row >= scrollbar_rect.y
-                    && row < scrollbar_rect.y + scrollbar_rect.height
-                {
-                    let relative_row = row.saturating_sub(scrollbar_rect.y) as usize;
-                    let is_on_thumb = relative_row >= *thumb_start && relative_row < *thumb_end;
-                    Some((*split_id, *buffer_id, *scrollbar_rect, is_on_thumb))
-                } else {
-                    None
-                }
-            },
-        );
+        let (thin_col, thin_row) = self.thin_target_position(col, row);
 
-        if let Some((split_id, buffer_id, scrollbar_rect, is_on_thumb)) = scrollbar_hit {
+        // Check if click is on a scrollbar
+        if let Some((split_id, buffer_id, scrollbar_rect, is_on_thumb)) =
+            self.scrollbar_at(thin_col, thin_row)
+        {
             self.focus_split(split_id, buffer_id);
 
             if is_on_thumb {
@@ -1268,7 +1469,13 @@ impl Editor {
             } else {
                 // Click on track - jump to position
                 self.mouse_state.dragging_scrollbar = Some(split_id);
-                self.handle_scrollbar_jump(col, row, split_id, buffer_id, scrollbar_rect)?;
+                self.handle_scrollbar_jump(
+                    thin_col,
+                    thin_row,
+                    split_id,
+                    buffer_id,
+                    scrollbar_rect,
+                )?;
             }
             return Ok(());
         }
@@ -1276,6 +1483,15 @@ impl Editor {
         // Check if click is on status bar indicators
         if let Some((status_row, _status_x, _status_width)) = self.cached_layout.status_bar_area {
             if row == status_row {
+                // Check indentation indicator - click opens indentation selector
+                if let Some((ind_row, ind_start, ind_end)) =
+                    self.cached_layout.status_bar_indentation_area
+                {
+                    if row == ind_row && col >= ind_start && col < ind_end {
+                        return self.handle_action(Action::SetIndentation);
+                    }
+                }
+
                 // Check line ending indicator - click opens line ending selector
                 if let Some((le_row, le_start, le_end)) =
                     self.cached_layout.status_bar_line_ending_area
@@ -1303,6 +1519,15 @@ impl Editor {
                     }
                 }
 
+                // Check cursor position - click opens Go to Line
+                if let Some((pos_row, pos_start, pos_end)) =
+                    self.cached_layout.status_bar_position_area
+                {
+                    if row == pos_row && col >= pos_start && col < pos_end {
+                        return self.handle_action(Action::GotoLine);
+                    }
+                }
+
                 // Check LSP indicator - click opens LSP status popup
                 if let Some((lsp_row, lsp_start, lsp_end)) = self.cached_layout.status_bar_lsp_area
                 {
@@ -1354,56 +1579,28 @@ impl Editor {
         }
 
         // Check if click is on file explorer border (for drag resizing)
-        if let Some(explorer_area) = self.cached_layout.file_explorer_area {
-            let border_x = explorer_area.x + explorer_area.width;
-            if col == border_x
-                && row >= explorer_area.y
-                && row < explorer_area.y + explorer_area.height
-            {
-                // Start file explorer border drag
-                self.mouse_state.dragging_file_explorer = true;
-                self.mouse_state.drag_start_position = Some((col, row));
-                self.mouse_state.drag_start_explorer_width = Some(self.file_explorer_width_percent);
-                return Ok(());
-            }
+        if self.is_on_file_explorer_border(thin_col, thin_row) {
+            // Start file explorer border drag
+            self.mouse_state.dragging_file_explorer = true;
+            self.mouse_state.drag_start_position = Some((col, row));
+            self.mouse_state.drag_start_explorer_width = Some(self.file_explorer_width_percent);
+            return Ok(());
         }
 
         // Check if click is on a split separator (for drag resizing)
-        for (split_id, direction, sep_x, sep_y, sep_length) in &self.cached_layout.separator_areas {
-            let is_on_separator = match direction {
-                SplitDirection::Horizontal => {
-                    // Horizontal separator: spans full width at a specific y
-                    row == *sep_y && col >= *sep_x && col < sep_x + sep_length
-                }
-                SplitDirection::Vertical => {
-                    // Vertical separator: spans full height at a specific x
-                    col == *sep_x && row >= *sep_y && row < sep_y + sep_length
-                }
-            };
-
-            if is_on_separator {
-                // Start separator drag
-                self.mouse_state.dragging_separator = Some((*split_id, *direction));
-                self.mouse_state.drag_start_position = Some((col, row));
-                // Store the initial ratio
-                if let Some(ratio) = self.split_manager.get_ratio(*split_id) {
-                    self.mouse_state.drag_start_ratio = Some(ratio);
-                }
-                return Ok(());
+        if let Some((split_id, direction)) = self.separator_at(thin_col, thin_row) {
+            // Start separator drag
+            self.mouse_state.dragging_separator = Some((split_id, direction));
+            self.mouse_state.drag_start_position = Some((col, row));
+            // Store the initial ratio
+            if let Some(ratio) = self.split_manager.get_ratio(split_id) {
+                self.mouse_state.drag_start_ratio = Some(ratio);
             }
+            return Ok(());
         }
 
         // Check if click is on a close split button
-        let close_split_click = self
-            .cached_layout
-            .close_split_areas
-            .iter()
-            .find(|(_, btn_row, start_col, end_col)| {
-                row == *btn_row && col >= *start_col && col < *end_col
-            })
-            .map(|(split_id, _, _, _)| *split_id);
-
-        if let Some(split_id) = close_split_click {
+        if let Some(split_id) = self.close_split_button_at(thin_col, thin_row) {
             if let Err(e) = self.split_manager.close_split(split_id) {
                 self.set_status_message(
                     t!("error.cannot_close_split", error = e.to_string()).to_string(),
@@ -1420,16 +1617,7 @@ impl Editor {
         }
 
         // Check if click is on a maximize split button
-        let maximize_split_click = self
-            .cached_layout
-            .maximize_split_areas
-            .iter()
-            .find(|(_, btn_row, start_col, end_col)| {
-                row == *btn_row && col >= *start_col && col < *end_col
-            })
-            .map(|(split_id, _, _, _)| *split_id);
-
-        if let Some(_split_id) = maximize_split_click {
+        if self.maximize_split_button_at(thin_col, thin_row).is_some() {
             // Toggle maximize state
             match self.split_manager.toggle_maximize() {
                 Ok(maximized) => {
@@ -1511,6 +1699,20 @@ impl Editor {
             }
         }
 
+        // Check if click is on the breadcrumb bar
+        let breadcrumb_hit = self
+            .cached_layout
+            .breadcrumb_layouts
+            .iter()
+            .find(|(_, layout)| layout.contains(col, row))
+            .map(|(split_id, layout)| (*split_id, layout.hit_test(col, row).cloned()));
+        if let Some((split_id, crumb)) = breadcrumb_hit {
+            if let Some(crumb) = crumb {
+                self.show_breadcrumb_dropdown(split_id, &crumb);
+            }
+            return Ok(());
+        }
+
         // Check if click is in editor content area
         tracing::debug!(
             "handle_mouse_click: checking {} split_areas for click at ({}, {})",
@@ -1737,6 +1939,22 @@ impl Editor {
                 return Ok(());
             };
 
+            // After a double- or triple-click, grow the selection by whole words or
+            // lines, keeping the word or line first clicked selected
+            let granularity = self.mouse_state.drag_selection_granularity;
+            let (anchor_position, target_position) = match &self.mouse_state.drag_selection_origin {
+                Some(origin) if granularity != SelectionGranularity::Character => {
+                    let unit =
+                        selection_unit_range(&mut state.buffer, granularity, target_position);
+                    if unit.start < origin.start {
+                        (origin.end, unit.start)
+                    } else {
+                        (origin.start, unit.end.max(origin.end))
+                    }
+                }
+                _ => (anchor_position, target_position),
+            };
+
             // Move cursor to target position while keeping anchor to create selection
             let primary_cursor_id = state.cursors.primary_id();
             let event = Event::MoveCursor {
@@ -1862,7 +2080,17 @@ impl Editor {
 
         if let Some((split_id, buffer_id)) = tab_hit {
             // Open tab context menu
-            self.tab_context_menu = Some(TabContextMenu::new(buffer_id, split_id, col, row + 1));
+            let pinned = self
+                .split_view_states
+                .get(&split_id)
+                .is_some_and(|vs| vs.is_pinned(buffer_id));
+            self.tab_context_menu = Some(TabContextMenu::new(
+                buffer_id,
+                split_id,
+                col,
+                row + 1,
+                pinned,
+            ));
         } else {
             // Click outside tab - close context menu if open
             self.tab_context_menu = None;
@@ -1940,6 +2168,15 @@ impl Editor {
             TabContextMenuItem::CloseAll => {
                 self.close_all_tabs_in_split(split_id);
             }
+            TabContextMenuItem::TogglePin => {
+                self.toggle_tab_pinned(buffer_id, split_id);
+            }
+            TabContextMenuItem::RevealInExplorer => {
+                self.reveal_tab_in_file_explorer(buffer_id, split_id);
+            }
+            TabContextMenuItem::CopyPath => {
+                self.copy_tab_path(buffer_id);
+            }
         }
 
         Ok(())
@@ -2204,3 +2441,23 @@ impl Editor {
         }
     }
 }
+
+/// The word or line around a position, for selections made by double- and
+/// triple-clicks; empty for single clicks
+fn selection_unit_range(
+    buffer: &mut Buffer,
+    granularity: SelectionGranularity,
+    position: usize,
+) -> std::ops::Range<usize> {
+    match granularity {
+        SelectionGranularity::Character => position..position,
+        SelectionGranularity::Word => word_range_at(buffer, position),
+        SelectionGranularity::Line => {
+            // The line includes its newline, like the Select Line action
+            let mut iter = buffer.line_iterator(position, 80);
+            iter.next_line()
+                .map(|(line_start, content)| line_start..line_start + content.len())
+                .unwrap_or(position..position)
+        }
+    }
+}
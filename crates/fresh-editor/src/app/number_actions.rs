@@ -0,0 +1,79 @@
+//! Insert Sequence: numbers counting up across the cursors.
+//!
+//! The prompt takes `start` or `start,step`, and every number is written
+//! like `start` (see [`number_literals::sequence`]). Cursors are numbered in
+//! buffer order and selections are replaced, all as one undo step.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::Event;
+use crate::primitives::number_literals;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Start the prompt for the first number (and step) of a sequence
+    pub(super) fn start_insert_sequence_prompt(&mut self) {
+        if self.is_editing_disabled() {
+            self.set_status_message(self.editing_disabled_message());
+            return;
+        }
+        self.start_prompt_with_initial_text(
+            t!("sequence.prompt").to_string(),
+            PromptType::InsertSequence,
+            "1".to_string(),
+        );
+    }
+
+    /// Handle InsertSequence prompt confirmation.
+    pub(super) fn handle_insert_sequence(&mut self, input: &str) {
+        let mut cursors: Vec<_> = self
+            .active_state()
+            .cursors
+            .iter()
+            .map(|(cursor_id, cursor)| {
+                let range = cursor
+                    .selection_range()
+                    .unwrap_or(cursor.position..cursor.position);
+                (cursor_id, range)
+            })
+            .collect();
+        cursors.sort_by_key(|(_, range)| range.start);
+
+        let Some(numbers) = number_literals::sequence(input, cursors.len()) else {
+            self.set_status_message(t!("sequence.invalid", input = input).to_string());
+            return;
+        };
+
+        // Edit from the end of the buffer so earlier positions stay valid
+        let mut events = Vec::new();
+        for ((cursor_id, range), number) in cursors.into_iter().zip(numbers).rev() {
+            if !range.is_empty() {
+                let deleted_text = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
+                events.push(Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id,
+                });
+            }
+            events.push(Event::Insert {
+                position: range.start,
+                text: number,
+                cursor_id,
+            });
+        }
+
+        if events.len() > 1 {
+            if let Some(bulk_edit) =
+                self.apply_events_as_bulk_edit(events, "Insert sequence".to_string())
+            {
+                self.active_event_log_mut().append(bulk_edit);
+            }
+        } else if let Some(event) = events.into_iter().next() {
+            self.active_event_log_mut().append(event.clone());
+            self.apply_event_to_active_buffer(&event);
+        }
+    }
+}
@@ -11,7 +11,6 @@ use std::time::Duration;
 use super::Editor;
 use crate::config::{FormatterConfig, OnSaveAction};
 use crate::model::event::Event;
-use crate::services::lsp::manager::detect_language;
 use rust_i18n::t;
 
 /// Result of running a formatter or on-save action
@@ -59,7 +58,11 @@ impl Editor {
         }
 
         // Detect language for this file
-        let language = match detect_language(&path, &self.config.languages) {
+        let language = match self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|metadata| metadata.language(&self.config.languages))
+        {
             Some(lang) => lang,
             None => return Ok(ran_any_action),
         };
@@ -133,7 +136,11 @@ impl Editor {
         };
 
         // Detect language for this file
-        let language = match detect_language(&path, &self.config.languages) {
+        let language = match self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .and_then(|metadata| metadata.language(&self.config.languages))
+        {
             Some(lang) => lang,
             None => return Err("No language detected for this file".to_string()),
         };
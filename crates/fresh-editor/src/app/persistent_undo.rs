@@ -0,0 +1,93 @@
+//! Undo history kept across editor restarts
+//!
+//! With `persistent_undo` on, a buffer's undo history is written when its file
+//! is saved and restored when the file is opened again (see
+//! `services::undo_history`). Encrypted files are left out, as their history
+//! holds the decrypted text.
+
+use std::path::Path;
+use std::time::Duration;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::encryption::EncryptionScheme;
+
+impl Editor {
+    /// Write the undo history of a buffer whose file was just saved
+    pub(super) fn persist_undo_history(&self, buffer_id: BufferId) {
+        if !self.config.editor.persistent_undo {
+            return;
+        }
+        let (Some(state), Some(log)) = (
+            self.buffers.get(&buffer_id),
+            self.event_logs.get(&buffer_id),
+        ) else {
+            return;
+        };
+        let Some(path) = state.buffer.file_path() else {
+            return;
+        };
+        if EncryptionScheme::for_path(path).is_some() {
+            return;
+        }
+        // Not available in large file mode
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+
+        let max_bytes = self
+            .config
+            .editor
+            .persistent_undo_max_size_kb
+            .saturating_mul(1024);
+        if let Err(e) = self.undo_store.save(
+            path,
+            content.as_bytes(),
+            log,
+            max_bytes,
+            self.recovery_service.codec(),
+        ) {
+            tracing::warn!("Failed to save undo history of {:?}: {}", path, e);
+        }
+    }
+
+    /// Restore the undo history of a newly opened file, if it still applies
+    pub(super) fn restore_undo_history(&mut self, buffer_id: BufferId, path: &Path) {
+        if !self.config.editor.persistent_undo || EncryptionScheme::for_path(path).is_some() {
+            return;
+        }
+        let Some(content) = self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string())
+        else {
+            return;
+        };
+        if let Some(log) = self.undo_store.load(
+            path,
+            content.as_bytes(),
+            self.persistent_undo_max_age(),
+            self.recovery_service.codec(),
+        ) {
+            tracing::debug!("Restored undo history of {:?}", path);
+            self.event_logs.insert(buffer_id, log);
+        }
+    }
+
+    /// Delete undo histories older than the age limit (called on startup)
+    pub(super) fn remove_expired_undo_histories(&self) {
+        match self
+            .undo_store
+            .remove_expired(self.persistent_undo_max_age())
+        {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Removed {} expired undo histories", n),
+            Err(e) => tracing::warn!("Failed to remove expired undo histories: {}", e),
+        }
+    }
+
+    fn persistent_undo_max_age(&self) -> Duration {
+        let days = self.config.editor.persistent_undo_max_age_days;
+        Duration::from_secs(days.saturating_mul(24 * 60 * 60))
+    }
+}
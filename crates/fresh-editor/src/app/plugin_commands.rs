@@ -1256,6 +1256,8 @@ impl Editor {
         // Convert LanguagePackConfig to the internal LanguageConfig format
         let lang_config = crate::config::LanguageConfig {
             comment_prefix: config.comment_prefix,
+            block_comment_start: config.block_comment_start,
+            block_comment_end: config.block_comment_end,
             auto_indent: config.auto_indent.unwrap_or(true),
             use_tabs: config.use_tabs.unwrap_or(false),
             tab_size: config.tab_size,
@@ -0,0 +1,213 @@
+//! Asking the user for plugin permissions
+//!
+//! When a sandboxed plugin tries something its permissions don't cover (see
+//! `services::plugins::sandbox`), the plugin thread sends a
+//! `RequestPermission` command. Requests queue up here and are asked one at
+//! a time: allow always (saved to the user config), allow for this session,
+//! or deny. A process the plugin wanted to run starts once it's allowed; a
+//! file it wanted to read or write has to be tried again.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::config::PermissionMode;
+use crate::services::plugins::api::{JsCallbackId, PermissionRequest, PluginCommand};
+use crate::services::plugins::sandbox::{permission_scope, Access};
+use crate::view::prompt::PromptType;
+use fresh_core::services::PermissionDecision;
+
+/// A plugin's request for permission, waiting for the user's answer
+pub(super) struct PermissionPrompt {
+    plugin_name: String,
+    request: PermissionRequest,
+    /// Requests waiting on the answer: the command to run once it's
+    /// allowed, and the callback to reject if it's denied
+    waiting: Vec<(
+        PermissionRequest,
+        Option<JsCallbackId>,
+        Option<PluginCommand>,
+    )>,
+}
+
+impl Editor {
+    /// Handle a plugin's request for permission: queue it to be asked, unless
+    /// it's been decided since the plugin asked
+    pub(super) fn handle_request_permission(
+        &mut self,
+        plugin_name: String,
+        request: PermissionRequest,
+        callback_id: Option<JsCallbackId>,
+        then: Option<PluginCommand>,
+    ) {
+        let decision = self
+            .plugin_sandbox
+            .read()
+            .unwrap()
+            .check(&plugin_name, &request);
+        match decision {
+            PermissionDecision::Allow => {
+                if let Some(command) = then {
+                    if let Err(e) = self.handle_plugin_command(command) {
+                        tracing::error!("Error handling TypeScript plugin command: {}", e);
+                    }
+                }
+            }
+            PermissionDecision::Deny => {
+                if let Some(callback_id) = callback_id {
+                    self.plugin_manager
+                        .reject_callback(callback_id, permission_denied(&plugin_name, &request));
+                }
+            }
+            PermissionDecision::Ask => {
+                // A read or write has already failed; only a process waits
+                let waiting = (callback_id.is_some() || then.is_some())
+                    .then(|| (request.clone(), callback_id, then));
+                // Ask once for everything the plugin wants of the same kind
+                let access = Access::of(&request);
+                if let Some(queued) = self.permission_requests.iter_mut().find(|queued| {
+                    queued.plugin_name == plugin_name && Access::of(&queued.request) == access
+                }) {
+                    queued.waiting.extend(waiting);
+                    return;
+                }
+                self.permission_requests.push(PermissionPrompt {
+                    plugin_name,
+                    request,
+                    waiting: waiting.into_iter().collect(),
+                });
+                self.show_permission_prompt();
+            }
+        }
+    }
+
+    /// Ask about the first queued permission request, unless another prompt
+    /// is open. Returns whether the prompt was opened.
+    pub(super) fn show_permission_prompt(&mut self) -> bool {
+        if self.is_prompting() {
+            return false;
+        }
+        let Some(next) = self.permission_requests.first() else {
+            return false;
+        };
+        let plugin = next.plugin_name.clone();
+        let message = match &next.request {
+            PermissionRequest::Read(path) => {
+                let dir = permission_scope(path, &self.working_dir);
+                t!(
+                    "plugin_permission.read",
+                    plugin = &plugin,
+                    dir = dir.display().to_string()
+                )
+            }
+            PermissionRequest::Write(path) => {
+                let dir = permission_scope(path, &self.working_dir);
+                t!(
+                    "plugin_permission.write",
+                    plugin = &plugin,
+                    dir = dir.display().to_string()
+                )
+            }
+            PermissionRequest::Subprocess(program) => {
+                t!(
+                    "plugin_permission.subprocess",
+                    plugin = &plugin,
+                    program = program
+                )
+            }
+        };
+        self.start_prompt(message.to_string(), PromptType::PluginPermission);
+        true
+    }
+
+    /// Handle PluginPermission prompt confirmation (cancelling denies).
+    pub(super) fn handle_plugin_permission(&mut self, input: &str) {
+        if self.permission_requests.is_empty() {
+            return;
+        }
+        let PermissionPrompt {
+            plugin_name,
+            request,
+            waiting,
+        } = self.permission_requests.remove(0);
+        let scope = match &request {
+            PermissionRequest::Read(path) | PermissionRequest::Write(path) => {
+                permission_scope(path, &self.working_dir)
+            }
+            PermissionRequest::Subprocess(_) => Default::default(),
+        };
+
+        let input_lower = input.trim().to_lowercase();
+        if input_lower == "a" || input_lower == "always" {
+            let permissions = &mut self
+                .config
+                .plugins
+                .entry(plugin_name.clone())
+                .or_default()
+                .permissions;
+            match &request {
+                PermissionRequest::Read(_) => permissions.read.push(scope),
+                PermissionRequest::Write(_) => permissions.write.push(scope),
+                PermissionRequest::Subprocess(_) => permissions.subprocess = PermissionMode::Allow,
+            }
+            self.plugin_sandbox
+                .write()
+                .unwrap()
+                .update_config(&self.config);
+            match self.save_config() {
+                Ok(()) => self.set_status_message(
+                    t!("plugin_permission.allowed", plugin = &plugin_name).to_string(),
+                ),
+                Err(e) => self
+                    .set_status_message(t!("plugin_permission.save_failed", error = e).to_string()),
+            }
+        } else if input_lower == "s" || input_lower == "session" {
+            self.plugin_sandbox
+                .write()
+                .unwrap()
+                .allow_for_session(&plugin_name, &request, scope);
+            self.set_status_message(
+                t!("plugin_permission.allowed_session", plugin = &plugin_name).to_string(),
+            );
+        } else {
+            self.plugin_sandbox
+                .write()
+                .unwrap()
+                .deny_for_session(&plugin_name, &request);
+            self.set_status_message(
+                t!("plugin_permission.denied", plugin = &plugin_name).to_string(),
+            );
+        }
+
+        // Run what was waiting on the answer
+        for (request, callback_id, then) in waiting {
+            self.handle_request_permission(plugin_name.clone(), request, callback_id, then);
+        }
+        self.show_permission_prompt();
+    }
+}
+
+/// The error a plugin gets when it isn't allowed to do something
+fn permission_denied(plugin_name: &str, request: &PermissionRequest) -> String {
+    match request {
+        PermissionRequest::Read(path) => {
+            format!(
+                "Plugin '{}' is not allowed to read {}",
+                plugin_name,
+                path.display()
+            )
+        }
+        PermissionRequest::Write(path) => {
+            format!(
+                "Plugin '{}' is not allowed to write {}",
+                plugin_name,
+                path.display()
+            )
+        }
+        PermissionRequest::Subprocess(program) => {
+            format!(
+                "Plugin '{}' is not allowed to run '{}'",
+                plugin_name, program
+            )
+        }
+    }
+}
@@ -69,6 +69,70 @@ impl Editor {
             return PopupConfirmResult::EarlyReturn;
         }
 
+        // Check if this is the spell suggestions popup
+        if let Some((buffer_id, range)) = self.pending_spell_suggestion.take() {
+            let choice = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+            self.hide_popup();
+            if let Some(choice) = choice {
+                self.handle_spell_suggestion_response(buffer_id, range, &choice);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
+        // Check if this is the color adjuster popup
+        if let Some((buffer_id, range)) = self.pending_color_adjustment.take() {
+            let choice = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+            self.hide_popup();
+            if let Some(choice) = choice {
+                self.handle_color_adjustment_response(buffer_id, range, &choice);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
+        // Check if this is a breadcrumb dropdown
+        if let Some(dropdown) = self.breadcrumb_dropdown.take() {
+            let choice = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+            self.hide_popup();
+            if let Some(choice) = choice {
+                self.handle_breadcrumb_choice(dropdown, &choice);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
+        // Check if this is the preview of edits for a file rename
+        if self
+            .pending_file_rename
+            .as_ref()
+            .is_some_and(|pending| pending.is_previewed())
+        {
+            let choice = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+            self.hide_popup();
+            if let (Some(pending), Some(choice)) = (self.pending_file_rename.take(), choice) {
+                self.handle_file_rename_choice(pending, &choice);
+            }
+            return PopupConfirmResult::EarlyReturn;
+        }
+
         // If it's a completion popup, insert the selected item
         let completion_text = if let Some(popup) = self.active_state().popups.top() {
             if let Some(title) = &popup.title {
@@ -195,6 +259,16 @@ impl Editor {
             self.pending_lsp_confirmation = None;
             self.set_status_message(t!("lsp.startup_cancelled_msg").to_string());
         }
+        self.pending_spell_suggestion = None;
+        self.pending_color_adjustment = None;
+        self.breadcrumb_dropdown = None;
+        if self
+            .pending_file_rename
+            .take_if(|pending| pending.is_previewed())
+            .is_some()
+        {
+            self.set_status_message(t!("explorer.rename_cancelled").to_string());
+        }
         self.hide_popup();
         // Clear completion items when popup is closed
         self.completion_items = None;
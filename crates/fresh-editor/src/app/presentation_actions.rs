@@ -0,0 +1,294 @@
+//! Presentation mode.
+//!
+//! Start Presenting broadcasts the active buffer, its scroll position and the
+//! cursor to read-only viewers, following the presenter from buffer to
+//! buffer. Viewers open the address in a browser, or run Follow Presentation
+//! in another editor to get a read-only buffer that tracks the presenter.
+
+use std::time::{Duration, Instant};
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::collab::presentation::{
+    PresentationMessage, PresentationServer, PresentationViewer,
+};
+use crate::view::prompt::PromptType;
+
+/// Address suggested when presenting (this machine only; other machines can
+/// watch once the presenter enters an address they can reach)
+const DEFAULT_PRESENT_ADDRESS: &str = "127.0.0.1:7071";
+
+/// Address suggested when following
+const DEFAULT_FOLLOW_ADDRESS: &str = "localhost:7071";
+
+/// Shortest time between two broadcasts of the buffer text while typing
+const BUFFER_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A presentation being given or followed
+pub(super) enum PresentationState {
+    Presenting(Presenter),
+    Following(Follower),
+}
+
+pub(super) struct Presenter {
+    server: PresentationServer,
+    /// Buffer and version viewers last got
+    buffer: Option<(BufferId, u64)>,
+    buffer_sent_at: Instant,
+    /// Top line, cursor and anchor viewers last got
+    view: Option<(usize, usize, Option<usize>)>,
+    /// Encrypted buffer being kept from viewers, once the presenter was told
+    withheld: Option<BufferId>,
+}
+
+pub(super) struct Follower {
+    viewer: PresentationViewer,
+    /// Read-only buffer showing the presented text
+    buffer_id: Option<BufferId>,
+}
+
+impl Editor {
+    /// Start the prompt for the address to present on
+    pub(super) fn start_presenting_prompt(&mut self) {
+        if self.presentation.is_some() {
+            self.set_status_message(t!("presentation.already_active").to_string());
+            return;
+        }
+        self.start_prompt_with_initial_text(
+            t!("presentation.start_prompt").to_string(),
+            PromptType::StartPresenting,
+            DEFAULT_PRESENT_ADDRESS.to_string(),
+        );
+    }
+
+    /// Start the prompt for the address of a presentation to follow
+    pub(super) fn start_follow_presentation_prompt(&mut self) {
+        if self.presentation.is_some() {
+            self.set_status_message(t!("presentation.already_active").to_string());
+            return;
+        }
+        self.start_prompt_with_initial_text(
+            t!("presentation.follow_prompt").to_string(),
+            PromptType::FollowPresentation,
+            DEFAULT_FOLLOW_ADDRESS.to_string(),
+        );
+    }
+
+    /// Handle StartPresenting prompt confirmation.
+    pub(super) fn handle_start_presenting(&mut self, input: &str) {
+        match PresentationServer::start(input.trim()) {
+            Ok(server) => {
+                let address = server.local_addr();
+                self.presentation = Some(PresentationState::Presenting(Presenter {
+                    server,
+                    buffer: None,
+                    buffer_sent_at: self.time_source.now(),
+                    view: None,
+                    withheld: None,
+                }));
+                self.set_status_message(
+                    t!("presentation.started", address = address.to_string()).to_string(),
+                );
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("presentation.error", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Handle FollowPresentation prompt confirmation.
+    pub(super) fn handle_follow_presentation(&mut self, input: &str) {
+        let address = input.trim();
+        match PresentationViewer::connect(address) {
+            Ok(viewer) => {
+                self.presentation = Some(PresentationState::Following(Follower {
+                    viewer,
+                    buffer_id: None,
+                }));
+                self.set_status_message(
+                    t!("presentation.following", address = address).to_string(),
+                );
+            }
+            Err(e) => {
+                self.set_status_message(
+                    t!("presentation.error", error = e.to_string()).to_string(),
+                );
+            }
+        }
+    }
+
+    /// Stop presenting or following
+    pub fn stop_presentation(&mut self) {
+        if self.presentation.take().is_some() {
+            self.set_status_message(t!("presentation.stopped").to_string());
+        } else {
+            self.set_status_message(t!("presentation.not_active").to_string());
+        }
+    }
+
+    /// Address the presentation is served on, when presenting
+    pub fn presentation_address(&self) -> Option<std::net::SocketAddr> {
+        match &self.presentation {
+            Some(PresentationState::Presenting(presenter)) => Some(presenter.server.local_addr()),
+            _ => None,
+        }
+    }
+
+    /// Broadcast what changed since the last frame when presenting, or follow
+    /// the presenter when viewing. Called once per frame.
+    ///
+    /// Returns true if anything on screen changed.
+    pub fn sync_presentation(&mut self) -> bool {
+        match self.presentation.take() {
+            Some(PresentationState::Presenting(mut presenter)) => {
+                self.broadcast_presentation(&mut presenter);
+                self.presentation = Some(PresentationState::Presenting(presenter));
+                false
+            }
+            Some(PresentationState::Following(mut follower)) => {
+                let Some(messages) = follower.viewer.poll() else {
+                    self.set_status_message(t!("presentation.presenter_stopped").to_string());
+                    return true;
+                };
+                let changed = !messages.is_empty();
+                for message in messages {
+                    self.follow_presentation(&mut follower, message);
+                }
+                self.presentation = Some(PresentationState::Following(follower));
+                changed
+            }
+            None => false,
+        }
+    }
+
+    fn broadcast_presentation(&mut self, presenter: &mut Presenter) {
+        let buffer_id = self.active_buffer();
+        // Decrypted text never leaves the editor; viewers keep what they had
+        if self.active_state().buffer.encryption().is_some() {
+            if presenter.withheld != Some(buffer_id) {
+                presenter.withheld = Some(buffer_id);
+                let name = self.get_buffer_display_name(buffer_id);
+                self.set_status_message(t!("presentation.encrypted", name = name).to_string());
+            }
+            return;
+        }
+        presenter.withheld = None;
+
+        let version = self.active_state().buffer.revision();
+        let now = self.time_source.now();
+        let switched = presenter.buffer.map(|(id, _)| id) != Some(buffer_id);
+        let edited = presenter.buffer != Some((buffer_id, version));
+        if switched || (edited && now.duration_since(presenter.buffer_sent_at) >= BUFFER_INTERVAL) {
+            // Buffers too large to hold in memory are not presented
+            if let Some(text) = self.active_state().buffer.to_string() {
+                presenter.server.publish(&PresentationMessage::Buffer {
+                    name: self.get_buffer_display_name(buffer_id),
+                    text,
+                });
+                presenter.buffer = Some((buffer_id, version));
+                presenter.buffer_sent_at = now;
+                // The view goes out again after the text it refers to
+                presenter.view = None;
+            }
+        }
+        if presenter.buffer != Some((buffer_id, version)) {
+            return;
+        }
+
+        let top_byte = self.active_viewport().top_byte;
+        let state = self.active_state();
+        let primary = state.cursors.primary();
+        let view = (
+            state.buffer.get_line_number(top_byte),
+            primary.position,
+            primary.anchor,
+        );
+        if presenter.view != Some(view) {
+            presenter.view = Some(view);
+            let (top_line, cursor, anchor) = view;
+            presenter.server.publish(&PresentationMessage::View {
+                top_line,
+                cursor,
+                anchor,
+            });
+        }
+    }
+
+    fn follow_presentation(&mut self, follower: &mut Follower, message: PresentationMessage) {
+        match message {
+            PresentationMessage::Buffer { name, text } => {
+                let display_name = format!("*presentation: {}*", name);
+                let buffer_id = match follower
+                    .buffer_id
+                    .filter(|id| self.buffers.contains_key(id))
+                {
+                    Some(id) => {
+                        if let Some(metadata) = self.buffer_metadata.get_mut(&id) {
+                            if metadata.display_name != display_name {
+                                metadata.display_name = display_name.clone();
+                                if let Some(state) = self.buffers.get_mut(&id) {
+                                    state.set_language_from_name(
+                                        &display_name,
+                                        &self.grammar_registry,
+                                    );
+                                }
+                            }
+                        }
+                        id
+                    }
+                    None => {
+                        let id = self.create_virtual_buffer(
+                            display_name,
+                            "presentation".to_string(),
+                            true,
+                        );
+                        if let Some(state) = self.buffers.get_mut(&id) {
+                            state.editing_disabled = true;
+                        }
+                        self.set_active_buffer(id);
+                        follower.buffer_id = Some(id);
+                        id
+                    }
+                };
+                if let Err(e) =
+                    self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(text)])
+                {
+                    tracing::warn!("Failed to show presented buffer: {}", e);
+                }
+                self.invalidate_layouts_for_buffer(buffer_id);
+            }
+            PresentationMessage::View {
+                top_line,
+                cursor,
+                anchor,
+            } => {
+                let Some(buffer_id) = follower.buffer_id else {
+                    return;
+                };
+                let Some(state) = self.buffers.get_mut(&buffer_id) else {
+                    return;
+                };
+                let len = state.buffer.len();
+                let cursor = state.buffer.snap_to_char_boundary(cursor.min(len));
+                let anchor =
+                    anchor.map(|anchor| state.buffer.snap_to_char_boundary(anchor.min(len)));
+                state.cursors.primary_mut().position = cursor;
+                state.cursors.primary_mut().anchor = anchor;
+                for split_id in self.split_manager.splits_for_buffer(buffer_id) {
+                    if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                        view_state.cursors.primary_mut().position = cursor;
+                        view_state.cursors.primary_mut().anchor = anchor;
+                        view_state.viewport.scroll_to(&mut state.buffer, top_line);
+                        // Keep the presenter's scroll position rather than the cursor's
+                        view_state.viewport.set_skip_ensure_visible();
+                    }
+                }
+            }
+        }
+    }
+}
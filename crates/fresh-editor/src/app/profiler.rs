@@ -0,0 +1,285 @@
+//! The Profiler buffer: frame times, render time per subsystem, input latency,
+//! LSP response times, memory and decoration counts (see
+//! `services::profiler`). It's updated once a second while it's shown, so it
+//! can be watched while reproducing a slowdown.
+
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::file_open::format_size;
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::profiler::{self, LspRequestKind, Subsystem, Timing, LATENCY_BUCKETS_MS};
+
+/// Mode name for the Profiler buffer
+const PROFILER_MODE: &str = "profiler";
+
+/// How often the Profiler buffer is updated while it's shown
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Width of the longest latency histogram bar
+const HISTOGRAM_WIDTH: u64 = 40;
+
+impl Editor {
+    /// Show the Profiler buffer
+    pub fn show_profiler(&mut self) -> BufferId {
+        let buffer_id = self.profiler_buffer().unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                t!("profiler.title").to_string(),
+                PROFILER_MODE.to_string(),
+                true,
+            )
+        });
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+        self.write_profiler(buffer_id);
+        buffer_id
+    }
+
+    /// Record that the last frame took `elapsed` to render and write to the
+    /// terminal, and check it against the frame budget
+    pub fn record_frame_drawn(&mut self, elapsed: Duration) {
+        self.profiler.finish_output(elapsed);
+        self.check_frame_budget(elapsed);
+    }
+
+    /// Record how long an input event waited until a frame showed it
+    pub fn record_input_latency(&mut self, latency: Duration) {
+        self.profiler.record_latency(latency);
+    }
+
+    /// Update the Profiler buffer if it's shown and hasn't been updated for
+    /// a second. Returns whether it was.
+    pub(super) fn update_profiler(&mut self) -> bool {
+        let Some(buffer_id) = self.profiler_buffer() else {
+            return false;
+        };
+        if self.split_manager.splits_for_buffer(buffer_id).is_empty() {
+            return false;
+        }
+        let now = self.time_source.now();
+        if self
+            .profiler_updated_at
+            .is_some_and(|updated_at| now.duration_since(updated_at) < UPDATE_INTERVAL)
+        {
+            return false;
+        }
+        self.write_profiler(buffer_id);
+        true
+    }
+
+    fn profiler_buffer(&self) -> Option<BufferId> {
+        self.buffer_metadata.iter().find_map(|(id, metadata)| {
+            (metadata.virtual_mode() == Some(PROFILER_MODE)).then_some(*id)
+        })
+    }
+
+    fn write_profiler(&mut self, buffer_id: BufferId) {
+        let text = self.profiler_report();
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(text)])
+        {
+            tracing::warn!("Failed to update the profiler: {}", e);
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+        self.profiler_updated_at = Some(self.time_source.now());
+    }
+
+    fn profiler_report(&self) -> String {
+        let mut report = format!("{}\n\n", t!("profiler.hint"));
+
+        report.push_str(&format!("{}\n", t!("profiler.frames")));
+        report.push_str(&table(
+            &[
+                vec![
+                    t!("profiler.frames_drawn").to_string(),
+                    self.profiler.frame_count().to_string(),
+                ],
+                vec![
+                    t!("profiler.frame_time").to_string(),
+                    format_timing(self.profiler.frame_timing()),
+                ],
+            ],
+            &[],
+        ));
+
+        report.push_str(&format!("\n{}\n", t!("profiler.render_time")));
+        let subsystems: Vec<_> = Subsystem::ALL
+            .iter()
+            .map(|&subsystem| {
+                vec![
+                    subsystem_name(subsystem),
+                    format_timing(self.profiler.subsystem_timing(subsystem)),
+                ]
+            })
+            .collect();
+        report.push_str(&table(&subsystems, &[]));
+
+        report.push_str(&format!("\n{}\n", t!("profiler.latency")));
+        let histogram = self.profiler.latency_histogram();
+        let most = histogram.iter().copied().max().unwrap_or(0).max(1);
+        let buckets: Vec<_> = histogram
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| {
+                let label = match LATENCY_BUCKETS_MS.get(i) {
+                    Some(bound) => format!("< {} ms", bound),
+                    None => format!(">= {} ms", LATENCY_BUCKETS_MS[i - 1]),
+                };
+                let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(most) as usize);
+                vec![label, count.to_string(), bar]
+            })
+            .collect();
+        report.push_str(&table(&buckets, &[1]));
+
+        report.push_str(&format!("\n{}\n", t!("profiler.lsp_requests")));
+        let mut requests = vec![vec![
+            t!("profiler.column_request").to_string(),
+            t!("profiler.column_answered").to_string(),
+            t!("profiler.column_cancelled").to_string(),
+            t!("profiler.column_response_time").to_string(),
+        ]];
+        requests.extend(LspRequestKind::ALL.iter().map(|&kind| {
+            let (answered, cancelled) = self.profiler.lsp_request_counts(kind);
+            vec![
+                lsp_request_name(kind),
+                answered.to_string(),
+                cancelled.to_string(),
+                format_timing(self.profiler.lsp_response_timing(kind)),
+            ]
+        }));
+        report.push_str(&table(&requests, &[1, 2]));
+
+        let mut ids: Vec<_> = self.buffers.keys().copied().collect();
+        ids.sort_by_key(|id| id.0);
+        let mut total_memory = 0;
+        let mut rows = vec![vec![
+            t!("profiler.column_buffer").to_string(),
+            t!("profiler.column_size").to_string(),
+            t!("profiler.column_memory").to_string(),
+            t!("profiler.column_undo").to_string(),
+            t!("profiler.column_overlays").to_string(),
+            t!("profiler.column_markers").to_string(),
+            t!("profiler.column_virtual_text").to_string(),
+        ]];
+        for id in ids {
+            let state = &self.buffers[&id];
+            let memory = state.buffer.memory_usage();
+            total_memory += memory;
+            let name = self
+                .buffer_metadata
+                .get(&id)
+                .map(|metadata| metadata.display_name.clone())
+                .unwrap_or_default();
+            rows.push(vec![
+                name,
+                format_size(state.buffer.len() as u64),
+                format_size(memory as u64),
+                self.event_logs
+                    .get(&id)
+                    .map_or(0, |log| log.len())
+                    .to_string(),
+                state.overlays.len().to_string(),
+                state.marker_list.marker_count().to_string(),
+                state.virtual_texts.len().to_string(),
+            ]);
+        }
+
+        report.push_str(&format!("\n{}\n", t!("profiler.memory")));
+        let plugin_lines: usize = self.seen_byte_ranges.values().map(|seen| seen.len()).sum();
+        report.push_str(&table(
+            &[
+                vec![
+                    t!("profiler.process_memory").to_string(),
+                    profiler::resident_memory()
+                        .map(|bytes| format_size(bytes as u64))
+                        .unwrap_or_else(|| t!("profiler.unknown").to_string()),
+                ],
+                vec![
+                    t!("profiler.buffer_memory").to_string(),
+                    format_size(total_memory as u64),
+                ],
+                vec![
+                    t!("profiler.plugin_line_cache").to_string(),
+                    t!("profiler.lines", count = plugin_lines).to_string(),
+                ],
+            ],
+            &[],
+        ));
+
+        report.push_str(&format!("\n{}\n", t!("profiler.buffers")));
+        report.push_str(&table(&rows, &[1, 2, 3, 4, 5, 6]));
+        report
+    }
+}
+
+fn subsystem_name(subsystem: Subsystem) -> String {
+    match subsystem {
+        Subsystem::Prepare => t!("profiler.prepare"),
+        Subsystem::FileExplorer => t!("profiler.file_explorer"),
+        Subsystem::PluginHooks => t!("profiler.plugin_hooks"),
+        Subsystem::Content => t!("profiler.content"),
+        Subsystem::Chrome => t!("profiler.chrome"),
+        Subsystem::Output => t!("profiler.output"),
+    }
+    .to_string()
+}
+
+fn lsp_request_name(kind: LspRequestKind) -> String {
+    match kind {
+        LspRequestKind::Completion => t!("profiler.completion"),
+        LspRequestKind::Hover => t!("profiler.hover"),
+        LspRequestKind::Definition => t!("profiler.definition"),
+        LspRequestKind::References => t!("profiler.references"),
+    }
+    .to_string()
+}
+
+fn format_timing(timing: Option<Timing>) -> String {
+    let ms = |duration: Duration| format!("{:.2} ms", duration.as_secs_f64() * 1000.0);
+    match timing {
+        Some(timing) => t!(
+            "profiler.timing",
+            average = ms(timing.average),
+            max = ms(timing.max)
+        )
+        .to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Indented rows with aligned columns, the `numeric` ones right-aligned
+fn table(rows: &[Vec<String>], numeric: &[usize]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    let padding = " ".repeat(widths[column] - cell.chars().count());
+                    if numeric.contains(&column) {
+                        format!("{}{}", padding, cell)
+                    } else {
+                        format!("{}{}", cell, padding)
+                    }
+                })
+                .collect();
+            format!("  {}\n", cells.join("  ").trim_end())
+        })
+        .collect()
+}
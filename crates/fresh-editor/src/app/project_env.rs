@@ -0,0 +1,135 @@
+//! The project's environment (see `services::project_env`): loaded on
+//! startup and when the workspace is trusted, and given to the language
+//! servers, terminals and tasks started afterwards. The Project Environment
+//! buffer lists the variables it sets, the files they came from and what
+//! failed to load; `g` reloads it, e.g. after `direnv allow` or editing
+//! `.env`.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::project_env::ProjectEnv;
+
+/// Mode name for the Project Environment buffer
+const PROJECT_ENV_MODE: &str = "project-env";
+
+impl Editor {
+    /// Load the project's environment for the processes started from now on.
+    /// An untrusted workspace's isn't loaded.
+    pub(super) fn load_project_env(&mut self) {
+        let env = if self.workspace_trusted {
+            ProjectEnv::load(&self.working_dir)
+        } else {
+            ProjectEnv::default()
+        };
+        for error in env.errors() {
+            tracing::warn!("Failed to load the project environment: {}", error);
+        }
+        if let Some(lsp) = self.lsp.as_mut() {
+            lsp.set_project_env(env.clone());
+        }
+        self.terminal_manager.set_project_env(env.clone());
+        self.project_env = env;
+    }
+
+    /// Load the project's environment again, updating its buffer if it's open
+    pub(super) fn reload_project_env(&mut self) {
+        self.load_project_env();
+        if let Some(buffer_id) = self.project_env_buffer() {
+            self.write_project_env(buffer_id);
+        }
+        let message = if !self.workspace_trusted {
+            t!("project_env.safe_mode").to_string()
+        } else if let Some(error) = self.project_env.errors().first() {
+            t!("project_env.reload_failed", error = error).to_string()
+        } else {
+            t!(
+                "project_env.reloaded",
+                count = self.project_env.vars().len()
+            )
+            .to_string()
+        };
+        self.set_status_message(message);
+    }
+
+    /// Show the Project Environment buffer
+    pub fn show_project_env(&mut self) -> BufferId {
+        let buffer_id = self.project_env_buffer().unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                t!("project_env.title").to_string(),
+                PROJECT_ENV_MODE.to_string(),
+                true,
+            )
+        });
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+        self.write_project_env(buffer_id);
+        buffer_id
+    }
+
+    fn project_env_buffer(&self) -> Option<BufferId> {
+        self.buffer_metadata.iter().find_map(|(id, metadata)| {
+            (metadata.virtual_mode() == Some(PROJECT_ENV_MODE)).then_some(*id)
+        })
+    }
+
+    fn write_project_env(&mut self, buffer_id: BufferId) {
+        let text = self.project_env_text();
+        if let Err(e) =
+            self.set_virtual_buffer_content(buffer_id, vec![TextPropertyEntry::text(text)])
+        {
+            tracing::warn!("Failed to update the project environment: {}", e);
+            return;
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+    }
+
+    fn project_env_text(&self) -> String {
+        let mut text = format!(
+            "{}\n{}\n\n",
+            t!(
+                "project_env.header",
+                dir = self.working_dir.display().to_string()
+            ),
+            t!("project_env.hint")
+        );
+        if !self.workspace_trusted {
+            text.push_str(&format!("{}\n", t!("project_env.safe_mode")));
+            return text;
+        }
+
+        let env = &self.project_env;
+        if env.sources().is_empty() {
+            text.push_str(&format!("{}\n", t!("project_env.no_sources")));
+        } else {
+            text.push_str(&format!(
+                "{}\n",
+                t!("project_env.sources", sources = env.sources().join(", "))
+            ));
+        }
+        if !env.errors().is_empty() {
+            text.push_str(&format!("\n-- {} --\n", t!("project_env.errors")));
+            for error in env.errors() {
+                text.push_str(&format!("{}\n", error));
+            }
+        }
+
+        text.push_str(&format!("\n-- {} --\n", t!("project_env.variables")));
+        if env.vars().is_empty() {
+            text.push_str(&format!("{}\n", t!("project_env.no_variables")));
+        }
+        for (key, value) in env.vars() {
+            match value {
+                Some(value) => text.push_str(&format!("{}={}\n", key, value.escape_debug())),
+                None => text.push_str(&format!("{} {}\n", key, t!("project_env.unset"))),
+            }
+        }
+        text.push_str(&format!("\n{}\n", t!("project_env.running_note")));
+        text
+    }
+}
@@ -12,6 +12,7 @@ use crate::input::keybindings::Action;
 use crate::primitives::path_utils::expand_tilde;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
+use crate::view::ui::status_bar::indentation_label;
 
 /// Result of handling a prompt confirmation.
 pub enum PromptResult {
@@ -43,14 +44,20 @@ impl Editor {
                     normalize_path(&self.working_dir.join(&expanded_path))
                 };
 
-                if let Err(e) = self.open_file(&resolved_path) {
-                    self.set_status_message(
+                let is_new_file = !self.filesystem.exists(&resolved_path);
+                match self.open_file(&resolved_path) {
+                    Ok(buffer_id) => {
+                        self.set_status_message(
+                            t!("buffer.opened", name = resolved_path.display().to_string())
+                                .to_string(),
+                        );
+                        if is_new_file {
+                            self.offer_file_templates(buffer_id);
+                        }
+                    }
+                    Err(e) => self.set_status_message(
                         t!("file.error_opening", error = e.to_string()).to_string(),
-                    );
-                } else {
-                    self.set_status_message(
-                        t!("buffer.opened", name = resolved_path.display().to_string()).to_string(),
-                    );
+                    ),
                 }
             }
             PromptType::SwitchProject => {
@@ -74,6 +81,15 @@ impl Editor {
                     );
                 }
             }
+            PromptType::AddWorkspaceFolder => {
+                let expanded_path = expand_tilde(&input);
+                let resolved_path = if expanded_path.is_absolute() {
+                    normalize_path(&expanded_path)
+                } else {
+                    normalize_path(&self.working_dir.join(&expanded_path))
+                };
+                self.add_workspace_folder(resolved_path);
+            }
             PromptType::SaveFileAs => {
                 self.handle_save_file_as(&input);
             }
@@ -144,6 +160,9 @@ impl Editor {
                 // Handle Quick Open confirmation based on prefix
                 return self.handle_quick_open_confirm(&input, selected_index);
             }
+            PromptType::GotoAnything => {
+                return self.handle_goto_anything_confirm(&input, selected_index);
+            }
             PromptType::SetBackgroundFile => {
                 if let Err(e) = self.load_ansi_background(&input) {
                     self.set_status_message(
@@ -299,8 +318,22 @@ impl Editor {
             }
             PromptType::ConfirmCloseBuffer { buffer_id } => {
                 if self.handle_confirm_close_buffer(&input, buffer_id) {
+                    self.close_review_queue.clear();
                     return PromptResult::EarlyReturn;
                 }
+                self.review_next_unsaved_buffer();
+            }
+            PromptType::ConfirmEditLockedFile { buffer_id } => {
+                self.handle_confirm_edit_locked_file(buffer_id, &input);
+            }
+            PromptType::TrustWorkspace => {
+                self.handle_trust_workspace(&input);
+            }
+            PromptType::PluginPermission => {
+                self.handle_plugin_permission(&input);
+            }
+            PromptType::CloseUnsavedBuffers { buffers } => {
+                self.handle_close_unsaved_buffers(buffers, &input);
             }
             PromptType::ConfirmQuitWithModified => {
                 let input_lower = input.trim().to_lowercase();
@@ -337,6 +370,27 @@ impl Editor {
             PromptType::StopLspServer => {
                 self.handle_stop_lsp_server(&input);
             }
+            PromptType::RemoveWorkspaceFolder => {
+                self.remove_workspace_folder(std::path::Path::new(input.trim()));
+            }
+            PromptType::SelectFileTemplate { buffer_id } => {
+                self.handle_select_file_template(buffer_id, &input);
+            }
+            PromptType::RunTask => {
+                let name = input.trim();
+                if !name.is_empty() {
+                    self.run_task(name);
+                }
+            }
+            PromptType::RecoverySelect => {
+                self.handle_recovery_select(&input);
+            }
+            PromptType::RecoveryAction { id } => {
+                self.handle_recovery_action(&id, &input);
+            }
+            PromptType::ShowConfigSource => {
+                self.show_config_source(input.trim());
+            }
             PromptType::SelectTheme { .. } => {
                 self.apply_theme(input.trim());
             }
@@ -367,6 +421,18 @@ impl Editor {
             PromptType::SetTabSize => {
                 self.handle_set_tab_size(&input);
             }
+            PromptType::InsertSequence => {
+                self.handle_insert_sequence(&input);
+            }
+            PromptType::RegexSelect { split } => {
+                self.handle_regex_select(&input, split);
+            }
+            PromptType::ExtractName { kind } => {
+                self.handle_extract_name(kind, &input);
+            }
+            PromptType::SetIndentation => {
+                self.handle_set_indentation(&input);
+            }
             PromptType::SetLineEnding => {
                 self.handle_set_line_ending(&input);
             }
@@ -376,6 +442,49 @@ impl Editor {
             PromptType::SetLanguage => {
                 self.handle_set_language(&input);
             }
+            PromptType::SetSpellLanguage => {
+                self.handle_set_spell_language(&input);
+            }
+            PromptType::NewScratchBuffer => {
+                self.handle_new_scratch_buffer(&input);
+            }
+            PromptType::CollabHost => {
+                self.handle_collab_host(&input);
+            }
+            PromptType::CollabJoin => {
+                self.handle_collab_join(&input);
+            }
+            PromptType::CompareFoldersLeft => {
+                if let Some(left) = self.compare_folders_prompt_path(&input) {
+                    self.start_prompt(
+                        t!("dir_diff.right_prompt", left = left.display().to_string()).to_string(),
+                        PromptType::CompareFoldersRight { left },
+                    );
+                }
+            }
+            PromptType::CompareFoldersRight { left } => {
+                if let Some(right) = self.compare_folders_prompt_path(&input) {
+                    if let Err(e) = self.open_dir_diff(&left, &right) {
+                        self.set_status_message(e.to_string());
+                    }
+                }
+            }
+            PromptType::StartPresenting => {
+                self.handle_start_presenting(&input);
+            }
+            PromptType::FollowPresentation => {
+                self.handle_follow_presentation(&input);
+            }
+            PromptType::DecryptPassphrase { buffer_id }
+            | PromptType::DecryptIdentity { buffer_id } => {
+                self.handle_decrypt(buffer_id, &input);
+            }
+            PromptType::JumpList => {
+                self.handle_jump_list_selection(&input);
+            }
+            PromptType::SelectTag => {
+                self.handle_tag_selection(&input);
+            }
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
@@ -436,7 +545,15 @@ impl Editor {
             before_len
         );
 
-        match self.active_state_mut().buffer.save_to_file(&full_path) {
+        // Save As may overwrite an existing file (after confirmation)
+        if let Err(e) = self.backup_before_save(&full_path) {
+            tracing::warn!("Failed to back up {:?}: {}", full_path, e);
+        }
+
+        let save_strategy = self.config.editor.save_strategy;
+        let buffer = &mut self.active_state_mut().buffer;
+        buffer.set_save_strategy(save_strategy);
+        match buffer.save_to_file(&full_path) {
             Ok(()) => {
                 let after_save_idx = self.active_event_log().current_index();
                 let after_save_len = self.active_event_log().len();
@@ -495,6 +612,7 @@ impl Editor {
                     } else {
                         self.set_status_message(t!("buffer.saved_and_closed").to_string());
                     }
+                    self.review_next_unsaved_buffer();
                 } else {
                     self.set_status_message(
                         t!("file.saved_as", path = full_path.display().to_string()).to_string(),
@@ -565,6 +683,41 @@ impl Editor {
         }
     }
 
+    /// Handle SetIndentation prompt confirmation: `tabs`, `spaces:N`,
+    /// `detect` or `default`.
+    fn handle_set_indentation(&mut self, input: &str) {
+        let buffer_id = self.active_buffer();
+        let configured = self.configured_buffer_settings(buffer_id);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        match input.trim() {
+            "tabs" => state.use_tabs = true,
+            "detect" => {
+                if state.detect_indentation().is_none() {
+                    self.set_status_message(t!("indentation.not_detected").to_string());
+                    return;
+                }
+            }
+            "default" => {
+                state.use_tabs = configured.use_tabs;
+                state.tab_size = configured.tab_size;
+            }
+            other => match other.strip_prefix("spaces:").map(str::parse::<usize>) {
+                Some(Ok(width)) if width > 0 => {
+                    state.use_tabs = false;
+                    state.tab_size = width;
+                }
+                _ => {
+                    self.set_status_message(t!("indentation.invalid", input = input).to_string());
+                    return;
+                }
+            },
+        }
+        let value = indentation_label(state.use_tabs, state.tab_size);
+        self.set_status_message(t!("indentation.set", value = value).to_string());
+    }
+
     /// Handle SetLineEnding prompt confirmation.
     fn handle_set_line_ending(&mut self, input: &str) {
         use crate::model::buffer::LineEnding;
@@ -626,9 +779,8 @@ impl Editor {
     }
 
     /// Handle SetLanguage prompt confirmation.
-    fn handle_set_language(&mut self, input: &str) {
+    pub(super) fn handle_set_language(&mut self, input: &str) {
         use crate::primitives::highlight_engine::HighlightEngine;
-        use crate::primitives::highlighter::Language;
 
         let trimmed = input.trim();
 
@@ -636,34 +788,18 @@ impl Editor {
         if trimmed == "Plain Text" || trimmed.to_lowercase() == "text" {
             let buffer_id = self.active_buffer();
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                state.language = "Plain Text".to_string();
                 state.highlighter = HighlightEngine::None;
+                self.override_buffer_language(buffer_id, "Plain Text".to_string());
                 self.set_status_message("Language set to Plain Text".to_string());
             }
             return;
         }
 
-        // Try to find the syntax by name in the grammar registry
-        // This supports all syntect syntaxes (100+) plus user-configured grammars
-        if self.grammar_registry.find_syntax_by_name(trimmed).is_some() {
-            // Try to detect a tree-sitter language for non-highlighting features
-            // (indentation, semantic highlighting). This is best-effort since
-            // tree-sitter only supports ~18 languages while syntect supports 100+.
-            let ts_language = Language::from_name(trimmed);
-
-            let buffer_id = self.active_buffer();
-            if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                state.language = trimmed.to_string();
-                state.highlighter =
-                    HighlightEngine::for_syntax_name(trimmed, &self.grammar_registry, ts_language);
-                // Update reference highlighter if tree-sitter language is available
-                if let Some(lang) = ts_language {
-                    state.reference_highlighter.set_language(&lang);
-                }
-                self.set_status_message(format!("Language set to {}", trimmed));
-            }
-        } else {
-            self.set_status_message(format!("Unknown language: {}", input));
+        // Any syntect syntax (100+) or user-configured grammar, by name
+        let buffer_id = self.active_buffer();
+        match self.set_buffer_language(buffer_id, trimmed) {
+            Some(name) => self.set_status_message(format!("Language set to {}", name)),
+            None => self.set_status_message(format!("Unknown language: {}", input)),
         }
     }
 
@@ -778,7 +914,7 @@ impl Editor {
     }
 
     /// Handle Quick Open prompt confirmation based on prefix routing
-    fn handle_quick_open_confirm(
+    pub(super) fn handle_quick_open_confirm(
         &mut self,
         input: &str,
         selected_index: Option<usize>,
@@ -902,7 +1038,7 @@ impl Editor {
     }
 
     /// Handle Quick Open file selection
-    fn handle_quick_open_file(
+    pub(super) fn handle_quick_open_file(
         &mut self,
         input: &str,
         selected_index: Option<usize>,
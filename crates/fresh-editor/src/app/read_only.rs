@@ -0,0 +1,90 @@
+//! Read-only file buffers
+//!
+//! Files the user can't write to open read-only, and any file buffer can be
+//! made read-only on request. While a buffer is read-only, editing keys show
+//! a status message instead of changing the text. Toggle Read-Only makes it
+//! editable again; saving a file that still isn't writable then falls back
+//! to the sudo save prompt.
+
+use std::path::Path;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+
+impl Editor {
+    /// Make a newly opened file read-only if the user can't write to it.
+    /// Returns whether it was made read-only.
+    pub(super) fn mark_unwritable_read_only(&mut self, buffer_id: BufferId, path: &Path) -> bool {
+        if !path.exists() || self.filesystem.is_writable(path) {
+            return false;
+        }
+        self.set_buffer_read_only(buffer_id, true);
+        let name = self.get_buffer_display_name(buffer_id);
+        self.set_status_message(t!("read_only.opened_unwritable", name = name).to_string());
+        true
+    }
+
+    /// Switch the active file buffer between read-only and editable
+    pub fn toggle_read_only(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(metadata) = self.buffer_metadata.get(&buffer_id) else {
+            return;
+        };
+        let Some(path) = metadata.file_path().cloned() else {
+            self.set_status_message(t!("read_only.cannot_toggle").to_string());
+            return;
+        };
+        let locked = self
+            .active_state()
+            .buffer
+            .encryption()
+            .is_some_and(|encryption| encryption.is_locked());
+        if path.as_os_str().is_empty() || metadata.binary || locked {
+            self.set_status_message(t!("read_only.cannot_toggle").to_string());
+            return;
+        }
+
+        let read_only = !metadata.read_only;
+        self.set_buffer_read_only(buffer_id, read_only);
+        let name = self.get_buffer_display_name(buffer_id);
+        let message = if read_only {
+            t!("read_only.enabled", name = name)
+        } else if path.exists() && !self.filesystem.is_writable(&path) {
+            t!("read_only.disabled_unwritable", name = name)
+        } else {
+            t!("read_only.disabled", name = name)
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Make a buffer read-only or editable
+    pub fn set_buffer_read_only(&mut self, buffer_id: BufferId, read_only: bool) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = read_only;
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.read_only = read_only;
+        }
+    }
+
+    /// Whether a file buffer was made read-only, by the user or because the
+    /// file isn't writable (binary files have their own indicator)
+    pub(super) fn is_read_only_file(&self, buffer_id: BufferId) -> bool {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .is_some_and(|metadata| {
+                metadata.read_only && !metadata.binary && !metadata.is_virtual()
+            })
+    }
+
+    /// Status message shown when an edit is blocked in the active buffer
+    pub(super) fn editing_disabled_message(&self) -> String {
+        if self.is_read_only_file(self.active_buffer()) {
+            t!("read_only.editing_blocked").to_string()
+        } else {
+            t!("buffer.editing_disabled").to_string()
+        }
+    }
+}
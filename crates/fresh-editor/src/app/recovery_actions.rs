@@ -8,17 +8,49 @@
 //! - Cleaning up recovery files
 
 use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
 
+use crate::input::commands::Suggestion;
+use crate::model::composite_buffer::{CompositeLayout, PaneStyle, SourcePane};
 use crate::model::event::BufferId;
+use crate::services::recovery::{RecoveryEntry, RecoveryResult};
+use crate::view::prompt::{Prompt, PromptType};
 
+use super::types::{BufferMetadata, RecoveryPreview};
 use super::Editor;
 
+/// Mode name for the recovery diff preview
+const RECOVERY_PREVIEW_MODE: &str = "recovery-preview";
+
 impl Editor {
     /// Start the recovery session (call on editor startup after recovery check)
     pub fn start_recovery_session(&mut self) -> AnyhowResult<()> {
+        // Locks left by crashed editors
+        match self.file_locks.remove_stale() {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Removed {} stale file locks", n),
+            Err(e) => tracing::warn!("Failed to remove stale file locks: {}", e),
+        }
+        self.remove_expired_undo_histories();
         Ok(self.recovery_service.start_session()?)
     }
 
+    /// Listen for "open file" requests from other `fresh` processes using the
+    /// same data directory. Called alongside `start_recovery_session`, since
+    /// the instance that owns the session lock is the one others forward to.
+    #[cfg(unix)]
+    pub fn start_instance_server(&mut self) -> AnyhowResult<()> {
+        let Some(bridge) = self.async_bridge.as_ref() else {
+            return Ok(());
+        };
+        let socket_path = self.dir_context.instance_socket_path();
+        self.instance_server = Some(crate::services::instance_server::InstanceServer::start(
+            socket_path,
+            bridge.sender(),
+        )?);
+        Ok(())
+    }
+
     /// End the recovery session cleanly (call on normal shutdown)
     pub fn end_recovery_session(&mut self) -> AnyhowResult<()> {
         Ok(self.recovery_service.end_session()?)
@@ -39,91 +71,411 @@ impl Editor {
     /// Recover all buffers from recovery files
     /// Returns the number of buffers recovered
     pub fn recover_all_buffers(&mut self) -> AnyhowResult<usize> {
-        use crate::services::recovery::RecoveryResult;
-
-        let entries = self.recovery_service.list_recoverable()?;
+        let entries = self.recoverable_entries()?;
         let mut recovered_count = 0;
 
         for entry in entries {
-            match self.recovery_service.accept_recovery(&entry) {
-                Ok(RecoveryResult::Recovered {
-                    original_path,
-                    content,
-                }) => {
-                    // Full content recovery (new/small buffers)
-                    let text = String::from_utf8_lossy(&content).into_owned();
-
-                    if let Some(path) = original_path {
-                        // Open the file path (this creates the buffer)
-                        if self.open_file(&path).is_ok() {
-                            // Replace buffer content with recovered content
-                            let state = self.active_state_mut();
-                            let total = state.buffer.total_bytes();
-                            state.buffer.delete(0..total);
-                            state.buffer.insert(0, &text);
-                            // Mark as modified since it differs from disk
-                            state.buffer.set_modified(true);
-                            recovered_count += 1;
-                            tracing::info!("Recovered buffer: {}", path.display());
-                        }
-                    } else {
-                        // Unsaved buffer - create new buffer with recovered content
-                        self.new_buffer();
+            if self.recover_entry(&entry) {
+                recovered_count += 1;
+            }
+        }
+
+        Ok(recovered_count)
+    }
+
+    /// Recover a single entry into a buffer
+    /// Returns true if a buffer was restored
+    fn recover_entry(&mut self, entry: &RecoveryEntry) -> bool {
+        match self.recovery_service.accept_recovery(entry) {
+            Ok(RecoveryResult::Recovered {
+                original_path,
+                content,
+            }) => {
+                // Full content recovery (new/small buffers)
+                let text = String::from_utf8_lossy(&content).into_owned();
+
+                if let Some(path) = original_path {
+                    // Open the file path (this creates the buffer)
+                    if self.open_file(&path).is_ok() {
+                        // Replace buffer content with recovered content
                         let state = self.active_state_mut();
+                        let total = state.buffer.total_bytes();
+                        state.buffer.delete(0..total);
                         state.buffer.insert(0, &text);
+                        // Mark as modified since it differs from disk
                         state.buffer.set_modified(true);
-                        recovered_count += 1;
-                        tracing::info!("Recovered unsaved buffer");
+                        tracing::info!("Recovered buffer: {}", path.display());
+                        return true;
                     }
+                    false
+                } else {
+                    // Unsaved buffer - create new buffer with recovered content
+                    self.new_buffer();
+                    let state = self.active_state_mut();
+                    state.buffer.insert(0, &text);
+                    state.buffer.set_modified(true);
+                    tracing::info!("Recovered unsaved buffer");
+                    true
                 }
-                Ok(RecoveryResult::RecoveredChunks {
-                    original_path,
-                    chunks,
-                }) => {
-                    // Chunked recovery for large files - apply chunks directly
-                    if self.open_file(&original_path).is_ok() {
-                        let state = self.active_state_mut();
-
-                        // Apply chunks in reverse order to preserve offsets
-                        // Each chunk: delete original_len bytes at offset, then insert content
-                        for chunk in chunks.into_iter().rev() {
-                            let text = String::from_utf8_lossy(&chunk.content).into_owned();
-                            if chunk.original_len > 0 {
-                                state
-                                    .buffer
-                                    .delete(chunk.offset..chunk.offset + chunk.original_len);
-                            }
-                            state.buffer.insert(chunk.offset, &text);
+            }
+            Ok(RecoveryResult::RecoveredChunks {
+                original_path,
+                chunks,
+            }) => {
+                // Chunked recovery for large files - apply chunks directly
+                if self.open_file(&original_path).is_ok() {
+                    let state = self.active_state_mut();
+
+                    // Apply chunks in reverse order to preserve offsets
+                    // Each chunk: delete original_len bytes at offset, then insert content
+                    for chunk in chunks.into_iter().rev() {
+                        let text = String::from_utf8_lossy(&chunk.content).into_owned();
+                        if chunk.original_len > 0 {
+                            state
+                                .buffer
+                                .delete(chunk.offset..chunk.offset + chunk.original_len);
                         }
+                        state.buffer.insert(chunk.offset, &text);
+                    }
 
-                        // Mark as modified since it differs from disk
-                        state.buffer.set_modified(true);
-                        recovered_count += 1;
-                        tracing::info!("Recovered buffer with chunks: {}", original_path.display());
+                    // Mark as modified since it differs from disk
+                    state.buffer.set_modified(true);
+                    tracing::info!("Recovered buffer with chunks: {}", original_path.display());
+                    return true;
+                }
+                false
+            }
+            Ok(RecoveryResult::OriginalFileModified { id, original_path }) => {
+                tracing::warn!(
+                    "Recovery file {} skipped: original file {} was modified",
+                    id,
+                    original_path.display()
+                );
+                // Delete the recovery file since it's no longer valid
+                let _ = self.recovery_service.discard_recovery(entry);
+                false
+            }
+            Ok(RecoveryResult::Corrupted { id, reason }) => {
+                tracing::warn!("Recovery file {} corrupted: {}", id, reason);
+                false
+            }
+            Ok(RecoveryResult::NotFound { id }) => {
+                tracing::warn!("Recovery file {} not found", id);
+                false
+            }
+            Err(e) => {
+                tracing::warn!("Failed to recover {}: {}", entry.id, e);
+                false
+            }
+        }
+    }
+
+    /// Recovery entries that are not backing a buffer edited in this session
+    ///
+    /// Auto-save writes recovery files for the running session too; entries whose
+    /// buffer is open and modified are live, not something to recover.
+    fn recoverable_entries(&self) -> std::io::Result<Vec<RecoveryEntry>> {
+        let live_ids: std::collections::HashSet<String> = self
+            .buffers
+            .iter()
+            .filter(|(_, state)| state.buffer.is_modified())
+            .filter_map(|(buffer_id, state)| {
+                let meta = self.buffer_metadata.get(buffer_id)?;
+                Some(meta.recovery_id.clone().unwrap_or_else(|| {
+                    self.recovery_service
+                        .get_buffer_id(state.buffer.file_path())
+                }))
+            })
+            .collect();
+
+        Ok(self
+            .recovery_service
+            .list_recoverable()?
+            .into_iter()
+            .filter(|entry| !live_ids.contains(&entry.id))
+            .collect())
+    }
+
+    /// Show the recovery picker listing every recoverable file
+    /// Returns false if there is nothing to recover
+    pub fn show_recovery_picker(&mut self) -> bool {
+        let entries = match self.recoverable_entries() {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to list recovery files: {}", e);
+                return false;
+            }
+        };
+        if entries.is_empty() {
+            return false;
+        }
+
+        let suggestions: Vec<Suggestion> = entries
+            .iter()
+            .map(|entry| {
+                let mut description = format!(
+                    "{} · {}",
+                    entry.age_display(),
+                    entry.metadata.format_description()
+                );
+                if entry.original_file_modified() {
+                    description.push_str(" · ");
+                    description.push_str(&t!("recovery.changed_on_disk"));
+                }
+                Suggestion {
+                    text: entry.metadata.display_name(),
+                    description: Some(description),
+                    value: Some(entry.id.clone()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("recovery.picker_prompt").to_string(),
+            PromptType::RecoverySelect,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+        true
+    }
+
+    /// Handle the RecoverFiles action
+    pub fn recover_files(&mut self) {
+        if !self.show_recovery_picker() {
+            self.set_status_message(t!("recovery.none").to_string());
+        }
+    }
+
+    /// Look up a recoverable entry by id
+    fn find_recovery_entry(&self, id: &str) -> Option<RecoveryEntry> {
+        self.recoverable_entries()
+            .ok()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+    }
+
+    /// Handle RecoverySelect prompt confirmation: preview the entry and ask what to do
+    pub(super) fn handle_recovery_select(&mut self, id: &str) {
+        let Some(entry) = self.find_recovery_entry(id.trim()) else {
+            self.show_recovery_picker();
+            return;
+        };
+
+        if let Err(e) = self.open_recovery_preview(&entry) {
+            self.set_status_message(
+                t!("recovery.preview_failed", error = e.to_string()).to_string(),
+            );
+        }
+
+        let name = entry.metadata.display_name();
+        let option = |text: String, description: Option<String>, value: &str| Suggestion {
+            text,
+            description,
+            value: Some(value.to_string()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        };
+        let suggestions = vec![
+            option(
+                t!("recovery.recover").to_string(),
+                Some(t!("recovery.recover_desc").to_string()),
+                "recover",
+            ),
+            option(
+                t!("recovery.discard").to_string(),
+                Some(t!("recovery.discard_desc").to_string()),
+                "discard",
+            ),
+            option(
+                t!("recovery.open_copy").to_string(),
+                Some(t!("recovery.open_copy_desc").to_string()),
+                "copy",
+            ),
+            option(t!("recovery.back").to_string(), None, "back"),
+        ];
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("recovery.action_prompt", name = name).to_string(),
+            PromptType::RecoveryAction { id: entry.id },
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Handle RecoveryAction prompt confirmation
+    pub(super) fn handle_recovery_action(&mut self, id: &str, action: &str) {
+        self.close_recovery_preview();
+
+        if let Some(entry) = self.find_recovery_entry(id) {
+            let name = entry.metadata.display_name();
+            match action.trim() {
+                "recover" => {
+                    if self.recover_entry(&entry) {
+                        self.set_status_message(t!("recovery.recovered", name = name).to_string());
+                    } else {
+                        self.set_status_message(t!("recovery.failed", name = name).to_string());
                     }
                 }
-                Ok(RecoveryResult::OriginalFileModified { id, original_path }) => {
-                    tracing::warn!(
-                        "Recovery file {} skipped: original file {} was modified",
-                        id,
-                        original_path.display()
-                    );
-                    // Delete the recovery file since it's no longer valid
-                    let _ = self.recovery_service.discard_recovery(&entry);
+                "discard" => {
+                    if let Err(e) = self.recovery_service.discard_recovery(&entry) {
+                        tracing::warn!("Failed to discard recovery {}: {}", entry.id, e);
+                    }
+                    self.set_status_message(t!("recovery.discarded", name = name).to_string());
                 }
-                Ok(RecoveryResult::Corrupted { id, reason }) => {
-                    tracing::warn!("Recovery file {} corrupted: {}", id, reason);
+                "copy" => {
+                    if self.open_recovery_copy(&entry) {
+                        self.set_status_message(
+                            t!("recovery.opened_copy", name = name).to_string(),
+                        );
+                    } else {
+                        self.set_status_message(t!("recovery.failed", name = name).to_string());
+                    }
                 }
-                Ok(RecoveryResult::NotFound { id }) => {
-                    tracing::warn!("Recovery file {} not found", id);
+                _ => {}
+            }
+        }
+
+        self.show_recovery_picker();
+    }
+
+    /// Handle cancellation of a recovery prompt
+    ///
+    /// Escaping the action prompt goes back to the picker. Escaping the picker
+    /// recovers everything that is left, so dismissing it never loses work.
+    pub(super) fn handle_recovery_prompt_cancelled(&mut self, prompt_type: &PromptType) {
+        match prompt_type {
+            PromptType::RecoveryAction { .. } => {
+                self.close_recovery_preview();
+                self.show_recovery_picker();
+            }
+            PromptType::RecoverySelect => match self.recover_all_buffers() {
+                Ok(count) => {
+                    self.set_status_message(
+                        t!("recovery.recovered_all", count = count).to_string(),
+                    );
                 }
                 Err(e) => {
-                    tracing::warn!("Failed to recover {}: {}", entry.id, e);
+                    tracing::warn!("Failed to recover buffers: {}", e);
                 }
+            },
+            _ => {}
+        }
+    }
+
+    /// Open a side-by-side diff of the file on disk against the recovered content
+    fn open_recovery_preview(&mut self, entry: &RecoveryEntry) -> std::io::Result<()> {
+        self.close_recovery_preview();
+
+        let recovered = self.recovery_service.load_full_content(entry)?;
+        let on_disk = match &entry.metadata.original_path {
+            Some(path) => self.filesystem.read_file(path).unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        let name = entry.metadata.display_name();
+        let previous_buffer = self.active_buffer();
+        let disk_label = t!("recovery.pane_disk").to_string();
+        let recovered_label = t!("recovery.pane_recovered").to_string();
+        let disk_id = self.create_recovery_preview_source(&disk_label, &name, &on_disk);
+        let recovered_id = self.create_recovery_preview_source(&recovered_label, &name, &recovered);
+
+        let composite_id = self.create_composite_buffer(
+            t!("recovery.preview_title", name = &name).to_string(),
+            RECOVERY_PREVIEW_MODE.to_string(),
+            CompositeLayout::SideBySide {
+                ratios: vec![0.5, 0.5],
+                show_separator: true,
+            },
+            vec![
+                SourcePane::new(disk_id, disk_label, false).with_style(PaneStyle::old_diff()),
+                SourcePane::new(recovered_id, recovered_label, false)
+                    .with_style(PaneStyle::new_diff()),
+            ],
+        );
+
+        self.align_diff_panes(
+            composite_id,
+            (disk_id, &on_disk),
+            (recovered_id, &recovered),
+        );
+
+        self.set_active_buffer(composite_id);
+        self.recovery_preview = Some(RecoveryPreview {
+            composite_id,
+            source_ids: vec![disk_id, recovered_id],
+            previous_buffer,
+        });
+        Ok(())
+    }
+
+    /// Create a hidden, read-only buffer holding one side of the recovery preview
+    fn create_recovery_preview_source(
+        &mut self,
+        label: &str,
+        name: &str,
+        content: &[u8],
+    ) -> BufferId {
+        // Keep the file name last so syntax highlighting picks up its extension
+        let buffer_name = format!("*{}* {}", label, name);
+        let buffer_id = self.create_virtual_buffer(
+            buffer_name.clone(),
+            RECOVERY_PREVIEW_MODE.to_string(),
+            true,
+        );
+        self.buffer_metadata.insert(
+            buffer_id,
+            BufferMetadata::hidden_virtual_buffer(buffer_name, RECOVERY_PREVIEW_MODE.to_string()),
+        );
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &String::from_utf8_lossy(content));
+            state.buffer.set_modified(false);
+            state.editing_disabled = true;
+        }
+        buffer_id
+    }
+
+    /// Close the recovery preview, if open, and return to the previous buffer
+    fn close_recovery_preview(&mut self) {
+        let Some(preview) = self.recovery_preview.take() else {
+            return;
+        };
+        self.close_composite_buffer(preview.composite_id);
+        for id in std::iter::once(preview.composite_id).chain(preview.source_ids) {
+            if let Err(e) = self.force_close_buffer(id) {
+                tracing::warn!("Failed to close recovery preview buffer {:?}: {}", id, e);
             }
         }
+        if self.buffers.contains_key(&preview.previous_buffer) {
+            self.set_active_buffer(preview.previous_buffer);
+        }
+    }
 
-        Ok(recovered_count)
+    /// Open the recovered content in a new unnamed buffer and drop the recovery file
+    fn open_recovery_copy(&mut self, entry: &RecoveryEntry) -> bool {
+        let content = match self.recovery_service.load_full_content(entry) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Failed to load recovery {}: {}", entry.id, e);
+                return false;
+            }
+        };
+
+        self.new_buffer();
+        let state = self.active_state_mut();
+        state.buffer.insert(0, &String::from_utf8_lossy(&content));
+        state.buffer.set_modified(true);
+        if let Err(e) = self.recovery_service.discard_recovery(entry) {
+            tracing::warn!("Failed to discard recovery {}: {}", entry.id, e);
+        }
+        true
     }
 
     /// Discard all recovery files (user decided not to recover)
@@ -169,6 +521,12 @@ impl Editor {
                         return None;
                     }
                 }
+                // Skip decrypted files unless recovery files are encrypted too
+                if state.buffer.encryption().is_some()
+                    && !self.recovery_service.codec().is_encrypted()
+                {
+                    return None;
+                }
                 if state.buffer.is_recovery_pending() {
                     Some(*buffer_id)
                 } else {
@@ -247,7 +605,7 @@ impl Editor {
 
                     // If no modifications, skip saving (original file is recovery)
                     if chunks.is_empty() {
-                        state.buffer.set_recovery_pending(false);
+                        state.buffer.mark_recovery_saved();
                         continue;
                     }
 
@@ -287,6 +645,14 @@ impl Editor {
                         original_size,
                         final_size
                     );
+                } else if let Some(records) = state
+                    .buffer
+                    .take_recovery_journal()
+                    .filter(|_| self.recovery_service.can_append_journal(&recovery_id))
+                {
+                    // Snapshot already on disk: only append the edits made since
+                    self.recovery_service
+                        .append_journal(&recovery_id, &records)?;
                 } else {
                     // For small files, save full content as a single chunk
                     let total_bytes = state.buffer.total_bytes();
@@ -310,10 +676,12 @@ impl Editor {
                         0,           // original_file_size = 0 for new/small files
                         total_bytes, // final_size
                     )?;
+                    // Later edits are journaled on top of this snapshot
+                    state.buffer.reset_recovery_journal();
                 }
 
-                // Clear recovery_pending flag after successful save
-                state.buffer.set_recovery_pending(false);
+                // The buffer is now recovered at its current revision
+                state.buffer.mark_recovery_saved();
                 saved_count += 1;
             }
         }
@@ -351,9 +719,9 @@ impl Editor {
 
         self.recovery_service.delete_buffer_recovery(&recovery_id)?;
 
-        // Clear recovery_pending since buffer is now saved
+        // Nothing is pending for recovery since buffer is now saved
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            state.buffer.set_recovery_pending(false);
+            state.buffer.mark_recovery_saved();
         }
         Ok(())
     }
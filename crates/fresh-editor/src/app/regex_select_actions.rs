@@ -0,0 +1,169 @@
+//! Selecting with a regex inside the selection
+//!
+//! Select Regex in Selection puts a cursor on every match of a regex inside
+//! the selections, with the match selected. Split Selection on Regex does the
+//! opposite and selects the text between the matches. Either way the cursors
+//! are replaced in one undo step.
+
+use std::ops::Range;
+
+use regex::Regex;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{CursorId, Event};
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Start the prompt for the regex to select (or split on) in the selection
+    pub(super) fn start_regex_select_prompt(&mut self, split: bool) {
+        if self.active_state().cursors.selections().is_empty() {
+            self.set_status_message(t!("regex_select.no_selection").to_string());
+            return;
+        }
+        let message = if split {
+            t!("regex_select.split_prompt")
+        } else {
+            t!("regex_select.prompt")
+        };
+        self.start_prompt(message.to_string(), PromptType::RegexSelect { split });
+    }
+
+    /// Handle RegexSelect prompt confirmation.
+    pub(super) fn handle_regex_select(&mut self, input: &str, split: bool) {
+        let regex = match Regex::new(input) {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        let mut selections = self.active_state().cursors.selections();
+        selections.sort_by_key(|range| range.start);
+        let mut ranges = Vec::new();
+        for selection in selections {
+            let text = self
+                .active_state_mut()
+                .get_text_range(selection.start, selection.end);
+            if split {
+                ranges.extend(split_ranges(&regex, &text, selection.start));
+            } else {
+                ranges.extend(match_ranges(&regex, &text, selection.start));
+            }
+        }
+        if ranges.is_empty() {
+            self.set_status_message(t!("regex_select.no_matches", pattern = input).to_string());
+            return;
+        }
+
+        let count = ranges.len();
+        self.select_ranges(ranges);
+        self.set_status_message(t!("regex_select.selected", count = count).to_string());
+    }
+
+    /// Replace the cursors with one selecting each range. The existing
+    /// cursors are moved first, so the fewest are added and removed.
+    fn select_ranges(&mut self, ranges: Vec<Range<usize>>) {
+        let state = self.active_state();
+        let mut cursors: Vec<_> = state
+            .cursors
+            .iter()
+            .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+            .collect();
+        cursors.sort_by_key(|(cursor_id, _)| cursor_id.0);
+        let mut next_id = cursors.last().map_or(0, |(cursor_id, _)| cursor_id.0 + 1);
+
+        // An empty match is a cursor without a selection
+        let anchor = |range: &Range<usize>| (!range.is_empty()).then_some(range.start);
+        let mut events = Vec::new();
+        let mut ranges = ranges.into_iter();
+        for (cursor_id, cursor) in &cursors {
+            match ranges.next() {
+                Some(range) => events.push(Event::MoveCursor {
+                    cursor_id: *cursor_id,
+                    old_position: cursor.position,
+                    new_position: range.end,
+                    old_anchor: cursor.anchor,
+                    new_anchor: anchor(&range),
+                    old_sticky_column: cursor.sticky_column,
+                    new_sticky_column: 0,
+                }),
+                None => events.push(Event::RemoveCursor {
+                    cursor_id: *cursor_id,
+                    position: cursor.position,
+                    anchor: cursor.anchor,
+                }),
+            }
+        }
+        for range in ranges {
+            events.push(Event::AddCursor {
+                cursor_id: CursorId(next_id),
+                position: range.end,
+                anchor: anchor(&range),
+            });
+            next_id += 1;
+        }
+
+        let batch = Event::Batch {
+            events,
+            description: "Select regex in selection".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+    }
+}
+
+/// The matches of `regex` in `text`, as buffer ranges (`text` starts at
+/// `offset`)
+fn match_ranges(regex: &Regex, text: &str, offset: usize) -> Vec<Range<usize>> {
+    regex
+        .find_iter(text)
+        .map(|m| offset + m.start()..offset + m.end())
+        .collect()
+}
+
+/// The text between the matches of `regex` in `text`, as buffer ranges
+/// (`text` starts at `offset`). Empty pieces are left out.
+fn split_ranges(regex: &Regex, text: &str, offset: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for m in regex.find_iter(text) {
+        ranges.push(offset + start..offset + m.start());
+        start = m.end();
+    }
+    ranges.push(offset + start..offset + text.len());
+    ranges.retain(|range| !range.is_empty());
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_ranges() {
+        let regex = Regex::new(r"\d+").unwrap();
+        assert_eq!(match_ranges(&regex, "a1 b22 c", 10), vec![11..12, 14..16]);
+        assert!(match_ranges(&regex, "none", 0).is_empty());
+
+        // Empty matches become cursors without a selection
+        let regex = Regex::new("(?m)^").unwrap();
+        assert_eq!(match_ranges(&regex, "a\nb", 0), vec![0..0, 2..2]);
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        let regex = Regex::new(r",\s*").unwrap();
+        assert_eq!(
+            split_ranges(&regex, "a, bb,c", 5),
+            vec![5..6, 8..10, 11..12]
+        );
+        // No match keeps the whole text, and empty pieces are dropped
+        assert_eq!(split_ranges(&regex, "abc", 0), vec![0..3]);
+        assert_eq!(split_ranges(&regex, ",a,,b,", 0), vec![1..2, 4..5]);
+        assert!(split_ranges(&regex, ",", 0).is_empty());
+    }
+}
@@ -0,0 +1,243 @@
+//! Re-indenting the selected lines, or the whole buffer
+//!
+//! If the buffer's language server formats ranges, it's asked to format the
+//! lines (`textDocument/rangeFormatting`). Otherwise, or if it fails, each
+//! line gets the indent auto-indent would give it when typed after the ones
+//! before it. Either way the changes are a single undo step, and the number
+//! of changed lines is shown.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+
+/// Lines re-indented at most at once
+const MAX_REINDENT_LINES: usize = 20_000;
+
+/// Bytes before the first line given to the indent engine as context
+const CONTEXT_BYTES: usize = 16 * 1024;
+
+const UNDO_DESCRIPTION: &str = "Re-indent Lines";
+
+/// Lines waiting for a language server to format them
+pub(super) struct PendingReindent {
+    request_id: u64,
+    buffer_id: BufferId,
+    lines: Range<usize>,
+}
+
+impl Editor {
+    /// Re-indent the lines of the primary selection, or the whole buffer
+    /// without one
+    pub(super) fn reindent_lines(&mut self) {
+        if self.is_editing_disabled() {
+            self.set_status_message(self.editing_disabled_message());
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let lines = {
+            let state = self.active_state();
+            let buffer = &state.buffer;
+            match state.cursors.primary().selection_range() {
+                Some(range) => {
+                    let start = buffer.get_line_number(range.start);
+                    let mut end = buffer.get_line_number(range.end);
+                    // A selection ending at the start of a line doesn't include it
+                    if buffer.line_start_offset(end) != Some(range.end) || end == start {
+                        end += 1;
+                    }
+                    Some(start..end)
+                }
+                None => buffer.line_count().map(|count| 0..count),
+            }
+        };
+        let Some(lines) = lines.filter(|lines| lines.len() <= MAX_REINDENT_LINES) else {
+            self.set_status_message(
+                t!("reindent.too_many_lines", max = MAX_REINDENT_LINES).to_string(),
+            );
+            return;
+        };
+
+        if !self.request_range_formatting(buffer_id, lines.clone()) {
+            self.reindent_with_engine(buffer_id, lines);
+        }
+    }
+
+    /// Ask the buffer's language server to format `lines`. Returns false if
+    /// it couldn't be asked.
+    fn request_range_formatting(&mut self, buffer_id: BufferId, lines: Range<usize>) -> bool {
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return false;
+        };
+        let buffer = &state.buffer;
+        let start = buffer.line_start_offset(lines.start).unwrap_or(0);
+        let end = buffer
+            .line_start_offset(lines.end)
+            .unwrap_or_else(|| buffer.len());
+        let (start_line, start_char) = buffer.position_to_lsp_position(start);
+        let (end_line, end_char) = buffer.position_to_lsp_position(end);
+        let tab_size = state.tab_size as u32;
+        let insert_spaces = !state.use_tabs;
+
+        let request_id = self.next_lsp_request_id;
+        let sent = self
+            .with_lsp_for_buffer(buffer_id, |handle, uri, _language| {
+                handle
+                    .range_formatting(
+                        request_id,
+                        uri.clone(),
+                        start_line as u32,
+                        start_char as u32,
+                        end_line as u32,
+                        end_char as u32,
+                        tab_size,
+                        insert_spaces,
+                    )
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if sent {
+            self.next_lsp_request_id += 1;
+            self.pending_reindent = Some(PendingReindent {
+                request_id,
+                buffer_id,
+                lines,
+            });
+            self.set_status_message(t!("reindent.formatting").to_string());
+        }
+        sent
+    }
+
+    /// Handle a language server's range formatting edits, re-indenting the
+    /// lines without it if it has none to give
+    pub(super) fn handle_range_formatting_response(
+        &mut self,
+        request_id: u64,
+        edits: Option<Vec<lsp_types::TextEdit>>,
+    ) {
+        let Some(pending) = self
+            .pending_reindent
+            .take_if(|pending| pending.request_id == request_id)
+        else {
+            return;
+        };
+        if !self.buffers.contains_key(&pending.buffer_id) {
+            return;
+        }
+        let Some(edits) = edits else {
+            self.reindent_with_engine(pending.buffer_id, pending.lines);
+            return;
+        };
+
+        let changed = changed_lines(&edits);
+        if let Err(e) =
+            self.apply_lsp_text_edits(pending.buffer_id, edits, UNDO_DESCRIPTION.to_string())
+        {
+            self.set_status_message(t!("reindent.failed", error = e.to_string()).to_string());
+            return;
+        }
+        self.report_reindented(changed);
+    }
+
+    /// Re-indent `lines` with the auto-indent engine
+    fn reindent_with_engine(&mut self, buffer_id: BufferId, lines: Range<usize>) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let buffer = &state.buffer;
+        let Some(start) = buffer.line_start_offset(lines.start) else {
+            return;
+        };
+        let end = buffer
+            .line_start_offset(lines.end)
+            .unwrap_or_else(|| buffer.len());
+
+        let context = buffer.slice_bytes(start.saturating_sub(CONTEXT_BYTES)..start);
+        let context = match context.iter().position(|&b| b == b'\n') {
+            Some(newline) if start > CONTEXT_BYTES => &context[newline + 1..],
+            _ => &context[..],
+        };
+        let before = String::from_utf8_lossy(context);
+        let text = state.get_text_range(start, end);
+        let old_lines: Vec<&str> = text
+            .split_inclusive('\n')
+            .map(|line| line.trim_end_matches(['\n', '\r']))
+            .collect();
+
+        let new_lines = state.indent_calculator.borrow_mut().reindent_lines(
+            &before,
+            &old_lines,
+            state.highlighter.language(),
+            state.tab_size,
+            state.use_tabs,
+        );
+
+        // Only the indentation of changed lines is replaced
+        let cursor_id = state.cursors.primary_id();
+        let mut events = Vec::new();
+        let mut changed = 0;
+        let mut line_start = start;
+        for (line, (old, new)) in text
+            .split_inclusive('\n')
+            .zip(old_lines.iter().zip(&new_lines))
+        {
+            if old != new {
+                changed += 1;
+                let old_indent = old.len() - old.trim_start_matches([' ', '\t']).len();
+                let new_indent = new.len() - new.trim_start_matches([' ', '\t']).len();
+                if old_indent > 0 {
+                    events.push(Event::Delete {
+                        range: line_start..line_start + old_indent,
+                        deleted_text: old[..old_indent].to_string(),
+                        cursor_id,
+                    });
+                }
+                if new_indent > 0 {
+                    events.push(Event::Insert {
+                        position: line_start,
+                        text: new[..new_indent].to_string(),
+                        cursor_id,
+                    });
+                }
+            }
+            line_start += line.len();
+        }
+
+        if let Err(e) = self.apply_events_to_buffer_as_bulk_edit(
+            buffer_id,
+            events,
+            UNDO_DESCRIPTION.to_string(),
+        ) {
+            self.set_status_message(t!("reindent.failed", error = e.to_string()).to_string());
+            return;
+        }
+        self.report_reindented(changed);
+    }
+
+    fn report_reindented(&mut self, changed: usize) {
+        if changed == 0 {
+            self.set_status_message(t!("reindent.unchanged").to_string());
+        } else {
+            self.set_status_message(t!("reindent.done", count = changed).to_string());
+        }
+    }
+}
+
+/// The number of lines that formatting edits change
+fn changed_lines(edits: &[lsp_types::TextEdit]) -> usize {
+    let mut lines = HashSet::new();
+    for edit in edits {
+        let range = edit.range;
+        // An edit ending at the start of a line doesn't change it
+        let end = if range.end.character == 0 && range.end.line > range.start.line {
+            range.end.line - 1
+        } else {
+            range.end.line
+        };
+        lines.extend(range.start.line..=end);
+    }
+    lines.len()
+}
@@ -1,4 +1,5 @@
 use super::*;
+use crate::services::profiler::Subsystem;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 
@@ -6,6 +7,8 @@ impl Editor {
     /// Render the editor to the terminal
     pub fn render(&mut self, frame: &mut Frame) {
         let _span = tracing::trace_span!("render").entered();
+        let render_start = std::time::Instant::now();
+        let mut lap = render_start;
         let size = frame.area();
 
         // For scroll sync groups, we need to update the active split's viewport position BEFORE
@@ -24,7 +27,7 @@ impl Editor {
 
         // Prepare all buffers for rendering (pre-load viewport data for lazy loading)
         // Each split may have a different viewport position on the same buffer
-        let mut semantic_ranges: std::collections::HashMap<BufferId, (usize, usize)> =
+        let mut visible_line_ranges: std::collections::HashMap<BufferId, (usize, usize)> =
             std::collections::HashMap::new();
         for (split_id, view_state) in &self.split_view_states {
             if let Some(buffer_id) = self.split_manager.get_buffer_id(*split_id) {
@@ -32,7 +35,7 @@ impl Editor {
                     let start_line = state.buffer.get_line_number(view_state.viewport.top_byte);
                     let visible_lines = view_state.viewport.visible_line_count().saturating_sub(1);
                     let end_line = start_line.saturating_add(visible_lines);
-                    semantic_ranges
+                    visible_line_ranges
                         .entry(buffer_id)
                         .and_modify(|(min_start, max_end)| {
                             *min_start = (*min_start).min(start_line);
@@ -42,14 +45,18 @@ impl Editor {
                 }
             }
         }
-        for (buffer_id, (start_line, end_line)) in semantic_ranges {
-            self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
-            self.maybe_request_semantic_tokens_full_debounced(buffer_id);
+        let highlight_references = !self.semantic_highlight_degraded();
+        if highlight_references {
+            for (&buffer_id, &(start_line, end_line)) in &visible_line_ranges {
+                self.maybe_request_semantic_tokens_range(buffer_id, start_line, end_line);
+                self.maybe_request_semantic_tokens_full_debounced(buffer_id);
+            }
         }
 
         for (split_id, view_state) in &self.split_view_states {
             if let Some(buffer_id) = self.split_manager.get_buffer_id(*split_id) {
                 if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                    state.reference_highlight_overlay.enabled = highlight_references;
                     let top_byte = view_state.viewport.top_byte;
                     let height = view_state.viewport.height;
                     if let Err(e) = state.prepare_for_render(top_byte, height) {
@@ -60,6 +67,14 @@ impl Editor {
             }
         }
 
+        // Underline misspelled words and links and add color swatches in the
+        // visible lines (needs the viewport data loaded above)
+        for (buffer_id, (start_line, end_line)) in visible_line_ranges {
+            self.update_spell_check_overlays(buffer_id, start_line, end_line);
+            self.update_link_overlays(buffer_id, start_line, end_line);
+            self.update_color_swatches(buffer_id, start_line, end_line);
+        }
+
         // Refresh search highlights only during incremental search (when prompt is active)
         // After search is confirmed, overlays exist for ALL matches and shouldn't be overwritten
         let is_search_prompt_active = self.prompt.as_ref().is_some_and(|p| {
@@ -75,6 +90,8 @@ impl Editor {
             }
         }
 
+        self.profiler.lap(Subsystem::Prepare, &mut lap);
+
         // Determine if we need to show search options bar
         let show_search_options = self.prompt.as_ref().is_some_and(|p| {
             matches!(
@@ -95,7 +112,10 @@ impl Editor {
         let has_file_browser = self.prompt.as_ref().is_some_and(|p| {
             matches!(
                 p.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                PromptType::OpenFile
+                    | PromptType::SwitchProject
+                    | PromptType::AddWorkspaceFolder
+                    | PromptType::SaveFileAs
             )
         }) && self.file_open_state.is_some();
 
@@ -196,6 +216,7 @@ impl Editor {
             self.cached_layout.file_explorer_area = None;
             editor_content_area = main_content_area;
         }
+        self.profiler.lap(Subsystem::FileExplorer, &mut lap);
 
         // Note: Tabs are now rendered within each split by SplitRenderer
 
@@ -318,6 +339,7 @@ impl Editor {
                 }
             }
         }
+        self.profiler.lap(Subsystem::PluginHooks, &mut lap);
 
         // Render editor content (same for both layouts)
         let lsp_waiting = self.pending_completion_request.is_some()
@@ -357,35 +379,49 @@ impl Editor {
         };
 
         let is_maximized = self.split_manager.is_maximized();
+        let ansi_background = if self.background_fade_degraded() {
+            None
+        } else {
+            self.ansi_background.as_ref()
+        };
 
-        let (split_areas, tab_layouts, close_split_areas, maximize_split_areas, view_line_mappings) =
-            SplitRenderer::render_content(
-                frame,
-                editor_content_area,
-                &self.split_manager,
-                &mut self.buffers,
-                &self.buffer_metadata,
-                &mut self.event_logs,
-                &self.composite_buffers,
-                &mut self.composite_view_states,
-                &self.theme,
-                self.ansi_background.as_ref(),
-                self.background_fade,
-                lsp_waiting,
-                self.config.editor.large_file_threshold_bytes,
-                self.config.editor.line_wrap,
-                self.config.editor.estimated_line_length,
-                self.config.editor.highlight_context_bytes,
-                Some(&mut self.split_view_states),
-                hide_cursor,
-                hovered_tab,
-                hovered_close_split,
-                hovered_maximize_split,
-                is_maximized,
-                self.config.editor.relative_line_numbers,
-                self.tab_bar_visible,
-                self.config.editor.use_terminal_bg,
-            );
+        let (
+            split_areas,
+            tab_layouts,
+            close_split_areas,
+            maximize_split_areas,
+            view_line_mappings,
+            breadcrumb_layouts,
+        ) = SplitRenderer::render_content(
+            frame,
+            editor_content_area,
+            &self.split_manager,
+            &mut self.buffers,
+            &self.buffer_metadata,
+            &mut self.event_logs,
+            &self.composite_buffers,
+            &mut self.composite_view_states,
+            &self.theme,
+            ansi_background,
+            self.background_fade,
+            lsp_waiting,
+            self.config.editor.large_file_threshold_bytes,
+            self.config.editor.line_wrap,
+            self.config.editor.estimated_line_length,
+            self.config.editor.highlight_context_bytes,
+            Some(&mut self.split_view_states),
+            hide_cursor,
+            hovered_tab,
+            hovered_close_split,
+            hovered_maximize_split,
+            is_maximized,
+            self.config.editor.effective_line_number_mode(),
+            self.tab_bar_visible,
+            self.breadcrumbs_visible,
+            &self.working_dir,
+            self.config.editor.use_terminal_bg,
+            &self.grammar_registry,
+        );
 
         // Detect viewport changes and fire hooks
         // Compare against previous frame's viewport state (stored in self.previous_viewports)
@@ -451,11 +487,16 @@ impl Editor {
         // Render terminal content on top of split content for terminal buffers
         self.render_terminal_splits(frame, &split_areas);
 
+        // Render image previews over image file buffers and the explorer thumbnail
+        self.render_image_previews(frame, &split_areas);
+        self.profiler.lap(Subsystem::Content, &mut lap);
+
         self.cached_layout.split_areas = split_areas;
         self.cached_layout.tab_layouts = tab_layouts;
         self.cached_layout.close_split_areas = close_split_areas;
         self.cached_layout.maximize_split_areas = maximize_split_areas;
         self.cached_layout.view_line_mappings = view_line_mappings;
+        self.cached_layout.breadcrumb_layouts = breadcrumb_layouts;
         self.cached_layout.separator_areas = self
             .split_manager
             .get_separators_with_ids(editor_content_area);
@@ -471,7 +512,10 @@ impl Editor {
             // For OpenFile/SwitchProject/SaveFileAs prompt, render the file browser popup
             if matches!(
                 prompt.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                PromptType::OpenFile
+                    | PromptType::SwitchProject
+                    | PromptType::AddWorkspaceFolder
+                    | PromptType::SaveFileAs
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     // Calculate popup area: position above prompt line, covering status bar
@@ -496,10 +540,15 @@ impl Editor {
                 // For other prompts, render suggestions as before
                 // Calculate overlay area: position above prompt line (which is below status bar)
                 let suggestion_count = prompt.suggestions.len().min(10);
-                let is_quick_open =
-                    prompt.prompt_type == crate::view::prompt::PromptType::QuickOpen;
-                let hints_height: u16 = if is_quick_open { 1 } else { 0 };
-                let height = suggestion_count as u16 + 2 + hints_height; // +2 for borders, +1 for hints if QuickOpen
+                let mode_hints = match prompt.prompt_type {
+                    crate::view::prompt::PromptType::QuickOpen => Some(t!("quick_open.mode_hints")),
+                    crate::view::prompt::PromptType::GotoAnything => {
+                        Some(t!("goto_anything.mode_hints"))
+                    }
+                    _ => None,
+                };
+                let hints_height: u16 = if mode_hints.is_some() { 1 } else { 0 };
+                let height = suggestion_count as u16 + 2 + hints_height; // +2 for borders, +1 for mode hints
 
                 // Position suggestions above the prompt line (and hints line if present)
                 // The prompt line is at main_chunks[prompt_line_idx], so suggestions go above it
@@ -521,8 +570,8 @@ impl Editor {
                     self.mouse_state.hover_target.as_ref(),
                 );
 
-                // Render hints line for QuickOpen between suggestions and prompt
-                if is_quick_open {
+                // Render mode hints line between suggestions and prompt
+                if let Some(hints_text) = mode_hints {
                     let hints_area = ratatui::layout::Rect {
                         x: 0,
                         y: main_chunks[prompt_line_idx].y.saturating_sub(hints_height),
@@ -530,7 +579,7 @@ impl Editor {
                         height: hints_height,
                     };
                     frame.render_widget(ratatui::widgets::Clear, hints_area);
-                    Self::render_quick_open_hints(frame, hints_area, &self.theme);
+                    Self::render_mode_hints(frame, hints_area, &hints_text, &self.theme);
                 }
             }
         }
@@ -570,16 +619,23 @@ impl Editor {
             let status_bar_hover = match &self.mouse_state.hover_target {
                 Some(HoverTarget::StatusBarLspIndicator) => StatusBarHover::LspIndicator,
                 Some(HoverTarget::StatusBarWarningBadge) => StatusBarHover::WarningBadge,
+                Some(HoverTarget::StatusBarIndentationIndicator) => {
+                    StatusBarHover::IndentationIndicator
+                }
                 Some(HoverTarget::StatusBarLineEndingIndicator) => {
                     StatusBarHover::LineEndingIndicator
                 }
                 Some(HoverTarget::StatusBarEncodingIndicator) => StatusBarHover::EncodingIndicator,
                 Some(HoverTarget::StatusBarLanguageIndicator) => StatusBarHover::LanguageIndicator,
+                Some(HoverTarget::StatusBarPositionIndicator) => StatusBarHover::PositionIndicator,
                 _ => StatusBarHover::None,
             };
 
             // Get remote connection info if editing remote files
             let remote_connection = self.remote_connection_info().map(|s| s.to_string());
+            let auto_save = self.is_file_auto_save_active(self.active_buffer());
+            let read_only = self.is_read_only_file(self.active_buffer());
+            let degraded = self.is_frame_budget_degraded();
 
             let status_bar_layout = StatusBarRenderer::render_status_bar(
                 frame,
@@ -597,6 +653,9 @@ impl Editor {
                 general_warning_count,        // Pass general warning count for badge
                 status_bar_hover,             // Pass hover state for indicator styling
                 remote_connection.as_deref(), // Pass remote connection info
+                auto_save,                    // Whether the file is auto-saved to disk
+                read_only,                    // Whether the file buffer is read-only
+                degraded,                     // Whether decorations are off under load
             );
 
             // Store status bar layout for click detection
@@ -605,11 +664,14 @@ impl Editor {
                 Some((status_bar_area.y, status_bar_area.x, status_bar_area.width));
             self.cached_layout.status_bar_lsp_area = status_bar_layout.lsp_indicator;
             self.cached_layout.status_bar_warning_area = status_bar_layout.warning_badge;
+            self.cached_layout.status_bar_indentation_area =
+                status_bar_layout.indentation_indicator;
             self.cached_layout.status_bar_line_ending_area =
                 status_bar_layout.line_ending_indicator;
             self.cached_layout.status_bar_encoding_area = status_bar_layout.encoding_indicator;
             self.cached_layout.status_bar_language_area = status_bar_layout.language_indicator;
             self.cached_layout.status_bar_message_area = status_bar_layout.message_area;
+            self.cached_layout.status_bar_position_area = status_bar_layout.position_indicator;
         }
 
         // Render search options bar when in search prompt
@@ -662,6 +724,7 @@ impl Editor {
                 prompt.prompt_type,
                 crate::view::prompt::PromptType::OpenFile
                     | crate::view::prompt::PromptType::SwitchProject
+                    | crate::view::prompt::PromptType::AddWorkspaceFolder
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     StatusBarRenderer::render_file_open_prompt(
@@ -847,6 +910,29 @@ impl Editor {
             crate::view::event_debug::render_event_debug(frame, size, debug, &self.theme);
         }
 
+        // Render keybinding cheat sheet if open
+        self.cached_layout.cheat_sheet_layout = None;
+        if let Some(ref mut sheet) = self.cheat_sheet {
+            // Dim the editor content behind the overlay
+            crate::view::dimming::apply_dimming(frame, size);
+            self.cached_layout.cheat_sheet_layout = Some(
+                crate::view::cheat_sheet::render_cheat_sheet(frame, size, sheet, &self.theme),
+            );
+        }
+
+        // Render the buffer switcher if open (not dimmed: the previewed
+        // buffer shows behind it)
+        self.cached_layout.buffer_switcher_layout = None;
+        if let Some(ref switcher) = self.buffer_switcher {
+            self.cached_layout.buffer_switcher_layout =
+                Some(crate::view::buffer_switcher::render_buffer_switcher(
+                    frame,
+                    size,
+                    switcher,
+                    &self.theme,
+                ));
+        }
+
         if self.menu_bar_visible {
             self.cached_layout.menu_layout = Some(crate::view::ui::MenuRenderer::render(
                 frame,
@@ -915,27 +1001,29 @@ impl Editor {
             frame.buffer_mut(),
             self.color_capability,
         );
+        self.profiler.lap(Subsystem::Chrome, &mut lap);
+        self.profiler.finish_frame(render_start.elapsed());
     }
 
-    /// Render the Quick Open hints line showing available mode prefixes
-    fn render_quick_open_hints(
+    /// Render the hints line showing the available mode prefixes of a
+    /// prefix-routed prompt (Quick Open, Go to Anything)
+    fn render_mode_hints(
         frame: &mut Frame,
         area: ratatui::layout::Rect,
+        hints_text: &str,
         theme: &crate::view::theme::Theme,
     ) {
         use ratatui::style::{Modifier, Style};
         use ratatui::text::{Line, Span};
         use ratatui::widgets::Paragraph;
-        use rust_i18n::t;
 
         let hints_style = Style::default()
             .fg(theme.line_number_fg)
             .bg(theme.suggestion_selected_bg)
             .add_modifier(Modifier::DIM);
-        let hints_text = t!("quick_open.mode_hints");
         // Left-align with small margin
         let left_margin = 2;
-        let hints_width = crate::primitives::display_width::str_width(&hints_text);
+        let hints_width = crate::primitives::display_width::str_width(hints_text);
         let mut spans = Vec::new();
         spans.push(Span::styled(" ".repeat(left_margin), hints_style));
         spans.push(Span::styled(hints_text.to_string(), hints_style));
@@ -1116,7 +1204,7 @@ impl Editor {
             };
 
             // Pad the label to fill the menu width
-            let label = item.label();
+            let label = item.label(menu.pinned);
             let content_width = (menu_width as usize).saturating_sub(2); // -2 for borders
             let padded_label = format!(" {:<width$}", label, width = content_width - 1);
 
@@ -1386,7 +1474,9 @@ impl Editor {
         // Clear hover state
         self.mouse_state.lsp_hover_state = None;
         self.mouse_state.lsp_hover_request_sent = false;
-        self.pending_hover_request = None;
+        if let Some(request_id) = self.pending_hover_request.take() {
+            self.cancel_lsp_request(request_id);
+        }
 
         // Clear hover symbol highlight if present
         if let Some(handle) = self.hover_symbol_overlay.take() {
@@ -1574,7 +1664,7 @@ impl Editor {
             }
         };
 
-        let file_language = match detect_language(path, &self.config.languages) {
+        let file_language = match metadata.language(&self.config.languages) {
             Some(l) => l,
             None => {
                 tracing::debug!(
@@ -1612,6 +1702,8 @@ impl Editor {
             return;
         };
 
+        let enable_inlay_hints = self.inlay_hints_shown();
+
         // Send didOpen to LSP (use force_spawn since this is called after user confirmation)
         if let Some(lsp) = &mut self.lsp {
             if let Some(client) = lsp.force_spawn(language) {
@@ -1636,7 +1728,7 @@ impl Editor {
                     }
 
                     // Request inlay hints if enabled
-                    if self.config.editor.enable_inlay_hints {
+                    if enable_inlay_hints {
                         let request_id = self.next_lsp_request_id;
                         self.next_lsp_request_id += 1;
                         self.pending_inlay_hints_request = Some(request_id);
@@ -1864,14 +1956,16 @@ impl Editor {
 
     /// Notify LSP of a file save
     pub(super) fn notify_lsp_save(&mut self) {
+        self.notify_lsp_save_buffer(self.active_buffer());
+    }
+
+    /// Notify LSP that a specific buffer was saved
+    pub(super) fn notify_lsp_save_buffer(&mut self, buffer_id: BufferId) {
         // Check if LSP is enabled for this buffer
-        let metadata = match self.buffer_metadata.get(&self.active_buffer()) {
+        let metadata = match self.buffer_metadata.get(&buffer_id) {
             Some(m) => m,
             None => {
-                tracing::debug!(
-                    "notify_lsp_save: no metadata for buffer {:?}",
-                    self.active_buffer()
-                );
+                tracing::debug!("notify_lsp_save: no metadata for buffer {:?}", buffer_id);
                 return;
             }
         };
@@ -1899,7 +1993,7 @@ impl Editor {
             }
         };
 
-        let language = match detect_language(path, &self.config.languages) {
+        let language = match metadata.language(&self.config.languages) {
             Some(l) => l,
             None => {
                 tracing::debug!("notify_lsp_save: no language detected for {:?}", path);
@@ -1908,7 +2002,11 @@ impl Editor {
         };
 
         // Get the full text to send with didSave
-        let full_text = match self.active_state().buffer.to_string() {
+        let full_text = match self
+            .buffers
+            .get(&buffer_id)
+            .and_then(|state| state.buffer.to_string())
+        {
             Some(t) => t,
             None => {
                 tracing::debug!("notify_lsp_save: buffer not fully loaded");
@@ -1949,7 +2047,8 @@ impl Editor {
     /// Convert an action into a list of events to apply to the active buffer
     /// Returns None for actions that don't generate events (like Quit)
     pub fn action_to_events(&mut self, action: Action) -> Option<Vec<Event>> {
-        let tab_size = self.config.editor.tab_size;
+        // The buffer's own indentation, which may be detected from its contents
+        let tab_size = self.active_state().tab_size;
         let auto_indent = self.config.editor.auto_indent;
         let estimated_line_length = self.config.editor.estimated_line_length;
 
@@ -2334,6 +2433,7 @@ impl Editor {
 
         // Move cursor to the first match
         let match_pos = matches[current_match_index];
+        self.record_jump();
         {
             let active_split = self.split_manager.active_split();
             let active_buffer = self.active_buffer();
@@ -2433,6 +2533,7 @@ impl Editor {
             search_state.current_match_index = Some(next_index);
             let match_pos = match_positions[next_index];
             let matches_len = match_positions.len();
+            self.record_jump();
 
             {
                 let active_split = self.split_manager.active_split();
@@ -2499,6 +2600,7 @@ impl Editor {
             search_state.current_match_index = Some(prev_index);
             let match_pos = match_positions[prev_index];
             let matches_len = match_positions.len();
+            self.record_jump();
 
             {
                 let active_split = self.split_manager.active_split();
@@ -3154,27 +3256,23 @@ impl Editor {
     }
 
     /// Toggle comment on the current line or selection
+    ///
+    /// Uses the language's line comment prefix. Languages without one (e.g.
+    /// Markdown) wrap each line in their block comment tokens instead.
     pub(super) fn toggle_comment(&mut self) {
-        // Determine comment prefix from language config
-        // If no language detected or no comment prefix configured, do nothing
+        // Determine comment tokens from language config
+        // If no language detected or no comment tokens configured, do nothing
         let language = &self.active_state().language;
-        let comment_prefix = self
-            .config
-            .languages
-            .get(language)
-            .and_then(|lang_config| lang_config.comment_prefix.clone());
-
-        let comment_prefix: String = match comment_prefix {
-            Some(prefix) => {
-                // Ensure there's a trailing space for consistent formatting
-                if prefix.ends_with(' ') {
-                    prefix
-                } else {
-                    format!("{} ", prefix)
-                }
-            }
-            None => return, // No comment prefix for this language, do nothing
-        };
+        let buffer_config = crate::config::BufferConfig::resolve(&self.config, Some(language));
+
+        // Ensure there's a space between the tokens and the text for consistent formatting
+        let (comment_prefix, comment_suffix): (String, Option<String>) =
+            match (buffer_config.comment_prefix, buffer_config.block_comment) {
+                (Some(prefix), _) if prefix.ends_with(' ') => (prefix, None),
+                (Some(prefix), _) => (format!("{} ", prefix), None),
+                (None, Some((start, end))) => (format!("{} ", start), Some(format!(" {}", end))),
+                (None, None) => return, // No comment tokens for this language, do nothing
+            };
 
         let estimated_line_length = self.config.editor.estimated_line_length;
 
@@ -3221,9 +3319,35 @@ impl Editor {
                 .line_iterator(current_pos, estimated_line_length);
         }
 
+        // End of each line's content (excluding the line ending), for block comment suffixes
+        let line_ends: Vec<usize> = if comment_suffix.is_some() {
+            line_starts
+                .iter()
+                .map(|&line_start| {
+                    let mut iter = state
+                        .buffer
+                        .line_iterator(line_start, estimated_line_length);
+                    let content = iter.next_line().map(|(_, c)| c).unwrap_or_default();
+                    line_start + content.trim_end_matches(['\n', '\r']).len()
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let line_text = |start: usize, end: usize| -> String {
+            String::from_utf8_lossy(&state.buffer.slice_bytes(start..end)).to_string()
+        };
+
         // Determine if we should comment or uncomment
         // If all lines are commented, uncomment; otherwise comment
-        let all_commented = line_starts.iter().all(|&line_start| {
+        let all_commented = line_starts.iter().enumerate().all(|(i, &line_start)| {
+            if let Some(suffix) = &comment_suffix {
+                let line_str = line_text(line_start, line_ends[i]);
+                let trimmed = line_str.trim();
+                return trimmed.starts_with(comment_prefix.trim())
+                    && trimmed.ends_with(suffix.trim())
+                    && trimmed.len() >= comment_prefix.trim().len() + suffix.trim().len();
+            }
             let line_bytes = state
                 .buffer
                 .slice_bytes(line_start..buffer_len.min(line_start + comment_prefix.len() + 10));
@@ -3238,8 +3362,28 @@ impl Editor {
         let mut position_deltas: Vec<(usize, isize)> = Vec::new();
 
         if all_commented {
-            // Uncomment: remove comment prefix from each line
-            for &line_start in line_starts.iter().rev() {
+            // Uncomment: remove comment prefix (and block comment suffix) from each line
+            for (i, &line_start) in line_starts.iter().enumerate().rev() {
+                if let Some(suffix) = &comment_suffix {
+                    // Remove the suffix first, since it comes after the prefix
+                    let line_str = line_text(line_start, line_ends[i]);
+                    let content = line_str.trim_end();
+                    let content_end = line_start + content.len();
+                    let remove_len = if content.ends_with(suffix.as_str()) {
+                        suffix.len()
+                    } else {
+                        suffix.trim().len()
+                    };
+                    let range = (content_end - remove_len)..content_end;
+                    let deleted_text = line_text(range.start, range.end);
+                    events.push(Event::Delete {
+                        range: range.clone(),
+                        deleted_text,
+                        cursor_id,
+                    });
+                    position_deltas.push((range.start, -(remove_len as isize)));
+                }
+
                 let line_bytes = state
                     .buffer
                     .slice_bytes(line_start..buffer_len.min(line_start + 100));
@@ -3272,9 +3416,17 @@ impl Editor {
                 }
             }
         } else {
-            // Comment: add comment prefix to each line
+            // Comment: add comment prefix (and block comment suffix) to each line
             let prefix_len = comment_prefix.len();
-            for &line_start in line_starts.iter().rev() {
+            for (i, &line_start) in line_starts.iter().enumerate().rev() {
+                if let Some(suffix) = &comment_suffix {
+                    events.push(Event::Insert {
+                        position: line_ends[i],
+                        text: suffix.clone(),
+                        cursor_id,
+                    });
+                    position_deltas.push((line_ends[i], suffix.len() as isize));
+                }
                 events.push(Event::Insert {
                     position: line_start,
                     text: comment_prefix.to_string(),
@@ -3803,6 +3955,7 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -3878,6 +4031,7 @@ impl Editor {
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         };
         self.buffer_metadata.insert(buffer_id, metadata);
 
@@ -4026,6 +4180,7 @@ impl Editor {
         // Use the shared function to calculate tab widths (same as render_for_split)
         let (tab_widths, rendered_buffer_ids) = crate::view::ui::tabs::calculate_tab_widths(
             &split_buffers,
+            &view_state.pinned_buffers,
             &self.buffers,
             &self.buffer_metadata,
             &self.composite_buffers,
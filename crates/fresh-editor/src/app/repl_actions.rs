@@ -0,0 +1,216 @@
+//! Scratch buffers and REPL evaluation.
+//!
+//! Scratch buffers are unsaved buffers with a language mode, meant for quick
+//! experiments. Code from any buffer can be sent to the language's REPL
+//! command (`languages.<name>.repl`): the output is either appended to a
+//! `*REPL: <language>*` buffer shown next to the source, or inserted below
+//! the evaluated code.
+
+use std::ops::Range;
+
+use rust_i18n::t;
+
+use super::shell_command::run_with_stdin;
+use super::Editor;
+use crate::model::event::{BufferId, Event, SplitDirection};
+use crate::view::prompt::{Prompt, PromptType};
+use crate::view::split::SplitViewState;
+
+impl Editor {
+    /// Start the prompt for choosing the language of a new scratch buffer
+    pub(super) fn start_new_scratch_buffer_prompt(&mut self) {
+        let suggestions = self.language_suggestions("Plain Text");
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("repl.scratch_language_prompt").to_string(),
+            PromptType::NewScratchBuffer,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Handle NewScratchBuffer prompt confirmation.
+    pub(super) fn handle_new_scratch_buffer(&mut self, input: &str) {
+        let language = match input.trim() {
+            "" => "Plain Text",
+            language => language,
+        };
+        let buffer_id = self.new_buffer();
+        self.handle_set_language(language);
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.display_name = format!("*scratch: {}*", language);
+        }
+        self.set_status_message(t!("repl.scratch_created", language = language).to_string());
+    }
+
+    /// Evaluate the selection (or the current line) with the buffer language's REPL.
+    ///
+    /// With `insert`, the output is inserted below the evaluated code; otherwise
+    /// it is appended to the language's REPL buffer.
+    pub fn send_to_repl(&mut self, insert: bool) {
+        let language = self.active_state().language.clone();
+        let Some(command) = self.repl_command(&language) else {
+            let language = language.to_lowercase();
+            self.set_status_message(t!("repl.not_configured", language = language).to_string());
+            return;
+        };
+
+        let range = self.repl_input_range();
+        let code = self
+            .active_state_mut()
+            .get_text_range(range.start, range.end);
+        if code.trim().is_empty() {
+            self.set_status_message(t!("repl.nothing_to_send").to_string());
+            return;
+        }
+
+        let result = run_with_stdin(&command, &code);
+        if insert {
+            match result {
+                Ok(output) => self.insert_repl_output(range.end, &output),
+                Err(err) => self.set_status_message(err),
+            }
+        } else {
+            let output = result.unwrap_or_else(|err| format!("{}\n", err));
+            self.append_to_repl_buffer(&language.to_lowercase(), &code, &output);
+        }
+    }
+
+    /// The REPL command configured for a language
+    fn repl_command(&self, language: &str) -> Option<String> {
+        self.config
+            .languages
+            .get(language)
+            .or_else(|| self.config.languages.get(&language.to_lowercase()))
+            .and_then(|lang_config| lang_config.repl.clone())
+            .filter(|command| !command.trim().is_empty())
+    }
+
+    /// The selection, or the whole line under the cursor if nothing is selected
+    fn repl_input_range(&self) -> Range<usize> {
+        let state = self.active_state();
+        let primary = state.cursors.primary();
+        if let Some(selection) = primary.selection_range() {
+            return selection.start.min(selection.end)..selection.start.max(selection.end);
+        }
+        let line = state.buffer.get_line_number(primary.position);
+        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or(state.buffer.len());
+        line_start..line_end
+    }
+
+    /// Insert REPL output at `position`, on its own lines
+    fn insert_repl_output(&mut self, position: usize, output: &str) {
+        let output = output.trim_end_matches('\n');
+        if output.is_empty() {
+            self.set_status_message(t!("repl.no_output").to_string());
+            return;
+        }
+        let at_line_start = position == 0
+            || self
+                .active_state_mut()
+                .get_text_range(position - 1, position)
+                .ends_with('\n');
+        let text = if at_line_start {
+            format!("{}\n", output)
+        } else {
+            format!("\n{}", output)
+        };
+
+        let cursor_id = self.active_state().cursors.primary_id();
+        let event = Event::Insert {
+            position,
+            text,
+            cursor_id,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+    }
+
+    /// Append the evaluated code and its output to the language's REPL buffer,
+    /// showing the buffer in a split next to the source if it isn't visible.
+    fn append_to_repl_buffer(&mut self, language: &str, code: &str, output: &str) {
+        let buffer_id = self.repl_buffer(language);
+
+        let mut transcript: String = code.lines().map(|line| format!("> {}\n", line)).collect();
+        transcript.push_str(output);
+        if !output.is_empty() && !output.ends_with('\n') {
+            transcript.push('\n');
+        }
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let separator = if state.buffer.is_empty() { "" } else { "\n" };
+        let position = state.buffer.len();
+        state
+            .buffer
+            .insert(position, &format!("{}{}", separator, transcript));
+        state.buffer.clear_modified();
+        let end = state.buffer.len();
+        state.cursors.primary_mut().position = end;
+
+        // Keep the REPL output scrolled to the latest result
+        for split_id in self.split_manager.splits_for_buffer(buffer_id) {
+            if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                view_state.cursors.primary_mut().position = end;
+            }
+        }
+        self.set_status_message(t!("repl.evaluated", language = language).to_string());
+    }
+
+    /// The REPL output buffer for a language, created and shown on first use
+    fn repl_buffer(&mut self, language: &str) -> BufferId {
+        let existing = self
+            .repl_buffers
+            .get(language)
+            .copied()
+            .filter(|id| self.buffers.contains_key(id));
+        let buffer_id = match existing {
+            Some(id) => id,
+            None => {
+                let source_split = self.split_manager.active_split();
+                let id = self.create_virtual_buffer(
+                    format!("*REPL: {}*", language),
+                    "repl".to_string(),
+                    true,
+                );
+                // create_virtual_buffer adds a tab to the source split; the
+                // output belongs in its own split instead
+                if let Some(view_state) = self.split_view_states.get_mut(&source_split) {
+                    view_state.remove_buffer(id);
+                }
+                self.repl_buffers.insert(language.to_string(), id);
+                id
+            }
+        };
+
+        if self.split_manager.splits_for_buffer(buffer_id).is_empty() {
+            let source_split = self.split_manager.active_split();
+            self.save_current_split_view_state();
+            match self
+                .split_manager
+                .split_active(SplitDirection::Vertical, buffer_id, 0.5)
+            {
+                Ok(repl_split) => {
+                    let mut view_state = SplitViewState::with_buffer(
+                        self.terminal_width,
+                        self.terminal_height,
+                        buffer_id,
+                    );
+                    view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                    self.split_view_states.insert(repl_split, view_state);
+                    self.split_manager.set_active_split(source_split);
+                }
+                Err(e) => {
+                    self.set_status_message(t!("split.error", error = e.to_string()).to_string());
+                }
+            }
+        }
+        buffer_id
+    }
+}
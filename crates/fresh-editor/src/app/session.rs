@@ -28,13 +28,15 @@ use std::time::Instant;
 
 use crate::state::EditorState;
 
+use crate::input::jump_list::{JumpEntry, JumpList};
 use crate::model::event::{BufferId, SplitDirection, SplitId};
 use crate::services::terminal::TerminalId;
 use crate::session::{
     FileExplorerState, PersistedFileSession, SearchOptions, SerializedBookmark, SerializedCursor,
-    SerializedFileState, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
-    SerializedSplitViewState, SerializedTabRef, SerializedTerminalSession, SerializedViewMode,
-    Session, SessionConfigOverrides, SessionError, SessionHistories, SESSION_VERSION,
+    SerializedFileState, SerializedJump, SerializedScroll, SerializedSplitDirection,
+    SerializedSplitNode, SerializedSplitViewState, SerializedTabRef, SerializedTerminalSession,
+    SerializedViewMode, Session, SessionConfigOverrides, SessionError, SessionHistories,
+    SESSION_VERSION,
 };
 use crate::state::ViewMode;
 use crate::view::split::{SplitNode, SplitViewState};
@@ -288,6 +290,7 @@ impl Editor {
             bookmarks,
             terminals,
             external_files,
+            workspace_folders: self.workspace_folders.clone(),
             saved_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -308,7 +311,7 @@ impl Editor {
         self.save_all_global_file_states();
 
         let session = self.capture_session();
-        session.save()
+        session.save(self.recovery_service.codec())
     }
 
     /// Save global file states for all open file buffers
@@ -367,7 +370,7 @@ impl Editor {
         };
 
         // Save to disk immediately
-        PersistedFileSession::save(&abs_path, file_state);
+        PersistedFileSession::save(&abs_path, file_state, self.recovery_service.codec());
     }
 
     /// Sync all active terminal visible screens to their backing files.
@@ -413,7 +416,7 @@ impl Editor {
     /// Returns true if a session was successfully loaded and applied.
     pub fn try_restore_session(&mut self) -> Result<bool, SessionError> {
         tracing::debug!("Attempting to restore session for {:?}", self.working_dir);
-        match Session::load(&self.working_dir)? {
+        match Session::load(&self.working_dir, self.recovery_service.codec())? {
             Some(session) => {
                 tracing::info!("Found session, applying...");
                 self.apply_session(&session)?;
@@ -482,7 +485,8 @@ impl Editor {
                 .push(item.clone());
         }
 
-        // 4. Restore file explorer state
+        // 4. Restore workspace folders and the file explorer state
+        self.restore_workspace_folders(&session.workspace_folders);
         self.file_explorer_visible = session.file_explorer.visible;
         self.file_explorer_width_percent = session.file_explorer.width_percent;
 
@@ -908,13 +912,15 @@ impl Editor {
         let mut active_buffer_id: Option<BufferId> = None;
 
         if !split_state.open_tabs.is_empty() {
-            for tab in &split_state.open_tabs {
+            for (tab_index, tab) in split_state.open_tabs.iter().enumerate() {
+                let pinned = tab_index < split_state.pinned_tabs;
                 match tab {
                     SerializedTabRef::File(rel_path) => {
                         if let Some(&buffer_id) = path_to_buffer.get(rel_path) {
                             if !view_state.open_buffers.contains(&buffer_id) {
                                 view_state.open_buffers.push(buffer_id);
                             }
+                            view_state.set_pinned(buffer_id, pinned);
                             if terminal_buffers.values().any(|&tid| tid == buffer_id) {
                                 view_state.viewport.line_wrap_enabled = false;
                             }
@@ -925,6 +931,7 @@ impl Editor {
                             if !view_state.open_buffers.contains(&buffer_id) {
                                 view_state.open_buffers.push(buffer_id);
                             }
+                            view_state.set_pinned(buffer_id, pinned);
                             view_state.viewport.line_wrap_enabled = false;
                         }
                     }
@@ -1013,9 +1020,24 @@ impl Editor {
         view_state.view_mode = match split_state.view_mode {
             SerializedViewMode::Source => ViewMode::Source,
             SerializedViewMode::Compose => ViewMode::Compose,
+            SerializedViewMode::Preview => ViewMode::Preview,
         };
         view_state.compose_width = split_state.compose_width;
         view_state.tab_scroll_offset = split_state.tab_scroll_offset;
+
+        // Restore the jump list, dropping entries for files that weren't reopened
+        let mut jump_entries = Vec::new();
+        let mut jump_index = None;
+        for (index, jump) in split_state.jump_list.iter().enumerate() {
+            if index == split_state.jump_list_index {
+                jump_index = Some(jump_entries.len());
+            }
+            if let Some(&buffer_id) = path_to_buffer.get(&jump.file_path) {
+                jump_entries.push(JumpEntry::new(buffer_id, jump.position));
+            }
+        }
+        let jump_index = jump_index.unwrap_or(jump_entries.len());
+        view_state.jump_list = JumpList::from_entries(jump_entries, jump_index);
     }
 }
 
@@ -1120,9 +1142,15 @@ fn serialize_split_view_state(
     let mut open_tabs = Vec::new();
     let mut open_files = Vec::new();
     let mut active_tab_index = None;
+    // Pinned tabs come first, so they're the tabs saved before the first
+    // unpinned one
+    let mut pinned_tabs = None;
 
     for buffer_id in &view_state.open_buffers {
         let tab_index = open_tabs.len();
+        if pinned_tabs.is_none() && !view_state.is_pinned(*buffer_id) {
+            pinned_tabs = Some(tab_index);
+        }
         if let Some(terminal_id) = terminal_buffers.get(buffer_id) {
             if let Some(idx) = terminal_indices.get(terminal_id) {
                 open_tabs.push(SerializedTabRef::Terminal(*idx));
@@ -1146,6 +1174,8 @@ fn serialize_split_view_state(
         }
     }
 
+    let pinned_tabs = pinned_tabs.unwrap_or(open_tabs.len());
+
     // Derive active_file_index for backward compatibility
     let active_file_index = active_tab_index
         .and_then(|idx| open_tabs.get(idx))
@@ -1195,9 +1225,30 @@ fn serialize_split_view_state(
         }
     }
 
+    // Serialize the jump list - only entries in files can be restored
+    let mut jump_list = Vec::new();
+    let mut jump_list_index = None;
+    for (index, entry) in view_state.jump_list.entries().iter().enumerate() {
+        if index == view_state.jump_list.index() {
+            jump_list_index = Some(jump_list.len());
+        }
+        if let Some(rel_path) = buffer_metadata
+            .get(&entry.buffer_id)
+            .and_then(|meta| meta.file_path())
+            .and_then(|abs_path| abs_path.strip_prefix(working_dir).ok())
+        {
+            jump_list.push(SerializedJump {
+                file_path: rel_path.to_path_buf(),
+                position: entry.position,
+            });
+        }
+    }
+    let jump_list_index = jump_list_index.unwrap_or(jump_list.len());
+
     SerializedSplitViewState {
         open_tabs,
         active_tab_index,
+        pinned_tabs,
         open_files,
         active_file_index,
         file_states,
@@ -1205,8 +1256,11 @@ fn serialize_split_view_state(
         view_mode: match view_state.view_mode {
             ViewMode::Source => SerializedViewMode::Source,
             ViewMode::Compose => SerializedViewMode::Compose,
+            ViewMode::Preview => SerializedViewMode::Preview,
         },
         compose_width: view_state.compose_width,
+        jump_list,
+        jump_list_index,
     }
 }
 
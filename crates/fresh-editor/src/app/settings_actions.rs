@@ -64,6 +64,7 @@ impl Editor {
     /// Save the settings from the modal to config
     pub fn save_settings(&mut self) {
         let old_theme = self.config.theme.clone();
+        let old_high_contrast = self.config.editor.high_contrast;
         let old_locale = self.config.locale.clone();
         let old_plugins = self.config.plugins.clone();
 
@@ -99,8 +100,8 @@ impl Editor {
         self.user_config_raw = Config::read_user_config_raw(&self.working_dir);
 
         // Apply runtime changes
-        if old_theme != self.config.theme {
-            if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
+        if old_theme != self.config.theme || old_high_contrast != self.config.editor.high_contrast {
+            if let Some(theme) = self.load_theme(&self.config.theme) {
                 self.theme = theme;
                 tracing::info!("Theme changed to '{}'", self.config.theme.0);
             } else {
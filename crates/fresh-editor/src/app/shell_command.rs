@@ -3,12 +3,18 @@
 //! This module provides functionality to:
 //! - Run shell commands with buffer or selection content as stdin
 //! - Output results to a new buffer or replace the input content
+//!
+//! Replacing runs the command as a filter in the background: the input range
+//! and buffer version are captured up front, and the output is only applied
+//! if the buffer hasn't been edited in the meantime.
 
 use std::io::Write;
+use std::ops::Range;
 use std::process::{Command, Stdio};
 
 use super::Editor;
-use crate::model::event::Event;
+use crate::model::event::{BufferId, Event, PopupContentData, PopupData, PopupPositionData};
+use crate::services::async_bridge::AsyncMessage;
 use crate::view::prompt::PromptType;
 use rust_i18n::t;
 
@@ -31,48 +37,7 @@ impl Editor {
         // Get the input text (selection or entire buffer)
         let input = self.get_shell_input();
 
-        // Detect the shell to use
-        let shell = detect_shell();
-
-        // Execute the command
-        let mut child = Command::new(&shell)
-            .args(["-c", command])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to spawn shell: {}", e))?;
-
-        // Write input to stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(input.as_bytes())
-                .map_err(|e| format!("Failed to write to stdin: {}", e))?;
-        }
-
-        // Wait for the command to complete
-        let output = child
-            .wait_with_output()
-            .map_err(|e| format!("Failed to wait for command: {}", e))?;
-
-        if output.status.success() {
-            String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8 in output: {}", e))
-        } else {
-            // Include stderr in error message
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if !stderr.is_empty() {
-                Err(format!("Command failed: {}", stderr.trim()))
-            } else if !stdout.is_empty() {
-                // Some commands output errors to stdout
-                Err(format!("Command failed: {}", stdout.trim()))
-            } else {
-                Err(format!(
-                    "Command failed with exit code: {:?}",
-                    output.status.code()
-                ))
-            }
-        }
+        run_with_stdin(command, &input)
     }
 
     /// Get the input for shell command (selection or entire buffer).
@@ -95,43 +60,155 @@ impl Editor {
     }
 
     /// Handle shell command execution after prompt confirmation.
-    /// If `replace` is true, replaces the selection/buffer with output.
+    /// If `replace` is true, filters the selection/buffer through the command
+    /// in the background and replaces it with the output.
     /// If `replace` is false, creates a new buffer with the output.
     pub fn handle_shell_command(&mut self, command: &str, replace: bool) {
-        // Capture selection range first
-        let selection_range = {
-            let state = self.active_state();
-            let primary = state.cursors.primary();
-            primary.selection_range().map(|sel| {
-                let start = sel.start.min(sel.end);
-                let end = sel.start.max(sel.end);
-                (start, end)
-            })
-        };
+        if replace {
+            self.start_shell_filter(command);
+            return;
+        }
 
-        // Now get the deleted text if there's a selection
-        let selection_info = if let Some((start, end)) = selection_range {
-            let deleted_text = self.active_state_mut().get_text_range(start, end);
-            Some((start, end, deleted_text))
-        } else {
-            None
+        match self.execute_shell_command(command) {
+            Ok(output) => self.create_shell_output_buffer(command, &output),
+            Err(err) => self.set_status_message(err),
+        }
+    }
+
+    /// Pipe the selection (or the whole buffer) through `command` in the background.
+    /// The result arrives as [`AsyncMessage::ShellFilterFinished`].
+    fn start_shell_filter(&mut self, command: &str) {
+        let buffer_id = self.active_buffer();
+        let selection = self
+            .active_state()
+            .cursors
+            .primary()
+            .selection_range()
+            .map(|sel| sel.start.min(sel.end)..sel.start.max(sel.end));
+        let version = self.active_state().buffer.revision();
+        let input = self.get_shell_input();
+
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            // No async runtime - run the filter inline
+            let result = run_with_stdin(command, &input);
+            self.handle_shell_filter_finished(
+                buffer_id,
+                selection,
+                version,
+                command.to_string(),
+                result,
+            );
+            return;
         };
-        let has_selection = selection_info.is_some();
 
-        match self.execute_shell_command(command) {
-            Ok(output) => {
-                if replace {
-                    self.replace_with_shell_output(&output, has_selection, selection_info);
-                } else {
-                    self.create_shell_output_buffer(command, &output);
-                }
-            }
+        let sender = bridge.sender();
+        let command = command.to_string();
+        let display_command = truncate_command(&command, 30);
+        runtime.spawn_blocking(move || {
+            let result = run_with_stdin(&command, &input);
+            let _ = sender.send(AsyncMessage::ShellFilterFinished {
+                buffer_id,
+                selection,
+                version,
+                command,
+                result,
+            });
+        });
+        self.set_status_message(t!("shell.filter_running", command = display_command).to_string());
+    }
+
+    /// Apply the result of a background filter started by `start_shell_filter`.
+    ///
+    /// Output replaces the filtered text as a single undoable edit; failures are
+    /// shown in a popup so multi-line stderr stays readable.
+    pub(super) fn handle_shell_filter_finished(
+        &mut self,
+        buffer_id: BufferId,
+        selection: Option<Range<usize>>,
+        version: u64,
+        command: String,
+        result: Result<String, String>,
+    ) {
+        let display_command = truncate_command(&command, 30);
+        let output = match result {
+            Ok(output) => output,
             Err(err) => {
-                self.set_status_message(err);
+                self.show_shell_filter_error(&display_command, &err);
+                return;
             }
+        };
+
+        let Some(current_version) = self.buffers.get(&buffer_id).map(|s| s.buffer.revision())
+        else {
+            return;
+        };
+        if current_version != version {
+            self.set_status_message(
+                t!("shell.filter_discarded", command = display_command).to_string(),
+            );
+            return;
+        }
+
+        if buffer_id == self.active_buffer() {
+            let selection_info = selection.map(|range| {
+                let deleted_text = self
+                    .active_state_mut()
+                    .get_text_range(range.start, range.end);
+                (range.start, range.end, deleted_text)
+            });
+            self.replace_with_shell_output(&output, selection_info.is_some(), selection_info);
+            return;
+        }
+
+        // The user switched buffers while the filter ran
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let range = selection.unwrap_or(0..state.buffer.len());
+        let deleted_text = state.get_text_range(range.start, range.end);
+        let cursor_id = state.cursors.primary_id();
+        let events = vec![
+            Event::Delete {
+                range: range.clone(),
+                deleted_text,
+                cursor_id,
+            },
+            Event::Insert {
+                position: range.start,
+                text: output,
+                cursor_id,
+            },
+        ];
+        match self.apply_events_to_buffer_as_bulk_edit(
+            buffer_id,
+            events,
+            "Shell command replace".to_string(),
+        ) {
+            Ok(()) => self.set_status_message(t!("status.shell_command_completed").to_string()),
+            Err(e) => self
+                .set_status_message(t!("shell.command_failed", error = e.to_string()).to_string()),
         }
     }
 
+    /// Show a failed filter's error output in a popup
+    fn show_shell_filter_error(&mut self, command: &str, error: &str) {
+        let error = error.strip_prefix("Command failed: ").unwrap_or(error);
+        let lines: Vec<String> = error.lines().map(|line| line.to_string()).collect();
+        let max_height = (lines.len() as u16).clamp(1, 15) + 2;
+        let popup = PopupData {
+            title: Some(t!("shell.filter_failed", command = command).to_string()),
+            description: None,
+            transient: false,
+            content: PopupContentData::Text(lines),
+            position: PopupPositionData::Centered,
+            width: 70,
+            max_height,
+            bordered: true,
+        };
+        self.show_popup(popup);
+        self.set_status_message(t!("shell.filter_failed", command = command).to_string());
+    }
+
     /// Replace the current selection or buffer with shell output.
     fn replace_with_shell_output(
         &mut self,
@@ -285,8 +362,59 @@ impl Editor {
     }
 }
 
+/// Run a shell command with `input` as stdin.
+/// Returns Ok(stdout) on success, Err(error_message) on failure.
+pub(super) fn run_with_stdin(command: &str, input: &str) -> Result<String, String> {
+    // Detect the shell to use
+    let shell = detect_shell();
+
+    // Execute the command
+    let mut child = Command::new(&shell)
+        .args(["-c", command])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn shell: {}", e))?;
+
+    // Write input to stdin. A command may exit without reading all of it;
+    // its exit status and output still say how it went.
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(input.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                return Err(format!("Failed to write to stdin: {}", e));
+            }
+            _ => {}
+        }
+    }
+
+    // Wait for the command to complete
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for command: {}", e))?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).map_err(|e| format!("Invalid UTF-8 in output: {}", e))
+    } else {
+        // Include stderr in error message
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !stderr.is_empty() {
+            Err(format!("Command failed: {}", stderr.trim()))
+        } else if !stdout.is_empty() {
+            // Some commands output errors to stdout
+            Err(format!("Command failed: {}", stdout.trim()))
+        } else {
+            Err(format!(
+                "Command failed with exit code: {:?}",
+                output.status.code()
+            ))
+        }
+    }
+}
+
 /// Detect the shell to use for executing commands.
-fn detect_shell() -> String {
+pub(super) fn detect_shell() -> String {
     // Try SHELL environment variable first
     if let Ok(shell) = std::env::var("SHELL") {
         if !shell.is_empty() {
@@ -0,0 +1,94 @@
+//! Reporting the editor's working directory, current file and progress to
+//! the terminal and the shell (see `services::shell_integration`)
+
+use std::path::PathBuf;
+
+use super::Editor;
+use crate::services::shell_integration::{self, TerminalProgress};
+
+impl Editor {
+    /// The directory the user is working in: the one selected in the file
+    /// explorer while it's open, otherwise the project root.
+    /// None when editing a remote machine, whose paths mean nothing locally.
+    pub fn current_directory(&self) -> Option<PathBuf> {
+        if self.remote_connection_info().is_some() {
+            return None;
+        }
+        let explorer_dir = self
+            .file_explorer
+            .as_ref()
+            .filter(|_| self.file_explorer_visible)
+            .and_then(|explorer| explorer.get_selected_entry())
+            .and_then(|entry| {
+                if entry.is_dir() {
+                    Some(entry.path.clone())
+                } else {
+                    entry.path.parent().map(|p| p.to_path_buf())
+                }
+            });
+        Some(explorer_dir.unwrap_or_else(|| self.working_dir.clone()))
+    }
+
+    /// Tell the terminal about the current directory with OSC 7 when it
+    /// changes. Called once per frame.
+    pub fn update_terminal_working_dir(&mut self) {
+        let Some(dir) = self.current_directory() else {
+            return;
+        };
+        if self.reported_working_dir.as_ref() == Some(&dir) {
+            return;
+        }
+        if let Err(e) = shell_integration::report_working_dir(&dir) {
+            tracing::debug!("Failed to report working directory: {}", e);
+        }
+        self.reported_working_dir = Some(dir);
+    }
+
+    /// The window title: the active buffer's name, marked `*` when modified
+    pub fn terminal_title(&self) -> String {
+        let buffer_id = self.active_buffer();
+        let name = self.get_buffer_display_name(buffer_id);
+        let modified = self
+            .buffers
+            .get(&buffer_id)
+            .is_some_and(|state| state.buffer.is_modified());
+        format!("{}{} - Fresh", name, if modified { "*" } else { "" })
+    }
+
+    /// Progress of the current long operation: language server work (with
+    /// its percentage when the server reports one) or reading stdin
+    pub fn terminal_progress(&self) -> TerminalProgress {
+        if let Some(percent) = self.lsp_progress.values().find_map(|info| info.percentage) {
+            return TerminalProgress::Percent(percent.min(100) as u8);
+        }
+        let reading_stdin = self
+            .stdin_streaming
+            .as_ref()
+            .is_some_and(|stream_state| !stream_state.complete);
+        if !self.lsp_progress.is_empty() || reading_stdin {
+            TerminalProgress::Indeterminate
+        } else {
+            TerminalProgress::Idle
+        }
+    }
+
+    /// Update the window title and the progress indicator when they change.
+    /// Called once per frame.
+    pub fn update_terminal_title(&mut self) {
+        let title = self.terminal_title();
+        if self.reported_title.as_ref() != Some(&title) {
+            if let Err(e) = shell_integration::set_title(&title) {
+                tracing::debug!("Failed to set terminal title: {}", e);
+            }
+            self.reported_title = Some(title);
+        }
+
+        let progress = self.terminal_progress();
+        if self.reported_progress != progress {
+            if let Err(e) = shell_integration::report_progress(progress) {
+                tracing::debug!("Failed to report progress: {}", e);
+            }
+            self.reported_progress = progress;
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Spell checking actions.
+//!
+//! This module underlines misspelled words in the visible part of each buffer
+//! and handles the spell checking commands: toggling, the suggestions popup,
+//! adding words to the personal dictionary and choosing the dictionary language.
+
+use std::ops::Range;
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::event::{
+    BufferId, Event, PopupContentData, PopupData, PopupListItemData, PopupPositionData,
+};
+use crate::services::spell::{self, SpellScope};
+use crate::view::overlay::{Overlay, OverlayFace, UnderlineStyle};
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Lines parsed above the viewport, so that comments and code blocks starting
+/// off-screen are still recognized
+const CONTEXT_LINES: usize = 100;
+
+/// Popup item data for "add to dictionary"; corrections use `replace:<word>`
+const ADD_TO_DICTIONARY: &str = "add-to-dictionary";
+
+impl Editor {
+    /// Underline the misspelled words in lines `start_line..=end_line` of a buffer.
+    ///
+    /// Called on every render; the check is skipped when neither the buffer nor
+    /// the visible range changed since the last one.
+    pub(super) fn update_spell_check_overlays(
+        &mut self,
+        buffer_id: BufferId,
+        start_line: usize,
+        end_line: usize,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let ns = spell::spell_namespace();
+        if !state.spell.enabled {
+            if state.spell.checked.take().is_some() {
+                state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            }
+            return;
+        }
+
+        let buffer_len = state.buffer.len();
+        let window_start = state
+            .buffer
+            .line_start_offset(start_line.saturating_sub(CONTEXT_LINES))
+            .unwrap_or(0);
+        let visible_start = state
+            .buffer
+            .line_start_offset(start_line)
+            .unwrap_or(window_start);
+        let visible_end = state
+            .buffer
+            .line_start_offset(end_line + 1)
+            .unwrap_or(buffer_len);
+        let checked = Some((state.buffer.revision(), visible_start..visible_end));
+        if state.spell.checked == checked {
+            return;
+        }
+        state.spell.checked = checked;
+
+        let bytes = state.buffer.slice_bytes(window_start..visible_end);
+        let text = match std::str::from_utf8(&bytes) {
+            Ok(text) => text,
+            Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+        };
+        let scope = SpellScope::for_language(&state.language, state.highlighter.language());
+        let ranges = scope.checkable_ranges(text);
+        let misspellings = self
+            .spell_checker
+            .misspellings(&state.spell.language, text, &ranges);
+
+        state.overlays.clear_namespace(&ns, &mut state.marker_list);
+        for word in misspellings {
+            let range = window_start + word.start..window_start + word.end;
+            if range.end <= visible_start {
+                continue;
+            }
+            let face = OverlayFace::Underline {
+                color: self.theme.diagnostic_info_fg,
+                style: UnderlineStyle::Wavy,
+            };
+            let overlay = Overlay::with_namespace(&mut state.marker_list, range, face, ns.clone());
+            state.overlays.add(overlay);
+        }
+    }
+
+    /// Toggle spell checking for the active buffer
+    pub fn toggle_spell_check(&mut self) {
+        let spell = &mut self.active_state_mut().spell;
+        spell.enabled = !spell.enabled;
+        let enabled = spell.enabled;
+        let language = spell.language.clone();
+
+        if enabled && !self.spell_checker.has_dictionary(&language) {
+            self.set_status_message(t!("spell.no_dictionary", language = language).to_string());
+            return;
+        }
+        let state = if enabled {
+            t!("view.state_enabled").to_string()
+        } else {
+            t!("view.state_disabled").to_string()
+        };
+        self.set_status_message(t!("spell.state", state = state).to_string());
+    }
+
+    /// Show correction suggestions for the misspelled word at the cursor
+    pub fn show_spell_suggestions(&mut self) {
+        let Some((range, word)) = self.spell_word_at_cursor() else {
+            self.set_status_message(t!("spell.no_word").to_string());
+            return;
+        };
+        let language = self.active_state().spell.language.clone();
+        if !self.spell_checker.has_dictionary(&language) {
+            self.set_status_message(t!("spell.no_dictionary", language = language).to_string());
+            return;
+        }
+        if self.spell_checker.is_correct(&language, &word) {
+            self.set_status_message(t!("spell.correct", word = word).to_string());
+            return;
+        }
+
+        let mut items: Vec<PopupListItemData> = self
+            .spell_checker
+            .suggest(&language, &word)
+            .into_iter()
+            .map(|suggestion| PopupListItemData {
+                data: Some(format!("replace:{}", suggestion)),
+                text: suggestion,
+                detail: None,
+                icon: None,
+            })
+            .collect();
+        items.push(PopupListItemData {
+            text: t!("spell.add_to_dictionary", word = word).to_string(),
+            detail: None,
+            icon: None,
+            data: Some(ADD_TO_DICTIONARY.to_string()),
+        });
+
+        let popup = PopupData {
+            title: Some(t!("spell.suggestions_title", word = word).to_string()),
+            description: None,
+            transient: false,
+            content: PopupContentData::List { items, selected: 0 },
+            position: PopupPositionData::BelowCursor,
+            width: 40,
+            max_height: 12,
+            bordered: true,
+        };
+        self.pending_spell_suggestion = Some((self.active_buffer(), range));
+        self.show_popup(popup);
+    }
+
+    /// Handle the choice made in the spell suggestions popup
+    pub(super) fn handle_spell_suggestion_response(
+        &mut self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        choice: &str,
+    ) {
+        if buffer_id != self.active_buffer() {
+            return;
+        }
+        if choice == ADD_TO_DICTIONARY {
+            let word = self
+                .active_state_mut()
+                .get_text_range(range.start, range.end);
+            self.add_word_to_dictionary(&word);
+        } else if let Some(replacement) = choice.strip_prefix("replace:") {
+            let cursor_id = self.active_state().cursors.primary_id();
+            let deleted_text = self
+                .active_state_mut()
+                .get_text_range(range.start, range.end);
+            let batch = Event::Batch {
+                events: vec![
+                    Event::Delete {
+                        range: range.clone(),
+                        deleted_text,
+                        cursor_id,
+                    },
+                    Event::Insert {
+                        position: range.start,
+                        text: replacement.to_string(),
+                        cursor_id,
+                    },
+                ],
+                description: t!("spell.correct_spelling").to_string(),
+            };
+            self.active_event_log_mut().append(batch.clone());
+            self.apply_event_to_active_buffer(&batch);
+        }
+    }
+
+    /// Add the word at the cursor to the personal dictionary
+    pub fn add_spell_word_at_cursor(&mut self) {
+        match self.spell_word_at_cursor() {
+            Some((_, word)) => self.add_word_to_dictionary(&word),
+            None => self.set_status_message(t!("spell.no_word").to_string()),
+        }
+    }
+
+    /// Start the prompt for choosing the active buffer's dictionary language
+    pub(super) fn start_set_spell_language_prompt(&mut self) {
+        let current_language = self.active_state().spell.language.clone();
+        let suggestions: Vec<Suggestion> = self
+            .spell_checker
+            .available_languages()
+            .into_iter()
+            .map(|language| Suggestion {
+                description: (language == current_language).then(|| "current".to_string()),
+                value: Some(language.clone()),
+                text: language,
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        let current_index = suggestions
+            .iter()
+            .position(|s| s.value.as_deref() == Some(current_language.as_str()));
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("spell.language_prompt").to_string(),
+            PromptType::SetSpellLanguage,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = current_index;
+        }
+    }
+
+    /// Handle SetSpellLanguage prompt confirmation.
+    pub(super) fn handle_set_spell_language(&mut self, input: &str) {
+        let language = input.trim().to_string();
+        if language.is_empty() {
+            return;
+        }
+        if !self.spell_checker.has_dictionary(&language) {
+            self.set_status_message(t!("spell.no_dictionary", language = language).to_string());
+            return;
+        }
+        let spell = &mut self.active_state_mut().spell;
+        spell.language = language.clone();
+        spell.checked = None;
+        self.set_status_message(t!("spell.language_set", language = language).to_string());
+    }
+
+    fn add_word_to_dictionary(&mut self, word: &str) {
+        if let Err(e) = self.spell_checker.add_to_personal_dictionary(word) {
+            tracing::warn!("Failed to save personal dictionary: {}", e);
+        }
+        // Recheck every buffer so the word stops being underlined everywhere
+        for state in self.buffers.values_mut() {
+            state.spell.checked = None;
+        }
+        self.set_status_message(t!("spell.word_added", word = word).to_string());
+    }
+
+    /// The word under (or just before) the primary cursor and its byte range
+    fn spell_word_at_cursor(&mut self) -> Option<(Range<usize>, String)> {
+        let state = self.active_state_mut();
+        let cursor = state.cursors.primary().position;
+        let line = state.buffer.get_line_number(cursor);
+        let line_start = state.buffer.line_start_offset(line)?;
+        let line_end = state
+            .buffer
+            .line_start_offset(line + 1)
+            .unwrap_or(state.buffer.len());
+        let text = state.get_text_range(line_start, line_end);
+        spell::words(&text)
+            .into_iter()
+            .find(|word| (line_start + word.start..=line_start + word.end).contains(&cursor))
+            .map(|word| {
+                let range = line_start + word.start..line_start + word.end;
+                (range, text[word].to_string())
+            })
+    }
+}
@@ -0,0 +1,73 @@
+//! Tab actions
+//!
+//! Pinning tabs, and the tab context menu actions that aren't about closing
+//! tabs: revealing a tab's file in the file explorer and copying its path.
+
+use super::Editor;
+use crate::model::event::{BufferId, SplitId};
+use rust_i18n::t;
+
+impl Editor {
+    /// Pin a tab of a split, or unpin it if it's pinned
+    pub fn toggle_tab_pinned(&mut self, buffer_id: BufferId, split_id: SplitId) {
+        let Some(view_state) = self.split_view_states.get_mut(&split_id) else {
+            return;
+        };
+        if !view_state.has_buffer(buffer_id) {
+            return;
+        }
+        let pinned = !view_state.is_pinned(buffer_id);
+        view_state.set_pinned(buffer_id, pinned);
+
+        let name = self.get_buffer_display_name(buffer_id);
+        let message = if pinned {
+            t!("tab.pinned", name = name)
+        } else {
+            t!("tab.unpinned", name = name)
+        };
+        self.set_status_message(message.to_string());
+
+        // The tab moved, so keep the active one in view
+        if split_id == self.split_manager.active_split() {
+            self.ensure_active_tab_visible(
+                split_id,
+                self.active_buffer(),
+                self.effective_tabs_width(),
+            );
+        }
+    }
+
+    /// Pin the active tab, or unpin it if it's pinned
+    pub fn toggle_active_tab_pinned(&mut self) {
+        self.toggle_tab_pinned(self.active_buffer(), self.split_manager.active_split());
+    }
+
+    /// Show a tab and select its file in the file explorer
+    pub(super) fn reveal_tab_in_file_explorer(&mut self, buffer_id: BufferId, split_id: SplitId) {
+        if self.tab_file_path(buffer_id).is_none() {
+            self.set_status_message(t!("tab.no_path").to_string());
+            return;
+        }
+        self.focus_split(split_id, buffer_id);
+        // Focusing the explorer opens it if needed and selects the active file
+        self.focus_file_explorer();
+    }
+
+    /// Copy the path of a tab's file to the clipboard
+    pub(super) fn copy_tab_path(&mut self, buffer_id: BufferId) {
+        let Some(path) = self.tab_file_path(buffer_id) else {
+            self.set_status_message(t!("tab.no_path").to_string());
+            return;
+        };
+        let path = path.display().to_string();
+        self.clipboard.copy(path.clone());
+        self.set_status_message(t!("tab.path_copied", path = path).to_string());
+    }
+
+    fn tab_file_path(&self, buffer_id: BufferId) -> Option<std::path::PathBuf> {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .and_then(|m| m.file_path())
+            .map(|path| path.to_path_buf())
+    }
+}
@@ -233,6 +233,8 @@ impl Editor {
                 };
                 let final_idx = adjusted_idx.min(view_state.open_buffers.len());
                 view_state.open_buffers.insert(final_idx, buffer_id);
+                // Tabs can't be dropped across the pinned/unpinned boundary
+                view_state.keep_pinned_first();
             }
         }
     }
@@ -254,7 +256,7 @@ impl Editor {
 
         // Remove from source split's tab bar
         if let Some(source_view_state) = self.split_view_states.get_mut(&source_split_id) {
-            source_view_state.open_buffers.retain(|&id| id != buffer_id);
+            source_view_state.remove_buffer(buffer_id);
 
             // If the source split was showing this buffer, switch to another
             if self.split_manager.get_buffer_id(source_split_id) == Some(buffer_id) {
@@ -273,6 +275,7 @@ impl Editor {
                 let idx = insert_idx.unwrap_or(target_view_state.open_buffers.len());
                 let final_idx = idx.min(target_view_state.open_buffers.len());
                 target_view_state.open_buffers.insert(final_idx, buffer_id);
+                target_view_state.keep_pinned_first();
             }
         }
 
@@ -313,7 +316,7 @@ impl Editor {
         let source_had_buffer =
             if let Some(source_view_state) = self.split_view_states.get_mut(&source_split_id) {
                 let had = source_view_state.open_buffers.contains(&buffer_id);
-                source_view_state.open_buffers.retain(|&id| id != buffer_id);
+                source_view_state.remove_buffer(buffer_id);
 
                 // If the source split was showing this buffer, switch to another
                 if self.split_manager.get_buffer_id(source_split_id) == Some(buffer_id) {
@@ -0,0 +1,328 @@
+//! Navigation with ctags and GNU Global, for projects without a language
+//! server
+//!
+//! Definitions are looked up in a ctags `tags` file in the working
+//! directory, or with `global` when there's a GTAGS database instead. Going
+//! to a tag pushes the location it was made from on the tag stack, and Pop
+//! Tag returns there. Regenerate Tags rebuilds the tags in the background.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::jump_list::JumpEntry;
+use crate::primitives::ctags::{self, Tag};
+use crate::primitives::word_navigation::{find_word_end, find_word_start};
+use crate::services::async_bridge::AsyncMessage;
+use crate::view::prompt::{Prompt, PromptType};
+
+/// Tags files looked for in the working directory
+const TAGS_FILE_NAMES: &[&str] = &["tags", ".tags"];
+
+/// The GNU Global database looked for in the working directory
+const GTAGS_FILE_NAME: &str = "GTAGS";
+
+/// The tags file, read again when it changes
+pub(super) struct TagsCache {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    /// Sorted by name, with files relative to the working directory resolved
+    tags: Vec<Tag>,
+    /// The names of the tags, for word completion
+    pub(super) names: Vec<String>,
+}
+
+impl Editor {
+    /// Read the tags file in the working directory if it's new or changed
+    pub(super) fn refresh_tags(&mut self) {
+        let found = TAGS_FILE_NAMES.iter().find_map(|name| {
+            let path = self.working_dir.join(name);
+            let metadata = self.filesystem.metadata_if_exists(&path)?;
+            (!self.filesystem.is_dir(&path).unwrap_or(false)).then_some((path, metadata.modified))
+        });
+        let Some((path, modified)) = found else {
+            self.tags_cache = None;
+            return;
+        };
+        if self
+            .tags_cache
+            .as_ref()
+            .is_some_and(|tags| tags.path == path && tags.modified == modified)
+        {
+            return;
+        }
+        self.tags_cache = match self.filesystem.read_file(&path) {
+            Ok(contents) => {
+                let mut tags = ctags::parse_tags(&String::from_utf8_lossy(&contents));
+                for tag in &mut tags {
+                    tag.file = self.working_dir.join(&tag.file);
+                }
+                Some(TagsCache {
+                    names: ctags::tag_names(&tags),
+                    path,
+                    modified,
+                    tags,
+                })
+            }
+            Err(e) => {
+                tracing::warn!("Failed to read tags file {}: {}", path.display(), e);
+                None
+            }
+        };
+    }
+
+    /// Whether definitions can be looked up in a tags file or GTAGS database
+    pub(super) fn has_tags(&mut self) -> bool {
+        self.refresh_tags();
+        self.tags_cache.is_some() || self.uses_gtags()
+    }
+
+    /// Whether tags come from GNU Global: there's a GTAGS database but no
+    /// tags file
+    fn uses_gtags(&self) -> bool {
+        self.tags_cache.is_none()
+            && self
+                .filesystem
+                .exists(&self.working_dir.join(GTAGS_FILE_NAME))
+    }
+
+    /// The definitions of `name`, or of every tag if `name` is `None`
+    fn find_tags(&mut self, name: Option<&str>) -> Vec<Tag> {
+        self.refresh_tags();
+        if let Some(cache) = &self.tags_cache {
+            return match name {
+                Some(name) => {
+                    let start = cache.tags.partition_point(|tag| tag.name.as_str() < name);
+                    cache.tags[start..]
+                        .iter()
+                        .take_while(|tag| tag.name == name)
+                        .cloned()
+                        .collect()
+                }
+                None => cache.tags.clone(),
+            };
+        }
+        if !self.uses_gtags() {
+            return Vec::new();
+        }
+        match run_in(
+            &self.working_dir,
+            "global",
+            &["-x", "-a", name.unwrap_or(".*")],
+        ) {
+            Ok(output) => ctags::parse_global_output(&output)
+                .into_iter()
+                .filter(|tag| name.is_none_or(|name| tag.name == name))
+                .collect(),
+            Err(e) => {
+                self.set_status_message(t!("tags.global_failed", error = e).to_string());
+                Vec::new()
+            }
+        }
+    }
+
+    /// Go to the definition at the cursor with the language server, or with
+    /// the tags if no language server is available
+    pub(super) fn goto_definition(&mut self) -> AnyhowResult<()> {
+        if !self.request_goto_definition()? && self.has_tags() {
+            self.goto_tag();
+        }
+        Ok(())
+    }
+
+    /// Go to the definition of the word at the cursor (Ctrl+] in vi mode).
+    /// With several definitions, they're offered in a picker.
+    pub(super) fn goto_tag(&mut self) {
+        let name = {
+            let state = self.active_state_mut();
+            let cursor = state.cursors.primary().position;
+            let start = find_word_start(&state.buffer, cursor);
+            let end = find_word_end(&state.buffer, cursor);
+            (start < end).then(|| state.get_text_range(start, end))
+        };
+        let Some(name) = name else {
+            self.set_status_message(t!("lsp.no_symbol_at_cursor").to_string());
+            return;
+        };
+        if !self.has_tags() {
+            self.set_status_message(t!("tags.no_tags").to_string());
+            return;
+        }
+
+        let mut tags = self.find_tags(Some(&name));
+        match tags.len() {
+            0 => self.set_status_message(t!("tags.not_found", name = name).to_string()),
+            1 => self.jump_to_tag(tags.remove(0)),
+            _ => self.start_tag_prompt_with(tags),
+        }
+    }
+
+    /// Pick any tag to go to
+    pub(super) fn start_tag_prompt(&mut self) {
+        if !self.has_tags() {
+            self.set_status_message(t!("tags.no_tags").to_string());
+            return;
+        }
+        let tags = self.find_tags(None);
+        if tags.is_empty() {
+            self.set_status_message(t!("tags.no_tags").to_string());
+            return;
+        }
+        self.start_tag_prompt_with(tags);
+    }
+
+    fn start_tag_prompt_with(&mut self, tags: Vec<Tag>) {
+        let suggestions = tags
+            .iter()
+            .enumerate()
+            .map(|(index, tag)| {
+                let file = tag
+                    .file
+                    .strip_prefix(&self.working_dir)
+                    .unwrap_or(&tag.file);
+                let location = match tag.line {
+                    Some(line) => format!("{}:{}", file.display(), line),
+                    None => file.display().to_string(),
+                };
+                Suggestion {
+                    text: tag.name.clone(),
+                    description: Some(match &tag.kind {
+                        Some(kind) => format!("{} {}", kind, location),
+                        None => location,
+                    }),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.tag_choices = tags;
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("tags.prompt").to_string(),
+            PromptType::SelectTag,
+            suggestions,
+        ));
+    }
+
+    /// Handle SelectTag prompt confirmation
+    pub(super) fn handle_tag_selection(&mut self, input: &str) {
+        let choices = std::mem::take(&mut self.tag_choices);
+        if let Some(tag) = input
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| choices.into_iter().nth(index))
+        {
+            self.jump_to_tag(tag);
+        }
+    }
+
+    /// Open the file of a tag at its definition, pushing the current
+    /// location on the tag stack
+    fn jump_to_tag(&mut self, tag: Tag) {
+        let from = JumpEntry::new(
+            self.active_buffer(),
+            self.active_state().cursors.primary().position,
+        );
+        self.record_jump();
+        let buffer_id = match self.open_file(&tag.file) {
+            Ok(buffer_id) => buffer_id,
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.error_opening", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        self.tag_stack.push(from);
+
+        let Some(state) = self.buffers.get(&buffer_id) else {
+            return;
+        };
+        let position = match state.buffer.to_string() {
+            Some(text) => ctags::locate(&text, &tag),
+            // Too large to search: trust the line number
+            None => tag
+                .line
+                .and_then(|line| state.buffer.line_start_offset(line.checked_sub(1)?)),
+        };
+        match position {
+            Some(position) => self.go_to_location(buffer_id, position),
+            None => {
+                self.set_status_message(t!("tags.definition_moved", name = &tag.name).to_string())
+            }
+        }
+    }
+
+    /// Go back to where the last tag was jumped to from (Ctrl+T in vi mode)
+    pub(super) fn pop_tag(&mut self) {
+        while let Some(entry) = self.tag_stack.pop() {
+            if self.buffers.contains_key(&entry.buffer_id) {
+                self.record_jump();
+                self.go_to_location(entry.buffer_id, entry.position);
+                return;
+            }
+        }
+        self.set_status_message(t!("tags.stack_empty").to_string());
+    }
+
+    /// Rebuild the tags in the background: `global -u` updates a GTAGS
+    /// database, otherwise `ctags -R` writes a tags file
+    pub(super) fn regenerate_tags(&mut self) {
+        self.refresh_tags();
+        let (program, args): (&'static str, &'static [&'static str]) = if self.uses_gtags() {
+            ("global", &["-u"])
+        } else {
+            ("ctags", &["-R"])
+        };
+        let dir = self.working_dir.clone();
+
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            let result = run_in(&dir, program, args).map(|_| ());
+            self.handle_tags_regenerated(result);
+            return;
+        };
+        let sender = bridge.sender();
+        runtime.spawn_blocking(move || {
+            let result = run_in(&dir, program, args).map(|_| ());
+            let _ = sender.send(AsyncMessage::TagsRegenerated { result });
+        });
+        self.set_status_message(t!("tags.regenerating", command = program).to_string());
+    }
+
+    /// Report the result of `regenerate_tags`
+    pub(super) fn handle_tags_regenerated(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                // Read the new file even if its modification time looks the same
+                self.tags_cache = None;
+                self.set_status_message(t!("tags.regenerated").to_string());
+            }
+            Err(e) => self.set_status_message(t!("tags.regenerate_failed", error = e).to_string()),
+        }
+    }
+}
+
+/// Run `program` in `dir`, returning its output or error
+fn run_in(dir: &Path, program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("{}: {}", program, e))?;
+    if output.status.success() {
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        Err(format!("{} failed: {}", program, output.status))
+    } else {
+        Err(stderr)
+    }
+}
@@ -0,0 +1,532 @@
+//! Running the project's tasks (`.fresh/tasks.toml`, see `services::tasks`).
+//!
+//! Each task is a "Task: NAME" command in the palette, and the `run_task`
+//! action runs one by name, so it can be bound to a key. A task runs in the
+//! background, its output following in a read-only buffer where the lines
+//! its problem matcher found are highlighted: Enter goes to the problem under
+//! the cursor and `g` runs the task again. Closing the buffer stops the task.
+//!
+//! Tasks run code from the project, so they only run in a trusted workspace.
+
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rust_i18n::t;
+
+use super::shell_command::detect_shell;
+use super::Editor;
+use crate::input::commands::{Command as PaletteCommand, CommandSource, Suggestion};
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::project_env::ProjectEnv;
+use crate::services::tasks::{
+    load_tasks, Problem, ProblemMatcher, ProblemSeverity, TaskDefinition,
+};
+use crate::view::overlay::{Overlay, OverlayFace, OverlayNamespace};
+use crate::view::prompt::PromptType;
+
+/// Mode name for task output buffers
+const TASK_OUTPUT_MODE: &str = "task-output";
+
+/// How often a task's output buffer is updated while the task is running
+const UPDATE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How often the background thread checks whether a task has exited
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn task_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("task-output".to_string())
+}
+
+/// A task run and the output it has written so far
+pub(super) struct TaskRun {
+    name: String,
+    command: String,
+    dir: PathBuf,
+    /// Tells this run's messages from those of an earlier run in the same
+    /// buffer
+    run_id: u64,
+    lines: Vec<String>,
+    /// The problems found, with the index of their line
+    problems: Vec<(usize, Problem)>,
+    matcher: Option<ProblemMatcher>,
+    /// The process, until it has exited
+    child: Option<Arc<Mutex<Child>>>,
+    /// The exit code (`None` if killed by a signal), or why waiting failed
+    result: Option<Result<Option<i32>, String>>,
+    /// Whether there's output not yet written to the buffer
+    dirty: bool,
+    written_at: Option<std::time::Instant>,
+}
+
+impl TaskRun {
+    fn is_running(&self) -> bool {
+        self.result.is_none()
+    }
+
+    fn push_line(&mut self, line: String) {
+        if let Some(problem) = self
+            .matcher
+            .as_mut()
+            .and_then(|matcher| matcher.match_line(&line))
+        {
+            self.problems.push((self.lines.len(), problem));
+        }
+        self.lines.push(line);
+        self.dirty = true;
+    }
+}
+
+impl Editor {
+    /// Run the project task `name`, or ask which one to run if it's empty
+    pub(super) fn run_task(&mut self, name: &str) {
+        if name.is_empty() {
+            self.start_run_task_prompt();
+            return;
+        }
+        if !self.workspace_trusted {
+            self.set_status_message(t!("tasks.untrusted").to_string());
+            return;
+        }
+        let tasks = match load_tasks(&self.working_dir) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                self.set_status_message(t!("tasks.load_failed", error = e).to_string());
+                return;
+            }
+        };
+        let Some(task) = tasks.tasks.get(name) else {
+            self.set_status_message(t!("tasks.unknown", name = name).to_string());
+            return;
+        };
+        let matcher = match task.problem_matcher.as_ref().map(ProblemMatcher::new) {
+            None => None,
+            Some(Ok(matcher)) => Some(matcher),
+            Some(Err(e)) => {
+                self.set_status_message(t!("tasks.load_failed", error = e).to_string());
+                return;
+            }
+        };
+
+        // A task has one output buffer, reused by its next run
+        let existing = self
+            .task_runs
+            .iter()
+            .find(|(_, run)| run.name == name)
+            .map(|(id, run)| (*id, run.is_running()));
+        if let Some((buffer_id, true)) = existing {
+            self.set_active_buffer(buffer_id);
+            self.set_status_message(t!("tasks.already_running", name = name).to_string());
+            return;
+        }
+
+        let dir = task.working_dir(&self.working_dir);
+        let mut child = match spawn_task(task, &dir, &self.project_env) {
+            Ok(child) => child,
+            Err(e) => {
+                self.set_status_message(
+                    t!("tasks.spawn_failed", name = name, error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        let buffer_id = existing.map(|(id, _)| id).unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                t!("tasks.title", name = name).to_string(),
+                TASK_OUTPUT_MODE.to_string(),
+                true,
+            )
+        });
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        let run_id = self.next_task_run_id;
+        self.next_task_run_id += 1;
+
+        let mut run = TaskRun {
+            name: name.to_string(),
+            command: task.command.clone(),
+            dir,
+            run_id,
+            lines: Vec::new(),
+            problems: Vec::new(),
+            matcher,
+            child: None,
+            result: None,
+            dirty: true,
+            written_at: None,
+        };
+
+        match (&self.tokio_runtime, &self.async_bridge) {
+            (Some(runtime), Some(bridge)) => {
+                let readers: Vec<_> = [
+                    child
+                        .stdout
+                        .take()
+                        .map(|out| Box::new(out) as Box<dyn Read + Send>),
+                    child
+                        .stderr
+                        .take()
+                        .map(|err| Box::new(err) as Box<dyn Read + Send>),
+                ]
+                .into_iter()
+                .flatten()
+                .map(|reader| {
+                    let sender = bridge.sender();
+                    std::thread::spawn(move || {
+                        let mut reader = BufReader::new(reader);
+                        let mut bytes = Vec::new();
+                        while reader.read_until(b'\n', &mut bytes).is_ok_and(|n| n > 0) {
+                            let line = String::from_utf8_lossy(&bytes)
+                                .trim_end_matches(['\n', '\r'])
+                                .to_string();
+                            bytes.clear();
+                            let message = AsyncMessage::TaskOutput {
+                                buffer_id,
+                                run_id,
+                                line,
+                            };
+                            if sender.send(message).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                })
+                .collect();
+
+                let child = Arc::new(Mutex::new(child));
+                run.child = Some(Arc::clone(&child));
+                let sender = bridge.sender();
+                runtime.spawn_blocking(move || {
+                    let result = wait_for_exit(&child);
+                    for reader in readers {
+                        let _ = reader.join();
+                    }
+                    let _ = sender.send(AsyncMessage::TaskFinished {
+                        buffer_id,
+                        run_id,
+                        result,
+                    });
+                });
+                self.task_runs.insert(buffer_id, run);
+                self.set_status_message(t!("tasks.started", name = name).to_string());
+            }
+            _ => {
+                // No async runtime - run the task inline
+                let result = child
+                    .wait_with_output()
+                    .map(|output| {
+                        for line in String::from_utf8_lossy(&output.stdout)
+                            .lines()
+                            .chain(String::from_utf8_lossy(&output.stderr).lines())
+                        {
+                            run.push_line(line.to_string());
+                        }
+                        output.status.code()
+                    })
+                    .map_err(|e| e.to_string());
+                self.task_runs.insert(buffer_id, run);
+                self.handle_task_finished(buffer_id, run_id, result);
+            }
+        }
+
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.cursors.primary_mut().position = 0;
+        }
+        self.write_task_output(buffer_id);
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Ask which of the project's tasks to run
+    fn start_run_task_prompt(&mut self) {
+        let tasks = match load_tasks(&self.working_dir) {
+            Ok(tasks) => tasks,
+            Err(e) => {
+                self.set_status_message(t!("tasks.load_failed", error = e).to_string());
+                return;
+            }
+        };
+        if tasks.tasks.is_empty() {
+            self.set_status_message(t!("tasks.no_tasks").to_string());
+            return;
+        }
+        let suggestions = tasks
+            .tasks
+            .iter()
+            .map(|(name, task)| Suggestion {
+                text: name.clone(),
+                description: Some(task_description(task)),
+                value: Some(name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        self.start_prompt_with_suggestions(
+            t!("tasks.prompt").to_string(),
+            PromptType::RunTask,
+            suggestions,
+        );
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Register a "Task: NAME" palette command for each of the project's
+    /// tasks, replacing those of the tasks file as it was last read
+    pub(super) fn refresh_task_commands(&mut self) {
+        let tasks = load_tasks(&self.working_dir).unwrap_or_else(|e| {
+            tracing::warn!("Failed to read the project's tasks: {}", e);
+            Default::default()
+        });
+        let registry = self.command_registry.read().unwrap();
+        for name in self.task_commands.drain(..) {
+            registry.unregister(&name);
+        }
+        for (name, task) in &tasks.tasks {
+            let command = PaletteCommand {
+                name: t!("tasks.command", name = name).to_string(),
+                description: task_description(task),
+                action: Action::RunTask(name.clone()),
+                contexts: vec![],
+                custom_contexts: vec![],
+                source: CommandSource::Builtin,
+            };
+            self.task_commands.push(command.name.clone());
+            registry.register(command);
+        }
+    }
+
+    /// Append a line of a task's output
+    pub(super) fn handle_task_output(&mut self, buffer_id: BufferId, run_id: u64, line: String) {
+        if let Some(run) = self
+            .task_runs
+            .get_mut(&buffer_id)
+            .filter(|run| run.run_id == run_id)
+        {
+            run.push_line(line);
+        }
+    }
+
+    /// Record how a task run ended, once all its output has arrived
+    pub(super) fn handle_task_finished(
+        &mut self,
+        buffer_id: BufferId,
+        run_id: u64,
+        result: Result<Option<i32>, String>,
+    ) {
+        let Some(run) = self
+            .task_runs
+            .get_mut(&buffer_id)
+            .filter(|run| run.run_id == run_id)
+        else {
+            return;
+        };
+        run.child = None;
+        run.dirty = true;
+        let name = run.name.clone();
+        let problems = run.problems.len();
+        let mut message = match &result {
+            Ok(Some(0)) => t!("tasks.succeeded", name = name).to_string(),
+            Ok(Some(code)) => t!("tasks.failed", name = name, code = code).to_string(),
+            Ok(None) => t!("tasks.killed", name = name).to_string(),
+            Err(e) => t!("tasks.wait_failed", name = name, error = e).to_string(),
+        };
+        if problems > 0 {
+            message.push_str(&format!(" {}", t!("tasks.problems", count = problems)));
+        }
+        run.result = Some(result);
+        self.set_status_message(message);
+    }
+
+    /// Write the output of the task runs that have new output to their
+    /// buffers. Returns whether any was written.
+    pub(super) fn update_task_outputs(&mut self) -> bool {
+        let now = self.time_source.now();
+        let due: Vec<BufferId> = self
+            .task_runs
+            .iter()
+            .filter(|(_, run)| {
+                run.dirty
+                    && (!run.is_running()
+                        || run
+                            .written_at
+                            .is_none_or(|at| now.duration_since(at) >= UPDATE_INTERVAL))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for buffer_id in &due {
+            self.write_task_output(*buffer_id);
+        }
+        !due.is_empty()
+    }
+
+    /// Go to the problem on the line under the cursor in a task's output
+    pub(super) fn task_output_open(&mut self) {
+        let Some(run) = self.task_runs.get(&self.active_buffer()) else {
+            return;
+        };
+        let problem = self
+            .get_text_properties_at_cursor()
+            .and_then(|props| {
+                props
+                    .iter()
+                    .find_map(|prop| prop.get("line").and_then(|v| v.as_u64()))
+            })
+            .and_then(|line| run.problems.iter().find(|(index, _)| *index as u64 == line))
+            .map(|(_, problem)| (run.dir.join(&problem.file), problem.clone()));
+        let Some((path, problem)) = problem else {
+            self.set_status_message(t!("tasks.no_problem").to_string());
+            return;
+        };
+        if let Err(e) = self.open_file(&path) {
+            self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string());
+            return;
+        }
+        self.goto_line_col(problem.line, problem.column);
+        self.set_status_message(problem.message);
+    }
+
+    /// Run the task of the active output buffer again
+    pub(super) fn task_output_rerun(&mut self) {
+        if let Some(name) = self
+            .task_runs
+            .get(&self.active_buffer())
+            .map(|run| run.name.clone())
+        {
+            self.run_task(&name);
+        }
+    }
+
+    /// Stop the task of an output buffer that's being closed
+    pub(super) fn stop_task(&mut self, buffer_id: BufferId) {
+        let Some(run) = self.task_runs.remove(&buffer_id) else {
+            return;
+        };
+        if let Some(child) = run.child {
+            if let Ok(mut child) = child.lock() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    /// Write a task's command, output and result to its buffer, keeping the
+    /// cursor at the end if it was there
+    fn write_task_output(&mut self, buffer_id: BufferId) {
+        let now = self.time_source.now();
+        let Some(run) = self.task_runs.get_mut(&buffer_id) else {
+            return;
+        };
+        run.dirty = false;
+        run.written_at = Some(now);
+
+        let mut content = vec![TextPropertyEntry::text(format!(
+            "$ {}\n{}\n\n",
+            run.command,
+            t!("tasks.hint")
+        ))];
+        let mut offset = content[0].text.len();
+        let mut highlights = Vec::new();
+        let mut problems = run.problems.iter().peekable();
+        for (index, line) in run.lines.iter().enumerate() {
+            if let Some((_, problem)) = problems.next_if(|(at, _)| *at == index) {
+                let color = match problem.severity {
+                    ProblemSeverity::Error => self.theme.diagnostic_error_bg,
+                    ProblemSeverity::Warning => self.theme.diagnostic_warning_bg,
+                    ProblemSeverity::Info => self.theme.diagnostic_info_bg,
+                };
+                highlights.push((offset..offset + line.len(), color));
+            }
+            offset += line.len() + 1;
+            content.push(
+                TextPropertyEntry::text(format!("{}\n", line))
+                    .with_property("line", serde_json::json!(index)),
+            );
+        }
+        let footer = match &run.result {
+            None => t!("tasks.running").to_string(),
+            Some(Ok(Some(0))) => t!("tasks.succeeded", name = run.name.as_str()).to_string(),
+            Some(Ok(Some(code))) => {
+                t!("tasks.failed", name = run.name.as_str(), code = code).to_string()
+            }
+            Some(Ok(None)) => t!("tasks.killed", name = run.name.as_str()).to_string(),
+            Some(Err(e)) => {
+                t!("tasks.wait_failed", name = run.name.as_str(), error = e).to_string()
+            }
+        };
+        content.push(TextPropertyEntry::text(format!("\n{}\n", footer)));
+
+        let following = self.buffers.get(&buffer_id).is_some_and(|state| {
+            let len = state.buffer.len();
+            len > 0 && state.cursors.primary().position >= len
+        });
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, content) {
+            tracing::warn!("Failed to update the task output: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            if following {
+                state.cursors.primary_mut().position = state.buffer.len();
+            }
+            let ns = task_namespace();
+            state.overlays.clear_namespace(&ns, &mut state.marker_list);
+            for (range, color) in highlights {
+                let overlay = Overlay::with_namespace(
+                    &mut state.marker_list,
+                    range,
+                    OverlayFace::Background { color },
+                    ns.clone(),
+                )
+                .with_extend_to_line_end(true);
+                state.overlays.add(overlay);
+            }
+        }
+        self.invalidate_layouts_for_buffer(buffer_id);
+    }
+}
+
+/// A task's description in the palette and the task prompt: its own, or
+/// else its command
+fn task_description(task: &TaskDefinition) -> String {
+    task.description
+        .clone()
+        .unwrap_or_else(|| task.command.clone())
+}
+
+/// Start a task's command through the shell, reading its output. The
+/// task's own variables override the project's environment.
+fn spawn_task(task: &TaskDefinition, dir: &Path, env: &ProjectEnv) -> std::io::Result<Child> {
+    let mut command = Command::new(detect_shell());
+    env.apply(&mut command);
+    command
+        .args(["-c", &task.command])
+        .current_dir(dir)
+        .envs(&task.env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+}
+
+/// Wait for a task to exit without holding its lock, so it can be killed
+/// meanwhile
+fn wait_for_exit(child: &Mutex<Child>) -> Result<Option<i32>, String> {
+    loop {
+        let status = child
+            .lock()
+            .map_err(|e| e.to_string())?
+            .try_wait()
+            .map_err(|e| e.to_string())?;
+        if let Some(status) = status {
+            return Ok(status.code());
+        }
+        std::thread::sleep(WAIT_POLL_INTERVAL);
+    }
+}
@@ -304,6 +304,15 @@ impl Editor {
         }
     }
 
+    /// Send pasted text to the active terminal
+    pub fn send_terminal_paste(&mut self, text: &str) {
+        let bracketed = self
+            .get_active_terminal_state()
+            .is_some_and(|s| s.uses_bracketed_paste());
+        let bytes = crate::services::terminal::pty::paste_to_pty_bytes(text, bracketed);
+        self.send_terminal_input(&bytes);
+    }
+
     /// Send a key event to the active terminal
     pub fn send_terminal_key(
         &mut self,
@@ -0,0 +1,139 @@
+//! The Terminal Info buffer: what the terminal was found to support, and what
+//! the editor does instead where it doesn't (see
+//! `services::terminal_capabilities`)
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::image_preview::GraphicsProtocol;
+use crate::services::terminal_capabilities::TerminalCapabilities;
+use crate::view::color_support::ColorCapability;
+
+/// Mode name for the Terminal Info buffer
+const TERMINAL_INFO_MODE: &str = "terminal-info";
+
+impl Editor {
+    /// Use what the terminal was found to support
+    pub fn set_terminal_capabilities(&mut self, capabilities: TerminalCapabilities) {
+        self.clipboard
+            .set_osc52_enabled(capabilities.clipboard != Some(false));
+        self.image_previews
+            .set_protocol(capabilities.graphics_protocol());
+        self.terminal_capabilities = capabilities;
+    }
+
+    /// What the terminal was found to support
+    pub fn terminal_capabilities(&self) -> &TerminalCapabilities {
+        &self.terminal_capabilities
+    }
+
+    /// Whether to draw frames with synchronized output: the terminal supports
+    /// it and it isn't turned off in the config
+    pub fn synchronized_output(&self) -> bool {
+        self.config.editor.synchronized_output
+            && self.terminal_capabilities.synchronized_output == Some(true)
+    }
+
+    /// Show the detected terminal capabilities in a read-only buffer
+    pub fn show_terminal_info(&mut self) -> BufferId {
+        let text = format_terminal_info(&self.terminal_capabilities);
+        let existing = self.buffer_metadata.iter().find_map(|(id, metadata)| {
+            (metadata.virtual_mode() == Some(TERMINAL_INFO_MODE)).then_some(*id)
+        });
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                t!("terminal_info.title").to_string(),
+                TERMINAL_INFO_MODE.to_string(),
+                true,
+            )
+        });
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let len = state.buffer.len();
+            state.buffer.delete(0..len);
+            state.buffer.insert(0, &text);
+            state.buffer.set_modified(false);
+            state.editing_disabled = true;
+        }
+        self.set_active_buffer(buffer_id);
+        buffer_id
+    }
+}
+
+/// One line per capability, with the fallback in use where it's missing
+fn format_terminal_info(capabilities: &TerminalCapabilities) -> String {
+    let support = |supported: Option<bool>, fallback: String| match supported {
+        Some(true) => t!("terminal_info.supported").to_string(),
+        Some(false) => format!("{} - {}", t!("terminal_info.unsupported"), fallback),
+        None => t!("terminal_info.unknown").to_string(),
+    };
+    let colors = match capabilities.color {
+        ColorCapability::TrueColor => t!("terminal_info.color_truecolor"),
+        ColorCapability::Color256 => t!("terminal_info.color_256"),
+        ColorCapability::Color16 => t!("terminal_info.color_16"),
+    };
+    let unknown = || t!("terminal_info.unknown").to_string();
+
+    let rows = [
+        (
+            t!("terminal_info.name").to_string(),
+            capabilities.name.clone().unwrap_or_else(unknown),
+        ),
+        (
+            "TERM".to_string(),
+            capabilities.term.clone().unwrap_or_else(unknown),
+        ),
+        (t!("terminal_info.colors").to_string(), colors.to_string()),
+        (
+            t!("terminal_info.keyboard").to_string(),
+            support(
+                capabilities.keyboard_enhancement,
+                t!("terminal_info.keyboard_fallback").to_string(),
+            ),
+        ),
+        (
+            t!("terminal_info.synchronized_output").to_string(),
+            support(
+                capabilities.synchronized_output,
+                t!("terminal_info.synchronized_output_fallback").to_string(),
+            ),
+        ),
+        (
+            t!("terminal_info.sgr_mouse").to_string(),
+            support(
+                capabilities.sgr_mouse,
+                t!("terminal_info.sgr_mouse_fallback").to_string(),
+            ),
+        ),
+        (
+            t!("terminal_info.images").to_string(),
+            match capabilities.graphics_protocol() {
+                Some(GraphicsProtocol::Kitty) => t!("terminal_info.images_kitty").to_string(),
+                Some(GraphicsProtocol::Sixel) => t!("terminal_info.images_sixel").to_string(),
+                None if capabilities.kitty_graphics.is_none() => {
+                    t!("terminal_info.unknown").to_string()
+                }
+                None => support(Some(false), t!("terminal_info.images_fallback").to_string()),
+            },
+        ),
+        (
+            t!("terminal_info.clipboard").to_string(),
+            support(
+                capabilities.clipboard,
+                t!("terminal_info.clipboard_fallback").to_string(),
+            ),
+        ),
+    ];
+
+    let width = rows
+        .iter()
+        .map(|(label, _)| label.chars().count())
+        .max()
+        .unwrap_or(0);
+    rows.iter()
+        .map(|(label, value)| {
+            let padding = width - label.chars().count();
+            format!("{}:{} {}\n", label, " ".repeat(padding), value)
+        })
+        .collect()
+}
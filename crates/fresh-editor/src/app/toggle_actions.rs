@@ -4,16 +4,26 @@
 //! - Toggle line numbers, debug highlights, menu bar
 //! - Toggle mouse capture, mouse hover, inlay hints
 //! - Reset buffer settings
-//! - Config dump, save, and reload
+//! - Config dump, save, and reload (including live reload on file changes)
+//! - Show the effective value and source layer of a setting
+//! - Light/dark theme selection (terminal background, schedule, manual toggle)
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
 use rust_i18n::t;
 
-use crate::config::Config;
+use crate::config::{BufferConfig, Config};
 use crate::config_io::{ConfigLayer, ConfigResolver};
+use crate::input::commands::Suggestion;
 use crate::input::keybindings::KeybindingResolver;
-use crate::services::lsp::manager::detect_language;
+use crate::services::spell::BufferSpellState;
+use crate::view::prompt::{Prompt, PromptType};
+use crate::view::theme::{configured_appearance, theme_for_appearance, Appearance, LocalTime};
+use crate::view::visual_guides::VisualGuides;
 
-use super::Editor;
+use super::{BufferId, Editor};
 
 impl Editor {
     /// Toggle line numbers in the gutter for the active buffer
@@ -78,40 +88,49 @@ impl Editor {
         self.tab_bar_visible
     }
 
-    /// Reset buffer settings (tab_size, use_tabs, show_whitespace_tabs) to config defaults
-    pub fn reset_buffer_settings(&mut self) {
-        let buffer_id = self.active_buffer();
+    /// Toggle breadcrumb bar visibility
+    pub fn toggle_breadcrumbs(&mut self) {
+        self.breadcrumbs_visible = !self.breadcrumbs_visible;
+        let status = if self.breadcrumbs_visible {
+            t!("toggle.breadcrumbs_shown")
+        } else {
+            t!("toggle.breadcrumbs_hidden")
+        };
+        self.set_status_message(status.to_string());
+    }
 
-        // Get the file path to determine language-specific settings
-        let file_path = self
+    /// The settings a buffer gets from the config, for its file's language
+    pub(super) fn configured_buffer_settings(&self, buffer_id: BufferId) -> BufferConfig {
+        let language = self
             .buffer_metadata
             .get(&buffer_id)
-            .and_then(|m| m.file_path().cloned());
-
-        // Determine settings from config (with language fallback)
-        let (tab_size, use_tabs, show_whitespace_tabs) = if let Some(path) = &file_path {
-            if let Some(language) = detect_language(path, &self.config.languages) {
-                if let Some(lang_config) = self.config.languages.get(&language) {
-                    (
-                        lang_config.tab_size.unwrap_or(self.config.editor.tab_size),
-                        lang_config.use_tabs,
-                        lang_config.show_whitespace_tabs,
-                    )
-                } else {
-                    (self.config.editor.tab_size, false, true)
-                }
-            } else {
-                (self.config.editor.tab_size, false, true)
-            }
-        } else {
-            (self.config.editor.tab_size, false, true)
-        };
+            .and_then(|m| m.language(&self.config.languages));
+        BufferConfig::resolve(&self.config, language.as_deref())
+    }
+
+    /// Reset buffer settings (tab_size, use_tabs, show_whitespace_tabs) to config defaults
+    pub fn reset_buffer_settings(&mut self) {
+        let buffer_id = self.active_buffer();
+        let buffer_config = self.configured_buffer_settings(buffer_id);
 
         // Apply settings to buffer
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            state.tab_size = tab_size;
-            state.use_tabs = use_tabs;
-            state.show_whitespace_tabs = show_whitespace_tabs;
+            state.tab_size = buffer_config.tab_size;
+            state.use_tabs = buffer_config.use_tabs;
+            state.show_whitespace_tabs = buffer_config.show_whitespace_tabs;
+            state.guides = VisualGuides::from_config(&self.config.editor, &buffer_config);
+            state.bidi_text = self.config.editor.bidi_text;
+            state
+                .concealer
+                .set_rules(&buffer_config.conceal, state.highlighter.language());
+            state
+                .indent_calculator
+                .get_mut()
+                .set_rules(buffer_config.indent_patterns.as_ref());
+            state.spell = BufferSpellState::new(
+                self.config.editor.spell_check,
+                &self.config.editor.spell_language,
+            );
         }
 
         self.set_status_message(t!("toggle.buffer_settings_reset").to_string());
@@ -175,8 +194,11 @@ impl Editor {
             self.set_status_message(t!("toggle.inlay_hints_enabled").to_string());
         } else {
             // Clear inlay hints from all buffers
+            let ns = super::lsp_requests::inlay_hint_namespace();
             for state in self.buffers.values_mut() {
-                state.virtual_texts.clear(&mut state.marker_list);
+                state
+                    .virtual_texts
+                    .clear_namespace(&mut state.marker_list, &ns);
             }
             self.set_status_message(t!("toggle.inlay_hints_disabled").to_string());
         }
@@ -242,25 +264,47 @@ impl Editor {
     /// and emits a config_changed event so plugins can update their state accordingly.
     /// Uses the layered config system to properly merge with defaults.
     pub fn reload_config(&mut self) {
-        let old_theme = self.config.theme.clone();
-        self.config = Config::load_with_layers(&self.dir_context, &self.working_dir);
+        let config = Config::load_with_layers_trusted(
+            &self.dir_context,
+            &self.working_dir,
+            self.workspace_trusted,
+        );
+        self.apply_reloaded_config(config);
+    }
+
+    /// Replace the active configuration and apply it to the running editor
+    pub(super) fn apply_reloaded_config(&mut self, config: Config) {
+        let old_theme = self.active_theme_name();
+        let old_high_contrast = self.config.editor.high_contrast;
+        self.config = config;
 
         // Refresh cached raw user config for plugins
         self.user_config_raw = Config::read_user_config_raw(&self.working_dir);
 
-        // Apply theme change if needed
-        if old_theme != self.config.theme {
-            if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
+        // Apply theme change if needed (`theme` or the appearance settings)
+        self.applied_appearance = self.current_appearance();
+        let new_theme = self.active_theme_name();
+        if old_theme != new_theme || old_high_contrast != self.config.editor.high_contrast {
+            if let Some(theme) = self.load_theme(&new_theme) {
                 self.theme = theme;
-                tracing::info!("Theme changed to '{}'", self.config.theme.0);
+                self.theme.set_terminal_cursor_color();
+                tracing::info!("Theme changed to '{}'", new_theme.0);
             } else {
-                tracing::error!("Theme '{}' not found", self.config.theme.0);
+                tracing::error!("Theme '{}' not found", new_theme.0);
             }
         }
 
         // Always reload keybindings (complex types don't implement PartialEq)
         self.keybindings = KeybindingResolver::new(&self.config);
 
+        // Sandbox settings and plugin permissions
+        self.plugin_sandbox
+            .write()
+            .unwrap()
+            .update_config(&self.config);
+        self.frame_budget
+            .set_budget(self.config.editor.frame_budget_ms);
+
         // Update LSP configs
         if let Some(ref mut lsp) = self.lsp {
             for (language, lsp_config) in &self.config.lsp {
@@ -278,6 +322,142 @@ impl Editor {
         );
     }
 
+    /// Poll the config files for changes (called from main loop)
+    ///
+    /// When a watched config file (user, platform or project) changes, the
+    /// layered config is re-resolved and applied live. If the new config fails
+    /// to parse, the current config is kept and the error is shown. User theme
+    /// files are checked at the same interval.
+    /// Returns true if the config or themes were reloaded (requires re-render).
+    pub fn poll_config_changes(&mut self) -> bool {
+        let poll_interval =
+            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        if self.time_source.elapsed_since(self.last_config_poll) < poll_interval {
+            return false;
+        }
+        self.last_config_poll = self.time_source.now();
+
+        let themes_reloaded = self.reload_changed_themes() | self.apply_scheduled_appearance();
+
+        let resolver = ConfigResolver::new(self.dir_context.clone(), self.working_dir.clone())
+            .with_safe_mode(!self.workspace_trusted);
+        let mod_times = Self::config_file_mod_times(&resolver);
+        if mod_times == self.config_mod_times {
+            return themes_reloaded;
+        }
+        self.config_mod_times = mod_times;
+
+        match resolver.resolve() {
+            Ok(config) => {
+                // Files written by the editor itself (settings, dump config)
+                // already match the active config
+                let unchanged =
+                    serde_json::to_value(&config).ok() == serde_json::to_value(&self.config).ok();
+                if unchanged {
+                    return themes_reloaded;
+                }
+                tracing::info!("Config file changed, reloading");
+                self.apply_reloaded_config(config);
+                self.set_status_message(t!("config.reloaded").to_string());
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Failed to reload config: {}", e);
+                self.set_status_message(
+                    t!("config.reload_failed", error = e.to_string()).to_string(),
+                );
+                true
+            }
+        }
+    }
+
+    /// Modification times of the watched config files (`None` if missing)
+    pub(super) fn config_file_mod_times(
+        resolver: &ConfigResolver,
+    ) -> HashMap<PathBuf, Option<SystemTime>> {
+        resolver
+            .watched_config_paths()
+            .into_iter()
+            .map(|path| {
+                let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, mtime)
+            })
+            .collect()
+    }
+
+    /// Show the effective value of a setting and the config layer it comes from
+    ///
+    /// `pointer` is a JSON pointer into the config, e.g. `/editor/tab_size`.
+    pub fn show_config_source(&mut self, pointer: &str) {
+        let value = serde_json::to_value(&self.config)
+            .ok()
+            .and_then(|json| json.pointer(pointer).cloned());
+        let Some(value) = value else {
+            self.set_status_message(t!("config.unknown_setting", setting = pointer).to_string());
+            return;
+        };
+
+        let resolver = ConfigResolver::new(self.dir_context.clone(), self.working_dir.clone());
+        let source = match resolver.value_source(pointer) {
+            Ok((layer, Some(path))) => {
+                let path = path.display().to_string();
+                let source = match layer {
+                    ConfigLayer::Session => t!("config.source_session", path = path),
+                    ConfigLayer::Project => t!("config.source_project", path = path),
+                    ConfigLayer::User | ConfigLayer::System => {
+                        t!("config.source_user", path = path)
+                    }
+                };
+                source.to_string()
+            }
+            Ok((_, None)) => t!("config.source_default").to_string(),
+            Err(e) => {
+                self.set_status_message(
+                    t!("config.reload_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+
+        self.set_status_message(
+            t!(
+                "config.value_source",
+                setting = pointer,
+                value = value.to_string(),
+                source = source
+            )
+            .to_string(),
+        );
+    }
+
+    /// Prompt for a setting and show its effective value and source
+    pub fn start_show_config_source_prompt(&mut self) {
+        let mut leaves = Vec::new();
+        if let Ok(json) = serde_json::to_value(&self.config) {
+            collect_config_leaves(&json, String::new(), &mut leaves);
+        }
+        let suggestions = leaves
+            .into_iter()
+            .map(|(pointer, value)| Suggestion {
+                text: pointer.clone(),
+                description: Some(value),
+                value: Some(pointer),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("config.show_source_prompt").to_string(),
+            PromptType::ShowConfigSource,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
     /// Reload the theme registry from disk.
     ///
     /// Call this after installing new theme packages or saving new themes.
@@ -285,12 +465,14 @@ impl Editor {
     pub fn reload_themes(&mut self) {
         use crate::view::theme::ThemeLoader;
 
-        let theme_loader = ThemeLoader::new();
+        let theme_loader = ThemeLoader::with_user_dir(Some(self.dir_context.themes_dir()));
         self.theme_registry = theme_loader.load_all();
+        self.theme_mod_times = theme_loader.user_theme_mod_times();
 
         // Re-apply current theme if it still exists, otherwise it might have been updated
-        if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
+        if let Some(theme) = self.load_theme(&self.active_theme_name()) {
             self.theme = theme;
+            self.theme.set_terminal_cursor_color();
         }
 
         tracing::info!(
@@ -301,4 +483,154 @@ impl Editor {
         // Emit event so plugins know themes changed
         self.emit_event("themes_changed", serde_json::json!({}));
     }
+
+    /// Reload the theme registry if a user theme file was added, edited or removed
+    ///
+    /// Returns true if the themes were reloaded.
+    fn reload_changed_themes(&mut self) -> bool {
+        use crate::view::theme::ThemeLoader;
+
+        let theme_loader = ThemeLoader::with_user_dir(Some(self.dir_context.themes_dir()));
+        if theme_loader.user_theme_mod_times() == self.theme_mod_times {
+            return false;
+        }
+        tracing::info!("Theme files changed, reloading");
+        self.reload_themes();
+        true
+    }
+
+    /// Set the detected terminal background (used by `appearance.mode = "auto"`)
+    /// and switch to the matching theme.
+    pub fn set_terminal_appearance(&mut self, appearance: Option<Appearance>) {
+        self.terminal_appearance = appearance;
+        self.applied_appearance = self.current_appearance();
+        self.apply_theme_by_name(&self.active_theme_name());
+    }
+
+    /// The light/dark appearance in effect, or `None` if `theme` applies.
+    ///
+    /// A manual toggle takes precedence over the configured mode.
+    pub fn current_appearance(&self) -> Option<Appearance> {
+        self.appearance_override.or_else(|| {
+            configured_appearance(
+                &self.config.appearance,
+                self.terminal_appearance,
+                local_time_now(),
+            )
+        })
+    }
+
+    /// Name of the theme selected by the config and the current appearance
+    pub fn active_theme_name(&self) -> crate::config::ThemeName {
+        match self.current_appearance() {
+            Some(appearance) => theme_for_appearance(&self.config.appearance, appearance).clone(),
+            None => self.config.theme.clone(),
+        }
+    }
+
+    /// Switch themes when the scheduled appearance changes (e.g. at sunset).
+    ///
+    /// Only a change of appearance switches themes, so a theme picked in
+    /// between is kept until the next switch time.
+    /// Returns true if the theme changed.
+    fn apply_scheduled_appearance(&mut self) -> bool {
+        let appearance = self.current_appearance();
+        if appearance == self.applied_appearance {
+            return false;
+        }
+        self.applied_appearance = appearance;
+        let theme_name = self.active_theme_name();
+        tracing::info!(
+            "Appearance changed to {:?}, switching to theme '{}'",
+            appearance,
+            theme_name.0
+        );
+        self.apply_theme_by_name(&theme_name)
+    }
+
+    /// Switch between the light and dark themes from the `appearance` config.
+    ///
+    /// The choice lasts for the rest of the session and overrides the
+    /// terminal background or schedule.
+    pub fn toggle_light_dark_theme(&mut self) {
+        let current = self
+            .current_appearance()
+            .unwrap_or_else(|| match self.theme.editor_bg {
+                ratatui::style::Color::Rgb(r, g, b) => Appearance::from_background(r, g, b),
+                _ => Appearance::Dark,
+            });
+        let appearance = current.toggled();
+        let theme_name = theme_for_appearance(&self.config.appearance, appearance).clone();
+        if self.theme_registry.get(&theme_name).is_none() {
+            self.set_status_message(t!("theme.not_found", name = &theme_name.0).to_string());
+            return;
+        }
+
+        self.appearance_override = Some(appearance);
+        self.applied_appearance = Some(appearance);
+        self.apply_theme_by_name(&theme_name);
+        let message = match appearance {
+            Appearance::Light => t!("view.switched_to_light_theme", theme = &theme_name.0),
+            Appearance::Dark => t!("view.switched_to_dark_theme", theme = &theme_name.0),
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// A copy of the theme called `name`, with `high_contrast` applied
+    pub(super) fn load_theme(&self, name: &str) -> Option<crate::view::theme::Theme> {
+        let mut theme = self.theme_registry.get_cloned(name)?;
+        if self.config.editor.high_contrast {
+            theme.enforce_min_contrast(crate::view::theme::HIGH_CONTRAST_RATIO);
+        }
+        Some(theme)
+    }
+
+    /// Make `name` the active theme (not persisted to config).
+    ///
+    /// Returns true if the theme changed.
+    fn apply_theme_by_name(&mut self, name: &crate::config::ThemeName) -> bool {
+        if self.theme.name == name.0 {
+            return false;
+        }
+        match self.load_theme(name) {
+            Some(theme) => {
+                self.theme = theme;
+                self.theme.set_terminal_cursor_color();
+                true
+            }
+            None => {
+                tracing::warn!("Theme '{}' not found", name.0);
+                false
+            }
+        }
+    }
+}
+
+/// Current local time, for evaluating the appearance schedule
+fn local_time_now() -> LocalTime {
+    use chrono::{Datelike, Offset, Timelike};
+
+    let now = chrono::Local::now();
+    LocalTime {
+        minutes: now.hour() * 60 + now.minute(),
+        day_of_year: now.ordinal(),
+        utc_offset_minutes: now.offset().fix().local_minus_utc() / 60,
+    }
+}
+
+/// Collect `(json pointer, value)` pairs for all leaf settings in a config value
+fn collect_config_leaves(
+    value: &serde_json::Value,
+    prefix: String,
+    out: &mut Vec<(String, String)>,
+) {
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, val) in map {
+                let key = key.replace('~', "~0").replace('/', "~1");
+                collect_config_leaves(val, format!("{}/{}", prefix, key), out);
+            }
+        }
+        _ => out.push((prefix, value.to_string())),
+    }
 }
@@ -0,0 +1,231 @@
+//! The Tutorial: an editable buffer of short lessons (`docs/tutor.txt`), each
+//! ending in a task that's checked off once it's done. Tasks are checked
+//! against the buffer's text, the search query, or the actions run, so they
+//! can be done however the user likes.
+
+use ratatui::style::Style;
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::model::event::BufferId;
+use crate::view::virtual_text::{VirtualTextNamespace, VirtualTextPosition};
+
+/// Mode name for the Tutorial buffer
+const TUTORIAL_MODE: &str = "tutorial";
+
+/// Virtual text namespace for the check marks
+const CHECK_NAMESPACE: &str = "tutorial";
+
+const TUTORIAL_TEXT: &str = include_str!("../../docs/tutor.txt");
+
+/// A lesson in `docs/tutor.txt`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lesson {
+    Editing,
+    CommandPalette,
+    Search,
+    MultiCursor,
+    Splits,
+}
+
+impl Lesson {
+    const ALL: [Lesson; 5] = [
+        Lesson::Editing,
+        Lesson::CommandPalette,
+        Lesson::Search,
+        Lesson::MultiCursor,
+        Lesson::Splits,
+    ];
+
+    /// The start of the lesson's heading line
+    fn heading(self) -> String {
+        let number = Self::ALL.iter().position(|&l| l == self).unwrap_or(0) + 1;
+        format!("Lesson {}:", number)
+    }
+
+    /// The lesson whose task is running `action`
+    fn done_by_action(action: &Action) -> Option<Lesson> {
+        match action {
+            Action::ToggleLineNumbers => Some(Lesson::CommandPalette),
+            Action::SplitVertical | Action::SplitHorizontal => Some(Lesson::Splits),
+            _ => None,
+        }
+    }
+
+    /// Whether the lesson's task is done, going by the buffer's text
+    fn done_by_text(self, text: &str) -> bool {
+        let lines = || text.lines().map(str::trim_end);
+        match self {
+            Lesson::Editing => {
+                lines().any(|line| line == "> The quick brown fox jumps over the lazy dog.")
+            }
+            Lesson::MultiCursor => {
+                (1..=3).all(|n| lines().any(|line| line == format!("> let value = {};", n)))
+                    && !lines().any(|line| line.starts_with("> let TODO"))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Progress through the tutorial
+#[derive(Debug)]
+pub(super) struct TutorialProgress {
+    buffer_id: BufferId,
+    done: Vec<Lesson>,
+    /// Lessons done by actions run since the last check
+    pending: Vec<Lesson>,
+    /// Buffer version the text was last checked at
+    checked_version: Option<u64>,
+}
+
+impl Editor {
+    /// Show the Tutorial buffer, starting it over if it was closed
+    pub fn show_tutorial(&mut self) -> BufferId {
+        let existing = self
+            .tutorial
+            .as_ref()
+            .map(|tutorial| tutorial.buffer_id)
+            .filter(|id| self.buffers.contains_key(id));
+        if let Some(buffer_id) = existing {
+            self.set_active_buffer(buffer_id);
+            return buffer_id;
+        }
+
+        let buffer_id = self.create_virtual_buffer(
+            t!("tutorial.title").to_string(),
+            TUTORIAL_MODE.to_string(),
+            false,
+        );
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, TUTORIAL_TEXT);
+            state.buffer.set_modified(false);
+        }
+        self.tutorial = Some(TutorialProgress {
+            buffer_id,
+            done: Vec::new(),
+            pending: Vec::new(),
+            checked_version: None,
+        });
+        self.set_active_buffer(buffer_id);
+        buffer_id
+    }
+
+    /// Show the Tutorial if this is the first launch: it's never been shown
+    /// and there's no user config yet. Returns whether it was shown.
+    pub fn show_tutorial_on_first_launch(&mut self) -> bool {
+        let marker = self.dir_context.tutorial_shown_path();
+        if marker.exists() {
+            return false;
+        }
+        if let Err(e) = std::fs::create_dir_all(&self.dir_context.data_dir)
+            .and_then(|()| std::fs::write(&marker, ""))
+        {
+            tracing::warn!("Failed to write {}: {}", marker.display(), e);
+        }
+        if self.dir_context.config_path().exists() {
+            return false;
+        }
+        self.show_tutorial();
+        true
+    }
+
+    /// Note an action run, for the lessons whose task is running it
+    pub(super) fn observe_tutorial_action(&mut self, action: &Action) {
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return;
+        };
+        if let Some(lesson) = Lesson::done_by_action(action) {
+            if !tutorial.done.contains(&lesson) && !tutorial.pending.contains(&lesson) {
+                tutorial.pending.push(lesson);
+            }
+        }
+    }
+
+    /// Check off the lessons whose task is done. Returns whether any were.
+    pub(super) fn update_tutorial(&mut self) -> bool {
+        let searched = self
+            .search_state
+            .as_ref()
+            .is_some_and(|search| search.query.trim().eq_ignore_ascii_case("needle"));
+        let Some(tutorial) = self.tutorial.as_mut() else {
+            return false;
+        };
+        let Some(state) = self.buffers.get_mut(&tutorial.buffer_id) else {
+            // Closed: start over next time
+            self.tutorial = None;
+            return false;
+        };
+
+        // Nothing here is worth saving, so don't ask before closing it
+        state.buffer.set_modified(false);
+
+        let mut newly_done = std::mem::take(&mut tutorial.pending);
+        if searched {
+            newly_done.push(Lesson::Search);
+        }
+        let version = state.buffer.revision();
+        if tutorial.checked_version != Some(version) {
+            tutorial.checked_version = Some(version);
+            let text = state.buffer.to_string().unwrap_or_default();
+            newly_done.extend(
+                Lesson::ALL
+                    .into_iter()
+                    .filter(|lesson| lesson.done_by_text(&text)),
+            );
+        }
+        newly_done.retain(|lesson| !tutorial.done.contains(lesson));
+        newly_done.dedup();
+        if newly_done.is_empty() {
+            return false;
+        }
+        tutorial.done.extend(newly_done);
+
+        // Put a check mark after the heading of every lesson done
+        let ns = VirtualTextNamespace::from_string(CHECK_NAMESPACE.to_string());
+        let style = Style::default().fg(self.theme.help_key_fg);
+        let text = state.buffer.to_string().unwrap_or_default();
+        state
+            .virtual_texts
+            .clear_namespace(&mut state.marker_list, &ns);
+        for lesson in &tutorial.done {
+            if let Some(end) = heading_end(&text, *lesson) {
+                state.virtual_texts.add_inline(
+                    &mut state.marker_list,
+                    end,
+                    "✓".to_string(),
+                    style,
+                    VirtualTextPosition::AfterChar,
+                    ns.clone(),
+                    0,
+                );
+            }
+        }
+
+        let done = tutorial.done.len();
+        let total = Lesson::ALL.len();
+        let message = if done == total {
+            t!("tutorial.finished").to_string()
+        } else {
+            t!("tutorial.lesson_done", done = done, total = total).to_string()
+        };
+        self.set_status_message(message);
+        true
+    }
+}
+
+/// Offset of the last character of `lesson`'s heading line
+fn heading_end(text: &str, lesson: Lesson) -> Option<usize> {
+    let heading = lesson.heading();
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end();
+        if content.starts_with(&heading) {
+            let last = content.chars().next_back()?;
+            return Some(offset + content.len() - last.len_utf8());
+        }
+        offset += line.len();
+    }
+    None
+}
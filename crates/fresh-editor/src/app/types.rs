@@ -1,7 +1,9 @@
 use crate::app::file_open::SortMode;
+use crate::config::LanguageConfig;
 use crate::input::keybindings::Action;
 use crate::model::event::{BufferId, SplitDirection, SplitId};
 use crate::services::async_bridge::LspMessageType;
+use crate::services::lsp::manager::detect_language;
 use ratatui::layout::Rect;
 use rust_i18n::t;
 use std::collections::{HashMap, HashSet};
@@ -46,6 +48,31 @@ pub(super) struct Bookmark {
     pub position: usize,
 }
 
+/// A file-backed buffer that was closed, remembered so it can be reopened
+#[derive(Debug, Clone)]
+pub(super) struct ClosedBuffer {
+    /// Path of the closed file
+    pub path: PathBuf,
+    /// Primary cursor position (byte offset)
+    pub cursor: usize,
+    /// First visible byte of the viewport
+    pub top_byte: usize,
+    /// Horizontal scroll offset
+    pub left_column: usize,
+}
+
+/// A file or directory moved to the trash from the file explorer
+#[derive(Debug, Clone)]
+pub(super) struct TrashedPath {
+    /// Where the path lived before it was trashed
+    pub original: PathBuf,
+    /// Location in fresh's own trash directory (remote files only);
+    /// local files go to the OS trash and are looked up by original path
+    pub remote_trash_path: Option<PathBuf>,
+    /// When the path was trashed, for the undo window
+    pub trashed_at: std::time::Instant,
+}
+
 /// State for interactive replace (query-replace)
 #[derive(Debug, Clone)]
 pub(super) struct InteractiveReplaceState {
@@ -116,6 +143,11 @@ pub struct BufferMetadata {
     /// For file-backed buffers, recovery ID is computed from the path hash.
     /// For unnamed buffers, this is generated once and reused across auto-saves.
     pub recovery_id: Option<String>,
+
+    /// Language chosen for the buffer (with Set Language or a modeline),
+    /// overriding the one its file name gives. A key of `config.languages`
+    /// when the language is configured there.
+    pub language_override: Option<String>,
 }
 
 impl BufferMetadata {
@@ -135,6 +167,15 @@ impl BufferMetadata {
         }
     }
 
+    /// The buffer's language: the override if there's one, else the
+    /// configured language its file name matches
+    pub fn language(&self, languages: &HashMap<String, LanguageConfig>) -> Option<String> {
+        self.language_override.clone().or_else(|| {
+            self.file_path()
+                .and_then(|path| detect_language(path, languages))
+        })
+    }
+
     /// Check if this is a virtual buffer
     pub fn is_virtual(&self) -> bool {
         matches!(self.kind, BufferKind::Virtual { .. })
@@ -171,6 +212,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         }
     }
 
@@ -190,6 +232,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         }
     }
 
@@ -228,6 +271,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         }
     }
 
@@ -334,6 +378,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
             recovery_id: None,
+            language_override: None,
         }
     }
 
@@ -351,6 +396,7 @@ impl BufferMetadata {
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: true,
             recovery_id: None,
+            language_override: None,
         }
     }
 
@@ -361,6 +407,17 @@ impl BufferMetadata {
     }
 }
 
+/// Side-by-side preview shown while choosing what to do with a recovery entry
+#[derive(Debug, Clone)]
+pub(crate) struct RecoveryPreview {
+    /// The composite diff view
+    pub composite_id: BufferId,
+    /// Hidden source buffers (on-disk content, recovered content)
+    pub source_ids: Vec<BufferId>,
+    /// Buffer that was active before the preview was opened
+    pub previous_buffer: BufferId,
+}
+
 /// State for macro recording
 #[derive(Debug, Clone)]
 pub(super) struct MacroRecordingState {
@@ -379,6 +436,15 @@ pub(super) struct LspProgressInfo {
     pub percentage: Option<u32>,
 }
 
+/// An LSP request the user is waiting for: the server it went to, so it
+/// can be cancelled there, and when, so the Profiler can time the response
+#[derive(Debug, Clone)]
+pub(super) struct InFlightLspRequest {
+    pub kind: crate::services::profiler::LspRequestKind,
+    pub language: String,
+    pub sent_at: std::time::Instant,
+}
+
 /// LSP message entry (for window messages and logs)
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -436,12 +502,16 @@ pub enum HoverTarget {
     StatusBarLspIndicator,
     /// Hovering over the status bar warning badge
     StatusBarWarningBadge,
+    /// Hovering over the status bar indentation indicator
+    StatusBarIndentationIndicator,
     /// Hovering over the status bar line ending indicator
     StatusBarLineEndingIndicator,
     /// Hovering over the status bar encoding indicator
     StatusBarEncodingIndicator,
     /// Hovering over the status bar language indicator
     StatusBarLanguageIndicator,
+    /// Hovering over the status bar cursor position
+    StatusBarPositionIndicator,
     /// Hovering over the search options "Case Sensitive" checkbox
     SearchOptionCaseSensitive,
     /// Hovering over the search options "Whole Word" checkbox
@@ -467,6 +537,12 @@ pub enum TabContextMenuItem {
     CloseToLeft,
     /// Close all tabs
     CloseAll,
+    /// Pin or unpin this tab
+    TogglePin,
+    /// Show this tab's file in the file explorer
+    RevealInExplorer,
+    /// Copy this tab's file path to the clipboard
+    CopyPath,
 }
 
 impl TabContextMenuItem {
@@ -478,17 +554,25 @@ impl TabContextMenuItem {
             Self::CloseToRight,
             Self::CloseToLeft,
             Self::CloseAll,
+            Self::TogglePin,
+            Self::RevealInExplorer,
+            Self::CopyPath,
         ]
     }
 
-    /// Get the display label for this menu item
-    pub fn label(&self) -> String {
+    /// Get the display label for this menu item, for a tab that is `pinned`
+    /// or not
+    pub fn label(&self, pinned: bool) -> String {
         match self {
             Self::Close => t!("tab.close").to_string(),
             Self::CloseOthers => t!("tab.close_others").to_string(),
             Self::CloseToRight => t!("tab.close_to_right").to_string(),
             Self::CloseToLeft => t!("tab.close_to_left").to_string(),
             Self::CloseAll => t!("tab.close_all").to_string(),
+            Self::TogglePin if pinned => t!("tab.unpin").to_string(),
+            Self::TogglePin => t!("tab.pin").to_string(),
+            Self::RevealInExplorer => t!("tab.reveal_in_explorer").to_string(),
+            Self::CopyPath => t!("tab.copy_path").to_string(),
         }
     }
 }
@@ -504,16 +588,19 @@ pub struct TabContextMenu {
     pub position: (u16, u16),
     /// Currently highlighted menu item index
     pub highlighted: usize,
+    /// Whether the tab is pinned
+    pub pinned: bool,
 }
 
 impl TabContextMenu {
     /// Create a new tab context menu
-    pub fn new(buffer_id: BufferId, split_id: SplitId, x: u16, y: u16) -> Self {
+    pub fn new(buffer_id: BufferId, split_id: SplitId, x: u16, y: u16, pinned: bool) -> Self {
         Self {
             buffer_id,
             split_id,
             position: (x, y),
             highlighted: 0,
+            pinned,
         }
     }
 
@@ -607,6 +694,27 @@ impl TabDragState {
     }
 }
 
+/// Unit a mouse selection grows by: a single click selects by character, a
+/// double-click by word and a triple-click by line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum SelectionGranularity {
+    #[default]
+    Character,
+    Word,
+    Line,
+}
+
+/// A smooth mouse wheel scroll in progress
+#[derive(Debug, Clone, Copy)]
+pub(super) struct SmoothScroll {
+    pub split_id: SplitId,
+    pub buffer_id: BufferId,
+    /// Lines left to scroll: negative scrolls up
+    pub pending: i32,
+    /// When the view last moved, to pace steps to the frame rate
+    pub last_step: Option<std::time::Instant>,
+}
+
 /// Mouse state tracking
 #[derive(Debug, Clone, Default)]
 pub(super) struct MouseState {
@@ -619,6 +727,8 @@ pub(super) struct MouseState {
     pub lsp_hover_state: Option<(usize, std::time::Instant, u16, u16)>,
     /// Whether we've already sent a hover request for the current position
     pub lsp_hover_request_sent: bool,
+    /// Link underlined under the mouse pointer: buffer and byte range
+    pub hovered_link: Option<(BufferId, Range<usize>)>,
     /// Initial mouse row when starting to drag the scrollbar thumb
     /// Used to calculate relative movement rather than jumping
     pub drag_start_row: Option<u16>,
@@ -643,6 +753,10 @@ pub(super) struct MouseState {
     pub drag_selection_split: Option<SplitId>,
     /// The buffer byte position where the selection anchor is
     pub drag_selection_anchor: Option<usize>,
+    /// Unit the text selection drag extends by
+    pub drag_selection_granularity: SelectionGranularity,
+    /// The word or line selected by the double- or triple-click that started the drag
+    pub drag_selection_origin: Option<Range<usize>>,
     /// Tab drag state (for drag-to-split functionality)
     pub dragging_tab: Option<TabDragState>,
     /// Whether we're currently dragging a popup scrollbar (popup index)
@@ -719,6 +833,8 @@ pub(crate) struct CachedLayout {
     pub suggestions_area: Option<(Rect, usize, usize, usize)>,
     /// Tab layouts per split for mouse interaction
     pub tab_layouts: HashMap<SplitId, crate::view::ui::tabs::TabLayout>,
+    /// Breadcrumb bar layouts per split for mouse interaction
+    pub breadcrumb_layouts: HashMap<SplitId, crate::view::ui::breadcrumbs::BreadcrumbLayout>,
     /// Close split button hit areas
     /// (split_id, row, start_col, end_col)
     pub close_split_areas: Vec<(SplitId, u16, u16, u16)>,
@@ -737,6 +853,8 @@ pub(crate) struct CachedLayout {
     pub status_bar_lsp_area: Option<(u16, u16, u16)>,
     /// Status bar warning badge area (row, start_col, end_col)
     pub status_bar_warning_area: Option<(u16, u16, u16)>,
+    /// Status bar indentation indicator area (row, start_col, end_col)
+    pub status_bar_indentation_area: Option<(u16, u16, u16)>,
     /// Status bar line ending indicator area (row, start_col, end_col)
     pub status_bar_line_ending_area: Option<(u16, u16, u16)>,
     /// Status bar encoding indicator area (row, start_col, end_col)
@@ -745,10 +863,16 @@ pub(crate) struct CachedLayout {
     pub status_bar_language_area: Option<(u16, u16, u16)>,
     /// Status bar message area (row, start_col, end_col) - clickable to show status log
     pub status_bar_message_area: Option<(u16, u16, u16)>,
+    /// Status bar cursor position area (row, start_col, end_col) - clickable to go to a line
+    pub status_bar_position_area: Option<(u16, u16, u16)>,
     /// Search options layout for checkbox hit testing
     pub search_options_layout: Option<crate::view::ui::status_bar::SearchOptionsLayout>,
     /// Menu bar layout for hit testing
     pub menu_layout: Option<crate::view::ui::menu::MenuLayout>,
+    /// Keybinding cheat sheet layout for hit testing
+    pub cheat_sheet_layout: Option<crate::view::cheat_sheet::CheatSheetLayout>,
+    /// Buffer switcher layout for hit testing
+    pub buffer_switcher_layout: Option<crate::view::buffer_switcher::BufferSwitcherLayout>,
 }
 
 impl CachedLayout {
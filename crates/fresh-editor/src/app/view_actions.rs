@@ -1,9 +1,12 @@
 //! View mode action handlers.
 //!
-//! This module contains handlers for view-related actions like compose mode toggling.
+//! This module contains handlers for view-related actions like compose mode
+//! and markdown preview toggling.
 
 use super::Editor;
+use crate::model::event::SplitDirection;
 use crate::state::ViewMode;
+use crate::view::split::SplitViewState;
 use rust_i18n::t;
 
 impl Editor {
@@ -35,14 +38,14 @@ impl Editor {
             // In Source mode, respect the user's default_wrap preference.
             vs.viewport.line_wrap_enabled = match view_mode {
                 ViewMode::Compose => false,
-                ViewMode::Source => default_wrap,
+                ViewMode::Source | ViewMode::Preview => default_wrap,
             };
             match view_mode {
                 ViewMode::Compose => {
                     vs.compose_prev_line_numbers = Some(current_line_numbers);
                     self.active_state_mut().margins.set_line_numbers(false);
                 }
-                ViewMode::Source => {
+                ViewMode::Source | ViewMode::Preview => {
                     // Clear compose width to remove margins
                     vs.compose_width = None;
                     vs.view_transform = None;
@@ -70,7 +73,70 @@ impl Editor {
         let mode_label = match view_mode {
             ViewMode::Compose => t!("view.compose").to_string(),
             ViewMode::Source => "Source".to_string(),
+            ViewMode::Preview => t!("view.preview").to_string(),
         };
         self.set_status_message(t!("view.mode", mode = mode_label).to_string());
     }
+
+    /// Toggle the active split between the markdown preview and Source mode.
+    pub fn handle_toggle_markdown_preview(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let current = self
+            .split_view_states
+            .get(&active_split)
+            .map(|vs| vs.view_mode.clone())
+            .unwrap_or_else(|| self.active_state().view_mode.clone());
+
+        // Leave compose mode first so its margins and transform are cleared
+        if current == ViewMode::Compose {
+            self.handle_toggle_compose_mode();
+        }
+
+        let view_mode = if current == ViewMode::Preview {
+            ViewMode::Source
+        } else {
+            ViewMode::Preview
+        };
+        if let Some(vs) = self.split_view_states.get_mut(&active_split) {
+            vs.view_mode = view_mode.clone();
+        }
+        self.active_state_mut().view_mode = view_mode.clone();
+
+        let mode_label = match view_mode {
+            ViewMode::Preview => t!("view.preview").to_string(),
+            _ => "Source".to_string(),
+        };
+        self.set_status_message(t!("view.mode", mode = mode_label).to_string());
+    }
+
+    /// Open a markdown preview of the active buffer in a split to the right.
+    ///
+    /// Focus stays in the source split; the preview follows its cursor.
+    pub fn open_markdown_preview_to_side(&mut self) {
+        let source_split = self.split_manager.active_split();
+        let buffer_id = self.active_buffer();
+        self.save_current_split_view_state();
+
+        match self
+            .split_manager
+            .split_active(SplitDirection::Vertical, buffer_id, 0.5)
+        {
+            Ok(preview_split) => {
+                let mut view_state = SplitViewState::with_buffer(
+                    self.terminal_width,
+                    self.terminal_height,
+                    buffer_id,
+                );
+                view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+                view_state.cursors = self.active_state().cursors.clone();
+                view_state.view_mode = ViewMode::Preview;
+                self.split_view_states.insert(preview_split, view_state);
+                self.split_manager.set_active_split(source_split);
+                self.set_status_message(t!("view.preview_opened").to_string());
+            }
+            Err(e) => {
+                self.set_status_message(t!("split.error", error = e.to_string()).to_string());
+            }
+        }
+    }
 }
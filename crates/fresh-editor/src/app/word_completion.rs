@@ -0,0 +1,140 @@
+//! Completion from the words of open buffers, for buffers without a
+//! language server
+//!
+//! Words are taken from every open buffer and from a ctags `tags` file in
+//! the working directory, and ranked as described in
+//! `primitives::word_completion`. They're shown in the same popup as
+//! language server completions.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::word_completion::{complete_words, typed_prefix, WordSource};
+use crate::primitives::word_navigation::find_completion_word_start;
+
+/// Bytes of each buffer searched for words; in the current buffer they're
+/// taken around the cursor, other buffers larger than this are skipped
+const MAX_SCANNED_BYTES: usize = 1024 * 1024;
+
+const MAX_WORD_COMPLETIONS: usize = 50;
+
+/// Text of a buffer to take words from
+struct ScannedBuffer {
+    text: String,
+    language: String,
+    cursor: Option<usize>,
+    label: Option<String>,
+}
+
+impl Editor {
+    /// Show the words of open buffers and the tags file that start with the
+    /// word being typed
+    pub(super) fn show_word_completions(&mut self) {
+        let buffer_id = self.active_buffer();
+        let (before, after, replaced_bytes) = {
+            let state = self.active_state();
+            let cursor = state.cursors.primary().position;
+            let start = cursor.saturating_sub(MAX_SCANNED_BYTES / 2);
+            let end = (cursor + MAX_SCANNED_BYTES / 2).min(state.buffer.len());
+            (
+                String::from_utf8_lossy(&state.buffer.slice_bytes(start..cursor)).into_owned(),
+                String::from_utf8_lossy(&state.buffer.slice_bytes(cursor..end)).into_owned(),
+                cursor - find_completion_word_start(&state.buffer, cursor),
+            )
+        };
+        let language = self.word_language(buffer_id);
+        let prefix = typed_prefix(&before, &language).to_string();
+        if prefix.is_empty() {
+            return;
+        }
+
+        let mut scanned = vec![ScannedBuffer {
+            cursor: Some(before.len()),
+            text: before + &after,
+            language,
+            label: None,
+        }];
+        for id in self.buffers_by_recency() {
+            if id == buffer_id {
+                continue;
+            }
+            let Some(state) = self.buffers.get(&id) else {
+                continue;
+            };
+            if state.buffer.len() > MAX_SCANNED_BYTES {
+                continue;
+            }
+            let Some(text) = state.buffer.to_string() else {
+                continue;
+            };
+            scanned.push(ScannedBuffer {
+                text,
+                language: self.word_language(id),
+                cursor: None,
+                label: Some(self.get_buffer_display_name(id)),
+            });
+        }
+        self.refresh_tags();
+
+        let sources: Vec<WordSource> = scanned
+            .iter()
+            .map(|buffer| WordSource {
+                text: &buffer.text,
+                language: &buffer.language,
+                cursor: buffer.cursor,
+                label: buffer.label.as_deref(),
+            })
+            .collect();
+        let tags = self.tags_cache.as_ref().map_or(&[][..], |tags| &tags.names);
+        let completions = complete_words(&prefix, &sources, tags, MAX_WORD_COMPLETIONS);
+        if completions.is_empty() {
+            self.set_status_message(t!("completion.no_words").to_string());
+            return;
+        }
+
+        // The popup replaces the letters, digits and `_` before the cursor,
+        // which can be less than the typed word (`size` of `font-size`)
+        let typed_chars = prefix.chars().count();
+        let replaced_chars = trailing_chars(&prefix, replaced_bytes);
+        let items = completions
+            .into_iter()
+            .map(|completion| {
+                let insert: String = completion
+                    .word
+                    .chars()
+                    .skip(typed_chars.saturating_sub(replaced_chars))
+                    .collect();
+                lsp_types::CompletionItem {
+                    label: completion.word,
+                    kind: Some(lsp_types::CompletionItemKind::TEXT),
+                    detail: completion.label,
+                    filter_text: Some(insert.clone()),
+                    insert_text: Some(insert),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        self.show_completion_popup(items);
+    }
+
+    /// The language a buffer's words are split by
+    fn word_language(&self, buffer_id: BufferId) -> String {
+        self.buffer_metadata
+            .get(&buffer_id)
+            .and_then(|metadata| metadata.language(&self.config.languages))
+            .or_else(|| {
+                self.buffers
+                    .get(&buffer_id)
+                    .map(|state| state.language.clone())
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// The number of characters in the last `bytes` bytes of `prefix`
+fn trailing_chars(prefix: &str, bytes: usize) -> usize {
+    prefix
+        .get(prefix.len().saturating_sub(bytes)..)
+        .map_or(0, |tail| tail.chars().count())
+}
@@ -0,0 +1,194 @@
+//! Workspace folders: directories opened alongside the working directory,
+//! such as the other projects of a monorepo. Every language server is told
+//! about all of them (`workspaceFolders`), so a file from any of them is
+//! served by the running server for its language. The file explorer shows
+//! each folder outside the working directory as another root.
+
+use std::path::{Path, PathBuf};
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::view::prompt::{Prompt, PromptType};
+
+impl Editor {
+    /// The workspace folders besides the working directory
+    pub fn workspace_folders(&self) -> &[PathBuf] {
+        &self.workspace_folders
+    }
+
+    /// Add a folder to the workspace. Returns whether it was added: it isn't
+    /// if it's the working directory, already in the workspace or not a
+    /// directory.
+    pub fn add_workspace_folder(&mut self, path: PathBuf) -> bool {
+        let path = path.canonicalize().unwrap_or(path);
+        if !self.filesystem.is_dir(&path).unwrap_or(false) {
+            self.set_status_message(
+                t!("workspace.not_a_folder", path = path.display().to_string()).to_string(),
+            );
+            return false;
+        }
+        if path == self.working_dir || self.workspace_folders.contains(&path) {
+            self.set_status_message(
+                t!("workspace.already_added", path = path.display().to_string()).to_string(),
+            );
+            return false;
+        }
+
+        self.workspace_folders.push(path.clone());
+        self.update_lsp_workspace_folders();
+        self.add_file_explorer_root(&path);
+        self.set_status_message(
+            t!("workspace.folder_added", path = path.display().to_string()).to_string(),
+        );
+        true
+    }
+
+    /// Remove a folder from the workspace. Returns whether it was in it.
+    pub fn remove_workspace_folder(&mut self, path: &Path) -> bool {
+        let Some(index) = self.workspace_folders.iter().position(|p| p == path) else {
+            self.set_status_message(
+                t!(
+                    "workspace.not_in_workspace",
+                    path = path.display().to_string()
+                )
+                .to_string(),
+            );
+            return false;
+        };
+
+        self.workspace_folders.remove(index);
+        self.update_lsp_workspace_folders();
+        if let Some(explorer) = &mut self.file_explorer {
+            if explorer.tree_mut().remove_root(path) {
+                let selected = explorer.get_selected();
+                if selected.is_none_or(|id| explorer.tree().get_node(id).is_none()) {
+                    explorer.set_selected(Some(explorer.tree().root_id()));
+                }
+                explorer.update_scroll_for_selection();
+            }
+        }
+        self.set_status_message(
+            t!(
+                "workspace.folder_removed",
+                path = path.display().to_string()
+            )
+            .to_string(),
+        );
+        true
+    }
+
+    /// Restore the workspace folders of a session, before the file explorer
+    /// is initialized
+    pub(super) fn restore_workspace_folders(&mut self, folders: &[PathBuf]) {
+        for folder in folders {
+            if *folder != self.working_dir
+                && !self.workspace_folders.contains(folder)
+                && self.filesystem.is_dir(folder).unwrap_or(false)
+            {
+                self.workspace_folders.push(folder.clone());
+            }
+        }
+        self.update_lsp_workspace_folders();
+    }
+
+    /// The workspace folders the file explorer shows as roots of their own:
+    /// those that aren't inside its root or another folder shown before them
+    pub(super) fn file_explorer_extra_roots(&self, root: &Path) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for folder in &self.workspace_folders {
+            if !folder.starts_with(root) && !roots.iter().any(|r| folder.starts_with(r)) {
+                roots.push(folder.clone());
+            }
+        }
+        roots
+    }
+
+    /// Tell the language servers about the current workspace folders. The
+    /// documents of a server restarted for them are opened in it again.
+    fn update_lsp_workspace_folders(&mut self) {
+        let folders: Vec<lsp_types::Uri> = self
+            .workspace_folders
+            .iter()
+            .filter_map(|folder| {
+                url::Url::from_file_path(folder)
+                    .ok()
+                    .and_then(|url| url.as_str().parse().ok())
+            })
+            .collect();
+        let restarted = match &mut self.lsp {
+            Some(lsp) => lsp.set_workspace_folders(folders),
+            None => return,
+        };
+        for language in restarted {
+            self.resend_did_open_for_language(&language);
+        }
+    }
+
+    /// Show a workspace folder in the file explorer, if it's open and the
+    /// folder isn't already in it
+    fn add_file_explorer_root(&mut self, path: &Path) {
+        let Some(root) = self
+            .file_explorer
+            .as_ref()
+            .map(|explorer| explorer.tree().root_path().to_path_buf())
+        else {
+            return;
+        };
+        if !self
+            .file_explorer_extra_roots(&root)
+            .iter()
+            .any(|r| r == path)
+        {
+            return;
+        }
+        let (Some(runtime), Some(explorer)) = (&self.tokio_runtime, &mut self.file_explorer) else {
+            return;
+        };
+        let tree = explorer.tree_mut();
+        let result = runtime.block_on(async {
+            let id = tree.add_root(path.to_path_buf()).await?;
+            tree.expand_node(id).await
+        });
+        match result {
+            Ok(()) => {
+                if let Err(e) = explorer.load_gitignore_for_dir(path) {
+                    tracing::warn!("Failed to load .gitignore from {:?}: {}", path, e);
+                }
+            }
+            Err(e) => tracing::warn!("Failed to show {:?} in the file explorer: {}", path, e),
+        }
+    }
+
+    /// Show the prompt for the workspace folder to remove
+    pub fn start_remove_workspace_folder_prompt(&mut self) {
+        if self.workspace_folders.is_empty() {
+            self.set_status_message(t!("workspace.no_folders").to_string());
+            return;
+        }
+        let suggestions: Vec<Suggestion> = self
+            .workspace_folders
+            .iter()
+            .map(|folder| {
+                let text = folder.display().to_string();
+                Suggestion {
+                    text: text.clone(),
+                    description: None,
+                    value: Some(text),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("workspace.remove_prompt").to_string(),
+            PromptType::RemoveWorkspaceFolder,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Trusting the working directory
+//!
+//! An untrusted workspace is opened in safe mode (see
+//! `services::workspace_trust`). If it has anything safe mode leaves out,
+//! the user is asked on startup whether to trust it for good, for this
+//! session only, or to stay in safe mode. Trusting it reloads the config
+//! with the project's commands, loads the project's environment and loads
+//! its plugins.
+
+use rust_i18n::t;
+
+use super::Editor;
+use crate::config::Config;
+use crate::services::workspace_trust;
+use crate::view::prompt::PromptType;
+
+impl Editor {
+    /// Whether the working directory is trusted (otherwise it's in safe mode)
+    pub fn is_workspace_trusted(&self) -> bool {
+        self.workspace_trusted
+    }
+
+    /// Ask whether to trust the working directory, if it's untrusted and has
+    /// plugins or commands that safe mode leaves out. Called on startup.
+    pub fn check_workspace_trust(&mut self) {
+        if self.workspace_trusted
+            || !workspace_trust::has_code(&self.dir_context, &self.working_dir)
+        {
+            return;
+        }
+        if self.is_prompting() {
+            // Don't replace the prompt the user is answering
+            self.set_status_message(t!("workspace_trust.safe_mode").to_string());
+            return;
+        }
+        self.start_trust_workspace_prompt();
+    }
+
+    /// Start the prompt asking whether to trust the working directory
+    pub(super) fn start_trust_workspace_prompt(&mut self) {
+        if self.workspace_trusted {
+            self.set_status_message(t!("workspace_trust.already_trusted").to_string());
+            return;
+        }
+        let dir = self.working_dir.display().to_string();
+        self.start_prompt(
+            t!("workspace_trust.prompt", dir = dir).to_string(),
+            PromptType::TrustWorkspace,
+        );
+    }
+
+    /// Handle TrustWorkspace prompt confirmation.
+    pub(super) fn handle_trust_workspace(&mut self, input: &str) {
+        let input_lower = input.trim().to_lowercase();
+        if input_lower == "t" || input_lower == "trust" {
+            if let Err(e) = workspace_trust::trust(&self.dir_context, &self.working_dir) {
+                tracing::warn!("Failed to save workspace trust: {}", e);
+                self.set_status_message(
+                    t!("workspace_trust.save_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+            self.trust_workspace();
+            self.set_status_message(t!("workspace_trust.trusted").to_string());
+        } else if input_lower == "s" || input_lower == "session" {
+            self.trust_workspace();
+            self.set_status_message(t!("workspace_trust.trusted_session").to_string());
+        } else {
+            self.set_status_message(t!("workspace_trust.safe_mode").to_string());
+        }
+    }
+
+    /// Leave safe mode: apply the project's commands and environment and load
+    /// its plugins
+    fn trust_workspace(&mut self) {
+        self.workspace_trusted = true;
+        let config = Config::load_with_layers_trusted(&self.dir_context, &self.working_dir, true);
+        self.apply_reloaded_config(config);
+        self.load_project_env();
+
+        let plugin_dir = self.working_dir.join("plugins");
+        if !self.plugin_manager.is_active() || !plugin_dir.is_dir() {
+            return;
+        }
+        tracing::info!("Loading TypeScript plugins from: {:?}", plugin_dir);
+        self.plugin_sandbox
+            .write()
+            .unwrap()
+            .add_plugin_dir(&plugin_dir, false);
+        let (errors, discovered_plugins) = self
+            .plugin_manager
+            .load_plugins_from_dir_with_config(&plugin_dir, &self.config.plugins);
+        self.config.plugins.extend(discovered_plugins);
+        for err in &errors {
+            tracing::error!("TypeScript plugin load error: {}", err);
+        }
+    }
+}
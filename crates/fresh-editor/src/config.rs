@@ -169,6 +169,16 @@ impl CursorStyle {
         }
     }
 
+    /// The non-blinking form of this style, for `reduced_motion`
+    pub fn steady(self) -> Self {
+        match self {
+            Self::Default | Self::BlinkingBlock => Self::SteadyBlock,
+            Self::BlinkingBar => Self::SteadyBar,
+            Self::BlinkingUnderline => Self::SteadyUnderline,
+            steady => steady,
+        }
+    }
+
     /// Parse from string (for command palette)
     pub fn parse(s: &str) -> Option<Self> {
         match s {
@@ -285,6 +295,40 @@ impl JsonSchema for LineEndingOption {
     }
 }
 
+/// How a file is written when it is saved
+///
+/// Writing a new file and renaming it over the original never leaves a
+/// half-written file behind, but the result is a new file: other hard links
+/// keep the old contents, and it belongs to whoever saved it. Overwriting the
+/// original in place keeps both, at the cost of a truncated file if the
+/// editor dies mid-write. Symlinks are followed either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaveStrategy {
+    /// Rename, unless the file has other hard links or belongs to another user
+    #[default]
+    Auto,
+    /// Always write a new file and rename it over the original
+    Rename,
+    /// Always overwrite the original file
+    InPlace,
+}
+
+impl JsonSchema for SaveStrategy {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("SaveStrategy")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How a file is written when it is saved",
+            "type": "string",
+            "enum": ["auto", "rename", "in_place"],
+            "default": "auto"
+        })
+    }
+}
+
 /// Controls whether Enter accepts a completion suggestion.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -313,6 +357,62 @@ impl JsonSchema for AcceptSuggestionOnEnter {
     }
 }
 
+/// How the gutter numbers lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineNumberMode {
+    /// Every line shows its own line number
+    #[default]
+    Absolute,
+    /// Every line shows its distance from the cursor line (0 on the cursor line)
+    Relative,
+    /// The cursor line shows its line number, other lines their distance from it
+    Hybrid,
+}
+
+impl JsonSchema for LineNumberMode {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("LineNumberMode")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How the gutter numbers lines",
+            "type": "string",
+            "enum": ["absolute", "relative", "hybrid"],
+            "default": "absolute"
+        })
+    }
+}
+
+/// When whitespace characters are drawn as visible glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderWhitespace {
+    /// Whitespace is drawn as blank space
+    #[default]
+    None,
+    /// Whitespace is drawn only inside the selection
+    Selection,
+    /// Whitespace is always drawn
+    All,
+}
+
+impl JsonSchema for RenderWhitespace {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("RenderWhitespace")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "When whitespace characters are drawn as visible glyphs",
+            "type": "string",
+            "enum": ["none", "selection", "all"],
+            "default": "none"
+        })
+    }
+}
+
 impl PartialEq<KeybindingMapName> for str {
     fn eq(&self, other: &KeybindingMapName) -> bool {
         self == other.0
@@ -345,6 +445,10 @@ pub struct Config {
     #[serde(default = "default_theme_name")]
     pub theme: ThemeName,
 
+    /// Automatic switching between a light and a dark theme
+    #[serde(default)]
+    pub appearance: AppearanceConfig,
+
     /// UI locale (language) for translations
     /// If not set, auto-detected from environment (LC_ALL, LC_MESSAGES, LANG)
     #[serde(default)]
@@ -422,6 +526,99 @@ fn default_theme_name() -> ThemeName {
     ThemeName("high-contrast".to_string())
 }
 
+/// How the active theme is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppearanceMode {
+    /// Always use `theme`
+    #[default]
+    Fixed,
+    /// Follow the terminal's background color
+    Auto,
+    /// Switch at the times in `light_at` and `dark_at`
+    Schedule,
+}
+
+impl JsonSchema for AppearanceMode {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("AppearanceMode")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "How the active theme is chosen",
+            "type": "string",
+            "enum": ["fixed", "auto", "schedule"],
+            "default": "fixed"
+        })
+    }
+}
+
+/// Automatic light/dark theme configuration
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AppearanceConfig {
+    /// How the active theme is chosen (default: "fixed")
+    /// - "fixed": always use `theme`
+    /// - "auto": use `light_theme` or `dark_theme` to match the terminal background
+    /// - "schedule": use `light_theme` from `light_at` until `dark_at`, and `dark_theme` otherwise
+    #[serde(default)]
+    pub mode: AppearanceMode,
+
+    /// Theme used on a light background or during the day (default: "light")
+    #[serde(default = "default_light_theme")]
+    pub light_theme: ThemeName,
+
+    /// Theme used on a dark background or at night (default: "dark")
+    #[serde(default = "default_dark_theme")]
+    pub dark_theme: ThemeName,
+
+    /// When to switch to the light theme: "HH:MM" or "sunrise" (default: "07:00")
+    #[serde(default = "default_light_at")]
+    pub light_at: String,
+
+    /// When to switch to the dark theme: "HH:MM" or "sunset" (default: "19:00")
+    #[serde(default = "default_dark_at")]
+    pub dark_at: String,
+
+    /// Latitude in degrees, used to compute "sunrise" and "sunset" (default: none)
+    #[serde(default)]
+    pub latitude: Option<f64>,
+
+    /// Longitude in degrees (east positive), used to compute "sunrise" and "sunset" (default: none)
+    #[serde(default)]
+    pub longitude: Option<f64>,
+}
+
+fn default_light_theme() -> ThemeName {
+    ThemeName("light".to_string())
+}
+
+fn default_dark_theme() -> ThemeName {
+    ThemeName("dark".to_string())
+}
+
+fn default_light_at() -> String {
+    "07:00".to_string()
+}
+
+fn default_dark_at() -> String {
+    "19:00".to_string()
+}
+
+impl Default for AppearanceConfig {
+    fn default() -> Self {
+        Self {
+            mode: AppearanceMode::Fixed,
+            light_theme: default_light_theme(),
+            dark_theme: default_dark_theme(),
+            light_at: default_light_at(),
+            dark_at: default_dark_at(),
+            latitude: None,
+            longitude: None,
+        }
+    }
+}
+
 /// Editor behavior configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EditorConfig {
@@ -432,10 +629,19 @@ pub struct EditorConfig {
     pub line_numbers: bool,
 
     /// Show line numbers relative to cursor position
+    /// (same as `line_number_mode: "hybrid"`)
     #[serde(default = "default_false")]
     #[schemars(extend("x-section" = "Display"))]
     pub relative_line_numbers: bool,
 
+    /// How line numbers are counted.
+    /// Options: absolute, relative (distance from the cursor line),
+    /// hybrid (absolute on the cursor line, relative elsewhere)
+    /// Default: absolute
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub line_number_mode: LineNumberMode,
+
     /// Wrap long lines to fit the window width (default for new views)
     #[serde(default = "default_true")]
     #[schemars(extend("x-section" = "Display"))]
@@ -462,6 +668,15 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_tab_bar: bool,
 
+    /// Whether the breadcrumb bar is visible by default.
+    /// The breadcrumb bar shows the file path and the definitions around the
+    /// cursor above each split pane; click a crumb to go to a sibling.
+    /// Can be toggled at runtime via command palette.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_breadcrumbs: bool,
+
     /// Use the terminal's default background color instead of the theme's editor background.
     /// When enabled, the editor background inherits from the terminal emulator,
     /// allowing transparency or custom terminal backgrounds to show through.
@@ -470,6 +685,26 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub use_terminal_bg: bool,
 
+    /// Draw each frame atomically (synchronized output, mode 2026) so large
+    /// repaints don't flicker. Only used when the terminal reports support.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub synchronized_output: bool,
+
+    /// Show a swatch before color literals (`#rrggbb`, `rgb()`, `hsl()`).
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub color_swatches: bool,
+
+    /// Underline URLs and `path:line` references (as printed by compilers and
+    /// grep) in the visible text.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub underline_links: bool,
+
     /// Cursor style for the terminal cursor.
     /// Options: blinking_block, steady_block, blinking_bar, steady_bar, blinking_underline, steady_underline
     /// Default: blinking_block
@@ -477,6 +712,67 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub cursor_style: CursorStyle,
 
+    /// Columns at which to draw vertical rulers (e.g. [80, 120]).
+    /// Languages can override this with `languages.<lang>.rulers`.
+    /// Default: [] (no rulers)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub rulers: Vec<usize>,
+
+    /// Highlight spaces and tabs at the end of lines.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_trailing_whitespace: bool,
+
+    /// Highlight indentation that mixes tabs and spaces on the same line.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_mixed_indentation: bool,
+
+    /// Highlight characters past this column as too long (e.g. 100).
+    /// Languages can override this with `languages.<lang>.max_line_length`.
+    /// Default: null (no limit)
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub max_line_length: Option<usize>,
+
+    /// Also list lines longer than `max_line_length` as warnings in the
+    /// diagnostics panel.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub line_length_diagnostics: bool,
+
+    /// Draw tabs as », spaces as · and line endings as ¶ (␍¶ for CRLF).
+    /// Options: none, selection (only inside the selection), all
+    /// Default: none
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Display"))]
+    pub render_whitespace: RenderWhitespace,
+
+    /// Highlight the line containing the cursor in the active split.
+    /// Themes color it with `editor.current_line_bg`.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub highlight_current_line: bool,
+
+    /// Highlight the column containing the cursor in the active split.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub highlight_current_column: bool,
+
+    /// Show lines containing right-to-left text (Arabic, Hebrew) in visual
+    /// order. Only the display changes; the cursor still moves through the
+    /// text in logical order.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub bidi_text: bool,
+
     // ===== Editing =====
     /// Number of spaces per tab character
     #[serde(default = "default_tab_size")]
@@ -488,6 +784,18 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub auto_indent: bool,
 
+    /// Detect whether an opened file is indented with tabs or spaces, and
+    /// the indent width, overriding tab_size and use_tabs for that file
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub detect_indentation: bool,
+
+    /// Apply the settings a file gives in a vim or Emacs modeline when it's
+    /// opened: language, tab width, tabs or spaces and line wrapping
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub modelines: bool,
+
     /// Minimum lines to keep visible above/below cursor when scrolling
     #[serde(default = "default_scroll_offset")]
     #[schemars(extend("x-section" = "Editing"))]
@@ -513,6 +821,19 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    // ===== New Files =====
+    /// Templates offered when creating a file that doesn't exist yet, from the
+    /// file explorer, Open File or New File.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "New Files"))]
+    pub file_templates: Vec<FileTemplate>,
+
+    /// Author inserted for `${AUTHOR}` in file templates.
+    /// If empty, git's user.name is used, then the USER environment variable.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "New Files"))]
+    pub file_template_author: String,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -561,6 +882,13 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Completion"))]
     pub accept_suggestion_on_enter: AcceptSuggestionOnEnter,
 
+    /// Whether completion suggests words from open buffers and the project's
+    /// `tags` file when no language server is available for the buffer.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Completion"))]
+    pub word_completion: bool,
+
     // ===== LSP =====
     /// Whether to enable LSP inlay hints (type hints, parameter hints, etc.)
     #[serde(default = "default_true")]
@@ -574,6 +902,29 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "LSP"))]
     pub enable_semantic_tokens_full: bool,
 
+    /// Seconds a language server is kept running after its last file is
+    /// closed, so reopening one doesn't wait for it to start and index the
+    /// project again. Set to 0 to keep servers running until the editor exits.
+    /// Default: 600 (10 minutes)
+    #[serde(default = "default_lsp_idle_timeout")]
+    #[schemars(extend("x-section" = "LSP"))]
+    pub lsp_idle_timeout_secs: u64,
+
+    // ===== Spell Check =====
+    /// Whether to check spelling in prose and in code comments and strings.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_check: bool,
+
+    /// Dictionary used for spell checking (e.g. "en_US", "de_DE").
+    /// Hunspell `.dic`/`.aff` files are looked up in the `dictionaries`
+    /// directory of the config dir, then in the system hunspell directories.
+    /// Default: "en_US"
+    #[serde(default = "default_spell_language")]
+    #[schemars(extend("x-section" = "Spell Check"))]
+    pub spell_language: String,
+
     // ===== Mouse =====
     /// Whether mouse hover triggers LSP hover requests.
     /// When enabled, hovering over code with the mouse will show documentation.
@@ -596,6 +947,49 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Mouse"))]
     pub double_click_time_ms: u64,
 
+    /// Number of lines the mouse wheel scrolls per notch.
+    /// Default: 3
+    #[serde(default = "default_mouse_scroll_lines")]
+    #[schemars(extend("x-section" = "Mouse"))]
+    pub mouse_scroll_lines: usize,
+
+    /// Number of columns a horizontal wheel (or Shift+wheel) scrolls per notch.
+    /// Default: 6
+    #[serde(default = "default_mouse_scroll_columns")]
+    #[schemars(extend("x-section" = "Mouse"))]
+    pub mouse_scroll_columns: usize,
+
+    /// Smooth (kinetic) mouse wheel scrolling.
+    /// When enabled, wheel scrolls glide over a few frames instead of jumping,
+    /// and turning the wheel quickly builds up momentum.
+    /// Default: false
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Mouse"))]
+    pub smooth_scrolling: bool,
+
+    // ===== Accessibility =====
+    /// Turn off motion: the cursor never blinks and mouse wheel scrolls jump
+    /// instead of gliding, whatever `cursor_style` and `smooth_scrolling` say.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Accessibility"))]
+    pub reduced_motion: bool,
+
+    /// Raise the contrast of the theme so that text is at least 7:1 against
+    /// its background (WCAG AAA). Colors that already reach it are kept.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Accessibility"))]
+    pub high_contrast: bool,
+
+    /// Make thin mouse targets (split separators, scrollbars, the file
+    /// explorer border and the split close and maximize buttons) easier to hit
+    /// by counting the text cell next to them as part of the target.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Accessibility"))]
+    pub large_hit_targets: bool,
+
     // ===== Recovery =====
     /// Whether to enable file recovery (Emacs-style auto-save)
     /// When enabled, buffers are periodically saved to recovery files
@@ -612,6 +1006,35 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_save_interval_secs: u32,
 
+    /// Compress recovery files and saved sessions on disk.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub recovery_compression: bool,
+
+    /// Encrypt recovery files and saved sessions on disk.
+    /// The passphrase is read from the FRESH_RECOVERY_PASSPHRASE environment
+    /// variable, or from the output of `recovery_passphrase_command`.
+    /// If no passphrase is available, recovery is disabled.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub recovery_encryption: bool,
+
+    /// Shell command that prints the encryption passphrase, e.g. one that
+    /// reads it from the OS keychain.
+    /// Only used when `recovery_encryption` is enabled.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub recovery_passphrase_command: String,
+
+    /// Lock files while they have unsaved changes, and warn before editing a
+    /// file that another running Fresh has locked.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub file_locks: bool,
+
     /// Poll interval in milliseconds for auto-reverting open buffers.
     /// When auto-revert is enabled, file modification times are checked at this interval.
     /// Lower values detect external changes faster but use more CPU.
@@ -620,6 +1043,111 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_revert_poll_interval_ms: u64,
 
+    /// Check open files for external changes as soon as the terminal regains
+    /// focus, instead of waiting for the next poll.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub auto_revert_on_focus_gain: bool,
+
+    /// When a file changes on disk while its buffer has unsaved changes,
+    /// merge the changes on disk into the buffer. Lines changed in both are
+    /// kept in both versions between conflict markers.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Recovery"))]
+    pub auto_revert_merge: bool,
+
+    // ===== Auto Save =====
+    /// Automatically save modified files to disk (not just to recovery files)
+    /// after they have been idle for `file_auto_save_delay_ms`.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Auto Save"))]
+    pub file_auto_save: bool,
+
+    /// Idle time in milliseconds after the last edit before a file is auto-saved.
+    /// Default: 1000ms
+    #[serde(default = "default_file_auto_save_delay")]
+    #[schemars(extend("x-section" = "Auto Save"))]
+    pub file_auto_save_delay_ms: u64,
+
+    /// Also auto-save modified files when the terminal loses focus.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Auto Save"))]
+    pub file_auto_save_on_focus_loss: bool,
+
+    /// Glob patterns (gitignore syntax) of files that are never auto-saved,
+    /// e.g. `*.lock` or `/etc/**`.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Auto Save"))]
+    pub file_auto_save_exclude: Vec<String>,
+
+    /// Languages whose files are never auto-saved, e.g. `["markdown"]`.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Auto Save"))]
+    pub file_auto_save_exclude_languages: Vec<String>,
+
+    // ===== Backup =====
+    /// Whether to write a backup of a file before it is first saved in a session
+    /// (Emacs-style `file.txt~`). Independent of crash recovery.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Backup"))]
+    pub backup_enabled: bool,
+
+    /// Keep numbered backups (`file.txt.~1~`, `file.txt.~2~`, ...) instead of
+    /// a single `file.txt~`.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Backup"))]
+    pub backup_numbered: bool,
+
+    /// Directory for backup files. Empty means next to the original file.
+    /// In a backup directory, the file's full path is encoded into the name.
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Backup"))]
+    pub backup_directory: String,
+
+    /// Number of numbered backups to keep per file; older ones are deleted.
+    /// Set to 0 to keep all versions.
+    /// Default: 5
+    #[serde(default = "default_backup_keep_versions")]
+    #[schemars(extend("x-section" = "Backup"))]
+    pub backup_keep_versions: usize,
+
+    /// How files are written on save: "rename" writes a new file and renames
+    /// it over the original, "in_place" overwrites the original (keeping
+    /// hard links and the owner), "auto" renames unless the file has other
+    /// hard links or belongs to another user. Symlinks are always followed.
+    /// Default: "auto"
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Backup"))]
+    pub save_strategy: SaveStrategy,
+
+    // ===== Undo =====
+    /// Keep the undo history of files across editor restarts. It is written
+    /// to the data directory when a file is saved and restored when the file
+    /// is opened again, unless the file was changed outside the editor.
+    /// Default: false
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Undo"))]
+    pub persistent_undo: bool,
+
+    /// Largest undo history kept per file, in kilobytes. The oldest changes
+    /// are dropped to fit.
+    /// Default: 1024
+    #[serde(default = "default_persistent_undo_max_size_kb")]
+    #[schemars(extend("x-section" = "Undo"))]
+    pub persistent_undo_max_size_kb: usize,
+
+    /// Days an undo history is kept after the file was last saved.
+    /// Default: 30
+    #[serde(default = "default_persistent_undo_max_age_days")]
+    #[schemars(extend("x-section" = "Undo"))]
+    pub persistent_undo_max_age_days: u64,
+
     // ===== Keyboard =====
     /// Enable keyboard enhancement: disambiguate escape codes using CSI-u sequences.
     /// This allows unambiguous reading of Escape and modified keys.
@@ -697,6 +1225,38 @@ pub struct EditorConfig {
     #[serde(default = "default_file_tree_poll_interval")]
     #[schemars(extend("x-section" = "Performance"))]
     pub file_tree_poll_interval_ms: u64,
+
+    /// How long drawing a frame may take, in milliseconds (0 = no limit).
+    /// When frames keep taking longer, semantic highlighting, inlay hints and
+    /// the background fade are turned off one at a time until they don't, and
+    /// turned back on once frames are fast again.
+    /// Default: 16
+    #[serde(default = "default_frame_budget")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub frame_budget_ms: u64,
+
+    // ===== Plugins =====
+    /// Check what plugins do against their permissions (`plugins.<name>.permissions`),
+    /// asking before one reads or writes outside its own directory or runs a
+    /// process. Plugins that ship with Fresh are not checked.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Plugins"))]
+    pub plugin_sandbox: bool,
+
+    /// Memory all plugins together may use, in megabytes (0 = unlimited).
+    /// Takes effect on restart.
+    /// Default: 512
+    #[serde(default = "default_plugin_memory_limit")]
+    #[schemars(extend("x-section" = "Plugins"))]
+    pub plugin_memory_limit_mb: usize,
+
+    /// How long a plugin may run without returning to the editor, in
+    /// milliseconds (0 = unlimited). A plugin that runs longer is interrupted.
+    /// Default: 5000
+    #[serde(default = "default_plugin_time_slice")]
+    #[schemars(extend("x-section" = "Plugins"))]
+    pub plugin_time_slice_ms: u64,
 }
 
 fn default_tab_size() -> usize {
@@ -744,14 +1304,38 @@ fn default_estimated_line_length() -> usize {
     80
 }
 
+fn default_lsp_idle_timeout() -> u64 {
+    600 // 10 minutes
+}
+
 fn default_auto_save_interval() -> u32 {
     2 // Auto-save every 2 seconds for fast recovery
 }
 
+fn default_file_auto_save_delay() -> u64 {
+    1000 // 1 second of idle time before writing to disk
+}
+
+fn default_backup_keep_versions() -> usize {
+    5
+}
+
+fn default_persistent_undo_max_size_kb() -> usize {
+    1024
+}
+
+fn default_persistent_undo_max_age_days() -> u64 {
+    30
+}
+
 fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
 
+fn default_spell_language() -> String {
+    "en_US".to_string()
+}
+
 fn default_mouse_hover_delay() -> u64 {
     500 // 500ms delay before showing hover info
 }
@@ -760,6 +1344,14 @@ fn default_double_click_time() -> u64 {
     500 // 500ms window for detecting double-clicks
 }
 
+fn default_mouse_scroll_lines() -> usize {
+    3
+}
+
+fn default_mouse_scroll_columns() -> usize {
+    6
+}
+
 fn default_auto_revert_poll_interval() -> u64 {
     2000 // 2 seconds between file mtime checks
 }
@@ -768,13 +1360,39 @@ fn default_file_tree_poll_interval() -> u64 {
     3000 // 3 seconds between directory mtime checks
 }
 
+fn default_plugin_memory_limit() -> usize {
+    512
+}
+
+fn default_plugin_time_slice() -> u64 {
+    5000
+}
+
+fn default_frame_budget() -> u64 {
+    16
+}
+
+impl EditorConfig {
+    /// The line number mode to use, with `relative_line_numbers` selecting
+    /// hybrid numbering when no mode is set
+    pub fn effective_line_number_mode(&self) -> LineNumberMode {
+        match self.line_number_mode {
+            LineNumberMode::Absolute if self.relative_line_numbers => LineNumberMode::Hybrid,
+            mode => mode,
+        }
+    }
+}
+
 impl Default for EditorConfig {
     fn default() -> Self {
         Self {
             tab_size: default_tab_size(),
             auto_indent: true,
+            detect_indentation: true,
+            modelines: true,
             line_numbers: true,
             relative_line_numbers: false,
+            line_number_mode: LineNumberMode::default(),
             scroll_offset: default_scroll_offset(),
             syntax_highlighting: true,
             line_wrap: true,
@@ -784,20 +1402,63 @@ impl Default for EditorConfig {
             estimated_line_length: default_estimated_line_length(),
             enable_inlay_hints: true,
             enable_semantic_tokens_full: false,
+            lsp_idle_timeout_secs: default_lsp_idle_timeout(),
+            spell_check: false,
+            spell_language: default_spell_language(),
             recovery_enabled: true,
             auto_save_interval_secs: default_auto_save_interval(),
+            recovery_compression: false,
+            recovery_encryption: false,
+            recovery_passphrase_command: String::new(),
+            file_locks: true,
             highlight_context_bytes: default_highlight_context_bytes(),
             mouse_hover_enabled: true,
             mouse_hover_delay_ms: default_mouse_hover_delay(),
             double_click_time_ms: default_double_click_time(),
+            mouse_scroll_lines: default_mouse_scroll_lines(),
+            mouse_scroll_columns: default_mouse_scroll_columns(),
+            smooth_scrolling: false,
+            reduced_motion: false,
+            high_contrast: false,
+            large_hit_targets: false,
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
+            auto_revert_on_focus_gain: true,
+            auto_revert_merge: false,
+            file_auto_save: false,
+            file_auto_save_delay_ms: default_file_auto_save_delay(),
+            file_auto_save_on_focus_loss: true,
+            file_auto_save_exclude: Vec::new(),
+            file_auto_save_exclude_languages: Vec::new(),
+            backup_enabled: false,
+            backup_numbered: false,
+            backup_directory: String::new(),
+            backup_keep_versions: default_backup_keep_versions(),
+            save_strategy: SaveStrategy::default(),
+            persistent_undo: false,
+            persistent_undo_max_size_kb: default_persistent_undo_max_size_kb(),
+            persistent_undo_max_age_days: default_persistent_undo_max_age_days(),
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
+            frame_budget_ms: default_frame_budget(),
+            plugin_sandbox: true,
+            plugin_memory_limit_mb: default_plugin_memory_limit(),
+            plugin_time_slice_ms: default_plugin_time_slice(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            file_templates: Vec::new(),
+            file_template_author: String::new(),
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
+            rulers: Vec::new(),
+            show_trailing_whitespace: false,
+            show_mixed_indentation: false,
+            max_line_length: None,
+            line_length_diagnostics: false,
+            render_whitespace: RenderWhitespace::default(),
+            highlight_current_line: false,
+            highlight_current_column: false,
+            bidi_text: false,
             keyboard_disambiguate_escape_codes: true,
             keyboard_report_event_types: false,
             keyboard_report_alternate_keys: true,
@@ -806,9 +1467,14 @@ impl Default for EditorConfig {
             quick_suggestions_delay_ms: default_quick_suggestions_delay(),
             suggest_on_trigger_characters: true,
             accept_suggestion_on_enter: default_accept_suggestion_on_enter(),
+            word_completion: true,
             show_menu_bar: true,
             show_tab_bar: true,
+            show_breadcrumbs: false,
             use_terminal_bg: false,
+            synchronized_output: true,
+            color_swatches: true,
+            underline_links: true,
         }
     }
 }
@@ -897,7 +1563,7 @@ impl Default for PackagesConfig {
 }
 
 // Re-export PluginConfig from fresh-core for shared type usage
-pub use fresh_core::config::PluginConfig;
+pub use fresh_core::config::{PermissionMode, PluginConfig, PluginPermissions};
 
 impl Default for FileExplorerConfig {
     fn default() -> Self {
@@ -1028,6 +1694,54 @@ fn default_on_save_timeout() -> u64 {
     10000
 }
 
+/// A conceal rule: matching text is rendered as `replacement` instead.
+///
+/// The buffer content is unchanged and lines containing a cursor are always
+/// shown as written. Set either `pattern` or `query`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/pattern"))]
+pub struct ConcealRule {
+    /// Regular expression matched within each line.
+    /// If it has capture groups, only the groups are concealed
+    /// (e.g. `(\*\*)[^*]+(\*\*)` hides the asterisks around bold text)
+    #[serde(default)]
+    pub pattern: Option<String>,
+
+    /// Tree-sitter query for the language's grammar; every captured node is concealed
+    /// (e.g. `(atx_h1_marker) @conceal`)
+    #[serde(default)]
+    pub query: Option<String>,
+
+    /// Text shown in place of the concealed text (empty hides it)
+    #[serde(default)]
+    pub replacement: String,
+}
+
+/// A template for new files. The first template matching a new file's path
+/// or language is preselected; its content is inserted into the buffer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[schemars(extend("x-display-field" = "/name"))]
+pub struct FileTemplate {
+    /// Name shown when choosing a template
+    pub name: String,
+
+    /// Glob patterns (gitignore syntax) of the paths the template is for,
+    /// e.g. `*.rs` or `src/components/*.tsx`
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Language the template is for, e.g. `rust`.
+    /// A template without patterns or language is offered for every file.
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// Content in snippet syntax: `$0` places the cursor, and `${FILENAME}`,
+    /// `${FILENAME_BASE}`, `${DIRECTORY}`, `${DATE}`, `${YEAR}` and `${AUTHOR}`
+    /// are replaced by their values
+    #[serde(default)]
+    pub content: String,
+}
+
 /// Language-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(extend("x-display-field" = "/grammar"))]
@@ -1048,6 +1762,15 @@ pub struct LanguageConfig {
     #[serde(default)]
     pub comment_prefix: Option<String>,
 
+    /// Block comment start token (e.g., "/*" or "<!--")
+    /// Used to toggle comments in languages without a line comment prefix
+    #[serde(default)]
+    pub block_comment_start: Option<String>,
+
+    /// Block comment end token (e.g., "*/" or "-->")
+    #[serde(default)]
+    pub block_comment_end: Option<String>,
+
     /// Whether to auto-indent
     #[serde(default = "default_true")]
     pub auto_indent: bool,
@@ -1090,6 +1813,62 @@ pub struct LanguageConfig {
     /// Note: Use `formatter` + `format_on_save` for formatting, not on_save
     #[serde(default)]
     pub on_save: Vec<OnSaveAction>,
+
+    /// Rules that display matching text differently without changing it
+    /// (e.g. hide the `**` around bold text, or show `->` as `→`)
+    #[serde(default)]
+    pub conceal: Vec<ConcealRule>,
+
+    /// Command that evaluates code read from stdin (e.g. "python3"), used by
+    /// Send to REPL. Runs through the shell once per evaluation.
+    #[serde(default)]
+    pub repl: Option<String>,
+
+    /// Columns at which to draw vertical rulers for this language
+    /// (e.g. [72] for commit messages). Overrides `editor.rulers` when set.
+    #[serde(default)]
+    pub rulers: Option<Vec<usize>>,
+
+    /// Column past which characters are highlighted as too long for this
+    /// language. Overrides `editor.max_line_length` when set.
+    #[serde(default)]
+    pub max_line_length: Option<usize>,
+
+    /// Regex for lines after which the next line is indented one more level
+    /// (e.g. `\b(then|do)\s*$`). Used when the language has no tree-sitter
+    /// indent query, along with the brackets.
+    #[serde(default)]
+    pub increase_indent_pattern: Option<String>,
+
+    /// Regex for lines indented one level less than the line before them
+    /// (e.g. `^\s*(fi|done)\b`). The line is dedented as it's typed.
+    #[serde(default)]
+    pub decrease_indent_pattern: Option<String>,
+
+    /// Regex for lines continued on the next line (e.g. `\\$`). Continuation
+    /// lines are indented one level more than the line starting the statement.
+    #[serde(default)]
+    pub continuation_pattern: Option<String>,
+}
+
+impl LanguageConfig {
+    /// The heuristic indent patterns of this language
+    pub fn indent_patterns(&self) -> IndentPatterns {
+        IndentPatterns {
+            increase: self.increase_indent_pattern.clone(),
+            decrease: self.decrease_indent_pattern.clone(),
+            continuation: self.continuation_pattern.clone(),
+        }
+    }
+}
+
+/// Indent patterns of a language without a tree-sitter indent query (see
+/// `primitives::indent_rules`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndentPatterns {
+    pub increase: Option<String>,
+    pub decrease: Option<String>,
+    pub continuation: Option<String>,
 }
 
 /// Resolved editor configuration for a specific buffer.
@@ -1126,6 +1905,25 @@ pub struct BufferConfig {
 
     /// Path to custom TextMate grammar (if any)
     pub textmate_grammar: Option<std::path::PathBuf>,
+
+    /// Line comment prefix
+    pub comment_prefix: Option<String>,
+
+    /// Block comment start and end tokens
+    pub block_comment: Option<(String, String)>,
+
+    /// Conceal rules
+    pub conceal: Vec<ConcealRule>,
+
+    /// Columns at which to draw vertical rulers
+    pub rulers: Vec<usize>,
+
+    /// Column past which characters are highlighted as too long
+    pub max_line_length: Option<usize>,
+
+    /// Heuristic indent patterns, or `None` for buffers without a
+    /// configured language
+    pub indent_patterns: Option<IndentPatterns>,
 }
 
 impl BufferConfig {
@@ -1151,6 +1949,12 @@ impl BufferConfig {
             on_save: Vec::new(),
             highlighter: HighlighterPreference::Auto,
             textmate_grammar: None,
+            comment_prefix: None,
+            block_comment: None,
+            conceal: Vec::new(),
+            rulers: editor.rulers.clone(),
+            max_line_length: editor.max_line_length,
+            indent_patterns: None,
         };
 
         // Apply language-specific overrides if available
@@ -1184,6 +1988,29 @@ impl BufferConfig {
 
                 // TextMate grammar path: from language config
                 config.textmate_grammar = lang_config.textmate_grammar.clone();
+
+                // Comment tokens: from language config (block comments need both tokens)
+                config.comment_prefix = lang_config.comment_prefix.clone();
+                config.block_comment = lang_config
+                    .block_comment_start
+                    .clone()
+                    .zip(lang_config.block_comment_end.clone());
+
+                // Conceal rules: from language config
+                config.conceal = lang_config.conceal.clone();
+
+                // Rulers: language setting if specified, else global
+                if let Some(rulers) = &lang_config.rulers {
+                    config.rulers = rulers.clone();
+                }
+
+                // Line length limit: language setting if specified, else global
+                if lang_config.max_line_length.is_some() {
+                    config.max_line_length = lang_config.max_line_length;
+                }
+
+                // Indent patterns: from language config
+                config.indent_patterns = Some(lang_config.indent_patterns());
             }
         }
 
@@ -1318,6 +2145,7 @@ impl Default for Config {
         Self {
             version: 0,
             theme: default_theme_name(),
+            appearance: AppearanceConfig::default(),
             locale: LocaleName::default(),
             check_for_updates: true,
             editor: EditorConfig::default(),
@@ -1397,6 +2225,27 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.file.close_other_buffers").to_string(),
+                        action: "close_other_buffers".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.file.close_saved_buffers").to_string(),
+                        action: "close_saved_buffers".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.file.close_all_buffers").to_string(),
+                        action: "close_all_buffers".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.file.switch_project").to_string(),
@@ -1405,6 +2254,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.file.add_workspace_folder").to_string(),
+                        action: "add_workspace_folder".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.file.quit").to_string(),
                         action: "quit".to_string(),
@@ -1453,6 +2309,13 @@ impl MenuConfig {
                         label: t!("menu.edit.copy_with_formatting").to_string(),
                         source: "copy_with_theme".to_string(),
                     },
+                    MenuItem::Action {
+                        label: t!("menu.edit.copy_as_ansi").to_string(),
+                        action: "copy_as_ansi".to_string(),
+                        args: HashMap::new(),
+                        when: Some(context_keys::HAS_SELECTION.to_string()),
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.edit.paste").to_string(),
                         action: "paste".to_string(),
@@ -1834,6 +2697,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.go.switch_buffer").to_string(),
+                        action: "switch_buffer_mru".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.go.command_palette").to_string(),
@@ -2016,6 +2886,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.help.tutorial").to_string(),
+                        action: "show_tutorial".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.help.keyboard_shortcuts").to_string(),
                         action: "keyboard_shortcuts".to_string(),
@@ -2023,6 +2900,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.help.cheat_sheet").to_string(),
+                        action: "toggle_cheat_sheet".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Action {
                         label: t!("menu.help.event_debug").to_string(),
@@ -2056,8 +2940,10 @@ impl Config {
             .map_err(|e| ConfigError::IoError(e.to_string()))?;
 
         // Deserialize as PartialConfig first, then resolve with defaults
+        let value = crate::config_edit::parse(&contents)
+            .map_err(|e| ConfigError::ParseError(e.to_string()))?;
         let partial: crate::partial_config::PartialConfig =
-            serde_json::from_str(&contents).map_err(|e| ConfigError::ParseError(e.to_string()))?;
+            serde_json::from_value(value).map_err(|e| ConfigError::ParseError(e.to_string()))?;
 
         Ok(partial.resolve())
     }
@@ -2138,6 +3024,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "rust".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2152,6 +3040,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2162,6 +3057,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "javascript".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2176,6 +3073,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: Some("node".to_string()),
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2186,6 +3090,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "typescript".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2200,6 +3106,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2210,6 +3123,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "python".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2228,6 +3143,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: Some("python3".to_string()),
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2238,6 +3160,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "c".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2252,6 +3176,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2269,6 +3200,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "cpp".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2283,6 +3216,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2293,6 +3233,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "c_sharp".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2302,6 +3244,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2327,6 +3276,8 @@ impl Config {
                 ],
                 grammar: "bash".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2336,6 +3287,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: Some("bash".to_string()),
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2350,6 +3308,8 @@ impl Config {
                 ],
                 grammar: "make".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2359,6 +3319,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: Some(r"^[^\s#:=][^:=]*::?([^=].*)?$".to_string()),
+                decrease_indent_pattern: None,
+                continuation_pattern: Some(r"\\$".to_string()),
             },
         );
 
@@ -2369,6 +3336,8 @@ impl Config {
                 filenames: vec!["Dockerfile".to_string(), "Containerfile".to_string()],
                 grammar: "dockerfile".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2378,6 +3347,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: Some(r"\\$".to_string()),
             },
         );
 
@@ -2388,6 +3364,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "json".to_string(),
                 comment_prefix: None,
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2402,6 +3380,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2412,6 +3397,8 @@ impl Config {
                 filenames: vec!["Cargo.lock".to_string()],
                 grammar: "toml".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2421,6 +3408,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2431,6 +3425,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "yaml".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2445,6 +3441,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2455,6 +3458,8 @@ impl Config {
                 filenames: vec!["README".to_string()],
                 grammar: "markdown".to_string(),
                 comment_prefix: None,
+                block_comment_start: Some("<!--".to_string()),
+                block_comment_end: Some("-->".to_string()),
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2464,6 +3469,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2475,6 +3487,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "go".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2489,6 +3503,13 @@ impl Config {
                 }),
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2499,6 +3520,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "odin".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2508,6 +3531,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2518,6 +3548,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "zig".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2527,6 +3559,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2537,6 +3576,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "java".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2546,6 +3587,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2563,6 +3611,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "latex".to_string(),
                 comment_prefix: Some("%".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2572,6 +3622,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: Some(r"^\s*\\begin\{".to_string()),
+                decrease_indent_pattern: Some(r"^\s*\\end\{".to_string()),
+                continuation_pattern: None,
             },
         );
 
@@ -2582,6 +3639,8 @@ impl Config {
                 filenames: vec![],
                 grammar: "go".to_string(), // Templ uses Go-like syntax
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2591,6 +3650,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2602,6 +3668,8 @@ impl Config {
                 filenames: vec!["git-rebase-todo".to_string()],
                 grammar: "Git Rebase Todo".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2611,6 +3679,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2626,6 +3701,8 @@ impl Config {
                 ],
                 grammar: "Git Commit Message".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2635,6 +3712,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2650,6 +3734,8 @@ impl Config {
                 ],
                 grammar: "Gitignore".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2659,6 +3745,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2669,6 +3762,8 @@ impl Config {
                 filenames: vec![".gitconfig".to_string(), ".gitmodules".to_string()],
                 grammar: "Git Config".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2678,6 +3773,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -2688,6 +3790,8 @@ impl Config {
                 filenames: vec![".gitattributes".to_string()],
                 grammar: "Git Attributes".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: false,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -2697,6 +3801,13 @@ impl Config {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -3262,6 +4373,8 @@ mod tests {
                 filenames: vec![],
                 grammar: "go".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: Some("/*".to_string()),
+                block_comment_end: Some("*/".to_string()),
                 auto_indent: true,
                 highlighter: HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -3276,6 +4389,13 @@ mod tests {
                 }),
                 format_on_save: true,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
@@ -3287,6 +4407,11 @@ mod tests {
         assert!(buffer_config.format_on_save);
         assert!(buffer_config.formatter.is_some());
         assert_eq!(buffer_config.formatter.as_ref().unwrap().command, "gofmt");
+        assert_eq!(buffer_config.comment_prefix.as_deref(), Some("//"));
+        assert_eq!(
+            buffer_config.block_comment,
+            Some(("/*".to_string(), "*/".to_string()))
+        );
     }
 
     #[test]
@@ -3299,6 +4424,25 @@ mod tests {
         assert!(!buffer_config.use_tabs);
     }
 
+    #[test]
+    fn test_buffer_config_rulers() {
+        let mut config = Config::default();
+        config.editor.rulers = vec![80, 120];
+        config.languages.insert(
+            "gitcommit".to_string(),
+            LanguageConfig {
+                rulers: Some(vec![72]),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(BufferConfig::resolve(&config, None).rulers, vec![80, 120]);
+        assert_eq!(
+            BufferConfig::resolve(&config, Some("gitcommit")).rulers,
+            vec![72]
+        );
+    }
+
     #[test]
     fn test_buffer_config_indent_string() {
         let config = Config::default();
@@ -0,0 +1,598 @@
+//! Format-preserving edits to JSON config files.
+//!
+//! Config files may contain comments (`//` and `/* */`) and trailing commas.
+//! When settings are saved, the original text is edited in place rather than
+//! re-serialized, so comments, key order and formatting are kept and only the
+//! keys that actually changed are touched.
+
+use serde_json::Value;
+use std::ops::Range;
+
+/// Parse config file text, allowing comments and trailing commas.
+pub fn parse(text: &str) -> Result<Value, serde_json::Error> {
+    serde_json::from_str(&strip_comments(text))
+}
+
+/// Replace comments and trailing commas with spaces so the text parses as
+/// strict JSON. Byte offsets (and line numbers in errors) are unchanged.
+pub fn strip_comments(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = bytes.to_vec();
+    // Comma not (yet) followed by another value
+    let mut pending_comma = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i = string_end(bytes, i);
+                pending_comma = None;
+            }
+            b'/' if matches!(bytes.get(i + 1), Some(b'/') | Some(b'*')) => {
+                let end = comment_end(bytes, i);
+                for b in &mut out[i..end] {
+                    if *b != b'\n' {
+                        *b = b' ';
+                    }
+                }
+                i = end;
+            }
+            b',' => {
+                pending_comma = Some(i);
+                i += 1;
+            }
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                }
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                pending_comma = None;
+                i += 1;
+            }
+        }
+    }
+    // Only whole characters are blanked, so this is still valid UTF-8
+    String::from_utf8(out).unwrap_or_else(|_| text.to_string())
+}
+
+/// A JSON config file that can be edited without losing comments or formatting.
+#[derive(Debug, Clone)]
+pub struct JsonDocument {
+    text: String,
+}
+
+/// An object member located in the document text
+#[derive(Debug)]
+struct Member {
+    key: String,
+    key_start: usize,
+    value: Range<usize>,
+    /// Position of the comma after the value, if any
+    comma: Option<usize>,
+}
+
+impl Default for JsonDocument {
+    fn default() -> Self {
+        Self {
+            text: "{}".to_string(),
+        }
+    }
+}
+
+impl JsonDocument {
+    /// Parse a document. The top-level value must be an object; a file with
+    /// only whitespace or comments is treated as an empty object.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        if strip_comments(text).trim().is_empty() {
+            let mut text = text.to_string();
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str("{}");
+            return Ok(Self { text });
+        }
+        match parse(text) {
+            Ok(Value::Object(_)) => Ok(Self {
+                text: text.to_string(),
+            }),
+            Ok(_) => Err("config must be a JSON object".to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The document text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The parsed value of the document
+    pub fn value(&self) -> Value {
+        parse(&self.text).unwrap_or_else(|_| Value::Object(Default::default()))
+    }
+
+    /// Set the value at a JSON pointer (e.g. `/editor/tab_size`), creating
+    /// intermediate objects as needed.
+    pub fn set(&mut self, pointer: &str, value: &Value) {
+        let parts = pointer_parts(pointer);
+        if parts.is_empty() {
+            return;
+        }
+
+        let mut open = self.root_open();
+        for (depth, part) in parts.iter().enumerate() {
+            let members = self.members(open);
+            let is_last = depth == parts.len() - 1;
+            let nested = || nest(&parts[depth + 1..], value.clone());
+            match members.iter().find(|m| m.key == *part) {
+                Some(m) if !is_last && self.text.as_bytes()[m.value.start] == b'{' => {
+                    open = m.value.start;
+                }
+                Some(m) => {
+                    let rendered = if self.is_multiline(open, &members) {
+                        self.render(&nested(), &line_indent(&self.text, m.key_start))
+                    } else {
+                        nested().to_string()
+                    };
+                    self.text.replace_range(m.value.clone(), &rendered);
+                    return;
+                }
+                None => {
+                    self.insert_member(open, &members, part, &nested());
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Remove the value at a JSON pointer, along with its line and any comment
+    /// on the same line. Objects left empty by the removal are removed too.
+    /// Returns false if there was nothing to remove.
+    pub fn remove(&mut self, pointer: &str) -> bool {
+        let parts = pointer_parts(pointer);
+        let Some((key, parents)) = parts.split_last() else {
+            return false;
+        };
+
+        let mut open = self.root_open();
+        for part in parents {
+            let members = self.members(open);
+            match members.iter().find(|m| m.key == *part) {
+                Some(m) if self.text.as_bytes()[m.value.start] == b'{' => open = m.value.start,
+                _ => return false,
+            }
+        }
+
+        let members = self.members(open);
+        let Some(idx) = members.iter().position(|m| m.key == *key) else {
+            return false;
+        };
+        let member = &members[idx];
+        let bytes = self.text.as_bytes();
+        let is_last = idx == members.len() - 1;
+        let prev = idx.checked_sub(1).map(|i| &members[i]);
+
+        let line_start = line_start(&self.text, member.key_start);
+        let own_line = self.text[line_start..member.key_start].trim().is_empty();
+        let mut start = member.key_start;
+        let mut end = member.comma.map_or(member.value.end, |c| c + 1);
+        // Comma of the previous member that would be left trailing
+        let mut dangling_comma = None;
+
+        if own_line {
+            let eol = same_line_end(bytes, end);
+            if bytes.get(eol) == Some(&b'\n') {
+                start = line_start;
+                end = eol + 1;
+            }
+            if is_last && member.comma.is_none() {
+                dangling_comma = prev.and_then(|p| p.comma);
+            }
+        } else if is_last && member.comma.is_none() {
+            // `{"a": 1, "b": 2}`: remove `, "b": 2`
+            if let Some(comma) = prev.and_then(|p| p.comma) {
+                start = comma;
+            }
+        } else {
+            end = skip_inline_space(bytes, end);
+        }
+
+        self.text.replace_range(start..end, "");
+        if let Some(comma) = dangling_comma {
+            self.text.remove(comma);
+        }
+
+        if members.len() == 1 && !parents.is_empty() {
+            let parent_pointer: String = parents
+                .iter()
+                .map(|p| format!("/{}", escape_pointer_part(p)))
+                .collect();
+            self.remove(&parent_pointer);
+        }
+        true
+    }
+
+    /// Like [`set`](Self::set), but when both the current and the new value
+    /// are objects, only the members that differ are edited.
+    pub fn update(&mut self, pointer: &str, value: &Value) {
+        let current = self.value();
+        match (current.pointer(pointer), value) {
+            (Some(Value::Object(old)), Value::Object(new)) if !new.is_empty() => {
+                self.update_object(pointer, old, new)
+            }
+            (Some(old), new) if old == new => {}
+            _ => self.set(pointer, value),
+        }
+    }
+
+    /// Make the minimal edits needed for the document to equal `target`.
+    pub fn update_to(&mut self, target: &Value) {
+        if let (Value::Object(current), Value::Object(target)) = (self.value(), target) {
+            self.update_object("", &current, target);
+        }
+    }
+
+    fn update_object(
+        &mut self,
+        prefix: &str,
+        current: &serde_json::Map<String, Value>,
+        target: &serde_json::Map<String, Value>,
+    ) {
+        for key in current.keys() {
+            if !target.contains_key(key) {
+                self.remove(&format!("{}/{}", prefix, escape_pointer_part(key)));
+            }
+        }
+        for (key, new) in target {
+            let pointer = format!("{}/{}", prefix, escape_pointer_part(key));
+            match (current.get(key), new) {
+                (Some(Value::Object(old)), Value::Object(new)) if !new.is_empty() => {
+                    self.update_object(&pointer, old, new)
+                }
+                (Some(old), new) if old == new => {}
+                _ => self.set(&pointer, new),
+            }
+        }
+    }
+
+    /// Position of the root object's opening brace
+    fn root_open(&self) -> usize {
+        skip_trivia(self.text.as_bytes(), 0)
+    }
+
+    /// Members of the object whose opening brace is at `open`
+    fn members(&self, open: usize) -> Vec<Member> {
+        let bytes = self.text.as_bytes();
+        let mut members = Vec::new();
+        let mut i = skip_trivia(bytes, open + 1);
+        while bytes.get(i) == Some(&b'"') {
+            let key_start = i;
+            let key_end = string_end(bytes, i);
+            let key = serde_json::from_str(&self.text[key_start..key_end]).unwrap_or_default();
+            i = skip_trivia(bytes, key_end);
+            if bytes.get(i) != Some(&b':') {
+                break;
+            }
+            let value_start = skip_trivia(bytes, i + 1);
+            let value_end = value_end(bytes, value_start);
+            i = skip_trivia(bytes, value_end);
+            let comma = (bytes.get(i) == Some(&b',')).then_some(i);
+            if comma.is_some() {
+                i = skip_trivia(bytes, i + 1);
+            }
+            members.push(Member {
+                key,
+                key_start,
+                value: value_start..value_end,
+                comma,
+            });
+        }
+        members
+    }
+
+    /// Whether the object at `open` has one member per line
+    fn is_multiline(&self, open: usize, members: &[Member]) -> bool {
+        match members.first() {
+            Some(first) => self.text[open..first.key_start].contains('\n'),
+            None => true,
+        }
+    }
+
+    /// Indentation step used by the document (defaults to two spaces)
+    fn indent_unit(&self) -> String {
+        let open = self.root_open();
+        let members = self.members(open);
+        match members.first() {
+            Some(first) if self.is_multiline(open, &members) => {
+                let indent = line_indent(&self.text, first.key_start);
+                if indent.is_empty() {
+                    "  ".to_string()
+                } else {
+                    indent
+                }
+            }
+            _ => "  ".to_string(),
+        }
+    }
+
+    /// Pretty-print a value for a member at the given indentation
+    fn render(&self, value: &Value, indent: &str) -> String {
+        let unit = self.indent_unit();
+        let mut buf = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(unit.as_bytes());
+        let mut ser = serde_json::Serializer::with_formatter(&mut buf, formatter);
+        if serde::Serialize::serialize(value, &mut ser).is_err() {
+            return value.to_string();
+        }
+        String::from_utf8_lossy(&buf).replace('\n', &format!("\n{}", indent))
+    }
+
+    /// Add a member at the end of the object at `open`
+    fn insert_member(&mut self, open: usize, members: &[Member], key: &str, value: &Value) {
+        let key_json = Value::String(key.to_string()).to_string();
+
+        let Some(last) = members.last() else {
+            let close = value_end(self.text.as_bytes(), open) - 1;
+            let outer = line_indent(&self.text, open);
+            let indent = format!("{}{}", outer, self.indent_unit());
+            let entry = format!("\n{}{}: {}", indent, key_json, self.render(value, &indent));
+            // Keep any comments inside the empty object before the new member
+            let content_end = open + 1 + self.text[open + 1..close].trim_end().len();
+            let tail = if self.text[content_end..close].contains('\n') {
+                self.text[content_end..close].to_string()
+            } else {
+                format!("\n{}", outer)
+            };
+            self.text
+                .replace_range(content_end..close, &format!("{}{}", entry, tail));
+            return;
+        };
+
+        if !self.is_multiline(open, members) {
+            let entry = format!("{}: {}", key_json, value);
+            match last.comma {
+                Some(comma) => self.text.insert_str(comma + 1, &format!(" {},", entry)),
+                None => self
+                    .text
+                    .insert_str(last.value.end, &format!(", {}", entry)),
+            }
+            return;
+        }
+
+        let indent = line_indent(&self.text, members[0].key_start);
+        let entry = format!("\n{}{}: {}", indent, key_json, self.render(value, &indent));
+        let after = last.comma.map_or(last.value.end, |c| c + 1);
+        // Insert after any comment on the last member's line
+        let pos = same_line_end(self.text.as_bytes(), after);
+        self.text.insert_str(pos, &entry);
+        if last.comma.is_none() {
+            self.text.insert(last.value.end, ',');
+        }
+    }
+}
+
+/// Split a JSON pointer into unescaped reference tokens
+fn pointer_parts(pointer: &str) -> Vec<String> {
+    let trimmed = pointer.trim_start_matches('/');
+    if trimmed.is_empty() {
+        return Vec::new();
+    }
+    trimmed
+        .split('/')
+        .map(|p| p.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+fn escape_pointer_part(part: &str) -> String {
+    part.replace('~', "~0").replace('/', "~1")
+}
+
+/// Wrap `value` in nested objects keyed by `parts`
+fn nest(parts: &[String], value: Value) -> Value {
+    parts.iter().rev().fold(value, |inner, key| {
+        let mut map = serde_json::Map::new();
+        map.insert(key.clone(), inner);
+        Value::Object(map)
+    })
+}
+
+/// End of the string literal starting at `start` (just past the closing quote)
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// End of the comment starting at `start` (a line comment ends before the newline)
+fn comment_end(bytes: &[u8], start: usize) -> usize {
+    if bytes.get(start + 1) == Some(&b'/') {
+        return (start..bytes.len())
+            .find(|&i| bytes[i] == b'\n')
+            .unwrap_or(bytes.len());
+    }
+    (start + 2..bytes.len().saturating_sub(1))
+        .find(|&i| bytes[i] == b'*' && bytes[i + 1] == b'/')
+        .map_or(bytes.len(), |i| i + 2)
+}
+
+fn is_comment_start(bytes: &[u8], i: usize) -> bool {
+    bytes[i] == b'/' && matches!(bytes.get(i + 1), Some(b'/') | Some(b'*'))
+}
+
+/// Skip whitespace and comments
+fn skip_trivia(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+        } else if is_comment_start(bytes, i) {
+            i = comment_end(bytes, i);
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Skip spaces and tabs
+fn skip_inline_space(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && matches!(bytes[i], b' ' | b'\t') {
+        i += 1;
+    }
+    i
+}
+
+/// Skip spaces, tabs and comments on the current line, stopping at the newline
+fn same_line_end(bytes: &[u8], mut i: usize) -> usize {
+    loop {
+        i = skip_inline_space(bytes, i);
+        if i < bytes.len() && is_comment_start(bytes, i) {
+            let end = comment_end(bytes, i);
+            if bytes[i..end].contains(&b'\n') {
+                return i;
+            }
+            i = end;
+        } else {
+            return i;
+        }
+    }
+}
+
+/// End of the value starting at `start`
+fn value_end(bytes: &[u8], start: usize) -> usize {
+    match bytes.get(start) {
+        Some(b'"') => string_end(bytes, start),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i);
+                        continue;
+                    }
+                    _ if is_comment_start(bytes, i) => {
+                        i = comment_end(bytes, i);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut i = start;
+            while i < bytes.len()
+                && !bytes[i].is_ascii_whitespace()
+                && !matches!(bytes[i], b',' | b'}' | b']')
+                && !is_comment_start(bytes, i)
+            {
+                i += 1;
+            }
+            i
+        }
+    }
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Leading whitespace of the line containing `pos`
+fn line_indent(text: &str, pos: usize) -> String {
+    let start = line_start(text, pos);
+    text[start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const COMMENTED: &str = r#"{
+    // Appearance
+    "theme": "dark", // my favourite
+    "editor": {
+        "tab_size": 4,
+        /* keep this */
+        "line_numbers": true,
+    }
+}
+"#;
+
+    #[test]
+    fn parse_allows_comments_and_trailing_commas() {
+        let value = parse(COMMENTED).unwrap();
+        assert_eq!(value["theme"], json!("dark"));
+        assert_eq!(value["editor"]["line_numbers"], json!(true));
+        assert!(parse(r#"{"url": "http://example.com"}"#).is_ok());
+    }
+
+    #[test]
+    fn set_replaces_only_the_changed_value() {
+        let mut doc = JsonDocument::parse(COMMENTED).unwrap();
+        doc.set("/editor/tab_size", &json!(2));
+        assert_eq!(
+            doc.as_str(),
+            COMMENTED.replace("\"tab_size\": 4", "\"tab_size\": 2")
+        );
+    }
+
+    #[test]
+    fn set_inserts_new_keys_with_file_indentation() {
+        let mut doc = JsonDocument::parse(COMMENTED).unwrap();
+        doc.set("/editor/auto_indent", &json!(false));
+        doc.set("/terminal/shell", &json!("zsh"));
+
+        let text = doc.as_str();
+        assert!(text.contains("// my favourite"));
+        assert!(text.contains("/* keep this */"));
+        assert!(text.contains("\n        \"auto_indent\": false"));
+        assert!(text.contains("\n    \"terminal\": {\n        \"shell\": \"zsh\"\n    }"));
+        assert_eq!(doc.value()["editor"]["tab_size"], json!(4));
+        assert_eq!(doc.value()["terminal"]["shell"], json!("zsh"));
+    }
+
+    #[test]
+    fn remove_drops_line_and_empty_parents() {
+        let mut doc = JsonDocument::parse(COMMENTED).unwrap();
+        assert!(doc.remove("/theme"));
+        assert!(!doc.as_str().contains("my favourite"));
+        assert!(doc.as_str().contains("// Appearance"));
+
+        assert!(doc.remove("/editor/tab_size"));
+        assert!(doc.remove("/editor/line_numbers"));
+        assert!(!doc.remove("/editor/line_numbers"));
+        assert_eq!(doc.value(), json!({}));
+        assert!(doc.as_str().contains("// Appearance"));
+    }
+
+    #[test]
+    fn update_to_matches_pretty_output_for_new_files() {
+        let target = json!({"editor": {"tab_size": 2}, "theme": "light"});
+        let mut doc = JsonDocument::default();
+        doc.update_to(&target);
+        assert_eq!(doc.as_str(), serde_json::to_string_pretty(&target).unwrap());
+
+        let mut doc = JsonDocument::parse(r#"{"a": 1, "b": 2}"#).unwrap();
+        doc.update_to(&json!({"a": 1, "c": 3}));
+        assert_eq!(doc.as_str(), r#"{"a": 1, "c": 3}"#);
+    }
+}
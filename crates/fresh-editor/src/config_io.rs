@@ -5,6 +5,7 @@
 //! These are separated from config.rs to allow schema-only builds.
 
 use crate::config::{Config, ConfigError};
+use crate::config_edit::{self, JsonDocument};
 use crate::partial_config::{Merge, PartialConfig, SessionConfig};
 use serde_json::Value;
 use std::path::{Path, PathBuf};
@@ -68,67 +69,18 @@ fn strip_empty_defaults(value: Value) -> Option<Value> {
     }
 }
 
-/// Set a value at a JSON pointer path, creating intermediate objects as needed.
-/// The pointer should be in JSON Pointer format (e.g., "/editor/tab_size").
-fn set_json_pointer(root: &mut Value, pointer: &str, value: Value) {
-    if pointer.is_empty() || pointer == "/" {
-        *root = value;
-        return;
-    }
-
-    let parts: Vec<&str> = pointer.trim_start_matches('/').split('/').collect();
-
-    let mut current = root;
-    for (i, part) in parts.iter().enumerate() {
-        if i == parts.len() - 1 {
-            // Last part - set the value
-            if let Value::Object(map) = current {
-                map.insert(part.to_string(), value);
-            }
-            return;
-        }
-
-        // Intermediate part - ensure it exists as an object
-        if let Value::Object(map) = current {
-            if !map.contains_key(*part) {
-                map.insert(part.to_string(), Value::Object(Default::default()));
-            }
-            current = map.get_mut(*part).unwrap();
-        } else {
-            return; // Can't traverse non-object
-        }
-    }
-}
-
-/// Remove a value at a JSON pointer path.
-fn remove_json_pointer(root: &mut Value, pointer: &str) {
-    if pointer.is_empty() || pointer == "/" {
-        return;
-    }
-
-    let parts: Vec<&str> = pointer.trim_start_matches('/').split('/').collect();
-
-    let mut current = root;
-    for (i, part) in parts.iter().enumerate() {
-        if i == parts.len() - 1 {
-            // Last part - remove the key
-            if let Value::Object(map) = current {
-                map.remove(*part);
-            }
-            return;
-        }
-
-        // Intermediate part - traverse
-        if let Value::Object(map) = current {
-            if let Some(next) = map.get_mut(*part) {
-                current = next;
-            } else {
-                return; // Path doesn't exist
-            }
-        } else {
-            return; // Can't traverse non-object
-        }
-    }
+/// Read a config file for in-place editing (an empty document if it doesn't exist).
+///
+/// A file that fails to parse is an error rather than being treated as empty,
+/// so saving never overwrites a config the user is in the middle of editing.
+fn read_config_document(path: &Path) -> Result<JsonDocument, ConfigError> {
+    if !path.exists() {
+        return Ok(JsonDocument::default());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
+    JsonDocument::parse(&content)
+        .map_err(|e| ConfigError::ParseError(format!("{}: {}", path.display(), e)))
 }
 
 // ============================================================================
@@ -207,6 +159,9 @@ impl ConfigLayer {
 pub struct ConfigResolver {
     dir_context: DirectoryContext,
     working_dir: PathBuf,
+    /// Leave the settings that run programs out of the Project and Session
+    /// layers (see [`crate::services::workspace_trust`])
+    safe_mode: bool,
 }
 
 impl ConfigResolver {
@@ -215,9 +170,17 @@ impl ConfigResolver {
         Self {
             dir_context,
             working_dir,
+            safe_mode: false,
         }
     }
 
+    /// Resolve without the Project and Session layers' commands, for an
+    /// untrusted workspace.
+    pub fn with_safe_mode(mut self, safe_mode: bool) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
     /// Load all layers and merge them into a resolved Config.
     ///
     /// Layers are merged from highest to lowest precedence:
@@ -234,6 +197,11 @@ impl ConfigResolver {
             merged.merge_from(&project_partial);
         }
 
+        // Both layers come with the project
+        if self.safe_mode && merged.remove_commands() {
+            tracing::info!("Safe mode: ignoring commands in the project config");
+        }
+
         // Merge in User Platform layer (e.g., config_linux.json)
         if let Some(platform_partial) = self.load_user_platform_layer()? {
             tracing::debug!("Loaded user platform config layer");
@@ -332,8 +300,8 @@ impl ConfigResolver {
         let content = std::fs::read_to_string(path)
             .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
 
-        // Parse as raw JSON first
-        let value: Value = serde_json::from_str(&content)
+        // Parse as raw JSON first (comments and trailing commas are allowed)
+        let value: Value = config_edit::parse(&content)
             .map_err(|e| ConfigError::ParseError(format!("{}: {}", path.display(), e)))?;
 
         // Apply migrations
@@ -384,13 +352,8 @@ impl ConfigResolver {
 
         // Read existing file content (if any) as PartialConfig.
         // This preserves any manual edits made externally while the editor was running.
-        let existing: PartialConfig = if path.exists() {
-            let content = std::fs::read_to_string(&path)
-                .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
-            serde_json::from_str(&content).unwrap_or_default()
-        } else {
-            PartialConfig::default()
-        };
+        let mut document = read_config_document(&path)?;
+        let existing: PartialConfig = serde_json::from_value(document.value()).unwrap_or_default();
 
         // Merge: delta values take precedence, existing fills in gaps where delta is None
         let mut merged = delta;
@@ -403,9 +366,9 @@ impl ConfigResolver {
         let clean_merged =
             strip_empty_defaults(stripped_nulls).unwrap_or(Value::Object(Default::default()));
 
-        let json = serde_json::to_string_pretty(&clean_merged)
-            .map_err(|e| ConfigError::SerializeError(e.to_string()))?;
-        std::fs::write(&path, json)
+        // Edit the existing file in place so comments and formatting survive
+        document.update_to(&clean_merged);
+        std::fs::write(&path, document.as_str())
             .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
 
         Ok(())
@@ -441,37 +404,36 @@ impl ConfigResolver {
                 .map_err(|e| ConfigError::IoError(format!("{}: {}", parent_dir.display(), e)))?;
         }
 
-        // Read existing file content as JSON
-        let mut config_value: Value = if path.exists() {
-            let content = std::fs::read_to_string(&path)
-                .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
-            serde_json::from_str(&content).unwrap_or(Value::Object(Default::default()))
-        } else {
-            Value::Object(Default::default())
-        };
+        // Edit the existing file in place: only the changed keys are touched,
+        // comments and formatting are preserved
+        let mut document = read_config_document(&path)?;
 
         // Apply deletions first
+        let mut deletions: Vec<&String> = deletions.iter().collect();
+        deletions.sort();
         for pointer in deletions {
-            remove_json_pointer(&mut config_value, pointer);
+            document.remove(pointer);
         }
 
-        // Apply changes using JSON pointers
+        // Apply changes using JSON pointers. Null and empty values are removed
+        // rather than written, to keep configs minimal.
+        let mut changes: Vec<(&String, &Value)> = changes.iter().collect();
+        changes.sort_by(|a, b| a.0.cmp(b.0));
         for (pointer, value) in changes {
-            set_json_pointer(&mut config_value, pointer, value.clone());
+            match strip_nulls(value.clone()).and_then(strip_empty_defaults) {
+                Some(value) => document.update(pointer, &value),
+                None => {
+                    document.remove(pointer);
+                }
+            }
         }
 
         // Validate the result can be deserialized
-        let _: PartialConfig = serde_json::from_value(config_value.clone()).map_err(|e| {
+        let _: PartialConfig = serde_json::from_value(document.value()).map_err(|e| {
             ConfigError::ValidationError(format!("Result config would be invalid: {}", e))
         })?;
 
-        // Strip null values and empty defaults to keep configs minimal
-        let stripped = strip_nulls(config_value).unwrap_or(Value::Object(Default::default()));
-        let clean = strip_empty_defaults(stripped).unwrap_or(Value::Object(Default::default()));
-
-        let json = serde_json::to_string_pretty(&clean)
-            .map_err(|e| ConfigError::SerializeError(e.to_string()))?;
-        std::fs::write(&path, json)
+        std::fs::write(&path, document.as_str())
             .map_err(|e| ConfigError::IoError(format!("{}: {}", path.display(), e)))?;
 
         Ok(())
@@ -588,6 +550,44 @@ impl ConfigResolver {
 
         Ok(sources)
     }
+
+    /// Config files that affect the resolved config and should be watched for
+    /// changes. The session layer is excluded since the editor writes it itself.
+    pub fn watched_config_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.user_config_path()];
+        paths.extend(self.user_platform_config_path());
+        paths.push(self.project_config_write_path());
+        paths.push(self.working_dir.join("config.json"));
+        paths
+    }
+
+    /// Determine which layer (and file) a setting's effective value comes from.
+    ///
+    /// `pointer` is a JSON pointer such as `/editor/tab_size`. Returns
+    /// `(ConfigLayer::System, None)` when no config file sets the value.
+    pub fn value_source(
+        &self,
+        pointer: &str,
+    ) -> Result<(ConfigLayer, Option<PathBuf>), ConfigError> {
+        let mut candidates = vec![
+            (ConfigLayer::Session, self.session_config_path()),
+            (ConfigLayer::Project, self.project_config_path()),
+        ];
+        if let Some(platform_path) = self.user_platform_config_path() {
+            candidates.push((ConfigLayer::User, platform_path));
+        }
+        candidates.push((ConfigLayer::User, self.user_config_path()));
+
+        for (layer, path) in candidates {
+            if let Some(partial) = self.load_layer_from_path(&path)? {
+                let json = serde_json::to_value(&partial).unwrap_or_default();
+                if json.pointer(pointer).is_some_and(|v| !v.is_null()) {
+                    return Ok((layer, Some(path)));
+                }
+            }
+        }
+        Ok((ConfigLayer::System, None))
+    }
 }
 
 /// Recursively collect all non-null leaf paths in a JSON value.
@@ -685,8 +685,22 @@ impl Config {
     ///
     /// Merges layers in precedence order: Session > Project > User > System
     /// Falls back to defaults for any unspecified values.
+    ///
+    /// Commands in the project's config are left out unless the workspace is
+    /// trusted (see [`crate::services::workspace_trust`]).
     pub fn load_with_layers(dir_context: &DirectoryContext, working_dir: &Path) -> Self {
-        let resolver = ConfigResolver::new(dir_context.clone(), working_dir.to_path_buf());
+        let trusted = crate::services::workspace_trust::is_trusted(dir_context, working_dir);
+        Self::load_with_layers_trusted(dir_context, working_dir, trusted)
+    }
+
+    /// Like [`Self::load_with_layers`], with the workspace's trust given.
+    pub fn load_with_layers_trusted(
+        dir_context: &DirectoryContext,
+        working_dir: &Path,
+        trusted: bool,
+    ) -> Self {
+        let resolver = ConfigResolver::new(dir_context.clone(), working_dir.to_path_buf())
+            .with_safe_mode(!trusted);
         match resolver.resolve() {
             Ok(config) => {
                 tracing::info!("Loaded layered config for {}", working_dir.display());
@@ -709,7 +723,7 @@ impl Config {
     pub fn read_user_config_raw(working_dir: &Path) -> serde_json::Value {
         for path in Self::config_search_paths(working_dir) {
             if let Ok(contents) = std::fs::read_to_string(&path) {
-                match serde_json::from_str(&contents) {
+                match config_edit::parse(&contents) {
                     Ok(value) => return value,
                     Err(e) => {
                         tracing::warn!("Failed to parse config from {}: {}", path.display(), e);
@@ -859,6 +873,31 @@ impl DirectoryContext {
         self.data_dir.join("recovery")
     }
 
+    /// Get the directory of lock files for files being edited
+    pub fn locks_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("locks")
+    }
+
+    /// Get the directory of undo histories kept across restarts
+    pub fn undo_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("undo")
+    }
+
+    /// Get the socket path used to forward file opens to a running instance
+    pub fn instance_socket_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("instance.sock")
+    }
+
+    /// Get the path of the list of trusted workspaces
+    pub fn trusted_workspaces_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("trusted_workspaces.json")
+    }
+
+    /// Get the path of the file marking that the Tutorial has been offered
+    pub fn tutorial_shown_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("tutorial_shown")
+    }
+
     /// Get the sessions directory path
     pub fn sessions_dir(&self) -> std::path::PathBuf {
         self.data_dir.join("sessions")
@@ -914,6 +953,11 @@ impl DirectoryContext {
         self.config_dir.join("grammars")
     }
 
+    /// Get the spell checking dictionaries directory path
+    pub fn dictionaries_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("dictionaries")
+    }
+
     /// Get the plugins directory path
     pub fn plugins_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("plugins")
@@ -983,6 +1027,42 @@ mod tests {
         drop(temp);
     }
 
+    #[test]
+    fn resolver_safe_mode_ignores_project_commands() {
+        let (temp, resolver) = create_test_resolver();
+
+        let user_config_path = resolver.user_config_path();
+        std::fs::create_dir_all(user_config_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &user_config_path,
+            r#"{"languages": {"rust": {"formatter": {"command": "rustfmt"}}}}"#,
+        )
+        .unwrap();
+        let project_config_path = resolver.project_config_path();
+        std::fs::create_dir_all(project_config_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &project_config_path,
+            r#"{"editor": {"tab_size": 8}, "languages": {"rust": {"formatter": {"command": "./format.sh"}}}}"#,
+        )
+        .unwrap();
+
+        let config = resolver.resolve().unwrap();
+        assert_eq!(
+            config.languages["rust"].formatter.as_ref().unwrap().command,
+            "./format.sh"
+        );
+
+        // The project's other settings still apply; the user's formatter is used
+        let resolver = resolver.with_safe_mode(true);
+        let config = resolver.resolve().unwrap();
+        assert_eq!(config.editor.tab_size, 8);
+        assert_eq!(
+            config.languages["rust"].formatter.as_ref().unwrap().command,
+            "rustfmt"
+        );
+        drop(temp);
+    }
+
     #[test]
     fn resolver_session_overrides_all() {
         let (temp, resolver) = create_test_resolver();
@@ -1056,6 +1136,42 @@ mod tests {
         drop(temp);
     }
 
+    #[test]
+    fn value_source_reports_winning_layer() {
+        let (temp, resolver) = create_test_resolver();
+
+        let user_config_path = resolver.user_config_path();
+        std::fs::create_dir_all(user_config_path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &user_config_path,
+            r#"{"editor": {"tab_size": 2, "line_numbers": false}}"#,
+        )
+        .unwrap();
+
+        let project_config_path = resolver.project_config_write_path();
+        std::fs::create_dir_all(project_config_path.parent().unwrap()).unwrap();
+        std::fs::write(&project_config_path, r#"{"editor": {"tab_size": 8}}"#).unwrap();
+
+        assert_eq!(
+            resolver.value_source("/editor/tab_size").unwrap(),
+            (ConfigLayer::Project, Some(project_config_path.clone()))
+        );
+        assert_eq!(
+            resolver.value_source("/editor/line_numbers").unwrap(),
+            (ConfigLayer::User, Some(user_config_path.clone()))
+        );
+        assert_eq!(
+            resolver.value_source("/editor/auto_indent").unwrap(),
+            (ConfigLayer::System, None)
+        );
+
+        let watched = resolver.watched_config_paths();
+        assert!(watched.contains(&user_config_path));
+        assert!(watched.contains(&project_config_path));
+        assert!(!watched.contains(&resolver.session_config_path()));
+        drop(temp);
+    }
+
     #[test]
     fn load_with_layers_works() {
         let temp = TempDir::new().unwrap();
@@ -1615,6 +1731,58 @@ mod tests {
         assert!(final_config.lsp["rust"].enabled, "rust should be enabled");
     }
 
+    /// Saving from the Settings UI edits only the changed keys and keeps the
+    /// user's comments and formatting
+    #[test]
+    fn settings_ui_save_preserves_comments_and_formatting() {
+        let (_temp, resolver) = create_test_resolver();
+        let user_config_path = resolver.user_config_path();
+        std::fs::create_dir_all(user_config_path.parent().unwrap()).unwrap();
+
+        let original = r#"{
+    // Font and colors
+    "theme": "dracula", // dark theme
+    "editor": {
+        "tab_size": 4,
+        "line_numbers": false
+    }
+}
+"#;
+        std::fs::write(&user_config_path, original).unwrap();
+        assert_eq!(resolver.resolve().unwrap().editor.tab_size, 4);
+
+        let mut changes = std::collections::HashMap::new();
+        changes.insert("/editor/tab_size".to_string(), serde_json::json!(2));
+        let mut deletions = std::collections::HashSet::new();
+        deletions.insert("/editor/line_numbers".to_string());
+        resolver
+            .save_changes_to_layer(&changes, &deletions, ConfigLayer::User)
+            .unwrap();
+
+        let saved = std::fs::read_to_string(&user_config_path).unwrap();
+        assert_eq!(
+            saved,
+            r#"{
+    // Font and colors
+    "theme": "dracula", // dark theme
+    "editor": {
+        "tab_size": 2
+    }
+}
+"#
+        );
+
+        // Invalid files are left alone rather than overwritten
+        std::fs::write(&user_config_path, r#"{"editor": {"tab_size": "#).unwrap();
+        assert!(resolver
+            .save_changes_to_layer(&changes, &deletions, ConfigLayer::User)
+            .is_err());
+        assert_eq!(
+            std::fs::read_to_string(&user_config_path).unwrap(),
+            r#"{"editor": {"tab_size": "#
+        );
+    }
+
     /// Issue #806 REPRODUCTION: Manual config.json edits are lost when saving from Settings UI.
     ///
     /// Scenario:
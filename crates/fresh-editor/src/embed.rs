@@ -0,0 +1,205 @@
+//! Embedding the editor in another ratatui application
+//!
+//! [`EditorPane`] hosts a complete editor (buffers, splits, prompts, LSP,
+//! plugins if enabled) in a rectangle of the host's frame. The host forwards
+//! key, mouse and paste events to it while it has focus, calls
+//! [`EditorPane::update`] from its event loop, and renders it like a widget:
+//!
+//! ```no_run
+//! use fresh::embed::{Config, DirectoryContext, EditorPane};
+//! use ratatui::layout::Rect;
+//!
+//! # fn run(terminal: &mut ratatui::DefaultTerminal) -> anyhow::Result<()> {
+//! let mut pane = EditorPane::new(Config::default(), DirectoryContext::from_system()?)?;
+//! pane.open_file(std::path::Path::new("notes.md"))?;
+//! terminal.draw(|frame| {
+//!     let area = frame.area();
+//!     let editor_area = Rect { x: 20, width: area.width.saturating_sub(20), ..area };
+//!     pane.render(frame, editor_area);
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The editor draws into an off-screen buffer the size of its rectangle,
+//! which is copied into the host's frame, so popups and menus stay within
+//! the pane. Mouse positions are translated from the host's screen.
+//!
+//! The types an embedder needs are re-exported here; this module is the
+//! supported surface for embedding, while the rest of the crate may change
+//! between releases. See `examples/embed.rs` for a complete host.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result as AnyhowResult;
+use crossterm::event::{KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::layout::{Position, Rect};
+use ratatui::{Frame, Terminal};
+
+pub use crate::app::Editor;
+pub use crate::config::Config;
+pub use crate::config_io::DirectoryContext;
+pub use crate::model::buffer::{Buffer, TextBuffer};
+pub use crate::model::event::BufferId;
+pub use crate::state::EditorState;
+pub use crate::view::color_support::ColorCapability;
+
+use crate::model::filesystem::StdFileSystem;
+
+/// Size of the pane before it's first rendered
+const INITIAL_SIZE: (u16, u16) = (80, 24);
+
+/// Where the off-screen cursor is parked before a draw. Drawing only moves it
+/// when the editor shows the cursor, so finding it still here means hidden.
+const PARKED_CURSOR: Position = Position {
+    x: u16::MAX,
+    y: u16::MAX,
+};
+
+/// An editor rendered into part of a host application's frame
+pub struct EditorPane {
+    editor: Editor,
+    /// Off-screen terminal the editor draws into
+    terminal: Terminal<TestBackend>,
+    /// Where the pane was last rendered on the host's screen
+    area: Rect,
+}
+
+impl EditorPane {
+    /// Create a pane editing files relative to the current directory, with
+    /// plugins disabled and colors detected from the environment
+    pub fn new(config: Config, dir_context: DirectoryContext) -> AnyhowResult<Self> {
+        Self::with_working_dir(config, None, dir_context, false, ColorCapability::detect())
+    }
+
+    /// Create a pane with an explicit working directory
+    pub fn with_working_dir(
+        config: Config,
+        working_dir: Option<PathBuf>,
+        dir_context: DirectoryContext,
+        plugins_enabled: bool,
+        color_capability: ColorCapability,
+    ) -> AnyhowResult<Self> {
+        let (width, height) = INITIAL_SIZE;
+        let editor = Editor::with_working_dir(
+            config,
+            width,
+            height,
+            working_dir,
+            dir_context,
+            plugins_enabled,
+            color_capability,
+            Arc::new(StdFileSystem),
+        )?;
+        let terminal = Terminal::new(TestBackend::new(width, height))?;
+        Ok(Self {
+            editor,
+            terminal,
+            area: Rect::new(0, 0, width, height),
+        })
+    }
+
+    /// Open a file in a new tab (or switch to it if it's already open)
+    pub fn open_file(&mut self, path: &Path) -> AnyhowResult<BufferId> {
+        self.editor.open_file(path)
+    }
+
+    /// Handle a key event; only presses are acted on
+    pub fn handle_key(&mut self, event: KeyEvent) -> AnyhowResult<()> {
+        if event.kind == KeyEventKind::Press {
+            self.editor.handle_key(event.code, event.modifiers)?;
+        }
+        Ok(())
+    }
+
+    /// Handle a mouse event in the host's screen coordinates. Events outside
+    /// the pane are ignored, except for drags and releases, which are kept
+    /// at its edge. Returns whether the pane needs rendering.
+    pub fn handle_mouse(&mut self, mut event: MouseEvent) -> AnyhowResult<bool> {
+        let area = self.area;
+        if area.is_empty() {
+            return Ok(false);
+        }
+        let inside = area.contains(Position::new(event.column, event.row));
+        let follows_press = matches!(event.kind, MouseEventKind::Drag(_) | MouseEventKind::Up(_));
+        if !inside && !follows_press {
+            return Ok(false);
+        }
+        event.column = event.column.clamp(area.left(), area.right() - 1) - area.x;
+        event.row = event.row.clamp(area.top(), area.bottom() - 1) - area.y;
+        self.editor.handle_mouse(event)
+    }
+
+    /// Paste text at the cursors
+    pub fn paste(&mut self, text: String) {
+        self.editor.paste_text(text);
+    }
+
+    /// Do the work that doesn't wait for input (language servers, timers,
+    /// auto-save, ...). Call this regularly from the host's event loop.
+    /// Returns whether the pane needs rendering.
+    pub fn update(&mut self) -> bool {
+        self.editor.update()
+    }
+
+    /// Whether the user asked the editor to quit
+    pub fn should_quit(&self) -> bool {
+        self.editor.should_quit()
+    }
+
+    /// Render the editor into `area` of the host's frame, placing the
+    /// terminal cursor if the editor shows it
+    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+        let area = area.intersection(frame.area());
+        if area.is_empty() {
+            return;
+        }
+        if area.width != self.area.width || area.height != self.area.height {
+            self.terminal.backend_mut().resize(area.width, area.height);
+            self.editor.resize(area.width, area.height);
+        }
+        self.area = area;
+
+        let _ = self.terminal.set_cursor_position(PARKED_CURSOR);
+        let editor = &mut self.editor;
+        if let Err(e) = self.terminal.draw(|frame| editor.render(frame)) {
+            tracing::error!("Failed to render embedded editor: {}", e);
+            return;
+        }
+
+        let source = self.terminal.backend().buffer();
+        let target = frame.buffer_mut();
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if let (Some(cell), Some(target_cell)) = (
+                    source.cell((x, y)),
+                    target.cell_mut((area.x + x, area.y + y)),
+                ) {
+                    *target_cell = cell.clone();
+                }
+            }
+        }
+        match self.terminal.get_cursor_position() {
+            Ok(cursor) if cursor != PARKED_CURSOR => {
+                frame.set_cursor_position((area.x + cursor.x, area.y + cursor.y));
+            }
+            _ => {}
+        }
+    }
+
+    /// The editor, for anything the pane doesn't wrap
+    pub fn editor(&self) -> &Editor {
+        &self.editor
+    }
+
+    pub fn editor_mut(&mut self) -> &mut Editor {
+        &mut self.editor
+    }
+
+    /// The state of the active buffer
+    pub fn active_state(&self) -> &EditorState {
+        self.editor.active_state()
+    }
+}
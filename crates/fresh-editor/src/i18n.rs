@@ -17,7 +17,7 @@
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
-use std::sync::RwLock;
+use std::sync::{OnceLock, RwLock};
 
 /// Type alias for the nested plugin strings map.
 /// Structure: plugin_name -> locale -> key -> translated_string
@@ -89,6 +89,64 @@ pub fn unregister_plugin_strings(plugin_name: &str) {
     all_strings.remove(plugin_name);
 }
 
+include!(concat!(env!("OUT_DIR"), "/embedded_locales.rs"));
+
+/// The translations from `locales/*.json`, each locale parsed the first time
+/// it's used.
+///
+/// The `i18n!` macro could embed them itself, but it adds every string from
+/// one generated function whose stack frame, in debug builds, grows with the
+/// number of strings and no longer fits a thread's default stack.
+pub struct EmbeddedTranslations {
+    parsed: Vec<OnceLock<HashMap<String, String>>>,
+}
+
+impl EmbeddedTranslations {
+    pub fn new() -> Self {
+        Self {
+            parsed: EMBEDDED_LOCALES.iter().map(|_| OnceLock::new()).collect(),
+        }
+    }
+}
+
+impl Default for EmbeddedTranslations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl rust_i18n::Backend for EmbeddedTranslations {
+    fn available_locales(&self) -> Vec<&str> {
+        EMBEDDED_LOCALES.iter().map(|(locale, _)| *locale).collect()
+    }
+
+    fn translate(&self, locale: &str, key: &str) -> Option<&str> {
+        let index = EMBEDDED_LOCALES.iter().position(|(l, _)| *l == locale)?;
+        self.parsed[index]
+            .get_or_init(|| parse_locale(locale, EMBEDDED_LOCALES[index].1))
+            .get(key)
+            .map(String::as_str)
+    }
+}
+
+/// Read a translation file's strings, skipping metadata such as `_version`
+fn parse_locale(locale: &str, json: &str) -> HashMap<String, String> {
+    let entries: HashMap<String, serde_json::Value> = match serde_json::from_str(json) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Failed to parse the {} translations: {}", locale, e);
+            return HashMap::new();
+        }
+    };
+    entries
+        .into_iter()
+        .filter_map(|(key, value)| match value {
+            serde_json::Value::String(text) => Some((key, text)),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Initialize i18n with the user's locale preference.
 ///
 /// This should be called early in application startup. It detects the system
@@ -220,6 +278,13 @@ pub fn switched_to_project_message(path: &str) -> String {
     rust_i18n::t!("file.switched_to_project", path = path).to_string()
 }
 
+/// Get the translated message for a `--language` the editor doesn't know.
+///
+/// Like [`switched_to_project_message`], this is for the binary crate.
+pub fn unknown_language_message(language: &str) -> String {
+    rust_i18n::t!("buffer.unknown_language", language = language).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,6 +318,18 @@ mod tests {
         assert_eq!(msg, "Locale changed to es");
     }
 
+    #[test]
+    fn test_translates_on_a_small_stack() {
+        use rust_i18n::t;
+        let text = std::thread::Builder::new()
+            .stack_size(256 * 1024)
+            .spawn(|| t!("locale.changed", locale = "de", locale_name = "en").to_string())
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(text, "Sprache geändert zu en");
+    }
+
     #[test]
     fn test_available_locales_includes_en() {
         let locales = available_locales();
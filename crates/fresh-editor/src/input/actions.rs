@@ -2,14 +2,22 @@
 
 use crate::input::keybindings::Action;
 use crate::model::buffer::{Buffer, LineEnding};
-use crate::model::cursor::{Position2D, SelectionMode};
+use crate::model::cursor::{Cursor, Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
-use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
+use crate::primitives::display_width::{
+    line_byte_offset_at_visual_column, line_visual_column_at_byte,
+};
+use crate::primitives::identifier_case::{self, IdentifierCase};
+use crate::primitives::indent::indent_to_string;
+use crate::primitives::line_operations::{self, LineOperation};
+use crate::primitives::number_literals;
+use crate::primitives::text_objects::{self, TextObject};
 use crate::primitives::word_navigation::{
     find_word_end, find_word_end_right, find_word_start, find_word_start_left,
     find_word_start_right,
 };
 use crate::state::EditorState;
+use fresh_languages::tree_sitter::Tree;
 use std::ops::Range;
 
 /// Direction for block selection movement
@@ -21,27 +29,15 @@ enum BlockDirection {
     Down,
 }
 
-/// Convert byte offset to 2D position (line, column)
-fn byte_to_2d(buffer: &Buffer, byte_pos: usize) -> Position2D {
-    let line = buffer.get_line_number(byte_pos);
-    let line_start = buffer.line_start_offset(line).unwrap_or(0);
-    let column = byte_pos.saturating_sub(line_start);
+/// Convert byte offset to 2D position (line, visual column)
+fn byte_to_2d(buffer: &Buffer, byte_pos: usize, tab_size: usize) -> Position2D {
+    let (line, column) = buffer.position_to_line_visual_col(byte_pos, tab_size);
     Position2D { line, column }
 }
 
-/// Convert 2D position to byte offset
-fn pos_2d_to_byte(buffer: &Buffer, pos: Position2D) -> usize {
-    let line_start = buffer.line_start_offset(pos.line).unwrap_or(0);
-    // Get line content to check bounds
-    let line_content = buffer.get_line(pos.line).unwrap_or_default();
-    // Clamp column to line length (excluding newline)
-    let line_len = if line_content.last() == Some(&b'\n') {
-        line_content.len().saturating_sub(1)
-    } else {
-        line_content.len()
-    };
-    let clamped_col = pos.column.min(line_len);
-    line_start + clamped_col
+/// Convert 2D position (line, visual column) to byte offset, clamped to the line
+fn pos_2d_to_byte(buffer: &Buffer, pos: Position2D, tab_size: usize) -> usize {
+    buffer.line_visual_col_to_position(pos.line, pos.column, tab_size)
 }
 
 /// Calculate the visual column (display width) at the cursor position.
@@ -50,6 +46,7 @@ fn calculate_visual_column(
     buffer: &mut Buffer,
     cursor_position: usize,
     estimated_line_length: usize,
+    tab_size: usize,
 ) -> (usize, usize) {
     let mut iter = buffer.line_iterator(cursor_position, estimated_line_length);
     let current_line_start = iter.current_position();
@@ -57,7 +54,10 @@ fn calculate_visual_column(
 
     if let Some((_, line_content)) = iter.next_line() {
         if byte_column > 0 && byte_column <= line_content.len() {
-            (str_width(&line_content[..byte_column]), byte_column)
+            (
+                line_visual_column_at_byte(&line_content, byte_column, tab_size),
+                byte_column,
+            )
         } else {
             (byte_column, byte_column) // Fallback for edge cases
         }
@@ -66,6 +66,17 @@ fn calculate_visual_column(
     }
 }
 
+/// Byte position at a visual column of a line, clamped to the end of the line.
+fn position_at_visual_column(
+    line_start: usize,
+    line_content: &str,
+    visual_column: usize,
+    tab_size: usize,
+) -> usize {
+    let line_text = line_content.trim_end_matches(LINE_ENDING_CHARS);
+    line_start + line_byte_offset_at_visual_column(line_text, visual_column, tab_size)
+}
+
 /// Pattern for matching line ending characters (\r and \n)
 const LINE_ENDING_CHARS: &[char] = &['\r', '\n'];
 
@@ -213,8 +224,9 @@ fn block_select_action(
         }
     };
 
+    let tab_size = state.tab_size;
     for (cursor_id, cursor) in state.cursors.iter() {
-        let current_2d = byte_to_2d(&state.buffer, cursor.position);
+        let current_2d = byte_to_2d(&state.buffer, cursor.position, tab_size);
 
         // If not in block mode, start block selection
         let block_anchor =
@@ -224,52 +236,60 @@ fn block_select_action(
                 cursor.block_anchor.unwrap()
             };
 
-        // Calculate new 2D position based on direction
-        let new_2d = match direction {
-            BlockDirection::Left => Position2D {
-                line: current_2d.line,
-                column: current_2d.column.saturating_sub(1),
-            },
-            BlockDirection::Right => {
-                // Get current line length to bound the column
-                let line_content = state.buffer.get_line(current_2d.line).unwrap_or_default();
-                let line_len = if line_content.last() == Some(&b'\n') {
-                    line_content.len().saturating_sub(1)
+        // Calculate new position based on direction. Left/Right step over one
+        // character, so a tab is crossed in a single move; Up/Down keep the
+        // visual column, like normal vertical movement.
+        let vertical_column = if cursor.selection_mode == SelectionMode::Block {
+            cursor.sticky_column
+        } else {
+            current_2d.column
+        };
+        let new_byte_pos = match direction {
+            BlockDirection::Left => {
+                let line_start = state.buffer.line_start_offset(current_2d.line).unwrap_or(0);
+                if cursor.position > line_start {
+                    state.buffer.prev_char_boundary(cursor.position)
                 } else {
-                    line_content.len()
-                };
-                Position2D {
-                    line: current_2d.line,
-                    column: (current_2d.column + 1).min(line_len),
+                    cursor.position
                 }
             }
-            BlockDirection::Up => {
-                if current_2d.line > 0 {
+            BlockDirection::Right => {
+                let line_end = pos_2d_to_byte(
+                    &state.buffer,
                     Position2D {
-                        line: current_2d.line - 1,
-                        column: current_2d.column,
-                    }
+                        line: current_2d.line,
+                        column: usize::MAX,
+                    },
+                    tab_size,
+                );
+                if cursor.position < line_end {
+                    state.buffer.next_char_boundary(cursor.position)
                 } else {
-                    current_2d
+                    cursor.position
                 }
             }
-            BlockDirection::Down => {
-                if current_2d.line + 1 < total_lines {
-                    Position2D {
-                        line: current_2d.line + 1,
-                        column: current_2d.column,
-                    }
-                } else {
-                    current_2d
-                }
+            BlockDirection::Up | BlockDirection::Down => {
+                let line = match direction {
+                    BlockDirection::Up => current_2d.line.saturating_sub(1),
+                    _ if current_2d.line + 1 < total_lines => current_2d.line + 1,
+                    _ => current_2d.line,
+                };
+                let new_2d = Position2D {
+                    line,
+                    column: vertical_column,
+                };
+                pos_2d_to_byte(&state.buffer, new_2d, tab_size)
+            }
+        };
+        let new_sticky_column = match direction {
+            BlockDirection::Up | BlockDirection::Down => vertical_column,
+            BlockDirection::Left | BlockDirection::Right => {
+                byte_to_2d(&state.buffer, new_byte_pos, tab_size).column
             }
         };
-
-        // Convert new 2D position back to byte offset
-        let new_byte_pos = pos_2d_to_byte(&state.buffer, new_2d);
 
         // Store the byte anchor for the event system (for undo/redo compatibility)
-        let byte_anchor = pos_2d_to_byte(&state.buffer, block_anchor);
+        let byte_anchor = pos_2d_to_byte(&state.buffer, block_anchor, tab_size);
 
         events.push(Event::MoveCursor {
             cursor_id,
@@ -278,7 +298,7 @@ fn block_select_action(
             old_anchor: cursor.anchor,
             new_anchor: Some(byte_anchor),
             old_sticky_column: cursor.sticky_column,
-            new_sticky_column: new_2d.column,
+            new_sticky_column,
         });
 
         // Note: We need to set block selection mode after the event is processed
@@ -292,7 +312,7 @@ fn block_select_action(
     let buffer_ref = &state.buffer;
     state.cursors.map(|cursor| {
         if cursor.selection_mode != SelectionMode::Block || cursor.block_anchor.is_none() {
-            let current_2d = byte_to_2d(buffer_ref, cursor.position);
+            let current_2d = byte_to_2d(buffer_ref, cursor.position, tab_size);
             cursor.start_block_selection(current_2d.line, current_2d.column);
         }
     });
@@ -314,13 +334,14 @@ pub fn clear_block_selection_if_active(state: &mut EditorState) {
 /// Returns events to add the new cursors (if any).
 fn convert_block_selection_to_cursors(state: &mut EditorState) -> Vec<Event> {
     let mut events = Vec::new();
+    let tab_size = state.tab_size;
 
     // Check if any cursor has a block selection
     let block_info: Option<(CursorId, Position2D, Position2D)> =
         state.cursors.iter().find_map(|(cursor_id, cursor)| {
             if cursor.has_block_selection() {
                 let block_anchor = cursor.block_anchor?;
-                let cursor_2d = byte_to_2d(&state.buffer, cursor.position);
+                let cursor_2d = byte_to_2d(&state.buffer, cursor.position, tab_size);
                 Some((cursor_id, block_anchor, cursor_2d))
             } else {
                 None
@@ -341,22 +362,24 @@ fn convert_block_selection_to_cursors(state: &mut EditorState) -> Vec<Event> {
     let mut cursor_positions: Vec<(usize, usize)> = Vec::new(); // (position, anchor)
 
     for line in min_line..=max_line {
-        let line_start = state.buffer.line_start_offset(line).unwrap_or(0);
-        let line_content = state.buffer.get_line(line).unwrap_or_default();
-
-        // Calculate line length excluding newline
-        let line_len = if line_content.last() == Some(&b'\n') {
-            line_content.len().saturating_sub(1)
-        } else {
-            line_content.len()
-        };
-
-        // Clamp columns to actual line length
-        let actual_min_col = min_col.min(line_len);
-        let actual_max_col = max_col.min(line_len);
-
-        let anchor = line_start + actual_min_col;
-        let position = line_start + actual_max_col;
+        // Columns are visual, so they are clamped to the line and snapped to
+        // the start of any tab they fall inside
+        let anchor = pos_2d_to_byte(
+            &state.buffer,
+            Position2D {
+                line,
+                column: min_col,
+            },
+            tab_size,
+        );
+        let position = pos_2d_to_byte(
+            &state.buffer,
+            Position2D {
+                line,
+                column: max_col,
+            },
+            tab_size,
+        );
 
         cursor_positions.push((position, anchor));
     }
@@ -414,40 +437,53 @@ pub fn get_auto_close_char(ch: char, auto_indent: bool, language: &str) -> Optio
     }
 }
 
-/// Calculate the correct indent for a closing delimiter using tree-sitter.
+/// Calculate the indent of a new line started at `position`: with tree-sitter
+/// when we have a highlighter, with the language's heuristic rules otherwise,
+/// falling back to pattern matching for files without a language (e.g. .txt).
+/// Returns None if auto-indent should be disabled.
+fn calculate_new_line_indent(
+    state: &EditorState,
+    position: usize,
+    tab_size: usize,
+) -> Option<usize> {
+    let mut calculator = state.indent_calculator.borrow_mut();
+    if let Some(language) = state.highlighter.language() {
+        return calculator.calculate_indent(&state.buffer, position, language, tab_size);
+    }
+    Some(
+        calculator
+            .calculate_indent_heuristic(&state.buffer, position, tab_size)
+            .unwrap_or_else(|| {
+                crate::primitives::indent::IndentCalculator::calculate_indent_no_language(
+                    &state.buffer,
+                    position,
+                    tab_size,
+                )
+            }),
+    )
+}
+
+/// Calculate the correct indent for a closing delimiter using tree-sitter,
+/// or the language's heuristic rules if there's no highlighter.
 fn calculate_closing_delimiter_indent(
     state: &mut EditorState,
     insert_position: usize,
     ch: char,
     tab_size: usize,
 ) -> usize {
-    if let Some(language) = state.highlighter.language() {
-        state
-            .indent_calculator
-            .borrow_mut()
-            .calculate_dedent_for_delimiter(&state.buffer, insert_position, ch, language, tab_size)
-            .unwrap_or(0)
-    } else {
-        0
-    }
-}
-
-/// Convert a visual indent width to actual indent characters.
-/// When `use_tabs` is true, uses tab characters; otherwise uses spaces.
-/// The `indent_width` is the visual width in columns, and `tab_size` is
-/// how many columns a tab character represents.
-fn indent_to_string(indent_width: usize, use_tabs: bool, tab_size: usize) -> String {
-    if use_tabs && tab_size > 0 {
-        let num_tabs = indent_width / tab_size;
-        let remaining_spaces = indent_width % tab_size;
-        let mut result = "\t".repeat(num_tabs);
-        if remaining_spaces > 0 {
-            result.push_str(&" ".repeat(remaining_spaces));
-        }
-        result
+    let mut calculator = state.indent_calculator.borrow_mut();
+    let indent = if let Some(language) = state.highlighter.language() {
+        calculator.calculate_dedent_for_delimiter(
+            &state.buffer,
+            insert_position,
+            ch,
+            language,
+            tab_size,
+        )
     } else {
-        " ".repeat(indent_width)
-    }
+        calculator.calculate_dedent_heuristic(&state.buffer, insert_position, tab_size)
+    };
+    indent.unwrap_or(0)
 }
 
 /// Handle skip-over with dedent: when typing a closing delimiter that exists after cursor,
@@ -545,6 +581,72 @@ fn handle_auto_dedent(
     });
 }
 
+/// Handle decrease-indent: when typing completes a line matching the language's
+/// decrease pattern (e.g. `fi` or `\end{...}`), dedent the line one level and
+/// insert the typed character, followed by its closing pair if auto-closed.
+/// Returns true if handled (caller should continue to next cursor).
+fn handle_decrease_indent(
+    state: &mut EditorState,
+    events: &mut Vec<Event>,
+    cursor_id: CursorId,
+    typed: &str,
+    insert_position: usize,
+    line_start: usize,
+    tab_size: usize,
+) -> bool {
+    let mut line_end = insert_position;
+    while line_end < state.buffer.len()
+        && state.buffer.slice_bytes(line_end..line_end + 1).first() != Some(&b'\n')
+    {
+        line_end += 1;
+    }
+    let before_cursor = state.get_text_range(line_start, insert_position);
+    let after_cursor = state.get_text_range(insert_position, line_end);
+    let after_cursor = after_cursor.trim_end_matches('\r');
+    let old_line = format!("{}{}", before_cursor, after_cursor);
+    let new_line = format!("{}{}{}", before_cursor, typed, after_cursor);
+
+    let Some(indent) = state.indent_calculator.borrow().calculate_decreased_indent(
+        &state.buffer,
+        line_start,
+        &old_line,
+        &new_line,
+        tab_size,
+    ) else {
+        return false;
+    };
+
+    let mut text = indent_to_string(indent, state.use_tabs, tab_size);
+    text.push_str(before_cursor.trim_start_matches([' ', '\t']));
+    text.push_str(typed);
+    let cursor_after_insert = line_start + text.len();
+    if insert_position > line_start {
+        events.push(Event::Delete {
+            range: line_start..insert_position,
+            deleted_text: before_cursor,
+            cursor_id,
+        });
+    }
+    events.push(Event::Insert {
+        position: line_start,
+        text,
+        cursor_id,
+    });
+    // Move cursor between an auto-closed pair
+    if let Some(close_char) = typed.chars().nth(1) {
+        events.push(Event::MoveCursor {
+            cursor_id,
+            old_position: cursor_after_insert,
+            new_position: cursor_after_insert - close_char.len_utf8(),
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+    }
+    true
+}
+
 /// Check if auto-close should happen based on character after cursor.
 fn should_auto_close(char_after: Option<u8>) -> bool {
     let is_alphanumeric_after = char_after
@@ -670,9 +772,13 @@ fn insert_char_events(
 ) {
     let is_closing_delimiter = matches!(ch, '}' | ')' | ']');
     let auto_close_char = get_auto_close_char(ch, auto_indent, &state.language);
+    let may_decrease_indent =
+        auto_indent && state.indent_calculator.borrow().has_decrease_pattern();
     let cursor_data = collect_insert_cursor_data(state);
 
     for data in cursor_data {
+        let has_selection = data.selection.is_some();
+
         // Delete selection if present
         if let (Some(range), Some(text)) = (data.selection, data.deleted_text) {
             events.push(Event::Delete {
@@ -727,6 +833,26 @@ fn insert_char_events(
             continue;
         }
 
+        // Try decrease-indent for lines like `fi` (not after deleting a selection,
+        // which would shift the line being checked)
+        if may_decrease_indent
+            && !has_selection
+            && handle_decrease_indent(
+                state,
+                events,
+                data.cursor_id,
+                &match auto_close_char.filter(|_| should_auto_close(data.char_after)) {
+                    Some(close_char) => format!("{}{}", ch, close_char),
+                    None => ch.to_string(),
+                },
+                data.insert_position,
+                data.line_start,
+                tab_size,
+            )
+        {
+            continue;
+        }
+
         // Try auto-close
         if let Some(close_char) = auto_close_char {
             if should_auto_close(data.char_after) {
@@ -794,6 +920,323 @@ where
     }
 }
 
+/// Apply a line operation to the lines touched by each selection
+///
+/// Selections are widened to whole lines; with no selection the whole
+/// buffer is used. The caller applies the events as one bulk edit, so the
+/// whole operation is a single undo step.
+fn transform_lines(
+    state: &mut EditorState,
+    events: &mut Vec<Event>,
+    estimated_line_length: usize,
+    operation: LineOperation,
+) {
+    let mut ranges: Vec<_> = state
+        .cursors
+        .iter()
+        .filter_map(|(cursor_id, cursor)| cursor.selection_range().map(|range| (cursor_id, range)))
+        .map(|(cursor_id, range)| {
+            let start = state
+                .buffer
+                .line_iterator(range.start, estimated_line_length)
+                .current_position();
+            let mut iter = state.buffer.line_iterator(range.end, estimated_line_length);
+            let line_start = iter.current_position();
+            // A selection ending at the start of a line doesn't include that line
+            let end = if line_start == range.end && range.end > start {
+                range.end
+            } else {
+                iter.next_line()
+                    .map(|(_, content)| line_start + content.len())
+                    .unwrap_or(range.end)
+            };
+            (cursor_id, start..end)
+        })
+        .collect();
+    if ranges.is_empty() {
+        ranges.push((state.cursors.primary_id(), 0..state.buffer.len()));
+    }
+    ranges.sort_by_key(|(_, range)| std::cmp::Reverse(range.start));
+    // Cursors on the same lines would transform them twice
+    ranges.dedup_by(|(_, next), (_, kept)| next.end > kept.start);
+
+    let line_ending = state.buffer.line_ending().as_str();
+    let seed = match operation {
+        LineOperation::Shuffle => {
+            use std::hash::{BuildHasher, Hasher};
+            std::collections::hash_map::RandomState::new()
+                .build_hasher()
+                .finish()
+        }
+        _ => 0,
+    };
+    for (cursor_id, range) in ranges {
+        let text = state.get_text_range(range.start, range.end);
+        let transformed = line_operations::transform_lines(&text, operation, line_ending, seed);
+        if transformed != text {
+            events.push(Event::Delete {
+                range: range.clone(),
+                deleted_text: text,
+                cursor_id,
+            });
+            events.push(Event::Insert {
+                position: range.start,
+                text: transformed,
+                cursor_id,
+            });
+        }
+    }
+}
+
+/// The text near `around` to search for text objects, where it starts in the
+/// buffer, and its syntax tree if `syntax` is set
+///
+/// Only the text near the cursors is searched, which keeps text objects fast
+/// in huge files.
+fn text_object_window(
+    state: &mut EditorState,
+    around: Range<usize>,
+    syntax: bool,
+) -> (usize, String, Option<Tree>) {
+    const WINDOW: usize = 64 * 1024;
+
+    let buffer_len = state.buffer.len();
+    let window_start = match around.start.checked_sub(WINDOW) {
+        Some(from) if from > 0 => state.buffer.prev_char_boundary(from + 1),
+        _ => 0,
+    };
+    let window_end = if around.end + WINDOW < buffer_len {
+        state.buffer.prev_char_boundary(around.end + WINDOW + 1)
+    } else {
+        buffer_len
+    };
+    let text = state.get_text_range(window_start, window_end);
+    let tree = if syntax {
+        state
+            .highlighter
+            .language()
+            .and_then(|language| language.ts_language())
+            .and_then(|language| text_objects::parse(&language, &text))
+    } else {
+        None
+    };
+    (window_start, text, tree)
+}
+
+/// The text window and syntax tree around `cursors`, see [`text_object_window`]
+fn argument_window(
+    state: &mut EditorState,
+    cursors: &[(CursorId, Cursor)],
+) -> (usize, String, Option<Tree>) {
+    let positions = || cursors.iter().map(|(_, cursor)| cursor.position);
+    let lowest = positions().min().unwrap_or(0);
+    let highest = positions().max().unwrap_or(0);
+    text_object_window(state, lowest..highest, true)
+}
+
+/// Select the text object around each cursor, or grow its selection to the
+/// next enclosing one
+///
+/// A cursor with nothing around it is left with an empty selection, so a
+/// delete or cut that follows removes nothing.
+fn select_text_object(
+    state: &mut EditorState,
+    events: &mut Vec<Event>,
+    object: TextObject,
+    inner: bool,
+) {
+    let cursors: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    let ranges = || {
+        cursors.iter().map(|(_, cursor)| {
+            cursor
+                .selection_range()
+                .unwrap_or(cursor.position..cursor.position)
+        })
+    };
+    let (Some(lowest), Some(highest)) = (
+        ranges().map(|range| range.start).min(),
+        ranges().map(|range| range.end).max(),
+    ) else {
+        return;
+    };
+    let (window_start, text, tree) =
+        text_object_window(state, lowest..highest, object.uses_syntax_tree());
+
+    for ((cursor_id, cursor), range) in cursors.iter().zip(ranges()) {
+        let selection = range.start - window_start..range.end - window_start;
+        let (start, end) =
+            match text_objects::find_text_object(&text, tree.as_ref(), selection, object, inner) {
+                Some(found) => (window_start + found.start, window_start + found.end),
+                None if cursor.anchor.is_some() => continue,
+                None => (cursor.position, cursor.position),
+            };
+        events.push(Event::MoveCursor {
+            cursor_id: *cursor_id,
+            old_position: cursor.position,
+            new_position: end,
+            old_anchor: cursor.anchor,
+            new_anchor: Some(start),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        });
+    }
+}
+
+/// Move each cursor to the start of the next or previous argument
+fn move_to_argument(state: &mut EditorState, events: &mut Vec<Event>, forward: bool) {
+    let cursors: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    let (window_start, text, tree) = argument_window(state, &cursors);
+    for (cursor_id, cursor) in cursors {
+        let offset = cursor.position - window_start;
+        let target = if forward {
+            text_objects::next_argument(&text, tree.as_ref(), offset)
+        } else {
+            text_objects::previous_argument(&text, tree.as_ref(), offset)
+        };
+        if let Some(target) = target {
+            events.push(Event::MoveCursor {
+                cursor_id,
+                old_position: cursor.position,
+                new_position: window_start + target,
+                old_anchor: cursor.anchor,
+                new_anchor: None,
+                old_sticky_column: cursor.sticky_column,
+                new_sticky_column: 0,
+            });
+        }
+    }
+}
+
+/// Swap the argument at each cursor with the next or previous one
+///
+/// Only the arguments trade places, so the commas and spacing between them
+/// stay put. The cursor ends up after the moved argument, where swapping
+/// again moves it further.
+fn swap_arguments(state: &mut EditorState, events: &mut Vec<Event>, forward: bool) {
+    let cursors: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    let (window_start, text, tree) = argument_window(state, &cursors);
+    let mut swaps: Vec<_> = cursors
+        .into_iter()
+        .filter_map(|(cursor_id, cursor)| {
+            let offset = cursor.position - window_start;
+            let (moved, other) =
+                text_objects::argument_swap(&text, tree.as_ref(), offset, forward)?;
+            Some((cursor_id, moved, other))
+        })
+        .collect();
+    swaps.sort_by_key(|(_, moved, other)| std::cmp::Reverse(moved.start.min(other.start)));
+    // Cursors whose swaps overlap swap once
+    let mut covered_from = usize::MAX;
+    for (cursor_id, moved, other) in swaps {
+        if moved.end.max(other.end) > covered_from {
+            continue;
+        }
+        covered_from = moved.start.min(other.start);
+        // The moved argument's edit comes last, so the cursor follows it
+        for (range, replacement) in [(&moved, &other), (&other, &moved)] {
+            events.push(Event::Delete {
+                range: window_start + range.start..window_start + range.end,
+                deleted_text: text[range.clone()].to_string(),
+                cursor_id,
+            });
+            events.push(Event::Insert {
+                position: window_start + range.start,
+                text: text[replacement.clone()].to_string(),
+                cursor_id,
+            });
+        }
+    }
+}
+
+/// Insert `, ` after the argument at each cursor, ready to type a new one
+fn add_argument(state: &mut EditorState, events: &mut Vec<Event>) {
+    let cursors: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, *cursor))
+        .collect();
+    let (window_start, text, tree) = argument_window(state, &cursors);
+    let mut inserts: Vec<_> = cursors
+        .into_iter()
+        .filter_map(|(cursor_id, cursor)| {
+            let offset = cursor.position - window_start;
+            let argument = text_objects::argument_at(&text, tree.as_ref(), offset)?;
+            Some((cursor_id, window_start + argument.end))
+        })
+        .collect();
+    inserts.sort_by_key(|&(_, position)| std::cmp::Reverse(position));
+    inserts.dedup_by_key(|(_, position)| *position);
+    for (cursor_id, position) in inserts {
+        events.push(Event::Insert {
+            position,
+            text: ", ".to_string(),
+            cursor_id,
+        });
+    }
+}
+
+/// Add `delta` to the number under or after each cursor on its line
+fn increment_numbers(
+    state: &mut EditorState,
+    events: &mut Vec<Event>,
+    estimated_line_length: usize,
+    delta: i128,
+) {
+    let positions: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, cursor.position))
+        .collect();
+
+    let mut edits = Vec::new();
+    for (cursor_id, position) in positions {
+        let mut iter = state.buffer.line_iterator(position, estimated_line_length);
+        let line_start = iter.current_position();
+        let Some((_, line)) = iter.next_line() else {
+            continue;
+        };
+        let line = line.trim_end_matches(['\n', '\r']);
+        if let Some((range, text)) =
+            number_literals::increment_at(line, position - line_start, delta)
+        {
+            edits.push((
+                cursor_id,
+                line_start + range.start..line_start + range.end,
+                text,
+            ));
+        }
+    }
+    edits.sort_by_key(|(_, range, _)| std::cmp::Reverse(range.start));
+    // Cursors on the same number change it once
+    edits.dedup_by(|(_, next, _), (_, kept, _)| next == kept);
+
+    for (cursor_id, range, text) in edits {
+        let deleted_text = state.get_text_range(range.start, range.end);
+        events.push(Event::Delete {
+            range: range.clone(),
+            deleted_text,
+            cursor_id,
+        });
+        events.push(Event::Insert {
+            position: range.start,
+            text,
+            cursor_id,
+        });
+    }
+}
+
 /// Convert an action into a sequence of events that can be applied to the editor state
 ///
 /// # Parameters
@@ -915,55 +1358,20 @@ pub fn action_to_events(
 
                 if auto_indent {
                     let use_tabs = state.use_tabs;
-                    if let Some(language) = state.highlighter.language() {
-                        // Use tree-sitter-based indent when we have a highlighter
-                        if let Some(indent_width) = state
-                            .indent_calculator
-                            .borrow_mut()
-                            .calculate_indent(&state.buffer, indent_position, language, tab_size)
-                        {
-                            let indent_str = indent_to_string(indent_width, use_tabs, tab_size);
-                            text.push_str(&indent_str);
-
-                            // For bracket expansion, add another newline with dedented closing bracket
-                            if bracket_expansion {
-                                // Record where cursor should end up (end of cursor line)
-                                cursor_line_end_position =
-                                    Some(indent_position + line_ending.len() + indent_str.len());
-
-                                // Calculate the dedent for the closing bracket line
-                                // It should match the indent of the line containing the opening bracket
-                                let opening_bracket_indent =
-                                    crate::primitives::indent::IndentCalculator::get_line_indent_at_position(
-                                        &state.buffer,
-                                        indent_position.saturating_sub(1),
-                                        tab_size,
-                                    );
-                                text.push_str(line_ending);
-                                text.push_str(&indent_to_string(
-                                    opening_bracket_indent,
-                                    use_tabs,
-                                    tab_size,
-                                ));
-                            }
-                        }
-                    } else {
-                        // Fallback for files without syntax highlighting (e.g., .txt)
-                        let indent_width =
-                            crate::primitives::indent::IndentCalculator::calculate_indent_no_language(
-                                &state.buffer,
-                                indent_position,
-                                tab_size,
-                            );
+                    if let Some(indent_width) =
+                        calculate_new_line_indent(state, indent_position, tab_size)
+                    {
                         let indent_str = indent_to_string(indent_width, use_tabs, tab_size);
                         text.push_str(&indent_str);
 
-                        // For bracket expansion in non-language files
+                        // For bracket expansion, add another newline with dedented closing bracket
                         if bracket_expansion {
                             // Record where cursor should end up (end of cursor line)
                             cursor_line_end_position =
                                 Some(indent_position + line_ending.len() + indent_str.len());
 
+                            // Calculate the dedent for the closing bracket line
+                            // It should match the indent of the line containing the opening bracket
                             let opening_bracket_indent =
                                 crate::primitives::indent::IndentCalculator::get_line_indent_at_position(
                                     &state.buffer,
@@ -1292,6 +1700,7 @@ pub fn action_to_events(
                     &mut state.buffer,
                     cursor.position,
                     estimated_line_length,
+                    state.tab_size,
                 );
 
                 // Use sticky_column if set (now stores visual column), otherwise use current visual column
@@ -1308,10 +1717,12 @@ pub fn action_to_events(
 
                 if let Some((prev_line_start, prev_line_content)) = iter.prev() {
                     // Calculate byte offset from visual column, ensuring valid character boundary
-                    let prev_line_text = prev_line_content.trim_end_matches('\n');
-                    let byte_offset =
-                        byte_offset_at_visual_column(prev_line_text, goal_visual_column);
-                    let new_pos = prev_line_start + byte_offset;
+                    let new_pos = position_at_visual_column(
+                        prev_line_start,
+                        &prev_line_content,
+                        goal_visual_column,
+                        state.tab_size,
+                    );
 
                     // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                     let new_anchor = if cursor.deselect_on_move {
@@ -1339,6 +1750,7 @@ pub fn action_to_events(
                     &mut state.buffer,
                     cursor.position,
                     estimated_line_length,
+                    state.tab_size,
                 );
 
                 // Use sticky_column if set (now stores visual column), otherwise use current visual column
@@ -1358,10 +1770,12 @@ pub fn action_to_events(
 
                 if let Some((next_line_start, next_line_content)) = iter.next_line() {
                     // Calculate byte offset from visual column, ensuring valid character boundary
-                    let next_line_text = next_line_content.trim_end_matches('\n');
-                    let byte_offset =
-                        byte_offset_at_visual_column(next_line_text, goal_visual_column);
-                    let new_pos = next_line_start + byte_offset;
+                    let new_pos = position_at_visual_column(
+                        next_line_start,
+                        &next_line_content,
+                        goal_visual_column,
+                        state.tab_size,
+                    );
 
                     // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                     let new_anchor = if cursor.deselect_on_move {
@@ -1544,11 +1958,15 @@ pub fn action_to_events(
             for (cursor_id, cursor) in state.cursors.iter() {
                 // Move up by viewport height
                 let lines_to_move = viewport_height.saturating_sub(1) as usize;
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
 
                 // Use sticky_column if set, otherwise use current column
                 let goal_column = if cursor.sticky_column > 0 {
@@ -1560,8 +1978,12 @@ pub fn action_to_events(
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
                     if let Some((line_start, line_content)) = iter.prev() {
-                        let line_len = line_content.trim_end_matches('\n').len();
-                        new_pos = line_start + goal_column.min(line_len);
+                        new_pos = position_at_visual_column(
+                            line_start,
+                            &line_content,
+                            goal_column,
+                            state.tab_size,
+                        );
                     } else {
                         new_pos = 0;
                         break;
@@ -1590,11 +2012,15 @@ pub fn action_to_events(
             for (cursor_id, cursor) in state.cursors.iter() {
                 // Move down by viewport height
                 let lines_to_move = viewport_height.saturating_sub(1) as usize;
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
 
                 // Use sticky_column if set, otherwise use current column
                 let goal_column = if cursor.sticky_column > 0 {
@@ -1609,8 +2035,12 @@ pub fn action_to_events(
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
                     if let Some((line_start, line_content)) = iter.next_line() {
-                        let line_len = line_content.trim_end_matches('\n').len();
-                        new_pos = line_start + goal_column.min(line_len);
+                        new_pos = position_at_visual_column(
+                            line_start,
+                            &line_content,
+                            goal_column,
+                            state.tab_size,
+                        );
                     } else {
                         // Reached end of buffer - clamp to last valid position
                         new_pos = max_cursor_position(&state.buffer);
@@ -1676,11 +2106,15 @@ pub fn action_to_events(
 
         Action::SelectUp => {
             for (cursor_id, cursor) in state.cursors.iter() {
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
@@ -1691,8 +2125,12 @@ pub fn action_to_events(
                 };
 
                 if let Some((prev_line_start, prev_line_content)) = iter.prev() {
-                    let prev_line_len = prev_line_content.trim_end_matches('\n').len();
-                    let new_pos = prev_line_start + goal_column.min(prev_line_len);
+                    let new_pos = position_at_visual_column(
+                        prev_line_start,
+                        &prev_line_content,
+                        goal_column,
+                        state.tab_size,
+                    );
 
                     events.push(Event::MoveCursor {
                         cursor_id,
@@ -1709,11 +2147,15 @@ pub fn action_to_events(
 
         Action::SelectDown => {
             for (cursor_id, cursor) in state.cursors.iter() {
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
@@ -1726,8 +2168,12 @@ pub fn action_to_events(
                 // Skip current line, then get next line
                 iter.next_line();
                 if let Some((next_line_start, next_line_content)) = iter.next_line() {
-                    let next_line_len = next_line_content.trim_end_matches('\n').len();
-                    let new_pos = next_line_start + goal_column.min(next_line_len);
+                    let new_pos = position_at_visual_column(
+                        next_line_start,
+                        &next_line_content,
+                        goal_column,
+                        state.tab_size,
+                    );
 
                     events.push(Event::MoveCursor {
                         cursor_id,
@@ -1941,11 +2387,15 @@ pub fn action_to_events(
         Action::SelectPageUp => {
             for (cursor_id, cursor) in state.cursors.iter() {
                 let lines_to_move = viewport_height.saturating_sub(1) as usize;
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
@@ -1958,8 +2408,12 @@ pub fn action_to_events(
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
                     if let Some((line_start, line_content)) = iter.prev() {
-                        let line_len = line_content.trim_end_matches('\n').len();
-                        new_pos = line_start + goal_column.min(line_len);
+                        new_pos = position_at_visual_column(
+                            line_start,
+                            &line_content,
+                            goal_column,
+                            state.tab_size,
+                        );
                     } else {
                         new_pos = 0;
                         break;
@@ -1981,11 +2435,15 @@ pub fn action_to_events(
         Action::SelectPageDown => {
             for (cursor_id, cursor) in state.cursors.iter() {
                 let lines_to_move = viewport_height.saturating_sub(1) as usize;
+                let (current_column, _) = calculate_visual_column(
+                    &mut state.buffer,
+                    cursor.position,
+                    estimated_line_length,
+                    state.tab_size,
+                );
                 let mut iter = state
                     .buffer
                     .line_iterator(cursor.position, estimated_line_length);
-                let current_line_start = iter.current_position();
-                let current_column = cursor.position - current_line_start;
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
 
                 // Use sticky_column if set, otherwise use current column
@@ -2001,8 +2459,12 @@ pub fn action_to_events(
                 let mut new_pos = cursor.position;
                 for _ in 0..lines_to_move {
                     if let Some((line_start, line_content)) = iter.next_line() {
-                        let line_len = line_content.trim_end_matches('\n').len();
-                        new_pos = line_start + goal_column.min(line_len);
+                        new_pos = position_at_visual_column(
+                            line_start,
+                            &line_content,
+                            goal_column,
+                            state.tab_size,
+                        );
                     } else {
                         // Reached end of buffer - clamp to last valid position
                         new_pos = max_cursor_position(&state.buffer);
@@ -2313,47 +2775,96 @@ pub fn action_to_events(
             transform_case(state, &mut events, |s| s.to_lowercase());
         }
 
+        Action::ToCamelCase => {
+            transform_case(state, &mut events, |s| {
+                identifier_case::convert_case(s, IdentifierCase::Camel)
+            });
+        }
+
+        Action::ToSnakeCase => {
+            transform_case(state, &mut events, |s| {
+                identifier_case::convert_case(s, IdentifierCase::Snake)
+            });
+        }
+
+        Action::ToScreamingSnakeCase => {
+            transform_case(state, &mut events, |s| {
+                identifier_case::convert_case(s, IdentifierCase::ScreamingSnake)
+            });
+        }
+
+        Action::ToKebabCase => {
+            transform_case(state, &mut events, |s| {
+                identifier_case::convert_case(s, IdentifierCase::Kebab)
+            });
+        }
+
+        Action::ToTitleCase => {
+            transform_case(state, &mut events, |s| {
+                identifier_case::convert_case(s, IdentifierCase::Title)
+            });
+        }
+
         Action::SortLines => {
-            // Sort selected lines alphabetically
-            // Process cursors in reverse order to avoid position shifts
-            let line_ending = state.buffer.line_ending().as_str();
-            let mut selections: Vec<_> = state
-                .cursors
-                .iter()
-                .filter_map(|(cursor_id, cursor)| {
-                    cursor.selection_range().map(|range| (cursor_id, range))
-                })
-                .collect();
-            selections.sort_by_key(|(_, range)| std::cmp::Reverse(range.start));
-
-            for (cursor_id, range) in selections {
-                let text = state.get_text_range(range.start, range.end);
-                // Split into lines, preserving the original line ending style
-                let mut lines: Vec<&str> = text.lines().collect();
-                // Check if original text ends with a newline
-                let ends_with_newline = text.ends_with('\n') || text.ends_with("\r\n");
-
-                if lines.len() > 1 {
-                    lines.sort();
-                    let mut sorted_text = lines.join(line_ending);
-                    if ends_with_newline {
-                        sorted_text.push_str(line_ending);
-                    }
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::SortAscending,
+            );
+        }
 
-                    if sorted_text != text {
-                        events.push(Event::Delete {
-                            range: range.clone(),
-                            deleted_text: text,
-                            cursor_id,
-                        });
-                        events.push(Event::Insert {
-                            position: range.start,
-                            text: sorted_text,
-                            cursor_id,
-                        });
-                    }
-                }
-            }
+        Action::SortLinesDescending => {
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::SortDescending,
+            );
+        }
+
+        Action::SortLinesNumeric => {
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::SortNumeric,
+            );
+        }
+
+        Action::UniqueLines => {
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::Unique,
+            );
+        }
+
+        Action::ReverseLines => {
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::Reverse,
+            );
+        }
+
+        Action::ShuffleLines => {
+            transform_lines(
+                state,
+                &mut events,
+                estimated_line_length,
+                LineOperation::Shuffle,
+            );
+        }
+
+        Action::IncrementNumber => {
+            increment_numbers(state, &mut events, estimated_line_length, 1);
+        }
+
+        Action::DecrementNumber => {
+            increment_numbers(state, &mut events, estimated_line_length, -1);
         }
 
         Action::OpenLine => {
@@ -2425,16 +2936,42 @@ pub fn action_to_events(
         | Action::SaveAs
         | Action::Open
         | Action::SwitchProject
+        | Action::AddWorkspaceFolder
+        | Action::RemoveWorkspaceFolder
+        | Action::CompareFolders
+        | Action::DirDiffOpen
+        | Action::DirDiffCopyToRight
+        | Action::DirDiffCopyToLeft
+        | Action::DirDiffRefresh
+        | Action::RunTask(_)
+        | Action::TaskOutputOpen
+        | Action::TaskOutputRerun
         | Action::New
         | Action::Close
         | Action::CloseTab
+        | Action::TogglePinTab
+        | Action::CloseAllBuffers
+        | Action::CloseSavedBuffers
+        | Action::CloseOtherBuffers
+        | Action::ReopenClosedBuffer
         | Action::GotoLine
         | Action::NextBuffer
         | Action::PrevBuffer
         | Action::SwitchToPreviousTab
         | Action::SwitchToTabByName
+        | Action::SwitchBufferMru
+        | Action::SwitchBufferMruReverse
         | Action::NavigateBack
         | Action::NavigateForward
+        | Action::JumpBack
+        | Action::JumpForward
+        | Action::ShowJumpList
+        | Action::GotoTag
+        | Action::SelectTag
+        | Action::PopTag
+        | Action::RegenerateTags
+        | Action::GotoLastChange
+        | Action::GotoNextChange
         | Action::SplitHorizontal
         | Action::SplitVertical
         | Action::CloseSplit
@@ -2442,6 +2979,7 @@ pub fn action_to_events(
         | Action::PrevSplit
         | Action::Copy
         | Action::CopyWithTheme(_)
+        | Action::CopyAsAnsi
         | Action::Cut
         | Action::Paste
         | Action::YankWordForward
@@ -2449,14 +2987,28 @@ pub fn action_to_events(
         | Action::YankToLineEnd
         | Action::YankToLineStart
         | Action::AddCursorNextMatch
+        | Action::SelectRegexInSelection
+        | Action::SplitSelectionOnRegex
+        | Action::CursorUndo
+        | Action::CursorRedo
         | Action::AddCursorAbove
         | Action::AddCursorBelow
         | Action::CommandPalette
         | Action::QuickOpen
+        | Action::GotoAnything
+        | Action::OpenLinkAtCursor
         | Action::ShowHelp
         | Action::ToggleLineWrap
+        | Action::ToggleConceal
         | Action::ToggleComposeMode
         | Action::SetComposeWidth
+        | Action::ToggleMarkdownPreview
+        | Action::OpenMarkdownPreviewToSide
+        | Action::ToggleSpellCheck
+        | Action::SpellCheckSuggestions
+        | Action::SpellCheckAddWord
+        | Action::SetSpellLanguage
+        | Action::AdjustColor
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
@@ -2469,6 +3021,13 @@ pub fn action_to_events(
         | Action::ShowWarnings
         | Action::ShowStatusLog
         | Action::ShowLspStatus
+        | Action::ShowLspLog
+        | Action::ShowProjectEnv
+        | Action::ReloadProjectEnv
+        | Action::ShowTerminalInfo
+        | Action::ShowProfiler
+        | Action::ShowTutorial
+        | Action::ToggleCheatSheet
         | Action::ClearWarnings
         | Action::SmartHome
         | Action::ToggleComment
@@ -2530,6 +3089,7 @@ pub fn action_to_events(
         | Action::ToggleFileExplorer
         | Action::ToggleMenuBar
         | Action::ToggleTabBar
+        | Action::ToggleBreadcrumbs
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::SetBackground
@@ -2545,6 +3105,7 @@ pub fn action_to_events(
         | Action::FileExplorerNewFile
         | Action::FileExplorerNewDirectory
         | Action::FileExplorerDelete
+        | Action::FileExplorerUndoDelete
         | Action::FileExplorerRename
         | Action::FileExplorerToggleHidden
         | Action::FileExplorerToggleGitignored
@@ -2557,13 +3118,18 @@ pub fn action_to_events(
         | Action::LspHover
         | Action::LspSignatureHelp
         | Action::LspCodeActions
+        | Action::ExtractVariable
+        | Action::ExtractFunction
         | Action::LspRestart
         | Action::LspStop
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
         | Action::ToggleLineNumbers
         | Action::ToggleMouseCapture
+        | Action::ToggleImagePreview
         | Action::DumpConfig
+        | Action::ShowConfigSource
+        | Action::TrustWorkspace
         | Action::Search
         | Action::FindInSelection
         | Action::FindNext
@@ -2586,14 +3152,18 @@ pub fn action_to_events(
         | Action::ScrollTabsLeft
         | Action::ScrollTabsRight
         | Action::SelectTheme
+        | Action::ToggleLightDarkTheme
         | Action::SelectKeybindingMap
         | Action::SelectCursorStyle
         | Action::SelectLocale
         | Action::Revert
         | Action::ToggleAutoRevert
+        | Action::ToggleReadOnly
+        | Action::RecoverFiles
         | Action::FormatBuffer
         | Action::TrimTrailingWhitespace
         | Action::EnsureFinalNewline
+        | Action::ReindentLines
         | Action::OpenTerminal
         | Action::CloseTerminal
         | Action::FocusTerminal
@@ -2611,15 +3181,28 @@ pub fn action_to_events(
         | Action::SettingsIncrement
         | Action::SettingsDecrement
         | Action::SetTabSize
+        | Action::SetIndentation
+        | Action::InsertSequence
         | Action::SetLineEnding
         | Action::SetEncoding
         | Action::SetLanguage
         | Action::ToggleIndentationStyle
         | Action::ToggleTabIndicators
+        | Action::ToggleWhitespace
+        | Action::ToggleLineLengthWarnings
         | Action::ToggleDebugHighlights
         | Action::ResetBufferSettings
         | Action::ShellCommand
         | Action::ShellCommandReplace
+        | Action::NewScratchBuffer
+        | Action::SendToRepl
+        | Action::SendToReplInsert
+        | Action::CollabHost
+        | Action::CollabJoin
+        | Action::CollabLeave
+        | Action::StartPresenting
+        | Action::FollowPresentation
+        | Action::StopPresenting
         | Action::CalibrateInput
         | Action::EventDebug => return None,
 
@@ -2664,6 +3247,66 @@ pub fn action_to_events(
             }
         }
 
+        Action::SelectInsideBrackets => {
+            select_text_object(state, &mut events, TextObject::Brackets, true);
+        }
+
+        Action::SelectAroundBrackets => {
+            select_text_object(state, &mut events, TextObject::Brackets, false);
+        }
+
+        Action::SelectInsideQuotes => {
+            select_text_object(state, &mut events, TextObject::Quotes, true);
+        }
+
+        Action::SelectAroundQuotes => {
+            select_text_object(state, &mut events, TextObject::Quotes, false);
+        }
+
+        Action::SelectInsideTag => {
+            select_text_object(state, &mut events, TextObject::Tag, true);
+        }
+
+        Action::SelectAroundTag => {
+            select_text_object(state, &mut events, TextObject::Tag, false);
+        }
+
+        Action::SelectInsideFunction => {
+            select_text_object(state, &mut events, TextObject::Function, true);
+        }
+
+        Action::SelectAroundFunction => {
+            select_text_object(state, &mut events, TextObject::Function, false);
+        }
+
+        Action::SelectInsideArgument => {
+            select_text_object(state, &mut events, TextObject::Argument, true);
+        }
+
+        Action::SelectAroundArgument => {
+            select_text_object(state, &mut events, TextObject::Argument, false);
+        }
+
+        Action::GotoNextArgument => {
+            move_to_argument(state, &mut events, true);
+        }
+
+        Action::GotoPreviousArgument => {
+            move_to_argument(state, &mut events, false);
+        }
+
+        Action::SwapArgumentNext => {
+            swap_arguments(state, &mut events, true);
+        }
+
+        Action::SwapArgumentPrevious => {
+            swap_arguments(state, &mut events, false);
+        }
+
+        Action::AddArgument => {
+            add_argument(state, &mut events);
+        }
+
         Action::ExpandSelection => {
             // Expand selection for each cursor
             for (cursor_id, cursor) in state.cursors.iter() {
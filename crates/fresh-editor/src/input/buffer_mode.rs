@@ -106,6 +106,49 @@ impl ModeRegistry {
 
         registry.register(special_mode);
 
+        // Directory diff views (see app::dir_diff)
+        let dir_diff_mode = BufferMode::new("dir-diff")
+            .with_parent("special")
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "dir_diff_open")
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "dir_diff_refresh")
+            .with_binding(
+                KeyCode::Char('>'),
+                KeyModifiers::NONE,
+                "dir_diff_copy_to_right",
+            )
+            .with_binding(
+                KeyCode::Char('>'),
+                KeyModifiers::SHIFT,
+                "dir_diff_copy_to_right",
+            )
+            .with_binding(
+                KeyCode::Char('<'),
+                KeyModifiers::NONE,
+                "dir_diff_copy_to_left",
+            )
+            .with_binding(
+                KeyCode::Char('<'),
+                KeyModifiers::SHIFT,
+                "dir_diff_copy_to_left",
+            );
+
+        registry.register(dir_diff_mode);
+
+        // Task output buffers (see app::tasks)
+        let task_output_mode = BufferMode::new("task-output")
+            .with_parent("special")
+            .with_binding(KeyCode::Enter, KeyModifiers::NONE, "task_output_open")
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "task_output_rerun");
+
+        registry.register(task_output_mode);
+
+        // The Project Environment buffer (see app::project_env)
+        let project_env_mode = BufferMode::new("project-env")
+            .with_parent("special")
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "reload_project_env");
+
+        registry.register(project_env_mode);
+
         registry
     }
 
@@ -173,6 +173,38 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.add_workspace_folder").to_string(),
+            description: t!("cmd.add_workspace_folder_desc").to_string(),
+            action: Action::AddWorkspaceFolder,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.remove_workspace_folder").to_string(),
+            description: t!("cmd.remove_workspace_folder_desc").to_string(),
+            action: Action::RemoveWorkspaceFolder,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.compare_folders").to_string(),
+            description: t!("cmd.compare_folders_desc").to_string(),
+            action: Action::CompareFolders,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.run_task").to_string(),
+            description: t!("cmd.run_task_desc").to_string(),
+            action: Action::RunTask(String::new()),
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.save_file").to_string(),
             description: t!("cmd.save_file_desc").to_string(),
@@ -213,6 +245,46 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_pin_tab").to_string(),
+            description: t!("cmd.toggle_pin_tab_desc").to_string(),
+            action: Action::TogglePinTab,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.close_all_buffers").to_string(),
+            description: t!("cmd.close_all_buffers_desc").to_string(),
+            action: Action::CloseAllBuffers,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.close_saved_buffers").to_string(),
+            description: t!("cmd.close_saved_buffers_desc").to_string(),
+            action: Action::CloseSavedBuffers,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.close_other_buffers").to_string(),
+            description: t!("cmd.close_other_buffers_desc").to_string(),
+            action: Action::CloseOtherBuffers,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.reopen_closed_buffer").to_string(),
+            description: t!("cmd.reopen_closed_buffer_desc").to_string(),
+            action: Action::ReopenClosedBuffer,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.revert_file").to_string(),
             description: t!("cmd.revert_file_desc").to_string(),
@@ -229,6 +301,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_read_only").to_string(),
+            description: t!("cmd.toggle_read_only_desc").to_string(),
+            action: Action::ToggleReadOnly,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.recover_files").to_string(),
+            description: t!("cmd.recover_files_desc").to_string(),
+            action: Action::RecoverFiles,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.format_buffer").to_string(),
             description: t!("cmd.format_buffer_desc").to_string(),
@@ -253,6 +341,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.reindent_lines").to_string(),
+            description: t!("cmd.reindent_lines_desc").to_string(),
+            action: Action::ReindentLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.quit").to_string(),
             description: t!("cmd.quit_desc").to_string(),
@@ -294,6 +390,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.copy_as_ansi").to_string(),
+            description: t!("cmd.copy_as_ansi_desc").to_string(),
+            action: Action::CopyAsAnsi,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.cut").to_string(),
             description: t!("cmd.cut_desc").to_string(),
@@ -366,6 +470,46 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.to_camel_case").to_string(),
+            description: t!("cmd.to_camel_case_desc").to_string(),
+            action: Action::ToCamelCase,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.to_snake_case").to_string(),
+            description: t!("cmd.to_snake_case_desc").to_string(),
+            action: Action::ToSnakeCase,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.to_screaming_snake_case").to_string(),
+            description: t!("cmd.to_screaming_snake_case_desc").to_string(),
+            action: Action::ToScreamingSnakeCase,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.to_kebab_case").to_string(),
+            description: t!("cmd.to_kebab_case_desc").to_string(),
+            action: Action::ToKebabCase,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.to_title_case").to_string(),
+            description: t!("cmd.to_title_case_desc").to_string(),
+            action: Action::ToTitleCase,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.sort_lines").to_string(),
             description: t!("cmd.sort_lines_desc").to_string(),
@@ -374,6 +518,70 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.sort_lines_descending").to_string(),
+            description: t!("cmd.sort_lines_descending_desc").to_string(),
+            action: Action::SortLinesDescending,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.sort_lines_numeric").to_string(),
+            description: t!("cmd.sort_lines_numeric_desc").to_string(),
+            action: Action::SortLinesNumeric,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.unique_lines").to_string(),
+            description: t!("cmd.unique_lines_desc").to_string(),
+            action: Action::UniqueLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.reverse_lines").to_string(),
+            description: t!("cmd.reverse_lines_desc").to_string(),
+            action: Action::ReverseLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.shuffle_lines").to_string(),
+            description: t!("cmd.shuffle_lines_desc").to_string(),
+            action: Action::ShuffleLines,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.increment_number").to_string(),
+            description: t!("cmd.increment_number_desc").to_string(),
+            action: Action::IncrementNumber,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.decrement_number").to_string(),
+            description: t!("cmd.decrement_number_desc").to_string(),
+            action: Action::DecrementNumber,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.insert_sequence").to_string(),
+            description: t!("cmd.insert_sequence_desc").to_string(),
+            action: Action::InsertSequence,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.open_line").to_string(),
             description: t!("cmd.open_line_desc").to_string(),
@@ -431,6 +639,126 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.select_inside_brackets").to_string(),
+            description: t!("cmd.select_inside_brackets_desc").to_string(),
+            action: Action::SelectInsideBrackets,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_around_brackets").to_string(),
+            description: t!("cmd.select_around_brackets_desc").to_string(),
+            action: Action::SelectAroundBrackets,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_inside_quotes").to_string(),
+            description: t!("cmd.select_inside_quotes_desc").to_string(),
+            action: Action::SelectInsideQuotes,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_around_quotes").to_string(),
+            description: t!("cmd.select_around_quotes_desc").to_string(),
+            action: Action::SelectAroundQuotes,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_inside_tag").to_string(),
+            description: t!("cmd.select_inside_tag_desc").to_string(),
+            action: Action::SelectInsideTag,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_around_tag").to_string(),
+            description: t!("cmd.select_around_tag_desc").to_string(),
+            action: Action::SelectAroundTag,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_inside_function").to_string(),
+            description: t!("cmd.select_inside_function_desc").to_string(),
+            action: Action::SelectInsideFunction,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_around_function").to_string(),
+            description: t!("cmd.select_around_function_desc").to_string(),
+            action: Action::SelectAroundFunction,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_inside_argument").to_string(),
+            description: t!("cmd.select_inside_argument_desc").to_string(),
+            action: Action::SelectInsideArgument,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_around_argument").to_string(),
+            description: t!("cmd.select_around_argument_desc").to_string(),
+            action: Action::SelectAroundArgument,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_next_argument").to_string(),
+            description: t!("cmd.goto_next_argument_desc").to_string(),
+            action: Action::GotoNextArgument,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_previous_argument").to_string(),
+            description: t!("cmd.goto_previous_argument_desc").to_string(),
+            action: Action::GotoPreviousArgument,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.swap_argument_next").to_string(),
+            description: t!("cmd.swap_argument_next_desc").to_string(),
+            action: Action::SwapArgumentNext,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.swap_argument_previous").to_string(),
+            description: t!("cmd.swap_argument_previous_desc").to_string(),
+            action: Action::SwapArgumentPrevious,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.add_argument").to_string(),
+            description: t!("cmd.add_argument_desc").to_string(),
+            action: Action::AddArgument,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Multi-cursor
         Command {
             name: t!("cmd.add_cursor_above").to_string(),
@@ -464,6 +792,38 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.select_regex_in_selection").to_string(),
+            description: t!("cmd.select_regex_in_selection_desc").to_string(),
+            action: Action::SelectRegexInSelection,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.split_selection_on_regex").to_string(),
+            description: t!("cmd.split_selection_on_regex_desc").to_string(),
+            action: Action::SplitSelectionOnRegex,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.cursor_undo").to_string(),
+            description: t!("cmd.cursor_undo_desc").to_string(),
+            action: Action::CursorUndo,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.cursor_redo").to_string(),
+            description: t!("cmd.cursor_redo_desc").to_string(),
+            action: Action::CursorRedo,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Buffer navigation
         Command {
             name: t!("cmd.next_buffer").to_string(),
@@ -489,6 +849,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.switch_buffer_mru").to_string(),
+            description: t!("cmd.switch_buffer_mru_desc").to_string(),
+            action: Action::SwitchBufferMru,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.switch_to_tab_by_name").to_string(),
             description: t!("cmd.switch_to_tab_by_name_desc").to_string(),
@@ -579,6 +947,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_image_preview").to_string(),
+            description: t!("cmd.toggle_image_preview_desc").to_string(),
+            action: Action::ToggleImagePreview,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Buffer settings commands
         Command {
             name: t!("cmd.set_tab_size").to_string(),
@@ -588,6 +964,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.set_indentation").to_string(),
+            description: t!("cmd.set_indentation_desc").to_string(),
+            action: Action::SetIndentation,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.set_line_ending").to_string(),
             description: t!("cmd.set_line_ending_desc").to_string(),
@@ -628,6 +1012,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_whitespace").to_string(),
+            description: t!("cmd.toggle_whitespace_desc").to_string(),
+            action: Action::ToggleWhitespace,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_line_length_warnings").to_string(),
+            description: t!("cmd.toggle_line_length_warnings_desc").to_string(),
+            action: Action::ToggleLineLengthWarnings,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.reset_buffer_settings").to_string(),
             description: t!("cmd.reset_buffer_settings_desc").to_string(),
@@ -713,6 +1113,18 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_breadcrumbs").to_string(),
+            description: t!("cmd.toggle_breadcrumbs_desc").to_string(),
+            action: Action::ToggleBreadcrumbs,
+            contexts: vec![
+                KeyContext::Normal,
+                KeyContext::FileExplorer,
+                KeyContext::Terminal,
+            ],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.focus_file_explorer").to_string(),
             description: t!("cmd.focus_file_explorer_desc").to_string(),
@@ -761,6 +1173,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.explorer_undo_delete").to_string(),
+            description: t!("cmd.explorer_undo_delete_desc").to_string(),
+            action: Action::FileExplorerUndoDelete,
+            contexts: vec![KeyContext::FileExplorer],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.explorer_rename").to_string(),
             description: t!("cmd.explorer_rename_desc").to_string(),
@@ -794,7 +1214,71 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_conceal").to_string(),
+            description: t!("cmd.toggle_conceal_desc").to_string(),
+            action: Action::ToggleConceal,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Note: Compose mode commands removed - markdown_compose plugin provides these
+        Command {
+            name: t!("cmd.toggle_markdown_preview").to_string(),
+            description: t!("cmd.toggle_markdown_preview_desc").to_string(),
+            action: Action::ToggleMarkdownPreview,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.open_markdown_preview_to_side").to_string(),
+            description: t!("cmd.open_markdown_preview_to_side_desc").to_string(),
+            action: Action::OpenMarkdownPreviewToSide,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_spell_check").to_string(),
+            description: t!("cmd.toggle_spell_check_desc").to_string(),
+            action: Action::ToggleSpellCheck,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.spell_check_suggestions").to_string(),
+            description: t!("cmd.spell_check_suggestions_desc").to_string(),
+            action: Action::SpellCheckSuggestions,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.spell_check_add_word").to_string(),
+            description: t!("cmd.spell_check_add_word_desc").to_string(),
+            action: Action::SpellCheckAddWord,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.set_spell_language").to_string(),
+            description: t!("cmd.set_spell_language_desc").to_string(),
+            action: Action::SetSpellLanguage,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.adjust_color").to_string(),
+            description: t!("cmd.adjust_color_desc").to_string(),
+            action: Action::AdjustColor,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.set_background").to_string(),
             description: t!("cmd.set_background_desc").to_string(),
@@ -887,6 +1371,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.goto_anything").to_string(),
+            description: t!("cmd.goto_anything_desc").to_string(),
+            action: Action::GotoAnything,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.open_link_at_cursor").to_string(),
+            description: t!("cmd.open_link_at_cursor_desc").to_string(),
+            action: Action::OpenLinkAtCursor,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.smart_home").to_string(),
             description: t!("cmd.smart_home_desc").to_string(),
@@ -983,6 +1483,78 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.jump_back").to_string(),
+            description: t!("cmd.jump_back_desc").to_string(),
+            action: Action::JumpBack,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.jump_forward").to_string(),
+            description: t!("cmd.jump_forward_desc").to_string(),
+            action: Action::JumpForward,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.jump_list").to_string(),
+            description: t!("cmd.jump_list_desc").to_string(),
+            action: Action::ShowJumpList,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_tag").to_string(),
+            description: t!("cmd.goto_tag_desc").to_string(),
+            action: Action::GotoTag,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.select_tag").to_string(),
+            description: t!("cmd.select_tag_desc").to_string(),
+            action: Action::SelectTag,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.pop_tag").to_string(),
+            description: t!("cmd.pop_tag_desc").to_string(),
+            action: Action::PopTag,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.regenerate_tags").to_string(),
+            description: t!("cmd.regenerate_tags_desc").to_string(),
+            action: Action::RegenerateTags,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_last_change").to_string(),
+            description: t!("cmd.goto_last_change_desc").to_string(),
+            action: Action::GotoLastChange,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_next_change").to_string(),
+            description: t!("cmd.goto_next_change_desc").to_string(),
+            action: Action::GotoNextChange,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Smart editing
         Command {
             name: t!("cmd.toggle_comment").to_string(),
@@ -1034,6 +1606,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.extract_variable").to_string(),
+            description: t!("cmd.extract_variable_desc").to_string(),
+            action: Action::ExtractVariable,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.extract_function").to_string(),
+            description: t!("cmd.extract_function_desc").to_string(),
+            action: Action::ExtractFunction,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Bookmarks and Macros
         Command {
             name: t!("cmd.list_bookmarks").to_string(),
@@ -1132,6 +1720,62 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.show_lsp_log").to_string(),
+            description: t!("cmd.show_lsp_log_desc").to_string(),
+            action: Action::ShowLspLog,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_project_env").to_string(),
+            description: t!("cmd.show_project_env_desc").to_string(),
+            action: Action::ShowProjectEnv,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.reload_project_env").to_string(),
+            description: t!("cmd.reload_project_env_desc").to_string(),
+            action: Action::ReloadProjectEnv,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_terminal_info").to_string(),
+            description: t!("cmd.show_terminal_info_desc").to_string(),
+            action: Action::ShowTerminalInfo,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_profiler").to_string(),
+            description: t!("cmd.show_profiler_desc").to_string(),
+            action: Action::ShowProfiler,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_tutorial").to_string(),
+            description: t!("cmd.show_tutorial_desc").to_string(),
+            action: Action::ShowTutorial,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_cheat_sheet").to_string(),
+            description: t!("cmd.toggle_cheat_sheet_desc").to_string(),
+            action: Action::ToggleCheatSheet,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.clear_warnings").to_string(),
             description: t!("cmd.clear_warnings_desc").to_string(),
@@ -1149,6 +1793,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.show_config_source").to_string(),
+            description: t!("cmd.show_config_source_desc").to_string(),
+            action: Action::ShowConfigSource,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.trust_workspace").to_string(),
+            description: t!("cmd.trust_workspace_desc").to_string(),
+            action: Action::TrustWorkspace,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.toggle_inlay_hints").to_string(),
             description: t!("cmd.toggle_inlay_hints_desc").to_string(),
@@ -1166,6 +1826,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_light_dark_theme").to_string(),
+            description: t!("cmd.toggle_light_dark_theme_desc").to_string(),
+            action: Action::ToggleLightDarkTheme,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Keybinding map selection
         Command {
             name: t!("cmd.select_keybinding_map").to_string(),
@@ -1261,6 +1929,81 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        // Scratch buffers and REPL
+        Command {
+            name: t!("cmd.new_scratch_buffer").to_string(),
+            description: t!("cmd.new_scratch_buffer_desc").to_string(),
+            action: Action::NewScratchBuffer,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.send_to_repl").to_string(),
+            description: t!("cmd.send_to_repl_desc").to_string(),
+            action: Action::SendToRepl,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.send_to_repl_insert").to_string(),
+            description: t!("cmd.send_to_repl_insert_desc").to_string(),
+            action: Action::SendToReplInsert,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // Collaborative editing
+        Command {
+            name: t!("cmd.collab_host").to_string(),
+            description: t!("cmd.collab_host_desc").to_string(),
+            action: Action::CollabHost,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.collab_join").to_string(),
+            description: t!("cmd.collab_join_desc").to_string(),
+            action: Action::CollabJoin,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.collab_leave").to_string(),
+            description: t!("cmd.collab_leave_desc").to_string(),
+            action: Action::CollabLeave,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // Presentation mode
+        Command {
+            name: t!("cmd.start_presenting").to_string(),
+            description: t!("cmd.start_presenting_desc").to_string(),
+            action: Action::StartPresenting,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.follow_presentation").to_string(),
+            description: t!("cmd.follow_presentation_desc").to_string(),
+            action: Action::FollowPresentation,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.stop_presenting").to_string(),
+            description: t!("cmd.stop_presenting_desc").to_string(),
+            action: Action::StopPresenting,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
     ]
 }
 
@@ -0,0 +1,159 @@
+/// Cursor history of a buffer for "cursor undo"
+///
+/// The cursors are looked at before each action. When they changed since the
+/// last look, the old cursors are kept so Cursor Undo can go back to them,
+/// e.g. after accidentally collapsing many cursors into one. This is separate
+/// from the text undo history: going back and forth never changes the text.
+use crate::model::cursor::Cursors;
+
+/// Past and undone cursor states of one buffer
+#[derive(Clone, Debug)]
+pub struct CursorHistory {
+    undo: Vec<Cursors>,
+    redo: Vec<Cursors>,
+    /// The cursors as last seen
+    current: Option<Cursors>,
+    max_entries: usize,
+}
+
+impl CursorHistory {
+    /// Create a new history with default max entries (100)
+    pub fn new() -> Self {
+        Self::with_capacity(100)
+    }
+
+    /// Create a new history with specified max entries
+    pub fn with_capacity(max_entries: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            current: None,
+            max_entries,
+        }
+    }
+
+    /// Look at the cursors. If they changed since last seen, the old ones
+    /// can be gone back to, and nothing can be redone anymore.
+    pub fn observe(&mut self, cursors: &Cursors) {
+        match &self.current {
+            Some(current) if same_cursors(current, cursors) => return,
+            Some(current) => {
+                self.undo.push(current.clone());
+                if self.undo.len() > self.max_entries {
+                    self.undo.remove(0);
+                }
+                self.redo.clear();
+            }
+            None => {}
+        }
+        self.current = Some(cursors.clone());
+    }
+
+    /// The cursors from before the last change, if any
+    pub fn undo(&mut self, cursors: &Cursors) -> Option<Cursors> {
+        self.observe(cursors);
+        let previous = self.undo.pop()?;
+        self.redo.push(cursors.clone());
+        self.current = Some(previous.clone());
+        Some(previous)
+    }
+
+    /// The cursors from before the last undo, if any
+    pub fn redo(&mut self, cursors: &Cursors) -> Option<Cursors> {
+        self.observe(cursors);
+        let next = self.redo.pop()?;
+        self.undo.push(cursors.clone());
+        self.current = Some(next.clone());
+        Some(next)
+    }
+}
+
+impl Default for CursorHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether two cursor sets have the same positions and selections
+fn same_cursors(a: &Cursors, b: &Cursors) -> bool {
+    let key = |cursors: &Cursors| {
+        let mut key: Vec<_> = cursors
+            .iter()
+            .map(|(_, cursor)| (cursor.position, cursor.anchor))
+            .collect();
+        key.sort();
+        key
+    };
+    a.count() == b.count() && key(a) == key(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::cursor::Cursor;
+
+    /// Cursors at `positions`, without selections
+    fn cursors(positions: &[usize]) -> Cursors {
+        let mut cursors = Cursors::new();
+        cursors.primary_mut().position = positions[0];
+        for &position in &positions[1..] {
+            cursors.add(Cursor::new(position));
+        }
+        cursors
+    }
+
+    fn positions(cursors: &Cursors) -> Vec<usize> {
+        let mut positions = cursors.positions();
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn test_undo_goes_back_to_changed_cursors() {
+        let mut history = CursorHistory::new();
+        history.observe(&cursors(&[0, 10, 20]));
+        // Unchanged cursors aren't another step
+        history.observe(&cursors(&[0, 10, 20]));
+        history.observe(&cursors(&[5]));
+
+        let previous = history.undo(&cursors(&[6])).unwrap();
+        assert_eq!(positions(&previous), vec![5]);
+        let previous = history.undo(&previous).unwrap();
+        assert_eq!(positions(&previous), vec![0, 10, 20]);
+        assert!(history.undo(&previous).is_none());
+
+        let next = history.redo(&previous).unwrap();
+        assert_eq!(positions(&next), vec![5]);
+        let next = history.redo(&next).unwrap();
+        assert_eq!(positions(&next), vec![6]);
+        assert!(history.redo(&next).is_none());
+    }
+
+    #[test]
+    fn test_moving_after_undo_clears_redo() {
+        let mut history = CursorHistory::new();
+        history.observe(&cursors(&[1, 2]));
+        let previous = history.undo(&cursors(&[3])).unwrap();
+        assert_eq!(positions(&previous), vec![1, 2]);
+
+        history.observe(&cursors(&[4]));
+        assert!(history.redo(&cursors(&[4])).is_none());
+        assert_eq!(
+            positions(&history.undo(&cursors(&[4])).unwrap()),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut history = CursorHistory::with_capacity(2);
+        for position in 0..5 {
+            history.observe(&cursors(&[position]));
+        }
+        let previous = history.undo(&cursors(&[4])).unwrap();
+        assert_eq!(positions(&previous), vec![3]);
+        let previous = history.undo(&previous).unwrap();
+        assert_eq!(positions(&previous), vec![2]);
+        assert!(history.undo(&previous).is_none());
+    }
+}
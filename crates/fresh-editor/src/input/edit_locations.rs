@@ -0,0 +1,255 @@
+/// Ring of recent edit locations for "go to last change"
+///
+/// Every edit records where it happened, across all buffers. Consecutive
+/// edits on the same line are merged into one location, so typing a word
+/// doesn't fill the ring. Recorded positions are shifted by later inserts and
+/// deletes in the same buffer so they keep pointing at the edited text.
+use std::ops::Range;
+
+use crate::model::event::BufferId;
+
+/// A location where the buffer was edited
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EditLocation {
+    /// The buffer ID
+    pub buffer_id: BufferId,
+
+    /// The cursor position after the edit (byte offset)
+    pub position: usize,
+}
+
+impl EditLocation {
+    /// Create a new edit location
+    pub fn new(buffer_id: BufferId, position: usize) -> Self {
+        Self {
+            buffer_id,
+            position,
+        }
+    }
+}
+
+/// Recent edit locations, oldest first, with a browsing index
+///
+/// `index == entries.len()` means the user is not browsing the ring. Any new
+/// edit resets browsing, so the next "go to last change" starts from the
+/// newest edit again.
+#[derive(Clone, Debug)]
+pub struct EditLocations {
+    entries: Vec<EditLocation>,
+    index: usize,
+    max_entries: usize,
+}
+
+impl EditLocations {
+    /// Create a new ring with default max entries (100)
+    pub fn new() -> Self {
+        Self::with_capacity(100)
+    }
+
+    /// Create a new ring with specified max entries
+    pub fn with_capacity(max_entries: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            index: 0,
+            max_entries,
+        }
+    }
+
+    /// Record an edit location
+    ///
+    /// With `merge`, the newest location is replaced instead of adding a new
+    /// one (used for further edits on the same line).
+    pub fn record(&mut self, location: EditLocation, merge: bool) {
+        if merge && !self.entries.is_empty() {
+            self.entries.pop();
+        }
+        self.entries.push(location);
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    /// The most recent edit location
+    pub fn newest(&self) -> Option<EditLocation> {
+        self.entries.last().copied()
+    }
+
+    /// Shift locations after an insertion of `len` bytes at `position`
+    pub fn adjust_for_insert(&mut self, buffer_id: BufferId, position: usize, len: usize) {
+        for entry in &mut self.entries {
+            if entry.buffer_id == buffer_id && entry.position >= position {
+                entry.position += len;
+            }
+        }
+    }
+
+    /// Shift locations after a deletion of `range`
+    pub fn adjust_for_delete(&mut self, buffer_id: BufferId, range: Range<usize>) {
+        for entry in &mut self.entries {
+            if entry.buffer_id != buffer_id || entry.position <= range.start {
+                continue;
+            }
+            if entry.position >= range.end {
+                entry.position -= range.len();
+            } else {
+                entry.position = range.start;
+            }
+        }
+    }
+
+    /// Go to the next older edit location that isn't `current`
+    pub fn older(&mut self, current: EditLocation) -> Option<EditLocation> {
+        let mut index = self.index.min(self.entries.len());
+        while index > 0 {
+            index -= 1;
+            if self.entries[index] != current {
+                self.index = index;
+                return Some(self.entries[index]);
+            }
+        }
+        None
+    }
+
+    /// Go to the next newer edit location that isn't `current`
+    pub fn newer(&mut self, current: EditLocation) -> Option<EditLocation> {
+        let mut index = self.index;
+        while index + 1 < self.entries.len() {
+            index += 1;
+            if self.entries[index] != current {
+                self.index = index;
+                return Some(self.entries[index]);
+            }
+        }
+        None
+    }
+
+    /// Remove all locations in a buffer (called when the buffer is closed)
+    pub fn remove_buffer(&mut self, buffer_id: BufferId) {
+        let removed_before = self.entries[..self.index.min(self.entries.len())]
+            .iter()
+            .filter(|entry| entry.buffer_id == buffer_id)
+            .count();
+        self.entries.retain(|entry| entry.buffer_id != buffer_id);
+        self.index = (self.index - removed_before).min(self.entries.len());
+    }
+
+    /// All locations, oldest first
+    pub fn entries(&self) -> &[EditLocation] {
+        &self.entries
+    }
+
+    /// Check if the ring is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for EditLocations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(buffer_id: usize, position: usize) -> EditLocation {
+        EditLocation::new(BufferId(buffer_id), position)
+    }
+
+    #[test]
+    fn test_older_walks_back_skipping_current_location() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(2, 5), false);
+        ring.record(edit(1, 40), false);
+
+        // The cursor is still at the newest edit: go straight to the one before
+        assert_eq!(ring.older(edit(1, 40)), Some(edit(2, 5)));
+        assert_eq!(ring.older(edit(2, 5)), Some(edit(1, 10)));
+        assert_eq!(ring.older(edit(1, 10)), None);
+    }
+
+    #[test]
+    fn test_older_starts_at_newest_after_navigating_away() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(1, 40), false);
+
+        assert_eq!(ring.older(edit(3, 0)), Some(edit(1, 40)));
+    }
+
+    #[test]
+    fn test_newer() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(1, 40), false);
+        ring.record(edit(1, 90), false);
+        ring.older(edit(1, 90));
+        ring.older(edit(1, 40));
+
+        assert_eq!(ring.newer(edit(1, 10)), Some(edit(1, 40)));
+        assert_eq!(ring.newer(edit(1, 40)), Some(edit(1, 90)));
+        assert_eq!(ring.newer(edit(1, 90)), None);
+    }
+
+    #[test]
+    fn test_record_resets_browsing() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(1, 40), false);
+        ring.older(edit(3, 0));
+        ring.older(edit(1, 40));
+
+        ring.record(edit(1, 60), false);
+        assert_eq!(ring.newer(edit(1, 60)), None);
+        assert_eq!(ring.older(edit(3, 0)), Some(edit(1, 60)));
+    }
+
+    #[test]
+    fn test_merge_replaces_newest() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(1, 11), true);
+        ring.record(edit(1, 12), true);
+        assert_eq!(ring.entries(), &[edit(1, 12)]);
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let mut ring = EditLocations::with_capacity(2);
+        for position in 0..4 {
+            ring.record(edit(1, position), false);
+        }
+        assert_eq!(ring.entries(), &[edit(1, 2), edit(1, 3)]);
+    }
+
+    #[test]
+    fn test_adjust_for_insert_and_delete() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 5), false);
+        ring.record(edit(1, 20), false);
+        ring.record(edit(2, 20), false);
+
+        ring.adjust_for_insert(BufferId(1), 10, 3);
+        assert_eq!(ring.entries(), &[edit(1, 5), edit(1, 23), edit(2, 20)]);
+
+        // A location inside the deleted range moves to its start
+        ring.adjust_for_delete(BufferId(1), 3..8);
+        assert_eq!(ring.entries(), &[edit(1, 3), edit(1, 18), edit(2, 20)]);
+    }
+
+    #[test]
+    fn test_remove_buffer() {
+        let mut ring = EditLocations::new();
+        ring.record(edit(1, 10), false);
+        ring.record(edit(2, 20), false);
+        ring.record(edit(1, 30), false);
+
+        ring.remove_buffer(BufferId(1));
+        assert_eq!(ring.entries(), &[edit(2, 20)]);
+        assert_eq!(ring.older(edit(3, 0)), Some(edit(2, 20)));
+    }
+}
@@ -0,0 +1,261 @@
+/// Jump list for Ctrl+O / Ctrl+I navigation like Vim
+///
+/// Unlike position history, which records every (coalesced) cursor movement,
+/// the jump list only records explicit jumps: go to definition, search,
+/// go to line and buffer switches. The location the cursor jumped *from* is
+/// recorded, so going back returns to where the user was before the jump.
+///
+/// Each split keeps its own jump list.
+use crate::model::event::BufferId;
+
+/// A location in the jump list
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JumpEntry {
+    /// The buffer ID
+    pub buffer_id: BufferId,
+
+    /// The cursor position (byte offset)
+    pub position: usize,
+}
+
+impl JumpEntry {
+    /// Create a new jump entry
+    pub fn new(buffer_id: BufferId, position: usize) -> Self {
+        Self {
+            buffer_id,
+            position,
+        }
+    }
+}
+
+/// Jump list with a browsing index
+///
+/// `index == entries.len()` means the user is not browsing the list (the
+/// cursor is at its newest location). Otherwise `index` points at the entry
+/// the user last navigated to.
+#[derive(Clone, Debug)]
+pub struct JumpList {
+    entries: Vec<JumpEntry>,
+    index: usize,
+    max_entries: usize,
+}
+
+impl JumpList {
+    /// Create a new jump list with default max entries (100)
+    pub fn new() -> Self {
+        Self::with_capacity(100)
+    }
+
+    /// Create a new jump list with specified max entries
+    pub fn with_capacity(max_entries: usize) -> Self {
+        Self {
+            entries: Vec::new(),
+            index: 0,
+            max_entries,
+        }
+    }
+
+    /// Restore a jump list (e.g. from a saved session)
+    pub fn from_entries(entries: Vec<JumpEntry>, index: usize) -> Self {
+        let mut list = Self::new();
+        list.entries = entries;
+        list.index = index.min(list.entries.len());
+        list
+    }
+
+    /// Record the location a jump starts from
+    ///
+    /// A jump made while browsing drops the entries after the current one.
+    pub fn record(&mut self, entry: JumpEntry) {
+        if self.index < self.entries.len() {
+            self.entries.truncate(self.index + 1);
+        }
+
+        if self.entries.last() != Some(&entry) {
+            self.entries.push(entry);
+            if self.entries.len() > self.max_entries {
+                self.entries.remove(0);
+            }
+        }
+        self.index = self.entries.len();
+    }
+
+    /// Go back to the previous jump location
+    ///
+    /// `current` is the cursor location; when not browsing yet it is added to
+    /// the list so [`forward`](Self::forward) can return to it.
+    pub fn back(&mut self, current: JumpEntry) -> Option<JumpEntry> {
+        self.start_browsing(current);
+        if self.index == 0 {
+            return None;
+        }
+        self.index -= 1;
+        self.entries.get(self.index).copied()
+    }
+
+    /// Go forward to the next jump location
+    pub fn forward(&mut self) -> Option<JumpEntry> {
+        if self.index + 1 >= self.entries.len() {
+            return None;
+        }
+        self.index += 1;
+        self.entries.get(self.index).copied()
+    }
+
+    /// Go directly to the entry at `index` (e.g. chosen from a picker)
+    pub fn select(&mut self, index: usize, current: JumpEntry) -> Option<JumpEntry> {
+        self.start_browsing(current);
+        let entry = self.entries.get(index).copied()?;
+        self.index = index;
+        Some(entry)
+    }
+
+    /// Remove all entries for a buffer (called when the buffer is closed)
+    pub fn remove_buffer(&mut self, buffer_id: BufferId) {
+        let removed_before = self.entries[..self.index.min(self.entries.len())]
+            .iter()
+            .filter(|entry| entry.buffer_id == buffer_id)
+            .count();
+        self.entries.retain(|entry| entry.buffer_id != buffer_id);
+        self.index = (self.index - removed_before).min(self.entries.len());
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> &[JumpEntry] {
+        &self.entries
+    }
+
+    /// Current browsing index (`entries().len()` when not browsing)
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add the current location as the newest entry when not browsing yet
+    fn start_browsing(&mut self, current: JumpEntry) {
+        if self.index < self.entries.len() {
+            return;
+        }
+        if self.entries.last() != Some(&current) {
+            self.entries.push(current);
+        }
+        self.index = self.entries.len().saturating_sub(1);
+    }
+}
+
+impl Default for JumpList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(buffer_id: usize, position: usize) -> JumpEntry {
+        JumpEntry::new(BufferId(buffer_id), position)
+    }
+
+    #[test]
+    fn test_back_returns_jump_origins_newest_first() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(1, 500));
+
+        assert_eq!(list.back(jump(2, 0)), Some(jump(1, 500)));
+        assert_eq!(list.back(jump(1, 500)), Some(jump(1, 10)));
+        assert_eq!(list.back(jump(1, 10)), None);
+    }
+
+    #[test]
+    fn test_forward_returns_to_location_before_going_back() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.back(jump(2, 7));
+
+        assert_eq!(list.forward(), Some(jump(2, 7)));
+        assert_eq!(list.forward(), None);
+    }
+
+    #[test]
+    fn test_back_on_empty_list() {
+        let mut list = JumpList::new();
+        assert_eq!(list.back(jump(1, 0)), None);
+    }
+
+    #[test]
+    fn test_back_skips_current_location_duplicate() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(1, 20));
+
+        // Already at the newest entry: go straight to the one before it
+        assert_eq!(list.back(jump(1, 20)), Some(jump(1, 10)));
+    }
+
+    #[test]
+    fn test_record_while_browsing_drops_forward_entries() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(1, 20));
+        list.record(jump(1, 30));
+        list.back(jump(1, 40));
+        list.back(jump(1, 30));
+
+        // Jump away from 20
+        list.record(jump(1, 20));
+        assert_eq!(list.entries(), &[jump(1, 10), jump(1, 20)]);
+        assert_eq!(list.forward(), None);
+    }
+
+    #[test]
+    fn test_consecutive_duplicates_are_not_recorded() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(1, 10));
+        assert_eq!(list.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let mut list = JumpList::with_capacity(3);
+        for position in 0..5 {
+            list.record(jump(1, position));
+        }
+        assert_eq!(list.entries(), &[jump(1, 2), jump(1, 3), jump(1, 4)]);
+        assert_eq!(list.index(), 3);
+    }
+
+    #[test]
+    fn test_select() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(2, 20));
+
+        assert_eq!(list.select(0, jump(3, 0)), Some(jump(1, 10)));
+        assert_eq!(list.forward(), Some(jump(2, 20)));
+        assert_eq!(list.forward(), Some(jump(3, 0)));
+        assert_eq!(list.select(7, jump(3, 0)), None);
+    }
+
+    #[test]
+    fn test_remove_buffer_keeps_index_on_same_entry() {
+        let mut list = JumpList::new();
+        list.record(jump(1, 10));
+        list.record(jump(2, 20));
+        list.record(jump(1, 30));
+        list.back(jump(3, 0));
+        list.back(jump(1, 30));
+        assert_eq!(list.index(), 1);
+
+        list.remove_buffer(BufferId(1));
+        assert_eq!(list.entries(), &[jump(2, 20), jump(3, 0)]);
+        assert_eq!(list.index(), 0);
+        assert_eq!(list.forward(), Some(jump(3, 0)));
+    }
+}
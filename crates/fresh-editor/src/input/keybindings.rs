@@ -255,6 +255,23 @@ pub enum Action {
     SelectWord,
     SelectLine,
     ExpandSelection,
+    // Text objects: the contents of, or the whole of, the nearest enclosing one
+    SelectInsideBrackets,
+    SelectAroundBrackets,
+    SelectInsideQuotes,
+    SelectAroundQuotes,
+    SelectInsideTag,
+    SelectAroundTag,
+    SelectInsideFunction,
+    SelectAroundFunction,
+    SelectInsideArgument,
+    SelectAroundArgument,
+    // Arguments: move between them, swap one with its neighbour, add one
+    GotoNextArgument,
+    GotoPreviousArgument,
+    SwapArgumentNext,
+    SwapArgumentPrevious,
+    AddArgument,
 
     // Block/rectangular selection (column-wise)
     BlockSelectLeft,
@@ -282,6 +299,7 @@ pub enum Action {
     // Clipboard
     Copy,
     CopyWithTheme(String),
+    CopyAsAnsi,
     Cut,
     Paste,
 
@@ -296,22 +314,44 @@ pub enum Action {
     AddCursorBelow,
     AddCursorNextMatch,
     RemoveSecondaryCursors,
+    SelectRegexInSelection, // Select every match of a regex inside the selection
+    SplitSelectionOnRegex,  // Select the text between matches of a regex
+    CursorUndo,             // Go back to the cursors from before the last cursor change
+    CursorRedo,             // Go forward again after a cursor undo
 
     // File operations
     Save,
     SaveAs,
     Open,
     SwitchProject,
+    AddWorkspaceFolder,
+    RemoveWorkspaceFolder,
+    CompareFolders,
+    DirDiffOpen,
+    DirDiffCopyToRight,
+    DirDiffCopyToLeft,
+    DirDiffRefresh,
+    RunTask(String), // Run a project task by name; an empty name asks which
+    TaskOutputOpen,
+    TaskOutputRerun,
     New,
     Close,
     CloseTab,
+    TogglePinTab,
+    CloseAllBuffers,
+    CloseSavedBuffers,
+    CloseOtherBuffers,
+    ReopenClosedBuffer,
     Quit,
     ForceQuit,
     Revert,
     ToggleAutoRevert,
+    ToggleReadOnly,
+    RecoverFiles,
     FormatBuffer,
     TrimTrailingWhitespace,
     EnsureFinalNewline,
+    ReindentLines, // Recompute the indentation of the selected lines or the buffer
 
     // Navigation
     GotoLine,
@@ -363,14 +403,34 @@ pub enum Action {
     ShowWarnings,
     ShowStatusLog,
     ShowLspStatus,
+    ShowLspLog,
+    ShowProjectEnv,
+    ReloadProjectEnv,
+    ShowTerminalInfo,
+    ShowProfiler,
+    ShowTutorial,
+    ToggleCheatSheet,
     ClearWarnings,
     CommandPalette, // TODO: Consider dropping this now that we have QuickOpen
     /// Quick Open - unified prompt with prefix-based provider routing
     QuickOpen,
+    /// Go to Anything - line (:), symbol (@), workspace symbol (#) or file
+    GotoAnything,
+    /// Open the URL or file path at the cursor
+    OpenLinkAtCursor,
     ToggleLineWrap,
+    ToggleConceal,
     ToggleComposeMode,
     SetComposeWidth,
+    ToggleMarkdownPreview,
+    OpenMarkdownPreviewToSide,
+    ToggleSpellCheck,
+    SpellCheckSuggestions,
+    SpellCheckAddWord,
+    SetSpellLanguage,
+    AdjustColor,
     SelectTheme,
+    ToggleLightDarkTheme,
     SelectKeybindingMap,
     SelectCursorStyle,
     SelectLocale,
@@ -380,6 +440,8 @@ pub enum Action {
     PrevBuffer,
     SwitchToPreviousTab,
     SwitchToTabByName,
+    SwitchBufferMru,
+    SwitchBufferMruReverse,
 
     // Tab scrolling
     ScrollTabsLeft,
@@ -389,6 +451,21 @@ pub enum Action {
     NavigateBack,
     NavigateForward,
 
+    // Jump list navigation (per split)
+    JumpBack,     // Go back to the location before the last jump
+    JumpForward,  // Go forward again after jumping back
+    ShowJumpList, // Pick a location from the jump list
+
+    // Tag navigation (ctags / GNU Global)
+    GotoTag,        // Go to the definition of the word at the cursor
+    SelectTag,      // Pick a tag to go to
+    PopTag,         // Return to where the last tag jump was made from
+    RegenerateTags, // Rebuild the tags in the background
+
+    // Edit location navigation (across buffers)
+    GotoLastChange, // Go back to the previous edit location
+    GotoNextChange, // Go forward to the next edit location
+
     // Split view operations
     SplitHorizontal,
     SplitVertical,
@@ -450,6 +527,8 @@ pub enum Action {
     ToggleMenuBar,
     // Tab bar visibility
     ToggleTabBar,
+    // Breadcrumb bar visibility
+    ToggleBreadcrumbs,
     FocusFileExplorer,
     FocusEditor,
     FileExplorerUp,
@@ -463,6 +542,7 @@ pub enum Action {
     FileExplorerNewFile,
     FileExplorerNewDirectory,
     FileExplorerDelete,
+    FileExplorerUndoDelete,
     FileExplorerRename,
     FileExplorerToggleHidden,
     FileExplorerToggleGitignored,
@@ -477,6 +557,8 @@ pub enum Action {
     LspHover,
     LspSignatureHelp,
     LspCodeActions,
+    ExtractVariable,
+    ExtractFunction,
     LspRestart,
     LspStop,
     ToggleInlayHints,
@@ -486,20 +568,26 @@ pub enum Action {
     ToggleLineNumbers,
     ToggleMouseCapture,
     ToggleDebugHighlights, // Debug mode: show highlight/overlay byte ranges
+    ToggleImagePreview,
     SetBackground,
     SetBackgroundBlend,
 
     // Buffer settings (per-buffer overrides)
     SetTabSize,
+    SetIndentation,
     SetLineEnding,
     SetEncoding,
     SetLanguage,
     ToggleIndentationStyle,
     ToggleTabIndicators,
+    ToggleWhitespace,
+    ToggleLineLengthWarnings,
     ResetBufferSettings,
 
     // Config operations
     DumpConfig,
+    ShowConfigSource,
+    TrustWorkspace,
 
     // Search and replace
     Search,
@@ -551,10 +639,40 @@ pub enum Action {
     ShellCommand,        // Run shell command on buffer/selection, output to new buffer
     ShellCommandReplace, // Run shell command on buffer/selection, replace content
 
+    // Scratch buffers and REPL
+    NewScratchBuffer, // Create an unsaved buffer with a chosen language mode
+    SendToRepl,       // Evaluate selection/line with the language's REPL, show output
+    SendToReplInsert, // Evaluate selection/line with the language's REPL, insert output
+
+    // Collaborative editing
+    CollabHost,  // Share the active buffer with other editors over the network
+    CollabJoin,  // Join a buffer shared by another editor
+    CollabLeave, // Leave the collaboration session
+
+    // Presentation mode
+    StartPresenting, // Broadcast the active buffer, scroll position and cursor to viewers
+    FollowPresentation, // Follow another editor's presentation in a read-only buffer
+    StopPresenting,  // Stop presenting or following
+
     // Case conversion
-    ToUpperCase, // Convert selection to uppercase
-    ToLowerCase, // Convert selection to lowercase
-    SortLines,   // Sort selected lines alphabetically
+    ToUpperCase,          // Convert selection to uppercase
+    ToLowerCase,          // Convert selection to lowercase
+    ToCamelCase,          // Convert identifiers to camelCase
+    ToSnakeCase,          // Convert identifiers to snake_case
+    ToScreamingSnakeCase, // Convert identifiers to SCREAMING_SNAKE_CASE
+    ToKebabCase,          // Convert identifiers to kebab-case
+    ToTitleCase,          // Convert identifiers to Title Case
+    SortLines,            // Sort selected lines alphabetically
+    SortLinesDescending,  // Sort selected lines in reverse alphabetical order
+    SortLinesNumeric,     // Sort selected lines by their leading number
+    UniqueLines,          // Remove duplicate lines, keeping the first of each
+    ReverseLines,         // Reverse the order of the selected lines
+    ShuffleLines,         // Shuffle the selected lines randomly
+
+    // Numbers
+    IncrementNumber, // Add one to the number under or after each cursor
+    DecrementNumber, // Subtract one from the number under or after each cursor
+    InsertSequence,  // Insert 1, 2, 3... across the cursors
 
     // Input calibration
     CalibrateInput, // Open the input calibration wizard
@@ -618,6 +736,21 @@ impl Action {
             "select_word" => Self::SelectWord,
             "select_line" => Self::SelectLine,
             "expand_selection" => Self::ExpandSelection,
+            "select_inside_brackets" => Self::SelectInsideBrackets,
+            "select_around_brackets" => Self::SelectAroundBrackets,
+            "select_inside_quotes" => Self::SelectInsideQuotes,
+            "select_around_quotes" => Self::SelectAroundQuotes,
+            "select_inside_tag" => Self::SelectInsideTag,
+            "select_around_tag" => Self::SelectAroundTag,
+            "select_inside_function" => Self::SelectInsideFunction,
+            "select_around_function" => Self::SelectAroundFunction,
+            "select_inside_argument" => Self::SelectInsideArgument,
+            "select_around_argument" => Self::SelectAroundArgument,
+            "goto_next_argument" => Self::GotoNextArgument,
+            "goto_previous_argument" => Self::GotoPreviousArgument,
+            "swap_argument_next" => Self::SwapArgumentNext,
+            "swap_argument_previous" => Self::SwapArgumentPrevious,
+            "add_argument" => Self::AddArgument,
 
             // Block/rectangular selection
             "block_select_left" => Self::BlockSelectLeft,
@@ -643,6 +776,7 @@ impl Action {
                 let theme = args.get("theme").and_then(|v| v.as_str()).unwrap_or("");
                 Self::CopyWithTheme(theme.to_string())
             }
+            "copy_as_ansi" => Self::CopyAsAnsi,
             "cut" => Self::Cut,
             "paste" => Self::Paste,
 
@@ -656,19 +790,44 @@ impl Action {
             "add_cursor_below" => Self::AddCursorBelow,
             "add_cursor_next_match" => Self::AddCursorNextMatch,
             "remove_secondary_cursors" => Self::RemoveSecondaryCursors,
+            "select_regex_in_selection" => Self::SelectRegexInSelection,
+            "split_selection_on_regex" => Self::SplitSelectionOnRegex,
+            "cursor_undo" => Self::CursorUndo,
+            "cursor_redo" => Self::CursorRedo,
 
             "save" => Self::Save,
             "save_as" => Self::SaveAs,
             "open" => Self::Open,
             "switch_project" => Self::SwitchProject,
+            "add_workspace_folder" => Self::AddWorkspaceFolder,
+            "remove_workspace_folder" => Self::RemoveWorkspaceFolder,
+            "compare_folders" => Self::CompareFolders,
+            "dir_diff_open" => Self::DirDiffOpen,
+            "dir_diff_copy_to_right" => Self::DirDiffCopyToRight,
+            "dir_diff_copy_to_left" => Self::DirDiffCopyToLeft,
+            "dir_diff_refresh" => Self::DirDiffRefresh,
+            "run_task" => {
+                let name = args.get("name").and_then(|v| v.as_str()).unwrap_or("");
+                Self::RunTask(name.to_string())
+            }
+            "task_output_open" => Self::TaskOutputOpen,
+            "task_output_rerun" => Self::TaskOutputRerun,
             "new" => Self::New,
             "close" => Self::Close,
             "close_tab" => Self::CloseTab,
+            "toggle_pin_tab" => Self::TogglePinTab,
+            "close_all_buffers" => Self::CloseAllBuffers,
+            "close_saved_buffers" => Self::CloseSavedBuffers,
+            "close_other_buffers" => Self::CloseOtherBuffers,
+            "reopen_closed_buffer" => Self::ReopenClosedBuffer,
             "quit" => Self::Quit,
             "force_quit" => Self::ForceQuit,
             "revert" => Self::Revert,
             "toggle_auto_revert" => Self::ToggleAutoRevert,
+            "toggle_read_only" => Self::ToggleReadOnly,
+            "recover_files" => Self::RecoverFiles,
             "format_buffer" => Self::FormatBuffer,
+            "reindent_lines" => Self::ReindentLines,
             "goto_line" => Self::GotoLine,
             "goto_matching_bracket" => Self::GoToMatchingBracket,
             "jump_to_next_error" => Self::JumpToNextError,
@@ -713,18 +872,46 @@ impl Action {
             "show_warnings" => Self::ShowWarnings,
             "show_status_log" => Self::ShowStatusLog,
             "show_lsp_status" => Self::ShowLspStatus,
+            "show_lsp_log" => Self::ShowLspLog,
+            "show_project_env" => Self::ShowProjectEnv,
+            "reload_project_env" => Self::ReloadProjectEnv,
+            "show_terminal_info" => Self::ShowTerminalInfo,
+            "show_profiler" => Self::ShowProfiler,
+            "show_tutorial" => Self::ShowTutorial,
+            "toggle_cheat_sheet" => Self::ToggleCheatSheet,
             "clear_warnings" => Self::ClearWarnings,
             "command_palette" => Self::CommandPalette,
             "quick_open" => Self::QuickOpen,
+            "goto_anything" => Self::GotoAnything,
+            "open_link_at_cursor" => Self::OpenLinkAtCursor,
             "toggle_line_wrap" => Self::ToggleLineWrap,
+            "toggle_conceal" => Self::ToggleConceal,
             "toggle_compose_mode" => Self::ToggleComposeMode,
             "set_compose_width" => Self::SetComposeWidth,
+            "toggle_markdown_preview" => Self::ToggleMarkdownPreview,
+            "open_markdown_preview_to_side" => Self::OpenMarkdownPreviewToSide,
+            "toggle_spell_check" => Self::ToggleSpellCheck,
+            "spell_check_suggestions" => Self::SpellCheckSuggestions,
+            "spell_check_add_word" => Self::SpellCheckAddWord,
+            "set_spell_language" => Self::SetSpellLanguage,
+            "adjust_color" => Self::AdjustColor,
 
             "next_buffer" => Self::NextBuffer,
             "prev_buffer" => Self::PrevBuffer,
+            "switch_buffer_mru" => Self::SwitchBufferMru,
+            "switch_buffer_mru_reverse" => Self::SwitchBufferMruReverse,
 
             "navigate_back" => Self::NavigateBack,
             "navigate_forward" => Self::NavigateForward,
+            "jump_back" => Self::JumpBack,
+            "jump_forward" => Self::JumpForward,
+            "jump_list" => Self::ShowJumpList,
+            "goto_tag" => Self::GotoTag,
+            "select_tag" => Self::SelectTag,
+            "pop_tag" => Self::PopTag,
+            "regenerate_tags" => Self::RegenerateTags,
+            "goto_last_change" => Self::GotoLastChange,
+            "goto_next_change" => Self::GotoNextChange,
 
             "split_horizontal" => Self::SplitHorizontal,
             "split_vertical" => Self::SplitVertical,
@@ -775,6 +962,7 @@ impl Action {
             "toggle_file_explorer" => Self::ToggleFileExplorer,
             "toggle_menu_bar" => Self::ToggleMenuBar,
             "toggle_tab_bar" => Self::ToggleTabBar,
+            "toggle_breadcrumbs" => Self::ToggleBreadcrumbs,
             "focus_file_explorer" => Self::FocusFileExplorer,
             "focus_editor" => Self::FocusEditor,
             "file_explorer_up" => Self::FileExplorerUp,
@@ -788,6 +976,7 @@ impl Action {
             "file_explorer_new_file" => Self::FileExplorerNewFile,
             "file_explorer_new_directory" => Self::FileExplorerNewDirectory,
             "file_explorer_delete" => Self::FileExplorerDelete,
+            "file_explorer_undo_delete" => Self::FileExplorerUndoDelete,
             "file_explorer_rename" => Self::FileExplorerRename,
             "file_explorer_toggle_hidden" => Self::FileExplorerToggleHidden,
             "file_explorer_toggle_gitignored" => Self::FileExplorerToggleGitignored,
@@ -801,6 +990,8 @@ impl Action {
             "lsp_hover" => Self::LspHover,
             "lsp_signature_help" => Self::LspSignatureHelp,
             "lsp_code_actions" => Self::LspCodeActions,
+            "extract_variable" => Self::ExtractVariable,
+            "extract_function" => Self::ExtractFunction,
             "lsp_restart" => Self::LspRestart,
             "lsp_stop" => Self::LspStop,
             "toggle_inlay_hints" => Self::ToggleInlayHints,
@@ -809,9 +1000,11 @@ impl Action {
             "toggle_line_numbers" => Self::ToggleLineNumbers,
             "toggle_mouse_capture" => Self::ToggleMouseCapture,
             "toggle_debug_highlights" => Self::ToggleDebugHighlights,
+            "toggle_image_preview" => Self::ToggleImagePreview,
             "set_background" => Self::SetBackground,
             "set_background_blend" => Self::SetBackgroundBlend,
             "select_theme" => Self::SelectTheme,
+            "toggle_light_dark_theme" => Self::ToggleLightDarkTheme,
             "select_keybinding_map" => Self::SelectKeybindingMap,
             "select_locale" => Self::SelectLocale,
 
@@ -819,11 +1012,16 @@ impl Action {
             "set_tab_size" => Self::SetTabSize,
             "set_line_ending" => Self::SetLineEnding,
             "set_encoding" => Self::SetEncoding,
+            "set_indentation" => Self::SetIndentation,
             "toggle_indentation_style" => Self::ToggleIndentationStyle,
             "toggle_tab_indicators" => Self::ToggleTabIndicators,
+            "toggle_whitespace" => Self::ToggleWhitespace,
+            "toggle_line_length_warnings" => Self::ToggleLineLengthWarnings,
             "reset_buffer_settings" => Self::ResetBufferSettings,
 
             "dump_config" => Self::DumpConfig,
+            "show_config_source" => Self::ShowConfigSource,
+            "trust_workspace" => Self::TrustWorkspace,
 
             "search" => Self::Search,
             "find_in_selection" => Self::FindInSelection,
@@ -862,11 +1060,35 @@ impl Action {
             // Shell command actions
             "shell_command" => Self::ShellCommand,
             "shell_command_replace" => Self::ShellCommandReplace,
+            "new_scratch_buffer" => Self::NewScratchBuffer,
+            "send_to_repl" => Self::SendToRepl,
+            "send_to_repl_insert" => Self::SendToReplInsert,
+            "collab_host" => Self::CollabHost,
+            "collab_join" => Self::CollabJoin,
+            "collab_leave" => Self::CollabLeave,
+            "start_presenting" => Self::StartPresenting,
+            "follow_presentation" => Self::FollowPresentation,
+            "stop_presenting" => Self::StopPresenting,
 
             // Case conversion
             "to_upper_case" => Self::ToUpperCase,
             "to_lower_case" => Self::ToLowerCase,
+            "to_camel_case" => Self::ToCamelCase,
+            "to_snake_case" => Self::ToSnakeCase,
+            "to_screaming_snake_case" => Self::ToScreamingSnakeCase,
+            "to_kebab_case" => Self::ToKebabCase,
+            "to_title_case" => Self::ToTitleCase,
             "sort_lines" => Self::SortLines,
+            "sort_lines_descending" => Self::SortLinesDescending,
+            "sort_lines_numeric" => Self::SortLinesNumeric,
+            "unique_lines" => Self::UniqueLines,
+            "reverse_lines" => Self::ReverseLines,
+            "shuffle_lines" => Self::ShuffleLines,
+
+            // Numbers
+            "increment_number" => Self::IncrementNumber,
+            "decrement_number" => Self::DecrementNumber,
+            "insert_sequence" => Self::InsertSequence,
 
             // Input calibration
             "calibrate_input" => Self::CalibrateInput,
@@ -929,6 +1151,21 @@ impl Action {
                 | Action::SelectWord
                 | Action::SelectLine
                 | Action::ExpandSelection
+                | Action::SelectInsideBrackets
+                | Action::SelectAroundBrackets
+                | Action::SelectInsideQuotes
+                | Action::SelectAroundQuotes
+                | Action::SelectInsideTag
+                | Action::SelectAroundTag
+                | Action::SelectInsideFunction
+                | Action::SelectAroundFunction
+                | Action::SelectInsideArgument
+                | Action::SelectAroundArgument
+                | Action::GotoNextArgument
+                | Action::GotoPreviousArgument
+                | Action::SwapArgumentNext
+                | Action::SwapArgumentPrevious
+                | Action::AddArgument
                 // Block selection
                 | Action::BlockSelectLeft
                 | Action::BlockSelectRight
@@ -1205,6 +1442,9 @@ impl KeybindingResolver {
                 // Tab/buffer navigation
                 | Action::NextBuffer
                 | Action::PrevBuffer
+                | Action::SwitchBufferMru
+                | Action::SwitchBufferMruReverse
+                | Action::TogglePinTab
                 | Action::Close
                 | Action::ScrollTabsLeft
                 | Action::ScrollTabsRight
@@ -1668,6 +1908,21 @@ impl KeybindingResolver {
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
             Action::ExpandSelection => t!("action.expand_selection"),
+            Action::SelectInsideBrackets => t!("action.select_inside_brackets"),
+            Action::SelectAroundBrackets => t!("action.select_around_brackets"),
+            Action::SelectInsideQuotes => t!("action.select_inside_quotes"),
+            Action::SelectAroundQuotes => t!("action.select_around_quotes"),
+            Action::SelectInsideTag => t!("action.select_inside_tag"),
+            Action::SelectAroundTag => t!("action.select_around_tag"),
+            Action::SelectInsideFunction => t!("action.select_inside_function"),
+            Action::SelectAroundFunction => t!("action.select_around_function"),
+            Action::SelectInsideArgument => t!("action.select_inside_argument"),
+            Action::SelectAroundArgument => t!("action.select_around_argument"),
+            Action::GotoNextArgument => t!("action.goto_next_argument"),
+            Action::GotoPreviousArgument => t!("action.goto_previous_argument"),
+            Action::SwapArgumentNext => t!("action.swap_argument_next"),
+            Action::SwapArgumentPrevious => t!("action.swap_argument_previous"),
+            Action::AddArgument => t!("action.add_argument"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
             Action::BlockSelectUp => t!("action.block_select_up"),
@@ -1686,6 +1941,7 @@ impl KeybindingResolver {
             Action::Copy => t!("action.copy"),
             Action::CopyWithTheme(theme) if theme.is_empty() => t!("action.copy_with_formatting"),
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
+            Action::CopyAsAnsi => t!("action.copy_as_ansi"),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
             Action::YankWordForward => t!("action.yank_word_forward"),
@@ -1696,20 +1952,43 @@ impl KeybindingResolver {
             Action::AddCursorBelow => t!("action.add_cursor_below"),
             Action::AddCursorNextMatch => t!("action.add_cursor_next_match"),
             Action::RemoveSecondaryCursors => t!("action.remove_secondary_cursors"),
+            Action::SelectRegexInSelection => t!("action.select_regex_in_selection"),
+            Action::SplitSelectionOnRegex => t!("action.split_selection_on_regex"),
+            Action::CursorUndo => t!("action.cursor_undo"),
+            Action::CursorRedo => t!("action.cursor_redo"),
             Action::Save => t!("action.save"),
             Action::SaveAs => t!("action.save_as"),
             Action::Open => t!("action.open"),
             Action::SwitchProject => t!("action.switch_project"),
+            Action::AddWorkspaceFolder => t!("action.add_workspace_folder"),
+            Action::RemoveWorkspaceFolder => t!("action.remove_workspace_folder"),
+            Action::CompareFolders => t!("action.compare_folders"),
+            Action::DirDiffOpen => t!("action.dir_diff_open"),
+            Action::DirDiffCopyToRight => t!("action.dir_diff_copy_to_right"),
+            Action::DirDiffCopyToLeft => t!("action.dir_diff_copy_to_left"),
+            Action::DirDiffRefresh => t!("action.dir_diff_refresh"),
+            Action::RunTask(name) if name.is_empty() => t!("action.run_task"),
+            Action::RunTask(name) => t!("action.run_named_task", name = name),
+            Action::TaskOutputOpen => t!("action.task_output_open"),
+            Action::TaskOutputRerun => t!("action.task_output_rerun"),
             Action::New => t!("action.new"),
             Action::Close => t!("action.close"),
             Action::CloseTab => t!("action.close_tab"),
+            Action::TogglePinTab => t!("action.toggle_pin_tab"),
+            Action::CloseAllBuffers => t!("action.close_all_buffers"),
+            Action::CloseSavedBuffers => t!("action.close_saved_buffers"),
+            Action::CloseOtherBuffers => t!("action.close_other_buffers"),
+            Action::ReopenClosedBuffer => t!("action.reopen_closed_buffer"),
             Action::Quit => t!("action.quit"),
             Action::ForceQuit => t!("action.force_quit"),
             Action::Revert => t!("action.revert"),
             Action::ToggleAutoRevert => t!("action.toggle_auto_revert"),
+            Action::ToggleReadOnly => t!("action.toggle_read_only"),
+            Action::RecoverFiles => t!("action.recover_files"),
             Action::FormatBuffer => t!("action.format_buffer"),
             Action::TrimTrailingWhitespace => t!("action.trim_trailing_whitespace"),
             Action::EnsureFinalNewline => t!("action.ensure_final_newline"),
+            Action::ReindentLines => t!("action.reindent_lines"),
             Action::GotoLine => t!("action.goto_line"),
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
@@ -1745,16 +2024,42 @@ impl KeybindingResolver {
             Action::ShowWarnings => t!("action.show_warnings"),
             Action::ShowStatusLog => t!("action.show_status_log"),
             Action::ShowLspStatus => t!("action.show_lsp_status"),
+            Action::ShowLspLog => t!("action.show_lsp_log"),
+            Action::ShowProjectEnv => t!("action.show_project_env"),
+            Action::ReloadProjectEnv => t!("action.reload_project_env"),
+            Action::ShowTerminalInfo => t!("action.show_terminal_info"),
+            Action::ShowProfiler => t!("action.show_profiler"),
+            Action::ShowTutorial => t!("action.show_tutorial"),
+            Action::ToggleCheatSheet => t!("action.toggle_cheat_sheet"),
             Action::ClearWarnings => t!("action.clear_warnings"),
             Action::CommandPalette => t!("action.command_palette"),
             Action::QuickOpen => t!("action.quick_open"),
+            Action::GotoAnything => t!("action.goto_anything"),
+            Action::OpenLinkAtCursor => t!("action.open_link_at_cursor"),
             Action::ToggleLineWrap => t!("action.toggle_line_wrap"),
+            Action::ToggleConceal => t!("action.toggle_conceal"),
             Action::ToggleComposeMode => t!("action.toggle_compose_mode"),
             Action::SetComposeWidth => t!("action.set_compose_width"),
+            Action::ToggleMarkdownPreview => t!("action.toggle_markdown_preview"),
+            Action::OpenMarkdownPreviewToSide => t!("action.open_markdown_preview_to_side"),
+            Action::ToggleSpellCheck => t!("action.toggle_spell_check"),
+            Action::SpellCheckSuggestions => t!("action.spell_check_suggestions"),
+            Action::SpellCheckAddWord => t!("action.spell_check_add_word"),
+            Action::SetSpellLanguage => t!("action.set_spell_language"),
+            Action::AdjustColor => t!("action.adjust_color"),
             Action::NextBuffer => t!("action.next_buffer"),
             Action::PrevBuffer => t!("action.prev_buffer"),
             Action::NavigateBack => t!("action.navigate_back"),
             Action::NavigateForward => t!("action.navigate_forward"),
+            Action::JumpBack => t!("action.jump_back"),
+            Action::JumpForward => t!("action.jump_forward"),
+            Action::ShowJumpList => t!("action.jump_list"),
+            Action::GotoTag => t!("action.goto_tag"),
+            Action::SelectTag => t!("action.select_tag"),
+            Action::PopTag => t!("action.pop_tag"),
+            Action::RegenerateTags => t!("action.regenerate_tags"),
+            Action::GotoLastChange => t!("action.goto_last_change"),
+            Action::GotoNextChange => t!("action.goto_next_change"),
             Action::SplitHorizontal => t!("action.split_horizontal"),
             Action::SplitVertical => t!("action.split_vertical"),
             Action::CloseSplit => t!("action.close_split"),
@@ -1804,6 +2109,7 @@ impl KeybindingResolver {
             Action::ToggleFileExplorer => t!("action.toggle_file_explorer"),
             Action::ToggleMenuBar => t!("action.toggle_menu_bar"),
             Action::ToggleTabBar => t!("action.toggle_tab_bar"),
+            Action::ToggleBreadcrumbs => t!("action.toggle_breadcrumbs"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::FileExplorerUp => t!("action.file_explorer_up"),
@@ -1817,6 +2123,7 @@ impl KeybindingResolver {
             Action::FileExplorerNewFile => t!("action.file_explorer_new_file"),
             Action::FileExplorerNewDirectory => t!("action.file_explorer_new_directory"),
             Action::FileExplorerDelete => t!("action.file_explorer_delete"),
+            Action::FileExplorerUndoDelete => t!("action.file_explorer_undo_delete"),
             Action::FileExplorerRename => t!("action.file_explorer_rename"),
             Action::FileExplorerToggleHidden => t!("action.file_explorer_toggle_hidden"),
             Action::FileExplorerToggleGitignored => t!("action.file_explorer_toggle_gitignored"),
@@ -1829,6 +2136,8 @@ impl KeybindingResolver {
             Action::LspHover => t!("action.lsp_hover"),
             Action::LspSignatureHelp => t!("action.lsp_signature_help"),
             Action::LspCodeActions => t!("action.lsp_code_actions"),
+            Action::ExtractVariable => t!("action.extract_variable"),
+            Action::ExtractFunction => t!("action.extract_function"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
@@ -1836,16 +2145,22 @@ impl KeybindingResolver {
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
             Action::ToggleMouseCapture => t!("action.toggle_mouse_capture"),
             Action::ToggleDebugHighlights => t!("action.toggle_debug_highlights"),
+            Action::ToggleImagePreview => t!("action.toggle_image_preview"),
             Action::SetBackground => t!("action.set_background"),
             Action::SetBackgroundBlend => t!("action.set_background_blend"),
             Action::SetTabSize => t!("action.set_tab_size"),
             Action::SetLineEnding => t!("action.set_line_ending"),
             Action::SetEncoding => t!("action.set_encoding"),
+            Action::SetIndentation => t!("action.set_indentation"),
             Action::SetLanguage => t!("action.set_language"),
             Action::ToggleIndentationStyle => t!("action.toggle_indentation_style"),
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
+            Action::ToggleWhitespace => t!("action.toggle_whitespace"),
+            Action::ToggleLineLengthWarnings => t!("action.toggle_line_length_warnings"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
+            Action::ShowConfigSource => t!("action.show_config_source"),
+            Action::TrustWorkspace => t!("action.trust_workspace"),
             Action::Search => t!("action.search"),
             Action::FindInSelection => t!("action.find_in_selection"),
             Action::FindNext => t!("action.find_next"),
@@ -1867,11 +2182,14 @@ impl KeybindingResolver {
             Action::ScrollTabsLeft => t!("action.scroll_tabs_left"),
             Action::ScrollTabsRight => t!("action.scroll_tabs_right"),
             Action::SelectTheme => t!("action.select_theme"),
+            Action::ToggleLightDarkTheme => t!("action.toggle_light_dark_theme"),
             Action::SelectKeybindingMap => t!("action.select_keybinding_map"),
             Action::SelectCursorStyle => t!("action.select_cursor_style"),
             Action::SelectLocale => t!("action.select_locale"),
             Action::SwitchToPreviousTab => t!("action.switch_to_previous_tab"),
             Action::SwitchToTabByName => t!("action.switch_to_tab_by_name"),
+            Action::SwitchBufferMru => t!("action.switch_buffer_mru"),
+            Action::SwitchBufferMruReverse => t!("action.switch_buffer_mru_reverse"),
             Action::OpenTerminal => t!("action.open_terminal"),
             Action::CloseTerminal => t!("action.close_terminal"),
             Action::FocusTerminal => t!("action.focus_terminal"),
@@ -1890,9 +2208,31 @@ impl KeybindingResolver {
             Action::SettingsDecrement => t!("action.settings_decrement"),
             Action::ShellCommand => t!("action.shell_command"),
             Action::ShellCommandReplace => t!("action.shell_command_replace"),
+            Action::NewScratchBuffer => t!("action.new_scratch_buffer"),
+            Action::SendToRepl => t!("action.send_to_repl"),
+            Action::SendToReplInsert => t!("action.send_to_repl_insert"),
+            Action::CollabHost => t!("action.collab_host"),
+            Action::CollabJoin => t!("action.collab_join"),
+            Action::CollabLeave => t!("action.collab_leave"),
+            Action::StartPresenting => t!("action.start_presenting"),
+            Action::FollowPresentation => t!("action.follow_presentation"),
+            Action::StopPresenting => t!("action.stop_presenting"),
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
+            Action::ToCamelCase => t!("action.to_camel_case"),
+            Action::ToSnakeCase => t!("action.to_snake_case"),
+            Action::ToScreamingSnakeCase => t!("action.to_screaming_snake_case"),
+            Action::ToKebabCase => t!("action.to_kebab_case"),
+            Action::ToTitleCase => t!("action.to_title_case"),
             Action::SortLines => t!("action.sort_lines"),
+            Action::SortLinesDescending => t!("action.sort_lines_descending"),
+            Action::SortLinesNumeric => t!("action.sort_lines_numeric"),
+            Action::UniqueLines => t!("action.unique_lines"),
+            Action::ReverseLines => t!("action.reverse_lines"),
+            Action::ShuffleLines => t!("action.shuffle_lines"),
+            Action::IncrementNumber => t!("action.increment_number"),
+            Action::DecrementNumber => t!("action.decrement_number"),
+            Action::InsertSequence => t!("action.insert_sequence"),
             Action::CalibrateInput => t!("action.calibrate_input"),
             Action::EventDebug => t!("action.event_debug"),
             Action::None => t!("action.none"),
@@ -7,9 +7,12 @@ pub mod buffer_mode;
 pub mod command_registry;
 pub mod commands;
 pub mod composite_router;
+pub mod cursor_history;
+pub mod edit_locations;
 pub mod fuzzy;
 pub mod handler;
 pub mod input_history;
+pub mod jump_list;
 pub mod key_translator;
 pub mod keybindings;
 pub mod multi_cursor;
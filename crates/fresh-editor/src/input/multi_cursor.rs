@@ -50,19 +50,6 @@ fn success_result(cursor: Cursor, state: &EditorState) -> AddCursorResult {
     }
 }
 
-/// Adjust cursor position if it's on a newline character
-/// Returns position + 1 if cursor is at a newline, otherwise returns position unchanged
-fn adjust_position_for_newline(state: &mut EditorState, position: usize) -> usize {
-    if position < state.buffer.len() {
-        if let Ok(byte_at_cursor) = state.buffer.get_text_range_mut(position, 1) {
-            if byte_at_cursor.first() == Some(&b'\n') {
-                return position + 1;
-            }
-        }
-    }
-    position
-}
-
 /// Add a cursor at the next occurrence of the selected text
 /// If no selection, selects the entire word at cursor position first
 pub fn add_cursor_at_next_match(state: &mut EditorState) -> AddCursorResult {
@@ -203,12 +190,8 @@ pub fn add_cursor_at_next_match(state: &mut EditorState) -> AddCursorResult {
 pub fn add_cursor_above(state: &mut EditorState) -> AddCursorResult {
     let position = state.cursors.primary().position;
 
-    // Adjust position if cursor is at a newline character
-    // This handles cases where add_cursor_above/below places cursor at same column
-    let adjusted_position = adjust_position_for_newline(state, position);
-
     // Get current line info
-    let Some(info) = get_cursor_line_info(state, adjusted_position) else {
+    let Some(info) = get_cursor_line_info(state, position) else {
         return AddCursorResult::Failed {
             message: "Unable to find current line".to_string(),
         };
@@ -221,10 +204,9 @@ pub fn add_cursor_above(state: &mut EditorState) -> AddCursorResult {
         };
     }
 
-    // Navigate to previous line using iterator
-    let mut iter = state.buffer.line_iterator(adjusted_position, 80);
-    iter.next_line(); // Consume current line
-    iter.prev(); // Move back to current line
+    // Navigate to previous line using iterator. Starting at the current line's
+    // start means prev() also works when the current line has no trailing newline.
+    let mut iter = state.buffer.line_iterator(info.line_start, 80);
 
     // Get the previous line
     if let Some((prev_line_start, prev_line_content)) = iter.prev() {
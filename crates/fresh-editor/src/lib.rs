@@ -1,12 +1,18 @@
 // Editor library - exposes all core modules for testing
 
-// Initialize i18n with translations from locales/ directory
-rust_i18n::i18n!("locales", fallback = "en");
+// Initialize i18n with translations from the locales/ directory. They're served by
+// `i18n::EmbeddedTranslations`, so the macro's own path has no files to load.
+rust_i18n::i18n!(
+    "locales/embedded",
+    fallback = "en",
+    backend = crate::i18n::EmbeddedTranslations::new()
+);
 
 pub mod i18n;
 
 // Core types and config are always available (needed for schema generation)
 pub mod config;
+pub mod config_edit;
 pub mod partial_config;
 pub mod types;
 
@@ -27,6 +33,8 @@ pub mod primitives;
 #[cfg(feature = "runtime")]
 pub mod app;
 #[cfg(feature = "runtime")]
+pub mod embed;
+#[cfg(feature = "runtime")]
 pub mod input;
 #[cfg(feature = "runtime")]
 pub mod services;
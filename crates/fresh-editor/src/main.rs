@@ -4,9 +4,12 @@ use crossterm::event::{
     poll as event_poll, read as event_read, Event as CrosstermEvent, KeyEvent, KeyEventKind,
     MouseEvent,
 };
+use crossterm::terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate};
 use fresh::input::key_translator::KeyTranslator;
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
+use fresh::services::terminal_background;
+use fresh::services::terminal_capabilities::TerminalCapabilities;
 use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
 use fresh::services::tracing_setup;
 use fresh::{
@@ -18,9 +21,11 @@ use fresh::{
     services::remote,
     services::signal_handler,
     services::tracing_setup::TracingHandles,
+    view::color_support::ColorCapability,
 };
 use ratatui::Terminal;
 use std::{
+    collections::VecDeque,
     io::{self, stdout},
     path::PathBuf,
     time::Duration,
@@ -32,15 +37,33 @@ use std::{
 #[command(about = "A terminal text editor with multi-cursor support", long_about = None)]
 #[command(version)]
 struct Args {
-    /// Files to open. Supports line:col syntax (e.g., file.txt:10:5), remote paths
-    /// (user@host:path), and "-" for stdin.
+    /// Files to open. Supports line:col syntax (e.g., file.txt:10:5), +LINE before
+    /// a file (e.g., +10 file.txt), remote paths (user@host:path), and "-" for stdin.
     #[arg(value_name = "FILES")]
     files: Vec<String>,
 
+    /// Compare two files in a side-by-side diff view, or two directories
+    /// in a tree of their differences
+    #[arg(long, num_args = 2, value_names = ["OLD", "NEW"])]
+    diff: Option<Vec<PathBuf>>,
+
+    /// Open files read-only
+    #[arg(short = 'R', long)]
+    readonly: bool,
+
+    /// Open each file in its own split, side by side, instead of in tabs
+    #[arg(short = 'O', long)]
+    split: bool,
+
     /// Read content from stdin (alternative to using "-" as filename)
     #[arg(long)]
     stdin: bool,
 
+    /// Language to highlight stdin and the opened files as (e.g., json). Stdin's
+    /// language is otherwise guessed from its first line, such as a shebang.
+    #[arg(long, value_name = "LANG")]
+    language: Option<String>,
+
     /// Disable plugin loading
     #[arg(long)]
     no_plugins: bool,
@@ -57,6 +80,15 @@ struct Args {
     #[arg(long, value_name = "LOG_FILE")]
     event_log: Option<PathBuf>,
 
+    /// Open the interactive tutorial
+    #[arg(long)]
+    tutor: bool,
+
+    /// Replay the input recorded by --event-log against FILES, and print the
+    /// screen it ends on
+    #[arg(long, value_name = "LOG_FILE")]
+    replay: Option<PathBuf>,
+
     /// Don't restore previous session (start fresh)
     #[arg(long)]
     no_session: bool,
@@ -84,6 +116,16 @@ struct Args {
     /// Initialize a new package (plugin, theme, or language pack)
     #[arg(long, value_name = "TYPE")]
     init: Option<Option<String>>,
+
+    /// On exit, write the directory the editor was last working in to PATH
+    /// (for shell wrappers that cd there)
+    #[arg(long, value_name = "PATH")]
+    cwd_file: Option<PathBuf>,
+
+    /// Always start a new editor, even if another instance is already running
+    /// (by default, plain file arguments are opened in the running instance)
+    #[arg(long)]
+    new_instance: bool,
 }
 
 /// Parsed file location from CLI argument in file:line:col format
@@ -111,10 +153,29 @@ enum ParsedLocation {
     Remote(RemoteLocation),
 }
 
+impl ParsedLocation {
+    /// Jump to `line` instead of any line:col given in the path
+    fn with_line(self, line: usize) -> Self {
+        match self {
+            Self::Local(loc) => Self::Local(FileLocation {
+                line: Some(line),
+                column: None,
+                ..loc
+            }),
+            Self::Remote(loc) => Self::Remote(RemoteLocation {
+                line: Some(line),
+                column: None,
+                ..loc
+            }),
+        }
+    }
+}
+
 struct IterationOutcome {
     loop_result: AnyhowResult<()>,
     update_result: Option<release_checker::ReleaseCheckResult>,
     restart_dir: Option<PathBuf>,
+    current_dir: Option<PathBuf>,
 }
 
 struct SetupState {
@@ -144,6 +205,8 @@ struct SetupState {
     /// Terminal mode state (raw mode, alternate screen, etc.)
     /// Drop impl restores terminal on cleanup
     terminal_modes: TerminalModes,
+    /// Detected terminal background (only queried for `appearance.mode = "auto"`)
+    terminal_appearance: Option<fresh::view::theme::Appearance>,
 }
 
 /// State for stdin streaming in background
@@ -309,45 +372,75 @@ fn handle_first_run_setup(
 
     // Handle stdin streaming (takes priority over files)
     // Opens with empty/partial buffer, content streams in background
+    let opened_stdin = stdin_stream.is_some();
     if let Some(mut stream_state) = stdin_stream.take() {
         tracing::info!("Opening stdin buffer from: {:?}", stream_state.temp_path);
-        editor.open_stdin_buffer(&stream_state.temp_path, stream_state.thread_handle.take())?;
+        let buffer_id =
+            editor.open_stdin_buffer(&stream_state.temp_path, stream_state.thread_handle.take())?;
+        set_cli_language(editor, args, buffer_id);
     }
 
+    let mut opened_any = false;
     for loc in file_locations {
         if loc.path.is_dir() {
             continue;
         }
+        if args.split && opened_any {
+            editor.split_pane_vertical();
+        }
         tracing::info!("[SYNTAX DEBUG] CLI opening file: {:?}", loc.path);
-        editor.open_file(&loc.path)?;
+        let buffer_id = editor.open_file(&loc.path)?;
+        opened_any = true;
 
+        if args.readonly {
+            editor.set_buffer_read_only(buffer_id, true);
+        }
+        set_cli_language(editor, args, buffer_id);
         if let Some(line) = loc.line {
             editor.goto_line_col(line, loc.column);
         }
     }
 
+    if let Some([old, new]) = args.diff.as_deref() {
+        if old.is_dir() && new.is_dir() {
+            editor.open_dir_diff(old, new)?;
+        } else {
+            editor.open_file_diff(old, new)?;
+        }
+    }
+
+    if args.tutor {
+        editor.show_tutorial();
+    } else if !opened_stdin && !opened_any && args.diff.is_none() {
+        editor.show_tutorial_on_first_launch();
+    }
+
     if show_file_explorer {
+        // Every argument is a directory: the first is the working directory
+        for loc in file_locations.iter().skip(1) {
+            editor.add_workspace_folder(loc.path.clone());
+        }
         editor.show_file_explorer();
     }
 
     if editor.has_recovery_files().unwrap_or(false) {
-        tracing::info!("Recovery files found from previous session, recovering...");
-        match editor.recover_all_buffers() {
-            Ok(count) if count > 0 => {
-                tracing::info!("Recovered {} buffer(s)", count);
-            }
-            Ok(_) => {
-                tracing::info!("No buffers to recover");
-            }
-            Err(e) => {
-                tracing::warn!("Failed to recover buffers: {}", e);
-            }
-        }
+        tracing::info!("Recovery files found from previous session, showing recovery picker");
+        editor.show_recovery_picker();
     }
 
     Ok(())
 }
 
+/// Apply the `--language` argument, if given, to a buffer opened from the
+/// command line
+fn set_cli_language(editor: &mut Editor, args: &Args, buffer_id: fresh::model::event::BufferId) {
+    if let Some(language) = &args.language {
+        if editor.set_buffer_language(buffer_id, language).is_none() {
+            editor.set_status_message(fresh::i18n::unknown_language_message(language));
+        }
+    }
+}
+
 /// Parse a file path that may include line and column information.
 /// Supports formats:
 /// - file.txt
@@ -511,6 +604,30 @@ fn parse_location(input: &str) -> ParsedLocation {
     ParsedLocation::Local(parse_file_location(input))
 }
 
+/// Parse the FILES arguments into locations.
+///
+/// A `+LINE` argument sets the line for the file that follows it, as in
+/// `fresh +10 file.txt`. "-" (stdin) is skipped; it's read separately.
+fn parse_locations(files: &[String]) -> Vec<ParsedLocation> {
+    let mut locations = Vec::new();
+    let mut next_line = None;
+    for file in files {
+        if let Some(line) = file.strip_prefix('+').and_then(|l| l.parse::<usize>().ok()) {
+            next_line = Some(line);
+            continue;
+        }
+        if file == "-" {
+            continue;
+        }
+        let location = parse_location(file);
+        locations.push(match next_line.take() {
+            Some(line) => location.with_line(line),
+            None => location,
+        });
+    }
+    locations
+}
+
 /// Holds resources needed for remote editing (kept alive for duration of session)
 struct RemoteSession {
     /// The SSH connection - dropping this closes the connection
@@ -641,12 +758,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     // Determine working directory early for config loading
     // Filter out "-" from files list since it's handled via stdin_stream
     // Parse locations which may be local or remote (user@host:path)
-    let parsed_locations: Vec<ParsedLocation> = args
-        .files
-        .iter()
-        .filter(|f| *f != "-")
-        .map(|f| parse_location(f))
-        .collect();
+    let parsed_locations = parse_locations(&args.files);
 
     // Check for remote locations - for now, collect them separately
     let remote_locations: Vec<&RemoteLocation> = parsed_locations
@@ -710,17 +822,18 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     let mut working_dir = None;
     let mut show_file_explorer = false;
 
-    // Only set working_dir if exactly one parameter is passed and it's a directory
-    if file_locations.len() == 1 {
-        if let Some(first_loc) = file_locations.first() {
-            // Use the filesystem to check if path is a directory
-            // This works for both local and remote paths
-            let is_directory = filesystem.is_dir(&first_loc.path).unwrap_or(false);
-            if is_directory {
-                working_dir = Some(first_loc.path.clone());
-                show_file_explorer = true;
-            }
-        }
+    // Only set working_dir if every parameter passed is a directory. The
+    // first one is the working directory, the others are opened as
+    // workspace folders alongside it (see handle_first_run_setup).
+    // Use the filesystem to check if path is a directory
+    // This works for both local and remote paths
+    if !file_locations.is_empty()
+        && file_locations
+            .iter()
+            .all(|loc| filesystem.is_dir(&loc.path).unwrap_or(false))
+    {
+        working_dir = Some(file_locations[0].path.clone());
+        show_file_explorer = true;
     }
 
     // Load config using the layered config system
@@ -773,6 +886,16 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     };
     let terminal_modes = TerminalModes::enable(Some(&keyboard_config))?;
 
+    // Query the background color now, while in raw mode but before the event
+    // loop reads input (the terminal's reply would otherwise look like keys)
+    let terminal_appearance = if config.appearance.mode == config::AppearanceMode::Auto {
+        let appearance = terminal_background::detect();
+        tracing::info!("Detected terminal appearance: {:?}", appearance);
+        appearance
+    } else {
+        None
+    };
+
     #[cfg(target_os = "linux")]
     let gpm_client = match GpmClient::connect() {
         Ok(client) => client,
@@ -790,8 +913,13 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     // Set cursor style from config
     use crossterm::ExecutableCommand;
-    let _ = stdout().execute(config.editor.cursor_style.to_crossterm_style());
-    tracing::info!("Set cursor style to {:?}", config.editor.cursor_style);
+    let cursor_style = if config.editor.reduced_motion {
+        config.editor.cursor_style.steady()
+    } else {
+        config.editor.cursor_style
+    };
+    let _ = stdout().execute(cursor_style.to_crossterm_style());
+    tracing::info!("Set cursor style to {:?}", cursor_style);
 
     let backend = ratatui::backend::CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
@@ -825,6 +953,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         key_translator,
         gpm_client,
         terminal_modes,
+        terminal_appearance,
         filesystem,
         process_spawner,
         _remote_session: remote_session,
@@ -856,14 +985,91 @@ fn run_editor_iteration(
 
     let update_result = editor.get_update_result().cloned();
     let restart_dir = editor.take_restart_dir();
+    let current_dir = editor.current_directory();
 
     Ok(IterationOutcome {
         loop_result,
         update_result,
         restart_dir,
+        current_dir,
     })
 }
 
+/// Forward the requested files to a running instance, if there is one.
+///
+/// Only done for plain local files without `--new-instance`: any other flag
+/// would be silently ignored by the running editor, so directories, stdin,
+/// remote paths and extra options always start a new editor. Returns true if
+/// the running instance accepted the files, in which case this process should
+/// exit.
+#[cfg(unix)]
+fn try_forward_to_running_instance(args: &Args) -> bool {
+    use fresh::services::instance_server::{forward_open_requests, OpenRequest};
+    use fresh::services::recovery::RecoveryStorage;
+
+    if args.new_instance
+        || args.stdin
+        || args.readonly
+        || args.split
+        || args.language.is_some()
+        || args.no_plugins
+        || args.config.is_some()
+        || args.log_file.is_some()
+        || args.event_log.is_some()
+        || args.tutor
+        || args.replay.is_some()
+        || args.no_session
+        || args.locale.is_some()
+        || args.cwd_file.is_some()
+        || args.diff.is_some()
+        || args.files.is_empty()
+        || args.files.iter().any(|f| f == "-")
+    {
+        return false;
+    }
+
+    let Ok(cwd) = std::env::current_dir() else {
+        return false;
+    };
+    let mut requests = Vec::new();
+    for location in parse_locations(&args.files) {
+        match location {
+            ParsedLocation::Local(loc) if !loc.path.is_dir() => {
+                requests.push(OpenRequest::new(&loc.path, loc.line, loc.column, &cwd));
+            }
+            _ => return false,
+        }
+    }
+
+    let Ok(dir_context) = DirectoryContext::from_system() else {
+        return false;
+    };
+    // The session lock tells us whether another editor owns this data dir
+    let owner = match RecoveryStorage::with_dir(dir_context.recovery_dir()).read_session_lock() {
+        Ok(Some(info)) if info.pid != std::process::id() && info.is_running() => info,
+        _ => return false,
+    };
+
+    match forward_open_requests(&dir_context.instance_socket_path(), &requests) {
+        Ok(true) => {
+            eprintln!(
+                "Opened {} file(s) in running fresh instance (pid {})",
+                requests.len(),
+                owner.pid
+            );
+            true
+        }
+        Ok(false) => false,
+        Err(e) => {
+            eprintln!(
+                "Warning: could not reach running fresh instance (pid {}): {}",
+                owner.pid, e
+            );
+            false
+        }
+    }
+}
+
 /// Check a plugin by bundling it and printing the output
 #[cfg(feature = "plugins")]
 fn check_plugin_bundle(plugin_path: &std::path::Path) -> AnyhowResult<()> {
@@ -1547,6 +1753,18 @@ fn main() -> AnyhowResult<()> {
         return init_package_command(pkg_type.clone());
     }
 
+    // Handle --replay early (renders to a virtual terminal, not this one)
+    if let Some(log_path) = &args.replay {
+        return replay_event_log(&args, log_path);
+    }
+
+    // Hand the files to an already running editor instead of starting a
+    // second one that would compete for the same recovery files
+    #[cfg(unix)]
+    if try_forward_to_running_instance(&args) {
+        return Ok(());
+    }
+
     let SetupState {
         config,
         mut tracing_handles,
@@ -1563,6 +1781,7 @@ fn main() -> AnyhowResult<()> {
         #[cfg(not(target_os = "linux"))]
         gpm_client,
         mut terminal_modes,
+        terminal_appearance,
         filesystem,
         process_spawner,
         _remote_session,
@@ -1578,13 +1797,17 @@ fn main() -> AnyhowResult<()> {
     let mut restore_session_on_restart = false;
 
     // Main editor loop - supports restarting with a new working directory
-    // Returns (loop_result, last_update_result) tuple
-    let (result, last_update_result) = loop {
+    // Returns (loop_result, last_update_result, last_current_dir) tuple
+    let (result, last_update_result, last_current_dir) = loop {
         let first_run = is_first_run;
-        let session_enabled = !args.no_session && file_locations.is_empty();
+        let session_enabled = !args.no_session && file_locations.is_empty() && args.diff.is_none();
 
-        // Detect terminal color capability
-        let color_capability = fresh::view::color_support::ColorCapability::detect();
+        // Use what the terminal was found to support
+        let terminal_capabilities = terminal_modes
+            .capabilities()
+            .cloned()
+            .unwrap_or_else(|| TerminalCapabilities::from_env(ColorCapability::detect()));
+        let color_capability = terminal_capabilities.color;
 
         // Use the filesystem created during initialization (supports both local and remote)
         let fs = filesystem.clone();
@@ -1604,6 +1827,9 @@ fn main() -> AnyhowResult<()> {
         // Set the process spawner (LocalProcessSpawner for local, RemoteProcessSpawner for remote)
         editor.set_process_spawner(process_spawner.clone());
 
+        editor.set_terminal_appearance(terminal_appearance);
+        editor.set_terminal_capabilities(terminal_capabilities);
+
         #[cfg(target_os = "linux")]
         if gpm_client.is_some() {
             editor.set_gpm_active(true);
@@ -1647,6 +1873,14 @@ fn main() -> AnyhowResult<()> {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
 
+        #[cfg(unix)]
+        if let Err(e) = editor.start_instance_server() {
+            tracing::warn!("Failed to start instance server: {}", e);
+        }
+
+        // Ask before running the project's plugins and commands
+        editor.check_workspace_trust();
+
         let iteration = run_editor_iteration(
             &mut editor,
             session_enabled,
@@ -1660,6 +1894,7 @@ fn main() -> AnyhowResult<()> {
         let update_result = iteration.update_result;
         let restart_dir = iteration.restart_dir;
         let loop_result = iteration.loop_result;
+        let current_dir = iteration.current_dir;
 
         drop(editor);
 
@@ -1677,12 +1912,18 @@ fn main() -> AnyhowResult<()> {
             continue;
         }
 
-        break (loop_result, update_result);
+        break (loop_result, update_result, current_dir);
     };
 
     // Restore terminal state
     terminal_modes.undo();
 
+    if let (Some(cwd_file), Some(dir)) = (&args.cwd_file, &last_current_dir) {
+        if let Err(e) = fresh::services::shell_integration::write_cwd_file(cwd_file, dir) {
+            eprintln!("Warning: could not write {}: {}", cwd_file.display(), e);
+        }
+    }
+
     // Check for updates after terminal is restored (using cached result)
     if let Some(update_result) = last_update_result {
         if update_result.update_available {
@@ -1707,6 +1948,97 @@ fn main() -> AnyhowResult<()> {
     result.context("Editor loop returned an error")
 }
 
+/// Replay the input recorded in an event log against the files given on the
+/// command line, and print the screen it ends on. The editor starts with
+/// the default config (or `--config`) and no session, draws to a virtual
+/// terminal of the recorded size, and reads the time from a clock that only
+/// moves as far as the log says, so a replay always ends the same way.
+fn replay_event_log(args: &Args, log_path: &std::path::Path) -> AnyhowResult<()> {
+    use fresh::services::event_replay;
+    use fresh::services::time_source::TestTimeSource;
+    use ratatui::backend::TestBackend;
+
+    const FRAME_MS: u64 = 16;
+
+    let log = event_replay::read_log(log_path)
+        .with_context(|| format!("Failed to read event log {}", log_path.display()))?;
+    let (width, height) = log.size;
+    let config = match &args.config {
+        Some(path) => config::Config::load_from_file(path)
+            .map_err(|e| anyhow::anyhow!("Failed to load config from {}: {}", path.display(), e))?,
+        None => config::Config::default(),
+    };
+
+    // Keep the replay away from the user's sessions and recovery files
+    let data_dir = std::env::temp_dir().join(format!("fresh-replay-{}", std::process::id()));
+    let time_source = TestTimeSource::shared();
+    let mut editor = Editor::with_time_source(
+        config,
+        width,
+        height,
+        std::env::current_dir().ok(),
+        DirectoryContext::for_testing(&data_dir),
+        !args.no_plugins,
+        ColorCapability::TrueColor,
+        std::sync::Arc::new(StdFileSystem),
+        time_source.clone(),
+    )
+    .context("Failed to create editor instance")?;
+
+    for file in args.files.iter().filter(|file| *file != "-") {
+        let location = parse_file_location(file);
+        let buffer_id = editor.open_file(&location.path)?;
+        if args.readonly {
+            editor.set_buffer_read_only(buffer_id, true);
+        }
+        set_cli_language(&mut editor, args, buffer_id);
+        if let Some(line) = location.line {
+            editor.goto_line_col(line, location.column);
+        }
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|frame| editor.render(frame))?;
+    let mut elapsed_ms = 0;
+    for recorded in &log.inputs {
+        // Let the time between inputs pass a frame at a time, as it does in
+        // the event loop
+        while elapsed_ms < recorded.elapsed_ms {
+            let step = FRAME_MS.min(recorded.elapsed_ms - elapsed_ms);
+            time_source.advance(Duration::from_millis(step));
+            elapsed_ms += step;
+            if editor.update() {
+                terminal.draw(|frame| editor.render(frame))?;
+            }
+        }
+        if editor.should_quit() {
+            break;
+        }
+        let Some(event) = recorded.input.to_event() else {
+            tracing::warn!("Skipping unreadable input: {:?}", recorded.input);
+            continue;
+        };
+        if let CrosstermEvent::Resize(w, h) = event {
+            terminal.backend_mut().resize(w, h);
+        }
+        if handle_event(&mut editor, event)? {
+            terminal.draw(|frame| editor.render(frame))?;
+        }
+    }
+    editor.update();
+    terminal.draw(|frame| editor.render(frame))?;
+    drop(editor);
+    let _ = std::fs::remove_dir_all(&data_dir);
+
+    let buffer = terminal.backend().buffer();
+    let area = buffer.area;
+    for y in 0..area.height {
+        let line: String = (0..area.width).map(|x| buffer[(x, y)].symbol()).collect();
+        println!("{}", line.trim_end());
+    }
+    Ok(())
+}
+
 /// Main event loop
 #[cfg(target_os = "linux")]
 fn run_event_loop(
@@ -1748,6 +2080,31 @@ fn run_event_loop(
     )
 }
 
+/// Draw a frame, clearing the screen first if `clear` is set. Where the
+/// terminal supports it the whole frame is shown at once, so large repaints
+/// (theme switches, split changes, full redraws) don't flicker.
+fn draw_frame(
+    terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
+    editor: &mut Editor,
+    clear: bool,
+) -> AnyhowResult<()> {
+    let start = std::time::Instant::now();
+    let synchronized = editor.synchronized_output();
+    if synchronized {
+        crossterm::execute!(terminal.backend_mut(), BeginSynchronizedUpdate)?;
+    }
+    let cleared = if clear { terminal.clear() } else { Ok(()) };
+    let result = cleared
+        .and_then(|()| terminal.draw(|frame| editor.render(frame)).map(|_| ()))
+        .and_then(|()| editor.write_image_previews(terminal.backend_mut(), clear));
+    // End the update even if drawing failed, or the terminal stops repainting
+    if synchronized {
+        crossterm::execute!(terminal.backend_mut(), EndSynchronizedUpdate)?;
+    }
+    editor.record_frame_drawn(start.elapsed());
+    Ok(result?)
+}
+
 fn run_event_loop_common<F>(
     editor: &mut Editor,
     terminal: &mut Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
@@ -1763,46 +2120,22 @@ where
     const FRAME_DURATION: Duration = Duration::from_millis(16); // 60fps
     let mut last_render = Instant::now();
     let mut needs_render = true;
-    let mut pending_event: Option<CrosstermEvent> = None;
+    let mut pending_events: VecDeque<CrosstermEvent> = VecDeque::new();
+    let mut clear_before_render = false;
+    // When the oldest input event that no frame has shown yet arrived
+    let mut input_received: Option<Instant> = None;
 
     loop {
-        // Process async messages and poll for file changes (auto-revert, file tree)
-        if editor.process_async_messages() {
+        if editor.update() {
             needs_render = true;
         }
 
-        // Check mouse hover timer for LSP hover requests
-        if editor.check_mouse_hover_timer() {
-            needs_render = true;
-        }
-
-        // Check semantic highlight debounce timer
-        if editor.check_semantic_highlight_timer() {
-            needs_render = true;
-        }
-
-        // Check completion trigger timer (debounced quick suggestions)
-        if editor.check_completion_trigger_timer() {
-            needs_render = true;
-        }
-
-        // Check for warnings and open warning log if any occurred
-        if editor.check_warning_log() {
-            needs_render = true;
-        }
-
-        // Poll stdin streaming progress (if active)
-        if editor.poll_stdin_streaming() {
-            needs_render = true;
-        }
-
-        if let Err(e) = editor.auto_save_dirty_buffers() {
-            tracing::debug!("Auto-save error: {}", e);
-        }
+        editor.update_terminal_working_dir();
+        editor.update_terminal_title();
 
         // Handle hard redraw requests (e.g. after returning from sudo)
         if editor.take_full_redraw_request() {
-            terminal.clear()?;
+            clear_before_render = true;
             needs_render = true;
         }
 
@@ -1818,13 +2151,16 @@ where
         }
 
         if needs_render && last_render.elapsed() >= FRAME_DURATION {
-            terminal.draw(|frame| editor.render(frame))?;
+            draw_frame(terminal, editor, std::mem::take(&mut clear_before_render))?;
             last_render = Instant::now();
             needs_render = false;
+            if let Some(received) = input_received.take() {
+                editor.record_input_latency(received.elapsed());
+            }
         }
 
-        let event = if let Some(e) = pending_event.take() {
-            Some(e)
+        let event = if let Some(e) = pending_events.pop_front() {
+            e
         } else {
             let timeout = if needs_render {
                 FRAME_DURATION.saturating_sub(last_render.elapsed())
@@ -1832,56 +2168,78 @@ where
                 Duration::from_millis(50)
             };
 
-            poll_event(timeout)?
-        };
+            let Some(event) = poll_event(timeout)? else {
+                continue;
+            };
 
-        let Some(event) = event else { continue };
+            let (event, next) = coalesce_mouse_moves(event)?;
+            let (event, read_ahead) = coalesce_typed_paste(editor, event)?;
+            pending_events.extend(read_ahead);
+            pending_events.extend(next);
+            event
+        };
+        let received = Instant::now();
+        editor.record_input(&event);
+
+        let is_input = !matches!(
+            event,
+            CrosstermEvent::Resize(..) | CrosstermEvent::FocusLost | CrosstermEvent::FocusGained
+        );
+        if handle_event(editor, event)? {
+            needs_render = true;
+            if is_input {
+                input_received.get_or_insert(received);
+            }
+        }
+    }
 
-        let (event, next) = coalesce_mouse_moves(event)?;
-        pending_event = next;
+    Ok(())
+}
 
-        // Event debug dialog receives ALL RAW events (before any translation or processing)
-        // This is essential for diagnosing terminal keybinding issues
-        if editor.is_event_debug_active() {
-            if let CrosstermEvent::Key(key_event) = event {
-                if key_event.kind == KeyEventKind::Press {
-                    editor.handle_event_debug_input(&key_event);
-                    needs_render = true;
-                }
+/// Handle an input event. Returns whether a frame needs drawing.
+fn handle_event(editor: &mut Editor, event: CrosstermEvent) -> AnyhowResult<bool> {
+    // Event debug dialog receives ALL RAW events (before any translation or processing)
+    // This is essential for diagnosing terminal keybinding issues
+    if editor.is_event_debug_active() {
+        if let CrosstermEvent::Key(key_event) = event {
+            if key_event.kind == KeyEventKind::Press {
+                editor.handle_event_debug_input(&key_event);
+                return Ok(true);
             }
-            // Consume all events while event debug is active
-            continue;
         }
+        // Consume all events while event debug is active
+        return Ok(false);
+    }
 
-        match event {
-            CrosstermEvent::Key(key_event) => {
-                if key_event.kind == KeyEventKind::Press {
-                    // Apply key translation (for input calibration)
-                    // Use editor's translator so calibration changes take effect immediately
-                    let translated_event = editor.key_translator().translate(key_event);
-                    handle_key_event(editor, translated_event)?;
-                    needs_render = true;
-                }
+    match event {
+        CrosstermEvent::Key(key_event) => {
+            if key_event.kind == KeyEventKind::Press {
+                // Apply key translation (for input calibration)
+                // Use editor's translator so calibration changes take effect immediately
+                let translated_event = editor.key_translator().translate(key_event);
+                handle_key_event(editor, translated_event)?;
+                return Ok(true);
             }
-            CrosstermEvent::Mouse(mouse_event) => {
-                if handle_mouse_event(editor, mouse_event)? {
-                    needs_render = true;
-                }
-            }
-            CrosstermEvent::Resize(w, h) => {
-                editor.resize(w, h);
-                needs_render = true;
-            }
-            CrosstermEvent::Paste(text) => {
-                // External paste from terminal (bracketed paste mode)
-                editor.paste_text(text);
-                needs_render = true;
+            if key_event.kind == KeyEventKind::Release {
+                // Releasing the modifier held for the buffer switcher picks
+                // the highlighted buffer
+                return Ok(editor.handle_key_release(&key_event));
             }
-            _ => {}
+            Ok(false)
         }
+        CrosstermEvent::Mouse(mouse_event) => handle_mouse_event(editor, mouse_event),
+        CrosstermEvent::Resize(w, h) => {
+            editor.resize(w, h);
+            Ok(true)
+        }
+        CrosstermEvent::Paste(text) => {
+            // External paste from terminal (bracketed paste mode)
+            editor.paste_text(text);
+            Ok(true)
+        }
+        CrosstermEvent::FocusLost => Ok(editor.handle_terminal_focus_lost() > 0),
+        CrosstermEvent::FocusGained => Ok(editor.handle_terminal_focus_gained()),
     }
-
-    Ok(())
 }
 
 /// Poll for events from both GPM and crossterm (Linux with libgpm available)
@@ -2036,6 +2394,36 @@ fn coalesce_mouse_moves(
     Ok((latest, None))
 }
 
+/// Read the keys queued up behind a typed key, and turn the burst into a
+/// paste if it looks like one (see `Editor::coalesce_typed_paste`). Returns
+/// the event to handle now and any events read ahead, to handle next.
+fn coalesce_typed_paste(
+    editor: &Editor,
+    event: CrosstermEvent,
+) -> AnyhowResult<(CrosstermEvent, Vec<CrosstermEvent>)> {
+    if editor.typed_paste_char(&event).is_none() {
+        return Ok((event, Vec::new()));
+    }
+
+    let mut burst = vec![event];
+    let mut next = None;
+    while event_poll(Duration::ZERO)? {
+        let event = event_read()?;
+        if editor.typed_paste_char(&event).is_some() {
+            burst.push(event);
+        } else if !matches!(&event, CrosstermEvent::Key(key) if key.kind != KeyEventKind::Press) {
+            // Key releases carry no text and are ignored anyway
+            next = Some(event);
+            break;
+        }
+    }
+
+    let mut events = editor.coalesce_typed_paste(burst);
+    events.extend(next);
+    let first = events.remove(0);
+    Ok((first, events))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2224,6 +2612,29 @@ mod tests {
             ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
         }
     }
+
+    #[test]
+    fn test_parse_locations_plus_line() {
+        let files: Vec<String> = ["+10", "a.txt", "b.txt:3:4", "+7", "c.txt:1:2", "-"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let lines: Vec<(PathBuf, Option<usize>, Option<usize>)> = parse_locations(&files)
+            .into_iter()
+            .map(|loc| match loc {
+                ParsedLocation::Local(fl) => (fl.path, fl.line, fl.column),
+                ParsedLocation::Remote(_) => panic!("Expected local, got remote"),
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                (PathBuf::from("a.txt"), Some(10), None),
+                (PathBuf::from("b.txt"), Some(3), Some(4)),
+                (PathBuf::from("c.txt"), Some(7), None),
+            ]
+        );
+    }
 }
 
 // Property tests use Unix-style path generation strategy, skip on Windows
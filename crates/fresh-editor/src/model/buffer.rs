@@ -1,5 +1,7 @@
 /// Text buffer that uses PieceTree with integrated line tracking
 /// Architecture where the tree is the single source of truth for text and line information
+use crate::config::SaveStrategy;
+use crate::model::edit::{BufferChanges, Edit, EditLog};
 use crate::model::encoding;
 use crate::model::filesystem::{FileMetadata, FileSystem, WriteOp};
 use crate::model::piece_tree::{
@@ -7,7 +9,12 @@ use crate::model::piece_tree::{
     StringBuffer, TreeStats,
 };
 use crate::model::piece_tree_diff::PieceTreeDiff;
+use crate::primitives::display_width::{
+    line_byte_offset_at_visual_column, line_visual_column_at_byte,
+};
 use crate::primitives::grapheme;
+use crate::services::encryption::FileEncryption;
+use crate::services::recovery::JournalRecord;
 use anyhow::{Context, Result};
 use regex::bytes::Regex;
 use std::io::{self, Write};
@@ -90,6 +97,9 @@ pub const LOAD_CHUNK_SIZE: usize = 1024 * 1024;
 /// Chunk alignment for lazy loading (64 KB)
 pub const CHUNK_ALIGNMENT: usize = 64 * 1024;
 
+/// Pending recovery journal size past which a full snapshot is cheaper (1 MB)
+const MAX_PENDING_JOURNAL_BYTES: usize = 1024 * 1024;
+
 /// Line ending format used in the file
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LineEnding {
@@ -245,10 +255,20 @@ pub struct TextBuffer {
     /// Has the buffer been modified since last save?
     modified: bool,
 
-    /// Does the buffer have unsaved changes for recovery auto-save?
-    /// This is separate from `modified` because recovery auto-save doesn't
-    /// clear `modified` (buffer still differs from on-disk file).
-    recovery_pending: bool,
+    /// The revision last saved for recovery. Recovery auto-save is pending
+    /// while the buffer is at another revision. This is separate from
+    /// `modified` because recovery auto-save doesn't clear `modified` (buffer
+    /// still differs from on-disk file).
+    recovered_revision: u64,
+
+    /// Edits made since the last recovery snapshot, drained into the recovery
+    /// journal on auto-save. `None` until a snapshot of this buffer has been
+    /// written, or after an edit that can't be journaled, so that the next
+    /// recovery save writes a full snapshot instead.
+    recovery_journal: Option<Vec<JournalRecord>>,
+
+    /// Encoded size of the records in `recovery_journal`
+    recovery_journal_bytes: usize,
 
     /// Is this a large file (no line indexing, lazy loading enabled)?
     large_file: bool,
@@ -277,8 +297,19 @@ pub struct TextBuffer {
     /// Updated when loading from file or after saving.
     saved_file_size: Option<usize>,
 
-    /// Monotonic version counter for change tracking.
-    version: u64,
+    /// Monotonic revision counter, advanced by every change to the contents.
+    /// Caches of anything derived from the contents are keyed off it.
+    revision: u64,
+
+    /// The recent edits, by revision
+    edit_log: EditLog,
+
+    /// Encryption of the file, for buffers opened from an encrypted file.
+    /// Saving encrypts the contents instead of writing them as they are.
+    encryption: Option<FileEncryption>,
+
+    /// Whether saving renames a new file over the old one or overwrites it
+    save_strategy: SaveStrategy,
 }
 
 impl TextBuffer {
@@ -296,7 +327,9 @@ impl TextBuffer {
             next_buffer_id: 1,
             file_path: None,
             modified: false,
-            recovery_pending: false,
+            recovered_revision: 0,
+            recovery_journal: None,
+            recovery_journal_bytes: 0,
             large_file: false,
             is_binary: false,
             line_ending,
@@ -304,13 +337,42 @@ impl TextBuffer {
             encoding,
             original_encoding: encoding,
             saved_file_size: None,
-            version: 0,
+            revision: 0,
+            edit_log: EditLog::default(),
+            encryption: None,
+            save_strategy: SaveStrategy::default(),
+        }
+    }
+
+    /// Current buffer revision (monotonic, wraps on overflow)
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// The changes made to the contents since `revision`
+    pub fn changes_since(&self, revision: u64) -> BufferChanges {
+        self.edit_log.since(revision, self.revision)
+    }
+
+    /// Whether the contents before `offset` are the same as at `revision`
+    pub fn unchanged_before(&self, revision: u64, offset: usize) -> bool {
+        match self.changes_since(revision) {
+            BufferChanges::Edits(edits) => edits.iter().all(|edit| edit.offset() >= offset),
+            BufferChanges::Reset => false,
         }
     }
 
-    /// Current buffer version (monotonic, wraps on overflow)
-    pub fn version(&self) -> u64 {
-        self.version
+    /// Continue the revisions of `previous`, a buffer this one replaces, so
+    /// that consumers keyed off its revisions see the replacement as a change
+    pub fn continue_revisions_from(&mut self, previous: &TextBuffer) {
+        let recovery_pending = self.is_recovery_pending();
+        self.revision = previous.revision.wrapping_add(1);
+        self.edit_log.reset(self.revision);
+        self.recovered_revision = if recovery_pending {
+            self.revision.wrapping_sub(1)
+        } else {
+            self.revision
+        };
     }
 
     /// Get a reference to the filesystem implementation used by this buffer.
@@ -324,15 +386,36 @@ impl TextBuffer {
     }
 
     #[inline]
-    fn bump_version(&mut self) {
-        self.version = self.version.wrapping_add(1);
+    fn bump_revision(&mut self) {
+        self.revision = self.revision.wrapping_add(1);
     }
 
     #[inline]
     fn mark_content_modified(&mut self) {
         self.modified = true;
-        self.recovery_pending = true;
-        self.bump_version();
+        // Not described by a journal record: recovery needs a fresh snapshot
+        self.recovery_journal = None;
+        self.bump_revision();
+        // Nor by edits of the earlier contents
+        self.edit_log.reset(self.revision);
+    }
+
+    /// Like `mark_content_modified`, for edits the recovery journal can replay
+    #[inline]
+    fn mark_content_modified_journaled(&mut self, record: impl FnOnce() -> JournalRecord) {
+        self.modified = true;
+        self.bump_revision();
+
+        if let Some(journal) = self.recovery_journal.as_mut() {
+            let record = record();
+            self.recovery_journal_bytes += record.encoded_len();
+            journal.push(record);
+            // Bound memory between auto-saves (e.g. when recovery is disabled)
+            if self.recovery_journal_bytes > MAX_PENDING_JOURNAL_BYTES {
+                self.recovery_journal = None;
+                self.recovery_journal_bytes = 0;
+            }
+        }
     }
 
     /// Create a text buffer from raw bytes WITHOUT encoding conversion.
@@ -367,11 +450,16 @@ impl TextBuffer {
             next_buffer_id: 1,
             file_path: None,
             modified: false,
-            recovery_pending: false,
+            recovered_revision: 0,
+            recovery_journal: None,
+            recovery_journal_bytes: 0,
             large_file: false,
             is_binary: true,
             saved_file_size: Some(bytes),
-            version: 0,
+            revision: 0,
+            edit_log: EditLog::default(),
+            encryption: None,
+            save_strategy: SaveStrategy::default(),
         }
     }
 
@@ -409,11 +497,16 @@ impl TextBuffer {
             next_buffer_id: 1,
             file_path: None,
             modified: false,
-            recovery_pending: false,
+            recovered_revision: 0,
+            recovery_journal: None,
+            recovery_journal_bytes: 0,
             large_file: false,
             is_binary: false,
             saved_file_size: Some(bytes), // Treat initial content as "saved" state
-            version: 0,
+            revision: 0,
+            edit_log: EditLog::default(),
+            encryption: None,
+            save_strategy: SaveStrategy::default(),
         }
     }
 
@@ -440,7 +533,9 @@ impl TextBuffer {
             next_buffer_id: 1,
             file_path: None,
             modified: false,
-            recovery_pending: false,
+            recovered_revision: 0,
+            recovery_journal: None,
+            recovery_journal_bytes: 0,
             large_file: false,
             is_binary: false,
             line_ending,
@@ -448,7 +543,10 @@ impl TextBuffer {
             encoding,
             original_encoding: encoding,
             saved_file_size: None,
-            version: 0,
+            revision: 0,
+            edit_log: EditLog::default(),
+            encryption: None,
+            save_strategy: SaveStrategy::default(),
         }
     }
 
@@ -669,7 +767,9 @@ impl TextBuffer {
             next_buffer_id: 1,
             file_path: Some(path.to_path_buf()),
             modified: false,
-            recovery_pending: false,
+            recovered_revision: 0,
+            recovery_journal: None,
+            recovery_journal_bytes: 0,
             large_file: true,
             is_binary,
             line_ending,
@@ -677,7 +777,10 @@ impl TextBuffer {
             encoding,
             original_encoding: encoding,
             saved_file_size: Some(file_size),
-            version: 0,
+            revision: 0,
+            edit_log: EditLog::default(),
+            encryption: None,
+            save_strategy: SaveStrategy::default(),
         })
     }
 
@@ -693,14 +796,24 @@ impl TextBuffer {
         }
     }
 
-    /// Check if we should use in-place writing to preserve file ownership.
-    /// Returns true if the file exists and is owned by a different user.
-    /// On Unix, only root or the file owner can change file ownership with chown.
-    /// When the current user is not the file owner, using atomic write (temp file + rename)
-    /// would change the file's ownership to the current user. To preserve ownership,
-    /// we must write directly to the existing file instead.
+    /// Check if we should overwrite the existing file in place rather than
+    /// write a new file and rename it over the old one.
+    ///
+    /// A renamed file is a new inode: it belongs to the current user, and other
+    /// hard links to the old file keep the old contents. On Unix, only root can
+    /// give a file back to its owner with chown, so with the `Auto` strategy
+    /// files owned by another user or with several links are written in place.
     fn should_use_inplace_write(&self, dest_path: &Path) -> bool {
-        !self.fs.is_owner(dest_path)
+        let Some(metadata) = self.fs.metadata_if_exists(dest_path) else {
+            return false;
+        };
+        match self.save_strategy {
+            SaveStrategy::Rename => false,
+            SaveStrategy::InPlace => true,
+            SaveStrategy::Auto => {
+                !self.fs.is_owner(dest_path) || metadata.nlink.is_some_and(|n| n > 1)
+            }
+        }
     }
 
     /// Build a write recipe from the piece tree for saving.
@@ -956,8 +1069,9 @@ impl TextBuffer {
     /// For remote filesystems, the recipe is sent to the agent which reconstructs
     /// the file server-side, avoiding transfer of unchanged content.
     ///
-    /// For local filesystems with ownership concerns (file owned by another user),
-    /// uses in-place writing to preserve ownership. Otherwise uses atomic writes.
+    /// For local filesystems, the save strategy decides between atomic writes and
+    /// in-place writing, which preserves the file's ownership and hard links.
+    /// Symlinks are written through to their target either way.
     ///
     /// If the line ending format has been changed (via set_line_ending), all content
     /// will be converted to the new format during save.
@@ -965,9 +1079,26 @@ impl TextBuffer {
         let dest_path = path.as_ref();
         let total = self.total_bytes();
 
+        // Check if we need in-place writing to preserve file ownership or hard
+        // links (local only). Remote filesystems handle this differently
+        let is_local = self.fs.remote_connection_info().is_none();
+        let use_inplace = is_local && self.should_use_inplace_write(dest_path);
+
+        if let Some(encryption) = &self.encryption {
+            // The plaintext never reaches the disk
+            let recipe = self.build_write_recipe()?;
+            if recipe.has_copy_ops() {
+                anyhow::bail!("encrypted buffer still refers to the file on disk");
+            }
+            let ciphertext = encryption.encrypt(&recipe.flatten_inserts())?;
+            self.write_whole_file(dest_path, &ciphertext, use_inplace)?;
+            self.finalize_save(dest_path)?;
+            return Ok(());
+        }
+
         // Handle empty files
         if total == 0 {
-            self.fs.write_file(dest_path, &[])?;
+            self.write_whole_file(dest_path, &[], use_inplace)?;
             self.finalize_save(dest_path)?;
             return Ok(());
         }
@@ -976,13 +1107,8 @@ impl TextBuffer {
         let recipe = self.build_write_recipe()?;
         let ops = recipe.to_write_ops();
 
-        // Check if we need in-place writing to preserve file ownership (local only)
-        // Remote filesystems handle this differently
-        let is_local = self.fs.remote_connection_info().is_none();
-        let use_inplace = is_local && self.should_use_inplace_write(dest_path);
-
         if use_inplace {
-            // In-place write: write directly to preserve ownership
+            // In-place write: write directly to preserve ownership and links
             self.save_with_inplace_write(dest_path, &recipe)?;
         } else if !recipe.has_copy_ops() && !is_local {
             // Remote with no Copy ops: use write_file directly (more efficient)
@@ -1120,6 +1246,22 @@ impl TextBuffer {
         }
     }
 
+    /// Write `data` as the whole file, in place or by rename
+    fn write_whole_file(
+        &self,
+        dest_path: &Path,
+        data: &[u8],
+        use_inplace: bool,
+    ) -> anyhow::Result<()> {
+        if use_inplace {
+            let original_metadata = self.fs.metadata_if_exists(dest_path);
+            self.write_data_inplace(dest_path, data, original_metadata)
+        } else {
+            self.fs.write_file(dest_path, data)?;
+            Ok(())
+        }
+    }
+
     /// Stream a file's content to a writer in chunks to avoid memory issues with large files.
     fn stream_file_to_writer(
         &self,
@@ -1313,12 +1455,35 @@ impl TextBuffer {
         self.piece_tree.line_count()
     }
 
+    /// Encryption applied when saving, if the file is encrypted
+    pub fn encryption(&self) -> Option<&FileEncryption> {
+        self.encryption.as_ref()
+    }
+
+    /// Set how the file is encrypted on save (`None` saves plaintext)
+    pub fn set_encryption(&mut self, encryption: Option<FileEncryption>) {
+        self.encryption = encryption;
+    }
+
+    /// Set how the file is written on save
+    pub fn set_save_strategy(&mut self, save_strategy: SaveStrategy) {
+        self.save_strategy = save_strategy;
+    }
+
     /// Snapshot the current tree as the saved baseline
     pub fn mark_saved_snapshot(&mut self) {
         self.saved_root = self.piece_tree.root();
         self.modified = false;
     }
 
+    /// The text as last saved or loaded
+    /// Returns None if any of it is unloaded (lazy loading)
+    pub fn saved_text(&self) -> Option<String> {
+        let len = self.tree_total_bytes(&self.saved_root);
+        let bytes = self.extract_range_from_tree(&self.saved_root, 0, len)?;
+        String::from_utf8(bytes).ok()
+    }
+
     /// Diff the current piece tree against the last saved snapshot.
     ///
     /// This compares actual byte content, not just tree structure. This means
@@ -1547,8 +1712,13 @@ impl TextBuffer {
             return self.piece_tree.cursor_at_offset(offset);
         }
 
-        // Mark as modified (updates version)
-        self.mark_content_modified();
+        // Mark as modified (updates revision)
+        self.mark_content_modified_journaled(|| JournalRecord::Insert {
+            offset,
+            content: text.clone(),
+        });
+        self.edit_log
+            .record(Edit::insert(self.revision, offset, text.len()));
 
         // Count line feeds in the text to insert
         let line_feed_cnt = Some(text.iter().filter(|&&b| b == b'\n').count());
@@ -1661,14 +1831,18 @@ impl TextBuffer {
 
     /// Delete text starting at the given byte offset
     pub fn delete_bytes(&mut self, offset: usize, bytes: usize) {
-        if bytes == 0 || offset >= self.total_bytes() {
+        let total = self.total_bytes();
+        if bytes == 0 || offset >= total {
             return;
         }
+        let bytes = bytes.min(total - offset);
 
         // Update piece tree
         self.piece_tree.delete(offset, bytes, &self.buffers);
 
-        self.mark_content_modified();
+        self.mark_content_modified_journaled(|| JournalRecord::Delete { offset, len: bytes });
+        self.edit_log
+            .record(Edit::delete(self.revision, offset, bytes));
     }
 
     /// Delete text in a range
@@ -1763,6 +1937,7 @@ impl TextBuffer {
         }
 
         // Now call apply_bulk_edits with a simple index-based closure
+        let total = self.total_bytes();
         let mut idx = 0;
         let delta = self
             .piece_tree
@@ -1772,7 +1947,23 @@ impl TextBuffer {
                 info
             });
 
-        self.mark_content_modified();
+        self.modified = true;
+        // Not described by a journal record: recovery needs a fresh snapshot
+        self.recovery_journal = None;
+        self.bump_revision();
+        // The edits are sorted descending by position, so applying them one
+        // after another gives the same contents: log them in that order
+        for &(offset, delete_len, text) in edits {
+            let delete_len = delete_len.min(total.saturating_sub(offset));
+            if delete_len > 0 {
+                self.edit_log
+                    .record(Edit::delete(self.revision, offset, delete_len));
+            }
+            if !text.is_empty() {
+                self.edit_log
+                    .record(Edit::insert(self.revision, offset, text.len()));
+            }
+        }
         delta
     }
 
@@ -2117,6 +2308,9 @@ impl TextBuffer {
             None, // line_feed_cnt unknown for unloaded chunk
             &self.buffers,
         );
+
+        // The streamed bytes aren't in any recovery snapshot
+        self.recovery_journal = None;
     }
 
     /// Check if the buffer has been modified since last save
@@ -2135,14 +2329,30 @@ impl TextBuffer {
         self.modified = modified;
     }
 
-    /// Check if buffer has pending changes for recovery auto-save
+    /// Check if buffer has changes since the last recovery auto-save
     pub fn is_recovery_pending(&self) -> bool {
-        self.recovery_pending
+        self.revision != self.recovered_revision
+    }
+
+    /// Record that the current contents were saved for recovery
+    pub fn mark_recovery_saved(&mut self) {
+        self.recovered_revision = self.revision;
+    }
+
+    /// Take the edits recorded since the last call, to append to the recovery journal
+    ///
+    /// Returns `None` if the journal can't describe the changes (no snapshot yet,
+    /// or a non-journaled edit happened), in which case the caller should save a
+    /// full snapshot and call `reset_recovery_journal`.
+    pub fn take_recovery_journal(&mut self) -> Option<Vec<JournalRecord>> {
+        self.recovery_journal_bytes = 0;
+        self.recovery_journal.as_mut().map(std::mem::take)
     }
 
-    /// Mark buffer as needing recovery auto-save (call after edits)
-    pub fn set_recovery_pending(&mut self, pending: bool) {
-        self.recovery_pending = pending;
+    /// Start journaling edits on top of a recovery snapshot of the current content
+    pub fn reset_recovery_journal(&mut self) {
+        self.recovery_journal = Some(Vec::new());
+        self.recovery_journal_bytes = 0;
     }
 
     /// Check if this is a large file with lazy loading enabled
@@ -2445,6 +2655,23 @@ impl TextBuffer {
         self.piece_tree.stats()
     }
 
+    /// Bytes held in memory by the loaded chunks and their line indexes.
+    /// Chunks of a large file that haven't been loaded don't count.
+    pub fn memory_usage(&self) -> usize {
+        self.buffers
+            .iter()
+            .map(|buffer| match &buffer.data {
+                BufferData::Loaded { data, line_starts } => {
+                    data.capacity()
+                        + line_starts
+                            .as_ref()
+                            .map_or(0, |starts| starts.capacity() * size_of::<usize>())
+                }
+                BufferData::Unloaded { .. } => 0,
+            })
+            .sum()
+    }
+
     // Search and Replace Operations
 
     /// Find the next occurrence of a pattern, with wrap-around
@@ -2735,6 +2962,48 @@ impl TextBuffer {
         }
     }
 
+    /// Convert byte position to (line, column) where the column is visual:
+    /// tabs reach the next tab stop and wide characters take two columns.
+    /// Lines that are not valid UTF-8 fall back to byte columns.
+    pub fn position_to_line_visual_col(&self, byte_pos: usize, tab_size: usize) -> (usize, usize) {
+        let line = self.get_line_number(byte_pos);
+        let line_start = self.line_start_offset(line).unwrap_or(0);
+        let column = byte_pos.saturating_sub(line_start);
+        let visual_column = self
+            .get_line(line)
+            .and_then(|bytes| {
+                std::str::from_utf8(&bytes)
+                    .ok()
+                    .map(|text| line_visual_column_at_byte(text, column, tab_size))
+            })
+            .unwrap_or(column);
+        (line, visual_column)
+    }
+
+    /// Convert (line, visual column) to byte position - the inverse of
+    /// `position_to_line_visual_col`. A column inside a tab or wide character
+    /// maps to its start, and columns past the end of the line clamp to it.
+    pub fn line_visual_col_to_position(
+        &self,
+        line: usize,
+        visual_column: usize,
+        tab_size: usize,
+    ) -> usize {
+        let Some(line_start) = self.line_start_offset(line) else {
+            return self.len();
+        };
+        let bytes = self.get_line(line).unwrap_or_default();
+        let content_len = bytes
+            .iter()
+            .rposition(|b| *b != b'\n' && *b != b'\r')
+            .map_or(0, |i| i + 1);
+        let offset = match std::str::from_utf8(&bytes[..content_len]) {
+            Ok(text) => line_byte_offset_at_visual_column(text, visual_column, tab_size),
+            Err(_) => visual_column.min(content_len),
+        };
+        line_start + offset
+    }
+
     /// Convert byte position to LSP position (line, UTF-16 code units)
     /// LSP protocol uses UTF-16 code units for character offsets
     pub fn position_to_lsp_position(&self, byte_pos: usize) -> (usize, usize) {
@@ -3531,6 +3800,31 @@ mod tests {
         assert_eq!(buffer.total_bytes(), 10);
     }
 
+    #[test]
+    fn test_recovery_journal_replays_edits() {
+        let mut buffer = TextBuffer::from_bytes(b"hello world".to_vec(), test_fs());
+
+        // No snapshot yet: edits are not journaled
+        buffer.insert_bytes(0, b">".to_vec());
+        assert!(buffer.take_recovery_journal().is_none());
+
+        let mut snapshot = buffer.get_all_text().unwrap();
+        buffer.reset_recovery_journal();
+        buffer.delete_bytes(6, 100);
+        buffer.insert_bytes(6, b"!".to_vec());
+
+        let records = buffer.take_recovery_journal().unwrap();
+        for record in &records {
+            record.apply(&mut snapshot).unwrap();
+        }
+        assert_eq!(snapshot, buffer.get_all_text().unwrap());
+        assert_eq!(snapshot, b">hello!");
+
+        // Edits the journal can't describe require a new snapshot
+        buffer.replace_content("replaced");
+        assert!(buffer.take_recovery_journal().is_none());
+    }
+
     #[test]
     fn test_delete_from_end() {
         let mut buffer = TextBuffer::from_bytes(b"hello world".to_vec(), test_fs());
@@ -4626,6 +4920,37 @@ mod tests {
         );
     }
 
+    // ===== Revision Tests =====
+
+    #[test]
+    fn test_revisions_track_recovery_and_replacement() {
+        let mut buffer = TextBuffer::from_str_test("hello");
+        assert!(!buffer.is_recovery_pending());
+
+        buffer.insert(5, " world");
+        assert!(buffer.is_recovery_pending());
+        buffer.mark_recovery_saved();
+        assert!(!buffer.is_recovery_pending());
+        assert!(buffer.unchanged_before(buffer.revision(), 0));
+
+        // Edits after an offset leave the contents before it unchanged
+        let revision = buffer.revision();
+        buffer.delete(8..10);
+        assert!(buffer.unchanged_before(revision, 8));
+        assert!(!buffer.unchanged_before(revision, 9));
+        assert!(buffer.is_recovery_pending());
+
+        // A replacement buffer continues the revisions, with the edits unknown
+        let mut replacement = TextBuffer::from_str_test("other");
+        replacement.continue_revisions_from(&buffer);
+        assert!(replacement.revision() > buffer.revision());
+        assert!(!replacement.is_recovery_pending());
+        assert_eq!(
+            replacement.changes_since(buffer.revision()),
+            BufferChanges::Reset
+        );
+    }
+
     // ===== Line Ending Conversion Tests =====
 
     mod line_ending_conversion {
@@ -4835,6 +5160,53 @@ mod tests {
 
             Ok(())
         }
+
+        #[test]
+        #[cfg(unix)]
+        fn test_save_keeps_hard_links() {
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("original.txt");
+            let link_path = temp_dir.path().join("link.txt");
+            std::fs::write(&file_path, "old").unwrap();
+            std::fs::hard_link(&file_path, &link_path).unwrap();
+
+            let mut buffer = TextBuffer::from_bytes(b"new".to_vec(), test_fs());
+            buffer.save_to_file(&file_path).unwrap();
+            assert_eq!(std::fs::read(&link_path).unwrap(), b"new");
+
+            // Renaming a new file into place leaves the other link behind
+            buffer.set_save_strategy(SaveStrategy::Rename);
+            buffer.insert(0, "newer ");
+            buffer.save_to_file(&file_path).unwrap();
+            assert_eq!(std::fs::read(&file_path).unwrap(), b"newer new");
+            assert_eq!(std::fs::read(&link_path).unwrap(), b"new");
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn test_save_through_symlink() {
+            use tempfile::TempDir;
+
+            let temp_dir = TempDir::new().unwrap();
+            let target = temp_dir.path().join("target.txt");
+            let link = temp_dir.path().join("link.txt");
+            std::fs::write(&target, "old").unwrap();
+            std::os::unix::fs::symlink(&target, &link).unwrap();
+
+            for strategy in [SaveStrategy::Rename, SaveStrategy::InPlace] {
+                let mut buffer = TextBuffer::from_bytes(b"new".to_vec(), test_fs());
+                buffer.set_save_strategy(strategy);
+                buffer.save_to_file(&link).unwrap();
+                assert!(std::fs::symlink_metadata(&link)
+                    .unwrap()
+                    .file_type()
+                    .is_symlink());
+                assert_eq!(std::fs::read(&target).unwrap(), b"new");
+                std::fs::write(&target, "old").unwrap();
+            }
+        }
     }
 
     mod large_file_encoding_tests {
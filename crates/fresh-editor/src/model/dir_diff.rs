@@ -0,0 +1,288 @@
+//! Comparison of two directory trees, for the directory diff view.
+//!
+//! The two trees are merged into one: every file or directory found on
+//! either side is an entry, marked with how it differs between them.
+//! Symbolic links to directories aren't followed, so a link cycle can't make
+//! the comparison endless, and `.git` directories are skipped.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::model::filesystem::{EntryType, FileSystem};
+
+/// Directories that aren't compared
+const SKIPPED_DIRS: &[&str] = &[".git"];
+
+/// How an entry differs between the left and right trees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirDiffStatus {
+    /// On both sides with the same content. A directory is the same if
+    /// everything in it is.
+    Same,
+    /// On both sides with different content
+    Modified,
+    /// Only on the right
+    Added,
+    /// Only on the left
+    Removed,
+}
+
+/// An entry of the merged tree of two directories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirDiffEntry {
+    /// Path relative to the compared directories
+    pub path: PathBuf,
+    /// How deep the entry is: 0 for entries directly in the compared directories
+    pub depth: usize,
+    pub is_dir: bool,
+    pub status: DirDiffStatus,
+}
+
+impl DirDiffEntry {
+    /// Whether the entry exists in the left tree
+    pub fn on_left(&self) -> bool {
+        self.status != DirDiffStatus::Added
+    }
+
+    /// Whether the entry exists in the right tree
+    pub fn on_right(&self) -> bool {
+        self.status != DirDiffStatus::Removed
+    }
+}
+
+/// Compare two directories. The entries of their merged tree are listed
+/// depth first, with the directories of each level before its files, both
+/// sorted by name.
+pub fn compare_dirs(
+    fs: &dyn FileSystem,
+    left: &Path,
+    right: &Path,
+) -> io::Result<Vec<DirDiffEntry>> {
+    let mut entries = Vec::new();
+    compare_level(fs, left, right, Path::new(""), 0, &mut entries)?;
+    Ok(entries)
+}
+
+/// One side's entry at a level of the merged tree
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+    Both,
+}
+
+/// Compare the contents of `relative` in both trees, appending their
+/// entries. Returns whether they're all the same.
+fn compare_level(
+    fs: &dyn FileSystem,
+    left: &Path,
+    right: &Path,
+    relative: &Path,
+    depth: usize,
+    entries: &mut Vec<DirDiffEntry>,
+) -> io::Result<bool> {
+    let mut names: BTreeMap<String, (Option<bool>, Option<bool>)> = BTreeMap::new();
+    for (name, is_dir) in children(fs, &left.join(relative))? {
+        names.entry(name).or_default().0 = Some(is_dir);
+    }
+    for (name, is_dir) in children(fs, &right.join(relative))? {
+        names.entry(name).or_default().1 = Some(is_dir);
+    }
+
+    // A name that's a file on one side and a directory on the other is two
+    // entries, one for each side
+    let mut items: Vec<(String, bool, Side)> = Vec::new();
+    for (name, sides) in names {
+        match sides {
+            (Some(l), Some(r)) if l == r => items.push((name, l, Side::Both)),
+            (l, r) => {
+                if let Some(is_dir) = l {
+                    items.push((name.clone(), is_dir, Side::Left));
+                }
+                if let Some(is_dir) = r {
+                    items.push((name, is_dir, Side::Right));
+                }
+            }
+        }
+    }
+    items.sort_by_key(|(_, is_dir, _)| !is_dir);
+
+    let mut all_same = true;
+    for (name, is_dir, side) in items {
+        let path = relative.join(&name);
+        let index = entries.len();
+        entries.push(DirDiffEntry {
+            path: path.clone(),
+            depth,
+            is_dir,
+            status: DirDiffStatus::Same,
+        });
+        let status = match side {
+            Side::Both if is_dir => {
+                if compare_level(fs, left, right, &path, depth + 1, entries)? {
+                    DirDiffStatus::Same
+                } else {
+                    DirDiffStatus::Modified
+                }
+            }
+            Side::Both => {
+                if files_equal(fs, &left.join(&path), &right.join(&path))? {
+                    DirDiffStatus::Same
+                } else {
+                    DirDiffStatus::Modified
+                }
+            }
+            Side::Left | Side::Right => {
+                let (root, status) = if side == Side::Left {
+                    (left, DirDiffStatus::Removed)
+                } else {
+                    (right, DirDiffStatus::Added)
+                };
+                if is_dir {
+                    list_one_side(fs, root, &path, depth + 1, status, entries)?;
+                }
+                status
+            }
+        };
+        entries[index].status = status;
+        all_same &= status == DirDiffStatus::Same;
+    }
+    Ok(all_same)
+}
+
+/// Append everything in a directory found on one side only
+fn list_one_side(
+    fs: &dyn FileSystem,
+    root: &Path,
+    relative: &Path,
+    depth: usize,
+    status: DirDiffStatus,
+    entries: &mut Vec<DirDiffEntry>,
+) -> io::Result<()> {
+    let mut items = children(fs, &root.join(relative))?;
+    items.sort_by(|(a, a_dir), (b, b_dir)| b_dir.cmp(a_dir).then_with(|| a.cmp(b)));
+    for (name, is_dir) in items {
+        let path = relative.join(&name);
+        entries.push(DirDiffEntry {
+            path: path.clone(),
+            depth,
+            is_dir,
+            status,
+        });
+        if is_dir {
+            list_one_side(fs, root, &path, depth + 1, status, entries)?;
+        }
+    }
+    Ok(())
+}
+
+/// The names of a directory's entries, with whether each is a directory.
+/// A directory that doesn't exist has none.
+fn children(fs: &dyn FileSystem, dir: &Path) -> io::Result<Vec<(String, bool)>> {
+    if !fs.exists(dir) {
+        return Ok(Vec::new());
+    }
+    Ok(fs
+        .read_dir(dir)?
+        .into_iter()
+        .filter(|entry| !(entry.entry_type == EntryType::Symlink && entry.symlink_target_is_dir))
+        .map(|entry| (entry.name, entry.entry_type == EntryType::Directory))
+        .filter(|(name, is_dir)| !(*is_dir && SKIPPED_DIRS.contains(&name.as_str())))
+        .collect())
+}
+
+fn files_equal(fs: &dyn FileSystem, left: &Path, right: &Path) -> io::Result<bool> {
+    if fs.metadata(left)?.size != fs.metadata(right)?.size {
+        return Ok(false);
+    }
+    Ok(fs.read_file(left)? == fs.read_file(right)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+    use std::fs;
+
+    fn entry(path: &str, depth: usize, is_dir: bool, status: DirDiffStatus) -> DirDiffEntry {
+        DirDiffEntry {
+            path: PathBuf::from(path),
+            depth,
+            is_dir,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_compare_dirs_marks_each_entry() {
+        let left = tempfile::tempdir().unwrap();
+        let right = tempfile::tempdir().unwrap();
+        fs::write(left.path().join("same.txt"), "same").unwrap();
+        fs::write(right.path().join("same.txt"), "same").unwrap();
+        fs::write(left.path().join("changed.txt"), "old").unwrap();
+        fs::write(right.path().join("changed.txt"), "new").unwrap();
+        fs::write(left.path().join("removed.txt"), "").unwrap();
+        fs::write(right.path().join("added.txt"), "").unwrap();
+
+        let entries = compare_dirs(&StdFileSystem, left.path(), right.path()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                entry("added.txt", 0, false, DirDiffStatus::Added),
+                entry("changed.txt", 0, false, DirDiffStatus::Modified),
+                entry("removed.txt", 0, false, DirDiffStatus::Removed),
+                entry("same.txt", 0, false, DirDiffStatus::Same),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_dirs_merges_subdirectories() {
+        let left = tempfile::tempdir().unwrap();
+        let right = tempfile::tempdir().unwrap();
+        for root in [left.path(), right.path()] {
+            fs::create_dir_all(root.join("equal")).unwrap();
+            fs::write(root.join("equal/a.txt"), "a").unwrap();
+            fs::create_dir_all(root.join("differs")).unwrap();
+            fs::write(root.join("top.txt"), "top").unwrap();
+        }
+        fs::write(left.path().join("differs/b.txt"), "left").unwrap();
+        fs::write(right.path().join("differs/b.txt"), "right").unwrap();
+        fs::create_dir_all(right.path().join("new/inner")).unwrap();
+        fs::write(right.path().join("new/inner/c.txt"), "c").unwrap();
+        fs::create_dir_all(left.path().join(".git")).unwrap();
+
+        let entries = compare_dirs(&StdFileSystem, left.path(), right.path()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                entry("differs", 0, true, DirDiffStatus::Modified),
+                entry("differs/b.txt", 1, false, DirDiffStatus::Modified),
+                entry("equal", 0, true, DirDiffStatus::Same),
+                entry("equal/a.txt", 1, false, DirDiffStatus::Same),
+                entry("new", 0, true, DirDiffStatus::Added),
+                entry("new/inner", 1, true, DirDiffStatus::Added),
+                entry("new/inner/c.txt", 2, false, DirDiffStatus::Added),
+                entry("top.txt", 0, false, DirDiffStatus::Same),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compare_dirs_file_replaced_by_directory() {
+        let left = tempfile::tempdir().unwrap();
+        let right = tempfile::tempdir().unwrap();
+        fs::write(left.path().join("thing"), "file").unwrap();
+        fs::create_dir_all(right.path().join("thing")).unwrap();
+
+        let entries = compare_dirs(&StdFileSystem, left.path(), right.path()).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                entry("thing", 0, true, DirDiffStatus::Added),
+                entry("thing", 0, false, DirDiffStatus::Removed),
+            ]
+        );
+    }
+}
@@ -1,12 +1,28 @@
+//! Edits of a buffer, by revision
+//!
+//! Every change to a buffer's contents advances its revision. The buffer
+//! keeps a log of its recent edits so that anything caching data about its
+//! contents (highlighting, language servers, plugins, ...) can remember the
+//! revision the data is for, and later ask for the edits made since, instead
+//! of keeping its own dirty flag.
+
+use std::collections::VecDeque;
+
+use crate::model::buffer::TextBuffer;
+
+/// Edits kept in a buffer's log; consumers further behind get a reset
+const MAX_LOGGED_EDITS: usize = 10_000;
+
 /// Represents a single edit operation in the buffer's history
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Edit {
-    pub version: u64,
+    /// The revision the edit produced
+    pub revision: u64,
     pub kind: EditKind,
 }
 
 /// The type of edit operation
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum EditKind {
     /// Insert operation: bytes were inserted at offset
     Insert { offset: usize, len: usize },
@@ -16,18 +32,176 @@ pub enum EditKind {
 
 impl Edit {
     /// Create a new insert edit
-    pub fn insert(version: u64, offset: usize, len: usize) -> Self {
+    pub fn insert(revision: u64, offset: usize, len: usize) -> Self {
         Self {
-            version,
+            revision,
             kind: EditKind::Insert { offset, len },
         }
     }
 
     /// Create a new delete edit
-    pub fn delete(version: u64, offset: usize, len: usize) -> Self {
+    pub fn delete(revision: u64, offset: usize, len: usize) -> Self {
         Self {
-            version,
+            revision,
             kind: EditKind::Delete { offset, len },
         }
     }
+
+    /// The byte offset the edit starts at
+    pub fn offset(&self) -> usize {
+        match self.kind {
+            EditKind::Insert { offset, .. } | EditKind::Delete { offset, .. } => offset,
+        }
+    }
+}
+
+/// The changes to a buffer since a revision
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BufferChanges {
+    /// The edits made since, oldest first, in the order they were applied.
+    /// Empty if nothing changed.
+    Edits(Vec<Edit>),
+    /// The edits aren't known: the revision is too old, or the contents
+    /// were replaced as a whole (undo of a bulk edit, reload, ...).
+    /// Anything derived from the contents must be recomputed.
+    Reset,
+}
+
+/// The recent edits of a buffer
+#[derive(Clone, Debug, Default)]
+pub struct EditLog {
+    edits: VecDeque<Edit>,
+    /// The revision from which every edit is in the log
+    complete_since: u64,
+}
+
+impl EditLog {
+    /// Record an edit, forgetting the oldest one if the log is full
+    pub fn record(&mut self, edit: Edit) {
+        if self.edits.len() == MAX_LOGGED_EDITS {
+            if let Some(oldest) = self.edits.pop_front() {
+                self.complete_since = oldest.revision;
+            }
+        }
+        self.edits.push_back(edit);
+    }
+
+    /// Forget all edits: the contents at `revision` can't be described by
+    /// edits of the earlier contents
+    pub fn reset(&mut self, revision: u64) {
+        self.edits.clear();
+        self.complete_since = revision;
+    }
+
+    /// The changes from `since` to `current`
+    pub fn since(&self, since: u64, current: u64) -> BufferChanges {
+        // A revision from the future is from contents this buffer replaced
+        if since < self.complete_since || since > current {
+            return BufferChanges::Reset;
+        }
+        let start = self.edits.partition_point(|edit| edit.revision <= since);
+        BufferChanges::Edits(self.edits.range(start..).cloned().collect())
+    }
+}
+
+/// The revision of a buffer a consumer last caught up with, to get the
+/// changes made after it
+#[derive(Clone, Debug, Default)]
+pub struct ChangeSubscription {
+    revision: Option<u64>,
+}
+
+impl ChangeSubscription {
+    /// The changes since the last call, or None if the buffer is unchanged.
+    /// The first call gets a reset.
+    pub fn poll(&mut self, buffer: &TextBuffer) -> Option<BufferChanges> {
+        let revision = buffer.revision();
+        let changes = match self.revision {
+            Some(seen) if seen == revision => return None,
+            Some(seen) => buffer.changes_since(seen),
+            None => BufferChanges::Reset,
+        };
+        self.revision = Some(revision);
+        Some(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edits_since_revision() {
+        let mut log = EditLog::default();
+        log.record(Edit::insert(1, 0, 5));
+        log.record(Edit::delete(2, 3, 1));
+        log.record(Edit::insert(3, 10, 2));
+
+        assert_eq!(
+            log.since(1, 3),
+            BufferChanges::Edits(vec![Edit::delete(2, 3, 1), Edit::insert(3, 10, 2)])
+        );
+        assert_eq!(log.since(3, 3), BufferChanges::Edits(Vec::new()));
+        // Contents replaced by a buffer with fewer revisions
+        assert_eq!(log.since(4, 3), BufferChanges::Reset);
+    }
+
+    #[test]
+    fn test_reset_and_truncation() {
+        let mut log = EditLog::default();
+        log.record(Edit::insert(1, 0, 1));
+        log.reset(2);
+        log.record(Edit::insert(3, 0, 1));
+        assert_eq!(log.since(1, 3), BufferChanges::Reset);
+        assert_eq!(
+            log.since(2, 3),
+            BufferChanges::Edits(vec![Edit::insert(3, 0, 1)])
+        );
+
+        let mut log = EditLog::default();
+        for revision in 1..=MAX_LOGGED_EDITS as u64 + 1 {
+            log.record(Edit::insert(revision, 0, 1));
+        }
+        assert_eq!(
+            log.since(0, MAX_LOGGED_EDITS as u64 + 1),
+            BufferChanges::Reset
+        );
+        assert!(
+            matches!(log.since(1, MAX_LOGGED_EDITS as u64 + 1), BufferChanges::Edits(edits) if edits.len() == MAX_LOGGED_EDITS)
+        );
+    }
+
+    #[test]
+    fn test_subscription() {
+        let mut buffer = TextBuffer::from_str_test("hello world");
+        let mut subscription = ChangeSubscription::default();
+        assert_eq!(subscription.poll(&buffer), Some(BufferChanges::Reset));
+        assert_eq!(subscription.poll(&buffer), None);
+
+        buffer.insert_bytes(5, b",".to_vec());
+        buffer.delete_bytes(0, 1);
+        let revision = buffer.revision();
+        assert_eq!(
+            subscription.poll(&buffer),
+            Some(BufferChanges::Edits(vec![
+                Edit::insert(revision - 1, 5, 1),
+                Edit::delete(revision, 0, 1)
+            ]))
+        );
+        assert_eq!(subscription.poll(&buffer), None);
+
+        // Several edits of a bulk edit share a revision
+        buffer.apply_bulk_edits(&[(6, 5, "there"), (0, 0, "H")]);
+        assert_eq!(
+            subscription.poll(&buffer),
+            Some(BufferChanges::Edits(vec![
+                Edit::delete(revision + 1, 6, 5),
+                Edit::insert(revision + 1, 6, 5),
+                Edit::insert(revision + 1, 0, 1)
+            ]))
+        );
+
+        buffer.restore_piece_tree(&buffer.snapshot_piece_tree());
+        assert_eq!(subscription.poll(&buffer), Some(BufferChanges::Reset));
+    }
 }
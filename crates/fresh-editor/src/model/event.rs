@@ -604,23 +604,15 @@ impl EventLog {
         }
     }
 
-    /// Enable streaming events to a file (runtime only)
+    /// Enable streaming events to a file, appending to it so several logs
+    /// can share one (runtime only)
     #[cfg(feature = "runtime")]
     pub fn enable_streaming<P: AsRef<std::path::Path>>(&mut self, path: P) -> std::io::Result<()> {
-        use std::io::Write;
-
-        let mut file = std::fs::OpenOptions::new()
+        let file = std::fs::OpenOptions::new()
             .create(true)
-            .write(true)
-            .truncate(true)
+            .append(true)
             .open(path)?;
 
-        // Write header
-        writeln!(file, "# Event Log Stream")?;
-        writeln!(file, "# Started at: {}", chrono::Local::now())?;
-        writeln!(file, "# Format: JSON Lines (one event per line)")?;
-        writeln!(file, "#")?;
-
         self.stream_file = Some(file);
         Ok(())
     }
@@ -837,6 +829,36 @@ impl EventLog {
         self.snapshots.clear();
     }
 
+    /// The entries that can still be undone or redone once the buffer is
+    /// reloaded from disk, and the current position among them
+    ///
+    /// Bulk edits keep their piece trees in memory only, so the history stops
+    /// at the nearest bulk edit on either side of the current position.
+    pub fn persistable_history(&self) -> (&[LogEntry], usize) {
+        let is_bulk_edit = |entry: &LogEntry| contains_bulk_edit(&entry.event);
+        let start = self.entries[..self.current_index]
+            .iter()
+            .rposition(is_bulk_edit)
+            .map_or(0, |index| index + 1);
+        let end = self.entries[self.current_index..]
+            .iter()
+            .position(is_bulk_edit)
+            .map_or(self.entries.len(), |index| self.current_index + index);
+        (&self.entries[start..end], self.current_index - start)
+    }
+
+    /// A log of persisted history, positioned (and marked saved) at
+    /// `current_index`
+    pub fn from_history(entries: Vec<LogEntry>, current_index: usize) -> Self {
+        let current_index = current_index.min(entries.len());
+        Self {
+            entries,
+            current_index,
+            saved_at_index: Some(current_index),
+            ..Self::new()
+        }
+    }
+
     /// Save event log to JSON Lines format
     pub fn save_to_file(&self, path: &std::path::Path) -> std::io::Result<()> {
         use std::io::Write;
@@ -879,6 +901,15 @@ impl EventLog {
     }
 }
 
+/// Whether an event is or contains a bulk edit
+fn contains_bulk_edit(event: &Event) -> bool {
+    match event {
+        Event::BulkEdit { .. } => true,
+        Event::Batch { events, .. } => events.iter().any(contains_bulk_edit),
+        _ => false,
+    }
+}
+
 impl Default for EventLog {
     fn default() -> Self {
         Self::new()
@@ -1154,4 +1185,40 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    #[test]
+    fn test_persistable_history_stops_at_bulk_edits() {
+        let insert = |position| Event::Insert {
+            position,
+            text: "x".to_string(),
+            cursor_id: CursorId(0),
+        };
+        let bulk_edit = || Event::BulkEdit {
+            old_tree: None,
+            new_tree: None,
+            old_cursors: Vec::new(),
+            new_cursors: Vec::new(),
+            description: "Indent".to_string(),
+        };
+
+        let mut log = EventLog::new();
+        log.append(insert(0));
+        log.append(bulk_edit());
+        log.append(insert(1));
+        log.append(insert(2));
+        log.append(bulk_edit());
+        log.append(insert(3));
+        log.undo();
+        log.undo();
+
+        // Between the two bulk edits, positioned after insert(2)
+        let (entries, current_index) = log.persistable_history();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(current_index, 2);
+
+        let restored = EventLog::from_history(entries.to_vec(), current_index);
+        assert_eq!(restored.current_index(), 2);
+        assert!(restored.is_at_saved_position());
+        assert!(!restored.can_redo());
+    }
 }
@@ -110,6 +110,8 @@ pub struct FileMetadata {
     /// File owner GID (Unix only)
     #[cfg(unix)]
     pub gid: Option<u32>,
+    /// Number of hard links to the file, if known
+    pub nlink: Option<u64>,
 }
 
 impl FileMetadata {
@@ -125,6 +127,7 @@ impl FileMetadata {
             uid: None,
             #[cfg(unix)]
             gid: None,
+            nlink: None,
         }
     }
 
@@ -455,6 +458,31 @@ pub trait FileSystem: Send + Sync {
         }
     }
 
+    /// Check if the current user may write to an existing file.
+    ///
+    /// A file without any write permission bits counts as unwritable even for
+    /// root, matching what other editors treat as read-only.
+    fn is_writable(&self, path: &Path) -> bool {
+        let Ok(meta) = self.metadata(path) else {
+            return true;
+        };
+        if meta.is_readonly {
+            return false;
+        }
+        #[cfg(unix)]
+        {
+            if let (Some(uid), Some(permissions)) = (meta.uid, &meta.permissions) {
+                let current_uid = self.current_uid();
+                if current_uid != 0 && uid != current_uid {
+                    // Group membership isn't known here, so group-writable
+                    // files are given the benefit of the doubt
+                    return permissions.mode() & 0o022 != 0;
+                }
+            }
+        }
+        true
+    }
+
     /// Get a temporary file path for atomic writes
     fn temp_path_for(&self, path: &Path) -> PathBuf {
         path.with_extension("tmp")
@@ -645,6 +673,7 @@ impl StdFileSystem {
                 is_readonly: meta.permissions().readonly(),
                 uid: Some(meta.uid()),
                 gid: Some(meta.gid()),
+                nlink: Some(meta.nlink()),
             }
         }
         #[cfg(not(unix))]
@@ -655,9 +684,27 @@ impl StdFileSystem {
                 permissions: Some(FilePermissions::from_std(meta.permissions())),
                 is_hidden: Self::is_hidden(path),
                 is_readonly: meta.permissions().readonly(),
+                nlink: None,
             }
         }
     }
+
+    /// Where a write to `path` should go: through a symlink to the file it
+    /// points to, so saving updates the target instead of replacing the link
+    fn write_target(path: &Path) -> PathBuf {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                std::fs::canonicalize(path).unwrap_or_else(|_| {
+                    // A dangling link: create the file it points to
+                    match (std::fs::read_link(path), path.parent()) {
+                        (Ok(target), Some(parent)) => parent.join(target),
+                        _ => path.to_path_buf(),
+                    }
+                })
+            }
+            _ => path.to_path_buf(),
+        }
+    }
 }
 
 impl FileSystem for StdFileSystem {
@@ -675,19 +722,25 @@ impl FileSystem for StdFileSystem {
     }
 
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
-        let original_metadata = self.metadata_if_exists(path);
-        let temp_path = self.temp_path_for(path);
+        let path = Self::write_target(path);
+        let original_metadata = self.metadata_if_exists(&path);
+        let temp_path = self.temp_path_for(&path);
         {
             let mut file = self.create_file(&temp_path)?;
             file.write_all(data)?;
             file.sync_all()?;
         }
         if let Some(ref meta) = original_metadata {
+            // Keep the group (and the owner, when running as root). This is
+            // best effort: other users' files are written in place instead.
+            #[cfg(unix)]
+            let _ = std::os::unix::fs::chown(&temp_path, meta.uid, meta.gid);
+            // After chown, which may clear the setuid and setgid bits
             if let Some(ref perms) = meta.permissions {
                 let _ = self.set_permissions(&temp_path, perms);
             }
         }
-        self.rename(&temp_path, path)?;
+        self.rename(&temp_path, &path)?;
         Ok(())
     }
 
@@ -1104,6 +1157,23 @@ mod tests {
         assert!(meta.is_readonly);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_is_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("readonly.txt");
+        assert!(fs.is_writable(&path));
+
+        std::fs::write(&path, "text").unwrap();
+        assert!(fs.is_writable(&path));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444)).unwrap();
+        assert!(!fs.is_writable(&path));
+    }
+
     #[test]
     fn test_atomic_write() {
         let fs = StdFileSystem;
@@ -1117,6 +1187,35 @@ mod tests {
         assert_eq!(fs.read_file(&path).unwrap(), b"updated");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_write_file_through_symlink() {
+        let fs = StdFileSystem;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let target = temp_dir.path().join("target.txt");
+        let link = temp_dir.path().join("link.txt");
+        fs.write_file(&target, b"initial").unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        fs.write_file(&link, b"updated").unwrap();
+        assert!(std::fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs.read_file(&target).unwrap(), b"updated");
+
+        // A dangling link gets its target created
+        let missing = temp_dir.path().join("missing.txt");
+        let dangling = temp_dir.path().join("dangling.txt");
+        std::os::unix::fs::symlink("missing.txt", &dangling).unwrap();
+        fs.write_file(&dangling, b"created").unwrap();
+        assert_eq!(fs.read_file(&missing).unwrap(), b"created");
+        assert!(std::fs::symlink_metadata(&dangling)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+    }
+
     #[test]
     fn test_write_patched_default_impl() {
         // Test that the default write_patched implementation works correctly
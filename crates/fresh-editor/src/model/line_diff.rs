@@ -84,14 +84,14 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
 
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
-struct LineMatch {
-    saved_idx: usize,
-    current_idx: usize,
+pub(crate) struct LineMatch {
+    pub(crate) saved_idx: usize,
+    pub(crate) current_idx: usize,
 }
 
 /// Find the longest common subsequence of lines between saved and current.
 /// Returns a list of LineMatch with both saved and current indices.
-fn longest_common_subsequence(saved: &[&[u8]], current: &[&[u8]]) -> Vec<LineMatch> {
+pub(crate) fn longest_common_subsequence(saved: &[&[u8]], current: &[&[u8]]) -> Vec<LineMatch> {
     let n = saved.len();
     let m = current.len();
 
@@ -275,6 +275,78 @@ fn classify_change(
     }
 }
 
+/// A contiguous block of differing lines, expressed as line ranges in both inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineHunk {
+    /// Lines in the "saved" (old) content
+    pub old: Range<usize>,
+    /// Lines in the "current" (new) content
+    pub new: Range<usize>,
+}
+
+/// Upper bound on the LCS table size (old lines × new lines) used by [`diff_hunks`].
+/// Beyond this the differing middle section is reported as a single hunk.
+const MAX_HUNK_LCS_CELLS: usize = 4_000_000;
+
+/// Compare two byte slices line by line and return the changed regions as hunks.
+///
+/// Unlike [`diff_lines`], which only reports ranges in the current buffer, each hunk
+/// carries the matching range in both inputs so the two sides can be aligned
+/// (e.g. for a side-by-side diff). Common leading and trailing lines are trimmed
+/// before running the LCS, so typical edits stay cheap even for long files.
+pub fn diff_hunks(saved: &[u8], current: &[u8]) -> Vec<LineHunk> {
+    if saved == current {
+        return vec![];
+    }
+
+    let saved_lines: Vec<&[u8]> = saved.split(|&b| b == b'\n').collect();
+    let current_lines: Vec<&[u8]> = current.split(|&b| b == b'\n').collect();
+
+    let prefix = saved_lines
+        .iter()
+        .zip(current_lines.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = saved_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(current_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &saved_lines[prefix..saved_lines.len() - suffix];
+    let new_mid = &current_lines[prefix..current_lines.len() - suffix];
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_HUNK_LCS_CELLS {
+        return vec![LineHunk {
+            old: prefix..prefix + old_mid.len(),
+            new: prefix..prefix + new_mid.len(),
+        }];
+    }
+
+    let lcs = longest_common_subsequence(old_mid, new_mid);
+
+    // Walk the matches; every gap between consecutive matches is a hunk
+    let mut hunks = Vec::new();
+    let mut old_pos = 0;
+    let mut new_pos = 0;
+    let sentinel = LineMatch {
+        saved_idx: old_mid.len(),
+        current_idx: new_mid.len(),
+    };
+    for m in lcs.iter().chain(std::iter::once(&sentinel)) {
+        if m.saved_idx > old_pos || m.current_idx > new_pos {
+            hunks.push(LineHunk {
+                old: prefix + old_pos..prefix + m.saved_idx,
+                new: prefix + new_pos..prefix + m.current_idx,
+            });
+        }
+        old_pos = m.saved_idx + 1;
+        new_pos = m.current_idx + 1;
+    }
+    hunks
+}
+
 /// Merge adjacent or overlapping ranges.
 pub fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
     if ranges.is_empty() {
@@ -486,6 +558,50 @@ mod tests {
         assert!(!diff.equal);
         assert_eq!(diff.changed_lines, vec![0..1]);
     }
+
+    #[test]
+    fn test_diff_hunks_identical() {
+        assert!(diff_hunks(b"a\nb\n", b"a\nb\n").is_empty());
+    }
+
+    #[test]
+    fn test_diff_hunks_modification_insertion_deletion() {
+        let saved = b"a\nb\nc\nd\ne\n";
+        let current = b"a\nB\nc\nd\nx\ny\ne\n";
+        assert_eq!(
+            diff_hunks(saved, current),
+            vec![
+                LineHunk {
+                    old: 1..2,
+                    new: 1..2
+                },
+                LineHunk {
+                    old: 4..4,
+                    new: 4..6
+                },
+            ]
+        );
+
+        let deleted = b"a\nc\nd\ne\n";
+        assert_eq!(
+            diff_hunks(saved, deleted),
+            vec![LineHunk {
+                old: 1..2,
+                new: 1..1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diff_hunks_against_empty() {
+        assert_eq!(
+            diff_hunks(b"", b"one\ntwo"),
+            vec![LineHunk {
+                old: 0..1,
+                new: 0..2
+            }]
+        );
+    }
 }
 
 #[cfg(test)]
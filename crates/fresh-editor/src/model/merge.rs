@@ -0,0 +1,247 @@
+//! Three-way merge of text, line by line
+//!
+//! Used when a file changes on disk while its buffer has unsaved changes: the
+//! changes made on disk since the buffer last saw the file (`base` to
+//! `theirs`) are merged into the buffer's text (`ours`). Where both changed
+//! the same lines differently, both versions are kept between conflict
+//! markers, for the user to resolve:
+//!
+//! ```text
+//! <<<<<<< buffer
+//! the lines as edited in the buffer
+//! =======
+//! the lines as changed on disk
+//! >>>>>>> disk
+//! ```
+
+use std::ops::Range;
+
+use super::line_diff::longest_common_subsequence;
+
+/// Most line pairs compared when diffing, after skipping the lines the texts
+/// start and end with in common
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// First line of a conflict, before the buffer's version
+pub const CONFLICT_OURS_MARKER: &str = "<<<<<<< buffer\n";
+/// Line between the buffer's and the disk's version of a conflict
+pub const CONFLICT_SEPARATOR: &str = "=======\n";
+/// Last line of a conflict, after the disk's version
+pub const CONFLICT_THEIRS_MARKER: &str = ">>>>>>> disk\n";
+
+/// A change to make to the text being merged into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// Byte range of the text to replace
+    pub range: Range<usize>,
+    pub text: String,
+    /// For a conflict, the byte ranges within `text` of the buffer's section
+    /// and of the disk's section (each with its marker lines)
+    pub conflict: Option<(Range<usize>, Range<usize>)>,
+}
+
+/// Merge the changes from `base` to `theirs` into `ours`. Returns the
+/// replacements to make in `ours`, sorted by position, or `None` if the
+/// texts are too different to be compared.
+pub fn merge3(base: &str, ours: &str, theirs: &str) -> Option<Vec<Replacement>> {
+    let base_lines = lines(base);
+    let ours_lines = lines(ours);
+    let theirs_lines = lines(theirs);
+    let to_ours = line_matches(&base_lines, &ours_lines)?;
+    let to_theirs = line_matches(&base_lines, &theirs_lines)?;
+
+    let mut ours_offsets = vec![0];
+    for line in &ours_lines {
+        ours_offsets.push(ours_offsets.last().unwrap() + line.len());
+    }
+
+    // Base lines still in both texts split them into chunks that changed on
+    // at most one side, or on both (a conflict)
+    let mut replacements = Vec::new();
+    let (mut b, mut o, mut t) = (0, 0, 0);
+    for k in 0..=base_lines.len() {
+        let anchor = if k == base_lines.len() {
+            Some((ours_lines.len(), theirs_lines.len()))
+        } else {
+            to_ours[k].zip(to_theirs[k])
+        };
+        let Some((a, c)) = anchor else {
+            continue;
+        };
+
+        let base_chunk = &base_lines[b..k];
+        let ours_chunk = &ours_lines[o..a];
+        let theirs_chunk = &theirs_lines[t..c];
+        if theirs_chunk != base_chunk && theirs_chunk != ours_chunk {
+            let range = ours_offsets[o]..ours_offsets[a];
+            if ours_chunk == base_chunk {
+                replacements.push(Replacement {
+                    range,
+                    text: theirs_chunk.concat(),
+                    conflict: None,
+                });
+            } else {
+                replacements.push(conflict(range, ours_chunk, theirs_chunk));
+            }
+        }
+        (b, o, t) = (k + 1, a + 1, c + 1);
+    }
+    Some(replacements)
+}
+
+/// The replacements turning `old` into `new`, or `None` if they're too
+/// different to be compared
+pub fn diff(old: &str, new: &str) -> Option<Vec<Replacement>> {
+    merge3(old, old, new)
+}
+
+/// Where `offset` ends up after the replacements. Offsets inside replaced
+/// text keep their distance from its start, within the replacement up to its
+/// final newline, so a cursor stays put on a changed line; offsets at an
+/// insertion move after it.
+pub fn map_offset(replacements: &[Replacement], offset: usize) -> usize {
+    let mut shift: isize = 0;
+    for replacement in replacements {
+        if offset < replacement.range.start {
+            break;
+        }
+        if offset < replacement.range.end {
+            let within = (offset - replacement.range.start)
+                .min(replacement.text.trim_end_matches('\n').len());
+            return (replacement.range.start as isize + shift) as usize + within;
+        }
+        shift += replacement.text.len() as isize - replacement.range.len() as isize;
+    }
+    (offset as isize + shift) as usize
+}
+
+/// The lines of `text`, each with its newline
+fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// For each line of `base`, the line of `other` it was matched with, if any
+fn line_matches(base: &[&str], other: &[&str]) -> Option<Vec<Option<usize>>> {
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let base_middle: Vec<&[u8]> = base[prefix..base.len() - suffix]
+        .iter()
+        .map(|line| line.as_bytes())
+        .collect();
+    let other_middle: Vec<&[u8]> = other[prefix..other.len() - suffix]
+        .iter()
+        .map(|line| line.as_bytes())
+        .collect();
+    if base_middle.len().saturating_mul(other_middle.len()) > MAX_DIFF_CELLS {
+        return None;
+    }
+
+    let mut matches = vec![None; base.len()];
+    for (i, line_match) in matches.iter_mut().enumerate().take(prefix) {
+        *line_match = Some(i);
+    }
+    for m in longest_common_subsequence(&base_middle, &other_middle) {
+        matches[prefix + m.saved_idx] = Some(prefix + m.current_idx);
+    }
+    for i in 0..suffix {
+        matches[base.len() - suffix + i] = Some(other.len() - suffix + i);
+    }
+    Some(matches)
+}
+
+/// Replace `range` with both versions of the lines, between conflict markers
+fn conflict(range: Range<usize>, ours: &[&str], theirs: &[&str]) -> Replacement {
+    let push_section = |text: &mut String, lines: &[&str]| {
+        text.push_str(&lines.concat());
+        if !text.ends_with('\n') {
+            text.push('\n');
+        }
+    };
+    let mut text = CONFLICT_OURS_MARKER.to_string();
+    push_section(&mut text, ours);
+    let ours_end = text.len();
+    text.push_str(CONFLICT_SEPARATOR);
+    push_section(&mut text, theirs);
+    text.push_str(CONFLICT_THEIRS_MARKER);
+    let conflict = Some((0..ours_end, ours_end..text.len()));
+    Replacement {
+        range,
+        text,
+        conflict,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, replacements: &[Replacement]) -> String {
+        let mut result = text.to_string();
+        for replacement in replacements.iter().rev() {
+            result.replace_range(replacement.range.clone(), &replacement.text);
+        }
+        result
+    }
+
+    #[test]
+    fn test_merge_changes_on_both_sides() {
+        let base = "a\nb\nc\nd\n";
+        let ours = "a\nB\nc\nd\n";
+        let theirs = "a\nb\nc\nD\ne\n";
+        let replacements = merge3(base, ours, theirs).unwrap();
+        assert_eq!(apply(ours, &replacements), "a\nB\nc\nD\ne\n");
+        assert!(replacements.iter().all(|r| r.conflict.is_none()));
+
+        // The same change on both sides is no conflict
+        let replacements = merge3(base, theirs, theirs).unwrap();
+        assert!(replacements.is_empty());
+    }
+
+    #[test]
+    fn test_merge_conflict() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nmine\nc\n";
+        let theirs = "a\ntheirs\nc\n";
+        let replacements = merge3(base, ours, theirs).unwrap();
+        assert_eq!(replacements.len(), 1);
+        assert_eq!(
+            apply(ours, &replacements),
+            "a\n<<<<<<< buffer\nmine\n=======\ntheirs\n>>>>>>> disk\nc\n"
+        );
+
+        let replacement = &replacements[0];
+        let (ours_section, theirs_section) = replacement.conflict.clone().unwrap();
+        assert_eq!(&replacement.text[ours_section], "<<<<<<< buffer\nmine\n");
+        assert_eq!(
+            &replacement.text[theirs_section],
+            "=======\ntheirs\n>>>>>>> disk\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_and_map_offset() {
+        let old = "one\ntwo\nthree\n";
+        let new = "zero\none\nthree\nfour\n";
+        let replacements = diff(old, new).unwrap();
+        assert_eq!(apply(old, &replacements), new);
+
+        // "one" moved down a line, "two" was removed, "three" follows
+        assert_eq!(map_offset(&replacements, 0), 5);
+        assert_eq!(map_offset(&replacements, 2), 7);
+        assert_eq!(map_offset(&replacements, 5), 9);
+        assert_eq!(map_offset(&replacements, 8), 9);
+        assert_eq!(map_offset(&replacements, 10), 11);
+        assert_eq!(map_offset(&replacements, old.len()), new.len());
+
+        // On a changed line, the cursor keeps its column
+        let replacements = diff("Line 3\nend\n", "Line X\nend\n").unwrap();
+        assert_eq!(map_offset(&replacements, 6), 6);
+        let replacements = diff("Line 3\nend\n", "Li\nend\n").unwrap();
+        assert_eq!(map_offset(&replacements, 6), 2);
+    }
+}
@@ -6,6 +6,7 @@ pub mod buffer;
 pub mod composite_buffer;
 pub mod control_event;
 pub mod cursor;
+pub mod dir_diff;
 pub mod document_model;
 pub mod edit;
 pub mod encoding;
@@ -14,5 +15,6 @@ pub mod filesystem;
 pub mod line_diff;
 pub mod marker;
 pub mod marker_tree;
+pub mod merge;
 pub mod piece_tree;
 pub mod piece_tree_diff;
@@ -4,9 +4,11 @@
 //! enabling a 4-level overlay architecture (System → User → Project → Session).
 
 use crate::config::{
-    AcceptSuggestionOnEnter, CursorStyle, FileBrowserConfig, FileExplorerConfig, FormatterConfig,
-    HighlighterPreference, Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig,
-    LineEndingOption, OnSaveAction, PluginConfig, TerminalConfig, ThemeName, WarningsConfig,
+    AcceptSuggestionOnEnter, AppearanceConfig, AppearanceMode, ConcealRule, CursorStyle,
+    FileBrowserConfig, FileExplorerConfig, FileTemplate, FormatterConfig, HighlighterPreference,
+    Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig, LineEndingOption, LineNumberMode,
+    OnSaveAction, PluginConfig, PluginPermissions, RenderWhitespace, SaveStrategy, TerminalConfig,
+    ThemeName, WarningsConfig,
 };
 use crate::types::LspServerConfig;
 use serde::{Deserialize, Serialize};
@@ -75,6 +77,7 @@ where
 pub struct PartialConfig {
     pub version: Option<u32>,
     pub theme: Option<ThemeName>,
+    pub appearance: Option<PartialAppearanceConfig>,
     pub locale: Option<String>,
     pub check_for_updates: Option<bool>,
     pub editor: Option<PartialEditorConfig>,
@@ -99,6 +102,7 @@ impl Merge for PartialConfig {
         self.check_for_updates.merge_from(&other.check_for_updates);
 
         // Nested structs: merge recursively
+        merge_partial(&mut self.appearance, &other.appearance);
         merge_partial(&mut self.editor, &other.editor);
         merge_partial(&mut self.file_explorer, &other.file_explorer);
         merge_partial(&mut self.file_browser, &other.file_browser);
@@ -135,8 +139,11 @@ fn merge_partial<T: Merge + Clone>(target: &mut Option<T>, other: &Option<T>) {
 pub struct PartialEditorConfig {
     pub tab_size: Option<usize>,
     pub auto_indent: Option<bool>,
+    pub detect_indentation: Option<bool>,
+    pub modelines: Option<bool>,
     pub line_numbers: Option<bool>,
     pub relative_line_numbers: Option<bool>,
+    pub line_number_mode: Option<LineNumberMode>,
     pub scroll_offset: Option<usize>,
     pub syntax_highlighting: Option<bool>,
     pub line_wrap: Option<bool>,
@@ -146,20 +153,63 @@ pub struct PartialEditorConfig {
     pub estimated_line_length: Option<usize>,
     pub enable_inlay_hints: Option<bool>,
     pub enable_semantic_tokens_full: Option<bool>,
+    pub lsp_idle_timeout_secs: Option<u64>,
+    pub spell_check: Option<bool>,
+    pub spell_language: Option<String>,
     pub recovery_enabled: Option<bool>,
     pub auto_save_interval_secs: Option<u32>,
+    pub recovery_compression: Option<bool>,
+    pub recovery_encryption: Option<bool>,
+    pub recovery_passphrase_command: Option<String>,
+    pub file_locks: Option<bool>,
     pub highlight_context_bytes: Option<usize>,
     pub mouse_hover_enabled: Option<bool>,
     pub mouse_hover_delay_ms: Option<u64>,
     pub double_click_time_ms: Option<u64>,
+    pub mouse_scroll_lines: Option<usize>,
+    pub mouse_scroll_columns: Option<usize>,
+    pub smooth_scrolling: Option<bool>,
+    pub reduced_motion: Option<bool>,
+    pub high_contrast: Option<bool>,
+    pub large_hit_targets: Option<bool>,
     pub auto_revert_poll_interval_ms: Option<u64>,
+    pub auto_revert_on_focus_gain: Option<bool>,
+    pub auto_revert_merge: Option<bool>,
+    pub file_auto_save: Option<bool>,
+    pub file_auto_save_delay_ms: Option<u64>,
+    pub file_auto_save_on_focus_loss: Option<bool>,
+    pub file_auto_save_exclude: Option<Vec<String>>,
+    pub file_auto_save_exclude_languages: Option<Vec<String>>,
+    pub backup_enabled: Option<bool>,
+    pub backup_numbered: Option<bool>,
+    pub backup_directory: Option<String>,
+    pub backup_keep_versions: Option<usize>,
+    pub save_strategy: Option<SaveStrategy>,
+    pub persistent_undo: Option<bool>,
+    pub persistent_undo_max_size_kb: Option<usize>,
+    pub persistent_undo_max_age_days: Option<u64>,
     pub file_tree_poll_interval_ms: Option<u64>,
+    pub frame_budget_ms: Option<u64>,
+    pub plugin_sandbox: Option<bool>,
+    pub plugin_memory_limit_mb: Option<usize>,
+    pub plugin_time_slice_ms: Option<u64>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub file_templates: Option<Vec<FileTemplate>>,
+    pub file_template_author: Option<String>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
+    pub rulers: Option<Vec<usize>>,
+    pub show_trailing_whitespace: Option<bool>,
+    pub show_mixed_indentation: Option<bool>,
+    pub max_line_length: Option<usize>,
+    pub line_length_diagnostics: Option<bool>,
+    pub render_whitespace: Option<RenderWhitespace>,
+    pub highlight_current_line: Option<bool>,
+    pub highlight_current_column: Option<bool>,
+    pub bidi_text: Option<bool>,
     pub keyboard_disambiguate_escape_codes: Option<bool>,
     pub keyboard_report_event_types: Option<bool>,
     pub keyboard_report_alternate_keys: Option<bool>,
@@ -168,18 +218,27 @@ pub struct PartialEditorConfig {
     pub quick_suggestions_delay_ms: Option<u64>,
     pub suggest_on_trigger_characters: Option<bool>,
     pub accept_suggestion_on_enter: Option<AcceptSuggestionOnEnter>,
+    pub word_completion: Option<bool>,
     pub show_menu_bar: Option<bool>,
     pub show_tab_bar: Option<bool>,
+    pub show_breadcrumbs: Option<bool>,
     pub use_terminal_bg: Option<bool>,
+    pub synchronized_output: Option<bool>,
+    pub color_swatches: Option<bool>,
+    pub underline_links: Option<bool>,
 }
 
 impl Merge for PartialEditorConfig {
     fn merge_from(&mut self, other: &Self) {
         self.tab_size.merge_from(&other.tab_size);
         self.auto_indent.merge_from(&other.auto_indent);
+        self.detect_indentation
+            .merge_from(&other.detect_indentation);
+        self.modelines.merge_from(&other.modelines);
         self.line_numbers.merge_from(&other.line_numbers);
         self.relative_line_numbers
             .merge_from(&other.relative_line_numbers);
+        self.line_number_mode.merge_from(&other.line_number_mode);
         self.scroll_offset.merge_from(&other.scroll_offset);
         self.syntax_highlighting
             .merge_from(&other.syntax_highlighting);
@@ -195,9 +254,20 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.enable_inlay_hints);
         self.enable_semantic_tokens_full
             .merge_from(&other.enable_semantic_tokens_full);
+        self.lsp_idle_timeout_secs
+            .merge_from(&other.lsp_idle_timeout_secs);
+        self.spell_check.merge_from(&other.spell_check);
+        self.spell_language.merge_from(&other.spell_language);
         self.recovery_enabled.merge_from(&other.recovery_enabled);
         self.auto_save_interval_secs
             .merge_from(&other.auto_save_interval_secs);
+        self.recovery_compression
+            .merge_from(&other.recovery_compression);
+        self.recovery_encryption
+            .merge_from(&other.recovery_encryption);
+        self.recovery_passphrase_command
+            .merge_from(&other.recovery_passphrase_command);
+        self.file_locks.merge_from(&other.file_locks);
         self.highlight_context_bytes
             .merge_from(&other.highlight_context_bytes);
         self.mouse_hover_enabled
@@ -206,20 +276,74 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.mouse_hover_delay_ms);
         self.double_click_time_ms
             .merge_from(&other.double_click_time_ms);
+        self.mouse_scroll_lines
+            .merge_from(&other.mouse_scroll_lines);
+        self.mouse_scroll_columns
+            .merge_from(&other.mouse_scroll_columns);
+        self.smooth_scrolling.merge_from(&other.smooth_scrolling);
+        self.reduced_motion.merge_from(&other.reduced_motion);
+        self.high_contrast.merge_from(&other.high_contrast);
+        self.large_hit_targets.merge_from(&other.large_hit_targets);
         self.auto_revert_poll_interval_ms
             .merge_from(&other.auto_revert_poll_interval_ms);
+        self.auto_revert_on_focus_gain
+            .merge_from(&other.auto_revert_on_focus_gain);
+        self.auto_revert_merge.merge_from(&other.auto_revert_merge);
+        self.file_auto_save.merge_from(&other.file_auto_save);
+        self.file_auto_save_delay_ms
+            .merge_from(&other.file_auto_save_delay_ms);
+        self.file_auto_save_on_focus_loss
+            .merge_from(&other.file_auto_save_on_focus_loss);
+        self.file_auto_save_exclude
+            .merge_from(&other.file_auto_save_exclude);
+        self.file_auto_save_exclude_languages
+            .merge_from(&other.file_auto_save_exclude_languages);
+        self.backup_enabled.merge_from(&other.backup_enabled);
+        self.backup_numbered.merge_from(&other.backup_numbered);
+        self.backup_directory.merge_from(&other.backup_directory);
+        self.backup_keep_versions
+            .merge_from(&other.backup_keep_versions);
+        self.save_strategy.merge_from(&other.save_strategy);
+        self.persistent_undo.merge_from(&other.persistent_undo);
+        self.persistent_undo_max_size_kb
+            .merge_from(&other.persistent_undo_max_size_kb);
+        self.persistent_undo_max_age_days
+            .merge_from(&other.persistent_undo_max_age_days);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
+        self.frame_budget_ms.merge_from(&other.frame_budget_ms);
+        self.plugin_sandbox.merge_from(&other.plugin_sandbox);
+        self.plugin_memory_limit_mb
+            .merge_from(&other.plugin_memory_limit_mb);
+        self.plugin_time_slice_ms
+            .merge_from(&other.plugin_time_slice_ms);
         self.default_line_ending
             .merge_from(&other.default_line_ending);
         self.trim_trailing_whitespace_on_save
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        self.file_templates.merge_from(&other.file_templates);
+        self.file_template_author
+            .merge_from(&other.file_template_author);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
         self.cursor_style.merge_from(&other.cursor_style);
+        self.rulers.merge_from(&other.rulers);
+        self.show_trailing_whitespace
+            .merge_from(&other.show_trailing_whitespace);
+        self.show_mixed_indentation
+            .merge_from(&other.show_mixed_indentation);
+        self.max_line_length.merge_from(&other.max_line_length);
+        self.line_length_diagnostics
+            .merge_from(&other.line_length_diagnostics);
+        self.render_whitespace.merge_from(&other.render_whitespace);
+        self.highlight_current_line
+            .merge_from(&other.highlight_current_line);
+        self.highlight_current_column
+            .merge_from(&other.highlight_current_column);
+        self.bidi_text.merge_from(&other.bidi_text);
         self.keyboard_disambiguate_escape_codes
             .merge_from(&other.keyboard_disambiguate_escape_codes);
         self.keyboard_report_event_types
@@ -235,9 +359,15 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.suggest_on_trigger_characters);
         self.accept_suggestion_on_enter
             .merge_from(&other.accept_suggestion_on_enter);
+        self.word_completion.merge_from(&other.word_completion);
         self.show_menu_bar.merge_from(&other.show_menu_bar);
         self.show_tab_bar.merge_from(&other.show_tab_bar);
+        self.show_breadcrumbs.merge_from(&other.show_breadcrumbs);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
+        self.synchronized_output
+            .merge_from(&other.synchronized_output);
+        self.color_swatches.merge_from(&other.color_swatches);
+        self.underline_links.merge_from(&other.underline_links);
     }
 }
 
@@ -290,6 +420,31 @@ impl Merge for PartialTerminalConfig {
     }
 }
 
+/// Partial appearance (light/dark theme switching) configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialAppearanceConfig {
+    pub mode: Option<AppearanceMode>,
+    pub light_theme: Option<ThemeName>,
+    pub dark_theme: Option<ThemeName>,
+    pub light_at: Option<String>,
+    pub dark_at: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl Merge for PartialAppearanceConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.mode.merge_from(&other.mode);
+        self.light_theme.merge_from(&other.light_theme);
+        self.dark_theme.merge_from(&other.dark_theme);
+        self.light_at.merge_from(&other.light_at);
+        self.dark_at.merge_from(&other.dark_at);
+        self.latitude.merge_from(&other.latitude);
+        self.longitude.merge_from(&other.longitude);
+    }
+}
+
 /// Partial warnings configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -325,12 +480,15 @@ pub struct PartialPluginConfig {
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<std::path::PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<PluginPermissions>,
 }
 
 impl Merge for PartialPluginConfig {
     fn merge_from(&mut self, other: &Self) {
         self.enabled.merge_from(&other.enabled);
         self.path.merge_from(&other.path);
+        self.permissions.merge_from(&other.permissions);
     }
 }
 
@@ -342,6 +500,8 @@ pub struct PartialLanguageConfig {
     pub filenames: Option<Vec<String>>,
     pub grammar: Option<String>,
     pub comment_prefix: Option<String>,
+    pub block_comment_start: Option<String>,
+    pub block_comment_end: Option<String>,
     pub auto_indent: Option<bool>,
     pub highlighter: Option<HighlighterPreference>,
     pub textmate_grammar: Option<std::path::PathBuf>,
@@ -351,6 +511,13 @@ pub struct PartialLanguageConfig {
     pub formatter: Option<FormatterConfig>,
     pub format_on_save: Option<bool>,
     pub on_save: Option<Vec<OnSaveAction>>,
+    pub conceal: Option<Vec<ConcealRule>>,
+    pub repl: Option<String>,
+    pub rulers: Option<Vec<usize>>,
+    pub max_line_length: Option<usize>,
+    pub increase_indent_pattern: Option<String>,
+    pub decrease_indent_pattern: Option<String>,
+    pub continuation_pattern: Option<String>,
 }
 
 impl Merge for PartialLanguageConfig {
@@ -359,6 +526,9 @@ impl Merge for PartialLanguageConfig {
         self.filenames.merge_from(&other.filenames);
         self.grammar.merge_from(&other.grammar);
         self.comment_prefix.merge_from(&other.comment_prefix);
+        self.block_comment_start
+            .merge_from(&other.block_comment_start);
+        self.block_comment_end.merge_from(&other.block_comment_end);
         self.auto_indent.merge_from(&other.auto_indent);
         self.highlighter.merge_from(&other.highlighter);
         self.textmate_grammar.merge_from(&other.textmate_grammar);
@@ -369,6 +539,16 @@ impl Merge for PartialLanguageConfig {
         self.formatter.merge_from(&other.formatter);
         self.format_on_save.merge_from(&other.format_on_save);
         self.on_save.merge_from(&other.on_save);
+        self.conceal.merge_from(&other.conceal);
+        self.repl.merge_from(&other.repl);
+        self.rulers.merge_from(&other.rulers);
+        self.max_line_length.merge_from(&other.max_line_length);
+        self.increase_indent_pattern
+            .merge_from(&other.increase_indent_pattern);
+        self.decrease_indent_pattern
+            .merge_from(&other.decrease_indent_pattern);
+        self.continuation_pattern
+            .merge_from(&other.continuation_pattern);
     }
 }
 
@@ -398,8 +578,11 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
         Self {
             tab_size: Some(cfg.tab_size),
             auto_indent: Some(cfg.auto_indent),
+            detect_indentation: Some(cfg.detect_indentation),
+            modelines: Some(cfg.modelines),
             line_numbers: Some(cfg.line_numbers),
             relative_line_numbers: Some(cfg.relative_line_numbers),
+            line_number_mode: Some(cfg.line_number_mode),
             scroll_offset: Some(cfg.scroll_offset),
             syntax_highlighting: Some(cfg.syntax_highlighting),
             line_wrap: Some(cfg.line_wrap),
@@ -409,20 +592,63 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             estimated_line_length: Some(cfg.estimated_line_length),
             enable_inlay_hints: Some(cfg.enable_inlay_hints),
             enable_semantic_tokens_full: Some(cfg.enable_semantic_tokens_full),
+            lsp_idle_timeout_secs: Some(cfg.lsp_idle_timeout_secs),
+            spell_check: Some(cfg.spell_check),
+            spell_language: Some(cfg.spell_language.clone()),
             recovery_enabled: Some(cfg.recovery_enabled),
             auto_save_interval_secs: Some(cfg.auto_save_interval_secs),
+            recovery_compression: Some(cfg.recovery_compression),
+            recovery_encryption: Some(cfg.recovery_encryption),
+            recovery_passphrase_command: Some(cfg.recovery_passphrase_command.clone()),
+            file_locks: Some(cfg.file_locks),
             highlight_context_bytes: Some(cfg.highlight_context_bytes),
             mouse_hover_enabled: Some(cfg.mouse_hover_enabled),
             mouse_hover_delay_ms: Some(cfg.mouse_hover_delay_ms),
             double_click_time_ms: Some(cfg.double_click_time_ms),
+            mouse_scroll_lines: Some(cfg.mouse_scroll_lines),
+            mouse_scroll_columns: Some(cfg.mouse_scroll_columns),
+            smooth_scrolling: Some(cfg.smooth_scrolling),
+            reduced_motion: Some(cfg.reduced_motion),
+            high_contrast: Some(cfg.high_contrast),
+            large_hit_targets: Some(cfg.large_hit_targets),
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
+            auto_revert_on_focus_gain: Some(cfg.auto_revert_on_focus_gain),
+            auto_revert_merge: Some(cfg.auto_revert_merge),
+            file_auto_save: Some(cfg.file_auto_save),
+            file_auto_save_delay_ms: Some(cfg.file_auto_save_delay_ms),
+            file_auto_save_on_focus_loss: Some(cfg.file_auto_save_on_focus_loss),
+            file_auto_save_exclude: Some(cfg.file_auto_save_exclude.clone()),
+            file_auto_save_exclude_languages: Some(cfg.file_auto_save_exclude_languages.clone()),
+            backup_enabled: Some(cfg.backup_enabled),
+            backup_numbered: Some(cfg.backup_numbered),
+            backup_directory: Some(cfg.backup_directory.clone()),
+            backup_keep_versions: Some(cfg.backup_keep_versions),
+            save_strategy: Some(cfg.save_strategy),
+            persistent_undo: Some(cfg.persistent_undo),
+            persistent_undo_max_size_kb: Some(cfg.persistent_undo_max_size_kb),
+            persistent_undo_max_age_days: Some(cfg.persistent_undo_max_age_days),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
+            frame_budget_ms: Some(cfg.frame_budget_ms),
+            plugin_sandbox: Some(cfg.plugin_sandbox),
+            plugin_memory_limit_mb: Some(cfg.plugin_memory_limit_mb),
+            plugin_time_slice_ms: Some(cfg.plugin_time_slice_ms),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            file_templates: Some(cfg.file_templates.clone()),
+            file_template_author: Some(cfg.file_template_author.clone()),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
+            rulers: Some(cfg.rulers.clone()),
+            show_trailing_whitespace: Some(cfg.show_trailing_whitespace),
+            show_mixed_indentation: Some(cfg.show_mixed_indentation),
+            max_line_length: cfg.max_line_length,
+            line_length_diagnostics: Some(cfg.line_length_diagnostics),
+            render_whitespace: Some(cfg.render_whitespace),
+            highlight_current_line: Some(cfg.highlight_current_line),
+            highlight_current_column: Some(cfg.highlight_current_column),
+            bidi_text: Some(cfg.bidi_text),
             keyboard_disambiguate_escape_codes: Some(cfg.keyboard_disambiguate_escape_codes),
             keyboard_report_event_types: Some(cfg.keyboard_report_event_types),
             keyboard_report_alternate_keys: Some(cfg.keyboard_report_alternate_keys),
@@ -433,9 +659,14 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             quick_suggestions_delay_ms: Some(cfg.quick_suggestions_delay_ms),
             suggest_on_trigger_characters: Some(cfg.suggest_on_trigger_characters),
             accept_suggestion_on_enter: Some(cfg.accept_suggestion_on_enter),
+            word_completion: Some(cfg.word_completion),
             show_menu_bar: Some(cfg.show_menu_bar),
             show_tab_bar: Some(cfg.show_tab_bar),
+            show_breadcrumbs: Some(cfg.show_breadcrumbs),
             use_terminal_bg: Some(cfg.use_terminal_bg),
+            synchronized_output: Some(cfg.synchronized_output),
+            color_swatches: Some(cfg.color_swatches),
+            underline_links: Some(cfg.underline_links),
         }
     }
 }
@@ -446,10 +677,15 @@ impl PartialEditorConfig {
         crate::config::EditorConfig {
             tab_size: self.tab_size.unwrap_or(defaults.tab_size),
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
+            detect_indentation: self
+                .detect_indentation
+                .unwrap_or(defaults.detect_indentation),
+            modelines: self.modelines.unwrap_or(defaults.modelines),
             line_numbers: self.line_numbers.unwrap_or(defaults.line_numbers),
             relative_line_numbers: self
                 .relative_line_numbers
                 .unwrap_or(defaults.relative_line_numbers),
+            line_number_mode: self.line_number_mode.unwrap_or(defaults.line_number_mode),
             scroll_offset: self.scroll_offset.unwrap_or(defaults.scroll_offset),
             syntax_highlighting: self
                 .syntax_highlighting
@@ -471,10 +707,27 @@ impl PartialEditorConfig {
             enable_semantic_tokens_full: self
                 .enable_semantic_tokens_full
                 .unwrap_or(defaults.enable_semantic_tokens_full),
+            lsp_idle_timeout_secs: self
+                .lsp_idle_timeout_secs
+                .unwrap_or(defaults.lsp_idle_timeout_secs),
+            spell_check: self.spell_check.unwrap_or(defaults.spell_check),
+            spell_language: self
+                .spell_language
+                .unwrap_or_else(|| defaults.spell_language.clone()),
             recovery_enabled: self.recovery_enabled.unwrap_or(defaults.recovery_enabled),
             auto_save_interval_secs: self
                 .auto_save_interval_secs
                 .unwrap_or(defaults.auto_save_interval_secs),
+            recovery_compression: self
+                .recovery_compression
+                .unwrap_or(defaults.recovery_compression),
+            recovery_encryption: self
+                .recovery_encryption
+                .unwrap_or(defaults.recovery_encryption),
+            recovery_passphrase_command: self
+                .recovery_passphrase_command
+                .unwrap_or(defaults.recovery_passphrase_command.clone()),
+            file_locks: self.file_locks.unwrap_or(defaults.file_locks),
             highlight_context_bytes: self
                 .highlight_context_bytes
                 .unwrap_or(defaults.highlight_context_bytes),
@@ -487,12 +740,63 @@ impl PartialEditorConfig {
             double_click_time_ms: self
                 .double_click_time_ms
                 .unwrap_or(defaults.double_click_time_ms),
+            mouse_scroll_lines: self
+                .mouse_scroll_lines
+                .unwrap_or(defaults.mouse_scroll_lines),
+            mouse_scroll_columns: self
+                .mouse_scroll_columns
+                .unwrap_or(defaults.mouse_scroll_columns),
+            smooth_scrolling: self.smooth_scrolling.unwrap_or(defaults.smooth_scrolling),
+            reduced_motion: self.reduced_motion.unwrap_or(defaults.reduced_motion),
+            high_contrast: self.high_contrast.unwrap_or(defaults.high_contrast),
+            large_hit_targets: self.large_hit_targets.unwrap_or(defaults.large_hit_targets),
             auto_revert_poll_interval_ms: self
                 .auto_revert_poll_interval_ms
                 .unwrap_or(defaults.auto_revert_poll_interval_ms),
+            auto_revert_on_focus_gain: self
+                .auto_revert_on_focus_gain
+                .unwrap_or(defaults.auto_revert_on_focus_gain),
+            auto_revert_merge: self.auto_revert_merge.unwrap_or(defaults.auto_revert_merge),
+            file_auto_save: self.file_auto_save.unwrap_or(defaults.file_auto_save),
+            file_auto_save_delay_ms: self
+                .file_auto_save_delay_ms
+                .unwrap_or(defaults.file_auto_save_delay_ms),
+            file_auto_save_on_focus_loss: self
+                .file_auto_save_on_focus_loss
+                .unwrap_or(defaults.file_auto_save_on_focus_loss),
+            file_auto_save_exclude: self
+                .file_auto_save_exclude
+                .unwrap_or_else(|| defaults.file_auto_save_exclude.clone()),
+            file_auto_save_exclude_languages: self
+                .file_auto_save_exclude_languages
+                .unwrap_or_else(|| defaults.file_auto_save_exclude_languages.clone()),
+            backup_enabled: self.backup_enabled.unwrap_or(defaults.backup_enabled),
+            backup_numbered: self.backup_numbered.unwrap_or(defaults.backup_numbered),
+            backup_directory: self
+                .backup_directory
+                .unwrap_or(defaults.backup_directory.clone()),
+            backup_keep_versions: self
+                .backup_keep_versions
+                .unwrap_or(defaults.backup_keep_versions),
+            save_strategy: self.save_strategy.unwrap_or(defaults.save_strategy),
+            persistent_undo: self.persistent_undo.unwrap_or(defaults.persistent_undo),
+            persistent_undo_max_size_kb: self
+                .persistent_undo_max_size_kb
+                .unwrap_or(defaults.persistent_undo_max_size_kb),
+            persistent_undo_max_age_days: self
+                .persistent_undo_max_age_days
+                .unwrap_or(defaults.persistent_undo_max_age_days),
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
+            frame_budget_ms: self.frame_budget_ms.unwrap_or(defaults.frame_budget_ms),
+            plugin_sandbox: self.plugin_sandbox.unwrap_or(defaults.plugin_sandbox),
+            plugin_memory_limit_mb: self
+                .plugin_memory_limit_mb
+                .unwrap_or(defaults.plugin_memory_limit_mb),
+            plugin_time_slice_ms: self
+                .plugin_time_slice_ms
+                .unwrap_or(defaults.plugin_time_slice_ms),
             default_line_ending: self
                 .default_line_ending
                 .unwrap_or(defaults.default_line_ending.clone()),
@@ -502,11 +806,36 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            file_templates: self
+                .file_templates
+                .unwrap_or_else(|| defaults.file_templates.clone()),
+            file_template_author: self
+                .file_template_author
+                .unwrap_or_else(|| defaults.file_template_author.clone()),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
             rainbow_brackets: self.rainbow_brackets.unwrap_or(defaults.rainbow_brackets),
             cursor_style: self.cursor_style.unwrap_or(defaults.cursor_style),
+            rulers: self.rulers.unwrap_or_else(|| defaults.rulers.clone()),
+            show_trailing_whitespace: self
+                .show_trailing_whitespace
+                .unwrap_or(defaults.show_trailing_whitespace),
+            show_mixed_indentation: self
+                .show_mixed_indentation
+                .unwrap_or(defaults.show_mixed_indentation),
+            max_line_length: self.max_line_length.or(defaults.max_line_length),
+            line_length_diagnostics: self
+                .line_length_diagnostics
+                .unwrap_or(defaults.line_length_diagnostics),
+            render_whitespace: self.render_whitespace.unwrap_or(defaults.render_whitespace),
+            highlight_current_line: self
+                .highlight_current_line
+                .unwrap_or(defaults.highlight_current_line),
+            highlight_current_column: self
+                .highlight_current_column
+                .unwrap_or(defaults.highlight_current_column),
+            bidi_text: self.bidi_text.unwrap_or(defaults.bidi_text),
             keyboard_disambiguate_escape_codes: self
                 .keyboard_disambiguate_escape_codes
                 .unwrap_or(defaults.keyboard_disambiguate_escape_codes),
@@ -529,9 +858,16 @@ impl PartialEditorConfig {
             accept_suggestion_on_enter: self
                 .accept_suggestion_on_enter
                 .unwrap_or(defaults.accept_suggestion_on_enter),
+            word_completion: self.word_completion.unwrap_or(defaults.word_completion),
             show_menu_bar: self.show_menu_bar.unwrap_or(defaults.show_menu_bar),
             show_tab_bar: self.show_tab_bar.unwrap_or(defaults.show_tab_bar),
+            show_breadcrumbs: self.show_breadcrumbs.unwrap_or(defaults.show_breadcrumbs),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
+            synchronized_output: self
+                .synchronized_output
+                .unwrap_or(defaults.synchronized_output),
+            color_swatches: self.color_swatches.unwrap_or(defaults.color_swatches),
+            underline_links: self.underline_links.unwrap_or(defaults.underline_links),
         }
     }
 }
@@ -596,6 +932,38 @@ impl PartialTerminalConfig {
     }
 }
 
+impl From<&AppearanceConfig> for PartialAppearanceConfig {
+    fn from(cfg: &AppearanceConfig) -> Self {
+        Self {
+            mode: Some(cfg.mode),
+            light_theme: Some(cfg.light_theme.clone()),
+            dark_theme: Some(cfg.dark_theme.clone()),
+            light_at: Some(cfg.light_at.clone()),
+            dark_at: Some(cfg.dark_at.clone()),
+            latitude: cfg.latitude,
+            longitude: cfg.longitude,
+        }
+    }
+}
+
+impl PartialAppearanceConfig {
+    pub fn resolve(self, defaults: &AppearanceConfig) -> AppearanceConfig {
+        AppearanceConfig {
+            mode: self.mode.unwrap_or(defaults.mode),
+            light_theme: self
+                .light_theme
+                .unwrap_or_else(|| defaults.light_theme.clone()),
+            dark_theme: self
+                .dark_theme
+                .unwrap_or_else(|| defaults.dark_theme.clone()),
+            light_at: self.light_at.unwrap_or_else(|| defaults.light_at.clone()),
+            dark_at: self.dark_at.unwrap_or_else(|| defaults.dark_at.clone()),
+            latitude: self.latitude.or(defaults.latitude),
+            longitude: self.longitude.or(defaults.longitude),
+        }
+    }
+}
+
 impl From<&WarningsConfig> for PartialWarningsConfig {
     fn from(cfg: &WarningsConfig) -> Self {
         Self {
@@ -638,6 +1006,7 @@ impl From<&PluginConfig> for PartialPluginConfig {
         Self {
             enabled: Some(cfg.enabled),
             path: cfg.path.clone(),
+            permissions: Some(cfg.permissions.clone()),
         }
     }
 }
@@ -647,6 +1016,9 @@ impl PartialPluginConfig {
         PluginConfig {
             enabled: self.enabled.unwrap_or(defaults.enabled),
             path: self.path.or_else(|| defaults.path.clone()),
+            permissions: self
+                .permissions
+                .unwrap_or_else(|| defaults.permissions.clone()),
         }
     }
 }
@@ -658,6 +1030,8 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             filenames: Some(cfg.filenames.clone()),
             grammar: Some(cfg.grammar.clone()),
             comment_prefix: cfg.comment_prefix.clone(),
+            block_comment_start: cfg.block_comment_start.clone(),
+            block_comment_end: cfg.block_comment_end.clone(),
             auto_indent: Some(cfg.auto_indent),
             highlighter: Some(cfg.highlighter),
             textmate_grammar: cfg.textmate_grammar.clone(),
@@ -667,6 +1041,13 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             formatter: cfg.formatter.clone(),
             format_on_save: Some(cfg.format_on_save),
             on_save: Some(cfg.on_save.clone()),
+            conceal: Some(cfg.conceal.clone()),
+            repl: cfg.repl.clone(),
+            rulers: cfg.rulers.clone(),
+            max_line_length: cfg.max_line_length,
+            increase_indent_pattern: cfg.increase_indent_pattern.clone(),
+            decrease_indent_pattern: cfg.decrease_indent_pattern.clone(),
+            continuation_pattern: cfg.continuation_pattern.clone(),
         }
     }
 }
@@ -682,6 +1063,12 @@ impl PartialLanguageConfig {
             comment_prefix: self
                 .comment_prefix
                 .or_else(|| defaults.comment_prefix.clone()),
+            block_comment_start: self
+                .block_comment_start
+                .or_else(|| defaults.block_comment_start.clone()),
+            block_comment_end: self
+                .block_comment_end
+                .or_else(|| defaults.block_comment_end.clone()),
             auto_indent: self.auto_indent.unwrap_or(defaults.auto_indent),
             highlighter: self.highlighter.unwrap_or(defaults.highlighter),
             textmate_grammar: self
@@ -695,6 +1082,19 @@ impl PartialLanguageConfig {
             formatter: self.formatter.or_else(|| defaults.formatter.clone()),
             format_on_save: self.format_on_save.unwrap_or(defaults.format_on_save),
             on_save: self.on_save.unwrap_or_else(|| defaults.on_save.clone()),
+            conceal: self.conceal.unwrap_or_else(|| defaults.conceal.clone()),
+            repl: self.repl.or_else(|| defaults.repl.clone()),
+            rulers: self.rulers.or_else(|| defaults.rulers.clone()),
+            max_line_length: self.max_line_length.or(defaults.max_line_length),
+            increase_indent_pattern: self
+                .increase_indent_pattern
+                .or_else(|| defaults.increase_indent_pattern.clone()),
+            decrease_indent_pattern: self
+                .decrease_indent_pattern
+                .or_else(|| defaults.decrease_indent_pattern.clone()),
+            continuation_pattern: self
+                .continuation_pattern
+                .or_else(|| defaults.continuation_pattern.clone()),
         }
     }
 }
@@ -704,6 +1104,7 @@ impl From<&crate::config::Config> for PartialConfig {
         Self {
             version: Some(cfg.version),
             theme: Some(cfg.theme.clone()),
+            appearance: Some(PartialAppearanceConfig::from(&cfg.appearance)),
             locale: cfg.locale.0.clone(),
             check_for_updates: Some(cfg.check_for_updates),
             editor: Some(PartialEditorConfig::from(&cfg.editor)),
@@ -728,13 +1129,18 @@ impl From<&crate::config::Config> for PartialConfig {
                 let non_default_plugins: HashMap<String, PartialPluginConfig> = cfg
                     .plugins
                     .iter()
-                    .filter(|(_, v)| v.enabled != default_plugin.enabled)
+                    .filter(|(_, v)| {
+                        v.enabled != default_plugin.enabled
+                            || v.permissions != default_plugin.permissions
+                    })
                     .map(|(k, v)| {
                         (
                             k.clone(),
                             PartialPluginConfig {
                                 enabled: Some(v.enabled),
                                 path: None, // Don't save path - it's auto-discovered
+                                permissions: (!v.permissions.is_default())
+                                    .then(|| v.permissions.clone()),
                             },
                         )
                     })
@@ -751,6 +1157,33 @@ impl From<&crate::config::Config> for PartialConfig {
 }
 
 impl PartialConfig {
+    /// Remove the settings that run programs: language servers, formatters,
+    /// on-save actions, REPL commands, the recovery passphrase command,
+    /// plugin paths and plugin permissions, along with the plugin sandbox
+    /// and its resource limits.
+    ///
+    /// Used for the project layers of an untrusted workspace. Returns whether
+    /// anything was removed.
+    pub fn remove_commands(&mut self) -> bool {
+        let mut removed = self.lsp.take().is_some_and(|lsp| !lsp.is_empty());
+        if let Some(editor) = &mut self.editor {
+            removed |= editor.recovery_passphrase_command.take().is_some();
+            removed |= editor.plugin_sandbox.take().is_some();
+            removed |= editor.plugin_memory_limit_mb.take().is_some();
+            removed |= editor.plugin_time_slice_ms.take().is_some();
+        }
+        for language in self.languages.iter_mut().flat_map(|l| l.values_mut()) {
+            removed |= language.formatter.take().is_some();
+            removed |= language.on_save.take().is_some_and(|a| !a.is_empty());
+            removed |= language.repl.take().is_some();
+        }
+        for plugin in self.plugins.iter_mut().flat_map(|p| p.values_mut()) {
+            removed |= plugin.path.take().is_some();
+            removed |= plugin.permissions.take().is_some();
+        }
+        removed
+    }
+
     /// Resolve this partial config to a concrete Config using system defaults.
     pub fn resolve(self) -> crate::config::Config {
         let defaults = crate::config::Config::default();
@@ -813,6 +1246,10 @@ impl PartialConfig {
         crate::config::Config {
             version: self.version.unwrap_or(defaults.version),
             theme: self.theme.unwrap_or_else(|| defaults.theme.clone()),
+            appearance: self
+                .appearance
+                .map(|e| e.resolve(&defaults.appearance))
+                .unwrap_or_else(|| defaults.appearance.clone()),
             locale: crate::config::LocaleName::from(
                 self.locale.or_else(|| defaults.locale.0.clone()),
             ),
@@ -863,6 +1300,8 @@ impl Default for LanguageConfig {
             filenames: Vec::new(),
             grammar: String::new(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: true,
             highlighter: HighlighterPreference::default(),
             textmate_grammar: None,
@@ -872,6 +1311,13 @@ impl Default for LanguageConfig {
             formatter: None,
             format_on_save: false,
             on_save: Vec::new(),
+            conceal: Vec::new(),
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         }
     }
 }
@@ -1178,6 +1624,7 @@ mod tests {
             PluginConfig {
                 enabled: true, // Default value
                 path: Some(std::path::PathBuf::from("/path/to/plugin.ts")),
+                permissions: Default::default(),
             },
         );
 
@@ -1199,6 +1646,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/path/to/enabled.ts")),
+                permissions: Default::default(),
             },
         );
         config.plugins.insert(
@@ -1206,6 +1654,7 @@ mod tests {
             PluginConfig {
                 enabled: false, // Not default!
                 path: Some(std::path::PathBuf::from("/path/to/disabled.ts")),
+                permissions: Default::default(),
             },
         );
 
@@ -1238,6 +1687,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/some/path/plugin.ts")),
+                permissions: Default::default(),
             },
         );
 
@@ -1260,6 +1710,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false),
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1288,6 +1739,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false), // User disabled
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1299,6 +1751,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(true), // Lower layer has it enabled
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1325,6 +1778,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/a.ts")),
+                permissions: Default::default(),
             },
         );
         config.plugins.insert(
@@ -1332,6 +1786,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/b.ts")),
+                permissions: Default::default(),
             },
         );
         config.plugins.insert(
@@ -1339,6 +1794,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/c.ts")),
+                permissions: Default::default(),
             },
         );
 
@@ -1371,4 +1827,65 @@ mod tests {
         assert!(plugins.contains_key("plugin_b"));
         assert_eq!(plugins.get("plugin_b").unwrap().enabled, Some(false));
     }
+
+    #[test]
+    fn remove_commands_keeps_other_settings() {
+        let mut partial: PartialConfig = serde_json::from_value(serde_json::json!({
+            "editor": {
+                "tab_size": 2,
+                "recovery_passphrase_command": "pass show fresh",
+                "plugin_sandbox": false,
+                "plugin_memory_limit_mb": 0,
+                "plugin_time_slice_ms": 0
+            },
+            "languages": {"python": {"tab_size": 4, "repl": "python3", "formatter": {"command": "black"}}},
+            "lsp": {"python": {"command": "./evil-server"}},
+            "plugins": {"todo": {
+                "enabled": false,
+                "path": "./plugins/todo.ts",
+                "permissions": {"subprocess": "allow"}
+            }}
+        }))
+        .unwrap();
+
+        assert!(partial.remove_commands());
+        let editor = partial.editor.as_ref().unwrap();
+        assert_eq!(editor.tab_size, Some(2));
+        assert_eq!(editor.recovery_passphrase_command, None);
+        assert_eq!(editor.plugin_sandbox, None);
+        assert_eq!(editor.plugin_memory_limit_mb, None);
+        assert_eq!(editor.plugin_time_slice_ms, None);
+        let python = &partial.languages.as_ref().unwrap()["python"];
+        assert_eq!(python.tab_size, Some(4));
+        assert!(python.repl.is_none() && python.formatter.is_none());
+        assert!(partial.lsp.is_none());
+        let todo = &partial.plugins.as_ref().unwrap()["todo"];
+        assert_eq!((todo.enabled, todo.path.as_ref()), (Some(false), None));
+        assert!(todo.permissions.is_none());
+
+        // Nothing left to remove
+        assert!(!partial.remove_commands());
+    }
+
+    #[test]
+    fn plugin_permissions_are_saved() {
+        let mut config = crate::config::Config::default();
+        let mut plugin = PluginConfig::new_with_path("/plugins/git.ts".into());
+        plugin.permissions.subprocess = crate::config::PermissionMode::Allow;
+        plugin.permissions.read.push("/home/user/src".into());
+        config.plugins.insert("git".to_string(), plugin);
+
+        let json = serde_json::to_value(PartialConfig::from(&config)).unwrap();
+        assert_eq!(
+            json["plugins"]["git"]["permissions"],
+            serde_json::json!({"subprocess": "allow", "read": ["/home/user/src"], "write": []})
+        );
+
+        let partial: PartialConfig = serde_json::from_value(json).unwrap();
+        let resolved = partial.resolve();
+        assert_eq!(
+            resolved.plugins["git"].permissions,
+            config.plugins["git"].permissions
+        );
+    }
 }
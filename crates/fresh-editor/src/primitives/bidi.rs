@@ -0,0 +1,216 @@
+//! Visual ordering of bidirectional text
+//!
+//! A simplified form of the Unicode Bidirectional Algorithm (UAX #9) for
+//! showing lines that mix right-to-left scripts (Arabic, Hebrew) with
+//! left-to-right text. It resolves an embedding level for each character and
+//! reorders them for display; explicit embedding controls (LRE, RLO, isolates,
+//! ...) are not supported. Only the display uses it: the buffer and cursor
+//! movement stay in logical order.
+
+/// Bidirectional class of a character, reduced to what the display needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BidiClass {
+    /// Strong left-to-right (Latin, CJK, ...)
+    Left,
+    /// Strong right-to-left (Hebrew, Arabic, ...)
+    Right,
+    /// Digits, which keep their left-to-right order inside right-to-left text
+    Number,
+    /// Whitespace, punctuation and symbols, which take the direction around them
+    Neutral,
+}
+
+fn bidi_class(c: char) -> BidiClass {
+    match c as u32 {
+        0x0660..=0x0669 | 0x06F0..=0x06F9 => BidiClass::Number,
+        0x0590..=0x08FF
+        | 0xFB1D..=0xFDFF
+        | 0xFE70..=0xFEFF
+        | 0x10800..=0x10FFF
+        | 0x1E800..=0x1EFFF => BidiClass::Right,
+        _ if c.is_ascii_digit() => BidiClass::Number,
+        _ if c.is_alphanumeric() => BidiClass::Left,
+        _ => BidiClass::Neutral,
+    }
+}
+
+/// Whether `text` contains any right-to-left characters, and so needs reordering
+pub fn has_rtl(text: &str) -> bool {
+    text.chars().any(|c| bidi_class(c) == BidiClass::Right)
+}
+
+/// Resolve the embedding level of each character of a line
+///
+/// The paragraph direction comes from the first strong character. Even levels
+/// are shown left to right and odd levels right to left.
+pub fn resolve_levels(chars: &[char]) -> Vec<u8> {
+    let classes: Vec<BidiClass> = chars.iter().map(|&c| bidi_class(c)).collect();
+    let base: u8 = match classes
+        .iter()
+        .find(|class| matches!(class, BidiClass::Left | BidiClass::Right))
+    {
+        Some(BidiClass::Right) => 1,
+        _ => 0,
+    };
+    let base_is_rtl = base == 1;
+
+    // Numbers take the direction of the last strong character before them
+    let mut is_rtl = Vec::with_capacity(classes.len());
+    let mut last_strong_rtl = base_is_rtl;
+    for class in &classes {
+        is_rtl.push(match class {
+            BidiClass::Left => {
+                last_strong_rtl = false;
+                Some(false)
+            }
+            BidiClass::Right => {
+                last_strong_rtl = true;
+                Some(true)
+            }
+            BidiClass::Number => Some(last_strong_rtl),
+            BidiClass::Neutral => None,
+        });
+    }
+
+    // Neutrals between two characters of the same direction take it,
+    // others take the paragraph direction
+    let mut i = 0;
+    while i < is_rtl.len() {
+        if is_rtl[i].is_some() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < is_rtl.len() && is_rtl[i].is_none() {
+            i += 1;
+        }
+        let before = start.checked_sub(1).and_then(|j| is_rtl[j]);
+        let after = is_rtl.get(i).copied().flatten();
+        let direction = match (before, after) {
+            (Some(b), Some(a)) if a == b => a,
+            _ => base_is_rtl,
+        };
+        is_rtl[start..i].fill(Some(direction));
+    }
+
+    let mut levels: Vec<u8> = classes
+        .iter()
+        .zip(&is_rtl)
+        .map(|(class, rtl)| match (class, rtl.unwrap_or(base_is_rtl)) {
+            // Numbers in right-to-left text still read left to right
+            (BidiClass::Number, true) => base + 2 - base % 2,
+            (_, true) => base | 1,
+            (_, false) if base_is_rtl => 2,
+            (_, false) => 0,
+        })
+        .collect();
+
+    // Trailing whitespace goes back to the paragraph level
+    for (level, c) in levels.iter_mut().zip(chars).rev() {
+        if !c.is_whitespace() {
+            break;
+        }
+        *level = base;
+    }
+    levels
+}
+
+/// Display order of a line with the given levels: the logical index of the
+/// character shown at each position, from left to right
+pub fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let max_level = levels.iter().copied().max().unwrap_or(0);
+    let min_odd_level = levels
+        .iter()
+        .copied()
+        .filter(|level| level % 2 == 1)
+        .min()
+        .unwrap_or(max_level + 1);
+
+    // Reverse every run at or above each level, from the highest level down
+    for level in (min_odd_level..=max_level).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]] < level {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < order.len() && levels[order[i]] >= level {
+                i += 1;
+            }
+            order[start..i].reverse();
+        }
+    }
+    order
+}
+
+/// The glyph shown for `c` in right-to-left text, where brackets face the
+/// other way
+pub fn mirrored(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        _ => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reorder `text` for display
+    fn display(text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let levels = resolve_levels(&chars);
+        visual_order(&levels)
+            .into_iter()
+            .map(|i| {
+                if levels[i] % 2 == 1 {
+                    mirrored(chars[i])
+                } else {
+                    chars[i]
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_left_to_right_is_unchanged() {
+        assert!(!has_rtl("plain text (1, 2)"));
+        assert_eq!(display("plain text (1, 2)"), "plain text (1, 2)");
+    }
+
+    #[test]
+    fn test_rtl_run_in_ltr_line() {
+        // Hebrew "shalom" inside an English sentence
+        assert!(has_rtl("say שלום now"));
+        assert_eq!(display("say שלום now"), "say םולש now");
+        // Spaces between two right-to-left words stay with them
+        assert_eq!(display("a אב גד b"), "a דג בא b");
+    }
+
+    #[test]
+    fn test_rtl_line() {
+        // The paragraph is right to left, so the English word moves to the left
+        assert_eq!(display("שלום abc"), "abc םולש");
+        // Brackets are mirrored
+        assert_eq!(display("(שלום)"), "(םולש)");
+        // Trailing whitespace stays at the logical end, on the left
+        assert_eq!(display("אב  "), "  בא");
+    }
+
+    #[test]
+    fn test_numbers_in_rtl_text() {
+        assert_eq!(display("אב 123 גד"), "דג 123 בא");
+        assert_eq!(display("abc 12 אב"), "abc 12 בא");
+    }
+}
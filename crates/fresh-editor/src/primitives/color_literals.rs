@@ -0,0 +1,433 @@
+//! Detection and editing of color literals in text
+//!
+//! Recognizes CSS-style hex colors (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`)
+//! and the `rgb()`, `rgba()`, `hsl()` and `hsla()` functions with comma or
+//! space separated arguments. Used to show a swatch next to each color and
+//! to adjust a color in place, keeping the notation it was written in.
+
+use std::ops::Range;
+
+/// Longest argument list of a color function, to stop scanning early on text
+/// that merely looks like a call
+const MAX_FUNCTION_ARGS_LEN: usize = 64;
+
+/// Lightness and saturation change of one adjustment step
+const ADJUST_STEP: f32 = 0.05;
+
+/// Hue change of one adjustment step, in degrees
+const HUE_STEP: f32 = 15.0;
+
+/// A color with channels in 0.0..=1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub alpha: f32,
+}
+
+/// How a color literal is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    Hex { uppercase: bool },
+    Rgb,
+    Hsl,
+}
+
+/// A color literal found in text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorLiteral {
+    /// Byte range of the literal within the text
+    pub range: Range<usize>,
+    pub color: Color,
+    pub notation: Notation,
+}
+
+/// A change made by the color adjuster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorAdjustment {
+    Lighten,
+    Darken,
+    Saturate,
+    Desaturate,
+    RotateHue,
+    ToHex,
+    ToRgb,
+    ToHsl,
+}
+
+impl Color {
+    /// The color as 8-bit RGB channels
+    pub fn to_rgb8(&self) -> (u8, u8, u8) {
+        let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        (channel(self.r), channel(self.g), channel(self.b))
+    }
+
+    /// Hue in degrees, saturation and lightness in 0.0..=1.0
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta <= f32::EPSILON {
+            return (0.0, 0.0, lightness);
+        }
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == self.r {
+            ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        } else {
+            (self.r - self.g) / delta + 4.0
+        };
+        (hue * 60.0, saturation.clamp(0.0, 1.0), lightness)
+    }
+
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = lightness - chroma / 2.0;
+        Self {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+            alpha,
+        }
+    }
+}
+
+impl ColorAdjustment {
+    /// Every adjustment, in the order the adjuster lists them
+    pub const ALL: [Self; 8] = [
+        Self::Lighten,
+        Self::Darken,
+        Self::Saturate,
+        Self::Desaturate,
+        Self::RotateHue,
+        Self::ToHex,
+        Self::ToRgb,
+        Self::ToHsl,
+    ];
+
+    /// Stable identifier, used as popup item data
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Lighten => "lighten",
+            Self::Darken => "darken",
+            Self::Saturate => "saturate",
+            Self::Desaturate => "desaturate",
+            Self::RotateHue => "rotate_hue",
+            Self::ToHex => "to_hex",
+            Self::ToRgb => "to_rgb",
+            Self::ToHsl => "to_hsl",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|adjustment| adjustment.id() == id)
+    }
+
+    /// The text replacing `literal` after this adjustment
+    pub fn apply(self, literal: &ColorLiteral) -> String {
+        let (hue, saturation, lightness) = literal.color.to_hsl();
+        let alpha = literal.color.alpha;
+        let (color, notation) = match self {
+            Self::Lighten => (
+                Color::from_hsl(hue, saturation, lightness + ADJUST_STEP, alpha),
+                literal.notation,
+            ),
+            Self::Darken => (
+                Color::from_hsl(hue, saturation, lightness - ADJUST_STEP, alpha),
+                literal.notation,
+            ),
+            Self::Saturate => (
+                Color::from_hsl(hue, saturation + ADJUST_STEP, lightness, alpha),
+                literal.notation,
+            ),
+            Self::Desaturate => (
+                Color::from_hsl(hue, saturation - ADJUST_STEP, lightness, alpha),
+                literal.notation,
+            ),
+            Self::RotateHue => (
+                Color::from_hsl(hue + HUE_STEP, saturation, lightness, alpha),
+                literal.notation,
+            ),
+            Self::ToHex => (literal.color, Notation::Hex { uppercase: false }),
+            Self::ToRgb => (literal.color, Notation::Rgb),
+            Self::ToHsl => (literal.color, Notation::Hsl),
+        };
+        format_color(&color, notation)
+    }
+}
+
+/// Find the color literals in `text`. `short_hex` enables the three and four
+/// digit hex forms, which in prose are more often issue numbers.
+pub fn find_color_literals(text: &str, short_hex: bool) -> Vec<ColorLiteral> {
+    let bytes = text.as_bytes();
+    let mut literals = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let previous = i.checked_sub(1).map(|p| bytes[p]);
+        let found = match bytes[i] {
+            // Not part of a word, an HTML entity (`&#123;`) or `##`
+            b'#' if !previous.is_some_and(|p| is_identifier_byte(p) || p == b'&' || p == b'#') => {
+                parse_hex(text, i, short_hex)
+            }
+            b'r' | b'R' | b'h' | b'H'
+                if !previous.is_some_and(|p| is_identifier_byte(p) || p == b'.') =>
+            {
+                parse_function(text, i)
+            }
+            _ => None,
+        };
+        match found {
+            Some(literal) => {
+                i = literal.range.end;
+                literals.push(literal);
+            }
+            None => i += 1,
+        }
+    }
+    literals
+}
+
+/// Write `color` in `notation`; the alpha channel is left out when opaque
+pub fn format_color(color: &Color, notation: Notation) -> String {
+    let (r, g, b) = color.to_rgb8();
+    let alpha = color.alpha.clamp(0.0, 1.0);
+    let alpha8 = (alpha * 255.0).round() as u8;
+    match notation {
+        Notation::Hex { uppercase } => {
+            let mut hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+            if alpha8 < 255 {
+                hex.push_str(&format!("{:02x}", alpha8));
+            }
+            if uppercase {
+                hex.make_ascii_uppercase();
+            }
+            hex
+        }
+        Notation::Rgb if alpha8 == 255 => format!("rgb({}, {}, {})", r, g, b),
+        Notation::Rgb => format!("rgba({}, {}, {}, {})", r, g, b, format_alpha(alpha)),
+        Notation::Hsl => {
+            let (hue, saturation, lightness) = color.to_hsl();
+            let hue = hue.round() as u32 % 360;
+            let saturation = (saturation * 100.0).round() as u32;
+            let lightness = (lightness * 100.0).round() as u32;
+            if alpha8 == 255 {
+                format!("hsl({}, {}%, {}%)", hue, saturation, lightness)
+            } else {
+                format!(
+                    "hsla({}, {}%, {}%, {})",
+                    hue,
+                    saturation,
+                    lightness,
+                    format_alpha(alpha)
+                )
+            }
+        }
+    }
+}
+
+/// Alpha with at most two decimals and no trailing zeros
+fn format_alpha(alpha: f32) -> String {
+    let text = format!("{:.2}", alpha);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+}
+
+fn parse_hex(text: &str, start: usize, short_hex: bool) -> Option<ColorLiteral> {
+    let digits = &text[start + 1..];
+    let len = digits.bytes().take_while(u8::is_ascii_hexdigit).count();
+    let end = start + 1 + len;
+    if text
+        .as_bytes()
+        .get(end)
+        .is_some_and(|&b| is_identifier_byte(b))
+    {
+        return None;
+    }
+    let digits = &digits[..len];
+    let channel = |i: usize, width: usize| {
+        let value = u8::from_str_radix(&digits[i * width..(i + 1) * width], 16).ok()?;
+        let value = if width == 1 { value * 17 } else { value };
+        Some(f32::from(value) / 255.0)
+    };
+    let width = match len {
+        3 | 4 if short_hex => 1,
+        6 | 8 => 2,
+        _ => return None,
+    };
+    let alpha = if len == 4 * width {
+        channel(3, width)?
+    } else {
+        1.0
+    };
+    let color = Color {
+        r: channel(0, width)?,
+        g: channel(1, width)?,
+        b: channel(2, width)?,
+        alpha,
+    };
+    let uppercase = digits.bytes().any(|b| b.is_ascii_uppercase());
+    Some(ColorLiteral {
+        range: start..end,
+        color,
+        notation: Notation::Hex { uppercase },
+    })
+}
+
+fn parse_function(text: &str, start: usize) -> Option<ColorLiteral> {
+    let rest = &text[start..];
+    let name_len = rest.bytes().take_while(u8::is_ascii_alphabetic).count();
+    let notation = match rest[..name_len].to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => Notation::Rgb,
+        "hsl" | "hsla" => Notation::Hsl,
+        _ => return None,
+    };
+    let args = rest[name_len..].strip_prefix('(')?;
+    let close = args.find(')')?;
+    let args = &args[..close];
+    if args.len() > MAX_FUNCTION_ARGS_LEN || args.contains(['(', '\n']) {
+        return None;
+    }
+    let parts: Vec<&str> = args
+        .split([',', '/', ' ', '\t'])
+        .filter(|part| !part.is_empty())
+        .collect();
+    if !(3..=4).contains(&parts.len()) {
+        return None;
+    }
+    let alpha = match parts.get(3) {
+        Some(alpha) => parse_fraction(alpha, 1.0)?,
+        None => 1.0,
+    };
+    let color = match notation {
+        Notation::Rgb => Color {
+            r: parse_fraction(parts[0], 255.0)?,
+            g: parse_fraction(parts[1], 255.0)?,
+            b: parse_fraction(parts[2], 255.0)?,
+            alpha,
+        },
+        _ => {
+            let hue = parse_number(parts[0].trim_end_matches("deg"))?;
+            let saturation = parse_fraction(parts[1], 100.0)?;
+            let lightness = parse_fraction(parts[2], 100.0)?;
+            Color::from_hsl(hue, saturation, lightness, alpha)
+        }
+    };
+    // name + '(' + arguments + ')'
+    let end = start + name_len + 1 + close + 1;
+    Some(ColorLiteral {
+        range: start..end,
+        color,
+        notation,
+    })
+}
+
+/// A number out of `max`, or a percentage, as a fraction in 0.0..=1.0
+fn parse_fraction(text: &str, max: f32) -> Option<f32> {
+    let value = match text.strip_suffix('%') {
+        Some(percent) => parse_number(percent)? / 100.0,
+        None => parse_number(text)? / max,
+    };
+    Some(value.clamp(0.0, 1.0))
+}
+
+fn parse_number(text: &str) -> Option<f32> {
+    text.parse::<f32>().ok().filter(|n| n.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<&str> {
+        find_color_literals(text, true)
+            .into_iter()
+            .map(|literal| &text[literal.range])
+            .collect()
+    }
+
+    #[test]
+    fn test_find_color_literals() {
+        assert_eq!(
+            found("color: #ff8800; background: rgba(0, 0, 0, 0.5);"),
+            vec!["#ff8800", "rgba(0, 0, 0, 0.5)"]
+        );
+        assert_eq!(
+            found("a { color: #FFF; border-color: hsl(120deg 50% 25% / 80%) }"),
+            vec!["#FFF", "hsl(120deg 50% 25% / 80%)"]
+        );
+        // Not colors: wrong length, part of a word, an entity, other functions
+        assert_eq!(found("#12345 a#fff &#123; #fffg"), Vec::<&str>::new());
+        assert_eq!(
+            found("mrgb(1, 2, 3) rgb(var(--x), 2, 3) rgb(1, 2)"),
+            Vec::<&str>::new()
+        );
+        // Short forms can be left out (issue numbers in prose)
+        assert_eq!(find_color_literals("see #123", false), vec![]);
+        assert_eq!(find_color_literals("#abcdef", false).len(), 1);
+    }
+
+    #[test]
+    fn test_parsed_colors() {
+        let literals =
+            find_color_literals("#f80 #11223380 rgb(100%, 0%, 0%) hsl(240, 100%, 50%)", true);
+        let rgb: Vec<_> = literals.iter().map(|l| l.color.to_rgb8()).collect();
+        assert_eq!(
+            rgb,
+            vec![(255, 136, 0), (17, 34, 51), (255, 0, 0), (0, 0, 255)]
+        );
+        assert!((literals[1].color.alpha - 128.0 / 255.0).abs() < 1e-6);
+        assert_eq!(literals[0].notation, Notation::Hex { uppercase: false });
+        assert_eq!(literals[3].notation, Notation::Hsl);
+    }
+
+    #[test]
+    fn test_adjustments_keep_notation() {
+        let adjust = |text: &str, adjustment: ColorAdjustment| {
+            let literal = find_color_literals(text, true).remove(0);
+            adjustment.apply(&literal)
+        };
+        assert_eq!(
+            adjust("hsl(120, 50%, 50%)", ColorAdjustment::Lighten),
+            "hsl(120, 50%, 55%)"
+        );
+        assert_eq!(
+            adjust("hsl(350, 50%, 50%)", ColorAdjustment::RotateHue),
+            "hsl(5, 50%, 50%)"
+        );
+        assert_eq!(adjust("#000000", ColorAdjustment::Darken), "#000000");
+        assert_eq!(adjust("#FF0000", ColorAdjustment::ToRgb), "rgb(255, 0, 0)");
+        assert_eq!(
+            adjust("rgba(255, 0, 0, 0.5)", ColorAdjustment::ToHex),
+            "#ff000080"
+        );
+        assert_eq!(
+            adjust("#ff000080", ColorAdjustment::ToHsl),
+            "hsla(0, 100%, 50%, 0.5)"
+        );
+        assert_eq!(adjust("#abc", ColorAdjustment::Desaturate), "#adbbc9");
+        assert_eq!(
+            ColorAdjustment::from_id("rotate_hue"),
+            Some(ColorAdjustment::RotateHue)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Reading ctags files and GNU Global output
+//!
+//! A ctags file has one tag per line: the name, the file, and an ex command
+//! finding the definition in it, either a line number or a search pattern
+//! like `/^int main(void)$/`, then optional extension fields such as the
+//! kind. `global -x` prints the name, line number, file and line text of
+//! each definition.
+
+use std::path::PathBuf;
+
+/// A definition found in a tags file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub file: PathBuf,
+    /// The line the definition is on, as given by the search pattern
+    /// (without the `^`/`$` anchors, which are kept in `anchored`)
+    pub pattern: Option<String>,
+    pub anchored: (bool, bool),
+    /// 1-based line number, from the ex command or the `line:` field
+    pub line: Option<usize>,
+    pub kind: Option<String>,
+}
+
+/// The tags of a ctags file, sorted by name
+pub fn parse_tags(contents: &str) -> Vec<Tag> {
+    let mut tags: Vec<Tag> = contents
+        .lines()
+        .filter(|line| !line.starts_with("!_"))
+        .filter_map(parse_tag_line)
+        .collect();
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+    tags
+}
+
+fn parse_tag_line(line: &str) -> Option<Tag> {
+    let mut fields = line.splitn(3, '\t');
+    let name = fields.next().filter(|name| !name.is_empty())?;
+    let file = fields.next().filter(|file| !file.is_empty())?;
+    let rest = fields.next()?;
+
+    // The ex command ends at `;"`, after which come the extension fields
+    let (address, extensions) = match rest.find(";\"\t") {
+        Some(end) => (&rest[..end], &rest[end + 3..]),
+        None => (rest.strip_suffix(";\"").unwrap_or(rest), ""),
+    };
+
+    let mut tag = Tag {
+        name: name.to_string(),
+        file: PathBuf::from(file),
+        pattern: None,
+        anchored: (false, false),
+        line: None,
+        kind: None,
+    };
+    if let Ok(line) = address.trim().parse::<usize>() {
+        tag.line = Some(line);
+    } else if let Some((pattern, anchored)) = parse_pattern(address) {
+        tag.pattern = Some(pattern);
+        tag.anchored = anchored;
+    }
+
+    for field in extensions.split('\t').filter(|field| !field.is_empty()) {
+        match field.split_once(':') {
+            Some(("kind", kind)) => tag.kind = Some(kind.to_string()),
+            Some(("line", line)) => tag.line = tag.line.or(line.parse().ok()),
+            Some(_) => {}
+            // A field without a name is the kind
+            None => tag.kind = Some(field.to_string()),
+        }
+    }
+    Some(tag)
+}
+
+/// The text of a `/.../` or `?...?` search pattern and whether it's
+/// anchored at the start and end of the line
+fn parse_pattern(address: &str) -> Option<(String, (bool, bool))> {
+    let delimiter = address.chars().next().filter(|&c| c == '/' || c == '?')?;
+    let body = address[1..].strip_suffix(delimiter)?;
+    let (body, start) = match body.strip_prefix('^') {
+        Some(body) => (body, true),
+        None => (body, false),
+    };
+    let (body, end) = match body.strip_suffix('$') {
+        Some(body) if !body.ends_with('\\') => (body, true),
+        _ => (body, false),
+    };
+
+    let mut pattern = String::with_capacity(body.len());
+    let mut chars = body.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // Only the delimiter and the backslash itself are escaped
+            pattern.extend(chars.next());
+        } else {
+            pattern.push(c);
+        }
+    }
+    Some((pattern, (start, end)))
+}
+
+/// The tags in the output of `global -x`
+pub fn parse_global_output(output: &str) -> Vec<Tag> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let line = fields.next()?.parse().ok()?;
+            let file = fields.next()?;
+            Some(Tag {
+                name: name.to_string(),
+                file: PathBuf::from(file),
+                pattern: None,
+                anchored: (false, false),
+                line: Some(line),
+                kind: None,
+            })
+        })
+        .collect()
+}
+
+/// The names of `tags`, which are sorted by name, without duplicates
+pub fn tag_names(tags: &[Tag]) -> Vec<String> {
+    let mut names: Vec<String> = tags.iter().map(|tag| tag.name.clone()).collect();
+    names.dedup();
+    names
+}
+
+/// Byte offset of the definition of `tag` in `text`: its name on the line
+/// the pattern matches, or on the tag's line number if no line does
+pub fn locate(text: &str, tag: &Tag) -> Option<usize> {
+    let by_pattern = tag.pattern.as_deref().and_then(|pattern| {
+        lines_with_offsets(text).find(|(_, line)| match tag.anchored {
+            (true, true) => *line == pattern,
+            (true, false) => line.starts_with(pattern),
+            (false, true) => line.ends_with(pattern),
+            (false, false) => line.contains(pattern),
+        })
+    });
+    let (start, line) =
+        by_pattern.or_else(|| lines_with_offsets(text).nth(tag.line?.checked_sub(1)?))?;
+    Some(start + line.find(tag.name.as_str()).unwrap_or(0))
+}
+
+/// The lines of `text` without their endings, and where they start
+fn lines_with_offsets(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_matches(['\n', '\r'])))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        let contents = "!_TAG_FILE_FORMAT\t2\t/extended format/\n\
+                        parse\tsrc/parse.c\t/^int parse(const char *s)$/;\"\tf\tline:12\n\
+                        Config\tsrc/config.h\t42;\"\tkind:struct\n\
+                        path\tsrc/a.c\t/^  char *path = \"a\\/b\";$/;\"\tv\n";
+        let tags = parse_tags(contents);
+        assert_eq!(tags.len(), 3);
+
+        assert_eq!(tags[0].name, "Config");
+        assert_eq!(tags[0].line, Some(42));
+        assert_eq!(tags[0].pattern, None);
+        assert_eq!(tags[0].kind.as_deref(), Some("struct"));
+
+        assert_eq!(tags[1].name, "parse");
+        assert_eq!(tags[1].file, PathBuf::from("src/parse.c"));
+        assert_eq!(tags[1].pattern.as_deref(), Some("int parse(const char *s)"));
+        assert_eq!(tags[1].anchored, (true, true));
+        assert_eq!(tags[1].line, Some(12));
+        assert_eq!(tags[1].kind.as_deref(), Some("f"));
+
+        assert_eq!(tags[2].pattern.as_deref(), Some("  char *path = \"a/b\";"));
+    }
+
+    #[test]
+    fn test_parse_global_output() {
+        let output = "main              10 /src/main.c      int main(void)\n\
+                      parse             12 /src/parse.c     int parse(const char *s)\n";
+        let tags = parse_global_output(output);
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[1].name, "parse");
+        assert_eq!(tags[1].line, Some(12));
+        assert_eq!(tags[1].file, PathBuf::from("/src/parse.c"));
+    }
+
+    #[test]
+    fn test_tag_names() {
+        let tags = parse_tags("b\tx.c\t1\na\tx.c\t2\nb\ty.c\t3\n");
+        assert_eq!(tag_names(&tags), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_locate_by_pattern_then_line() {
+        let text = "// parse\n\nint parse(const char *s)\n{\n}\n";
+        let mut tag = parse_tags("parse\tp.c\t/^int parse(const char *s)$/;\"\tf\n").remove(0);
+        assert_eq!(locate(text, &tag), Some(text.find("parse(").unwrap()));
+
+        // The line moved since the tags were made: fall back to the line number
+        tag.pattern = Some("int parse(void)".to_string());
+        tag.line = Some(1);
+        assert_eq!(locate(text, &tag), Some(3));
+
+        let tag = parse_tags("parse\tp.c\t3\n").remove(0);
+        assert_eq!(locate(text, &tag), Some(text.find("parse(").unwrap()));
+        let tag = parse_tags("parse\tp.c\t30\n").remove(0);
+        assert_eq!(locate(text, &tag), None);
+    }
+}
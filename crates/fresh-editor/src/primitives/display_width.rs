@@ -72,6 +72,53 @@ pub fn byte_offset_at_visual_column(s: &str, visual_col: usize) -> usize {
     s.len()
 }
 
+/// Width of a tab that starts at visual column `col`: it reaches the next tab stop.
+///
+/// A `tab_size` of 0 is treated as 4, matching the renderer.
+#[inline]
+pub fn tab_width_at(col: usize, tab_size: usize) -> usize {
+    let tab_size = if tab_size == 0 { 4 } else { tab_size };
+    tab_size - col % tab_size
+}
+
+/// Calculate the display width of a character that starts at visual column `col`.
+///
+/// Like `char_width`, except that a tab reaches the next tab stop.
+#[inline]
+pub fn char_width_at(c: char, col: usize, tab_size: usize) -> usize {
+    if c == '\t' {
+        tab_width_at(col, tab_size)
+    } else {
+        char_width(c)
+    }
+}
+
+/// Calculate the visual column at a given byte offset within a line, as rendered.
+///
+/// Like `visual_column_at_byte`, except that tabs reach the next tab stop.
+pub fn line_visual_column_at_byte(line: &str, byte_offset: usize, tab_size: usize) -> usize {
+    line.char_indices()
+        .take_while(|(byte_idx, _)| *byte_idx < byte_offset)
+        .fold(0, |col, (_, ch)| col + char_width_at(ch, col, tab_size))
+}
+
+/// Convert a visual column within a line, as rendered, to a byte offset.
+///
+/// Returns the byte offset of the character covering the visual column, so a
+/// column in the middle of a tab or a double-width character maps to its
+/// start, like a mouse click there. If the visual column is beyond the line's
+/// width, returns the line's length.
+pub fn line_byte_offset_at_visual_column(line: &str, visual_col: usize, tab_size: usize) -> usize {
+    let mut current_col = 0;
+    for (byte_idx, ch) in line.char_indices() {
+        current_col += char_width_at(ch, current_col, tab_size);
+        if current_col > visual_col {
+            return byte_idx;
+        }
+    }
+    line.len()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +187,31 @@ mod tests {
         let string = String::from("Hello🚀");
         assert_eq!(string.display_width(), 7);
     }
+
+    #[test]
+    fn test_tab_stops() {
+        assert_eq!(tab_width_at(0, 4), 4);
+        assert_eq!(tab_width_at(3, 4), 1);
+        assert_eq!(tab_width_at(4, 4), 4);
+        assert_eq!(tab_width_at(1, 0), 3);
+
+        let line = "a\tb\t\tc";
+        assert_eq!(line_visual_column_at_byte(line, 1, 4), 1);
+        assert_eq!(line_visual_column_at_byte(line, 2, 4), 4);
+        assert_eq!(line_visual_column_at_byte(line, 4, 4), 8);
+        assert_eq!(line_visual_column_at_byte(line, 5, 4), 12);
+        assert_eq!(line_visual_column_at_byte(line, 100, 8), 25);
+
+        assert_eq!(line_byte_offset_at_visual_column(line, 0, 4), 0);
+        assert_eq!(line_byte_offset_at_visual_column(line, 1, 4), 1);
+        assert_eq!(line_byte_offset_at_visual_column(line, 3, 4), 1);
+        assert_eq!(line_byte_offset_at_visual_column(line, 4, 4), 2);
+        assert_eq!(line_byte_offset_at_visual_column(line, 11, 4), 4);
+        assert_eq!(line_byte_offset_at_visual_column(line, 12, 4), 5);
+        assert_eq!(line_byte_offset_at_visual_column(line, 50, 4), line.len());
+
+        // Tab stops count from the start of the line, past wide characters
+        assert_eq!(line_visual_column_at_byte("你\tx", 4, 4), 4);
+        assert_eq!(line_byte_offset_at_visual_column("你\tx", 1, 4), 0);
+    }
 }
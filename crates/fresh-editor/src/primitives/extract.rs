@@ -0,0 +1,566 @@
+//! Extract Variable and Extract Function without a language server
+//!
+//! The syntax tree gives the statement and the top-level item the selection
+//! sits in. A variable is declared just before that statement, at its
+//! indentation; a function goes next to that item. Extracted functions take
+//! the variables the selection uses from earlier in the enclosing function as
+//! parameters, and return the ones it declares that are used after it.
+//!
+//! Parameters get no types, so functions are only extracted in Python,
+//! JavaScript and TypeScript.
+//!
+//! With a language server its own refactoring is used instead;
+//! [`new_identifier`] and [`rename_identifier`] put the chosen name in place
+//! of the placeholder the server makes up.
+
+use fresh_languages::tree_sitter::{Node, Tree};
+use fresh_languages::Language;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// What to extract the selection into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractKind {
+    Variable,
+    Function,
+}
+
+/// A replacement of a range of the original text
+pub type Edit = (Range<usize>, String);
+
+/// Node kinds whose children are statements
+const STATEMENT_LISTS: &[&str] = &[
+    "block",
+    "statement_block",
+    "statement_list",
+    "program",
+    "module",
+    "source_file",
+];
+
+/// Whether `kind` can be extracted in `language` without a language server
+pub fn supports(language: &Language, kind: ExtractKind) -> bool {
+    match kind {
+        ExtractKind::Variable => matches!(
+            language,
+            Language::Rust
+                | Language::Go
+                | Language::Python
+                | Language::JavaScript
+                | Language::TypeScript
+        ),
+        ExtractKind::Function => matches!(
+            language,
+            Language::Python | Language::JavaScript | Language::TypeScript
+        ),
+    }
+}
+
+/// Whether `name` can name a variable or function
+pub fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// The edits that extract `selection` of `text` into a variable or function
+/// called `name`
+///
+/// Edits are in order and neither overlap nor share a position; the last
+/// one that replaces text is the one replacing the selection. Returns `None`
+/// when the selection isn't an expression (or, for functions, a run of whole
+/// statements).
+pub fn extract(
+    language: &Language,
+    text: &str,
+    tree: &Tree,
+    selection: Range<usize>,
+    kind: ExtractKind,
+    name: &str,
+) -> Option<Vec<Edit>> {
+    if !supports(language, kind) {
+        return None;
+    }
+    let selection = trim(text, selection);
+    if selection.is_empty() {
+        return None;
+    }
+    let mut edits = match kind {
+        ExtractKind::Variable => extract_variable(language, text, tree, selection, name)?,
+        ExtractKind::Function => extract_function(language, text, tree, selection, name)?,
+    };
+    edits.sort_by_key(|(range, _)| (range.start, !range.is_empty()));
+
+    // An insertion where the selection starts becomes part of its replacement
+    let mut merged: Vec<Edit> = Vec::with_capacity(edits.len());
+    for (range, text) in edits {
+        match merged.last_mut() {
+            Some((last, inserted)) if last.start == range.start && last.end == range.start => {
+                *last = range;
+                inserted.push_str(&text);
+            }
+            _ => merged.push((range, text)),
+        }
+    }
+    Some(merged)
+}
+
+fn extract_variable(
+    language: &Language,
+    text: &str,
+    tree: &Tree,
+    selection: Range<usize>,
+    name: &str,
+) -> Option<Vec<Edit>> {
+    let node = expression_at(tree, &selection)?;
+    let statement = statement_of(node)?;
+    let expression = &text[selection.clone()];
+    let declaration = match language {
+        Language::Python => format!("{name} = {expression}"),
+        Language::Go => format!("{name} := {expression}"),
+        Language::Rust => format!("let {name} = {expression};"),
+        _ => format!("const {name} = {expression};"),
+    };
+
+    let start = statement.start_byte();
+    let line_start = line_start(text, start);
+    let before = &text[line_start..start];
+    let declaration = if before.trim().is_empty() {
+        format!("{declaration}\n{before}")
+    } else {
+        format!("{declaration} ")
+    };
+    Some(vec![
+        (start..start, declaration),
+        (selection, name.to_string()),
+    ])
+}
+
+fn extract_function(
+    language: &Language,
+    text: &str,
+    tree: &Tree,
+    selection: Range<usize>,
+    name: &str,
+) -> Option<Vec<Edit>> {
+    let python = matches!(language, Language::Python);
+    let statements = statements_in(tree, &selection);
+    let first = match &statements {
+        Some(statements) => statements[0],
+        None => expression_at(tree, &selection)?,
+    };
+    let scope = first
+        .parent()
+        .and_then(enclosing_function)
+        .unwrap_or_else(|| tree.root_node());
+
+    // Variables declared before the selection become parameters; the ones it
+    // declares are returned if the rest of the function uses them
+    let mut declared = HashSet::new();
+    let mut params = Vec::new();
+    let mut returns = Vec::new();
+    let mut used_after = HashSet::new();
+    for identifier in identifiers(scope) {
+        let word = &text[identifier.byte_range()];
+        if identifier.end_byte() <= selection.start {
+            if is_declaration(identifier) {
+                declared.insert(word);
+            }
+        } else if identifier.start_byte() >= selection.end {
+            used_after.insert(word);
+        } else if is_declaration(identifier) {
+            if statements.is_some() && !returns.contains(&word) {
+                returns.push(word);
+            }
+        } else if declared.contains(word) && !params.contains(&word) && !is_member(identifier) {
+            params.push(word);
+        }
+    }
+    returns.retain(|word| used_after.contains(word));
+    let params = params.join(", ");
+    let returns = returns.join(", ");
+
+    let unit = indent_unit(text);
+    let base = &text[line_start(text, selection.start)..selection.start];
+    let base = if base.trim().is_empty() { base } else { "" };
+    let mut body: Vec<String> = Vec::new();
+    match statements {
+        Some(_) => {
+            for line in text[selection.clone()].lines() {
+                let line = line.strip_prefix(base).unwrap_or(line);
+                body.push(if line.trim().is_empty() {
+                    String::new()
+                } else {
+                    format!("{unit}{line}")
+                });
+            }
+            if !returns.is_empty() {
+                body.push(match (python, returns.contains(',')) {
+                    (true, _) => format!("{unit}return {returns}"),
+                    (false, false) => format!("{unit}return {returns};"),
+                    (false, true) => format!("{unit}return [{returns}];"),
+                });
+            }
+        }
+        None => {
+            let expression = &text[selection.clone()];
+            body.push(if python {
+                format!("{unit}return {expression}")
+            } else {
+                format!("{unit}return {expression};")
+            });
+        }
+    }
+    let body = body.join("\n");
+    let function = if python {
+        format!("def {name}({params}):\n{body}")
+    } else {
+        format!("function {name}({params}) {{\n{body}\n}}")
+    };
+
+    let call = format!("{name}({params})");
+    let call = match (statements.is_some(), python, returns.contains(',')) {
+        (false, _, _) => call,
+        (true, true, _) if returns.is_empty() => call,
+        (true, true, _) => format!("{returns} = {call}"),
+        (true, false, _) if returns.is_empty() => format!("{call};"),
+        (true, false, false) => format!("let {returns} = {call};"),
+        (true, false, true) => format!("let [{returns}] = {call};"),
+    };
+
+    // Next to the top-level item: after a function or class, so the
+    // extracted function sits with it, otherwise before the code using it
+    let separator = if python { "\n\n\n" } else { "\n\n" };
+    let mut top = first;
+    while let Some(parent) = top.parent() {
+        if parent.parent().is_none() {
+            break;
+        }
+        top = parent;
+    }
+    let definition = ["function", "class", "decorated_definition", "export"]
+        .iter()
+        .any(|kind| top.kind().contains(kind));
+    let insertion = if definition {
+        (
+            top.end_byte()..top.end_byte(),
+            format!("{separator}{function}"),
+        )
+    } else {
+        (
+            top.start_byte()..top.start_byte(),
+            format!("{function}{separator}"),
+        )
+    };
+    Some(vec![insertion, (selection, call)])
+}
+
+/// The innermost node spanning exactly `range`, if it's an expression
+fn expression_at<'t>(tree: &'t Tree, range: &Range<usize>) -> Option<Node<'t>> {
+    let node = tree
+        .root_node()
+        .named_descendant_for_byte_range(range.start, range.end)?;
+    let kind = node.kind();
+    let statement = ["statement", "declaration", "definition", "_item"]
+        .iter()
+        .any(|suffix| kind.ends_with(suffix));
+    (node.byte_range() == *range && !statement && !STATEMENT_LISTS.contains(&kind)).then_some(node)
+}
+
+/// The statement `node` is part of
+fn statement_of(node: Node<'_>) -> Option<Node<'_>> {
+    let mut node = node;
+    loop {
+        let parent = node.parent()?;
+        if STATEMENT_LISTS.contains(&parent.kind()) {
+            return Some(node);
+        }
+        node = parent;
+    }
+}
+
+/// The whole statements that `range` is made of, if it's made of any
+fn statements_in<'t>(tree: &'t Tree, range: &Range<usize>) -> Option<Vec<Node<'t>>> {
+    let mut list = tree
+        .root_node()
+        .descendant_for_byte_range(range.start, range.end)?;
+    while !STATEMENT_LISTS.contains(&list.kind()) {
+        list = list.parent()?;
+    }
+    let mut cursor = list.walk();
+    let mut statements = Vec::new();
+    for child in list.named_children(&mut cursor) {
+        if child.end_byte() <= range.start || child.start_byte() >= range.end {
+            continue;
+        }
+        if child.start_byte() < range.start || child.end_byte() > range.end {
+            return None;
+        }
+        statements.push(child);
+    }
+    let first = statements.first()?;
+    let last = statements.last()?;
+    (first.start_byte() == range.start && last.end_byte() == range.end).then_some(statements)
+}
+
+/// The innermost function around `node`, including `node` itself
+fn enclosing_function(node: Node<'_>) -> Option<Node<'_>> {
+    let mut node = node;
+    loop {
+        let kind = node.kind();
+        if (kind.contains("function") || kind == "method_definition")
+            && node.child_by_field_name("body").is_some()
+        {
+            return Some(node);
+        }
+        node = node.parent()?;
+    }
+}
+
+/// The variable-like identifiers under `node`, in order
+fn identifiers(node: Node<'_>) -> Vec<Node<'_>> {
+    let mut found = Vec::new();
+    let mut cursor = node.walk();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        if matches!(
+            node.kind(),
+            "identifier" | "shorthand_property_identifier_pattern"
+        ) {
+            found.push(node);
+        }
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    found
+}
+
+/// Whether `node` is `parent`'s `field`
+fn is_field(parent: Node<'_>, field: &str, node: Node<'_>) -> bool {
+    parent.child_by_field_name(field) == Some(node)
+}
+
+/// Whether the identifier `node` declares (or assigns) a variable
+fn is_declaration(node: Node<'_>) -> bool {
+    let Some(parent) = node.parent() else {
+        return false;
+    };
+    let kind = parent.kind();
+    if kind == "keyword_argument" {
+        return false;
+    }
+    let assignment = kind.contains("assignment") || kind.starts_with("for");
+    kind.contains("parameter")
+        || kind.contains("pattern")
+        || (assignment && is_field(parent, "left", node))
+        || ["name", "pattern", "parameter"]
+            .iter()
+            .any(|field| is_field(parent, field, node))
+}
+
+/// Whether the identifier `node` names a member, as `b` in Python's `a.b`
+fn is_member(node: Node<'_>) -> bool {
+    node.parent()
+        .is_some_and(|parent| is_field(parent, "attribute", node))
+}
+
+/// `range` without its leading and trailing whitespace
+fn trim(text: &str, range: Range<usize>) -> Range<usize> {
+    let selected = &text[range.clone()];
+    let start = range.start + (selected.len() - selected.trim_start().len());
+    let end = range.end - (selected.len() - selected.trim_end().len());
+    start..end.max(start)
+}
+
+fn line_start(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |newline| newline + 1)
+}
+
+/// The indentation of the first indented line, or four spaces
+fn indent_unit(text: &str) -> &str {
+    text.lines()
+        .find(|line| line.starts_with([' ', '\t']) && !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("    ")
+}
+
+/// The words of `text` that could be identifiers, in order
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = None;
+    let boundary = text.len();
+    text.char_indices()
+        .chain(std::iter::once((boundary, ' ')))
+        .filter_map(move |(i, c)| {
+            if c.is_alphanumeric() || c == '_' {
+                start.get_or_insert(i);
+                None
+            } else {
+                start.take().map(|start| (start, &text[start..i]))
+            }
+        })
+        .filter(|(_, word)| is_identifier(word))
+}
+
+/// The first identifier that `text` has more than once and `document`
+/// doesn't have at all
+///
+/// This is the name a language server made up for what it extracted, which
+/// its edits both declare and use.
+pub fn new_identifier<'a>(document: &str, text: &'a str) -> Option<&'a str> {
+    let existing: HashSet<&str> = words(document).map(|(_, word)| word).collect();
+    let mut seen = HashSet::new();
+    words(text)
+        .map(|(_, word)| word)
+        .filter(|word| !existing.contains(word))
+        .find(|word| !seen.insert(*word))
+}
+
+/// `text` with the identifier `from` replaced by `to` wherever it is a
+/// whole word
+pub fn rename_identifier(text: &str, from: &str, to: &str) -> String {
+    let mut renamed = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, word) in words(text) {
+        if word == from {
+            renamed.push_str(&text[copied..start]);
+            renamed.push_str(to);
+            copied = start + word.len();
+        }
+    }
+    renamed.push_str(&text[copied..]);
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::text_objects::parse;
+
+    /// `text` after extracting the part between the `^` markers
+    fn extracted(language: Language, text: &str, kind: ExtractKind) -> Option<String> {
+        let start = text.find('^').unwrap();
+        let end = text.rfind('^').unwrap() - 1;
+        let text = text.replace('^', "");
+        let tree = parse(&language.ts_language()?, &text)?;
+        let edits = extract(&language, &text, &tree, start..end, kind, "extracted")?;
+        let mut result = text.clone();
+        for (range, new_text) in edits.into_iter().rev() {
+            result.replace_range(range, &new_text);
+        }
+        Some(result)
+    }
+
+    #[test]
+    fn test_extract_variable() {
+        assert_eq!(
+            extracted(
+                Language::Rust,
+                "fn f() {\n    let x = a + ^b * 2^;\n}\n",
+                ExtractKind::Variable
+            )
+            .unwrap(),
+            "fn f() {\n    let extracted = b * 2;\n    let x = a + extracted;\n}\n"
+        );
+        assert_eq!(
+            extracted(
+                Language::Python,
+                "if x:\n    print(^len(y)^)\n",
+                ExtractKind::Variable
+            )
+            .unwrap(),
+            "if x:\n    extracted = len(y)\n    print(extracted)\n"
+        );
+        assert_eq!(
+            extracted(
+                Language::Go,
+                "func f() {\n\treturn ^g(1)^\n}\n",
+                ExtractKind::Variable
+            )
+            .unwrap(),
+            "func f() {\n\textracted := g(1)\n\treturn extracted\n}\n"
+        );
+        // A statement that doesn't start its line gets the declaration inline
+        assert_eq!(
+            extracted(
+                Language::JavaScript,
+                "function f() { return ^1 + 2^; }",
+                ExtractKind::Variable
+            )
+            .unwrap(),
+            "function f() { const extracted = 1 + 2; return extracted; }"
+        );
+        // The declaration goes where the selection starts
+        assert_eq!(
+            extracted(
+                Language::TypeScript,
+                "  ^f(x)^.then(g);\n",
+                ExtractKind::Variable
+            )
+            .unwrap(),
+            "  const extracted = f(x);\n  extracted.then(g);\n"
+        );
+        // Not an expression
+        assert_eq!(
+            extracted(Language::Rust, "fn f() { ^a + ^b; }", ExtractKind::Variable),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extract_function() {
+        let text = "def f(a, b):\n    c = 1\n    ^d = a + c\n    print(d)^\n    return d\n";
+        assert_eq!(
+            extracted(Language::Python, text, ExtractKind::Function).unwrap(),
+            "def f(a, b):\n    c = 1\n    d = extracted(a, c)\n    return d\n\n\n\
+             def extracted(a, c):\n    d = a + c\n    print(d)\n    return d\n"
+        );
+
+        let text = "function f(x) {\n  if (x) {\n    ^log(x.size);^\n  }\n}\n";
+        assert_eq!(
+            extracted(Language::JavaScript, text, ExtractKind::Function).unwrap(),
+            "function f(x) {\n  if (x) {\n    extracted(x);\n  }\n}\n\n\
+             function extracted(x) {\n  log(x.size);\n}\n"
+        );
+
+        // An expression is returned; module-level code gets the function first
+        assert_eq!(
+            extracted(
+                Language::Python,
+                "n = 2\nprint(^n * n^)\n",
+                ExtractKind::Function
+            )
+            .unwrap(),
+            "n = 2\ndef extracted(n):\n    return n * n\n\n\nprint(extracted(n))\n"
+        );
+
+        // Part of a statement, and a language that needs parameter types
+        assert_eq!(
+            extracted(
+                Language::Python,
+                "def f():\n    ^a = 1\n    b^ = 2\n",
+                ExtractKind::Function
+            ),
+            None
+        );
+        assert_eq!(
+            extracted(Language::Rust, "fn f() { ^g();^ }", ExtractKind::Function),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rename_placeholder() {
+        let document = "fn f() { g(1 + 2); }";
+        let edit = "let var_name = 1 + 2;\n    g(var_name)";
+        assert_eq!(new_identifier(document, edit), Some("var_name"));
+        assert_eq!(
+            rename_identifier(edit, "var_name", "sum"),
+            "let sum = 1 + 2;\n    g(sum)"
+        );
+        assert_eq!(rename_identifier("a ab a_b a", "a", "x"), "x ab a_b x");
+        assert_eq!(new_identifier("g(1)", "g(1)"), None);
+        assert!(is_identifier("_tmp2"));
+        assert!(!is_identifier("2tmp"));
+        assert!(!is_identifier("a b"));
+    }
+}
@@ -523,6 +523,8 @@ mod tests {
                 filenames: vec!["CUSTOMBUILD".to_string()],
                 grammar: "Bourne Again Shell (bash)".to_string(),
                 comment_prefix: Some("#".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -532,6 +534,13 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
 
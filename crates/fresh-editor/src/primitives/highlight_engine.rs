@@ -18,6 +18,7 @@
 
 use crate::model::buffer::Buffer;
 use crate::primitives::grammar::GrammarRegistry;
+use crate::primitives::highlight_types::CacheSpan;
 use crate::primitives::highlighter::{
     highlight_color, HighlightCategory, HighlightSpan, Highlighter, Language,
 };
@@ -202,7 +203,6 @@ pub struct TextMateEngine {
     syntax_set: Arc<SyntaxSet>,
     syntax_index: usize,
     cache: Option<TextMateCache>,
-    last_buffer_len: usize,
     /// Tree-sitter language for non-highlighting features (indentation, semantic highlighting)
     /// Even when using syntect for highlighting, we track the language for other features
     ts_language: Option<Language>,
@@ -210,7 +210,7 @@ pub struct TextMateEngine {
 
 #[derive(Debug, Clone)]
 struct TextMateCache {
-    range: Range<usize>,
+    span: CacheSpan,
     spans: Vec<CachedSpan>,
 }
 
@@ -230,7 +230,6 @@ impl TextMateEngine {
             syntax_set,
             syntax_index,
             cache: None,
-            last_buffer_len: 0,
             ts_language: None,
         }
     }
@@ -245,7 +244,6 @@ impl TextMateEngine {
             syntax_set,
             syntax_index,
             cache: None,
-            last_buffer_len: 0,
             ts_language,
         }
     }
@@ -270,11 +268,8 @@ impl TextMateEngine {
         use syntect::parsing::{ParseState, ScopeStack};
 
         // Check cache validity
-        if let Some(cache) = &self.cache {
-            if cache.range.start <= viewport_start
-                && cache.range.end >= viewport_end
-                && self.last_buffer_len == buffer.len()
-            {
+        if let Some(cache) = &mut self.cache {
+            if cache.span.covers(buffer, viewport_start..viewport_end) {
                 return cache
                     .spans
                     .iter()
@@ -416,10 +411,9 @@ impl TextMateEngine {
 
         // Update cache
         self.cache = Some(TextMateCache {
-            range: parse_start..parse_end,
+            span: CacheSpan::new(buffer, parse_start..parse_end),
             spans: spans.clone(),
         });
-        self.last_buffer_len = buffer.len();
 
         // Filter and resolve colors
         spans
@@ -468,7 +462,7 @@ impl TextMateEngine {
     /// Invalidate cache for edited range
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
         if let Some(cache) = &self.cache {
-            if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
+            if edit_range.start < cache.span.range.end && edit_range.end > cache.span.range.start {
                 self.cache = None;
             }
         }
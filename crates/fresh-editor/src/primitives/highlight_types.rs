@@ -3,6 +3,7 @@
 //! This module provides the base types needed for syntax highlighting
 //! without depending on tree-sitter (which is not WASM-compatible).
 
+use crate::model::buffer::Buffer;
 use ratatui::style::Color;
 use std::ops::Range;
 
@@ -34,6 +35,41 @@ pub struct HighlightSpan {
     pub color: Color,
 }
 
+/// The byte range a highlight cache was built from, and the buffer
+/// revision it matches.
+#[derive(Debug, Clone)]
+pub struct CacheSpan {
+    /// Byte range the cached spans cover
+    pub range: Range<usize>,
+    /// Buffer revision the spans are valid for
+    pub revision: u64,
+}
+
+impl CacheSpan {
+    /// A span over `range` of the buffer at its current revision
+    pub fn new(buffer: &Buffer, range: Range<usize>) -> Self {
+        Self {
+            range,
+            revision: buffer.revision(),
+        }
+    }
+
+    /// Whether the cache covers `range` of the buffer as it is now. Edits
+    /// after the cached range leave it valid.
+    pub fn covers(&mut self, buffer: &Buffer, range: Range<usize>) -> bool {
+        if self.range.start > range.start || self.range.end < range.end {
+            return false;
+        }
+        if self.revision != buffer.revision() {
+            if !buffer.unchanged_before(self.revision, self.range.end) {
+                return false;
+            }
+            self.revision = buffer.revision();
+        }
+        true
+    }
+}
+
 /// Get the color for a highlight category from the theme.
 pub fn highlight_color(category: HighlightCategory, theme: &crate::view::theme::Theme) -> Color {
     match category {
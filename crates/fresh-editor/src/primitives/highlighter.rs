@@ -8,20 +8,56 @@
 //! # Performance
 //! Must work instantly when loading a 1GB file and jumping to an arbitrary offset.
 //! This is achieved by only parsing the visible viewport (~50 lines), not the entire file.
+//!
+//! # Injections
+//! Embedded languages (JavaScript/CSS in HTML, fenced code in Markdown, SQL in
+//! string literals) are highlighted with their own grammar. Their highlight
+//! configurations are built on first use and shared by all highlighters.
 
 use crate::config::LARGE_FILE_THRESHOLD_BYTES;
 use crate::model::buffer::Buffer;
+use crate::primitives::highlight_types::CacheSpan;
 use crate::view::theme::Theme;
 use fresh_languages::tree_sitter_highlight::{
     HighlightConfiguration, HighlightEvent, Highlighter as TSHighlighter,
 };
 pub use fresh_languages::{HighlightCategory, Language};
 use ratatui::style::Color;
+use std::collections::HashMap;
 use std::ops::Range;
+use std::sync::{Mutex, OnceLock};
 
 /// Maximum bytes to parse in a single operation (for viewport highlighting)
 const MAX_PARSE_BYTES: usize = LARGE_FILE_THRESHOLD_BYTES as usize; // 1MB
 
+/// Highlight configuration for a language embedded in another one.
+///
+/// Configurations are leaked into a process-wide cache: the tree-sitter
+/// highlighter needs them to outlive the highlight iterator, and there is at
+/// most one per language.
+fn injection_config(language: Language) -> Option<&'static HighlightConfiguration> {
+    static CONFIGS: OnceLock<Mutex<HashMap<&'static str, &'static HighlightConfiguration>>> =
+        OnceLock::new();
+    let mut configs = CONFIGS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .ok()?;
+    if let Some(config) = configs.get(language.id()) {
+        return Some(config);
+    }
+    match language.highlight_config() {
+        Ok(config) => {
+            let config: &'static HighlightConfiguration = Box::leak(Box::new(config));
+            configs.insert(language.id(), config);
+            Some(config)
+        }
+        Err(e) => {
+            tracing::debug!("No highlight config for injected {}: {}", language.id(), e);
+            None
+        }
+    }
+}
+
 /// Get the color for a highlight category from the theme
 pub fn highlight_color(category: HighlightCategory, theme: &Theme) -> Color {
     match category {
@@ -61,7 +97,7 @@ struct CachedSpan {
 #[derive(Debug, Clone)]
 struct HighlightCache {
     /// Byte range this cache covers
-    range: Range<usize>,
+    span: CacheSpan,
     /// Highlighted spans within this range (stores categories for theme-independent caching)
     spans: Vec<CachedSpan>,
 }
@@ -76,8 +112,6 @@ pub struct Highlighter {
     config: HighlightConfiguration,
     /// Cache of highlighted spans (only for visible viewport)
     cache: Option<HighlightCache>,
-    /// Last known buffer length (for detecting complete buffer changes)
-    last_buffer_len: usize,
 }
 
 impl Highlighter {
@@ -89,7 +123,6 @@ impl Highlighter {
             language,
             config,
             cache: None,
-            last_buffer_len: 0,
         })
     }
 
@@ -109,11 +142,8 @@ impl Highlighter {
         context_bytes: usize,
     ) -> Vec<HighlightSpan> {
         // Check if cache is valid for this range
-        if let Some(cache) = &self.cache {
-            if cache.range.start <= viewport_start
-                && cache.range.end >= viewport_end
-                && self.last_buffer_len == buffer.len()
-            {
+        if let Some(cache) = &mut self.cache {
+            if cache.span.covers(buffer, viewport_start..viewport_end) {
                 // Cache hit! Filter spans to the requested range and resolve colors from theme
                 return cache
                     .spans
@@ -151,11 +181,19 @@ impl Highlighter {
 
         // Highlight the source - store categories for theme-independent caching
         let mut cached_spans = Vec::new();
+        let host = self.language;
         match self.ts_highlighter.highlight(
             &self.config,
             &source,
-            None,     // cancellation flag
-            |_| None, // injection callback
+            None, // cancellation flag
+            move |name| {
+                // Injected highlights are mapped with the host's capture list
+                let injected = Language::from_injection_name(name)?;
+                if !host.shares_highlight_captures(&injected) {
+                    return None;
+                }
+                injection_config(injected)
+            },
         ) {
             Ok(highlights) => {
                 let mut current_highlight: Option<usize> = None;
@@ -197,10 +235,9 @@ impl Highlighter {
 
         // Update cache
         self.cache = Some(HighlightCache {
-            range: parse_range,
+            span: CacheSpan::new(buffer, parse_range),
             spans: cached_spans.clone(),
         });
-        self.last_buffer_len = buffer.len();
 
         // Filter to requested viewport and resolve colors from theme
         cached_spans
@@ -219,7 +256,7 @@ impl Highlighter {
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
         if let Some(cache) = &self.cache {
             // If edit intersects cache, invalidate it
-            if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
+            if edit_range.start < cache.span.range.end && edit_range.end > cache.span.range.start {
                 self.cache = None;
             }
         }
@@ -337,9 +374,14 @@ mod tests {
         let path = std::path::Path::new("test.p");
         assert!(matches!(Language::from_path(path), Some(Language::Pascal)));
 
-        // Markdown disabled due to tree-sitter version conflict
-        // let path = std::path::Path::new("test.md");
-        // assert!(matches!(Language::from_path(path), Some(Language::Markdown)));
+        let path = std::path::Path::new("test.md");
+        assert!(matches!(
+            Language::from_path(path),
+            Some(Language::Markdown)
+        ));
+
+        let path = std::path::Path::new("test.sql");
+        assert!(matches!(Language::from_path(path), Some(Language::Sql)));
 
         let path = std::path::Path::new("test.txt");
         assert!(Language::from_path(path).is_none());
@@ -417,6 +459,52 @@ mod tests {
         assert!(highlighter.cache.is_none());
     }
 
+    #[test]
+    fn test_cache_follows_revisions() {
+        let mut buffer = Buffer::from_str_test("fn main() {}\nfn other() {}\n");
+        let mut highlighter = Highlighter::new(Language::Rust).unwrap();
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        highlighter.highlight_viewport(&buffer, 0, 12, &theme, 0);
+        let cache = &mut highlighter.cache.as_mut().unwrap().span;
+        assert!(cache.covers(&buffer, 0..12));
+
+        // Edits after the cached range keep it
+        buffer.insert(buffer.len(), "fn third() {}\n");
+        assert!(cache.covers(&buffer, 0..12));
+        assert_eq!(cache.revision, buffer.revision());
+
+        // Edits within it don't, even if the length is unchanged
+        buffer.delete(3..4);
+        buffer.insert(3, "x");
+        assert!(!cache.covers(&buffer, 0..12));
+    }
+
+    #[test]
+    fn test_highlighter_injections() {
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let keyword = highlight_color(HighlightCategory::Keyword, &theme);
+
+        // JavaScript inside an HTML <script> element
+        let source = "<p>hi</p>\n<script>\nconst x = 1;\n</script>\n";
+        let buffer = Buffer::from_str_test(source);
+        let mut highlighter = Highlighter::new(Language::HTML).unwrap();
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+        let const_start = source.find("const").unwrap();
+        assert!(spans
+            .iter()
+            .any(|span| span.range == (const_start..const_start + 5) && span.color == keyword));
+
+        // Rust in a Markdown code fence
+        let source = "# Title\n\n```rust\nfn main() {}\n```\n";
+        let buffer = Buffer::from_str_test(source);
+        let mut highlighter = Highlighter::new(Language::Markdown).unwrap();
+        let spans = highlighter.highlight_viewport(&buffer, 0, buffer.len(), &theme, 100_000);
+        let fn_start = source.find("fn main").unwrap();
+        assert!(spans
+            .iter()
+            .any(|span| span.range == (fn_start..fn_start + 2) && span.color == keyword));
+    }
+
     #[test]
     fn test_theme_affects_colors() {
         let buffer = Buffer::from_str_test("fn main() {\n    println!(\"Hello\");\n}");
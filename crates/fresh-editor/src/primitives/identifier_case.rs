@@ -0,0 +1,213 @@
+//! Conversion between identifier styles: camelCase, snake_case,
+//! SCREAMING_SNAKE_CASE, kebab-case and Title Case
+//!
+//! An identifier is split into words at `_`, `-` and spaces, and where the
+//! case changes (`fooBar`, `HTTPServer`), then the words are joined in the
+//! target style.
+
+/// An identifier style to convert to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    Title,
+}
+
+/// Convert the identifiers in `text` to `case`
+///
+/// A line made only of words and spaces, like `Hello World`, is one
+/// identifier. Otherwise each run of letters, digits, `_` and `-` is
+/// converted separately and the rest of the line is left alone, so a
+/// selection of code keeps its punctuation.
+pub fn convert_case(text: &str, case: IdentifierCase) -> String {
+    text.split_inclusive('\n')
+        .map(|line| {
+            let content = line.trim_end_matches(['\n', '\r']);
+            let ending = &line[content.len()..];
+            let trimmed = content.trim();
+            let converted = if trimmed.contains(' ')
+                && trimmed.chars().all(|c| c == ' ' || is_identifier_char(c))
+            {
+                let start = content.len() - content.trim_start().len();
+                format!(
+                    "{}{}{}",
+                    &content[..start],
+                    convert_identifier(trimmed, case),
+                    &content[start + trimmed.len()..]
+                )
+            } else {
+                convert_runs(content, case)
+            };
+            converted + ending
+        })
+        .collect()
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Convert each run of identifier characters in `text`
+fn convert_runs(text: &str, case: IdentifierCase) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut run_start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (run_start, is_identifier_char(c)) {
+            (None, true) => run_start = Some(i),
+            (Some(start), false) => {
+                result.push_str(&convert_identifier(&text[start..i], case));
+                run_start = None;
+            }
+            _ => {}
+        }
+        if run_start.is_none() && i < text.len() {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Convert one identifier, keeping leading and trailing separators
+/// (as in `_private` or `__init__`)
+fn convert_identifier(identifier: &str, case: IdentifierCase) -> String {
+    let is_separator = |c: char| c == '_' || c == '-' || c == ' ';
+    let core = identifier.trim_matches(is_separator);
+    if core.is_empty() {
+        return identifier.to_string();
+    }
+    let prefix_len = identifier.len() - identifier.trim_start_matches(is_separator).len();
+    let prefix = &identifier[..prefix_len];
+    let suffix = &identifier[prefix_len + core.len()..];
+
+    let words = split_words(core);
+    let joined = match case {
+        IdentifierCase::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.to_lowercase()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        IdentifierCase::Snake => join_mapped(&words, "_", str::to_lowercase),
+        IdentifierCase::ScreamingSnake => join_mapped(&words, "_", str::to_uppercase),
+        IdentifierCase::Kebab => join_mapped(&words, "-", str::to_lowercase),
+        IdentifierCase::Title => join_mapped(&words, " ", capitalize),
+    };
+    format!("{prefix}{joined}{suffix}")
+}
+
+fn join_mapped(words: &[&str], separator: &str, map: impl Fn(&str) -> String) -> String {
+    words
+        .iter()
+        .map(|word| map(word))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+/// Split an identifier into its words
+///
+/// A new word starts after a separator, at an uppercase letter following a
+/// lowercase letter or digit (`fooBar`), and at the last capital of an
+/// acronym followed by lowercase (`HTTPServer`). Digits stay with the word
+/// before them (`utf8Decode`).
+fn split_words(identifier: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = identifier.char_indices().collect();
+    let mut words = Vec::new();
+    let mut word_start = None;
+    for (n, &(i, c)) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if let Some(start) = word_start.take() {
+                words.push(&identifier[start..i]);
+            }
+            continue;
+        }
+        if let Some(start) = word_start {
+            let prev = chars[n - 1].1;
+            let next_is_lower = chars.get(n + 1).is_some_and(|(_, c)| c.is_lowercase());
+            let boundary = c.is_uppercase()
+                && ((prev.is_lowercase() || prev.is_ascii_digit())
+                    || (prev.is_uppercase() && next_is_lower));
+            if boundary {
+                words.push(&identifier[start..i]);
+                word_start = Some(i);
+            }
+        } else {
+            word_start = Some(i);
+        }
+    }
+    if let Some(start) = word_start {
+        words.push(&identifier[start..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("fooBarBaz"), vec!["foo", "Bar", "Baz"]);
+        assert_eq!(split_words("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split_words("parse_HTML-doc"), vec!["parse", "HTML", "doc"]);
+        assert_eq!(split_words("utf8Decode"), vec!["utf8", "Decode"]);
+        assert_eq!(split_words("Hello  World"), vec!["Hello", "World"]);
+    }
+
+    #[test]
+    fn test_convert_identifier() {
+        let cases = [
+            (IdentifierCase::Camel, "httpServerUrl"),
+            (IdentifierCase::Snake, "http_server_url"),
+            (IdentifierCase::ScreamingSnake, "HTTP_SERVER_URL"),
+            (IdentifierCase::Kebab, "http-server-url"),
+            (IdentifierCase::Title, "Http Server Url"),
+        ];
+        for (case, expected) in cases {
+            for input in ["HTTPServerURL", "http_server_url", "Http Server Url"] {
+                assert_eq!(convert_case(input, case), expected, "{input} to {case:?}");
+            }
+        }
+        assert_eq!(
+            convert_case("__init__", IdentifierCase::ScreamingSnake),
+            "__INIT__"
+        );
+        assert_eq!(
+            convert_case("_privateVar", IdentifierCase::Snake),
+            "_private_var"
+        );
+    }
+
+    #[test]
+    fn test_convert_code() {
+        assert_eq!(
+            convert_case("let fooBar = bazQux(1);\n", IdentifierCase::Snake),
+            "let foo_bar = baz_qux(1);\n"
+        );
+        assert_eq!(
+            convert_case("  first name\r\nlast_name", IdentifierCase::Camel),
+            "  firstName\r\nlastName"
+        );
+        assert_eq!(convert_case("", IdentifierCase::Kebab), "");
+    }
+}
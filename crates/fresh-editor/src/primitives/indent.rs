@@ -39,19 +39,43 @@
 //! # Query Captures (when tree-sitter is used)
 //! - `@indent`: Increase indent after this node (e.g., `block`)
 //! - `@dedent`: Decrease indent for this node (e.g., closing `}`)
+//!
+//! # Injected Languages
+//! Inside an injected region (a Markdown code fence, an HTML `<script>` or
+//! `<style>`), the embedded language's `indents.scm` is used, and only the
+//! region itself is parsed.
+//!
+//! # Languages Without an Indents Query
+//! Buffers of a configured language that has no `indents.scm` (including
+//! those highlighted with TextMate grammars) are indented with the language's
+//! heuristic rules instead, see [`crate::primitives::indent_rules`].
 
+use crate::config::IndentPatterns;
 use crate::model::buffer::Buffer;
+use crate::model::filesystem::NoopFileSystem;
 use crate::primitives::highlighter::Language;
+use crate::primitives::indent_rules::IndentRules;
 use fresh_languages::tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Maximum bytes to parse before cursor for indent calculation
 const MAX_PARSE_BYTES: usize = 2000;
 
+/// Maximum bytes to parse on each side of the cursor when looking for an
+/// injected region (larger, so the start of a long code fence is found).
+/// Text after the cursor is needed too: an HTML `<script>` without its closing
+/// tag doesn't parse as a script element.
+const MAX_INJECTION_PARSE_BYTES: usize = 16 * 1024;
+
 /// Indent calculator using tree-sitter queries
 pub struct IndentCalculator {
     /// Map of language to (parser, query)
     configs: HashMap<&'static str, (Parser, Query)>,
+    /// Map of host language to (parser, injections query)
+    injection_configs: HashMap<&'static str, (Parser, Query)>,
+    /// Heuristic rules of the buffer's language, if it has a configuration
+    rules: Option<IndentRules>,
 }
 
 impl IndentCalculator {
@@ -59,9 +83,17 @@ impl IndentCalculator {
     pub fn new() -> Self {
         Self {
             configs: HashMap::new(),
+            injection_configs: HashMap::new(),
+            rules: None,
         }
     }
 
+    /// Set the heuristic indent patterns of the buffer's language, or `None`
+    /// for buffers without a configured language
+    pub fn set_rules(&mut self, patterns: Option<&IndentPatterns>) {
+        self.rules = patterns.map(IndentRules::new);
+    }
+
     /// Get or create parser and query for a language
     fn get_config(&mut self, language: &Language) -> Option<(&mut Parser, &Query)> {
         let (lang_name, ts_language, query_str) = match language {
@@ -155,6 +187,7 @@ impl IndentCalculator {
                 fresh_languages::tree_sitter_odin::LANGUAGE.into(),
                 include_str!("../../queries/odin/indents.scm"),
             ),
+            Language::Markdown | Language::Sql => return None,
         };
 
         // Check if we already have this config
@@ -183,6 +216,114 @@ impl IndentCalculator {
         Some((parser, query))
     }
 
+    /// Get or create parser and injections query for a host language
+    fn get_injection_config(&mut self, language: &Language) -> Option<(&mut Parser, &Query)> {
+        let ts_language: fresh_languages::tree_sitter::Language = match language {
+            Language::HTML => fresh_languages::tree_sitter_html::LANGUAGE.into(),
+            Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
+            Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
+            Language::Markdown => fresh_languages::tree_sitter_md::LANGUAGE.into(),
+            _ => return None,
+        };
+        let lang_name = language.id();
+
+        if !self.injection_configs.contains_key(lang_name) {
+            let mut parser = Parser::new();
+            if parser.set_language(&ts_language).is_err() {
+                tracing::error!("Failed to set language for {}", lang_name);
+                return None;
+            }
+
+            let query = match Query::new(&ts_language, &language.injections_query()) {
+                Ok(q) => q,
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to create injections query for {}: {:?}",
+                        lang_name,
+                        e
+                    );
+                    return None;
+                }
+            };
+
+            self.injection_configs.insert(lang_name, (parser, query));
+        }
+
+        let (parser, query) = self.injection_configs.get_mut(lang_name)?;
+        Some((parser, query))
+    }
+
+    /// Find the injected language at `position` (e.g. JavaScript inside an HTML
+    /// `<script>`). Returns the language and the byte offset where its region starts.
+    fn injection_at(
+        &mut self,
+        buffer: &Buffer,
+        position: usize,
+        language: &Language,
+    ) -> Option<(Language, usize)> {
+        let (parser, query) = self.get_injection_config(language)?;
+
+        let parse_start = position.saturating_sub(MAX_INJECTION_PARSE_BYTES);
+        let parse_end = (position + MAX_INJECTION_PARSE_BYTES).min(buffer.len());
+        let source = buffer.slice_bytes(parse_start..parse_end);
+        let tree = parser.parse(&source, None)?;
+        let cursor_offset = position - parse_start;
+
+        let content_idx = query.capture_index_for_name("injection.content")?;
+        let language_idx = query.capture_index_for_name("injection.language");
+
+        let mut query_cursor = QueryCursor::new();
+        let mut matches = query_cursor.matches(query, tree.root_node(), source.as_slice());
+        let mut found = None;
+        while let Some(m) = matches.next() {
+            let Some(content) = m.captures.iter().find(|c| c.index == content_idx) else {
+                continue;
+            };
+            let node = content.node;
+            if cursor_offset < node.start_byte() || cursor_offset > node.end_byte() {
+                continue;
+            }
+
+            // The language comes from a captured node (a code fence's info
+            // string) or a `#set! injection.language` property
+            let name = language_idx
+                .and_then(|idx| m.captures.iter().find(|c| c.index == idx))
+                .and_then(|c| c.node.utf8_text(&source).ok())
+                .or_else(|| {
+                    query
+                        .property_settings(m.pattern_index)
+                        .iter()
+                        .find(|p| &*p.key == "injection.language")
+                        .and_then(|p| p.value.as_deref())
+                });
+            if let Some(injected) = name.and_then(Language::from_injection_name) {
+                if injected != *language {
+                    found = Some((injected, parse_start + node.start_byte()));
+                }
+            }
+        }
+        found
+    }
+
+    /// Language to compute indentation with at `position`, and the start of
+    /// the region to parse (0 unless inside an injected region).
+    ///
+    /// Injected languages without an indents query keep the host language.
+    fn indent_language_at(
+        &mut self,
+        buffer: &Buffer,
+        position: usize,
+        language: &Language,
+    ) -> (Language, usize) {
+        match self.injection_at(buffer, position, language) {
+            Some((injected, region_start)) if self.get_config(&injected).is_some() => {
+                tracing::debug!("Using injected language {} for indent", injected);
+                (injected, region_start)
+            }
+            _ => (*language, 0),
+        }
+    }
+
     /// Calculate indent for a new line at the given position
     ///
     /// Returns the number of spaces to indent, or None if auto-indent should be disabled
@@ -193,9 +334,18 @@ impl IndentCalculator {
         language: &Language,
         tab_size: usize,
     ) -> Option<usize> {
+        let (language, region_start) = self.indent_language_at(buffer, position, language);
+
+        // Languages without an indents query use the heuristic rules
+        if self.get_config(&language).is_none() {
+            if let Some(indent) = self.calculate_indent_heuristic(buffer, position, tab_size) {
+                return Some(indent);
+            }
+        }
+
         // Try tree-sitter-based indent
         if let Some(indent) =
-            self.calculate_indent_tree_sitter(buffer, position, language, tab_size)
+            self.calculate_indent_tree_sitter(buffer, position, &language, region_start, tab_size)
         {
             return Some(indent);
         }
@@ -226,6 +376,208 @@ impl IndentCalculator {
         Self::get_current_line_indent(buffer, position, tab_size)
     }
 
+    /// Whether the buffer's language has a decrease pattern, so typed lines
+    /// may need to be dedented
+    pub fn has_decrease_pattern(&self) -> bool {
+        self.rules
+            .as_ref()
+            .is_some_and(IndentRules::has_decrease_pattern)
+    }
+
+    /// Calculate indent with the language's heuristic rules, or None if the
+    /// buffer has no configured language
+    pub fn calculate_indent_heuristic(
+        &self,
+        buffer: &Buffer,
+        position: usize,
+        tab_size: usize,
+    ) -> Option<usize> {
+        let rules = self.rules.as_ref()?;
+        let before = Self::text_before(buffer, position);
+        Some(rules.indent_for_new_line(&before, tab_size))
+    }
+
+    /// The indent `new_line` should move to when typing turns the line
+    /// starting at `line_start` from `old_line` into `new_line`: one level
+    /// less if it now matches the language's decrease pattern (`fi`,
+    /// `\end{...}`)
+    pub fn calculate_decreased_indent(
+        &self,
+        buffer: &Buffer,
+        line_start: usize,
+        old_line: &str,
+        new_line: &str,
+        tab_size: usize,
+    ) -> Option<usize> {
+        let rules = self.rules.as_ref()?;
+        if rules.decreases(old_line) {
+            return None;
+        }
+        let before = Self::text_before(buffer, line_start);
+        rules.decreased_indent(&before, new_line, tab_size)
+    }
+
+    /// Calculate the indent of a closing delimiter being typed in a language
+    /// without an indents query, or None if the buffer has no configured
+    /// language
+    pub fn calculate_dedent_heuristic(
+        &self,
+        buffer: &Buffer,
+        position: usize,
+        tab_size: usize,
+    ) -> Option<usize> {
+        self.rules.as_ref()?;
+        Self::calculate_dedent_pattern(buffer, position, tab_size)
+    }
+
+    /// Up to `MAX_PARSE_BYTES` of text before `position`, from a line start
+    fn text_before(buffer: &Buffer, position: usize) -> String {
+        let start = position.saturating_sub(MAX_PARSE_BYTES);
+        let bytes = buffer.slice_bytes(start..position);
+        let bytes = match bytes.iter().position(|&b| b == b'\n') {
+            Some(newline) if start > 0 => &bytes[newline + 1..],
+            _ => &bytes[..],
+        };
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+
+    /// Recompute the indent of `lines`, as if each were typed after the
+    /// ones before it, following `before` (text from a line start that is
+    /// empty or ends with a line break). Returns the re-indented lines.
+    ///
+    /// A line gets the indent Enter gives at the end of the line before it,
+    /// then moves back if it starts with a closing delimiter or matches the
+    /// language's decrease pattern. Blank lines are left as they are, and
+    /// the ` * ` lines of a block comment stay lined up under its `/*`.
+    pub fn reindent_lines(
+        &mut self,
+        before: &str,
+        lines: &[&str],
+        language: Option<&Language>,
+        tab_size: usize,
+        use_tabs: bool,
+    ) -> Vec<String> {
+        let context = before
+            .strip_suffix('\n')
+            .map(|text| text.strip_suffix('\r').unwrap_or(text));
+        let mut scratch = Buffer::from_str(context.unwrap_or(""), 0, Arc::new(NoopFileSystem));
+        // Whether there's a line before, to press Enter at the end of
+        let mut started = context.is_some();
+        // The content and indent of the last non-blank line
+        let split = |line: &str| {
+            let content = line.trim_start_matches([' ', '\t']);
+            let indent = line[..line.len() - content.len()]
+                .chars()
+                .map(|c| if c == '\t' { tab_size } else { 1 })
+                .sum::<usize>();
+            (content.to_string(), indent)
+        };
+        let mut previous = context
+            .and_then(|text| text.rsplit('\n').next())
+            .map(&split);
+
+        let mut reindented = Vec::with_capacity(lines.len());
+        for line in lines {
+            let end_of_previous = scratch.len();
+            if started {
+                scratch.insert(end_of_previous, "\n");
+            }
+            let line_start = scratch.len();
+            let content = line.trim_start_matches([' ', '\t']);
+            if content.is_empty() {
+                scratch.insert(line_start, line);
+                reindented.push(line.to_string());
+                continue;
+            }
+
+            let continues_comment = content.starts_with('*')
+                && previous.as_ref().is_some_and(|(previous, _)| {
+                    (previous.starts_with("/*") || previous.starts_with('*'))
+                        && !previous.ends_with("*/")
+                });
+            let indent = if !started {
+                Some(0)
+            } else if continues_comment {
+                previous
+                    .as_ref()
+                    .map(|(previous, indent)| indent + usize::from(previous.starts_with("/*")))
+            } else {
+                self.reindented_indent(
+                    &mut scratch,
+                    end_of_previous,
+                    content,
+                    language,
+                    tab_size,
+                    use_tabs,
+                )
+            };
+            let new_line = match indent {
+                Some(indent) => format!(
+                    "{}{}",
+                    indent_to_string(indent, use_tabs, tab_size),
+                    content
+                ),
+                None => line.to_string(),
+            };
+            scratch.insert(line_start, &new_line);
+            previous = Some(split(&new_line));
+            started = true;
+            reindented.push(new_line);
+        }
+        reindented
+    }
+
+    /// The indent of a line with `content`, starting at the end of `scratch`
+    /// after a line ending at `end_of_previous`
+    fn reindented_indent(
+        &mut self,
+        scratch: &mut Buffer,
+        end_of_previous: usize,
+        content: &str,
+        language: Option<&Language>,
+        tab_size: usize,
+        use_tabs: bool,
+    ) -> Option<usize> {
+        let mut indent = match language {
+            Some(language) => {
+                self.calculate_indent(scratch, end_of_previous, language, tab_size)?
+            }
+            None => self
+                .calculate_indent_heuristic(scratch, end_of_previous, tab_size)
+                .unwrap_or_else(|| {
+                    Self::calculate_indent_no_language(scratch, end_of_previous, tab_size)
+                }),
+        };
+
+        let line_start = scratch.len();
+        if let Some(delimiter) = content
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '}' | ')' | ']'))
+        {
+            scratch.insert(line_start, &indent_to_string(indent, use_tabs, tab_size));
+            let position = scratch.len();
+            let dedent = match language {
+                Some(language) => self.calculate_dedent_for_delimiter(
+                    scratch, position, delimiter, language, tab_size,
+                ),
+                None => Self::calculate_dedent_pattern(scratch, position, tab_size),
+            };
+            scratch.delete(line_start..position);
+            indent = dedent.unwrap_or(indent);
+        }
+
+        let line = format!(
+            "{}{}",
+            indent_to_string(indent, use_tabs, tab_size),
+            content
+        );
+        Some(
+            self.calculate_decreased_indent(scratch, line_start, "", &line, tab_size)
+                .unwrap_or(indent),
+        )
+    }
+
     /// Calculate the correct indent for a closing delimiter being typed
     ///
     /// # Strategy: Tree-sitter with Pattern Fallback
@@ -264,11 +616,17 @@ impl IndentCalculator {
         language: &Language,
         tab_size: usize,
     ) -> Option<usize> {
+        let (language, region_start) = self.indent_language_at(buffer, position, language);
+
+        if self.get_config(&language).is_none() {
+            return self.calculate_dedent_heuristic(buffer, position, tab_size);
+        }
+
         // Get parser and query for this language
-        let (parser, query) = self.get_config(language)?;
+        let (parser, query) = self.get_config(&language)?;
 
         // Extract context before cursor (for parsing)
-        let parse_start = position.saturating_sub(MAX_PARSE_BYTES);
+        let parse_start = position.saturating_sub(MAX_PARSE_BYTES).max(region_start);
         let parse_range = parse_start..position;
 
         if parse_range.is_empty() {
@@ -678,18 +1036,21 @@ impl IndentCalculator {
     }
 
     /// Calculate indent using tree-sitter queries
+    ///
+    /// Parsing starts no earlier than `region_start` (the start of an injected region).
     fn calculate_indent_tree_sitter(
         &mut self,
         buffer: &Buffer,
         position: usize,
         language: &Language,
+        region_start: usize,
         tab_size: usize,
     ) -> Option<usize> {
         // Get parser and query
         let (parser, query) = self.get_config(language)?;
 
         // Extract context before cursor (for parsing)
-        let parse_start = position.saturating_sub(MAX_PARSE_BYTES);
+        let parse_start = position.saturating_sub(MAX_PARSE_BYTES).max(region_start);
         let parse_range = parse_start..position;
 
         if parse_range.is_empty() {
@@ -1007,6 +1368,24 @@ impl Default for IndentCalculator {
     }
 }
 
+/// Convert a visual indent width to actual indent characters.
+/// When `use_tabs` is true, uses tab characters; otherwise uses spaces.
+/// The `indent_width` is the visual width in columns, and `tab_size` is
+/// how many columns a tab character represents.
+pub fn indent_to_string(indent_width: usize, use_tabs: bool, tab_size: usize) -> String {
+    if use_tabs && tab_size > 0 {
+        let num_tabs = indent_width / tab_size;
+        let remaining_spaces = indent_width % tab_size;
+        let mut result = "\t".repeat(num_tabs);
+        if remaining_spaces > 0 {
+            result.push_str(&" ".repeat(remaining_spaces));
+        }
+        result
+    } else {
+        " ".repeat(indent_width)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1090,7 +1469,7 @@ mod tests {
 
         // Tree-sitter should recognize this is a complete block
         // Pattern matching would see '}' and not indent, but tree-sitter context should work
-        let ts_result = calc.calculate_indent_tree_sitter(&buffer, position, &Language::Rust, 4);
+        let ts_result = calc.calculate_indent_tree_sitter(&buffer, position, &Language::Rust, 0, 4);
 
         // Tree-sitter should return Some (even if it's 0 indent)
         assert!(
@@ -1183,6 +1562,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_heuristic_rules_without_indents_query() {
+        // SQL has no indents query: its heuristic rules apply once set
+        let mut calc = IndentCalculator::new();
+        let buffer = Buffer::from_str_test("SELECT coalesce(a,");
+        let position = buffer.len();
+        assert_eq!(
+            calc.calculate_indent(&buffer, position, &Language::Sql, 4),
+            Some(0)
+        );
+
+        calc.set_rules(Some(&IndentPatterns::default()));
+        assert_eq!(
+            calc.calculate_indent(&buffer, position, &Language::Sql, 4),
+            Some(16),
+            "Should line up with the first argument"
+        );
+
+        let buffer = Buffer::from_str_test("BEGIN\n    SELECT 1;\n    ");
+        calc.set_rules(Some(&IndentPatterns {
+            decrease: Some(r"^\s*END\b".to_string()),
+            ..Default::default()
+        }));
+        assert_eq!(
+            calc.calculate_decreased_indent(&buffer, 20, "    EN", "    END", 4),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_reindent_lines() {
+        let mut calc = IndentCalculator::new();
+        let lines = [
+            "if (x) {", "foo(", "1,", "2", ")", "  }", "", "/**", "* doc", "*/",
+        ];
+        assert_eq!(
+            calc.reindent_lines("", &lines, None, 4, false),
+            vec![
+                "if (x) {",
+                "    foo(",
+                "        1,",
+                "        2",
+                "    )",
+                "}",
+                "",
+                "/**",
+                " * doc",
+                " */",
+            ]
+        );
+
+        // Lines after the first follow the text before them
+        assert_eq!(
+            calc.reindent_lines("\tif (a) {\r\n", &["b", "  }"], None, 4, true),
+            vec!["\t\tb", "\t}"]
+        );
+    }
+
     #[test]
     fn test_tree_sitter_enter_after_close_brace_returns_zero() {
         // Verify tree-sitter correctly handles Enter after closing brace
@@ -1199,7 +1636,7 @@ mod tests {
         );
 
         // Verify tree-sitter is being used (not just pattern fallback)
-        let ts_result = calc.calculate_indent_tree_sitter(&buffer, position, &Language::Rust, 4);
+        let ts_result = calc.calculate_indent_tree_sitter(&buffer, position, &Language::Rust, 0, 4);
         assert!(ts_result.is_some(), "Tree-sitter should handle this case");
     }
 
@@ -1311,4 +1748,71 @@ mod tests {
             "After empty line in function body (incomplete syntax), should indent to 4 spaces using reference line"
         );
     }
+
+    #[test]
+    fn test_injected_language_detection() {
+        let mut calc = IndentCalculator::new();
+
+        let markdown = Buffer::from_str_test("# Notes\n\n```py\ndef f():\n    pass\n");
+        let (language, region_start) = calc
+            .injection_at(&markdown, markdown.len(), &Language::Markdown)
+            .unwrap();
+        assert_eq!(language, Language::Python);
+        assert_eq!(region_start, "# Notes\n\n```py\n".len());
+
+        let prose = Buffer::from_str_test("# Notes\n\nSome text\n");
+        assert_eq!(
+            calc.injection_at(&prose, prose.len(), &Language::Markdown),
+            None
+        );
+
+        let script = Buffer::from_str_test("<html>\n<script>\nfunction f() {\n</script>");
+        let position = "<html>\n<script>\nfunction f() {\n".len();
+        let (language, region_start) = calc
+            .injection_at(&script, position, &Language::HTML)
+            .unwrap();
+        assert_eq!(language, Language::JavaScript);
+        assert_eq!(region_start, "<html>\n<script>".len());
+
+        let style = Buffer::from_str_test("<style>\nbody {\n</style>");
+        let position = "<style>\nbody {\n".len();
+        assert_eq!(
+            calc.injection_at(&style, position, &Language::HTML)
+                .map(|(language, _)| language),
+            Some(Language::CSS)
+        );
+
+        // The SQL string queries compile against their grammars
+        assert!(calc.get_injection_config(&Language::Python).is_some());
+        assert!(calc.get_injection_config(&Language::JavaScript).is_some());
+    }
+
+    #[test]
+    fn test_indent_inside_injected_regions() {
+        let mut calc = IndentCalculator::new();
+
+        // Rust code fence in Markdown
+        let markdown = Buffer::from_str_test("Example:\n\n```rust\nfn main() {\n    let x = 1;");
+        let indent = calc.calculate_indent(&markdown, markdown.len(), &Language::Markdown, 4);
+        assert_eq!(indent, Some(4));
+
+        // JavaScript in an HTML <script>, nested inside indented markup
+        let before = "<body>\n  <script>\n    function f() {";
+        let html = Buffer::from_str_test(&format!("{before}\n  </script>\n</body>"));
+        let indent = calc.calculate_indent(&html, before.len(), &Language::HTML, 2);
+        assert_eq!(indent, Some(6));
+
+        // Closing brace inside the fence dedents relative to the Rust code
+        let markdown = Buffer::from_str_test(
+            "Example:\n\n```rust\nfn main() {\n    if x {\n        y();\n        ",
+        );
+        let dedent = calc.calculate_dedent_for_delimiter(
+            &markdown,
+            markdown.len(),
+            '}',
+            &Language::Markdown,
+            4,
+        );
+        assert_eq!(dedent, Some(4));
+    }
 }
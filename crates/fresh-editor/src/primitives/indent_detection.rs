@@ -0,0 +1,109 @@
+//! Detecting the indentation style of a file from its contents
+//!
+//! Lines are sampled from the start of the file. Tabs are used if more lines
+//! start with a tab than with spaces. For spaces, the width is the change in
+//! indentation seen most often between consecutive lines: a file indented by
+//! four has mostly steps of 4, with the odd 8 where two blocks end at once.
+//! Lines indented by a single space (block comment continuations, alignment)
+//! aren't counted.
+
+/// Lines sampled from the start of a file
+const MAX_SAMPLED_LINES: usize = 10_000;
+
+/// The widths that can be detected for space indentation
+const WIDTHS: std::ops::RangeInclusive<usize> = 2..=8;
+
+/// How a file is indented
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indentation {
+    Tabs,
+    Spaces(usize),
+}
+
+/// Detect the indentation of `text`, or None if too few lines are indented
+/// to tell
+pub fn detect_indentation(text: &str) -> Option<Indentation> {
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut steps = [0usize; 9];
+    let mut previous = 0;
+
+    for line in text.lines().take(MAX_SAMPLED_LINES) {
+        let content = line.trim_start_matches([' ', '\t']);
+        if content.is_empty() {
+            continue;
+        }
+        let indent = &line[..line.len() - content.len()];
+        if indent.starts_with('\t') {
+            tab_lines += 1;
+            continue;
+        }
+        if indent.contains('\t') {
+            continue;
+        }
+        let spaces = indent.len();
+        if spaces == 1 {
+            continue;
+        }
+        if spaces > 0 {
+            space_lines += 1;
+        }
+        let step = spaces.abs_diff(previous);
+        if WIDTHS.contains(&step) {
+            steps[step] += 1;
+        }
+        previous = spaces;
+    }
+
+    if tab_lines == 0 && space_lines == 0 {
+        return None;
+    }
+    if tab_lines > space_lines {
+        return Some(Indentation::Tabs);
+    }
+    // The most frequent step, the narrowest if several are as frequent
+    let width = WIDTHS
+        .rev()
+        .max_by_key(|&width| steps[width])
+        .filter(|&width| steps[width] > 0)?;
+    Some(Indentation::Spaces(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spaces() {
+        let text = "fn main() {\n    if x {\n        y();\n    }\n}\n";
+        assert_eq!(detect_indentation(text), Some(Indentation::Spaces(4)));
+        let text = "a:\n  b:\n    c: 1\n  d: 2\ne: 3\n";
+        assert_eq!(detect_indentation(text), Some(Indentation::Spaces(2)));
+    }
+
+    #[test]
+    fn test_tabs() {
+        let text = "all:\n\tcc -o a a.c\n\tstrip a\n\nclean:\n\trm a\n";
+        assert_eq!(detect_indentation(text), Some(Indentation::Tabs));
+    }
+
+    #[test]
+    fn test_more_frequent_step_wins() {
+        // Closing two blocks at once is a step of 8, but 4 is more frequent
+        let text = "a {\n    b {\n        c\n        d\n    }\n    e {\n        f\n}\n";
+        assert_eq!(detect_indentation(text), Some(Indentation::Spaces(4)));
+    }
+
+    #[test]
+    fn test_comment_continuations_are_ignored() {
+        let text = "/**\n * Docs\n */\nint x;\nvoid f() {\n  g();\n}\n";
+        assert_eq!(detect_indentation(text), Some(Indentation::Spaces(2)));
+    }
+
+    #[test]
+    fn test_nothing_to_detect() {
+        assert_eq!(detect_indentation(""), None);
+        assert_eq!(detect_indentation("one\ntwo\n\nthree\n"), None);
+        assert_eq!(detect_indentation("/*\n * a\n */\n"), None);
+    }
+}
@@ -0,0 +1,301 @@
+//! Heuristic indentation for languages without a tree-sitter indent query
+//!
+//! A language can give regular expressions for lines after which the next
+//! line is indented one more level (`increase_indent_pattern`), lines
+//! indented one level less than the line before them, such as `end` or `fi`
+//! (`decrease_indent_pattern`, applied as the line is typed), and lines that
+//! continue on the next one, such as a trailing `\` (`continuation_pattern`).
+//!
+//! Brackets are followed the same way as by the pattern fallback of the
+//! tree-sitter path: a line ending in an open bracket or `:` is followed by
+//! an indented line. In addition, arguments continued after an unclosed
+//! bracket are lined up with the first one (a hanging indent), continuation
+//! lines are indented one level from the line starting the statement, and
+//! the line after a statement spanning several lines returns to its indent.
+//!
+//! ```text
+//! result = compute(first,
+//!                  second)     <- hanging indent, lined up with `first`
+//! value = 1 + \
+//!     2 + \                    <- continuation, one level in
+//!     3
+//! next = 4                     <- back to the statement's indent
+//! ```
+
+use regex::Regex;
+
+use crate::config::IndentPatterns;
+
+/// Compiled indent patterns of a language
+#[derive(Debug, Clone, Default)]
+pub struct IndentRules {
+    increase: Option<Regex>,
+    decrease: Option<Regex>,
+    continuation: Option<Regex>,
+}
+
+/// The brackets of a single line
+#[derive(Debug, Default)]
+struct LineBrackets {
+    /// Byte offsets of the opening brackets not closed on the line
+    unclosed: Vec<usize>,
+    /// Closing brackets of openings on earlier lines
+    closed_earlier: usize,
+}
+
+impl IndentRules {
+    /// Compile a language's patterns. Invalid ones are logged and ignored.
+    pub fn new(patterns: &IndentPatterns) -> Self {
+        let compile = |pattern: &Option<String>| {
+            let pattern = pattern.as_deref()?;
+            Regex::new(pattern)
+                .map_err(|e| tracing::warn!("Invalid indent pattern {:?}: {}", pattern, e))
+                .ok()
+        };
+        Self {
+            increase: compile(&patterns.increase),
+            decrease: compile(&patterns.decrease),
+            continuation: compile(&patterns.continuation),
+        }
+    }
+
+    /// Indent of a new line started at the end of `before`, the text
+    /// preceding it from the start of some line
+    pub fn indent_for_new_line(&self, before: &str, tab_size: usize) -> usize {
+        let lines: Vec<&str> = before
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
+        let Some(reference) = lines.iter().rposition(|line| !line.trim().is_empty()) else {
+            return 0;
+        };
+        let line = lines[reference];
+
+        if let Some(&open) = scan_brackets(line).unclosed.last() {
+            let after = &line[open + 1..];
+            if after.trim().is_empty() {
+                return indent_width(line, tab_size) + tab_size;
+            }
+            // Hanging indent: line up the next argument with the first
+            if after.trim_end().ends_with(',') {
+                let content = open + 1 + after.len() - after.trim_start().len();
+                return column(&line[..content], tab_size);
+            }
+            return indent_width(line, tab_size);
+        }
+
+        let start = self.statement_start(&lines, reference);
+        let start_indent = indent_width(lines[start], tab_size);
+        if self.increases(line) || self.continues(line) {
+            start_indent + tab_size
+        } else {
+            start_indent
+        }
+    }
+
+    /// Indent of `line`, typed after `before` (which ends with a newline or
+    /// is empty), if it matches the decrease pattern and is still at the
+    /// indent a new line would get
+    pub fn decreased_indent(&self, before: &str, line: &str, tab_size: usize) -> Option<usize> {
+        if !self.decreases(line) {
+            return None;
+        }
+        let expected = self.indent_for_new_line(before, tab_size);
+        (expected > 0 && indent_width(line, tab_size) == expected)
+            .then(|| expected.saturating_sub(tab_size))
+    }
+
+    pub fn has_decrease_pattern(&self) -> bool {
+        self.decrease.is_some()
+    }
+
+    /// Whether a line is indented one level less than the line before it
+    pub fn decreases(&self, line: &str) -> bool {
+        self.decrease.as_ref().is_some_and(|re| re.is_match(line))
+    }
+
+    /// Whether a line is followed by a more indented one
+    fn increases(&self, line: &str) -> bool {
+        line.trim_end().ends_with(':') || self.increase.as_ref().is_some_and(|re| re.is_match(line))
+    }
+
+    fn continues(&self, line: &str) -> bool {
+        self.continuation
+            .as_ref()
+            .is_some_and(|re| re.is_match(line))
+    }
+
+    /// The line starting the statement that `lines[index]` is part of,
+    /// following brackets opened on earlier lines and continued lines
+    fn statement_start(&self, lines: &[&str], index: usize) -> usize {
+        let mut start = index;
+        loop {
+            let mut needed = scan_brackets(lines[start]).closed_earlier;
+            let mut line = start;
+            while needed > 0 && line > 0 {
+                line -= 1;
+                let brackets = scan_brackets(lines[line]);
+                needed = needed.saturating_sub(brackets.unclosed.len()) + brackets.closed_earlier;
+            }
+            // Closing brackets without an opening in sight are ignored
+            if needed == 0 {
+                start = line;
+            }
+
+            match start.checked_sub(1) {
+                Some(previous) if self.continues(lines[previous]) => start = previous,
+                _ => return start,
+            }
+        }
+    }
+}
+
+/// Find the brackets of a line, skipping those in quoted strings
+fn scan_brackets(line: &str) -> LineBrackets {
+    let mut brackets = LineBrackets::default();
+    let mut chars = line.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => brackets.unclosed.push(i),
+            ')' | ']' | '}' => match brackets.unclosed.pop() {
+                Some(_) => {}
+                None => brackets.closed_earlier += 1,
+            },
+            // A quote only starts a string if it's closed on the line, so
+            // apostrophes in comments don't hide brackets
+            '"' | '\'' | '`' => {
+                if let Some(end) = string_end(&line[i + 1..], c) {
+                    let closing = i + 1 + end;
+                    while chars.next().is_some_and(|(j, _)| j < closing) {}
+                }
+            }
+            _ => {}
+        }
+    }
+    brackets
+}
+
+/// Byte offset of the quote closing a string, in the text after its opening
+fn string_end(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            c if c == quote => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Width of a line's leading whitespace, counting a tab as `tab_size`
+fn indent_width(line: &str, tab_size: usize) -> usize {
+    line.chars()
+        .map_while(|c| match c {
+            ' ' => Some(1),
+            '\t' => Some(tab_size),
+            _ => None,
+        })
+        .sum()
+}
+
+/// Display column at the end of `text`, with tabs `tab_size` wide
+fn column(text: &str, tab_size: usize) -> usize {
+    text.chars()
+        .map(|c| if c == '\t' { tab_size } else { 1 })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(
+        increase: Option<&str>,
+        decrease: Option<&str>,
+        continuation: Option<&str>,
+    ) -> IndentRules {
+        IndentRules::new(&IndentPatterns {
+            increase: increase.map(str::to_string),
+            decrease: decrease.map(str::to_string),
+            continuation: continuation.map(str::to_string),
+        })
+    }
+
+    fn shell() -> IndentRules {
+        rules(
+            Some(r"\b(then|do|else)\s*$"),
+            Some(r"^\s*(fi|done|else)\b"),
+            Some(r"\\$"),
+        )
+    }
+
+    #[test]
+    fn test_brackets_and_colon() {
+        let rules = IndentRules::default();
+        assert_eq!(rules.indent_for_new_line("", 4), 0);
+        assert_eq!(rules.indent_for_new_line("fn main() {", 4), 4);
+        assert_eq!(rules.indent_for_new_line("  key:", 2), 4);
+        assert_eq!(rules.indent_for_new_line("    x = 1\n\n", 4), 4);
+        // Brackets in strings don't count
+        assert_eq!(rules.indent_for_new_line("print(\"(\")", 4), 0);
+        assert_eq!(rules.indent_for_new_line("# don't {", 4), 4);
+        assert_eq!(rules.indent_for_new_line("  see (below", 4), 2);
+    }
+
+    #[test]
+    fn test_hanging_indent() {
+        let rules = IndentRules::default();
+        let before = "result = compute(first,";
+        assert_eq!(rules.indent_for_new_line(before, 4), 17);
+        let before = "result = compute(first,\n                 second,";
+        assert_eq!(rules.indent_for_new_line(before, 4), 17);
+        // After the closing bracket, back to the statement's indent
+        let before = "  result = compute(first,\n                   second)";
+        assert_eq!(rules.indent_for_new_line(before, 2), 2);
+        let before = "if check(a,\n         b):";
+        assert_eq!(rules.indent_for_new_line(before, 4), 4);
+    }
+
+    #[test]
+    fn test_increase_pattern() {
+        let rules = shell();
+        assert_eq!(rules.indent_for_new_line("if true; then", 4), 4);
+        assert_eq!(rules.indent_for_new_line("for x in a b; do", 2), 2);
+        assert_eq!(rules.indent_for_new_line("echo done", 4), 0);
+    }
+
+    #[test]
+    fn test_continuation_lines() {
+        let rules = shell();
+        let before = "  value=1 \\";
+        assert_eq!(rules.indent_for_new_line(before, 4), 6);
+        let before = "  value=1 \\\n      2 \\";
+        assert_eq!(rules.indent_for_new_line(before, 4), 6);
+        let before = "  value=1 \\\n      2 \\\n      3";
+        assert_eq!(rules.indent_for_new_line(before, 4), 2);
+    }
+
+    #[test]
+    fn test_decreased_indent() {
+        let rules = shell();
+        let before = "if true; then\n    echo\n";
+        assert_eq!(rules.decreased_indent(before, "    fi", 4), Some(0));
+        assert_eq!(rules.decreased_indent(before, "    else", 4), Some(0));
+        assert_eq!(rules.decreased_indent(before, "    echo", 4), None);
+        // Already moved by hand
+        assert_eq!(rules.decreased_indent(before, "  fi", 4), None);
+        assert_eq!(
+            rules.indent_for_new_line("if true; then\n    echo\nelse", 4),
+            4
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_ignored() {
+        let rules = rules(Some("("), None, None);
+        assert_eq!(rules.indent_for_new_line("if (", 4), 4);
+        assert_eq!(rules.indent_for_new_line("x", 4), 0);
+    }
+}
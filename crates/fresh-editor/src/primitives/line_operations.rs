@@ -0,0 +1,153 @@
+//! Line-wise transformations: sorting, removing duplicates, reversing and
+//! shuffling lines
+//!
+//! Lines are borrowed from the input text and only the result is allocated,
+//! so large ranges cost one copy of the text rather than one per line.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// A transformation of a range of lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineOperation {
+    SortAscending,
+    SortDescending,
+    /// Sort by the number each line starts with; lines without one go first
+    SortNumeric,
+    /// Remove repeated lines, keeping the first occurrence of each
+    Unique,
+    Reverse,
+    Shuffle,
+}
+
+/// Apply `operation` to the lines of `text`, joining them with `line_ending`
+///
+/// A trailing line ending stays at the end of the result. `seed` picks the
+/// order for [`LineOperation::Shuffle`] and is ignored otherwise. Text with
+/// fewer than two lines is returned unchanged.
+pub fn transform_lines(
+    text: &str,
+    operation: LineOperation,
+    line_ending: &str,
+    seed: u64,
+) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    if lines.len() < 2 {
+        return text.to_string();
+    }
+
+    match operation {
+        LineOperation::SortAscending => lines.sort(),
+        LineOperation::SortDescending => lines.sort_by(|a, b| b.cmp(a)),
+        LineOperation::SortNumeric => {
+            let mut keyed: Vec<(Option<f64>, &str)> = lines
+                .iter()
+                .map(|line| (leading_number(line), *line))
+                .collect();
+            keyed.sort_by(|(a, _), (b, _)| match (a, b) {
+                (Some(a), Some(b)) => a.total_cmp(b),
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+            lines = keyed.into_iter().map(|(_, line)| line).collect();
+        }
+        LineOperation::Unique => {
+            let mut seen = HashSet::new();
+            lines.retain(|line| seen.insert(*line));
+        }
+        LineOperation::Reverse => lines.reverse(),
+        LineOperation::Shuffle => shuffle(&mut lines, seed),
+    }
+
+    let mut result = lines.join(line_ending);
+    if text.ends_with('\n') {
+        result.push_str(line_ending);
+    }
+    result
+}
+
+/// The number at the start of a line (after indentation), as in `sort -n`
+fn leading_number(line: &str) -> Option<f64> {
+    let trimmed = line.trim_start();
+    let bytes = trimmed.as_bytes();
+    let mut end = usize::from(matches!(bytes.first(), Some(b'-' | b'+')));
+    let digits_start = end;
+    while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+        end += 1;
+    }
+    if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end += 1;
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+    }
+    if end == digits_start {
+        return None;
+    }
+    trimmed[..end].parse().ok()
+}
+
+/// Fisher-Yates shuffle driven by a splitmix64 generator
+fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    for i in (1..items.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        items.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(text: &str, operation: LineOperation) -> String {
+        transform_lines(text, operation, "\n", 0)
+    }
+
+    #[test]
+    fn test_sorting() {
+        assert_eq!(
+            apply("b\nc\na\n", LineOperation::SortAscending),
+            "a\nb\nc\n"
+        );
+        assert_eq!(apply("b\nc\na", LineOperation::SortDescending), "c\nb\na");
+        assert_eq!(
+            apply("10 x\n9 y\nnone\n-2\n  3.5 z\n", LineOperation::SortNumeric),
+            "none\n-2\n  3.5 z\n9 y\n10 x\n"
+        );
+        // CRLF text is rejoined with the buffer's line ending
+        assert_eq!(
+            transform_lines("b\r\na\r\n", LineOperation::SortAscending, "\r\n", 0),
+            "a\r\nb\r\n"
+        );
+        assert_eq!(apply("only\n", LineOperation::Reverse), "only\n");
+    }
+
+    #[test]
+    fn test_unique_and_reverse() {
+        assert_eq!(apply("a\nb\na\nc\nb\n", LineOperation::Unique), "a\nb\nc\n");
+        assert_eq!(apply("1\n2\n3", LineOperation::Reverse), "3\n2\n1");
+    }
+
+    #[test]
+    fn test_shuffle_keeps_lines() {
+        let text = (0..50).map(|i| format!("{i}\n")).collect::<String>();
+        let shuffled = transform_lines(&text, LineOperation::Shuffle, "\n", 42);
+        assert_ne!(shuffled, text);
+        let mut lines: Vec<&str> = shuffled.lines().collect();
+        lines.sort_by_key(|line| line.parse::<u32>().unwrap());
+        assert_eq!(lines.join("\n") + "\n", text);
+        assert_eq!(
+            transform_lines(&text, LineOperation::Shuffle, "\n", 42),
+            shuffled
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! Detection of URLs and file paths in a line of text
+//!
+//! Used to underline links and open them with Ctrl+Click or Open Link at
+//! Cursor. Detection is purely textual: whether a path exists is up to
+//! the caller.
+
+use std::ops::Range;
+
+/// URL schemes recognized as links
+const URL_SCHEMES: &[&str] = &["https://", "http://", "file://"];
+
+/// What a link points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// A URL to open in the browser
+    Url(String),
+    /// A file path, optionally followed by `:line` or `:line:column` (1-based)
+    Path {
+        path: String,
+        line: Option<usize>,
+        column: Option<usize>,
+    },
+}
+
+/// A link found in a line of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Byte range of the link within the line
+    pub range: Range<usize>,
+    pub target: LinkTarget,
+}
+
+/// Find the link covering byte `offset` of `line`, if any
+///
+/// A link is a whitespace-delimited word (quotes and brackets also end it)
+/// that is either a URL or looks like a path: it contains a `/`, as in
+/// `src/main.rs`, `./build.sh` or `~/notes.md`. Trailing punctuation such as
+/// a sentence's final period is not part of the link.
+pub fn link_at(line: &str, offset: usize) -> Option<Link> {
+    if offset >= line.len() || !line.is_char_boundary(offset) {
+        return None;
+    }
+    if line[offset..].starts_with(is_delimiter) {
+        return None;
+    }
+
+    let start = line[..offset]
+        .rfind(is_delimiter)
+        .map(|i| i + line[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    let end = line[offset..]
+        .find(is_delimiter)
+        .map(|i| offset + i)
+        .unwrap_or(line.len());
+    link_in_word(line, start..end).filter(|link| link.range.contains(&offset))
+}
+
+/// Find all the links in `line`, in order (see [`link_at`])
+pub fn find_links(line: &str) -> Vec<Link> {
+    let mut links = Vec::new();
+    let mut word_start = None;
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        match (word_start, is_delimiter(c)) {
+            (None, false) => word_start = Some(i),
+            (Some(start), true) => {
+                links.extend(link_in_word(line, start..i));
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    links
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace()
+        || matches!(
+            c,
+            '"' | '\'' | '`' | '<' | '>' | '(' | ')' | '[' | ']' | '{' | '}'
+        )
+}
+
+/// The link in the word at `word` of `line`, if any
+fn link_in_word(line: &str, word: Range<usize>) -> Option<Link> {
+    let start = word.start;
+    let word = line[word].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    let end = start + word.len();
+    if word.is_empty() {
+        return None;
+    }
+
+    if let Some(url_start) = URL_SCHEMES.iter().filter_map(|s| word.find(s)).min() {
+        let url = &word[url_start..];
+        if URL_SCHEMES.contains(&url) {
+            return None;
+        }
+        return Some(Link {
+            range: start + url_start..end,
+            target: LinkTarget::Url(url.to_string()),
+        });
+    }
+
+    let (path, line_number, column) = split_position_suffix(word);
+    if !path.contains('/') || !path.chars().any(|c| c.is_alphanumeric()) || path.contains("//") {
+        return None;
+    }
+    Some(Link {
+        range: start..end,
+        target: LinkTarget::Path {
+            path: path.to_string(),
+            line: line_number,
+            column,
+        },
+    })
+}
+
+/// Split `path:line:column` into its parts, as printed by compilers and grep
+fn split_position_suffix(word: &str) -> (&str, Option<usize>, Option<usize>) {
+    let parse = |s: &str| s.parse::<usize>().ok().filter(|n| *n > 0);
+    let mut parts = word.rsplitn(3, ':');
+    let last = parts.next().unwrap_or_default();
+    let middle = parts.next();
+    let first = parts.next();
+
+    match (first, middle.and_then(parse), parse(last)) {
+        (Some(path), Some(line), Some(column)) => (path, Some(line), Some(column)),
+        _ => match (word.rsplit_once(':'), parse(last)) {
+            (Some((path, _)), Some(line)) => (path, Some(line), None),
+            _ => (word, None, None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(path: &str, line: Option<usize>, column: Option<usize>) -> LinkTarget {
+        LinkTarget::Path {
+            path: path.to_string(),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn test_urls() {
+        let line = "see https://example.com/docs?q=1. for more";
+        let link = link_at(line, 10).unwrap();
+        assert_eq!(&line[link.range.clone()], "https://example.com/docs?q=1");
+        assert_eq!(
+            link.target,
+            LinkTarget::Url("https://example.com/docs?q=1".to_string())
+        );
+        assert_eq!(link_at(line, 2), None);
+        assert_eq!(link_at(line, 3), None);
+
+        // Only the URL part of a word is a link
+        let line = "href=\"http://a.b/c\" url=file:///tmp/x";
+        assert_eq!(link_at(line, 8).unwrap().range, 6..18);
+        assert_eq!(link_at(line, 21), None);
+        assert_eq!(
+            link_at(line, 30).unwrap().target,
+            LinkTarget::Url("file:///tmp/x".to_string())
+        );
+        assert_eq!(link_at("https://", 2), None);
+    }
+
+    #[test]
+    fn test_paths() {
+        let line = "error at src/main.rs:12:5: oops";
+        let link = link_at(line, 12).unwrap();
+        assert_eq!(&line[link.range.clone()], "src/main.rs:12:5");
+        assert_eq!(link.target, path("src/main.rs", Some(12), Some(5)));
+
+        assert_eq!(
+            link_at("(./build.sh)", 3).unwrap().target,
+            path("./build.sh", None, None)
+        );
+        assert_eq!(
+            link_at("~/notes.md:3", 0).unwrap().target,
+            path("~/notes.md", Some(3), None)
+        );
+        assert_eq!(
+            link_at("lib/a.rs:x", 0).unwrap().target,
+            path("lib/a.rs:x", None, None)
+        );
+    }
+
+    #[test]
+    fn test_not_links() {
+        assert_eq!(link_at("plain words", 3), None);
+        assert_eq!(link_at("// comment", 0), None);
+        assert_eq!(link_at("a / b", 2), None);
+        assert_eq!(link_at("x", 5), None);
+    }
+
+    #[test]
+    fn test_find_links() {
+        let line = "src/a.rs:3:1: see https://x.io/y, and (lib/b.rs). done";
+        let links: Vec<&str> = find_links(line)
+            .into_iter()
+            .map(|link| &line[link.range])
+            .collect();
+        assert_eq!(links, vec!["src/a.rs:3:1", "https://x.io/y", "lib/b.rs"]);
+        assert!(find_links("no links here / at all").is_empty());
+        assert!(find_links("").is_empty());
+    }
+}
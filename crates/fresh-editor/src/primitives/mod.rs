@@ -15,12 +15,23 @@
 //! | Reference highlighting | `reference_highlight_text` | `reference_highlighter` |
 
 // Pure modules - available for both runtime and WASM
+pub mod bidi;
+pub mod color_literals;
+pub mod ctags;
 pub mod display_width;
 pub mod grapheme;
+pub mod identifier_case;
+pub mod indent_detection;
+pub mod indent_rules;
+pub mod line_operations;
 pub mod line_wrapping;
+pub mod link_detection;
+pub mod modeline;
+pub mod number_literals;
 pub mod path_utils;
 pub mod snippet;
 pub mod text_property;
+pub mod word_completion;
 
 // Modules depending on model::buffer - available for both runtime and WASM
 pub mod line_iterator;
@@ -57,10 +68,16 @@ pub mod textmate_engine;
 // Runtime-only modules (depend on tree-sitter)
 // These provide enhanced features using AST analysis
 #[cfg(feature = "runtime")]
+pub mod extract;
+#[cfg(feature = "runtime")]
 pub mod highlight_engine;
 #[cfg(feature = "runtime")]
 pub mod highlighter;
 #[cfg(feature = "runtime")]
 pub mod indent;
 #[cfg(feature = "runtime")]
+pub mod outline;
+#[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod text_objects;
@@ -0,0 +1,246 @@
+//! Reading per-file settings from modelines
+//!
+//! Files can carry their own settings in a comment, so a script without an
+//! extension or a file indented unlike the rest of the project is still
+//! edited as it should be. Two conventions are read:
+//!
+//! - Emacs, on the first line (or the second after a shebang):
+//!   `-*- mode: python; tab-width: 2; indent-tabs-mode: nil -*-`, or just
+//!   `-*- python -*-`
+//! - Vim, on one of the first or last five lines:
+//!   `vim: set ft=python ts=2 et :` or `vi:ts=8:noet:nowrap`
+//!
+//! Only the language, tab width, tabs or spaces and line wrapping are read;
+//! other options are ignored. A language is returned as written and
+//! resolved like a name given to Set Language, by grammar name or file
+//! extension.
+
+/// Lines at each end of a file searched for a vim modeline
+const VIM_MODELINE_LINES: usize = 5;
+
+/// Largest tab width accepted from a modeline
+const MAX_TAB_SIZE: usize = 16;
+
+/// The settings found in a file's modelines
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Modeline {
+    pub language: Option<String>,
+    pub tab_size: Option<usize>,
+    pub use_tabs: Option<bool>,
+    pub line_wrap: Option<bool>,
+}
+
+impl Modeline {
+    /// Read the modelines in `head`, the start of a file, and `tail`, its
+    /// end (which may overlap `head` for short files). A vim modeline's
+    /// settings take precedence over an Emacs one's.
+    pub fn parse(head: &str, tail: &str) -> Self {
+        let mut modeline = Self::default();
+
+        let mut first_lines = head.lines();
+        let emacs = match first_lines.next() {
+            Some(line) if line.starts_with("#!") => first_lines.next(),
+            line => line,
+        };
+        if let Some(variables) = emacs.and_then(emacs_variables) {
+            modeline.read_emacs(variables);
+        }
+
+        let last_lines: Vec<&str> = tail.lines().rev().take(VIM_MODELINE_LINES).collect();
+        for options in head
+            .lines()
+            .take(VIM_MODELINE_LINES)
+            .chain(last_lines.into_iter().rev())
+            .filter_map(vim_options)
+        {
+            modeline.read_vim(options);
+        }
+        modeline
+    }
+
+    /// Whether no setting was found
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Read the variables between the `-*-` markers
+    fn read_emacs(&mut self, variables: &str) {
+        let variables = variables.trim();
+        if !variables.contains(':') {
+            // `-*- python -*-` names just the mode
+            if !variables.is_empty() {
+                self.language = Some(variables.to_string());
+            }
+            return;
+        }
+        for variable in variables.split(';') {
+            let Some((name, value)) = variable.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "mode" if !value.is_empty() => self.language = Some(value.to_string()),
+                "tab-width" => self.tab_size = tab_size(value).or(self.tab_size),
+                "indent-tabs-mode" => self.use_tabs = Some(value != "nil"),
+                "truncate-lines" => self.line_wrap = Some(value == "nil"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Read the options of a vim modeline. The shift width is the indent
+    /// width, so it wins over the tab stop.
+    fn read_vim(&mut self, options: &str) {
+        let mut shift_width = None;
+        for option in options
+            .split(|c: char| c == ':' || c.is_whitespace())
+            .filter(|option| !option.is_empty())
+        {
+            match option.split_once('=') {
+                Some(("ft" | "filetype" | "syn" | "syntax", value)) if !value.is_empty() => {
+                    self.language = Some(value.to_string())
+                }
+                Some(("ts" | "tabstop", value)) => {
+                    self.tab_size = tab_size(value).or(self.tab_size)
+                }
+                Some(("sw" | "shiftwidth", value)) => shift_width = tab_size(value),
+                Some(_) => {}
+                None => match option {
+                    "et" | "expandtab" => self.use_tabs = Some(false),
+                    "noet" | "noexpandtab" => self.use_tabs = Some(true),
+                    "wrap" => self.line_wrap = Some(true),
+                    "nowrap" => self.line_wrap = Some(false),
+                    _ => {}
+                },
+            }
+        }
+        if shift_width.is_some() {
+            self.tab_size = shift_width;
+        }
+    }
+}
+
+/// A tab width given in a modeline, if it's a sensible one
+fn tab_size(value: &str) -> Option<usize> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|size| (1..=MAX_TAB_SIZE).contains(size))
+}
+
+/// The variables of an Emacs `-*- ... -*-` line
+fn emacs_variables(line: &str) -> Option<&str> {
+    let (_, rest) = line.split_once("-*-")?;
+    let (variables, _) = rest.split_once("-*-")?;
+    Some(variables)
+}
+
+/// The options of a vim modeline: `vim:`, `vi:` or `ex:` after whitespace
+/// (or at the start of the line), then options separated by spaces or
+/// colons. In the `set` form they end at the next colon, so the end of a
+/// comment after it isn't read.
+fn vim_options(line: &str) -> Option<&str> {
+    let options = ["vim:", "vi:", "ex:"].iter().find_map(|marker| {
+        line.match_indices(marker).find_map(|(index, _)| {
+            let before = &line[..index];
+            (before.is_empty() || before.ends_with(char::is_whitespace))
+                .then(|| &line[index + marker.len()..])
+        })
+    })?;
+    let options = options.trim_start();
+    match options
+        .strip_prefix("set ")
+        .or_else(|| options.strip_prefix("se "))
+    {
+        Some(set) => Some(set.split_once(':').map_or(set, |(set, _)| set)),
+        None => Some(options),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Modeline {
+        Modeline::parse(text, text)
+    }
+
+    #[test]
+    fn test_emacs_modeline() {
+        assert_eq!(
+            parse("# -*- mode: python -*-\nx = 1\n").language.as_deref(),
+            Some("python")
+        );
+        assert_eq!(
+            parse("/* -*- C++ -*- */\n").language.as_deref(),
+            Some("C++")
+        );
+        assert_eq!(
+            parse("#!/bin/sh\n# -*- coding: utf-8; Mode: sh; tab-width: 2; indent-tabs-mode: nil -*-\n"),
+            Modeline {
+                language: Some("sh".to_string()),
+                tab_size: Some(2),
+                use_tabs: Some(false),
+                line_wrap: None,
+            }
+        );
+        assert_eq!(
+            parse("; -*- truncate-lines: t -*-\n").line_wrap,
+            Some(false)
+        );
+        // Only the first line, or the second after a shebang
+        assert!(parse("x = 1\n# -*- mode: python -*-\n").is_empty());
+        assert!(parse("# -*- coding: utf-8 -*-\n").is_empty());
+    }
+
+    #[test]
+    fn test_vim_modeline() {
+        assert_eq!(
+            parse("# vim: set ft=python ts=2 et :\n"),
+            Modeline {
+                language: Some("python".to_string()),
+                tab_size: Some(2),
+                use_tabs: Some(false),
+                line_wrap: None,
+            }
+        );
+        assert_eq!(
+            parse("// vi:ts=8:noet:nowrap\n"),
+            Modeline {
+                language: None,
+                tab_size: Some(8),
+                use_tabs: Some(true),
+                line_wrap: Some(false),
+            }
+        );
+        // The shift width is the indent width
+        assert_eq!(parse("# vim: ts=8 sw=4\n").tab_size, Some(4));
+        // A `set` modeline ends at the colon
+        assert_eq!(parse("/* vim: set ts=4 : wrap */\n").line_wrap, None);
+        assert_eq!(parse("/* vim: ts=99 */\n").tab_size, None);
+        // The marker must follow whitespace
+        assert!(parse("novim: ft=python\n").is_empty());
+    }
+
+    #[test]
+    fn test_vim_modeline_at_end() {
+        let middle: String = (0..20).map(|n| format!("line {}\n", n)).collect();
+        let text = format!("start\n{}# vim: ft=ruby\n", middle);
+        assert_eq!(parse(&text).language.as_deref(), Some("ruby"));
+
+        let text = format!("start\n# vim: ft=ruby\n{}{}", middle, middle);
+        assert_eq!(parse(&text).language.as_deref(), Some("ruby"));
+
+        let text = format!("start\n{}# vim: ft=ruby\n{}", middle, middle);
+        assert!(parse(&text).is_empty());
+    }
+
+    #[test]
+    fn test_vim_overrides_emacs() {
+        let text = "# -*- mode: sh; tab-width: 8 -*-\n# vim: ts=2\n";
+        let modeline = parse(text);
+        assert_eq!(modeline.language.as_deref(), Some("sh"));
+        assert_eq!(modeline.tab_size, Some(2));
+    }
+}
@@ -0,0 +1,224 @@
+//! Number literals in text, for incrementing them and generating sequences
+//!
+//! Recognizes decimal numbers (with a leading `-`), and hex, octal and binary
+//! numbers with a `0x`, `0o` or `0b` prefix. Incremented numbers keep their
+//! notation: prefix, zero padding and the case of hex digits.
+
+use std::ops::Range;
+
+/// How a number is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    radix: u32,
+    /// `0x`, `0X`, `0o`, `0b`... as written, empty for decimal
+    prefix: String,
+    /// Minimum number of digits, for zero-padded numbers like `007`
+    width: usize,
+    uppercase: bool,
+}
+
+impl NumberFormat {
+    /// Parse a whole number literal such as `42`, `-7`, `007` or `0xFF`
+    pub fn parse(literal: &str) -> Option<(i128, NumberFormat)> {
+        let (negative, unsigned) = match literal.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, literal),
+        };
+        let (radix, prefix_len) = match unsigned.as_bytes() {
+            [b'0', b'x' | b'X', ..] => (16, 2),
+            [b'0', b'o' | b'O', ..] => (8, 2),
+            [b'0', b'b' | b'B', ..] => (2, 2),
+            _ => (10, 0),
+        };
+        // Only decimal numbers have a sign
+        if negative && radix != 10 {
+            return None;
+        }
+        let digits = &unsigned[prefix_len..];
+        if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+        let magnitude = i128::from_str_radix(digits, radix).ok()?;
+        let format = NumberFormat {
+            radix,
+            prefix: unsigned[..prefix_len].to_string(),
+            width: if digits.len() > 1 && digits.starts_with('0') {
+                digits.len()
+            } else {
+                1
+            },
+            uppercase: digits.chars().any(|c| c.is_ascii_uppercase()),
+        };
+        Some((if negative { -magnitude } else { magnitude }, format))
+    }
+
+    /// Write `value` in this format
+    ///
+    /// Hex, octal and binary numbers can't go below zero.
+    pub fn format(&self, value: i128) -> String {
+        let value = if self.radix == 10 {
+            value
+        } else {
+            value.max(0)
+        };
+        let magnitude = value.unsigned_abs();
+        let mut digits = match self.radix {
+            16 => format!("{magnitude:x}"),
+            8 => format!("{magnitude:o}"),
+            2 => format!("{magnitude:b}"),
+            _ => magnitude.to_string(),
+        };
+        if self.uppercase {
+            digits = digits.to_uppercase();
+        }
+        let sign = if value < 0 { "-" } else { "" };
+        format!("{sign}{}{digits:0>width$}", self.prefix, width = self.width)
+    }
+}
+
+/// A number literal found in a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberLiteral {
+    /// Byte range within the line, including any sign and prefix
+    pub range: Range<usize>,
+    pub value: i128,
+    pub format: NumberFormat,
+}
+
+/// Find the number under `offset` in `line`, or else the first one after it
+///
+/// A number that ends right at `offset` counts as under it, so the cursor
+/// left after an incremented number still picks that number.
+pub fn number_at(line: &str, offset: usize) -> Option<NumberLiteral> {
+    find_numbers(line)
+        .into_iter()
+        .find(|number| number.range.end >= offset)
+}
+
+/// Add `delta` to the number at `offset` in `line`
+///
+/// Returns the range of the number and its new text.
+pub fn increment_at(line: &str, offset: usize, delta: i128) -> Option<(Range<usize>, String)> {
+    let number = number_at(line, offset)?;
+    let text = number.format.format(number.value.saturating_add(delta));
+    Some((number.range, text))
+}
+
+/// All the number literals in `line`, in order
+fn find_numbers(line: &str) -> Vec<NumberLiteral> {
+    let bytes = line.as_bytes();
+    let is_word = |i: usize| bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_';
+    let mut numbers = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let mut end = i + 1;
+        let radix = match bytes.get(i + 1) {
+            Some(b'x' | b'X') if bytes[i] == b'0' => 16,
+            Some(b'o' | b'O') if bytes[i] == b'0' => 8,
+            Some(b'b' | b'B') if bytes[i] == b'0' => 2,
+            _ => 10,
+        };
+        let prefixed = radix != 10
+            && (i == 0 || !is_word(i - 1))
+            && bytes
+                .get(i + 2)
+                .is_some_and(|&b| (b as char).is_digit(radix));
+        if prefixed {
+            end = i + 2;
+            while end < bytes.len() && (bytes[end] as char).is_digit(radix) {
+                end += 1;
+            }
+        } else {
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+        }
+        // A `-` that isn't part of a word before it, as in `x = -3` but not `a-3`
+        let signed = !prefixed && i > 0 && bytes[i - 1] == b'-' && (i == 1 || !is_word(i - 2));
+        let start = if signed { i - 1 } else { i };
+        if let Some((value, format)) = NumberFormat::parse(&line[start..end]) {
+            numbers.push(NumberLiteral {
+                range: start..end,
+                value,
+                format,
+            });
+        }
+        i = end;
+    }
+    numbers
+}
+
+/// The numbers of a sequence described as `start` or `start,step`
+///
+/// Every number is written like `start`: `1` gives `1, 2, 3`, `001` gives
+/// `001, 002, 003` and `0x0a,0x10` gives `0x0a, 0x1a, 0x2a`.
+pub fn sequence(spec: &str, count: usize) -> Option<Vec<String>> {
+    let (start, step) = match spec.split_once(',') {
+        Some((start, step)) => (start.trim(), NumberFormat::parse(step.trim())?.0),
+        None => (spec.trim(), 1),
+    };
+    let (start, format) = NumberFormat::parse(start)?;
+    Some(
+        (0..count as i128)
+            .map(|i| format.format(start.saturating_add(i.saturating_mul(step))))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn increment(line: &str, offset: usize, delta: i128) -> Option<String> {
+        let (range, text) = increment_at(line, offset, delta)?;
+        Some(format!(
+            "{}{text}{}",
+            &line[..range.start],
+            &line[range.end..]
+        ))
+    }
+
+    #[test]
+    fn test_increment_decimal() {
+        assert_eq!(increment("x = 41;", 0, 1).unwrap(), "x = 42;");
+        assert_eq!(increment("x = 41;", 6, 1).unwrap(), "x = 42;");
+        assert_eq!(increment("a 1 b 2", 4, 5).unwrap(), "a 1 b 7");
+        assert_eq!(increment("x = -1", 0, 3).unwrap(), "x = 2");
+        assert_eq!(increment("x = 1", 0, -3).unwrap(), "x = -2");
+        assert_eq!(increment("item-3", 0, 1).unwrap(), "item-4");
+        assert_eq!(increment("v007", 0, 1).unwrap(), "v008");
+        assert_eq!(increment("099", 0, 1).unwrap(), "100");
+        assert_eq!(increment("no numbers", 0, 1), None);
+        assert_eq!(increment("1 and", 3, 1), None);
+    }
+
+    #[test]
+    fn test_increment_prefixed() {
+        assert_eq!(increment("0xff", 0, 1).unwrap(), "0x100");
+        assert_eq!(increment("0x0F", 3, 1).unwrap(), "0x10");
+        assert_eq!(increment("0X00fe", 0, 1).unwrap(), "0X00ff");
+        assert_eq!(increment("0o17", 0, 1).unwrap(), "0o20");
+        assert_eq!(increment("0b0111", 0, 1).unwrap(), "0b1000");
+        assert_eq!(increment("0x01", 0, -5).unwrap(), "0x00");
+        // Not a prefix inside a word
+        assert_eq!(increment("a0x1", 0, 1).unwrap(), "a1x1");
+    }
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(sequence("1", 3).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(sequence("008", 3).unwrap(), vec!["008", "009", "010"]);
+        assert_eq!(
+            sequence("0x0a, 0x10", 3).unwrap(),
+            vec!["0x0a", "0x1a", "0x2a"]
+        );
+        assert_eq!(sequence("10,-5", 3).unwrap(), vec!["10", "5", "0"]);
+        assert_eq!(sequence("0o6", 3).unwrap(), vec!["0o6", "0o7", "0o10"]);
+        assert_eq!(sequence("abc", 3), None);
+        assert_eq!(sequence("1,x", 3), None);
+    }
+}
@@ -0,0 +1,192 @@
+//! Document outline: the named definitions of a file, nested as in the source
+//!
+//! Functions, types, modules and the like are recognized in the tree-sitter
+//! syntax tree by their node kinds, so every grammar works without a query of
+//! its own. The breadcrumb bar shows the definitions around the cursor and
+//! offers the ones next to them to jump to.
+
+use fresh_languages::tree_sitter::{Node, Tree};
+use std::ops::Range;
+
+/// Words in the node kinds of definitions
+const DEFINITION_WORDS: &[&str] = &[
+    "function",
+    "method",
+    "constructor",
+    "class",
+    "struct",
+    "enum",
+    "union",
+    "interface",
+    "trait",
+    "impl",
+    "mod_item",
+    "module",
+    "namespace",
+];
+
+/// Words in the node kinds that contain a definition word but aren't one,
+/// such as `call_expression` or `function_declarator`
+const NON_DEFINITION_WORDS: &[&str] = &[
+    "call",
+    "expression",
+    "pattern",
+    "type",
+    "identifier",
+    "declarator",
+    "body",
+    "list",
+    "clause",
+];
+
+/// A named definition and the definitions inside it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineSymbol {
+    pub name: String,
+    /// Byte range of the whole definition
+    pub range: Range<usize>,
+    /// Where the name starts, the place to jump to
+    pub name_start: usize,
+    pub children: Vec<OutlineSymbol>,
+}
+
+/// The definitions of `text`, parsed into `tree`
+pub fn outline(text: &str, tree: &Tree) -> Vec<OutlineSymbol> {
+    let mut symbols = Vec::new();
+    collect(text, tree.root_node(), &mut symbols);
+    symbols
+}
+
+/// The definitions around `offset`, outermost first
+pub fn symbol_path(symbols: &[OutlineSymbol], offset: usize) -> Vec<&OutlineSymbol> {
+    let mut path = Vec::new();
+    let mut level = symbols;
+    while let Some(symbol) = level
+        .iter()
+        .find(|symbol| symbol.range.start <= offset && offset < symbol.range.end)
+    {
+        path.push(symbol);
+        level = &symbol.children;
+    }
+    path
+}
+
+/// Add the definitions among the descendants of `node` to `symbols`
+fn collect(text: &str, node: Node, symbols: &mut Vec<OutlineSymbol>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        match definition_name(text, child) {
+            Some((name, name_start)) => {
+                let mut children = Vec::new();
+                collect(text, child, &mut children);
+                symbols.push(OutlineSymbol {
+                    name,
+                    range: child.byte_range(),
+                    name_start,
+                    children,
+                });
+            }
+            None => collect(text, child, symbols),
+        }
+    }
+}
+
+/// The name of a definition node and where it starts, or `None` if the node
+/// isn't a named definition
+fn definition_name(text: &str, node: Node) -> Option<(String, usize)> {
+    let kind = node.kind();
+    if !DEFINITION_WORDS.iter().any(|word| kind.contains(word))
+        || NON_DEFINITION_WORDS.iter().any(|word| kind.contains(word))
+    {
+        return None;
+    }
+
+    let (name, start) = match node
+        .child_by_field_name("name")
+        .or_else(|| declarator_name(node))
+    {
+        Some(name) => (&text[name.byte_range()], name.start_byte()),
+        // `impl Trait for Type` is named by its header
+        None if kind.contains("impl") => {
+            let body = node.child_by_field_name("body")?;
+            (
+                &text[node.start_byte()..body.start_byte()],
+                node.start_byte(),
+            )
+        }
+        None => return None,
+    };
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!name.is_empty()).then_some((name, start))
+}
+
+/// The identifier of a C-style declarator chain, e.g. `f` in `int *f(void)`
+fn declarator_name(node: Node) -> Option<Node> {
+    let mut node = node.child_by_field_name("declarator")?;
+    while !node.kind().contains("identifier") {
+        node = node.child_by_field_name("declarator")?;
+    }
+    Some(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::highlighter::Language;
+    use crate::primitives::text_objects::parse;
+
+    fn outline_of(language: Language, text: &str) -> Vec<OutlineSymbol> {
+        let tree = parse(&language.ts_language().unwrap(), text).unwrap();
+        outline(text, &tree)
+    }
+
+    /// The names of the definitions, indented by nesting
+    fn names(symbols: &[OutlineSymbol]) -> Vec<String> {
+        fn add(symbols: &[OutlineSymbol], depth: usize, out: &mut Vec<String>) {
+            for symbol in symbols {
+                out.push(format!("{}{}", "  ".repeat(depth), symbol.name));
+                add(&symbol.children, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        add(symbols, 0, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_rust_outline() {
+        let text = "mod shapes {\n    struct Circle { r: f64 }\n    impl Shape for Circle {\n        fn area(&self) -> f64 {\n            let f = |x: f64| x * x;\n            f(self.r)\n        }\n    }\n}\nfn main() {}\n";
+        let symbols = outline_of(Language::Rust, text);
+        assert_eq!(
+            names(&symbols),
+            vec![
+                "shapes",
+                "  Circle",
+                "  impl Shape for Circle",
+                "    area",
+                "main"
+            ]
+        );
+
+        let offset = text.find("f(self").unwrap();
+        let path: Vec<&str> = symbol_path(&symbols, offset)
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert_eq!(path, vec!["shapes", "impl Shape for Circle", "area"]);
+        assert_eq!(
+            symbol_path(&symbols, text.find("fn main").unwrap())[0].name_start,
+            text.find("main").unwrap()
+        );
+        assert!(symbol_path(&symbols, text.len()).is_empty());
+    }
+
+    #[test]
+    fn test_python_outline() {
+        let text = "class Greeter:\n    def __init__(self):\n        pass\n\n    @property\n    def name(self):\n        return 'x'\n\ndef main():\n    pass\n";
+        assert_eq!(
+            names(&outline_of(Language::Python, text)),
+            vec!["Greeter", "  __init__", "  name", "main"]
+        );
+    }
+}
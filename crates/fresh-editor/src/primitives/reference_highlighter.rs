@@ -224,6 +224,8 @@ impl ReferenceHighlighter {
             Language::CSS => fresh_languages::tree_sitter_css::LANGUAGE.into(),
             Language::CSharp => fresh_languages::tree_sitter_c_sharp::LANGUAGE.into(),
             Language::Odin => fresh_languages::tree_sitter_odin::LANGUAGE.into(),
+            Language::Markdown => fresh_languages::tree_sitter_md::LANGUAGE.into(),
+            Language::Sql => fresh_languages::tree_sitter_sequel::LANGUAGE.into(),
         };
 
         // Create parser
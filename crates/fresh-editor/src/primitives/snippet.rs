@@ -7,6 +7,9 @@
 //! - `${n:text}` - tabstops with default text (uses the default)
 //! - `${n|choice1,choice2|}` - choices (uses first choice)
 //! - `\\$` - escaped dollar sign
+//!
+//! Variables (`$NAME` or `${NAME}`) are replaced by [`substitute_variables`]
+//! before expanding.
 
 /// Result of expanding a snippet
 #[derive(Debug, Clone, PartialEq)]
@@ -173,6 +176,70 @@ fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
     }
 }
 
+/// Replace the variables of a snippet, `$NAME` or `${NAME}`, by their
+/// values. A variable name is uppercase letters, digits and underscores,
+/// starting with a letter; names `value` returns None for are kept as
+/// written. The values are escaped, so `$` or `}` in a file name is inserted
+/// as is by [`expand_snippet`].
+pub fn substitute_variables(snippet: &str, value: impl Fn(&str) -> Option<String>) -> String {
+    let mut result = String::new();
+    let mut rest = snippet;
+
+    while let Some(i) = rest.find(['\\', '$']) {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if let Some(escaped) = rest.strip_prefix('\\') {
+            // Keep the escape and the escaped character for expand_snippet
+            let len = 1 + escaped.chars().next().map_or(0, char::len_utf8);
+            result.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        let braced = rest[1..].starts_with('{');
+        let name_start = if braced { 2 } else { 1 };
+        let name_len = variable_name_len(&rest[name_start..]);
+        let end = name_start + name_len;
+        let closed = !braced || rest[end..].starts_with('}');
+        let replacement = (name_len > 0 && closed)
+            .then(|| value(&rest[name_start..end]))
+            .flatten();
+        match replacement {
+            Some(replacement) => {
+                result.push_str(&escape(&replacement));
+                rest = &rest[if braced { end + 1 } else { end }..];
+            }
+            None => {
+                result.push('$');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Length of the variable name at the start of `text`, 0 if there's none
+fn variable_name_len(text: &str) -> usize {
+    if !text.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return 0;
+    }
+    text.find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+/// Escape the characters with a meaning in snippet syntax
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '$' | '}' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 /// Check if a string contains snippet syntax
 pub fn is_snippet(text: &str) -> bool {
     let mut chars = text.chars().peekable();
@@ -277,4 +344,18 @@ mod tests {
         assert_eq!(result.text, "foo()");
         assert_eq!(result.cursor_offset, 5);
     }
+
+    #[test]
+    fn test_substitute_variables() {
+        let value = |name: &str| match name {
+            "FILENAME" => Some("a$b}.rs".to_string()),
+            "YEAR" => Some("2026".to_string()),
+            _ => None,
+        };
+        let snippet = substitute_variables("// ${FILENAME} $YEAR\\$YEAR $UNKNOWN ${1:x}$0", value);
+        assert_eq!(snippet, "// a\\$b\\}.rs 2026\\$YEAR $UNKNOWN ${1:x}$0");
+        let result = expand_snippet(&snippet);
+        assert_eq!(result.text, "// a$b}.rs 2026$YEAR $UNKNOWN x");
+        assert_eq!(result.cursor_offset, result.text.len());
+    }
 }
@@ -0,0 +1,631 @@
+//! Text objects: the brackets, quotes, tag, function or argument around the
+//! cursor, for the "select inside/around" commands
+//!
+//! Brackets, quotes and tags are found by scanning the text. Functions and
+//! arguments come from the tree-sitter syntax tree; without one, arguments
+//! fall back to the comma-separated items between the enclosing brackets.
+//!
+//! When the selection already covers an object, the next enclosing one is
+//! returned, so repeating a command grows the selection outwards.
+//!
+//! The argument lists also back the commands that move between arguments
+//! and swap them.
+
+use fresh_languages::tree_sitter::{Language, Node, Parser, Tree};
+use std::ops::Range;
+
+/// A kind of text object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextObject {
+    /// `()`, `[]` or `{}`, whichever is closest
+    Brackets,
+    /// A `"`, `'` or `` ` `` string on the cursor line
+    Quotes,
+    /// An HTML/XML element
+    Tag,
+    /// A function, method or closure
+    Function,
+    /// An item of an argument or parameter list
+    Argument,
+}
+
+impl TextObject {
+    /// Whether finding this object needs the syntax tree
+    pub fn uses_syntax_tree(self) -> bool {
+        matches!(self, TextObject::Function | TextObject::Argument)
+    }
+}
+
+/// Parse `text` for [`find_text_object`]
+pub fn parse(language: &Language, text: &str) -> Option<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(language).ok()?;
+    parser.parse(text, None)
+}
+
+/// The range of `object` around `selection` in `text`
+///
+/// `inner` selects the contents only, otherwise the delimiters are included
+/// (and for arguments, the separator to the next one).
+pub fn find_text_object(
+    text: &str,
+    tree: Option<&Tree>,
+    selection: Range<usize>,
+    object: TextObject,
+    inner: bool,
+) -> Option<Range<usize>> {
+    match object {
+        TextObject::Brackets => {
+            // On an opening bracket, that bracket's pair is the one wanted
+            let selection = match text.as_bytes().get(selection.start) {
+                Some(b'(' | b'[' | b'{') if selection.is_empty() => {
+                    selection.start + 1..selection.start + 1
+                }
+                _ => selection,
+            };
+            let candidates = enclosing_pairs(text, selection.start)
+                .into_iter()
+                .map(|(open, close)| pair_range(text, open, close, inner));
+            grow(candidates, &selection)
+        }
+        TextObject::Quotes => {
+            let (open, close) = quote_pair(text, selection.start)?;
+            let range = if inner {
+                open + 1..close
+            } else {
+                open..close + 1
+            };
+            (selection.is_empty() || encloses(&range, &selection)).then_some(range)
+        }
+        TextObject::Tag => {
+            let mut elements: Vec<_> = elements(text)
+                .into_iter()
+                .filter(|e| e.open.start <= selection.start && e.close.end >= selection.end)
+                .collect();
+            elements.sort_by_key(|e| e.close.end - e.open.start);
+            let candidates = elements.into_iter().map(|e| {
+                if inner {
+                    e.open.end..e.close.start
+                } else {
+                    e.open.start..e.close.end
+                }
+            });
+            grow(candidates, &selection)
+        }
+        TextObject::Function => {
+            let candidates = ancestors(tree?, &selection)
+                .into_iter()
+                .filter_map(|node| function_range(text, node, inner));
+            grow(candidates, &selection)
+        }
+        TextObject::Argument => argument_lists(text, tree, &selection)
+            .iter()
+            .find_map(|items| pick_item(items, &selection, inner)),
+    }
+}
+
+/// Where the argument after `offset` starts
+///
+/// The innermost argument list with an item after `offset` is used, so
+/// moving past the last argument of a nested call continues in the outer one.
+pub fn next_argument(text: &str, tree: Option<&Tree>, offset: usize) -> Option<usize> {
+    argument_lists(text, tree, &(offset..offset))
+        .iter()
+        .find_map(|items| items.iter().find(|item| item.start > offset))
+        .map(|item| item.start)
+}
+
+/// Where the argument before `offset` starts, or the one `offset` is inside
+pub fn previous_argument(text: &str, tree: Option<&Tree>, offset: usize) -> Option<usize> {
+    argument_lists(text, tree, &(offset..offset))
+        .iter()
+        .find_map(|items| items.iter().rev().find(|item| item.start < offset))
+        .map(|item| item.start)
+}
+
+/// The argument at `offset` and the neighbour to swap it with, the next one
+/// if `forward`, otherwise the previous one
+pub fn argument_swap(
+    text: &str,
+    tree: Option<&Tree>,
+    offset: usize,
+    forward: bool,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let selection = offset..offset;
+    argument_lists(text, tree, &selection)
+        .iter()
+        .find_map(|items| {
+            let index = item_at(items, &selection)?;
+            let other = if forward {
+                index + 1
+            } else {
+                index.checked_sub(1)?
+            };
+            Some((items[index].clone(), items.get(other)?.clone()))
+        })
+}
+
+/// The argument at `offset`, to add a new one after
+pub fn argument_at(text: &str, tree: Option<&Tree>, offset: usize) -> Option<Range<usize>> {
+    let selection = offset..offset;
+    argument_lists(text, tree, &selection)
+        .iter()
+        .find_map(|items| Some(items[item_at(items, &selection)?].clone()))
+}
+
+/// The first candidate, from innermost to outermost, that encloses the
+/// selection
+fn grow(
+    mut candidates: impl Iterator<Item = Range<usize>>,
+    selection: &Range<usize>,
+) -> Option<Range<usize>> {
+    candidates.find(|range| encloses(range, selection))
+}
+
+/// Whether `range` contains the selection and, unless the selection is just
+/// a cursor, is bigger than it
+fn encloses(range: &Range<usize>, selection: &Range<usize>) -> bool {
+    range.start <= selection.start
+        && range.end >= selection.end
+        && (selection.is_empty() || range != selection)
+}
+
+/// Positions of the bracket pairs around `offset`, innermost first
+fn enclosing_pairs(text: &str, offset: usize) -> Vec<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let offset = offset.min(bytes.len());
+    let mut pairs = Vec::new();
+    let mut depth = 0usize;
+    for open in (0..offset).rev() {
+        match bytes[open] {
+            b')' | b']' | b'}' => depth += 1,
+            b'(' | b'[' | b'{' if depth > 0 => depth -= 1,
+            b'(' | b'[' | b'{' => match matching_close(bytes, open) {
+                Some(close) if close >= offset => pairs.push((open, close)),
+                Some(_) => {}
+                // Unclosed, so nothing further out can be closed either
+                None => break,
+            },
+            _ => {}
+        }
+    }
+    pairs
+}
+
+fn matching_close(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate().skip(open) {
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The range of a bracket pair, or of its contents
+fn pair_range(text: &str, open: usize, close: usize, inner: bool) -> Range<usize> {
+    if inner {
+        block_contents(text, open + 1..close)
+    } else {
+        open..close + 1
+    }
+}
+
+/// The contents of a block, without the line break after the opening
+/// bracket and the indentation before the closing one
+///
+/// So the inside of a multi-line block is its whole lines, and replacing it
+/// leaves the brackets on their own lines.
+fn block_contents(text: &str, contents: Range<usize>) -> Range<usize> {
+    let body = &text[contents.clone()];
+    let first_line = body.split_inclusive('\n').next().unwrap_or("");
+    if !first_line.ends_with('\n') || !first_line.trim().is_empty() {
+        return contents;
+    }
+    let start = contents.start + first_line.len();
+    let last_line_start = body.rfind('\n').map_or(0, |i| i + 1);
+    let end = if body[last_line_start..].trim().is_empty() {
+        contents.start + last_line_start
+    } else {
+        contents.end
+    };
+    start..end.max(start)
+}
+
+/// The quote pair on the line of `offset` that contains it, or else the
+/// first one after it
+fn quote_pair(text: &str, offset: usize) -> Option<(usize, usize)> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+    let bytes = &text.as_bytes()[..line_end];
+    let mut pairs = Vec::new();
+    let mut i = line_start;
+    while i < line_end {
+        let quote = bytes[i];
+        if matches!(quote, b'"' | b'\'' | b'`') {
+            let mut j = i + 1;
+            while j < line_end && bytes[j] != quote {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            if j >= line_end {
+                break;
+            }
+            pairs.push((i, j));
+            i = j;
+        }
+        i += 1;
+    }
+    pairs
+        .iter()
+        .find(|&&(open, close)| open <= offset && offset <= close)
+        .or_else(|| pairs.iter().find(|&&(open, _)| open > offset))
+        .copied()
+}
+
+/// An HTML/XML element: the ranges of its opening and closing tags
+#[derive(Debug)]
+struct Element {
+    open: Range<usize>,
+    close: Range<usize>,
+}
+
+/// All the elements with both an opening and a closing tag
+fn elements(text: &str) -> Vec<Element> {
+    let bytes = text.as_bytes();
+    let is_name = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b':' | b'.');
+    let mut open_tags: Vec<(&str, Range<usize>)> = Vec::new();
+    let mut elements = Vec::new();
+    let mut i = 0;
+    while let Some(found) = text[i..].find('<') {
+        let start = i + found;
+        i = start + 1;
+        if text[start..].starts_with("<!--") {
+            i = text[start..]
+                .find("-->")
+                .map_or(text.len(), |end| start + end + 3);
+            continue;
+        }
+        let closing = bytes.get(start + 1) == Some(&b'/');
+        let name_start = start + 1 + closing as usize;
+        let mut name_end = name_start;
+        while name_end < bytes.len() && is_name(bytes[name_end]) {
+            name_end += 1;
+        }
+        if name_end == name_start || !bytes[name_start].is_ascii_alphabetic() {
+            continue;
+        }
+        let Some(end) = tag_end(bytes, name_end) else {
+            break;
+        };
+        i = end;
+        let name = &text[name_start..name_end];
+        if closing {
+            if let Some(depth) = open_tags.iter().rposition(|(open, _)| *open == name) {
+                // Unclosed tags inside it, like `<br>`, end with it
+                let (_, open) = open_tags.remove(depth);
+                open_tags.truncate(depth);
+                elements.push(Element {
+                    open,
+                    close: start..end,
+                });
+            }
+        } else if bytes[end - 2] != b'/' {
+            open_tags.push((name, start..end));
+        }
+    }
+    elements
+}
+
+/// The position after the `>` ending a tag, skipping quoted attribute values
+fn tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut quote = None;
+    for (i, &b) in bytes.iter().enumerate().skip(from) {
+        match (quote, b) {
+            (None, b'>') => return Some(i + 1),
+            (None, b'"' | b'\'') => quote = Some(b),
+            (Some(q), _) if q == b => quote = None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The syntax nodes around the selection, innermost first
+fn ancestors<'tree>(tree: &'tree Tree, selection: &Range<usize>) -> Vec<Node<'tree>> {
+    let mut node = tree
+        .root_node()
+        .descendant_for_byte_range(selection.start, selection.end);
+    let mut nodes = Vec::new();
+    while let Some(current) = node {
+        nodes.push(current);
+        node = current.parent();
+    }
+    nodes
+}
+
+/// The range of a function node, or of its body
+fn function_range(text: &str, node: Node, inner: bool) -> Option<Range<usize>> {
+    let kind = node.kind();
+    let is_function = ["function", "method", "lambda", "closure", "constructor"]
+        .iter()
+        .any(|word| kind.contains(word))
+        && !["call", "signature", "type"]
+            .iter()
+            .any(|word| kind.contains(word));
+    let body = node.child_by_field_name("body").filter(|_| is_function)?;
+    if !inner {
+        return Some(node.byte_range());
+    }
+    let range = body.byte_range();
+    Some(match (text.as_bytes().get(range.start), range.len()) {
+        (Some(b'{'), 2..) => block_contents(text, range.start + 1..range.end - 1),
+        _ => range,
+    })
+}
+
+/// The items of the argument and parameter lists around the selection,
+/// innermost first
+///
+/// Lists from the syntax tree come first. The comma-separated items between
+/// the enclosing `()` and `[]` follow, for lists the grammar doesn't call
+/// arguments and for text without a grammar.
+fn argument_lists(
+    text: &str,
+    tree: Option<&Tree>,
+    selection: &Range<usize>,
+) -> Vec<Vec<Range<usize>>> {
+    let mut lists: Vec<_> = tree
+        .map(|tree| ancestors(tree, selection))
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|node| is_argument_list(node.kind()))
+        .map(|list| {
+            let mut cursor = list.walk();
+            list.named_children(&mut cursor)
+                .filter(|child| !child.is_extra())
+                .map(|child| child.byte_range())
+                .collect()
+        })
+        .collect();
+    lists.extend(
+        enclosing_pairs(text, selection.start)
+            .into_iter()
+            .filter(|&(open, _)| matches!(text.as_bytes()[open], b'(' | b'['))
+            .map(|(open, close)| list_items(text, open, close)),
+    );
+    lists
+}
+
+fn is_argument_list(kind: &str) -> bool {
+    ["arguments", "parameters", "argument_list", "parameter_list"]
+        .iter()
+        .any(|suffix| kind.ends_with(suffix))
+}
+
+/// The comma-separated items between a pair of brackets, without their
+/// surrounding whitespace
+fn list_items(text: &str, open: usize, close: usize) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut items = Vec::new();
+    let mut item_start = open + 1;
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate().take(close + 1).skip(open + 1) {
+        let at_separator = depth == 0 && (i == close || b == b',');
+        match b {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if at_separator {
+            let item = &text[item_start..i];
+            let leading = item.len() - item.trim_start().len();
+            let trimmed = item.trim();
+            if !trimmed.is_empty() {
+                let start = item_start + leading;
+                items.push(start..start + trimmed.len());
+            }
+            item_start = i + 1;
+        }
+    }
+    items
+}
+
+/// The index of the list item around the selection, or of the next one
+/// after a selection between items
+fn item_at(items: &[Range<usize>], selection: &Range<usize>) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| item.start <= selection.start && selection.end <= item.end)
+        .or_else(|| {
+            let in_list =
+                items.first()?.start <= selection.start && selection.end <= items.last()?.end;
+            in_list
+                .then(|| items.iter().position(|item| item.start >= selection.end))
+                .flatten()
+        })
+}
+
+/// The list item around the selection, see [`item_at`]
+///
+/// Around an item includes the separator to the next item, or for the last
+/// item, the one from the previous item.
+fn pick_item(
+    items: &[Range<usize>],
+    selection: &Range<usize>,
+    inner: bool,
+) -> Option<Range<usize>> {
+    let index = item_at(items, selection)?;
+    let item = &items[index];
+    let range = if inner {
+        item.clone()
+    } else if let Some(next) = items.get(index + 1) {
+        item.start..next.start
+    } else if let Some(previous) = index.checked_sub(1).map(|i| &items[i]) {
+        previous.end..item.end
+    } else {
+        item.clone()
+    };
+    (selection.is_empty() || range != *selection).then_some(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::highlighter::Language as SyntaxLanguage;
+
+    /// The text of `object` with the cursor at `^`
+    fn select(text: &str, object: TextObject, inner: bool) -> Option<String> {
+        let offset = text.find('^').unwrap();
+        let text = text.replace('^', "");
+        let tree = if object.uses_syntax_tree() {
+            SyntaxLanguage::Rust
+                .ts_language()
+                .and_then(|language| parse(&language, &text))
+        } else {
+            None
+        };
+        let range = find_text_object(&text, tree.as_ref(), offset..offset, object, inner)?;
+        Some(text[range].to_string())
+    }
+
+    #[test]
+    fn test_brackets() {
+        let text = "f(a, [b, ^c], {d})";
+        assert_eq!(select(text, TextObject::Brackets, true).unwrap(), "b, c");
+        assert_eq!(select(text, TextObject::Brackets, false).unwrap(), "[b, c]");
+        assert_eq!(
+            select("f^(a, (b))", TextObject::Brackets, false).unwrap(),
+            "(a, (b))"
+        );
+        assert_eq!(
+            select("x {\n    ^a;\n    b;\n}", TextObject::Brackets, true).unwrap(),
+            "    a;\n    b;\n"
+        );
+        assert_eq!(select("f^()", TextObject::Brackets, true).unwrap(), "");
+        assert_eq!(select("no ^brackets", TextObject::Brackets, true), None);
+    }
+
+    #[test]
+    fn test_repeat_grows_selection() {
+        let text = "f(a, [b, c])";
+        let inner = find_text_object(text, None, 6..10, TextObject::Brackets, true);
+        assert_eq!(inner, Some(2..11));
+        let around = find_text_object(text, None, 5..11, TextObject::Brackets, false);
+        assert_eq!(around, Some(1..12));
+    }
+
+    #[test]
+    fn test_quotes() {
+        let text = r#"say("a \"b\"", 'c^d')"#;
+        assert_eq!(select(text, TextObject::Quotes, true).unwrap(), "cd");
+        assert_eq!(select(text, TextObject::Quotes, false).unwrap(), "'cd'");
+        assert_eq!(
+            select(r#"s^ay("a \"b\"")"#, TextObject::Quotes, true).unwrap(),
+            r#"a \"b\""#
+        );
+        assert_eq!(select("no ^quotes", TextObject::Quotes, true), None);
+    }
+
+    #[test]
+    fn test_tags() {
+        let text = "<ul class=\"x>y\">\n  <li>one <b>t^wo</b></li>\n  <br/>\n</ul>";
+        assert_eq!(select(text, TextObject::Tag, true).unwrap(), "two");
+        assert_eq!(select(text, TextObject::Tag, false).unwrap(), "<b>two</b>");
+        assert_eq!(
+            select("<p>a <!-- <p> --> ^b</p>", TextObject::Tag, true).unwrap(),
+            "a <!-- <p> --> b"
+        );
+        assert_eq!(select("<p>a</p> ^b", TextObject::Tag, true), None);
+    }
+
+    #[test]
+    fn test_function() {
+        let text =
+            "fn outer() {\n    let f = |x| x + 1;\n    fn inner(a: i32) {\n        ^a;\n    }\n}\n";
+        assert_eq!(
+            select(text, TextObject::Function, true).unwrap(),
+            "        a;\n"
+        );
+        assert_eq!(
+            select(text, TextObject::Function, false).unwrap(),
+            "fn inner(a: i32) {\n        a;\n    }"
+        );
+        assert_eq!(
+            select(
+                "fn f() {\n    let g = |x| ^x + 1;\n}",
+                TextObject::Function,
+                true
+            )
+            .unwrap(),
+            "x + 1"
+        );
+        assert_eq!(
+            select("const ^X: i32 = 1;", TextObject::Function, true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_argument() {
+        let text = "fn f() { g(a, h(b, c), ^d); }";
+        assert_eq!(select(text, TextObject::Argument, true).unwrap(), "d");
+        assert_eq!(select(text, TextObject::Argument, false).unwrap(), ", d");
+        assert_eq!(
+            select("fn f(x: i32, ^y: u8) {}", TextObject::Argument, true).unwrap(),
+            "y: u8"
+        );
+        assert_eq!(
+            select("fn f() { g(^a, b); }", TextObject::Argument, false).unwrap(),
+            "a, "
+        );
+        assert_eq!(
+            select("fn f() { g(a,^ b); }", TextObject::Argument, true).unwrap(),
+            "b"
+        );
+    }
+
+    #[test]
+    fn test_argument_navigation() {
+        let text = "fn f() { g(a, h(b, c), d); }";
+        let tree = SyntaxLanguage::Rust
+            .ts_language()
+            .and_then(|language| parse(&language, text));
+        let at = |s: &str| text.find(s).unwrap();
+        let tree = tree.as_ref();
+
+        assert_eq!(next_argument(text, tree, at("a,")), Some(at("h(")));
+        // Past the last argument of `h`, on to the next one of `g`
+        assert_eq!(next_argument(text, tree, at("c)")), Some(at("d)")));
+        assert_eq!(next_argument(text, tree, at("d)")), None);
+        assert_eq!(previous_argument(text, tree, at("c)")), Some(at("b,")));
+        assert_eq!(previous_argument(text, tree, at("b,")), Some(at("h(")));
+
+        assert_eq!(
+            argument_swap(text, tree, at("a,"), true),
+            Some((at("a,")..at("a,") + 1, at("h(")..at(", d")))
+        );
+        assert_eq!(argument_swap(text, tree, at("a,"), false), None);
+        assert_eq!(
+            argument_at(text, tree, at("c)") + 1),
+            Some(at("c)")..at("c)") + 1)
+        );
+    }
+
+    #[test]
+    fn test_argument_without_syntax_tree() {
+        let text = "call(one, [2, ^3], {four: 4})";
+        let offset = text.find('^').unwrap();
+        let text = text.replace('^', "");
+        let range = find_text_object(&text, None, offset..offset, TextObject::Argument, true);
+        assert_eq!(&text[range.unwrap()], "3");
+        let range = find_text_object(&text, None, 10..16, TextObject::Argument, false);
+        assert_eq!(&text[range.unwrap()], "[2, 3], ");
+    }
+}
@@ -11,7 +11,9 @@
 
 use crate::model::buffer::Buffer;
 use crate::primitives::grammar::GrammarRegistry;
-use crate::primitives::highlight_types::{highlight_color, HighlightCategory, HighlightSpan};
+use crate::primitives::highlight_types::{
+    highlight_color, CacheSpan, HighlightCategory, HighlightSpan,
+};
 use crate::view::theme::Theme;
 use std::ops::Range;
 use std::path::Path;
@@ -29,12 +31,11 @@ pub struct TextMateEngine {
     syntax_set: Arc<SyntaxSet>,
     syntax_index: usize,
     cache: Option<TextMateCache>,
-    last_buffer_len: usize,
 }
 
 #[derive(Debug, Clone)]
 struct TextMateCache {
-    range: Range<usize>,
+    span: CacheSpan,
     spans: Vec<CachedSpan>,
 }
 
@@ -51,7 +52,6 @@ impl TextMateEngine {
             syntax_set,
             syntax_index,
             cache: None,
-            last_buffer_len: 0,
         }
     }
 
@@ -86,11 +86,8 @@ impl TextMateEngine {
         use syntect::parsing::{ParseState, ScopeStack};
 
         // Check cache validity
-        if let Some(cache) = &self.cache {
-            if cache.range.start <= viewport_start
-                && cache.range.end >= viewport_end
-                && self.last_buffer_len == buffer.len()
-            {
+        if let Some(cache) = &mut self.cache {
+            if cache.span.covers(buffer, viewport_start..viewport_end) {
                 return cache
                     .spans
                     .iter()
@@ -224,10 +221,9 @@ impl TextMateEngine {
 
         // Update cache
         self.cache = Some(TextMateCache {
-            range: parse_start..parse_end,
+            span: CacheSpan::new(buffer, parse_start..parse_end),
             spans: spans.clone(),
         });
-        self.last_buffer_len = buffer.len();
 
         // Filter and resolve colors
         spans
@@ -276,7 +272,7 @@ impl TextMateEngine {
     /// Invalidate cache for edited range
     pub fn invalidate_range(&mut self, edit_range: Range<usize>) {
         if let Some(cache) = &self.cache {
-            if edit_range.start < cache.range.end && edit_range.end > cache.range.start {
+            if edit_range.start < cache.span.range.end && edit_range.end > cache.span.range.start {
                 self.cache = None;
             }
         }
@@ -0,0 +1,238 @@
+//! Completion from the words of open buffers and a tags file
+//!
+//! Used when no language server provides completions. Each buffer is split
+//! into identifiers by the rules of its language (`font-size` is one word in
+//! CSS, `$el` in JavaScript), then the words starting with the typed prefix
+//! are ranked: words of the current buffer first, nearest to the cursor
+//! first, then words of the other buffers in the order they were last used,
+//! then names from the tags file.
+
+use std::collections::HashSet;
+
+/// Characters besides letters, digits and `_` that are part of an
+/// identifier in `language`
+pub fn extra_word_chars(language: &str) -> &'static [char] {
+    match language {
+        "css" | "scss" | "less" | "html" | "lisp" | "clojure" | "scheme" | "racket" | "elisp" => {
+            &['-']
+        }
+        "javascript" | "typescript" | "php" => &['$'],
+        _ => &[],
+    }
+}
+
+fn is_word_char(c: char, extra: &[char]) -> bool {
+    c.is_alphanumeric() || c == '_' || extra.contains(&c)
+}
+
+/// Whether a run of word characters is worth suggesting: it has a letter
+/// or `_`, and doesn't start with a digit
+fn is_identifier(word: &str) -> bool {
+    !word.starts_with(|c: char| c.is_ascii_digit())
+        && word.chars().any(|c| c.is_alphabetic() || c == '_')
+}
+
+/// The identifiers of `text` and their byte ranges
+pub fn words<'a>(
+    text: &'a str,
+    language: &str,
+) -> impl Iterator<Item = (std::ops::Range<usize>, &'a str)> + 'a {
+    let extra = extra_word_chars(language);
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || loop {
+        let (start, _) = loop {
+            let (i, c) = chars.next()?;
+            if is_word_char(c, extra) {
+                break (i, c);
+            }
+        };
+        let mut end = text.len();
+        while let Some(&(i, c)) = chars.peek() {
+            if !is_word_char(c, extra) {
+                end = i;
+                break;
+            }
+            chars.next();
+        }
+        let word = &text[start..end];
+        if is_identifier(word) {
+            return Some((start..end, word));
+        }
+    })
+}
+
+/// The part of a word typed before the cursor, given the text before it
+pub fn typed_prefix<'a>(before_cursor: &'a str, language: &str) -> &'a str {
+    let extra = extra_word_chars(language);
+    let start = before_cursor
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_word_char(c, extra))
+        .last()
+        .map_or(before_cursor.len(), |(i, _)| i);
+    let prefix = &before_cursor[start..];
+    if is_identifier(prefix) {
+        prefix
+    } else {
+        ""
+    }
+}
+
+/// Text to take words from
+pub struct WordSource<'a> {
+    pub text: &'a str,
+    pub language: &'a str,
+    /// Byte offset of the cursor in `text`, for the current buffer. Words
+    /// closer to it rank higher and the word under it is left out.
+    pub cursor: Option<usize>,
+    /// Shown next to the words found here, such as the file name
+    pub label: Option<&'a str>,
+}
+
+/// A suggested word and where it was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordCompletion {
+    pub word: String,
+    pub label: Option<String>,
+}
+
+/// The words of `sources` and `tags` starting with `prefix` (ignoring case),
+/// best first and at most `limit` of them
+///
+/// `sources` are given most recently used first, starting with the current
+/// buffer. A word found in several places is listed once, where it ranks
+/// highest.
+pub fn complete_words(
+    prefix: &str,
+    sources: &[WordSource],
+    tags: &[String],
+    limit: usize,
+) -> Vec<WordCompletion> {
+    let prefix_lower = prefix.to_lowercase();
+    let matches = |word: &str| word != prefix && word.to_lowercase().starts_with(&prefix_lower);
+
+    let mut seen = HashSet::new();
+    let mut completions = Vec::new();
+    for source in sources {
+        let mut found: Vec<(usize, &str)> = words(source.text, source.language)
+            .filter(|(range, _)| {
+                source
+                    .cursor
+                    .is_none_or(|cursor| cursor < range.start || cursor > range.end)
+            })
+            .filter(|(_, word)| matches(word))
+            .map(|(range, word)| {
+                let distance = source.cursor.map_or(0, |cursor| {
+                    cursor.abs_diff(range.start).min(cursor.abs_diff(range.end))
+                });
+                (distance, word)
+            })
+            .collect();
+        found.sort();
+        for (_, word) in found {
+            if seen.insert(word.to_string()) {
+                completions.push(WordCompletion {
+                    word: word.to_string(),
+                    label: source.label.map(str::to_string),
+                });
+            }
+        }
+    }
+    for name in tags.iter().filter(|name| matches(name)) {
+        if seen.insert(name.clone()) {
+            completions.push(WordCompletion {
+                word: name.clone(),
+                label: None,
+            });
+        }
+    }
+    completions.truncate(limit);
+    completions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source<'a>(text: &'a str, language: &'a str, cursor: Option<usize>) -> WordSource<'a> {
+        WordSource {
+            text,
+            language,
+            cursor,
+            label: None,
+        }
+    }
+
+    fn suggested(completions: Vec<WordCompletion>) -> Vec<String> {
+        completions.into_iter().map(|c| c.word).collect()
+    }
+
+    #[test]
+    fn test_words_follow_language() {
+        let text = "a { font-size: 12px; } $el 3d x2";
+        let css: Vec<&str> = words(text, "css").map(|(_, w)| w).collect();
+        assert_eq!(css, vec!["a", "font-size", "el", "x2"]);
+        let js: Vec<&str> = words(text, "javascript").map(|(_, w)| w).collect();
+        assert_eq!(js, vec!["a", "font", "size", "$el", "x2"]);
+    }
+
+    #[test]
+    fn test_typed_prefix() {
+        assert_eq!(typed_prefix("let foo_b", "rust"), "foo_b");
+        assert_eq!(typed_prefix("  margin-to", "css"), "margin-to");
+        assert_eq!(typed_prefix("  margin-to", "rust"), "to");
+        assert_eq!(typed_prefix("x = 12", "rust"), "");
+        assert_eq!(typed_prefix("x.", "rust"), "");
+    }
+
+    #[test]
+    fn test_nearest_words_rank_first() {
+        let text = "counter = 1\ncount\ncountdown = 2\n";
+        let cursor = text.find("count\n").unwrap() + 5;
+        let completions = complete_words("count", &[source(text, "text", Some(cursor))], &[], 10);
+        assert_eq!(suggested(completions), vec!["countdown", "counter"]);
+    }
+
+    #[test]
+    fn test_other_buffers_then_tags() {
+        let current = "value";
+        let other = "validate values";
+        let tags = vec!["valid_tag".to_string(), "validate".to_string()];
+        let completions = complete_words(
+            "val",
+            &[
+                source(current, "text", Some(3)),
+                WordSource {
+                    label: Some("other.txt"),
+                    ..source(other, "text", None)
+                },
+            ],
+            &tags,
+            10,
+        );
+        assert_eq!(
+            completions,
+            vec![
+                WordCompletion {
+                    word: "validate".to_string(),
+                    label: Some("other.txt".to_string()),
+                },
+                WordCompletion {
+                    word: "values".to_string(),
+                    label: Some("other.txt".to_string()),
+                },
+                WordCompletion {
+                    word: "valid_tag".to_string(),
+                    label: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_matching_ignores_case() {
+        let completions =
+            complete_words("http", &[source("HttpServer http", "text", None)], &[], 10);
+        assert_eq!(suggested(completions), vec!["HttpServer"]);
+    }
+}
@@ -1,6 +1,8 @@
 //! Word boundary detection and navigation helpers
 
 use crate::model::buffer::Buffer;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Check if a byte is a word character (alphanumeric or underscore)
 pub fn is_word_char(byte: u8) -> bool {
@@ -423,6 +425,82 @@ pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
     start + current_idx
 }
 
+/// Find the byte range of the word at the given position, for double-click
+/// selection
+///
+/// Words follow the Unicode word boundaries of UAX #29, so contractions like
+/// `can't` and accented words are kept whole, except that `.` and `:` split
+/// identifiers like `self.value` (but not numbers like `3.14`). On whitespace
+/// or punctuation the range covers that run instead, and at the end of a line
+/// it covers whatever comes before it.
+pub fn word_range_at(buffer: &Buffer, pos: usize) -> Range<usize> {
+    let pos = pos.min(buffer.len());
+
+    // Only read a small window around the position, cut to its line
+    let start = pos.saturating_sub(1000);
+    let end = (pos + 1000).min(buffer.len());
+    let bytes = buffer.slice_bytes(start..end);
+    let offset = pos - start;
+    let line_start = match bytes[..offset].iter().rposition(|&b| b == b'\n') {
+        Some(newline) => newline + 1,
+        // Skip a character cut in half by the window
+        None => bytes[..offset]
+            .iter()
+            .position(|&b| b & 0xC0 != 0x80)
+            .unwrap_or(offset),
+    };
+    let line_end = bytes[offset..]
+        .iter()
+        .position(|&b| b == b'\n' || b == b'\r')
+        .map_or(bytes.len(), |i| offset + i);
+    let line = &bytes[line_start..line_end];
+    let text = match std::str::from_utf8(line) {
+        Ok(text) => text,
+        Err(e) => std::str::from_utf8(&line[..e.valid_up_to()]).unwrap_or_default(),
+    };
+
+    let offset = offset - line_start;
+    let Some((seg_start, segment)) = text
+        .split_word_bound_indices()
+        .find(|(i, segment)| offset < i + segment.len())
+        .or_else(|| text.split_word_bound_indices().next_back())
+    else {
+        return pos..pos;
+    };
+
+    // Split the segment at `.` and `:`, keeping the piece around the position
+    let offset = offset.clamp(seg_start, seg_start + segment.len() - 1) - seg_start;
+    let splits_word = |i: usize, c: char| {
+        let digit_at = |j: Option<usize>| {
+            j.and_then(|j| segment[j..].chars().next())
+                .is_some_and(|c| c.is_ascii_digit())
+        };
+        match c {
+            '.' => !(digit_at(i.checked_sub(1)) && digit_at(Some(i + 1))),
+            ':' => true,
+            _ => false,
+        }
+    };
+    let mut piece = 0..segment.len();
+    for (i, c) in segment.char_indices() {
+        if !splits_word(i, c) {
+            continue;
+        }
+        if i + c.len_utf8() <= offset {
+            piece.start = i + c.len_utf8();
+        } else if i > offset {
+            piece.end = i;
+            break;
+        } else {
+            piece = i..i + c.len_utf8();
+            break;
+        }
+    }
+
+    let base = start + line_start + seg_start;
+    base + piece.start..base + piece.end
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -456,6 +534,22 @@ mod tests {
         assert_eq!(find_word_end(&buffer, 6), 11); // End of "world"
     }
 
+    #[test]
+    fn test_word_range_at() {
+        let text = "let café = self.value + 3.14;\nit can't be";
+        let buffer = Buffer::from_str_test(text);
+        let word = |pos| &text[word_range_at(&buffer, pos)];
+        assert_eq!(word(5), "café");
+        assert_eq!(word(13), "self");
+        assert_eq!(word(16), ".");
+        assert_eq!(word(18), "value");
+        assert_eq!(word(26), "3.14");
+        assert_eq!(word(3), " ");
+        assert_eq!(word(30), ";"); // The newline
+        assert_eq!(word(36), "can't");
+        assert_eq!(word(text.len()), "be");
+    }
+
     #[test]
     fn test_find_word_start_left() {
         let buffer = Buffer::from_str_test("hello world test");
@@ -15,7 +15,7 @@ use crate::view::file_tree::{FileTreeView, NodeId};
 use lsp_types::{
     CodeActionOrCommand, CompletionItem, Diagnostic, InlayHint, Location,
     SemanticTokensFullDeltaResult, SemanticTokensLegend, SemanticTokensRangeResult,
-    SemanticTokensResult, SignatureHelp,
+    SemanticTokensResult, SignatureHelp, SymbolKind,
 };
 use serde_json::Value;
 use std::sync::mpsc;
@@ -28,6 +28,16 @@ pub enum LspSemanticTokensResponse {
     Range(Result<Option<SemanticTokensRangeResult>, String>),
 }
 
+/// A document or workspace symbol, flattened from the server's response.
+#[derive(Debug, Clone)]
+pub struct LspSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Name of the enclosing symbol or module, if known
+    pub container: Option<String>,
+    pub location: Location,
+}
+
 /// Messages sent from async tasks to the synchronous main loop
 #[derive(Debug)]
 pub enum AsyncMessage {
@@ -50,6 +60,8 @@ pub enum AsyncMessage {
         semantic_tokens_full_delta: bool,
         /// Whether the server supports range semantic tokens
         semantic_tokens_range: bool,
+        /// Whether the server takes workspace folder changes
+        workspace_folder_changes: bool,
     },
 
     /// LSP server crashed or failed
@@ -96,6 +108,32 @@ pub enum AsyncMessage {
         locations: Vec<Location>,
     },
 
+    /// LSP document symbols response (textDocument/documentSymbol)
+    LspDocumentSymbols {
+        request_id: u64,
+        symbols: Vec<LspSymbol>,
+    },
+
+    /// LSP workspace symbols response (workspace/symbol)
+    LspWorkspaceSymbols {
+        request_id: u64,
+        symbols: Vec<LspSymbol>,
+    },
+
+    /// Edits to make before renaming a file (workspace/willRenameFiles),
+    /// `None` if the server has none or wasn't asked
+    LspWillRenameFiles {
+        request_id: u64,
+        edit: Option<lsp_types::WorkspaceEdit>,
+    },
+
+    /// Edits formatting a range (textDocument/rangeFormatting), `None` if
+    /// the server doesn't format ranges or the request failed
+    LspRangeFormatting {
+        request_id: u64,
+        edits: Option<Vec<lsp_types::TextEdit>>,
+    },
+
     /// LSP signature help response
     LspSignatureHelp {
         request_id: u64,
@@ -167,6 +205,9 @@ pub enum AsyncMessage {
     /// File open dialog: async shortcuts (Windows drive letters) loaded
     FileOpenShortcutsLoaded(Vec<crate::app::file_open::NavigationShortcut>),
 
+    /// Another `fresh` process asked this instance to open a file
+    OpenFileRequest(crate::services::instance_server::OpenRequest),
+
     /// Terminal output received (triggers redraw)
     TerminalOutput { terminal_id: TerminalId },
 
@@ -222,6 +263,37 @@ pub enum AsyncMessage {
         exit_code: i32,
     },
 
+    /// Shell filter command completed (Shell Command (Replace))
+    ShellFilterFinished {
+        buffer_id: crate::model::event::BufferId,
+        /// Filtered selection, or `None` for the whole buffer
+        selection: Option<std::ops::Range<usize>>,
+        /// Buffer version when the filter started
+        version: u64,
+        command: String,
+        /// Filter stdout, or the error message (including stderr)
+        result: Result<String, String>,
+    },
+
+    /// Tags regeneration (`ctags -R` or `global -u`) finished
+    TagsRegenerated { result: Result<(), String> },
+
+    /// A line a project task wrote to stdout or stderr
+    TaskOutput {
+        /// The task's output buffer
+        buffer_id: crate::model::event::BufferId,
+        run_id: u64,
+        line: String,
+    },
+
+    /// A project task exited, after all its output was sent
+    TaskFinished {
+        buffer_id: crate::model::event::BufferId,
+        run_id: u64,
+        /// Exit code (`None` if killed by a signal), or why waiting failed
+        result: Result<Option<i32>, String>,
+    },
+
     /// LSP server status update (progress, messages, etc.)
     LspStatusUpdate {
         language: String,
@@ -356,6 +428,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
@@ -390,6 +463,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
         sender
@@ -400,6 +474,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
@@ -432,6 +507,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
         sender2
@@ -442,6 +518,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
@@ -544,6 +621,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
@@ -565,6 +643,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
@@ -591,6 +670,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
         sender
@@ -601,6 +681,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
         sender
@@ -611,6 +692,7 @@ mod tests {
                 semantic_tokens_full: false,
                 semantic_tokens_full_delta: false,
                 semantic_tokens_range: false,
+                workspace_folder_changes: false,
             })
             .unwrap();
 
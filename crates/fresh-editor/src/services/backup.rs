@@ -0,0 +1,169 @@
+//! Emacs-style backup files
+//!
+//! The first time a file is saved in a session, its previous on-disk contents
+//! are copied to a backup file. This is independent of crash recovery: backups
+//! preserve the last *saved* version, recovery files preserve unsaved edits.
+//!
+//! ## Naming
+//!
+//! - Simple: `file.txt~`
+//! - Numbered: `file.txt.~1~`, `file.txt.~2~`, ... (oldest versions beyond the
+//!   retention limit are deleted)
+//!
+//! Backups are written next to the file, or into a backup directory where the
+//! file's absolute path is encoded into the name (e.g.
+//! `home_user_file.txt.~3~`) so files with the same name don't collide.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::model::filesystem::FileSystem;
+use crate::session::encode_path_for_filename;
+
+/// Backup settings
+#[derive(Debug, Clone, Default)]
+pub struct BackupConfig {
+    /// Keep numbered versions instead of a single `~` backup
+    pub numbered: bool,
+    /// Directory for backup files (`None` = next to the original file)
+    pub directory: Option<PathBuf>,
+    /// Numbered versions to keep per file (0 = keep all)
+    pub keep_versions: usize,
+}
+
+impl BackupConfig {
+    /// Directory and file name prefix for backups of `path`
+    fn location(&self, path: &Path) -> Option<(PathBuf, String)> {
+        match &self.directory {
+            Some(dir) => Some((dir.clone(), encode_path_for_filename(path))),
+            None => {
+                let parent = path.parent()?.to_path_buf();
+                let name = path.file_name()?.to_string_lossy().into_owned();
+                Some((parent, name))
+            }
+        }
+    }
+
+    /// Path of the simple (non-numbered) backup for `path`
+    pub fn simple_backup_path(&self, path: &Path) -> Option<PathBuf> {
+        let (dir, name) = self.location(path)?;
+        Some(dir.join(format!("{}~", name)))
+    }
+
+    /// Existing numbered backups of `path`, sorted by version (oldest first)
+    pub fn numbered_backups(
+        &self,
+        fs: &dyn FileSystem,
+        path: &Path,
+    ) -> io::Result<Vec<(u32, PathBuf)>> {
+        let Some((dir, name)) = self.location(path) else {
+            return Ok(Vec::new());
+        };
+        if !fs.exists(&dir) {
+            return Ok(Vec::new());
+        }
+
+        let prefix = format!("{}.~", name);
+        let mut backups: Vec<(u32, PathBuf)> = fs
+            .read_dir(&dir)?
+            .into_iter()
+            .filter_map(|entry| {
+                let version = entry
+                    .name
+                    .strip_prefix(&prefix)?
+                    .strip_suffix('~')?
+                    .parse()
+                    .ok()?;
+                Some((version, entry.path))
+            })
+            .collect();
+        backups.sort_by_key(|(version, _)| *version);
+        Ok(backups)
+    }
+
+    /// Copy the current on-disk contents of `path` to a backup file
+    ///
+    /// Returns the backup path, or `None` if there was nothing to back up
+    /// (the file doesn't exist yet). For numbered backups, versions beyond
+    /// `keep_versions` are deleted afterwards.
+    pub fn make_backup(&self, fs: &dyn FileSystem, path: &Path) -> io::Result<Option<PathBuf>> {
+        if !fs.is_file(path).unwrap_or(false) {
+            return Ok(None);
+        }
+        let Some((dir, name)) = self.location(path) else {
+            return Ok(None);
+        };
+        fs.create_dir_all(&dir)?;
+
+        if !self.numbered {
+            let backup = dir.join(format!("{}~", name));
+            fs.copy(path, &backup)?;
+            return Ok(Some(backup));
+        }
+
+        let mut existing = self.numbered_backups(fs, path)?;
+        let next = existing.last().map_or(1, |(version, _)| version + 1);
+        let backup = dir.join(format!("{}.~{}~", name, next));
+        fs.copy(path, &backup)?;
+        existing.push((next, backup.clone()));
+
+        if self.keep_versions > 0 && existing.len() > self.keep_versions {
+            let excess = existing.len() - self.keep_versions;
+            for (_, old) in existing.drain(..excess) {
+                if let Err(e) = fs.remove_file(&old) {
+                    tracing::warn!("Failed to delete old backup {:?}: {}", old, e);
+                }
+            }
+        }
+        Ok(Some(backup))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_simple_backup_next_to_file() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("notes.txt");
+        std::fs::write(&file, "v1").unwrap();
+
+        let config = BackupConfig::default();
+        let backup = config.make_backup(&StdFileSystem, &file).unwrap().unwrap();
+        assert_eq!(backup, temp.path().join("notes.txt~"));
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "v1");
+
+        // Nothing to back up for a new file
+        let missing = temp.path().join("new.txt");
+        assert!(config
+            .make_backup(&StdFileSystem, &missing)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_numbered_backups_with_retention() {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("src").join("main.rs");
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        let config = BackupConfig {
+            numbered: true,
+            directory: Some(temp.path().join("backups")),
+            keep_versions: 2,
+        };
+
+        for version in 1..=3 {
+            std::fs::write(&file, format!("v{}", version)).unwrap();
+            config.make_backup(&StdFileSystem, &file).unwrap().unwrap();
+        }
+
+        let backups = config.numbered_backups(&StdFileSystem, &file).unwrap();
+        let versions: Vec<u32> = backups.iter().map(|(v, _)| *v).collect();
+        assert_eq!(versions, vec![2, 3]);
+        assert_eq!(std::fs::read_to_string(&backups[1].1).unwrap(), "v3");
+        assert!(backups[1].1.starts_with(temp.path().join("backups")));
+    }
+}
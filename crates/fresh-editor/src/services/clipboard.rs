@@ -23,6 +23,8 @@ pub struct Clipboard {
     internal: String,
     /// When true, paste() uses internal clipboard only (for testing)
     internal_only: bool,
+    /// When true, copy() doesn't send OSC 52 (the terminal doesn't support it)
+    osc52_disabled: bool,
 }
 
 impl Clipboard {
@@ -31,9 +33,15 @@ impl Clipboard {
         Self {
             internal: String::new(),
             internal_only: false,
+            osc52_disabled: false,
         }
     }
 
+    /// Whether copy() also sets the clipboard through the terminal (OSC 52)
+    pub fn set_osc52_enabled(&mut self, enabled: bool) {
+        self.osc52_disabled = !enabled;
+    }
+
     /// Enable internal-only mode (for testing)
     /// When enabled, paste() uses internal clipboard only, ignoring system clipboard
     pub fn set_internal_only(&mut self, enabled: bool) {
@@ -88,12 +96,14 @@ impl Clipboard {
         // Try OSC 52 first (works in modern terminals)
         // Note: This doesn't "fail" in a detectable way - it just sends escape sequences
         // that the terminal may or may not handle
-        let osc52_result = execute!(stdout(), CopyToClipboard::to_clipboard_from(&text));
-        if let Err(e) = &osc52_result {
-            tracing::debug!("Crossterm OSC 52 clipboard copy failed: {}", e);
+        if !self.osc52_disabled {
+            let osc52_result = execute!(stdout(), CopyToClipboard::to_clipboard_from(&text));
+            if let Err(e) = &osc52_result {
+                tracing::debug!("Crossterm OSC 52 clipboard copy failed: {}", e);
+            }
+            // Ensure the escape sequence is flushed to the terminal
+            let _ = stdout().flush();
         }
-        // Ensure the escape sequence is flushed to the terminal
-        let _ = stdout().flush();
 
         // Also try arboard (works via X11/Wayland in terminals without OSC 52 support)
         // This provides coverage for Gnome Console, XFCE Terminal, and similar
@@ -0,0 +1,426 @@
+//! Sequence CRDT for shared buffers
+//!
+//! A replicated growable array (RGA): every character ever inserted keeps a
+//! unique [`OpId`] and the id of the character it was typed after. Deleted
+//! characters stay behind as tombstones, so operations from other peers can
+//! still refer to them. Replicas that apply the same operations end up with
+//! the same text whatever order concurrent operations arrive in; an operation
+//! whose dependencies have not arrived yet is reported by [`Document::can_apply`]
+//! and should be held back until they do.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Unique id of an inserted character: a Lamport clock and the site that
+/// inserted it
+///
+/// Ids are totally ordered by clock, then site. Between characters inserted at
+/// the same place concurrently, the one with the larger id comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OpId {
+    pub clock: u64,
+    pub site: u32,
+}
+
+impl OpId {
+    /// Id of the `n`th character of a run starting at this id
+    fn offset(self, n: usize) -> Self {
+        Self {
+            clock: self.clock + n as u64,
+            site: self.site,
+        }
+    }
+}
+
+/// One edit to a shared document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Op {
+    /// Insert `text` after the character `origin` (`None` for the start of
+    /// the document). Its characters get consecutive ids starting at `id`.
+    Insert {
+        id: OpId,
+        origin: Option<OpId>,
+        text: String,
+    },
+    /// Delete the characters with these ids
+    Delete { ids: Vec<OpId> },
+}
+
+/// A character of the document, possibly deleted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Item {
+    pub id: OpId,
+    pub origin: Option<OpId>,
+    pub ch: char,
+    pub deleted: bool,
+}
+
+/// A change to the visible text caused by applying an operation, in byte
+/// offsets of the text at the time it is applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextEdit {
+    Insert { position: usize, text: String },
+    Delete { range: Range<usize> },
+}
+
+/// One replica of a shared document
+#[derive(Debug, Clone)]
+pub struct Document {
+    site: u32,
+    clock: u64,
+    items: Vec<Item>,
+}
+
+impl Document {
+    /// An empty document edited from `site`
+    pub fn new(site: u32) -> Self {
+        Self {
+            site,
+            clock: 0,
+            items: Vec::new(),
+        }
+    }
+
+    /// A document holding `text`, as the first replica of a session
+    pub fn from_text(site: u32, text: &str) -> Self {
+        let mut doc = Self::new(site);
+        if !text.is_empty() {
+            doc.local_insert(0, text);
+        }
+        doc
+    }
+
+    /// A replica built from another replica's [`Document::items`]
+    pub fn from_items(site: u32, items: Vec<Item>) -> Self {
+        let clock = items.iter().map(|item| item.id.clock).max().unwrap_or(0);
+        Self { site, clock, items }
+    }
+
+    /// The site this replica edits as
+    pub fn site(&self) -> u32 {
+        self.site
+    }
+
+    /// Every character of the document, including tombstones
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// The visible text
+    pub fn text(&self) -> String {
+        self.visible().map(|item| item.ch).collect()
+    }
+
+    fn visible(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter().filter(|item| !item.deleted)
+    }
+
+    fn index_of(&self, id: OpId) -> Option<usize> {
+        self.items.iter().position(|item| item.id == id)
+    }
+
+    /// The character just before byte offset `position` of the visible text,
+    /// which anchors a cursor or insertion there
+    pub fn anchor_at(&self, position: usize) -> Option<OpId> {
+        let mut offset = 0;
+        let mut anchor = None;
+        for item in self.visible() {
+            if offset >= position {
+                break;
+            }
+            offset += item.ch.len_utf8();
+            anchor = Some(item.id);
+        }
+        anchor
+    }
+
+    /// Byte offset just after the character `anchor`, or `None` if this
+    /// replica has not seen it
+    ///
+    /// A deleted anchor still resolves, to where it used to be.
+    pub fn position_after(&self, anchor: Option<OpId>) -> Option<usize> {
+        let Some(anchor) = anchor else {
+            return Some(0);
+        };
+        let mut offset = 0;
+        for item in &self.items {
+            if !item.deleted {
+                offset += item.ch.len_utf8();
+            }
+            if item.id == anchor {
+                return Some(offset);
+            }
+        }
+        None
+    }
+
+    /// Insert `text` at byte offset `position`, returning the operation to
+    /// send to other replicas
+    pub fn local_insert(&mut self, position: usize, text: &str) -> Op {
+        let op = Op::Insert {
+            id: OpId {
+                clock: self.clock + 1,
+                site: self.site,
+            },
+            origin: self.anchor_at(position),
+            text: text.to_string(),
+        };
+        self.apply(&op);
+        op
+    }
+
+    /// Delete the visible text in byte `range`, returning the operation to
+    /// send to other replicas
+    pub fn local_delete(&mut self, range: Range<usize>) -> Op {
+        let mut offset = 0;
+        let mut ids = Vec::new();
+        for item in self.items.iter_mut().filter(|item| !item.deleted) {
+            if offset >= range.end {
+                break;
+            }
+            if offset >= range.start {
+                item.deleted = true;
+                ids.push(item.id);
+            }
+            offset += item.ch.len_utf8();
+        }
+        Op::Delete { ids }
+    }
+
+    /// Whether every character `op` refers to is known, so it can be applied
+    pub fn can_apply(&self, op: &Op) -> bool {
+        match op {
+            Op::Insert { origin, .. } => origin.is_none_or(|id| self.index_of(id).is_some()),
+            Op::Delete { ids } => {
+                let known: HashSet<OpId> = self.items.iter().map(|item| item.id).collect();
+                ids.iter().all(|id| known.contains(id))
+            }
+        }
+    }
+
+    /// Apply an operation, returning the edits it makes to the visible text
+    ///
+    /// Applying an operation twice has no further effect. Callers must check
+    /// [`Document::can_apply`] first; an insert whose origin is unknown is
+    /// dropped.
+    pub fn apply(&mut self, op: &Op) -> Vec<TextEdit> {
+        match op {
+            Op::Insert { id, origin, text } => {
+                let count = text.chars().count();
+                self.clock = self.clock.max(id.clock + count.saturating_sub(1) as u64);
+                self.integrate_insert(*id, *origin, text)
+            }
+            Op::Delete { ids } => self.integrate_delete(ids),
+        }
+    }
+
+    fn integrate_insert(&mut self, id: OpId, origin: Option<OpId>, text: &str) -> Vec<TextEdit> {
+        if text.is_empty() || self.index_of(id).is_some() {
+            return Vec::new();
+        }
+        let mut index = match origin {
+            None => 0,
+            Some(origin) => match self.index_of(origin) {
+                Some(index) => index + 1,
+                None => return Vec::new(),
+            },
+        };
+        // Concurrent inserts after the same character are ordered by id,
+        // larger first. Everything with a larger id than this run belongs to
+        // one of them, so the run goes after all of it.
+        while index < self.items.len() && self.items[index].id > id {
+            index += 1;
+        }
+        let position = self.items[..index]
+            .iter()
+            .filter(|item| !item.deleted)
+            .map(|item| item.ch.len_utf8())
+            .sum();
+
+        // Each character of the run follows the previous one
+        let mut previous = origin;
+        let run: Vec<Item> = text
+            .chars()
+            .enumerate()
+            .map(|(n, ch)| {
+                let item = Item {
+                    id: id.offset(n),
+                    origin: previous,
+                    ch,
+                    deleted: false,
+                };
+                previous = Some(item.id);
+                item
+            })
+            .collect();
+        self.items.splice(index..index, run);
+        vec![TextEdit::Insert {
+            position,
+            text: text.to_string(),
+        }]
+    }
+
+    fn integrate_delete(&mut self, ids: &[OpId]) -> Vec<TextEdit> {
+        let index_of: HashMap<OpId, usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (item.id, index))
+            .collect();
+        let mut indices: Vec<usize> = ids
+            .iter()
+            .filter_map(|id| index_of.get(id).copied())
+            .filter(|&index| !self.items[index].deleted)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let mut offsets = Vec::with_capacity(self.items.len());
+        let mut offset = 0;
+        for item in &self.items {
+            offsets.push(offset);
+            if !item.deleted {
+                offset += item.ch.len_utf8();
+            }
+        }
+
+        // Delete from the end so earlier offsets stay valid
+        let mut edits: Vec<TextEdit> = Vec::new();
+        for &index in indices.iter().rev() {
+            let item = &mut self.items[index];
+            item.deleted = true;
+            let range = offsets[index]..offsets[index] + item.ch.len_utf8();
+            match edits.last_mut() {
+                Some(TextEdit::Delete { range: run }) if run.start == range.end => {
+                    run.start = range.start;
+                }
+                _ => edits.push(TextEdit::Delete { range }),
+            }
+        }
+        edits
+    }
+}
+
+/// The edit that turns `old` into `new`: the byte range of `old` to replace
+/// and its replacement, or `None` if they are equal
+pub fn diff(old: &str, new: &str) -> Option<(Range<usize>, String)> {
+    if old == new {
+        return None;
+    }
+    let prefix = old
+        .char_indices()
+        .zip(new.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((index, _), _)| index)
+        .unwrap_or(old.len().min(new.len()));
+    let max_suffix = (old.len() - prefix).min(new.len() - prefix);
+    let suffix = old[prefix..]
+        .chars()
+        .rev()
+        .zip(new[prefix..].chars().rev())
+        .take_while(|(a, b)| a == b)
+        .map(|(c, _)| c.len_utf8())
+        .scan(0, |total, len| {
+            *total += len;
+            Some(*total)
+        })
+        .take_while(|&total| total <= max_suffix)
+        .last()
+        .unwrap_or(0);
+    Some((
+        prefix..old.len() - suffix,
+        new[prefix..new.len() - suffix].to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply `ops` to `doc` in order, checking that each edit it reports keeps
+    /// a plain string in step with the document
+    fn apply_all(doc: &mut Document, ops: &[Op]) {
+        let mut mirror = doc.text();
+        for op in ops {
+            assert!(doc.can_apply(op));
+            for edit in doc.apply(op) {
+                match edit {
+                    TextEdit::Insert { position, text } => mirror.insert_str(position, &text),
+                    TextEdit::Delete { range } => mirror.replace_range(range, ""),
+                }
+            }
+            assert_eq!(mirror, doc.text());
+        }
+    }
+
+    fn replica(doc: &Document, site: u32) -> Document {
+        Document::from_items(site, doc.items().to_vec())
+    }
+
+    #[test]
+    fn test_local_edits() {
+        let mut doc = Document::from_text(0, "hello world");
+        doc.local_insert(5, ",");
+        doc.local_delete(7..12);
+        doc.local_insert(7, "there");
+        assert_eq!(doc.text(), "hello, there");
+        assert_eq!(doc.anchor_at(0), None);
+        assert_eq!(doc.position_after(doc.anchor_at(5)), Some(5));
+    }
+
+    #[test]
+    fn test_concurrent_inserts_converge() {
+        let base = Document::from_text(0, "ac");
+        let mut a = replica(&base, 1);
+        let mut b = replica(&base, 2);
+        let op_a = a.local_insert(1, "XY");
+        let op_b = b.local_insert(1, "é");
+        let op_a2 = a.local_insert(4, "!");
+
+        apply_all(&mut a, std::slice::from_ref(&op_b));
+        apply_all(&mut b, &[op_a, op_a2]);
+        assert_eq!(a.text(), b.text());
+        // The later site wins the tie, so its text comes first
+        assert_eq!(a.text(), "aéXYc!");
+    }
+
+    #[test]
+    fn test_concurrent_delete_and_insert() {
+        let base = Document::from_text(0, "one two three");
+        let mut a = replica(&base, 1);
+        let mut b = replica(&base, 2);
+        let delete = a.local_delete(3..7);
+        let insert = b.local_insert(5, "w");
+        let overlapping = b.local_delete(2..5);
+
+        apply_all(&mut a, &[insert, overlapping]);
+        apply_all(&mut b, &[delete]);
+        assert_eq!(a.text(), b.text());
+        assert_eq!(a.text(), "onw three");
+    }
+
+    #[test]
+    fn test_missing_dependency_and_duplicates() {
+        let base = Document::from_text(0, "x");
+        let mut a = replica(&base, 1);
+        let mut b = replica(&base, 2);
+        let first = a.local_insert(1, "y");
+        let second = a.local_insert(2, "z");
+        assert!(!b.can_apply(&second));
+
+        apply_all(&mut b, &[first.clone(), second]);
+        assert!(b.apply(&first).is_empty());
+        assert_eq!(b.text(), "xyz");
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("abc", "abc"), None);
+        assert_eq!(diff("abc", "abXc"), Some((2..2, "X".to_string())));
+        assert_eq!(diff("aaa", "aa"), Some((2..3, String::new())));
+        assert_eq!(diff("héllo", "hallo"), Some((1..3, "a".to_string())));
+        assert_eq!(diff("", "new"), Some((0..0, "new".to_string())));
+    }
+}
@@ -0,0 +1,101 @@
+//! Collaborative editing of a buffer over the network
+//!
+//! One editor hosts a session for a buffer and others join it over TCP. Edits
+//! travel as CRDT operations (see [`crdt`]), so concurrent edits merge the same
+//! way on every peer without locking. The host relays everything between
+//! guests, so guests only connect to the host.
+//!
+//! Protocol: one JSON-encoded [`Message`] per line. The host greets a new
+//! guest with [`Message::Welcome`], carrying the document, once the guest
+//! has introduced itself with [`Message::Hello`] and the session token; after
+//! that both sides send operations and cursor positions.
+//!
+//! [`presentation`] is the read-only variant, for viewers who only follow.
+
+pub mod crdt;
+pub mod presentation;
+pub mod session;
+
+use serde::{Deserialize, Serialize};
+
+use crdt::{Item, Op, OpId};
+
+pub use session::{CollabSession, SessionEvent};
+
+/// Site id of the host; guests are numbered from 1 in the order they join
+pub const HOST_SITE: u32 = 0;
+
+/// Most characters a buffer may have to be shared. The CRDT keeps one item
+/// per character, and a `Welcome` sends them all.
+pub const MAX_SHARED_CHARS: usize = 64 * 1024;
+
+/// A position in the shared document, as the character it follows (`None`
+/// for the start), so it stays put while other peers edit around it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Caret {
+    pub after: Option<OpId>,
+}
+
+/// A message between peers of a session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// First message from a guest, with the session token the host made up
+    Hello { user: String, token: String },
+    /// The host's first message to a guest: the guest's site id, the name
+    /// and language of the shared buffer and the whole document
+    Welcome {
+        site: u32,
+        name: String,
+        language: String,
+        items: Vec<Item>,
+    },
+    /// An edit
+    Op { op: Op },
+    /// Where a peer's cursor is, and the other end of its selection
+    Cursor {
+        site: u32,
+        user: String,
+        position: Caret,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<Caret>,
+    },
+    /// A peer left the session
+    Leave { site: u32 },
+}
+
+/// Parse one protocol line into a message
+fn parse_message_line(line: &str) -> Option<Message> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(message) => Some(message),
+        Err(e) => {
+            tracing::warn!("Ignoring malformed collaboration message: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let message = Message::Cursor {
+            site: 2,
+            user: "ana".to_string(),
+            position: Caret {
+                after: Some(OpId { clock: 4, site: 1 }),
+            },
+            anchor: Some(Caret { after: None }),
+        };
+        let line = serde_json::to_string(&message).unwrap();
+        assert_eq!(parse_message_line(&line), Some(message));
+        assert!(parse_message_line("").is_none());
+        assert!(parse_message_line("{\"type\":\"bogus\"}").is_none());
+    }
+}
@@ -0,0 +1,346 @@
+//! Read-only broadcasting of what a presenter is looking at
+//!
+//! A lighter sibling of collaboration sessions: the presenter's buffer, scroll
+//! position and cursor are streamed to any number of viewers, who can't edit.
+//! The stream is Server-Sent Events over HTTP, so opening the address in a
+//! browser shows a page that follows along, and another editor follows with
+//! [`PresentationViewer`]. Viewers that connect late first get the latest
+//! buffer and view.
+
+use super::session::wake_addr;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// How long following waits for the presenter to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Page served to browsers
+const VIEWER_PAGE: &str = include_str!("presentation.html");
+
+/// Path of the event stream
+const EVENTS_PATH: &str = "/events";
+
+/// What viewers are sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresentationMessage {
+    /// The presented buffer, sent when it changes or another one is shown
+    Buffer { name: String, text: String },
+    /// The first visible line, and the cursor and selection anchor as byte
+    /// offsets
+    View {
+        top_line: usize,
+        cursor: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<usize>,
+    },
+}
+
+/// The latest messages, and the queues of connected viewers
+#[derive(Default)]
+struct Broadcast {
+    buffer: Option<String>,
+    view: Option<String>,
+    viewers: Vec<mpsc::Sender<String>>,
+}
+
+/// Serves the presentation to viewers
+pub struct PresentationServer {
+    local_addr: SocketAddr,
+    broadcast: Arc<Mutex<Broadcast>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PresentationServer {
+    /// Listen for viewers on `addr` (e.g. `127.0.0.1:7071`)
+    pub fn start(addr: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let broadcast = Arc::new(Mutex::new(Broadcast::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_broadcast = broadcast.clone();
+        let thread_shutdown = shutdown.clone();
+        let thread = std::thread::Builder::new()
+            .name("presentation-accept".to_string())
+            .spawn(move || accept_loop(listener, thread_broadcast, thread_shutdown))?;
+
+        tracing::info!("Presenting on {}", local_addr);
+        Ok(Self {
+            local_addr,
+            broadcast,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Number of connected viewers
+    pub fn viewer_count(&self) -> usize {
+        self.broadcast
+            .lock()
+            .map(|broadcast| broadcast.viewers.len())
+            .unwrap_or(0)
+    }
+
+    /// Send `message` to every viewer, and remember it for later ones
+    pub fn publish(&self, message: &PresentationMessage) {
+        let event = match serde_json::to_string(message) {
+            Ok(json) => format!("data: {}\n\n", json),
+            Err(e) => {
+                tracing::warn!("Failed to encode presentation message: {}", e);
+                return;
+            }
+        };
+        let Ok(mut broadcast) = self.broadcast.lock() else {
+            return;
+        };
+        match message {
+            PresentationMessage::Buffer { .. } => broadcast.buffer = Some(event.clone()),
+            PresentationMessage::View { .. } => broadcast.view = Some(event.clone()),
+        }
+        broadcast
+            .viewers
+            .retain(|viewer| viewer.send(event.clone()).is_ok());
+    }
+}
+
+impl Drop for PresentationServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Dropping the queues ends the viewer connections
+        if let Ok(mut broadcast) = self.broadcast.lock() {
+            broadcast.viewers.clear();
+        }
+        // Wake the blocking accept() so the thread notices the shutdown flag
+        let _ = TcpStream::connect_timeout(&wake_addr(self.local_addr), CONNECT_TIMEOUT);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn accept_loop(listener: TcpListener, broadcast: Arc<Mutex<Broadcast>>, shutdown: Arc<AtomicBool>) {
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                let broadcast = broadcast.clone();
+                let spawned = std::thread::Builder::new()
+                    .name("presentation-viewer".to_string())
+                    .spawn(move || {
+                        if let Err(e) = handle_viewer(stream, broadcast) {
+                            tracing::debug!("Presentation viewer disconnected: {}", e);
+                        }
+                    });
+                if let Err(e) = spawned {
+                    tracing::warn!("Presentation viewer thread failed: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("Presentation accept failed: {}", e),
+        }
+    }
+}
+
+/// Answer one HTTP request: the event stream, or the viewer page
+fn handle_viewer(mut stream: TcpStream, broadcast: Arc<Mutex<Broadcast>>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Skip the headers
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next(), parts.next());
+    match (method, path) {
+        (Some("GET"), Some(EVENTS_PATH)) => {}
+        (Some("GET"), Some("/")) => {
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                VIEWER_PAGE.len(),
+                VIEWER_PAGE
+            )?;
+            return Ok(());
+        }
+        _ => {
+            stream.write_all(
+                b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            )?;
+            return Ok(());
+        }
+    }
+
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    let (sender, events) = mpsc::channel();
+    {
+        let Ok(mut broadcast) = broadcast.lock() else {
+            return Ok(());
+        };
+        for event in broadcast.buffer.iter().chain(broadcast.view.iter()) {
+            let _ = sender.send(event.clone());
+        }
+        broadcast.viewers.push(sender);
+    }
+    drop(broadcast);
+    for event in events {
+        stream.write_all(event.as_bytes())?;
+    }
+    stream.shutdown(Shutdown::Both)
+}
+
+/// Follows a presentation from another editor
+pub struct PresentationViewer {
+    stream: TcpStream,
+    messages: mpsc::Receiver<PresentationMessage>,
+}
+
+impl PresentationViewer {
+    /// Connect to the presentation at `addr`
+    pub fn connect(addr: &str) -> io::Result<Self> {
+        let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "address did not resolve")
+        })?;
+        let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nAccept: text/event-stream\r\n\r\n",
+            EVENTS_PATH, addr
+        )?;
+        let (sender, messages) = mpsc::channel();
+        let reader = stream.try_clone()?;
+        std::thread::Builder::new()
+            .name("presentation-follow".to_string())
+            .spawn(move || read_events(reader, sender))?;
+        Ok(Self { stream, messages })
+    }
+
+    /// Messages received since the last poll, or `None` once the presenter
+    /// has stopped and every message has been handed out
+    pub fn poll(&self) -> Option<Vec<PresentationMessage>> {
+        let mut messages = Vec::new();
+        loop {
+            match self.messages.try_recv() {
+                Ok(message) => messages.push(message),
+                Err(mpsc::TryRecvError::Empty) => return Some(messages),
+                Err(mpsc::TryRecvError::Disconnected) if messages.is_empty() => return None,
+                Err(mpsc::TryRecvError::Disconnected) => return Some(messages),
+            }
+        }
+    }
+}
+
+impl Drop for PresentationViewer {
+    fn drop(&mut self) {
+        // Ends the reader thread
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+fn read_events(stream: TcpStream, sender: mpsc::Sender<PresentationMessage>) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    let mut in_body = false;
+    loop {
+        line.clear();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let line = line.trim_end();
+        if !in_body {
+            // Status line and headers end with a blank line
+            in_body = line.is_empty();
+            continue;
+        }
+        let Some(data) = line.strip_prefix("data: ") else {
+            continue;
+        };
+        match serde_json::from_str(data) {
+            Ok(message) => {
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+            Err(e) => tracing::warn!("Ignoring malformed presentation message: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::time::Instant;
+
+    /// Poll `viewer` until it has received `count` messages
+    fn wait_for_messages(viewer: &PresentationViewer, count: usize) -> Vec<PresentationMessage> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut messages = Vec::new();
+        while messages.len() < count {
+            assert!(Instant::now() < deadline, "missing presentation messages");
+            messages.extend(viewer.poll().unwrap());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        messages
+    }
+
+    #[test]
+    fn test_viewer_follows_presentation() {
+        let server = PresentationServer::start("127.0.0.1:0").unwrap();
+        let buffer = PresentationMessage::Buffer {
+            name: "notes.md".to_string(),
+            text: "# Notes\n".to_string(),
+        };
+        server.publish(&buffer);
+
+        // A late viewer still gets the buffer
+        let viewer = PresentationViewer::connect(&server.local_addr().to_string()).unwrap();
+        assert_eq!(wait_for_messages(&viewer, 1), vec![buffer]);
+
+        let view = PresentationMessage::View {
+            top_line: 0,
+            cursor: 2,
+            anchor: Some(0),
+        };
+        server.publish(&view);
+        assert_eq!(wait_for_messages(&viewer, 1), vec![view]);
+        assert_eq!(server.viewer_count(), 1);
+
+        drop(server);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while viewer.poll().is_some() {
+            assert!(Instant::now() < deadline, "viewer did not see the end");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_serves_viewer_page() {
+        let server = PresentationServer::start("127.0.0.1:0").unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("new EventSource(\"/events\")"));
+    }
+}
@@ -0,0 +1,588 @@
+//! TCP transport for collaboration sessions
+//!
+//! Every connection gets a reader thread that parses incoming lines and a
+//! writer thread that drains a queue of outgoing ones, so a slow peer never
+//! blocks the editor. Everything received is queued for [`CollabSession::poll`],
+//! which the editor calls from its main loop.
+//!
+//! The host makes up a random token for each session. A guest's first line
+//! must be a [`Message::Hello`] carrying it; a connection that doesn't send
+//! one in time is closed before the editor hears of it. Only a few
+//! connections may be waiting to send their Hello at once.
+//!
+//! The transport is plaintext: the token and every edit cross the network
+//! unencrypted, so anyone who can watch the traffic can read the buffer and
+//! join. Sessions are meant for a trusted network, or a tunnel such as SSH.
+
+use super::{parse_message_line, Message, HOST_SITE, MAX_SHARED_CHARS};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long joining waits for the host to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a new connection has to send its whole Hello
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest Hello accepted from a connection that hasn't shown the token yet
+const MAX_HELLO_LEN: usize = 4 * 1024;
+
+/// Most connections that may be waiting to send their Hello at once; more
+/// are closed right away
+const MAX_PENDING_HANDSHAKES: usize = 16;
+
+/// Longest line accepted from a peer. A `Welcome` carries the whole document,
+/// at up to about 160 bytes per character.
+const MAX_LINE_LEN: usize = MAX_SHARED_CHARS * 160;
+
+/// Something that happened in a session since the last poll
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// A guest connected (host only); it must be sent a `Welcome` first
+    Joined { site: u32 },
+    /// A message arrived from a peer
+    Message { from: u32, message: Message },
+    /// A peer disconnected. For a guest, the host leaving ends the session.
+    Left { site: u32 },
+}
+
+/// What the connection threads hand to the editor thread
+enum Incoming {
+    Connected { site: u32, stream: TcpStream },
+    Message { from: u32, message: Message },
+    Disconnected { site: u32 },
+}
+
+/// A connected peer
+struct Peer {
+    stream: TcpStream,
+    outgoing: mpsc::Sender<String>,
+}
+
+/// The network side of a collaboration session, either hosting or joined
+pub struct CollabSession {
+    hosting: bool,
+    local_addr: Option<SocketAddr>,
+    /// What guests must send in their Hello (host only)
+    token: Option<String>,
+    incoming: mpsc::Receiver<Incoming>,
+    peers: HashMap<u32, Peer>,
+    shutdown: Arc<AtomicBool>,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+impl CollabSession {
+    /// Listen for guests on `addr` (e.g. `127.0.0.1:7070`) with a new
+    /// session token
+    pub fn host(addr: &str) -> io::Result<Self> {
+        let token = new_token()?;
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let (sender, incoming) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = shutdown.clone();
+        let thread_token = token.clone();
+        let accept_thread = std::thread::Builder::new()
+            .name("collab-accept".to_string())
+            .spawn(move || accept_loop(listener, sender, thread_shutdown, thread_token))?;
+
+        tracing::info!("Hosting collaboration session on {}", local_addr);
+        Ok(Self {
+            hosting: true,
+            local_addr: Some(local_addr),
+            token: Some(token),
+            incoming,
+            peers: HashMap::new(),
+            shutdown,
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    /// Connect to the session hosted at `addr`. The first message sent must
+    /// be a Hello with the session's token.
+    pub fn join(addr: &str) -> io::Result<Self> {
+        let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "address did not resolve")
+        })?;
+        let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+        let (sender, incoming) = mpsc::channel();
+        let mut session = Self {
+            hosting: false,
+            local_addr: None,
+            token: None,
+            incoming,
+            peers: HashMap::new(),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            accept_thread: None,
+        };
+        spawn_reader(HOST_SITE, stream.try_clone()?, sender)?;
+        session.add_peer(HOST_SITE, stream)?;
+        Ok(session)
+    }
+
+    /// Whether this side hosts the session
+    pub fn is_host(&self) -> bool {
+        self.hosting
+    }
+
+    /// Address the host is listening on
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    /// Token guests must present to join, when hosting
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Number of connected peers
+    pub fn peer_count(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Everything that happened since the last poll, in order
+    pub fn poll(&mut self) -> Vec<SessionEvent> {
+        let mut events = Vec::new();
+        while let Ok(incoming) = self.incoming.try_recv() {
+            match incoming {
+                Incoming::Connected { site, stream } => match self.add_peer(site, stream) {
+                    Ok(()) => events.push(SessionEvent::Joined { site }),
+                    Err(e) => tracing::warn!("Collaboration peer {} failed: {}", site, e),
+                },
+                Incoming::Message { from, message } => {
+                    if self.peers.contains_key(&from) {
+                        events.push(SessionEvent::Message { from, message });
+                    }
+                }
+                Incoming::Disconnected { site } => {
+                    if let Some(peer) = self.peers.remove(&site) {
+                        let _ = peer.stream.shutdown(Shutdown::Both);
+                        events.push(SessionEvent::Left { site });
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// Send `message` to every peer except `except`
+    pub fn broadcast(&self, message: &Message, except: Option<u32>) {
+        let Some(line) = encode(message) else {
+            return;
+        };
+        for (&site, peer) in &self.peers {
+            if Some(site) != except {
+                let _ = peer.outgoing.send(line.clone());
+            }
+        }
+    }
+
+    /// Send `message` to one peer
+    pub fn send_to(&self, site: u32, message: &Message) {
+        if let (Some(peer), Some(line)) = (self.peers.get(&site), encode(message)) {
+            let _ = peer.outgoing.send(line);
+        }
+    }
+
+    fn add_peer(&mut self, site: u32, stream: TcpStream) -> io::Result<()> {
+        let (outgoing, lines) = mpsc::channel::<String>();
+        let mut writer = stream.try_clone()?;
+        std::thread::Builder::new()
+            .name(format!("collab-writer-{}", site))
+            .spawn(move || {
+                for line in lines {
+                    if writer.write_all(line.as_bytes()).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        self.peers.insert(site, Peer { stream, outgoing });
+        Ok(())
+    }
+}
+
+impl Drop for CollabSession {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Closing the sockets ends the reader threads; dropping the queues
+        // ends the writer threads
+        for peer in self.peers.values() {
+            let _ = peer.stream.shutdown(Shutdown::Both);
+        }
+        self.peers.clear();
+        // Wake the blocking accept() so the thread notices the shutdown flag
+        if let Some(addr) = self.local_addr {
+            let _ = TcpStream::connect_timeout(&wake_addr(addr), CONNECT_TIMEOUT);
+        }
+        if let Some(thread) = self.accept_thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// An address that reaches a listener bound to `addr`, which may be a
+/// wildcard address
+pub(super) fn wake_addr(addr: SocketAddr) -> SocketAddr {
+    let mut wake = addr;
+    if addr.ip().is_unspecified() {
+        wake.set_ip(match addr {
+            SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    wake
+}
+
+/// A random session token, as hex
+fn new_token() -> io::Result<String> {
+    let mut bytes = [0u8; 16];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| io::Error::other("failed to generate session token"))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn encode(message: &Message) -> Option<String> {
+    match serde_json::to_string(message) {
+        Ok(mut line) => {
+            line.push('\n');
+            Some(line)
+        }
+        Err(e) => {
+            tracing::warn!("Failed to encode collaboration message: {}", e);
+            None
+        }
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    sender: mpsc::Sender<Incoming>,
+    shutdown: Arc<AtomicBool>,
+    token: String,
+) {
+    let mut next_site = HOST_SITE + 1;
+    let pending = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        if shutdown.load(Ordering::SeqCst) {
+            break;
+        }
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("Collaboration accept failed: {}", e);
+                continue;
+            }
+        };
+        if pending.fetch_add(1, Ordering::SeqCst) >= MAX_PENDING_HANDSHAKES {
+            pending.fetch_sub(1, Ordering::SeqCst);
+            tracing::warn!(
+                "Refused collaboration peer {:?}: too many pending connections",
+                stream.peer_addr().ok()
+            );
+            let _ = stream.shutdown(Shutdown::Both);
+            continue;
+        }
+        let site = next_site;
+        next_site += 1;
+        let sender = sender.clone();
+        let token = token.clone();
+        let thread_pending = pending.clone();
+        let spawned = std::thread::Builder::new()
+            .name(format!("collab-reader-{}", site))
+            .spawn(move || {
+                let handshaken = handshake(&stream, &token);
+                thread_pending.fetch_sub(1, Ordering::SeqCst);
+                let Some((reader, hello)) = handshaken else {
+                    return;
+                };
+                // The editor learns about the peer before any of its messages
+                let hello = Incoming::Message {
+                    from: site,
+                    message: hello,
+                };
+                if sender.send(Incoming::Connected { site, stream }).is_ok()
+                    && sender.send(hello).is_ok()
+                {
+                    read_messages(site, reader, sender);
+                }
+            });
+        if let Err(e) = spawned {
+            pending.fetch_sub(1, Ordering::SeqCst);
+            tracing::warn!("Collaboration reader for {} failed: {}", site, e);
+        }
+    }
+}
+
+/// Read a new guest's Hello and check its token. Returns the reader to go on
+/// with and the Hello, or `None` after closing a connection without the token.
+fn handshake(stream: &TcpStream, token: &str) -> Option<(BufReader<TcpStream>, Message)> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let hello = read_hello(&mut reader, Instant::now() + HANDSHAKE_TIMEOUT)
+        .ok()
+        .and_then(|line| parse_message_line(&line));
+    match hello {
+        Some(Message::Hello { user, token: given })
+            if tokens_match(&given, token) && stream.set_read_timeout(None).is_ok() =>
+        {
+            Some((reader, Message::Hello { user, token: given }))
+        }
+        _ => {
+            tracing::warn!(
+                "Refused collaboration peer {:?}: no valid session token",
+                stream.peer_addr().ok()
+            );
+            let _ = stream.shutdown(Shutdown::Both);
+            None
+        }
+    }
+}
+
+/// Read the first line of a new connection, giving up at `deadline`. A
+/// timeout per read would let a peer that trickles in a byte at a time keep
+/// its handshake slot for as long as it likes.
+fn read_hello(reader: &mut BufReader<TcpStream>, deadline: Instant) -> io::Result<String> {
+    let mut line = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        reader.get_ref().set_read_timeout(Some(remaining))?;
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let used = newline.map_or(available.len(), |i| i + 1);
+        line.extend_from_slice(&available[..used]);
+        reader.consume(used);
+        if line.len() > MAX_HELLO_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line longer than {} bytes", MAX_HELLO_LEN),
+            ));
+        }
+        if newline.is_some() {
+            return String::from_utf8(line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+    }
+}
+
+/// Compare tokens in time that doesn't depend on where they first differ, so
+/// the token can't be guessed a byte at a time from how fast guesses fail
+fn tokens_match(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn spawn_reader(site: u32, stream: TcpStream, sender: mpsc::Sender<Incoming>) -> io::Result<()> {
+    std::thread::Builder::new()
+        .name(format!("collab-reader-{}", site))
+        .spawn(move || read_messages(site, BufReader::new(stream), sender))?;
+    Ok(())
+}
+
+/// Queue the messages of a peer until it disconnects
+fn read_messages(site: u32, mut reader: BufReader<TcpStream>, sender: mpsc::Sender<Incoming>) {
+    let mut line = String::new();
+    loop {
+        match read_line_limited(&mut reader, &mut line, MAX_LINE_LEN) {
+            Ok(0) => break,
+            Ok(_) => {
+                if let Some(message) = parse_message_line(&line) {
+                    let incoming = Incoming::Message {
+                        from: site,
+                        message,
+                    };
+                    if sender.send(incoming).is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::debug!("Collaboration peer {} disconnected: {}", site, e);
+                break;
+            }
+        }
+    }
+    let _ = sender.send(Incoming::Disconnected { site });
+}
+
+/// Read a line into `line`, failing once it's longer than `limit` bytes so a
+/// peer that never sends a newline can't make the buffer grow without bound
+fn read_line_limited(
+    reader: &mut impl BufRead,
+    line: &mut String,
+    limit: usize,
+) -> io::Result<usize> {
+    line.clear();
+    let read = reader.by_ref().take(limit as u64 + 1).read_line(line)?;
+    if read > limit && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line longer than {} bytes", limit),
+        ));
+    }
+    Ok(read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Poll `session` until it has reported `count` events
+    fn wait_for_events(session: &mut CollabSession, count: usize) -> Vec<SessionEvent> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut events = Vec::new();
+        while events.len() < count {
+            assert!(Instant::now() < deadline, "missing session events");
+            events.extend(session.poll());
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        events
+    }
+
+    #[test]
+    fn test_host_and_guest_exchange_messages() {
+        let mut host = CollabSession::host("127.0.0.1:0").unwrap();
+        let addr = host.local_addr().unwrap().to_string();
+        let mut guest = CollabSession::join(&addr).unwrap();
+        assert!(host.is_host() && !guest.is_host());
+
+        let hello = Message::Hello {
+            user: "guest".to_string(),
+            token: host.token().unwrap().to_string(),
+        };
+        guest.send_to(HOST_SITE, &hello);
+        assert_eq!(
+            wait_for_events(&mut host, 2),
+            vec![
+                SessionEvent::Joined { site: 1 },
+                SessionEvent::Message {
+                    from: 1,
+                    message: hello
+                }
+            ]
+        );
+
+        host.broadcast(&Message::Leave { site: 7 }, None);
+        assert_eq!(
+            wait_for_events(&mut guest, 1),
+            vec![SessionEvent::Message {
+                from: HOST_SITE,
+                message: Message::Leave { site: 7 }
+            }]
+        );
+
+        drop(host);
+        assert_eq!(
+            wait_for_events(&mut guest, 1),
+            vec![SessionEvent::Left { site: HOST_SITE }]
+        );
+        assert_eq!(guest.peer_count(), 0);
+    }
+
+    #[test]
+    fn test_guest_without_token_is_refused() {
+        let mut host = CollabSession::host("127.0.0.1:0").unwrap();
+        let addr = host.local_addr().unwrap().to_string();
+        let mut guest = CollabSession::join(&addr).unwrap();
+
+        guest.send_to(
+            HOST_SITE,
+            &Message::Hello {
+                user: "guest".to_string(),
+                token: "wrong".to_string(),
+            },
+        );
+        assert_eq!(
+            wait_for_events(&mut guest, 1),
+            vec![SessionEvent::Left { site: HOST_SITE }]
+        );
+        assert!(host.poll().is_empty());
+        assert_eq!(host.peer_count(), 0);
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc124", "abc123"));
+        assert!(!tokens_match("abc12", "abc123"));
+        assert!(!tokens_match("", "abc123"));
+    }
+
+    #[test]
+    fn test_too_many_pending_handshakes_are_closed() {
+        let host = CollabSession::host("127.0.0.1:0").unwrap();
+        let addr = host.local_addr().unwrap();
+        // Connections that never send their Hello
+        let _silent: Vec<TcpStream> = (0..MAX_PENDING_HANDSHAKES)
+            .map(|_| TcpStream::connect(addr).unwrap())
+            .collect();
+
+        let mut extra = TcpStream::connect(addr).unwrap();
+        extra
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let mut buf = [0u8; 1];
+        assert_eq!(extra.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_hello_must_arrive_before_the_deadline() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let trickle = std::thread::spawn(move || {
+            // Each byte arrives well within a per-read timeout
+            for _ in 0..20 {
+                if peer.write_all(b"{").is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(50));
+            }
+        });
+
+        let mut reader = BufReader::new(stream);
+        let started = Instant::now();
+        assert!(read_hello(&mut reader, started + Duration::from_millis(200)).is_err());
+        assert!(started.elapsed() < Duration::from_millis(800));
+        drop(reader);
+        trickle.join().unwrap();
+    }
+
+    #[test]
+    fn test_welcome_for_largest_shared_buffer_fits_a_line() {
+        let text = "\u{1}".repeat(MAX_SHARED_CHARS);
+        let doc = crate::services::collab::crdt::Document::from_text(HOST_SITE, &text);
+        let welcome = Message::Welcome {
+            site: u32::MAX,
+            name: "buffer".to_string(),
+            language: "text".to_string(),
+            items: doc.items().to_vec(),
+        };
+        assert!(serde_json::to_string(&welcome).unwrap().len() < MAX_LINE_LEN);
+    }
+
+    #[test]
+    fn test_read_line_limited() {
+        let mut reader = io::Cursor::new("short\nmuch too long\n");
+        let mut line = String::new();
+        assert_eq!(read_line_limited(&mut reader, &mut line, 8).unwrap(), 6);
+        assert_eq!(line, "short\n");
+        let err = read_line_limited(&mut reader, &mut line, 8).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,236 @@
+//! Transparent editing of encrypted files (`.age`, `.gpg`)
+//!
+//! Files are decrypted and encrypted by piping them through the `age` or `gpg`
+//! command, so the plaintext only ever exists in the editor's memory. gpg files
+//! are unlocked with a passphrase (symmetric encryption). age files are
+//! unlocked with an identity file, and re-encrypted to that identity's
+//! recipient.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::primitives::path_utils::expand_tilde;
+
+/// How a file is encrypted, from its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionScheme {
+    Age,
+    Gpg,
+}
+
+impl EncryptionScheme {
+    /// Scheme of an encrypted file, or `None` for ordinary files
+    pub fn for_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("age") {
+            Some(Self::Age)
+        } else if extension.eq_ignore_ascii_case("gpg") {
+            Some(Self::Gpg)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the key is a secret typed by the user (a gpg passphrase) rather
+    /// than the path of an age identity file
+    pub fn key_is_passphrase(&self) -> bool {
+        matches!(self, Self::Gpg)
+    }
+
+    fn program(&self) -> &'static str {
+        match self {
+            Self::Age => "age",
+            Self::Gpg => "gpg",
+        }
+    }
+}
+
+/// File name with the encryption extension removed (`notes.md.gpg` ->
+/// `notes.md`), used to pick the language of the decrypted text
+pub fn plaintext_name(path: &Path) -> Option<&str> {
+    path.file_stem()?.to_str()
+}
+
+/// Encryption of a buffer's file, and the key to decrypt and re-encrypt it
+///
+/// Locked until the user gives the key; a locked file can't be saved, so an
+/// undecrypted buffer never overwrites the file.
+#[derive(Clone)]
+pub struct FileEncryption {
+    scheme: EncryptionScheme,
+    key: Option<String>,
+}
+
+impl fmt::Debug for FileEncryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the passphrase
+        f.debug_struct("FileEncryption")
+            .field("scheme", &self.scheme)
+            .field("locked", &self.key.is_none())
+            .finish()
+    }
+}
+
+impl FileEncryption {
+    /// Encryption whose key is not known yet
+    pub fn locked(scheme: EncryptionScheme) -> Self {
+        Self { scheme, key: None }
+    }
+
+    /// Encryption unlocked with `key`: a gpg passphrase or an age identity file
+    pub fn with_key(scheme: EncryptionScheme, key: &str) -> Self {
+        let key = match scheme {
+            EncryptionScheme::Age => expand_tilde(key.trim()).to_string_lossy().into_owned(),
+            EncryptionScheme::Gpg => key.to_string(),
+        };
+        Self {
+            scheme,
+            key: Some(key),
+        }
+    }
+
+    pub fn scheme(&self) -> EncryptionScheme {
+        self.scheme
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.key.is_none()
+    }
+
+    /// Decrypt the contents of an encrypted file
+    pub fn decrypt(&self, ciphertext: &[u8]) -> io::Result<Vec<u8>> {
+        self.run("--decrypt", ciphertext)
+    }
+
+    /// Encrypt text for writing to the file
+    pub fn encrypt(&self, plaintext: &[u8]) -> io::Result<Vec<u8>> {
+        match self.scheme {
+            EncryptionScheme::Age => self.run("--encrypt", plaintext),
+            EncryptionScheme::Gpg => self.run("--symmetric", plaintext),
+        }
+    }
+
+    /// Pipe `input` through the encryption program in `mode`
+    fn run(&self, mode: &str, input: &[u8]) -> io::Result<Vec<u8>> {
+        let Some(key) = &self.key else {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the file has not been decrypted",
+            ));
+        };
+
+        let mut command = Command::new(self.scheme.program());
+        // gpg reads the passphrase from the first line of stdin, followed by
+        // the data, so neither goes through the command line or a file
+        let stdin_prefix = match self.scheme {
+            EncryptionScheme::Age => {
+                command.args([mode, "--identity", key.as_str()]);
+                None
+            }
+            EncryptionScheme::Gpg => {
+                command.args([
+                    "--batch",
+                    "--quiet",
+                    "--yes",
+                    "--no-symkey-cache",
+                    "--pinentry-mode",
+                    "loopback",
+                    "--passphrase-fd",
+                    "0",
+                    mode,
+                ]);
+                Some(format!("{}\n", key))
+            }
+        };
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("cannot run {}: {}", self.scheme.program(), e),
+                )
+            })?;
+
+        // Write from another thread so a full stdout pipe can't deadlock us
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = input.to_vec();
+        let writer = std::thread::spawn(move || -> io::Result<()> {
+            if let Some(prefix) = stdin_prefix {
+                stdin.write_all(prefix.as_bytes())?;
+            }
+            stdin.write_all(&input)
+        });
+        let output = child.wait_with_output()?;
+        // A program that fails early closes stdin; its exit status says why
+        let written = writer
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("stdin writer panicked")));
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr.trim();
+            return Err(io::Error::other(if message.is_empty() {
+                format!(
+                    "{} exited with {:?}",
+                    self.scheme.program(),
+                    output.status.code()
+                )
+            } else {
+                message.to_string()
+            }));
+        }
+        written?;
+        Ok(output.stdout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_for_path() {
+        assert_eq!(
+            EncryptionScheme::for_path(Path::new("notes.md.gpg")),
+            Some(EncryptionScheme::Gpg)
+        );
+        assert_eq!(
+            EncryptionScheme::for_path(Path::new("/tmp/secrets.AGE")),
+            Some(EncryptionScheme::Age)
+        );
+        assert_eq!(EncryptionScheme::for_path(Path::new("notes.md")), None);
+        assert_eq!(
+            plaintext_name(Path::new("dir/notes.md.gpg")),
+            Some("notes.md")
+        );
+    }
+
+    #[test]
+    fn test_locked_file_cannot_be_encrypted() {
+        let encryption = FileEncryption::locked(EncryptionScheme::Gpg);
+        assert!(encryption.is_locked());
+        assert!(encryption.encrypt(b"secret").is_err());
+        let unlocked = FileEncryption::with_key(EncryptionScheme::Gpg, "hunter2");
+        assert!(!format!("{:?}", unlocked).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_gpg_roundtrip() {
+        if Command::new("gpg").arg("--version").output().is_err() {
+            eprintln!("Skipping test: gpg is not installed");
+            return;
+        }
+        let encryption = FileEncryption::with_key(EncryptionScheme::Gpg, "correct horse");
+        let ciphertext = encryption.encrypt(b"top secret\n").unwrap();
+        assert!(!ciphertext.windows(6).any(|w| w == b"secret"));
+        assert_eq!(encryption.decrypt(&ciphertext).unwrap(), b"top secret\n");
+
+        let wrong = FileEncryption::with_key(EncryptionScheme::Gpg, "battery staple");
+        assert!(wrong.decrypt(&ciphertext).is_err());
+    }
+}
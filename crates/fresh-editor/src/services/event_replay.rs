@@ -0,0 +1,431 @@
+//! Recording input to the event log, and reading it back for `--replay`
+//!
+//! With `--event-log <FILE>`, every key press, mouse event, paste and resize
+//! is written to the log along with when it happened, next to the buffer
+//! events the log already streams:
+//!
+//! ```text
+//! {"type":"start","width":120,"height":40}
+//! {"type":"input","elapsed_ms":812,"input":"key","key":"a","modifiers":0}
+//! {"type":"input","elapsed_ms":1204,"input":"mouse","kind":"down:left","column":10,"row":3,"modifiers":0}
+//! ```
+//!
+//! `--replay <FILE>` feeds those inputs back, with the same gaps between
+//! them, to an editor opened on the same files with a terminal of the same
+//! size. Everything else in the log is ignored.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+    MouseEvent, MouseEventKind,
+};
+use serde::{Deserialize, Serialize};
+
+/// Terminal size used when the log doesn't record one
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// An input event, in a form that can be written to the log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "input", rename_all = "snake_case")]
+pub enum Input {
+    Key {
+        key: String,
+        modifiers: u8,
+    },
+    Mouse {
+        kind: String,
+        column: u16,
+        row: u16,
+        modifiers: u8,
+    },
+    Paste {
+        text: String,
+    },
+    Resize {
+        width: u16,
+        height: u16,
+    },
+    FocusLost,
+    FocusGained,
+}
+
+impl Input {
+    /// The loggable form of `event`, if it's one that's replayed
+    pub fn from_event(event: &CrosstermEvent) -> Option<Self> {
+        match event {
+            CrosstermEvent::Key(key) if key.kind == KeyEventKind::Press => Some(Input::Key {
+                key: key_name(key.code)?,
+                modifiers: key.modifiers.bits(),
+            }),
+            CrosstermEvent::Key(_) => None,
+            CrosstermEvent::Mouse(mouse) => Some(Input::Mouse {
+                kind: mouse_kind_name(mouse.kind),
+                column: mouse.column,
+                row: mouse.row,
+                modifiers: mouse.modifiers.bits(),
+            }),
+            CrosstermEvent::Paste(text) => Some(Input::Paste { text: text.clone() }),
+            CrosstermEvent::Resize(width, height) => Some(Input::Resize {
+                width: *width,
+                height: *height,
+            }),
+            CrosstermEvent::FocusLost => Some(Input::FocusLost),
+            CrosstermEvent::FocusGained => Some(Input::FocusGained),
+        }
+    }
+
+    /// The event to replay, or `None` if it can't be read
+    pub fn to_event(&self) -> Option<CrosstermEvent> {
+        Some(match self {
+            Input::Key { key, modifiers } => CrosstermEvent::Key(KeyEvent::new(
+                parse_key_name(key)?,
+                KeyModifiers::from_bits_truncate(*modifiers),
+            )),
+            Input::Mouse {
+                kind,
+                column,
+                row,
+                modifiers,
+            } => CrosstermEvent::Mouse(MouseEvent {
+                kind: parse_mouse_kind(kind)?,
+                column: *column,
+                row: *row,
+                modifiers: KeyModifiers::from_bits_truncate(*modifiers),
+            }),
+            Input::Paste { text } => CrosstermEvent::Paste(text.clone()),
+            Input::Resize { width, height } => CrosstermEvent::Resize(*width, *height),
+            Input::FocusLost => CrosstermEvent::FocusLost,
+            Input::FocusGained => CrosstermEvent::FocusGained,
+        })
+    }
+}
+
+/// An input event and when it happened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedInput {
+    /// Milliseconds since the log was started
+    pub elapsed_ms: u64,
+    #[serde(flatten)]
+    pub input: Input,
+}
+
+/// A record in the log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Record {
+    Start { width: u16, height: u16 },
+    Input(RecordedInput),
+}
+
+/// Writes input events to the event log
+#[derive(Debug)]
+pub struct InputRecorder {
+    file: File,
+    started: Instant,
+}
+
+impl InputRecorder {
+    /// Start a new log at `path`, for a terminal of `width` x `height`
+    pub fn create(path: &Path, width: u16, height: u16, now: Instant) -> io::Result<Self> {
+        File::create(path)?;
+        // The buffer event logs append to the same file, so this has to
+        // append too rather than write over their lines
+        let mut file = OpenOptions::new().append(true).open(path)?;
+        writeln!(file, "# Event Log Stream")?;
+        writeln!(file, "# Started at: {}", chrono::Local::now())?;
+        writeln!(file, "# Format: JSON Lines (one event per line)")?;
+        writeln!(file, "# Replay with: fresh --replay <this file> <files>")?;
+        writeln!(file, "#")?;
+        let mut recorder = Self { file, started: now };
+        recorder.write(&Record::Start { width, height })?;
+        Ok(recorder)
+    }
+
+    /// Write `event` to the log, if it's one that's replayed
+    pub fn record(&mut self, event: &CrosstermEvent, now: Instant) {
+        let Some(input) = Input::from_event(event) else {
+            return;
+        };
+        let record = Record::Input(RecordedInput {
+            elapsed_ms: now.duration_since(self.started).as_millis() as u64,
+            input,
+        });
+        if let Err(e) = self.write(&record) {
+            tracing::trace!("Warning: Failed to write input to event stream: {e}");
+        }
+    }
+
+    fn write(&mut self, record: &Record) -> io::Result<()> {
+        let line = serde_json::to_string(record).map_err(io::Error::other)?;
+        writeln!(self.file, "{line}")?;
+        self.file.flush()
+    }
+}
+
+/// The inputs recorded in an event log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayLog {
+    /// Terminal width and height
+    pub size: (u16, u16),
+    pub inputs: Vec<RecordedInput>,
+}
+
+/// Read the inputs from the event log at `path`
+pub fn read_log(path: &Path) -> io::Result<ReplayLog> {
+    parse_log(BufReader::new(File::open(path)?))
+}
+
+fn parse_log(reader: impl BufRead) -> io::Result<ReplayLog> {
+    let mut log = ReplayLog {
+        size: DEFAULT_SIZE,
+        inputs: Vec::new(),
+    };
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let value: serde_json::Value = serde_json::from_str(line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", number + 1, e),
+            )
+        })?;
+        // Buffer events, keystrokes and render records aren't replayed
+        if !matches!(
+            value.get("type").and_then(|t| t.as_str()),
+            Some("start" | "input")
+        ) {
+            continue;
+        }
+        match serde_json::from_value(value) {
+            Ok(Record::Start { width, height }) => log.size = (width, height),
+            Ok(Record::Input(input)) => log.inputs.push(input),
+            Err(e) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {}", number + 1, e),
+                ))
+            }
+        }
+    }
+    Ok(log)
+}
+
+fn key_name(code: KeyCode) -> Option<String> {
+    Some(match code {
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BackTab".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        _ => return None,
+    })
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    Some(match name {
+        "Backspace" => KeyCode::Backspace,
+        "Enter" => KeyCode::Enter,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Delete" => KeyCode::Delete,
+        "Insert" => KeyCode::Insert,
+        "Esc" => KeyCode::Esc,
+        _ => KeyCode::F(name.strip_prefix('F')?.parse().ok()?),
+    })
+}
+
+fn mouse_kind_name(kind: MouseEventKind) -> String {
+    let button = |button: MouseButton| match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    };
+    match kind {
+        MouseEventKind::Down(b) => format!("down:{}", button(b)),
+        MouseEventKind::Up(b) => format!("up:{}", button(b)),
+        MouseEventKind::Drag(b) => format!("drag:{}", button(b)),
+        MouseEventKind::Moved => "moved".to_string(),
+        MouseEventKind::ScrollDown => "scroll_down".to_string(),
+        MouseEventKind::ScrollUp => "scroll_up".to_string(),
+        MouseEventKind::ScrollLeft => "scroll_left".to_string(),
+        MouseEventKind::ScrollRight => "scroll_right".to_string(),
+    }
+}
+
+fn parse_mouse_kind(name: &str) -> Option<MouseEventKind> {
+    if let Some((action, button)) = name.split_once(':') {
+        let button = match button {
+            "left" => MouseButton::Left,
+            "right" => MouseButton::Right,
+            "middle" => MouseButton::Middle,
+            _ => return None,
+        };
+        return match action {
+            "down" => Some(MouseEventKind::Down(button)),
+            "up" => Some(MouseEventKind::Up(button)),
+            "drag" => Some(MouseEventKind::Drag(button)),
+            _ => None,
+        };
+    }
+    match name {
+        "moved" => Some(MouseEventKind::Moved),
+        "scroll_down" => Some(MouseEventKind::ScrollDown),
+        "scroll_up" => Some(MouseEventKind::ScrollUp),
+        "scroll_left" => Some(MouseEventKind::ScrollLeft),
+        "scroll_right" => Some(MouseEventKind::ScrollRight),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_round_trip() {
+        let events = [
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL)),
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT)),
+            CrosstermEvent::Key(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE)),
+            CrosstermEvent::Key(KeyEvent::new(
+                KeyCode::PageDown,
+                KeyModifiers::ALT | KeyModifiers::SHIFT,
+            )),
+            CrosstermEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::Drag(MouseButton::Right),
+                column: 4,
+                row: 7,
+                modifiers: KeyModifiers::NONE,
+            }),
+            CrosstermEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                column: 0,
+                row: 0,
+                modifiers: KeyModifiers::CONTROL,
+            }),
+            CrosstermEvent::Paste("a\nb".to_string()),
+            CrosstermEvent::Resize(100, 30),
+            CrosstermEvent::FocusLost,
+            CrosstermEvent::FocusGained,
+        ];
+        for event in events {
+            let input = Input::from_event(&event).unwrap();
+            let json = serde_json::to_string(&input).unwrap();
+            let input: Input = serde_json::from_str(&json).unwrap();
+            assert_eq!(input.to_event(), Some(event));
+        }
+
+        // Key releases aren't recorded
+        let mut release = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        assert_eq!(Input::from_event(&CrosstermEvent::Key(release)), None);
+    }
+
+    #[test]
+    fn parse_log_reads_inputs_only() {
+        let log = r#"# Event Log Stream
+#
+{"type":"start","width":120,"height":40}
+{"index":0,"timestamp":"2024-01-01T00:00:00Z","event":{"Insert":{"position":0,"text":"a","cursor_id":0}}}
+{"type":"keystroke","timestamp":"2024-01-01T00:00:00Z","key":"Char('a')","modifiers":"KeyModifiers(0x0)"}
+{"type":"input","elapsed_ms":5,"input":"key","key":"a","modifiers":0}
+{"type":"input","elapsed_ms":9,"input":"resize","width":90,"height":20}
+"#;
+        let log = parse_log(log.as_bytes()).unwrap();
+        assert_eq!(log.size, (120, 40));
+        assert_eq!(
+            log.inputs,
+            vec![
+                RecordedInput {
+                    elapsed_ms: 5,
+                    input: Input::Key {
+                        key: "a".to_string(),
+                        modifiers: 0
+                    }
+                },
+                RecordedInput {
+                    elapsed_ms: 9,
+                    input: Input::Resize {
+                        width: 90,
+                        height: 20
+                    }
+                },
+            ]
+        );
+
+        let err = parse_log(r#"{"type":"input","elapsed_ms":1}"#.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(parse_log("not json".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn recorder_writes_a_replayable_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.log");
+        let start = Instant::now();
+        let mut recorder = InputRecorder::create(&path, 100, 30, start).unwrap();
+        recorder.record(
+            &CrosstermEvent::Paste("hi".to_string()),
+            start + std::time::Duration::from_millis(250),
+        );
+
+        // A buffer event log streaming to the same file
+        let mut events = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(events, r#"{{"index":0,"event":{{"Insert":{{}}}}}}"#).unwrap();
+        recorder.record(
+            &CrosstermEvent::Paste("there".to_string()),
+            start + std::time::Duration::from_millis(300),
+        );
+
+        let log = read_log(&path).unwrap();
+        assert_eq!(log.size, (100, 30));
+        assert_eq!(
+            log.inputs,
+            vec![
+                RecordedInput {
+                    elapsed_ms: 250,
+                    input: Input::Paste {
+                        text: "hi".to_string()
+                    }
+                },
+                RecordedInput {
+                    elapsed_ms: 300,
+                    input: Input::Paste {
+                        text: "there".to_string()
+                    }
+                },
+            ]
+        );
+    }
+}
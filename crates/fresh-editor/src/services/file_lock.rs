@@ -0,0 +1,205 @@
+//! Lock files that stop two editors from changing the same file at once
+//!
+//! Like Vim's swap files, a file is locked when its buffer is first modified
+//! and unlocked when the buffer is closed or the editor exits. Locks live in
+//! the data directory rather than next to the file, one per file, named after
+//! the file's encoded path:
+//!
+//! ```text
+//! ~/.local/share/fresh/locks/
+//! └── home_user_notes.txt.lock   # {"pid": 4242, "locked_at": 1760000000}
+//! ```
+//!
+//! A lock whose process is no longer running was left by a crash; it is
+//! ignored and deleted when found, and swept on startup.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::recovery::types::is_process_running;
+use crate::session::encode_path_for_filename;
+
+/// The editor process holding a lock
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockOwner {
+    pub pid: u32,
+    /// When the lock was taken (Unix seconds)
+    pub locked_at: u64,
+}
+
+/// The locks held by one editor process
+#[derive(Debug)]
+pub struct FileLocks {
+    dir: PathBuf,
+    pid: u32,
+    /// Files this editor has locked
+    held: HashSet<PathBuf>,
+    /// Files this editor won't lock because another editor had them locked
+    skipped: HashSet<PathBuf>,
+}
+
+impl FileLocks {
+    /// Locks for the current process, stored in `dir`
+    pub fn new(dir: PathBuf) -> Self {
+        Self::for_process(dir, std::process::id())
+    }
+
+    /// Locks taken on behalf of process `pid`
+    pub fn for_process(dir: PathBuf, pid: u32) -> Self {
+        Self {
+            dir,
+            pid,
+            held: HashSet::new(),
+            skipped: HashSet::new(),
+        }
+    }
+
+    fn lock_path(&self, path: &Path) -> PathBuf {
+        self.dir
+            .join(format!("{}.lock", encode_path_for_filename(path)))
+    }
+
+    /// Another running editor's lock on `path`, if any
+    pub fn owner(&self, path: &Path) -> Option<LockOwner> {
+        let lock_path = self.lock_path(path);
+        let owner: LockOwner = serde_json::from_slice(&fs::read(&lock_path).ok()?).ok()?;
+        if owner.pid == self.pid {
+            return None;
+        }
+        if !is_process_running(owner.pid) {
+            tracing::debug!("Removing stale lock {:?} of PID {}", lock_path, owner.pid);
+            let _ = fs::remove_file(&lock_path);
+            return None;
+        }
+        Some(owner)
+    }
+
+    /// Whether this editor holds the lock on `path`
+    pub fn is_held(&self, path: &Path) -> bool {
+        self.held.contains(path)
+    }
+
+    /// Whether this editor gave up locking `path`
+    pub fn is_skipped(&self, path: &Path) -> bool {
+        self.skipped.contains(path)
+    }
+
+    /// Files this editor has locked or skipped
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.held.iter().chain(&self.skipped)
+    }
+
+    /// Lock `path` for this editor, replacing any lock left by a crash
+    pub fn acquire(&mut self, path: &Path) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let owner = LockOwner {
+            pid: self.pid,
+            locked_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+        let json = serde_json::to_vec(&owner).map_err(io::Error::other)?;
+        fs::write(self.lock_path(path), json)?;
+        self.held.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Don't lock `path`: another editor has it locked
+    pub fn skip(&mut self, path: &Path) {
+        self.skipped.insert(path.to_path_buf());
+    }
+
+    /// Unlock `path`, e.g. when its buffer is closed
+    pub fn release(&mut self, path: &Path) {
+        self.skipped.remove(path);
+        if self.held.remove(path) {
+            if let Err(e) = fs::remove_file(self.lock_path(path)) {
+                tracing::debug!("Failed to remove lock for {:?}: {}", path, e);
+            }
+        }
+    }
+
+    /// Delete locks left by editors that are no longer running.
+    /// Returns the number of locks deleted.
+    pub fn remove_stale(&self) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "lock") {
+                continue;
+            }
+            let stale = fs::read(&path)
+                .ok()
+                .and_then(|data| serde_json::from_slice::<LockOwner>(&data).ok())
+                .is_none_or(|owner| !is_process_running(owner.pid));
+            if stale && fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+impl Drop for FileLocks {
+    fn drop(&mut self) {
+        for path in std::mem::take(&mut self.held) {
+            let _ = fs::remove_file(self.lock_path(&path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_seen_by_other_editors_until_released() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("locks");
+        let file = temp_dir.path().join("notes.txt");
+
+        let mut ours = FileLocks::new(dir.clone());
+        ours.acquire(&file).unwrap();
+        assert!(ours.is_held(&file));
+        // A lock of our own process doesn't count as another editor's
+        assert_eq!(ours.owner(&file), None);
+
+        let other = FileLocks::for_process(dir.clone(), u32::MAX);
+        assert_eq!(other.owner(&file).map(|o| o.pid), Some(std::process::id()));
+
+        ours.release(&file);
+        assert_eq!(other.owner(&file), None);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_locks_of_dead_processes_are_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("locks");
+        let file = temp_dir.path().join("notes.txt");
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+
+        // Locks dropped without release (a crash) stay on disk
+        let mut crashed = FileLocks::for_process(dir.clone(), child.id());
+        crashed.acquire(&file).unwrap();
+        std::mem::forget(crashed);
+
+        let ours = FileLocks::new(dir.clone());
+        assert_eq!(ours.remove_stale().unwrap(), 1);
+        assert_eq!(ours.owner(&file), None);
+    }
+}
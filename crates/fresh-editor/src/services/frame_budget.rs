@@ -0,0 +1,188 @@
+//! Degrading decorations when frames take too long
+//!
+//! When frames keep taking longer than the budget, the most expensive
+//! decorations are turned off one at a time, in the order of `Layer::ALL`.
+//! Once frames are comfortably fast again they're turned back on, last one
+//! first.
+
+use std::time::Duration;
+
+/// Consecutive frames over the budget before a layer is turned off
+const FRAMES_OVER_BUDGET: u32 = 5;
+
+/// Consecutive frames under half the budget before a layer is turned back on
+const FRAMES_UNDER_BUDGET: u32 = 60;
+
+/// A decoration that's turned off under load
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// Highlighting other occurrences of the word under the cursor, and
+    /// requesting LSP semantic tokens
+    SemanticHighlight,
+    /// LSP inlay hints
+    InlayHints,
+    /// The faded background image
+    BackgroundFade,
+}
+
+impl Layer {
+    /// Layers in the order they're turned off
+    pub const ALL: [Layer; 3] = [
+        Layer::SemanticHighlight,
+        Layer::InlayHints,
+        Layer::BackgroundFade,
+    ];
+}
+
+/// A layer being turned off or back on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetChange {
+    Disabled(Layer),
+    Restored(Layer),
+}
+
+/// Tracks frame times against the budget and which layers are off
+#[derive(Debug, Default)]
+pub struct FrameBudget {
+    /// `None` when there's no budget
+    budget: Option<Duration>,
+    /// Consecutive frames over the budget
+    over: u32,
+    /// Consecutive frames under half the budget
+    under: u32,
+    /// How many layers (from the start of `Layer::ALL`) are off
+    disabled: usize,
+}
+
+impl FrameBudget {
+    /// A budget of `budget_ms` milliseconds per frame (0 = no budget)
+    pub fn new(budget_ms: u64) -> Self {
+        Self {
+            budget: (budget_ms > 0).then(|| Duration::from_millis(budget_ms)),
+            ..Self::default()
+        }
+    }
+
+    /// Change the budget, keeping the layers that are off
+    pub fn set_budget(&mut self, budget_ms: u64) {
+        self.budget = (budget_ms > 0).then(|| Duration::from_millis(budget_ms));
+        self.over = 0;
+        self.under = 0;
+    }
+
+    /// Record that a frame took `elapsed`. Returns the layer to turn off or
+    /// back on, if any.
+    pub fn record(&mut self, elapsed: Duration) -> Option<BudgetChange> {
+        let Some(budget) = self.budget else {
+            // Without a budget, turn everything back on
+            return self.restore();
+        };
+        if elapsed > budget {
+            self.under = 0;
+            self.over += 1;
+            if self.over >= FRAMES_OVER_BUDGET && self.disabled < Layer::ALL.len() {
+                self.over = 0;
+                self.disabled += 1;
+                return Some(BudgetChange::Disabled(Layer::ALL[self.disabled - 1]));
+            }
+        } else if elapsed < budget / 2 {
+            self.over = 0;
+            self.under += 1;
+            if self.under >= FRAMES_UNDER_BUDGET {
+                self.under = 0;
+                return self.restore();
+            }
+        } else {
+            self.over = 0;
+            self.under = 0;
+        }
+        None
+    }
+
+    /// Whether `layer` is off
+    pub fn is_disabled(&self, layer: Layer) -> bool {
+        Layer::ALL[..self.disabled].contains(&layer)
+    }
+
+    /// Whether any layer is off
+    pub fn is_degraded(&self) -> bool {
+        self.disabled > 0
+    }
+
+    fn restore(&mut self) -> Option<BudgetChange> {
+        if self.disabled == 0 {
+            return None;
+        }
+        self.disabled -= 1;
+        Some(BudgetChange::Restored(Layer::ALL[self.disabled]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn degrades_and_restores_in_order() {
+        let mut budget = FrameBudget::new(16);
+        let mut changes = Vec::new();
+        for _ in 0..FRAMES_OVER_BUDGET * 4 {
+            changes.extend(budget.record(ms(40)));
+        }
+        assert_eq!(
+            changes,
+            Layer::ALL.map(BudgetChange::Disabled).to_vec(),
+            "every layer is turned off, in order"
+        );
+        assert!(budget.is_disabled(Layer::BackgroundFade));
+
+        // A frame that's fast, but not fast enough, doesn't count
+        for _ in 0..FRAMES_UNDER_BUDGET - 1 {
+            assert_eq!(budget.record(ms(1)), None);
+        }
+        assert_eq!(budget.record(ms(10)), None);
+        for _ in 0..FRAMES_UNDER_BUDGET - 1 {
+            assert_eq!(budget.record(ms(1)), None);
+        }
+        assert_eq!(
+            budget.record(ms(1)),
+            Some(BudgetChange::Restored(Layer::BackgroundFade))
+        );
+        assert!(budget.is_disabled(Layer::InlayHints));
+        assert!(!budget.is_disabled(Layer::BackgroundFade));
+        assert!(budget.is_degraded());
+    }
+
+    #[test]
+    fn occasional_slow_frames_are_ignored() {
+        let mut budget = FrameBudget::new(16);
+        for _ in 0..100 {
+            for _ in 0..FRAMES_OVER_BUDGET - 1 {
+                assert_eq!(budget.record(ms(40)), None);
+            }
+            assert_eq!(budget.record(ms(1)), None);
+        }
+        assert!(!budget.is_degraded());
+    }
+
+    #[test]
+    fn no_budget() {
+        let mut budget = FrameBudget::new(16);
+        for _ in 0..FRAMES_OVER_BUDGET {
+            budget.record(ms(40));
+        }
+        assert!(budget.is_disabled(Layer::SemanticHighlight));
+
+        budget.set_budget(0);
+        assert_eq!(
+            budget.record(ms(40)),
+            Some(BudgetChange::Restored(Layer::SemanticHighlight))
+        );
+        assert_eq!(budget.record(ms(40)), None);
+        assert!(!budget.is_degraded());
+    }
+}
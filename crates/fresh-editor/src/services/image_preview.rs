@@ -0,0 +1,552 @@
+//! Image previews drawn with the kitty graphics protocol or sixel
+//!
+//! Images are decoded, scaled to the cells they're shown in and encoded for
+//! the terminal on a background thread; until then the caller shows a
+//! placeholder. Each frame the editor says where images go (`place`), and after
+//! ratatui has drawn the frame `write` sends them to the terminal, only when
+//! something changed. Kitty images sit on their own layer and are deleted
+//! with a command; sixel images are pixels in the cells, so removing one
+//! needs a full redraw.
+//!
+//! SVG files are rasterized with resvg. Files over `MAX_IMAGE_FILE_BYTES` or
+//! images over `MAX_IMAGE_DIMENSION` pixels on a side aren't decoded.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+
+use base64::Engine;
+
+/// Largest image file that is previewed
+pub const MAX_IMAGE_FILE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Largest image width or height, in pixels, that is decoded
+pub const MAX_IMAGE_DIMENSION: u32 = 16384;
+
+/// Cell size assumed when the terminal doesn't report its pixel size
+const DEFAULT_CELL_SIZE: (u32, u32) = (8, 16);
+
+/// Decoded images kept around
+const MAX_CACHED_IMAGES: usize = 32;
+
+/// Base64 bytes per kitty graphics escape sequence
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Extensions of the image files that can be previewed
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg"];
+
+/// How the terminal draws images
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+/// Whether `path` looks like an image file that can be previewed
+pub fn is_image_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// An image ready to be written to the terminal
+#[derive(Debug)]
+pub struct EncodedImage {
+    /// Escape sequence drawing the image at the cursor
+    sequence: String,
+    /// Cells covered by the image
+    pub cols: u16,
+    pub rows: u16,
+}
+
+/// State of an image preview
+#[derive(Debug, Clone)]
+pub enum ImageStatus {
+    Loading,
+    Ready(Arc<EncodedImage>),
+    Failed(String),
+}
+
+/// An image to be fitted into `cols` x `rows` cells
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ImageKey {
+    path: PathBuf,
+    cols: u16,
+    rows: u16,
+}
+
+#[derive(Debug, Clone)]
+struct Placement {
+    x: u16,
+    y: u16,
+    image: Arc<EncodedImage>,
+}
+
+impl PartialEq for Placement {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && Arc::ptr_eq(&self.image, &other.image)
+    }
+}
+
+type DecodeResult = (ImageKey, Result<EncodedImage, String>);
+
+/// Decoded images and where they are on screen
+pub struct ImagePreviews {
+    protocol: Option<GraphicsProtocol>,
+    /// Pixel size of a cell
+    cell_size: (u32, u32),
+    images: HashMap<ImageKey, ImageStatus>,
+    sender: Sender<DecodeResult>,
+    receiver: Receiver<DecodeResult>,
+    /// Images placed in the frame being drawn
+    placements: Vec<Placement>,
+    /// Images on screen
+    drawn: Vec<Placement>,
+}
+
+impl std::fmt::Debug for ImagePreviews {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImagePreviews")
+            .field("protocol", &self.protocol)
+            .field("images", &self.images.len())
+            .finish()
+    }
+}
+
+impl Default for ImagePreviews {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl ImagePreviews {
+    pub fn new(protocol: Option<GraphicsProtocol>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            protocol,
+            cell_size: DEFAULT_CELL_SIZE,
+            images: HashMap::new(),
+            sender,
+            receiver,
+            placements: Vec::new(),
+            drawn: Vec::new(),
+        }
+    }
+
+    /// How images are drawn, or None if the terminal can't show them
+    pub fn protocol(&self) -> Option<GraphicsProtocol> {
+        self.protocol
+    }
+
+    pub fn set_protocol(&mut self, protocol: Option<GraphicsProtocol>) {
+        if self.protocol != protocol {
+            self.protocol = protocol;
+            self.images.clear();
+        }
+    }
+
+    /// Start a frame: forget the previous frame's placements and pick up the
+    /// terminal's current cell size
+    pub fn begin_frame(&mut self) {
+        self.placements.clear();
+        if self.protocol.is_some() {
+            self.cell_size = cell_size();
+        }
+    }
+
+    /// The image at `path` fitted into `cols` x `rows` cells. The first
+    /// request starts decoding it in the background.
+    pub fn get(&mut self, path: &Path, cols: u16, rows: u16) -> ImageStatus {
+        let Some(protocol) = self.protocol else {
+            return ImageStatus::Failed(String::new());
+        };
+        let key = ImageKey {
+            path: path.to_path_buf(),
+            cols,
+            rows,
+        };
+        if let Some(status) = self.images.get(&key) {
+            return status.clone();
+        }
+
+        if self.images.len() >= MAX_CACHED_IMAGES {
+            self.images
+                .retain(|_, status| matches!(status, ImageStatus::Loading));
+        }
+        self.images.insert(key.clone(), ImageStatus::Loading);
+        let sender = self.sender.clone();
+        let max_size = (
+            u32::from(cols) * self.cell_size.0,
+            u32::from(rows) * self.cell_size.1,
+        );
+        let cell_size = self.cell_size;
+        std::thread::spawn(move || {
+            let result = load_image(&key.path, max_size, cell_size, protocol);
+            // The editor may be gone by the time decoding finishes
+            let _ = sender.send((key, result));
+        });
+        ImageStatus::Loading
+    }
+
+    /// Pick up images decoded in the background. Returns true if any finished.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok((key, result)) = self.receiver.try_recv() {
+            let status = match result {
+                Ok(image) => ImageStatus::Ready(Arc::new(image)),
+                Err(e) => {
+                    tracing::debug!("Can't preview {}: {}", key.path.display(), e);
+                    ImageStatus::Failed(e)
+                }
+            };
+            self.images.insert(key, status);
+            finished = true;
+        }
+        finished
+    }
+
+    /// Show `image` with its top left corner at cell (`x`, `y`) this frame
+    pub fn place(&mut self, x: u16, y: u16, image: Arc<EncodedImage>) {
+        self.placements.push(Placement { x, y, image });
+    }
+
+    /// Send this frame's images to the terminal if they changed. `cleared`
+    /// says the screen was cleared, which removed any images on it.
+    ///
+    /// Returns true if the screen has to be cleared and redrawn to remove
+    /// sixel images, which are then drawn on the next frame.
+    pub fn write(&mut self, out: &mut impl Write, cleared: bool) -> io::Result<bool> {
+        if cleared {
+            self.drawn.clear();
+        }
+        if self.placements == self.drawn {
+            return Ok(false);
+        }
+        match self.protocol {
+            Some(GraphicsProtocol::Kitty) => {
+                // Delete every image placed before, and free its data
+                out.write_all(b"\x1b_Ga=d,d=A,q=2\x1b\\")?;
+            }
+            Some(GraphicsProtocol::Sixel) if !self.drawn.is_empty() => {
+                self.drawn.clear();
+                return Ok(true);
+            }
+            _ => {}
+        }
+        // Save the cursor, which ratatui has positioned, and restore it after
+        out.write_all(b"\x1b7")?;
+        for placement in &self.placements {
+            write!(out, "\x1b[{};{}H", placement.y + 1, placement.x + 1)?;
+            out.write_all(placement.image.sequence.as_bytes())?;
+        }
+        out.write_all(b"\x1b8")?;
+        out.flush()?;
+        self.drawn = self.placements.clone();
+        Ok(false)
+    }
+}
+
+/// Pixel size of a terminal cell
+fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns).max(1),
+            u32::from(size.height / size.rows).max(1),
+        ),
+        _ => DEFAULT_CELL_SIZE,
+    }
+}
+
+/// Read, decode, scale and encode an image so it fits in `max_size` pixels
+fn load_image(
+    path: &Path,
+    max_size: (u32, u32),
+    cell_size: (u32, u32),
+    protocol: GraphicsProtocol,
+) -> Result<EncodedImage, String> {
+    let len = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+    if len > MAX_IMAGE_FILE_BYTES {
+        return Err(format!(
+            "file is larger than {} MB",
+            MAX_IMAGE_FILE_BYTES >> 20
+        ));
+    }
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let is_svg = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+    let image = if is_svg {
+        rasterize_svg(&bytes, max_size)?
+    } else {
+        let image = decode_image(&bytes)?;
+        let (width, height) = fit_size(image.dimensions(), max_size, false);
+        if (width, height) == image.dimensions() {
+            image
+        } else {
+            image::imageops::resize(&image, width, height, image::imageops::FilterType::Triangle)
+        }
+    };
+
+    let (width, height) = image.dimensions();
+    let sequence = match protocol {
+        GraphicsProtocol::Kitty => kitty_sequence(&image)?,
+        GraphicsProtocol::Sixel => sixel_sequence(&image),
+    };
+    Ok(EncodedImage {
+        sequence,
+        cols: width.div_ceil(cell_size.0).max(1) as u16,
+        rows: height.div_ceil(cell_size.1).max(1) as u16,
+    })
+}
+
+fn decode_image(bytes: &[u8]) -> Result<image::RgbaImage, String> {
+    let mut reader = image::ImageReader::new(io::Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let mut limits = image::Limits::default();
+    limits.max_image_width = Some(MAX_IMAGE_DIMENSION);
+    limits.max_image_height = Some(MAX_IMAGE_DIMENSION);
+    reader.limits(limits);
+    let image = reader.decode().map_err(|e| e.to_string())?;
+    Ok(image.into_rgba8())
+}
+
+fn rasterize_svg(bytes: &[u8], max_size: (u32, u32)) -> Result<image::RgbaImage, String> {
+    use resvg::{tiny_skia, usvg};
+
+    let tree =
+        usvg::Tree::from_data(bytes, &usvg::Options::default()).map_err(|e| e.to_string())?;
+    let size = tree.size();
+    let natural = (size.width().ceil() as u32, size.height().ceil() as u32);
+    // Vector images scale up to fill the space
+    let (width, height) = fit_size(natural, max_size, true);
+    let mut pixmap =
+        tiny_skia::Pixmap::new(width, height).ok_or_else(|| "empty image".to_string())?;
+    let scale = width as f32 / size.width();
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    image::RgbaImage::from_raw(width, height, pixels).ok_or_else(|| "bad image size".to_string())
+}
+
+/// The largest size with the aspect ratio of `size` that fits in `max`,
+/// never growing it unless `upscale` is set
+pub fn fit_size(size: (u32, u32), max: (u32, u32), upscale: bool) -> (u32, u32) {
+    let (width, height) = (size.0.max(1) as f64, size.1.max(1) as f64);
+    let mut scale = (max.0 as f64 / width).min(max.1 as f64 / height);
+    if !upscale {
+        scale = scale.min(1.0);
+    }
+    (
+        ((width * scale).round() as u32).max(1),
+        ((height * scale).round() as u32).max(1),
+    )
+}
+
+/// Kitty graphics protocol: compressed RGBA pixels, sent in base64 chunks.
+/// The cursor doesn't move and the terminal doesn't reply.
+fn kitty_sequence(image: &image::RgbaImage) -> Result<String, String> {
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::fast());
+    encoder
+        .write_all(image.as_raw())
+        .map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+    let data = base64::engine::general_purpose::STANDARD.encode(compressed);
+
+    let (width, height) = image.dimensions();
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(KITTY_CHUNK_SIZE)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    let mut sequence = String::with_capacity(data.len() + chunks.len() * 16 + 64);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=32,o=z,s={},v={},C=1,q=2,m={};{}\x1b\\",
+                width, height, more, chunk
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    Ok(sequence)
+}
+
+/// Sixel: pixels quantized to a 6x6x6 color cube, six rows per band, each
+/// color's run-length encoded columns overprinted. Transparent pixels are
+/// left as they are.
+pub fn sixel_sequence(image: &image::RgbaImage) -> String {
+    const LEVELS: u32 = 6;
+    let level = |channel: u8| (u32::from(channel) * (LEVELS - 1) + 127) / 255;
+    let (width, height) = image.dimensions();
+
+    // Palette index of each pixel, None where transparent
+    let indices: Vec<Option<u16>> = image
+        .pixels()
+        .map(|pixel| {
+            let [r, g, b, a] = pixel.0;
+            (a >= 128).then(|| (level(r) * LEVELS * LEVELS + level(g) * LEVELS + level(b)) as u16)
+        })
+        .collect();
+
+    let mut used = [false; (LEVELS * LEVELS * LEVELS) as usize];
+    for index in indices.iter().flatten() {
+        used[*index as usize] = true;
+    }
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    for (index, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let index = index as u32;
+        let percent = |level: u32| level * 100 / (LEVELS - 1);
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            index,
+            percent(index / (LEVELS * LEVELS)),
+            percent(index / LEVELS % LEVELS),
+            percent(index % LEVELS)
+        ));
+    }
+
+    for band_top in (0..height).step_by(6) {
+        let band_rows = (band_top..(band_top + 6).min(height)).collect::<Vec<_>>();
+        let mut colors: Vec<u16> = band_rows
+            .iter()
+            .flat_map(|&y| &indices[(y * width) as usize..((y + 1) * width) as usize])
+            .flatten()
+            .copied()
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+
+        for (i, color) in colors.iter().enumerate() {
+            if i > 0 {
+                out.push('$');
+            }
+            out.push_str(&format!("#{}", color));
+            let column_bits = (0..width).map(|x| {
+                band_rows.iter().enumerate().fold(0u8, |bits, (bit, &y)| {
+                    if indices[(y * width + x) as usize] == Some(*color) {
+                        bits | (1 << bit)
+                    } else {
+                        bits
+                    }
+                })
+            });
+            push_run_length_encoded(&mut out, column_bits);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append sixel characters for `bits`, collapsing runs into `!count char`
+fn push_run_length_encoded(out: &mut String, bits: impl Iterator<Item = u8>) {
+    let mut run: Option<(u8, usize)> = None;
+    let flush = |out: &mut String, (bits, count): (u8, usize)| {
+        let ch = char::from(0x3f + bits);
+        if count > 3 {
+            out.push_str(&format!("!{}{}", count, ch));
+        } else {
+            out.extend(std::iter::repeat_n(ch, count));
+        }
+    };
+    for b in bits {
+        run = match run {
+            Some((bits, count)) if bits == b => Some((bits, count + 1)),
+            Some(previous) => {
+                flush(out, previous);
+                Some((b, 1))
+            }
+            None => Some((b, 1)),
+        };
+    }
+    if let Some(last) = run {
+        flush(out, last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_image_path() {
+        assert!(is_image_path(Path::new("logo.PNG")));
+        assert!(is_image_path(Path::new("icons/arrow.svg")));
+        assert!(!is_image_path(Path::new("main.rs")));
+        assert!(!is_image_path(Path::new("png")));
+    }
+
+    #[test]
+    fn test_fit_size() {
+        assert_eq!(fit_size((400, 200), (100, 100), false), (100, 50));
+        assert_eq!(fit_size((10, 20), (100, 100), false), (10, 20));
+        assert_eq!(fit_size((10, 20), (100, 100), true), (50, 100));
+    }
+
+    #[test]
+    fn test_sixel_sequence() {
+        // Two red pixels over a transparent and a white one
+        let image = image::RgbaImage::from_raw(
+            2,
+            2,
+            vec![
+                255, 0, 0, 255, 255, 0, 0, 255, //
+                0, 0, 0, 0, 255, 255, 255, 255,
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            sixel_sequence(&image),
+            "\x1bP0;1;0q\"1;1;2;2#180;2;100;0;0#215;2;100;100;100#180@@$#215?A-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_write_placements_only_on_change() {
+        let mut previews = ImagePreviews::new(Some(GraphicsProtocol::Kitty));
+        let image = Arc::new(EncodedImage {
+            sequence: "<img>".to_string(),
+            cols: 2,
+            rows: 1,
+        });
+        let mut out = Vec::new();
+        previews.begin_frame();
+        previews.place(3, 4, image.clone());
+        assert!(!previews.write(&mut out, false).unwrap());
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "\x1b_Ga=d,d=A,q=2\x1b\\\x1b7\x1b[5;4H<img>\x1b8"
+        );
+
+        // Same placement: nothing is written
+        out.clear();
+        previews.begin_frame();
+        previews.place(3, 4, image.clone());
+        previews.write(&mut out, false).unwrap();
+        assert!(out.is_empty());
+
+        // Sixel images can only be removed by redrawing the screen
+        let mut previews = ImagePreviews::new(Some(GraphicsProtocol::Sixel));
+        previews.begin_frame();
+        previews.place(0, 0, image);
+        assert!(!previews.write(&mut out, false).unwrap());
+        previews.begin_frame();
+        assert!(previews.write(&mut out, false).unwrap());
+    }
+}
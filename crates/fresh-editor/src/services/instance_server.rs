@@ -0,0 +1,289 @@
+//! Single-instance support: forward "open file" requests to a running editor
+//!
+//! The first editor started for a data directory owns the recovery session
+//! lock and listens on a Unix socket next to it. When `fresh file.txt` is run
+//! while that instance is alive, the new process sends the requested paths
+//! (with optional line:col) over the socket and exits, instead of starting a
+//! second editor that would fight over the same recovery files.
+//!
+//! Protocol: one JSON-encoded [`OpenRequest`] per line. After the client
+//! closes its write half, the server answers with a single `ok` line.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A request to open a file in the running instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenRequest {
+    /// Absolute path of the file to open
+    pub path: PathBuf,
+    /// 1-based line to jump to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column to jump to (only used together with `line`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl OpenRequest {
+    /// Build a request, resolving relative paths against `cwd` so the
+    /// receiving instance (which has its own working directory) opens the
+    /// same file.
+    pub fn new(path: &Path, line: Option<usize>, column: Option<usize>, cwd: &Path) -> Self {
+        let path = if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            cwd.join(path)
+        };
+        Self { path, line, column }
+    }
+}
+
+/// Parse one protocol line into a request
+fn parse_request_line(line: &str) -> Option<OpenRequest> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(request) => Some(request),
+        Err(e) => {
+            tracing::warn!("Ignoring malformed instance request {:?}: {}", line, e);
+            None
+        }
+    }
+}
+
+#[cfg(unix)]
+pub use unix::{forward_open_requests, InstanceServer};
+
+#[cfg(unix)]
+mod unix {
+    use super::{parse_request_line, OpenRequest};
+    use crate::services::async_bridge::AsyncMessage;
+    use std::io::{self, BufRead, BufReader, Write};
+    use std::net::Shutdown;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{mpsc, Arc};
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    /// How long a client waits for the running instance to acknowledge
+    const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Send open requests to the instance listening on `socket_path`.
+    ///
+    /// Returns `Ok(false)` when nothing is listening (no socket file, or a
+    /// stale one left behind by a crashed editor), so the caller can fall
+    /// back to starting a normal editor.
+    pub fn forward_open_requests(socket_path: &Path, requests: &[OpenRequest]) -> io::Result<bool> {
+        let mut stream = match UnixStream::connect(socket_path) {
+            Ok(stream) => stream,
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::ConnectionRefused
+                ) =>
+            {
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        };
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+        for request in requests {
+            let mut line = serde_json::to_string(request).map_err(io::Error::other)?;
+            line.push('\n');
+            stream.write_all(line.as_bytes())?;
+        }
+        stream.shutdown(Shutdown::Write)?;
+
+        let mut reply = String::new();
+        BufReader::new(&stream).read_line(&mut reply)?;
+        if reply.trim() == "ok" {
+            Ok(true)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unexpected reply from running instance: {:?}", reply.trim()),
+            ))
+        }
+    }
+
+    /// Listener that receives [`OpenRequest`]s from other `fresh` processes
+    /// and hands them to the editor's main loop via the async bridge.
+    ///
+    /// The socket file is removed when the server is dropped.
+    pub struct InstanceServer {
+        socket_path: PathBuf,
+        shutdown: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl InstanceServer {
+        /// Bind `socket_path` and start accepting clients on a background thread.
+        ///
+        /// Fails with `AddrInUse` if another live instance is already listening.
+        pub fn start(socket_path: PathBuf, sender: mpsc::Sender<AsyncMessage>) -> io::Result<Self> {
+            if socket_path.exists() {
+                if UnixStream::connect(&socket_path).is_ok() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AddrInUse,
+                        "another instance is already listening",
+                    ));
+                }
+                // Stale socket from an editor that did not shut down cleanly
+                std::fs::remove_file(&socket_path)?;
+            }
+            if let Some(parent) = socket_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let listener = UnixListener::bind(&socket_path)?;
+            let shutdown = Arc::new(AtomicBool::new(false));
+            let thread_shutdown = shutdown.clone();
+            let thread = std::thread::Builder::new()
+                .name("instance-server".to_string())
+                .spawn(move || accept_loop(listener, sender, thread_shutdown))?;
+
+            tracing::info!("Instance server listening on {:?}", socket_path);
+            Ok(Self {
+                socket_path,
+                shutdown,
+                thread: Some(thread),
+            })
+        }
+
+        /// Path of the socket this server is listening on
+        pub fn socket_path(&self) -> &Path {
+            &self.socket_path
+        }
+    }
+
+    impl Drop for InstanceServer {
+        fn drop(&mut self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+            // Wake the blocking accept() so the thread notices the shutdown flag
+            let _ = UnixStream::connect(&self.socket_path);
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+            let _ = std::fs::remove_file(&self.socket_path);
+        }
+    }
+
+    fn accept_loop(
+        listener: UnixListener,
+        sender: mpsc::Sender<AsyncMessage>,
+        shutdown: Arc<AtomicBool>,
+    ) {
+        for stream in listener.incoming() {
+            if shutdown.load(Ordering::SeqCst) {
+                break;
+            }
+            match stream {
+                Ok(stream) => {
+                    if let Err(e) = handle_client(stream, &sender) {
+                        tracing::warn!("Instance server client error: {}", e);
+                    }
+                }
+                Err(e) => tracing::warn!("Instance server accept failed: {}", e),
+            }
+        }
+    }
+
+    fn handle_client(stream: UnixStream, sender: &mpsc::Sender<AsyncMessage>) -> io::Result<()> {
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut reader = BufReader::new(&stream);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            if let Some(request) = parse_request_line(&line) {
+                if sender.send(AsyncMessage::OpenFileRequest(request)).is_err() {
+                    // Editor is shutting down; the client will fall back to a new editor
+                    return Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "editor is no longer running",
+                    ));
+                }
+            }
+        }
+        (&stream).write_all(b"ok\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_request_resolves_relative_paths() {
+        let cwd = Path::new("/work/project");
+        let req = OpenRequest::new(Path::new("src/main.rs"), Some(10), Some(4), cwd);
+        assert_eq!(req.path, PathBuf::from("/work/project/src/main.rs"));
+        assert_eq!(req.line, Some(10));
+        assert_eq!(req.column, Some(4));
+
+        let abs = OpenRequest::new(Path::new("/etc/hosts"), None, None, cwd);
+        assert_eq!(abs.path, PathBuf::from("/etc/hosts"));
+    }
+
+    #[test]
+    fn test_parse_request_line() {
+        let req = parse_request_line(r#"{"path":"/tmp/a.txt","line":3}"#).unwrap();
+        assert_eq!(req.path, PathBuf::from("/tmp/a.txt"));
+        assert_eq!(req.line, Some(3));
+        assert_eq!(req.column, None);
+
+        assert!(parse_request_line("").is_none());
+        assert!(parse_request_line("not json").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_forward_without_server_returns_false() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("instance.sock");
+        let req = OpenRequest::new(Path::new("/tmp/a.txt"), None, None, dir.path());
+        assert!(!forward_open_requests(&socket, &[req]).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_forward_roundtrip() {
+        use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("instance.sock");
+        let bridge = AsyncBridge::new();
+        let server = InstanceServer::start(socket.clone(), bridge.sender()).unwrap();
+
+        // A second server for the same socket must not steal it
+        assert!(InstanceServer::start(socket.clone(), bridge.sender()).is_err());
+
+        let requests = vec![
+            OpenRequest::new(Path::new("/tmp/a.txt"), Some(2), Some(5), dir.path()),
+            OpenRequest::new(Path::new("/tmp/b.txt"), None, None, dir.path()),
+        ];
+        assert!(forward_open_requests(&socket, &requests).unwrap());
+
+        let received: Vec<OpenRequest> = bridge
+            .try_recv_all()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                AsyncMessage::OpenFileRequest(req) => Some(req),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, requests);
+
+        drop(server);
+        assert!(!socket.exists());
+    }
+}
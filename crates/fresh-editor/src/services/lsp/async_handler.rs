@@ -13,18 +13,19 @@
 
 use crate::services::async_bridge::{
     AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
-    LspServerStatus,
+    LspServerStatus, LspSymbol,
 };
 use crate::services::process_limits::ProcessLimits;
+use crate::services::project_env::ProjectEnv;
 use lsp_types::{
     notification::{
-        DidChangeTextDocument, DidOpenTextDocument, DidSaveTextDocument, Initialized, Notification,
-        PublishDiagnostics,
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Initialized, Notification, PublishDiagnostics,
     },
     request::{Initialize, Request},
-    ClientCapabilities, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializeParams, InitializeResult, InitializedParams,
-    PublishDiagnosticsParams, SemanticTokenModifier, SemanticTokenType,
+    ClientCapabilities, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
+    InitializedParams, PublishDiagnosticsParams, SemanticTokenModifier, SemanticTokenType,
     SemanticTokensClientCapabilities, SemanticTokensClientCapabilitiesRequests,
     SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult,
     SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentContentChangeEvent,
@@ -200,8 +201,11 @@ impl LspClientState {
 /// Create common LSP client capabilities with workDoneProgress support
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
-        GeneralClientCapabilities, RenameClientCapabilities, TextDocumentClientCapabilities,
-        WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        CodeActionClientCapabilities, CodeActionKind, CodeActionKindLiteralSupport,
+        CodeActionLiteralSupport, DocumentSymbolClientCapabilities, GeneralClientCapabilities,
+        RenameClientCapabilities, TextDocumentClientCapabilities, WorkspaceClientCapabilities,
+        WorkspaceEditClientCapabilities, WorkspaceFileOperationsClientCapabilities,
+        WorkspaceSymbolClientCapabilities,
     };
 
     ClientCapabilities {
@@ -215,9 +219,43 @@ fn create_client_capabilities() -> ClientCapabilities {
                 document_changes: Some(true),
                 ..Default::default()
             }),
+            symbol: Some(WorkspaceSymbolClientCapabilities::default()),
+            workspace_folders: Some(true),
+            // Renaming a file in the explorer asks for the edits that go with
+            // it, such as updated imports
+            file_operations: Some(WorkspaceFileOperationsClientCapabilities {
+                will_rename: Some(true),
+                did_rename: Some(true),
+                ..Default::default()
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
+            document_symbol: Some(DocumentSymbolClientCapabilities {
+                hierarchical_document_symbol_support: Some(true),
+                ..Default::default()
+            }),
+            // Code action literals carry their edit, which Extract
+            // Variable/Function apply directly
+            code_action: Some(CodeActionClientCapabilities {
+                code_action_literal_support: Some(CodeActionLiteralSupport {
+                    code_action_kind: CodeActionKindLiteralSupport {
+                        value_set: [
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_INLINE,
+                            CodeActionKind::REFACTOR_REWRITE,
+                            CodeActionKind::SOURCE,
+                            CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                        ]
+                        .iter()
+                        .map(|kind| kind.as_str().to_string())
+                        .collect(),
+                    },
+                }),
+                ..Default::default()
+            }),
             rename: Some(RenameClientCapabilities {
                 dynamic_registration: Some(true),
                 prepare_support: Some(true),
@@ -286,6 +324,130 @@ fn create_client_capabilities() -> ClientCapabilities {
     }
 }
 
+/// Flatten nested document symbols, recording each child's parent as its container
+fn flatten_document_symbols(
+    uri: &Uri,
+    symbols: Vec<lsp_types::DocumentSymbol>,
+    container: Option<&str>,
+    out: &mut Vec<LspSymbol>,
+) {
+    for symbol in symbols {
+        out.push(LspSymbol {
+            name: symbol.name.clone(),
+            kind: symbol.kind,
+            container: container.map(str::to_string),
+            location: lsp_types::Location {
+                uri: uri.clone(),
+                range: symbol.selection_range,
+            },
+        });
+        if let Some(children) = symbol.children {
+            flatten_document_symbols(uri, children, Some(&symbol.name), out);
+        }
+    }
+}
+
+/// Parameters of willRenameFiles and didRenameFiles for a single rename
+fn rename_files_params(
+    old_path: &std::path::Path,
+    new_path: &std::path::Path,
+) -> lsp_types::RenameFilesParams {
+    let uri = |path: &std::path::Path| {
+        url::Url::from_file_path(path)
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| path.to_string_lossy().into_owned())
+    };
+    lsp_types::RenameFilesParams {
+        files: vec![lsp_types::FileRename {
+            old_uri: uri(old_path),
+            new_uri: uri(new_path),
+        }],
+    }
+}
+
+/// Whether a file operation on `path` is one the server registered for
+fn file_operation_matches(
+    options: &lsp_types::FileOperationRegistrationOptions,
+    path: &std::path::Path,
+    is_dir: bool,
+) -> bool {
+    use lsp_types::FileOperationPatternKind;
+
+    let path = path.to_string_lossy().replace('\\', "/");
+    options.filters.iter().any(|filter| {
+        let pattern = &filter.pattern;
+        let scheme_matches = filter
+            .scheme
+            .as_deref()
+            .is_none_or(|scheme| scheme == "file");
+        let kind_matches = match pattern.matches {
+            Some(FileOperationPatternKind::File) => !is_dir,
+            Some(FileOperationPatternKind::Folder) => is_dir,
+            None => true,
+        };
+        let ignore_case = pattern
+            .options
+            .as_ref()
+            .and_then(|options| options.ignore_case)
+            .unwrap_or(false);
+        scheme_matches
+            && kind_matches
+            && glob_regex(&pattern.glob, ignore_case).is_some_and(|regex| regex.is_match(&path))
+    })
+}
+
+/// The regex for an LSP glob pattern: `*` and `?` within a path segment,
+/// `**` across segments, `{a,b}` alternatives and `[...]` character ranges
+fn glob_regex(glob: &str, ignore_case: bool) -> Option<regex::Regex> {
+    let mut pattern = String::from(if ignore_case { "(?i)^" } else { "^" });
+    let mut chars = glob.chars().peekable();
+    let mut in_group = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    pattern.push_str("(?:.*/)?");
+                } else {
+                    pattern.push_str(".*");
+                }
+            }
+            '*' => pattern.push_str("[^/]*"),
+            '?' => pattern.push_str("[^/]"),
+            '{' if !in_group => {
+                in_group = true;
+                pattern.push_str("(?:");
+            }
+            '}' if in_group => {
+                in_group = false;
+                pattern.push(')');
+            }
+            ',' if in_group => pattern.push('|'),
+            '[' => {
+                pattern.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    pattern.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        pattern.push('\\');
+                    }
+                    pattern.push(c);
+                }
+                pattern.push(']');
+            }
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    regex::Regex::new(&pattern).ok()
+}
+
 fn extract_semantic_token_capability(
     capabilities: &ServerCapabilities,
 ) -> (Option<SemanticTokensLegend>, bool, bool, bool) {
@@ -326,12 +488,45 @@ fn semantic_tokens_full_delta_supported(full: &Option<SemanticTokensFullOptions>
     }
 }
 
+/// Whether a server takes `workspace/didChangeWorkspaceFolders`
+fn supports_workspace_folder_changes(capabilities: &ServerCapabilities) -> bool {
+    capabilities
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.workspace_folders.as_ref())
+        .is_some_and(|folders| {
+            folders.supported == Some(true)
+                && !matches!(
+                    folders.change_notifications,
+                    None | Some(lsp_types::OneOf::Left(false))
+                )
+        })
+}
+
+/// A workspace folder named after its directory
+fn workspace_folder(uri: &Uri) -> WorkspaceFolder {
+    WorkspaceFolder {
+        uri: uri.clone(),
+        name: uri
+            .path()
+            .as_str()
+            .trim_end_matches('/')
+            .split('/')
+            .next_back()
+            .filter(|name| !name.is_empty())
+            .unwrap_or("workspace")
+            .to_string(),
+    }
+}
+
 /// Commands sent from the main loop to the LSP task
 #[derive(Debug)]
 enum LspCommand {
     /// Initialize the server
     Initialize {
         root_uri: Option<Uri>,
+        /// Workspace folders besides the root
+        workspace_folders: Vec<Uri>,
         initialization_options: Option<Value>,
         response: oneshot::Sender<Result<InitializeResult, String>>,
     },
@@ -347,11 +542,16 @@ enum LspCommand {
     DidChange {
         uri: Uri,
         content_changes: Vec<TextDocumentContentChangeEvent>,
+        /// Buffer revision after the changes
+        revision: u64,
     },
 
     /// Notify document saved
     DidSave { uri: Uri, text: Option<String> },
 
+    /// Notify document closed
+    DidClose { uri: Uri },
+
     /// Request completion at position
     Completion {
         request_id: u64,
@@ -393,6 +593,43 @@ enum LspCommand {
         character: u32,
     },
 
+    /// Request the symbols of a document
+    DocumentSymbols { request_id: u64, uri: Uri },
+
+    /// Request workspace symbols matching a query
+    WorkspaceSymbols { request_id: u64, query: String },
+
+    /// Request the edits formatting a range of a document
+    RangeFormatting {
+        request_id: u64,
+        uri: Uri,
+        start_line: u32,
+        start_char: u32,
+        end_line: u32,
+        end_char: u32,
+        tab_size: u32,
+        insert_spaces: bool,
+    },
+
+    /// Ask for the edits that go with renaming a file or directory
+    /// (workspace/willRenameFiles), before it's renamed
+    WillRenameFiles {
+        request_id: u64,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+    },
+
+    /// Notify that a file or directory was renamed
+    DidRenameFiles {
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+    },
+
+    /// Notify that workspace folders were added or removed
+    ChangeWorkspaceFolders { added: Vec<Uri>, removed: Vec<Uri> },
+
     /// Request signature help
     SignatureHelp {
         request_id: u64,
@@ -495,6 +732,10 @@ struct LspState {
     /// Mapping from editor request_id to LSP JSON-RPC id for cancellation
     /// Key: editor request_id, Value: LSP JSON-RPC id
     active_requests: HashMap<u64, i64>,
+
+    /// The workspace folders the server was told about (shared with the
+    /// stdout reader, which answers `workspace/workspaceFolders`)
+    workspace_folders: Arc<Mutex<Vec<WorkspaceFolder>>>,
 }
 
 impl LspState {
@@ -527,16 +768,21 @@ impl LspState {
                 LspCommand::DidChange {
                     uri,
                     content_changes,
+                    revision,
                 } => {
                     tracing::info!("Replaying DidChange for {}", uri.as_str());
                     let _ = self
-                        .handle_did_change_sequential(uri, content_changes, pending)
+                        .handle_did_change_sequential(uri, content_changes, revision, pending)
                         .await;
                 }
                 LspCommand::DidSave { uri, text } => {
                     tracing::info!("Replaying DidSave for {}", uri.as_str());
                     let _ = self.handle_did_save(uri, text).await;
                 }
+                LspCommand::DidClose { uri } => {
+                    tracing::info!("Replaying DidClose for {}", uri.as_str());
+                    let _ = self.handle_did_close(uri).await;
+                }
                 LspCommand::SemanticTokensFull { request_id, uri } => {
                     tracing::info!("Replaying semantic tokens request for {}", uri.as_str());
                     let _ = self
@@ -690,33 +936,31 @@ impl LspState {
     async fn handle_initialize_sequential(
         &mut self,
         root_uri: Option<Uri>,
+        workspace_folders: Vec<Uri>,
         initialization_options: Option<Value>,
         pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
     ) -> Result<InitializeResult, String> {
         tracing::info!(
-            "Initializing async LSP server with root_uri: {:?}, initialization_options: {:?}",
+            "Initializing async LSP server with root_uri: {:?}, workspace_folders: {:?}, initialization_options: {:?}",
             root_uri,
+            workspace_folders,
             initialization_options
         );
 
-        let workspace_folders = root_uri.as_ref().map(|uri| {
-            vec![WorkspaceFolder {
-                uri: uri.clone(),
-                name: uri
-                    .path()
-                    .as_str()
-                    .split('/')
-                    .next_back()
-                    .unwrap_or("workspace")
-                    .to_string(),
-            }]
-        });
+        // The root is the first folder
+        let mut folders: Vec<WorkspaceFolder> = Vec::new();
+        for uri in root_uri.iter().chain(&workspace_folders) {
+            if !folders.iter().any(|folder| folder.uri == *uri) {
+                folders.push(workspace_folder(uri));
+            }
+        }
+        *self.workspace_folders.lock().unwrap() = folders.clone();
 
         #[allow(deprecated)]
         let params = InitializeParams {
             process_id: Some(std::process::id()),
             capabilities: create_client_capabilities(),
-            workspace_folders,
+            workspace_folders: (!folders.is_empty()).then_some(folders),
             initialization_options,
             // Set the deprecated root_uri field for compatibility with LSP servers
             // like csharp-ls that still require it (see issue #366)
@@ -759,6 +1003,7 @@ impl LspState {
             semantic_tokens_full,
             semantic_tokens_full_delta,
             semantic_tokens_range,
+            workspace_folder_changes: supports_workspace_folder_changes(&result.capabilities),
         });
 
         // Send running status
@@ -813,6 +1058,7 @@ impl LspState {
         &mut self,
         uri: Uri,
         content_changes: Vec<TextDocumentContentChangeEvent>,
+        revision: u64,
         _pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
     ) -> Result<(), String> {
         tracing::trace!("LSP: did_change for {}", uri.as_str());
@@ -848,8 +1094,11 @@ impl LspState {
             self.pending_opens.remove(&path);
         }
 
+        // The document version follows the buffer revision, so that versions
+        // the server reports can be matched with buffer revisions. It must
+        // still increase if the buffer was replaced by one with fewer.
         let version = self.document_versions.entry(path).or_insert(0);
-        *version += 1;
+        *version = (*version + 1).max(revision.min(i32::MAX as u64) as i64);
 
         let params = DidChangeTextDocumentParams {
             text_document: VersionedTextDocumentIdentifier {
@@ -875,6 +1124,22 @@ impl LspState {
         self.send_notification::<DidSaveTextDocument>(params).await
     }
 
+    /// Handle did_close command
+    async fn handle_did_close(&mut self, uri: Uri) -> Result<(), String> {
+        tracing::trace!("LSP: did_close for {}", uri.as_str());
+
+        // A later didOpen of the document starts it over
+        let path = PathBuf::from(uri.path().as_str());
+        self.document_versions.remove(&path);
+        self.pending_opens.remove(&path);
+
+        let params = DidCloseTextDocumentParams {
+            text_document: TextDocumentIdentifier { uri },
+        };
+
+        self.send_notification::<DidCloseTextDocument>(params).await
+    }
+
     /// Handle completion request
     #[allow(clippy::type_complexity)]
     async fn handle_completion(
@@ -1286,6 +1551,341 @@ impl LspState {
         }
     }
 
+    /// Handle document symbols request
+    #[allow(clippy::type_complexity)]
+    async fn handle_document_symbols(
+        &mut self,
+        request_id: u64,
+        uri: Uri,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            DocumentSymbolParams, DocumentSymbolResponse, PartialResultParams,
+            WorkDoneProgressParams,
+        };
+
+        tracing::trace!("LSP: document symbols request for {}", uri.as_str());
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Value>(
+                "textDocument/documentSymbol",
+                Some(params),
+                pending,
+            )
+            .await
+        {
+            Ok(result) => {
+                // The response is either flat SymbolInformation or nested DocumentSymbols
+                let symbols = match serde_json::from_value::<Option<DocumentSymbolResponse>>(result)
+                {
+                    Ok(Some(DocumentSymbolResponse::Flat(symbols))) => symbols
+                        .into_iter()
+                        .map(|symbol| LspSymbol {
+                            name: symbol.name,
+                            kind: symbol.kind,
+                            container: symbol.container_name,
+                            location: symbol.location,
+                        })
+                        .collect(),
+                    Ok(Some(DocumentSymbolResponse::Nested(symbols))) => {
+                        let mut flattened = Vec::new();
+                        flatten_document_symbols(&uri, symbols, None, &mut flattened);
+                        flattened
+                    }
+                    Ok(None) | Err(_) => Vec::new(),
+                };
+
+                tracing::trace!("LSP: found {} document symbols", symbols.len());
+
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Document symbols request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                    request_id,
+                    symbols: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Handle workspace symbols request
+    #[allow(clippy::type_complexity)]
+    async fn handle_workspace_symbols(
+        &mut self,
+        request_id: u64,
+        query: String,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            OneOf, PartialResultParams, WorkDoneProgressParams, WorkspaceSymbolParams,
+            WorkspaceSymbolResponse,
+        };
+
+        tracing::trace!("LSP: workspace symbols request for {:?}", query);
+
+        let params = WorkspaceSymbolParams {
+            query,
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        };
+
+        match self
+            .send_request_sequential::<_, Value>("workspace/symbol", Some(params), pending)
+            .await
+        {
+            Ok(result) => {
+                let symbols =
+                    match serde_json::from_value::<Option<WorkspaceSymbolResponse>>(result) {
+                        Ok(Some(WorkspaceSymbolResponse::Flat(symbols))) => symbols
+                            .into_iter()
+                            .map(|symbol| LspSymbol {
+                                name: symbol.name,
+                                kind: symbol.kind,
+                                container: symbol.container_name,
+                                location: symbol.location,
+                            })
+                            .collect(),
+                        Ok(Some(WorkspaceSymbolResponse::Nested(symbols))) => symbols
+                            .into_iter()
+                            .map(|symbol| {
+                                // Symbols without a range point at the start of their file
+                                let location = match symbol.location {
+                                    OneOf::Left(location) => location,
+                                    OneOf::Right(location) => lsp_types::Location {
+                                        uri: location.uri,
+                                        range: lsp_types::Range::default(),
+                                    },
+                                };
+                                LspSymbol {
+                                    name: symbol.name,
+                                    kind: symbol.kind,
+                                    container: symbol.container_name,
+                                    location,
+                                }
+                            })
+                            .collect(),
+                        Ok(None) | Err(_) => Vec::new(),
+                    };
+
+                tracing::trace!("LSP: found {} workspace symbols", symbols.len());
+
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols,
+                });
+                Ok(())
+            }
+            Err(e) => {
+                tracing::error!("Workspace symbols request failed: {}", e);
+                let _ = self.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                    request_id,
+                    symbols: Vec::new(),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Whether the server registered for renames of `path`, before they
+    /// happen (`will`) or after
+    fn renames_file(&self, will: bool, path: &std::path::Path, is_dir: bool) -> bool {
+        self.capabilities
+            .as_ref()
+            .and_then(|capabilities| capabilities.workspace.as_ref())
+            .and_then(|workspace| workspace.file_operations.as_ref())
+            .and_then(|operations| {
+                if will {
+                    operations.will_rename.as_ref()
+                } else {
+                    operations.did_rename.as_ref()
+                }
+            })
+            .is_some_and(|options| file_operation_matches(options, path, is_dir))
+    }
+
+    /// Handle a request for the edits that go with renaming a file. Servers
+    /// that don't want to hear about the file answer with no edit straight
+    /// away, and so does one that doesn't answer in time: the rename waits
+    /// for the answer.
+    #[allow(clippy::type_complexity)]
+    async fn handle_will_rename_files(
+        &mut self,
+        request_id: u64,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        let mut edit = None;
+        if self.renames_file(true, &old_path, is_dir) {
+            tracing::trace!("LSP: will rename {:?} to {:?}", old_path, new_path);
+            let params = rename_files_params(&old_path, &new_path);
+            let request = self.send_request_sequential::<_, Value>(
+                "workspace/willRenameFiles",
+                Some(params),
+                pending,
+            );
+            match tokio::time::timeout(std::time::Duration::from_secs(5), request).await {
+                Ok(Ok(result)) => {
+                    edit = serde_json::from_value::<Option<lsp_types::WorkspaceEdit>>(result)
+                        .unwrap_or_else(|e| {
+                            tracing::error!("Failed to parse willRenameFiles response: {}", e);
+                            None
+                        });
+                }
+                Ok(Err(e)) => tracing::error!("willRenameFiles request failed: {}", e),
+                Err(_) => tracing::warn!("willRenameFiles request timed out"),
+            }
+        }
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspWillRenameFiles { request_id, edit });
+        Ok(())
+    }
+
+    /// Handle a range formatting request. A server that doesn't format
+    /// ranges, or fails to, answers `None` so the editor can fall back to
+    /// its own indentation.
+    #[allow(clippy::type_complexity)]
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_range_formatting(
+        &mut self,
+        request_id: u64,
+        uri: Uri,
+        start_line: u32,
+        start_char: u32,
+        end_line: u32,
+        end_char: u32,
+        tab_size: u32,
+        insert_spaces: bool,
+        pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
+    ) -> Result<(), String> {
+        use lsp_types::{
+            DocumentRangeFormattingParams, FormattingOptions, OneOf, Position, Range,
+            WorkDoneProgressParams,
+        };
+
+        let supported = self
+            .capabilities
+            .as_ref()
+            .and_then(|capabilities| capabilities.document_range_formatting_provider.as_ref())
+            .is_some_and(|provider| !matches!(provider, OneOf::Left(false)));
+        let mut edits = None;
+        if supported {
+            tracing::trace!(
+                "LSP: range formatting request for {} ({}:{} - {}:{})",
+                uri.as_str(),
+                start_line,
+                start_char,
+                end_line,
+                end_char
+            );
+            let params = DocumentRangeFormattingParams {
+                text_document: TextDocumentIdentifier { uri },
+                range: Range {
+                    start: Position {
+                        line: start_line,
+                        character: start_char,
+                    },
+                    end: Position {
+                        line: end_line,
+                        character: end_char,
+                    },
+                },
+                options: FormattingOptions {
+                    tab_size,
+                    insert_spaces,
+                    ..Default::default()
+                },
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            };
+            let request = self.send_request_sequential::<_, Option<Vec<lsp_types::TextEdit>>>(
+                "textDocument/rangeFormatting",
+                Some(params),
+                pending,
+            );
+            match tokio::time::timeout(std::time::Duration::from_secs(5), request).await {
+                Ok(Ok(result)) => edits = Some(result.unwrap_or_default()),
+                Ok(Err(e)) => tracing::error!("Range formatting request failed: {}", e),
+                Err(_) => tracing::warn!("Range formatting request timed out"),
+            }
+        }
+        let _ = self
+            .async_tx
+            .send(AsyncMessage::LspRangeFormatting { request_id, edits });
+        Ok(())
+    }
+
+    /// Handle the notification that a file was renamed
+    async fn handle_did_rename_files(
+        &mut self,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+    ) -> Result<(), String> {
+        if !self.renames_file(false, &old_path, is_dir) {
+            return Ok(());
+        }
+        tracing::trace!("LSP: did rename {:?} to {:?}", old_path, new_path);
+        self.send_notification::<lsp_types::notification::DidRenameFiles>(rename_files_params(
+            &old_path, &new_path,
+        ))
+        .await
+    }
+
+    /// Handle workspace folders being added or removed. Servers that can't
+    /// be told are restarted by the manager instead.
+    async fn handle_change_workspace_folders(
+        &mut self,
+        added: Vec<Uri>,
+        removed: Vec<Uri>,
+    ) -> Result<(), String> {
+        use lsp_types::{DidChangeWorkspaceFoldersParams, WorkspaceFoldersChangeEvent};
+
+        {
+            let mut folders = self.workspace_folders.lock().unwrap();
+            folders.retain(|folder| !removed.contains(&folder.uri));
+            for uri in &added {
+                if !folders.iter().any(|folder| folder.uri == *uri) {
+                    folders.push(workspace_folder(uri));
+                }
+            }
+        }
+        if !self
+            .capabilities
+            .as_ref()
+            .is_some_and(supports_workspace_folder_changes)
+        {
+            return Ok(());
+        }
+
+        tracing::trace!(
+            "LSP: workspace folders added {:?}, removed {:?}",
+            added,
+            removed
+        );
+        let params = DidChangeWorkspaceFoldersParams {
+            event: WorkspaceFoldersChangeEvent {
+                added: added.iter().map(workspace_folder).collect(),
+                removed: removed.iter().map(workspace_folder).collect(),
+            },
+        };
+        self.send_notification::<lsp_types::notification::DidChangeWorkspaceFolders>(params)
+            .await
+    }
+
     /// Handle signature help request
     #[allow(clippy::type_complexity)]
     async fn handle_signature_help(
@@ -1947,6 +2547,7 @@ impl LspTask {
         language: String,
         async_tx: std_mpsc::Sender<AsyncMessage>,
         process_limits: &ProcessLimits,
+        env: &ProjectEnv,
         stderr_log_path: std::path::PathBuf,
     ) -> Result<Self, String> {
         tracing::info!("Spawning async LSP server: {} {:?}", command, args);
@@ -1955,7 +2556,7 @@ impl LspTask {
 
         // Check if the command exists before trying to spawn
         // This provides a clearer error message than the generic "No such file or directory"
-        if !Self::command_exists(command) {
+        if !Self::command_exists(command, env) {
             return Err(format!(
                 "LSP server executable '{}' not found. Please install it or check your PATH.",
                 command
@@ -1977,6 +2578,9 @@ impl LspTask {
             .stderr(std::process::Stdio::from(stderr_file))
             .kill_on_drop(true);
 
+        // Run with the project's environment, as the user's shell would
+        env.apply(&mut cmd);
+
         // Apply resource limits to the process
         process_limits
             .apply_to_command(&mut cmd)
@@ -2024,8 +2628,9 @@ impl LspTask {
         })
     }
 
-    /// Check if a command exists in PATH or as an absolute path
-    fn command_exists(command: &str) -> bool {
+    /// Check if a command exists in PATH (the project's, if it sets one) or
+    /// as an absolute path
+    fn command_exists(command: &str, env: &ProjectEnv) -> bool {
         use std::path::Path;
 
         // If it's an absolute path, check if the file exists and is executable
@@ -2035,21 +2640,16 @@ impl LspTask {
         }
 
         // Otherwise, search in PATH
-        if let Ok(path_var) = std::env::var("PATH") {
-            #[cfg(unix)]
-            let separator = ':';
-            #[cfg(windows)]
-            let separator = ';';
-
-            for dir in path_var.split(separator) {
-                let full_path = Path::new(dir).join(command);
+        if let Some(path_var) = env.var("PATH") {
+            for dir in std::env::split_paths(&path_var) {
+                let full_path = dir.join(command);
                 if full_path.exists() && full_path.is_file() {
                     return true;
                 }
                 // On Windows, also check with .exe extension
                 #[cfg(windows)]
                 {
-                    let with_exe = Path::new(dir).join(format!("{}.exe", command));
+                    let with_exe = dir.join(format!("{}.exe", command));
                     if with_exe.exists() && with_exe.is_file() {
                         return true;
                     }
@@ -2070,6 +2670,7 @@ impl LspTask {
         language: String,
         server_command: String,
         stdin_writer: Arc<tokio::sync::Mutex<ChildStdin>>,
+        workspace_folders: Arc<Mutex<Vec<WorkspaceFolder>>>,
         stderr_log_path: std::path::PathBuf,
         shutting_down: Arc<AtomicBool>,
     ) {
@@ -2086,6 +2687,7 @@ impl LspTask {
                             &language,
                             &server_command,
                             &stdin_writer,
+                            &workspace_folders,
                         )
                         .await
                         {
@@ -2138,6 +2740,7 @@ impl LspTask {
             async_tx: self.async_tx.clone(),
             language: self.language.clone(),
             active_requests: HashMap::new(),
+            workspace_folders: Arc::new(Mutex::new(Vec::new())),
         };
 
         let pending = Arc::new(Mutex::new(self.pending));
@@ -2155,6 +2758,7 @@ impl LspTask {
             language_clone.clone(),
             self.server_command.clone(),
             stdin_writer.clone(),
+            state.workspace_folders.clone(),
             self.stderr_log_path,
             shutting_down.clone(),
         );
@@ -2170,7 +2774,7 @@ impl LspTask {
                 Some(cmd) = command_rx.recv() => {
                     tracing::trace!("LspTask received command: {:?}", cmd);
                     match cmd {
-                        LspCommand::Initialize { root_uri, initialization_options, response } => {
+                        LspCommand::Initialize { root_uri, workspace_folders, initialization_options, response } => {
                             // Send initializing status
                             let _ = async_tx.send(AsyncMessage::LspStatusUpdate {
                                 language: language_clone.clone(),
@@ -2179,7 +2783,7 @@ impl LspTask {
                             });
                             tracing::info!("Processing Initialize command");
                             let result =
-                                state.handle_initialize_sequential(root_uri, initialization_options, &pending).await;
+                                state.handle_initialize_sequential(root_uri, workspace_folders, initialization_options, &pending).await;
                             let success = result.is_ok();
                             let _ = response.send(result);
 
@@ -2214,11 +2818,17 @@ impl LspTask {
                         LspCommand::DidChange {
                             uri,
                             content_changes,
+                            revision,
                         } => {
                             if state.initialized {
                                 tracing::trace!("Processing DidChange for {}", uri.as_str());
                                 let _ = state
-                                    .handle_did_change_sequential(uri, content_changes, &pending)
+                                    .handle_did_change_sequential(
+                                        uri,
+                                        content_changes,
+                                        revision,
+                                        &pending,
+                                    )
                                     .await;
                             } else {
                                 tracing::trace!(
@@ -2228,6 +2838,7 @@ impl LspTask {
                                 pending_commands.push(LspCommand::DidChange {
                                     uri,
                                     content_changes,
+                                    revision,
                                 });
                             }
                         }
@@ -2243,6 +2854,18 @@ impl LspTask {
                                 pending_commands.push(LspCommand::DidSave { uri, text });
                             }
                         }
+                        LspCommand::DidClose { uri } => {
+                            if state.initialized {
+                                tracing::info!("Processing DidClose for {}", uri.as_str());
+                                let _ = state.handle_did_close(uri).await;
+                            } else {
+                                tracing::trace!(
+                                    "Queueing DidClose for {} until initialization completes",
+                                    uri.as_str()
+                                );
+                                pending_commands.push(LspCommand::DidClose { uri });
+                            }
+                        }
                         LspCommand::Completion {
                             request_id,
                             uri,
@@ -2351,6 +2974,113 @@ impl LspTask {
                                 });
                             }
                         }
+                        LspCommand::DocumentSymbols { request_id, uri } => {
+                            if state.initialized {
+                                tracing::info!(
+                                    "Processing DocumentSymbols request for {}",
+                                    uri.as_str()
+                                );
+                                let _ = state
+                                    .handle_document_symbols(request_id, uri, &pending)
+                                    .await;
+                            } else {
+                                tracing::trace!("LSP not initialized, cannot get document symbols");
+                                let _ = state.async_tx.send(AsyncMessage::LspDocumentSymbols {
+                                    request_id,
+                                    symbols: Vec::new(),
+                                });
+                            }
+                        }
+                        LspCommand::WorkspaceSymbols { request_id, query } => {
+                            if state.initialized {
+                                tracing::info!("Processing WorkspaceSymbols request");
+                                let _ = state
+                                    .handle_workspace_symbols(request_id, query, &pending)
+                                    .await;
+                            } else {
+                                tracing::trace!(
+                                    "LSP not initialized, cannot get workspace symbols"
+                                );
+                                let _ = state.async_tx.send(AsyncMessage::LspWorkspaceSymbols {
+                                    request_id,
+                                    symbols: Vec::new(),
+                                });
+                            }
+                        }
+                        LspCommand::WillRenameFiles {
+                            request_id,
+                            old_path,
+                            new_path,
+                            is_dir,
+                        } => {
+                            if state.initialized {
+                                tracing::info!("Processing WillRenameFiles for {:?}", old_path);
+                                let _ = state
+                                    .handle_will_rename_files(
+                                        request_id, old_path, new_path, is_dir, &pending,
+                                    )
+                                    .await;
+                            } else {
+                                // Nothing to edit for a server that isn't ready
+                                let _ = state.async_tx.send(AsyncMessage::LspWillRenameFiles {
+                                    request_id,
+                                    edit: None,
+                                });
+                            }
+                        }
+                        LspCommand::RangeFormatting {
+                            request_id,
+                            uri,
+                            start_line,
+                            start_char,
+                            end_line,
+                            end_char,
+                            tab_size,
+                            insert_spaces,
+                        } => {
+                            if state.initialized {
+                                tracing::info!(
+                                    "Processing RangeFormatting request for {}",
+                                    uri.as_str()
+                                );
+                                let _ = state
+                                    .handle_range_formatting(
+                                        request_id,
+                                        uri,
+                                        start_line,
+                                        start_char,
+                                        end_line,
+                                        end_char,
+                                        tab_size,
+                                        insert_spaces,
+                                        &pending,
+                                    )
+                                    .await;
+                            } else {
+                                let _ = state.async_tx.send(AsyncMessage::LspRangeFormatting {
+                                    request_id,
+                                    edits: None,
+                                });
+                            }
+                        }
+                        LspCommand::DidRenameFiles {
+                            old_path,
+                            new_path,
+                            is_dir,
+                        } => {
+                            if state.initialized {
+                                let _ = state
+                                    .handle_did_rename_files(old_path, new_path, is_dir)
+                                    .await;
+                            }
+                        }
+                        LspCommand::ChangeWorkspaceFolders { added, removed } => {
+                            if state.initialized {
+                                let _ = state
+                                    .handle_change_workspace_folders(added, removed)
+                                    .await;
+                            }
+                        }
                         LspCommand::SignatureHelp {
                             request_id,
                             uri,
@@ -2664,6 +3394,7 @@ async fn handle_message_dispatch(
     language: &str,
     server_command: &str,
     stdin_writer: &Arc<tokio::sync::Mutex<ChildStdin>>,
+    workspace_folders: &Arc<Mutex<Vec<WorkspaceFolder>>>,
 ) -> Result<(), String> {
     match message {
         JsonRpcMessage::Response(response) => {
@@ -2755,6 +3486,23 @@ async fn handle_message_dispatch(
                         error: None,
                     }
                 }
+                "workspace/workspaceFolders" => {
+                    let folders = workspace_folders.lock().unwrap().clone();
+                    tracing::trace!(
+                        "Responding to workspace/workspaceFolders with {:?}",
+                        folders
+                    );
+                    JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: request.id,
+                        result: Some(if folders.is_empty() {
+                            Value::Null
+                        } else {
+                            serde_json::to_value(folders).unwrap_or(Value::Null)
+                        }),
+                        error: None,
+                    }
+                }
                 "client/registerCapability" => {
                     // Server wants to register a capability dynamically - acknowledge
                     tracing::trace!(
@@ -3052,6 +3800,7 @@ impl LspHandle {
         language: String,
         async_bridge: &AsyncBridge,
         process_limits: ProcessLimits,
+        env: ProjectEnv,
     ) -> Result<Self, String> {
         let (command_tx, command_rx) = mpsc::channel(100); // Buffer up to 100 commands
         let async_tx = async_bridge.sender();
@@ -3079,6 +3828,7 @@ impl LspHandle {
                 language_clone.clone(),
                 async_tx.clone(),
                 &process_limits,
+                &env,
                 stderr_log_path_clone.clone(),
             )
             .await
@@ -3134,6 +3884,7 @@ impl LspHandle {
     pub fn initialize(
         &self,
         root_uri: Option<Uri>,
+        workspace_folders: Vec<Uri>,
         initialization_options: Option<Value>,
     ) -> Result<(), String> {
         // Validate state transition
@@ -3157,6 +3908,7 @@ impl LspHandle {
         self.command_tx
             .try_send(LspCommand::Initialize {
                 root_uri,
+                workspace_folders,
                 initialization_options,
                 response: tx,
             })
@@ -3219,17 +3971,19 @@ impl LspHandle {
             .map_err(|_| "Failed to send did_open command".to_string())
     }
 
-    /// Notify document changed
+    /// Notify document changed, with the buffer revision after the changes
     pub fn did_change(
         &self,
         uri: Uri,
         content_changes: Vec<TextDocumentContentChangeEvent>,
+        revision: u64,
     ) -> Result<(), String> {
         // Send command to LspTask which will queue it if not initialized yet
         self.command_tx
             .try_send(LspCommand::DidChange {
                 uri,
                 content_changes,
+                revision,
             })
             .map_err(|_| "Failed to send did_change command".to_string())
     }
@@ -3241,6 +3995,13 @@ impl LspHandle {
             .map_err(|_| "Failed to send did_save command".to_string())
     }
 
+    /// Send didClose notification
+    pub fn did_close(&self, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DidClose { uri })
+            .map_err(|_| "Failed to send did_close command".to_string())
+    }
+
     /// Request completion at position
     pub fn completion(
         &self,
@@ -3333,6 +4094,94 @@ impl LspHandle {
             .map_err(|_| "Failed to send references command".to_string())
     }
 
+    /// Request the symbols of a document
+    pub fn document_symbols(&self, request_id: u64, uri: Uri) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DocumentSymbols { request_id, uri })
+            .map_err(|_| "Failed to send document symbols command".to_string())
+    }
+
+    /// Request workspace symbols matching `query`
+    pub fn workspace_symbols(&self, request_id: u64, query: String) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WorkspaceSymbols { request_id, query })
+            .map_err(|_| "Failed to send workspace symbols command".to_string())
+    }
+
+    /// Ask for the edits formatting a range of a document; the answer comes
+    /// as `AsyncMessage::LspRangeFormatting`
+    #[allow(clippy::too_many_arguments)]
+    pub fn range_formatting(
+        &self,
+        request_id: u64,
+        uri: Uri,
+        start_line: u32,
+        start_char: u32,
+        end_line: u32,
+        end_char: u32,
+        tab_size: u32,
+        insert_spaces: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::RangeFormatting {
+                request_id,
+                uri,
+                start_line,
+                start_char,
+                end_line,
+                end_char,
+                tab_size,
+                insert_spaces,
+            })
+            .map_err(|_| "Failed to send range formatting command".to_string())
+    }
+
+    /// Ask for the edits that go with renaming `old_path` to `new_path`;
+    /// the answer comes as `AsyncMessage::LspWillRenameFiles`
+    pub fn will_rename_files(
+        &self,
+        request_id: u64,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::WillRenameFiles {
+                request_id,
+                old_path,
+                new_path,
+                is_dir,
+            })
+            .map_err(|_| "Failed to send will rename files command".to_string())
+    }
+
+    /// Notify that `old_path` was renamed to `new_path`
+    pub fn did_rename_files(
+        &self,
+        old_path: PathBuf,
+        new_path: PathBuf,
+        is_dir: bool,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::DidRenameFiles {
+                old_path,
+                new_path,
+                is_dir,
+            })
+            .map_err(|_| "Failed to send did rename files command".to_string())
+    }
+
+    /// Notify the server that workspace folders were added or removed
+    pub fn change_workspace_folders(
+        &self,
+        added: Vec<Uri>,
+        removed: Vec<Uri>,
+    ) -> Result<(), String> {
+        self.command_tx
+            .try_send(LspCommand::ChangeWorkspaceFolders { added, removed })
+            .map_err(|_| "Failed to send change workspace folders command".to_string())
+    }
+
     /// Request signature help
     pub fn signature_help(
         &self,
@@ -3545,6 +4394,44 @@ impl Drop for LspHandle {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_flatten_document_symbols() {
+        let uri = "file:///test.rs".parse::<Uri>().unwrap();
+        let range = |line: u32| {
+            serde_json::json!({
+                "start": {"line": line, "character": 0},
+                "end": {"line": line, "character": 4}
+            })
+        };
+        let symbols: Vec<lsp_types::DocumentSymbol> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "Point",
+                "kind": 23,
+                "range": range(0),
+                "selectionRange": range(0),
+                "children": [
+                    {"name": "x", "kind": 8, "range": range(1), "selectionRange": range(1)}
+                ]
+            },
+            {"name": "main", "kind": 12, "range": range(4), "selectionRange": range(4)}
+        ]))
+        .unwrap();
+
+        let mut flattened = Vec::new();
+        flatten_document_symbols(&uri, symbols, None, &mut flattened);
+
+        let names: Vec<_> = flattened
+            .iter()
+            .map(|symbol| (symbol.name.as_str(), symbol.container.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Point", None), ("x", Some("Point")), ("main", None)]
+        );
+        assert_eq!(flattened[1].location.range.start.line, 1);
+        assert_eq!(flattened[1].location.uri, uri);
+    }
+
     #[test]
     fn test_json_rpc_request_serialization() {
         let request = JsonRpcRequest {
@@ -3695,6 +4582,7 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         );
 
         // Should succeed in spawning
@@ -3721,6 +4609,7 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         )
         .unwrap();
 
@@ -3747,6 +4636,7 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         )
         .unwrap();
 
@@ -3761,6 +4651,7 @@ mod tests {
                 range_length: None,
                 text: "fn main() {}".to_string(),
             }],
+            1,
         );
 
         // Should succeed (command is queued)
@@ -3779,6 +4670,7 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         )
         .unwrap();
 
@@ -3793,6 +4685,7 @@ mod tests {
                 range_length: None,
                 text: String::new(), // Empty string for deletion
             }],
+            1,
         );
 
         // Should succeed (command is queued)
@@ -3812,6 +4705,7 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         );
 
         // Should succeed in creating handle (error happens asynchronously)
@@ -3849,6 +4743,7 @@ mod tests {
                     "test".to_string(),
                     &async_bridge,
                     ProcessLimits::unlimited(),
+                    ProjectEnv::default(),
                 )
                 .unwrap()
             });
@@ -3918,12 +4813,13 @@ mod tests {
             "test".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         )
         .unwrap();
 
         // Immediately call initialize - this is what get_or_spawn() does
         // Before the fix, this would fail with "Cannot initialize: client is in state Starting"
-        let result = handle.initialize(None, None);
+        let result = handle.initialize(None, Vec::new(), None);
 
         assert!(
             result.is_ok(),
@@ -3967,11 +4863,12 @@ mod tests {
             "fake".to_string(),
             &async_bridge,
             ProcessLimits::unlimited(),
+            ProjectEnv::default(),
         )
         .unwrap();
 
         // This is the critical test: initialize must succeed from Starting state
-        let init_result = handle.initialize(None, None);
+        let init_result = handle.initialize(None, Vec::new(), None);
         assert!(
             init_result.is_ok(),
             "initialize() failed from Starting state: {:?}",
@@ -3995,4 +4892,50 @@ mod tests {
         // Cleanup
         let _ = handle.shutdown();
     }
+
+    #[test]
+    fn test_file_operation_matches() {
+        use lsp_types::{
+            FileOperationFilter, FileOperationPattern, FileOperationPatternKind,
+            FileOperationRegistrationOptions,
+        };
+        use std::path::Path;
+
+        let filter = |glob: &str, matches| FileOperationFilter {
+            scheme: Some("file".to_string()),
+            pattern: FileOperationPattern {
+                glob: glob.to_string(),
+                matches,
+                options: None,
+            },
+        };
+        let options = FileOperationRegistrationOptions {
+            filters: vec![
+                filter("**/*.{ts,tsx}", Some(FileOperationPatternKind::File)),
+                filter("**", Some(FileOperationPatternKind::Folder)),
+            ],
+        };
+        assert!(file_operation_matches(
+            &options,
+            Path::new("/p/src/a.ts"),
+            false
+        ));
+        assert!(file_operation_matches(
+            &options,
+            Path::new("/p/src/a.tsx"),
+            false
+        ));
+        assert!(!file_operation_matches(
+            &options,
+            Path::new("/p/src/a.js"),
+            false
+        ));
+        assert!(file_operation_matches(&options, Path::new("/p/src"), true));
+
+        let matches = |glob: &str, path: &str| glob_regex(glob, false).unwrap().is_match(path);
+        assert!(matches("/p/*/[a-c].rs", "/p/src/b.rs"));
+        assert!(!matches("/p/*.rs", "/p/src/b.rs"));
+        assert!(!matches("/p/*/[!a-c].rs", "/p/src/b.rs"));
+        assert!(glob_regex("**/*.RS", true).unwrap().is_match("/p/b.rs"));
+    }
 }
@@ -8,6 +8,7 @@
 
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::lsp::async_handler::LspHandle;
+use crate::services::project_env::ProjectEnv;
 use crate::types::LspServerConfig;
 use lsp_types::{SemanticTokensLegend, Uri};
 use std::collections::{HashMap, HashSet};
@@ -44,6 +45,14 @@ pub struct LspManager {
     /// Per-language root URIs (allows plugins to specify project roots)
     per_language_root_uris: HashMap<String, Uri>,
 
+    /// Workspace folders besides the root, such as the other projects of a
+    /// monorepo. Every server is told about all of them.
+    workspace_folders: Vec<Uri>,
+
+    /// Whether a language's server takes workspace folder changes (from
+    /// server capabilities)
+    workspace_folder_changes: HashMap<String, bool>,
+
     /// Tokio runtime reference
     runtime: Option<tokio::runtime::Handle>,
 
@@ -81,6 +90,14 @@ pub struct LspManager {
 
     /// Whether a language supports range semantic tokens
     semantic_tokens_range_support: HashMap<String, bool>,
+
+    /// Languages whose servers have no open documents, and since when.
+    /// Idle servers are kept running so reopening one of their files doesn't
+    /// wait for a new server to start and index the project again.
+    idle_since: HashMap<String, Instant>,
+
+    /// The project's environment, which servers are started with
+    project_env: ProjectEnv,
 }
 
 impl LspManager {
@@ -91,6 +108,8 @@ impl LspManager {
             config: HashMap::new(),
             root_uri,
             per_language_root_uris: HashMap::new(),
+            workspace_folders: Vec::new(),
+            workspace_folder_changes: HashMap::new(),
             runtime: None,
             async_bridge: None,
             restart_attempts: HashMap::new(),
@@ -103,6 +122,8 @@ impl LspManager {
             semantic_tokens_full_support: HashMap::new(),
             semantic_tokens_full_delta_support: HashMap::new(),
             semantic_tokens_range_support: HashMap::new(),
+            idle_since: HashMap::new(),
+            project_env: ProjectEnv::default(),
         }
     }
 
@@ -209,6 +230,7 @@ impl LspManager {
     pub fn try_spawn(&mut self, language: &str) -> LspSpawnResult {
         // If handle already exists, return success
         if self.handles.contains_key(language) {
+            self.idle_since.remove(language);
             return LspSpawnResult::Spawned;
         }
 
@@ -245,6 +267,12 @@ impl LspManager {
         self.async_bridge = Some(async_bridge);
     }
 
+    /// Set the project's environment. Servers started afterwards run with
+    /// it; running servers keep the one they were started with.
+    pub fn set_project_env(&mut self, env: ProjectEnv) {
+        self.project_env = env;
+    }
+
     /// Set configuration for a language
     pub fn set_language_config(&mut self, language: String, config: LspServerConfig) {
         self.config.insert(language, config);
@@ -292,6 +320,76 @@ impl LspManager {
             .or_else(|| self.root_uri.clone())
     }
 
+    /// Get the workspace folders besides the root
+    pub fn workspace_folders(&self) -> &[Uri] {
+        &self.workspace_folders
+    }
+
+    /// Set whether a language's server takes workspace folder changes
+    pub fn set_workspace_folder_changes_support(&mut self, language: &str, supported: bool) {
+        self.workspace_folder_changes
+            .insert(language.to_string(), supported);
+    }
+
+    /// Set the workspace folders besides the root
+    ///
+    /// Running servers that take workspace folder changes are told about the
+    /// folders added and removed. Others are restarted so they're initialized
+    /// with the new folders, or shut down if they're idle.
+    /// Returns the languages whose servers were restarted.
+    pub fn set_workspace_folders(&mut self, folders: Vec<Uri>) -> Vec<String> {
+        let added: Vec<Uri> = folders
+            .iter()
+            .filter(|uri| !self.workspace_folders.contains(uri))
+            .cloned()
+            .collect();
+        let removed: Vec<Uri> = self
+            .workspace_folders
+            .iter()
+            .filter(|uri| !folders.contains(uri))
+            .cloned()
+            .collect();
+        self.workspace_folders = folders;
+        if added.is_empty() && removed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut languages: Vec<String> = self.handles.keys().cloned().collect();
+        languages.sort();
+        let mut restarted = Vec::new();
+        for language in languages {
+            if self.workspace_folder_changes.get(&language) == Some(&true) {
+                if let Some(handle) = self.handles.get(&language) {
+                    if let Err(e) = handle.change_workspace_folders(added.clone(), removed.clone())
+                    {
+                        tracing::warn!(
+                            "Failed to change workspace folders for {}: {}",
+                            language,
+                            e
+                        );
+                    }
+                }
+                continue;
+            }
+
+            if let Some(handle) = self.handles.remove(&language) {
+                let _ = handle.shutdown();
+            }
+            if self.idle_since.remove(&language).is_some() {
+                // Spawned with the new folders when one of its files is opened
+                continue;
+            }
+            tracing::info!(
+                "Restarting {} LSP server for the new workspace folders",
+                language
+            );
+            if self.force_spawn(&language).is_some() {
+                restarted.push(language);
+            }
+        }
+        restarted
+    }
+
     /// Reset the manager for a new project
     ///
     /// This shuts down all servers and clears state, preparing for a fresh start.
@@ -344,6 +442,7 @@ impl LspManager {
     pub fn force_spawn(&mut self, language: &str) -> Option<&mut LspHandle> {
         // Return existing handle if available
         if self.handles.contains_key(language) {
+            self.idle_since.remove(language);
             return self.handles.get_mut(language);
         }
 
@@ -384,15 +483,18 @@ impl LspManager {
             language.to_string(),
             async_bridge,
             config.process_limits.clone(),
+            self.project_env.clone(),
         ) {
             Ok(handle) => {
                 // Initialize the handle (non-blocking)
                 // The handle will become ready asynchronously
                 // Use per-language root URI if set, otherwise fall back to default
                 let effective_root = self.get_effective_root_uri(language);
-                if let Err(e) =
-                    handle.initialize(effective_root, config.initialization_options.clone())
-                {
+                if let Err(e) = handle.initialize(
+                    effective_root,
+                    self.workspace_folders.clone(),
+                    config.initialization_options.clone(),
+                ) {
                     tracing::error!("Failed to send initialize command for {}: {}", language, e);
                     return None;
                 }
@@ -401,6 +503,7 @@ impl LspManager {
                     "LSP initialization started for {}, will be ready asynchronously",
                     language
                 );
+                self.workspace_folder_changes.remove(language);
                 self.handles.insert(language.to_string(), handle);
                 self.handles.get_mut(language)
             }
@@ -419,6 +522,7 @@ impl LspManager {
         if let Some(handle) = self.handles.remove(language) {
             let _ = handle.shutdown(); // Best-effort cleanup
         }
+        self.idle_since.remove(language);
 
         // Check if server was explicitly disabled by user (via stop command)
         // Don't auto-restart disabled servers
@@ -566,6 +670,7 @@ impl LspManager {
         if let Some(handle) = self.handles.remove(language) {
             let _ = handle.shutdown();
         }
+        self.idle_since.remove(language);
 
         // Spawn new server (bypassing auto_start for user-initiated restart)
         if self.force_spawn(language).is_some() {
@@ -624,6 +729,7 @@ impl LspManager {
             self.disabled_languages.insert(language.to_string());
             // Cancel any pending restarts
             self.pending_restarts.remove(language);
+            self.idle_since.remove(language);
             // Remove from restart cooldown
             self.restart_cooldown.remove(language);
             // Also remove from allowed languages so it will require confirmation again
@@ -643,6 +749,53 @@ impl LspManager {
             let _ = handle.shutdown();
         }
         self.handles.clear();
+        self.idle_since.clear();
+    }
+
+    /// Mark a language's server as idle after its last open document was
+    /// closed. It keeps running until `release_idle_servers` finds it idle for
+    /// longer than the idle timeout, or a file of the language is opened again
+    /// and `try_spawn` reattaches to it.
+    pub fn mark_idle(&mut self, language: &str) {
+        if self.handles.contains_key(language) {
+            tracing::debug!("LSP server for {} has no open documents", language);
+            self.idle_since
+                .entry(language.to_string())
+                .or_insert_with(Instant::now);
+        }
+    }
+
+    /// Check if a language's server is running with no open documents
+    pub fn is_idle(&self, language: &str) -> bool {
+        self.idle_since.contains_key(language)
+    }
+
+    /// Shut down the servers that have been idle for at least `idle_timeout`
+    ///
+    /// Unlike `shutdown_server`, this doesn't disable the language: its server
+    /// is spawned again the next time one of its files is opened.
+    /// Returns the languages whose servers were shut down.
+    pub fn release_idle_servers(&mut self, idle_timeout: Duration) -> Vec<String> {
+        let now = Instant::now();
+        let expired: Vec<String> = self
+            .idle_since
+            .iter()
+            .filter(|(_, since)| now.duration_since(**since) >= idle_timeout)
+            .map(|(language, _)| language.clone())
+            .collect();
+
+        for language in &expired {
+            self.idle_since.remove(language);
+            if let Some(handle) = self.handles.remove(language) {
+                tracing::info!(
+                    "Shutting down LSP server for {} after {}s without open documents",
+                    language,
+                    idle_timeout.as_secs()
+                );
+                let _ = handle.shutdown();
+            }
+        }
+        expired
     }
 }
 
@@ -781,6 +934,30 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_lsp_manager_idle_without_server() {
+        let mut manager = LspManager::new(None);
+
+        // Only running servers can be idle
+        manager.mark_idle("rust");
+        assert!(!manager.is_idle("rust"));
+        assert!(manager.release_idle_servers(Duration::ZERO).is_empty());
+    }
+
+    #[test]
+    fn test_lsp_manager_workspace_folders_without_server() {
+        let mut manager = LspManager::new(None);
+        let folder: Uri = "file:///repo/web".parse().unwrap();
+
+        // No server to tell or restart
+        assert!(manager
+            .set_workspace_folders(vec![folder.clone()])
+            .is_empty());
+        assert_eq!(manager.workspace_folders(), &[folder]);
+        assert!(manager.set_workspace_folders(Vec::new()).is_empty());
+        assert!(manager.workspace_folders().is_empty());
+    }
+
     #[test]
     fn test_lsp_manager_shutdown_all() {
         let mut manager = LspManager::new(None);
@@ -799,6 +976,8 @@ mod tests {
                 filenames: vec![],
                 grammar: "rust".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -808,6 +987,13 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
         languages.insert(
@@ -817,6 +1003,8 @@ mod tests {
                 filenames: vec![],
                 grammar: "javascript".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -826,6 +1014,13 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
         languages.insert(
@@ -835,6 +1030,8 @@ mod tests {
                 filenames: vec![],
                 grammar: "c_sharp".to_string(),
                 comment_prefix: Some("//".to_string()),
+                block_comment_start: None,
+                block_comment_end: None,
                 auto_indent: true,
                 highlighter: crate::config::HighlighterPreference::Auto,
                 textmate_grammar: None,
@@ -844,6 +1041,13 @@ mod tests {
                 formatter: None,
                 format_on_save: false,
                 on_save: vec![],
+                conceal: vec![],
+                repl: None,
+                rulers: None,
+                max_line_length: None,
+                increase_indent_pattern: None,
+                decrease_indent_pattern: None,
+                continuation_pattern: None,
             },
         );
         languages
@@ -106,6 +106,12 @@
 //! - If not, we send `didOpen` first, then add the ID to the set
 //! - This handles: multiple servers per buffer, server restarts (new ID)
 //!
+//! Closing a buffer sends `didClose` to the servers it was opened with. A
+//! server left with no open documents is marked idle in the `LspManager` and
+//! kept running for `lsp_idle_timeout_secs`; opening a file of its language
+//! in that time reattaches to it through `try_spawn()`, otherwise it's shut
+//! down (without being disabled, so the next file respawns it).
+//!
 //! # Error Handling
 //!
 //! - **Server crashes**: Automatic restart with exponential backoff
@@ -4,23 +4,40 @@
 //! I/O, and async operations.
 
 pub mod async_bridge;
+pub mod backup;
 pub mod clipboard;
+pub mod collab;
+pub mod encryption;
+pub mod event_replay;
+pub mod file_lock;
+pub mod frame_budget;
 pub mod fs;
 #[cfg(target_os = "linux")]
 pub mod gpm;
+pub mod image_preview;
+pub mod instance_server;
 pub mod log_dirs;
 pub mod lsp;
 pub mod plugins;
 pub mod process_limits;
+pub mod profiler;
+pub mod project_env;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
+pub mod shell_integration;
 pub mod signal_handler;
+pub mod spell;
 pub mod status_log;
 pub mod styled_html;
+pub mod tasks;
 pub mod telemetry;
 pub mod terminal;
+pub mod terminal_background;
+pub mod terminal_capabilities;
 pub mod terminal_modes;
 pub mod time_source;
 pub mod tracing_setup;
+pub mod undo_history;
 pub mod warning_log;
+pub mod workspace_trust;
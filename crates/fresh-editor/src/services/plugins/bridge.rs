@@ -1,9 +1,11 @@
 use crate::config_io::DirectoryContext;
 use crate::i18n;
 use crate::input::command_registry::CommandRegistry;
+use crate::services::plugins::sandbox::PluginSandbox;
 use crate::services::signal_handler;
 use crate::view::theme;
-use fresh_core::services::PluginServiceBridge;
+use fresh_core::api::PermissionRequest;
+use fresh_core::services::{PermissionDecision, PluginLimits, PluginServiceBridge};
 use std::any::Any;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -12,6 +14,7 @@ use std::sync::{Arc, RwLock};
 pub struct EditorServiceBridge {
     pub command_registry: Arc<RwLock<CommandRegistry>>,
     pub dir_context: DirectoryContext,
+    pub sandbox: Arc<RwLock<PluginSandbox>>,
 }
 
 impl PluginServiceBridge for EditorServiceBridge {
@@ -99,4 +102,16 @@ impl PluginServiceBridge for EditorServiceBridge {
     fn config_dir(&self) -> PathBuf {
         self.dir_context.config_dir.clone()
     }
+
+    fn check_permission(
+        &self,
+        plugin_name: &str,
+        request: &PermissionRequest,
+    ) -> PermissionDecision {
+        self.sandbox.read().unwrap().check(plugin_name, request)
+    }
+
+    fn plugin_limits(&self) -> PluginLimits {
+        self.sandbox.read().unwrap().limits()
+    }
 }
@@ -3,5 +3,5 @@
 //! Re-exports hook system types from fresh-core for backward compatibility.
 
 pub use fresh_core::hooks::{
-    hook_args_to_json, HookArgs, HookCallback, HookRegistry, LineInfo, LspLocation,
+    hook_args_to_json, EditInfo, HookArgs, HookCallback, HookRegistry, LineInfo, LspLocation,
 };
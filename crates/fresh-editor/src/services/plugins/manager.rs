@@ -5,6 +5,7 @@
 //! disabled, all methods are no-ops, avoiding the need for cfg attributes
 //! scattered throughout the codebase.
 
+use super::sandbox::PluginSandbox;
 use crate::config_io::DirectoryContext;
 use crate::input::command_registry::CommandRegistry;
 use fresh_core::config::PluginConfig;
@@ -32,11 +33,12 @@ impl PluginManager {
     /// Create a new plugin manager.
     ///
     /// When `plugins` feature is enabled and `enable` is true, spawns the plugin thread.
-    /// Otherwise, creates a no-op manager.
+    /// Otherwise, creates a no-op manager. `sandbox` decides what plugins may do.
     pub fn new(
         enable: bool,
         command_registry: Arc<RwLock<CommandRegistry>>,
         dir_context: DirectoryContext,
+        sandbox: Arc<RwLock<PluginSandbox>>,
     ) -> Self {
         #[cfg(feature = "plugins")]
         {
@@ -44,6 +46,7 @@ impl PluginManager {
                 let services = Arc::new(EditorServiceBridge {
                     command_registry: command_registry.clone(),
                     dir_context,
+                    sandbox,
                 });
                 match PluginThreadHandle::spawn(services) {
                     Ok(handle) => {
@@ -67,6 +70,7 @@ impl PluginManager {
         {
             let _ = command_registry; // Suppress unused warning
             let _ = dir_context; // Suppress unused warning
+            let _ = sandbox; // Suppress unused warning
             if enable {
                 tracing::warn!("Plugins requested but compiled without plugin support");
             }
@@ -13,12 +13,14 @@ pub mod bridge;
 pub mod event_hooks;
 pub mod hooks;
 pub mod manager;
+pub mod sandbox;
 
 #[cfg(feature = "embed-plugins")]
 pub mod embedded;
 
 // Re-export the main interface
 pub use manager::PluginManager;
+pub use sandbox::PluginSandbox;
 
 // Re-export thread module for oneshot channels used by plugin action execution
 #[cfg(feature = "plugins")]
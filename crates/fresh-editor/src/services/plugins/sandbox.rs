@@ -0,0 +1,320 @@
+//! Plugin sandbox
+//!
+//! With `editor.plugin_sandbox` on, a plugin may read and write files in its
+//! own directory; anything else needs permission: reading or writing other
+//! files, and running processes (which is also how plugins reach the
+//! network). Permissions come from `plugins.<name>.permissions` in the
+//! config, or from the user when the plugin first tries: the editor asks
+//! whether to allow it always (saved to the config), for this session, or
+//! not at all. Plugins that ship with Fresh aren't checked.
+//!
+//! The sandbox also holds the runtime's resource limits: the heap size and
+//! how long a plugin may run before it's interrupted.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use fresh_core::api::PermissionRequest;
+use fresh_core::services::{PermissionDecision, PluginLimits};
+
+use crate::config::{Config, PermissionMode, PluginPermissions};
+
+/// What a permission request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Access {
+    Read,
+    Write,
+    Subprocess,
+}
+
+impl Access {
+    pub fn of(request: &PermissionRequest) -> Self {
+        match request {
+            PermissionRequest::Read(_) => Access::Read,
+            PermissionRequest::Write(_) => Access::Write,
+            PermissionRequest::Subprocess(_) => Access::Subprocess,
+        }
+    }
+}
+
+/// What plugins may do
+#[derive(Debug, Default)]
+pub struct PluginSandbox {
+    enabled: bool,
+    limits: PluginLimits,
+    /// The script each plugin was loaded from
+    plugin_files: HashMap<String, PathBuf>,
+    /// The directories of the plugins that ship with Fresh
+    bundled_dirs: Vec<PathBuf>,
+    /// Permissions from the config
+    permissions: HashMap<String, PluginPermissions>,
+    /// Permissions the user granted for this session
+    session: HashMap<String, PluginPermissions>,
+    /// Requests the user denied for this session
+    denied: HashSet<(String, Access)>,
+}
+
+impl PluginSandbox {
+    pub fn new(config: &Config) -> Self {
+        let mut sandbox = Self::default();
+        sandbox.update_config(config);
+        sandbox
+    }
+
+    /// Apply the sandbox settings and plugin permissions from `config`
+    pub fn update_config(&mut self, config: &Config) {
+        let editor = &config.editor;
+        self.enabled = editor.plugin_sandbox;
+        self.limits = PluginLimits {
+            memory_limit: (editor.plugin_memory_limit_mb > 0)
+                .then(|| editor.plugin_memory_limit_mb * 1024 * 1024),
+            time_slice: (editor.plugin_time_slice_ms > 0)
+                .then(|| Duration::from_millis(editor.plugin_time_slice_ms)),
+        };
+        self.permissions = config
+            .plugins
+            .iter()
+            .map(|(name, plugin)| (name.clone(), plugin.permissions.clone()))
+            .collect();
+    }
+
+    pub fn limits(&self) -> PluginLimits {
+        self.limits
+    }
+
+    /// Record the plugins in `dir` before they're loaded, so they can use
+    /// their own directory. `bundled` plugins ship with Fresh. A plugin
+    /// loaded later under the same name replaces the one recorded before.
+    pub fn add_plugin_dir(&mut self, dir: &Path, bundled: bool) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        if bundled {
+            self.bundled_dirs.push(resolve(dir));
+        }
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if !path
+                .extension()
+                .is_some_and(|ext| ext == "ts" || ext == "js")
+            {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            self.plugin_files.insert(name.to_string(), resolve(&path));
+        }
+    }
+
+    /// Whether `plugin` may do what `request` asks
+    pub fn check(&self, plugin: &str, request: &PermissionRequest) -> PermissionDecision {
+        let plugin_dir = self.plugin_files.get(plugin).and_then(|file| file.parent());
+        let bundled = plugin_dir.is_some_and(|dir| self.bundled_dirs.iter().any(|b| b == dir));
+        if !self.enabled || bundled {
+            return PermissionDecision::Allow;
+        }
+        let allowed = match request {
+            PermissionRequest::Read(path) | PermissionRequest::Write(path) => {
+                let path = resolve(path);
+                let writing = matches!(request, PermissionRequest::Write(_));
+                let in_scope = |dirs: &[PathBuf]| dirs.iter().any(|dir| path.starts_with(dir));
+                plugin_dir.is_some_and(|dir| path.starts_with(dir))
+                    || [self.permissions.get(plugin), self.session.get(plugin)]
+                        .into_iter()
+                        .flatten()
+                        .any(|permissions| {
+                            in_scope(&permissions.write)
+                                || (!writing && in_scope(&permissions.read))
+                        })
+            }
+            PermissionRequest::Subprocess(_) => {
+                let configured = self
+                    .permissions
+                    .get(plugin)
+                    .map(|permissions| permissions.subprocess)
+                    .unwrap_or_default();
+                match configured {
+                    PermissionMode::Allow => true,
+                    PermissionMode::Deny => return PermissionDecision::Deny,
+                    PermissionMode::Ask => self
+                        .session
+                        .get(plugin)
+                        .is_some_and(|session| session.subprocess == PermissionMode::Allow),
+                }
+            }
+        };
+        if allowed {
+            PermissionDecision::Allow
+        } else if self
+            .denied
+            .contains(&(plugin.to_string(), Access::of(request)))
+        {
+            PermissionDecision::Deny
+        } else {
+            PermissionDecision::Ask
+        }
+    }
+
+    /// Allow `plugin` to do what `request` asks for the rest of the session
+    pub fn allow_for_session(&mut self, plugin: &str, request: &PermissionRequest, scope: PathBuf) {
+        let permissions = self.session.entry(plugin.to_string()).or_default();
+        match request {
+            PermissionRequest::Read(_) => permissions.read.push(scope),
+            PermissionRequest::Write(_) => permissions.write.push(scope),
+            PermissionRequest::Subprocess(_) => permissions.subprocess = PermissionMode::Allow,
+        }
+    }
+
+    /// Don't let `plugin` do what `request` asks, or ask again, this session
+    pub fn deny_for_session(&mut self, plugin: &str, request: &PermissionRequest) {
+        self.denied
+            .insert((plugin.to_string(), Access::of(request)));
+    }
+}
+
+/// The directory to grant access to for a read or write of `path`: the
+/// working directory if it's inside it, otherwise the directory itself or
+/// the one holding the file
+pub fn permission_scope(path: &Path, working_dir: &Path) -> PathBuf {
+    let path = resolve(path);
+    let working_dir = resolve(working_dir);
+    if path.starts_with(&working_dir) {
+        working_dir
+    } else if path.is_dir() {
+        path
+    } else {
+        path.parent().map(Path::to_path_buf).unwrap_or(path)
+    }
+}
+
+/// `path` made absolute, with symlinks and `..` resolved where it exists, so
+/// a plugin can't step out of a directory it may use
+fn resolve(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+    if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+        if let Ok(parent) = parent.canonicalize() {
+            return parent.join(name);
+        }
+    }
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            component => resolved.push(component),
+        }
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sandbox(temp: &TempDir) -> PluginSandbox {
+        let plugins = temp.path().join("plugins");
+        std::fs::create_dir_all(&plugins).unwrap();
+        std::fs::write(plugins.join("todo.ts"), "").unwrap();
+        let mut sandbox = PluginSandbox::new(&Config::default());
+        sandbox.add_plugin_dir(&plugins, false);
+        sandbox
+    }
+
+    #[test]
+    fn plugins_may_use_their_own_directory() {
+        let temp = TempDir::new().unwrap();
+        let sandbox = sandbox(&temp);
+        let own = temp.path().join("plugins").join("todo.json");
+        let other = temp.path().join("notes.txt");
+
+        for request in [
+            PermissionRequest::Read(own.clone()),
+            PermissionRequest::Write(own),
+        ] {
+            assert_eq!(sandbox.check("todo", &request), PermissionDecision::Allow);
+        }
+        let escape = temp.path().join("plugins").join("..").join("notes.txt");
+        for request in [
+            PermissionRequest::Read(other),
+            PermissionRequest::Write(escape),
+            PermissionRequest::Subprocess("git".to_string()),
+        ] {
+            assert_eq!(sandbox.check("todo", &request), PermissionDecision::Ask);
+        }
+    }
+
+    #[test]
+    fn only_plugins_loaded_from_the_bundled_directory_are_exempt() {
+        let temp = TempDir::new().unwrap();
+        let bundled = temp.path().join("bundled");
+        let user = temp.path().join("user");
+        for dir in [&bundled, &user] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("git.ts"), "").unwrap();
+        }
+        std::fs::write(bundled.join("todo.ts"), "").unwrap();
+        let mut sandbox = PluginSandbox::new(&Config::default());
+        sandbox.add_plugin_dir(&bundled, true);
+        sandbox.add_plugin_dir(&user, false);
+
+        let request = PermissionRequest::Subprocess("git".to_string());
+        assert_eq!(sandbox.check("todo", &request), PermissionDecision::Allow);
+        // The user's plugin of the same name replaces the bundled one
+        assert_eq!(sandbox.check("git", &request), PermissionDecision::Ask);
+    }
+
+    #[test]
+    fn grants_and_denials() {
+        let temp = TempDir::new().unwrap();
+        let mut sandbox = sandbox(&temp);
+        let notes = temp.path().join("notes").join("today.md");
+        let read = PermissionRequest::Read(notes.clone());
+        let write = PermissionRequest::Write(notes);
+
+        sandbox.allow_for_session("todo", &read, temp.path().join("notes"));
+        assert_eq!(sandbox.check("todo", &read), PermissionDecision::Allow);
+        assert_eq!(sandbox.check("todo", &write), PermissionDecision::Ask);
+        sandbox.deny_for_session("todo", &write);
+        assert_eq!(sandbox.check("todo", &write), PermissionDecision::Deny);
+
+        // Config permissions, and a config denial wins over a session grant
+        let git = PermissionRequest::Subprocess("git".to_string());
+        sandbox.allow_for_session("todo", &git, PathBuf::new());
+        assert_eq!(sandbox.check("todo", &git), PermissionDecision::Allow);
+        let mut config = Config::default();
+        let mut plugin = crate::config::PluginConfig::default();
+        plugin.permissions.subprocess = PermissionMode::Deny;
+        config.plugins.insert("todo".to_string(), plugin);
+        sandbox.update_config(&config);
+        assert_eq!(sandbox.check("todo", &git), PermissionDecision::Deny);
+
+        // Everything is allowed with the sandbox off
+        config.editor.plugin_sandbox = false;
+        sandbox.update_config(&config);
+        assert_eq!(sandbox.check("todo", &git), PermissionDecision::Allow);
+    }
+
+    #[test]
+    fn scope_is_the_working_directory_or_the_parent() {
+        let temp = TempDir::new().unwrap();
+        let working_dir = temp.path().join("project");
+        std::fs::create_dir_all(working_dir.join("src")).unwrap();
+        let root = temp.path().canonicalize().unwrap();
+
+        assert_eq!(
+            permission_scope(&working_dir.join("src").join("main.rs"), &working_dir),
+            root.join("project")
+        );
+        assert_eq!(
+            permission_scope(&temp.path().join("notes.txt"), &working_dir),
+            root
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! Frame and latency measurements for the Profiler buffer
+//!
+//! Each frame records how long every part of rendering took, and the event
+//! loop records how long an input event waited until a frame showing it was
+//! drawn. Only the most recent frames are kept; latencies go into a
+//! histogram covering the whole session. Language server requests the user
+//! waits for are timed from sending to the response, or counted as
+//! cancelled when the user moved on first.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How many frames to keep
+const FRAME_HISTORY: usize = 120;
+
+/// Upper bounds of the latency histogram buckets, in milliseconds. A last
+/// bucket holds everything slower.
+pub const LATENCY_BUCKETS_MS: [u64; 8] = [1, 2, 4, 8, 16, 33, 66, 100];
+
+/// How many responses to keep the times of, per kind of LSP request
+const LSP_RESPONSE_HISTORY: usize = 50;
+
+/// A part of drawing a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Subsystem {
+    /// Loading visible text and updating spell check, link and search overlays
+    Prepare,
+    /// The file explorer
+    FileExplorer,
+    /// Plugin render hooks and the commands they send
+    PluginHooks,
+    /// Buffer contents, tabs and terminals
+    Content,
+    /// Status bar, prompt, popups, menus and dialogs
+    Chrome,
+    /// Writing the frame to the terminal
+    Output,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::Prepare,
+        Subsystem::FileExplorer,
+        Subsystem::PluginHooks,
+        Subsystem::Content,
+        Subsystem::Chrome,
+        Subsystem::Output,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A kind of LSP request whose response the user waits for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LspRequestKind {
+    Completion,
+    Hover,
+    Definition,
+    References,
+}
+
+impl LspRequestKind {
+    pub const ALL: [LspRequestKind; 4] = [
+        LspRequestKind::Completion,
+        LspRequestKind::Hover,
+        LspRequestKind::Definition,
+        LspRequestKind::References,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Responses to and cancellations of one kind of LSP request
+#[derive(Debug, Default)]
+struct LspRequests {
+    /// Times of the recent responses
+    recent: VecDeque<Duration>,
+    answered: u64,
+    cancelled: u64,
+}
+
+/// The time one frame took, in total and per subsystem
+#[derive(Debug, Clone, Copy, Default)]
+struct Frame {
+    total: Duration,
+    subsystems: [Duration; Subsystem::ALL.len()],
+}
+
+/// Average and worst of a set of durations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub average: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn of(durations: impl Iterator<Item = Duration>) -> Option<Self> {
+        let (count, sum, max) = durations.fold(
+            (0u32, Duration::ZERO, Duration::ZERO),
+            |(count, sum, max), duration| (count + 1, sum + duration, max.max(duration)),
+        );
+        (count > 0).then(|| Timing {
+            average: sum / count,
+            max,
+        })
+    }
+}
+
+/// Measurements of recent frames and of input latency
+#[derive(Debug, Default)]
+pub struct Profiler {
+    frames: VecDeque<Frame>,
+    /// The frame being drawn
+    current: Frame,
+    /// Frames drawn this session
+    frame_count: u64,
+    latency: [u64; LATENCY_BUCKETS_MS.len() + 1],
+    lsp_requests: [LspRequests; LspRequestKind::ALL.len()],
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to the time `subsystem` took in the frame being drawn
+    pub fn record(&mut self, subsystem: Subsystem, elapsed: Duration) {
+        self.current.subsystems[subsystem.index()] += elapsed;
+    }
+
+    /// Add the time since `start` to `subsystem`, and restart `start` for the
+    /// subsystem that comes next
+    pub fn lap(&mut self, subsystem: Subsystem, start: &mut Instant) {
+        let now = Instant::now();
+        self.record(subsystem, now - *start);
+        *start = now;
+    }
+
+    /// Finish the frame being drawn, which took `elapsed` to render
+    pub fn finish_frame(&mut self, elapsed: Duration) {
+        let mut frame = std::mem::take(&mut self.current);
+        frame.total = elapsed;
+        if self.frames.len() == FRAME_HISTORY {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+        self.frame_count += 1;
+    }
+
+    /// Record that the last frame took `elapsed` in total, including writing
+    /// it to the terminal
+    pub fn finish_output(&mut self, elapsed: Duration) {
+        if let Some(frame) = self.frames.back_mut() {
+            frame.subsystems[Subsystem::Output.index()] = elapsed.saturating_sub(frame.total);
+            frame.total = frame.total.max(elapsed);
+        }
+    }
+
+    /// Record how long an input event waited until a frame showed it
+    pub fn record_latency(&mut self, latency: Duration) {
+        let ms = latency.as_millis();
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| ms < u128::from(bound))
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency[bucket] += 1;
+    }
+
+    /// Frames drawn this session
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Frame times over the recent frames
+    pub fn frame_timing(&self) -> Option<Timing> {
+        Timing::of(self.frames.iter().map(|frame| frame.total))
+    }
+
+    /// Time `subsystem` took over the recent frames
+    pub fn subsystem_timing(&self, subsystem: Subsystem) -> Option<Timing> {
+        Timing::of(
+            self.frames
+                .iter()
+                .map(|frame| frame.subsystems[subsystem.index()]),
+        )
+    }
+
+    /// How many events fell in each latency bucket, slowest last
+    pub fn latency_histogram(&self) -> &[u64] {
+        &self.latency
+    }
+
+    /// Record that a language server answered a request after `elapsed`
+    pub fn record_lsp_response(&mut self, kind: LspRequestKind, elapsed: Duration) {
+        let requests = &mut self.lsp_requests[kind.index()];
+        if requests.recent.len() == LSP_RESPONSE_HISTORY {
+            requests.recent.pop_front();
+        }
+        requests.recent.push_back(elapsed);
+        requests.answered += 1;
+    }
+
+    /// Record that a request was cancelled before it was answered
+    pub fn record_lsp_cancelled(&mut self, kind: LspRequestKind) {
+        self.lsp_requests[kind.index()].cancelled += 1;
+    }
+
+    /// How many requests of a kind were answered and cancelled this session
+    pub fn lsp_request_counts(&self, kind: LspRequestKind) -> (u64, u64) {
+        let requests = &self.lsp_requests[kind.index()];
+        (requests.answered, requests.cancelled)
+    }
+
+    /// Response times of the recent requests of a kind
+    pub fn lsp_response_timing(&self, kind: LspRequestKind) -> Option<Timing> {
+        Timing::of(self.lsp_requests[kind.index()].recent.iter().copied())
+    }
+}
+
+/// Memory the process uses, in bytes, where the OS reports it
+pub fn resident_memory() -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let kb = status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse::<usize>()
+            .ok()?;
+        Some(kb * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn frames_and_subsystems() {
+        let mut profiler = Profiler::new();
+        assert_eq!(profiler.frame_timing(), None);
+
+        profiler.record(Subsystem::Content, ms(2));
+        profiler.record(Subsystem::Content, ms(1));
+        profiler.finish_frame(ms(4));
+        profiler.finish_output(ms(10));
+        profiler.record(Subsystem::Content, ms(5));
+        profiler.finish_frame(ms(6));
+
+        assert_eq!(profiler.frame_count(), 2);
+        assert_eq!(
+            profiler.frame_timing(),
+            Some(Timing {
+                average: ms(8),
+                max: ms(10)
+            })
+        );
+        assert_eq!(
+            profiler.subsystem_timing(Subsystem::Content),
+            Some(Timing {
+                average: ms(4),
+                max: ms(5)
+            })
+        );
+        assert_eq!(
+            profiler.subsystem_timing(Subsystem::Output).unwrap().max,
+            ms(6)
+        );
+
+        // Only recent frames are kept
+        for _ in 0..FRAME_HISTORY {
+            profiler.finish_frame(ms(1));
+        }
+        assert_eq!(profiler.frame_timing().unwrap().max, ms(1));
+        assert_eq!(profiler.frame_count(), 2 + FRAME_HISTORY as u64);
+    }
+
+    #[test]
+    fn latency_buckets() {
+        let mut profiler = Profiler::new();
+        for latency in [0, 1, 15, 16, 99, 100, 2000] {
+            profiler.record_latency(ms(latency));
+        }
+        assert_eq!(profiler.latency_histogram(), &[1, 1, 0, 0, 1, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn lsp_requests() {
+        let mut profiler = Profiler::new();
+        assert_eq!(profiler.lsp_response_timing(LspRequestKind::Hover), None);
+
+        profiler.record_lsp_response(LspRequestKind::Hover, ms(10));
+        profiler.record_lsp_response(LspRequestKind::Hover, ms(30));
+        profiler.record_lsp_cancelled(LspRequestKind::Hover);
+        profiler.record_lsp_cancelled(LspRequestKind::Completion);
+
+        assert_eq!(profiler.lsp_request_counts(LspRequestKind::Hover), (2, 1));
+        assert_eq!(
+            profiler.lsp_response_timing(LspRequestKind::Hover),
+            Some(Timing {
+                average: ms(20),
+                max: ms(30)
+            })
+        );
+        assert_eq!(
+            profiler.lsp_request_counts(LspRequestKind::Completion),
+            (0, 1)
+        );
+        assert_eq!(
+            profiler.lsp_response_timing(LspRequestKind::Completion),
+            None
+        );
+
+        // Only recent responses are timed
+        for _ in 0..LSP_RESPONSE_HISTORY {
+            profiler.record_lsp_response(LspRequestKind::Hover, ms(1));
+        }
+        assert_eq!(
+            profiler
+                .lsp_response_timing(LspRequestKind::Hover)
+                .unwrap()
+                .max,
+            ms(1)
+        );
+        assert_eq!(
+            profiler.lsp_request_counts(LspRequestKind::Hover).0,
+            2 + LSP_RESPONSE_HISTORY as u64
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! The project's environment
+//!
+//! Tools are often set up per project: direnv loads `.envrc` when the shell
+//! enters the project directory, and many projects keep variables in a
+//! `.env` file. The processes the editor spawns (language servers, tasks and
+//! terminals) get the same environment, so they find the same tool versions
+//! and paths as the user's shell:
+//!
+//! - `.env` is read first: `KEY=value` lines, optionally starting with
+//!   `export`, with `#` comments. Values may be single quoted (taken as
+//!   written) or double quoted (`\n`, `\"` and `\\` escapes).
+//! - Then, if there's an `.envrc`, `direnv export json` is run in the project
+//!   and what it sets or unsets overrides `.env`. The `.envrc` must have been
+//!   allowed with `direnv allow`.
+//!
+//! Both come from the project, so they're only loaded in a trusted workspace.
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+/// The dotenv file, relative to the working directory
+pub const DOTENV_FILE: &str = ".env";
+
+/// direnv's file, relative to the working directory
+pub const ENVRC_FILE: &str = ".envrc";
+
+/// What the project's environment changes in the editor's own
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectEnv {
+    /// Each variable's value, or `None` for one that's unset
+    vars: BTreeMap<String, Option<String>>,
+    /// The files the variables came from
+    sources: Vec<String>,
+    /// Why a file couldn't be loaded, or a line of it
+    errors: Vec<String>,
+}
+
+impl ProjectEnv {
+    /// Load the environment of the project at `working_dir`
+    pub fn load(working_dir: &Path) -> Self {
+        let mut env = Self::default();
+
+        let dotenv = working_dir.join(DOTENV_FILE);
+        match std::fs::read_to_string(&dotenv) {
+            Ok(content) => {
+                env.sources.push(DOTENV_FILE.to_string());
+                for entry in parse_dotenv(&content) {
+                    match entry {
+                        Ok((key, value)) => {
+                            env.vars.insert(key, Some(value));
+                        }
+                        Err(e) => env.errors.push(format!("{}: {}", DOTENV_FILE, e)),
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => env.errors.push(format!("{}: {}", DOTENV_FILE, e)),
+        }
+
+        if working_dir.join(ENVRC_FILE).is_file() {
+            env.sources.push(format!("{} (direnv)", ENVRC_FILE));
+            match direnv_export(working_dir) {
+                Ok(vars) => env.vars.extend(vars),
+                Err(e) => env.errors.push(format!("{}: {}", ENVRC_FILE, e)),
+            }
+        }
+        env
+    }
+
+    /// The variables set (`Some`) or unset (`None`)
+    pub fn vars(&self) -> &BTreeMap<String, Option<String>> {
+        &self.vars
+    }
+
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// A variable as the spawned processes see it: the project's value, or
+    /// else the editor's
+    pub fn var(&self, key: &str) -> Option<OsString> {
+        match self.vars.get(key) {
+            Some(value) => value.as_ref().map(OsString::from),
+            None => std::env::var_os(key),
+        }
+    }
+
+    /// Set and unset the project's variables for a command
+    pub fn apply(&self, command: &mut impl CommandEnv) {
+        for (key, value) in &self.vars {
+            match value {
+                Some(value) => command.set_var(key, value),
+                None => command.remove_var(key),
+            }
+        }
+    }
+}
+
+/// A command about to be spawned, whose environment can be changed
+pub trait CommandEnv {
+    fn set_var(&mut self, key: &str, value: &str);
+    fn remove_var(&mut self, key: &str);
+}
+
+impl CommandEnv for Command {
+    fn set_var(&mut self, key: &str, value: &str) {
+        self.env(key, value);
+    }
+
+    fn remove_var(&mut self, key: &str) {
+        self.env_remove(key);
+    }
+}
+
+impl CommandEnv for tokio::process::Command {
+    fn set_var(&mut self, key: &str, value: &str) {
+        self.env(key, value);
+    }
+
+    fn remove_var(&mut self, key: &str) {
+        self.env_remove(key);
+    }
+}
+
+impl CommandEnv for portable_pty::CommandBuilder {
+    fn set_var(&mut self, key: &str, value: &str) {
+        self.env(key, value);
+    }
+
+    fn remove_var(&mut self, key: &str) {
+        self.env_remove(key);
+    }
+}
+
+/// The variables of a dotenv file, or why a line isn't one, in order
+pub fn parse_dotenv(content: &str) -> Vec<Result<(String, String), String>> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            Some(parse_dotenv_line(line).map_err(|e| format!("line {}: {}", index + 1, e)))
+        })
+        .collect()
+}
+
+fn parse_dotenv_line(line: &str) -> Result<(String, String), String> {
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let Some((key, value)) = line.split_once('=') else {
+        return Err("expected KEY=value".to_string());
+    };
+    let key = key.trim();
+    let valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        return Err(format!("invalid variable name {:?}", key));
+    }
+
+    let value = value.trim();
+    let value = if let Some(rest) = value.strip_prefix('\'') {
+        let Some(end) = rest.find('\'') else {
+            return Err("unterminated quote".to_string());
+        };
+        rest[..end].to_string()
+    } else if let Some(rest) = value.strip_prefix('"') {
+        let mut unescaped = String::new();
+        let mut chars = rest.chars();
+        loop {
+            match chars.next() {
+                None => return Err("unterminated quote".to_string()),
+                Some('"') => break,
+                Some('\\') => match chars.next() {
+                    Some('n') => unescaped.push('\n'),
+                    Some('t') => unescaped.push('\t'),
+                    Some(c) => unescaped.push(c),
+                    None => return Err("unterminated quote".to_string()),
+                },
+                Some(c) => unescaped.push(c),
+            }
+        }
+        unescaped
+    } else {
+        // An unquoted value ends at a comment
+        match value.find(" #") {
+            Some(end) => value[..end].trim_end().to_string(),
+            None => value.to_string(),
+        }
+    };
+    Ok((key.to_string(), value))
+}
+
+/// What direnv changes for `working_dir`: `direnv export json` prints an
+/// object of the variables it sets, with `null` for those it unsets
+fn direnv_export(working_dir: &Path) -> Result<BTreeMap<String, Option<String>>, String> {
+    let output = Command::new("direnv")
+        .args(["export", "json"])
+        .current_dir(working_dir)
+        // Only errors are written to stderr
+        .env("DIRENV_LOG_FORMAT", "")
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "direnv isn't installed".to_string(),
+            _ => e.to_string(),
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or("direnv failed")
+            .trim()
+            .to_string());
+    }
+    parse_direnv_export(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse the output of `direnv export json`, which is empty when there's
+/// nothing to change
+fn parse_direnv_export(output: &str) -> Result<BTreeMap<String, Option<String>>, String> {
+    if output.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_json::from_str(output).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dotenv() {
+        let content = r#"
+# Tools
+export PATH=/opt/node/bin:/usr/bin
+NODE_ENV=development # inline comment
+GREETING="hello \"world\"\nbye"
+RAW='a $b \n'
+EMPTY=
+1BAD=x
+no equals sign
+"#;
+        let entries = parse_dotenv(content);
+        let ok = |key: &str, value: &str| Ok((key.to_string(), value.to_string()));
+        assert_eq!(
+            entries,
+            vec![
+                ok("PATH", "/opt/node/bin:/usr/bin"),
+                ok("NODE_ENV", "development"),
+                ok("GREETING", "hello \"world\"\nbye"),
+                ok("RAW", "a $b \\n"),
+                ok("EMPTY", ""),
+                Err("line 8: invalid variable name \"1BAD\"".to_string()),
+                Err("line 9: expected KEY=value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_direnv_export() {
+        assert_eq!(parse_direnv_export("\n"), Ok(BTreeMap::new()));
+        let vars = parse_direnv_export(r#"{"GOPATH": "/home/u/go", "OLD": null}"#).unwrap();
+        assert_eq!(vars["GOPATH"].as_deref(), Some("/home/u/go"));
+        assert_eq!(vars["OLD"], None);
+    }
+
+    #[test]
+    fn test_load_reads_dotenv_and_applies_it() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(DOTENV_FILE), "FRESH_TEST_VAR=42\n").unwrap();
+        let env = ProjectEnv::load(dir.path());
+        assert_eq!(env.sources(), [DOTENV_FILE.to_string()]);
+        assert!(env.errors().is_empty());
+        assert_eq!(env.var("FRESH_TEST_VAR"), Some(OsString::from("42")));
+
+        let mut command = Command::new("true");
+        env.apply(&mut command);
+        assert!(command
+            .get_envs()
+            .any(|(key, value)| key == "FRESH_TEST_VAR" && value == Some("42".as_ref())));
+    }
+}
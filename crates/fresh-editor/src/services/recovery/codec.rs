@@ -0,0 +1,395 @@
+//! Optional compression and encryption for recovery and session data
+//!
+//! Recovery chunks are plaintext copies of the user's documents, and sessions
+//! record which files were open. [`DataCodec`] can zstd-compress and/or
+//! encrypt (ChaCha20-Poly1305) these files at rest.
+//!
+//! ## Format
+//!
+//! ```text
+//! MAGIC (8 bytes) | flags (1 byte) | [nonce (12 bytes)] | payload
+//! ```
+//!
+//! The payload is compressed first if `FLAG_COMPRESSED` is set, then sealed if
+//! `FLAG_ENCRYPTED` is set (the header is authenticated as associated data).
+//! Data without the header is returned unchanged, so files written with both
+//! options off, or by older versions, stay readable.
+//!
+//! The encryption key is derived from a passphrase with PBKDF2-HMAC-SHA256
+//! and a random salt kept in the data directory. The passphrase itself is
+//! never stored: it comes from `FRESH_RECOVERY_PASSPHRASE` or from the output
+//! of a user-configured command (e.g. one that reads the OS keychain).
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::process::Command;
+
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Environment variable holding the encryption passphrase
+pub const PASSPHRASE_ENV: &str = "FRESH_RECOVERY_PASSPHRASE";
+
+/// Marks data written by [`DataCodec::encode`]
+const MAGIC: &[u8; 8] = b"\0FRESHDC";
+/// Payload is zstd-compressed
+const FLAG_COMPRESSED: u8 = 0b01;
+/// Payload is sealed with ChaCha20-Poly1305
+const FLAG_ENCRYPTED: u8 = 0b10;
+/// zstd compression level (the library's default)
+const ZSTD_LEVEL: i32 = 3;
+/// Size of the key derivation salt
+const SALT_LEN: usize = 16;
+/// PBKDF2 rounds; the key is derived once per editor start
+const PBKDF2_ITERATIONS: u32 = 200_000;
+
+/// Encryption state of a codec
+#[derive(Clone)]
+enum Encryption {
+    Off,
+    Key([u8; 32]),
+    /// Encryption was requested but no key could be obtained. Writes fail
+    /// rather than silently falling back to plaintext.
+    Unavailable(String),
+}
+
+/// Encodes and decodes recovery and session files
+#[derive(Clone)]
+pub struct DataCodec {
+    compress: bool,
+    encryption: Encryption,
+}
+
+impl fmt::Debug for DataCodec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encryption = match &self.encryption {
+            Encryption::Off => "off",
+            Encryption::Key(_) => "on",
+            Encryption::Unavailable(_) => "unavailable",
+        };
+        f.debug_struct("DataCodec")
+            .field("compress", &self.compress)
+            .field("encryption", &encryption)
+            .finish()
+    }
+}
+
+impl Default for DataCodec {
+    fn default() -> Self {
+        Self::plain()
+    }
+}
+
+impl DataCodec {
+    /// Codec that stores data as-is
+    pub fn plain() -> Self {
+        Self {
+            compress: false,
+            encryption: Encryption::Off,
+        }
+    }
+
+    /// Codec that only compresses
+    pub fn compressed() -> Self {
+        Self {
+            compress: true,
+            encryption: Encryption::Off,
+        }
+    }
+
+    /// Add encryption with a key derived from `passphrase` and `salt`
+    pub fn with_passphrase(mut self, passphrase: &str, salt: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).expect("iterations are non-zero"),
+            salt,
+            passphrase.as_bytes(),
+            &mut key,
+        );
+        self.encryption = Encryption::Key(key);
+        self
+    }
+
+    /// Build a codec from the editor settings
+    ///
+    /// When encryption is enabled the passphrase is read from
+    /// [`PASSPHRASE_ENV`], falling back to the stdout of `passphrase_command`.
+    /// The salt is created at `salt_path` on first use.
+    pub fn from_settings(
+        compress: bool,
+        encrypt: bool,
+        passphrase_command: &str,
+        salt_path: &Path,
+    ) -> Self {
+        let codec = Self {
+            compress,
+            encryption: Encryption::Off,
+        };
+        if !encrypt {
+            return codec;
+        }
+
+        let key = read_passphrase(passphrase_command).and_then(|passphrase| {
+            let salt = load_or_create_salt(salt_path)
+                .map_err(|e| format!("cannot read salt {}: {}", salt_path.display(), e))?;
+            Ok(codec.clone().with_passphrase(&passphrase, &salt))
+        });
+        match key {
+            Ok(codec) => codec,
+            Err(reason) => {
+                tracing::warn!("Recovery/session encryption unavailable: {}", reason);
+                Self {
+                    encryption: Encryption::Unavailable(reason),
+                    ..codec
+                }
+            }
+        }
+    }
+
+    /// Whether data can be written (false if encryption is requested but has no key)
+    pub fn is_usable(&self) -> bool {
+        !matches!(self.encryption, Encryption::Unavailable(_))
+    }
+
+    /// Whether encoded data is encrypted
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self.encryption, Encryption::Key(_))
+    }
+
+    /// Whether `encode` leaves data unchanged
+    pub fn is_plain(&self) -> bool {
+        !self.compress && matches!(self.encryption, Encryption::Off)
+    }
+
+    /// Encode data for writing to disk
+    pub fn encode(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let key = match &self.encryption {
+            Encryption::Off => None,
+            Encryption::Key(key) => Some(key),
+            Encryption::Unavailable(reason) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("encryption key unavailable: {}", reason),
+                ))
+            }
+        };
+        if !self.compress && key.is_none() {
+            return Ok(data.to_vec());
+        }
+
+        let mut flags = 0;
+        let mut payload = if self.compress {
+            flags |= FLAG_COMPRESSED;
+            zstd::encode_all(data, ZSTD_LEVEL)?
+        } else {
+            data.to_vec()
+        };
+
+        let mut out = MAGIC.to_vec();
+        match key {
+            Some(key) => {
+                flags |= FLAG_ENCRYPTED;
+                out.push(flags);
+
+                let mut nonce = [0u8; NONCE_LEN];
+                SystemRandom::new()
+                    .fill(&mut nonce)
+                    .map_err(|_| io::Error::other("failed to generate nonce"))?;
+                sealing_key(key)
+                    .seal_in_place_append_tag(
+                        Nonce::assume_unique_for_key(nonce),
+                        Aad::from(out.clone()),
+                        &mut payload,
+                    )
+                    .map_err(|_| io::Error::other("encryption failed"))?;
+                out.extend_from_slice(&nonce);
+            }
+            None => out.push(flags),
+        }
+        out.extend_from_slice(&payload);
+        Ok(out)
+    }
+
+    /// Decode data read from disk
+    pub fn decode(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let Some(rest) = data.strip_prefix(MAGIC.as_slice()) else {
+            return Ok(data.to_vec());
+        };
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        let (&flags, rest) = rest
+            .split_first()
+            .ok_or_else(|| invalid("truncated header"))?;
+        let mut payload = if flags & FLAG_ENCRYPTED != 0 {
+            let key = match &self.encryption {
+                Encryption::Key(key) => key,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "data is encrypted but no passphrase is configured",
+                    ))
+                }
+            };
+            if rest.len() < NONCE_LEN {
+                return Err(invalid("truncated nonce"));
+            }
+            let (nonce, sealed) = rest.split_at(NONCE_LEN);
+            let nonce =
+                Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid("invalid nonce"))?;
+            let header = &data[..MAGIC.len() + 1];
+
+            let mut in_out = sealed.to_vec();
+            let len = sealing_key(key)
+                .open_in_place(nonce, Aad::from(header), &mut in_out)
+                .map_err(|_| invalid("decryption failed (wrong passphrase or corrupted data)"))?
+                .len();
+            in_out.truncate(len);
+            in_out
+        } else {
+            rest.to_vec()
+        };
+
+        if flags & FLAG_COMPRESSED != 0 {
+            payload = zstd::decode_all(payload.as_slice())?;
+        }
+        Ok(payload)
+    }
+}
+
+/// Build the AEAD key from raw key bytes
+fn sealing_key(key: &[u8; 32]) -> LessSafeKey {
+    LessSafeKey::new(
+        UnboundKey::new(&aead::CHACHA20_POLY1305, key).expect("key has the algorithm's length"),
+    )
+}
+
+/// Get the passphrase from the environment or the configured command
+fn read_passphrase(command: &str) -> Result<String, String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+
+    if command.trim().is_empty() {
+        return Err(format!(
+            "set {} or configure a passphrase command",
+            PASSPHRASE_ENV
+        ));
+    }
+
+    let shell = crate::services::terminal::detect_shell();
+    let output = Command::new(&shell)
+        .args(["-c", command])
+        .output()
+        .map_err(|e| format!("failed to run passphrase command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "passphrase command exited with {:?}",
+            output.status.code()
+        ));
+    }
+
+    let passphrase = String::from_utf8(output.stdout)
+        .map_err(|_| "passphrase command printed invalid UTF-8".to_string())?;
+    let passphrase = passphrase.trim_end_matches(['\r', '\n']).to_string();
+    if passphrase.is_empty() {
+        return Err("passphrase command printed nothing".to_string());
+    }
+    Ok(passphrase)
+}
+
+/// Read the key derivation salt, creating it on first use. A malformed salt
+/// file is an error rather than replaced: data encrypted with the old salt
+/// could no longer be decrypted.
+fn load_or_create_salt(path: &Path) -> io::Result<Vec<u8>> {
+    match fs::read(path) {
+        Ok(salt) if salt.len() == SALT_LEN => return Ok(salt),
+        Ok(salt) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected {} bytes, found {}", SALT_LEN, salt.len()),
+            ));
+        }
+        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+        Err(_) => {}
+    }
+
+    let mut salt = vec![0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| io::Error::other("failed to generate salt"))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, &salt)?;
+    Ok(salt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_codec_is_identity() {
+        let codec = DataCodec::plain();
+        assert!(codec.is_plain());
+        assert_eq!(codec.encode(b"hello").unwrap(), b"hello");
+        assert_eq!(codec.decode(b"hello").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        let codec = DataCodec::compressed();
+        let data = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".repeat(64);
+        let encoded = codec.encode(&data).unwrap();
+        assert!(encoded.len() < data.len());
+        assert_eq!(codec.decode(&encoded).unwrap(), data);
+
+        // A plain codec can still read compressed data
+        assert_eq!(DataCodec::plain().decode(&encoded).unwrap(), data);
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip_and_wrong_passphrase() {
+        let salt = [7u8; SALT_LEN];
+        let codec = DataCodec::compressed().with_passphrase("secret", &salt);
+        let encoded = codec.encode(b"my document").unwrap();
+        assert!(!encoded.windows(b"document".len()).any(|w| w == b"document"));
+        assert_eq!(codec.decode(&encoded).unwrap(), b"my document");
+
+        let wrong = DataCodec::plain().with_passphrase("guess", &salt);
+        assert!(wrong.decode(&encoded).is_err());
+        assert!(DataCodec::plain().decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_salt_is_created_once_and_malformed_salt_is_kept() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("encryption.salt");
+        let salt = load_or_create_salt(&path).unwrap();
+        assert_eq!(salt.len(), SALT_LEN);
+        assert_eq!(load_or_create_salt(&path).unwrap(), salt);
+
+        fs::write(&path, b"short").unwrap();
+        let err = load_or_create_salt(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(fs::read(&path).unwrap(), b"short");
+    }
+
+    #[test]
+    fn test_unavailable_key_refuses_to_write() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let codec =
+            DataCodec::from_settings(false, true, "exit 1", &temp.path().join("encryption.salt"));
+        if std::env::var(PASSPHRASE_ENV).is_err() {
+            assert!(!codec.is_usable());
+            assert!(codec.encode(b"data").is_err());
+        }
+    }
+}
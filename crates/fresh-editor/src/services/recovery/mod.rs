@@ -19,6 +19,7 @@
 //! ├── {hash}.meta.json       # Recovery metadata with chunk index
 //! ├── {hash}.chunk.0         # Chunk 0 binary content
 //! ├── {hash}.chunk.1         # Chunk 1 binary content
+//! ├── {hash}.journal         # Edits made since the chunks were written
 //! └── ...
 //! ```
 //!
@@ -28,6 +29,11 @@
 //! - For small files/new buffers: single chunk containing full content
 //! - For large files: only modified regions stored as chunks
 //!
+//! Small files/new buffers only write a full snapshot the first time and then
+//! append their edits to a journal, which is replayed on top of the snapshot
+//! during recovery. Once the journal grows past [`RecoveryConfig`]'s limits the
+//! next auto-save writes a fresh snapshot, compacting the journal away.
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -49,14 +55,16 @@
 //! recovery.end_session()?;
 //! ```
 
+pub mod codec;
 mod storage;
 pub mod types;
 
+pub use codec::DataCodec;
 pub use storage::RecoveryStorage;
 pub use types::{
     generate_buffer_id, path_hash, ChunkMeta, ChunkedRecoveryData, ChunkedRecoveryIndex,
-    InplaceWriteRecovery, RecoveryChunk, RecoveryEntry, RecoveryMetadata, RecoveryResult,
-    SessionInfo, MAX_CHUNK_SIZE,
+    InplaceWriteRecovery, JournalRecord, RecoveryChunk, RecoveryEntry, RecoveryMetadata,
+    RecoveryResult, SessionInfo, MAX_CHUNK_SIZE,
 };
 
 use std::collections::HashMap;
@@ -73,6 +81,12 @@ pub struct RecoveryConfig {
     pub auto_save_interval_secs: u32,
     /// Maximum age of recovery files before cleanup (in seconds)
     pub max_recovery_age_secs: u64,
+    /// Journal records appended before the next auto-save writes a full snapshot
+    pub max_journal_records: usize,
+    /// Journal size in bytes after which the next auto-save writes a full snapshot
+    pub max_journal_bytes: usize,
+    /// Compression/encryption applied to recovered content
+    pub codec: DataCodec,
 }
 
 impl Default for RecoveryConfig {
@@ -81,10 +95,20 @@ impl Default for RecoveryConfig {
             enabled: true,
             auto_save_interval_secs: 2,
             max_recovery_age_secs: 7 * 24 * 60 * 60, // 7 days
+            max_journal_records: 4096,
+            max_journal_bytes: 4 * 1024 * 1024, // 4 MB
+            codec: DataCodec::plain(),
         }
     }
 }
 
+/// Records and bytes appended to a buffer's journal since its last snapshot
+#[derive(Debug, Clone, Copy, Default)]
+struct JournalSize {
+    records: usize,
+    bytes: usize,
+}
+
 /// The main recovery service
 ///
 /// This is the high-level interface for the recovery system.
@@ -97,6 +121,9 @@ pub struct RecoveryService {
     config: RecoveryConfig,
     /// Last auto-save time per buffer
     last_save_times: HashMap<String, Instant>,
+    /// Journal size per buffer, present only for buffers with a snapshot
+    /// written by this session that the journal can be appended to
+    journals: HashMap<String, JournalSize>,
     /// Session started flag
     session_started: bool,
 }
@@ -108,6 +135,7 @@ impl RecoveryService {
             storage: RecoveryStorage::new()?,
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         })
     }
@@ -115,9 +143,10 @@ impl RecoveryService {
     /// Create a new recovery service with custom config
     pub fn with_config(config: RecoveryConfig) -> io::Result<Self> {
         Ok(Self {
-            storage: RecoveryStorage::new()?,
+            storage: RecoveryStorage::new()?.with_codec(config.codec.clone()),
             config,
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         })
     }
@@ -129,6 +158,7 @@ impl RecoveryService {
             storage: RecoveryStorage::with_dir(storage_dir),
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         }
     }
@@ -136,9 +166,10 @@ impl RecoveryService {
     /// Create a new recovery service with custom config and storage directory
     pub fn with_config_and_dir(config: RecoveryConfig, storage_dir: PathBuf) -> Self {
         Self {
-            storage: RecoveryStorage::with_dir(storage_dir),
+            storage: RecoveryStorage::with_dir(storage_dir).with_codec(config.codec.clone()),
             config,
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         }
     }
@@ -148,6 +179,11 @@ impl RecoveryService {
         self.config.enabled
     }
 
+    /// Codec used for recovery files, also applied to saved sessions
+    pub fn codec(&self) -> &DataCodec {
+        &self.config.codec
+    }
+
     /// Get the storage backend
     pub fn storage(&self) -> &RecoveryStorage {
         &self.storage
@@ -195,6 +231,8 @@ impl RecoveryService {
         let cleaned = self.storage.cleanup_all()?;
         tracing::info!("Cleaned up {} recovery files", cleaned);
 
+        self.journals.clear();
+
         // Remove session lock
         self.storage.remove_session_lock()?;
         self.session_started = false;
@@ -217,8 +255,10 @@ impl RecoveryService {
     /// Check if a buffer needs auto-save
     ///
     /// Returns true if recovery_pending is true AND enough time has passed since
-    /// the last recovery save. The recovery_pending flag is now tracked on the
-    /// buffer itself (TextBuffer.recovery_pending) rather than in this service.
+    /// the last recovery save. Whether recovery is pending is tracked on the
+    /// buffer itself (its revision differs from the one last saved for
+    /// recovery, see `TextBuffer::is_recovery_pending`) rather than in this
+    /// service.
     pub fn needs_auto_save(&self, buffer_id: &str, recovery_pending: bool) -> bool {
         if !self.config.enabled {
             return false;
@@ -287,6 +327,13 @@ impl RecoveryService {
         )?;
         self.last_save_times
             .insert(buffer_id.to_string(), Instant::now());
+        // Only full-content snapshots can be extended with journal records
+        if original_file_size == 0 {
+            self.journals
+                .insert(buffer_id.to_string(), JournalSize::default());
+        } else {
+            self.journals.remove(buffer_id);
+        }
 
         tracing::trace!(
             "Saved recovery for buffer {} (original: {} bytes, final: {} bytes)",
@@ -297,6 +344,50 @@ impl RecoveryService {
         Ok(())
     }
 
+    /// Check whether edits to a buffer can be appended to its journal
+    ///
+    /// Returns false if this session has not written a full snapshot for the
+    /// buffer yet, or if the journal has outgrown the configured limits and
+    /// should be compacted into a new snapshot.
+    pub fn can_append_journal(&self, buffer_id: &str) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+
+        self.journals.get(buffer_id).is_some_and(|size| {
+            size.records < self.config.max_journal_records
+                && size.bytes < self.config.max_journal_bytes
+        })
+    }
+
+    /// Append edits made since the last save to a buffer's journal
+    ///
+    /// Callers must check `can_append_journal` first. On failure the buffer
+    /// falls back to a full snapshot on its next save.
+    pub fn append_journal(&mut self, buffer_id: &str, records: &[JournalRecord]) -> io::Result<()> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        if let Err(e) = self.storage.append_journal(buffer_id, records) {
+            self.journals.remove(buffer_id);
+            return Err(e);
+        }
+
+        let size = self.journals.entry(buffer_id.to_string()).or_default();
+        size.records += records.len();
+        size.bytes += records.iter().map(|r| r.encoded_len()).sum::<usize>();
+        self.last_save_times
+            .insert(buffer_id.to_string(), Instant::now());
+
+        tracing::trace!(
+            "Appended {} journal record(s) for buffer {}",
+            records.len(),
+            buffer_id
+        );
+        Ok(())
+    }
+
     /// Delete recovery for a buffer (call when buffer is saved normally or closed)
     pub fn delete_buffer_recovery(&mut self, buffer_id: &str) -> io::Result<()> {
         if !self.config.enabled {
@@ -305,6 +396,7 @@ impl RecoveryService {
 
         self.storage.delete_recovery(buffer_id)?;
         self.last_save_times.remove(buffer_id);
+        self.journals.remove(buffer_id);
 
         tracing::debug!("Deleted recovery for buffer {}", buffer_id);
         Ok(())
@@ -372,9 +464,30 @@ impl RecoveryService {
 
         // For original_file_size == 0, we expect exactly one chunk with offset=0
         if chunked_data.chunks.len() == 1 && chunked_data.chunks[0].offset == 0 {
+            let mut content = chunked_data.chunks[0].content.clone();
+
+            // Replay edits journaled after the snapshot
+            let journal = match self.storage.read_journal(&entry.id) {
+                Ok(journal) => journal,
+                Err(e) => {
+                    return Ok(RecoveryResult::Corrupted {
+                        id: entry.id.clone(),
+                        reason: format!("Invalid recovery journal: {}", e),
+                    })
+                }
+            };
+            for record in &journal {
+                if let Err(reason) = record.apply(&mut content) {
+                    return Ok(RecoveryResult::Corrupted {
+                        id: entry.id.clone(),
+                        reason,
+                    });
+                }
+            }
+
             Ok(RecoveryResult::Recovered {
                 original_path: entry.metadata.original_path.clone(),
-                content: chunked_data.chunks[0].content.clone(),
+                content,
             })
         } else {
             Ok(RecoveryResult::Corrupted {
@@ -401,23 +514,49 @@ impl RecoveryService {
         })
     }
 
+    /// Load the complete recovered content for an entry
+    ///
+    /// Chunked entries are reconstructed on top of the original file. Used where
+    /// the whole text is needed rather than a patch, e.g. previews and copies.
+    pub fn load_full_content(&self, entry: &RecoveryEntry) -> io::Result<Vec<u8>> {
+        match self.load_recovery(entry)? {
+            RecoveryResult::Recovered { content, .. } => Ok(content),
+            RecoveryResult::RecoveredChunks { original_path, .. } => self
+                .storage
+                .reconstruct_from_chunks(&entry.id, &original_path),
+            RecoveryResult::OriginalFileModified { original_path, .. } => Err(io::Error::other(
+                format!("{} was modified since recovery", original_path.display()),
+            )),
+            RecoveryResult::Corrupted { reason, .. } => {
+                Err(io::Error::new(io::ErrorKind::InvalidData, reason))
+            }
+            RecoveryResult::NotFound { id } => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("recovery entry {} not found", id),
+            )),
+        }
+    }
+
     /// Accept recovery for an entry (load and delete recovery file)
     pub fn accept_recovery(&mut self, entry: &RecoveryEntry) -> io::Result<RecoveryResult> {
         let result = self.load_recovery(entry)?;
         // Delete the recovery file after successful load
         if matches!(result, RecoveryResult::Recovered { .. }) {
             self.storage.delete_recovery(&entry.id)?;
+            self.journals.remove(&entry.id);
         }
         Ok(result)
     }
 
     /// Discard recovery for an entry
     pub fn discard_recovery(&mut self, entry: &RecoveryEntry) -> io::Result<()> {
+        self.journals.remove(&entry.id);
         self.storage.delete_recovery(&entry.id)
     }
 
     /// Discard all recovery files
     pub fn discard_all_recovery(&mut self) -> io::Result<usize> {
+        self.journals.clear();
         self.storage.cleanup_all()
     }
 
@@ -461,6 +600,7 @@ impl Default for RecoveryService {
             storage: RecoveryStorage::default(),
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         })
     }
@@ -478,6 +618,7 @@ mod tests {
             storage,
             config: RecoveryConfig::default(),
             last_save_times: HashMap::new(),
+            journals: HashMap::new(),
             session_started: false,
         };
         (service, temp_dir)
@@ -530,6 +671,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_journal_replayed_on_recovery() {
+        let (mut service, _temp) = create_test_service();
+        service.start_session().unwrap();
+        service.config.max_journal_records = 2;
+
+        let id = "journal-buffer";
+        assert!(!service.can_append_journal(id));
+
+        let chunks = vec![RecoveryChunk::new(0, 0, b"hello".to_vec())];
+        service
+            .save_buffer(id, chunks, None, Some("Untitled"), Some(1), 0, 5)
+            .unwrap();
+        assert!(service.can_append_journal(id));
+
+        service
+            .append_journal(
+                id,
+                &[
+                    JournalRecord::Insert {
+                        offset: 5,
+                        content: b" world".to_vec(),
+                    },
+                    JournalRecord::Delete { offset: 0, len: 1 },
+                ],
+            )
+            .unwrap();
+        // Journal is full, the next save should be a snapshot
+        assert!(!service.can_append_journal(id));
+
+        let entries = service.list_recoverable().unwrap();
+        match service.load_recovery(&entries[0]).unwrap() {
+            RecoveryResult::Recovered { content, .. } => assert_eq!(content, b"ello world"),
+            other => panic!("Expected Recovered result, got {:?}", other),
+        }
+
+        // Deleting the recovery forgets the snapshot
+        service.delete_buffer_recovery(id).unwrap();
+        assert!(!service.can_append_journal(id));
+    }
+
     #[test]
     fn test_needs_auto_save() {
         let (service, _temp) = create_test_service();
@@ -615,4 +797,34 @@ mod tests {
             _ => panic!("Expected RecoveredChunks result, got {:?}", result),
         }
     }
+
+    #[test]
+    fn test_load_full_content_reconstructs_chunks() {
+        use std::fs;
+
+        let (mut service, temp_dir) = create_test_service();
+        service.start_session().unwrap();
+
+        let original_content = b"original content";
+        let original_path = temp_dir.path().join("original.txt");
+        fs::write(&original_path, original_content).unwrap();
+
+        let id = service.get_buffer_id(Some(&original_path));
+        let chunks = vec![RecoveryChunk::new(0, 0, b"PREFIX: ".to_vec())];
+        service
+            .save_buffer(
+                &id,
+                chunks,
+                Some(&original_path),
+                None,
+                Some(1),
+                original_content.len(),
+                original_content.len() + 8,
+            )
+            .unwrap();
+
+        let entries = service.list_recoverable().unwrap();
+        let content = service.load_full_content(&entries[0]).unwrap();
+        assert_eq!(content, b"PREFIX: original content");
+    }
 }
@@ -3,12 +3,13 @@
 //! This module handles reading and writing recovery files with atomic operations
 //! to ensure data integrity even during crashes.
 
+use super::codec::DataCodec;
 use super::types::{
-    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, RecoveryChunk,
-    RecoveryEntry, RecoveryMetadata, SessionInfo,
+    generate_buffer_id, path_hash, ChunkedRecoveryData, ChunkedRecoveryIndex, JournalRecord,
+    RecoveryChunk, RecoveryEntry, RecoveryMetadata, SessionInfo,
 };
 use crate::input::input_history::get_data_dir;
-use std::fs::{self, File};
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
@@ -20,6 +21,8 @@ use std::time::SystemTime;
 pub struct RecoveryStorage {
     /// Base directory for recovery files
     recovery_dir: PathBuf,
+    /// Encoding applied to chunk, content and journal files
+    codec: DataCodec,
 }
 
 impl RecoveryStorage {
@@ -27,18 +30,31 @@ impl RecoveryStorage {
     const META_EXT: &'static str = "meta.json";
     /// File extension for content files
     const CONTENT_EXT: &'static str = "content";
+    /// File extension for edit journals
+    const JOURNAL_EXT: &'static str = "journal";
     /// Session lock file name
     const SESSION_LOCK: &'static str = "session.lock";
+    /// Tag of a codec-encoded journal frame (distinct from record tags)
+    const JOURNAL_FRAME_TAG: u8 = b'F';
 
     /// Create a new recovery storage manager
     pub fn new() -> io::Result<Self> {
         let recovery_dir = Self::get_recovery_dir()?;
-        Ok(Self { recovery_dir })
+        Ok(Self::with_dir(recovery_dir))
     }
 
     /// Create a recovery storage with a custom directory (for testing)
     pub fn with_dir(recovery_dir: PathBuf) -> Self {
-        Self { recovery_dir }
+        Self {
+            recovery_dir,
+            codec: DataCodec::plain(),
+        }
+    }
+
+    /// Set the codec used to compress/encrypt recovered content
+    pub fn with_codec(mut self, codec: DataCodec) -> Self {
+        self.codec = codec;
+        self
     }
 
     /// Get the recovery directory path
@@ -146,6 +162,12 @@ impl RecoveryStorage {
             .join(format!("{id}.chunk.{}", chunk_index))
     }
 
+    /// Get path for the edit journal
+    fn journal_path(&self, id: &str) -> PathBuf {
+        self.recovery_dir
+            .join(format!("{id}.{}", Self::JOURNAL_EXT))
+    }
+
     /// List all chunk files for a given ID
     fn list_chunk_paths(&self, id: &str) -> io::Result<Vec<PathBuf>> {
         let mut paths = Vec::new();
@@ -203,6 +225,12 @@ impl RecoveryStorage {
         // Delete any existing chunk files first
         self.delete_chunk_files(id)?;
 
+        // A new snapshot supersedes the journal of edits made on top of the old one
+        let journal_path = self.journal_path(id);
+        if journal_path.exists() {
+            fs::remove_file(&journal_path)?;
+        }
+
         // Create chunked data structure
         let chunked_data = ChunkedRecoveryData::new(original_file_size, final_size, chunks);
 
@@ -210,7 +238,7 @@ impl RecoveryStorage {
         let mut total_chunk_bytes = 0u64;
         for (i, chunk) in chunked_data.chunks.iter().enumerate() {
             let chunk_path = self.chunk_path(id, i);
-            self.atomic_write(&chunk_path, &chunk.content)?;
+            self.atomic_write(&chunk_path, &self.codec.encode(&chunk.content)?)?;
             total_chunk_bytes += chunk.content.len() as u64;
         }
 
@@ -275,6 +303,82 @@ impl RecoveryStorage {
         Ok(metadata)
     }
 
+    /// Append edit records to the journal for a buffer
+    ///
+    /// The records must describe edits made after the snapshot written by the
+    /// last `save_recovery` call for this ID. Records are written with a single
+    /// append so a crash can at worst leave one truncated record at the end,
+    /// which `read_journal` ignores.
+    ///
+    /// With a non-plain codec each append is written as one encoded frame:
+    /// `'F' | u32 LE length | encoded records`.
+    pub fn append_journal(&self, id: &str, records: &[JournalRecord]) -> io::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut data = Vec::with_capacity(records.iter().map(|r| r.encoded_len()).sum());
+        for record in records {
+            record.encode(&mut data);
+        }
+        if !self.codec.is_plain() {
+            let encoded = self.codec.encode(&data)?;
+            let len = u32::try_from(encoded.len()).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "journal frame too large")
+            })?;
+            data = Vec::with_capacity(5 + encoded.len());
+            data.push(Self::JOURNAL_FRAME_TAG);
+            data.extend_from_slice(&len.to_le_bytes());
+            data.extend_from_slice(&encoded);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path(id))?;
+        file.write_all(&data)
+    }
+
+    /// Read the edit journal for a buffer (empty if there is none)
+    pub fn read_journal(&self, id: &str) -> io::Result<Vec<JournalRecord>> {
+        let journal_path = self.journal_path(id);
+        if !journal_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let data = fs::read(&journal_path)?;
+        if data.first() != Some(&Self::JOURNAL_FRAME_TAG) {
+            return JournalRecord::decode_all(&data)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+        }
+
+        // Encoded journal: a torn frame at the end is ignored like a torn record
+        let mut records = Vec::new();
+        let mut rest = data.as_slice();
+        while rest.len() >= 5 && rest[0] == Self::JOURNAL_FRAME_TAG {
+            let len = u32::from_le_bytes([rest[1], rest[2], rest[3], rest[4]]) as usize;
+            let Some(frame) = rest.get(5..5 + len) else {
+                break;
+            };
+            let decoded = self.codec.decode(frame)?;
+            records.extend(
+                JournalRecord::decode_all(&decoded)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            );
+            rest = &rest[5 + len..];
+        }
+        if rest
+            .first()
+            .is_some_and(|&tag| tag != Self::JOURNAL_FRAME_TAG)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid journal frame tag",
+            ));
+        }
+        Ok(records)
+    }
+
     /// Read chunked recovery index from metadata file
     pub fn read_chunked_index(&self, id: &str) -> io::Result<Option<ChunkedRecoveryIndex>> {
         let (meta_path, _) = self.recovery_paths(id);
@@ -319,7 +423,7 @@ impl RecoveryStorage {
                 ));
             }
 
-            let content = fs::read(&chunk_path)?;
+            let content = self.codec.decode(&fs::read(&chunk_path)?)?;
 
             chunks.push(RecoveryChunk {
                 offset: chunk_meta.offset,
@@ -410,7 +514,7 @@ impl RecoveryStorage {
         if !content_path.exists() {
             return Ok(None);
         }
-        Ok(Some(fs::read(&content_path)?))
+        Ok(Some(self.codec.decode(&fs::read(&content_path)?)?))
     }
 
     /// Load a complete recovery entry
@@ -421,13 +525,24 @@ impl RecoveryStorage {
             return Ok(None);
         }
 
-        let metadata = self.read_metadata(id)?.ok_or_else(|| {
+        let mut metadata = self.read_metadata(id)?.ok_or_else(|| {
             io::Error::new(
                 io::ErrorKind::NotFound,
                 "Metadata file exists but couldn't be read",
             )
         })?;
 
+        // Journal appends don't rewrite the metadata, so the journal's mtime is
+        // when the recovered content was last updated
+        let journal_mtime = fs::metadata(self.journal_path(id))
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        if let Some(mtime) = journal_mtime {
+            metadata.updated_at = metadata.updated_at.max(mtime);
+        }
+
         // Require at least one chunk file
         let chunk_paths = self.list_chunk_paths(id)?;
         if chunk_paths.is_empty() {
@@ -454,6 +569,12 @@ impl RecoveryStorage {
         // Delete chunk files (for Chunked format)
         self.delete_chunk_files(id)?;
 
+        // Delete the edit journal
+        let journal_path = self.journal_path(id);
+        if journal_path.exists() {
+            fs::remove_file(&journal_path)?;
+        }
+
         // Delete metadata file
         if meta_path.exists() {
             fs::remove_file(&meta_path)?;
@@ -518,6 +639,9 @@ impl RecoveryStorage {
                 } else if name.ends_with(&format!(".{}", Self::CONTENT_EXT)) {
                     name.trim_end_matches(&format!(".{}", Self::CONTENT_EXT))
                         .to_string()
+                } else if name.ends_with(&format!(".{}", Self::JOURNAL_EXT)) {
+                    name.trim_end_matches(&format!(".{}", Self::JOURNAL_EXT))
+                        .to_string()
                 } else if name.contains(".chunk.") {
                     // Handle chunk files like "id.chunk.0"
                     name.split(".chunk.").next().unwrap_or("").to_string()
@@ -540,6 +664,7 @@ impl RecoveryStorage {
                 if !is_valid {
                     let _ = fs::remove_file(&meta_path);
                     let _ = self.delete_chunk_files(&id);
+                    let _ = fs::remove_file(self.journal_path(&id));
                     cleaned += 1;
                 }
             }
@@ -671,9 +796,7 @@ impl RecoveryStorage {
 
 impl Default for RecoveryStorage {
     fn default() -> Self {
-        Self::new().unwrap_or_else(|_| Self {
-            recovery_dir: PathBuf::from("/tmp/fresh-recovery"),
-        })
+        Self::new().unwrap_or_else(|_| Self::with_dir(PathBuf::from("/tmp/fresh-recovery")))
     }
 }
 
@@ -684,9 +807,7 @@ mod tests {
 
     fn create_test_storage() -> (RecoveryStorage, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let storage = RecoveryStorage {
-            recovery_dir: temp_dir.path().to_path_buf(),
-        };
+        let storage = RecoveryStorage::with_dir(temp_dir.path().to_path_buf());
         (storage, temp_dir)
     }
 
@@ -777,6 +898,80 @@ mod tests {
         assert!(storage.load_entry(id).unwrap().is_none());
     }
 
+    #[test]
+    fn test_journal_lifecycle() {
+        let (storage, _temp) = create_test_storage();
+
+        let id = "journal-id";
+        let chunks = vec![RecoveryChunk::new(0, 0, b"content".to_vec())];
+        storage
+            .save_recovery(id, chunks, None, Some("Test"), None, 0, 7)
+            .unwrap();
+        assert!(storage.read_journal(id).unwrap().is_empty());
+
+        let first = JournalRecord::Insert {
+            offset: 7,
+            content: b"!".to_vec(),
+        };
+        let second = JournalRecord::Delete { offset: 0, len: 1 };
+        storage
+            .append_journal(id, std::slice::from_ref(&first))
+            .unwrap();
+        storage
+            .append_journal(id, std::slice::from_ref(&second))
+            .unwrap();
+        assert_eq!(storage.read_journal(id).unwrap(), vec![first, second]);
+
+        // A new snapshot compacts the journal away
+        let chunks = vec![RecoveryChunk::new(0, 0, b"ontent!".to_vec())];
+        storage
+            .save_recovery(id, chunks, None, Some("Test"), None, 0, 7)
+            .unwrap();
+        assert!(storage.read_journal(id).unwrap().is_empty());
+
+        storage
+            .append_journal(id, &[JournalRecord::Delete { offset: 0, len: 1 }])
+            .unwrap();
+        storage.delete_recovery(id).unwrap();
+        assert!(!storage.journal_path(id).exists());
+    }
+
+    #[test]
+    fn test_encrypted_chunks_and_journal() {
+        let (storage, _temp) = create_test_storage();
+        let storage = storage.with_codec(DataCodec::compressed().with_passphrase("pw", b"salt"));
+
+        let id = "encrypted-id";
+        let chunks = vec![RecoveryChunk::new(0, 0, b"secret text".to_vec())];
+        storage
+            .save_recovery(id, chunks, None, Some("Test"), None, 0, 11)
+            .unwrap();
+        let on_disk = fs::read(storage.chunk_path(id, 0)).unwrap();
+        assert!(!on_disk.windows(6).any(|w| w == b"secret"));
+
+        let record = JournalRecord::Insert {
+            offset: 11,
+            content: b"!".to_vec(),
+        };
+        storage
+            .append_journal(id, std::slice::from_ref(&record))
+            .unwrap();
+        storage
+            .append_journal(id, std::slice::from_ref(&record))
+            .unwrap();
+        assert_eq!(
+            storage.read_journal(id).unwrap(),
+            vec![record.clone(), record]
+        );
+
+        let data = storage.read_chunked_content(id).unwrap().unwrap();
+        assert_eq!(data.chunks[0].content, b"secret text");
+
+        // Without the passphrase the content cannot be read
+        let other = RecoveryStorage::with_dir(storage.base_dir().to_path_buf());
+        assert!(other.read_chunked_content(id).is_err());
+    }
+
     #[test]
     fn test_cleanup_orphans() {
         let (storage, _temp) = create_test_storage();
@@ -10,6 +10,10 @@
 //!
 //! For small files or new buffers, there's typically a single chunk containing
 //! the full content. For large files, only modified regions are stored as chunks.
+//!
+//! Small buffers additionally keep an append-only `{id}.journal` of edits made
+//! since that snapshot (see [`JournalRecord`]), so auto-save only writes what
+//! changed instead of the whole buffer.
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -65,6 +69,127 @@ impl RecoveryChunk {
     }
 }
 
+/// A single edit in a recovery journal (`{id}.journal`)
+///
+/// Journals are append-only logs of edits made since the last full snapshot.
+/// Offsets are byte positions in the buffer at the time the edit was applied,
+/// so records must be replayed in order on top of the snapshot content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalRecord {
+    /// Bytes inserted at an offset
+    Insert { offset: usize, content: Vec<u8> },
+    /// Bytes removed starting at an offset
+    Delete { offset: usize, len: usize },
+}
+
+impl JournalRecord {
+    /// Record tag for insertions
+    const INSERT_TAG: u8 = b'I';
+    /// Record tag for deletions
+    const DELETE_TAG: u8 = b'D';
+    /// Tag byte plus two little-endian u64 fields (offset, length)
+    const HEADER_LEN: usize = 17;
+
+    /// Number of bytes this record occupies on disk
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Self::Insert { content, .. } => Self::HEADER_LEN + content.len(),
+            Self::Delete { .. } => Self::HEADER_LEN,
+        }
+    }
+
+    /// Append the binary encoding of this record to `out`
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let (tag, offset, len) = match self {
+            Self::Insert { offset, content } => (Self::INSERT_TAG, *offset, content.len()),
+            Self::Delete { offset, len } => (Self::DELETE_TAG, *offset, *len),
+        };
+        out.push(tag);
+        out.extend_from_slice(&(offset as u64).to_le_bytes());
+        out.extend_from_slice(&(len as u64).to_le_bytes());
+        if let Self::Insert { content, .. } = self {
+            out.extend_from_slice(content);
+        }
+    }
+
+    /// Decode every complete record in a journal
+    ///
+    /// A truncated final record (the editor died mid-append) is ignored, since
+    /// everything before it is still a consistent prefix of the edit history.
+    /// An unknown tag means the file is damaged and is reported as an error.
+    pub fn decode_all(data: &[u8]) -> Result<Vec<Self>, String> {
+        let mut records = Vec::new();
+        let mut pos = 0;
+
+        while data.len() - pos >= Self::HEADER_LEN {
+            let tag = data[pos];
+            let field = |start: usize| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&data[start..start + 8]);
+                u64::from_le_bytes(bytes) as usize
+            };
+            let offset = field(pos + 1);
+            let len = field(pos + 9);
+            pos += Self::HEADER_LEN;
+
+            match tag {
+                Self::INSERT_TAG => {
+                    if data.len() - pos < len {
+                        break;
+                    }
+                    records.push(Self::Insert {
+                        offset,
+                        content: data[pos..pos + len].to_vec(),
+                    });
+                    pos += len;
+                }
+                Self::DELETE_TAG => records.push(Self::Delete { offset, len }),
+                other => {
+                    return Err(format!(
+                        "Unknown journal record tag {:#04x} at byte {}",
+                        other,
+                        pos - Self::HEADER_LEN
+                    ))
+                }
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Replay this record on top of `content`
+    pub fn apply(&self, content: &mut Vec<u8>) -> Result<(), String> {
+        match self {
+            Self::Insert {
+                offset,
+                content: inserted,
+            } => {
+                if *offset > content.len() {
+                    return Err(format!(
+                        "Journal insert at {} is past the end of the content ({} bytes)",
+                        offset,
+                        content.len()
+                    ));
+                }
+                content.splice(*offset..*offset, inserted.iter().copied());
+            }
+            Self::Delete { offset, len } => {
+                let end = offset.saturating_add(*len);
+                if end > content.len() {
+                    return Err(format!(
+                        "Journal delete of {}..{} is past the end of the content ({} bytes)",
+                        offset,
+                        end,
+                        content.len()
+                    ));
+                }
+                content.drain(*offset..end);
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Chunked recovery index (stored as JSON in .meta.json for Chunked format)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkedRecoveryIndex {
@@ -342,7 +467,7 @@ pub enum RecoveryResult {
 
 /// Check if a process with the given PID is running
 #[cfg(unix)]
-fn is_process_running(pid: u32) -> bool {
+pub(crate) fn is_process_running(pid: u32) -> bool {
     // On Unix, we can use kill with signal 0 to check if process exists
     // Returns 0 if process exists and we can signal it
     // Returns -1 with EPERM if process exists but we can't signal it
@@ -357,7 +482,7 @@ fn is_process_running(pid: u32) -> bool {
 }
 
 #[cfg(windows)]
-fn is_process_running(pid: u32) -> bool {
+pub(crate) fn is_process_running(pid: u32) -> bool {
     use windows_sys::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
     use windows_sys::Win32::System::Threading::{
         GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
@@ -376,7 +501,7 @@ fn is_process_running(pid: u32) -> bool {
 }
 
 #[cfg(not(any(unix, windows)))]
-fn is_process_running(_pid: u32) -> bool {
+pub(crate) fn is_process_running(_pid: u32) -> bool {
     // On other platforms, assume not running (safer for recovery)
     false
 }
@@ -489,6 +614,54 @@ mod tests {
         assert_eq!(meta.original_file_size, 0);
     }
 
+    #[test]
+    fn test_journal_roundtrip_and_replay() {
+        let records = vec![
+            JournalRecord::Insert {
+                offset: 5,
+                content: b", world".to_vec(),
+            },
+            JournalRecord::Delete { offset: 0, len: 1 },
+            JournalRecord::Insert {
+                offset: 0,
+                content: b"H".to_vec(),
+            },
+        ];
+        let mut encoded = Vec::new();
+        for record in &records {
+            record.encode(&mut encoded);
+        }
+        assert_eq!(
+            encoded.len(),
+            records.iter().map(|r| r.encoded_len()).sum::<usize>()
+        );
+        assert_eq!(JournalRecord::decode_all(&encoded).unwrap(), records);
+
+        let mut content = b"hello".to_vec();
+        for record in &records {
+            record.apply(&mut content).unwrap();
+        }
+        assert_eq!(content, b"Hello, world");
+    }
+
+    #[test]
+    fn test_journal_ignores_torn_tail() {
+        let mut encoded = Vec::new();
+        JournalRecord::Delete { offset: 1, len: 2 }.encode(&mut encoded);
+        JournalRecord::Insert {
+            offset: 0,
+            content: b"lost".to_vec(),
+        }
+        .encode(&mut encoded);
+        encoded.truncate(encoded.len() - 2);
+
+        assert_eq!(
+            JournalRecord::decode_all(&encoded).unwrap(),
+            vec![JournalRecord::Delete { offset: 1, len: 2 }]
+        );
+        assert!(JournalRecord::decode_all(b"X0123456789abcdefg").is_err());
+    }
+
     #[test]
     fn test_session_info_new() {
         let info = SessionInfo::new();
@@ -0,0 +1,171 @@
+//! Telling the terminal and the shell what the editor is doing
+//!
+//! The working directory is reported to the terminal with OSC 7
+//! (`ESC ] 7 ; file://host/path ESC \`), which terminals and tmux use to open
+//! new tabs and panes in the same directory. `fresh --cwd-file FILE` writes
+//! the final directory to FILE on exit so a shell wrapper can `cd` there:
+//!
+//! ```sh
+//! fcd() {
+//!     local tmp="$(mktemp)"
+//!     fresh --cwd-file "$tmp" "$@"
+//!     cd "$(cat "$tmp")" && rm -f "$tmp"
+//! }
+//! ```
+//!
+//! The window title (OSC 2) names the current file, and long operations
+//! report their progress with OSC 9;4, which some terminals (Windows
+//! Terminal, Ghostty, WezTerm, ...) show as a progress bar. tmux picks up
+//! the title itself but only forwards the progress sequence wrapped in its
+//! passthrough escape (with `set -g allow-passthrough on`).
+
+use std::io::{self, Write};
+use std::path::Path;
+
+/// The OSC 7 sequence reporting `dir` as the working directory on `host`
+pub fn osc7_sequence(host: &str, dir: &Path) -> Option<String> {
+    let mut url = url::Url::from_directory_path(dir).ok()?;
+    if !host.is_empty() {
+        url.set_host(Some(host)).ok()?;
+    }
+    Some(format!("\x1b]7;{}\x1b\\", url))
+}
+
+/// Report `dir` to the terminal as the working directory
+pub fn report_working_dir(dir: &Path) -> io::Result<()> {
+    match osc7_sequence(&hostname(), dir) {
+        Some(sequence) => write_sequence(&sequence),
+        None => Ok(()),
+    }
+}
+
+/// Write `dir` to `cwd_file` for a shell wrapper to `cd` to
+pub fn write_cwd_file(cwd_file: &Path, dir: &Path) -> io::Result<()> {
+    std::fs::write(cwd_file, dir.as_os_str().as_encoded_bytes())
+}
+
+/// Progress of a long operation as shown by the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerminalProgress {
+    /// Nothing running
+    #[default]
+    Idle,
+    /// Running, with no known end
+    Indeterminate,
+    /// Running, this many percent done
+    Percent(u8),
+}
+
+/// The OSC 2 sequence setting the window title, without control characters
+/// that would end it early
+pub fn title_sequence(title: &str) -> String {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    format!("\x1b]2;{}\x1b\\", title)
+}
+
+/// The OSC 9;4 sequence showing `progress`
+pub fn progress_sequence(progress: TerminalProgress) -> String {
+    match progress {
+        TerminalProgress::Idle => "\x1b]9;4;0;0\x1b\\".to_string(),
+        TerminalProgress::Indeterminate => "\x1b]9;4;3;0\x1b\\".to_string(),
+        TerminalProgress::Percent(percent) => {
+            format!("\x1b]9;4;1;{}\x1b\\", percent.min(100))
+        }
+    }
+}
+
+/// Wrap `sequence` so tmux passes it on to the outer terminal
+fn tmux_passthrough(sequence: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+/// Set the terminal window title
+pub fn set_title(title: &str) -> io::Result<()> {
+    write_sequence(&title_sequence(title))
+}
+
+/// Show `progress` in the terminal
+pub fn report_progress(progress: TerminalProgress) -> io::Result<()> {
+    let sequence = progress_sequence(progress);
+    if std::env::var_os("TMUX").is_some() {
+        write_sequence(&tmux_passthrough(&sequence))
+    } else {
+        write_sequence(&sequence)
+    }
+}
+
+/// Save the terminal's title so `restore_title` can put it back on exit
+/// (xterm title stack, CSI 22 t)
+pub fn save_title() -> io::Result<()> {
+    write_sequence("\x1b[22;0t")
+}
+
+/// Clear any progress indicator and restore the title saved by
+/// `save_title` (CSI 23 t)
+pub fn restore_title() -> io::Result<()> {
+    report_progress(TerminalProgress::Idle)?;
+    write_sequence("\x1b[23;0t")
+}
+
+fn write_sequence(sequence: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()
+}
+
+/// This machine's host name, or "" if unknown
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: gethostname writes at most buf.len() bytes into buf
+    let result = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if result != 0 {
+        return String::new();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_osc7_sequence() {
+        assert_eq!(
+            osc7_sequence("box", Path::new("/home/me/my project")).as_deref(),
+            Some("\x1b]7;file://box/home/me/my%20project/\x1b\\")
+        );
+        assert_eq!(
+            osc7_sequence("", Path::new("/tmp")).as_deref(),
+            Some("\x1b]7;file:///tmp/\x1b\\")
+        );
+        assert_eq!(osc7_sequence("box", Path::new("relative")), None);
+    }
+
+    #[test]
+    fn test_title_and_progress_sequences() {
+        assert_eq!(
+            title_sequence("main.rs*\x07 - Fresh"),
+            "\x1b]2;main.rs* - Fresh\x1b\\"
+        );
+        assert_eq!(
+            progress_sequence(TerminalProgress::Percent(42)),
+            "\x1b]9;4;1;42\x1b\\"
+        );
+        assert_eq!(
+            progress_sequence(TerminalProgress::Indeterminate),
+            "\x1b]9;4;3;0\x1b\\"
+        );
+        assert_eq!(
+            tmux_passthrough(&progress_sequence(TerminalProgress::Idle)),
+            "\x1bPtmux;\x1b\x1b]9;4;0;0\x1b\x1b\\\x1b\\"
+        );
+    }
+}
@@ -0,0 +1,639 @@
+//! Hunspell dictionary reader
+//!
+//! Reads the `.aff`/`.dic` pair used by Hunspell, MySpell and most
+//! LibreOffice dictionaries and expands every stem with its prefix and
+//! suffix rules up front, so checking a word is a set lookup.
+//!
+//! Only the commonly used part of the affix format is understood: `SET`,
+//! `FLAG`, `TRY`, `REP`, `PFX`, `SFX`, `NEEDAFFIX` and `FORBIDDENWORD`.
+//! Compounding and twofold (continuation) affixes are ignored.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Maximum number of suggestions returned for a word
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Words longer than this only get single-edit suggestions
+const MAX_DOUBLE_EDIT_LEN: usize = 10;
+
+/// How flags are written in the affix and dictionary files (`FLAG` directive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FlagMode {
+    /// One character per flag (the default, also used for `FLAG UTF-8`)
+    Char,
+    /// Two characters per flag (`FLAG long`)
+    Long,
+    /// Comma-separated numbers (`FLAG num`)
+    Num,
+}
+
+/// One element of an affix condition, e.g. `[^aeiou]` or `y`
+#[derive(Debug, Clone)]
+enum Condition {
+    Any,
+    Char(char),
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl Condition {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Condition::Any => true,
+            Condition::Char(expected) => c == *expected,
+            Condition::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// A single `PFX`/`SFX` rule line
+#[derive(Debug, Clone)]
+struct AffixEntry {
+    strip: Vec<char>,
+    add: String,
+    condition: Vec<Condition>,
+}
+
+impl AffixEntry {
+    fn apply_prefix(&self, word: &[char]) -> Option<String> {
+        if word.len() < self.condition.len() || !word.starts_with(&self.strip) {
+            return None;
+        }
+        let condition_holds = self
+            .condition
+            .iter()
+            .zip(word)
+            .all(|(cond, c)| cond.matches(*c));
+        if !condition_holds {
+            return None;
+        }
+        let rest: String = word[self.strip.len()..].iter().collect();
+        let result = format!("{}{}", self.add, rest);
+        (!result.is_empty()).then_some(result)
+    }
+
+    fn apply_suffix(&self, word: &[char]) -> Option<String> {
+        if word.len() < self.condition.len() || !word.ends_with(&self.strip) {
+            return None;
+        }
+        let tail = &word[word.len() - self.condition.len()..];
+        let condition_holds = self
+            .condition
+            .iter()
+            .zip(tail)
+            .all(|(cond, c)| cond.matches(*c));
+        if !condition_holds {
+            return None;
+        }
+        let mut result: String = word[..word.len() - self.strip.len()].iter().collect();
+        result.push_str(&self.add);
+        (!result.is_empty()).then_some(result)
+    }
+}
+
+/// All rules sharing one affix flag
+#[derive(Debug)]
+struct AffixClass {
+    is_prefix: bool,
+    /// Whether the class combines with affixes of the other kind
+    cross_product: bool,
+    entries: Vec<AffixEntry>,
+}
+
+/// A loaded dictionary with every word form expanded
+pub struct Dictionary {
+    words: HashSet<String>,
+    forbidden: HashSet<String>,
+    /// Characters tried when generating suggestions, most common first
+    try_chars: Vec<char>,
+    /// Common misspellings (`REP from to`)
+    replacements: Vec<(String, String)>,
+}
+
+impl Dictionary {
+    /// Load a dictionary from its `.aff` and `.dic` files
+    pub fn load(aff_path: &Path, dic_path: &Path) -> std::io::Result<Self> {
+        let aff_bytes = std::fs::read(aff_path)?;
+        let dic_bytes = std::fs::read(dic_path)?;
+        let encoding = declared_encoding(&aff_bytes);
+        Ok(Self::parse(
+            &decode(&aff_bytes, encoding),
+            &decode(&dic_bytes, encoding),
+        ))
+    }
+
+    /// Build a dictionary from the contents of the affix and dictionary files
+    pub fn parse(aff: &str, dic: &str) -> Self {
+        let mut flag_mode = FlagMode::Char;
+        let mut try_chars = Vec::new();
+        let mut replacements = Vec::new();
+        let mut need_affix = None;
+        let mut forbidden_flag = None;
+        let mut classes: HashMap<u32, AffixClass> = HashMap::new();
+
+        // FLAG decides how every other flag is read, so find it first
+        for line in aff.lines() {
+            let mut fields = line.split_whitespace();
+            if fields.next() == Some("FLAG") {
+                flag_mode = match fields.next() {
+                    Some("long") => FlagMode::Long,
+                    Some("num") => FlagMode::Num,
+                    _ => FlagMode::Char,
+                };
+            }
+        }
+
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["TRY", chars, ..] => try_chars = chars.chars().collect(),
+                ["REP", from, to, ..] => {
+                    replacements.push((from.replace('_', " "), to.replace('_', " ")))
+                }
+                ["NEEDAFFIX", flag, ..] => {
+                    need_affix = parse_flags(flag, flag_mode).first().copied()
+                }
+                ["FORBIDDENWORD", flag, ..] => {
+                    forbidden_flag = parse_flags(flag, flag_mode).first().copied()
+                }
+                [kind @ ("PFX" | "SFX"), flag, rest @ ..] => {
+                    let Some(flag) = parse_flags(flag, flag_mode).first().copied() else {
+                        continue;
+                    };
+                    let is_prefix = *kind == "PFX";
+                    match classes.get_mut(&flag) {
+                        // Header line: `SFX D Y 4`
+                        None => {
+                            classes.insert(
+                                flag,
+                                AffixClass {
+                                    is_prefix,
+                                    cross_product: rest.first() == Some(&"Y"),
+                                    entries: Vec::new(),
+                                },
+                            );
+                        }
+                        // Rule line: `SFX D y ied [^aeiou]y`
+                        Some(class) => {
+                            if let [strip, add, condition @ ..] = rest {
+                                class.entries.push(AffixEntry {
+                                    strip: if *strip == "0" {
+                                        Vec::new()
+                                    } else {
+                                        strip.chars().collect()
+                                    },
+                                    add: parse_affix_text(add),
+                                    condition: parse_condition(condition.first().unwrap_or(&".")),
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut words = HashSet::new();
+        let mut forbidden = HashSet::new();
+        for line in dic.lines().skip(1) {
+            let Some((word, flags)) = parse_dic_line(line, flag_mode) else {
+                continue;
+            };
+            if forbidden_flag.is_some_and(|f| flags.contains(&f)) {
+                forbidden.insert(word);
+                continue;
+            }
+            if !need_affix.is_some_and(|f| flags.contains(&f)) {
+                words.insert(word.clone());
+            }
+
+            let stem: Vec<char> = word.chars().collect();
+            let affixes = || flags.iter().filter_map(|f| classes.get(f));
+
+            let mut cross_prefixed = Vec::new();
+            for class in affixes().filter(|c| c.is_prefix) {
+                for entry in &class.entries {
+                    if let Some(form) = entry.apply_prefix(&stem) {
+                        if class.cross_product {
+                            cross_prefixed.push(form.chars().collect::<Vec<_>>());
+                        }
+                        words.insert(form);
+                    }
+                }
+            }
+            for class in affixes().filter(|c| !c.is_prefix) {
+                for entry in &class.entries {
+                    words.extend(entry.apply_suffix(&stem));
+                    if class.cross_product {
+                        for prefixed in &cross_prefixed {
+                            words.extend(entry.apply_suffix(prefixed));
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            words,
+            forbidden,
+            try_chars,
+            replacements,
+        }
+    }
+
+    /// Number of word forms known to the dictionary
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether the dictionary has no words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Check whether a word is spelled correctly.
+    ///
+    /// Capitalized and all-caps words are accepted when their lowercase (or
+    /// capitalized) form is in the dictionary, so "Hello" and "HELLO" match
+    /// "hello", while "paris" does not match "Paris".
+    pub fn check(&self, word: &str) -> bool {
+        let word = normalize_apostrophes(word);
+        self.known_form(&word).is_some()
+            || match WordCase::of(&word) {
+                WordCase::Capitalized => self.known_form(&word.to_lowercase()).is_some(),
+                WordCase::Upper => {
+                    let lower = word.to_lowercase();
+                    self.known_form(&lower).is_some()
+                        || self.known_form(&capitalize(&lower)).is_some()
+                }
+                WordCase::Lower | WordCase::Mixed => false,
+            }
+    }
+
+    /// Suggest corrections for a misspelled word, best first.
+    ///
+    /// Candidates come from the `REP` table, then from single edits (swap,
+    /// replace, delete, insert, split in two), then from double edits for
+    /// short words. The case of the original word is kept.
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let word = normalize_apostrophes(word);
+        let case = WordCase::of(&word);
+        let lower = word.to_lowercase();
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+        let mut consider = |candidate: String, found: &mut Vec<String>| {
+            if found.len() >= MAX_SUGGESTIONS || !seen.insert(candidate.clone()) {
+                return;
+            }
+            let known = candidate
+                .split(' ')
+                .map(|part| self.lookup_lowercase(part))
+                .collect::<Option<Vec<_>>>();
+            if let Some(parts) = known {
+                let suggestion = case.apply(&parts.join(" "));
+                if suggestion != word && !found.contains(&suggestion) {
+                    found.push(suggestion);
+                }
+            }
+        };
+
+        for (from, to) in &self.replacements {
+            for (index, _) in lower.match_indices(from.as_str()) {
+                let candidate =
+                    format!("{}{}{}", &lower[..index], to, &lower[index + from.len()..]);
+                consider(candidate, &mut found);
+            }
+        }
+
+        let chars: Vec<char> = lower.chars().collect();
+        let single_edits = self.edits(&chars);
+        for candidate in &single_edits {
+            consider(candidate.clone(), &mut found);
+        }
+        for split in 1..chars.len() {
+            let left: String = chars[..split].iter().collect();
+            let right: String = chars[split..].iter().collect();
+            consider(format!("{} {}", left, right), &mut found);
+        }
+
+        if found.is_empty() && chars.len() <= MAX_DOUBLE_EDIT_LEN {
+            for first in &single_edits {
+                let first: Vec<char> = first.chars().collect();
+                for candidate in self.edits(&first) {
+                    consider(candidate, &mut found);
+                }
+                if found.len() >= MAX_SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+
+        found
+    }
+
+    /// The dictionary spelling of a word, which is either as written or
+    /// capitalized (for proper nouns)
+    fn known_form(&self, word: &str) -> Option<String> {
+        (self.words.contains(word) && !self.forbidden.contains(word)).then(|| word.to_string())
+    }
+
+    fn lookup_lowercase(&self, word: &str) -> Option<String> {
+        if word.is_empty() {
+            return None;
+        }
+        self.known_form(word)
+            .or_else(|| self.known_form(&capitalize(word)))
+    }
+
+    /// All strings one edit away from `word`
+    fn edits(&self, word: &[char]) -> Vec<String> {
+        let alphabet: Vec<char> = if self.try_chars.is_empty() {
+            ('a'..='z').collect()
+        } else {
+            self.try_chars
+                .iter()
+                .filter(|c| !c.is_uppercase())
+                .copied()
+                .collect()
+        };
+        let collect = |chars: &[char]| chars.iter().collect::<String>();
+        let mut edits = Vec::new();
+
+        for i in 0..word.len().saturating_sub(1) {
+            let mut swapped = word.to_vec();
+            swapped.swap(i, i + 1);
+            edits.push(collect(&swapped));
+        }
+        for i in 0..word.len() {
+            for &c in &alphabet {
+                if c != word[i] {
+                    let mut replaced = word.to_vec();
+                    replaced[i] = c;
+                    edits.push(collect(&replaced));
+                }
+            }
+        }
+        for i in 0..word.len() {
+            let mut deleted = word.to_vec();
+            deleted.remove(i);
+            edits.push(collect(&deleted));
+        }
+        for i in 0..=word.len() {
+            for &c in &alphabet {
+                let mut inserted = word.to_vec();
+                inserted.insert(i, c);
+                edits.push(collect(&inserted));
+            }
+        }
+        edits
+    }
+}
+
+/// Capitalization pattern of a word, kept when suggesting replacements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WordCase {
+    Lower,
+    Capitalized,
+    Upper,
+    Mixed,
+}
+
+impl WordCase {
+    fn of(word: &str) -> Self {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let Some(first) = letters.next() else {
+            return WordCase::Lower;
+        };
+        let rest: Vec<char> = letters.collect();
+        if first.is_lowercase() {
+            if rest.iter().all(|c| c.is_lowercase()) {
+                WordCase::Lower
+            } else {
+                WordCase::Mixed
+            }
+        } else if rest.iter().all(|c| c.is_lowercase()) {
+            WordCase::Capitalized
+        } else if rest.iter().all(|c| c.is_uppercase()) {
+            WordCase::Upper
+        } else {
+            WordCase::Mixed
+        }
+    }
+
+    fn apply(self, word: &str) -> String {
+        match self {
+            WordCase::Capitalized => capitalize(word),
+            WordCase::Upper => word.to_uppercase(),
+            WordCase::Lower | WordCase::Mixed => word.to_string(),
+        }
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn normalize_apostrophes(word: &str) -> String {
+    word.replace('\u{2019}', "'")
+}
+
+/// The encoding named by the affix file's `SET` line
+fn declared_encoding(aff: &[u8]) -> &'static encoding_rs::Encoding {
+    String::from_utf8_lossy(aff)
+        .lines()
+        .find_map(|line| {
+            line.trim()
+                .strip_prefix("SET ")
+                .map(str::trim)
+                .map(str::to_string)
+        })
+        .and_then(|label| encoding_rs::Encoding::for_label(label.as_bytes()))
+        .unwrap_or(encoding_rs::UTF_8)
+}
+
+fn decode(bytes: &[u8], encoding: &'static encoding_rs::Encoding) -> String {
+    let (text, _, _) = encoding.decode(bytes);
+    text.trim_start_matches('\u{feff}').to_string()
+}
+
+fn parse_flags(flags: &str, mode: FlagMode) -> Vec<u32> {
+    match mode {
+        FlagMode::Char => flags.chars().map(|c| c as u32).collect(),
+        FlagMode::Long => flags
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .map(|pair| pair.iter().fold(0u32, |acc, c| (acc << 16) | *c as u32))
+            .collect(),
+        FlagMode::Num => flags
+            .split(',')
+            .filter_map(|n| n.trim().parse().ok())
+            .collect(),
+    }
+}
+
+/// The text added by an affix rule, without continuation flags (`ed/XY`)
+fn parse_affix_text(add: &str) -> String {
+    let add = add.split('/').next().unwrap_or_default();
+    if add == "0" {
+        String::new()
+    } else {
+        add.to_string()
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<Condition> {
+    if condition == "." {
+        return Vec::new();
+    }
+    let mut result = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => result.push(Condition::Any),
+            '[' => {
+                let mut set: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                result.push(Condition::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            c => result.push(Condition::Char(c)),
+        }
+    }
+    result
+}
+
+/// Split a `.dic` line (`word/FLAGS morphology...`) into the word and its flags
+fn parse_dic_line(line: &str, mode: FlagMode) -> Option<(String, Vec<u32>)> {
+    let entry = line.split('\t').next()?.split_whitespace().next()?;
+    if entry.starts_with('#') {
+        return None;
+    }
+    // A slash escaped with a backslash is part of the word
+    let mut word = String::new();
+    let mut flags = "";
+    let mut chars = entry.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some((_, '/'))) => {
+                word.push('/');
+                chars.next();
+            }
+            '/' => {
+                flags = &entry[i + 1..];
+                break;
+            }
+            c => word.push(c),
+        }
+    }
+    (!word.is_empty()).then(|| (word, parse_flags(flags, mode)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AFF: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+REP 1
+REP f ph
+
+PFX A Y 1
+PFX A   0     re         .
+
+SFX D Y 3
+SFX D   0     d          e
+SFX D   y     ied        [^aeiou]y
+SFX D   0     ed         [^ey]
+
+SFX S Y 2
+SFX S   0     s          [^sy]
+SFX S   y     ies        [^aeiou]y
+
+NEEDAFFIX X
+FORBIDDENWORD !
+";
+
+    const DIC: &str = "8
+hello
+try/DS
+work/ADS
+Paris
+phone/S
+colour/!
+the
+ize/X
+";
+
+    fn dictionary() -> Dictionary {
+        Dictionary::parse(AFF, DIC)
+    }
+
+    #[test]
+    fn test_affix_expansion() {
+        let dict = dictionary();
+        for word in [
+            "try", "tried", "tries", "work", "worked", "works", "rework", "reworked",
+        ] {
+            assert!(dict.check(word), "{} should be accepted", word);
+        }
+        for word in ["tryed", "trys", "retry", "workd"] {
+            assert!(!dict.check(word), "{} should be rejected", word);
+        }
+    }
+
+    #[test]
+    fn test_needaffix_and_forbidden() {
+        let dict = dictionary();
+        assert!(!dict.check("ize"));
+        assert!(!dict.check("colour"));
+    }
+
+    #[test]
+    fn test_case_handling() {
+        let dict = dictionary();
+        assert!(dict.check("Hello"));
+        assert!(dict.check("HELLO"));
+        assert!(!dict.check("hELLo"));
+        assert!(dict.check("Paris"));
+        assert!(dict.check("PARIS"));
+        assert!(!dict.check("paris"));
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let dict = dictionary();
+        assert_eq!(
+            dict.suggest("helo").first().map(String::as_str),
+            Some("hello")
+        );
+        assert_eq!(
+            dict.suggest("Wrok").first().map(String::as_str),
+            Some("Work")
+        );
+        assert!(dict.suggest("fone").contains(&"phone".to_string()));
+        assert!(dict.suggest("thework").contains(&"the work".to_string()));
+        assert!(dict.suggest("paris").contains(&"Paris".to_string()));
+        assert!(dict.suggest("colr").iter().all(|s| s != "colour"));
+    }
+
+    #[test]
+    fn test_long_and_numeric_flags() {
+        let aff = "FLAG long\nSFX Ab Y 1\nSFX Ab 0 s .\n";
+        let dict = Dictionary::parse(aff, "1\ncat/Ab\n");
+        assert!(dict.check("cats"));
+
+        let aff = "FLAG num\nSFX 12 Y 1\nSFX 12 0 ing .\n";
+        let dict = Dictionary::parse(aff, "1\nsing/3,12\n");
+        assert!(dict.check("singing"));
+    }
+}
@@ -0,0 +1,499 @@
+//! Spell checking
+//!
+//! Words are checked against Hunspell dictionaries (`<lang>.aff` and
+//! `<lang>.dic`) found in the `dictionaries/` config directory or in the
+//! system dictionary directories. What gets checked depends on the buffer:
+//! - Plain text and commit messages are checked in full
+//! - Markdown is checked except for code blocks, HTML blocks and inline code
+//! - Source code only has its comments and strings checked, located with the
+//!   language's tree-sitter grammar; languages without one are not checked
+//!
+//! Words added by the user are stored in `dictionaries/personal.txt` and are
+//! accepted in every language.
+
+pub mod dictionary;
+
+use crate::primitives::highlighter::Language;
+use crate::view::overlay::OverlayNamespace;
+use dictionary::Dictionary;
+use fresh_languages::tree_sitter::{Parser, Tree};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Language names (as stored on the buffer) that are treated as prose
+const PROSE_LANGUAGES: &[&str] = &[
+    "text",
+    "plain text",
+    "git-commit",
+    "git commit message",
+    "gitcommit",
+];
+
+/// Markdown blocks that hold code rather than prose
+const MARKDOWN_CODE_BLOCKS: &[&str] = &["fenced_code_block", "indented_code_block", "html_block"];
+
+/// System directories searched for dictionaries, after the config directory
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/usr/local/share/hunspell",
+    "/Library/Spelling",
+];
+
+/// Namespace for misspelling overlays
+pub fn spell_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("spell".to_string())
+}
+
+/// Per-buffer spell checking settings
+#[derive(Debug, Clone)]
+pub struct BufferSpellState {
+    /// Whether misspellings are underlined in this buffer
+    pub enabled: bool,
+    /// Dictionary language, e.g. "en_US"
+    pub language: String,
+    /// Buffer version and byte range of the last check, so unchanged
+    /// viewports are not checked again on every render
+    pub checked: Option<(u64, Range<usize>)>,
+}
+
+impl BufferSpellState {
+    pub fn new(enabled: bool, language: &str) -> Self {
+        Self {
+            enabled,
+            language: language.to_string(),
+            checked: None,
+        }
+    }
+}
+
+impl Default for BufferSpellState {
+    fn default() -> Self {
+        Self::new(false, "en_US")
+    }
+}
+
+/// Loads dictionaries on demand and checks text against them
+pub struct SpellChecker {
+    search_dirs: Vec<PathBuf>,
+    personal_path: PathBuf,
+    personal_words: HashSet<String>,
+    /// Loaded dictionaries by language; `None` if none was found
+    dictionaries: HashMap<String, Option<Dictionary>>,
+}
+
+impl SpellChecker {
+    /// Create a spell checker that looks in `dictionaries_dir` first, then in
+    /// the system dictionary directories
+    pub fn new(dictionaries_dir: PathBuf) -> Self {
+        let mut search_dirs = vec![dictionaries_dir.clone()];
+        search_dirs.extend(SYSTEM_DICTIONARY_DIRS.iter().map(PathBuf::from));
+        if let Some(home) = dirs::home_dir() {
+            search_dirs.push(home.join("Library/Spelling"));
+        }
+
+        let personal_path = dictionaries_dir.join("personal.txt");
+        let personal_words = std::fs::read_to_string(&personal_path)
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|word| !word.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            search_dirs,
+            personal_path,
+            personal_words,
+            dictionaries: HashMap::new(),
+        }
+    }
+
+    /// Languages with a dictionary installed, sorted by name
+    pub fn available_languages(&self) -> Vec<String> {
+        let mut languages: Vec<String> = self
+            .search_dirs
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "dic" || !path.with_extension("aff").is_file() {
+                    return None;
+                }
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .collect();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Whether a dictionary for `language` can be loaded
+    pub fn has_dictionary(&mut self, language: &str) -> bool {
+        self.dictionary(language).is_some()
+    }
+
+    /// Check a single word
+    pub fn is_correct(&mut self, language: &str, word: &str) -> bool {
+        if self.is_personal_word(word) {
+            return true;
+        }
+        self.dictionary(language)
+            .is_none_or(|dictionary| dictionary.check(word))
+    }
+
+    /// Suggested corrections for a word, best first
+    pub fn suggest(&mut self, language: &str, word: &str) -> Vec<String> {
+        self.dictionary(language)
+            .map(|dictionary| dictionary.suggest(word))
+            .unwrap_or_default()
+    }
+
+    /// Accept `word` in every language from now on and save it to the
+    /// personal dictionary file
+    pub fn add_to_personal_dictionary(&mut self, word: &str) -> std::io::Result<()> {
+        if !self.personal_words.insert(word.to_string()) {
+            return Ok(());
+        }
+        if let Some(parent) = self.personal_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.personal_path)?;
+        writeln!(file, "{}", word)
+    }
+
+    /// Byte ranges of misspelled words within `ranges` of `text`
+    pub fn misspellings(
+        &mut self,
+        language: &str,
+        text: &str,
+        ranges: &[Range<usize>],
+    ) -> Vec<Range<usize>> {
+        if !self.has_dictionary(language) {
+            return Vec::new();
+        }
+        let mut result = Vec::new();
+        for range in ranges {
+            for word in words(&text[range.clone()]) {
+                let word = range.start + word.start..range.start + word.end;
+                if !self.is_correct(language, &text[word.clone()]) {
+                    result.push(word);
+                }
+            }
+        }
+        result
+    }
+
+    fn is_personal_word(&self, word: &str) -> bool {
+        self.personal_words.contains(word) || self.personal_words.contains(&word.to_lowercase())
+    }
+
+    fn dictionary(&mut self, language: &str) -> Option<&Dictionary> {
+        if !self.dictionaries.contains_key(language) {
+            let dictionary = self.load_dictionary(language);
+            self.dictionaries.insert(language.to_string(), dictionary);
+        }
+        self.dictionaries.get(language)?.as_ref()
+    }
+
+    fn load_dictionary(&self, language: &str) -> Option<Dictionary> {
+        let dic_path = self
+            .search_dirs
+            .iter()
+            .map(|dir| dir.join(format!("{}.dic", language)))
+            .find(|path| path.with_extension("aff").is_file() && path.is_file())?;
+        match Dictionary::load(&dic_path.with_extension("aff"), &dic_path) {
+            Ok(dictionary) => {
+                tracing::info!(
+                    "Loaded {} dictionary from {:?} ({} words)",
+                    language,
+                    dic_path,
+                    dictionary.len()
+                );
+                Some(dictionary)
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load dictionary {:?}: {}", dic_path, e);
+                None
+            }
+        }
+    }
+}
+
+/// Which parts of a buffer are spell checked
+pub enum SpellScope {
+    /// The whole text
+    Prose,
+    /// Everything except code blocks and inline code
+    Markdown(fresh_languages::tree_sitter::Language),
+    /// Comments and strings only
+    Code(fresh_languages::tree_sitter::Language),
+    /// Nothing
+    Unsupported,
+}
+
+impl SpellScope {
+    /// Pick the scope for a buffer from its language
+    pub fn for_language(language_name: &str, language: Option<&Language>) -> Self {
+        match language {
+            Some(Language::Markdown) => language
+                .and_then(|l| l.ts_language())
+                .map_or(SpellScope::Prose, SpellScope::Markdown),
+            Some(language) => language
+                .ts_language()
+                .map_or(SpellScope::Unsupported, SpellScope::Code),
+            None if language_name == "markdown"
+                || PROSE_LANGUAGES.contains(&language_name.to_lowercase().as_str()) =>
+            {
+                SpellScope::Prose
+            }
+            None => SpellScope::Unsupported,
+        }
+    }
+
+    /// Byte ranges of `text` that should be checked
+    pub fn checkable_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match self {
+            SpellScope::Prose => {
+                let whole = 0..text.len();
+                vec![whole]
+            }
+            SpellScope::Markdown(ts_language) => {
+                let code_blocks = parse(ts_language, text)
+                    .map(|tree| named_nodes(&tree, |kind| MARKDOWN_CODE_BLOCKS.contains(&kind)))
+                    .unwrap_or_default();
+                let mut ranges = Vec::new();
+                let mut start = 0;
+                for block in code_blocks {
+                    ranges.extend(without_code_spans(text, start..block.start));
+                    start = block.end;
+                }
+                ranges.extend(without_code_spans(text, start..text.len()));
+                ranges
+            }
+            SpellScope::Code(ts_language) => parse(ts_language, text)
+                .map(|tree| {
+                    named_nodes(&tree, |kind| {
+                        kind.contains("comment") || kind.contains("string")
+                    })
+                })
+                .unwrap_or_default(),
+            SpellScope::Unsupported => Vec::new(),
+        }
+    }
+}
+
+fn parse(ts_language: &fresh_languages::tree_sitter::Language, text: &str) -> Option<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(ts_language).ok()?;
+    parser.parse(text, None)
+}
+
+/// Ranges of the outermost named nodes whose kind matches
+fn named_nodes(tree: &Tree, matches: impl Fn(&str) -> bool) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        if node.is_named() && matches(node.kind()) {
+            ranges.push(node.byte_range());
+        } else if cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return ranges;
+            }
+        }
+    }
+}
+
+/// Split `range` around Markdown inline code spans (`` `code` ``)
+fn without_code_spans(text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut start = range.start;
+    let mut i = range.start;
+    while i < range.end {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let fence_len = bytes[i..range.end]
+            .iter()
+            .take_while(|&&b| b == b'`')
+            .count();
+        let fence = &text[i..i + fence_len];
+        match text[i + fence_len..range.end].find(fence) {
+            Some(close) => {
+                if start < i {
+                    ranges.push(start..i);
+                }
+                i += fence_len + close + fence_len;
+                start = i;
+            }
+            None => i += fence_len,
+        }
+    }
+    if start < range.end {
+        ranges.push(start..range.end);
+    }
+    ranges
+}
+
+/// Byte ranges of the words in `text` worth checking.
+///
+/// A word is a run of letters, possibly with inner apostrophes ("don't").
+/// Identifiers and other non-prose tokens are skipped: anything containing
+/// digits or underscores, camelCase and ALL-CAPS words, single letters, and
+/// whitespace-separated chunks that look like URLs or email addresses. A
+/// backslash escape (`\n`) separates words.
+pub fn words(text: &str) -> Vec<Range<usize>> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for chunk in text.split(char::is_whitespace) {
+        let is_link = chunk.contains("://") || chunk.starts_with("www.") || chunk.contains('@');
+        if !is_link {
+            chunk_words(chunk, offset, &mut result);
+        }
+        // The separator is a single whitespace character
+        offset += chunk.len();
+        offset += text[offset..].chars().next().map_or(0, char::len_utf8);
+    }
+    result
+}
+
+fn chunk_words(chunk: &str, offset: usize, result: &mut Vec<Range<usize>>) {
+    let chars: Vec<(usize, char)> = chunk.char_indices().collect();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i].1;
+        if c == '\\' {
+            i += 2;
+            continue;
+        }
+        if !is_word_char(c) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < chars.len() {
+            let c = chars[i].1;
+            let inner_apostrophe = (c == '\'' || c == '\u{2019}')
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|(_, next)| next.is_alphabetic());
+            if is_word_char(c) || inner_apostrophe {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+
+        let start_byte = chars[start].0;
+        let end_byte = chars.get(i).map_or(chunk.len(), |(pos, _)| *pos);
+        if is_checkable(&chunk[start_byte..end_byte]) {
+            result.push(offset + start_byte..offset + end_byte);
+        }
+    }
+}
+
+fn is_checkable(word: &str) -> bool {
+    let mut chars = word.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let rest: Vec<char> = chars.collect();
+    first.is_alphabetic()
+        && !rest.is_empty()
+        && rest
+            .iter()
+            .all(|c| c.is_lowercase() || *c == '\'' || *c == '\u{2019}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_list(text: &str) -> Vec<&str> {
+        words(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn test_words_skip_identifiers_and_links() {
+        assert_eq!(
+            word_list("Don't parse fooBar, HTTP or x_y; see https://exmaple.com"),
+            vec!["Don't", "parse", "or", "see"]
+        );
+        assert_eq!(word_list("a b2 mail me@hoem.org"), vec!["mail"]);
+        assert_eq!(
+            word_list("line\\nnext \u{e9}t\u{e9}"),
+            vec!["line", "next", "\u{e9}t\u{e9}"]
+        );
+    }
+
+    /// Words checked in `text` for a buffer of the given language
+    fn checked_words<'a>(text: &'a str, name: &str, language: Option<&Language>) -> Vec<&'a str> {
+        SpellScope::for_language(name, language)
+            .checkable_ranges(text)
+            .into_iter()
+            .flat_map(|range| {
+                words(&text[range.clone()])
+                    .into_iter()
+                    .map(move |word| &text[range.start + word.start..range.start + word.end])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_markdown_code_is_skipped() {
+        let text = "Some prose\n\n```\nnot prose\n```\n\nUse `codeword` here\n";
+        assert_eq!(
+            checked_words(text, "markdown", Some(&Language::Markdown)),
+            vec!["Some", "prose", "Use", "here"]
+        );
+    }
+
+    #[test]
+    fn test_code_checks_comments_and_strings() {
+        let text = "// a coment here\nfn mian() { let s = \"helo wrld\"; }\n";
+        assert_eq!(
+            checked_words(text, "rust", Some(&Language::Rust)),
+            vec!["coment", "here", "helo", "wrld"]
+        );
+        assert!(checked_words(text, "unknown", None).is_empty());
+    }
+
+    #[test]
+    fn test_personal_dictionary() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("xx.aff"), "SET UTF-8\n").unwrap();
+        std::fs::write(dir.path().join("xx.dic"), "1\nhello\n").unwrap();
+
+        let mut checker = SpellChecker::new(dir.path().to_path_buf());
+        assert!(checker.available_languages().contains(&"xx".to_string()));
+        let text = "hello fresh";
+        let whole = std::slice::from_ref(&(0..text.len())).to_vec();
+        assert_eq!(checker.misspellings("xx", text, &whole), vec![6..11]);
+
+        checker.add_to_personal_dictionary("fresh").unwrap();
+        assert!(checker.misspellings("xx", text, &whole).is_empty());
+
+        // Personal words are persisted
+        let mut reloaded = SpellChecker::new(dir.path().to_path_buf());
+        assert!(reloaded.is_correct("xx", "fresh"));
+    }
+}
@@ -1,7 +1,8 @@
 //! Styled text rendering for clipboard copy feature
 //!
 //! This module renders styled text with syntax highlighting as HTML
-//! for pasting into rich text editors (Google Docs, Word, etc.)
+//! for pasting into rich text editors (Google Docs, Word, etc.), or as
+//! ANSI-colored text for terminals and chat clients
 
 use crate::primitives::highlighter::HighlightSpan;
 use crate::view::theme::Theme;
@@ -119,6 +120,74 @@ pub fn render_styled_html(text: &str, highlight_spans: &[HighlightSpan], theme:
     html
 }
 
+/// Build a foreground SGR escape sequence for a ratatui Color
+///
+/// Returns None for `Color::Reset`, which keeps the terminal's own color.
+fn color_to_ansi(color: Color) -> Option<String> {
+    let code = match color {
+        Color::Rgb(r, g, b) => return Some(format!("\x1b[38;2;{};{};{}m", r, g, b)),
+        Color::Indexed(i) => return Some(format!("\x1b[38;5;{}m", i)),
+        Color::Reset => return None,
+        Color::Black => 30,
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Yellow => 33,
+        Color::Blue => 34,
+        Color::Magenta => 35,
+        Color::Cyan => 36,
+        Color::Gray => 37,
+        Color::DarkGray => 90,
+        Color::LightRed => 91,
+        Color::LightGreen => 92,
+        Color::LightYellow => 93,
+        Color::LightBlue => 94,
+        Color::LightMagenta => 95,
+        Color::LightCyan => 96,
+        Color::White => 97,
+    };
+    Some(format!("\x1b[{}m", code))
+}
+
+/// Render styled text with syntax highlighting as ANSI-colored text
+///
+/// Only highlighted spans are colored, so unhighlighted text keeps the
+/// terminal's own colors. Colors are reset at the end of every line so that
+/// pasting part of the output never leaves the terminal colored.
+pub fn render_styled_ansi(text: &str, highlight_spans: &[HighlightSpan]) -> String {
+    let mut color_map: Vec<Option<Color>> = vec![None; text.len()];
+    for span in highlight_spans {
+        let start = span.range.start.min(text.len());
+        let end = span.range.end.min(text.len());
+        for slot in &mut color_map[start..end] {
+            *slot = Some(span.color);
+        }
+    }
+
+    let mut out = String::new();
+    let mut current: Option<String> = None;
+    for (byte_offset, ch) in text.char_indices() {
+        let escape = if ch == '\n' {
+            None
+        } else {
+            color_map[byte_offset].and_then(color_to_ansi)
+        };
+        if escape != current {
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+            if let Some(escape) = &escape {
+                out.push_str(escape);
+            }
+            current = escape;
+        }
+        out.push(ch);
+    }
+    if current.is_some() {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,6 +237,28 @@ mod tests {
         assert!(html.contains("main()"));
     }
 
+    #[test]
+    fn test_render_ansi() {
+        let text = "fn main() {\n    1\n}";
+        let spans = vec![
+            HighlightSpan {
+                range: 0..2,
+                color: Color::Rgb(255, 128, 0),
+            },
+            // A span running over a line break is closed before it
+            HighlightSpan {
+                range: 10..17,
+                color: Color::Blue,
+            },
+        ];
+
+        assert_eq!(
+            render_styled_ansi(text, &spans),
+            "\x1b[38;2;255;128;0mfn\x1b[0m main() \x1b[34m{\x1b[0m\n\x1b[34m    1\x1b[0m\n}"
+        );
+        assert_eq!(render_styled_ansi("plain", &[]), "plain");
+    }
+
     #[test]
     fn test_color_to_css() {
         assert_eq!(color_to_css(Color::Black, "#fff"), "#000000");
@@ -0,0 +1,348 @@
+//! Project tasks
+//!
+//! A project declares named commands in `.fresh/tasks.toml`, such as build,
+//! test or deploy:
+//!
+//! ```toml
+//! [tasks.build]
+//! command = "cargo build"
+//! problem_matcher = "rust"
+//!
+//! [tasks.lint]
+//! command = "eslint --format unix src"
+//! cwd = "web"
+//! env = { NODE_ENV = "development" }
+//! problem_matcher = { regexp = '^(.+):(\d+):(\d+): (.*)$', message = 4 }
+//! ```
+//!
+//! A task runs its command through the shell in `cwd` (relative to the
+//! project) with `env` added to the project's environment (see
+//! `services::project_env`). Its output is read line by line, and a problem
+//! matcher picks out the lines that point at a location in a file: one of
+//! the built-in matchers (`rust`, `gcc`, `tsc`) or a regular expression with
+//! the groups of the file, line, column and message.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+
+/// The tasks file, relative to the working directory
+pub const TASKS_FILE: &str = ".fresh/tasks.toml";
+
+/// The contents of a tasks file
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TaskFile {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, TaskDefinition>,
+}
+
+/// A task: a command and where and how it runs
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskDefinition {
+    /// Shell command line
+    pub command: String,
+    /// Shown in the command palette instead of the command
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Directory to run in, relative to the project
+    #[serde(default)]
+    pub cwd: Option<PathBuf>,
+    /// Environment variables added for the command
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub problem_matcher: Option<ProblemMatcherConfig>,
+}
+
+impl TaskDefinition {
+    /// The directory the task runs in
+    pub fn working_dir(&self, project: &Path) -> PathBuf {
+        match &self.cwd {
+            Some(cwd) => project.join(cwd),
+            None => project.to_path_buf(),
+        }
+    }
+}
+
+/// A built-in problem matcher by name, or a custom pattern
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ProblemMatcherConfig {
+    Named(String),
+    Pattern(PatternConfig),
+}
+
+/// A problem matcher's regular expression and the numbers of its groups
+#[derive(Debug, Clone, Deserialize)]
+pub struct PatternConfig {
+    pub regexp: String,
+    #[serde(default = "default_file_group")]
+    pub file: usize,
+    #[serde(default = "default_line_group")]
+    pub line: usize,
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// Group holding `error`, `warning`, `note`...
+    #[serde(default)]
+    pub severity: Option<usize>,
+    #[serde(default)]
+    pub message: Option<usize>,
+}
+
+fn default_file_group() -> usize {
+    1
+}
+
+fn default_line_group() -> usize {
+    2
+}
+
+/// Read the tasks of the project at `working_dir`. A project without a
+/// tasks file has none.
+pub fn load_tasks(working_dir: &Path) -> Result<TaskFile, String> {
+    let path = working_dir.join(TASKS_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(TaskFile::default()),
+        Err(e) => return Err(e.to_string()),
+    };
+    toml::from_str(&content).map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl ProblemSeverity {
+    fn parse(text: &str) -> Self {
+        let text = text.to_ascii_lowercase();
+        if text.contains("error") {
+            Self::Error
+        } else if text.contains("warn") {
+            Self::Warning
+        } else {
+            Self::Info
+        }
+    }
+}
+
+/// A location a task's output points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// As written in the output, relative to the task's directory or absolute
+    pub file: PathBuf,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, if given
+    pub column: Option<usize>,
+    pub severity: ProblemSeverity,
+    pub message: String,
+}
+
+/// Finds the problems in a task's output, line by line
+pub enum ProblemMatcher {
+    Pattern {
+        regex: Regex,
+        groups: PatternConfig,
+    },
+    /// rustc and cargo: a `error[E0425]: message` heading, followed a few
+    /// lines below by `--> file:line:column`
+    Rust {
+        heading: Regex,
+        location: Regex,
+        pending: Option<(ProblemSeverity, String)>,
+    },
+}
+
+impl ProblemMatcher {
+    pub fn new(config: &ProblemMatcherConfig) -> Result<Self, String> {
+        let pattern = |regexp: &str, column, severity, message| {
+            ProblemMatcherConfig::Pattern(PatternConfig {
+                regexp: regexp.to_string(),
+                file: 1,
+                line: 2,
+                column: Some(column),
+                severity: Some(severity),
+                message: Some(message),
+            })
+        };
+        match config {
+            ProblemMatcherConfig::Named(name) => match name.as_str() {
+                "rust" => Ok(Self::Rust {
+                    heading: Regex::new(r"^(error|warning)(?:\[\w+\])?: (.*)$").unwrap(),
+                    location: Regex::new(r"^\s*--> (.+):(\d+):(\d+)$").unwrap(),
+                    pending: None,
+                }),
+                "gcc" => Self::new(&pattern(
+                    r"^(.+?):(\d+):(\d+):\s+(fatal error|error|warning|note):\s+(.*)$",
+                    3,
+                    4,
+                    5,
+                )),
+                "tsc" => Self::new(&pattern(
+                    r"^(.+?)\((\d+),(\d+)\):\s+(error|warning)\s+(.*)$",
+                    3,
+                    4,
+                    5,
+                )),
+                _ => Err(format!("unknown problem matcher {:?}", name)),
+            },
+            ProblemMatcherConfig::Pattern(groups) => Ok(Self::Pattern {
+                regex: Regex::new(&groups.regexp).map_err(|e| e.to_string())?,
+                groups: groups.clone(),
+            }),
+        }
+    }
+
+    /// The problem a line of output points at, if any
+    pub fn match_line(&mut self, line: &str) -> Option<Problem> {
+        match self {
+            Self::Pattern { regex, groups } => {
+                let captures = regex.captures(line)?;
+                let group = |index: Option<usize>| {
+                    index
+                        .and_then(|i| captures.get(i))
+                        .map(|m| m.as_str().trim())
+                };
+                Some(Problem {
+                    file: PathBuf::from(group(Some(groups.file))?),
+                    line: group(Some(groups.line))?.parse().ok()?,
+                    column: group(groups.column).and_then(|c| c.parse().ok()),
+                    severity: group(groups.severity)
+                        .map_or(ProblemSeverity::Error, ProblemSeverity::parse),
+                    message: group(groups.message).unwrap_or(line.trim()).to_string(),
+                })
+            }
+            Self::Rust {
+                heading,
+                location,
+                pending,
+            } => {
+                if let Some(captures) = heading.captures(line) {
+                    *pending = Some((
+                        ProblemSeverity::parse(&captures[1]),
+                        captures[2].to_string(),
+                    ));
+                    return None;
+                }
+                let captures = location.captures(line)?;
+                let (severity, message) = pending.take()?;
+                Some(Problem {
+                    file: PathBuf::from(&captures[1]),
+                    line: captures[2].parse().ok()?,
+                    column: captures[3].parse().ok(),
+                    severity,
+                    message,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(name: &str) -> ProblemMatcher {
+        ProblemMatcher::new(&ProblemMatcherConfig::Named(name.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tasks_file() {
+        let file: TaskFile = toml::from_str(
+            r#"
+            [tasks.build]
+            command = "cargo build"
+            problem_matcher = "rust"
+
+            [tasks.lint]
+            command = "eslint src"
+            cwd = "web"
+            env = { NODE_ENV = "development" }
+            problem_matcher = { regexp = '^(.+):(\d+): (.*)$', message = 3 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(file.tasks.keys().collect::<Vec<_>>(), vec!["build", "lint"]);
+        let lint = &file.tasks["lint"];
+        assert_eq!(lint.working_dir(Path::new("/p")), PathBuf::from("/p/web"));
+        assert_eq!(lint.env["NODE_ENV"], "development");
+        assert!(matches!(
+            lint.problem_matcher,
+            Some(ProblemMatcherConfig::Pattern(PatternConfig {
+                file: 1,
+                line: 2,
+                message: Some(3),
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_rust_matcher_pairs_heading_and_location() {
+        let mut matcher = matcher("rust");
+        let output = [
+            "   Compiling app v0.1.0",
+            "error[E0425]: cannot find value `x` in this scope",
+            "  --> src/main.rs:4:13",
+            "   |",
+            "note: defined here",
+            "  --> src/lib.rs:1:1",
+            "warning: unused variable: `y`",
+            " --> src/main.rs:2:9",
+        ];
+        let problems: Vec<Problem> = output
+            .iter()
+            .filter_map(|line| matcher.match_line(line))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                Problem {
+                    file: PathBuf::from("src/main.rs"),
+                    line: 4,
+                    column: Some(13),
+                    severity: ProblemSeverity::Error,
+                    message: "cannot find value `x` in this scope".to_string(),
+                },
+                Problem {
+                    file: PathBuf::from("src/main.rs"),
+                    line: 2,
+                    column: Some(9),
+                    severity: ProblemSeverity::Warning,
+                    message: "unused variable: `y`".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gcc_and_custom_matchers() {
+        let problem = matcher("gcc")
+            .match_line("src/a.c:10:5: warning: unused variable 'x'")
+            .unwrap();
+        assert_eq!(problem.file, PathBuf::from("src/a.c"));
+        assert_eq!((problem.line, problem.column), (10, Some(5)));
+        assert_eq!(problem.severity, ProblemSeverity::Warning);
+        assert_eq!(problem.message, "unused variable 'x'");
+
+        let mut custom = ProblemMatcher::new(&ProblemMatcherConfig::Pattern(PatternConfig {
+            regexp: r"^(.+):(\d+): (.*)$".to_string(),
+            file: 1,
+            line: 2,
+            column: None,
+            severity: None,
+            message: Some(3),
+        }))
+        .unwrap();
+        let problem = custom.match_line("lib/x.py:7: bad indent").unwrap();
+        assert_eq!((problem.line, problem.column), (7, None));
+        assert_eq!(problem.severity, ProblemSeverity::Error);
+        assert!(custom.match_line("all good").is_none());
+    }
+}
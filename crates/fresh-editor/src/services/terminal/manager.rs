@@ -20,6 +20,7 @@
 
 use super::term::TerminalState;
 use crate::services::async_bridge::AsyncBridge;
+use crate::services::project_env::ProjectEnv;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
@@ -110,6 +111,8 @@ pub struct TerminalManager {
     next_id: usize,
     /// Async bridge for sending notifications to main loop
     async_bridge: Option<AsyncBridge>,
+    /// The project's environment, which shells are started with
+    project_env: ProjectEnv,
 }
 
 impl TerminalManager {
@@ -119,9 +122,15 @@ impl TerminalManager {
             terminals: HashMap::new(),
             next_id: 0,
             async_bridge: None,
+            project_env: ProjectEnv::default(),
         }
     }
 
+    /// Set the project's environment for the terminals opened afterwards
+    pub fn set_project_env(&mut self, env: ProjectEnv) {
+        self.project_env = env;
+    }
+
     /// Set the async bridge for communication with main loop
     pub fn set_async_bridge(&mut self, bridge: AsyncBridge) {
         self.async_bridge = Some(bridge);
@@ -176,6 +185,7 @@ impl TerminalManager {
             if let Some(ref dir) = cwd {
                 cmd.cwd(dir);
             }
+            self.project_env.apply(&mut cmd);
 
             // Spawn the shell process
             let mut child = pty_pair
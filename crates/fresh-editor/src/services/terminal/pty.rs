@@ -177,6 +177,23 @@ pub fn key_to_pty_bytes(code: KeyCode, modifiers: KeyModifiers) -> Option<Vec<u8
     }
 }
 
+/// Convert pasted text to bytes to send to the PTY
+///
+/// Line breaks are sent as carriage returns, like the Enter key. When the
+/// program enabled bracketed paste the text is wrapped in paste markers, and
+/// any end marker inside the text is dropped so it can't end the paste early.
+pub fn paste_to_pty_bytes(text: &str, bracketed: bool) -> Vec<u8> {
+    const PASTE_START: &str = "\x1b[200~";
+    const PASTE_END: &str = "\x1b[201~";
+
+    let text = text.replace("\r\n", "\r").replace('\n', "\r");
+    if bracketed {
+        format!("{PASTE_START}{}{PASTE_END}", text.replace(PASTE_END, "")).into_bytes()
+    } else {
+        text.into_bytes()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +241,20 @@ mod tests {
         let bytes = key_to_pty_bytes(KeyCode::Char('x'), KeyModifiers::ALT);
         assert_eq!(bytes, Some(vec![0x1b, b'x']));
     }
+
+    #[test]
+    fn test_paste() {
+        assert_eq!(
+            paste_to_pty_bytes("ls\npwd\n", false),
+            b"ls\rpwd\r".to_vec()
+        );
+        assert_eq!(
+            paste_to_pty_bytes("a\r\nb", true),
+            b"\x1b[200~a\rb\x1b[201~".to_vec()
+        );
+        assert_eq!(
+            paste_to_pty_bytes("x\x1b[201~rm -rf", true),
+            b"\x1b[200~xrm -rf\x1b[201~".to_vec()
+        );
+    }
 }
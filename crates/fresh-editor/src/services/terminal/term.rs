@@ -297,6 +297,12 @@ impl TerminalState {
         self.term.mode().contains(TermMode::ALTERNATE_SCROLL)
     }
 
+    /// Check if bracketed paste mode is enabled.
+    /// When enabled, pasted text should be wrapped in paste markers.
+    pub fn uses_bracketed_paste(&self) -> bool {
+        self.term.mode().contains(TermMode::BRACKETED_PASTE)
+    }
+
     // =========================================================================
     // Incremental scrollback streaming
     // =========================================================================
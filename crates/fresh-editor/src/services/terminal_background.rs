@@ -0,0 +1,122 @@
+//! Terminal background detection (light or dark)
+//!
+//! Used by `appearance.mode = "auto"` to pick the light or dark theme.
+//! Sources, in order:
+//! - `FRESH_BACKGROUND` env var ("light" or "dark"), as a manual override
+//! - `COLORFGBG` env var, set by some terminals (e.g. rxvt, Konsole)
+//! - An OSC 11 query asking the terminal for its background color (Unix only)
+//!
+//! The OSC 11 query must run in raw mode, before the event loop starts reading
+//! input; otherwise the reply would be read as key presses.
+
+use std::time::Duration;
+
+use crate::view::theme::Appearance;
+
+/// How long to wait for the terminal to answer the background query
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Detect whether the terminal has a light or dark background
+pub fn detect() -> Option<Appearance> {
+    if let Some(appearance) = std::env::var("FRESH_BACKGROUND")
+        .ok()
+        .and_then(|value| Appearance::parse(&value))
+    {
+        return Some(appearance);
+    }
+
+    if let Some(appearance) = std::env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| parse_colorfgbg(&value))
+    {
+        return Some(appearance);
+    }
+
+    #[cfg(unix)]
+    if let Some((r, g, b)) = query_background_color(QUERY_TIMEOUT) {
+        tracing::info!("Terminal background color: #{:02x}{:02x}{:02x}", r, g, b);
+        return Some(Appearance::from_background(r, g, b));
+    }
+
+    None
+}
+
+/// Parse `COLORFGBG` ("fg;bg" or "fg;default;bg"): the last field is the
+/// background's ANSI color index
+pub fn parse_colorfgbg(value: &str) -> Option<Appearance> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    match bg {
+        // White and the bright colors except bright black
+        7 | 9..=15 => Some(Appearance::Light),
+        0..=6 | 8 => Some(Appearance::Dark),
+        _ => None,
+    }
+}
+
+/// Parse the terminal's reply to an OSC 11 query.
+///
+/// The reply looks like `ESC ] 11 ; rgb:RRRR/GGGG/BBBB` terminated by BEL or
+/// ST. Each channel has 1-4 hex digits.
+pub fn parse_osc11_response(response: &[u8]) -> Option<(u8, u8, u8)> {
+    let text = String::from_utf8_lossy(response);
+    let start = text.find("]11;")? + 4;
+    let body = &text[start..];
+    let end = body.find(['\x07', '\x1b']).unwrap_or(body.len());
+    let spec = &body[..end];
+    let channels = spec
+        .strip_prefix("rgb:")
+        .or_else(|| spec.strip_prefix("rgba:"))?;
+
+    let mut parts = channels.split('/').map(|part| {
+        if part.is_empty() || part.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(part, 16).ok()?;
+        let max = (1u32 << (4 * part.len())) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+/// Ask the terminal for its background color (OSC 11).
+///
+/// The query is followed by a primary device attributes request (DA1), which
+/// all terminals answer, so terminals that ignore OSC 11 don't cost the full
+/// timeout. The terminal must already be in raw mode.
+#[cfg(unix)]
+fn query_background_color(timeout: Duration) -> Option<(u8, u8, u8)> {
+    let response = super::terminal_capabilities::query_tty(b"\x1b]11;?\x1b\\\x1b[c", timeout)?;
+    parse_osc11_response(&response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Appearance::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Appearance::Light));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(Appearance::Light));
+        assert_eq!(parse_colorfgbg("7;8"), Some(Appearance::Dark));
+        assert_eq!(parse_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_parse_osc11_response() {
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:ffff/ffff/ffff\x1b\\"),
+            Some((255, 255, 255))
+        );
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:1e1e/1e1e/2e2e\x07\x1b[?62;22c"),
+            Some((30, 30, 46))
+        );
+        assert_eq!(
+            parse_osc11_response(b"\x1b]11;rgb:f/8/0\x07"),
+            Some((255, 136, 0))
+        );
+        // Only the DA1 reply: the terminal doesn't support OSC 11
+        assert_eq!(parse_osc11_response(b"\x1b[?1;2c"), None);
+    }
+}
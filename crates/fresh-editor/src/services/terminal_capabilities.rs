@@ -0,0 +1,343 @@
+//! Terminal capability detection
+//!
+//! At startup the terminal is asked what it supports, and each enhancement is
+//! only used when it is there:
+//! - Kitty keyboard protocol (`CSI ? u`): otherwise keys use legacy encoding
+//! - Synchronized output (DECRQM 2026): otherwise frames are drawn unwrapped
+//! - SGR mouse reporting (DECRQM 1006): reported only; crossterm falls back to
+//!   the legacy encoding, which can't address columns past 223
+//! - OSC 52 clipboard (DA1 attribute 52): otherwise copies only go through
+//!   the system clipboard
+//! - Kitty graphics (a query action) or sixel (DA1 attribute 4): otherwise
+//!   image files are opened as text
+//! - The terminal's name and version (XTVERSION), used to recognise true color
+//!   terminals that don't set `COLORTERM` (e.g. over SSH)
+//!
+//! The queries end with a primary device attributes request (DA1), which all
+//! terminals answer, so a query the terminal ignores is known to be
+//! unsupported as soon as the DA1 reply arrives. Without a reply (or off
+//! Unix) a feature is unknown and the previous behavior is kept.
+//!
+//! Like the background query, the probe must run in raw mode before the event
+//! loop reads input; otherwise the replies would be read as key presses.
+
+use std::time::Duration;
+
+use crate::services::image_preview::GraphicsProtocol;
+use crate::view::color_support::ColorCapability;
+
+/// How long to wait for the terminal to answer
+#[cfg(unix)]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Kitty keyboard flags, synchronized output and SGR mouse mode, XTVERSION,
+/// a kitty graphics query (a 1x1 image that isn't stored), then DA1
+#[cfg(unix)]
+const PROBE_QUERY: &[u8] =
+    b"\x1b[?u\x1b[?2026$p\x1b[?1006$p\x1b[>0q\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c";
+
+/// Terminals known to support 24-bit color, by XTVERSION name
+const TRUECOLOR_TERMINALS: &[&str] = &[
+    "kitty",
+    "wezterm",
+    "foot",
+    "ghostty",
+    "iterm2",
+    "contour",
+    "alacritty",
+    "konsole",
+    "xterm.js",
+];
+
+/// What the terminal supports. `None` means it couldn't be determined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// Name and version reported by the terminal, or `TERM_PROGRAM`
+    pub name: Option<String>,
+    /// The `TERM` environment variable
+    pub term: Option<String>,
+    /// Color support
+    pub color: ColorCapability,
+    /// Kitty keyboard protocol
+    pub keyboard_enhancement: Option<bool>,
+    /// Synchronized output (mode 2026)
+    pub synchronized_output: Option<bool>,
+    /// SGR mouse reporting (mode 1006)
+    pub sgr_mouse: Option<bool>,
+    /// Setting the clipboard with OSC 52
+    pub clipboard: Option<bool>,
+    /// Kitty graphics protocol
+    pub kitty_graphics: Option<bool>,
+    /// Sixel graphics
+    pub sixel: Option<bool>,
+}
+
+/// Replies to the probe query
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ProbeReplies {
+    /// Whether the DA1 reply arrived (so missing replies mean "unsupported")
+    pub answered: bool,
+    pub keyboard_enhancement: bool,
+    /// DECRPM status for modes 2026 and 1006
+    pub synchronized_output: Option<u8>,
+    pub sgr_mouse: Option<u8>,
+    /// Whether the kitty graphics query was answered with OK
+    pub kitty_graphics: bool,
+    pub version: Option<String>,
+    /// DA1 attributes
+    pub attributes: Vec<u16>,
+}
+
+impl TerminalCapabilities {
+    /// Capabilities known without asking the terminal
+    pub fn from_env(color: ColorCapability) -> Self {
+        let term = std::env::var("TERM").ok();
+        let clipboard = match term.as_deref() {
+            Some("linux" | "dumb") => Some(false),
+            _ => None,
+        };
+        Self {
+            name: std::env::var("TERM_PROGRAM").ok(),
+            term,
+            color,
+            keyboard_enhancement: None,
+            synchronized_output: None,
+            sgr_mouse: None,
+            clipboard,
+            kitty_graphics: None,
+            sixel: None,
+        }
+    }
+
+    /// Ask the terminal what it supports. The terminal must be in raw mode.
+    pub fn probe(color: ColorCapability) -> Self {
+        #[cfg(unix)]
+        let replies = query_tty(PROBE_QUERY, QUERY_TIMEOUT)
+            .map(|response| parse_probe_replies(&response))
+            .unwrap_or_default();
+        #[cfg(not(unix))]
+        let replies = ProbeReplies::default();
+
+        let mut capabilities = Self::from_env(color);
+        capabilities.apply(&replies);
+        tracing::info!("Terminal capabilities: {:?}", capabilities);
+        capabilities
+    }
+
+    /// Fill in what the terminal's replies tell us
+    pub fn apply(&mut self, replies: &ProbeReplies) {
+        if !replies.answered {
+            return;
+        }
+        // DECRPM: 1 = set, 2 = reset (both supported), 0 = unknown mode,
+        // 4 = permanently reset
+        let mode_supported = |status: Option<u8>| matches!(status, Some(1 | 2));
+        self.keyboard_enhancement = Some(replies.keyboard_enhancement);
+        self.synchronized_output = Some(mode_supported(replies.synchronized_output));
+        self.sgr_mouse = Some(mode_supported(replies.sgr_mouse));
+        if replies.attributes.contains(&52) {
+            self.clipboard = Some(true);
+        }
+        self.kitty_graphics = Some(replies.kitty_graphics);
+        self.sixel = Some(replies.attributes.contains(&4));
+        if let Some(version) = &replies.version {
+            self.name = Some(version.clone());
+            let lower = version.to_lowercase();
+            if self.color == ColorCapability::Color256
+                && std::env::var_os("FRESH_COLOR_MODE").is_none()
+                && TRUECOLOR_TERMINALS.iter().any(|t| lower.starts_with(t))
+            {
+                self.color = ColorCapability::TrueColor;
+            }
+        }
+    }
+
+    /// How images can be drawn, preferring kitty graphics
+    pub fn graphics_protocol(&self) -> Option<GraphicsProtocol> {
+        if self.kitty_graphics == Some(true) {
+            Some(GraphicsProtocol::Kitty)
+        } else if self.sixel == Some(true) {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse the terminal's replies to `PROBE_QUERY`
+pub fn parse_probe_replies(response: &[u8]) -> ProbeReplies {
+    let text = String::from_utf8_lossy(response);
+    let mut replies = ProbeReplies::default();
+
+    let mut rest = text.as_ref();
+    while let Some(start) = rest.find('\x1b') {
+        rest = &rest[start + 1..];
+        if let Some(body) = rest.strip_prefix("P>|") {
+            // XTVERSION: DCS > | text ST
+            let end = body.find(['\x1b', '\x07']).unwrap_or(body.len());
+            replies.version = Some(body[..end].to_string());
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("_G") {
+            // Kitty graphics: APC G i=31;OK ST
+            let end = body.find('\x1b').unwrap_or(body.len());
+            if body[..end].ends_with(";OK") {
+                replies.kitty_graphics = true;
+            }
+            continue;
+        }
+        let Some(body) = rest.strip_prefix("[?") else {
+            continue;
+        };
+        let end = body
+            .find(|c: char| !(c.is_ascii_digit() || c == ';' || c == '$'))
+            .unwrap_or(body.len());
+        let params = &body[..end];
+        match body[end..].chars().next() {
+            Some('u') => replies.keyboard_enhancement = true,
+            Some('y') => {
+                let mut fields = params.trim_end_matches('$').split(';');
+                let mode = fields.next();
+                let status = fields.next().and_then(|s| s.parse().ok());
+                match mode {
+                    Some("2026") => replies.synchronized_output = status,
+                    Some("1006") => replies.sgr_mouse = status,
+                    _ => {}
+                }
+            }
+            Some('c') => {
+                replies.answered = true;
+                replies.attributes = params.split(';').filter_map(|p| p.parse().ok()).collect();
+            }
+            _ => {}
+        }
+    }
+    replies
+}
+
+/// Write `query` to the terminal and collect its replies until the DA1
+/// answer (`ESC [ ? ... c`) arrives or `timeout` passes. `query` must end
+/// with a DA1 request. The terminal must already be in raw mode.
+#[cfg(unix)]
+pub(crate) fn query_tty(query: &[u8], timeout: Duration) -> Option<Vec<u8>> {
+    use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+    use std::io::{Read, Write};
+    use std::os::fd::AsFd;
+    use std::time::Instant;
+
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    tty.write_all(query).ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut response = Vec::new();
+    let mut buf = [0u8; 256];
+    while !has_device_attributes(&response) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            tracing::debug!("Timed out waiting for the terminal to answer");
+            break;
+        }
+        let timeout_ms = remaining.as_millis().min(u16::MAX as u128) as u16;
+        let mut poll_fds = [PollFd::new(tty.as_fd(), PollFlags::POLLIN)];
+        match poll(&mut poll_fds, PollTimeout::from(timeout_ms)) {
+            Ok(ready) if ready > 0 => {}
+            _ => break,
+        }
+        match tty.read(&mut buf) {
+            Ok(n) if n > 0 => response.extend_from_slice(&buf[..n]),
+            _ => break,
+        }
+    }
+    Some(response)
+}
+
+/// Whether the reply contains the DA1 answer (`ESC [ ? ... c`)
+#[cfg(unix)]
+fn has_device_attributes(response: &[u8]) -> bool {
+    let mut rest = response;
+    while let Some(start) = rest.windows(3).position(|w| w == b"\x1b[?") {
+        rest = &rest[start + 3..];
+        let end = rest
+            .iter()
+            .position(|b| !(b.is_ascii_digit() || *b == b';'))
+            .unwrap_or(rest.len());
+        if rest.get(end) == Some(&b'c') {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_probe_replies() {
+        let replies = parse_probe_replies(
+            b"\x1b[?1u\x1b[?2026;2$y\x1b[?1006;1$y\x1bP>|WezTerm 20240203\x1b\\\x1b_Gi=31;OK\x1b\\\x1b[?65;4;52c",
+        );
+        assert_eq!(
+            replies,
+            ProbeReplies {
+                answered: true,
+                keyboard_enhancement: true,
+                synchronized_output: Some(2),
+                sgr_mouse: Some(1),
+                kitty_graphics: true,
+                version: Some("WezTerm 20240203".to_string()),
+                attributes: vec![65, 4, 52],
+            }
+        );
+
+        // A terminal that only answers DA1
+        let replies = parse_probe_replies(b"\x1b[?1;2c");
+        assert!(replies.answered);
+        assert!(!replies.keyboard_enhancement);
+        assert_eq!(replies.synchronized_output, None);
+
+        assert!(!parse_probe_replies(b"").answered);
+    }
+
+    #[test]
+    fn test_apply_probe_replies() {
+        let mut capabilities = TerminalCapabilities::from_env(ColorCapability::Color256);
+        capabilities.apply(&parse_probe_replies(
+            b"\x1b[?2026;0$y\x1b[?1006;1$y\x1b[?1;2c",
+        ));
+        assert_eq!(capabilities.keyboard_enhancement, Some(false));
+        assert_eq!(capabilities.synchronized_output, Some(false));
+        assert_eq!(capabilities.sgr_mouse, Some(true));
+        assert_eq!(capabilities.graphics_protocol(), None);
+
+        // Sixel only
+        let mut capabilities = TerminalCapabilities::from_env(ColorCapability::Color256);
+        capabilities.apply(&parse_probe_replies(
+            b"\x1b_Gi=31;ENOTSUPPORTED:\x1b\\\x1b[?62;4c",
+        ));
+        assert_eq!(capabilities.kitty_graphics, Some(false));
+        assert_eq!(
+            capabilities.graphics_protocol(),
+            Some(GraphicsProtocol::Sixel)
+        );
+
+        // No reply at all: nothing is known
+        let mut capabilities = TerminalCapabilities::from_env(ColorCapability::Color256);
+        capabilities.apply(&parse_probe_replies(b""));
+        assert_eq!(capabilities.keyboard_enhancement, None);
+        assert_eq!(capabilities.synchronized_output, None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_has_device_attributes() {
+        assert!(has_device_attributes(b"\x1b[?1u\x1b[?62;22c"));
+        // A kitty keyboard reply or DECRPM alone is not the DA1 answer
+        assert!(!has_device_attributes(b"\x1b[?1u\x1b[?2026;2$y"));
+    }
+}
@@ -6,6 +6,11 @@
 //! - Mouse capture
 //! - Keyboard enhancement flags
 //! - Bracketed paste
+//! - Focus change reporting
+//! - Window title (saved on entry and restored on exit)
+//!
+//! Enhancements the terminal doesn't support (see `terminal_capabilities`)
+//! are left off.
 //!
 //! It provides a `TerminalModes` struct that tracks which modes were enabled
 //! and can restore the terminal to its original state via the `undo()` method.
@@ -14,8 +19,9 @@ use anyhow::Result;
 use crossterm::{
     cursor::SetCursorStyle,
     event::{
-        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-        KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
     },
     terminal::{
         disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen,
@@ -25,6 +31,10 @@ use crossterm::{
 };
 use std::io::{stdout, Write};
 
+use super::shell_integration;
+use super::terminal_capabilities::TerminalCapabilities;
+use crate::view::color_support::ColorCapability;
+
 /// Configuration for keyboard enhancement flags.
 #[derive(Debug, Clone)]
 pub struct KeyboardConfig {
@@ -88,6 +98,9 @@ pub struct TerminalModes {
     mouse_capture: bool,
     keyboard_enhancement: bool,
     bracketed_paste: bool,
+    focus_change: bool,
+    title_saved: bool,
+    capabilities: Option<TerminalCapabilities>,
 }
 
 impl TerminalModes {
@@ -130,11 +143,18 @@ impl TerminalModes {
         modes.alternate_screen = true;
         tracing::debug!("Entered alternate screen");
 
+        // Ask the terminal what it supports before enabling any enhancements
+        let capabilities = TerminalCapabilities::probe(ColorCapability::detect());
+
         // Check and enable keyboard enhancement flags (if any are configured)
         // This must happen AFTER entering alternate screen so the flags are pushed
         // to the alternate screen's stack, not the main screen's stack.
         if keyboard_config.any_enabled() {
-            match supports_keyboard_enhancement() {
+            let supported = match capabilities.keyboard_enhancement {
+                Some(supported) => Ok(supported),
+                None => supports_keyboard_enhancement(),
+            };
+            match supported {
                 Ok(true) => {
                     let flags = keyboard_config.to_flags();
                     if let Err(e) = stdout().execute(PushKeyboardEnhancementFlags(flags)) {
@@ -174,6 +194,24 @@ impl TerminalModes {
             tracing::debug!("Enabled bracketed paste mode");
         }
 
+        // Enable focus change reporting (used for auto-save on focus loss)
+        if let Err(e) = stdout().execute(EnableFocusChange) {
+            tracing::warn!("Failed to enable focus change reporting: {}", e);
+            // Non-fatal, continue without it
+        } else {
+            modes.focus_change = true;
+            tracing::debug!("Enabled focus change reporting");
+        }
+
+        // Save the window title, which the editor replaces with the file name
+        if let Err(e) = shell_integration::save_title() {
+            tracing::warn!("Failed to save terminal title: {}", e);
+        } else {
+            modes.title_saved = true;
+        }
+
+        modes.capabilities = Some(capabilities);
+
         Ok(modes)
     }
 
@@ -196,12 +234,25 @@ impl TerminalModes {
             tracing::debug!("Disabled bracketed paste");
         }
 
+        // Disable focus change reporting
+        if self.focus_change {
+            let _ = stdout().execute(DisableFocusChange);
+            self.focus_change = false;
+            tracing::debug!("Disabled focus change reporting");
+        }
+
         // Reset cursor style to default
         let _ = stdout().execute(SetCursorStyle::DefaultUserShape);
 
         // Reset terminal cursor color
         crate::view::theme::Theme::reset_terminal_cursor_color();
 
+        // Clear the progress indicator and restore the window title
+        if self.title_saved {
+            let _ = shell_integration::restore_title();
+            self.title_saved = false;
+        }
+
         // Pop keyboard enhancement flags
         if self.keyboard_enhancement {
             let _ = stdout().execute(PopKeyboardEnhancementFlags);
@@ -251,6 +302,11 @@ impl TerminalModes {
     pub fn alternate_screen_enabled(&self) -> bool {
         self.alternate_screen
     }
+
+    /// What the terminal was found to support by `enable()`.
+    pub fn capabilities(&self) -> Option<&TerminalCapabilities> {
+        self.capabilities.as_ref()
+    }
 }
 
 impl Drop for TerminalModes {
@@ -271,12 +327,18 @@ pub fn emergency_cleanup() {
     // Disable bracketed paste
     let _ = stdout().execute(DisableBracketedPaste);
 
+    // Disable focus change reporting
+    let _ = stdout().execute(DisableFocusChange);
+
     // Reset cursor style to default
     let _ = stdout().execute(SetCursorStyle::DefaultUserShape);
 
     // Reset terminal cursor color
     crate::view::theme::Theme::reset_terminal_cursor_color();
 
+    // Clear the progress indicator and restore the window title
+    let _ = shell_integration::restore_title();
+
     // Pop keyboard enhancement flags
     let _ = stdout().execute(PopKeyboardEnhancementFlags);
 
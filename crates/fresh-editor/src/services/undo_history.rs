@@ -0,0 +1,306 @@
+//! Undo history that survives restarting the editor
+//!
+//! When a file is saved, its undo history is written to the data directory,
+//! one file per edited file, named after a hash of its path:
+//!
+//! ```text
+//! ~/.local/share/fresh/undo/
+//! └── 3f2a9c0d1e4b5a67.json   # version, path, content checksum, entries
+//! ```
+//!
+//! Reopening the file restores the history, but only if the file still has
+//! the content it was saved with: a file changed by another program would
+//! make the recorded positions meaningless, so its history is thrown away.
+//! Histories are trimmed to a size limit when written and deleted once older
+//! than an age limit. They go through the recovery [`DataCodec`], so they are
+//! compressed and encrypted like recovery files when that is configured.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::model::event::{EventLog, LogEntry};
+use crate::services::recovery::types::path_hash;
+use crate::services::recovery::DataCodec;
+
+/// Current format version
+const UNDO_HISTORY_VERSION: u32 = 1;
+
+/// The undo history of one file, as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedUndoHistory {
+    version: u32,
+    /// The file the history belongs to (a hash collision is not a match)
+    path: PathBuf,
+    /// SHA-256 of the file content the history was saved with
+    checksum: String,
+    /// When the history was saved (Unix seconds)
+    saved_at: u64,
+    /// Position in `entries` matching the saved content
+    current_index: usize,
+    entries: Vec<LogEntry>,
+}
+
+/// Undo histories kept in one directory
+#[derive(Debug, Clone)]
+pub struct UndoStore {
+    dir: PathBuf,
+}
+
+impl UndoStore {
+    /// Undo histories stored in `dir`
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn history_path(&self, path: &Path) -> PathBuf {
+        self.dir.join(format!("{}.json", path_hash(path)))
+    }
+
+    /// Write the undo history of `path`, whose content is now `content`.
+    /// Entries are dropped to stay under `max_bytes`; a history with nothing
+    /// left to undo or redo removes the stored one.
+    pub fn save(
+        &self,
+        path: &Path,
+        content: &[u8],
+        log: &EventLog,
+        max_bytes: usize,
+        codec: &DataCodec,
+    ) -> io::Result<()> {
+        let (entries, current_index) = log.persistable_history();
+        let (entries, current_index) = trim_to_size(entries, current_index, max_bytes);
+        if entries.is_empty() {
+            self.remove(path);
+            return Ok(());
+        }
+
+        let history = PersistedUndoHistory {
+            version: UNDO_HISTORY_VERSION,
+            path: path.to_path_buf(),
+            checksum: checksum(content),
+            saved_at: unix_now(),
+            current_index,
+            entries: entries.to_vec(),
+        };
+        let json = serde_json::to_vec(&history).map_err(io::Error::other)?;
+        let data = codec.encode(&json)?;
+
+        // Write atomically: temp file + rename
+        fs::create_dir_all(&self.dir)?;
+        let history_path = self.history_path(path);
+        let temp_path = history_path.with_extension("json.tmp");
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &history_path)
+    }
+
+    /// The undo history of `path`, if one was saved for `content` less than
+    /// `max_age` ago. A history that no longer applies is deleted.
+    pub fn load(
+        &self,
+        path: &Path,
+        content: &[u8],
+        max_age: Duration,
+        codec: &DataCodec,
+    ) -> Option<EventLog> {
+        let history_path = self.history_path(path);
+        let data = fs::read(&history_path).ok()?;
+        let history: PersistedUndoHistory = match codec
+            .decode(&data)
+            .and_then(|json| serde_json::from_slice(&json).map_err(io::Error::other))
+        {
+            Ok(history) => history,
+            Err(e) => {
+                // Possibly encrypted with a key that isn't available now
+                tracing::debug!("Failed to read undo history {:?}: {}", history_path, e);
+                return None;
+            }
+        };
+
+        if history.path != path {
+            return None;
+        }
+        if history.version > UNDO_HISTORY_VERSION
+            || history.checksum != checksum(content)
+            || is_expired(history.saved_at, max_age)
+        {
+            tracing::debug!("Discarding stale undo history of {:?}", path);
+            let _ = fs::remove_file(&history_path);
+            return None;
+        }
+        Some(EventLog::from_history(
+            history.entries,
+            history.current_index,
+        ))
+    }
+
+    /// Forget the undo history of `path`
+    pub fn remove(&self, path: &Path) {
+        let _ = fs::remove_file(self.history_path(path));
+    }
+
+    /// Delete the histories saved more than `max_age` ago. Returns how many
+    /// were deleted.
+    pub fn remove_expired(&self, max_age: Duration) -> io::Result<usize> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let modified = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
+            if is_expired(modified, max_age) && fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// SHA-256 of file content, in hex
+fn checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn is_expired(saved_at: u64, max_age: Duration) -> bool {
+    unix_now().saturating_sub(saved_at) > max_age.as_secs()
+}
+
+/// The entries to keep so their JSON fits in `max_bytes`, and the current
+/// position among them. Redo entries go first, then the oldest undo entries.
+fn trim_to_size(
+    entries: &[LogEntry],
+    current_index: usize,
+    max_bytes: usize,
+) -> (&[LogEntry], usize) {
+    let sizes: Vec<usize> = entries
+        .iter()
+        .map(|entry| serde_json::to_vec(entry).map_or(0, |json| json.len()))
+        .collect();
+    let mut total: usize = sizes.iter().sum();
+    let (mut start, mut end) = (0, entries.len());
+    while total > max_bytes && end > current_index {
+        end -= 1;
+        total -= sizes[end];
+    }
+    while total > max_bytes && start < end {
+        total -= sizes[start];
+        start += 1;
+    }
+    (&entries[start..end], current_index - start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::event::{CursorId, Event};
+    use tempfile::TempDir;
+
+    fn log_with_inserts(count: usize) -> EventLog {
+        let mut log = EventLog::new();
+        for position in 0..count {
+            log.append(Event::Insert {
+                position,
+                text: "x".to_string(),
+                cursor_id: CursorId(0),
+            });
+        }
+        log
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = TempDir::new().unwrap();
+        let store = UndoStore::new(dir.path().join("undo"));
+        let path = Path::new("/project/notes.txt");
+        let codec = DataCodec::plain();
+
+        let mut log = log_with_inserts(3);
+        log.undo();
+        store.save(path, b"xx", &log, usize::MAX, &codec).unwrap();
+
+        let day = Duration::from_secs(24 * 60 * 60);
+        let restored = store.load(path, b"xx", day, &codec).unwrap();
+        assert_eq!(restored.current_index(), 2);
+        assert!(restored.can_redo());
+        assert!(restored.is_at_saved_position());
+
+        // Another file with no history of its own
+        assert!(store
+            .load(Path::new("/project/other.txt"), b"xx", day, &codec)
+            .is_none());
+    }
+
+    #[test]
+    fn test_changed_content_discards_history() {
+        let dir = TempDir::new().unwrap();
+        let store = UndoStore::new(dir.path().to_path_buf());
+        let path = Path::new("/project/notes.txt");
+        let codec = DataCodec::plain();
+        let day = Duration::from_secs(24 * 60 * 60);
+
+        store
+            .save(path, b"xxx", &log_with_inserts(3), usize::MAX, &codec)
+            .unwrap();
+        assert!(store.load(path, b"xxx, edited", day, &codec).is_none());
+        // Deleted, so it isn't restored even if the content is changed back
+        assert!(store.load(path, b"xxx", day, &codec).is_none());
+    }
+
+    #[test]
+    fn test_expired_history() {
+        let dir = TempDir::new().unwrap();
+        let store = UndoStore::new(dir.path().to_path_buf());
+        let path = Path::new("/project/notes.txt");
+        let codec = DataCodec::plain();
+
+        store
+            .save(path, b"x", &log_with_inserts(1), usize::MAX, &codec)
+            .unwrap();
+        assert_eq!(store.remove_expired(Duration::from_secs(3600)).unwrap(), 0);
+        assert!(store.history_path(path).exists());
+
+        // saved_at is in whole seconds, so wait until it's in the past
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(store.load(path, b"x", Duration::ZERO, &codec).is_none());
+        assert!(!store.history_path(path).exists());
+    }
+
+    #[test]
+    fn test_trim_to_size() {
+        let log = log_with_inserts(4);
+        let (entries, current_index) = log.persistable_history();
+        let size = serde_json::to_vec(&entries[0]).unwrap().len();
+
+        assert_eq!(trim_to_size(entries, 2, usize::MAX).0.len(), 4);
+
+        // Redo entries are dropped before undo entries
+        let (kept, index) = trim_to_size(entries, 2, 2 * size);
+        assert_eq!((kept.len(), index), (2, 2));
+
+        // Then the oldest undo entries
+        let (kept, index) = trim_to_size(entries, current_index, 3 * size);
+        assert_eq!((kept.len(), index), (3, 3));
+        assert!(matches!(kept[0].event, Event::Insert { position: 1, .. }));
+
+        assert!(trim_to_size(entries, current_index, 0).0.is_empty());
+    }
+}
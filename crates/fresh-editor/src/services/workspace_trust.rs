@@ -0,0 +1,177 @@
+//! Workspace trust
+//!
+//! Opening a project can run code: plugins in its `plugins` directory, the
+//! language servers, formatters, on-save actions and REPL commands set in
+//! its config (`.fresh/config.json`, `.fresh/session.json`), the tasks in
+//! `.fresh/tasks.toml`, and the environment in `.envrc` (run by direnv) and
+//! `.env`, which can put the project's own programs on the PATH. None of it
+//! runs until the user trusts the workspace. Until then the editor is in safe
+//! mode: those settings are left out of the config (see
+//! [`PartialConfig::remove_commands`]), the project's plugins aren't loaded,
+//! its tasks don't run and its environment isn't loaded. Editing works as
+//! usual.
+//!
+//! Workspaces trusted for good are listed in the data directory; trusting a
+//! directory also trusts the directories below it:
+//!
+//! ```text
+//! ~/.local/share/fresh/trusted_workspaces.json   # {"workspaces": ["/home/user/src"]}
+//! ```
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config_io::{ConfigResolver, DirectoryContext};
+use crate::partial_config::PartialConfig;
+use crate::services::project_env::{DOTENV_FILE, ENVRC_FILE};
+use crate::services::tasks::TASKS_FILE;
+
+/// The workspaces the user trusts
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(default)]
+    workspaces: BTreeSet<PathBuf>,
+}
+
+impl TrustStore {
+    /// Read the store at `path`; a missing or unreadable store trusts nothing
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether `dir` or a directory above it is trusted
+    pub fn is_trusted(&self, dir: &Path) -> bool {
+        let dir = canonical(dir);
+        dir.ancestors().any(|dir| self.workspaces.contains(dir))
+    }
+
+    /// Trust `dir` and save the store to `path`
+    pub fn trust(&mut self, dir: &Path, path: &Path) -> io::Result<()> {
+        self.workspaces.insert(canonical(dir));
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+}
+
+fn canonical(dir: &Path) -> PathBuf {
+    dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Whether the user trusts the workspace at `working_dir`
+pub fn is_trusted(dir_context: &DirectoryContext, working_dir: &Path) -> bool {
+    TrustStore::load(&dir_context.trusted_workspaces_path()).is_trusted(working_dir)
+}
+
+/// Trust the workspace at `working_dir` for good
+pub fn trust(dir_context: &DirectoryContext, working_dir: &Path) -> io::Result<()> {
+    let path = dir_context.trusted_workspaces_path();
+    TrustStore::load(&path).trust(working_dir, &path)
+}
+
+/// Whether the workspace has anything that safe mode leaves out: plugins,
+/// tasks, an environment, or commands in its config
+pub fn has_code(dir_context: &DirectoryContext, working_dir: &Path) -> bool {
+    has_plugins(&working_dir.join("plugins"))
+        || [TASKS_FILE, ENVRC_FILE, DOTENV_FILE]
+            .iter()
+            .any(|file| working_dir.join(file).is_file())
+        || {
+            let resolver = ConfigResolver::new(dir_context.clone(), working_dir.to_path_buf());
+            [resolver.load_project_layer(), resolver.load_session_layer()]
+                .into_iter()
+                .any(|layer| {
+                    layer
+                        .ok()
+                        .flatten()
+                        .as_mut()
+                        .is_some_and(PartialConfig::remove_commands)
+                })
+        }
+}
+
+/// Whether `dir` holds any plugin scripts
+fn has_plugins(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == "ts" || ext == "js")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn trust_covers_subdirectories_and_persists() {
+        let temp = TempDir::new().unwrap();
+        let store_path = temp.path().join("data").join("trusted_workspaces.json");
+        let src = temp.path().join("src");
+        let project = src.join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let mut store = TrustStore::load(&store_path);
+        assert!(!store.is_trusted(&project));
+        store.trust(&src, &store_path).unwrap();
+        assert!(store.is_trusted(&project));
+
+        let store = TrustStore::load(&store_path);
+        assert!(store.is_trusted(&project));
+        assert!(!store.is_trusted(temp.path()));
+    }
+
+    #[test]
+    fn has_code_finds_plugins_tasks_environment_and_commands() {
+        let temp = TempDir::new().unwrap();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let project = temp.path().join("project");
+        fs::create_dir_all(project.join(".fresh")).unwrap();
+        assert!(!has_code(&dir_context, &project));
+
+        // Plain settings and an empty plugins directory are harmless
+        fs::write(
+            project.join(".fresh").join("config.json"),
+            r#"{"editor": {"tab_size": 2}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(project.join("plugins")).unwrap();
+        assert!(!has_code(&dir_context, &project));
+
+        fs::write(
+            project.join(".fresh").join("config.json"),
+            r#"{"lsp": {"rust": {"command": "./server"}}}"#,
+        )
+        .unwrap();
+        assert!(has_code(&dir_context, &project));
+
+        fs::remove_file(project.join(".fresh").join("config.json")).unwrap();
+        fs::write(project.join("plugins").join("hello.ts"), "").unwrap();
+        assert!(has_code(&dir_context, &project));
+
+        fs::remove_file(project.join("plugins").join("hello.ts")).unwrap();
+        fs::write(
+            project.join(".fresh").join("tasks.toml"),
+            "[tasks.build]\ncommand = \"make\"\n",
+        )
+        .unwrap();
+        assert!(has_code(&dir_context, &project));
+
+        fs::remove_file(project.join(".fresh").join("tasks.toml")).unwrap();
+        fs::write(project.join(".envrc"), "use flake\n").unwrap();
+        assert!(has_code(&dir_context, &project));
+    }
+}
@@ -22,6 +22,12 @@
 //!
 //! Uses atomic writes: write to temp file, then rename.
 //! This ensures the session file is never left in a corrupted state.
+//!
+//! ## Compression and Encryption
+//!
+//! Session and file state files are passed through the recovery
+//! [`DataCodec`], so they are compressed/encrypted when the
+//! `recovery_compression`/`recovery_encryption` settings are on.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,6 +36,7 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::input::input_history::get_data_dir;
+use crate::services::recovery::DataCodec;
 
 /// Current session file format version
 pub const SESSION_VERSION: u32 = 1;
@@ -83,6 +90,10 @@ pub struct Session {
     #[serde(default)]
     pub external_files: Vec<PathBuf>,
 
+    /// Folders opened alongside the working directory (absolute paths)
+    #[serde(default)]
+    pub workspace_folders: Vec<PathBuf>,
+
     /// Timestamp when session was saved (Unix epoch seconds)
     pub saved_at: u64,
 }
@@ -125,6 +136,10 @@ pub struct SerializedSplitViewState {
     #[serde(default)]
     pub active_tab_index: Option<usize>,
 
+    /// Number of pinned tabs at the start of open_tabs
+    #[serde(default)]
+    pub pinned_tabs: usize,
+
     /// Open files in tab order (paths relative to working_dir)
     /// Deprecated; retained for backward compatibility.
     #[serde(default)]
@@ -149,6 +164,14 @@ pub struct SerializedSplitViewState {
     /// Compose width if in compose mode
     #[serde(default)]
     pub compose_width: Option<u16>,
+
+    /// Jump list locations, oldest first
+    #[serde(default)]
+    pub jump_list: Vec<SerializedJump>,
+
+    /// Jump list browsing index (`jump_list.len()` when not browsing)
+    #[serde(default)]
+    pub jump_list_index: usize,
 }
 
 /// Per-file state within a split
@@ -194,6 +217,7 @@ pub enum SerializedViewMode {
     #[default]
     Source,
     Compose,
+    Preview,
 }
 
 /// Config overrides that differ from base config
@@ -284,6 +308,15 @@ pub struct SerializedBookmark {
     pub position: usize,
 }
 
+/// Serialized jump list entry (file path + byte offset)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedJump {
+    /// File path (relative to working_dir)
+    pub file_path: PathBuf,
+    /// Byte offset position in the file
+    pub position: usize,
+}
+
 /// Reference to an open tab (file path or terminal index)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SerializedTabRef {
@@ -367,7 +400,7 @@ impl PersistedFileSession {
     }
 
     /// Load the state for a file by its absolute path (from disk)
-    pub fn load(path: &Path) -> Option<SerializedFileState> {
+    pub fn load(path: &Path, codec: &DataCodec) -> Option<SerializedFileState> {
         let state_path = match Self::state_file_path(path) {
             Ok(p) => p,
             Err(_) => return None,
@@ -377,12 +410,12 @@ impl PersistedFileSession {
             return None;
         }
 
-        let content = match std::fs::read_to_string(&state_path) {
+        let content = match std::fs::read(&state_path).and_then(|c| codec.decode(&c)) {
             Ok(c) => c,
             Err(_) => return None,
         };
 
-        let persisted: PersistedFileState = match serde_json::from_str(&content) {
+        let persisted: PersistedFileState = match serde_json::from_slice(&content) {
             Ok(p) => p,
             Err(_) => return None,
         };
@@ -396,7 +429,7 @@ impl PersistedFileSession {
     }
 
     /// Save the state for a file by its absolute path (to disk, atomic write)
-    pub fn save(path: &Path, state: SerializedFileState) {
+    pub fn save(path: &Path, state: SerializedFileState, codec: &DataCodec) {
         let state_path = match Self::state_file_path(path) {
             Ok(p) => p,
             Err(e) => {
@@ -426,8 +459,9 @@ impl PersistedFileSession {
         let temp_path = state_path.with_extension("json.tmp");
 
         let write_result = (|| -> io::Result<()> {
+            let content = codec.encode(content.as_bytes())?;
             let mut file = std::fs::File::create(&temp_path)?;
-            file.write_all(content.as_bytes())?;
+            file.write_all(&content)?;
             file.sync_all()?;
             std::fs::rename(&temp_path, &state_path)?;
             Ok(())
@@ -610,7 +644,7 @@ impl From<serde_json::Error> for SessionError {
 
 impl Session {
     /// Load session for a working directory (if exists)
-    pub fn load(working_dir: &Path) -> Result<Option<Session>, SessionError> {
+    pub fn load(working_dir: &Path, codec: &DataCodec) -> Result<Option<Session>, SessionError> {
         let path = get_session_path(working_dir)?;
         tracing::debug!("Looking for session at {:?}", path);
 
@@ -620,8 +654,8 @@ impl Session {
         }
 
         tracing::debug!("Loading session from {:?}", path);
-        let content = std::fs::read_to_string(&path)?;
-        let session: Session = serde_json::from_str(&content)?;
+        let content = codec.decode(&std::fs::read(&path)?)?;
+        let session: Session = serde_json::from_slice(&content)?;
 
         tracing::debug!(
             "Loaded session: version={}, split_states={}, active_split={}",
@@ -670,7 +704,7 @@ impl Session {
     /// 1. Write to a temporary file in the same directory
     /// 2. Sync to disk (fsync)
     /// 3. Atomically rename to the final path
-    pub fn save(&self) -> Result<(), SessionError> {
+    pub fn save(&self, codec: &DataCodec) -> Result<(), SessionError> {
         let path = get_session_path(&self.working_dir)?;
         tracing::debug!("Saving session to {:?}", path);
 
@@ -682,6 +716,7 @@ impl Session {
         // Serialize to JSON
         let content = serde_json::to_string_pretty(self)?;
         tracing::trace!("Session JSON size: {} bytes", content.len());
+        let content = codec.encode(content.as_bytes())?;
 
         // Write atomically: temp file + rename
         let temp_path = path.with_extension("json.tmp");
@@ -689,7 +724,7 @@ impl Session {
         // Write to temp file
         {
             let mut file = std::fs::File::create(&temp_path)?;
-            file.write_all(content.as_bytes())?;
+            file.write_all(&content)?;
             file.sync_all()?; // Ensure data is on disk before rename
         }
 
@@ -727,6 +762,7 @@ impl Session {
             bookmarks: HashMap::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
+            workspace_folders: Vec::new(),
             saved_at: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -970,12 +1006,18 @@ mod tests {
                     SerializedTabRef::File(PathBuf::from("src/lib.rs")),
                 ],
                 active_tab_index: Some(0),
+                pinned_tabs: 1,
                 open_files: vec![PathBuf::from("README.md"), PathBuf::from("src/lib.rs")],
                 active_file_index: 0,
                 file_states: HashMap::new(),
                 tab_scroll_offset: 0,
                 view_mode: SerializedViewMode::Source,
                 compose_width: None,
+                jump_list: vec![SerializedJump {
+                    file_path: PathBuf::from("src/lib.rs"),
+                    position: 42,
+                }],
+                jump_list_index: 1,
             },
         );
 
@@ -1008,6 +1050,9 @@ mod tests {
         let split_state = restored.split_states.get(&1).unwrap();
         assert_eq!(split_state.open_files.len(), 2);
         assert_eq!(split_state.open_files[0], PathBuf::from("README.md"));
+        assert_eq!(split_state.jump_list.len(), 1);
+        assert_eq!(split_state.jump_list[0].position, 42);
+        assert_eq!(split_state.jump_list_index, 1);
     }
 
     #[test]
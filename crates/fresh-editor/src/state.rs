@@ -13,9 +13,14 @@ use crate::primitives::grammar::GrammarRegistry;
 use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::highlighter::Language;
 use crate::primitives::indent::IndentCalculator;
+use crate::primitives::indent_detection::{detect_indentation, Indentation};
+use crate::primitives::outline::{self, OutlineSymbol};
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::text_objects;
 use crate::primitives::text_property::TextPropertyManager;
+use crate::services::spell::BufferSpellState;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
+use crate::view::conceal::Concealer;
 use crate::view::margin::{MarginAnnotation, MarginContent, MarginManager, MarginPosition};
 use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, UnderlineStyle};
 use crate::view::popup::{
@@ -23,6 +28,7 @@ use crate::view::popup::{
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
 use crate::view::virtual_text::VirtualTextManager;
+use crate::view::visual_guides::VisualGuides;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
 use rust_i18n::t;
@@ -30,6 +36,9 @@ use std::cell::RefCell;
 use std::ops::Range;
 use std::sync::Arc;
 
+/// Bytes at the start of a file sampled to detect its indentation
+const INDENT_DETECTION_BYTES: usize = 64 * 1024;
+
 /// Display mode for a buffer
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ViewMode {
@@ -37,6 +46,8 @@ pub enum ViewMode {
     Source,
     /// Semi-WYSIWYG compose rendering
     Compose,
+    /// Rendered markdown preview (see [`crate::view::markdown_preview`])
+    Preview,
 }
 
 /// The complete editor state - everything needed to represent the current editing session
@@ -101,6 +112,14 @@ pub struct EditorState {
     /// Set based on language config; defaults to true
     pub show_whitespace_tabs: bool,
 
+    /// Rulers and whitespace highlighting drawn over this buffer
+    /// Set based on editor and language config; defaults to none
+    pub guides: VisualGuides,
+
+    /// Whether lines with right-to-left text are shown in visual order
+    /// Set based on editor config; defaults to false
+    pub bidi_text: bool,
+
     /// Whether pressing Tab should insert a tab character instead of spaces.
     /// Set based on language config; defaults to false (insert spaces).
     pub use_tabs: bool,
@@ -109,6 +128,11 @@ pub struct EditorState {
     /// Used for visual display of tab characters and indent calculations.
     pub tab_size: usize,
 
+    /// Indentation detected from the contents (or given by a modeline) when
+    /// the file was opened.
+    /// None if detection is off or the file has too few indented lines.
+    pub detected_indentation: Option<Indentation>,
+
     /// Semantic highlighter for word occurrence highlighting
     pub reference_highlighter: ReferenceHighlighter,
 
@@ -137,6 +161,21 @@ pub struct EditorState {
     /// Bracket matching highlight overlay
     pub bracket_highlight_overlay: BracketHighlightOverlay,
 
+    /// Conceal rules for this buffer's language
+    pub concealer: Concealer,
+
+    /// Spell checking settings for this buffer
+    pub spell: BufferSpellState,
+
+    /// Buffer version and byte range the color swatches were last added for
+    pub color_swatches_shown: Option<(u64, std::ops::Range<usize>)>,
+
+    /// Buffer version and byte range the links were last underlined for
+    pub links_underlined: Option<(u64, std::ops::Range<usize>)>,
+
+    /// Buffer version and the outline last parsed for it (see `outline`)
+    outline_cache: Option<(u64, Vec<OutlineSymbol>)>,
+
     /// Cached LSP semantic tokens (converted to buffer byte ranges)
     pub semantic_tokens: Option<SemanticTokenStore>,
 
@@ -172,8 +211,11 @@ impl EditorState {
             editing_disabled: false,
             is_composite_buffer: false,
             show_whitespace_tabs: true,
+            guides: VisualGuides::default(),
+            bidi_text: false,
             use_tabs: false,
             tab_size: 4, // Default tab size
+            detected_indentation: None,
             reference_highlighter: ReferenceHighlighter::new(),
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
@@ -183,6 +225,11 @@ impl EditorState {
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            concealer: Concealer::new(),
+            spell: BufferSpellState::default(),
+            color_swatches_shown: None,
+            links_underlined: None,
+            outline_cache: None,
             semantic_tokens: None,
             language: "text".to_string(), // Default to plain text
         }
@@ -219,6 +266,38 @@ impl EditorState {
         );
     }
 
+    /// Highlight the buffer with the grammar named `syntax_name`, with
+    /// tree-sitter indentation and reference highlighting when there's a
+    /// tree-sitter grammar for it
+    pub fn set_syntax(&mut self, syntax_name: &str, registry: &GrammarRegistry) {
+        // Tree-sitter covers fewer languages than syntect
+        let ts_language = Language::from_name(syntax_name);
+        self.highlighter = HighlightEngine::for_syntax_name(syntax_name, registry, ts_language);
+        if let Some(language) = ts_language {
+            self.reference_highlighter.set_language(&language);
+        }
+    }
+
+    /// Detect the indentation of the start of the buffer, and indent with
+    /// it: tabs, or spaces of the detected width. Settings are left as they
+    /// are if nothing is detected.
+    pub fn detect_indentation(&mut self) -> Option<Indentation> {
+        let sample = self
+            .buffer
+            .get_text_range_mut(0, INDENT_DETECTION_BYTES)
+            .unwrap_or_default();
+        self.detected_indentation = detect_indentation(&String::from_utf8_lossy(&sample));
+        match self.detected_indentation {
+            Some(Indentation::Tabs) => self.use_tabs = true,
+            Some(Indentation::Spaces(width)) => {
+                self.use_tabs = false;
+                self.tab_size = width;
+            }
+            None => {}
+        }
+        self.detected_indentation
+    }
+
     /// Create an editor state from a file
     ///
     /// Note: width/height parameters are kept for backward compatibility but
@@ -265,8 +344,11 @@ impl EditorState {
             editing_disabled: false,
             is_composite_buffer: false,
             show_whitespace_tabs: true,
+            guides: VisualGuides::default(),
+            bidi_text: false,
             use_tabs: false,
             tab_size: 4,
+            detected_indentation: None,
             reference_highlighter,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
@@ -276,6 +358,11 @@ impl EditorState {
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            concealer: Concealer::new(),
+            spell: BufferSpellState::default(),
+            color_swatches_shown: None,
+            links_underlined: None,
+            outline_cache: None,
             semantic_tokens: None,
             language: language_name,
         })
@@ -333,8 +420,11 @@ impl EditorState {
             editing_disabled: false,
             is_composite_buffer: false,
             show_whitespace_tabs: true,
+            guides: VisualGuides::default(),
+            bidi_text: false,
             use_tabs: false,
             tab_size: 4,
+            detected_indentation: None,
             reference_highlighter,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
@@ -344,6 +434,11 @@ impl EditorState {
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            concealer: Concealer::new(),
+            spell: BufferSpellState::default(),
+            color_swatches_shown: None,
+            links_underlined: None,
+            outline_cache: None,
             semantic_tokens: None,
             language: language_name,
         })
@@ -968,6 +1063,33 @@ impl EditorState {
         }
     }
 
+    /// The definitions in the buffer, parsed again only once it has changed.
+    /// Empty without a tree-sitter grammar and for large files.
+    pub fn outline(&mut self) -> &[OutlineSymbol] {
+        let version = self.buffer.revision();
+        if self.outline_cache.as_ref().map(|(v, _)| *v) != Some(version) {
+            let len = self.buffer.len();
+            let ts_language = self
+                .highlighter
+                .language()
+                .and_then(|language| language.ts_language())
+                .filter(|_| len as u64 <= crate::config::LARGE_FILE_THRESHOLD_BYTES);
+            let symbols = match ts_language {
+                Some(ts_language) => {
+                    let text = self.get_text_range(0, len);
+                    text_objects::parse(&ts_language, &text)
+                        .map(|tree| outline::outline(&text, &tree))
+                        .unwrap_or_default()
+                }
+                None => Vec::new(),
+            };
+            self.outline_cache = Some((version, symbols));
+        }
+        self.outline_cache
+            .as_ref()
+            .map_or(&[], |(_, symbols)| symbols.as_slice())
+    }
+
     /// Get the content of a line by its byte offset
     ///
     /// Returns the line containing the given offset, along with its start position.
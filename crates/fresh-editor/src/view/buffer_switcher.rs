@@ -0,0 +1,159 @@
+//! Buffer switcher rendering
+//!
+//! Renders the buffer switcher as a popup centered near the top of the
+//! screen, listing the buffers most recently used first.
+
+use crate::app::buffer_switcher::BufferSwitcher;
+use crate::view::theme::Theme;
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+/// Narrowest the popup gets
+const MIN_WIDTH: u16 = 40;
+
+/// Layout of the buffer switcher, for mouse hit testing
+#[derive(Debug, Clone, Default)]
+pub struct BufferSwitcherLayout {
+    /// The whole popup
+    pub area: Rect,
+    /// Entry rows shown (entry index, area)
+    pub entries: Vec<(usize, Rect)>,
+}
+
+impl BufferSwitcherLayout {
+    /// Whether a screen position is on the popup
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.area.contains(Position::new(x, y))
+    }
+
+    /// The entry at a screen position, if any
+    pub fn entry_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|(_, area)| area.contains(Position::new(x, y)))
+            .map(|(index, _)| *index)
+    }
+}
+
+/// Render the buffer switcher popup
+pub fn render_buffer_switcher(
+    frame: &mut Frame,
+    area: Rect,
+    switcher: &BufferSwitcher,
+    theme: &Theme,
+) -> BufferSwitcherLayout {
+    let entries = switcher.entries();
+    let content_width = entries
+        .iter()
+        .map(|entry| {
+            let detail = entry.detail.as_deref().map_or(0, |d| d.width() + 2);
+            entry.name.width() + detail + 4
+        })
+        .max()
+        .unwrap_or(0) as u16;
+    let width = content_width
+        .saturating_add(2)
+        .max(MIN_WIDTH)
+        .min(area.width.saturating_sub(4));
+    // Entries, key hints and borders
+    let height = (entries.len() as u16 + 3).min(area.height.saturating_sub(2));
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + 1.min(area.height),
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", t!("buffer_switcher.title")))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.popup_border_fg))
+        .style(Style::default().bg(theme.popup_bg).fg(theme.popup_text_fg));
+    let inner_area = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let list_height = inner_area.height.saturating_sub(1) as usize;
+    let mut layout = BufferSwitcherLayout {
+        area: popup_area,
+        entries: Vec::new(),
+    };
+    if list_height == 0 {
+        return layout;
+    }
+
+    // Keep the highlighted entry in view
+    let scroll = (switcher.selected() + 1).saturating_sub(list_height);
+    let mut lines = Vec::new();
+    for (offset, (index, entry)) in entries
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(list_height)
+        .enumerate()
+    {
+        let style = if index == switcher.selected() {
+            Style::default()
+                .bg(theme.popup_selection_bg)
+                .fg(theme.popup_selection_fg)
+        } else {
+            Style::default()
+        };
+        let marker = if entry.modified { "● " } else { "  " };
+        let mut spans = vec![
+            Span::styled(marker, style.fg(theme.help_key_fg)),
+            Span::raw(entry.name.clone()),
+        ];
+        if let Some(detail) = &entry.detail {
+            spans.push(Span::styled(
+                format!("  {}", detail),
+                style.fg(theme.line_number_fg),
+            ));
+        }
+        lines.push(Line::from(spans).style(style));
+        layout.entries.push((
+            index,
+            Rect {
+                x: inner_area.x,
+                y: inner_area.y + offset as u16,
+                width: inner_area.width,
+                height: 1,
+            },
+        ));
+    }
+    let list_area = Rect {
+        height: list_height as u16,
+        ..inner_area
+    };
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    // Key hints
+    let hint = |key: &'static str, label: String| {
+        [
+            Span::styled(key, Style::default().fg(theme.help_key_fg)),
+            Span::raw(format!(" {}  ", label)),
+        ]
+    };
+    let hints: Vec<Span> = [
+        hint("[Ctrl+W]", t!("buffer_switcher.close").to_string()),
+        hint("[Esc]", t!("buffer_switcher.cancel").to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let hints_area = Rect {
+        y: inner_area.y + list_height as u16,
+        height: 1,
+        ..inner_area
+    };
+    frame.render_widget(Paragraph::new(Line::from(hints)), hints_area);
+
+    layout
+}
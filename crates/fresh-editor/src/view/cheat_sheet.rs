@@ -0,0 +1,180 @@
+//! Keybinding cheat sheet rendering
+//!
+//! Renders the cheat sheet as a full-screen overlay: the search line, the
+//! commands grouped by category, and the keys that drive it.
+
+use crate::app::cheat_sheet::{CheatSheet, CheatSheetRow};
+use crate::view::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+/// Widest the keys column gets
+const MAX_KEYS_WIDTH: usize = 24;
+
+/// Layout of the cheat sheet, for mouse hit testing
+#[derive(Debug, Clone, Default)]
+pub struct CheatSheetLayout {
+    /// The whole overlay
+    pub area: Rect,
+    /// Entry rows shown (entry index, area)
+    pub entries: Vec<(usize, Rect)>,
+}
+
+impl CheatSheetLayout {
+    /// Whether a screen position is on the overlay
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        self.area.contains(Position::new(x, y))
+    }
+
+    /// The entry at a screen position, if any
+    pub fn entry_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|(_, area)| area.contains(Position::new(x, y)))
+            .map(|(index, _)| *index)
+    }
+}
+
+/// Render the cheat sheet overlay
+pub fn render_cheat_sheet(
+    frame: &mut Frame,
+    area: Rect,
+    sheet: &mut CheatSheet,
+    theme: &Theme,
+) -> CheatSheetLayout {
+    let area = Rect {
+        x: area.x + 2.min(area.width / 2),
+        y: area.y + 1.min(area.height / 2),
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" {} ", t!("cheat_sheet.title")))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.popup_border_fg))
+        .style(Style::default().bg(theme.popup_bg).fg(theme.popup_text_fg));
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Layout: search line, list, key hints
+    let chunks = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(1),
+        Constraint::Length(1),
+    ])
+    .split(inner_area);
+    let list_area = chunks[1];
+
+    // Search line
+    let search = Line::from(vec![
+        Span::styled(
+            format!("{} ", t!("cheat_sheet.search")),
+            Style::default().fg(theme.help_key_fg),
+        ),
+        Span::raw(sheet.query.clone()),
+        Span::styled("█", Style::default().fg(theme.cursor)),
+    ]);
+    frame.render_widget(Paragraph::new(search), chunks[0]);
+
+    // Command list
+    sheet.update_scroll(list_area.height as usize);
+    let keys_width = sheet
+        .entries()
+        .iter()
+        .filter_map(|entry| entry.keys.as_deref())
+        .map(UnicodeWidthStr::width)
+        .max()
+        .unwrap_or(0)
+        .min(MAX_KEYS_WIDTH);
+    let rows = sheet.rows();
+    let mut layout = CheatSheetLayout {
+        area,
+        entries: Vec::new(),
+    };
+    let mut lines = Vec::new();
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("cheat_sheet.no_matches").to_string(),
+            Style::default().fg(theme.line_number_fg),
+        )));
+    }
+    for (offset, row) in rows
+        .iter()
+        .skip(sheet.scroll)
+        .take(list_area.height as usize)
+        .enumerate()
+    {
+        match *row {
+            CheatSheetRow::Category(category) => {
+                lines.push(Line::from(Span::styled(
+                    category.to_string(),
+                    Style::default()
+                        .fg(theme.help_key_fg)
+                        .add_modifier(Modifier::BOLD),
+                )));
+            }
+            CheatSheetRow::Entry(index) => {
+                let entry = &sheet.entries()[index];
+                let style = if sheet.selected() == Some(index) {
+                    Style::default()
+                        .bg(theme.popup_selection_bg)
+                        .fg(theme.popup_selection_fg)
+                } else {
+                    Style::default()
+                };
+                let keys = entry.keys.as_deref().unwrap_or("");
+                let padding = keys_width.saturating_sub(keys.width());
+                lines.push(
+                    Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            format!("{}{}", keys, " ".repeat(padding)),
+                            style.fg(theme.help_key_fg),
+                        ),
+                        Span::raw("  "),
+                        Span::raw(entry.name.clone()),
+                    ])
+                    .style(style),
+                );
+                layout.entries.push((
+                    index,
+                    Rect {
+                        x: list_area.x,
+                        y: list_area.y + offset as u16,
+                        width: list_area.width,
+                        height: 1,
+                    },
+                ));
+            }
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), list_area);
+
+    // Key hints
+    let hint = |key: &'static str, label: String| {
+        [
+            Span::styled(key, Style::default().fg(theme.help_key_fg)),
+            Span::raw(format!(" {}  ", label)),
+        ]
+    };
+    let hints: Vec<Span> = [
+        hint("[↑↓]", t!("cheat_sheet.select").to_string()),
+        hint("[Enter]", t!("cheat_sheet.run").to_string()),
+        hint("[Esc]", t!("cheat_sheet.close").to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    frame.render_widget(Paragraph::new(Line::from(hints)), chunks[2]);
+
+    layout
+}
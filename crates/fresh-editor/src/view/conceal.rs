@@ -0,0 +1,427 @@
+//! Concealment: text displayed as something else while the buffer is unchanged
+//!
+//! Conceal rules come from the language config (`languages.<lang>.conceal`).
+//! Each rule matches either a regular expression or a tree-sitter query, and
+//! the matched text is rendered as the rule's replacement (or hidden when the
+//! replacement is empty). For example `**bold**` can be shown without its
+//! asterisks, or `->` as `→`.
+//!
+//! Lines containing a cursor are always shown as written so the markup can be
+//! edited. Concealment is applied to the view token stream (after plugin
+//! transforms, before wrapping), so cursor movement, selections and mouse
+//! clicks keep working on the underlying text.
+
+use crate::config::ConcealRule;
+use crate::primitives::highlighter::Language;
+use fresh_core::api::{ViewTokenWire, ViewTokenWireKind};
+use fresh_languages::tree_sitter::{Parser, Query, QueryCursor, StreamingIterator};
+use regex::bytes::Regex;
+use std::ops::Range;
+
+/// A byte range of the buffer displayed as `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConcealRange {
+    pub range: Range<usize>,
+    pub replacement: String,
+}
+
+enum Matcher {
+    Pattern(Regex),
+    Query(Query),
+}
+
+struct CompiledRule {
+    matcher: Matcher,
+    replacement: String,
+}
+
+/// Per-buffer conceal state: the compiled rules and whether they are applied
+pub struct Concealer {
+    /// Whether concealment is enabled for this buffer
+    pub enabled: bool,
+    rules: Vec<CompiledRule>,
+    /// Parser for query rules (None if the language has no tree-sitter grammar)
+    parser: Option<Parser>,
+}
+
+impl Default for Concealer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Concealer {
+    /// Create a concealer without rules
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            rules: Vec::new(),
+            parser: None,
+        }
+    }
+
+    /// Compile the conceal rules for a buffer's language.
+    ///
+    /// Invalid patterns and queries are logged and skipped, as are query rules
+    /// for languages without a tree-sitter grammar.
+    pub fn set_rules(&mut self, rules: &[ConcealRule], language: Option<&Language>) {
+        let ts_language = language.and_then(|l| l.ts_language());
+        self.rules.clear();
+        self.parser = None;
+
+        for rule in rules {
+            let matcher = match (&rule.pattern, &rule.query) {
+                (Some(pattern), _) => match Regex::new(pattern) {
+                    Ok(regex) => Matcher::Pattern(regex),
+                    Err(e) => {
+                        tracing::warn!("Invalid conceal pattern {:?}: {}", pattern, e);
+                        continue;
+                    }
+                },
+                (None, Some(query)) => {
+                    let Some(ts_language) = &ts_language else {
+                        tracing::warn!(
+                            "Conceal query ignored: language has no tree-sitter grammar"
+                        );
+                        continue;
+                    };
+                    match Query::new(ts_language, query) {
+                        Ok(query) => Matcher::Query(query),
+                        Err(e) => {
+                            tracing::warn!("Invalid conceal query {:?}: {:?}", query, e);
+                            continue;
+                        }
+                    }
+                }
+                (None, None) => continue,
+            };
+            self.rules.push(CompiledRule {
+                matcher,
+                replacement: rule.replacement.clone(),
+            });
+        }
+
+        let has_query = self
+            .rules
+            .iter()
+            .any(|r| matches!(r.matcher, Matcher::Query(_)));
+        if let (true, Some(ts_language)) = (has_query, ts_language) {
+            let mut parser = Parser::new();
+            if parser.set_language(&ts_language).is_ok() {
+                self.parser = Some(parser);
+            }
+        }
+    }
+
+    /// Whether there is anything to conceal
+    pub fn is_active(&self) -> bool {
+        self.enabled && !self.rules.is_empty()
+    }
+
+    /// Find the conceal ranges in `source`, which starts at buffer byte `offset`.
+    ///
+    /// Ranges on lines containing one of `cursor_positions` are left out. The
+    /// result is sorted and non-overlapping; of two overlapping matches the
+    /// one starting first wins.
+    pub fn conceal_ranges(
+        &mut self,
+        source: &[u8],
+        offset: usize,
+        cursor_positions: &[usize],
+    ) -> Vec<ConcealRange> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+
+        let mut ranges = Vec::new();
+        let tree = if self.parser.is_some() {
+            self.parser.as_mut().and_then(|p| p.parse(source, None))
+        } else {
+            None
+        };
+
+        for rule in &self.rules {
+            match &rule.matcher {
+                Matcher::Pattern(regex) => {
+                    for captures in regex.captures_iter(source) {
+                        let groups: Vec<_> = if captures.len() > 1 {
+                            captures.iter().skip(1).flatten().collect()
+                        } else {
+                            captures.get(0).into_iter().collect()
+                        };
+                        for group in groups {
+                            ranges.push(ConcealRange {
+                                range: offset + group.start()..offset + group.end(),
+                                replacement: rule.replacement.clone(),
+                            });
+                        }
+                    }
+                }
+                Matcher::Query(query) => {
+                    let Some(tree) = &tree else { continue };
+                    let mut cursor = QueryCursor::new();
+                    let mut captures = cursor.captures(query, tree.root_node(), source);
+                    while let Some((m, index)) = captures.next() {
+                        let node = m.captures[*index].node;
+                        ranges.push(ConcealRange {
+                            range: offset + node.start_byte()..offset + node.end_byte(),
+                            replacement: rule.replacement.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Reveal lines with a cursor
+        let revealed: Vec<Range<usize>> = cursor_positions
+            .iter()
+            .filter(|&&pos| pos >= offset && pos <= offset + source.len())
+            .map(|&pos| {
+                let rel = pos - offset;
+                let start = source[..rel]
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map_or(0, |i| i + 1);
+                let end = source[rel..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(source.len(), |i| rel + i);
+                offset + start..offset + end
+            })
+            .collect();
+
+        ranges.retain(|c| {
+            !c.range.is_empty()
+                && !revealed
+                    .iter()
+                    .any(|line| c.range.start <= line.end && c.range.end > line.start)
+        });
+        ranges.sort_by_key(|c| c.range.start);
+
+        let mut result: Vec<ConcealRange> = Vec::with_capacity(ranges.len());
+        for conceal in ranges {
+            if result
+                .last()
+                .is_some_and(|last| conceal.range.start < last.range.end)
+            {
+                continue;
+            }
+            result.push(conceal);
+        }
+        result
+    }
+}
+
+/// Replace concealed source text in a view token stream.
+///
+/// The first token of each concealed range is replaced by the replacement
+/// text (mapped to the range start), the rest of the range is dropped.
+/// Newlines are never concealed so the line structure is preserved.
+pub fn apply_conceal_transform(
+    tokens: Vec<ViewTokenWire>,
+    conceals: &[ConcealRange],
+) -> Vec<ViewTokenWire> {
+    if conceals.is_empty() {
+        return tokens;
+    }
+
+    let mut result = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let Some(offset) = token.source_offset else {
+            result.push(token);
+            continue;
+        };
+        let text = match &token.kind {
+            ViewTokenWireKind::Text(text) => text.as_str(),
+            ViewTokenWireKind::Space => " ",
+            _ => {
+                result.push(token);
+                continue;
+            }
+        };
+        let end = offset + text.len();
+
+        let mut index = conceals.partition_point(|c| c.range.end <= offset);
+        if conceals.get(index).is_none_or(|c| c.range.start >= end) {
+            result.push(token);
+            continue;
+        }
+
+        let mut pos = offset;
+        while let Some(conceal) = conceals.get(index).filter(|c| c.range.start < end) {
+            let start = conceal.range.start.max(pos);
+            if !text.is_char_boundary(start - offset)
+                || !text.is_char_boundary(conceal.range.end.min(end) - offset)
+            {
+                break;
+            }
+            if start > pos {
+                result.push(text_token(&text[pos - offset..start - offset], pos));
+            }
+            if conceal.range.start >= offset {
+                result.extend(
+                    conceal
+                        .replacement
+                        .chars()
+                        .map(|ch| text_token(ch.encode_utf8(&mut [0; 4]), conceal.range.start)),
+                );
+            }
+            pos = conceal.range.end.min(end);
+            index += 1;
+        }
+        if pos < end {
+            result.push(text_token(&text[pos - offset..], pos));
+        }
+    }
+    result
+}
+
+fn text_token(text: &str, source_offset: usize) -> ViewTokenWire {
+    let kind = if text == " " {
+        ViewTokenWireKind::Space
+    } else {
+        ViewTokenWireKind::Text(text.to_string())
+    };
+    ViewTokenWire {
+        source_offset: Some(source_offset),
+        kind,
+        style: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(pattern: &str, replacement: &str) -> ConcealRule {
+        ConcealRule {
+            pattern: Some(pattern.to_string()),
+            query: None,
+            replacement: replacement.to_string(),
+        }
+    }
+
+    fn tokens(text: &str) -> Vec<ViewTokenWire> {
+        let mut tokens = Vec::new();
+        for (i, ch) in text.char_indices() {
+            let kind = match ch {
+                '\n' => ViewTokenWireKind::Newline,
+                ' ' => ViewTokenWireKind::Space,
+                _ => ViewTokenWireKind::Text(ch.to_string()),
+            };
+            tokens.push(ViewTokenWire {
+                source_offset: Some(i),
+                kind,
+                style: None,
+            });
+        }
+        tokens
+    }
+
+    fn render(tokens: &[ViewTokenWire]) -> String {
+        tokens
+            .iter()
+            .map(|t| match &t.kind {
+                ViewTokenWireKind::Text(s) => s.clone(),
+                ViewTokenWireKind::Space => " ".to_string(),
+                ViewTokenWireKind::Newline => "\n".to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    fn conceal(text: &str, rules: &[ConcealRule], cursors: &[usize]) -> String {
+        let mut concealer = Concealer::new();
+        concealer.set_rules(rules, None);
+        let ranges = concealer.conceal_ranges(text.as_bytes(), 0, cursors);
+        render(&apply_conceal_transform(tokens(text), &ranges))
+    }
+
+    #[test]
+    fn test_pattern_groups_are_concealed() {
+        let rules = [pattern(r"(\*\*)[^*]+(\*\*)", "")];
+        assert_eq!(conceal("a **bold** b", &rules, &[]), "a bold b");
+    }
+
+    #[test]
+    fn test_pattern_replacement() {
+        let rules = [pattern("->", "→")];
+        assert_eq!(conceal("x -> y", &rules, &[]), "x → y");
+    }
+
+    #[test]
+    fn test_cursor_line_is_revealed() {
+        let rules = [pattern("->", "→")];
+        let text = "a -> b\nc -> d\n";
+        assert_eq!(conceal(text, &rules, &[8]), "a → b\nc -> d\n");
+        assert_eq!(conceal(text, &rules, &[0]), "a -> b\nc → d\n");
+    }
+
+    #[test]
+    fn test_replacement_keeps_source_mapping() {
+        let rules = [pattern("->", "→")];
+        let mut concealer = Concealer::new();
+        concealer.set_rules(&rules, None);
+        let ranges = concealer.conceal_ranges(b"x->y", 0, &[]);
+        assert_eq!(
+            ranges,
+            vec![ConcealRange {
+                range: 1..3,
+                replacement: "→".to_string()
+            }]
+        );
+
+        let base = vec![ViewTokenWire {
+            source_offset: Some(0),
+            kind: ViewTokenWireKind::Text("x->y".to_string()),
+            style: None,
+        }];
+        let offsets: Vec<_> = apply_conceal_transform(base, &ranges)
+            .iter()
+            .map(|t| (render(std::slice::from_ref(t)), t.source_offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                ("x".to_string(), Some(0)),
+                ("→".to_string(), Some(1)),
+                ("y".to_string(), Some(3))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_overlapping_rules_first_wins() {
+        let rules = [pattern("<->", "↔"), pattern("->", "→")];
+        assert_eq!(conceal("a <-> b -> c", &rules, &[]), "a ↔ b → c");
+    }
+
+    #[test]
+    fn test_disabled_and_invalid_rules() {
+        let mut concealer = Concealer::new();
+        concealer.set_rules(&[pattern("(", "x")], None);
+        assert!(!concealer.is_active());
+
+        concealer.set_rules(&[pattern("a", "b")], None);
+        assert!(concealer.is_active());
+        concealer.enabled = false;
+        assert!(concealer.conceal_ranges(b"aaa", 0, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_query_rule() {
+        let rule = ConcealRule {
+            pattern: None,
+            query: Some("(atx_h1_marker) @conceal".to_string()),
+            replacement: "§".to_string(),
+        };
+        let text = "# Title\n\ntext\n";
+        let mut concealer = Concealer::new();
+        concealer.set_rules(&[rule], Some(&Language::Markdown));
+        let ranges = concealer.conceal_ranges(text.as_bytes(), 0, &[]);
+        assert_eq!(
+            render(&apply_conceal_transform(tokens(text), &ranges)),
+            "§ Title\n\ntext\n"
+        );
+    }
+}
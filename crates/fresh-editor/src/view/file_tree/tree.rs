@@ -21,6 +21,8 @@ pub struct FileTree {
     path_to_node: HashMap<PathBuf, NodeId>,
     /// Root node ID
     root_id: NodeId,
+    /// Roots shown after the main one, for the other workspace folders
+    extra_roots: Vec<NodeId>,
     /// Next node ID to assign
     next_id: usize,
     /// Filesystem manager for async operations
@@ -67,6 +69,7 @@ impl FileTree {
             nodes,
             path_to_node,
             root_id,
+            extra_roots: Vec::new(),
             next_id: 1,
             fs_manager,
         })
@@ -82,6 +85,56 @@ impl FileTree {
         &self.root_path
     }
 
+    /// Get the IDs of all roots: the main one, then the others in the order
+    /// they were added
+    pub fn root_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::once(self.root_id).chain(self.extra_roots.iter().copied())
+    }
+
+    /// Check if a node is one of the roots
+    pub fn is_root(&self, id: NodeId) -> bool {
+        self.root_ids().any(|root_id| root_id == id)
+    }
+
+    /// Add a directory as another root, shown after the existing ones.
+    /// Returns its node ID, which is the existing one if it's already a root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path doesn't exist or isn't a directory.
+    pub async fn add_root(&mut self, path: PathBuf) -> io::Result<NodeId> {
+        if let Some(id) = self.root_ids().find(|&id| {
+            self.get_node(id)
+                .is_some_and(|node| node.entry.path == path)
+        }) {
+            return Ok(id);
+        }
+        if !self.fs_manager.is_dir(&path).await? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Path is not a directory: {:?}", path),
+            ));
+        }
+        let entry = self.fs_manager.get_entry(&path).await?;
+        let id = self.add_node(entry, None);
+        self.extra_roots.push(id);
+        Ok(id)
+    }
+
+    /// Remove a root added with `add_root`. Returns whether there was one
+    /// for the path.
+    pub fn remove_root(&mut self, path: &Path) -> bool {
+        let Some(index) = self.extra_roots.iter().position(|&id| {
+            self.get_node(id)
+                .is_some_and(|node| node.entry.path == path)
+        }) else {
+            return false;
+        };
+        let id = self.extra_roots.remove(index);
+        self.remove_node_recursive(id);
+        true
+    }
+
     /// Get a node by ID
     pub fn get_node(&self, id: NodeId) -> Option<&TreeNode> {
         self.nodes.get(&id)
@@ -233,7 +286,9 @@ impl FileTree {
     /// the expansion state of parent directories.
     pub fn get_visible_nodes(&self) -> Vec<NodeId> {
         let mut visible = Vec::new();
-        self.collect_visible_recursive(self.root_id, &mut visible);
+        for root_id in self.root_ids() {
+            self.collect_visible_recursive(root_id, &mut visible);
+        }
         visible
     }
 
@@ -324,7 +379,7 @@ impl FileTree {
     /// # Returns
     ///
     /// Returns the NodeId of the target if found, or None if:
-    /// - The path is not under the root directory
+    /// - The path is not under one of the root directories
     /// - The path doesn't exist
     /// - There was an error expanding intermediate directories
     ///
@@ -338,11 +393,14 @@ impl FileTree {
     /// }
     /// ```
     pub async fn expand_to_path(&mut self, path: &Path) -> Option<NodeId> {
-        // Check if path is under root
-        let relative_path = path.strip_prefix(&self.root_path).ok()?;
-
-        // Start from root
-        let mut current_id = self.root_id;
+        // Start from the innermost root the path is under
+        let (mut current_id, relative_path) = self
+            .root_ids()
+            .filter_map(|id| {
+                let root_path = &self.get_node(id)?.entry.path;
+                Some((id, path.strip_prefix(root_path).ok()?.to_path_buf()))
+            })
+            .min_by_key(|(_, relative_path)| relative_path.components().count())?;
 
         // Walk through each component of the path
         for component in relative_path.components() {
@@ -627,6 +685,37 @@ mod tests {
         assert_eq!(dir1.children.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_extra_roots() {
+        let (_temp_dir, mut tree) = create_test_tree().await;
+        let other_dir = TempDir::new().unwrap();
+        std_fs::create_dir(other_dir.path().join("src")).unwrap();
+        std_fs::write(other_dir.path().join("src/lib.rs"), "").unwrap();
+
+        let other_id = tree.add_root(other_dir.path().to_path_buf()).await.unwrap();
+        assert!(tree.is_root(other_id));
+        assert_eq!(tree.get_depth(other_id), 0);
+        // Adding it again returns the same root
+        assert_eq!(
+            tree.add_root(other_dir.path().to_path_buf()).await.unwrap(),
+            other_id
+        );
+        assert_eq!(tree.get_visible_nodes(), vec![tree.root_id(), other_id]);
+
+        // Paths under the other root are revealed in it
+        let lib_id = tree
+            .expand_to_path(&other_dir.path().join("src/lib.rs"))
+            .await
+            .unwrap();
+        assert_eq!(tree.get_ancestors(lib_id)[0], other_id);
+        assert_eq!(tree.get_visible_nodes().len(), 4);
+
+        assert!(tree.remove_root(other_dir.path()));
+        assert!(!tree.remove_root(other_dir.path()));
+        assert_eq!(tree.get_visible_nodes(), vec![tree.root_id()]);
+        assert_eq!(tree.node_count(), 1);
+    }
+
     #[tokio::test]
     async fn test_find_by_relative_path() {
         let (_temp_dir, mut tree) = create_test_tree().await;
@@ -1,3 +1,4 @@
+use crate::config::LineNumberMode;
 use crate::model::marker::{MarkerId, MarkerList};
 use ratatui::style::{Color, Style};
 use std::collections::BTreeMap;
@@ -145,6 +146,9 @@ pub struct MarginConfig {
 
     /// Default separator style
     pub separator_style: Style,
+
+    /// How line numbers are counted (absolute, or relative to the cursor line)
+    pub line_number_mode: LineNumberMode,
 }
 
 impl MarginConfig {
@@ -158,6 +162,7 @@ impl MarginConfig {
             separator: " │ ".to_string(), // Separator with spaces: " │ " (space before for indicators, space after for readability)
             style: Style::default().fg(Color::DarkGray),
             separator_style: Style::default().fg(Color::DarkGray),
+            line_number_mode: LineNumberMode::Absolute,
         }
     }
 
@@ -171,6 +176,7 @@ impl MarginConfig {
             separator: String::new(),
             style: Style::default(),
             separator_style: Style::default(),
+            line_number_mode: LineNumberMode::Absolute,
         }
     }
 
@@ -189,6 +195,25 @@ impl MarginConfig {
             0
         }
     }
+
+    /// The number shown for `line` when the primary cursor is on `cursor_line`
+    /// (both 0-indexed)
+    ///
+    /// Only depends on the two lines, so moving the cursor needs no state
+    /// beyond the cursor line itself.
+    pub fn line_number_label(&self, line: usize, cursor_line: usize) -> usize {
+        match self.line_number_mode {
+            LineNumberMode::Absolute => line + 1,
+            LineNumberMode::Hybrid if line == cursor_line => line + 1,
+            LineNumberMode::Relative | LineNumberMode::Hybrid => line.abs_diff(cursor_line),
+        }
+    }
+
+    /// Whether `line` is the cursor line of a relative numbering, which is
+    /// drawn brighter than the other numbers
+    pub fn highlights_cursor_line(&self, line: usize, cursor_line: usize) -> bool {
+        self.line_number_mode != LineNumberMode::Absolute && line == cursor_line
+    }
 }
 
 /// A margin annotation for a specific line
@@ -572,11 +597,13 @@ impl MarginManager {
 
     /// Get the content to render for a specific line in a margin
     /// If show_line_numbers is true and position is Left, includes line number
+    /// counted according to the left margin's line number mode
     pub fn render_line(
         &self,
         line: usize,
         position: MarginPosition,
         _buffer_total_lines: usize,
+        cursor_line: usize,
     ) -> MarginContent {
         let annotations = match position {
             MarginPosition::Left => &self.left_annotations,
@@ -588,7 +615,10 @@ impl MarginManager {
 
         // For left margin, combine with line numbers if enabled
         if position == MarginPosition::Left && self.show_line_numbers {
-            let line_num = MarginContent::text(format!("{}", line + 1));
+            let line_num = MarginContent::text(format!(
+                "{}",
+                self.left_config.line_number_label(line, cursor_line)
+            ));
 
             if user_annotations.is_empty() {
                 return line_num;
@@ -643,6 +673,11 @@ impl MarginManager {
         }
     }
 
+    /// Set how the left margin counts line numbers
+    pub fn set_line_number_mode(&mut self, mode: LineNumberMode) {
+        self.left_config.line_number_mode = mode;
+    }
+
     /// Get the number of annotations in a position
     pub fn annotation_count(&self, position: MarginPosition) -> usize {
         match position {
@@ -739,7 +774,7 @@ mod tests {
         manager.show_line_numbers = true;
 
         // Without annotations, should render line number
-        let content = manager.render_line(5, MarginPosition::Left, 100);
+        let content = manager.render_line(5, MarginPosition::Left, 100, 0);
         let (rendered, _) = content.render(4);
         assert!(rendered.contains("6")); // Line 5 is displayed as "6" (1-indexed)
 
@@ -747,10 +782,35 @@ mod tests {
         manager.add_annotation(MarginAnnotation::breakpoint(5));
 
         // Should now render stacked content (line number + breakpoint)
-        let content = manager.render_line(5, MarginPosition::Left, 100);
+        let content = manager.render_line(5, MarginPosition::Left, 100, 0);
         assert!(matches!(content, MarginContent::Stacked(_)));
     }
 
+    #[test]
+    fn test_line_number_modes() {
+        let mut manager = MarginManager::new();
+        let label = |manager: &MarginManager, line| {
+            manager
+                .render_line(line, MarginPosition::Left, 100, 5)
+                .render(4)
+                .0
+        };
+
+        assert_eq!(label(&manager, 2), "   3");
+        assert_eq!(label(&manager, 5), "   6");
+
+        manager.set_line_number_mode(LineNumberMode::Relative);
+        assert_eq!(label(&manager, 2), "   3");
+        assert_eq!(label(&manager, 5), "   0");
+        assert_eq!(label(&manager, 7), "   2");
+
+        manager.set_line_number_mode(LineNumberMode::Hybrid);
+        assert_eq!(label(&manager, 5), "   6");
+        assert_eq!(label(&manager, 7), "   2");
+        assert!(manager.left_config.highlights_cursor_line(5, 5));
+        assert!(!manager.left_config.highlights_cursor_line(7, 5));
+    }
+
     #[test]
     fn test_margin_manager_update_width() {
         let mut manager = MarginManager::new();
@@ -778,7 +838,7 @@ mod tests {
         let manager = MarginManager::without_line_numbers();
         assert!(!manager.show_line_numbers);
 
-        let content = manager.render_line(5, MarginPosition::Left, 100);
+        let content = manager.render_line(5, MarginPosition::Left, 100, 0);
         assert!(content.is_empty());
     }
 
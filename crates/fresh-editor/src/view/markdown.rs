@@ -285,7 +285,7 @@ impl Default for StyledLine {
 }
 
 /// Convert highlight spans to styled lines for code blocks
-fn highlight_code_to_styled_lines(
+pub fn highlight_code_to_styled_lines(
     code: &str,
     spans: &[HighlightSpan],
     theme: &crate::view::theme::Theme,
@@ -0,0 +1,858 @@
+//! Rendered preview of markdown buffers
+//!
+//! Lays out a whole markdown document as styled lines for the preview view
+//! mode. Unlike [`parse_markdown`](super::markdown::parse_markdown), which
+//! formats hover documentation, this keeps the document structure: heading
+//! rules, list bullets and numbers, task checkboxes, tables, block quotes and
+//! syntax-highlighted code blocks.
+//!
+//! Every preview line records the source line it was rendered from, so the
+//! preview can scroll along with the cursor in the source.
+
+use super::markdown::{highlight_code_to_styled_lines, wrap_styled_lines, StyledLine};
+use crate::primitives::grammar::GrammarRegistry;
+use crate::primitives::highlight_engine::highlight_string;
+use crate::view::theme::Theme;
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use ratatui::style::{Color, Modifier, Style};
+use unicode_width::UnicodeWidthStr;
+
+/// Narrowest width the preview lays out for
+const MIN_WIDTH: usize = 20;
+
+/// Bullets for unordered lists, by nesting depth
+const BULLETS: [&str; 3] = ["• ", "◦ ", "▪ "];
+
+/// A line of the rendered preview
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewLine {
+    pub line: StyledLine,
+    /// Source line (0-indexed) the line was rendered from
+    pub source_line: usize,
+}
+
+/// Render a markdown document into preview lines at most `width` columns wide.
+///
+/// If `registry` is provided, fenced code blocks are syntax highlighted.
+pub fn render_markdown_preview(
+    text: &str,
+    width: usize,
+    theme: &Theme,
+    registry: Option<&GrammarRegistry>,
+) -> Vec<PreviewLine> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let mut renderer = PreviewRenderer::new(text, width.max(MIN_WIDTH), theme, registry);
+    for (event, range) in Parser::new_ext(text, options).into_offset_iter() {
+        renderer.event(event, range.start);
+    }
+    renderer.finish()
+}
+
+/// Index of the first preview line rendered from the block containing
+/// `source_line` (or from the closest block before it)
+pub fn preview_line_for_source(lines: &[PreviewLine], source_line: usize) -> usize {
+    let end = lines.partition_point(|line| line.source_line <= source_line);
+    let Some(last) = end.checked_sub(1) else {
+        return 0;
+    };
+    let block_line = lines[last].source_line;
+    lines[..end].partition_point(|line| line.source_line < block_line)
+}
+
+/// Per-split preview state: the rendered document and its scroll position
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownPreview {
+    /// First preview line shown
+    pub top: usize,
+    lines: Vec<PreviewLine>,
+    /// Buffer version, width and theme the lines were rendered for
+    rendered_for: Option<(u64, usize, String)>,
+    /// Cursor line and viewport top line the scroll position last followed
+    followed: Option<(usize, usize)>,
+}
+
+impl MarkdownPreview {
+    /// The rendered lines
+    pub fn lines(&self) -> &[PreviewLine] {
+        &self.lines
+    }
+
+    /// Re-render the document if the buffer, width or theme changed
+    pub fn update(
+        &mut self,
+        version: u64,
+        width: usize,
+        theme: &Theme,
+        render: impl FnOnce() -> Vec<PreviewLine>,
+    ) {
+        let key = (version, width, theme.name.clone());
+        if self.rendered_for.as_ref() != Some(&key) {
+            self.lines = render();
+            self.rendered_for = Some(key);
+        }
+    }
+
+    /// Scroll to follow the source.
+    ///
+    /// When the cursor moved, the block under it is kept in view. When only
+    /// the source viewport scrolled (e.g. with the mouse wheel), the preview
+    /// scrolls to the block at its top.
+    pub fn follow(&mut self, cursor_line: usize, viewport_top_line: usize, height: usize) {
+        let previous = self.followed.replace((cursor_line, viewport_top_line));
+        match previous {
+            Some((cursor, top)) if cursor == cursor_line => {
+                if top != viewport_top_line {
+                    self.top = preview_line_for_source(&self.lines, viewport_top_line);
+                }
+            }
+            _ => {
+                let target = preview_line_for_source(&self.lines, cursor_line);
+                if target < self.top || target >= self.top + height {
+                    self.top = target.saturating_sub(height / 3);
+                }
+            }
+        }
+        self.top = self.top.min(self.lines.len().saturating_sub(height));
+    }
+}
+
+/// A fenced or indented code block being collected
+struct CodeBlock {
+    lang: String,
+    text: String,
+    source_line: usize,
+}
+
+/// A table being collected
+struct Table {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<StyledLine>>,
+    source_lines: Vec<usize>,
+    header_rows: usize,
+}
+
+/// Event-driven layout state
+struct PreviewRenderer<'a> {
+    theme: &'a Theme,
+    registry: Option<&'a GrammarRegistry>,
+    width: usize,
+    line_starts: Vec<usize>,
+    lines: Vec<PreviewLine>,
+    /// Inline content of the paragraph, heading or list item being built
+    inline: StyledLine,
+    /// Source line of `inline`
+    inline_source: usize,
+    style_stack: Vec<Style>,
+    link_url: Option<String>,
+    quote_depth: usize,
+    /// Open lists: the next number for ordered lists
+    lists: Vec<Option<u64>>,
+    /// Marker widths of the open list items
+    items: Vec<usize>,
+    /// Marker of the current list item, until its first line is written
+    pending_marker: Option<String>,
+    code: Option<CodeBlock>,
+    table: Option<Table>,
+    /// Whether a blank line separates the next block from the previous one
+    needs_gap: bool,
+}
+
+impl<'a> PreviewRenderer<'a> {
+    fn new(
+        text: &str,
+        width: usize,
+        theme: &'a Theme,
+        registry: Option<&'a GrammarRegistry>,
+    ) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            theme,
+            registry,
+            width,
+            line_starts,
+            lines: Vec::new(),
+            inline: StyledLine::new(),
+            inline_source: 0,
+            style_stack: vec![Style::default()],
+            link_url: None,
+            quote_depth: 0,
+            lists: Vec::new(),
+            items: Vec::new(),
+            pending_marker: None,
+            code: None,
+            table: None,
+            needs_gap: false,
+        }
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1)
+    }
+
+    fn style(&self) -> Style {
+        *self.style_stack.last().unwrap_or(&Style::default())
+    }
+
+    fn push_style(&mut self, f: impl FnOnce(Style) -> Style) {
+        let style = f(self.style());
+        self.style_stack.push(style);
+    }
+
+    fn pop_style(&mut self) {
+        if self.style_stack.len() > 1 {
+            self.style_stack.pop();
+        }
+    }
+
+    fn event(&mut self, event: Event<'_>, offset: usize) {
+        match event {
+            Event::Start(tag) => self.start(tag, offset),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.text.push_str(&text);
+                } else {
+                    self.push_inline(&text, self.style(), offset);
+                }
+            }
+            Event::Code(code) => {
+                let style = Style::default()
+                    .fg(self.theme.help_key_fg)
+                    .bg(self.theme.inline_code_bg);
+                self.push_inline(&code, style, offset);
+            }
+            Event::InlineHtml(html) => {
+                let style = Style::default().fg(self.theme.syntax_comment);
+                self.push_inline(&html, style, offset);
+            }
+            Event::Html(html) => {
+                let style = Style::default().fg(self.theme.syntax_comment);
+                let line = self.line_of(offset);
+                for (i, part) in html.trim_end_matches('\n').split('\n').enumerate() {
+                    self.push_inline(part, style, offset);
+                    self.inline_source = line + i;
+                    self.flush_inline();
+                }
+            }
+            Event::FootnoteReference(label) => {
+                self.push_inline(&format!("[^{label}]"), self.style(), offset);
+            }
+            Event::SoftBreak => self.push_inline(" ", self.style(), offset),
+            Event::HardBreak => self.flush_inline(),
+            Event::Rule => {
+                self.start_block();
+                let width = self.width.saturating_sub(self.prefix_width());
+                let rule = styled_line(
+                    "─".repeat(width),
+                    Style::default().fg(self.theme.line_number_fg),
+                );
+                self.push_line(rule, self.line_of(offset), true);
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => {
+                let (mark, color) = if checked {
+                    ("☑ ", self.theme.syntax_string)
+                } else {
+                    ("☐ ", self.theme.line_number_fg)
+                };
+                self.push_inline(mark, Style::default().fg(color), offset);
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>, offset: usize) {
+        match tag {
+            Tag::Paragraph => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                let theme = self.theme;
+                self.push_style(|style| {
+                    let color = match level {
+                        HeadingLevel::H1 | HeadingLevel::H2 => theme.syntax_keyword,
+                        _ => theme.syntax_function,
+                    };
+                    style.fg(color).add_modifier(Modifier::BOLD)
+                });
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.quote_depth += 1;
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let line = self.line_of(offset);
+                let (lang, source_line) = match kind {
+                    // Content starts on the line after the opening fence
+                    CodeBlockKind::Fenced(info) => (code_block_lang(&info), line + 1),
+                    CodeBlockKind::Indented => (String::new(), line),
+                };
+                self.code = Some(CodeBlock {
+                    lang,
+                    text: String::new(),
+                    source_line,
+                });
+            }
+            Tag::HtmlBlock => self.start_block(),
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    // Text of the enclosing item goes before the nested list
+                    self.flush_inline();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush_inline();
+                let depth = self.lists.len().saturating_sub(1);
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    _ => BULLETS[depth % BULLETS.len()].to_string(),
+                };
+                self.items.push(marker.width());
+                self.pending_marker = Some(marker);
+                self.inline_source = self.line_of(offset);
+            }
+            Tag::Table(alignments) => {
+                self.start_block();
+                self.table = Some(Table {
+                    alignments,
+                    rows: Vec::new(),
+                    source_lines: Vec::new(),
+                    header_rows: 0,
+                });
+            }
+            Tag::TableHead | Tag::TableRow => {
+                let line = self.line_of(offset);
+                if let Some(table) = &mut self.table {
+                    table.rows.push(Vec::new());
+                    table.source_lines.push(line);
+                }
+            }
+            Tag::TableCell => self.inline = StyledLine::new(),
+            Tag::Emphasis => self.push_style(|style| style.add_modifier(Modifier::ITALIC)),
+            Tag::Strong => self.push_style(|style| style.add_modifier(Modifier::BOLD)),
+            Tag::Strikethrough => {
+                self.push_style(|style| style.add_modifier(Modifier::CROSSED_OUT))
+            }
+            Tag::Link { dest_url, .. } => {
+                self.push_style(|style| style.add_modifier(Modifier::UNDERLINED).fg(Color::Cyan));
+                self.link_url = Some(dest_url.to_string());
+            }
+            Tag::Image { .. } => {
+                self.push_style(|style| style.add_modifier(Modifier::ITALIC).fg(Color::Cyan));
+                self.push_inline("[", self.style(), offset);
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock => self.end_block(),
+            TagEnd::Heading(level) => {
+                self.pop_style();
+                let source_line = self.inline_source;
+                let width = self.inline_width();
+                self.flush_inline();
+                let rule = match level {
+                    HeadingLevel::H1 => Some("═"),
+                    HeadingLevel::H2 => Some("─"),
+                    _ => None,
+                };
+                if let Some(rule) = rule {
+                    let width = width.min(self.width.saturating_sub(self.prefix_width()));
+                    let line = styled_line(
+                        rule.repeat(width),
+                        Style::default().fg(self.theme.syntax_keyword),
+                    );
+                    self.push_line(line, source_line, true);
+                }
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) => {
+                self.flush_inline();
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.needs_gap = true;
+            }
+            TagEnd::CodeBlock => {
+                if let Some(code) = self.code.take() {
+                    self.render_code_block(code);
+                }
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.flush_inline();
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.needs_gap = true;
+                }
+            }
+            TagEnd::Item => {
+                self.flush_inline();
+                self.items.pop();
+                self.pending_marker = None;
+            }
+            TagEnd::TableCell => {
+                let cell = std::mem::take(&mut self.inline);
+                if let Some(row) = self.table.as_mut().and_then(|table| table.rows.last_mut()) {
+                    row.push(cell);
+                }
+            }
+            TagEnd::TableHead => {
+                if let Some(table) = &mut self.table {
+                    table.header_rows = table.rows.len();
+                }
+            }
+            TagEnd::Table => {
+                if let Some(table) = self.table.take() {
+                    self.render_table(table);
+                }
+                self.end_block();
+            }
+            TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough => self.pop_style(),
+            TagEnd::Link => {
+                self.pop_style();
+                self.link_url = None;
+            }
+            TagEnd::Image => {
+                self.push_inline("]", self.style(), 0);
+                self.pop_style();
+            }
+            _ => {}
+        }
+    }
+
+    fn push_inline(&mut self, text: &str, style: Style, offset: usize) {
+        if text.is_empty() {
+            return;
+        }
+        if self.inline.spans.is_empty() && self.pending_marker.is_none() && self.table.is_none() {
+            self.inline_source = self.line_of(offset);
+        }
+        self.inline
+            .push_with_link(text.to_string(), style, self.link_url.clone());
+    }
+
+    fn inline_width(&self) -> usize {
+        self.inline.plain_text().width()
+    }
+
+    /// Prefix for a line: block quote bars, then list indentation (with the
+    /// pending item marker on the first line of an item)
+    fn prefix(&mut self, first: bool) -> StyledLine {
+        let mut prefix = StyledLine::new();
+        for _ in 0..self.quote_depth {
+            prefix.push(
+                "▎ ".to_string(),
+                Style::default().fg(self.theme.line_number_fg),
+            );
+        }
+        let indent: usize = self.items.iter().sum();
+        let marker = if first {
+            self.pending_marker.take()
+        } else {
+            None
+        };
+        match marker {
+            Some(marker) => {
+                let marker_width = marker.width();
+                if indent > marker_width {
+                    prefix.push(" ".repeat(indent - marker_width), Style::default());
+                }
+                prefix.push(marker, Style::default().fg(self.theme.syntax_keyword));
+            }
+            None if indent > 0 => prefix.push(" ".repeat(indent), Style::default()),
+            None => {}
+        }
+        prefix
+    }
+
+    fn prefix_width(&self) -> usize {
+        self.quote_depth * 2 + self.items.iter().sum::<usize>()
+    }
+
+    /// Add a line, prefixed for the enclosing block quotes and list items
+    fn push_line(&mut self, line: StyledLine, source_line: usize, first: bool) {
+        let mut full = self.prefix(first);
+        full.spans.extend(line.spans);
+        self.lines.push(PreviewLine {
+            line: full,
+            source_line,
+        });
+    }
+
+    /// Write out the pending inline content, word-wrapped to the width
+    fn flush_inline(&mut self) {
+        if self.table.is_some() {
+            return;
+        }
+        if self.inline.spans.is_empty() && self.pending_marker.is_none() {
+            return;
+        }
+        let inline = std::mem::take(&mut self.inline);
+        let source_line = self.inline_source;
+        let available = self.width.saturating_sub(self.prefix_width()).max(1);
+        let mut wrapped = wrap_styled_lines(&[inline], available);
+        if wrapped.is_empty() {
+            wrapped.push(StyledLine::new());
+        }
+        for (i, line) in wrapped.into_iter().enumerate() {
+            let line = if i > 0 { trim_start(line) } else { line };
+            self.push_line(line, source_line, i == 0);
+        }
+    }
+
+    /// Begin a block, separating it from the previous one by a blank line.
+    ///
+    /// A pending list marker is kept, so it goes on the block's first line.
+    fn start_block(&mut self) {
+        if !self.inline.spans.is_empty() {
+            self.flush_inline();
+        }
+        if self.needs_gap && !self.lines.is_empty() {
+            let source_line = self.lines.last().map_or(0, |line| line.source_line);
+            let mut gap = StyledLine::new();
+            for _ in 0..self.quote_depth {
+                gap.push(
+                    "▎ ".to_string(),
+                    Style::default().fg(self.theme.line_number_fg),
+                );
+            }
+            self.lines.push(PreviewLine {
+                line: gap,
+                source_line,
+            });
+        }
+        self.needs_gap = false;
+    }
+
+    fn end_block(&mut self) {
+        self.flush_inline();
+        self.needs_gap = true;
+    }
+
+    fn render_code_block(&mut self, code: CodeBlock) {
+        let text = code.text.strip_suffix('\n').unwrap_or(&code.text);
+        let spans = match self.registry {
+            Some(registry) if !code.lang.is_empty() => {
+                highlight_string(text, &code.lang, registry, self.theme)
+            }
+            _ => Vec::new(),
+        };
+        let code_style = Style::default()
+            .fg(self.theme.help_key_fg)
+            .bg(self.theme.inline_code_bg);
+        let code_lines = if spans.is_empty() {
+            text.split('\n')
+                .map(|line| styled_line(line.to_string(), code_style))
+                .collect()
+        } else {
+            highlight_code_to_styled_lines(text, &spans, self.theme)
+        };
+
+        // Code is not wrapped: lines are padded (or cut) to the block width
+        let width = self.width.saturating_sub(self.prefix_width());
+        let pad = Style::default().bg(self.theme.inline_code_bg);
+        for (i, line) in code_lines.into_iter().enumerate() {
+            let mut padded = styled_line(" ".to_string(), pad);
+            padded.spans.extend(line.spans);
+            let mut padded = truncate(padded, width);
+            let used = padded.plain_text().width();
+            if used < width {
+                padded.push(" ".repeat(width - used), pad);
+            }
+            self.push_line(padded, code.source_line + i, true);
+        }
+    }
+
+    fn render_table(&mut self, table: Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return;
+        }
+        let mut widths = vec![1; columns];
+        for row in &table.rows {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(cell.plain_text().width());
+            }
+        }
+
+        // Shrink the widest columns until the table fits
+        let separators = 3 * (columns - 1);
+        let available = self
+            .width
+            .saturating_sub(self.prefix_width() + separators)
+            .max(columns);
+        while widths.iter().sum::<usize>() > available {
+            let Some(widest) = (0..columns).max_by_key(|&column| widths[column]) else {
+                break;
+            };
+            if widths[widest] <= 1 {
+                break;
+            }
+            widths[widest] -= 1;
+        }
+
+        let border = Style::default().fg(self.theme.line_number_fg);
+        for (row_index, row) in table.rows.into_iter().enumerate() {
+            let is_header = row_index < table.header_rows;
+            let mut line = StyledLine::new();
+            for (column, &width) in widths.iter().enumerate() {
+                if column > 0 {
+                    line.push(" │ ".to_string(), border);
+                }
+                let mut cell = row.get(column).cloned().unwrap_or_default();
+                if is_header {
+                    for span in &mut cell.spans {
+                        span.style = span.style.add_modifier(Modifier::BOLD);
+                    }
+                }
+                let cell = truncate(cell, width);
+                let fill = width.saturating_sub(cell.plain_text().width());
+                let (left, right) = match table.alignments.get(column) {
+                    Some(Alignment::Right) => (fill, 0),
+                    Some(Alignment::Center) => (fill / 2, fill - fill / 2),
+                    _ => (0, fill),
+                };
+                if left > 0 {
+                    line.push(" ".repeat(left), Style::default());
+                }
+                line.spans.extend(cell.spans);
+                if right > 0 {
+                    line.push(" ".repeat(right), Style::default());
+                }
+            }
+            let source_line = table.source_lines.get(row_index).copied().unwrap_or(0);
+            self.push_line(line, source_line, true);
+
+            if row_index + 1 == table.header_rows {
+                let rule = widths
+                    .iter()
+                    .map(|&width| "─".repeat(width))
+                    .collect::<Vec<_>>()
+                    .join("─┼─");
+                let line = styled_line(rule, border);
+                self.push_line(line, source_line, true);
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<PreviewLine> {
+        self.flush_inline();
+        while self
+            .lines
+            .last()
+            .is_some_and(|line| line.line.plain_text().trim().is_empty())
+        {
+            self.lines.pop();
+        }
+        self.lines
+    }
+}
+
+/// A line with a single span
+fn styled_line(text: String, style: Style) -> StyledLine {
+    let mut line = StyledLine::new();
+    line.push(text, style);
+    line
+}
+
+/// Language of a fenced code block from its info string ("rust,ignore" -> "rust")
+fn code_block_lang(info: &str) -> String {
+    info.split([',', ' ', '{'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Drop leading spaces left at the start of a wrapped continuation line
+fn trim_start(mut line: StyledLine) -> StyledLine {
+    while let Some(first) = line.spans.first_mut() {
+        let trimmed = first.text.trim_start_matches(' ');
+        if trimmed.is_empty() {
+            line.spans.remove(0);
+        } else {
+            first.text = trimmed.to_string();
+            break;
+        }
+    }
+    line
+}
+
+/// Cut a styled line to `width` columns, ending with "…" if it was cut
+fn truncate(line: StyledLine, width: usize) -> StyledLine {
+    if line.plain_text().width() <= width {
+        return line;
+    }
+    let mut result = StyledLine::new();
+    let mut used = 0;
+    let limit = width.saturating_sub(1);
+    let mut last_style = Style::default();
+    'spans: for span in line.spans {
+        let mut text = String::new();
+        for ch in span.text.chars() {
+            let ch_width = unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0);
+            if used + ch_width > limit {
+                if !text.is_empty() {
+                    result.push_with_link(text, span.style, span.link_url.clone());
+                }
+                last_style = span.style;
+                break 'spans;
+            }
+            used += ch_width;
+            text.push(ch);
+        }
+        last_style = span.style;
+        result.push_with_link(text, span.style, span.link_url);
+    }
+    if width > 0 {
+        result.push("…".to_string(), last_style);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::theme;
+
+    fn render(text: &str, width: usize) -> Vec<PreviewLine> {
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        render_markdown_preview(text, width, &theme, None)
+    }
+
+    fn texts(lines: &[PreviewLine]) -> Vec<String> {
+        lines.iter().map(|line| line.line.plain_text()).collect()
+    }
+
+    #[test]
+    fn test_headings_and_paragraphs() {
+        let lines = render("# Title\n\nSome *styled* **text**.\n\n## Part\n", 40);
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "Title",
+                "═════",
+                "",
+                "Some styled text.",
+                "",
+                "Part",
+                "────"
+            ]
+        );
+        let title = &lines[0].line.spans[0];
+        assert!(title.style.add_modifier.contains(Modifier::BOLD));
+        let styled = lines[3]
+            .line
+            .spans
+            .iter()
+            .find(|span| span.text == "styled")
+            .unwrap();
+        assert!(styled.style.add_modifier.contains(Modifier::ITALIC));
+        assert_eq!(lines[3].source_line, 2);
+        assert_eq!(lines[5].source_line, 4);
+    }
+
+    #[test]
+    fn test_lists_and_task_items() {
+        let lines = render(
+            "- one\n- two\n  1. first\n  2. second\n- [ ] todo\n- [x] done\n",
+            40,
+        );
+        assert_eq!(
+            texts(&lines),
+            vec![
+                "• one",
+                "• two",
+                "  1. first",
+                "  2. second",
+                "• ☐ todo",
+                "• ☑ done"
+            ]
+        );
+        assert_eq!(lines[3].source_line, 3);
+    }
+
+    #[test]
+    fn test_wrapping_keeps_list_indent() {
+        let lines = render("- alpha beta gamma delta epsilon\n", 20);
+        assert_eq!(texts(&lines), vec!["• alpha beta gamma", "  delta epsilon"]);
+    }
+
+    #[test]
+    fn test_table() {
+        let lines = render("| Name | Qty |\n|------|----:|\n| apple | 3 |\n", 40);
+        assert_eq!(
+            texts(&lines),
+            vec!["Name  │ Qty", "──────┼────", "apple │   3"]
+        );
+        assert_eq!(lines[2].source_line, 2);
+    }
+
+    #[test]
+    fn test_code_block_and_quote() {
+        let lines = render("> quoted\n\n```rust\nfn main() {}\n```\n", 24);
+        let texts = texts(&lines);
+        assert_eq!(texts[0], "▎ quoted");
+        assert_eq!(texts[1], "");
+        assert_eq!(texts[2].trim_end(), " fn main() {}");
+        // Code lines are padded to the full width with the code background
+        assert_eq!(texts[2].width(), 24);
+        assert_eq!(lines[2].source_line, 3);
+    }
+
+    #[test]
+    fn test_links_keep_url() {
+        let lines = render("See [the docs](https://example.com).", 40);
+        let link = lines[0]
+            .line
+            .spans
+            .iter()
+            .find(|span| span.text == "the docs")
+            .unwrap();
+        assert_eq!(link.link_url.as_deref(), Some("https://example.com"));
+        assert!(link.style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_preview_follows_source_line() {
+        let lines = render("# A\n\npara one\nstill one\n\npara two\n", 40);
+        // Cursor on the second line of the first paragraph: its first line
+        assert_eq!(preview_line_for_source(&lines, 3), 3);
+        assert_eq!(lines[3].line.plain_text(), "para one still one");
+        assert_eq!(preview_line_for_source(&lines, 5), 5);
+
+        let mut preview = MarkdownPreview {
+            lines: (0..100)
+                .map(|i| PreviewLine {
+                    line: StyledLine::new(),
+                    source_line: i,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        preview.follow(50, 0, 10);
+        assert!(preview.top <= 50 && 50 < preview.top + 10);
+        // Viewport scrolled without the cursor moving
+        preview.follow(50, 80, 10);
+        assert_eq!(preview.top, 80);
+        // Never scrolls past the end
+        preview.follow(99, 80, 10);
+        assert_eq!(preview.top, 90);
+    }
+}
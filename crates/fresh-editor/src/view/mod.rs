@@ -41,8 +41,14 @@ pub mod settings;
 #[cfg(feature = "runtime")]
 pub mod bracket_highlight_overlay;
 #[cfg(feature = "runtime")]
+pub mod buffer_switcher;
+#[cfg(feature = "runtime")]
 pub mod calibration_wizard;
 #[cfg(feature = "runtime")]
+pub mod cheat_sheet;
+#[cfg(feature = "runtime")]
+pub mod conceal;
+#[cfg(feature = "runtime")]
 pub mod event_debug;
 #[cfg(feature = "runtime")]
 pub mod file_browser_input;
@@ -51,6 +57,8 @@ pub mod file_tree;
 #[cfg(feature = "runtime")]
 pub mod markdown;
 #[cfg(feature = "runtime")]
+pub mod markdown_preview;
+#[cfg(feature = "runtime")]
 pub mod popup;
 #[cfg(feature = "runtime")]
 pub mod popup_input;
@@ -68,3 +76,5 @@ pub mod reference_highlight_overlay;
 pub mod split;
 #[cfg(feature = "runtime")]
 pub mod stream;
+#[cfg(feature = "runtime")]
+pub mod visual_guides;
@@ -13,6 +13,8 @@ pub enum PromptType {
     OpenFile,
     /// Switch to a different project folder (change working directory)
     SwitchProject,
+    /// Add a folder to the workspace
+    AddWorkspaceFolder,
     /// Save current buffer to a new file
     SaveFileAs,
     /// Search for text in buffer
@@ -32,6 +34,9 @@ pub enum PromptType {
     /// Quick Open - unified prompt with prefix-based provider routing
     /// Supports file finding (default), commands (>), buffers (#), goto line (:)
     QuickOpen,
+    /// Go to Anything - files (default), goto line (:), document symbols (@)
+    /// and workspace symbols (#)
+    GotoAnything,
     /// Go to a specific line number
     GotoLine,
     /// Choose an ANSI background file
@@ -61,14 +66,52 @@ pub enum PromptType {
     SetComposeWidth,
     /// Set tab size for current buffer
     SetTabSize,
+    /// Set tabs or spaces and the indent width for current buffer
+    SetIndentation,
     /// Set line ending format for current buffer
     SetLineEnding,
     /// Set text encoding format for current buffer
     SetEncoding,
     /// Set language/syntax highlighting for current buffer
     SetLanguage,
+    /// Set spell checking dictionary language for current buffer
+    SetSpellLanguage,
+    /// Choose the language of a new scratch buffer
+    NewScratchBuffer,
+    /// Address to share the active buffer on
+    CollabHost,
+    /// Address of a collaboration session to join
+    CollabJoin,
+    /// Address to present the active buffer on
+    StartPresenting,
+    /// Address of a presentation to follow
+    FollowPresentation,
+    /// Passphrase of an encrypted (gpg) file
+    DecryptPassphrase {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Identity file for an encrypted (age) file
+    DecryptIdentity {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Pick a location from the active split's jump list
+    JumpList,
+    /// Pick a tag to go to its definition
+    SelectTag,
     /// Stop a running LSP server (select from list)
     StopLspServer,
+    /// Remove a folder from the workspace (select from list)
+    RemoveWorkspaceFolder,
+    /// The first of two folders to compare
+    CompareFoldersLeft,
+    /// The folder to compare the first one with
+    CompareFoldersRight { left: std::path::PathBuf },
+    /// Pick a template for a new file (select from list)
+    SelectFileTemplate {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Pick a project task to run (select from list)
+    RunTask,
     /// Select a theme (select from list)
     /// Stores the original theme name for restoration on cancel
     SelectTheme { original_theme: String },
@@ -95,8 +138,27 @@ pub enum PromptType {
     ConfirmCloseBuffer {
         buffer_id: crate::model::event::BufferId,
     },
+    /// Confirm editing a file that another editor has locked
+    ConfirmEditLockedFile {
+        buffer_id: crate::model::event::BufferId,
+    },
+    /// Ask whether to trust the working directory or stay in safe mode
+    TrustWorkspace,
+    /// Ask whether a plugin may do something its permissions don't cover
+    PluginPermission,
     /// Confirm quitting with modified buffers
     ConfirmQuitWithModified,
+    /// Choose what to do with the buffers with unsaved changes when closing
+    /// many buffers (select from list)
+    CloseUnsavedBuffers {
+        buffers: Vec<crate::model::event::BufferId>,
+    },
+    /// Pick a crash-recovery entry to preview (select from list)
+    RecoverySelect,
+    /// Choose what to do with the previewed recovery entry
+    RecoveryAction { id: String },
+    /// Show the effective value and source of a setting (select from list)
+    ShowConfigSource,
     /// File Explorer rename operation
     /// Stores the original path and name for the file/directory being renamed
     FileExplorerRename {
@@ -117,6 +179,15 @@ pub enum PromptType {
     /// If replace is true, replace the input with the output
     /// If replace is false, output goes to a new buffer
     ShellCommand { replace: bool },
+    /// Insert a number sequence across the cursors ("start" or "start,step")
+    InsertSequence,
+    /// Regex whose matches inside the selection get selected
+    /// If split is true, the text between the matches is selected instead
+    RegexSelect { split: bool },
+    /// Name the variable or function to extract the selection into
+    ExtractName {
+        kind: crate::primitives::extract::ExtractKind,
+    },
     /// Async prompt from plugin (for editor.prompt() API)
     /// The result is returned via callback resolution
     AsyncPrompt,
@@ -528,6 +599,22 @@ impl Prompt {
         self.cursor_pos += text.len();
     }
 
+    /// Whether the input is a secret that is drawn masked and never copied
+    pub fn is_secret(&self) -> bool {
+        matches!(self.prompt_type, PromptType::DecryptPassphrase { .. })
+    }
+
+    /// Copy of the prompt with each character of the input drawn as `*`
+    pub fn masked(&self) -> Self {
+        let masked_pos = |pos: usize| self.input[..pos].chars().count();
+        Self {
+            input: "*".repeat(self.input.chars().count()),
+            cursor_pos: masked_pos(self.cursor_pos),
+            selection_anchor: self.selection_anchor.map(masked_pos),
+            ..self.clone()
+        }
+    }
+
     // ========================================================================
     // Selection support
     // ========================================================================
@@ -1140,6 +1227,26 @@ mod tests {
         assert_eq!(prompt.selected_text(), Some("two three".to_string()));
     }
 
+    #[test]
+    fn test_masked_secret() {
+        let mut prompt = Prompt::new(
+            "Passphrase: ".to_string(),
+            PromptType::DecryptPassphrase {
+                buffer_id: crate::model::event::BufferId(1),
+            },
+        );
+        prompt.input = "pässword".to_string();
+        prompt.cursor_pos = prompt.input.len();
+        prompt.selection_anchor = Some(1);
+        assert!(prompt.is_secret());
+
+        let masked = prompt.masked();
+        assert_eq!(masked.input, "********");
+        assert_eq!(masked.cursor_pos, 8);
+        assert_eq!(masked.selection_anchor, Some(1));
+        assert!(!Prompt::new(String::new(), PromptType::Search).is_secret());
+    }
+
     // Property-based tests for Prompt operations
     #[cfg(test)]
     mod property_tests {
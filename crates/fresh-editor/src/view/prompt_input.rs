@@ -29,9 +29,13 @@ impl InputHandler for Prompt {
 
             // Character input (no modifiers or just shift)
             KeyCode::Char(c) if !ctrl => {
-                // Delete any selection before inserting
+                // Delete any selection before inserting. An empty one (Ctrl+A on
+                // an empty input) is dropped, or it would grow to cover this
+                // character and the next one would replace it.
                 if self.has_selection() {
                     self.delete_selection();
+                } else {
+                    self.clear_selection();
                 }
                 if shift {
                     self.insert_char(c.to_ascii_uppercase());
@@ -143,6 +147,7 @@ impl InputHandler for Prompt {
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
                                 | crate::view::prompt::PromptType::QuickOpen
+                                | crate::view::prompt::PromptType::GotoAnything
                         ) {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
                                 self.input = suggestion.get_value().to_string();
@@ -183,6 +188,7 @@ impl InputHandler for Prompt {
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
                                 | crate::view::prompt::PromptType::QuickOpen
+                                | crate::view::prompt::PromptType::GotoAnything
                         ) {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
                                 self.input = suggestion.get_value().to_string();
@@ -248,6 +254,16 @@ impl InputHandler for Prompt {
                                 } else {
                                     self.input = value;
                                 }
+                            } else if matches!(
+                                self.prompt_type,
+                                crate::view::prompt::PromptType::GotoAnything
+                            ) {
+                                // Symbols complete to their name, lines and files to their value
+                                self.input = match self.input.chars().next() {
+                                    Some(p @ ('@' | '#')) => format!("{}{}", p, suggestion.text),
+                                    Some(':') => format!(":{}", value),
+                                    _ => value,
+                                };
                             } else {
                                 self.input = value;
                             }
@@ -400,6 +416,19 @@ mod tests {
         assert_eq!(prompt.selected_text(), Some("hello world".to_string()));
     }
 
+    #[test]
+    fn test_prompt_typing_after_select_all_on_empty_input() {
+        let mut prompt = Prompt::new("Test: ".to_string(), PromptType::Search);
+        let mut ctx = InputContext::new();
+
+        prompt.handle_key_event(&key_with_ctrl('a'), &mut ctx);
+        prompt.handle_key_event(&key(KeyCode::Char('h')), &mut ctx);
+        prompt.handle_key_event(&key(KeyCode::Char('i')), &mut ctx);
+
+        assert_eq!(prompt.input, "hi");
+        assert!(!prompt.has_selection());
+    }
+
     #[test]
     fn test_prompt_enter_confirms() {
         let mut prompt = Prompt::new("Test: ".to_string(), PromptType::Search);
@@ -23,8 +23,10 @@
 /// └────────────────────┘      └──────────┴─────────┘
 ///  (horizontal split)          (mixed splits)
 /// ```
+use crate::input::jump_list::JumpList;
 use crate::model::cursor::Cursors;
 use crate::model::event::{BufferId, SplitDirection, SplitId};
+use crate::view::markdown_preview::MarkdownPreview;
 use crate::view::ui::view_pipeline::Layout;
 use crate::view::viewport::Viewport;
 use crate::{services::plugins::api::ViewTransformPayload, state::ViewMode};
@@ -78,6 +80,10 @@ pub struct SplitViewState {
     /// The currently displayed buffer is tracked in the SplitNode::Leaf
     pub open_buffers: Vec<BufferId>,
 
+    /// Pinned tabs of this split: kept leftmost in `open_buffers`, drawn
+    /// compact and skipped when closing other tabs
+    pub pinned_buffers: Vec<BufferId>,
+
     /// Horizontal scroll offset for the tabs in this split
     pub tab_scroll_offset: usize,
 
@@ -116,6 +122,12 @@ pub struct SplitViewState {
     /// the composite layout. This makes the source buffer the "active buffer"
     /// so normal keybindings work directly.
     pub composite_view: Option<BufferId>,
+
+    /// Rendered document and scroll position for the markdown preview mode
+    pub markdown_preview: MarkdownPreview,
+
+    /// Jump list for Ctrl+O / Ctrl+I navigation within this split
+    pub jump_list: JumpList,
 }
 
 impl SplitViewState {
@@ -125,6 +137,7 @@ impl SplitViewState {
             cursors: Cursors::new(),
             viewport: Viewport::new(width, height),
             open_buffers: Vec::new(),
+            pinned_buffers: Vec::new(),
             tab_scroll_offset: 0,
             view_mode: ViewMode::Source,
             compose_width: None,
@@ -136,6 +149,8 @@ impl SplitViewState {
             focus_history: Vec::new(),
             sync_group: None,
             composite_view: None,
+            markdown_preview: MarkdownPreview::default(),
+            jump_list: JumpList::new(),
         }
     }
 
@@ -145,6 +160,7 @@ impl SplitViewState {
             cursors: Cursors::new(),
             viewport: Viewport::new(width, height),
             open_buffers: vec![buffer_id],
+            pinned_buffers: Vec::new(),
             tab_scroll_offset: 0,
             view_mode: ViewMode::Source,
             compose_width: None,
@@ -156,6 +172,8 @@ impl SplitViewState {
             focus_history: Vec::new(),
             sync_group: None,
             composite_view: None,
+            markdown_preview: MarkdownPreview::default(),
+            jump_list: JumpList::new(),
         }
     }
 
@@ -203,6 +221,34 @@ impl SplitViewState {
     /// Remove a buffer from this split's tabs
     pub fn remove_buffer(&mut self, buffer_id: BufferId) {
         self.open_buffers.retain(|&id| id != buffer_id);
+        self.pinned_buffers.retain(|&id| id != buffer_id);
+    }
+
+    /// Check if a buffer's tab is pinned in this split
+    pub fn is_pinned(&self, buffer_id: BufferId) -> bool {
+        self.pinned_buffers.contains(&buffer_id)
+    }
+
+    /// Pin or unpin a buffer's tab. A pinned tab moves to the end of the
+    /// pinned tabs, an unpinned one to the start of the others.
+    pub fn set_pinned(&mut self, buffer_id: BufferId, pinned: bool) {
+        if !self.open_buffers.contains(&buffer_id) {
+            return;
+        }
+        self.pinned_buffers.retain(|&id| id != buffer_id);
+        if pinned {
+            self.pinned_buffers.push(buffer_id);
+            // Put it after the other pinned tabs, not wherever it was
+            self.open_buffers.retain(|&id| id != buffer_id);
+            self.open_buffers.push(buffer_id);
+        }
+        self.keep_pinned_first();
+    }
+
+    /// Move the pinned tabs before the others, keeping their order
+    pub fn keep_pinned_first(&mut self) {
+        let pinned = &self.pinned_buffers;
+        self.open_buffers.sort_by_key(|id| !pinned.contains(id));
     }
 
     /// Check if a buffer is open in this split
@@ -232,9 +278,10 @@ impl SplitViewState {
         self.focus_history.pop()
     }
 
-    /// Remove a buffer from the focus history (called when buffer is closed)
+    /// Remove a buffer from the focus history and jump list (called when buffer is closed)
     pub fn remove_from_history(&mut self, buffer_id: BufferId) {
         self.focus_history.retain(|&id| id != buffer_id);
+        self.jump_list.remove_buffer(buffer_id);
     }
 }
 
@@ -1057,4 +1104,39 @@ mod tests {
         assert_eq!(first.x, 0);
         assert_eq!(second.x, 51); // first.x + first.width + 1 (separator)
     }
+
+    #[test]
+    fn test_pinned_tabs_stay_first() {
+        let mut view_state = SplitViewState::new(80, 24);
+        for id in 0..4 {
+            view_state.add_buffer(BufferId(id));
+        }
+
+        view_state.set_pinned(BufferId(2), true);
+        view_state.set_pinned(BufferId(3), true);
+        assert_eq!(
+            view_state.open_buffers,
+            vec![BufferId(2), BufferId(3), BufferId(0), BufferId(1)]
+        );
+
+        // New tabs open after the pinned ones
+        view_state.add_buffer(BufferId(4));
+        assert_eq!(view_state.open_buffers.last(), Some(&BufferId(4)));
+
+        // Unpinning puts the tab right after the pinned ones
+        view_state.set_pinned(BufferId(2), false);
+        assert_eq!(
+            view_state.open_buffers,
+            vec![
+                BufferId(3),
+                BufferId(2),
+                BufferId(0),
+                BufferId(1),
+                BufferId(4)
+            ]
+        );
+
+        view_state.remove_buffer(BufferId(3));
+        assert!(!view_state.is_pinned(BufferId(3)));
+    }
 }
@@ -0,0 +1,270 @@
+//! Light/dark appearance selection.
+//!
+//! Decides whether the `light_theme` or `dark_theme` from the `appearance`
+//! config applies: by terminal background (mode "auto") or by time of day
+//! (mode "schedule"). Schedule times are either fixed ("07:30") or
+//! "sunrise"/"sunset", computed from the configured latitude and longitude.
+
+use crate::config::{AppearanceConfig, AppearanceMode, ThemeName};
+
+/// Switch times used when `light_at`/`dark_at` can't be resolved
+const DEFAULT_LIGHT_AT: u32 = 7 * 60;
+const DEFAULT_DARK_AT: u32 = 19 * 60;
+
+/// Whether a light or a dark theme is wanted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appearance {
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Appearance matching a background color, by perceived brightness
+    pub fn from_background(r: u8, g: u8, b: u8) -> Self {
+        let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+        if luma > 128 * 1000 {
+            Self::Light
+        } else {
+            Self::Dark
+        }
+    }
+
+    /// Parse "light" or "dark" (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            _ => None,
+        }
+    }
+
+    /// The opposite appearance
+    pub fn toggled(self) -> Self {
+        match self {
+            Self::Light => Self::Dark,
+            Self::Dark => Self::Light,
+        }
+    }
+}
+
+/// Local date and time used to evaluate an appearance schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    /// Minutes since local midnight
+    pub minutes: u32,
+    /// Day of the year (1-366)
+    pub day_of_year: u32,
+    /// Offset of local time from UTC, in minutes
+    pub utc_offset_minutes: i32,
+}
+
+/// Appearance selected by the config, or `None` if `theme` should be used.
+///
+/// `terminal` is the detected terminal background, used in "auto" mode. If
+/// it is unknown, `theme` is used.
+pub fn configured_appearance(
+    config: &AppearanceConfig,
+    terminal: Option<Appearance>,
+    now: LocalTime,
+) -> Option<Appearance> {
+    match config.mode {
+        AppearanceMode::Fixed => None,
+        AppearanceMode::Auto => terminal,
+        AppearanceMode::Schedule => Some(scheduled_appearance(config, now)),
+    }
+}
+
+/// Theme configured for an appearance
+pub fn theme_for_appearance(config: &AppearanceConfig, appearance: Appearance) -> &ThemeName {
+    match appearance {
+        Appearance::Light => &config.light_theme,
+        Appearance::Dark => &config.dark_theme,
+    }
+}
+
+/// Appearance at `now` according to the `light_at`/`dark_at` schedule.
+///
+/// "sunrise" and "sunset" need both latitude and longitude; without them
+/// (or during polar day/night) 07:00 and 19:00 are used.
+pub fn scheduled_appearance(config: &AppearanceConfig, now: LocalTime) -> Appearance {
+    let sun = match (config.latitude, config.longitude) {
+        (Some(latitude), Some(longitude)) => {
+            sun_times(now.day_of_year, latitude, longitude, now.utc_offset_minutes)
+        }
+        _ => None,
+    };
+    let resolve = |spec: &str, default: u32| match spec.trim() {
+        "sunrise" => sun.map(|(sunrise, _)| sunrise).unwrap_or(DEFAULT_LIGHT_AT),
+        "sunset" => sun.map(|(_, sunset)| sunset).unwrap_or(DEFAULT_DARK_AT),
+        time => parse_time_of_day(time).unwrap_or(default),
+    };
+    let light_at = resolve(&config.light_at, DEFAULT_LIGHT_AT);
+    let dark_at = resolve(&config.dark_at, DEFAULT_DARK_AT);
+
+    let is_light = if light_at <= dark_at {
+        now.minutes >= light_at && now.minutes < dark_at
+    } else {
+        // Light period wraps around midnight
+        now.minutes >= light_at || now.minutes < dark_at
+    };
+    if is_light {
+        Appearance::Light
+    } else {
+        Appearance::Dark
+    }
+}
+
+/// Parse "HH:MM" into minutes since midnight
+fn parse_time_of_day(s: &str) -> Option<u32> {
+    let (hours, minutes) = s.split_once(':')?;
+    let hours: u32 = hours.trim().parse().ok()?;
+    let minutes: u32 = minutes.trim().parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Local sunrise and sunset as minutes since midnight.
+///
+/// Uses the NOAA solar position approximation (accurate to a few minutes).
+/// Returns `None` when the sun doesn't rise or set on that day.
+pub fn sun_times(
+    day_of_year: u32,
+    latitude: f64,
+    longitude: f64,
+    utc_offset_minutes: i32,
+) -> Option<(u32, u32)> {
+    use std::f64::consts::PI;
+
+    // Fractional year in radians
+    let gamma = 2.0 * PI / 365.0 * (day_of_year as f64 - 1.0);
+    // Equation of time (minutes) and solar declination (radians)
+    let eqtime = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+    let decl = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+
+    // Hour angle of the sun at rise/set (accounting for refraction)
+    let lat = latitude.to_radians();
+    let cos_ha = 90.833_f64.to_radians().cos() / (lat.cos() * decl.cos()) - lat.tan() * decl.tan();
+    if !(-1.0..=1.0).contains(&cos_ha) {
+        return None;
+    }
+    let ha = cos_ha.acos().to_degrees();
+
+    let to_local = |utc_minutes: f64| {
+        ((utc_minutes + utc_offset_minutes as f64)
+            .rem_euclid(1440.0)
+            .round() as u32)
+            % 1440
+    };
+    Some((
+        to_local(720.0 - 4.0 * (longitude + ha) - eqtime),
+        to_local(720.0 - 4.0 * (longitude - ha) - eqtime),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(hours: u32, minutes: u32) -> LocalTime {
+        LocalTime {
+            minutes: hours * 60 + minutes,
+            day_of_year: 172,
+            utc_offset_minutes: 60,
+        }
+    }
+
+    fn schedule(light_at: &str, dark_at: &str) -> AppearanceConfig {
+        AppearanceConfig {
+            mode: AppearanceMode::Schedule,
+            light_at: light_at.to_string(),
+            dark_at: dark_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_background_brightness() {
+        assert_eq!(Appearance::from_background(0, 0, 0), Appearance::Dark);
+        assert_eq!(Appearance::from_background(30, 30, 30), Appearance::Dark);
+        assert_eq!(
+            Appearance::from_background(255, 255, 255),
+            Appearance::Light
+        );
+        assert_eq!(
+            Appearance::from_background(253, 246, 227),
+            Appearance::Light
+        );
+    }
+
+    #[test]
+    fn test_fixed_schedule() {
+        let config = schedule("08:30", "18:00");
+        assert_eq!(scheduled_appearance(&config, at(8, 29)), Appearance::Dark);
+        assert_eq!(scheduled_appearance(&config, at(8, 30)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(17, 59)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(18, 0)), Appearance::Dark);
+
+        // Light period spanning midnight
+        let config = schedule("22:00", "06:00");
+        assert_eq!(scheduled_appearance(&config, at(23, 0)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(5, 0)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(12, 0)), Appearance::Dark);
+    }
+
+    #[test]
+    fn test_sunrise_sunset_schedule() {
+        // London around the June solstice: sunrise ~04:43, sunset ~21:21 (BST)
+        let (sunrise, sunset) = sun_times(172, 51.5, -0.13, 60).unwrap();
+        assert!(sunrise.abs_diff(4 * 60 + 43) <= 5, "sunrise {}", sunrise);
+        assert!(sunset.abs_diff(21 * 60 + 21) <= 5, "sunset {}", sunset);
+
+        let mut config = schedule("sunrise", "sunset");
+        config.latitude = Some(51.5);
+        config.longitude = Some(-0.13);
+        assert_eq!(scheduled_appearance(&config, at(5, 0)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(21, 0)), Appearance::Light);
+        assert_eq!(scheduled_appearance(&config, at(22, 0)), Appearance::Dark);
+
+        // Midnight sun in Svalbard
+        assert_eq!(sun_times(172, 78.2, 15.6, 120), None);
+
+        // Without a location, the default times are used
+        let config = schedule("sunrise", "sunset");
+        assert_eq!(scheduled_appearance(&config, at(6, 59)), Appearance::Dark);
+        assert_eq!(scheduled_appearance(&config, at(7, 0)), Appearance::Light);
+    }
+
+    #[test]
+    fn test_configured_appearance_by_mode() {
+        let mut config = AppearanceConfig::default();
+        let noon = at(12, 0);
+        assert_eq!(
+            configured_appearance(&config, Some(Appearance::Light), noon),
+            None
+        );
+
+        config.mode = AppearanceMode::Auto;
+        assert_eq!(
+            configured_appearance(&config, Some(Appearance::Light), noon),
+            Some(Appearance::Light)
+        );
+        assert_eq!(configured_appearance(&config, None, noon), None);
+
+        config.mode = AppearanceMode::Schedule;
+        assert_eq!(
+            configured_appearance(&config, Some(Appearance::Dark), noon),
+            Some(Appearance::Light)
+        );
+        assert_eq!(
+            theme_for_appearance(&config, Appearance::Dark).0,
+            "dark".to_string()
+        );
+    }
+}
@@ -3,11 +3,20 @@
 //! This module provides:
 //! - `ThemeRegistry`: A pure data structure holding all loaded themes
 //! - `ThemeLoader`: Scans and loads themes into a registry
+//!
+//! User theme files may be written in JSON (`.json`) or TOML (`.toml`).
+//! VS Code color themes (JSON) are converted on load, including themes
+//! contributed by VS Code extensions in the packages directory.
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 use super::types::{Theme, ThemeFile, ThemeInfo, BUILTIN_THEMES};
+use super::vscode::{import_vscode_theme, is_vscode_theme, merge_vscode_include};
+
+/// Maximum depth of `include` chains in VS Code themes
+const MAX_VSCODE_INCLUDE_DEPTH: usize = 8;
 
 /// A registry holding all loaded themes.
 ///
@@ -144,6 +153,18 @@ impl ThemeLoader {
         ThemeRegistry { themes, theme_list }
     }
 
+    /// Modification times of all theme files under the user themes directory.
+    ///
+    /// Used to detect when user themes are added, edited or removed so the
+    /// registry can be reloaded.
+    pub fn user_theme_mod_times(&self) -> HashMap<PathBuf, SystemTime> {
+        let mut mod_times = HashMap::new();
+        if let Some(ref user_dir) = self.user_themes_dir {
+            collect_theme_mod_times(user_dir, &mut mod_times);
+        }
+        mod_times
+    }
+
     /// Load themes from a package with package.json manifest.
     fn load_package_themes(
         &self,
@@ -172,34 +193,61 @@ impl ThemeLoader {
                         entry.get("file").and_then(|f| f.as_str()),
                         entry.get("name").and_then(|n| n.as_str()),
                     ) {
-                        let theme_path = pkg_dir.join(file);
-                        if theme_path.exists() {
-                            if let Ok(content) = std::fs::read_to_string(&theme_path) {
-                                if let Ok(theme_file) = serde_json::from_str::<ThemeFile>(&content)
-                                {
-                                    let theme: Theme = theme_file.into();
-                                    let normalized_name = name.to_lowercase().replace(' ', "-");
-                                    // Don't overwrite existing themes
-                                    if !themes.contains_key(&normalized_name) {
-                                        themes.insert(normalized_name.clone(), theme);
-                                        let pack_name = format!("pkg/{}", pkg_name);
-                                        theme_list
-                                            .push(ThemeInfo::new(normalized_name, &pack_name));
-                                    }
-                                }
-                            }
-                        }
+                        self.load_manifest_theme(pkg_dir, pkg_name, file, name, themes, theme_list);
                     }
                 }
                 return;
             }
         }
 
+        // VS Code extensions list their color themes in contributes.themes
+        if let Some(theme_entries) = manifest
+            .get("contributes")
+            .and_then(|c| c.get("themes"))
+            .and_then(|t| t.as_array())
+        {
+            for entry in theme_entries {
+                if let (Some(file), Some(label)) = (
+                    entry.get("path").and_then(|p| p.as_str()),
+                    entry.get("label").and_then(|l| l.as_str()),
+                ) {
+                    self.load_manifest_theme(pkg_dir, pkg_name, file, label, themes, theme_list);
+                }
+            }
+            return;
+        }
+
         // Fallback: if no fresh.themes, scan for JSON files
         let pack_name = format!("pkg/{}", pkg_name);
         self.scan_directory(pkg_dir, &pack_name, themes, theme_list);
     }
 
+    /// Load a single theme listed in a package manifest.
+    fn load_manifest_theme(
+        &self,
+        pkg_dir: &Path,
+        pkg_name: &str,
+        file: &str,
+        name: &str,
+        themes: &mut HashMap<String, Theme>,
+        theme_list: &mut Vec<ThemeInfo>,
+    ) {
+        let theme_path = pkg_dir.join(file);
+        let normalized_name = name.to_lowercase().replace(' ', "-");
+        // Don't overwrite existing themes
+        if themes.contains_key(&normalized_name) {
+            return;
+        }
+        if let Ok(content) = std::fs::read_to_string(&theme_path) {
+            if let Some(theme_file) = parse_theme_file(&theme_path, &content, &normalized_name) {
+                let theme: Theme = theme_file.into();
+                themes.insert(normalized_name.clone(), theme);
+                let pack_name = format!("pkg/{}", pkg_name);
+                theme_list.push(ThemeInfo::new(normalized_name, &pack_name));
+            }
+        }
+    }
+
     /// Recursively scan a directory for theme files.
     fn scan_directory(
         &self,
@@ -225,7 +273,7 @@ impl ThemeLoader {
                     format!("{}/{}", pack, subdir_name)
                 };
                 self.scan_directory(&path, &new_pack, themes, theme_list);
-            } else if path.extension().is_some_and(|ext| ext == "json") {
+            } else if is_theme_file(&path) {
                 // Load theme file
                 let name = path.file_stem().unwrap().to_string_lossy().to_string();
 
@@ -235,7 +283,7 @@ impl ThemeLoader {
                 }
 
                 if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(theme_file) = serde_json::from_str::<ThemeFile>(&content) {
+                    if let Some(theme_file) = parse_theme_file(&path, &content, &name) {
                         let theme: Theme = theme_file.into();
                         themes.insert(name.clone(), theme);
                         theme_list.push(ThemeInfo::new(name, pack));
@@ -246,6 +294,76 @@ impl ThemeLoader {
     }
 }
 
+/// Whether a path has a theme file extension (`.json` or `.toml`).
+fn is_theme_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "json" || ext == "toml")
+}
+
+/// Parse a theme file, choosing the format from the file extension.
+///
+/// JSON files that are VS Code color themes are converted and named `name`.
+fn parse_theme_file(path: &Path, content: &str, name: &str) -> Option<ThemeFile> {
+    let result = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str::<ThemeFile>(content).map_err(|e| e.to_string())
+    } else {
+        serde_json::from_str::<ThemeFile>(content)
+            .map_err(|e| e.to_string())
+            .or_else(|e| match read_vscode_theme(path, content, 0) {
+                Some(value) => import_vscode_theme(name, &value),
+                None => Err(e),
+            })
+    };
+    match result {
+        Ok(theme_file) => Some(theme_file),
+        Err(e) => {
+            tracing::debug!("Failed to parse theme file {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Parse a VS Code color theme, merging in the theme it `include`s.
+///
+/// VS Code themes may contain comments and trailing commas. Returns `None`
+/// if the content isn't a VS Code theme.
+fn read_vscode_theme(path: &Path, content: &str, depth: usize) -> Option<serde_json::Value> {
+    let value = crate::config_edit::parse(content).ok()?;
+    if !is_vscode_theme(&value) {
+        return None;
+    }
+    let included = value
+        .get("include")
+        .and_then(|include| include.as_str())
+        .filter(|_| depth < MAX_VSCODE_INCLUDE_DEPTH)
+        .and_then(|include| {
+            let include_path = path.parent()?.join(include);
+            let include_content = std::fs::read_to_string(&include_path).ok()?;
+            read_vscode_theme(&include_path, &include_content, depth + 1)
+        });
+    Some(match included {
+        Some(included) => merge_vscode_include(included, value),
+        None => value,
+    })
+}
+
+/// Recursively collect modification times of theme files in a directory.
+fn collect_theme_mod_times(dir: &Path, mod_times: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_theme_mod_times(&path, mod_times);
+        } else if is_theme_file(&path) {
+            if let Ok(mtime) = entry.metadata().and_then(|m| m.modified()) {
+                mod_times.insert(path, mtime);
+            }
+        }
+    }
+}
+
 // Cursor color methods on Theme (no I/O for theme loading)
 impl Theme {
     /// Set the terminal cursor color using OSC 12 escape sequence.
@@ -321,6 +439,66 @@ mod tests {
         assert!(!registry.contains("nonexistent"));
     }
 
+    #[test]
+    fn test_theme_loader_loads_toml_user_themes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dark = BUILTIN_THEMES.iter().find(|t| t.name == "dark").unwrap();
+        let mut theme_file: ThemeFile = serde_json::from_str(dark.json).unwrap();
+        theme_file.name = "my-toml-theme".to_string();
+        let toml_path = temp_dir.path().join("my-toml-theme.toml");
+        std::fs::write(&toml_path, toml::to_string(&theme_file).unwrap()).unwrap();
+
+        let loader = ThemeLoader::with_user_dir(Some(temp_dir.path().to_path_buf()));
+        let registry = loader.load_all();
+        let theme = registry.get("my-toml-theme").unwrap();
+        assert_eq!(theme.name, "my-toml-theme");
+        assert!(registry
+            .list()
+            .iter()
+            .any(|t| t.name == "my-toml-theme" && t.pack == "user"));
+
+        let mod_times = loader.user_theme_mod_times();
+        assert_eq!(mod_times.len(), 1);
+        assert!(mod_times.contains_key(&toml_path));
+    }
+
+    #[test]
+    fn test_theme_loader_imports_vscode_extension_themes() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let pkg_dir = temp_dir.path().join("packages").join("my-extension");
+        std::fs::create_dir_all(pkg_dir.join("themes")).unwrap();
+        std::fs::write(
+            pkg_dir.join("package.json"),
+            r#"{"contributes": {"themes": [
+                {"label": "My Plus", "uiTheme": "vs-dark", "path": "./themes/plus.json"}
+            ]}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            pkg_dir.join("themes").join("base.json"),
+            r##"{"colors": {"editor.background": "#101010"}}"##,
+        )
+        .unwrap();
+        std::fs::write(
+            pkg_dir.join("themes").join("plus.json"),
+            r##"{
+                // VS Code themes may contain comments
+                "include": "./base.json",
+                "tokenColors": [
+                    {"scope": "keyword", "settings": {"foreground": "#ff0000"}},
+                ],
+            }"##,
+        )
+        .unwrap();
+
+        let loader = ThemeLoader::with_user_dir(Some(temp_dir.path().to_path_buf()));
+        let registry = loader.load_all();
+        let theme = registry.get("my-plus").unwrap();
+        assert_eq!(theme.name, "my-plus");
+        assert_eq!(theme.editor_bg, ratatui::style::Color::Rgb(16, 16, 16));
+        assert_eq!(theme.syntax_keyword, ratatui::style::Color::Rgb(255, 0, 0));
+    }
+
     #[test]
     fn test_theme_loader_load_all() {
         let loader = ThemeLoader::new();
@@ -2,6 +2,8 @@
 //!
 //! This module is split into:
 //! - `types`: Pure data types (WASM-compatible, no filesystem access)
+//! - `appearance`: Light/dark theme selection by terminal background or time of day
+//! - `vscode`: Conversion of VS Code color themes (pure, no filesystem access)
 //! - `loader`: ThemeLoader creates ThemeRegistry from embedded + user themes (runtime only)
 //!
 //! # Example
@@ -20,12 +22,16 @@
 //! let themes = registry.list();
 //! ```
 
+mod appearance;
 // Loader requires filesystem access - runtime only
 #[cfg(feature = "runtime")]
 mod loader;
 mod types;
+mod vscode;
 
 // Re-export all public items for backward compatibility
+pub use appearance::*;
 #[cfg(feature = "runtime")]
 pub use loader::*;
 pub use types::*;
+pub use vscode::*;
@@ -79,6 +79,60 @@ pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
     }
 }
 
+/// Contrast ratio that `high_contrast` raises theme text to (WCAG AAA)
+pub const HIGH_CONTRAST_RATIO: f64 = 7.0;
+
+/// Relative luminance of an sRGB color, as defined by WCAG 2
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = c as f64 / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 (none) to 21.0 (black on
+/// white). Returns None if either color has no known RGB value.
+pub fn contrast_ratio(a: Color, b: Color) -> Option<f64> {
+    let la = relative_luminance(color_to_rgb(a)?);
+    let lb = relative_luminance(color_to_rgb(b)?);
+    let (light, dark) = if la > lb { (la, lb) } else { (lb, la) };
+    Some((light + 0.05) / (dark + 0.05))
+}
+
+/// Move `fg` towards black or white, whichever stands out more against `bg`,
+/// just far enough to reach `ratio`. Colors that already reach it, and colors
+/// without a known RGB value, are returned unchanged.
+pub fn with_min_contrast(fg: Color, bg: Color, ratio: f64) -> Color {
+    let (Some(rgb), Some(_)) = (color_to_rgb(fg), color_to_rgb(bg)) else {
+        return fg;
+    };
+    if contrast_ratio(fg, bg).is_some_and(|current| current >= ratio) {
+        return fg;
+    }
+    let target = if contrast_ratio(Color::White, bg) >= contrast_ratio(Color::Black, bg) {
+        255.0
+    } else {
+        0.0
+    };
+    let blend = |c: u8, t: f64| (c as f64 + (target - c as f64) * t).round() as u8;
+    (1..=20)
+        .map(|step| {
+            let t = step as f64 / 20.0;
+            Color::Rgb(blend(rgb.0, t), blend(rgb.1, t), blend(rgb.2, t))
+        })
+        .find(|color| contrast_ratio(*color, bg).is_some_and(|c| c >= ratio))
+        .unwrap_or(if target > 0.0 {
+            Color::Rgb(255, 255, 255)
+        } else {
+            Color::Rgb(0, 0, 0)
+        })
+}
+
 /// Brighten a color by adding an amount to each RGB component.
 /// Clamps values to 255.
 pub fn brighten_color(color: Color, amount: u8) -> Color {
@@ -218,6 +272,15 @@ pub struct EditorColors {
     /// Diff modified line background
     #[serde(default = "default_diff_modify_bg")]
     pub diff_modify_bg: ColorDef,
+    /// Column ruler background
+    #[serde(default = "default_ruler_bg")]
+    pub ruler_bg: ColorDef,
+    /// Trailing whitespace and mixed indentation background
+    #[serde(default = "default_whitespace_warning_bg")]
+    pub whitespace_warning_bg: ColorDef,
+    /// Background of characters past the maximum line length
+    #[serde(default = "default_line_length_warning_bg")]
+    pub line_length_warning_bg: ColorDef,
 }
 
 // Default editor colors (for minimal themes)
@@ -254,6 +317,15 @@ fn default_diff_remove_bg() -> ColorDef {
 fn default_diff_modify_bg() -> ColorDef {
     ColorDef::Rgb(40, 38, 30) // Very subtle yellow tint, close to dark bg
 }
+fn default_ruler_bg() -> ColorDef {
+    ColorDef::Rgb(45, 45, 45)
+}
+fn default_whitespace_warning_bg() -> ColorDef {
+    ColorDef::Rgb(90, 45, 45) // Muted red
+}
+fn default_line_length_warning_bg() -> ColorDef {
+    ColorDef::Rgb(90, 70, 35) // Muted amber
+}
 
 /// UI element colors (tabs, menus, status bar, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -809,6 +881,11 @@ pub struct Theme {
     /// Brighter background for inline diff highlighting on removed content
     pub diff_remove_highlight_bg: Color,
 
+    // Visual guide colors
+    pub ruler_bg: Color,
+    pub whitespace_warning_bg: Color,
+    pub line_length_warning_bg: Color,
+
     // UI element colors
     pub tab_active_fg: Color,
     pub tab_active_bg: Color,
@@ -938,6 +1015,9 @@ impl From<ThemeFile> for Theme {
             diff_add_bg: file.editor.diff_add_bg.clone().into(),
             diff_remove_bg: file.editor.diff_remove_bg.clone().into(),
             diff_modify_bg: file.editor.diff_modify_bg.into(),
+            ruler_bg: file.editor.ruler_bg.into(),
+            whitespace_warning_bg: file.editor.whitespace_warning_bg.into(),
+            line_length_warning_bg: file.editor.line_length_warning_bg.into(),
             // Compute brighter highlight colors from base diff colors
             diff_add_highlight_bg: brighten_color(file.editor.diff_add_bg.into(), 40),
             diff_remove_highlight_bg: brighten_color(file.editor.diff_remove_bg.into(), 40),
@@ -1042,6 +1122,9 @@ impl From<Theme> for ThemeFile {
                 diff_add_bg: theme.diff_add_bg.into(),
                 diff_remove_bg: theme.diff_remove_bg.into(),
                 diff_modify_bg: theme.diff_modify_bg.into(),
+                ruler_bg: theme.ruler_bg.into(),
+                whitespace_warning_bg: theme.whitespace_warning_bg.into(),
+                line_length_warning_bg: theme.line_length_warning_bg.into(),
             },
             ui: UiColors {
                 tab_active_fg: theme.tab_active_fg.into(),
@@ -1153,6 +1236,53 @@ impl Theme {
         Ok(theme_file.into())
     }
 
+    /// Raise the foreground colors of the theme until text reaches `ratio`
+    /// against the background it is drawn on. Used by `high_contrast`.
+    pub fn enforce_min_contrast(&mut self, ratio: f64) {
+        let editor_bg = self.editor_bg;
+        let pairs = [
+            (&mut self.editor_fg, editor_bg),
+            (&mut self.syntax_keyword, editor_bg),
+            (&mut self.syntax_string, editor_bg),
+            (&mut self.syntax_comment, editor_bg),
+            (&mut self.syntax_function, editor_bg),
+            (&mut self.syntax_type, editor_bg),
+            (&mut self.syntax_variable, editor_bg),
+            (&mut self.syntax_constant, editor_bg),
+            (&mut self.syntax_operator, editor_bg),
+            (&mut self.line_number_fg, self.line_number_bg),
+            (&mut self.tab_active_fg, self.tab_active_bg),
+            (&mut self.tab_inactive_fg, self.tab_inactive_bg),
+            (&mut self.menu_fg, self.menu_bg),
+            (&mut self.menu_active_fg, self.menu_active_bg),
+            (&mut self.menu_dropdown_fg, self.menu_dropdown_bg),
+            (&mut self.menu_highlight_fg, self.menu_highlight_bg),
+            (&mut self.menu_hover_fg, self.menu_hover_bg),
+            (&mut self.status_bar_fg, self.status_bar_bg),
+            (&mut self.prompt_fg, self.prompt_bg),
+            (&mut self.prompt_selection_fg, self.prompt_selection_bg),
+            (&mut self.popup_text_fg, self.popup_bg),
+            (&mut self.popup_selection_fg, self.popup_selection_bg),
+            (&mut self.help_fg, self.help_bg),
+            (&mut self.help_key_fg, self.help_bg),
+            (&mut self.help_indicator_fg, self.help_indicator_bg),
+            (&mut self.terminal_fg, self.terminal_bg),
+            (
+                &mut self.status_warning_indicator_fg,
+                self.status_warning_indicator_bg,
+            ),
+            (
+                &mut self.status_error_indicator_fg,
+                self.status_error_indicator_bg,
+            ),
+            (&mut self.settings_selected_fg, self.settings_selected_bg),
+            (&mut self.search_match_fg, self.search_match_bg),
+        ];
+        for (fg, bg) in pairs {
+            *fg = with_min_contrast(*fg, bg, ratio);
+        }
+    }
+
     /// Resolve a theme key to a Color.
     ///
     /// Theme keys use dot notation: "section.field"
@@ -1185,6 +1315,9 @@ impl Theme {
                 "diff_add_bg" => Some(self.diff_add_bg),
                 "diff_remove_bg" => Some(self.diff_remove_bg),
                 "diff_modify_bg" => Some(self.diff_modify_bg),
+                "ruler_bg" => Some(self.ruler_bg),
+                "whitespace_warning_bg" => Some(self.whitespace_warning_bg),
+                "line_length_warning_bg" => Some(self.line_length_warning_bg),
                 _ => None,
             },
             "ui" => match field {
@@ -1304,6 +1437,35 @@ mod tests {
         assert_eq!(theme.name, "test");
     }
 
+    #[test]
+    fn test_contrast_ratio() {
+        assert_eq!(contrast_ratio(Color::Black, Color::White), Some(21.0));
+        assert_eq!(contrast_ratio(Color::Red, Color::Red), Some(1.0));
+        assert_eq!(contrast_ratio(Color::Reset, Color::White), None);
+    }
+
+    #[test]
+    fn test_enforce_min_contrast() {
+        let mut theme = Theme::load_builtin(THEME_DARK).expect("Dark theme must exist");
+        theme.editor_bg = Color::Rgb(30, 30, 30);
+        theme.syntax_comment = Color::Rgb(90, 90, 90);
+        theme.status_bar_fg = Color::Reset;
+        let keyword = Color::Rgb(250, 250, 250);
+        theme.syntax_keyword = keyword;
+
+        theme.enforce_min_contrast(HIGH_CONTRAST_RATIO);
+        let comment = contrast_ratio(theme.syntax_comment, theme.editor_bg).unwrap();
+        assert!(comment >= HIGH_CONTRAST_RATIO, "comment contrast {comment}");
+        // Comments stay gray, only lighter
+        let Color::Rgb(r, g, b) = theme.syntax_comment else {
+            panic!("comment color should be RGB");
+        };
+        assert!(r == g && g == b && r > 90);
+        // Colors that already stand out, or have no RGB value, are kept
+        assert_eq!(theme.syntax_keyword, keyword);
+        assert_eq!(theme.status_bar_fg, Color::Reset);
+    }
+
     #[test]
     fn test_default_reset_color() {
         // Test that "Default" maps to Color::Reset
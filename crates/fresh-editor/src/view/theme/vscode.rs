@@ -0,0 +1,524 @@
+//! Conversion of VS Code (TextMate-based) color themes.
+//!
+//! VS Code themes have two parts:
+//! - `colors`: workbench colors keyed by UI element (`editor.background`, ...)
+//! - `tokenColors`: TextMate scope rules used for syntax highlighting
+//!
+//! [`import_vscode_theme`] maps both onto a [`ThemeFile`]. Syntax colors are
+//! resolved by looking up the TextMate scopes that correspond to each
+//! highlight category (the tree-sitter capture names). Anything the VS Code
+//! theme doesn't define is taken from the built-in dark, light or
+//! high-contrast theme, depending on the theme's `type`.
+//!
+//! This module is pure: resolving `include`d theme files is left to the caller.
+
+use serde_json::{json, Map, Value};
+
+use super::types::{ThemeFile, BUILTIN_THEMES, THEME_DARK, THEME_HIGH_CONTRAST, THEME_LIGHT};
+
+/// Workbench color mappings: (section, field, VS Code color keys in priority order)
+const COLOR_MAPPINGS: &[(&str, &str, &[&str])] = &[
+    ("editor", "bg", &["editor.background"]),
+    ("editor", "fg", &["editor.foreground", "foreground"]),
+    ("editor", "cursor", &["editorCursor.foreground"]),
+    ("editor", "selection_bg", &["editor.selectionBackground"]),
+    (
+        "editor",
+        "current_line_bg",
+        &["editor.lineHighlightBackground"],
+    ),
+    ("editor", "line_number_fg", &["editorLineNumber.foreground"]),
+    (
+        "editor",
+        "line_number_bg",
+        &["editorGutter.background", "editor.background"],
+    ),
+    (
+        "editor",
+        "diff_add_bg",
+        &[
+            "diffEditor.insertedLineBackground",
+            "diffEditor.insertedTextBackground",
+        ],
+    ),
+    (
+        "editor",
+        "diff_remove_bg",
+        &[
+            "diffEditor.removedLineBackground",
+            "diffEditor.removedTextBackground",
+        ],
+    ),
+    ("ui", "tab_active_fg", &["tab.activeForeground"]),
+    (
+        "ui",
+        "tab_active_bg",
+        &["tab.activeBackground", "editor.background"],
+    ),
+    ("ui", "tab_inactive_fg", &["tab.inactiveForeground"]),
+    (
+        "ui",
+        "tab_inactive_bg",
+        &["tab.inactiveBackground", "editorGroupHeader.tabsBackground"],
+    ),
+    (
+        "ui",
+        "tab_separator_bg",
+        &["editorGroupHeader.tabsBackground"],
+    ),
+    ("ui", "tab_hover_bg", &["tab.hoverBackground"]),
+    ("ui", "menu_bg", &["titleBar.activeBackground"]),
+    ("ui", "menu_fg", &["titleBar.activeForeground"]),
+    ("ui", "menu_active_bg", &["menubar.selectionBackground"]),
+    ("ui", "menu_active_fg", &["menubar.selectionForeground"]),
+    ("ui", "menu_dropdown_bg", &["menu.background"]),
+    ("ui", "menu_dropdown_fg", &["menu.foreground"]),
+    ("ui", "menu_highlight_bg", &["menu.selectionBackground"]),
+    ("ui", "menu_highlight_fg", &["menu.selectionForeground"]),
+    ("ui", "menu_hover_bg", &["menu.selectionBackground"]),
+    ("ui", "menu_hover_fg", &["menu.selectionForeground"]),
+    ("ui", "menu_border_fg", &["menu.border"]),
+    ("ui", "menu_separator_fg", &["menu.separatorBackground"]),
+    ("ui", "status_bar_fg", &["statusBar.foreground"]),
+    ("ui", "status_bar_bg", &["statusBar.background"]),
+    ("ui", "prompt_fg", &["input.foreground"]),
+    ("ui", "prompt_bg", &["input.background"]),
+    (
+        "ui",
+        "prompt_selection_fg",
+        &["list.activeSelectionForeground"],
+    ),
+    (
+        "ui",
+        "prompt_selection_bg",
+        &["list.activeSelectionBackground"],
+    ),
+    (
+        "ui",
+        "popup_border_fg",
+        &["editorWidget.border", "editorHoverWidget.border"],
+    ),
+    (
+        "ui",
+        "popup_bg",
+        &["editorWidget.background", "editorHoverWidget.background"],
+    ),
+    ("ui", "popup_text_fg", &["editorWidget.foreground"]),
+    (
+        "ui",
+        "popup_selection_fg",
+        &["list.activeSelectionForeground"],
+    ),
+    (
+        "ui",
+        "popup_selection_bg",
+        &["list.activeSelectionBackground"],
+    ),
+    ("ui", "suggestion_bg", &["editorSuggestWidget.background"]),
+    (
+        "ui",
+        "suggestion_selected_bg",
+        &["editorSuggestWidget.selectedBackground"],
+    ),
+    ("ui", "split_separator_fg", &["editorGroup.border"]),
+    ("ui", "scrollbar_thumb_fg", &["scrollbarSlider.background"]),
+    (
+        "ui",
+        "scrollbar_thumb_hover_fg",
+        &["scrollbarSlider.hoverBackground"],
+    ),
+    (
+        "ui",
+        "semantic_highlight_bg",
+        &["editor.wordHighlightBackground"],
+    ),
+    (
+        "ui",
+        "terminal_bg",
+        &["terminal.background", "panel.background"],
+    ),
+    ("ui", "terminal_fg", &["terminal.foreground"]),
+    (
+        "ui",
+        "settings_selected_fg",
+        &["list.activeSelectionForeground"],
+    ),
+    (
+        "ui",
+        "settings_selected_bg",
+        &["list.activeSelectionBackground"],
+    ),
+    (
+        "search",
+        "match_bg",
+        &[
+            "editor.findMatchBackground",
+            "editor.findMatchHighlightBackground",
+        ],
+    ),
+    ("diagnostic", "error_fg", &["editorError.foreground"]),
+    ("diagnostic", "warning_fg", &["editorWarning.foreground"]),
+    ("diagnostic", "info_fg", &["editorInfo.foreground"]),
+    ("diagnostic", "hint_fg", &["editorHint.foreground"]),
+];
+
+/// Syntax color mappings: (field, TextMate scopes in priority order)
+///
+/// Field names are the highlight categories that tree-sitter captures and
+/// TextMate scopes are mapped to.
+const SYNTAX_MAPPINGS: &[(&str, &[&str])] = &[
+    ("keyword", &["keyword.control", "keyword", "storage.type"]),
+    ("string", &["string.quoted", "string"]),
+    ("comment", &["comment.line", "comment"]),
+    (
+        "function",
+        &[
+            "entity.name.function",
+            "support.function",
+            "meta.function-call",
+        ],
+    ),
+    (
+        "type",
+        &[
+            "entity.name.type",
+            "entity.name.class",
+            "support.type",
+            "support.class",
+            "storage.type",
+        ],
+    ),
+    ("variable", &["variable.other", "variable"]),
+    (
+        "constant",
+        &["constant.numeric", "constant.language", "constant"],
+    ),
+    ("operator", &["keyword.operator"]),
+];
+
+/// Check whether a parsed JSON theme file is a VS Code theme.
+///
+/// VS Code themes have `colors` and/or `tokenColors`, while native themes
+/// have `editor` and `syntax` sections.
+pub fn is_vscode_theme(value: &Value) -> bool {
+    let Some(obj) = value.as_object() else {
+        return false;
+    };
+    (obj.contains_key("colors") || obj.contains_key("tokenColors"))
+        && !obj.contains_key("editor")
+        && !obj.contains_key("syntax")
+}
+
+/// Convert a VS Code color theme to a Fresh theme named `name`.
+pub fn import_vscode_theme(name: &str, value: &Value) -> Result<ThemeFile, String> {
+    if !is_vscode_theme(value) {
+        return Err("not a VS Code color theme".to_string());
+    }
+
+    let empty = Map::new();
+    let colors = value
+        .get("colors")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let token_colors = value
+        .get("tokenColors")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+
+    let color = |key: &str| colors.get(key).and_then(Value::as_str).and_then(parse_hex);
+    let global = global_token_settings(token_colors);
+
+    let editor_bg = color("editor.background")
+        .or_else(|| global.0.as_deref().and_then(parse_hex))
+        .map(|(r, g, b, _)| (r, g, b));
+    let base_name = base_theme_name(value.get("type").and_then(Value::as_str), editor_bg);
+    let mut theme = BUILTIN_THEMES
+        .iter()
+        .find(|t| t.name == base_name)
+        .and_then(|t| serde_json::from_str::<Value>(t.json).ok())
+        .unwrap_or_else(|| json!({}));
+    let bg = editor_bg
+        .or_else(|| color_from_value(&theme["editor"]["bg"]))
+        .unwrap_or((0, 0, 0));
+
+    let mut set = |section: &str, field: &str, rgba: (u8, u8, u8, u8)| {
+        let (r, g, b) = blend(rgba, bg);
+        if !theme[section].is_object() {
+            theme[section] = json!({});
+        }
+        theme[section][field] = json!([r, g, b]);
+    };
+
+    // Token colors without a scope set the default foreground and background
+    if let Some(rgba) = global.0.as_deref().and_then(parse_hex) {
+        set("editor", "bg", rgba);
+    }
+    if let Some(rgba) = global.1.as_deref().and_then(parse_hex) {
+        set("editor", "fg", rgba);
+    }
+
+    for (section, field, keys) in COLOR_MAPPINGS {
+        if let Some(rgba) = keys.iter().find_map(|key| color(key)) {
+            set(section, field, rgba);
+        }
+    }
+
+    for (field, scopes) in SYNTAX_MAPPINGS {
+        let found = scopes
+            .iter()
+            .find_map(|scope| token_foreground(token_colors, scope))
+            .and_then(|fg| parse_hex(&fg));
+        if let Some(rgba) = found {
+            set("syntax", field, rgba);
+        }
+    }
+
+    theme["name"] = json!(name);
+    serde_json::from_value(theme).map_err(|e| e.to_string())
+}
+
+/// Merge a VS Code theme with the theme it `include`s.
+///
+/// The including theme's colors win, and its token rules are applied after
+/// the included ones.
+pub fn merge_vscode_include(included: Value, theme: Value) -> Value {
+    let mut merged = included;
+    if !merged.is_object() {
+        merged = json!({});
+    }
+    let Value::Object(theme) = theme else {
+        return merged;
+    };
+    for (key, value) in theme {
+        match (key.as_str(), merged.get_mut(&key)) {
+            ("colors", Some(Value::Object(base))) => {
+                if let Value::Object(colors) = value {
+                    base.extend(colors);
+                }
+            }
+            ("tokenColors", Some(Value::Array(base))) => {
+                if let Value::Array(rules) = value {
+                    base.extend(rules);
+                }
+            }
+            ("include", _) => {}
+            _ => {
+                merged[key] = value;
+            }
+        }
+    }
+    merged
+}
+
+/// Pick the built-in theme used for colors the VS Code theme doesn't define
+fn base_theme_name(theme_type: Option<&str>, editor_bg: Option<(u8, u8, u8)>) -> &'static str {
+    match theme_type.map(str::to_ascii_lowercase).as_deref() {
+        Some("light") => THEME_LIGHT,
+        Some("dark") => THEME_DARK,
+        Some(t) if t.starts_with("hc") => THEME_HIGH_CONTRAST,
+        _ => match editor_bg {
+            Some((r, g, b)) if (r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000 > 128 => {
+                THEME_LIGHT
+            }
+            _ => THEME_DARK,
+        },
+    }
+}
+
+/// Background and foreground of the token rule without a scope, if any
+fn global_token_settings(token_colors: &[Value]) -> (Option<String>, Option<String>) {
+    let mut bg = None;
+    let mut fg = None;
+    for rule in token_colors {
+        if rule.get("scope").is_some() {
+            continue;
+        }
+        let settings = &rule["settings"];
+        if let Some(value) = settings["background"].as_str() {
+            bg = Some(value.to_string());
+        }
+        if let Some(value) = settings["foreground"].as_str() {
+            fg = Some(value.to_string());
+        }
+    }
+    (bg, fg)
+}
+
+/// Foreground of the token rule that best matches a TextMate scope.
+///
+/// A selector matches the scope if it is equal to it or a dot-separated
+/// prefix of it. The longest matching selector wins; among equally specific
+/// selectors the last rule wins, as in VS Code. Descendant selectors (with
+/// spaces) are ignored.
+fn token_foreground(token_colors: &[Value], scope: &str) -> Option<String> {
+    let mut best: Option<(usize, String)> = None;
+    for rule in token_colors {
+        let Some(foreground) = rule["settings"]["foreground"].as_str() else {
+            continue;
+        };
+        let selectors: Vec<&str> = match &rule["scope"] {
+            Value::String(s) => s.split(',').collect(),
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            _ => continue,
+        };
+        for selector in selectors {
+            let selector = selector.trim();
+            if selector.is_empty() || selector.contains(' ') {
+                continue;
+            }
+            let matches = scope == selector
+                || (scope.starts_with(selector) && scope[selector.len()..].starts_with('.'));
+            if matches && best.as_ref().is_none_or(|(len, _)| selector.len() >= *len) {
+                best = Some((selector.len(), foreground.to_string()));
+            }
+        }
+    }
+    best.map(|(_, foreground)| foreground)
+}
+
+/// Parse a `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` color
+fn parse_hex(s: &str) -> Option<(u8, u8, u8, u8)> {
+    let hex = s.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digits: Vec<u8> = match hex.len() {
+        3 | 4 => hex
+            .chars()
+            .map(|c| u8::from_str_radix(&c.to_string().repeat(2), 16).ok())
+            .collect::<Option<_>>()?,
+        6 | 8 => (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    Some((
+        digits[0],
+        digits[1],
+        digits[2],
+        *digits.get(3).unwrap_or(&255),
+    ))
+}
+
+/// Blend a color with an alpha channel over an opaque background
+fn blend((r, g, b, a): (u8, u8, u8, u8), (bg_r, bg_g, bg_b): (u8, u8, u8)) -> (u8, u8, u8) {
+    let mix = |fg: u8, bg: u8| ((fg as u32 * a as u32 + bg as u32 * (255 - a as u32)) / 255) as u8;
+    (mix(r, bg_r), mix(g, bg_g), mix(b, bg_b))
+}
+
+/// Read an `[r, g, b]` color from a native theme JSON value
+fn color_from_value(value: &Value) -> Option<(u8, u8, u8)> {
+    let rgb = value.as_array()?;
+    let channel = |i: usize| rgb.get(i)?.as_u64().map(|c| c as u8);
+    Some((channel(0)?, channel(1)?, channel(2)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::theme::{ColorDef, Theme};
+    use ratatui::style::Color;
+
+    fn sample_theme() -> Value {
+        json!({
+            "name": "Sample",
+            "type": "dark",
+            "colors": {
+                "editor.background": "#102030",
+                "editor.foreground": "#eeeeee",
+                "editor.selectionBackground": "#ffffff80",
+                "statusBar.background": "#abc"
+            },
+            "tokenColors": [
+                { "settings": { "foreground": "#dddddd" } },
+                { "scope": "comment", "settings": { "foreground": "#6a9955", "fontStyle": "italic" } },
+                { "scope": ["keyword", "storage"], "settings": { "foreground": "#569cd6" } },
+                { "scope": "keyword.control", "settings": { "foreground": "#c586c0" } },
+                { "scope": "string, string.quoted", "settings": { "foreground": "#ce9178" } },
+                { "scope": "source.rust entity.name.function", "settings": { "foreground": "#000000" } },
+                { "scope": "entity.name.function", "settings": { "foreground": "#dcdcaa" } }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_detects_vscode_themes() {
+        assert!(is_vscode_theme(&sample_theme()));
+        let native: Value = serde_json::from_str(BUILTIN_THEMES[0].json).unwrap();
+        assert!(!is_vscode_theme(&native));
+        assert!(import_vscode_theme("native", &native).is_err());
+    }
+
+    #[test]
+    fn test_imports_workbench_and_token_colors() {
+        let theme: Theme = import_vscode_theme("sample", &sample_theme())
+            .unwrap()
+            .into();
+        assert_eq!(theme.name, "sample");
+        assert_eq!(theme.editor_bg, Color::Rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.editor_fg, Color::Rgb(0xee, 0xee, 0xee));
+        assert_eq!(theme.status_bar_bg, Color::Rgb(0xaa, 0xbb, 0xcc));
+        // Translucent selection is blended over the editor background
+        assert_eq!(theme.selection_bg, Color::Rgb(0x87, 0x8f, 0x97));
+
+        // The most specific scope wins; descendant selectors are ignored
+        assert_eq!(theme.syntax_keyword, Color::Rgb(0xc5, 0x86, 0xc0));
+        assert_eq!(theme.syntax_comment, Color::Rgb(0x6a, 0x99, 0x55));
+        assert_eq!(theme.syntax_string, Color::Rgb(0xce, 0x91, 0x78));
+        assert_eq!(theme.syntax_function, Color::Rgb(0xdc, 0xdc, 0xaa));
+        // `storage.type` falls back to the `storage` rule
+        assert_eq!(theme.syntax_type, Color::Rgb(0x56, 0x9c, 0xd6));
+    }
+
+    #[test]
+    fn test_unset_colors_come_from_base_theme() {
+        let light = json!({
+            "type": "light",
+            "colors": { "editor.background": "#ffffff" }
+        });
+        let imported = import_vscode_theme("my-light", &light).unwrap();
+        let base: ThemeFile = serde_json::from_str(
+            BUILTIN_THEMES
+                .iter()
+                .find(|t| t.name == THEME_LIGHT)
+                .unwrap()
+                .json,
+        )
+        .unwrap();
+        let color = |c: ColorDef| Color::from(c);
+        assert_eq!(color(imported.ui.menu_bg), color(base.ui.menu_bg));
+        assert_eq!(color(imported.syntax.keyword), color(base.syntax.keyword));
+    }
+
+    #[test]
+    fn test_merge_include() {
+        let included = json!({
+            "colors": { "editor.background": "#000000", "editor.foreground": "#ffffff" },
+            "tokenColors": [{ "scope": "comment", "settings": { "foreground": "#111111" } }]
+        });
+        let theme = json!({
+            "include": "./base.json",
+            "colors": { "editor.background": "#222222" },
+            "tokenColors": [{ "scope": "comment", "settings": { "foreground": "#333333" } }]
+        });
+        let merged = merge_vscode_include(included, theme);
+        assert_eq!(merged["colors"]["editor.background"], "#222222");
+        assert_eq!(merged["colors"]["editor.foreground"], "#ffffff");
+        assert!(merged.get("include").is_none());
+
+        let theme: Theme = import_vscode_theme("merged", &merged).unwrap().into();
+        assert_eq!(theme.syntax_comment, Color::Rgb(0x33, 0x33, 0x33));
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("#abc"), Some((0xaa, 0xbb, 0xcc, 0xff)));
+        assert_eq!(parse_hex("#abc8"), Some((0xaa, 0xbb, 0xcc, 0x88)));
+        assert_eq!(parse_hex("#102030"), Some((0x10, 0x20, 0x30, 0xff)));
+        assert_eq!(parse_hex("#10203040"), Some((0x10, 0x20, 0x30, 0x40)));
+        assert_eq!(parse_hex("red"), None);
+        assert_eq!(parse_hex("#12345"), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Breadcrumb bar rendering, above each split's content
+//!
+//! The path of the buffer's file (relative to the working directory) is
+//! followed by the definitions around the cursor, from the buffer's outline.
+//! Clicking a crumb offers its siblings to go to: the other entries of the
+//! same directory, or the other definitions at the same level.
+
+use crate::primitives::display_width::str_width;
+use crate::primitives::outline::OutlineSymbol;
+use crate::view::ui::layout::point_in_rect;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use std::path::{Component, Path, PathBuf};
+
+/// Drawn between crumbs
+const SEPARATOR: &str = " › ";
+/// Drawn first when crumbs at the start don't fit
+const ELLIPSIS: &str = "…";
+
+/// What a crumb stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrumbTarget {
+    /// A directory, or the file itself
+    Path(PathBuf),
+    /// The definition at this depth around the cursor
+    Symbol(usize),
+    /// Nothing to go to, e.g. the name of a buffer without a file
+    None,
+}
+
+/// One segment of the breadcrumb bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Crumb {
+    pub label: String,
+    pub target: CrumbTarget,
+}
+
+/// The crumbs of a buffer: its file's path, then the definitions in `symbols`
+/// (outermost first)
+pub fn crumbs(
+    file_path: Option<&Path>,
+    display_name: &str,
+    working_dir: &Path,
+    symbols: &[&OutlineSymbol],
+) -> Vec<Crumb> {
+    let mut crumbs = Vec::new();
+    match file_path {
+        Some(path) => {
+            let (mut prefix, relative) = match path.strip_prefix(working_dir) {
+                Ok(relative) => (working_dir.to_path_buf(), relative),
+                Err(_) => (PathBuf::new(), path),
+            };
+            for component in relative.components() {
+                prefix.push(component);
+                if let Component::Normal(name) = component {
+                    crumbs.push(Crumb {
+                        label: name.to_string_lossy().into_owned(),
+                        target: CrumbTarget::Path(prefix.clone()),
+                    });
+                }
+            }
+        }
+        None => crumbs.push(Crumb {
+            label: display_name.to_string(),
+            target: CrumbTarget::None,
+        }),
+    }
+    crumbs.extend(symbols.iter().enumerate().map(|(depth, symbol)| Crumb {
+        label: symbol.name.clone(),
+        target: CrumbTarget::Symbol(depth),
+    }));
+    crumbs
+}
+
+/// Hit area for a single crumb
+#[derive(Debug, Clone)]
+pub struct CrumbHitArea {
+    pub target: CrumbTarget,
+    pub area: Rect,
+}
+
+/// Layout information for hit testing breadcrumb clicks
+///
+/// Returned by `BreadcrumbsRenderer::render()`.
+#[derive(Debug, Clone, Default)]
+pub struct BreadcrumbLayout {
+    /// Hit areas for each visible crumb
+    pub crumbs: Vec<CrumbHitArea>,
+    /// The full breadcrumb bar area
+    pub bar_area: Rect,
+}
+
+impl BreadcrumbLayout {
+    /// Whether the position is on the bar at all
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        point_in_rect(self.bar_area, x, y)
+    }
+
+    /// The crumb at the given position
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<&CrumbHitArea> {
+        self.crumbs
+            .iter()
+            .find(|crumb| point_in_rect(crumb.area, x, y))
+    }
+}
+
+/// Renders the breadcrumb bar
+pub struct BreadcrumbsRenderer;
+
+impl BreadcrumbsRenderer {
+    /// Render `crumbs` in `area`, dropping crumbs from the start if they
+    /// don't all fit: the ones nearest the cursor matter most
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        crumbs: &[Crumb],
+        theme: &crate::view::theme::Theme,
+        is_active: bool,
+    ) -> BreadcrumbLayout {
+        let base_style = Style::default().bg(theme.editor_bg);
+        let separator_style = base_style.fg(theme.line_number_fg);
+        let label_style = if is_active {
+            base_style.fg(theme.editor_fg)
+        } else {
+            base_style.fg(theme.line_number_fg)
+        };
+
+        let separator_width = str_width(SEPARATOR);
+        let widths: Vec<usize> = crumbs.iter().map(|crumb| str_width(&crumb.label)).collect();
+        let width_from = |first: usize| {
+            let labels: usize = widths[first..].iter().sum();
+            let separators = widths.len().saturating_sub(first + 1) * separator_width;
+            let ellipsis = if first > 0 {
+                str_width(ELLIPSIS) + separator_width
+            } else {
+                0
+            };
+            labels + separators + ellipsis
+        };
+        // One column is left blank before the first crumb
+        let available = (area.width as usize).saturating_sub(1);
+        let mut first = 0;
+        while first + 1 < crumbs.len() && width_from(first) > available {
+            first += 1;
+        }
+
+        let mut layout = BreadcrumbLayout {
+            crumbs: Vec::new(),
+            bar_area: area,
+        };
+        let mut spans = vec![Span::styled(" ", base_style)];
+        let mut x = area.x + 1;
+        if first > 0 {
+            spans.push(Span::styled(ELLIPSIS, separator_style));
+            spans.push(Span::styled(SEPARATOR, separator_style));
+            x += (str_width(ELLIPSIS) + separator_width) as u16;
+        }
+        let last = crumbs.len().saturating_sub(1);
+        for (index, crumb) in crumbs.iter().enumerate().skip(first) {
+            let style = if index == last {
+                label_style.add_modifier(Modifier::BOLD)
+            } else {
+                label_style
+            };
+            spans.push(Span::styled(crumb.label.clone(), style));
+
+            let right = area.x + area.width;
+            let width = (widths[index] as u16).min(right.saturating_sub(x));
+            layout.crumbs.push(CrumbHitArea {
+                target: crumb.target.clone(),
+                area: Rect::new(x, area.y, width, 1),
+            });
+            x = x.saturating_add(widths[index] as u16);
+            if index < last {
+                spans.push(Span::styled(SEPARATOR, separator_style));
+                x = x.saturating_add(separator_width as u16);
+            }
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)).style(base_style), area);
+        layout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crumbs() {
+        let symbol = OutlineSymbol {
+            name: "main".to_string(),
+            range: 0..10,
+            name_start: 3,
+            children: Vec::new(),
+        };
+        let result = crumbs(
+            Some(Path::new("/project/src/main.rs")),
+            "main.rs",
+            Path::new("/project"),
+            &[&symbol],
+        );
+        assert_eq!(
+            result,
+            vec![
+                Crumb {
+                    label: "src".to_string(),
+                    target: CrumbTarget::Path(PathBuf::from("/project/src")),
+                },
+                Crumb {
+                    label: "main.rs".to_string(),
+                    target: CrumbTarget::Path(PathBuf::from("/project/src/main.rs")),
+                },
+                Crumb {
+                    label: "main".to_string(),
+                    target: CrumbTarget::Symbol(0),
+                },
+            ]
+        );
+
+        // Outside the working directory the whole path is shown
+        let labels: Vec<String> = crumbs(
+            Some(Path::new("/etc/hosts")),
+            "hosts",
+            Path::new("/project"),
+            &[],
+        )
+        .into_iter()
+        .map(|crumb| crumb.label)
+        .collect();
+        assert_eq!(labels, vec!["etc", "hosts"]);
+    }
+}
@@ -4,6 +4,7 @@
 //! separated into focused submodules:
 //! - `menu` - Menu bar rendering
 //! - `tabs` - Tab bar rendering for multiple buffers
+//! - `breadcrumbs` - Breadcrumb bar rendering above each split
 //! - `status_bar` - Status bar and prompt/minibuffer display
 //! - `suggestions` - Autocomplete and command palette UI
 //! - `split_rendering` - Split pane layout and rendering
@@ -22,6 +23,8 @@ pub mod view_pipeline;
 
 // Runtime-only modules (depend on state, services, input, etc.)
 #[cfg(feature = "runtime")]
+pub mod breadcrumbs;
+#[cfg(feature = "runtime")]
 pub mod file_browser;
 #[cfg(feature = "runtime")]
 pub mod file_explorer;
@@ -40,6 +43,8 @@ pub mod tabs;
 
 // Re-export main types for convenience
 #[cfg(feature = "runtime")]
+pub use breadcrumbs::{BreadcrumbLayout, BreadcrumbsRenderer, Crumb, CrumbHitArea, CrumbTarget};
+#[cfg(feature = "runtime")]
 pub use file_browser::{FileBrowserLayout, FileBrowserRenderer};
 #[cfg(feature = "runtime")]
 pub use file_explorer::FileExplorerRenderer;
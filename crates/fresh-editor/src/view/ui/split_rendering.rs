@@ -4,14 +4,19 @@ use std::collections::BTreeMap;
 
 use crate::app::types::ViewLineMapping;
 use crate::app::BufferMetadata;
+use crate::config::LineNumberMode;
 use crate::model::buffer::Buffer;
 use crate::model::cursor::SelectionMode;
 use crate::model::event::{BufferId, EventLog, SplitDirection};
 use crate::primitives::ansi::AnsiParser;
 use crate::primitives::ansi_background::AnsiBackground;
 use crate::primitives::display_width::char_width;
+use crate::primitives::outline;
 use crate::state::{EditorState, ViewMode};
+use crate::view::conceal::apply_conceal_transform;
+use crate::view::markdown_preview::{render_markdown_preview, MarkdownPreview};
 use crate::view::split::SplitManager;
+use crate::view::ui::breadcrumbs::{self, BreadcrumbLayout, BreadcrumbsRenderer};
 use crate::view::ui::tabs::TabsRenderer;
 use crate::view::ui::view_pipeline::{
     should_show_line_number, LineStart, ViewLine, ViewLineIterator,
@@ -25,6 +30,7 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 use std::collections::{HashMap, HashSet};
 use std::ops::Range;
+use std::path::Path;
 
 /// Maximum line width before forced wrapping is applied, even when line wrapping is disabled.
 /// This prevents memory exhaustion when opening files with extremely long lines (e.g., 10MB
@@ -327,6 +333,10 @@ struct DecorationContext {
     diagnostic_lines: HashSet<usize>,
     /// Line indicators indexed by line number (highest priority indicator per line)
     line_indicators: BTreeMap<usize, crate::view::margin::LineIndicator>,
+    /// Trailing whitespace and mixed indentation to highlight
+    whitespace_warnings: Vec<Range<usize>>,
+    /// Characters past the maximum line length
+    line_length_warnings: Vec<Range<usize>>,
 }
 
 struct LineRenderOutput {
@@ -335,6 +345,8 @@ struct LineRenderOutput {
     last_line_end: Option<LastLineEnd>,
     content_lines_rendered: usize,
     view_line_mappings: Vec<ViewLineMapping>,
+    /// Rows showing the primary cursor's line, including wrapped continuations
+    cursor_line_rows: Vec<u16>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -345,6 +357,7 @@ struct LastLineEnd {
 
 struct SplitLayout {
     tabs_rect: Rect,
+    breadcrumbs_rect: Rect,
     content_rect: Rect,
     scrollbar_rect: Rect,
 }
@@ -374,8 +387,6 @@ struct LineRenderInput<'a> {
     estimated_lines: usize,
     /// Left column offset for horizontal scrolling
     left_column: usize,
-    /// Whether to show relative line numbers (distance from cursor)
-    relative_line_numbers: bool,
 }
 
 /// Context for computing the style of a single character
@@ -389,6 +400,8 @@ struct CharStyleContext<'a> {
     highlight_spans: &'a [crate::primitives::highlighter::HighlightSpan],
     semantic_token_spans: &'a [crate::primitives::highlighter::HighlightSpan],
     viewport_overlays: &'a [(crate::view::overlay::Overlay, Range<usize>)],
+    whitespace_warnings: &'a [Range<usize>],
+    line_length_warnings: &'a [Range<usize>],
     primary_cursor_position: usize,
     is_active: bool,
 }
@@ -411,8 +424,6 @@ struct LeftMarginContext<'a> {
     line_indicators: &'a BTreeMap<usize, crate::view::margin::LineIndicator>,
     /// Line number where the primary cursor is located (for relative line numbers)
     cursor_line: usize,
-    /// Whether to show relative line numbers
-    relative_line_numbers: bool,
 }
 
 /// Render the left margin (indicators + line numbers + separator) to line_spans
@@ -474,38 +485,25 @@ fn render_left_margin(
             Style::default().fg(ctx.theme.line_number_fg),
             None,
         );
-    } else if ctx.relative_line_numbers {
-        // Relative line numbers: show distance from cursor, or absolute for cursor line
-        let display_num = if ctx.current_source_line_num == ctx.cursor_line {
-            // Show absolute line number for the cursor line (1-indexed)
-            ctx.current_source_line_num + 1
-        } else {
-            // Show relative distance for other lines
-            ctx.current_source_line_num.abs_diff(ctx.cursor_line)
-        };
-        let rendered_text = format!(
-            "{:>width$}",
-            display_num,
-            width = ctx.state.margins.left_config.width
-        );
-        // Use brighter color for the cursor line
-        let margin_style = if ctx.current_source_line_num == ctx.cursor_line {
-            Style::default().fg(ctx.theme.editor_fg)
-        } else {
-            Style::default().fg(ctx.theme.line_number_fg)
-        };
-        push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
     } else {
         let margin_content = ctx.state.margins.render_line(
             ctx.current_source_line_num,
             crate::view::margin::MarginPosition::Left,
             ctx.estimated_lines,
+            ctx.cursor_line,
         );
         let (rendered_text, style_opt) = margin_content.render(ctx.state.margins.left_config.width);
 
         // Use custom style if provided, otherwise use default theme color
-        let margin_style =
-            style_opt.unwrap_or_else(|| Style::default().fg(ctx.theme.line_number_fg));
+        // (brighter for the cursor line when numbers are relative)
+        let margin_style = style_opt.unwrap_or_else(|| {
+            let left_config = &ctx.state.margins.left_config;
+            if left_config.highlights_cursor_line(ctx.current_source_line_num, ctx.cursor_line) {
+                Style::default().fg(ctx.theme.editor_fg)
+            } else {
+                Style::default().fg(ctx.theme.line_number_fg)
+            }
+        });
 
         push_span_with_map(line_spans, line_view_map, rendered_text, margin_style, None);
     }
@@ -656,6 +654,24 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
         }
     }
 
+    // Highlight characters past the maximum line length
+    if ctx.byte_pos.is_some_and(|bp| {
+        ctx.line_length_warnings
+            .iter()
+            .any(|range| range.contains(&bp))
+    }) {
+        style = style.bg(ctx.theme.line_length_warning_bg);
+    }
+
+    // Highlight trailing whitespace and mixed indentation
+    if ctx.byte_pos.is_some_and(|bp| {
+        ctx.whitespace_warnings
+            .iter()
+            .any(|range| range.contains(&bp))
+    }) {
+        style = style.bg(ctx.theme.whitespace_warning_bg);
+    }
+
     // Apply selection highlighting
     if ctx.is_selected {
         style = Style::default()
@@ -684,6 +700,27 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
     }
 }
 
+/// Glyph drawn in place of a whitespace character when whitespace rendering
+/// is on: » for tabs, · for spaces and ¶ (␍¶ in CRLF files) for line endings
+///
+/// Tabs are already expanded to spaces, so only the first column of a tab
+/// gets a glyph and the columns it fills stay blank.
+fn whitespace_glyph(
+    ch: char,
+    is_tab_start: bool,
+    is_tab_fill: bool,
+    crlf: bool,
+) -> Option<&'static str> {
+    match ch {
+        '\n' if crlf => Some("␍¶"),
+        '\n' => Some("¶"),
+        ' ' if is_tab_start => Some("»"),
+        ' ' if is_tab_fill => None,
+        ' ' => Some("·"),
+        _ => None,
+    }
+}
+
 /// Renders split panes and their content
 pub struct SplitRenderer;
 
@@ -703,6 +740,8 @@ impl SplitRenderer {
     /// * `line_wrap` - Whether line wrapping is enabled
     /// * `estimated_line_length` - Estimated average line length for large file line estimation
     /// * `hide_cursor` - Whether to hide the hardware cursor (e.g., when menu is open)
+    /// * `breadcrumbs_visible` - Whether to show the breadcrumb bar above each split's content
+    /// * `working_dir` - The directory breadcrumb paths are relative to
     ///
     /// # Returns
     /// * Vec of (split_id, buffer_id, content_rect, scrollbar_rect, thumb_start, thumb_end) for mouse handling
@@ -736,9 +775,12 @@ impl SplitRenderer {
         hovered_close_split: Option<crate::model::event::SplitId>,
         hovered_maximize_split: Option<crate::model::event::SplitId>,
         is_maximized: bool,
-        relative_line_numbers: bool,
+        line_number_mode: LineNumberMode,
         tab_bar_visible: bool,
+        breadcrumbs_visible: bool,
+        working_dir: &Path,
         use_terminal_bg: bool,
+        grammar_registry: &crate::primitives::grammar::GrammarRegistry,
     ) -> (
         Vec<(
             crate::model::event::SplitId,
@@ -752,6 +794,7 @@ impl SplitRenderer {
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // close split button areas
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // maximize split button areas
         HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>>, // view line mappings for mouse clicks
+        HashMap<crate::model::event::SplitId, BreadcrumbLayout>,     // breadcrumb layouts per split
     ) {
         let _span = tracing::trace_span!("render_content").entered();
 
@@ -770,13 +813,15 @@ impl SplitRenderer {
         let mut maximize_split_areas = Vec::new();
         let mut view_line_mappings: HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>> =
             HashMap::new();
+        let mut breadcrumb_layouts: HashMap<crate::model::event::SplitId, BreadcrumbLayout> =
+            HashMap::new();
 
         // Render each split
         for (split_id, buffer_id, split_area) in visible_buffers {
             let is_active = split_id == active_split_id;
 
-            let layout = Self::split_layout(split_area, tab_bar_visible);
-            let (split_buffers, tab_scroll_offset) =
+            let layout = Self::split_layout(split_area, tab_bar_visible, breadcrumbs_visible);
+            let (split_buffers, pinned_buffers, tab_scroll_offset) =
                 Self::split_buffers_for_tabs(split_view_states.as_deref(), split_id, buffer_id);
 
             // Determine hover state for this split's tabs
@@ -795,6 +840,7 @@ impl SplitRenderer {
                     frame,
                     layout.tabs_rect,
                     &split_buffers,
+                    &pinned_buffers,
                     buffers,
                     buffer_metadata,
                     composite_buffers,
@@ -855,6 +901,23 @@ impl SplitRenderer {
                 }
             }
 
+            if layout.breadcrumbs_rect.height > 0 {
+                if let Some(state) = buffers.get_mut(&buffer_id) {
+                    let breadcrumb_layout = Self::render_breadcrumbs(
+                        frame,
+                        layout.breadcrumbs_rect,
+                        state,
+                        buffer_metadata.get(&buffer_id),
+                        split_view_states.as_deref(),
+                        split_id,
+                        is_active,
+                        working_dir,
+                        theme,
+                    );
+                    breadcrumb_layouts.insert(split_id, breadcrumb_layout);
+                }
+            }
+
             // Get references separately to avoid double borrow
             let state_opt = buffers.get_mut(&buffer_id);
             let event_log_opt = event_logs.get_mut(&buffer_id);
@@ -942,6 +1005,63 @@ impl SplitRenderer {
                         });
                 let mut viewport = viewport_clone;
 
+                // The markdown preview replaces the buffer rendering. It uses the
+                // buffer's current cursors rather than this split's, so a preview
+                // beside the source follows the cursor being edited there.
+                let view_mode =
+                    Self::resolve_view_preferences(state, split_view_states.as_deref(), split_id)
+                        .view_mode;
+                if view_mode == ViewMode::Preview {
+                    Self::sync_viewport_to_content(
+                        &mut viewport,
+                        &mut state.buffer,
+                        &state.cursors,
+                        layout.content_rect,
+                    );
+                    let mut fallback = MarkdownPreview::default();
+                    let preview = match split_view_states.as_deref_mut() {
+                        Some(view_states) => match view_states.get_mut(&split_id) {
+                            Some(view_state) => {
+                                view_state.viewport = viewport.clone();
+                                &mut view_state.markdown_preview
+                            }
+                            None => &mut fallback,
+                        },
+                        None => &mut fallback,
+                    };
+                    Self::render_markdown_preview(
+                        frame,
+                        state,
+                        &viewport,
+                        preview,
+                        layout.content_rect,
+                        theme,
+                        grammar_registry,
+                        use_terminal_bg,
+                    );
+                    let (thumb_start, thumb_end) = Self::render_scrollbar(
+                        frame,
+                        state,
+                        &viewport,
+                        layout.scrollbar_rect,
+                        is_active,
+                        theme,
+                        large_file_threshold_bytes,
+                        preview.lines().len(),
+                        preview.top,
+                    );
+                    split_areas.push((
+                        split_id,
+                        buffer_id,
+                        layout.content_rect,
+                        layout.scrollbar_rect,
+                        thumb_start,
+                        thumb_end,
+                    ));
+                    view_line_mappings.insert(split_id, Vec::new());
+                    continue;
+                }
+
                 let saved_cursors = Self::temporary_split_state(
                     state,
                     split_view_states.as_deref(),
@@ -976,7 +1096,7 @@ impl SplitRenderer {
                     highlight_context_bytes,
                     buffer_id,
                     hide_cursor,
-                    relative_line_numbers,
+                    line_number_mode,
                     use_terminal_bg,
                 );
 
@@ -1048,9 +1168,43 @@ impl SplitRenderer {
             close_split_areas,
             maximize_split_areas,
             view_line_mappings,
+            breadcrumb_layouts,
         )
     }
 
+    /// Render a split's breadcrumb bar: the buffer's file path and the
+    /// definitions around the split's cursor
+    #[allow(clippy::too_many_arguments)]
+    fn render_breadcrumbs(
+        frame: &mut Frame,
+        area: Rect,
+        state: &mut EditorState,
+        metadata: Option<&BufferMetadata>,
+        split_view_states: Option<
+            &HashMap<crate::model::event::SplitId, crate::view::split::SplitViewState>,
+        >,
+        split_id: crate::model::event::SplitId,
+        is_active: bool,
+        working_dir: &Path,
+        theme: &crate::view::theme::Theme,
+    ) -> BreadcrumbLayout {
+        // Other splits keep their own cursors in their view state
+        let position = match split_view_states.and_then(|states| states.get(&split_id)) {
+            Some(view_state) if !is_active => view_state.cursors.primary().position,
+            _ => state.cursors.primary().position,
+        };
+        let file_path = metadata.and_then(|metadata| metadata.file_path());
+        let display_name = metadata.map_or("", |metadata| metadata.display_name.as_str());
+        let symbols = outline::symbol_path(state.outline(), position);
+        let crumbs = breadcrumbs::crumbs(
+            file_path.map(|path| path.as_path()),
+            display_name,
+            working_dir,
+            &symbols,
+        );
+        BreadcrumbsRenderer::render(frame, area, &crumbs, theme, is_active)
+    }
+
     /// Render a split separator line
     fn render_separator(
         frame: &mut Frame,
@@ -1720,26 +1874,44 @@ impl SplitRenderer {
         (thumb_start, thumb_end)
     }
 
-    fn split_layout(split_area: Rect, tab_bar_visible: bool) -> SplitLayout {
+    fn split_layout(
+        split_area: Rect,
+        tab_bar_visible: bool,
+        breadcrumbs_visible: bool,
+    ) -> SplitLayout {
         let tabs_height = if tab_bar_visible { 1u16 } else { 0u16 };
+        // The breadcrumb bar only takes a row the split can spare
+        let breadcrumbs_height = if breadcrumbs_visible && split_area.height > tabs_height + 1 {
+            1u16
+        } else {
+            0u16
+        };
+        let header_height = tabs_height + breadcrumbs_height;
         let scrollbar_width = 1u16;
 
         let tabs_rect = Rect::new(split_area.x, split_area.y, split_area.width, tabs_height);
-        let content_rect = Rect::new(
+        let breadcrumbs_rect = Rect::new(
             split_area.x,
             split_area.y + tabs_height,
+            split_area.width,
+            breadcrumbs_height,
+        );
+        let content_rect = Rect::new(
+            split_area.x,
+            split_area.y + header_height,
             split_area.width.saturating_sub(scrollbar_width),
-            split_area.height.saturating_sub(tabs_height),
+            split_area.height.saturating_sub(header_height),
         );
         let scrollbar_rect = Rect::new(
             split_area.x + split_area.width.saturating_sub(scrollbar_width),
-            split_area.y + tabs_height,
+            split_area.y + header_height,
             scrollbar_width,
-            split_area.height.saturating_sub(tabs_height),
+            split_area.height.saturating_sub(header_height),
         );
 
         SplitLayout {
             tabs_rect,
+            breadcrumbs_rect,
             content_rect,
             scrollbar_rect,
         }
@@ -1751,16 +1923,17 @@ impl SplitRenderer {
         >,
         split_id: crate::model::event::SplitId,
         buffer_id: BufferId,
-    ) -> (Vec<BufferId>, usize) {
+    ) -> (Vec<BufferId>, Vec<BufferId>, usize) {
         if let Some(view_states) = split_view_states {
             if let Some(view_state) = view_states.get(&split_id) {
                 return (
                     view_state.open_buffers.clone(),
+                    view_state.pinned_buffers.clone(),
                     view_state.tab_scroll_offset,
                 );
             }
         }
-        (vec![buffer_id], 0)
+        (vec![buffer_id], Vec::new(), 0)
     }
 
     fn temporary_split_state(
@@ -2010,6 +2183,11 @@ impl SplitRenderer {
         // Use plugin transform if available, otherwise use base tokens
         let mut tokens = view_transform.map(|vt| vt.tokens).unwrap_or(base_tokens);
 
+        // Conceal markup before wrapping so wrapped lines use the concealed width
+        if state.concealer.is_active() {
+            tokens = Self::apply_conceal(tokens, state);
+        }
+
         // Apply wrapping transform - always enabled for safety, but with different thresholds.
         // When line_wrap is on: wrap at viewport width for normal text flow.
         // When line_wrap is off: wrap at MAX_SAFE_LINE_WIDTH to prevent memory exhaustion
@@ -2027,15 +2205,51 @@ impl SplitRenderer {
         // Enable ANSI awareness for non-binary content to handle escape sequences correctly
         let is_binary = state.buffer.is_binary();
         let ansi_aware = !is_binary; // ANSI parsing for normal text files
-        let source_lines: Vec<ViewLine> =
+        let mut source_lines: Vec<ViewLine> =
             ViewLineIterator::new(&tokens, is_binary, ansi_aware, state.tab_size).collect();
 
+        // Show right-to-left text in display order
+        if state.bidi_text && !is_binary {
+            for line in &mut source_lines {
+                line.reorder_bidi();
+            }
+        }
+
         // Inject virtual lines (LineAbove/LineBelow) from VirtualTextManager
         let lines = Self::inject_virtual_lines(source_lines, state);
 
         ViewData { lines }
     }
 
+    /// Apply the buffer's conceal rules to the visible tokens, revealing the
+    /// lines that have a cursor
+    fn apply_conceal(
+        tokens: Vec<fresh_core::api::ViewTokenWire>,
+        state: &mut EditorState,
+    ) -> Vec<fresh_core::api::ViewTokenWire> {
+        use fresh_core::api::ViewTokenWireKind;
+
+        let Some(start) = tokens.iter().filter_map(|t| t.source_offset).min() else {
+            return tokens;
+        };
+        let end = tokens
+            .iter()
+            .filter_map(|t| match &t.kind {
+                ViewTokenWireKind::Text(s) => t.source_offset.map(|o| o + s.len()),
+                _ => t.source_offset.map(|o| o + 1),
+            })
+            .max()
+            .unwrap_or(start)
+            .min(state.buffer.len());
+
+        let source = state.buffer.slice_bytes(start..end);
+        let cursor_positions: Vec<usize> = state.cursors.iter().map(|(_, c)| c.position).collect();
+        let conceals = state
+            .concealer
+            .conceal_ranges(&source, start, &cursor_positions);
+        apply_conceal_transform(tokens, &conceals)
+    }
+
     /// Create a ViewLine from virtual text content (for LineAbove/LineBelow)
     fn create_virtual_line(text: &str, style: ratatui::style::Style) -> ViewLine {
         use fresh_core::api::ViewTokenStyle;
@@ -2816,10 +3030,10 @@ impl SplitRenderer {
             .filter_map(|(_, cursor)| {
                 if cursor.selection_mode == SelectionMode::Block {
                     if let Some(anchor) = cursor.block_anchor {
-                        // Convert cursor position to 2D coords
-                        let cur_line = state.buffer.get_line_number(cursor.position);
-                        let cur_line_start = state.buffer.line_start_offset(cur_line).unwrap_or(0);
-                        let cur_col = cursor.position.saturating_sub(cur_line_start);
+                        // Convert cursor position to (line, visual column)
+                        let (cur_line, cur_col) = state
+                            .buffer
+                            .position_to_line_visual_col(cursor.position, state.tab_size);
 
                         // Return normalized rectangle (min values first)
                         Some((
@@ -2949,6 +3163,18 @@ impl SplitRenderer {
             |byte_offset| state.buffer.get_line_number(byte_offset),
         );
 
+        let whitespace_warnings = if state.guides.highlights_whitespace() {
+            Self::whitespace_warnings(state, viewport_start, viewport_end, primary_cursor_position)
+        } else {
+            Vec::new()
+        };
+
+        let line_length_warnings = if state.guides.line_length_limit().is_some() {
+            Self::line_length_warnings(state, viewport_start, viewport_end)
+        } else {
+            Vec::new()
+        };
+
         DecorationContext {
             highlight_spans,
             semantic_token_spans,
@@ -2956,7 +3182,56 @@ impl SplitRenderer {
             virtual_text_lookup,
             diagnostic_lines,
             line_indicators,
+            whitespace_warnings,
+            line_length_warnings,
+        }
+    }
+
+    /// Trailing whitespace and mixed indentation in the visible lines
+    ///
+    /// Trailing whitespace right before the primary cursor is not highlighted,
+    /// so typing a space between words doesn't flash.
+    fn whitespace_warnings(
+        state: &mut EditorState,
+        viewport_start: usize,
+        viewport_end: usize,
+        primary_cursor_position: usize,
+    ) -> Vec<Range<usize>> {
+        let text = state.get_text_range(viewport_start, viewport_end);
+        let mut warnings = Vec::new();
+        let mut line_start = viewport_start;
+        for line in text.split_inclusive('\n') {
+            let content_end = line_start + line.trim_end_matches(['\n', '\r']).len();
+            warnings.extend(
+                state
+                    .guides
+                    .whitespace_ranges(line, line_start)
+                    .into_iter()
+                    .filter(|range| {
+                        !(range.end == content_end && range.end == primary_cursor_position)
+                    }),
+            );
+            line_start += line.len();
         }
+        warnings
+    }
+
+    /// The parts of the visible lines past the maximum line length
+    fn line_length_warnings(
+        state: &mut EditorState,
+        viewport_start: usize,
+        viewport_end: usize,
+    ) -> Vec<Range<usize>> {
+        let text = state.get_text_range(viewport_start, viewport_end);
+        let mut warnings = Vec::new();
+        let mut line_start = viewport_start;
+        for line in text.split_inclusive('\n') {
+            if let Some(overlong) = state.guides.overlong(line, state.tab_size) {
+                warnings.push(line_start + overlong.start..line_start + overlong.end);
+            }
+            line_start += line.len();
+        }
+        warnings
     }
 
     // semantic token colors are mapped when overlays are created
@@ -2998,7 +3273,6 @@ impl SplitRenderer {
             line_wrap,
             estimated_lines,
             left_column,
-            relative_line_numbers,
         } = input;
 
         let selection_ranges = &selection.ranges;
@@ -3018,6 +3292,7 @@ impl SplitRenderer {
 
         let mut lines = Vec::new();
         let mut view_line_mappings = Vec::new();
+        let mut cursor_line_rows = Vec::new();
         let mut lines_rendered = 0usize;
         let mut view_iter_idx = view_anchor.start_line_idx;
         let mut cursor_screen_x = 0u16;
@@ -3125,14 +3400,12 @@ impl SplitRenderer {
                     diagnostic_lines,
                     line_indicators,
                     cursor_line,
-                    relative_line_numbers,
                 },
                 &mut line_spans,
                 &mut line_view_map,
             );
 
             // Check if this line has any selected text
-            let mut byte_index = 0; // Byte offset in line_content string
             let mut display_char_idx = 0usize; // Character index in text (for char_source_bytes)
             let mut col_offset = 0usize; // Visual column position
 
@@ -3210,7 +3483,6 @@ impl SplitRenderer {
                                     have_cursor = true;
                                 }
                             }
-                            byte_index += ch.len_utf8();
                             display_char_idx += 1;
                             // Note: col_offset not incremented - ANSI chars have 0 visual width
                             continue;
@@ -3222,7 +3494,7 @@ impl SplitRenderer {
                 };
 
                 // Performance: skip expensive style calculations for characters beyond visible range
-                // Use visible_char_count (not byte_index) since ANSI codes don't take up visible space
+                // Use visible_char_count (not display_char_idx) since ANSI codes don't take up visible space
                 if visible_char_count > max_chars_to_process {
                     // Fast path: skip remaining characters without processing
                     // This is critical for performance with very long lines (e.g., 100KB single line)
@@ -3258,8 +3530,8 @@ impl SplitRenderer {
                         |(start_line, start_col, end_line, end_col)| {
                             current_source_line_num >= *start_line
                                 && current_source_line_num <= *end_line
-                                && byte_index >= *start_col
-                                && byte_index <= *end_col
+                                && col_offset >= *start_col
+                                && col_offset <= *end_col
                         },
                     );
 
@@ -3294,15 +3566,45 @@ impl SplitRenderer {
                         highlight_spans,
                         semantic_token_spans,
                         viewport_overlays,
+                        whitespace_warnings: &decorations.whitespace_warnings,
+                        line_length_warnings: &decorations.line_length_warnings,
                         primary_cursor_position,
                         is_active,
                     });
 
+                    // Whitespace rendering: swap whitespace for a dimmed glyph
+                    // (debug mode shows line endings its own way)
+                    let whitespace = byte_pos
+                        .filter(|_| {
+                            debug_tracker.is_none() && state.guides.renders_whitespace(is_selected)
+                        })
+                        .and_then(|bp| {
+                            let is_tab_fill = display_char_idx > 0
+                                && line_char_source_bytes
+                                    .get(display_char_idx - 1)
+                                    .copied()
+                                    .flatten()
+                                    == Some(bp);
+                            whitespace_glyph(
+                                ch,
+                                is_tab_start,
+                                is_tab_fill,
+                                state.buffer.line_ending()
+                                    == crate::model::buffer::LineEnding::CRLF,
+                            )
+                        });
+                    let style = match whitespace {
+                        Some(_) => style.fg(theme.line_number_fg),
+                        None => style,
+                    };
+
                     // Determine display character (tabs already expanded in ViewLineIterator)
                     // Show tab indicator (→) at the start of tab expansions (if enabled for this language)
                     let tab_indicator: String;
                     let display_char: &str = if is_cursor && lsp_waiting && is_active {
                         "⋯"
+                    } else if let Some(glyph) = whitespace {
+                        glyph
                     } else if debug_tracker.is_some() && ch == '\r' {
                         // Debug mode: show CR explicitly
                         "\\r"
@@ -3408,6 +3710,8 @@ impl SplitRenderer {
                                 .iter()
                                 .filter(|v| v.position == VirtualTextPosition::AfterChar)
                             {
+                                // Flush accumulated text before appending virtual text
+                                span_acc.flush(&mut line_spans, &mut line_view_map);
                                 let text_with_space = format!(" {}", vtext.text);
                                 push_span_with_map(
                                     &mut line_spans,
@@ -3420,7 +3724,8 @@ impl SplitRenderer {
                         }
                     }
 
-                    if is_cursor && ch == '\n' {
+                    // A drawn line ending glyph already shows the cursor
+                    if is_cursor && ch == '\n' && display_char.is_empty() {
                         let should_add_indicator =
                             if is_active { is_secondary_cursor } else { true };
                         if should_add_indicator {
@@ -3446,9 +3751,20 @@ impl SplitRenderer {
                             );
                         }
                     }
+                } else if col_offset + char_width(ch) > left_col {
+                    // A double-width character cut by the left edge: blank the half
+                    // still on screen so the following columns stay aligned
+                    for _ in left_col..col_offset + char_width(ch) {
+                        span_acc.push(
+                            ' ',
+                            Style::default(),
+                            byte_pos,
+                            &mut line_spans,
+                            &mut line_view_map,
+                        );
+                    }
                 }
 
-                byte_index += ch.len_utf8();
                 display_char_idx += 1; // Increment character index for next lookup
                                        // col_offset tracks visual column position (for indexing into visual_to_char)
                                        // visual_to_char has one entry per visual column, not per character
@@ -3675,6 +3991,12 @@ impl SplitRenderer {
                 line_end_byte,
             });
 
+            // Virtual lines below the cursor line don't belong to it
+            let has_source = line_char_source_bytes.iter().any(Option::is_some);
+            if current_source_line_num == cursor_line && (has_source || is_empty_buffer) {
+                cursor_line_rows.push(lines.len() as u16);
+            }
+
             // Track if line was empty before moving line_spans
             let line_was_empty = line_spans.is_empty();
             lines.push(Line::from(line_spans));
@@ -3743,11 +4065,18 @@ impl SplitRenderer {
                         implicit_line_num,
                         crate::view::margin::MarginPosition::Left,
                         estimated_lines,
+                        cursor_line,
                     );
                     let (rendered_text, style_opt) =
                         margin_content.render(state.margins.left_config.width);
-                    let margin_style =
-                        style_opt.unwrap_or_else(|| Style::default().fg(theme.line_number_fg));
+                    let margin_style = style_opt.unwrap_or_else(|| {
+                        let left_config = &state.margins.left_config;
+                        if left_config.highlights_cursor_line(implicit_line_num, cursor_line) {
+                            Style::default().fg(theme.editor_fg)
+                        } else {
+                            Style::default().fg(theme.line_number_fg)
+                        }
+                    });
                     implicit_line_spans.push(Span::styled(rendered_text, margin_style));
 
                     // Separator
@@ -3760,6 +4089,9 @@ impl SplitRenderer {
                 }
 
                 let implicit_y = lines.len() as u16;
+                if implicit_line_num == cursor_line {
+                    cursor_line_rows.push(implicit_y);
+                }
                 lines.push(Line::from(implicit_line_spans));
                 lines_rendered += 1;
 
@@ -3812,6 +4144,7 @@ impl SplitRenderer {
             last_line_end,
             content_lines_rendered: lines_rendered,
             view_line_mappings,
+            cursor_line_rows,
         }
     }
 
@@ -3862,7 +4195,7 @@ impl SplitRenderer {
         highlight_context_bytes: usize,
         _buffer_id: BufferId,
         hide_cursor: bool,
-        relative_line_numbers: bool,
+        line_number_mode: LineNumberMode,
         use_terminal_bg: bool,
     ) -> Vec<ViewLineMapping> {
         let _span = tracing::trace_span!("render_buffer_in_split").entered();
@@ -3886,6 +4219,7 @@ impl SplitRenderer {
         let buffer_len = state.buffer.len();
         let estimated_lines = (buffer_len / 80).max(1);
         state.margins.update_width_for_buffer(estimated_lines);
+        state.margins.set_line_number_mode(line_number_mode);
         let gutter_width = state.margins.left_total_width();
 
         let compose_layout = Self::calculate_compose_layout(area, &view_mode, compose_width);
@@ -4034,7 +4368,6 @@ impl SplitRenderer {
             line_wrap,
             estimated_lines,
             left_column: viewport.left_column,
-            relative_line_numbers,
         });
 
         let mut lines = render_output.lines;
@@ -4083,6 +4416,17 @@ impl SplitRenderer {
             }
         }
 
+        Self::render_rulers(
+            frame,
+            &state.guides.rulers,
+            render_area,
+            gutter_width,
+            viewport.left_column,
+            render_output.content_lines_rendered,
+            effective_editor_bg,
+            theme.ruler_bg,
+        );
+
         let buffer_ends_with_newline = if !state.buffer.is_empty() {
             let last_char = state.get_text_range(state.buffer.len() - 1, state.buffer.len());
             last_char == "\n"
@@ -4100,6 +4444,20 @@ impl SplitRenderer {
             gutter_width,
         );
 
+        if is_active && state.show_cursors {
+            Self::render_cursor_highlights(
+                frame,
+                &state.guides,
+                render_area,
+                gutter_width,
+                &render_output.cursor_line_rows,
+                cursor.map(|(x, _)| x),
+                render_output.content_lines_rendered,
+                effective_editor_bg,
+                theme.current_line_bg,
+            );
+        }
+
         if is_active && state.show_cursors && !hide_cursor {
             if let Some((cursor_screen_x, cursor_screen_y)) = cursor {
                 // cursor_screen_x already includes gutter width from line_view_map
@@ -4128,6 +4486,163 @@ impl SplitRenderer {
         render_output.view_line_mappings
     }
 
+    /// Draw rulers by tinting the background of their columns
+    ///
+    /// Only cells with the plain editor background are tinted, so selections
+    /// and other highlights stay visible where a ruler crosses them.
+    #[allow(clippy::too_many_arguments)]
+    fn render_rulers(
+        frame: &mut Frame,
+        rulers: &[usize],
+        render_area: Rect,
+        gutter_width: usize,
+        left_column: usize,
+        content_lines_rendered: usize,
+        editor_bg: Color,
+        ruler_bg: Color,
+    ) {
+        let content_x = render_area.x as usize + gutter_width;
+        let area_end = render_area.x as usize + render_area.width as usize;
+        let rows = content_lines_rendered.min(render_area.height as usize) as u16;
+        let buf = frame.buffer_mut();
+
+        for &column in rulers {
+            // Rulers scrolled off to the left are not drawn
+            let Some(offset) = column.checked_sub(left_column) else {
+                continue;
+            };
+            let x = content_x + offset;
+            if x >= area_end {
+                continue;
+            }
+            for row in 0..rows {
+                if let Some(cell) = buf.cell_mut((x as u16, render_area.y + row)) {
+                    if cell.bg == editor_bg {
+                        cell.set_bg(ruler_bg);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tint the cursor's line and column
+    ///
+    /// Like rulers, only cells with the plain editor background are tinted,
+    /// so selections, diagnostics and other highlights win over it.
+    #[allow(clippy::too_many_arguments)]
+    fn render_cursor_highlights(
+        frame: &mut Frame,
+        guides: &crate::view::visual_guides::VisualGuides,
+        render_area: Rect,
+        gutter_width: usize,
+        cursor_line_rows: &[u16],
+        cursor_x: Option<u16>,
+        content_lines_rendered: usize,
+        editor_bg: Color,
+        highlight_bg: Color,
+    ) {
+        let content_x = render_area.x + gutter_width as u16;
+        let area_end = render_area.x + render_area.width;
+        let rows = content_lines_rendered.min(render_area.height as usize) as u16;
+        let buf = frame.buffer_mut();
+        let mut tint = |x: u16, y: u16| {
+            if let Some(cell) = buf.cell_mut((x, render_area.y + y)) {
+                // Reversed cells are cursors, drawn in the inverted colors
+                if cell.bg == editor_bg && !cell.modifier.contains(Modifier::REVERSED) {
+                    cell.set_bg(highlight_bg);
+                }
+            }
+        };
+
+        if guides.current_line {
+            for &row in cursor_line_rows.iter().filter(|&&row| row < rows) {
+                for x in content_x..area_end {
+                    tint(x, row);
+                }
+            }
+        }
+
+        if guides.current_column {
+            // The cursor x includes the gutter
+            let column = cursor_x
+                .map(|x| render_area.x + x)
+                .filter(|x| (content_x..area_end).contains(x));
+            if let Some(x) = column {
+                for row in 0..rows {
+                    tint(x, row);
+                }
+            }
+        }
+    }
+
+    /// Render the markdown preview of a buffer, scrolled to follow the cursor
+    #[allow(clippy::too_many_arguments)]
+    fn render_markdown_preview(
+        frame: &mut Frame,
+        state: &EditorState,
+        viewport: &crate::view::viewport::Viewport,
+        preview: &mut MarkdownPreview,
+        area: Rect,
+        theme: &crate::view::theme::Theme,
+        grammar_registry: &crate::primitives::grammar::GrammarRegistry,
+        use_terminal_bg: bool,
+    ) {
+        let editor_bg = if use_terminal_bg {
+            Color::Reset
+        } else {
+            theme.editor_bg
+        };
+        // One column of padding on each side
+        let text_area = Rect::new(
+            area.x.saturating_add(1),
+            area.y,
+            area.width.saturating_sub(2),
+            area.height,
+        );
+
+        preview.update(
+            state.buffer.revision(),
+            text_area.width as usize,
+            theme,
+            || {
+                let text = state.buffer.to_string().unwrap_or_default();
+                render_markdown_preview(
+                    &text,
+                    text_area.width as usize,
+                    theme,
+                    Some(grammar_registry),
+                )
+            },
+        );
+        let cursor_line = state
+            .buffer
+            .get_line_number(state.cursors.primary().position);
+        let top_line = state.buffer.get_line_number(viewport.top_byte);
+        preview.follow(cursor_line, top_line, area.height as usize);
+
+        let lines: Vec<Line<'static>> = preview
+            .lines()
+            .iter()
+            .skip(preview.top)
+            .take(area.height as usize)
+            .map(|preview_line| {
+                Line::from(
+                    preview_line
+                        .line
+                        .spans
+                        .iter()
+                        .map(|span| Span::styled(span.text.clone(), span.style))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+
+        frame.render_widget(Clear, area);
+        let background = Block::default().style(Style::default().bg(editor_bg).fg(theme.editor_fg));
+        frame.render_widget(background, area);
+        frame.render_widget(Paragraph::new(lines), text_area);
+    }
+
     /// Apply styles from original line_spans to a wrapped segment
     ///
     /// Maps each character in the segment text back to its original span to preserve
@@ -4308,7 +4823,6 @@ mod tests {
             line_wrap: viewport.line_wrap_enabled,
             estimated_lines,
             left_column: viewport.left_column,
-            relative_line_numbers: false,
         });
 
         (
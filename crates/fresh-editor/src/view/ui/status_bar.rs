@@ -20,6 +20,8 @@ pub struct StatusBarLayout {
     pub lsp_indicator: Option<(u16, u16, u16)>,
     /// Warning badge area (row, start_col, end_col) - None if no warnings
     pub warning_badge: Option<(u16, u16, u16)>,
+    /// Indentation indicator area (row, start_col, end_col)
+    pub indentation_indicator: Option<(u16, u16, u16)>,
     /// Line ending indicator area (row, start_col, end_col)
     pub line_ending_indicator: Option<(u16, u16, u16)>,
     /// Encoding indicator area (row, start_col, end_col)
@@ -28,6 +30,8 @@ pub struct StatusBarLayout {
     pub language_indicator: Option<(u16, u16, u16)>,
     /// Status message area (row, start_col, end_col) - clickable to show full history
     pub message_area: Option<(u16, u16, u16)>,
+    /// Cursor position ("Ln, Col") area (row, start_col, end_col) - None if not shown
+    pub position_indicator: Option<(u16, u16, u16)>,
 }
 
 /// Status bar hover state for styling clickable indicators
@@ -39,6 +43,8 @@ pub enum StatusBarHover {
     LspIndicator,
     /// Mouse is over the warning badge
     WarningBadge,
+    /// Mouse is over the indentation indicator
+    IndentationIndicator,
     /// Mouse is over the line ending indicator
     LineEndingIndicator,
     /// Mouse is over the encoding indicator
@@ -47,6 +53,8 @@ pub enum StatusBarHover {
     LanguageIndicator,
     /// Mouse is over the status message area
     MessageArea,
+    /// Mouse is over the cursor position
+    PositionIndicator,
 }
 
 /// Which search option checkbox is being hovered
@@ -242,6 +250,16 @@ pub fn truncate_path(path: &Path, max_len: usize) -> TruncatedPath {
     }
 }
 
+/// How a buffer is indented, as shown in the status bar: "Spaces: 4" or
+/// "Tab Size: 8"
+pub fn indentation_label(use_tabs: bool, tab_size: usize) -> String {
+    if use_tabs {
+        t!("status.indent_tabs", size = tab_size).to_string()
+    } else {
+        t!("status.indent_spaces", size = tab_size).to_string()
+    }
+}
+
 /// Renders the status bar and prompt/minibuffer
 pub struct StatusBarRenderer;
 
@@ -261,6 +279,9 @@ impl StatusBarRenderer {
     /// * `warning_level` - LSP warning level (for coloring LSP indicator)
     /// * `general_warning_count` - Number of general warnings (for badge display)
     /// * `remote_connection` - Optional remote connection info (e.g., "user@host")
+    /// * `auto_save` - Whether the buffer is auto-saved to disk
+    /// * `read_only` - Whether the file buffer is read-only
+    /// * `degraded` - Whether decorations are turned off because frames take too long
     ///
     /// # Returns
     /// Layout information with positions of clickable indicators
@@ -281,6 +302,9 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        auto_save: bool,
+        read_only: bool,
+        degraded: bool,
     ) -> StatusBarLayout {
         Self::render_status(
             frame,
@@ -298,6 +322,9 @@ impl StatusBarRenderer {
             general_warning_count,
             hover,
             remote_connection,
+            auto_save,
+            read_only,
+            degraded,
         )
     }
 
@@ -308,6 +335,13 @@ impl StatusBarRenderer {
         prompt: &Prompt,
         theme: &crate::view::theme::Theme,
     ) {
+        let masked;
+        let prompt = if prompt.is_secret() {
+            masked = prompt.masked();
+            &masked
+        } else {
+            prompt
+        };
         let base_style = Style::default().fg(theme.prompt_fg).bg(theme.prompt_bg);
 
         // Create spans for the prompt
@@ -481,17 +515,23 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        auto_save: bool,
+        read_only: bool,
+        degraded: bool,
     ) -> StatusBarLayout {
         // Initialize layout tracking
         let mut layout = StatusBarLayout::default();
         // Use the pre-computed display name from buffer metadata
         let filename = display_name;
 
-        let modified = if state.buffer.is_modified() {
-            " [+]"
-        } else {
-            ""
+        let modified = match (state.buffer.is_modified(), auto_save) {
+            (true, true) => " [+] [auto]",
+            (true, false) => " [+]",
+            (false, true) => " [auto]",
+            (false, false) => "",
         };
+        let read_only = if read_only { " [RO]" } else { "" };
+        let degraded = if degraded { " [degraded]" } else { "" };
 
         // Format chord state if present
         let chord_display = if !chord_state.is_empty() {
@@ -597,16 +637,19 @@ impl StatusBarRenderer {
         let remote_prefix = remote_connection
             .map(|conn| format!("[SSH:{}] ", conn))
             .unwrap_or_default();
+        let file_status = format!("{remote_prefix}{filename}{modified}{read_only}{degraded}");
+        let position_text = format!("Ln {}, Col {}", line + 1, col + 1);
         let base_status = if state.show_cursors {
-            format!(
-                "{remote_prefix}{filename}{modified} | Ln {}, Col {}{diagnostics_summary}{cursor_count_indicator}",
-                line + 1,
-                col + 1
-            )
+            format!("{file_status} | {position_text}{diagnostics_summary}{cursor_count_indicator}")
         } else {
             // Virtual buffer - just show filename and modified indicator
-            format!("{remote_prefix}{filename}{modified}{diagnostics_summary}")
+            format!("{file_status}{diagnostics_summary}")
         };
+        // Byte range of the position in the left status, for click detection
+        let position_range = state.show_cursors.then(|| {
+            let start = file_status.len() + " | ".len();
+            start..start + position_text.len()
+        });
 
         // Track where the message starts for click detection
         let base_and_chord_width = str_width(&base_status) + str_width(&chord_display);
@@ -615,9 +658,13 @@ impl StatusBarRenderer {
         let left_status = format!("{base_status}{chord_display}{message_suffix}");
 
         // Build right-side indicators (these stay fixed on the right)
-        // Order: [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Order: [Indentation] [Line ending] [Language] [LSP indicator] [warning badge] [update] [Palette]
         // Note: Remote indicator is now on the left side, before the filename
 
+        // Indentation indicator (clickable to change indentation)
+        let indentation_text = format!(" {} ", indentation_label(state.use_tabs, state.tab_size));
+        let indentation_width = str_width(&indentation_text);
+
         // Line ending indicator (clickable to change format)
         let line_ending_text = format!(" {} ", state.buffer.line_ending().display_name());
         let line_ending_width = str_width(&line_ending_text);
@@ -664,16 +711,23 @@ impl StatusBarRenderer {
         let padded_cmd_palette = format!(" {} ", cmd_palette_indicator);
 
         // Calculate available width and right side width
-        // Right side: [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
+        // Right side: [Indentation] [Line ending] [Encoding] [Language] [LSP indicator] [warning badge] [update] [Palette]
         let available_width = area.width as usize;
         let cmd_palette_width = str_width(&padded_cmd_palette);
-        let right_side_width = line_ending_width
+        let mut right_side_width = line_ending_width
             + encoding_width
             + language_width
             + lsp_indicator_width
             + warning_badge_width
             + update_width
             + cmd_palette_width;
+        // The indentation indicator is the first to go when space is short,
+        // so that it never truncates the status message
+        let show_indentation =
+            str_width(&left_status) + indentation_width + right_side_width < available_width;
+        if show_indentation {
+            right_side_width += indentation_width;
+        }
 
         // Only show command palette indicator if there's enough space (at least 15 chars for minimal display)
         let spans = if available_width >= 15 {
@@ -726,12 +780,41 @@ impl StatusBarRenderer {
                 }
             }
 
-            spans.push(Span::styled(
-                displayed_left.clone(),
-                Style::default()
-                    .fg(theme.status_bar_fg)
-                    .bg(theme.status_bar_bg),
-            ));
+            // Track the cursor position for click detection (opens Go to Line),
+            // unless truncation cut it off
+            let position_range = position_range
+                .filter(|range| displayed_left.starts_with(&left_status[..range.end]));
+            if let Some(range) = &position_range {
+                let start = str_width(&left_status[..range.start]);
+                let end = start + str_width(&position_text);
+                layout.position_indicator =
+                    Some((area.y, area.x + start as u16, area.x + end as u16));
+            }
+
+            let left_style = Style::default()
+                .fg(theme.status_bar_fg)
+                .bg(theme.status_bar_bg);
+            match position_range.filter(|_| hover == StatusBarHover::PositionIndicator) {
+                Some(range) => {
+                    let hover_style = Style::default()
+                        .fg(theme.menu_hover_fg)
+                        .bg(theme.menu_hover_bg)
+                        .add_modifier(Modifier::UNDERLINED);
+                    spans.push(Span::styled(
+                        displayed_left[..range.start].to_string(),
+                        left_style,
+                    ));
+                    spans.push(Span::styled(
+                        displayed_left[range.clone()].to_string(),
+                        hover_style,
+                    ));
+                    spans.push(Span::styled(
+                        displayed_left[range.end..].to_string(),
+                        left_style,
+                    ));
+                }
+                None => spans.push(Span::styled(displayed_left.clone(), left_style)),
+            }
 
             // Add spacing to push right side indicators to the right
             if displayed_left_len + right_side_width < available_width {
@@ -758,6 +841,25 @@ impl StatusBarRenderer {
                 current_col = area.x + (available_width - right_side_width) as u16;
             }
 
+            // Add indentation indicator (clickable to change indentation)
+            if show_indentation {
+                let is_hovering = hover == StatusBarHover::IndentationIndicator;
+                // Record position for click detection
+                layout.indentation_indicator =
+                    Some((area.y, current_col, current_col + indentation_width as u16));
+                let (fg, bg) = if is_hovering {
+                    (theme.menu_hover_fg, theme.menu_hover_bg)
+                } else {
+                    (theme.status_bar_fg, theme.status_bar_bg)
+                };
+                let mut style = Style::default().fg(fg).bg(bg);
+                if is_hovering {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                spans.push(Span::styled(indentation_text.clone(), style));
+                current_col += indentation_width as u16;
+            }
+
             // Add line ending indicator (clickable to change format)
             {
                 let is_hovering = hover == StatusBarHover::LineEndingIndicator;
@@ -2,7 +2,7 @@
 
 use crate::app::BufferMetadata;
 use crate::model::event::BufferId;
-use crate::primitives::display_width::str_width;
+use crate::primitives::display_width::{char_width, str_width};
 use crate::state::EditorState;
 use crate::view::ui::layout::point_in_rect;
 use ratatui::layout::Rect;
@@ -116,6 +116,40 @@ impl TabLayout {
 /// Renders the tab bar showing open buffers
 pub struct TabsRenderer;
 
+/// Mark shown on pinned tabs
+const PIN_MARK: &str = "⚑";
+
+/// Widest the name on a pinned tab gets
+const PINNED_NAME_MAX_WIDTH: usize = 12;
+
+/// The text of a tab's name and close button: " {name}{modified}{binary} "
+/// and "× ". Pinned tabs are compact: a pin mark, a shortened name and no
+/// close button.
+fn tab_texts(
+    name: &str,
+    modified: &str,
+    binary_indicator: &str,
+    pinned: bool,
+) -> (String, &'static str) {
+    if !pinned {
+        return (format!(" {name}{modified}{binary_indicator} "), "× ");
+    }
+    let name = if str_width(name) > PINNED_NAME_MAX_WIDTH {
+        let mut short = String::new();
+        for c in name.chars() {
+            if str_width(&short) + char_width(c) >= PINNED_NAME_MAX_WIDTH {
+                break;
+            }
+            short.push(c);
+        }
+        short.push('…');
+        short
+    } else {
+        name.to_string()
+    };
+    (format!(" {PIN_MARK}{name}{modified} "), "")
+}
+
 /// Compute scroll offset to bring the active tab into view.
 /// Always scrolls to put the active tab at a comfortable position.
 /// `tab_widths` includes separators between tabs.
@@ -162,6 +196,7 @@ pub fn scroll_to_show_tab(
 /// This uses the same logic as render_for_split to ensure consistency.
 pub fn calculate_tab_widths(
     split_buffers: &[BufferId],
+    pinned_buffers: &[BufferId],
     buffers: &HashMap<BufferId, EditorState>,
     buffer_metadata: &HashMap<BufferId, BufferMetadata>,
     composite_buffers: &HashMap<BufferId, crate::model::composite_buffer::CompositeBuffer>,
@@ -225,9 +260,13 @@ pub fn calculate_tab_widths(
             ""
         };
 
-        // Same format as render_for_split: " {name}{modified}{binary_indicator} " + "× "
-        let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
-        let close_text = "× ";
+        // Same format as render_for_split
+        let (tab_name_text, close_text) = tab_texts(
+            name,
+            modified,
+            binary_indicator,
+            pinned_buffers.contains(id),
+        );
         let tab_width = str_width(&tab_name_text) + str_width(close_text);
 
         // Add separator if not first tab
@@ -249,6 +288,7 @@ impl TabsRenderer {
     /// * `frame` - The ratatui frame to render to
     /// * `area` - The rectangular area to render the tabs in
     /// * `split_buffers` - List of buffer IDs open in this split (in order)
+    /// * `pinned_buffers` - Buffer IDs whose tabs are pinned in this split
     /// * `buffers` - All open buffers (for accessing state/metadata)
     /// * `buffer_metadata` - Metadata for buffers (contains display names for virtual buffers)
     /// * `active_buffer` - The currently active buffer ID for this split
@@ -263,6 +303,7 @@ impl TabsRenderer {
         frame: &mut Frame,
         area: Rect,
         split_buffers: &[BufferId],
+        pinned_buffers: &[BufferId],
         buffers: &HashMap<BufferId, EditorState>,
         buffer_metadata: &HashMap<BufferId, BufferMetadata>,
         composite_buffers: &HashMap<BufferId, crate::model::composite_buffer::CompositeBuffer>,
@@ -379,12 +420,14 @@ impl TabsRenderer {
                 base_style
             };
 
-            // Build tab content: " {name}{modified}{binary_indicator} "
-            let tab_name_text = format!(" {name}{modified}{binary_indicator} ");
+            // Build tab content and close button
+            let (tab_name_text, close_text) = tab_texts(
+                name,
+                modified,
+                binary_indicator,
+                pinned_buffers.contains(id),
+            );
             let tab_name_width = str_width(&tab_name_text);
-
-            // Close button: "× "
-            let close_text = "× ";
             let close_width = str_width(close_text);
 
             let total_width = tab_name_width + close_width;
@@ -640,6 +683,7 @@ impl TabsRenderer {
             frame,
             area,
             &buffer_ids,
+            &[],
             buffers,
             buffer_metadata,
             composite_buffers,
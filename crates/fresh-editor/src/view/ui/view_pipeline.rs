@@ -21,6 +21,7 @@
 //! not reconstructed from flattened text.
 
 use crate::primitives::ansi::AnsiParser;
+use crate::primitives::bidi;
 use crate::primitives::display_width::char_width;
 use fresh_core::api::{ViewTokenStyle, ViewTokenWire, ViewTokenWireKind};
 use std::collections::HashSet;
@@ -88,6 +89,72 @@ impl ViewLine {
     pub fn visual_width(&self) -> usize {
         self.visual_to_char.len()
     }
+
+    /// Reorder a line containing right-to-left text into display order
+    ///
+    /// Characters keep their source bytes and styles, so the cursor and mouse
+    /// clicks follow them to their new columns. A trailing newline stays at the
+    /// end. Lines without right-to-left text or with ANSI escapes are unchanged.
+    pub fn reorder_bidi(&mut self) {
+        if !bidi::has_rtl(&self.text) || self.text.contains('\x1b') {
+            return;
+        }
+        let chars: Vec<char> = self.text.chars().collect();
+        let content_len = chars.len() - usize::from(chars.last() == Some(&'\n'));
+        let levels = bidi::resolve_levels(&chars[..content_len]);
+        let mut order = bidi::visual_order(&levels);
+        order.extend(content_len..chars.len());
+
+        let width_of = |idx: usize| {
+            let next_col = self
+                .char_visual_cols
+                .get(idx + 1)
+                .copied()
+                .unwrap_or(self.visual_to_char.len());
+            next_col - self.char_visual_cols[idx]
+        };
+
+        let mut text = String::with_capacity(self.text.len());
+        let mut char_source_bytes = Vec::with_capacity(chars.len());
+        let mut char_styles = Vec::with_capacity(chars.len());
+        let mut char_visual_cols = Vec::with_capacity(chars.len());
+        let mut visual_to_char = Vec::with_capacity(self.visual_to_char.len());
+        let mut new_index = vec![0; chars.len()];
+        for (new_idx, &old_idx) in order.iter().enumerate() {
+            let ch = match levels.get(old_idx) {
+                Some(level) if level % 2 == 1 => bidi::mirrored(chars[old_idx]),
+                _ => chars[old_idx],
+            };
+            text.push(ch);
+            char_source_bytes.push(self.char_source_bytes[old_idx]);
+            char_styles.push(self.char_styles[old_idx].clone());
+            char_visual_cols.push(visual_to_char.len());
+            visual_to_char.extend(std::iter::repeat_n(new_idx, width_of(old_idx)));
+            new_index[old_idx] = new_idx;
+        }
+
+        // A tab expansion may have been reversed: it now starts at whichever
+        // of its spaces is leftmost
+        let tab_starts = self
+            .tab_starts
+            .iter()
+            .filter_map(|col| self.visual_to_char.get(*col).copied())
+            .map(|start| {
+                let source = self.char_source_bytes[start];
+                let expansion = (start..chars.len())
+                    .take_while(|idx| self.char_source_bytes[*idx] == source)
+                    .map(|idx| new_index[idx]);
+                char_visual_cols[expansion.min().unwrap_or(new_index[start])]
+            })
+            .collect();
+
+        self.text = text;
+        self.char_source_bytes = char_source_bytes;
+        self.char_styles = char_styles;
+        self.char_visual_cols = char_visual_cols;
+        self.visual_to_char = visual_to_char;
+        self.tab_starts = tab_starts;
+    }
 }
 
 /// What preceded the start of a display line
@@ -1136,4 +1203,31 @@ mod tests {
             "Line 2 col 2 (newline)"
         );
     }
+
+    #[test]
+    fn test_reorder_bidi() {
+        // "ab " then Hebrew alef and bet, a tab and a bracket
+        let text = "ab \u{5d0}\u{5d1}\t(";
+        let tokens = vec![
+            make_text_token(text, Some(0)),
+            make_newline_token(Some(text.len())),
+        ];
+        let mut lines: Vec<_> = ViewLineIterator::new(&tokens, false, false, 4).collect();
+        let line = &mut lines[0];
+        line.reorder_bidi();
+
+        // The Hebrew run is reversed; the tab after it and the bracket are
+        // neutral at the end of a left-to-right line, so they stay in place
+        assert_eq!(line.text, "ab \u{5d1}\u{5d0}   (\n");
+        assert_eq!(line.source_byte_at_visual_col(3), Some(5));
+        assert_eq!(line.source_byte_at_visual_col(4), Some(3));
+        assert_eq!(line.source_byte_at_visual_col(9), Some(text.len()));
+        assert!(line.tab_starts.contains(&5));
+
+        // Lines without right-to-left text are left alone
+        let tokens = vec![make_text_token("plain", Some(0))];
+        let mut lines: Vec<_> = ViewLineIterator::new(&tokens, false, false, 4).collect();
+        lines[0].reorder_bidi();
+        assert_eq!(lines[0].text, "plain");
+    }
 }
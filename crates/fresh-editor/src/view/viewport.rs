@@ -1,6 +1,6 @@
 use crate::model::buffer::Buffer;
 use crate::model::cursor::Cursor;
-use crate::primitives::display_width::{char_width, str_width};
+use crate::primitives::display_width::{char_width, str_width, visual_column_at_byte};
 use crate::primitives::line_wrapping::{char_position_to_segment, wrap_line, WrapConfig};
 use crate::view::ui::view_pipeline::ViewLine;
 /// The viewport - what portion of the buffer is visible
@@ -163,6 +163,36 @@ impl Viewport {
         self.set_top_byte_with_limit(buffer, new_position);
     }
 
+    /// Scroll left (negative) or right (positive) by N columns
+    ///
+    /// Stops once the end of the longest visible line is reached. Does
+    /// nothing when lines wrap, since every column is already visible.
+    pub fn scroll_horizontal(&mut self, buffer: &mut Buffer, columns: isize) {
+        if self.line_wrap_enabled {
+            return;
+        }
+
+        let scrollbar_width = 1;
+        let visible_width = (self.width as usize)
+            .saturating_sub(self.gutter_width(buffer))
+            .saturating_sub(scrollbar_width);
+        let mut iter = buffer.line_iterator(self.top_byte, 80);
+        let longest_line = std::iter::from_fn(|| iter.next_line())
+            .take(self.visible_line_count())
+            .map(|(_, line)| line.trim_end_matches(['\n', '\r']).chars().count())
+            .max()
+            .unwrap_or(0);
+        let max_left_column = longest_line.saturating_sub(visible_width.saturating_sub(1));
+
+        let new_left_column = self.left_column.saturating_add_signed(columns);
+        self.left_column = if columns > 0 {
+            // Never jump back left when the long lines scrolled out of view
+            new_left_column.min(max_left_column.max(self.left_column))
+        } else {
+            new_left_column
+        };
+    }
+
     /// Scroll through ViewLines (view-transform aware)
     ///
     /// This method scrolls through display lines rather than source lines,
@@ -395,20 +425,17 @@ impl Viewport {
 
             // Only handle horizontal scroll if cursor is actually within this line
             if cursor.position < line_end_byte {
-                let cursor_byte_offset = cursor.position.saturating_sub(line_start);
-
-                // Calculate visual column by walking through characters and summing widths
-                // until we've consumed cursor_byte_offset bytes
+                // Take the cursor's visual column from the layout, which has tabs
+                // expanded to their tab stops; past the last character it is the
+                // end of the line
                 let line_text = line.text.trim_end_matches('\n');
-                let mut bytes_consumed = 0usize;
-                let mut cursor_visual_col = 0usize;
-                for ch in line_text.chars() {
-                    if bytes_consumed >= cursor_byte_offset {
-                        break;
-                    }
-                    cursor_visual_col += char_width(ch);
-                    bytes_consumed += ch.len_utf8();
-                }
+                let cursor_visual_col = line
+                    .char_source_bytes
+                    .iter()
+                    .zip(&line.char_visual_cols)
+                    .find(|(source_byte, _)| source_byte.is_some_and(|b| b >= cursor.position))
+                    .map(|(_, col)| *col)
+                    .unwrap_or_else(|| str_width(line_text));
 
                 let line_visual_width = str_width(line_text);
                 self.ensure_column_visible_simple(
@@ -697,7 +724,10 @@ impl Viewport {
                         let segments_count = segments.len().max(1); // Empty line is 1 row
 
                         // Find which segment the cursor is in
-                        let cursor_column = cursor.position.saturating_sub(cursor_line_start);
+                        let cursor_column = char_index_at_byte(
+                            &line_content,
+                            cursor.position.saturating_sub(cursor_line_start),
+                        );
                         let (cursor_segment_idx, _) =
                             char_position_to_segment(cursor_column, &segments);
 
@@ -782,7 +812,10 @@ impl Viewport {
                         &line_content
                     };
                     let segments = wrap_line(line_text, &wrap_config);
-                    let cursor_column = cursor.position.saturating_sub(cursor_line_start);
+                    let cursor_column = char_index_at_byte(
+                        line_text,
+                        cursor.position.saturating_sub(cursor_line_start),
+                    );
                     let (cursor_segment_idx, _) =
                         char_position_to_segment(cursor_column, &segments);
                     visual_rows_counted += cursor_segment_idx + 1;
@@ -836,16 +869,19 @@ impl Viewport {
         // Horizontal scrolling - skip if line wrapping is enabled
         // When wrapping is enabled, all columns are always visible via wrapping
         if !self.line_wrap_enabled {
-            let cursor_column = cursor.position.saturating_sub(cursor_line_start);
-
-            // Get the line content to know its length (for limiting horizontal scroll)
+            // Columns are visual, so double-width characters count twice
             let mut line_iter = buffer.line_iterator(cursor_line_start, 80);
-            let line_length = if let Some((_start, content)) = line_iter.next_line() {
-                // Line length without the newline character
-                content.trim_end_matches('\n').len()
-            } else {
-                0
-            };
+            let (cursor_column, line_length) =
+                if let Some((_start, content)) = line_iter.next_line() {
+                    let line_text = content.trim_end_matches('\n');
+                    let cursor_byte = cursor.position.saturating_sub(cursor_line_start);
+                    (
+                        visual_column_at_byte(line_text, cursor_byte),
+                        str_width(line_text),
+                    )
+                } else {
+                    (0, 0)
+                };
 
             self.ensure_column_visible(cursor_column, line_length, buffer);
         } else {
@@ -1038,7 +1074,7 @@ impl Viewport {
         // Find line start using iterator
         let cursor_iter = buffer.line_iterator(cursor.position, 80);
         let line_start = cursor_iter.current_position();
-        let column = cursor.position.saturating_sub(line_start);
+        let byte_column = cursor.position.saturating_sub(line_start);
 
         // Count lines from top_byte to cursor to get screen row
         let mut iter = buffer.line_iterator(self.top_byte, 80);
@@ -1051,30 +1087,42 @@ impl Viewport {
             screen_row += 1;
         }
 
+        // Get the line text, without its newline
+        let mut line_iter = buffer.line_iterator(line_start, 80);
+        let line_text = if let Some((_start, content)) = line_iter.next_line() {
+            content.trim_end_matches('\n').to_string()
+        } else {
+            String::new()
+        };
+
         // Calculate screen column and additional wrapped rows if line wrapping is enabled
         let (screen_col, additional_rows) = if self.line_wrap_enabled {
             // Use new clean wrapping implementation
             let gutter_width = self.gutter_width(buffer);
             let config = WrapConfig::new(self.width as usize, gutter_width, true);
 
-            // Get the line text for wrapping
-            let mut line_iter = buffer.line_iterator(line_start, 80);
-            let line_text = if let Some((_start, content)) = line_iter.next_line() {
-                // Remove trailing newline if present
-                content.trim_end_matches('\n').to_string()
-            } else {
-                String::new()
-            };
-
             // Wrap the line
             let segments = wrap_line(&line_text, &config);
 
             // Find which segment the cursor is in
+            let column = char_index_at_byte(&line_text, byte_column);
             let (segment_idx, col_in_segment) = char_position_to_segment(column, &segments);
-
-            (col_in_segment as u16, segment_idx)
+            let screen_col: usize = segments
+                .get(segment_idx)
+                .map(|segment| {
+                    segment
+                        .text
+                        .chars()
+                        .take(col_in_segment)
+                        .map(char_width)
+                        .sum()
+                })
+                .unwrap_or(col_in_segment);
+
+            (screen_col as u16, segment_idx)
         } else {
             // No wrapping - account for horizontal scrolling
+            let column = visual_column_at_byte(&line_text, byte_column);
             let screen_col = column.saturating_sub(self.left_column) as u16;
             (screen_col, 0)
         };
@@ -1085,6 +1133,14 @@ impl Viewport {
     }
 }
 
+/// Index of the character at `byte_offset` within a line, as used by
+/// `char_position_to_segment`
+fn char_index_at_byte(line: &str, byte_offset: usize) -> usize {
+    line.char_indices()
+        .take_while(|(byte_idx, _)| *byte_idx < byte_offset)
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
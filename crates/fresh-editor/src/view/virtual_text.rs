@@ -244,6 +244,48 @@ impl VirtualTextManager {
         id
     }
 
+    /// Add inline virtual text (BeforeChar or AfterChar) with a namespace for
+    /// bulk removal
+    ///
+    /// Used by features that replace all of their virtual text at once, such
+    /// as inlay hints and color swatches.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_inline(
+        &mut self,
+        marker_list: &mut MarkerList,
+        position: usize,
+        text: String,
+        style: Style,
+        placement: VirtualTextPosition,
+        namespace: VirtualTextNamespace,
+        priority: i32,
+    ) -> VirtualTextId {
+        debug_assert!(
+            placement.is_inline(),
+            "add_inline requires BeforeChar or AfterChar"
+        );
+
+        let marker_id = marker_list.create(position, false);
+
+        let id = VirtualTextId(self.next_id);
+        self.next_id += 1;
+
+        self.texts.insert(
+            id,
+            VirtualText {
+                marker_id,
+                text,
+                style,
+                position: placement,
+                priority,
+                string_id: None,
+                namespace: Some(namespace),
+            },
+        );
+
+        id
+    }
+
     /// Remove a virtual text entry by its string identifier
     pub fn remove_by_id(&mut self, marker_list: &mut MarkerList, string_id: &str) -> bool {
         // Find the entry with matching string_id
@@ -0,0 +1,252 @@
+//! Visual guides drawn over the buffer content
+//!
+//! - Rulers: vertical lines at configured columns (e.g. 80 and 120)
+//! - Trailing whitespace: spaces and tabs before the end of a line
+//! - Mixed indentation: leading whitespace that mixes tabs and spaces
+//! - Whitespace rendering: tabs, spaces and line endings drawn as glyphs
+//! - Cursor line and column: the row and column of the cursor, tinted
+//! - Line length: characters past `max_line_length`, highlighted
+//!
+//! Rulers and the line length limit come from `editor.rulers` and
+//! `editor.max_line_length` (or their `languages.<lang>` overrides), the
+//! whitespace layers from `editor.show_trailing_whitespace`,
+//! `editor.show_mixed_indentation` and `editor.render_whitespace`, and the
+//! cursor highlights from `editor.highlight_current_line` and
+//! `editor.highlight_current_column`. None of them change the buffer.
+
+use crate::config::{BufferConfig, EditorConfig, RenderWhitespace};
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+/// Per-buffer visual guide settings
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VisualGuides {
+    /// Columns (0-based visual columns) where rulers are drawn
+    pub rulers: Vec<usize>,
+    /// Highlight whitespace at the end of lines
+    pub trailing_whitespace: bool,
+    /// Highlight leading whitespace that mixes tabs and spaces
+    pub mixed_indentation: bool,
+    /// When to draw whitespace characters as glyphs
+    pub render_whitespace: RenderWhitespace,
+    /// Tint the line containing the cursor
+    pub current_line: bool,
+    /// Tint the column containing the cursor
+    pub current_column: bool,
+    /// Column (0-based visual column) past which characters are highlighted
+    pub max_line_length: Option<usize>,
+    /// Whether the line length warnings are shown (toggled per buffer)
+    pub line_length_warnings: bool,
+    /// Also list overlong lines in the diagnostics panel
+    pub line_length_diagnostics: bool,
+}
+
+/// Where a line goes past the line length limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overlong {
+    /// Byte offset in the line of the first character past the limit
+    pub start: usize,
+    /// Byte offset in the line where its content ends
+    pub end: usize,
+    /// Width of the line in columns
+    pub width: usize,
+}
+
+impl VisualGuides {
+    /// Guides for a buffer: rulers from its resolved config, whitespace
+    /// layers from the editor config
+    pub fn from_config(editor: &EditorConfig, buffer_config: &BufferConfig) -> Self {
+        Self {
+            rulers: buffer_config.rulers.clone(),
+            trailing_whitespace: editor.show_trailing_whitespace,
+            mixed_indentation: editor.show_mixed_indentation,
+            render_whitespace: editor.render_whitespace,
+            current_line: editor.highlight_current_line,
+            current_column: editor.highlight_current_column,
+            max_line_length: buffer_config.max_line_length,
+            line_length_warnings: true,
+            line_length_diagnostics: editor.line_length_diagnostics,
+        }
+    }
+
+    /// Whether whitespace is drawn as glyphs for a character, given whether
+    /// it is selected
+    pub fn renders_whitespace(&self, is_selected: bool) -> bool {
+        match self.render_whitespace {
+            RenderWhitespace::None => false,
+            RenderWhitespace::Selection => is_selected,
+            RenderWhitespace::All => true,
+        }
+    }
+
+    /// Whether any whitespace layer is enabled
+    pub fn highlights_whitespace(&self) -> bool {
+        self.trailing_whitespace || self.mixed_indentation
+    }
+
+    /// Byte ranges of `line` (starting at buffer offset `line_start`) to
+    /// highlight as whitespace problems
+    ///
+    /// `line` may include its line ending, which is never highlighted.
+    pub fn whitespace_ranges(&self, line: &str, line_start: usize) -> Vec<Range<usize>> {
+        let content = line.trim_end_matches(['\n', '\r']);
+        let mut ranges = Vec::new();
+
+        if self.mixed_indentation {
+            let indent_len = content.len() - content.trim_start_matches([' ', '\t']).len();
+            let indent = &content[..indent_len];
+            if indent.contains(' ') && indent.contains('\t') {
+                ranges.push(line_start..line_start + indent_len);
+            }
+        }
+
+        if self.trailing_whitespace {
+            let trimmed_len = content.trim_end_matches([' ', '\t']).len();
+            if trimmed_len < content.len() {
+                let start = line_start + trimmed_len;
+                let end = line_start + content.len();
+                // Whitespace-only lines may already be covered as mixed indentation
+                match ranges.last_mut() {
+                    Some(last) if last.end >= start => last.end = last.end.max(end),
+                    _ => ranges.push(start..end),
+                }
+            }
+        }
+
+        ranges
+    }
+
+    /// The column past which characters are highlighted, if the warnings
+    /// are shown
+    pub fn line_length_limit(&self) -> Option<usize> {
+        self.max_line_length.filter(|_| self.line_length_warnings)
+    }
+
+    /// Where `line` goes past the line length limit, if it does
+    ///
+    /// Columns are counted as drawn: tabs advance to the next tab stop and
+    /// wide characters take two columns. `line` may include its line ending.
+    pub fn overlong(&self, line: &str, tab_size: usize) -> Option<Overlong> {
+        let limit = self.line_length_limit()?;
+        let content = line.trim_end_matches(['\n', '\r']);
+        let tab_size = tab_size.max(1);
+        let mut start = None;
+        let mut width = 0;
+        for (offset, ch) in content.char_indices() {
+            if width >= limit && start.is_none() {
+                start = Some(offset);
+            }
+            width += match ch {
+                '\t' => tab_size - width % tab_size,
+                _ => ch.width().unwrap_or(0),
+            };
+        }
+        start.map(|start| Overlong {
+            start,
+            end: content.len(),
+            width,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guides(trailing_whitespace: bool, mixed_indentation: bool) -> VisualGuides {
+        VisualGuides {
+            rulers: Vec::new(),
+            trailing_whitespace,
+            mixed_indentation,
+            render_whitespace: RenderWhitespace::None,
+            current_line: false,
+            current_column: false,
+            max_line_length: None,
+            line_length_warnings: false,
+            line_length_diagnostics: false,
+        }
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        let guides = guides(true, false);
+        assert_eq!(
+            guides.whitespace_ranges("let x = 1;  \t\n", 10),
+            vec![20..23]
+        );
+        assert!(guides.whitespace_ranges("let x = 1;\r\n", 10).is_empty());
+        assert_eq!(guides.whitespace_ranges("   \n", 0), vec![0..3]);
+        assert!(guides.whitespace_ranges("", 0).is_empty());
+    }
+
+    #[test]
+    fn test_mixed_indentation() {
+        let guides = guides(false, true);
+        assert_eq!(guides.whitespace_ranges("\t  x\n", 5), vec![5..8]);
+        assert!(guides.whitespace_ranges("\t\tx\n", 5).is_empty());
+        assert!(guides.whitespace_ranges("    x \n", 5).is_empty());
+    }
+
+    #[test]
+    fn test_both_layers_merge_on_whitespace_only_lines() {
+        let guides = guides(true, true);
+        assert_eq!(guides.whitespace_ranges(" \t \n", 0), vec![0..3]);
+        assert_eq!(guides.whitespace_ranges(" \tx \n", 0), vec![0..2, 3..4]);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let guides = VisualGuides::default();
+        assert!(!guides.highlights_whitespace());
+        assert!(guides.whitespace_ranges("\t x  \n", 0).is_empty());
+        assert!(!guides.renders_whitespace(true));
+    }
+
+    #[test]
+    fn test_render_whitespace_modes() {
+        let mut guides = VisualGuides {
+            render_whitespace: RenderWhitespace::Selection,
+            ..Default::default()
+        };
+        assert!(guides.renders_whitespace(true));
+        assert!(!guides.renders_whitespace(false));
+
+        guides.render_whitespace = RenderWhitespace::All;
+        assert!(guides.renders_whitespace(false));
+    }
+
+    #[test]
+    fn test_overlong_lines() {
+        let guides = VisualGuides {
+            max_line_length: Some(4),
+            line_length_warnings: true,
+            ..Default::default()
+        };
+        assert_eq!(guides.overlong("abcd\n", 4), None);
+        assert_eq!(
+            guides.overlong("abcdef\r\n", 4),
+            Some(Overlong {
+                start: 4,
+                end: 6,
+                width: 6
+            })
+        );
+        // A tab reaches the next tab stop, a wide character takes two columns
+        assert_eq!(guides.overlong("\tx", 4).map(|o| o.start), Some(1));
+        assert_eq!(guides.overlong("ab日本", 4).map(|o| o.start), Some(5));
+        assert_eq!(guides.overlong("ab日本", 4).map(|o| o.width), Some(6));
+    }
+
+    #[test]
+    fn test_line_length_warnings_toggled_off() {
+        let mut guides = VisualGuides {
+            max_line_length: Some(2),
+            line_length_warnings: true,
+            ..Default::default()
+        };
+        assert_eq!(guides.line_length_limit(), Some(2));
+        guides.line_length_warnings = false;
+        assert_eq!(guides.line_length_limit(), None);
+        assert_eq!(guides.overlong("abcdef", 4), None);
+    }
+}
@@ -0,0 +1,79 @@
+//! JavaScript bindings for the browser playground
+//!
+//! The page owns a terminal emulator (xterm.js): it forwards `keydown` and
+//! paste events to a `Playground` and writes what `render` returns to the
+//! terminal. See `web/` for the page.
+
+use ratatui::buffer::Buffer as ScreenBuffer;
+use ratatui::layout::Rect;
+use wasm_bindgen::prelude::*;
+
+use super::input::translate_key;
+use super::render::to_ansi;
+use super::WasmEditor;
+
+/// An editor shown in a browser terminal of `cols` by `rows`
+#[wasm_bindgen]
+pub struct Playground {
+    editor: WasmEditor,
+    screen: ScreenBuffer,
+}
+
+#[wasm_bindgen]
+impl Playground {
+    /// Create a playground editing `content`, highlighted for the file
+    /// `name`
+    #[wasm_bindgen(constructor)]
+    pub fn new(name: &str, content: &str, cols: u16, rows: u16) -> Playground {
+        let mut editor = WasmEditor::with_content(content);
+        editor.set_name(name);
+        Playground {
+            editor,
+            screen: ScreenBuffer::empty(Rect::new(0, 0, cols, rows)),
+        }
+    }
+
+    /// Follow a resize of the terminal
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.screen.resize(Rect::new(0, 0, cols, rows));
+    }
+
+    /// Handle a `keydown` event. Returns whether the editor used it, in
+    /// which case the page should prevent the browser's default action.
+    #[wasm_bindgen(js_name = handleKey)]
+    pub fn handle_key(
+        &mut self,
+        key: &str,
+        ctrl: bool,
+        alt: bool,
+        shift: bool,
+        meta: bool,
+    ) -> bool {
+        translate_key(key, ctrl, alt, shift, meta)
+            .is_some_and(|event| self.editor.handle_key(event))
+    }
+
+    /// Insert pasted text at the cursor
+    pub fn paste(&mut self, text: &str) {
+        self.editor.insert_text(text);
+    }
+
+    /// Switch to a builtin theme, e.g. `"light"`. Returns false if there's
+    /// no theme by that name.
+    #[wasm_bindgen(js_name = setTheme)]
+    pub fn set_theme(&mut self, name: &str) -> bool {
+        self.editor.set_theme(name)
+    }
+
+    /// Render the editor, returning the ANSI output redrawing the terminal
+    pub fn render(&mut self) -> String {
+        let area = self.screen.area;
+        let cursor = self.editor.render(area, &mut self.screen);
+        to_ansi(&self.screen, cursor)
+    }
+
+    /// The text being edited
+    pub fn content(&self) -> String {
+        self.editor.content().unwrap_or_default()
+    }
+}
@@ -0,0 +1,129 @@
+//! Browser keyboard events
+//!
+//! Browsers name keys with `KeyboardEvent.key`: the character typed for
+//! printable keys (already shifted, so `"A"` or `"{"`) and names such as
+//! `"ArrowLeft"` or `"Enter"` for the others. They're translated to the
+//! crossterm key events the rest of the editor handles.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Translate a browser `KeyboardEvent` to a key event. Returns None for keys
+/// the editor has no use for (lone modifiers, media keys, dead keys of an
+/// unfinished composition, ...).
+pub fn translate_key(
+    key: &str,
+    ctrl: bool,
+    alt: bool,
+    shift: bool,
+    meta: bool,
+) -> Option<KeyEvent> {
+    let code = match key {
+        "Enter" => KeyCode::Enter,
+        "Tab" if shift => KeyCode::BackTab,
+        "Tab" => KeyCode::Tab,
+        "Backspace" => KeyCode::Backspace,
+        "Delete" => KeyCode::Delete,
+        "Escape" => KeyCode::Esc,
+        "Insert" => KeyCode::Insert,
+        "ArrowLeft" => KeyCode::Left,
+        "ArrowRight" => KeyCode::Right,
+        "ArrowUp" => KeyCode::Up,
+        "ArrowDown" => KeyCode::Down,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Spacebar" => KeyCode::Char(' '),
+        _ => {
+            if let Some(n) = key.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+                KeyCode::F(n)
+            } else {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return None,
+                }
+            }
+        }
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    if ctrl {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if alt {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if meta {
+        modifiers |= KeyModifiers::SUPER;
+    }
+    // The case of a typed character already says whether shift was held,
+    // as it does for crossterm, except with other modifiers
+    let typed = matches!(code, KeyCode::Char(_)) && !ctrl && !alt && !meta;
+    if shift && !typed && code != KeyCode::BackTab {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    // Ctrl+letter comes as a lowercase letter, like crossterm reports it
+    let code = match code {
+        KeyCode::Char(c) if ctrl => KeyCode::Char(c.to_ascii_lowercase()),
+        code => code,
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_printable_keys() {
+        assert_eq!(
+            translate_key("a", false, false, false, false),
+            Some(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            translate_key("{", false, false, true, false),
+            Some(KeyEvent::new(KeyCode::Char('{'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            translate_key("é", false, false, false, false),
+            Some(KeyEvent::new(KeyCode::Char('é'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            translate_key("S", true, false, true, false),
+            Some(KeyEvent::new(
+                KeyCode::Char('s'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+    }
+
+    #[test]
+    fn test_named_keys() {
+        assert_eq!(
+            translate_key("ArrowLeft", true, false, true, false),
+            Some(KeyEvent::new(
+                KeyCode::Left,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            ))
+        );
+        assert_eq!(
+            translate_key("Tab", false, false, true, false),
+            Some(KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(
+            translate_key("F12", false, false, false, false),
+            Some(KeyEvent::new(KeyCode::F(12), KeyModifiers::NONE))
+        );
+    }
+
+    #[test]
+    fn test_unused_keys() {
+        assert_eq!(translate_key("Shift", false, false, true, false), None);
+        assert_eq!(translate_key("Dead", false, false, false, false), None);
+        assert_eq!(
+            translate_key("AudioVolumeUp", false, false, false, false),
+            None
+        );
+    }
+}
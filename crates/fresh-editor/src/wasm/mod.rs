@@ -7,19 +7,41 @@
 //!
 //! The WASM build shares the following modules with native:
 //! - `model/*` - Buffer, piece tree, cursors, events
-//! - `primitives/*` - Pure text manipulation utilities
+//! - `primitives/*` - Pure text manipulation utilities, TextMate highlighting
+//! - `view::theme` - Themes
 //!
 //! WASM-specific code handles:
-//! - Browser event handling (keyboard/mouse via Ratzilla or similar)
-//! - Virtual filesystem (in-memory or IndexedDB-backed)
-//! - Rendering to browser terminal (via Ratzilla)
+//! - Browser keyboard events, translated to crossterm key events (`input`)
+//! - Rendering into a ratatui buffer, serialized as ANSI for a browser
+//!   terminal such as xterm.js (`render`)
+//! - JavaScript bindings for the playground (`bindings`, wasm32 only)
 //!
 //! # Usage
 //!
 //! Build with: `cargo build --no-default-features --features wasm`
+//!
+//! `scripts/build-web.sh` builds the browser playground in `web/`.
 
+use std::path::Path;
 use std::sync::Arc;
 
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::primitives::display_width::{
+    line_byte_offset_at_visual_column, line_visual_column_at_byte,
+};
+use crate::primitives::grammar::GrammarRegistry;
+use crate::primitives::grapheme::{next_grapheme_boundary, prev_grapheme_boundary};
+use crate::primitives::indent_pattern::PatternIndentCalculator;
+use crate::primitives::textmate_engine::TextMateEngine;
+use crate::primitives::word_navigation::{find_word_start_left, find_word_start_right};
+use crate::view::theme::{Theme, THEME_DARK};
+
+#[cfg(target_arch = "wasm32")]
+mod bindings;
+pub mod input;
+pub mod render;
+
 // Re-export core types for WASM consumers
 pub use crate::model::buffer::{Buffer, LineEnding, TextBuffer};
 pub use crate::model::cursor::{Cursor, Cursors};
@@ -30,31 +52,88 @@ pub use crate::model::piece_tree::{PieceTree, Position};
 /// Default large file threshold for WASM (100MB)
 const LARGE_FILE_THRESHOLD: usize = 100 * 1024 * 1024;
 
+/// Columns per indentation level and between tab stops
+const TAB_SIZE: usize = 4;
+
 /// WASM-specific editor state
 ///
-/// This provides a simple wrapper around the core Buffer type
-/// that uses NoopFileSystem since browsers don't have direct filesystem access.
+/// A single buffer with one cursor, edited with the keys of a plain text
+/// editor and syntax highlighted by file name. It uses NoopFileSystem since
+/// browsers don't have direct filesystem access: the host sets and reads the
+/// content.
 pub struct WasmEditor {
     buffer: Buffer,
+    /// Byte offset of the cursor
+    cursor: usize,
+    /// Visual column that moving up and down keeps to
+    goal_column: Option<usize>,
+    /// First line and column shown; rendering scrolls them to the cursor
+    top_line: usize,
+    left_column: usize,
+    /// Lines of text last rendered, for paging
+    page_lines: usize,
+    /// File name shown in the status bar, which picks the syntax
+    name: String,
+    theme: Theme,
+    highlighter: Option<TextMateEngine>,
 }
 
 impl WasmEditor {
     /// Create a new WASM editor with an empty buffer
     pub fn new() -> Self {
         let fs: Arc<dyn FileSystem + Send + Sync> = Arc::new(NoopFileSystem);
-        Self {
-            buffer: Buffer::empty(fs),
-        }
+        Self::from_buffer(Buffer::empty(fs))
     }
 
     /// Create a new WASM editor with initial content
     pub fn with_content(content: &str) -> Self {
         let fs: Arc<dyn FileSystem + Send + Sync> = Arc::new(NoopFileSystem);
+        Self::from_buffer(Buffer::from_str(content, LARGE_FILE_THRESHOLD, fs))
+    }
+
+    fn from_buffer(buffer: Buffer) -> Self {
         Self {
-            buffer: Buffer::from_str(content, LARGE_FILE_THRESHOLD, fs),
+            buffer,
+            cursor: 0,
+            goal_column: None,
+            top_line: 0,
+            left_column: 0,
+            page_lines: 1,
+            name: String::new(),
+            theme: Theme::load_builtin(THEME_DARK).expect("dark theme must exist"),
+            highlighter: None,
         }
     }
 
+    /// Name the content, highlighting it with the grammar for the file name
+    /// (e.g. `main.rs`). Returns whether a grammar was found.
+    pub fn set_name(&mut self, name: &str) -> bool {
+        self.name = name.to_string();
+        self.highlighter = TextMateEngine::for_file(Path::new(name), &GrammarRegistry::default());
+        self.highlighter.is_some()
+    }
+
+    /// The name given with `set_name`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Switch to a builtin theme. Returns false if there's no theme by that
+    /// name.
+    pub fn set_theme(&mut self, name: &str) -> bool {
+        match Theme::load_builtin(name) {
+            Some(theme) => {
+                self.theme = theme;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
     /// Get the buffer content as a string
     ///
     /// Returns None if the buffer contains invalid UTF-8
@@ -65,11 +144,17 @@ impl WasmEditor {
     /// Insert text at the given byte offset
     pub fn insert(&mut self, offset: usize, text: &str) {
         self.buffer.insert(offset, text);
+        if offset <= self.cursor {
+            self.cursor += text.len();
+        }
     }
 
     /// Delete a range of text (start..end in bytes)
     pub fn delete(&mut self, start: usize, end: usize) {
         self.buffer.delete(start..end);
+        if self.cursor > start {
+            self.cursor = start.max(self.cursor.saturating_sub(end - start));
+        }
     }
 
     /// Get the total length of the buffer in bytes
@@ -89,6 +174,17 @@ impl WasmEditor {
         self.buffer.line_count()
     }
 
+    /// Byte offset of the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor.min(self.buffer.len())
+    }
+
+    /// Move the cursor to a byte offset, which must be at a character boundary
+    pub fn set_cursor(&mut self, offset: usize) {
+        self.cursor = offset.min(self.buffer.len());
+        self.goal_column = None;
+    }
+
     /// Get a reference to the underlying buffer
     pub fn buffer(&self) -> &Buffer {
         &self.buffer
@@ -98,6 +194,178 @@ impl WasmEditor {
     pub fn buffer_mut(&mut self) -> &mut Buffer {
         &mut self.buffer
     }
+
+    /// Insert text at the cursor, as typed or pasted
+    pub fn insert_text(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n");
+        let cursor = self.cursor();
+        self.insert(cursor, &text);
+        self.goal_column = None;
+    }
+
+    /// Handle a key press. Returns false for keys the editor has no use for,
+    /// which the host may handle instead (browser shortcuts, focus changes).
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        // Shortcuts, not text
+        let command = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SUPER);
+        self.cursor = self.cursor();
+        let goal_column = self.goal_column.take();
+        match key.code {
+            KeyCode::Char(c) if !command => self.type_char(c),
+            KeyCode::Enter if !command => self.insert_newline(),
+            KeyCode::Tab if !command => {
+                let (start, text) = self.line(self.cursor_line());
+                let column = line_visual_column_at_byte(&text, self.cursor - start, TAB_SIZE);
+                self.insert_text(&" ".repeat(TAB_SIZE - column % TAB_SIZE));
+            }
+            KeyCode::Backspace => {
+                let start = self.prev_position(self.cursor);
+                if start < self.cursor {
+                    self.delete(start, self.cursor);
+                }
+            }
+            KeyCode::Delete => {
+                let end = self.next_position(self.cursor);
+                if end > self.cursor {
+                    self.delete(self.cursor, end);
+                }
+            }
+            KeyCode::Left if ctrl => self.cursor = find_word_start_left(&self.buffer, self.cursor),
+            KeyCode::Right if ctrl => {
+                self.cursor = find_word_start_right(&self.buffer, self.cursor)
+            }
+            KeyCode::Left => self.cursor = self.prev_position(self.cursor),
+            KeyCode::Right => self.cursor = self.next_position(self.cursor),
+            KeyCode::Up => self.move_lines(-1, goal_column),
+            KeyCode::Down => self.move_lines(1, goal_column),
+            KeyCode::PageUp => self.move_lines(-(self.page_lines as isize), goal_column),
+            KeyCode::PageDown => self.move_lines(self.page_lines as isize, goal_column),
+            KeyCode::Home if ctrl => self.cursor = 0,
+            KeyCode::End if ctrl => self.cursor = self.buffer.len(),
+            KeyCode::Home => {
+                // To the first non-blank character, or the start of the line
+                // if the cursor is already there
+                let (start, text) = self.line(self.cursor_line());
+                let indent = text.len() - text.trim_start_matches([' ', '\t']).len();
+                self.cursor = if self.cursor == start + indent {
+                    start
+                } else {
+                    start + indent
+                };
+            }
+            KeyCode::End => {
+                let (start, text) = self.line(self.cursor_line());
+                self.cursor = start + text.len();
+            }
+            _ => {
+                self.goal_column = goal_column;
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Type a character, aligning a closing delimiter typed on a blank line
+    /// with the line that opened it
+    fn type_char(&mut self, c: char) {
+        if matches!(c, '}' | ']' | ')') {
+            let (start, text) = self.line(self.cursor_line());
+            let before = &text[..self.cursor - start];
+            if before.chars().all(|c| c == ' ' || c == '\t') {
+                if let Some(indent) = PatternIndentCalculator::calculate_dedent_for_delimiter(
+                    &self.buffer,
+                    self.cursor,
+                    c,
+                    TAB_SIZE,
+                ) {
+                    if start < self.cursor {
+                        self.delete(start, self.cursor);
+                    }
+                    if indent > 0 {
+                        self.insert(start, &" ".repeat(indent));
+                    }
+                }
+            }
+        }
+        self.insert_text(c.encode_utf8(&mut [0; 4]));
+    }
+
+    /// Start a new line, indented for the code before the cursor
+    fn insert_newline(&mut self) {
+        let indent = PatternIndentCalculator::calculate_indent(&self.buffer, self.cursor, TAB_SIZE);
+        self.insert_text(&format!("\n{}", " ".repeat(indent)));
+    }
+
+    /// Move the cursor `delta` lines up (negative) or down, keeping to the
+    /// goal column
+    fn move_lines(&mut self, delta: isize, goal_column: Option<usize>) {
+        let line = self.cursor_line();
+        let (start, text) = self.line(line);
+        let column = goal_column
+            .unwrap_or_else(|| line_visual_column_at_byte(&text, self.cursor - start, TAB_SIZE));
+        let last_line = self.buffer.line_count().unwrap_or(1).saturating_sub(1);
+        let target = line.saturating_add_signed(delta).min(last_line);
+        let (start, text) = self.line(target);
+        self.cursor = start + line_byte_offset_at_visual_column(&text, column, TAB_SIZE);
+        self.goal_column = Some(column);
+    }
+
+    /// The position one grapheme before `offset`, across line endings
+    fn prev_position(&self, offset: usize) -> usize {
+        let (start, text) = self.line(self.buffer.get_line_number(offset));
+        if offset > start {
+            return start + prev_grapheme_boundary(&text, offset - start);
+        }
+        // Before the line ending of the previous line, "\r\n" included
+        let bytes = self.buffer.slice_bytes(offset.saturating_sub(2)..offset);
+        offset
+            - if bytes == b"\r\n" {
+                2
+            } else {
+                bytes.len().min(1)
+            }
+    }
+
+    /// The position one grapheme after `offset`, across line endings
+    fn next_position(&self, offset: usize) -> usize {
+        let (start, text) = self.line(self.buffer.get_line_number(offset));
+        if offset < start + text.len() {
+            return start + next_grapheme_boundary(&text, offset - start);
+        }
+        let end = (offset + 2).min(self.buffer.len());
+        let bytes = self.buffer.slice_bytes(offset..end);
+        offset
+            + if bytes == b"\r\n" {
+                2
+            } else {
+                bytes.len().min(1)
+            }
+    }
+
+    /// The line the cursor is on
+    fn cursor_line(&self) -> usize {
+        self.buffer.get_line_number(self.cursor())
+    }
+
+    /// The start of a line and its text, without the line ending
+    fn line(&self, line: usize) -> (usize, String) {
+        let start = self
+            .buffer
+            .line_start_offset(line)
+            .unwrap_or(self.buffer.len());
+        let bytes = self.buffer.get_line(line).unwrap_or_default();
+        let mut text = String::from_utf8_lossy(&bytes).into_owned();
+        if text.ends_with('\n') {
+            text.pop();
+            if text.ends_with('\r') {
+                text.pop();
+            }
+        }
+        (start, text)
+    }
 }
 
 impl Default for WasmEditor {
@@ -135,4 +403,75 @@ mod tests {
         editor.delete(5, 13); // Delete ", World!"
         assert_eq!(editor.content(), Some("Hello".to_string()));
     }
+
+    fn press(editor: &mut WasmEditor, code: KeyCode) -> bool {
+        editor.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn type_text(editor: &mut WasmEditor, text: &str) {
+        for c in text.chars() {
+            press(editor, KeyCode::Char(c));
+        }
+    }
+
+    #[test]
+    fn test_typing_indents_blocks() {
+        let mut editor = WasmEditor::with_content("fn f() {");
+        editor.set_cursor(editor.len());
+        press(&mut editor, KeyCode::Enter);
+        type_text(&mut editor, "x;");
+        press(&mut editor, KeyCode::Enter);
+        type_text(&mut editor, "}");
+        assert_eq!(editor.content(), Some("fn f() {\n    x;\n}".to_string()));
+        assert_eq!(editor.cursor(), editor.len());
+    }
+
+    #[test]
+    fn test_backspace_and_delete_across_lines() {
+        let mut editor = WasmEditor::with_content("ab\r\ncd");
+        editor.set_cursor(4);
+        press(&mut editor, KeyCode::Backspace);
+        assert_eq!(editor.content(), Some("abcd".to_string()));
+        assert_eq!(editor.cursor(), 2);
+        press(&mut editor, KeyCode::Delete);
+        assert_eq!(editor.content(), Some("abd".to_string()));
+
+        // Nothing to delete at the ends of the buffer
+        editor.set_cursor(0);
+        press(&mut editor, KeyCode::Backspace);
+        editor.set_cursor(3);
+        press(&mut editor, KeyCode::Delete);
+        assert_eq!(editor.content(), Some("abd".to_string()));
+    }
+
+    #[test]
+    fn test_vertical_movement_keeps_column() {
+        let mut editor = WasmEditor::with_content("long line\nab\nanother line");
+        editor.set_cursor(6);
+        press(&mut editor, KeyCode::Down);
+        assert_eq!(editor.cursor(), 12);
+        press(&mut editor, KeyCode::Down);
+        assert_eq!(editor.cursor(), 19);
+        press(&mut editor, KeyCode::Up);
+        press(&mut editor, KeyCode::Up);
+        assert_eq!(editor.cursor(), 6);
+
+        press(&mut editor, KeyCode::End);
+        assert_eq!(editor.cursor(), 9);
+        press(&mut editor, KeyCode::Right);
+        assert_eq!(editor.cursor(), 10);
+        press(&mut editor, KeyCode::Left);
+        assert_eq!(editor.cursor(), 9);
+        press(&mut editor, KeyCode::Home);
+        assert_eq!(editor.cursor(), 0);
+    }
+
+    #[test]
+    fn test_shortcuts_are_left_to_the_host() {
+        let mut editor = WasmEditor::with_content("text");
+        let copy = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(!editor.handle_key(copy));
+        assert!(!press(&mut editor, KeyCode::F(1)));
+        assert_eq!(editor.content(), Some("text".to_string()));
+    }
 }
@@ -0,0 +1,328 @@
+//! Rendering the WASM editor
+//!
+//! The editor draws into a ratatui buffer like any other ratatui widget:
+//! line numbers, syntax highlighted text and a status bar. Browsers show it
+//! in a terminal emulator such as xterm.js, which `to_ansi` writes the whole
+//! screen for.
+
+use std::fmt::Write;
+
+use ratatui::buffer::Buffer as ScreenBuffer;
+use ratatui::layout::{Position as ScreenPosition, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{WasmEditor, TAB_SIZE};
+use crate::primitives::display_width::{line_visual_column_at_byte, str_width, tab_width_at};
+
+/// Bytes before and after the visible lines parsed for highlighting
+const HIGHLIGHT_CONTEXT_BYTES: usize = 10_000;
+
+impl WasmEditor {
+    /// Render the editor into `area` of `screen`, scrolling so the cursor is
+    /// visible. Returns where the cursor is on the screen.
+    pub fn render(&mut self, area: Rect, screen: &mut ScreenBuffer) -> Option<ScreenPosition> {
+        let area = area.intersection(screen.area);
+        if area.height < 2 || area.width == 0 {
+            return None;
+        }
+        let theme = &self.theme;
+        let text_style = Style::default().fg(theme.editor_fg).bg(theme.editor_bg);
+        for position in area.positions() {
+            if let Some(cell) = screen.cell_mut(position) {
+                cell.reset();
+                cell.set_style(text_style);
+            }
+        }
+
+        let line_count = self.buffer.line_count().unwrap_or(1).max(1);
+        let gutter_width = line_count.to_string().len().max(3) + 1;
+        let text_width = (area.width as usize).saturating_sub(gutter_width);
+        let text_height = area.height as usize - 1;
+        self.page_lines = text_height;
+
+        // Scroll to the cursor
+        let cursor = self.cursor();
+        let cursor_line = self.buffer.get_line_number(cursor);
+        let (cursor_line_start, cursor_line_text) = self.line(cursor_line);
+        let cursor_column =
+            line_visual_column_at_byte(&cursor_line_text, cursor - cursor_line_start, TAB_SIZE);
+        if cursor_line < self.top_line {
+            self.top_line = cursor_line;
+        } else if cursor_line >= self.top_line + text_height {
+            self.top_line = cursor_line + 1 - text_height;
+        }
+        if cursor_column < self.left_column {
+            self.left_column = cursor_column;
+        } else if cursor_column >= self.left_column + text_width {
+            self.left_column = cursor_column + 1 - text_width.max(1);
+        }
+
+        // Colors of the visible bytes
+        let len = self.buffer.len();
+        let view_start = self.buffer.line_start_offset(self.top_line).unwrap_or(len);
+        let view_end = self
+            .buffer
+            .line_start_offset(self.top_line + text_height)
+            .unwrap_or(len);
+        let mut colors: Vec<Option<Color>> = vec![None; view_end.saturating_sub(view_start)];
+        if let Some(highlighter) = &mut self.highlighter {
+            let spans = highlighter.highlight_viewport(
+                &self.buffer,
+                view_start,
+                view_end,
+                &self.theme,
+                HIGHLIGHT_CONTEXT_BYTES,
+            );
+            for span in spans {
+                let start = span.range.start.clamp(view_start, view_end) - view_start;
+                let end = span.range.end.clamp(view_start, view_end) - view_start;
+                for slot in &mut colors[start..end] {
+                    *slot = Some(span.color);
+                }
+            }
+        }
+
+        let theme = &self.theme;
+        let gutter_style = Style::default()
+            .fg(theme.line_number_fg)
+            .bg(theme.line_number_bg);
+        let last_line = (self.top_line + text_height).min(line_count);
+        for (row, line) in (self.top_line..last_line).enumerate() {
+            let y = area.y + row as u16;
+            let number = format!("{:>1$} ", line + 1, gutter_width - 1);
+            screen.set_stringn(area.x, y, &number, gutter_width, gutter_style);
+
+            let bg = if line == cursor_line {
+                theme.current_line_bg
+            } else {
+                theme.editor_bg
+            };
+            let text_x = area.x + gutter_width as u16;
+            screen.set_style(
+                Rect::new(text_x, y, text_width as u16, 1),
+                Style::default().bg(bg),
+            );
+
+            let (start, text) = self.line(line);
+            let mut column = 0;
+            for (index, grapheme) in text.grapheme_indices(true) {
+                let width = if grapheme == "\t" {
+                    tab_width_at(column, TAB_SIZE)
+                } else {
+                    str_width(grapheme)
+                };
+                if column + width > self.left_column + text_width {
+                    break;
+                }
+                if column >= self.left_column {
+                    let fg = colors
+                        .get(start + index - view_start)
+                        .copied()
+                        .flatten()
+                        .unwrap_or(theme.editor_fg);
+                    let x = text_x + (column - self.left_column) as u16;
+                    // Tabs are drawn as the spaces they take up
+                    let symbol = if grapheme == "\t" { " " } else { grapheme };
+                    screen.set_stringn(x, y, symbol, width, Style::default().fg(fg).bg(bg));
+                }
+                column += width;
+            }
+        }
+
+        // Status bar
+        let y = area.bottom() - 1;
+        let status_style = Style::default()
+            .fg(theme.status_bar_fg)
+            .bg(theme.status_bar_bg);
+        screen.set_style(Rect::new(area.x, y, area.width, 1), status_style);
+        let name = if self.name.is_empty() {
+            "[No Name]"
+        } else {
+            &self.name
+        };
+        let modified = if self.buffer.is_modified() {
+            " [+]"
+        } else {
+            ""
+        };
+        let left = format!(" {}{}", name, modified);
+        let syntax = self
+            .highlighter
+            .as_ref()
+            .map_or("Plain Text", |highlighter| highlighter.syntax_name());
+        let right = format!(
+            "Ln {}, Col {}  {} ",
+            cursor_line + 1,
+            cursor_column + 1,
+            syntax
+        );
+        screen.set_stringn(area.x, y, &left, area.width as usize, status_style);
+        let right_width = str_width(&right);
+        if str_width(&left) + right_width < area.width as usize {
+            let x = area.right() - right_width as u16;
+            screen.set_stringn(x, y, &right, right_width, status_style);
+        }
+
+        let row = cursor_line - self.top_line;
+        let column = cursor_column - self.left_column;
+        (column < text_width).then(|| {
+            ScreenPosition::new(area.x + (gutter_width + column) as u16, area.y + row as u16)
+        })
+    }
+}
+
+/// Write a screen as ANSI escape sequences redrawing a terminal of its size
+/// from the top left, then show the terminal's cursor at `cursor` (or hide
+/// it)
+pub fn to_ansi(screen: &ScreenBuffer, cursor: Option<ScreenPosition>) -> String {
+    let area = screen.area;
+    let mut out = String::from("\x1b[?25l");
+    for y in area.top()..area.bottom() {
+        let _ = write!(out, "\x1b[{};1H", y - area.y + 1);
+        let mut current = None;
+        // Cells covered by a wide character aren't written
+        let mut covered = 0;
+        for x in area.left()..area.right() {
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let cell = &screen[(x, y)];
+            let style = cell.style();
+            if current != Some(style) {
+                out.push_str(&sgr(style));
+                current = Some(style);
+            }
+            out.push_str(cell.symbol());
+            covered = str_width(cell.symbol()).saturating_sub(1);
+        }
+    }
+    out.push_str("\x1b[0m");
+    if let Some(cursor) = cursor {
+        let _ = write!(
+            out,
+            "\x1b[{};{}H\x1b[?25h",
+            cursor.y - area.y + 1,
+            cursor.x - area.x + 1
+        );
+    }
+    out
+}
+
+/// The SGR escape sequence setting a cell's style from scratch
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    let modifier = style.add_modifier;
+    for (flag, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if modifier.contains(flag) {
+            codes.push(code.to_string());
+        }
+    }
+    if let Some(fg) = style.fg.and_then(|color| color_code(color, 30)) {
+        codes.push(fg);
+    }
+    if let Some(bg) = style.bg.and_then(|color| color_code(color, 40)) {
+        codes.push(bg);
+    }
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+/// The SGR parameters of a color, with `base` 30 for the foreground and 40
+/// for the background. None for `Color::Reset`, the terminal's own color.
+fn color_code(color: Color, base: u8) -> Option<String> {
+    let named = |index: u8| {
+        if index < 8 {
+            base + index
+        } else {
+            base + 60 + index - 8
+        }
+    };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+        Color::Indexed(i) => return Some(format!("{};5;{}", base + 8, i)),
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => named(8),
+        Color::LightRed => named(9),
+        Color::LightGreen => named(10),
+        Color::LightYellow => named(11),
+        Color::LightBlue => named(12),
+        Color::LightMagenta => named(13),
+        Color::LightCyan => named(14),
+        Color::White => named(15),
+    };
+    Some(code.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row_text(screen: &ScreenBuffer, y: u16) -> String {
+        (0..screen.area.width)
+            .map(|x| screen[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_lines_and_status_bar() {
+        let mut editor = WasmEditor::with_content("fn main() {\n\tx\n}\n");
+        editor.set_cursor(13);
+        let area = Rect::new(0, 0, 20, 5);
+        let mut screen = ScreenBuffer::empty(area);
+        let cursor = editor.render(area, &mut screen);
+
+        assert_eq!(row_text(&screen, 0), "  1 fn main() {     ");
+        assert_eq!(row_text(&screen, 1), "  2     x           ");
+        assert_eq!(row_text(&screen, 2), "  3 }               ");
+        assert_eq!(row_text(&screen, 3), "  4                 ");
+        assert!(row_text(&screen, 4).starts_with(" [No Name]"));
+        // After the tab, which takes up four columns
+        assert_eq!(cursor, Some(ScreenPosition::new(8, 1)));
+    }
+
+    #[test]
+    fn test_render_scrolls_to_cursor() {
+        let content: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+        let mut editor = WasmEditor::with_content(&content);
+        editor.set_cursor(content.find("line 15").unwrap());
+        let area = Rect::new(0, 0, 20, 4);
+        let mut screen = ScreenBuffer::empty(area);
+        let cursor = editor.render(area, &mut screen);
+
+        assert_eq!(row_text(&screen, 2), " 15 line 15         ");
+        assert_eq!(cursor, Some(ScreenPosition::new(4, 2)));
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let area = Rect::new(0, 0, 3, 2);
+        let mut screen = ScreenBuffer::empty(area);
+        screen.set_string(0, 0, "a", Style::default().fg(Color::Rgb(1, 2, 3)));
+        screen.set_string(0, 1, "界", Style::default().add_modifier(Modifier::BOLD));
+
+        let ansi = to_ansi(&screen, Some(ScreenPosition::new(1, 1)));
+        assert_eq!(
+            ansi,
+            "\x1b[?25l\
+             \x1b[1;1H\x1b[0;38;2;1;2;3ma\x1b[0m  \
+             \x1b[2;1H\x1b[0;1m界\x1b[0m \
+             \x1b[0m\x1b[2;2H\x1b[?25h"
+        );
+    }
+}
@@ -72,7 +72,7 @@ while true; do
 case "$method" in
     "initialize")
         # Send initialize response
-        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"completionProvider":{"triggerCharacters":[".",":",":"]},"definitionProvider":true,"hoverProvider":true,"textDocumentSync":1,"semanticTokensProvider":{"legend":{"tokenTypes":["keyword","function","variable"],"tokenModifiers":["declaration","deprecated"]},"full":{"delta":true},"range":true}}}}'
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"completionProvider":{"triggerCharacters":[".",":",":"]},"definitionProvider":true,"documentSymbolProvider":true,"hoverProvider":true,"textDocumentSync":1,"semanticTokensProvider":{"legend":{"tokenTypes":["keyword","function","variable"],"tokenModifiers":["declaration","deprecated"]},"full":{"delta":true},"range":true}}}}'
         ;;
     "textDocument/hover")
         # Send hover response with range
@@ -92,6 +92,10 @@ case "$method" in
         uri=$(echo "$msg" | grep -o '"uri":"[^"]*"' | head -1 | cut -d'"' -f4)
         send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"uri":"'$uri'","range":{"start":{"line":0,"character":0},"end":{"line":0,"character":10}}}}'
         ;;
+    "textDocument/documentSymbol")
+        # Send a function with a nested local variable (line 0 and line 1)
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[{"name":"main","kind":12,"range":{"start":{"line":0,"character":0},"end":{"line":2,"character":1}},"selectionRange":{"start":{"line":0,"character":3},"end":{"line":0,"character":7}},"children":[{"name":"value","kind":13,"range":{"start":{"line":1,"character":4},"end":{"line":1,"character":18}},"selectionRange":{"start":{"line":1,"character":8},"end":{"line":1,"character":13}}}]}]}'
+        ;;
     "textDocument/semanticTokens/full")
         send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"resultId":"1","data":[0,0,2,0,0,0,3,4,1,0]}}'
         ;;
@@ -1153,6 +1157,8 @@ while true; do
 
 case "$method" in
     "initialize")
+        # Log the workspace folders the server is started with
+        echo "$msg" | grep -o '"workspaceFolders":\[[^]]*\]' | grep -o '"uri":"[^"]*"' | cut -d'"' -f4 | sed 's/^/workspaceFolder /' >> "$LOG_FILE"
         # Send initialize response
         send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"completionProvider":{"triggerCharacters":[".",":",":"]},"definitionProvider":true,"hoverProvider":true,"textDocumentSync":1}}}'
         ;;
@@ -1323,6 +1329,107 @@ done
         std::env::temp_dir().join("fake_lsp_server_no_range.sh")
     }
 
+    /// Spawn a fake LSP server that updates imports when a Rust file is renamed
+    ///
+    /// It answers workspace/willRenameFiles with an edit replacing the
+    /// characters 4..7 of the first line of `main.rs`, next to the renamed
+    /// file, with "new" (as in `mod old;` -> `mod new;`).
+    pub fn spawn_with_file_rename() -> anyhow::Result<Self> {
+        let (stop_tx, stop_rx) = mpsc::channel();
+
+        let script = r#"#!/bin/bash
+
+# Function to read a message
+read_message() {
+    # Read headers
+    local content_length=0
+    while IFS=: read -r key value; do
+        key=$(echo "$key" | tr -d '\r\n')
+        value=$(echo "$value" | tr -d '\r\n ')
+        if [ "$key" = "Content-Length" ]; then
+            content_length=$value
+        fi
+        # Empty line marks end of headers
+        if [ -z "$key" ]; then
+            break
+        fi
+    done
+
+    # Read content
+    if [ $content_length -gt 0 ]; then
+        dd bs=1 count=$content_length 2>/dev/null
+    fi
+}
+
+# Function to send a message
+send_message() {
+    local message="$1"
+    local length=${#message}
+    echo -en "Content-Length: $length\r\n\r\n$message"
+}
+
+# Main loop
+while true; do
+    # Read incoming message
+    msg=$(read_message)
+
+    if [ -z "$msg" ]; then
+        break
+    fi
+
+    # Extract method from JSON
+    method=$(echo "$msg" | grep -o '"method":"[^"]*"' | cut -d'"' -f4)
+    msg_id=$(echo "$msg" | grep -o '"id":[0-9]*' | cut -d':' -f2)
+
+    case "$method" in
+    "initialize")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"capabilities":{"textDocumentSync":1,"workspace":{"fileOperations":{"willRename":{"filters":[{"scheme":"file","pattern":{"glob":"**/*.rs","matches":"file"}}]}}}}}}'
+        ;;
+    "workspace/willRenameFiles")
+        old_uri=$(echo "$msg" | grep -o '"oldUri":"[^"]*"' | cut -d'"' -f4)
+        dir="${old_uri%/*}"
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"changes":{"'$dir'/main.rs":[{"range":{"start":{"line":0,"character":4},"end":{"line":0,"character":7}},"newText":"new"}]}}}'
+        ;;
+    "textDocument/diagnostic")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":{"items":[]}}'
+        ;;
+    "textDocument/inlayHint")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":[]}'
+        ;;
+    "shutdown")
+        send_message '{"jsonrpc":"2.0","id":'$msg_id',"result":null}'
+        break
+        ;;
+    esac
+done
+"#;
+
+        // Write script to a temporary file
+        let script_path = Self::file_rename_script_path();
+        std::fs::write(&script_path, script)?;
+
+        // Make it executable
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = std::fs::metadata(&script_path)?.permissions();
+            perms.set_mode(0o755);
+            std::fs::set_permissions(&script_path, perms)?;
+        }
+
+        let handle = Some(thread::spawn(move || {
+            // Wait for stop signal
+            let _ = stop_rx.recv();
+        }));
+
+        Ok(Self { handle, stop_tx })
+    }
+
+    /// Get the path to the file rename fake LSP server script
+    pub fn file_rename_script_path() -> std::path::PathBuf {
+        std::env::temp_dir().join("fake_lsp_server_file_rename.sh")
+    }
+
     /// Stop the server
     pub fn stop(&mut self) {
         let _ = self.stop_tx.send(());
@@ -17,7 +17,10 @@ fn init_test_environment() {
     fresh_plugin_runtime::backend::set_panic_on_js_errors(true);
 }
 
-use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
 
 /// Terminal layout constants
 /// The editor uses a fixed layout with reserved rows for UI elements
@@ -152,6 +155,14 @@ pub struct HarnessOptions {
     /// Preserve the keybinding map from the config (don't force "default").
     /// Set this when testing a specific keymap like emacs.
     pub preserve_keybinding_map: bool,
+    /// Don't trust the working directory, so the editor starts in safe mode.
+    pub untrusted_workspace: bool,
+    /// Keep `editor.plugin_sandbox` from the config. Off by default, since
+    /// test plugins are loaded from the working directory.
+    pub plugin_sandbox: bool,
+    /// Load the bundled syntax grammars. Off by default, since loading them
+    /// is slow; tests of language detection need them.
+    pub full_grammars: bool,
 }
 
 impl HarnessOptions {
@@ -168,6 +179,9 @@ impl HarnessOptions {
             slow_fs_config: None,
             filesystem: None,
             preserve_keybinding_map: false,
+            untrusted_workspace: false,
+            plugin_sandbox: false,
+            full_grammars: false,
         }
     }
 
@@ -233,6 +247,25 @@ impl HarnessOptions {
         self.preserve_keybinding_map = true;
         self
     }
+
+    /// Don't trust the working directory: its plugins and config commands
+    /// are left out until the user trusts it.
+    pub fn with_untrusted_workspace(mut self) -> Self {
+        self.untrusted_workspace = true;
+        self
+    }
+
+    /// Sandbox the plugins, so they have to ask for permissions
+    pub fn with_plugin_sandbox(mut self) -> Self {
+        self.plugin_sandbox = true;
+        self
+    }
+
+    /// Load the bundled syntax grammars, for tests that detect languages
+    pub fn with_full_grammars(mut self) -> Self {
+        self.full_grammars = true;
+        self
+    }
 }
 
 /// A wrapper that captures CrosstermBackend output for vt100 parsing
@@ -364,6 +397,11 @@ impl EditorTestHarness {
             )
         });
 
+        // Tests run the project's plugins unless they test safe mode
+        if !options.untrusted_workspace {
+            fresh::services::workspace_trust::trust(&dir_context, &working_dir)?;
+        }
+
         // Create TestTimeSource for controllable time in tests
         let test_time_source = Arc::new(TestTimeSource::new());
         let time_source: SharedTimeSource = test_time_source.clone();
@@ -383,6 +421,9 @@ impl EditorTestHarness {
             config.active_keybinding_map = fresh::config::KeybindingMapName("default".to_string());
         }
         config.check_for_updates = false; // Disable update checking in tests
+        if !options.plugin_sandbox {
+            config.editor.plugin_sandbox = false;
+        }
 
         // Initialize i18n with the config's locale before creating the editor
         // This ensures menu defaults are created with the correct translations
@@ -406,6 +447,12 @@ impl EditorTestHarness {
         let backend = TestBackend::new(width, height);
         let terminal = Terminal::new(backend)?;
 
+        let grammar_registry = if options.full_grammars {
+            fresh::primitives::grammar::GrammarRegistry::for_editor()
+        } else {
+            fresh::primitives::grammar::GrammarRegistry::empty()
+        };
+
         // Create editor
         let mut editor = Editor::for_test(
             config,
@@ -416,6 +463,7 @@ impl EditorTestHarness {
             fresh::view::color_support::ColorCapability::TrueColor,
             filesystem,
             Some(time_source),
+            grammar_registry,
         )?;
 
         // Process any pending plugin commands
@@ -700,6 +748,33 @@ impl EditorTestHarness {
         Ok(())
     }
 
+    /// Simulate a string of text arriving as key presses that are all queued
+    /// up at once, like a paste in a terminal without bracketed paste. The
+    /// burst is coalesced the way main.rs does it before being handled.
+    pub fn send_typed_burst(&mut self, text: &str) -> anyhow::Result<()> {
+        let burst = text
+            .chars()
+            .map(|ch| {
+                let code = match ch {
+                    '\n' => KeyCode::Enter,
+                    '\t' => KeyCode::Tab,
+                    ch => KeyCode::Char(ch),
+                };
+                CrosstermEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+            })
+            .collect();
+        for event in self.editor.coalesce_typed_paste(burst) {
+            match event {
+                CrosstermEvent::Paste(text) => self.editor.paste_text(text),
+                CrosstermEvent::Key(key) => self.editor.handle_key(key.code, key.modifiers)?,
+                _ => {}
+            }
+        }
+        let _ = self.editor.process_async_messages();
+        self.render()?;
+        Ok(())
+    }
+
     /// Simulate a mouse event
     pub fn send_mouse(&mut self, mouse_event: MouseEvent) -> anyhow::Result<()> {
         // Delegate to the editor's handle_mouse method (just like main.rs does)
@@ -751,6 +826,28 @@ impl EditorTestHarness {
         Ok(())
     }
 
+    /// Simulate a ctrl+click at specific coordinates (for opening links and go to definition)
+    pub fn mouse_ctrl_click(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
+        let mouse_event = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: col,
+            row,
+            modifiers: KeyModifiers::CONTROL,
+        };
+        self.send_mouse(mouse_event)?;
+
+        // Also send the release event
+        let mouse_up = MouseEvent {
+            kind: MouseEventKind::Up(MouseButton::Left),
+            column: col,
+            row,
+            modifiers: KeyModifiers::CONTROL,
+        };
+        self.send_mouse(mouse_up)?;
+        self.render()?;
+        Ok(())
+    }
+
     /// Simulate a mouse move (hover) at specific coordinates
     pub fn mouse_move(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
         let mouse_event = MouseEvent {
@@ -1037,6 +1134,11 @@ impl EditorTestHarness {
         buffer.content.get(pos).map(|cell| cell.style())
     }
 
+    /// Get the background color of a cell at the given position
+    pub fn get_cell_bg(&self, x: u16, y: u16) -> Option<ratatui::style::Color> {
+        self.get_cell_style(x, y).and_then(|style| style.bg)
+    }
+
     /// Check if a cell at the given position is a scrollbar thumb.
     ///
     /// Since the scrollbar is rendered using background colors (not characters),
@@ -1659,6 +1761,15 @@ impl EditorTestHarness {
         self.wait_until(|h| !h.editor().is_prompting())
     }
 
+    /// Run a command from the command palette by typing its name
+    pub fn run_command(&mut self, name: &str) -> anyhow::Result<()> {
+        self.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+        self.wait_for_prompt()?;
+        self.type_text(name)?;
+        self.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+        self.render()
+    }
+
     /// Open the settings dialog via command palette
     /// This is the preferred way to open settings since Ctrl+, doesn't work reliably in terminals
     pub fn open_settings(&mut self) -> anyhow::Result<()> {
@@ -14,6 +14,9 @@ pub mod git_test_helper;
 pub mod harness;
 #[cfg(test)]
 #[allow(dead_code)]
+pub mod peers;
+#[cfg(test)]
+#[allow(dead_code)]
 pub mod scrollbar;
 #[cfg(test)]
 #[allow(dead_code)]
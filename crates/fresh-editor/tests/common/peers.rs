@@ -0,0 +1,43 @@
+//! Helpers for tests of editors connected to each other, in collaboration
+//! sessions or presentations
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
+
+/// Run a command that prompts for an address or join code, replacing any
+/// default with `address`
+pub fn run_with_address(harness: &mut EditorTestHarness, command: &str, address: &str) {
+    harness.run_command(command).unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(address).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Let both editors exchange messages until `condition` holds
+pub fn sync_until(
+    first: &mut EditorTestHarness,
+    second: &mut EditorTestHarness,
+    condition: impl Fn(&EditorTestHarness, &EditorTestHarness) -> bool,
+) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        for harness in [&mut *first, &mut *second] {
+            harness.editor_mut().sync_collab();
+            harness.editor_mut().sync_presentation();
+            // Presenters send the buffer text at most every 100ms while typing
+            harness.advance_time(Duration::from_millis(100));
+            harness.render().unwrap();
+        }
+        if condition(first, second) {
+            return;
+        }
+        assert!(Instant::now() < deadline, "peers did not sync");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
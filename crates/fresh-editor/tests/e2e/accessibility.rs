@@ -0,0 +1,88 @@
+//! Tests for the accessibility settings
+//!
+//! Tests that:
+//! - `reduced_motion` makes mouse wheel scrolls jump even with smooth scrolling
+//! - `high_contrast` raises theme colors to a 7:1 contrast ratio
+//! - `large_hit_targets` lets the text next to a split separator start a drag
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::view::theme::{contrast_ratio, HIGH_CONTRAST_RATIO};
+use tempfile::TempDir;
+
+/// Create a vertical split via the command palette
+fn split_vertically(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("split vert").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_reduced_motion_disables_smooth_scrolling() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    let content: String = (1..=100).map(|i| format!("line {i:03}\n")).collect();
+    std::fs::write(&file, content).unwrap();
+
+    let mut config = Config::default();
+    config.editor.smooth_scrolling = true;
+    config.editor.reduced_motion = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness.mouse_scroll_down(40, 10).unwrap();
+    assert_eq!(harness.top_line_number(), 3);
+    assert!(!harness.editor_mut().step_smooth_scroll());
+}
+
+#[test]
+fn test_high_contrast_theme() {
+    let mut config = Config::default();
+    config.editor.high_contrast = true;
+    let harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    let theme = harness.editor().theme();
+    for (name, fg, bg) in [
+        ("editor", theme.editor_fg, theme.editor_bg),
+        ("comment", theme.syntax_comment, theme.editor_bg),
+        ("line number", theme.line_number_fg, theme.line_number_bg),
+        ("status bar", theme.status_bar_fg, theme.status_bar_bg),
+    ] {
+        if let Some(ratio) = contrast_ratio(fg, bg) {
+            assert!(
+                ratio >= HIGH_CONTRAST_RATIO,
+                "{name} contrast is only {ratio:.2}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_large_hit_targets_separator() {
+    let drag_next_to_separator = |large_hit_targets: bool| {
+        let mut config = Config::default();
+        config.editor.large_hit_targets = large_hit_targets;
+        let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+        split_vertically(&mut harness);
+        let separators = harness.editor().get_separator_areas();
+        assert_eq!(separators.len(), 1, "the split wasn't created");
+
+        let (split_id, _, sep_x, sep_y, sep_length) = separators[0];
+        let initial_ratio = harness.editor().get_split_ratio(split_id).unwrap();
+        let row = sep_y + sep_length / 2;
+        // Grab the first text column of the right split and drag it right
+        harness.mouse_drag(sep_x + 1, row, sep_x + 11, row).unwrap();
+        harness.editor().get_split_ratio(split_id).unwrap() - initial_ratio
+    };
+
+    assert_eq!(drag_next_to_separator(false), 0.0);
+    assert!(drag_next_to_separator(true) > 0.0);
+}
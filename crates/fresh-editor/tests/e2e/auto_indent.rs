@@ -1687,3 +1687,112 @@ fn test_bracket_expansion_jsx_component() {
         content
     );
 }
+
+/// Open `name` with `content` from `temp_dir`, as the working directory, and
+/// move the cursor to the end. The shadow string only follows plain
+/// keystrokes, not the indentation Enter inserts, so it isn't validated here.
+fn harness_with_file(temp_dir: &TempDir, name: &str, content: &str) -> EditorTestHarness {
+    let file_path = temp_dir.path().join(name);
+    std::fs::write(&file_path, content).unwrap();
+    let mut config = Config::default();
+    config.editor.auto_indent = true;
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_working_dir(temp_dir.path().to_path_buf())
+            .without_empty_plugins_dir(),
+    )
+    .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+}
+
+/// Languages without an indents query use their increase and decrease patterns
+#[test]
+fn test_heuristic_indent_patterns_latex() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = harness_with_file(&temp_dir, "doc.tex", "\\begin{itemize}");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("\\item one").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("\\begin{itemize}\n    \\item one\n    ");
+
+    // Typing `\end{` dedents the line
+    harness.type_text("\\end{").unwrap();
+    harness.assert_buffer_content("\\begin{itemize}\n    \\item one\n\\end{}");
+}
+
+/// Continuation lines are indented one level, and the next statement goes
+/// back to the indent of the first
+#[test]
+fn test_heuristic_indent_continuation_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = harness_with_file(&temp_dir, "Dockerfile", "RUN apt-get update && \\");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("apt-get install -y curl").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("RUN apt-get update && \\\n    apt-get install -y curl\n");
+}
+
+/// Arguments continued after an unclosed bracket line up with the first
+#[test]
+fn test_heuristic_hanging_indent() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = harness_with_file(&temp_dir, "deps.toml", "names = [\"one\",");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("names = [\"one\",\n         ");
+}
+
+/// Re-indent Lines fixes the whole file as one undo step, or only the
+/// selected lines
+#[test]
+fn test_reindent_lines_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "\\begin{itemize}\n\\item one\n        \\item two\n    \\end{itemize}\n";
+    let mut harness = harness_with_file(&temp_dir, "doc.tex", content);
+
+    harness.run_command("Re-indent Lines").unwrap();
+    harness.assert_buffer_content(
+        "\\begin{itemize}\n    \\item one\n    \\item two\n\\end{itemize}\n",
+    );
+    harness.assert_screen_contains("Re-indented 3 line(s)");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(content);
+
+    // A selection ending at the start of a line leaves that line alone
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.run_command("Re-indent Lines").unwrap();
+    harness.assert_buffer_content(
+        "\\begin{itemize}\n    \\item one\n        \\item two\n    \\end{itemize}\n",
+    );
+    harness.assert_screen_contains("Re-indented 1 line(s)");
+}
@@ -466,3 +466,134 @@ fn test_auto_revert_with_temp_rename_save() {
         harness.assert_buffer_content(&new_content);
     }
 }
+
+/// Regaining terminal focus checks open files for external changes right
+/// away, without waiting for the poll interval
+#[test]
+fn test_auto_revert_on_focus_gained() {
+    let mut config = fresh::config::Config::default();
+    // Long enough that polling never picks the change up during the test
+    config.editor.auto_revert_poll_interval_ms = 600_000;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("focus.txt");
+    write_and_sync(&file_path, "Before");
+
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("Before");
+
+    // Change the file with an mtime that surely differs from the one seen
+    write_and_sync(&file_path, "After");
+    File::options()
+        .write(true)
+        .open(&file_path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+
+    assert!(harness.editor_mut().handle_terminal_focus_gained());
+    harness.assert_buffer_content("After");
+
+    // Nothing changed since
+    assert!(!harness.editor_mut().handle_terminal_focus_gained());
+}
+
+/// The check on focus gained can be turned off
+#[test]
+fn test_auto_revert_on_focus_gained_disabled() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_poll_interval_ms = 600_000;
+    config.editor.auto_revert_on_focus_gain = false;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("focus.txt");
+    write_and_sync(&file_path, "Before");
+
+    harness.open_file(&file_path).unwrap();
+    write_and_sync(&file_path, "After");
+    File::options()
+        .write(true)
+        .open(&file_path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+
+    assert!(!harness.editor_mut().handle_terminal_focus_gained());
+    harness.assert_buffer_content("Before");
+}
+
+/// Write `content` to `path` with an mtime that surely differs from the one
+/// the editor last saw
+fn write_with_later_mtime(path: &Path, content: &str) {
+    write_and_sync(path, content);
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + Duration::from_secs(10))
+        .unwrap();
+}
+
+/// With merging turned on, changes on disk to lines the buffer didn't change
+/// are merged into a buffer with unsaved changes, as a single undo step
+#[test]
+fn test_auto_revert_merges_into_modified_buffer() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_poll_interval_ms = 600_000;
+    config.editor.auto_revert_merge = true;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("merge.txt");
+    write_and_sync(&file_path, "one\ntwo\nthree\n");
+
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("zero ").unwrap();
+    harness.assert_buffer_content("zero one\ntwo\nthree\n");
+
+    write_with_later_mtime(&file_path, "one\ntwo\nthree\nfour\n");
+    assert!(harness.editor_mut().handle_terminal_focus_gained());
+    harness.assert_buffer_content("zero one\ntwo\nthree\nfour\n");
+    assert!(harness.editor().active_state().buffer.is_modified());
+
+    // The cursor stays after the text typed
+    assert_eq!(harness.cursor_position(), 5);
+
+    // Undoing the merge leaves the local edit
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("zero one\ntwo\nthree\n");
+}
+
+/// Lines changed both in the buffer and on disk are kept in both versions,
+/// between conflict markers
+#[test]
+fn test_auto_revert_merge_conflict() {
+    let mut config = fresh::config::Config::default();
+    config.editor.auto_revert_poll_interval_ms = 600_000;
+    config.editor.auto_revert_merge = true;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("conflict.txt");
+    write_and_sync(&file_path, "one\ntwo\n");
+
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("mine ").unwrap();
+
+    write_with_later_mtime(&file_path, "theirs one\ntwo\n");
+    assert!(harness.editor_mut().handle_terminal_focus_gained());
+    harness.assert_buffer_content(
+        "<<<<<<< buffer\nmine one\n=======\ntheirs one\n>>>>>>> disk\ntwo\n",
+    );
+    let status = harness.editor().get_status_message().cloned().unwrap();
+    assert!(status.contains("1 conflict"), "status: {}", status);
+
+    // Changed on disk again: merged against the last version seen, so the
+    // conflict isn't repeated
+    write_with_later_mtime(&file_path, "theirs one\ntwo\nthree\n");
+    assert!(harness.editor_mut().handle_terminal_focus_gained());
+    harness.assert_buffer_content(
+        "<<<<<<< buffer\nmine one\n=======\ntheirs one\n>>>>>>> disk\ntwo\nthree\n",
+    );
+}
@@ -542,3 +542,37 @@ fn test_block_selection_copy_copies_rectangular_region() {
         "Block selection copy should produce exactly the rectangular region"
     );
 }
+
+/// Test that block selection columns are visual columns, so a tab counts up
+/// to the next tab stop rather than as a single column
+#[test]
+fn test_block_selection_columns_expand_tabs() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file = temp_dir.path().join("tabs.txt");
+    std::fs::write(&file, "\tAB x\n    CD y\n").unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.tab_size = 4;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.editor_mut().set_clipboard_for_test("".to_string());
+
+    // Past the tab, which is visual column 4 on both lines
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), "\tAB x\n    ".len());
+
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::ALT | KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor_mut().clipboard_content_for_test(), "AB\nCD");
+}
@@ -0,0 +1,76 @@
+//! Tests for the breadcrumb bar
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+/// The breadcrumb bar is the row under the tab bar
+const BREADCRUMB_ROW: u16 = 2;
+
+const SOURCE: &str = "fn alpha() {\n    let x = 1;\n}\n\nfn beta() {}\n";
+
+fn harness_with_breadcrumbs() -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.show_breadcrumbs = true;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let path = harness.project_dir().unwrap().join("main.rs");
+    std::fs::write(&path, SOURCE).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// Click the crumb with the given label
+fn click_crumb(harness: &mut EditorTestHarness, label: &str) {
+    let row = harness.get_screen_row(BREADCRUMB_ROW as usize);
+    let byte = row.find(label).expect("crumb on the breadcrumb bar");
+    let col = row[..byte].chars().count() as u16;
+    harness.mouse_click(col, BREADCRUMB_ROW).unwrap();
+}
+
+/// The bar shows the file, then the definition around the cursor
+#[test]
+fn test_breadcrumbs_show_enclosing_symbol() {
+    let mut harness = harness_with_breadcrumbs();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let row = harness.get_screen_row(BREADCRUMB_ROW as usize);
+    assert!(row.contains("main.rs › alpha"), "Got: {}", row);
+
+    // Outside any definition only the file is shown
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    let row = harness.get_screen_row(BREADCRUMB_ROW as usize);
+    assert!(row.trim_end().ends_with("main.rs"), "Got: {}", row);
+}
+
+/// Clicking a symbol crumb offers its siblings to go to
+#[test]
+fn test_breadcrumb_dropdown_goes_to_symbol() {
+    let mut harness = harness_with_breadcrumbs();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    click_crumb(&mut harness, "alpha");
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), SOURCE.find("beta").unwrap());
+    let row = harness.get_screen_row(BREADCRUMB_ROW as usize);
+    assert!(row.contains("main.rs › beta"), "Got: {}", row);
+}
+
+/// Toggling hides the bar and gives its row back to the content
+#[test]
+fn test_toggle_breadcrumbs() {
+    let mut harness = harness_with_breadcrumbs();
+    harness.editor_mut().toggle_breadcrumbs();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Breadcrumbs hidden");
+    let row = harness.get_screen_row(BREADCRUMB_ROW as usize);
+    assert!(row.contains("fn alpha()"), "Got: {}", row);
+}
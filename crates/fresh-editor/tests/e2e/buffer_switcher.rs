@@ -0,0 +1,103 @@
+//! E2E tests for the Ctrl+Tab buffer switcher
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers, ModifierKeyCode};
+use tempfile::TempDir;
+
+/// A harness with a.txt, b.txt and c.txt opened in that order
+fn harness_with_files() -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, temp_dir.path().to_path_buf()).unwrap();
+    for (name, content) in [("a.txt", "alpha"), ("b.txt", "bravo"), ("c.txt", "charlie")] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    (harness, temp_dir)
+}
+
+fn ctrl_tab(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Tab, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn release_ctrl(harness: &mut EditorTestHarness) {
+    let release = KeyEvent::new_with_kind(
+        KeyCode::Modifier(ModifierKeyCode::LeftControl),
+        KeyModifiers::CONTROL,
+        KeyEventKind::Release,
+    );
+    harness.editor_mut().handle_key_release(&release);
+    harness.render().unwrap();
+}
+
+/// Ctrl+Tab previews buffers in most recently used order, and releasing Ctrl
+/// keeps the highlighted one
+#[test]
+fn test_buffer_switcher_mru_preview_and_release() {
+    let (mut harness, _temp_dir) = harness_with_files();
+
+    ctrl_tab(&mut harness);
+    harness.assert_screen_contains("Switch Buffer");
+    assert_eq!(harness.get_buffer_content().unwrap(), "bravo");
+
+    ctrl_tab(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "alpha");
+
+    release_ctrl(&mut harness);
+    assert!(!harness.editor().is_buffer_switcher_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "alpha");
+
+    // The buffer switched from is now the most recently used
+    ctrl_tab(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "charlie");
+
+    // Esc goes back to the buffer the switcher was opened from
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert!(!harness.editor().is_buffer_switcher_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "alpha");
+}
+
+/// Ctrl+W closes the highlighted buffer without closing the switcher, but
+/// not if it has unsaved changes
+#[test]
+fn test_buffer_switcher_close_buffer() {
+    let (mut harness, _temp_dir) = harness_with_files();
+    harness.type_text("modified ").unwrap();
+
+    ctrl_tab(&mut harness);
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert!(harness.editor().is_buffer_switcher_open());
+    harness.assert_screen_not_contains("b.txt");
+    assert_eq!(harness.get_buffer_content().unwrap(), "alpha");
+
+    // The modified buffer stays open
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("c.txt has unsaved changes");
+    assert_eq!(harness.get_buffer_content().unwrap(), "modified charlie");
+}
+
+/// A key the switcher doesn't use keeps the highlighted buffer and is then
+/// handled as usual
+#[test]
+fn test_buffer_switcher_other_key_commits() {
+    let (mut harness, _temp_dir) = harness_with_files();
+
+    ctrl_tab(&mut harness);
+    harness.type_text("x").unwrap();
+    harness.render().unwrap();
+    assert!(!harness.editor().is_buffer_switcher_open());
+    harness.assert_screen_not_contains("Switch Buffer");
+    assert_eq!(harness.get_buffer_content().unwrap(), "xbravo");
+}
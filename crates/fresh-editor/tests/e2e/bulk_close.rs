@@ -0,0 +1,137 @@
+//! E2E tests for Close All / Close Saved / Close Others and the prompt for
+//! buffers with unsaved changes
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// A harness with a.txt, b.txt and c.txt opened in that order
+fn harness_with_files() -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness =
+        EditorTestHarness::with_working_dir(120, 30, temp_dir.path().to_path_buf()).unwrap();
+    for (name, content) in [("a.txt", "alpha"), ("b.txt", "bravo"), ("c.txt", "charlie")] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Open a file that's already open and type `text` at its start
+fn edit(harness: &mut EditorTestHarness, temp_dir: &TempDir, name: &str, text: &str) {
+    harness.open_file(&temp_dir.path().join(name)).unwrap();
+    harness.type_text(text).unwrap();
+}
+
+fn press(harness: &mut EditorTestHarness, code: KeyCode) {
+    harness.send_key(code, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+}
+
+/// Close Saved keeps the buffers with unsaved changes, and Close All closes
+/// a buffer edited back to its saved content without asking
+#[test]
+fn test_close_saved_and_edited_back_buffers() {
+    let (mut harness, temp_dir) = harness_with_files();
+    edit(&mut harness, &temp_dir, "b.txt", "x");
+
+    harness.editor_mut().close_saved_buffers();
+    harness.render().unwrap();
+    let tabs = harness.get_tab_bar();
+    assert!(tabs.contains("b.txt"), "tabs: {}", tabs);
+    assert!(
+        !tabs.contains("a.txt") && !tabs.contains("c.txt"),
+        "tabs: {}",
+        tabs
+    );
+
+    // Typed and deleted again: marked modified, but nothing to lose
+    press(&mut harness, KeyCode::Backspace);
+    harness.editor_mut().close_all_buffers();
+    harness.render().unwrap();
+    assert!(!harness.editor().is_prompting());
+    assert!(!harness.get_tab_bar().contains("b.txt"));
+}
+
+/// Discard All closes the unsaved buffers without writing them
+#[test]
+fn test_close_all_discard_all() {
+    let (mut harness, temp_dir) = harness_with_files();
+    edit(&mut harness, &temp_dir, "a.txt", "x");
+    edit(&mut harness, &temp_dir, "c.txt", "y");
+
+    harness.editor_mut().close_all_buffers();
+    harness.render().unwrap();
+    harness.assert_screen_contains("2 buffer(s) with unsaved changes: a.txt, c.txt");
+    harness.assert_screen_contains("Review Each");
+
+    press(&mut harness, KeyCode::Down);
+    press(&mut harness, KeyCode::Enter);
+    assert!(!harness.editor().is_prompting());
+    let tabs = harness.get_tab_bar();
+    assert!(
+        !tabs.contains("a.txt") && !tabs.contains("c.txt"),
+        "tabs: {}",
+        tabs
+    );
+    let a = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+    assert_eq!(a, "alpha");
+}
+
+/// Save All writes the unsaved buffers, then closes them
+#[test]
+fn test_close_others_save_all() {
+    let (mut harness, temp_dir) = harness_with_files();
+    edit(&mut harness, &temp_dir, "a.txt", "x");
+    harness.open_file(&temp_dir.path().join("b.txt")).unwrap();
+
+    harness.editor_mut().close_other_buffers();
+    harness.render().unwrap();
+    press(&mut harness, KeyCode::Enter);
+
+    harness.assert_screen_contains("Saved and closed 1 buffer(s)");
+    let tabs = harness.get_tab_bar();
+    assert!(tabs.contains("b.txt"), "tabs: {}", tabs);
+    assert!(
+        !tabs.contains("a.txt") && !tabs.contains("c.txt"),
+        "tabs: {}",
+        tabs
+    );
+    let a = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+    assert_eq!(a, "xalpha");
+}
+
+/// Review Each asks about the unsaved buffers one after the other
+#[test]
+fn test_close_all_review_each() {
+    let (mut harness, temp_dir) = harness_with_files();
+    edit(&mut harness, &temp_dir, "a.txt", "x");
+    edit(&mut harness, &temp_dir, "b.txt", "y");
+
+    harness.editor_mut().close_all_buffers();
+    harness.render().unwrap();
+    press(&mut harness, KeyCode::Down);
+    press(&mut harness, KeyCode::Down);
+    press(&mut harness, KeyCode::Enter);
+
+    harness.assert_screen_contains("'a.txt' modified");
+    harness.type_text("d").unwrap();
+    press(&mut harness, KeyCode::Enter);
+
+    harness.assert_screen_contains("'b.txt' modified");
+    harness.type_text("s").unwrap();
+    press(&mut harness, KeyCode::Enter);
+
+    assert!(!harness.editor().is_prompting());
+    let a = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+    let b = std::fs::read_to_string(temp_dir.path().join("b.txt")).unwrap();
+    assert_eq!((a.as_str(), b.as_str()), ("alpha", "ybravo"));
+    let tabs = harness.get_tab_bar();
+    assert!(
+        !tabs.contains("a.txt") && !tabs.contains("b.txt"),
+        "tabs: {}",
+        tabs
+    );
+}
@@ -398,3 +398,56 @@ fn test_case_conversion_from_command_palette() {
         "Text should be converted to uppercase via command palette"
     );
 }
+
+/// Test converting the word under each cursor between identifier styles
+#[test]
+fn test_identifier_case_multicursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("fooBar = 1\nHTTPServer = 2").unwrap();
+
+    // One cursor at the start of each line, with no selection
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().add_cursor_above();
+    harness.render().unwrap();
+
+    harness.run_command("Convert to snake_case").unwrap();
+    harness.assert_buffer_content("foo_bar = 1\nhttp_server = 2");
+
+    harness
+        .run_command("Convert to SCREAMING_SNAKE_CASE")
+        .unwrap();
+    harness.assert_buffer_content("FOO_BAR = 1\nHTTP_SERVER = 2");
+
+    harness.run_command("Convert to camelCase").unwrap();
+    harness.assert_buffer_content("fooBar = 1\nhttpServer = 2");
+}
+
+/// Test converting a selection of code and of words
+#[test]
+fn test_identifier_case_selection() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("let user_name = get_user_id();").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.run_command("Convert to kebab-case").unwrap();
+    harness.assert_buffer_content("let user-name = get-user-id();");
+
+    // A line of plain words is one identifier
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("first name").unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.run_command("Convert to camelCase").unwrap();
+    harness.assert_buffer_content("firstName");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.run_command("Convert to Title Case").unwrap();
+    harness.assert_buffer_content("First Name");
+}
@@ -0,0 +1,88 @@
+//! E2E tests for the keybinding cheat sheet overlay
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn toggle_cheat_sheet(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(
+            KeyCode::Char('k'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A config binding F7 to Toggle Line Numbers, which has no key by default
+fn config_with_f7() -> Config {
+    let mut config = Config::default();
+    config.keybindings.push(
+        serde_json::from_value(serde_json::json!({
+            "key": "F7",
+            "modifiers": [],
+            "action": "toggle_line_numbers",
+            "when": "normal"
+        }))
+        .unwrap(),
+    );
+    config
+}
+
+/// The cheat sheet lists commands with their keys, grouped by category, and
+/// its toggle key closes it again
+#[test]
+fn test_cheat_sheet_lists_keymap() {
+    let mut harness = EditorTestHarness::new(100, 40).unwrap();
+    toggle_cheat_sheet(&mut harness);
+    harness.assert_screen_contains("Keybinding Cheat Sheet");
+    harness.assert_screen_contains("File");
+    harness.assert_screen_contains("Ctrl+S");
+
+    toggle_cheat_sheet(&mut harness);
+    harness.assert_screen_not_contains("Keybinding Cheat Sheet");
+}
+
+/// User rebindings show up in the cheat sheet, which can be searched, and
+/// Enter runs the selected command
+#[test]
+fn test_cheat_sheet_search_and_execute() {
+    let mut harness = EditorTestHarness::with_config(100, 40, config_with_f7()).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains(" │ ");
+
+    toggle_cheat_sheet(&mut harness);
+    harness.type_text("line numbers").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("F7");
+    harness.assert_screen_contains("Toggle Line Numbers");
+    harness.assert_screen_not_contains("Ctrl+S");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Keybinding Cheat Sheet");
+    harness.assert_screen_not_contains(" │ ");
+}
+
+/// Clicking an entry runs it, and clicking outside the overlay closes it
+#[test]
+fn test_cheat_sheet_mouse() {
+    let mut harness = EditorTestHarness::with_config(100, 40, config_with_f7()).unwrap();
+    toggle_cheat_sheet(&mut harness);
+    harness.type_text("Toggle Line Numbers").unwrap();
+    harness.render().unwrap();
+
+    // The search field also shows the query, so click the entry by its key
+    let (col, row) = harness.find_text_on_screen("F7").expect("entry shown");
+    harness.mouse_click(col, row).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Keybinding Cheat Sheet");
+    harness.assert_screen_not_contains(" │ ");
+
+    toggle_cheat_sheet(&mut harness);
+    harness.mouse_click(0, 20).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Keybinding Cheat Sheet");
+}
@@ -0,0 +1,119 @@
+//! Tests for collaborative editing
+//!
+//! Tests that:
+//! - A guest joining a hosted session gets a copy of the shared buffer
+//! - A guest without the session token is refused
+//! - Encrypted buffers can't be shared
+//! - Concurrent edits on both sides merge to the same text
+//! - Each peer's cursor is drawn in the other's buffer, and removed when it leaves
+
+use crate::common::harness::EditorTestHarness;
+use crate::common::peers::{run_with_address, sync_until};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::encryption::{EncryptionScheme, FileEncryption};
+use ratatui::style::Color;
+use tempfile::TempDir;
+
+#[test]
+fn test_collab_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("shared.txt");
+    std::fs::write(&file, "hello world\n").unwrap();
+
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.open_file(&file).unwrap();
+    host.render().unwrap();
+    run_with_address(&mut host, "Collaboration: Host Session", "127.0.0.1:0");
+    let code = host.editor().collab_join_code().unwrap();
+
+    let mut guest = EditorTestHarness::new(80, 24).unwrap();
+    run_with_address(&mut guest, "Collaboration: Join Session", &code);
+    sync_until(&mut host, &mut guest, |_, guest| {
+        guest.get_buffer_content().as_deref() == Some("hello world\n")
+    });
+    assert!(guest.screen_to_string().contains("*collab: "));
+
+    // Both peers type at once
+    host.type_text("A").unwrap();
+    guest.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    guest.type_text("!").unwrap();
+    sync_until(&mut host, &mut guest, |host, guest| {
+        host.get_buffer_content().as_deref() == Some("Ahello world!\n")
+            && guest.get_buffer_content() == host.get_buffer_content()
+    });
+
+    // The guest's cursor shows up in the host's buffer, in the guest's color
+    guest.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    // find_text_on_screen gives a byte offset, and the gutter separator takes
+    // more than one byte
+    let (offset, y) = host.find_text_on_screen("Ahello").unwrap();
+    let row = host
+        .screen_to_string()
+        .lines()
+        .nth(y as usize)
+        .unwrap()
+        .to_string();
+    let x = row[..offset as usize].chars().count() as u16;
+    let guest_color = Color::Rgb(50, 205, 50);
+    sync_until(&mut host, &mut guest, |host, _| {
+        host.get_cell_bg(x, y) == Some(guest_color)
+    });
+
+    // Leaving removes it
+    guest.run_command("Collaboration: Leave Session").unwrap();
+    assert!(guest.editor().collab_buffer().is_none());
+    sync_until(&mut host, &mut guest, |host, _| {
+        host.get_cell_bg(x, y) != Some(guest_color)
+    });
+    assert_eq!(
+        host.get_buffer_content().as_deref(),
+        Some("Ahello world!\n")
+    );
+}
+
+#[test]
+fn test_collab_join_with_wrong_token() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("shared.txt");
+    std::fs::write(&file, "secret\n").unwrap();
+
+    let mut host = EditorTestHarness::new(80, 24).unwrap();
+    host.open_file(&file).unwrap();
+    host.render().unwrap();
+    run_with_address(&mut host, "Collaboration: Host Session", "127.0.0.1:0");
+    let address = host.editor().collab_address().unwrap();
+
+    let mut guest = EditorTestHarness::new(100, 24).unwrap();
+    run_with_address(
+        &mut guest,
+        "Collaboration: Join Session",
+        &format!("guess@{}", address),
+    );
+    sync_until(&mut host, &mut guest, |_, guest| {
+        guest
+            .screen_to_string()
+            .contains("The host refused the connection")
+    });
+    assert!(guest.editor().collab_buffer().is_none());
+    assert!(!guest.screen_to_string().contains("secret"));
+}
+
+#[test]
+fn test_collab_refuses_encrypted_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("secret.txt");
+    std::fs::write(&file, "the password\n").unwrap();
+
+    let mut host =
+        EditorTestHarness::with_working_dir(120, 24, temp_dir.path().to_path_buf()).unwrap();
+    host.open_file(&file).unwrap();
+    host.editor_mut()
+        .active_state_mut()
+        .buffer
+        .set_encryption(Some(FileEncryption::with_key(EncryptionScheme::Gpg, "key")));
+    host.render().unwrap();
+    run_with_address(&mut host, "Collaboration: Host Session", "127.0.0.1:0");
+
+    host.assert_screen_contains("secret.txt is encrypted and can't be shared");
+    assert!(host.editor().collab_address().is_none());
+}
@@ -0,0 +1,73 @@
+//! Tests for color swatches and the color adjuster
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn open_css(harness: &mut EditorTestHarness, content: &str) {
+    let path = harness.project_dir().unwrap().join("style.css");
+    std::fs::write(&path, content).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+}
+
+/// A swatch is drawn before each color literal, and none with the setting off
+#[test]
+fn test_color_swatches_shown_before_literals() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    open_css(&mut harness, "a { color: #ff0000; }\n");
+    harness.assert_screen_contains("color: ■ #ff0000;");
+
+    let mut config = Config::default();
+    config.editor.color_swatches = false;
+    let mut harness = EditorTestHarness::create(
+        80,
+        24,
+        HarnessOptions::new()
+            .with_config(config)
+            .with_project_root(),
+    )
+    .unwrap();
+    open_css(&mut harness, "a { color: #ff0000; }\n");
+    harness.assert_screen_contains("color: #ff0000;");
+    harness.assert_screen_not_contains("■");
+}
+
+/// The color adjuster rewrites the literal at the cursor and stays open
+#[test]
+fn test_adjust_color_converts_literal() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    open_css(&mut harness, "#ff0000\n");
+
+    harness.editor_mut().show_color_adjuster();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Convert to rgb()");
+
+    // Lighten, Darken, Saturate, Desaturate, Rotate hue, Convert to hex, Convert to rgb()
+    for _ in 0..6 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "rgb(255, 0, 0)\n");
+    harness.assert_screen_contains("Adjust rgb(255, 0, 0)");
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "#ff0000\n");
+}
+
+/// Without a color at the cursor, the adjuster says so instead of opening
+#[test]
+fn test_adjust_color_without_color() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    open_css(&mut harness, "a { color: red; }\n");
+
+    harness.editor_mut().show_color_adjuster();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No color at cursor");
+}
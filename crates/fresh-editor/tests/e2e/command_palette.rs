@@ -19,6 +19,9 @@ fn test_command_palette_trigger() {
     // Check that suggestions are visible (commands sorted alphabetically, so Add Cursor commands appear first)
     harness.assert_screen_contains("Add Cursor Above");
     harness.assert_screen_contains("Add Cursor Below");
+
+    // Further down the list, found by filtering
+    harness.type_text("close b").unwrap();
     harness.assert_screen_contains("Close Buffer");
 }
 
@@ -567,7 +570,7 @@ fn test_command_palette_down_no_wraparound() {
         .unwrap();
 
     // Filter to get only two commands
-    harness.type_text("save f").unwrap();
+    harness.type_text("save file").unwrap();
     harness.render().unwrap();
 
     // Should match "Save File" and "Save File As"
@@ -703,8 +706,11 @@ fn test_command_palette_shows_shortcuts() {
     // Add Cursor Below should show Ctrl+Alt+↓
     harness.assert_screen_contains("Add Cursor Below");
 
-    // Copy should show Ctrl+C (or ⌘+C on macOS)
+    // Copy should show Ctrl+C (or ⌘+C on macOS). Too many commands sort
+    // before it to fit on the first page, so narrow the list down first
+    harness.type_text("copy").unwrap();
     harness.assert_screen_contains("Copy");
+    let screen = harness.screen_to_string();
     assert!(
         screen.contains("Ctrl+C") || screen.contains("⌘+C"),
         "Should show shortcut for Copy"
@@ -0,0 +1,71 @@
+//! Tests for conceal rules
+//!
+//! Tests that:
+//! - Text matching a conceal rule is displayed replaced, the buffer is unchanged
+//! - The line with the cursor is shown as written
+//! - Toggle Conceal turns concealment off for the buffer
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{ConcealRule, Config};
+use tempfile::TempDir;
+
+fn conceal_harness(content: &str) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.md");
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut config = Config::default();
+    config.languages.get_mut("markdown").unwrap().conceal = vec![
+        ConcealRule {
+            pattern: Some(r"(\*\*)[^*\n]+(\*\*)".to_string()),
+            query: None,
+            replacement: String::new(),
+        },
+        ConcealRule {
+            pattern: Some("->".to_string()),
+            query: None,
+            replacement: "→".to_string(),
+        },
+    ];
+
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_config(config)).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Test that conceal rules apply outside the cursor line
+#[test]
+fn test_conceal_outside_cursor_line() {
+    let (mut harness, _temp_dir) = conceal_harness("first line\nsome **bold** text -> next\n");
+
+    // Cursor is on the first line, so the second line is concealed
+    harness.assert_screen_contains("some bold text → next");
+    harness.assert_screen_not_contains("**bold**");
+
+    // Moving onto the line reveals it
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("some **bold** text -> next");
+
+    // The buffer itself is unchanged
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "first line\nsome **bold** text -> next\n"
+    );
+}
+
+/// Test that Toggle Conceal shows the text as written
+#[test]
+fn test_toggle_conceal() {
+    let (mut harness, _temp_dir) = conceal_harness("first line\nsome **bold** text\n");
+    harness.assert_screen_contains("some bold text");
+
+    harness.run_command("Toggle Conceal").unwrap();
+    harness.assert_screen_contains("some **bold** text");
+
+    harness.run_command("Toggle Conceal").unwrap();
+    harness.assert_screen_contains("some bold text");
+}
@@ -0,0 +1,111 @@
+//! E2E tests for applying config file changes while the editor is running
+
+use crate::common::harness::EditorTestHarness;
+use ratatui::style::Color;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// User config path for the harness's `DirectoryContext::for_testing`
+fn user_config_path(harness: &EditorTestHarness) -> PathBuf {
+    let temp_dir = harness
+        .project_dir()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let config_dir = temp_dir.join("config");
+    fs::create_dir_all(&config_dir).unwrap();
+    config_dir.join("config.json")
+}
+
+fn poll_config(harness: &mut EditorTestHarness) {
+    harness.advance_time(Duration::from_secs(5));
+    harness.editor_mut().poll_config_changes();
+}
+
+/// Editing the user config applies the change without a restart
+#[test]
+fn test_user_config_change_is_applied_live() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let config_path = user_config_path(&harness);
+    assert_eq!(harness.editor().config().editor.tab_size, 4);
+
+    fs::write(&config_path, r#"{"editor": {"tab_size": 2}}"#).unwrap();
+    poll_config(&mut harness);
+    assert_eq!(harness.editor().config().editor.tab_size, 2);
+
+    // A broken config keeps the last good one
+    fs::write(&config_path, r#"{"editor": {"tab_size": "#).unwrap();
+    poll_config(&mut harness);
+    assert_eq!(harness.editor().config().editor.tab_size, 2);
+    let status = harness.editor().get_status_message().cloned().unwrap();
+    assert!(status.contains("not reloaded"), "status: {}", status);
+}
+
+/// Project config overrides the user config, and the source can be queried
+#[test]
+fn test_project_config_overrides_user_and_reports_source() {
+    let mut harness = EditorTestHarness::with_temp_project(120, 24).unwrap();
+    let user_path = user_config_path(&harness);
+    fs::write(&user_path, r#"{"editor": {"tab_size": 2}}"#).unwrap();
+
+    let project_path = harness
+        .project_dir()
+        .unwrap()
+        .join(".fresh")
+        .join("config.json");
+    fs::create_dir_all(project_path.parent().unwrap()).unwrap();
+    fs::write(&project_path, r#"{"editor": {"tab_size": 8}}"#).unwrap();
+
+    poll_config(&mut harness);
+    assert_eq!(harness.editor().config().editor.tab_size, 8);
+
+    harness.editor_mut().show_config_source("/editor/tab_size");
+    let status = harness.editor().get_status_message().cloned().unwrap();
+    assert!(status.contains("= 8"), "status: {}", status);
+    assert!(status.contains("project config"), "status: {}", status);
+
+    harness
+        .editor_mut()
+        .show_config_source("/editor/line_numbers");
+    let status = harness.editor().get_status_message().cloned().unwrap();
+    assert!(status.contains("default"), "status: {}", status);
+}
+
+/// A TOML user theme is picked up, and edits to it are applied live
+#[test]
+fn test_user_toml_theme_is_hot_reloaded() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let config_path = user_config_path(&harness);
+    let themes_dir = config_path.parent().unwrap().join("themes");
+    fs::create_dir_all(&themes_dir).unwrap();
+
+    let theme_path = themes_dir.join("hot.toml");
+    let write_theme = |bg: &str| {
+        let content = format!(
+            "name = \"hot\"\n\n[editor]\nbg = {}\n\n[ui]\n\n[search]\n\n[diagnostic]\n\n[syntax]\n",
+            bg
+        );
+        fs::write(&theme_path, content).unwrap();
+    };
+    write_theme("[1, 2, 3]");
+    fs::write(&config_path, r#"{"theme": "hot"}"#).unwrap();
+
+    poll_config(&mut harness);
+    assert_eq!(harness.editor().theme().name, "hot");
+    assert_eq!(harness.editor().theme().editor_bg, Color::Rgb(1, 2, 3));
+
+    write_theme("[4, 5, 6]");
+    // Make sure the change is visible even on coarse mtime filesystems
+    let later = std::time::SystemTime::now() + Duration::from_secs(10);
+    fs::File::options()
+        .write(true)
+        .open(&theme_path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+
+    poll_config(&mut harness);
+    assert_eq!(harness.editor().theme().editor_bg, Color::Rgb(4, 5, 6));
+}
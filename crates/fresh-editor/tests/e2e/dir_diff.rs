@@ -0,0 +1,97 @@
+//! E2E tests for the directory diff opened by `fresh --diff` with two
+//! directories or the Compare Folders command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+use tempfile::TempDir;
+
+/// Two directories with a file on both sides that differs, one on each
+/// side only, and a directory on the left only
+fn create_dirs() -> (TempDir, TempDir) {
+    let left = TempDir::new().unwrap();
+    let right = TempDir::new().unwrap();
+    for dir in [&left, &right] {
+        fs::write(dir.path().join("a.txt"), "same\n").unwrap();
+    }
+    fs::write(left.path().join("changed.txt"), "old line\n").unwrap();
+    fs::write(right.path().join("changed.txt"), "new line\n").unwrap();
+    fs::write(left.path().join("gone.txt"), "gone\n").unwrap();
+    fs::write(right.path().join("new.txt"), "new\n").unwrap();
+    fs::create_dir(left.path().join("sub")).unwrap();
+    fs::write(left.path().join("sub/x.txt"), "x\n").unwrap();
+    (left, right)
+}
+
+/// Each entry is marked, and `>` and `<` copy the one under the cursor
+#[test]
+fn test_dir_diff_marks_entries_and_copies_them() {
+    let (left, right) = create_dirs();
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness
+        .editor_mut()
+        .open_dir_diff(left.path(), right.path())
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("- sub/");
+    harness.assert_screen_contains("-   x.txt");
+    harness.assert_screen_contains("M changed.txt");
+    harness.assert_screen_contains("- gone.txt");
+    harness.assert_screen_contains("+ new.txt");
+
+    // The cursor starts on the first entry, sub/
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('>'), KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(right.path().join("changed.txt")).unwrap(),
+        "old line\n"
+    );
+    harness.render().unwrap();
+    harness.assert_screen_contains("Copied changed.txt to the right");
+    harness.assert_screen_not_contains("M changed.txt");
+
+    for _ in 0..2 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Char('<'), KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(left.path().join("new.txt")).unwrap(),
+        "new\n"
+    );
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("+ new.txt");
+}
+
+/// Enter on a file found on both sides compares it side by side
+#[test]
+fn test_dir_diff_opens_file_pair() {
+    let (left, right) = create_dirs();
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness
+        .editor_mut()
+        .open_dir_diff(left.path(), right.path())
+        .unwrap();
+
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    let rows: Vec<&str> = screen
+        .lines()
+        .filter(|line| line.contains("old line"))
+        .collect();
+    assert_eq!(rows.len(), 1, "screen:\n{}", screen);
+    assert!(rows[0].contains("new line"), "screen:\n{}", screen);
+}
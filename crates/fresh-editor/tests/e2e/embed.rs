@@ -0,0 +1,72 @@
+//! Tests of the editor embedded in another ratatui application
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use fresh::config::KeybindingMapName;
+use fresh::embed::{ColorCapability, Config, DirectoryContext, EditorPane};
+use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::Terminal;
+
+fn row_text(terminal: &Terminal<TestBackend>, y: u16) -> String {
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.width)
+        .map(|x| buffer[(x, y)].symbol())
+        .collect()
+}
+
+#[test]
+fn test_embedded_pane_renders_in_its_area() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let file = project.join("hello.txt");
+    std::fs::write(&file, "hello embed\n").unwrap();
+
+    let config = Config {
+        active_keybinding_map: KeybindingMapName("default".to_string()),
+        check_for_updates: false,
+        ..Default::default()
+    };
+    let mut pane = EditorPane::with_working_dir(
+        config,
+        Some(project),
+        DirectoryContext::for_testing(temp_dir.path()),
+        false,
+        ColorCapability::TrueColor,
+    )
+    .unwrap();
+    pane.open_file(&file).unwrap();
+
+    let mut terminal = Terminal::new(TestBackend::new(60, 16)).unwrap();
+    let area = Rect::new(12, 3, 40, 10);
+    terminal.draw(|frame| pane.render(frame, area)).unwrap();
+
+    // The editor stays within its area
+    let rows: Vec<String> = (0..16).map(|y| row_text(&terminal, y)).collect();
+    let text_row = rows
+        .iter()
+        .position(|row| row.contains("hello embed"))
+        .expect("file text should be shown");
+    assert!((3..13).contains(&text_row));
+    assert!(rows[text_row].find("hello embed").unwrap() >= 12);
+    for row in rows[..3].iter().chain(&rows[13..]) {
+        assert!(row.trim().is_empty(), "drawn outside the pane: {:?}", row);
+    }
+    assert!(rows.iter().all(|row| row[..12].trim().is_empty()));
+
+    pane.handle_key(KeyEvent::new(KeyCode::Char('X'), KeyModifiers::NONE))
+        .unwrap();
+    assert_eq!(
+        pane.active_state().buffer.to_string().unwrap(),
+        "Xhello embed\n"
+    );
+
+    // Clicks outside the pane are left to the host
+    let click = |column, row| MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    };
+    assert!(!pane.handle_mouse(click(2, 1)).unwrap());
+}
@@ -0,0 +1,121 @@
+//! Tests for editing encrypted files
+//!
+//! Tests that:
+//! - A `.gpg` file is decrypted after the passphrase is entered, masked
+//! - Saving writes the file encrypted again
+//! - With a wrong passphrase the buffer stays locked and the file untouched
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+fn gpg_available() -> bool {
+    let available = Command::new("gpg")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !available {
+        eprintln!("Skipping test: gpg is not installed or not in PATH");
+    }
+    available
+}
+
+/// Run gpg with `passphrase` and `data` on stdin
+fn gpg(mode: &str, passphrase: &str, data: &[u8]) -> Vec<u8> {
+    let mut child = Command::new("gpg")
+        .args([
+            "--batch",
+            "--quiet",
+            "--yes",
+            "--no-symkey-cache",
+            "--pinentry-mode",
+            "loopback",
+            "--passphrase-fd",
+            "0",
+            mode,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin
+        .write_all(format!("{}\n", passphrase).as_bytes())
+        .unwrap();
+    stdin.write_all(data).unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "gpg {} failed", mode);
+    output.stdout
+}
+
+fn write_encrypted(path: &Path, passphrase: &str, text: &str) {
+    std::fs::write(path, gpg("--symmetric", passphrase, text.as_bytes())).unwrap();
+}
+
+fn enter_passphrase(harness: &mut EditorTestHarness, passphrase: &str) {
+    harness.type_text(passphrase).unwrap();
+    harness.render().unwrap();
+    // The passphrase is masked
+    assert!(harness
+        .screen_to_string()
+        .contains(&"*".repeat(passphrase.len())));
+    assert!(!harness.screen_to_string().contains(passphrase));
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_edit_gpg_file() {
+    if !gpg_available() {
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.md.gpg");
+    write_encrypted(&file, "opensesame", "# Secret\n");
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.assert_screen_contains("Passphrase for");
+    enter_passphrase(&mut harness, "opensesame");
+    assert_eq!(harness.get_buffer_content().as_deref(), Some("# Secret\n"));
+    assert_eq!(harness.editor().active_state().language, "markdown");
+
+    // Saving encrypts the edited text
+    harness.type_text("> ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    let on_disk = std::fs::read(&file).unwrap();
+    assert!(!on_disk.windows(6).any(|w| w == b"Secret"));
+    assert_eq!(gpg("--decrypt", "opensesame", &on_disk), b"> # Secret\n");
+}
+
+#[test]
+fn test_wrong_passphrase_keeps_file_locked() {
+    if !gpg_available() {
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt.gpg");
+    write_encrypted(&file, "opensesame", "secret\n");
+    let original = std::fs::read(&file).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    enter_passphrase(&mut harness, "guess");
+    harness.assert_screen_contains("Could not decrypt");
+
+    // The locked buffer can't be edited or saved over the file
+    harness.type_text("oops").unwrap();
+    assert!(harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .is_err());
+    assert_eq!(harness.get_buffer_content().as_deref(), Some(""));
+    assert_eq!(std::fs::read(&file).unwrap(), original);
+}
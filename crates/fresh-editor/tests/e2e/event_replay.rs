@@ -0,0 +1,53 @@
+//! E2E tests for recording input to the event log and replaying it
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use fresh::services::event_replay::read_log;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Input recorded with `--event-log` replays to the same screen
+#[test]
+fn test_recorded_input_replays_to_the_same_screen() {
+    let temp_dir = TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("events.log");
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness
+        .editor_mut()
+        .enable_event_streaming(&log_path)
+        .unwrap();
+    let keys = [
+        (KeyCode::Char('h'), KeyModifiers::NONE),
+        (KeyCode::Char('i'), KeyModifiers::NONE),
+        (KeyCode::Enter, KeyModifiers::NONE),
+        (KeyCode::Char('x'), KeyModifiers::NONE),
+        (KeyCode::Left, KeyModifiers::SHIFT),
+        (KeyCode::Char('y'), KeyModifiers::NONE),
+    ];
+    for (code, modifiers) in keys {
+        harness.advance_time(Duration::from_millis(100));
+        harness
+            .editor_mut()
+            .record_input(&Event::Key(KeyEvent::new(code, modifiers)));
+        harness.send_key(code, modifiers).unwrap();
+    }
+    harness.render().unwrap();
+    let recorded_screen = harness.screen_to_string();
+
+    let log = read_log(&log_path).unwrap();
+    assert_eq!(log.size, (80, 24));
+    assert_eq!(log.inputs.len(), keys.len());
+    assert_eq!(log.inputs[2].elapsed_ms, 300);
+
+    let mut replay = EditorTestHarness::new(log.size.0, log.size.1).unwrap();
+    for recorded in &log.inputs {
+        let Some(Event::Key(key)) = recorded.input.to_event() else {
+            panic!("expected a key, got {:?}", recorded.input);
+        };
+        replay.send_key(key.code, key.modifiers).unwrap();
+    }
+    replay.render().unwrap();
+    assert_eq!(replay.get_buffer_content().unwrap(), "hi\ny");
+    assert_eq!(replay.screen_to_string(), recorded_screen);
+}
@@ -0,0 +1,105 @@
+//! Tests for the Extract Variable / Extract Function commands
+//!
+//! Without a language server the syntax tree is used. Tests that:
+//! - The selection is replaced by the name, declared before the statement
+//! - An extracted function gets parameters and a return value
+//! - Extracting is one undo step
+//! - Without a selection, or with an invalid name, nothing changes
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+fn open(file_name: &str, content: &str) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join(file_name);
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    (harness, temp_dir)
+}
+
+/// Run `command` from the palette and answer its prompt with `name`
+fn extract(harness: &mut EditorTestHarness, command: &str, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text(command).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    if harness.editor().is_prompting() {
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_extract_variable() {
+    let content = "def area(r):\n    return 3.14 * r * r\n";
+    let (mut harness, _temp_dir) = open("area.py", content);
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in 0.."3.14 * r * r".len() {
+        harness
+            .send_key(KeyCode::Left, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+
+    extract(&mut harness, "Extract Variable", "result");
+    harness.assert_buffer_content("def area(r):\n    result = 3.14 * r * r\n    return result\n");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content(content);
+}
+
+#[test]
+fn test_extract_function() {
+    let (mut harness, _temp_dir) = open(
+        "total.js",
+        "function total(items) {\n  let sum = 0;\n  for (const item of items) sum += item.price;\n  return sum;\n}\n",
+    );
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+
+    extract(&mut harness, "Extract Function", "computeSum");
+    harness.assert_buffer_content(
+        "function total(items) {\n  let sum = computeSum(items);\n  return sum;\n}\n\n\
+         function computeSum(items) {\n  let sum = 0;\n  for (const item of items) sum += item.price;\n  return sum;\n}\n",
+    );
+}
+
+#[test]
+fn test_extract_needs_selection_and_name() {
+    let content = "x = f(1)\n";
+    let (mut harness, _temp_dir) = open("main.py", content);
+
+    // No selection: no prompt
+    extract(&mut harness, "Extract Variable", "y");
+    harness.assert_buffer_content(content);
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in 0.."f(1)".len() {
+        harness
+            .send_key(KeyCode::Left, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+    extract(&mut harness, "Extract Variable", "not a name");
+    harness.assert_buffer_content(content);
+}
@@ -0,0 +1,66 @@
+//! E2E tests for auto-saving modified files to disk
+
+use crate::common::harness::EditorTestHarness;
+use std::fs;
+use std::time::Duration;
+
+fn auto_save_config() -> fresh::config::Config {
+    let mut config = fresh::config::Config::default();
+    config.editor.file_auto_save = true;
+    config.editor.file_auto_save_delay_ms = 500;
+    config
+}
+
+/// A modified file is written to disk once it has been idle for the delay
+#[test]
+fn test_file_auto_saved_after_idle_delay() {
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(80, 24, auto_save_config()).unwrap();
+    let file_path = harness.project_dir().unwrap().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("X").unwrap();
+
+    // First tick only starts the idle timer
+    assert_eq!(harness.editor_mut().auto_save_files(), 0);
+    harness.advance_time(Duration::from_millis(200));
+    assert_eq!(harness.editor_mut().auto_save_files(), 0);
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "hello");
+
+    harness.advance_time(Duration::from_millis(400));
+    assert_eq!(harness.editor_mut().auto_save_files(), 1);
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "Xhello");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    harness.render().unwrap();
+    harness.assert_screen_contains("[auto]");
+}
+
+/// Excluded files are never auto-saved, even on focus loss
+#[test]
+fn test_file_auto_save_exclusions() {
+    let mut config = auto_save_config();
+    config.editor.file_auto_save_exclude = vec!["*.lock".to_string()];
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+
+    let locked = project_dir.join("deps.lock");
+    fs::write(&locked, "a").unwrap();
+    harness.open_file(&locked).unwrap();
+    harness.type_text("B").unwrap();
+
+    harness.editor_mut().auto_save_files();
+    harness.advance_time(Duration::from_secs(1));
+    assert_eq!(harness.editor_mut().auto_save_files(), 0);
+    assert_eq!(harness.editor_mut().handle_terminal_focus_lost(), 0);
+    assert_eq!(fs::read_to_string(&locked).unwrap(), "a");
+
+    // A regular file is saved as soon as the terminal loses focus
+    let regular = project_dir.join("main.txt");
+    fs::write(&regular, "c").unwrap();
+    harness.open_file(&regular).unwrap();
+    harness.type_text("D").unwrap();
+    assert_eq!(harness.editor_mut().handle_terminal_focus_lost(), 1);
+    assert_eq!(fs::read_to_string(&regular).unwrap(), "Dc");
+}
@@ -0,0 +1,35 @@
+//! E2E tests for the side-by-side file diff opened by `fresh --diff`
+
+use crate::common::harness::EditorTestHarness;
+use std::fs;
+use tempfile::TempDir;
+
+/// Both files are shown side by side, lined up on their differences
+#[test]
+fn test_open_file_diff() {
+    let temp_dir = TempDir::new().unwrap();
+    let old_path = temp_dir.path().join("old.txt");
+    let new_path = temp_dir.path().join("new.txt");
+    fs::write(&old_path, "same line\nold only\nlast line\n").unwrap();
+    fs::write(&new_path, "same line\nnew only\nextra line\nlast line\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness
+        .editor_mut()
+        .open_file_diff(&old_path, &new_path)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("old only");
+    harness.assert_screen_contains("new only");
+    harness.assert_screen_contains("extra line");
+
+    // "last line" is aligned across the panes, so it shares a screen row
+    let screen = harness.screen_to_string();
+    let last_rows: Vec<&str> = screen
+        .lines()
+        .filter(|line| line.contains("last line"))
+        .collect();
+    assert_eq!(last_rows.len(), 1, "screen:\n{}", screen);
+    assert_eq!(last_rows[0].matches("last line").count(), 2);
+}
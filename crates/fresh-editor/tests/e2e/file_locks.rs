@@ -0,0 +1,107 @@
+//! E2E tests for lock files that warn about files open in another editor
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use fresh::services::file_lock::FileLocks;
+use std::fs;
+use tempfile::TempDir;
+
+/// Harness sharing `dir_context`, so tests can see its lock files
+fn harness_with_locks(temp_dir: &TempDir) -> (EditorTestHarness, DirectoryContext) {
+    let dir_context = DirectoryContext::for_testing(temp_dir.path());
+    let harness = EditorTestHarness::with_shared_dir_context(
+        100,
+        24,
+        Default::default(),
+        temp_dir.path().to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap();
+    (harness, dir_context)
+}
+
+/// A file is locked once it is modified, and unlocked when its buffer closes
+#[test]
+fn test_modified_file_locked_until_closed() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, dir_context) = harness_with_locks(&temp_dir);
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    let file_path = fs::canonicalize(&file_path).unwrap();
+    // How another editor process sees the locks
+    let other = FileLocks::for_process(dir_context.locks_dir(), u32::MAX);
+
+    harness.open_file(&file_path).unwrap();
+    harness.editor_mut().update_file_locks();
+    assert_eq!(other.owner(&file_path), None);
+
+    harness.type_text("X").unwrap();
+    harness.editor_mut().update_file_locks();
+    assert_eq!(
+        other.owner(&file_path).map(|owner| owner.pid),
+        Some(std::process::id())
+    );
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().force_close_buffer(buffer_id).unwrap();
+    harness.editor_mut().update_file_locks();
+    assert_eq!(other.owner(&file_path), None);
+}
+
+/// A file locked by another running editor opens read-only unless the user
+/// chooses to edit it anyway
+#[test]
+#[cfg(unix)]
+fn test_file_locked_by_other_editor_opens_read_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let (mut harness, dir_context) = harness_with_locks(&temp_dir);
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    let file_path = fs::canonicalize(&file_path).unwrap();
+
+    let Ok(mut other_editor) = std::process::Command::new("sleep").arg("30").spawn() else {
+        eprintln!("Skipping test: sleep is not available");
+        return;
+    };
+    let mut other = FileLocks::for_process(dir_context.locks_dir(), other_editor.id());
+    other.acquire(&file_path).unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("being edited by another Fresh");
+    harness.assert_screen_contains(&format!("PID {}", other_editor.id()));
+
+    // Keep it read-only
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+
+    // Reopening asks again; edit anyway. Another buffer stays open, or closing
+    // the last one would focus the file explorer.
+    let buffer_id = harness.editor().active_buffer();
+    harness.new_buffer().unwrap();
+    harness.editor_mut().force_close_buffer(buffer_id).unwrap();
+    harness.editor_mut().update_file_locks();
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("e").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "Xhello");
+
+    // Editing anyway doesn't take the other editor's lock
+    harness.editor_mut().update_file_locks();
+    assert_eq!(
+        FileLocks::new(dir_context.locks_dir())
+            .owner(&file_path)
+            .map(|owner| owner.pid),
+        Some(other_editor.id())
+    );
+
+    let _ = other_editor.kill();
+    let _ = other_editor.wait();
+}
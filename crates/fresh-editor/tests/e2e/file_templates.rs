@@ -0,0 +1,96 @@
+//! E2E tests for the templates offered for new files
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, FileTemplate};
+use std::fs;
+
+fn config_with_templates() -> Config {
+    let template =
+        |name: &str, patterns: &[&str], language: Option<&str>, content: &str| FileTemplate {
+            name: name.to_string(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            language: language.map(str::to_string),
+            content: content.to_string(),
+        };
+    let mut config = Config::default();
+    config.editor.file_template_author = "Ada".to_string();
+    config.editor.file_templates = vec![
+        template(
+            "Rust module",
+            &[],
+            Some("rust"),
+            "// ${FILENAME} by ${AUTHOR}\n$0\n",
+        ),
+        template(
+            "Meeting notes",
+            &["notes/*.md"],
+            None,
+            "# ${FILENAME_BASE}\n",
+        ),
+        template("Main", &[], Some("rust"), "fn main() {\n    $0\n}\n"),
+    ];
+    config
+}
+
+/// Opening a file that doesn't exist offers the templates for its language,
+/// and the chosen one is inserted with its variables replaced
+#[test]
+fn test_file_template_for_new_file() {
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(100, 24, config_with_templates()).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    fs::create_dir_all(project_dir.join("src")).unwrap();
+    let path = project_dir.join("src/new.rs");
+
+    harness
+        .send_key(KeyCode::Char('o'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(path.to_str().unwrap()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Template for the new file:")
+        .unwrap();
+    harness.assert_screen_contains("Rust module");
+    harness.assert_screen_contains("Empty file");
+    harness.assert_screen_not_contains("Meeting notes");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "// new.rs by Ada\nx\n"
+    );
+}
+
+/// A new untitled buffer is offered every template, starting with none
+#[test]
+fn test_file_template_for_untitled_buffer() {
+    let mut harness = EditorTestHarness::with_config(100, 24, config_with_templates()).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('n'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Template for the new file:")
+        .unwrap();
+    harness.assert_screen_contains("Meeting notes");
+
+    // Empty file, Rust module, Meeting notes, Main
+    for _ in 0..3 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("run();").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn main() {\n    run();\n}\n"
+    );
+}
@@ -0,0 +1,52 @@
+//! E2E tests for turning decorations off when frames take too long
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use std::time::Duration;
+
+/// Slow frames turn decorations off one at a time, with a status bar
+/// indicator, and fast frames turn them back on
+#[test]
+fn test_frame_budget_degrades_and_restores() {
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("[degraded]");
+
+    for _ in 0..5 {
+        harness
+            .editor_mut()
+            .record_frame_drawn(Duration::from_millis(50));
+    }
+    harness.render().unwrap();
+    assert!(harness.editor().is_frame_budget_degraded());
+    harness.assert_screen_contains("[degraded]");
+    harness.assert_screen_contains("turned off semantic highlighting");
+
+    for _ in 0..60 {
+        harness
+            .editor_mut()
+            .record_frame_drawn(Duration::from_millis(1));
+    }
+    harness.render().unwrap();
+    assert!(!harness.editor().is_frame_budget_degraded());
+    harness.assert_screen_not_contains("[degraded]");
+    harness.assert_screen_contains("turned semantic highlighting back on");
+}
+
+/// With no budget, slow frames change nothing
+#[test]
+fn test_frame_budget_off() {
+    let mut config = Config::default();
+    config.editor.frame_budget_ms = 0;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+
+    for _ in 0..20 {
+        harness
+            .editor_mut()
+            .record_frame_drawn(Duration::from_millis(50));
+    }
+    harness.render().unwrap();
+    assert!(!harness.editor().is_frame_budget_degraded());
+    harness.assert_screen_not_contains("[degraded]");
+}
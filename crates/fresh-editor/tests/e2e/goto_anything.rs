@@ -0,0 +1,132 @@
+//! Tests for the Go to Anything prompt
+//!
+//! Tests that:
+//! - `:line` moves the cursor to a line in the current buffer
+//! - Plain text fuzzy-matches and opens project files
+//! - `@symbol` lists the buffer's symbols from the language server and jumps to one
+//! - `@symbol` without a language server says so instead of hanging
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn open_goto_anything(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(
+            KeyCode::Char('o'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_goto_anything_line() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    std::fs::write(&file, "one\ntwo\nthree\nfour\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+
+    open_goto_anything(&mut harness);
+    harness.assert_screen_contains("@symbol");
+    harness.type_text(":3").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.cursor_position(), "one\ntwo\n".len());
+}
+
+#[test]
+fn test_goto_anything_opens_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    std::fs::create_dir(project_dir.join("src")).unwrap();
+    std::fs::write(project_dir.join("src").join("parser.rs"), "// parser\n").unwrap();
+    std::fs::write(project_dir.join("readme.md"), "# readme\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Config::default(), project_dir)
+            .unwrap();
+    harness.render().unwrap();
+
+    open_goto_anything(&mut harness);
+    harness.type_text("parser").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("src/parser.rs");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("// parser\n");
+}
+
+#[test]
+fn test_goto_anything_document_symbol() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {\n    let value = 1;\n}\n")?;
+
+    let mut config = Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::script_path().to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    open_goto_anything(&mut harness);
+    harness.type_text("@val")?;
+    // Symbols arrive asynchronously from the language server
+    harness.wait_until(|h| h.screen_to_string().contains("variable in main"))?;
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    assert_eq!(harness.cursor_position(), "fn main() {\n    let ".len());
+    harness.assert_screen_contains("Jumped to value (line 2)");
+
+    Ok(())
+}
+
+#[test]
+fn test_goto_anything_symbols_without_language_server() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "plain text\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+
+    open_goto_anything(&mut harness);
+    harness.type_text("@").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No language server for this buffer");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("plain text\n");
+}
@@ -0,0 +1,69 @@
+//! Tests for Go to Last Change / Go to Next Change
+//!
+//! Tests that:
+//! - Go to Last Change returns to an edit in another buffer
+//! - Recorded locations follow later edits earlier in the buffer
+//! - Go to Next Change walks forward again and stops at the newest edit
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+#[test]
+fn test_goto_last_change_across_buffers() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_a = temp_dir.path().join("a.txt");
+    let file_b = temp_dir.path().join("b.txt");
+    std::fs::write(&file_a, "one\ntwo\nthree\n").unwrap();
+    std::fs::write(&file_b, "other file\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.type_text("X").unwrap();
+
+    harness.open_file(&file_b).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+
+    harness.run_command("Go to Last Change").unwrap();
+    harness.assert_buffer_content("one\nXtwo\nthree\n");
+    assert_eq!(harness.cursor_position(), "one\nX".len());
+}
+
+#[test]
+fn test_goto_last_and_next_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("changes.txt");
+    std::fs::write(&file, "one\ntwo\nthree\nfour\nfive\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file).unwrap();
+
+    // Edit line 5, then the start of the file
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.type_text("X").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("YY").unwrap();
+    harness.assert_buffer_content("YYone\ntwo\nthree\nfour\nXfive\n");
+
+    // The cursor is at the newest change, so the first step goes to the
+    // edit before it, shifted by the text typed since
+    harness.run_command("Go to Last Change").unwrap();
+    assert_eq!(
+        harness.cursor_position(),
+        "YYone\ntwo\nthree\nfour\nX".len()
+    );
+
+    harness.run_command("Go to Last Change").unwrap();
+    harness.assert_screen_contains("No older change");
+
+    harness.run_command("Go to Next Change").unwrap();
+    assert_eq!(harness.cursor_position(), "YY".len());
+
+    harness.run_command("Go to Next Change").unwrap();
+    harness.assert_screen_contains("No newer change");
+}
@@ -0,0 +1,68 @@
+//! E2E tests for image previews
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::terminal_capabilities::TerminalCapabilities;
+use fresh::view::color_support::ColorCapability;
+use std::fs;
+
+fn capabilities(kitty_graphics: Option<bool>) -> TerminalCapabilities {
+    TerminalCapabilities {
+        name: None,
+        term: None,
+        color: ColorCapability::TrueColor,
+        keyboard_enhancement: None,
+        synchronized_output: None,
+        sgr_mouse: None,
+        clipboard: None,
+        kitty_graphics,
+        sixel: None,
+    }
+}
+
+/// An image file is previewed instead of showing its bytes, and Toggle Image
+/// Preview switches back to the text
+#[test]
+fn test_image_preview_replaces_binary_text() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let path = harness.project_dir().unwrap().join("broken.png");
+    fs::write(&path, b"\x89PNG\r\n\x1a\n\x00\x00\x00\x00not really a png").unwrap();
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(capabilities(Some(true)));
+
+    harness.open_file(&path).unwrap();
+    harness
+        .wait_for_screen_contains("Can't preview image")
+        .unwrap();
+    harness.assert_screen_not_contains("not really a png");
+
+    harness.editor_mut().toggle_image_preview();
+    harness.render().unwrap();
+    harness.assert_screen_contains("not really a png");
+    harness.assert_screen_not_contains("Can't preview image");
+}
+
+/// Without kitty graphics or sixel, image files open as text
+#[test]
+fn test_image_opens_as_text_without_graphics() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let path = harness.project_dir().unwrap().join("broken.png");
+    fs::write(&path, b"\x89PNG\r\n\x1a\n\x00\x00\x00\x00not really a png").unwrap();
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(capabilities(Some(false)));
+
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("not really a png");
+    assert_eq!(
+        harness
+            .editor()
+            .image_preview_path(harness.editor().active_buffer()),
+        None
+    );
+
+    harness.editor_mut().toggle_image_preview();
+    harness.render().unwrap();
+    harness.assert_screen_contains("can't show images");
+}
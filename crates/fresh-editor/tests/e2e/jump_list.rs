@@ -0,0 +1,146 @@
+//! Tests for the jump list
+//!
+//! Tests that:
+//! - Go to line records a jump that Jump Back / Jump Forward walk
+//! - Switching buffers records a jump back to the previous buffer
+//! - The Jump List picker previews lines and jumps to the chosen entry
+//! - The jump list is restored with the session
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn goto_line(harness: &mut EditorTestHarness, line: usize) {
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text(&line.to_string()).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+fn numbered_lines() -> String {
+    (1..=60).map(|n| format!("line {}\n", n)).collect()
+}
+
+fn line_offset(content: &str, line: usize) -> usize {
+    content.find(&format!("line {}\n", line)).unwrap()
+}
+
+#[test]
+fn test_goto_line_jump_back_and_forward() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("jumps.txt");
+    let content = numbered_lines();
+    std::fs::write(&file, &content).unwrap();
+
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file).unwrap();
+
+    goto_line(&mut harness, 40);
+    assert_eq!(harness.cursor_position(), line_offset(&content, 40));
+
+    harness.run_command("Jump Back").unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+
+    harness.run_command("Jump Forward").unwrap();
+    assert_eq!(harness.cursor_position(), line_offset(&content, 40));
+
+    harness.run_command("Jump Forward").unwrap();
+    harness.assert_screen_contains("Already at the newest jump");
+}
+
+#[test]
+fn test_jump_back_after_buffer_switch() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_a = temp_dir.path().join("a.txt");
+    let file_b = temp_dir.path().join("b.txt");
+    std::fs::write(&file_a, "first\nsecond\nthird\n").unwrap();
+    std::fs::write(&file_b, "other file\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.open_file(&file_b).unwrap();
+    harness.assert_buffer_content("other file\n");
+
+    harness.run_command("Jump Back").unwrap();
+    harness.assert_buffer_content("first\nsecond\nthird\n");
+    assert_eq!(harness.cursor_position(), "first\nsecond\n".len());
+}
+
+#[test]
+fn test_jump_list_picker() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("jumps.txt");
+    let content = numbered_lines();
+    std::fs::write(&file, &content).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    goto_line(&mut harness, 40);
+    goto_line(&mut harness, 10);
+
+    harness.run_command("Jump List").unwrap();
+    harness.assert_screen_contains("Jump to:");
+    harness.assert_screen_contains("jumps.txt:40");
+
+    // The newest jump is preselected
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), line_offset(&content, 40));
+
+    // Choosing from the picker keeps the place we came from reachable
+    harness.run_command("Jump Forward").unwrap();
+    assert_eq!(harness.cursor_position(), line_offset(&content, 10));
+}
+
+#[test]
+fn test_jump_list_empty() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("Jump List").unwrap();
+    harness.assert_screen_contains("The jump list is empty");
+}
+
+#[test]
+fn test_jump_list_restored_with_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let file = project_dir.join("jumps.txt");
+    let content = numbered_lines();
+    std::fs::write(&file, &content).unwrap();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        harness.open_file(&file).unwrap();
+        goto_line(&mut harness, 40);
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Config::default(),
+        project_dir.clone(),
+    )
+    .unwrap();
+    harness.editor_mut().try_restore_session().unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), line_offset(&content, 40));
+
+    harness.run_command("Jump Back").unwrap();
+    assert_eq!(harness.cursor_position(), 0);
+}
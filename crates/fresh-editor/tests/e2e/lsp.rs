@@ -888,7 +888,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
         state
             .semantic_tokens
             .as_ref()
-            .map(|store| store.version == state.buffer.version())
+            .map(|store| store.version == state.buffer.revision())
             .unwrap_or(false)
     })?;
 
@@ -902,7 +902,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
         if let Some(store) = &state.semantic_tokens {
             assert_ne!(
                 store.version,
-                state.buffer.version(),
+                state.buffer.revision(),
                 "Semantic tokens should be stale immediately after edits"
             );
         }
@@ -913,7 +913,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
         state
             .semantic_tokens
             .as_ref()
-            .map(|store| store.version == state.buffer.version())
+            .map(|store| store.version == state.buffer.revision())
             .unwrap_or(false)
     })?;
 
@@ -929,7 +929,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
         );
         assert_eq!(
             store.version,
-            state.buffer.version(),
+            state.buffer.revision(),
             "Semantic tokens must match the buffer version"
         );
     }
@@ -1590,6 +1590,67 @@ fn test_lsp_completion_canceled_on_text_edit() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that a hover request the server hasn't answered is canceled when
+/// the cursor moves on
+#[test]
+fn test_lsp_hover_canceled_on_cursor_move() -> anyhow::Result<()> {
+    use crate::common::fake_lsp::FakeLspServer;
+
+    // A server that never answers, so the request stays pending
+    let _fake_server = FakeLspServer::spawn_blocking()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {\n    let value = 1;\n}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::blocking_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|h| {
+        h.editor()
+            .running_lsp_servers()
+            .contains(&"rust".to_string())
+    })?;
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE)?;
+    harness.send_key(KeyCode::End, KeyModifiers::NONE)?;
+
+    // Request hover (Alt+K)
+    harness.send_key(KeyCode::Char('k'), KeyModifiers::ALT)?;
+    assert!(
+        harness.editor().has_pending_lsp_requests(),
+        "Expected the hover request to be pending"
+    );
+
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE)?;
+    assert!(
+        !harness.editor().has_pending_lsp_requests(),
+        "Expected no pending LSP requests after cursor move"
+    );
+
+    Ok(())
+}
+
 /// Test LSP rename with real rust-analyzer to reproduce "content modified" error
 /// Skip if rust-analyzer is not installed
 #[test]
@@ -7035,3 +7096,62 @@ fn test_hover_popup_scroll_to_bottom() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Renaming a file asks the language server for the edits that go with it,
+/// and applies them once confirmed in the preview
+#[test]
+#[cfg_attr(
+    windows,
+    ignore = "FakeLspServer uses a Bash script which is not available on Windows"
+)]
+fn test_file_rename_applies_will_rename_edits() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_file_rename()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let main_file = temp_dir.path().join("main.rs");
+    let old_file = temp_dir.path().join("old.rs");
+    std::fs::write(&main_file, "mod old;\n")?;
+    std::fs::write(&old_file, "pub fn f() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::file_rename_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&main_file)?;
+    harness.wait_until(|h| h.screen_to_string().contains("LSP (rust) ready"))?;
+
+    harness.editor_mut().perform_file_explorer_rename(
+        old_file.clone(),
+        "old.rs".to_string(),
+        "new.rs".to_string(),
+        false,
+    );
+    harness.wait_until(|h| h.screen_to_string().contains("Rename old.rs to new.rs"))?;
+    harness.assert_screen_contains("1 edits in: main.rs (1)");
+    // Nothing is renamed until the edits are confirmed
+    assert!(old_file.exists());
+
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+    assert!(!old_file.exists());
+    assert!(temp_dir.path().join("new.rs").exists());
+    assert_eq!(harness.get_buffer_content().unwrap(), "mod new;\n");
+
+    Ok(())
+}
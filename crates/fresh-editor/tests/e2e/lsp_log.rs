@@ -0,0 +1,61 @@
+//! E2E tests for the LSP Log buffer
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+
+#[test]
+fn test_lsp_log_without_servers() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+
+    let log = harness.editor_mut().show_lsp_log();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*LSP Log*");
+    harness.assert_screen_contains("No language server has started yet.");
+
+    // Showing it again reuses the buffer, which can't be edited
+    harness.editor_mut().show_lsp_log();
+    assert_eq!(harness.editor().active_buffer(), log);
+    harness.type_text("X").unwrap();
+    assert!(!harness.get_buffer_content().unwrap().contains('X'));
+}
+
+/// The log follows a server starting up while the cursor is at its end
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses Bash-based fake LSP server
+fn test_lsp_log_follows_server_status() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::script_path().to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        100,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.editor_mut().show_lsp_log();
+    harness.wait_until(|h| {
+        h.get_buffer_content()
+            .is_some_and(|log| log.contains("== rust (ready) ==") && log.contains("Server ready"))
+    })?;
+
+    let log = harness.get_buffer_content().unwrap();
+    assert!(log.contains("[info] Server starting"), "{}", log);
+    assert_eq!(harness.cursor_position(), log.len());
+    Ok(())
+}
@@ -1,7 +1,9 @@
 //! E2E tests for LSP message ordering
 //!
 //! These tests verify that LSP messages are sent in the correct order,
-//! particularly that didOpen is sent before any requests for a file.
+//! particularly that didOpen is sent before any requests for a file, and
+//! that closing a file's last buffer closes its document on a server that
+//! stays running for the idle timeout.
 
 use crate::common::fake_lsp::FakeLspServer;
 use crate::common::harness::EditorTestHarness;
@@ -134,3 +136,105 @@ fn test_did_open_sent_before_hover() -> anyhow::Result<()> {
     eprintln!("[TEST] Test completed successfully");
     Ok(())
 }
+
+/// Config using the logging fake LSP server for Rust, logging to `log_file`
+fn logging_lsp_config(log_file: &std::path::Path) -> fresh::config::Config {
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::logging_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![log_file.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+    config
+}
+
+fn count_logged(log_file: &std::path::Path, method: &str) -> usize {
+    std::fs::read_to_string(log_file)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| *line == method)
+        .count()
+}
+
+/// Closing a file's only buffer sends didClose, and reopening it within the
+/// idle timeout reattaches to the running server instead of starting another
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses Bash-based fake LSP server
+fn test_reopen_reattaches_to_idle_server() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_logging()?;
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_idle_test_log.txt");
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        logging_lsp_config(&log_file),
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| count_logged(&log_file, "textDocument/didOpen") == 1)?;
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(buffer_id)?;
+    harness.wait_until(|_| count_logged(&log_file, "textDocument/didClose") == 1)?;
+    assert!(harness
+        .editor()
+        .running_lsp_servers()
+        .contains(&"rust".to_string()));
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| count_logged(&log_file, "textDocument/didOpen") == 2)?;
+    assert_eq!(count_logged(&log_file, "initialize"), 1);
+    Ok(())
+}
+
+/// A server left without open documents is shut down after the idle
+/// timeout, and started again when one of its files is opened
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses Bash-based fake LSP server
+fn test_idle_server_released_after_timeout() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_logging()?;
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_idle_test_log.txt");
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {}\n")?;
+
+    let mut config = logging_lsp_config(&log_file);
+    config.editor.lsp_idle_timeout_secs = 1;
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| count_logged(&log_file, "textDocument/didOpen") == 1)?;
+
+    let buffer_id = harness.editor().active_buffer();
+    harness.editor_mut().close_buffer(buffer_id)?;
+    harness.wait_until(|h| {
+        !h.editor()
+            .running_lsp_servers()
+            .contains(&"rust".to_string())
+    })?;
+
+    // The new server starts a new log
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| {
+        count_logged(&log_file, "textDocument/didClose") == 0
+            && count_logged(&log_file, "textDocument/didOpen") == 1
+    })?;
+    Ok(())
+}
@@ -549,3 +549,67 @@ fn test_line_numbers_update_with_navigation_keys() {
     harness.render().unwrap();
     harness.assert_screen_contains("   1 │");
 }
+
+fn open_with_line_number_mode(
+    temp_dir: &TempDir,
+    config: fresh::config::Config,
+) -> EditorTestHarness {
+    let file_path = temp_dir.path().join("relative.txt");
+    std::fs::write(&file_path, "Line 1\nLine 2\nLine 3\nLine 4\nLine 5\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// Test that relative line numbers count from the cursor line and follow it
+#[test]
+fn test_relative_line_numbers() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = fresh::config::Config::default();
+    config.editor.line_number_mode = fresh::config::LineNumberMode::Relative;
+    let mut harness = open_with_line_number_mode(&temp_dir, config);
+
+    harness.assert_screen_contains("   0 │ Line 1");
+    harness.assert_screen_contains("   1 │ Line 2");
+    harness.assert_screen_contains("   4 │ Line 5");
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("   2 │ Line 1");
+    harness.assert_screen_contains("   0 │ Line 3");
+    harness.assert_screen_contains("   2 │ Line 5");
+}
+
+/// Test that hybrid line numbers keep the absolute number on the cursor line
+#[test]
+fn test_hybrid_line_numbers() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = fresh::config::Config::default();
+    config.editor.line_number_mode = fresh::config::LineNumberMode::Hybrid;
+    let mut harness = open_with_line_number_mode(&temp_dir, config);
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("   2 │ Line 1");
+    harness.assert_screen_contains("   3 │ Line 3");
+    harness.assert_screen_contains("   1 │ Line 4");
+}
+
+/// Test that the older relative_line_numbers flag still selects hybrid numbering
+#[test]
+fn test_relative_line_numbers_flag_is_hybrid() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = fresh::config::Config::default();
+    config.editor.relative_line_numbers = true;
+    let harness = open_with_line_number_mode(&temp_dir, config);
+
+    harness.assert_screen_contains("   1 │ Line 1");
+    harness.assert_screen_contains("   1 │ Line 2");
+    harness.assert_screen_contains("   4 │ Line 5");
+}
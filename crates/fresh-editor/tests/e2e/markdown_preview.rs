@@ -0,0 +1,76 @@
+//! Tests for the markdown preview view mode
+//!
+//! Tests that:
+//! - Toggling preview replaces the source with rendered markdown and back
+//! - The side-by-side preview follows the cursor in the source split
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Test that Toggle Markdown Preview renders headings, task lists and tables
+#[test]
+fn test_toggle_markdown_preview() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("doc.md");
+    std::fs::write(
+        &file_path,
+        "# Title\n\n- [x] done\n- [ ] todo\n\n| a | b |\n|---|---|\n| 1 | 2 |\n",
+    )
+    .unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("# Title");
+
+    harness.run_command("Toggle Markdown Preview").unwrap();
+    harness.assert_screen_contains("☑ done");
+    harness.assert_screen_contains("☐ todo");
+    harness.assert_screen_contains("a │ b");
+    harness.assert_screen_contains("═════");
+    harness.assert_screen_not_contains("# Title");
+
+    harness.run_command("Toggle Markdown Preview").unwrap();
+    harness.assert_screen_contains("# Title");
+    harness.assert_screen_not_contains("☑ done");
+}
+
+/// Test that the side-by-side preview scrolls along with the source cursor
+#[test]
+fn test_markdown_preview_to_side_follows_cursor() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("doc.md");
+    let content: String = (1..=40)
+        .map(|i| format!("## Section {i}\n\nBody {i}\n\n"))
+        .collect();
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .run_command("Open Markdown Preview to the Side")
+        .unwrap();
+    // The source keeps focus and the preview shows the rendered heading rule
+    harness.assert_screen_contains("## Section 1");
+    harness.assert_screen_contains("─────");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("## Section 40"),
+        "Source should scroll to the end:\n{screen}"
+    );
+    // Raw heading in the source split, rendered heading in the preview
+    assert_eq!(
+        screen.matches("Section 40").count(),
+        2,
+        "Preview should follow the cursor:\n{screen}"
+    );
+}
@@ -1,23 +1,46 @@
+pub mod accessibility;
 pub mod ansi_cursor;
 pub mod auto_indent;
 pub mod auto_revert;
 pub mod basic;
 pub mod binary_file;
 pub mod block_selection;
+pub mod breadcrumbs;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
+pub mod buffer_switcher;
+pub mod bulk_close;
 pub mod case_conversion;
+pub mod cheat_sheet;
+pub mod collab;
+pub mod color_swatches;
 pub mod command_palette;
+pub mod conceal;
+pub mod config_hot_reload;
 pub mod crash_repro;
 pub mod crlf_rendering;
+pub mod dir_diff;
 pub mod document_model;
 pub mod emacs_actions;
+pub mod embed;
 pub mod encoding;
+pub mod encrypted_files;
+pub mod event_replay;
 pub mod explorer_menu;
+pub mod extract;
+pub mod file_auto_save;
 pub mod file_browser;
+pub mod file_diff;
 pub mod file_explorer;
+pub mod file_locks;
 pub mod file_permissions;
+pub mod file_templates;
+pub mod frame_budget;
+pub mod goto_anything;
+pub mod goto_last_change;
+pub mod image_preview;
 pub mod indent_dedent;
+pub mod jump_list;
 pub mod language_features_e2e;
 pub mod large_file_inplace_write_bug;
 pub mod large_file_mode;
@@ -26,20 +49,26 @@ pub mod line_wrapping;
 pub mod live_grep;
 pub mod locale;
 pub mod lsp;
+pub mod lsp_log;
 pub mod lsp_order;
 pub mod macros;
 pub mod margin;
 pub mod markdown_compose;
+pub mod markdown_preview;
 pub mod menu_bar;
 pub mod merge_conflict;
 pub mod mouse;
+pub mod mouse_links;
+pub mod mouse_wheel;
 pub mod movement;
 pub mod multi_file_opening;
 pub mod multibyte_characters;
 pub mod multicursor;
+pub mod number_increment;
 pub mod on_save_actions;
 pub mod open_folder;
 pub mod paste;
+pub mod persistent_undo;
 #[cfg(feature = "plugins")]
 pub mod plugins;
 pub mod popup_selection;
@@ -47,11 +76,18 @@ pub mod position_history;
 pub mod position_history_bugs;
 pub mod position_history_debug;
 pub mod position_history_truncate_debug;
+pub mod presentation;
+pub mod profiler;
+#[cfg(unix)]
+pub mod project_env;
 pub mod prompt;
 pub mod prompt_editing;
+pub mod read_only_files;
 pub mod recovery;
 pub mod remote_fs_test;
 pub mod rendering;
+pub mod reopen_closed_buffer;
+pub mod repl;
 pub mod save_as_language_detection;
 pub mod scroll_clearing;
 pub mod scrolling;
@@ -66,6 +102,7 @@ pub mod shell_command;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod sort_lines;
+pub mod spell_check;
 pub mod split_tabs;
 pub mod split_view;
 pub mod split_view_expectations;
@@ -74,16 +111,24 @@ pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
 pub mod tab_config;
+pub mod tab_context_menu;
 pub mod tab_drag;
 pub mod tab_indent_selection;
 pub mod tab_scrolling;
+pub mod tags;
+#[cfg(unix)]
+pub mod tasks;
 pub mod terminal;
 pub mod terminal_close;
+pub mod terminal_info;
 pub mod terminal_resize;
+pub mod terminal_title;
 pub mod test_scrollbar_keybinds_cursor;
+pub mod text_objects;
 pub mod theme;
 pub mod toggle_bars;
 pub mod toggle_comment;
+pub mod tutorial;
 pub mod undo_redo;
 pub mod unicode_cursor;
 pub mod unicode_prompt_bugs;
@@ -91,5 +136,10 @@ pub mod update_notification;
 #[cfg(feature = "plugins")]
 pub mod vi_mode;
 pub mod virtual_lines;
+pub mod visual_guides;
 pub mod visual_regression;
 pub mod warning_indicators;
+pub mod whitespace_rendering;
+pub mod word_completion;
+pub mod workspace_folders;
+pub mod workspace_trust;
@@ -1969,3 +1969,146 @@ fn test_blinking_bar_selection_first_char_color() {
         second_char_cell.bg
     );
 }
+
+/// Send a left-button drag event to a position
+fn mouse_drag_to(harness: &mut EditorTestHarness, col: u16, row: u16) {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            column: col,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Send a left-button press without a release, so a drag can follow
+fn mouse_down(harness: &mut EditorTestHarness, col: u16, row: u16) {
+    use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: col,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })
+        .unwrap();
+}
+
+/// Test that double-click selects a word by Unicode word boundaries, but
+/// stops at `.` between identifiers
+#[test]
+fn test_double_click_selects_unicode_word() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("let café = self.value;\nit can't be\n")
+        .unwrap();
+    harness.render().unwrap();
+    // find_text_on_screen gives a byte offset; count characters for the column
+    let column_of = |harness: &EditorTestHarness, text: &str| {
+        let (offset, y) = harness.find_text_on_screen(text).unwrap();
+        let row = harness.get_row_text(y);
+        (row[..offset as usize].chars().count() as u16, y)
+    };
+
+    let (x, y) = column_of(&harness, "café");
+    harness.mouse_click(x + 1, y).unwrap();
+    harness.mouse_click(x + 1, y).unwrap();
+    assert_eq!(harness.get_selected_text(), "café");
+
+    let (x, y) = column_of(&harness, "value");
+    harness.mouse_click(x + 2, y).unwrap();
+    harness.mouse_click(x + 2, y).unwrap();
+    assert_eq!(harness.get_selected_text(), "value");
+
+    let (x, y) = column_of(&harness, "can't");
+    harness.mouse_click(x, y).unwrap();
+    harness.mouse_click(x, y).unwrap();
+    assert_eq!(harness.get_selected_text(), "can't");
+}
+
+/// Test that triple-click selects the whole line, and a fourth click starts over
+#[test]
+fn test_triple_click_selects_line() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("first line\nsecond line\nthird\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = harness.find_text_on_screen("second").unwrap();
+    for _ in 0..3 {
+        harness.mouse_click(x + 2, y).unwrap();
+    }
+    assert_eq!(harness.get_selected_text(), "second line\n");
+
+    harness.mouse_click(x + 2, y).unwrap();
+    assert_eq!(harness.get_selected_text(), "");
+}
+
+/// Test that dragging after a double-click extends the selection by whole words
+#[test]
+fn test_double_click_drag_extends_by_words() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("alpha beta gamma delta\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = harness.find_text_on_screen("beta").unwrap();
+    harness.mouse_click(x + 1, y).unwrap();
+    mouse_down(&mut harness, x + 1, y);
+    assert_eq!(harness.get_selected_text(), "beta");
+
+    // Forward into the middle of "gamma" selects all of it
+    mouse_drag_to(&mut harness, x + 7, y);
+    assert_eq!(harness.get_selected_text(), "beta gamma");
+
+    // Backward into "alpha" keeps "beta" selected
+    mouse_drag_to(&mut harness, x - 4, y);
+    assert_eq!(harness.get_selected_text(), "alpha beta");
+}
+
+/// Test that dragging after a triple-click extends the selection by whole lines
+#[test]
+fn test_triple_click_drag_extends_by_lines() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let _fixture = harness
+        .load_buffer_from_text("one\ntwo\nthree\nfour\n")
+        .unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = harness.find_text_on_screen("two").unwrap();
+    harness.mouse_click(x, y).unwrap();
+    harness.mouse_click(x, y).unwrap();
+    mouse_down(&mut harness, x, y);
+    assert_eq!(harness.get_selected_text(), "two\n");
+
+    mouse_drag_to(&mut harness, x + 1, y + 1);
+    assert_eq!(harness.get_selected_text(), "two\nthree\n");
+
+    mouse_drag_to(&mut harness, x + 1, y - 1);
+    assert_eq!(harness.get_selected_text(), "one\ntwo\n");
+}
+
+/// Test that clicking the line and column in the status bar opens Go to Line
+#[test]
+fn test_click_status_bar_position_opens_goto_line() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    let content: String = (1..=30).map(|n| format!("line {}\n", n)).collect();
+    let _fixture = harness.load_buffer_from_text(&content).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = harness.find_text_on_screen("Ln 1, Col 1").unwrap();
+    harness.mouse_click(x + 2, y).unwrap();
+    harness.assert_screen_contains("Go to line:");
+
+    harness.type_text("20").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.cursor_position(), content.find("line 20").unwrap());
+}
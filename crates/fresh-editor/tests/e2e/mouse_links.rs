@@ -0,0 +1,210 @@
+//! Tests for links
+//!
+//! Tests that:
+//! - URLs and `path:line` references in the visible lines are underlined
+//! - Hovering a bare path underlines it, and moving away removes the underline
+//! - Paths of missing files are not links
+//! - Open Link at Cursor opens the file at its `:line`
+//! - Ctrl+Click on a path opens the file at its `:line`
+//! - Ctrl+Click on a symbol goes to its definition
+//! - Shift+Click still extends the selection
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use ratatui::style::Modifier;
+use tempfile::TempDir;
+
+fn is_underlined(harness: &EditorTestHarness, x: u16, y: u16) -> bool {
+    harness
+        .get_cell_style(x, y)
+        .is_some_and(|style| style.add_modifier.contains(Modifier::UNDERLINED))
+}
+
+/// Screen column and row of `text`; the gutter's `│` is wider in bytes than on screen
+fn find_on_screen(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    let (offset, y) = harness.find_text_on_screen(text).unwrap();
+    let row = harness.get_row_text(y);
+    (row[..offset as usize].chars().count() as u16, y)
+}
+
+/// Create `src/target.rs` and a notes file with `content` in a temp dir
+fn notes_with_target(content: &str) -> (TempDir, std::path::PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+    std::fs::write(
+        temp_dir.path().join("src").join("target.rs"),
+        "one\ntwo\nthree\n",
+    )
+    .unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, content).unwrap();
+    (temp_dir, file)
+}
+
+#[test]
+fn test_visible_links_underlined() {
+    let (_temp_dir, file) =
+        notes_with_target("https://example.com src/target.rs:2 src/target.rs src/missing.rs:1\n");
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = find_on_screen(&harness, "https://");
+    assert!(is_underlined(&harness, x, y));
+    assert!(is_underlined(&harness, x + 18, y));
+    assert!(!is_underlined(&harness, x + 19, y));
+    let (x, y) = find_on_screen(&harness, "src/target.rs:2");
+    assert!(is_underlined(&harness, x, y));
+    assert!(is_underlined(&harness, x + 14, y));
+    // Bare paths and missing files are not underlined
+    let (x, y) = find_on_screen(&harness, "src/target.rs src");
+    assert!(!is_underlined(&harness, x, y));
+    let (x, y) = find_on_screen(&harness, "src/missing.rs");
+    assert!(!is_underlined(&harness, x, y));
+
+    let mut config = Config::default();
+    config.editor.underline_links = false;
+    let mut harness = EditorTestHarness::with_config(100, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    let (x, y) = find_on_screen(&harness, "https://");
+    assert!(!is_underlined(&harness, x, y));
+}
+
+#[test]
+fn test_hover_underlines_path() {
+    let (_temp_dir, file) = notes_with_target("see src/target.rs now\n");
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = find_on_screen(&harness, "src/target.rs");
+    assert!(!is_underlined(&harness, x, y));
+
+    harness.mouse_move(x + 5, y).unwrap();
+    assert!(is_underlined(&harness, x, y));
+    assert!(is_underlined(&harness, x + 12, y));
+    assert!(!is_underlined(&harness, x - 2, y));
+    assert!(!is_underlined(&harness, x + 14, y));
+
+    harness.mouse_move(x - 2, y).unwrap();
+    assert!(!is_underlined(&harness, x, y));
+}
+
+#[test]
+fn test_missing_file_is_not_a_link() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("notes.txt");
+    std::fs::write(&file, "see src/missing.rs\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = find_on_screen(&harness, "src/missing.rs");
+    harness.mouse_move(x + 3, y).unwrap();
+    assert!(!is_underlined(&harness, x + 3, y));
+}
+
+#[test]
+fn test_ctrl_click_opens_file_at_line() {
+    let (_temp_dir, file) = notes_with_target("error in src/target.rs:3:2 here\n");
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = find_on_screen(&harness, "src/target.rs");
+    harness.mouse_move(x + 2, y).unwrap();
+    assert!(is_underlined(&harness, x + 2, y));
+
+    harness.mouse_ctrl_click(x + 2, y).unwrap();
+    harness.assert_buffer_content("one\ntwo\nthree\n");
+    assert_eq!(harness.cursor_position(), "one\ntwo\nt".len());
+}
+
+#[test]
+fn test_open_link_at_cursor() {
+    let (_temp_dir, file) = notes_with_target("plain src/target.rs:2:3\n");
+
+    // Wide enough for the status message next to the temp path
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness.editor_mut().open_link_at_cursor();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No link at cursor");
+
+    // The end of the link still counts as at it
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().open_link_at_cursor();
+    harness.render().unwrap();
+    harness.assert_buffer_content("one\ntwo\nthree\n");
+    assert_eq!(harness.cursor_position(), "one\ntw".len());
+}
+
+#[test]
+fn test_ctrl_click_goes_to_definition() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn()?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(&test_file, "fn main() {\n    let value = 1;\n}\n")?;
+
+    let mut config = Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::script_path().to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    // The fake server always answers with the start of the file
+    let (x, y) = find_on_screen(&harness, "value");
+    harness.mouse_ctrl_click(x + 1, y)?;
+    harness.wait_until(|h| h.cursor_position() == 0)?;
+
+    // No selection was started by the click
+    assert_eq!(
+        harness.editor().active_state().cursors.primary().anchor,
+        None
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shift_click_still_extends_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("select.txt");
+    std::fs::write(&file, "hello world\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let (x, y) = find_on_screen(&harness, "world");
+    harness.mouse_shift_click(x, y).unwrap();
+    let cursor = harness.editor().active_state().cursors.primary();
+    assert_eq!(cursor.position, "hello ".len());
+    assert_eq!(cursor.anchor, Some(0));
+}
@@ -0,0 +1,204 @@
+//! Tests for mouse wheel scrolling
+//!
+//! Tests that:
+//! - The wheel scrolls the split under the pointer, not just the active one
+//! - `mouse_scroll_lines` sets how far one wheel notch scrolls
+//! - Horizontal wheel events and Shift+wheel scroll sideways
+//! - Smooth scrolling spreads a notch over several frames
+//! - The wheel over the tab bar switches between the split's buffers
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
+use fresh::config::Config;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn numbered_lines() -> String {
+    (1..=100).map(|i| format!("line {i:03}\n")).collect()
+}
+
+fn scroll(
+    harness: &mut EditorTestHarness,
+    kind: MouseEventKind,
+    modifiers: KeyModifiers,
+    col: u16,
+    row: u16,
+) {
+    harness
+        .send_mouse(MouseEvent {
+            kind,
+            column: col,
+            row,
+            modifiers,
+        })
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_wheel_scrolls_split_under_pointer() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    std::fs::write(&file, numbered_lines()).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("split vert").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    // The new split on the right is active; scroll the left one
+    let (_, y) = harness.find_text_on_screen("line 001").unwrap();
+    harness.mouse_scroll_down(10, y + 5).unwrap();
+
+    assert_eq!(harness.top_byte(), 0);
+    let (x, _) = harness.find_text_on_screen("line 001").unwrap();
+    assert!(x >= 40, "only the right split still shows the first line");
+    let (x, top) = harness.find_text_on_screen("line 004").unwrap();
+    assert!(x < 40);
+    assert_eq!(top, y);
+}
+
+#[test]
+fn test_wheel_over_tabs_switches_buffer() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("first.txt");
+    let second = temp_dir.path().join("second.txt");
+    std::fs::write(&first, "alpha").unwrap();
+    std::fs::write(&second, "bravo").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&first).unwrap();
+    harness.open_file(&second).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "bravo");
+
+    let (x, y) = harness.find_text_on_screen("first.txt").unwrap();
+    harness.mouse_scroll_up(x, y).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "alpha");
+    harness.mouse_scroll_down(x, y).unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "bravo");
+}
+
+#[test]
+fn test_mouse_scroll_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    std::fs::write(&file, numbered_lines()).unwrap();
+
+    let mut config = Config::default();
+    config.editor.mouse_scroll_lines = 5;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness.mouse_scroll_down(40, 10).unwrap();
+    assert_eq!(harness.top_line_number(), 5);
+    harness.mouse_scroll_up(40, 10).unwrap();
+    assert_eq!(harness.top_line_number(), 0);
+}
+
+#[test]
+fn test_horizontal_scroll() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("wide.txt");
+    let long_line = format!("start{}end\n", "x".repeat(150));
+    std::fs::write(&file, &long_line).unwrap();
+
+    let mut config = Config::default();
+    config.editor.line_wrap = false;
+    config.editor.mouse_scroll_columns = 6;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    let (_, y) = harness.find_text_on_screen("start").unwrap();
+
+    scroll(
+        &mut harness,
+        MouseEventKind::ScrollRight,
+        KeyModifiers::NONE,
+        40,
+        y,
+    );
+    assert!(harness.find_text_on_screen("start").is_none());
+    assert_eq!(harness.editor().active_viewport().left_column, 6);
+
+    // Shift+wheel scrolls sideways too
+    scroll(
+        &mut harness,
+        MouseEventKind::ScrollDown,
+        KeyModifiers::SHIFT,
+        40,
+        y,
+    );
+    assert_eq!(harness.editor().active_viewport().left_column, 12);
+    scroll(
+        &mut harness,
+        MouseEventKind::ScrollUp,
+        KeyModifiers::SHIFT,
+        40,
+        y,
+    );
+    scroll(
+        &mut harness,
+        MouseEventKind::ScrollLeft,
+        KeyModifiers::NONE,
+        40,
+        y,
+    );
+    assert_eq!(harness.editor().active_viewport().left_column, 0);
+    assert!(harness.find_text_on_screen("start").is_some());
+
+    // Scrolling stops once the end of the line is in view
+    for _ in 0..50 {
+        scroll(
+            &mut harness,
+            MouseEventKind::ScrollRight,
+            KeyModifiers::NONE,
+            40,
+            y,
+        );
+    }
+    assert!(harness.find_text_on_screen("xend").is_some());
+}
+
+#[test]
+fn test_smooth_scrolling() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    std::fs::write(&file, numbered_lines()).unwrap();
+
+    let mut config = Config::default();
+    config.editor.smooth_scrolling = true;
+    config.editor.mouse_scroll_lines = 6;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    // The wheel only queues the scroll; frames play it out
+    harness.mouse_scroll_down(40, 10).unwrap();
+    assert_eq!(harness.top_line_number(), 0);
+
+    assert!(harness.editor_mut().step_smooth_scroll());
+    assert_eq!(harness.top_line_number(), 2);
+
+    // Steps are paced to the frame rate
+    assert!(harness.editor_mut().step_smooth_scroll());
+    assert_eq!(harness.top_line_number(), 2);
+
+    let mut frames = 0;
+    loop {
+        harness.advance_time(Duration::from_millis(20));
+        if !harness.editor_mut().step_smooth_scroll() {
+            break;
+        }
+        frames += 1;
+    }
+    assert!(frames > 1);
+    assert_eq!(harness.top_line_number(), 6);
+}
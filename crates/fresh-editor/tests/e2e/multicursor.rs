@@ -1743,3 +1743,94 @@ fn test_multicursor_cut_undo_batched() {
         "Single undo should restore all 'hello' instances (undo should be batched)"
     );
 }
+
+/// Run a command from the command palette, answering its prompt
+fn run_prompt_command(harness: &mut EditorTestHarness, command: &str, input: &str) {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    harness.run_command(command).unwrap();
+    harness.type_text(input).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Select Regex in Selection puts a cursor on every match inside the
+/// selection, and leaves the text outside it alone
+#[test]
+fn test_select_regex_in_selection() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.type_text("let a = 1, b = 22;\nc = 3").unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+
+    run_prompt_command(&mut harness, "Select Regex in Selection", r"\d+");
+    harness.assert_screen_contains("2 selections");
+    assert_eq!(harness.editor().active_state().cursors.count(), 2);
+
+    harness.type_text("0").unwrap();
+    harness.assert_buffer_content("let a = 0, b = 0;\nc = 3");
+
+    // Nothing selected
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.run_command("Select Regex in Selection").unwrap();
+    harness.assert_screen_contains("Select some text first");
+}
+
+/// Split Selection on Regex selects the text between the matches
+#[test]
+fn test_split_selection_on_regex() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.type_text("red, green,blue").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::SHIFT)
+        .unwrap();
+
+    run_prompt_command(&mut harness, "Split Selection on Regex", r",\s*");
+    harness.assert_screen_contains("3 selections");
+
+    harness.type_text("x").unwrap();
+    harness.assert_buffer_content("x, x,x");
+}
+
+/// Cursor Undo brings back cursors collapsed by Esc without touching the
+/// text, and Cursor Redo collapses them again
+#[test]
+fn test_cursor_undo_and_redo() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.type_text("Line 1\nLine 2\nLine 3").unwrap();
+    harness.editor_mut().add_cursor_above();
+    harness.editor_mut().add_cursor_above();
+    assert_eq!(harness.editor().active_state().cursors.count(), 3);
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.editor().active_state().cursors.count(), 1);
+
+    harness
+        .send_key(KeyCode::Char('u'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_state().cursors.count(), 3);
+    harness.assert_screen_contains("Restored 3 cursor(s)");
+    harness.assert_buffer_content("Line 1\nLine 2\nLine 3");
+
+    harness
+        .send_key(
+            KeyCode::Char('u'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    assert_eq!(harness.editor().active_state().cursors.count(), 1);
+    harness
+        .send_key(KeyCode::Char('u'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.editor().active_state().cursors.count(), 3);
+
+    // The restored cursors are usable
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("Line 1!\nLine 2!\nLine 3!");
+}
@@ -0,0 +1,100 @@
+//! Tests for incrementing numbers and inserting sequences
+//!
+//! Tests that:
+//! - Ctrl+Alt+A / Ctrl+Alt+X change the number under or after each cursor
+//! - Insert Sequence numbers the cursors in order, keeping zero padding
+//! - Each command is a single undo step
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[test]
+fn test_increment_and_decrement() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("x = 9;").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    // The first number after the cursor is changed
+    harness
+        .send_key(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.assert_buffer_content("x = 10;");
+    harness
+        .send_key(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.assert_buffer_content("x = 11;");
+    harness
+        .send_key(
+            KeyCode::Char('x'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.assert_buffer_content("x = 10;");
+}
+
+#[test]
+fn test_increment_multicursor() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("a = 0x0f\nb = 007\nc = -1").unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.editor_mut().add_cursor_above();
+    harness.editor_mut().add_cursor_above();
+    harness.render().unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL | KeyModifiers::ALT,
+        )
+        .unwrap();
+    harness.assert_buffer_content("a = 0x10\nb = 008\nc = 0");
+
+    // All the cursors' changes are undone together
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("a = 0x0f\nb = 007\nc = -1");
+}
+
+#[test]
+fn test_insert_sequence() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("id \nid \nid ").unwrap();
+    harness.editor_mut().add_cursor_above();
+    harness.editor_mut().add_cursor_above();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text("Insert Sequence").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Sequence start[,step]:");
+
+    // Replace the default start of 1
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("009,2").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("id 009\nid 011\nid 013");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("id \nid \nid ");
+}
@@ -41,6 +41,8 @@ fn test_format_on_save() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -50,6 +52,13 @@ fn test_format_on_save() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -104,6 +113,8 @@ fn test_on_save_linter_style() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -113,6 +124,13 @@ fn test_on_save_linter_style() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -167,6 +185,8 @@ fn test_on_save_action_failure() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -176,6 +196,13 @@ fn test_on_save_action_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -239,6 +266,8 @@ fn test_on_save_file_placeholder() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -248,6 +277,13 @@ fn test_on_save_file_placeholder() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -305,6 +341,8 @@ fn test_formatter_stdin_mode() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -314,6 +352,13 @@ fn test_formatter_stdin_mode() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -378,6 +423,8 @@ fn test_on_save_stops_on_failure() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -387,6 +434,13 @@ fn test_on_save_stops_on_failure() {
             formatter: None,
             format_on_save: false,
             on_save: vec![action1, action2],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
@@ -472,6 +526,8 @@ fn test_formatter_not_found_shows_message() {
             filenames: vec![],
             grammar: "plaintext".to_string(),
             comment_prefix: None,
+            block_comment_start: None,
+            block_comment_end: None,
             auto_indent: false,
             highlighter: Default::default(),
             textmate_grammar: None,
@@ -481,6 +537,13 @@ fn test_formatter_not_found_shows_message() {
             formatter: Some(formatter),
             format_on_save: true,
             on_save: vec![],
+            conceal: vec![],
+            repl: None,
+            rulers: None,
+            max_line_length: None,
+            increase_indent_pattern: None,
+            decrease_indent_pattern: None,
+            continuation_pattern: None,
         },
     );
 
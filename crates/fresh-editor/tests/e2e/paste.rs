@@ -4,6 +4,8 @@
 //! - Paste with selection (should replace selection)
 //! - Multi-cursor paste
 //! - Paste undo atomicity
+//! - No auto-indent inside pasted text
+//! - Typed bursts pasted at once
 //!
 //! Issue #372: External paste should behave like internal paste
 
@@ -285,6 +287,44 @@ fn test_paste_multiline_text() {
     harness.assert_buffer_content("");
 }
 
+/// Test that an external paste keeps its own indentation
+/// Pasted lines must not be auto-indented like typed ones
+#[test]
+fn test_external_paste_is_not_auto_indented() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("    indented").unwrap();
+    harness
+        .editor_mut()
+        .paste_text("\nfirst\n  second\n".to_string());
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("    indented\nfirst\n  second\n");
+
+    // Single undo removes the whole paste
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("    indented");
+}
+
+/// Test that a burst of queued key presses with a line break is pasted
+/// at once, like a paste from a terminal without bracketed paste
+#[test]
+fn test_typed_burst_is_pasted() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("    indented").unwrap();
+    harness.send_typed_burst("\nfirst\n  second").unwrap();
+    harness.assert_buffer_content("    indented\nfirst\n  second");
+
+    // Single undo removes the whole burst
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("    indented");
+}
+
 /// Test that paste at end of line works correctly
 #[test]
 fn test_paste_at_end_of_line() {
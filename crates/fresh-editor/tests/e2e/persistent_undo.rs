@@ -0,0 +1,80 @@
+//! E2E tests for undo history kept across editor restarts
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// An editor with persistent undo on, sharing its data directory with the
+/// other editors of the test
+fn start_editor(temp_dir: &TempDir) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.persistent_undo = true;
+    EditorTestHarness::with_shared_dir_context(
+        100,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+        DirectoryContext::for_testing(temp_dir.path()),
+    )
+    .unwrap()
+}
+
+/// Type " world" at the end of `path` and save it, in a new editor
+fn edit_and_save(temp_dir: &TempDir, path: &Path) {
+    let mut harness = start_editor(temp_dir);
+    harness.open_file(path).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" world").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(fs::read_to_string(path).unwrap(), "hello world");
+}
+
+fn undo_all(harness: &mut EditorTestHarness) {
+    for _ in 0..10 {
+        harness
+            .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+}
+
+/// Edits saved in one editor can be undone after reopening the file
+#[test]
+fn test_undo_history_restored_after_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    let file_path = fs::canonicalize(&file_path).unwrap();
+    edit_and_save(&temp_dir, &file_path);
+
+    let mut harness = start_editor(&temp_dir);
+    harness.open_file(&file_path).unwrap();
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    undo_all(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+}
+
+/// A file changed outside the editor doesn't get its old history back
+#[test]
+fn test_undo_history_discarded_after_external_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    let file_path = fs::canonicalize(&file_path).unwrap();
+    edit_and_save(&temp_dir, &file_path);
+    fs::write(&file_path, "hello world, edited elsewhere").unwrap();
+
+    let mut harness = start_editor(&temp_dir);
+    harness.open_file(&file_path).unwrap();
+    undo_all(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "hello world, edited elsewhere"
+    );
+}
@@ -10,4 +10,8 @@ pub mod language_pack;
 pub mod lsp_find_references;
 pub mod package_manager;
 pub mod plugin;
+pub mod plugin_permissions;
+pub mod search_edit;
+pub mod search_replace;
 pub mod theme_editor;
+pub mod todo_panel;
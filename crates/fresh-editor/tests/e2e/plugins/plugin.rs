@@ -1282,3 +1282,99 @@ editor.debug("Theme overlay test plugin loaded");
     let screen = harness.screen_to_string();
     println!("Final screen:\n{}", screen);
 }
+
+/// Test that applyEdits changes several files all or none
+#[test]
+fn test_plugin_apply_edits_all_or_none() {
+    init_tracing_from_env();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file_a = project_root.join("a.txt");
+    let file_b = project_root.join("b.txt");
+    fs::write(&file_a, "alpha one\n").unwrap();
+    fs::write(&file_b, "beta one\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    // The first run expects the wrong text in b.txt, the second the right one
+    let test_plugin = format!(
+        r#"
+const editor = getEditor();
+let runs = 0;
+
+function replaceOne(path: string, oldText: string): JsFileEdits {{
+    return {{
+        path,
+        edits: [{{
+            range: {{ start: {{ line: 0, character: 0 }}, end: {{ line: 0, character: oldText.length }} }},
+            newText: oldText.replace("one", "two"),
+            oldText,
+        }}],
+    }};
+}}
+
+globalThis.test_apply_edits = async function(): Promise<void> {{
+    runs++;
+    const b = runs === 1 ? "gamma one" : "beta one";
+    try {{
+        const count = await editor.applyEdits(
+            [replaceOne({a:?}, "alpha one"), replaceOne({b:?}, b)],
+            "Test Edits",
+        );
+        editor.setStatus(`Applied ${{count}} edits`);
+    }} catch (e) {{
+        editor.setStatus(`Run ${{runs}} failed`);
+    }}
+}};
+
+editor.registerCommand("Test: Apply Edits", "Apply test edits", "test_apply_edits", null);
+"#,
+        a = file_a.to_string_lossy(),
+        b = file_b.to_string_lossy(),
+    );
+    fs::write(plugins_dir.join("test_apply_edits.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Test: Apply Edits").unwrap();
+        harness
+            .wait_until(|h| h.screen_to_string().contains("Apply test edits"))
+            .unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    // b.txt doesn't hold the expected text, so a.txt is left alone too
+    run_command(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Run 1 failed"))
+        .unwrap();
+    harness.assert_buffer_content("alpha one\n");
+
+    run_command(&mut harness);
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Applied 2 edits"))
+        .unwrap();
+    harness.assert_buffer_content("alpha two\n");
+    harness.open_file(&file_b).unwrap();
+    harness.assert_buffer_content("beta two\n");
+
+    // Each file's edits are one undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("beta one\n");
+}
@@ -0,0 +1,105 @@
+//! Tests for the plugin sandbox
+//!
+//! Tests that:
+//! - A sandboxed plugin's process waits for the user to allow it
+//! - A denied process fails in the plugin, and isn't asked about again
+
+use crate::common::harness::{copy_plugin_lib, EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// A sandboxed harness with a plugin whose command runs `echo`
+fn sandboxed_plugin() -> (tempfile::TempDir, EditorTestHarness) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    fs::write(
+        plugins_dir.join("echo_test.ts"),
+        r#"
+const editor = getEditor();
+
+globalThis.run_echo = async function(): Promise<void> {
+    try {
+        const result = await editor.spawnProcess("echo", ["sandboxed"]);
+        editor.setStatus(`Ran: ${result.stdout.trim()}`);
+    } catch (e) {
+        editor.setStatus(`Failed: ${e}`);
+    }
+};
+
+editor.registerCommand("Run Echo", "Run echo", "run_echo", null);
+"#,
+    )
+    .unwrap();
+
+    // Wide enough for the permission errors in the status bar
+    let harness = EditorTestHarness::create(
+        160,
+        24,
+        HarnessOptions::new()
+            .with_working_dir(project_root)
+            .without_empty_plugins_dir()
+            .with_plugin_sandbox(),
+    )
+    .unwrap();
+    (temp_dir, harness)
+}
+
+fn answer(harness: &mut EditorTestHarness, text: &str) {
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn run_echo(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Run Echo").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Run echo"))
+        .unwrap();
+    answer(harness, "");
+}
+
+#[test]
+fn test_allowed_process_runs_after_prompt() {
+    let (_temp_dir, mut harness) = sandboxed_plugin();
+    run_echo(&mut harness);
+    harness
+        .wait_for_screen_contains("wants to run 'echo'")
+        .unwrap();
+
+    answer(&mut harness, "s");
+    harness.wait_for_screen_contains("Ran: sandboxed").unwrap();
+
+    // Allowed for the rest of the session
+    run_echo(&mut harness);
+    harness.wait_for_screen_contains("Ran: sandboxed").unwrap();
+    harness.assert_screen_not_contains("wants to run");
+}
+
+#[test]
+fn test_denied_process_fails() {
+    let (_temp_dir, mut harness) = sandboxed_plugin();
+    run_echo(&mut harness);
+    harness
+        .wait_for_screen_contains("wants to run 'echo'")
+        .unwrap();
+
+    answer(&mut harness, "d");
+    harness
+        .wait_for_screen_contains("Failed: Error: Plugin 'echo_test' is not allowed to run 'echo'")
+        .unwrap();
+
+    // Denied without asking again
+    harness.editor_mut().set_status_message(String::new());
+    run_echo(&mut harness);
+    harness
+        .wait_for_screen_contains("is not allowed to run 'echo'")
+        .unwrap();
+}
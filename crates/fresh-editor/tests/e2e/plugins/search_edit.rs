@@ -0,0 +1,96 @@
+//! E2E tests for the search_edit plugin
+
+use crate::common::git_test_helper::GitTestRepo;
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+/// Search for `pattern` with Search and Edit in Project
+fn search_and_edit(harness: &mut EditorTestHarness, pattern: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Search and Edit in Project").unwrap();
+    harness
+        .wait_for_screen_contains("Search and Edit in Project")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(pattern).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+}
+
+/// Append `text` to line `line` (0-based) of the active buffer
+fn append_to_line(harness: &mut EditorTestHarness, line: usize, text: &str) {
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    for _ in 0..line {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(text).unwrap();
+}
+
+/// Edited match lines are written back to their files on Ctrl+S, both to a
+/// file open in a buffer and to one that isn't, and a file changed since
+/// the search is left alone
+#[test]
+fn test_search_edit_writes_back_and_detects_conflicts() {
+    let repo = GitTestRepo::new();
+    let plugins_dir = repo.path.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "search_edit");
+    copy_plugin_lib(&plugins_dir);
+    let a = repo.create_file("src/a.rs", "let old_name = 1;\nprint(old_name);\n");
+    let b = repo.create_file("src/b.rs", "// old_name here\n");
+    repo.git_add(&["src"]);
+    repo.git_commit("Initial commit");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&b).unwrap();
+
+    search_and_edit(&mut harness, "old_name");
+    harness.wait_for_screen_contains("3 matches").unwrap();
+    harness.assert_screen_contains("src/a.rs:2: print(old_name);");
+
+    // Lines 0 and 1 are the header; the matches follow in file order
+    append_to_line(&mut harness, 2, " // a");
+    append_to_line(&mut harness, 4, " too");
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|_| {
+            fs::read_to_string(&a).unwrap() == "let old_name = 1; // a\nprint(old_name);\n"
+                && fs::read_to_string(&b).unwrap() == "// old_name here too\n"
+        })
+        .unwrap();
+    harness.wait_for_screen_contains("Wrote 2 lines").unwrap();
+
+    // A file changed on disk since the search isn't written
+    fs::write(&a, "let old_name = 1; // a\nprint(old_name, 2);\n").unwrap();
+    append_to_line(&mut harness, 3, " // b");
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("src/a.rs changed since the search")
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(&a).unwrap(),
+        "let old_name = 1; // a\nprint(old_name, 2);\n"
+    );
+}
@@ -0,0 +1,72 @@
+//! E2E tests for the search_replace plugin
+
+use crate::common::git_test_helper::GitTestRepo;
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+/// Replacing saves the files that had no unsaved changes, whether they were
+/// open or not, and leaves a buffer with unsaved changes for the user to save
+#[test]
+fn test_search_replace_saves_unmodified_files() {
+    let repo = GitTestRepo::new();
+    let plugins_dir = repo.path.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "search_replace");
+    copy_plugin_lib(&plugins_dir);
+    let a = repo.create_file("src/a.rs", "let old_name = 1;\n");
+    let b = repo.create_file("src/b.rs", "// old_name here\n");
+    let c = repo.create_file("src/c.rs", "old_name();\nother();\n");
+    repo.git_add(&["src"]);
+    repo.git_commit("Initial commit");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&b).unwrap();
+    harness.open_file(&c).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" // unsaved").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Search and Replace in Project").unwrap();
+    harness
+        .wait_for_screen_contains("Search and Replace in Project")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("old_name").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("new_name").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("src/c.rs:1").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness
+        .wait_until(|_| {
+            fs::read_to_string(&a).unwrap() == "let new_name = 1;\n"
+                && fs::read_to_string(&b).unwrap() == "// new_name here\n"
+        })
+        .unwrap();
+    assert_eq!(fs::read_to_string(&c).unwrap(), "old_name();\nother();\n");
+    harness.open_file(&c).unwrap();
+    harness.assert_buffer_content("new_name();\nother(); // unsaved\n");
+}
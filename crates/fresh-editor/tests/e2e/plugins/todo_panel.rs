@@ -0,0 +1,64 @@
+//! E2E tests for the todo_panel plugin
+
+use crate::common::git_test_helper::GitTestRepo;
+use crate::common::harness::{copy_plugin, copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::fs;
+
+/// The panel lists the tagged comments of the workspace, leaving out ignored
+/// files, jumps to one on Enter, and picks up new ones when a file is saved
+#[test]
+fn test_todo_panel_collects_and_updates_on_save() {
+    if std::process::Command::new("rg")
+        .arg("--version")
+        .output()
+        .is_err()
+    {
+        eprintln!("Skipping test: rg not found in PATH");
+        return;
+    }
+
+    let repo = GitTestRepo::new();
+    let plugins_dir = repo.path.join("plugins");
+    fs::create_dir_all(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "todo_panel");
+    copy_plugin_lib(&plugins_dir);
+    repo.create_file(".gitignore", "plugins/\nignored/\n");
+    repo.create_file(
+        "src/main.rs",
+        "fn main() {}\n// TODO: wire up config\n// FIXME: broken on empty input\n",
+    );
+    repo.create_file("ignored/gen.rs", "// TODO: generated, not ours\n");
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    harness.run_command("Show TODO Panel").unwrap();
+    harness.wait_for_screen_contains("TODOs (2):").unwrap();
+    harness.assert_screen_contains("wire up config");
+    harness.assert_screen_contains("broken on empty input");
+    harness.assert_screen_contains("TODO 1 | FIXME 1 | HACK 0");
+    harness.assert_screen_not_contains("generated, not ours");
+
+    // Enter on the first item opens the file at the comment
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap().contains("wire up config"))
+        .unwrap();
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.type_text("let x = 1; // HACK: quick fix ").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_screen_contains("quick fix").unwrap();
+    harness.wait_for_screen_contains("HACK 1").unwrap();
+}
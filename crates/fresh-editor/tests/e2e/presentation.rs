@@ -0,0 +1,113 @@
+//! Tests for presentation mode
+//!
+//! Tests that:
+//! - A follower gets the presented buffer in a read-only buffer
+//! - Edits and cursor moves on the presenter reach the follower
+//! - The follower is told when the presenter stops
+//! - Encrypted buffers are not presented
+
+use crate::common::harness::EditorTestHarness;
+use crate::common::peers::{run_with_address, sync_until};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::services::encryption::{EncryptionScheme, FileEncryption};
+use tempfile::TempDir;
+
+#[test]
+fn test_follow_presentation() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("talk.txt");
+    std::fs::write(&file, "first line\nsecond line\n").unwrap();
+
+    let mut presenter = EditorTestHarness::new(80, 24).unwrap();
+    presenter.open_file(&file).unwrap();
+    presenter.render().unwrap();
+    run_with_address(&mut presenter, "Presentation: Start", "127.0.0.1:0");
+    let address = presenter
+        .editor()
+        .presentation_address()
+        .unwrap()
+        .to_string();
+
+    // Wide enough for status messages next to the presented path
+    let mut follower = EditorTestHarness::new(160, 24).unwrap();
+    run_with_address(&mut follower, "Presentation: Follow", &address);
+    sync_until(&mut presenter, &mut follower, |_, follower| {
+        follower.get_buffer_content().as_deref() == Some("first line\nsecond line\n")
+    });
+    // Named like the presenter's tab, which is the full path outside its working dir
+    let screen = follower.screen_to_string();
+    assert!(screen.contains("*presentation: ") && screen.contains("talk.txt*"));
+
+    // Edits and cursor moves follow along
+    presenter.type_text("> ").unwrap();
+    presenter
+        .send_key(KeyCode::Down, KeyModifiers::NONE)
+        .unwrap();
+    let cursor = presenter.editor().active_state().cursors.primary().position;
+    sync_until(&mut presenter, &mut follower, |_, follower| {
+        follower.get_buffer_content().as_deref() == Some("> first line\nsecond line\n")
+            && follower.editor().active_state().cursors.primary().position == cursor
+    });
+
+    // The follower can't edit
+    follower.type_text("x").unwrap();
+    assert_eq!(
+        follower.get_buffer_content().as_deref(),
+        Some("> first line\nsecond line\n")
+    );
+
+    // Stopping the presentation tells the follower
+    presenter
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    presenter.type_text("Presentation: Stop").unwrap();
+    presenter
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert!(presenter.editor().presentation_address().is_none());
+    sync_until(&mut presenter, &mut follower, |_, follower| {
+        follower
+            .screen_to_string()
+            .contains("The presenter stopped")
+    });
+}
+
+#[test]
+fn test_encrypted_buffer_is_not_presented() {
+    let temp_dir = TempDir::new().unwrap();
+    let notes = temp_dir.path().join("notes.txt");
+    std::fs::write(&notes, "public notes\n").unwrap();
+    let secret = temp_dir.path().join("secret.txt");
+    std::fs::write(&secret, "the password\n").unwrap();
+
+    // Wide enough for the status message next to the temp path
+    let mut presenter = EditorTestHarness::new(120, 24).unwrap();
+    presenter.open_file(&notes).unwrap();
+    presenter.render().unwrap();
+    run_with_address(&mut presenter, "Presentation: Start", "127.0.0.1:0");
+    presenter.editor_mut().sync_presentation();
+
+    // Switching to an encrypted buffer keeps it from viewers
+    presenter.open_file(&secret).unwrap();
+    presenter
+        .editor_mut()
+        .active_state_mut()
+        .buffer
+        .set_encryption(Some(FileEncryption::with_key(EncryptionScheme::Gpg, "key")));
+    presenter.editor_mut().sync_presentation();
+    presenter.render().unwrap();
+    presenter.assert_screen_contains("secret.txt is encrypted");
+
+    // A viewer joining now gets the last buffer that was presented
+    let address = presenter
+        .editor()
+        .presentation_address()
+        .unwrap()
+        .to_string();
+    let mut follower = EditorTestHarness::new(80, 24).unwrap();
+    run_with_address(&mut follower, "Presentation: Follow", &address);
+    sync_until(&mut presenter, &mut follower, |_, follower| {
+        follower.get_buffer_content().as_deref() == Some("public notes\n")
+    });
+    assert!(!follower.screen_to_string().contains("password"));
+}
@@ -0,0 +1,38 @@
+//! E2E tests for the Profiler buffer
+
+use crate::common::harness::EditorTestHarness;
+use std::time::Duration;
+
+/// The Profiler shows frame, latency, memory and buffer statistics, and
+/// updates once a second while it's shown
+#[test]
+fn test_show_profiler() {
+    // Tall enough to show every section
+    let mut harness = EditorTestHarness::new(120, 60).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+
+    harness.editor_mut().show_profiler();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Frames drawn");
+    harness.assert_screen_contains("Buffer contents");
+    harness.assert_screen_contains(">= 100 ms");
+    harness.assert_screen_contains("In memory");
+    harness.assert_screen_contains("*Profiler*");
+    let first = harness.get_buffer_content().unwrap();
+
+    // Not updated again within a second
+    harness.process_async_and_render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), first);
+
+    harness.advance_time(Duration::from_secs(1));
+    harness.process_async_and_render().unwrap();
+    assert_ne!(harness.get_buffer_content().unwrap(), first);
+
+    // Showing it again reuses the buffer, which can't be edited
+    let profiler = harness.editor().active_buffer();
+    harness.editor_mut().show_profiler();
+    assert_eq!(harness.editor().active_buffer(), profiler);
+    harness.type_text("X").unwrap();
+    assert!(!harness.get_buffer_content().unwrap().starts_with('X'));
+}
@@ -0,0 +1,67 @@
+//! E2E tests for the project environment loaded from `.env` and `.envrc`
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// A `.env` and a task that prints one of its variables
+fn write_project(harness: &EditorTestHarness) {
+    let project_dir = harness.project_dir().unwrap();
+    fs::write(
+        project_dir.join(".env"),
+        "# Project settings\nexport FRESH_GREETING=\"hello from dotenv\"\n",
+    )
+    .unwrap();
+    fs::create_dir_all(project_dir.join(".fresh")).unwrap();
+    fs::write(
+        project_dir.join(".fresh/tasks.toml"),
+        "[tasks.greet]\ncommand = 'echo \"greeting: $FRESH_GREETING\"'\n",
+    )
+    .unwrap();
+}
+
+/// After a reload the buffer lists the `.env` variables, and tasks get them
+#[test]
+fn test_reload_shows_dotenv_and_tasks_get_it() {
+    // Wide enough for the reload message in the status bar
+    let mut harness = EditorTestHarness::with_temp_project(140, 24).unwrap();
+    write_project(&harness);
+
+    // The files were written after startup
+    harness.run_command("Show Project Environment").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("No .envrc or .env in the project.");
+
+    harness
+        .send_key(KeyCode::Char('g'), KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Loaded from: .env");
+    harness.assert_screen_contains("FRESH_GREETING=hello from dotenv");
+    harness.assert_screen_contains("Project environment reloaded (variables: 1)");
+
+    harness.run_command("Task: greet").unwrap();
+    harness
+        .wait_for_screen_contains("greeting: hello from dotenv")
+        .unwrap();
+}
+
+/// An untrusted project's environment isn't loaded
+#[test]
+fn test_project_env_not_loaded_in_safe_mode() {
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_project_root()
+            .with_untrusted_workspace(),
+    )
+    .unwrap();
+    write_project(&harness);
+
+    harness.run_command("Reload Project Environment").unwrap();
+    harness.run_command("Show Project Environment").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("the project's environment isn't loaded");
+    harness.assert_screen_not_contains("FRESH_GREETING");
+}
@@ -0,0 +1,62 @@
+//! E2E tests for read-only file buffers
+
+use crate::common::harness::EditorTestHarness;
+use std::fs;
+use tempfile::TempDir;
+
+/// A file without write permission opens read-only until toggled
+#[test]
+#[cfg(unix)]
+fn test_unwritable_file_opens_read_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+    fs::set_permissions(&file_path, fs::Permissions::from_mode(0o444)).unwrap();
+
+    // Wide enough for the status message next to the temp path
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[RO]");
+    harness.assert_screen_contains("is not writable, opened read-only");
+
+    harness.type_text("X").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+    harness.assert_screen_contains("run Toggle Read-Only to edit");
+
+    harness.editor_mut().toggle_read_only();
+    harness.render().unwrap();
+    harness.assert_screen_contains("saving may ask to use sudo");
+    harness.assert_screen_not_contains("[RO]");
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "Xhello");
+}
+
+/// Any file can be made read-only, and stays read-only after a revert
+#[test]
+fn test_toggle_read_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.txt");
+    fs::write(&file_path, "hello").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.editor_mut().toggle_read_only();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[RO]");
+
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+
+    fs::write(&file_path, "changed").unwrap();
+    harness.editor_mut().revert_file().unwrap();
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "changed");
+
+    harness.editor_mut().toggle_read_only();
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "Xchanged");
+}
@@ -1018,13 +1018,16 @@ fn test_unnamed_buffer_created_via_new_buffer_has_stable_recovery() {
         unsaved_entries.len()
     );
 
-    // Verify content is the latest version
+    // Verify content is the latest version: the snapshot plus the journaled edits
     let chunked = storage
         .read_chunked_content(&unsaved_entries[0].id)
         .unwrap()
         .unwrap();
-    let content = String::from_utf8_lossy(&chunked.chunks[0].content);
-    assert_eq!(content, "First content more");
+    let mut content = chunked.chunks[0].content.clone();
+    for record in storage.read_journal(&unsaved_entries[0].id).unwrap() {
+        record.apply(&mut content).unwrap();
+    }
+    assert_eq!(String::from_utf8_lossy(&content), "First content more");
 }
 
 /// Regression test: inserting at the end of a large file should not crash recovery
@@ -0,0 +1,86 @@
+//! Tests for reopening closed buffers
+//!
+//! Tests that:
+//! - Ctrl+Shift+T reopens the most recently closed file at its cursor position
+//! - Closed buffers are reopened in most-recently-closed order
+//! - A status message is shown when there is nothing to reopen
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+fn close_tab(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+fn reopen_closed_buffer(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(
+            KeyCode::Char('t'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_reopen_restores_file_and_cursor() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_root = harness.project_dir().unwrap();
+    let file = project_root.join("notes.txt");
+    fs::write(&file, "first line\nsecond line\nthird line\n").unwrap();
+
+    harness.open_file(&file).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let cursor = harness.cursor_position();
+    assert_eq!(cursor, "first line\nsecond line".len());
+
+    close_tab(&mut harness);
+    assert_ne!(
+        harness.get_buffer_content().unwrap(),
+        "first line\nsecond line\nthird line\n"
+    );
+
+    reopen_closed_buffer(&mut harness);
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "first line\nsecond line\nthird line\n"
+    );
+    assert_eq!(harness.cursor_position(), cursor);
+    harness.assert_screen_contains("Reopened");
+}
+
+#[test]
+fn test_reopen_in_most_recently_closed_order() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_root = harness.project_dir().unwrap();
+    let first = project_root.join("first.txt");
+    let second = project_root.join("second.txt");
+    fs::write(&first, "first file\n").unwrap();
+    fs::write(&second, "second file\n").unwrap();
+
+    harness.open_file(&first).unwrap();
+    harness.open_file(&second).unwrap();
+    close_tab(&mut harness);
+    close_tab(&mut harness);
+
+    reopen_closed_buffer(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "first file\n");
+
+    reopen_closed_buffer(&mut harness);
+    assert_eq!(harness.get_buffer_content().unwrap(), "second file\n");
+}
+
+#[test]
+fn test_reopen_with_nothing_closed() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    harness.render().unwrap();
+
+    reopen_closed_buffer(&mut harness);
+    harness.assert_screen_contains("No closed buffers to reopen");
+}
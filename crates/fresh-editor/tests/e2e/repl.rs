@@ -0,0 +1,82 @@
+//! Tests for scratch buffers and sending code to a REPL
+//!
+//! Tests that:
+//! - New Scratch Buffer creates an unsaved buffer named after its language
+//! - Send to REPL (Insert Result) inserts the output below the current line
+//! - Send to REPL shows the code and its output in a REPL buffer
+//! - Languages without a REPL command report it instead of running anything
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::path::PathBuf;
+
+/// Create a harness with `sh` as the bash REPL and a script file open
+fn repl_harness(file_name: &str, content: &str) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.languages.get_mut("bash").unwrap().repl = Some("sh".to_string());
+    let mut harness =
+        EditorTestHarness::create(100, 24, HarnessOptions::new().with_config(config)).unwrap();
+
+    let temp_dir: PathBuf = harness.project_dir().unwrap().parent().unwrap().into();
+    let file_path = temp_dir.join(file_name);
+    std::fs::write(&file_path, content).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+#[test]
+fn test_new_scratch_buffer() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("New Scratch Buffer").unwrap();
+    harness.assert_screen_contains("Scratch buffer language:");
+
+    // Plain Text is preselected
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*scratch: Plain Text*");
+}
+
+#[test]
+fn test_send_to_repl_inserts_result() {
+    let mut harness = repl_harness("calc.sh", "echo $((6 * 7))\necho done\n");
+
+    harness.run_command("Send to REPL (Insert Result)").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "echo $((6 * 7))\n42\necho done\n"
+    );
+}
+
+#[test]
+fn test_send_selection_to_repl_buffer() {
+    let mut harness = repl_harness("greet.sh", "echo hello\necho world\n");
+
+    // Select both lines
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        .unwrap();
+    harness.run_command("Send to REPL").unwrap();
+
+    harness.assert_screen_contains("*REPL: bash*");
+    harness.assert_screen_contains("> echo world");
+    harness.assert_screen_contains("world");
+    // The source buffer is left unchanged and keeps focus
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "echo hello\necho world\n"
+    );
+}
+
+#[test]
+fn test_send_to_repl_without_repl_command() {
+    let mut harness = repl_harness("notes.txt", "hello\n");
+
+    harness.run_command("Send to REPL").unwrap();
+    harness.assert_screen_contains("No REPL configured for text");
+}
@@ -2229,3 +2229,41 @@ fn test_ctrl_d_at_word_end() {
         "Ctrl+D at word end should select entire 'word'"
     );
 }
+
+/// Test that Copy as ANSI copies the selection with the highlighter's colors
+#[test]
+fn test_copy_selection_as_ansi() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("main.rs");
+    std::fs::write(&file, "fn main() {\n    let x = 1;\n}\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Copy as ANSI").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    let copied = harness.editor_mut().clipboard_content_for_test();
+    assert!(copied.contains('\x1b'), "no colors in {copied:?}");
+
+    // Without the escapes it is the selected text
+    let mut plain = String::new();
+    let mut chars = copied.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(ch);
+        }
+    }
+    assert_eq!(plain, "fn main() {\n    let x = 1;\n}\n");
+}
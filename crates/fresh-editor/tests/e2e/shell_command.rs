@@ -5,6 +5,7 @@
 //! - Output to new buffer vs replace mode
 //! - Selection vs entire buffer
 //! - Command failure handling
+//! - Replace mode running as an asynchronous filter
 
 use crate::common::harness::EditorTestHarness;
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -87,8 +88,10 @@ fn test_shell_command_replace_buffer() {
         .unwrap();
     harness.wait_for_prompt_closed().unwrap();
 
-    // Buffer should be replaced with sorted content
-    harness.assert_buffer_content("apple\nbanana\ncherry\n");
+    // Buffer should be replaced with sorted content once the filter finishes
+    harness
+        .wait_for_buffer_content("apple\nbanana\ncherry\n")
+        .unwrap();
 
     // Should still be in the same buffer (not a new one)
     harness.assert_screen_contains("unsorted.txt");
@@ -219,7 +222,7 @@ fn test_shell_command_tr_transform() {
     harness.wait_for_prompt_closed().unwrap();
 
     // Content should be uppercase
-    harness.assert_buffer_content("HELLO WORLD\n");
+    harness.wait_for_buffer_content("HELLO WORLD\n").unwrap();
 }
 
 /// Test shell command undo after replace
@@ -258,7 +261,7 @@ fn test_shell_command_replace_undo() {
     harness.wait_for_prompt_closed().unwrap();
 
     // Verify replaced content
-    harness.assert_buffer_content("ORIGINAL CONTENT");
+    harness.wait_for_buffer_content("ORIGINAL CONTENT").unwrap();
 
     // Undo should restore original content (atomic undo via Event::Batch)
     harness
@@ -394,7 +397,9 @@ fn test_shell_command_replace_preserves_cursor_position() {
     harness.wait_for_prompt_closed().unwrap();
 
     // Verify content is uppercase
-    harness.assert_buffer_content("HELLO WORLD\nFOO BAR\nBAZ QUX\n");
+    harness
+        .wait_for_buffer_content("HELLO WORLD\nFOO BAR\nBAZ QUX\n")
+        .unwrap();
 
     // Verify cursor is at same position as before
     let cursor_pos_after = harness.editor().active_state().cursors.primary().position;
@@ -474,3 +479,75 @@ fn test_shell_command_replace_clamps_cursor_when_buffer_shrinks() {
         "Cursor should be clamped to new buffer length"
     );
 }
+
+/// Run the "Shell Command (Replace)" filter with `command`
+fn run_filter(harness: &mut EditorTestHarness, command: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("shell command (replace)").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text(command).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_prompt_closed().unwrap();
+}
+
+/// Test filtering a shift-selection replaces only the selected lines, undoable in one step
+#[test]
+#[cfg_attr(not(unix), ignore = "Shell commands require Unix-like environment")]
+fn test_shell_filter_selection_single_undo() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("mixed.txt");
+    std::fs::write(&file_path, "header\ncherry\napple\nbanana\nfooter\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Select the three fruit lines
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    for _ in 0..3 {
+        harness
+            .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+            .unwrap();
+    }
+
+    run_filter(&mut harness, "sort");
+    harness
+        .wait_for_buffer_content("header\napple\nbanana\ncherry\nfooter\n")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("header\ncherry\napple\nbanana\nfooter\n");
+}
+
+/// Test a failing filter leaves the buffer untouched and shows stderr in a popup
+#[test]
+#[cfg_attr(not(unix), ignore = "Shell commands require Unix-like environment")]
+fn test_shell_filter_failure_shows_stderr_popup() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "some content\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    run_filter(
+        &mut harness,
+        "echo first problem >&2; echo second problem >&2; exit 3",
+    );
+    harness.wait_for_screen_contains("Filter failed").unwrap();
+
+    harness.assert_screen_contains("first problem");
+    harness.assert_screen_contains("second problem");
+    harness.assert_buffer_content("some content\n");
+}
@@ -399,3 +399,71 @@ fn test_sort_lines_with_empty_lines() {
         "Empty lines should sort to the beginning"
     );
 }
+
+/// Test that without a selection the whole buffer is transformed
+#[test]
+fn test_line_operations_whole_buffer() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("b\n10 x\na\n2 y\na\n").unwrap();
+
+    harness.run_command("Sort Lines Descending").unwrap();
+    harness.assert_buffer_content("b\na\na\n2 y\n10 x\n");
+
+    harness.run_command("Sort Lines Numerically").unwrap();
+    harness.assert_buffer_content("b\na\na\n2 y\n10 x\n");
+
+    harness.run_command("Remove Duplicate Lines").unwrap();
+    harness.assert_buffer_content("b\na\n2 y\n10 x\n");
+
+    harness.run_command("Reverse Lines").unwrap();
+    harness.assert_buffer_content("10 x\n2 y\na\nb\n");
+
+    // Each command was a single undo step
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("b\na\n2 y\n10 x\n");
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("b\na\na\n2 y\n10 x\n");
+}
+
+/// Test that a selection is widened to the whole lines it touches
+#[test]
+fn test_line_operations_widen_selection_to_lines() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("keep\ncc\nbb\naa\nlast").unwrap();
+
+    // Select from the middle of "cc" to the middle of "aa"
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+
+    harness.run_command("Reverse Lines").unwrap();
+    harness.assert_buffer_content("keep\naa\nbb\ncc\nlast");
+}
+
+/// Test that shuffling keeps every line
+#[test]
+fn test_shuffle_lines_keeps_lines() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    let text: String = (0..30).map(|i| format!("line {i}\n")).collect();
+    harness.type_text(&text).unwrap();
+
+    harness.run_command("Shuffle Lines").unwrap();
+    let shuffled = harness.get_buffer_content().unwrap();
+    let mut lines: Vec<&str> = shuffled.lines().collect();
+    lines.sort_by_key(|line| line[5..].parse::<u32>().unwrap());
+    assert_eq!(lines.join("\n") + "\n", text);
+}
@@ -0,0 +1,115 @@
+//! Tests for spell checking
+//!
+//! Tests that:
+//! - Misspelled words are underlined, known words are not
+//! - Only comments and strings are checked in source code
+//! - The suggestions popup replaces the word with the chosen correction
+//! - Words added to the personal dictionary are no longer underlined
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::services::spell::spell_namespace;
+use std::path::PathBuf;
+
+/// Create a harness with spell checking on and a small test dictionary
+fn spell_harness(file_name: &str, content: &str) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.spell_check = true;
+    config.editor.spell_language = "xx_TEST".to_string();
+    // Wide enough for the spell check status messages
+    let mut harness =
+        EditorTestHarness::create(120, 24, HarnessOptions::new().with_config(config)).unwrap();
+
+    // The harness's DirectoryContext keeps the config dir next to the project root
+    let temp_dir: PathBuf = harness.project_dir().unwrap().parent().unwrap().into();
+    let dictionaries_dir = temp_dir.join("config").join("dictionaries");
+    std::fs::create_dir_all(&dictionaries_dir).unwrap();
+    std::fs::write(
+        dictionaries_dir.join("xx_TEST.aff"),
+        "SET UTF-8\nTRY esianrtolcdugmphbyfvkwz\n\nSFX S Y 1\nSFX S 0 s .\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dictionaries_dir.join("xx_TEST.dic"),
+        "5\nhello\nworld/S\nthe\nfn\nlet\n",
+    )
+    .unwrap();
+
+    let file_path = temp_dir.join(file_name);
+    std::fs::write(&file_path, content).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// The text of every misspelling overlay in the active buffer
+fn underlined_words(harness: &EditorTestHarness) -> Vec<String> {
+    let state = harness.editor().active_state();
+    let content = harness.get_buffer_content().unwrap();
+    let mut words: Vec<(usize, String)> = state
+        .overlays
+        .all()
+        .iter()
+        .filter(|overlay| overlay.namespace.as_ref() == Some(&spell_namespace()))
+        .map(|overlay| {
+            let range = overlay.range(&state.marker_list);
+            (range.start, content[range].to_string())
+        })
+        .collect();
+    words.sort();
+    words.into_iter().map(|(_, word)| word).collect()
+}
+
+#[test]
+fn test_misspelled_words_are_underlined() {
+    let harness = spell_harness("notes.txt", "Hello wrold, the worlds helo\n");
+    assert_eq!(underlined_words(&harness), vec!["wrold", "helo"]);
+}
+
+#[test]
+fn test_only_comments_and_strings_are_checked_in_code() {
+    let harness = spell_harness(
+        "main.rs",
+        "// hello wrold\nfn mian() {\n    let s = \"helo\";\n}\n",
+    );
+    assert_eq!(underlined_words(&harness), vec!["wrold", "helo"]);
+}
+
+#[test]
+fn test_toggle_spell_check_removes_underlines() {
+    let mut harness = spell_harness("notes.txt", "hello wrold\n");
+    assert_eq!(underlined_words(&harness), vec!["wrold"]);
+
+    harness.run_command("Toggle Spell Check").unwrap();
+    assert!(underlined_words(&harness).is_empty());
+}
+
+#[test]
+fn test_suggestion_replaces_word() {
+    let mut harness = spell_harness("notes.txt", "hello wrold\n");
+
+    // Move the cursor onto "wrold"
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.run_command("Spelling Suggestions").unwrap();
+    harness.assert_screen_contains("Suggestions for 'wrold'");
+    harness.assert_screen_contains("world");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello world\n");
+    assert!(underlined_words(&harness).is_empty());
+}
+
+#[test]
+fn test_add_word_to_personal_dictionary() {
+    let mut harness = spell_harness("notes.txt", "hello fresh\n");
+    assert_eq!(underlined_words(&harness), vec!["fresh"]);
+
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.run_command("Add Word to Dictionary").unwrap();
+    harness.assert_screen_contains("Added 'fresh' to personal dictionary");
+    assert!(underlined_words(&harness).is_empty());
+}
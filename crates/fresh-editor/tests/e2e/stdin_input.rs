@@ -1,4 +1,4 @@
-use crate::common::harness::EditorTestHarness;
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use std::io::Write;
 use tempfile::NamedTempFile;
 
@@ -255,3 +255,51 @@ fn test_stdin_large_file_lazy_loading() {
     harness.render().unwrap();
     harness.assert_screen_contains("[stdin]");
 }
+
+/// Test that stdin's language is guessed from a shebang line
+#[test]
+fn test_stdin_language_from_shebang() {
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_full_grammars()).unwrap();
+
+    let temp_file = create_stdin_temp_file("#!/usr/bin/env python3\nprint('hi')\n");
+
+    harness
+        .editor_mut()
+        .open_stdin_buffer(temp_file.path(), None)
+        .unwrap();
+
+    assert_eq!(harness.editor().active_state().language, "python");
+}
+
+/// Test setting stdin's language explicitly, as `--language json` does
+#[test]
+fn test_stdin_language_set_explicitly() {
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_full_grammars()).unwrap();
+
+    let temp_file = create_stdin_temp_file("{\"key\": [1, 2, 3]}\n");
+
+    let buffer_id = harness
+        .editor_mut()
+        .open_stdin_buffer(temp_file.path(), None)
+        .unwrap();
+    assert_eq!(harness.editor().active_state().language, "text");
+
+    let name = harness.editor_mut().set_buffer_language(buffer_id, "json");
+    assert_eq!(name.as_deref(), Some("JSON"));
+    assert_eq!(harness.editor().active_state().language, "json");
+
+    // File extensions work too
+    assert_eq!(
+        harness
+            .editor_mut()
+            .set_buffer_language(buffer_id, "rs")
+            .as_deref(),
+        Some("Rust")
+    );
+    assert_eq!(
+        harness.editor_mut().set_buffer_language(buffer_id, "nope"),
+        None
+    );
+}
@@ -1,6 +1,8 @@
 //! Tests for per-language tab configuration options:
 //! - `show_whitespace_tabs`: Whether to display tab indicators (→) in the editor
 //! - `use_tabs`: Whether pressing Tab inserts a tab character or spaces
+//! - `detect_indentation`: Whether opened files use the indentation found in them
+//! - `modelines`: Whether opened files use the indentation their modelines give
 
 use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -669,3 +671,138 @@ fn test_issue_384_indent_respects_tab_size_in_calculation() {
         visual_indent
     );
 }
+
+// =============================================================================
+// Indentation Detection Tests
+// =============================================================================
+// These use a wide terminal: the status bar drops the indentation indicator
+// when it would truncate the status message.
+
+/// Test that the indentation detected in an opened file overrides the config
+#[test]
+fn test_detected_indentation_overrides_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "a\n  b\n    c\n  d\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(160, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 2");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(
+        content, "a\n  b\n    c\n  d\n  ",
+        "Tab should insert 2 spaces"
+    );
+}
+
+/// Test that detection can be turned off with `detect_indentation`
+#[test]
+fn test_detect_indentation_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "a\n  b\n    c\n  d\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.detect_indentation = false;
+    let mut harness = EditorTestHarness::with_config(160, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 4");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "a\n  b\n    c\n  d\n    ");
+}
+
+/// Test that "Set Indentation" overrides the detected indentation
+#[test]
+fn test_set_indentation_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "a\n  b\n    c\n  d\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(160, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Set Indentation").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    // The detected indentation is marked
+    harness.assert_screen_contains("detected");
+
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Indent with Tabs").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Tab Size: 2");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "a\n  b\n    c\n  d\n\t");
+}
+
+// =============================================================================
+// Modeline Tests
+// =============================================================================
+
+/// Test that a vim modeline's indentation wins over the detected one
+#[test]
+fn test_modeline_overrides_detected_indentation() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(&file_path, "a\n  b\n    c\n# vim: set ts=3 et :\n").unwrap();
+
+    let mut harness = EditorTestHarness::with_config(160, 24, Config::default()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 3");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "a\n  b\n    c\n# vim: set ts=3 et :\n   ");
+}
+
+/// Test that modelines are ignored with `modelines` off
+#[test]
+fn test_modelines_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("test.txt");
+    std::fs::write(
+        &file_path,
+        "a\n  b\n    c\n# vim: set ts=3 et ft=python :\n",
+    )
+    .unwrap();
+
+    let mut config = Config::default();
+    config.editor.modelines = false;
+    let mut harness = EditorTestHarness::with_config(160, 24, config).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Spaces: 2");
+    assert_ne!(harness.editor().active_state().language, "python");
+}
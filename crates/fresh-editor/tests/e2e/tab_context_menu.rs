@@ -0,0 +1,98 @@
+//! E2E tests for the tab context menu and pinned tabs
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use fresh::model::event::BufferId;
+use tempfile::TempDir;
+
+/// Index of the "Close Others" menu item
+const CLOSE_OTHERS: u16 = 1;
+/// Index of the "Pin" menu item
+const PIN: u16 = 5;
+/// Index of the "Copy Path" menu item
+const COPY_PATH: u16 = 7;
+
+/// A harness with file1.txt, file2.txt and file3.txt opened in that order
+fn harness_with_files() -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    // Files in the working directory are named without their directory
+    let mut harness =
+        EditorTestHarness::with_working_dir(100, 30, temp_dir.path().to_path_buf()).unwrap();
+    for i in 1..=3 {
+        let path = temp_dir.path().join(format!("file{}.txt", i));
+        std::fs::write(&path, format!("Content of file {}", i)).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Names of the tabs of the active split, in order
+fn tab_names(harness: &EditorTestHarness) -> Vec<String> {
+    let tabs: Vec<BufferId> = harness
+        .editor()
+        .get_tab_layouts()
+        .values()
+        .flat_map(|layout| layout.tabs.iter().map(|tab| tab.buffer_id))
+        .collect();
+    tabs.into_iter()
+        .map(|id| harness.editor().get_buffer_display_name(id))
+        .collect()
+}
+
+/// Right-click the tab named `name` and pick the menu item at `item`
+fn tab_menu(harness: &mut EditorTestHarness, name: &str, item: u16) {
+    let area = harness
+        .editor()
+        .get_tab_layouts()
+        .values()
+        .flat_map(|layout| layout.tabs.iter())
+        .find(|tab| harness.editor().get_buffer_display_name(tab.buffer_id) == name)
+        .map(|tab| tab.tab_area)
+        .unwrap_or_else(|| panic!("no tab named {}", name));
+    let (col, row) = (area.x + 1, area.y);
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: col,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    // The menu opens below the pointer, inside a border
+    harness.mouse_click(col + 1, row + 2 + item).unwrap();
+}
+
+/// Pinning a tab moves it first and keeps it open through "Close Others"
+#[test]
+fn test_pinned_tab_survives_close_others() {
+    let (mut harness, _temp_dir) = harness_with_files();
+    assert_eq!(tab_names(&harness), ["file1.txt", "file2.txt", "file3.txt"]);
+
+    tab_menu(&mut harness, "file3.txt", PIN);
+    harness.assert_screen_contains("Pinned file3.txt");
+    harness.assert_screen_contains("⚑");
+    assert_eq!(tab_names(&harness), ["file3.txt", "file1.txt", "file2.txt"]);
+
+    tab_menu(&mut harness, "file2.txt", CLOSE_OTHERS);
+    assert_eq!(tab_names(&harness), ["file3.txt", "file2.txt"]);
+
+    // The menu offers to unpin a pinned tab
+    tab_menu(&mut harness, "file3.txt", PIN);
+    harness.assert_screen_contains("Unpinned file3.txt");
+    harness.assert_screen_not_contains("⚑");
+}
+
+/// "Copy Path" puts the tab's file path on the clipboard
+#[test]
+fn test_copy_tab_path() {
+    let (mut harness, temp_dir) = harness_with_files();
+
+    tab_menu(&mut harness, "file2.txt", COPY_PATH);
+    let path = temp_dir.path().join("file2.txt");
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        path.display().to_string()
+    );
+}
@@ -0,0 +1,59 @@
+//! Tests for navigating with a ctags file
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+const TAGS: &str = "!_TAG_FILE_SORTED\t1\t//\n\
+                    alpha\tlib.c\t/^int alpha(void)$/;\"\tf\n\
+                    beta\tlib.c\t/^int beta(void)$/;\"\tf\n";
+const LIB: &str = "// lib\nint alpha(void)\n{\n}\n\nint beta(void)\n{\n}\n";
+const MAIN: &str = "int main() { return alpha(); }\n";
+
+fn harness_with_tags() -> EditorTestHarness {
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(100, 24, Config::default()).unwrap();
+    let dir = harness.project_dir().unwrap();
+    std::fs::write(dir.join("tags"), TAGS).unwrap();
+    std::fs::write(dir.join("lib.c"), LIB).unwrap();
+    std::fs::write(dir.join("main.c"), MAIN).unwrap();
+    harness.open_file(&dir.join("main.c")).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
+/// Go to Tag opens the definition of the word at the cursor, and Pop Tag
+/// comes back
+#[test]
+fn test_goto_tag_and_pop_tag() {
+    let mut harness = harness_with_tags();
+    let call = MAIN.find("alpha").unwrap() + 2;
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in call..MAIN.trim_end().len() {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+    assert_eq!(harness.cursor_position(), call);
+
+    harness.run_command("Go to Tag").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), LIB);
+    assert_eq!(harness.cursor_position(), LIB.find("alpha").unwrap());
+
+    harness.run_command("Pop Tag").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), MAIN);
+    assert_eq!(harness.cursor_position(), call);
+}
+
+/// Find Tag lists every tag to pick from
+#[test]
+fn test_find_tag_picker() {
+    let mut harness = harness_with_tags();
+    harness.run_command("Find Tag").unwrap();
+    harness.assert_screen_contains("lib.c");
+    harness.type_text("beta").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), LIB);
+    assert_eq!(harness.cursor_position(), LIB.find("beta").unwrap());
+}
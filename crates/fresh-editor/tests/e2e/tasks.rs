@@ -0,0 +1,71 @@
+//! E2E tests for the project tasks in `.fresh/tasks.toml`
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// A `build` task whose output points at line 2, column 3 of src/a.c
+fn write_project(harness: &EditorTestHarness) {
+    let project_dir = harness.project_dir().unwrap();
+    fs::create_dir_all(project_dir.join("src")).unwrap();
+    fs::write(project_dir.join("src/a.c"), "int a;\nint b;\n").unwrap();
+    fs::create_dir_all(project_dir.join(".fresh")).unwrap();
+    fs::write(
+        project_dir.join(".fresh/tasks.toml"),
+        r#"
+[tasks.build]
+command = "echo compiling; echo 'src/a.c:2:3: error: boom'; exit 2"
+problem_matcher = "gcc"
+"#,
+    )
+    .unwrap();
+}
+
+/// A task is a palette command; its output follows in a buffer where Enter
+/// on a problem goes to its location
+#[test]
+fn test_run_task_and_go_to_problem() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    write_project(&harness);
+
+    harness.run_command("Task: build").unwrap();
+    harness
+        .wait_for_screen_contains("Task build failed with exit code 2")
+        .unwrap();
+    harness.assert_screen_contains("$ echo compiling");
+    harness.assert_screen_contains("src/a.c:2:3: error: boom");
+
+    // The output starts below the command and the hint
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().as_deref(),
+        Some("int a;\nint b;\n")
+    );
+    assert_eq!(harness.cursor_position(), 9);
+    harness.assert_screen_contains("boom");
+}
+
+/// Tasks don't run until the workspace is trusted
+#[test]
+fn test_task_does_not_run_in_safe_mode() {
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_project_root()
+            .with_untrusted_workspace(),
+    )
+    .unwrap();
+    write_project(&harness);
+
+    harness.run_command("Task: build").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Tasks don't run in safe mode");
+    harness.assert_screen_not_contains("compiling");
+}
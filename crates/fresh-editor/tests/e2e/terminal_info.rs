@@ -0,0 +1,80 @@
+//! E2E tests for the Terminal Info buffer
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use fresh::services::terminal_capabilities::TerminalCapabilities;
+use fresh::view::color_support::ColorCapability;
+
+/// Terminal Info lists the detected capabilities and the fallbacks in use
+#[test]
+fn test_show_terminal_info() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(TerminalCapabilities {
+            name: Some("WezTerm 20240203".to_string()),
+            term: Some("xterm-256color".to_string()),
+            color: ColorCapability::TrueColor,
+            keyboard_enhancement: Some(true),
+            synchronized_output: Some(false),
+            sgr_mouse: Some(true),
+            clipboard: None,
+            kitty_graphics: Some(false),
+            sixel: Some(true),
+        });
+    harness.editor_mut().show_terminal_info();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("WezTerm 20240203");
+    harness.assert_screen_contains("24-bit (true color)");
+    harness.assert_screen_contains("Synchronized output:     no - frames are drawn");
+    harness.assert_screen_contains("Kitty keyboard protocol: yes");
+    harness.assert_screen_contains("Clipboard (OSC 52):      unknown");
+    harness.assert_screen_contains("Images:                  yes (sixel)");
+
+    // Showing it again reuses the buffer
+    let first = harness.editor().active_buffer();
+    harness.editor_mut().show_terminal_info();
+    assert_eq!(harness.editor().active_buffer(), first);
+    harness.type_text("X").unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("Terminal"));
+}
+
+/// Frames are synchronized only when the terminal supports it and the config
+/// allows it
+#[test]
+fn test_synchronized_output_follows_terminal_and_config() {
+    let capabilities = |synchronized_output| TerminalCapabilities {
+        name: None,
+        term: None,
+        color: ColorCapability::TrueColor,
+        keyboard_enhancement: None,
+        synchronized_output,
+        sgr_mouse: None,
+        clipboard: None,
+        kitty_graphics: None,
+        sixel: None,
+    };
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    assert!(!harness.editor().synchronized_output());
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(capabilities(Some(true)));
+    assert!(harness.editor().synchronized_output());
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(capabilities(None));
+    assert!(!harness.editor().synchronized_output());
+
+    let mut config = Config::default();
+    config.editor.synchronized_output = false;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness
+        .editor_mut()
+        .set_terminal_capabilities(capabilities(Some(true)));
+    assert!(!harness.editor().synchronized_output());
+}
@@ -0,0 +1,26 @@
+//! E2E tests for the terminal window title and progress reporting
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::shell_integration::TerminalProgress;
+use std::fs;
+
+/// The title follows the active buffer and its modified state
+#[test]
+fn test_terminal_title_follows_active_buffer() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let first = project_dir.join("first.txt");
+    let second = project_dir.join("second.txt");
+    fs::write(&first, "one").unwrap();
+    fs::write(&second, "two").unwrap();
+
+    harness.open_file(&first).unwrap();
+    assert_eq!(harness.editor().terminal_title(), "first.txt - Fresh");
+
+    harness.type_text("X").unwrap();
+    assert_eq!(harness.editor().terminal_title(), "first.txt* - Fresh");
+
+    harness.open_file(&second).unwrap();
+    assert_eq!(harness.editor().terminal_title(), "second.txt - Fresh");
+    assert_eq!(harness.editor().terminal_progress(), TerminalProgress::Idle);
+}
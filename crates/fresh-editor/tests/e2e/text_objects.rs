@@ -0,0 +1,145 @@
+//! Tests for the Select Inside / Select Around text object commands
+//!
+//! Tests that:
+//! - Brackets, quotes and tags are selected with or without their delimiters
+//! - Repeating a command grows the selection to the enclosing object
+//! - Functions and arguments come from the syntax tree
+//! - With nothing to select, a following delete leaves the buffer alone
+//! - Arguments can be walked through, swapped with a neighbour and added
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Open `content` as `file_name` with the cursor `left` characters before
+/// the end of the first line
+fn open_at(file_name: &str, content: &str, left: usize) -> (EditorTestHarness, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join(file_name);
+    std::fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    for _ in 0..left {
+        harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    }
+    (harness, temp_dir)
+}
+
+#[test]
+fn test_select_brackets() {
+    let (mut harness, _temp_dir) = open_at("call.txt", "call(a, [b, c])", 3);
+
+    harness.run_command("Select Inside Brackets").unwrap();
+    assert_eq!(harness.get_selected_text(), "b, c");
+
+    // Again selects inside the enclosing pair
+    harness.run_command("Select Inside Brackets").unwrap();
+    assert_eq!(harness.get_selected_text(), "a, [b, c]");
+
+    harness.run_command("Select Around Brackets").unwrap();
+    assert_eq!(harness.get_selected_text(), "(a, [b, c])");
+
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("call");
+}
+
+#[test]
+fn test_select_quotes_and_tags() {
+    let (mut harness, _temp_dir) = open_at(
+        "page.html",
+        "<p title=\"greeting\">Hello <b>world</b></p>",
+        10,
+    );
+
+    harness.run_command("Select Inside Tag").unwrap();
+    assert_eq!(harness.get_selected_text(), "world");
+    harness.run_command("Select Around Tag").unwrap();
+    assert_eq!(harness.get_selected_text(), "<b>world</b>");
+    harness.run_command("Select Inside Tag").unwrap();
+    assert_eq!(harness.get_selected_text(), "Hello <b>world</b>");
+
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness.run_command("Select Inside Quotes").unwrap();
+    assert_eq!(harness.get_selected_text(), "greeting");
+}
+
+#[test]
+fn test_select_function_and_argument() {
+    let (mut harness, _temp_dir) = open_at("main.rs", "fn main() { let x = add(1, 2); }\n", 5);
+
+    harness.run_command("Select Inside Argument").unwrap();
+    assert_eq!(harness.get_selected_text(), "2");
+    harness.run_command("Select Around Argument").unwrap();
+    assert_eq!(harness.get_selected_text(), ", 2");
+
+    harness.run_command("Select Inside Function").unwrap();
+    assert_eq!(harness.get_selected_text(), " let x = add(1, 2); ");
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("fn main() {}\n");
+
+    harness.run_command("Select Around Function").unwrap();
+    assert_eq!(harness.get_selected_text(), "fn main() {}");
+}
+
+#[test]
+fn test_argument_navigation() {
+    let (mut harness, _temp_dir) = open_at("main.rs", "fn f() { g(a, h(b, c), d); }", 17);
+
+    harness.run_command("Go to Next Argument").unwrap();
+    assert_eq!(harness.cursor_position(), 14);
+    harness.run_command("Go to Next Argument").unwrap();
+    assert_eq!(harness.cursor_position(), 23);
+    harness.run_command("Go to Next Argument").unwrap();
+    assert_eq!(harness.cursor_position(), 23);
+    harness.run_command("Go to Previous Argument").unwrap();
+    assert_eq!(harness.cursor_position(), 14);
+    harness.run_command("Go to Previous Argument").unwrap();
+    assert_eq!(harness.cursor_position(), 11);
+}
+
+#[test]
+fn test_swap_and_add_argument() {
+    let (mut harness, _temp_dir) = open_at(
+        "main.rs",
+        "fn f(first: u8, second: &str, third: bool) {}",
+        40,
+    );
+
+    harness.run_command("Swap Argument with Next").unwrap();
+    harness.assert_buffer_content("fn f(second: &str, first: u8, third: bool) {}");
+    // The cursor follows the argument, so swapping again moves it further
+    harness.run_command("Swap Argument with Next").unwrap();
+    harness.assert_buffer_content("fn f(second: &str, third: bool, first: u8) {}");
+    harness.run_command("Swap Argument with Previous").unwrap();
+    harness.assert_buffer_content("fn f(second: &str, first: u8, third: bool) {}");
+
+    // One undo step per swap
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("fn f(second: &str, third: bool, first: u8) {}");
+
+    harness.run_command("Add Argument").unwrap();
+    harness.type_text("fourth: char").unwrap();
+    harness.assert_buffer_content("fn f(second: &str, third: bool, first: u8, fourth: char) {}");
+}
+
+#[test]
+fn test_nothing_to_select() {
+    let (mut harness, _temp_dir) = open_at("plain.txt", "plain text", 2);
+
+    harness.run_command("Select Inside Quotes").unwrap();
+    assert_eq!(harness.get_selected_text(), "");
+
+    // The empty selection makes delete a no-op, as for vi's `di"`
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("plain text");
+}
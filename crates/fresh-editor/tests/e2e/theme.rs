@@ -291,3 +291,55 @@ fn test_theme_with_underscore_variant() {
     // Should still load high-contrast theme (accepts both - and _)
     assert_eq!(theme.name, "high-contrast");
 }
+
+#[test]
+fn test_auto_appearance_follows_terminal_background() {
+    use fresh::config::{AppearanceConfig, AppearanceMode};
+    use fresh::view::theme::Appearance;
+
+    let config = Config {
+        theme: "high-contrast".into(),
+        appearance: AppearanceConfig {
+            mode: AppearanceMode::Auto,
+            light_theme: "light".into(),
+            dark_theme: "dark".into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    // Unknown background: `theme` is used
+    assert_eq!(harness.editor().theme().name, "high-contrast");
+
+    harness
+        .editor_mut()
+        .set_terminal_appearance(Some(Appearance::Light));
+    assert_eq!(harness.editor().theme().name, "light");
+
+    // The manual toggle overrides the detected background
+    harness.editor_mut().toggle_light_dark_theme();
+    assert_eq!(harness.editor().theme().name, "dark");
+    harness.editor_mut().toggle_light_dark_theme();
+    assert_eq!(harness.editor().theme().name, "light");
+}
+
+#[test]
+fn test_scheduled_appearance_selects_theme() {
+    use fresh::config::{AppearanceConfig, AppearanceMode};
+
+    // Equal switch times leave no light period, so this is dark at any time
+    let config = Config {
+        appearance: AppearanceConfig {
+            mode: AppearanceMode::Schedule,
+            dark_theme: "nostalgia".into(),
+            light_at: "00:00".to_string(),
+            dark_at: "00:00".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    assert_eq!(harness.editor().theme().name, "nostalgia");
+}
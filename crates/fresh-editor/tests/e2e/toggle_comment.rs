@@ -2,7 +2,9 @@
 //!
 //! Tests that:
 //! - Toggle comment uses language-specific comment prefixes from config
+//! - Languages without a line comment prefix fall back to block comment tokens
 //! - Selection is preserved after commenting/uncommenting
+//! - A language set for the buffer (with Set Language or a modeline) picks the prefix
 
 use crate::common::harness::{EditorTestHarness, HarnessOptions};
 use crossterm::event::{KeyCode, KeyModifiers};
@@ -390,3 +392,84 @@ fn test_toggle_comment_selection_at_buffer_end() {
         content
     );
 }
+
+/// Test that languages without a line comment prefix use their block comment tokens
+#[test]
+fn test_toggle_comment_markdown_block_tokens() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("notes.md");
+    std::fs::write(&file_path, "# Title\nSome text\n").unwrap();
+
+    let config = Config::default();
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_config(config)).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    // Select all and comment
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Toggle Comment");
+
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content.starts_with("<!-- # Title -->\n<!-- Some text -->\n"),
+        "Markdown lines should be wrapped in block comments. Got: {:?}",
+        content
+    );
+
+    // Toggling again removes the tokens
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    run_command(&mut harness, "Toggle Comment");
+
+    let content = harness.get_buffer_content().unwrap();
+    assert_eq!(content, "# Title\nSome text\n");
+}
+
+/// Test that Set Language changes the comment prefix of a buffer whatever
+/// its extension
+#[test]
+fn test_toggle_comment_after_set_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("script.txt");
+    std::fs::write(&file_path, "x = 1\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_full_grammars()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    run_command(&mut harness, "Set Language");
+    harness.type_text("Python").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_state().language, "python");
+
+    run_command(&mut harness, "Toggle Comment");
+    assert_eq!(harness.get_buffer_content().unwrap(), "# x = 1\n");
+}
+
+/// Test that a vim modeline sets the language of a file when it's opened
+#[test]
+fn test_toggle_comment_modeline_language() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("build.txt");
+    std::fs::write(&file_path, "x = 1\n// vim: ft=python\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_full_grammars()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_state().language, "python");
+
+    run_command(&mut harness, "Toggle Comment");
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .starts_with("# x = 1\n"));
+}
@@ -0,0 +1,60 @@
+//! E2E tests for the Tutorial buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Lessons are checked off as their tasks are done, by editing the buffer or
+/// by running commands, and the edits never count as unsaved changes
+#[test]
+fn test_tutorial_lessons_are_checked_off() {
+    let mut harness = EditorTestHarness::new(100, 40).unwrap();
+    let tutorial = harness.editor_mut().show_tutorial();
+    harness.render().unwrap();
+    harness.assert_screen_contains("*Tutorial*");
+    harness.assert_screen_contains("Lesson 1: Editing");
+    harness.assert_screen_not_contains("✓");
+
+    // Lesson 1: delete the repeated "the " on line 22
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Down, KeyModifiers::NONE, 21)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 6)
+        .unwrap();
+    harness
+        .send_key_repeat(KeyCode::Delete, KeyModifiers::NONE, 4)
+        .unwrap();
+    harness.process_async_and_render().unwrap();
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("> The quick brown fox jumps over the lazy dog.\n"));
+    harness.assert_screen_contains("Tutorial: lesson done (1/5)");
+    assert!(!harness.editor().active_state().buffer.is_modified());
+
+    // Lesson 2: run Toggle Line Numbers from the command palette
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Toggle Line Numbers").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.process_async_and_render().unwrap();
+    harness.assert_screen_contains("Tutorial: lesson done (2/5)");
+
+    // Scroll back up to see the check marks
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Lesson 1: Editing ✓");
+    harness.assert_screen_contains("Lesson 2: The command palette ✓");
+
+    // Showing it again goes back to the same buffer
+    harness.editor_mut().show_tutorial();
+    assert_eq!(harness.editor().active_buffer(), tutorial);
+}
@@ -1118,3 +1118,45 @@ fn test_left_arrow_at_long_position_file_loaded() {
          If this is 51, the bug is present: left arrow fell back to code point movement."
     );
 }
+
+/// Test that a double-width character cut in half by horizontal scrolling
+/// keeps the rest of the line in its columns, both on screen and for clicks
+#[test]
+fn test_wide_char_cut_by_horizontal_scroll() {
+    use crossterm::event::{MouseEvent, MouseEventKind};
+
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("wide.txt");
+    std::fs::write(&file, format!("你好abc{}\n", "x".repeat(200))).unwrap();
+
+    let mut config = fresh::config::Config::default();
+    config.editor.line_wrap = false;
+    config.editor.mouse_scroll_columns = 1;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let row = harness.content_area_rows().0 as u16;
+    let a_x = (0..80)
+        .find(|&x| harness.get_cell(x, row).as_deref() == Some("a"))
+        .unwrap();
+    let text_x = a_x - 4;
+
+    // Scroll one column right, cutting 你 in half
+    harness
+        .send_mouse(MouseEvent {
+            kind: MouseEventKind::ScrollRight,
+            column: 40,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().active_viewport().left_column, 1);
+    assert_eq!(harness.get_cell(text_x + 1, row).as_deref(), Some("好"));
+    assert_eq!(harness.get_cell(text_x + 3, row).as_deref(), Some("a"));
+
+    // Clicking the right half of 好 puts the cursor on it
+    harness.mouse_click(text_x + 2, row).unwrap();
+    assert_eq!(harness.cursor_position(), "你".len());
+}
@@ -189,6 +189,30 @@ fn test_vi_word_navigation() {
     harness.assert_buffer_content("hello world test\n");
 }
 
+/// Test that a burst of queued keys in normal mode runs as commands
+/// Holding 'j' queues up keys like a paste does, but they must not be pasted
+#[test]
+fn test_vi_key_burst_is_not_pasted() {
+    let (mut harness, _temp_dir) = vi_mode_harness(80, 24);
+
+    let content = "a\n".repeat(20);
+    let fixture = TestFixture::new("test.txt", &content).unwrap();
+    harness.open_file(&fixture.path).unwrap();
+    harness.render().unwrap();
+
+    enable_vi_mode(&mut harness);
+
+    harness.send_typed_burst(&"j".repeat(16)).unwrap();
+    harness.wait_until(|h| h.cursor_position() == 32).unwrap();
+
+    // A short burst with a line break isn't pasted either: Enter is unbound
+    // in normal mode, so only 'j' does anything
+    harness.send_typed_burst("j\n").unwrap();
+    harness.wait_until(|h| h.cursor_position() == 34).unwrap();
+
+    harness.assert_buffer_content(&content);
+}
+
 // =============================================================================
 // Mode Switching Tests
 // =============================================================================
@@ -0,0 +1,218 @@
+//! Tests for rulers and whitespace highlighting
+//!
+//! Tests that:
+//! - Rulers tint the configured column, measured from the start of the content
+//! - A language's rulers replace the editor's rulers
+//! - Trailing whitespace is highlighted, except right before the cursor
+//! - Leading whitespace mixing tabs and spaces is highlighted
+//! - The cursor line and column are tinted without hiding selections
+//! - Characters past the maximum line length are highlighted, per language
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, LanguageConfig};
+use tempfile::TempDir;
+
+/// Screen column and row of `text`; the gutter's `│` is wider in bytes than on screen
+fn find_on_screen(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    let (offset, y) = harness.find_text_on_screen(text).unwrap();
+    let row = harness.get_row_text(y);
+    (row[..offset as usize].chars().count() as u16, y)
+}
+
+#[test]
+fn test_ruler_column() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("ruler.txt");
+    std::fs::write(&file, "short line\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.rulers = vec![20];
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let ruler_bg = harness.editor().theme().ruler_bg;
+    let (x, y) = find_on_screen(&harness, "short line");
+    assert_eq!(harness.get_cell_bg(x + 20, y), Some(ruler_bg));
+    assert_ne!(harness.get_cell_bg(x + 19, y), Some(ruler_bg));
+    assert_ne!(harness.get_cell_bg(x + 21, y), Some(ruler_bg));
+}
+
+#[test]
+fn test_language_rulers_override_editor_rulers() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("main.py");
+    std::fs::write(&file, "import os\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.rulers = vec![20];
+    let python = config
+        .languages
+        .entry("python".to_string())
+        .or_insert_with(LanguageConfig::default);
+    python.rulers = Some(vec![30]);
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let ruler_bg = harness.editor().theme().ruler_bg;
+    let (x, y) = find_on_screen(&harness, "import os");
+    assert_eq!(harness.get_cell_bg(x + 30, y), Some(ruler_bg));
+    assert_ne!(harness.get_cell_bg(x + 20, y), Some(ruler_bg));
+}
+
+#[test]
+fn test_trailing_whitespace() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("trailing.txt");
+    std::fs::write(&file, "code   \nmore  \n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.show_trailing_whitespace = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let warning_bg = harness.editor().theme().whitespace_warning_bg;
+    let (x, y) = find_on_screen(&harness, "code");
+    assert_ne!(harness.get_cell_bg(x + 3, y), Some(warning_bg));
+    assert_eq!(harness.get_cell_bg(x + 4, y), Some(warning_bg));
+    assert_eq!(harness.get_cell_bg(x + 6, y), Some(warning_bg));
+
+    // Whitespace just typed before the cursor isn't flagged yet
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let (x, y) = find_on_screen(&harness, "more");
+    assert_ne!(harness.get_cell_bg(x + 4, y), Some(warning_bg));
+}
+
+#[test]
+fn test_mixed_indentation() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("indent.txt");
+    std::fs::write(&file, "\t  mixed\n    spaces\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.show_mixed_indentation = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let warning_bg = harness.editor().theme().whitespace_warning_bg;
+    let (x, y) = find_on_screen(&harness, "mixed");
+    assert_eq!(harness.get_cell_bg(x - 1, y), Some(warning_bg));
+    let (x, y) = find_on_screen(&harness, "spaces");
+    assert_ne!(harness.get_cell_bg(x - 1, y), Some(warning_bg));
+}
+
+#[test]
+fn test_current_line_highlight() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("lines.txt");
+    std::fs::write(&file, "first\nsecond\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.highlight_current_line = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let line_bg = harness.editor().theme().current_line_bg;
+    let (x, first_y) = find_on_screen(&harness, "first");
+    let (_, second_y) = find_on_screen(&harness, "second");
+    assert_eq!(harness.get_cell_bg(x + 20, first_y), Some(line_bg));
+    assert_ne!(harness.get_cell_bg(x + 20, second_y), Some(line_bg));
+    // The gutter is left alone
+    assert_ne!(harness.get_cell_bg(0, first_y), Some(line_bg));
+
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_ne!(harness.get_cell_bg(x + 20, first_y), Some(line_bg));
+    assert_eq!(harness.get_cell_bg(x + 20, second_y), Some(line_bg));
+
+    // Selections keep their own background
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+    let selection_bg = harness.editor().theme().selection_bg;
+    assert_eq!(harness.get_cell_bg(x + 1, second_y), Some(selection_bg));
+    assert_eq!(harness.get_cell_bg(x + 20, second_y), Some(line_bg));
+}
+
+#[test]
+fn test_current_column_highlight() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("columns.txt");
+    std::fs::write(&file, "abcdef\nabcdef\nabcdef\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.highlight_current_column = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let line_bg = harness.editor().theme().current_line_bg;
+    let (x, y) = find_on_screen(&harness, "abcdef");
+    assert_eq!(harness.get_cell_bg(x + 2, y + 1), Some(line_bg));
+    assert_eq!(harness.get_cell_bg(x + 2, y + 2), Some(line_bg));
+    assert_ne!(harness.get_cell_bg(x + 3, y + 1), Some(line_bg));
+    // Without highlight_current_line the rest of the cursor line stays plain
+    assert_ne!(harness.get_cell_bg(x + 3, y), Some(line_bg));
+}
+
+#[test]
+fn test_max_line_length_highlight() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("long.txt");
+    std::fs::write(&file, "0123456789abc\nshort\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.max_line_length = Some(10);
+    // Wide enough for the status message next to the temp path
+    let mut harness = EditorTestHarness::with_config(120, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let warning_bg = harness.editor().theme().line_length_warning_bg;
+    let (x, y) = find_on_screen(&harness, "0123456789abc");
+    assert_ne!(harness.get_cell_bg(x + 9, y), Some(warning_bg));
+    assert_eq!(harness.get_cell_bg(x + 10, y), Some(warning_bg));
+    assert_eq!(harness.get_cell_bg(x + 12, y), Some(warning_bg));
+    assert_ne!(harness.get_cell_bg(x + 13, y), Some(warning_bg));
+
+    // The warnings can be turned off for the buffer
+    harness.editor_mut().toggle_line_length_warnings();
+    harness.render().unwrap();
+    assert_ne!(harness.get_cell_bg(x + 10, y), Some(warning_bg));
+    harness.assert_screen_contains("Line length warnings disabled");
+}
+
+#[test]
+fn test_language_max_line_length_overrides_editor() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("main.py");
+    std::fs::write(&file, "import os, sys\n").unwrap();
+
+    let mut config = Config::default();
+    config.editor.max_line_length = Some(4);
+    let python = config
+        .languages
+        .entry("python".to_string())
+        .or_insert_with(LanguageConfig::default);
+    python.max_line_length = Some(10);
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let warning_bg = harness.editor().theme().line_length_warning_bg;
+    let (x, y) = find_on_screen(&harness, "import os, sys");
+    assert_ne!(harness.get_cell_bg(x + 4, y), Some(warning_bg));
+    assert_eq!(harness.get_cell_bg(x + 10, y), Some(warning_bg));
+}
@@ -0,0 +1,102 @@
+//! Tests for whitespace rendering
+//!
+//! Tests that:
+//! - `render_whitespace: all` draws tabs as », spaces as · and line endings as ¶
+//! - CRLF line endings are drawn as ␍¶
+//! - `render_whitespace: selection` only draws whitespace inside the selection
+//! - "Toggle Whitespace Rendering" turns it on and off for the buffer
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::{Config, RenderWhitespace};
+use tempfile::TempDir;
+
+fn harness_with_mode(mode: RenderWhitespace) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.render_whitespace = mode;
+    EditorTestHarness::with_config(80, 24, config).unwrap()
+}
+
+#[test]
+fn test_render_all_whitespace() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("spaces.txt");
+    std::fs::write(&file, "\tx y\nnext\n").unwrap();
+
+    let mut harness = harness_with_mode(RenderWhitespace::All);
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("»   x·y¶");
+    harness.assert_screen_contains("next¶");
+    harness.assert_screen_not_contains("→");
+
+    // Glyphs are dimmed, not drawn in the text color
+    // find_text_on_screen gives a byte offset; count characters for the column
+    let (offset, y) = harness.find_text_on_screen("x·y").unwrap();
+    let x = harness.get_row_text(y)[..offset as usize].chars().count() as u16;
+    let fg = harness.get_cell_style(x + 1, y).and_then(|style| style.fg);
+    assert_eq!(fg, Some(harness.editor().theme().line_number_fg));
+}
+
+#[test]
+fn test_render_crlf_line_endings() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("crlf.txt");
+    std::fs::write(&file, "first\r\nsecond\r\n").unwrap();
+
+    let mut harness = harness_with_mode(RenderWhitespace::All);
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("first␍¶");
+    harness.assert_screen_contains("second␍¶");
+}
+
+#[test]
+fn test_render_whitespace_in_selection() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("selection.txt");
+    std::fs::write(&file, "one two\nthree four\n").unwrap();
+
+    let mut harness = harness_with_mode(RenderWhitespace::Selection);
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("one two");
+
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("one·two");
+    harness.assert_screen_contains("three four");
+    harness.assert_screen_not_contains("¶");
+}
+
+#[test]
+fn test_toggle_whitespace_command() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("toggle.txt");
+    std::fs::write(&file, "a b\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("a b");
+
+    let run_toggle = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Toggle Whitespace Rendering").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.render().unwrap();
+    };
+
+    run_toggle(&mut harness);
+    harness.assert_screen_contains("a·b¶");
+
+    run_toggle(&mut harness);
+    harness.assert_screen_contains("a b");
+    harness.assert_screen_not_contains("¶");
+}
@@ -0,0 +1,81 @@
+//! Tests for completing words from open buffers and the tags file when no
+//! language server is available
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn harness_with_files(files: &[(&str, &str)]) -> EditorTestHarness {
+    let mut harness =
+        EditorTestHarness::with_temp_project_and_config(80, 24, Config::default()).unwrap();
+    let dir = harness.project_dir().unwrap();
+    for (name, content) in files {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+    for (name, _) in files.iter().filter(|(name, _)| *name != "tags") {
+        harness.open_file(&dir.join(name)).unwrap();
+    }
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+}
+
+fn complete(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char(' '), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// Words near the cursor come before words of other buffers
+#[test]
+fn test_word_completion_ranks_current_buffer_first() {
+    let mut harness = harness_with_files(&[
+        ("other.txt", "confidential\n"),
+        ("notes.txt", "configuration\nconf"),
+    ]);
+    complete(&mut harness);
+    harness.assert_screen_contains("configuration");
+    harness.assert_screen_contains("confidential");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "configuration\nconfiguration"
+    );
+}
+
+/// Names in the project's tags file are suggested too
+#[test]
+fn test_word_completion_from_tags_file() {
+    let mut harness = harness_with_files(&[
+        (
+            "tags",
+            "!_TAG_FILE_SORTED\t1\t//\nparse_header\tsrc/parse.c\t12;\"\tf\n",
+        ),
+        ("notes.txt", "call parse_h"),
+    ]);
+    complete(&mut harness);
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "call parse_header");
+}
+
+/// Words are split by the buffer's language, so CSS properties keep their
+/// hyphens
+#[test]
+fn test_word_completion_follows_language() {
+    let mut harness = harness_with_files(&[("style.css", "a { font-size: 1px; }\nb { font-s")]);
+    complete(&mut harness);
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "a { font-size: 1px; }\nb { font-size"
+    );
+}
@@ -0,0 +1,99 @@
+//! E2E tests for workspace folders: folders opened alongside the working
+//! directory, shown as roots of the file explorer and advertised to language
+//! servers.
+
+use crate::common::fake_lsp::FakeLspServer;
+use crate::common::harness::EditorTestHarness;
+use std::fs;
+
+/// An added folder is shown as another root of the file explorer, and is
+/// gone from it when removed
+#[test]
+fn test_workspace_folder_shown_in_file_explorer() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::with_temp_project(120, 40)?;
+    let project_root = harness.project_dir().unwrap();
+    fs::write(project_root.join("main_file.txt"), "main")?;
+    let other = tempfile::tempdir()?;
+    fs::write(other.path().join("other_file.txt"), "other")?;
+    let other_path = other.path().canonicalize()?;
+
+    harness.editor_mut().focus_file_explorer();
+    harness.wait_for_file_explorer()?;
+    harness.wait_for_file_explorer_item("main_file.txt")?;
+
+    assert!(harness
+        .editor_mut()
+        .add_workspace_folder(other_path.clone()));
+    harness.wait_for_file_explorer_item("other_file.txt")?;
+    harness.assert_screen_contains("main_file.txt");
+    assert_eq!(
+        harness.editor().workspace_folders(),
+        std::slice::from_ref(&other_path)
+    );
+
+    // Adding it again does nothing
+    assert!(!harness
+        .editor_mut()
+        .add_workspace_folder(other_path.clone()));
+
+    assert!(harness.editor_mut().remove_workspace_folder(&other_path));
+    harness.render()?;
+    harness.assert_screen_not_contains("other_file.txt");
+    harness.assert_screen_contains("main_file.txt");
+    assert!(harness.editor().workspace_folders().is_empty());
+    Ok(())
+}
+
+/// A running server that can't be told about new folders is restarted with
+/// them, and the open documents are opened in it again
+#[test]
+#[cfg_attr(target_os = "windows", ignore)] // Uses Bash-based fake LSP server
+fn test_workspace_folder_advertised_to_language_server() -> anyhow::Result<()> {
+    let _fake_server = FakeLspServer::spawn_with_logging()?;
+    let temp_dir = tempfile::tempdir()?;
+    let log_file = temp_dir.path().join("lsp_workspace_folders_log.txt");
+    let test_file = temp_dir.path().join("test.rs");
+    fs::write(&test_file, "fn main() {}\n")?;
+    let other = tempfile::tempdir()?;
+    let other_path = other.path().canonicalize()?;
+
+    let mut config = fresh::config::Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: FakeLspServer::logging_script_path()
+                .to_string_lossy()
+                .to_string(),
+            args: vec![log_file.to_string_lossy().to_string()],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+        },
+    );
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        30,
+        config,
+        temp_dir.path().to_path_buf(),
+    )?;
+
+    harness.open_file(&test_file)?;
+    harness.wait_until(|_| {
+        fs::read_to_string(&log_file)
+            .unwrap_or_default()
+            .contains("textDocument/didOpen")
+    })?;
+
+    assert!(harness
+        .editor_mut()
+        .add_workspace_folder(other_path.clone()));
+
+    // The restarted server starts a new log
+    let expected = format!("workspaceFolder file://{}", other_path.display());
+    harness.wait_until(|_| {
+        let log = fs::read_to_string(&log_file).unwrap_or_default();
+        log.lines().any(|line| line == expected) && log.contains("textDocument/didOpen")
+    })?;
+    Ok(())
+}
@@ -0,0 +1,105 @@
+//! Tests for workspace trust and safe mode
+//!
+//! Tests that:
+//! - An untrusted project's commands are left out of the config, its other
+//!   settings still apply, and the user is asked whether to trust it
+//! - Trusting for the session applies the commands without remembering it
+//! - Trusting for good is remembered for the next start
+
+use crate::common::harness::{EditorTestHarness, HarnessOptions};
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use fresh::services::workspace_trust;
+use std::fs;
+
+/// An untrusted project whose config sets a tab size and a REPL command
+fn untrusted_project() -> EditorTestHarness {
+    let mut harness = EditorTestHarness::create(
+        100,
+        24,
+        HarnessOptions::new()
+            .with_project_root()
+            .with_untrusted_workspace(),
+    )
+    .unwrap();
+    let config_path = harness
+        .project_dir()
+        .unwrap()
+        .join(".fresh")
+        .join("config.json");
+    fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+    fs::write(
+        &config_path,
+        r#"{"editor": {"tab_size": 3}, "languages": {"python": {"repl": "./repl.sh"}}}"#,
+    )
+    .unwrap();
+
+    harness.editor_mut().reload_config();
+    harness.editor_mut().check_workspace_trust();
+    harness.render().unwrap();
+    harness
+}
+
+fn repl(harness: &EditorTestHarness) -> Option<String> {
+    harness.editor().config().languages["python"].repl.clone()
+}
+
+/// Whether the project is listed as trusted in the harness's data directory
+fn remembered(harness: &EditorTestHarness) -> bool {
+    let project_dir = harness.project_dir().unwrap();
+    let dir_context = DirectoryContext::for_testing(project_dir.parent().unwrap());
+    workspace_trust::is_trusted(&dir_context, &project_dir)
+}
+
+fn answer(harness: &mut EditorTestHarness, text: &str) {
+    harness.type_text(text).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+#[test]
+fn test_untrusted_workspace_runs_in_safe_mode() {
+    let mut harness = untrusted_project();
+    assert_eq!(harness.editor().config().editor.tab_size, 3);
+    // The project's REPL is dropped in favor of the built-in one
+    assert_eq!(repl(&harness).as_deref(), Some("python3"));
+    harness.assert_screen_contains("has plugins or commands that run code");
+
+    answer(&mut harness, "n");
+    assert!(!harness.editor().is_workspace_trusted());
+    harness.assert_screen_contains("Safe mode");
+
+    // Reloading the config keeps the commands out
+    harness.editor_mut().reload_config();
+    assert_eq!(repl(&harness).as_deref(), Some("python3"));
+}
+
+#[test]
+fn test_trust_workspace_for_session() {
+    let mut harness = untrusted_project();
+    answer(&mut harness, "s");
+    assert!(harness.editor().is_workspace_trusted());
+    assert_eq!(repl(&harness).as_deref(), Some("./repl.sh"));
+    assert!(!remembered(&harness));
+}
+
+#[test]
+fn test_trust_workspace_from_command_palette() {
+    let mut harness = untrusted_project();
+    answer(&mut harness, "n");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    answer(&mut harness, "Trust Workspace");
+    answer(&mut harness, "t");
+    assert!(harness.editor().is_workspace_trusted());
+    assert_eq!(repl(&harness).as_deref(), Some("./repl.sh"));
+    harness.assert_screen_contains("Workspace trusted");
+    assert!(remembered(&harness));
+}
@@ -0,0 +1,117 @@
+// Fresh in the browser: the editor core compiled to WebAssembly, drawn in
+// xterm.js. Build the module with scripts/build-web.sh.
+
+import init, { Playground } from "./pkg/fresh.js";
+
+const SAMPLES = {
+  "main.rs": `use std::collections::HashMap;
+
+/// Count the words of a text
+fn word_counts(text: &str) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn main() {
+    let counts = word_counts("the quick brown fox jumps over the lazy dog");
+    println!("{:?}", counts.get("the"));
+}
+`,
+  "app.ts": `interface Todo {
+  title: string;
+  done: boolean;
+}
+
+const todos: Todo[] = [];
+
+function add(title: string): Todo {
+  const todo = { title, done: false };
+  todos.push(todo);
+  return todo;
+}
+
+add("Try Fresh in the browser");
+console.log(todos.filter((todo) => !todo.done).length);
+`,
+  "notes.md": `# Fresh Playground
+
+This editor runs entirely in your browser.
+
+- Arrow keys, Home/End and Page Up/Down move the cursor
+- Ctrl+Left/Right move by word
+- Enter keeps the indentation, and indents after an opening brace
+`,
+};
+
+await init();
+
+const container = document.getElementById("terminal");
+const terminal = new Terminal({
+  cursorBlink: true,
+  fontFamily: "Menlo, Consolas, 'DejaVu Sans Mono', monospace",
+  fontSize: 14,
+});
+const fit = new FitAddon.FitAddon();
+terminal.loadAddon(fit);
+terminal.open(container);
+fit.fit();
+
+let playground;
+
+function draw() {
+  terminal.write(playground.render());
+}
+
+function load(name) {
+  playground?.free();
+  playground = new Playground(name, SAMPLES[name], terminal.cols, terminal.rows);
+  playground.setTheme(document.getElementById("theme").value);
+  draw();
+  terminal.focus();
+}
+
+// Keys go to the editor, not to xterm.js's own input handling. Keys the
+// editor doesn't use keep their browser behavior.
+terminal.attachCustomKeyEventHandler((event) => {
+  if (event.type === "keydown" && !event.isComposing) {
+    const handled = playground.handleKey(
+      event.key,
+      event.ctrlKey,
+      event.altKey,
+      event.shiftKey,
+      event.metaKey,
+    );
+    if (handled) {
+      event.preventDefault();
+      draw();
+    }
+  }
+  return false;
+});
+
+// With keys handled above, input only arrives here for pastes and IME
+// compositions. xterm.js sends pasted line breaks as carriage returns.
+terminal.onData((data) => {
+  playground.paste(data.replace(/\r\n?/g, "\n"));
+  draw();
+});
+
+new ResizeObserver(() => {
+  fit.fit();
+  playground.resize(terminal.cols, terminal.rows);
+  draw();
+}).observe(container);
+
+document.getElementById("sample").addEventListener("change", (event) => {
+  load(event.target.value);
+});
+document.getElementById("theme").addEventListener("change", (event) => {
+  playground.setTheme(event.target.value);
+  draw();
+  terminal.focus();
+});
+
+load(document.getElementById("sample").value);
@@ -0,0 +1,9 @@
+; SQL in string literals, e.g. db.query("SELECT * FROM users WHERE id = $1")
+; (tagged templates like sql`...` are covered by the grammar's own injections)
+
+([
+  (string (string_fragment) @injection.content)
+  (template_string (string_fragment) @injection.content)
+ ]
+ (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with)\\s")
+ (#set! injection.language "sql"))
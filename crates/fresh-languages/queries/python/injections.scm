@@ -0,0 +1,6 @@
+; SQL in string literals, e.g. cursor.execute("SELECT * FROM users WHERE id = ?")
+
+((string
+  (string_content) @injection.content)
+ (#match? @injection.content "^\\s*(?i:select|insert|update|delete|create|alter|drop|with)\\s")
+ (#set! injection.language "sql"))
@@ -28,6 +28,8 @@ pub use tree_sitter_javascript;
 pub use tree_sitter_json;
 #[cfg(feature = "tree-sitter-lua")]
 pub use tree_sitter_lua;
+#[cfg(feature = "tree-sitter-md")]
+pub use tree_sitter_md;
 #[cfg(feature = "tree-sitter-odin")]
 pub use tree_sitter_odin;
 #[cfg(feature = "tree-sitter-pascal")]
@@ -40,6 +42,8 @@ pub use tree_sitter_python;
 pub use tree_sitter_ruby;
 #[cfg(feature = "tree-sitter-rust")]
 pub use tree_sitter_rust;
+#[cfg(feature = "tree-sitter-sequel")]
+pub use tree_sitter_sequel;
 #[cfg(feature = "tree-sitter-typescript")]
 pub use tree_sitter_typescript;
 
@@ -130,6 +134,8 @@ pub enum Language {
     Lua,
     Pascal,
     Odin,
+    Markdown,
+    Sql,
 }
 
 impl Language {
@@ -154,6 +160,8 @@ impl Language {
             "lua" => Some(Language::Lua),
             "pas" | "p" => Some(Language::Pascal),
             "odin" => Some(Language::Odin),
+            "md" | "markdown" => Some(Language::Markdown),
+            "sql" => Some(Language::Sql),
             _ => None,
         }
     }
@@ -185,7 +193,7 @@ impl Language {
                         tree_sitter_python::LANGUAGE.into(),
                         "python",
                         tree_sitter_python::HIGHLIGHTS_QUERY,
-                        "",
+                        &self.injections_query(),
                         "",
                     )
                     .map_err(|e| format!("Failed to create Python highlight config: {e}"))?;
@@ -202,7 +210,7 @@ impl Language {
                         tree_sitter_javascript::LANGUAGE.into(),
                         "javascript",
                         tree_sitter_javascript::HIGHLIGHT_QUERY,
-                        "",
+                        &self.injections_query(),
                         "",
                     )
                     .map_err(|e| format!("Failed to create JavaScript highlight config: {e}"))?;
@@ -244,7 +252,7 @@ impl Language {
                         tree_sitter_html::LANGUAGE.into(),
                         "html",
                         tree_sitter_html::HIGHLIGHTS_QUERY,
-                        "",
+                        &self.injections_query(),
                         "",
                     )
                     .map_err(|e| format!("Failed to create HTML highlight config: {e}"))?;
@@ -475,7 +483,152 @@ impl Language {
                 #[cfg(not(feature = "tree-sitter-odin"))]
                 Err("Odin language support not enabled".to_string())
             }
+            Self::Markdown => {
+                #[cfg(feature = "tree-sitter-md")]
+                {
+                    let mut config = HighlightConfiguration::new(
+                        tree_sitter_md::LANGUAGE.into(),
+                        "markdown",
+                        tree_sitter_md::HIGHLIGHT_QUERY_BLOCK,
+                        &self.injections_query(),
+                        "",
+                    )
+                    .map_err(|e| format!("Failed to create Markdown highlight config: {e}"))?;
+                    config.configure(DEFAULT_HIGHLIGHT_CAPTURES);
+                    Ok(config)
+                }
+                #[cfg(not(feature = "tree-sitter-md"))]
+                Err("Markdown language support not enabled".to_string())
+            }
+            Self::Sql => {
+                #[cfg(feature = "tree-sitter-sequel")]
+                {
+                    let mut config = HighlightConfiguration::new(
+                        tree_sitter_sequel::LANGUAGE.into(),
+                        "sql",
+                        tree_sitter_sequel::HIGHLIGHTS_QUERY,
+                        "",
+                        "",
+                    )
+                    .map_err(|e| format!("Failed to create SQL highlight config: {e}"))?;
+                    config.configure(DEFAULT_HIGHLIGHT_CAPTURES);
+                    Ok(config)
+                }
+                #[cfg(not(feature = "tree-sitter-sequel"))]
+                Err("SQL language support not enabled".to_string())
+            }
+        }
+    }
+
+    /// Tree-sitter grammar for this language, if its support is enabled
+    pub fn ts_language(&self) -> Option<tree_sitter::Language> {
+        match self {
+            #[cfg(feature = "tree-sitter-rust")]
+            Self::Rust => Some(tree_sitter_rust::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-python")]
+            Self::Python => Some(tree_sitter_python::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-javascript")]
+            Self::JavaScript => Some(tree_sitter_javascript::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-typescript")]
+            Self::TypeScript => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            #[cfg(feature = "tree-sitter-html")]
+            Self::HTML => Some(tree_sitter_html::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-css")]
+            Self::CSS => Some(tree_sitter_css::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c")]
+            Self::C => Some(tree_sitter_c::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-cpp")]
+            Self::Cpp => Some(tree_sitter_cpp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-go")]
+            Self::Go => Some(tree_sitter_go::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-json")]
+            Self::Json => Some(tree_sitter_json::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-java")]
+            Self::Java => Some(tree_sitter_java::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-c-sharp")]
+            Self::CSharp => Some(tree_sitter_c_sharp::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-php")]
+            Self::Php => Some(tree_sitter_php::LANGUAGE_PHP.into()),
+            #[cfg(feature = "tree-sitter-ruby")]
+            Self::Ruby => Some(tree_sitter_ruby::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-bash")]
+            Self::Bash => Some(tree_sitter_bash::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-lua")]
+            Self::Lua => Some(tree_sitter_lua::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-pascal")]
+            Self::Pascal => Some(tree_sitter_pascal::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-odin")]
+            Self::Odin => Some(tree_sitter_odin::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-md")]
+            Self::Markdown => Some(tree_sitter_md::LANGUAGE.into()),
+            #[cfg(feature = "tree-sitter-sequel")]
+            Self::Sql => Some(tree_sitter_sequel::LANGUAGE.into()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Tree-sitter injection query for this language (empty if none).
+    ///
+    /// Injections mark regions written in another language: code fences in
+    /// Markdown, `<script>`/`<style>` in HTML and SQL string literals. The
+    /// embedded language is resolved with [`Language::from_injection_name`].
+    pub fn injections_query(&self) -> String {
+        match self {
+            Self::HTML => {
+                #[cfg(feature = "tree-sitter-html")]
+                return tree_sitter_html::INJECTIONS_QUERY.to_string();
+            }
+            Self::JavaScript => {
+                #[cfg(feature = "tree-sitter-javascript")]
+                return format!(
+                    "{}\n{}",
+                    tree_sitter_javascript::INJECTIONS_QUERY,
+                    include_str!("../queries/javascript/injections.scm")
+                );
+            }
+            Self::Python => return include_str!("../queries/python/injections.scm").to_string(),
+            Self::Markdown => {
+                #[cfg(feature = "tree-sitter-md")]
+                return tree_sitter_md::INJECTION_QUERY_BLOCK.to_string();
+            }
+            _ => {}
         }
+        String::new()
+    }
+
+    /// Resolve the language named by an injection (e.g. a code fence's info
+    /// string like "py" or "rust,ignore")
+    pub fn from_injection_name(name: &str) -> Option<Self> {
+        let name = name
+            .split(|c: char| c == ',' || c == '{' || c.is_whitespace())
+            .find(|part| !part.is_empty())?;
+        if let Some(language) = Self::from_id(name) {
+            return Some(language);
+        }
+        match name.to_lowercase().as_str() {
+            "rs" => Some(Self::Rust),
+            "py" | "python3" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "tsx" => Some(Self::TypeScript),
+            "htm" | "xhtml" => Some(Self::HTML),
+            "h" => Some(Self::C),
+            "cc" | "cxx" | "hpp" => Some(Self::Cpp),
+            "golang" => Some(Self::Go),
+            "jsonc" => Some(Self::Json),
+            "cs" => Some(Self::CSharp),
+            "rb" => Some(Self::Ruby),
+            "sh" | "shell" | "zsh" | "console" => Some(Self::Bash),
+            "md" => Some(Self::Markdown),
+            "postgres" | "postgresql" | "mysql" | "sqlite" | "plsql" => Some(Self::Sql),
+            _ => None,
+        }
+    }
+
+    /// Whether highlights from `other` can be mapped with this language's
+    /// capture list (needed to highlight `other` when injected into `self`)
+    pub fn shares_highlight_captures(&self, other: &Language) -> bool {
+        (*self == Self::TypeScript) == (*other == Self::TypeScript)
     }
 
     /// Map tree-sitter highlight index to a highlight category
@@ -509,6 +662,8 @@ impl Language {
             Language::Lua,
             Language::Pascal,
             Language::Odin,
+            Language::Markdown,
+            Language::Sql,
         ]
     }
 
@@ -533,6 +688,8 @@ impl Language {
             Self::Lua => "lua",
             Self::Pascal => "pascal",
             Self::Odin => "odin",
+            Self::Markdown => "markdown",
+            Self::Sql => "sql",
         }
     }
 
@@ -557,6 +714,8 @@ impl Language {
             Self::Lua => "Lua",
             Self::Pascal => "Pascal",
             Self::Odin => "Odin",
+            Self::Markdown => "Markdown",
+            Self::Sql => "SQL",
         }
     }
 
@@ -582,6 +741,8 @@ impl Language {
             "lua" => Some(Self::Lua),
             "pascal" => Some(Self::Pascal),
             "odin" => Some(Self::Odin),
+            "markdown" => Some(Self::Markdown),
+            "sql" => Some(Self::Sql),
             _ => None,
         }
     }
@@ -622,6 +783,8 @@ impl Language {
             "lua" => Some(Self::Lua),
             "pascal" => Some(Self::Pascal),
             "odin" => Some(Self::Odin),
+            "markdown" | "multimarkdown" => Some(Self::Markdown),
+            "sql" => Some(Self::Sql),
             _ => {
                 // Try matching shell variants
                 if name_lower.contains("bash") || name_lower.contains("shell") {
@@ -89,11 +89,12 @@
 use anyhow::{anyhow, Result};
 use fresh_core::api::{
     ActionSpec, BufferInfo, CompositeHunk, CreateCompositeBufferOptions, EditorStateSnapshot,
-    JsCallbackId, LanguagePackConfig, LspServerPackConfig, OverlayOptions, PluginCommand,
-    PluginResponse,
+    JsCallbackId, LanguagePackConfig, LspServerPackConfig, OverlayOptions, PermissionRequest,
+    PluginCommand, PluginResponse,
 };
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
+use fresh_core::services::PermissionDecision;
 use fresh_core::text_property::TextPropertyEntry;
 use fresh_core::{BufferId, SplitId};
 use fresh_parser_js::{
@@ -106,7 +107,9 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::{mpsc, Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 /// Convert a QuickJS Value to serde_json::Value
 fn js_to_json(ctx: &rquickjs::Ctx<'_>, val: Value<'_>) -> serde_json::Value {
@@ -486,6 +489,67 @@ pub struct JsEditorApi {
     pub plugin_name: String,
 }
 
+impl JsEditorApi {
+    /// Whether this plugin may do what `request` asks for. If the user
+    /// hasn't decided yet, the editor is told to ask them and this returns
+    /// false: the plugin can try again once they've allowed it.
+    fn permitted(&self, request: PermissionRequest) -> bool {
+        match self.services.check_permission(&self.plugin_name, &request) {
+            PermissionDecision::Allow => true,
+            PermissionDecision::Deny => false,
+            PermissionDecision::Ask => {
+                let _ = self.command_sender.send(PluginCommand::RequestPermission {
+                    plugin_name: self.plugin_name.clone(),
+                    request,
+                    callback_id: None,
+                    then: None,
+                });
+                false
+            }
+        }
+    }
+
+    /// Send `command`, which runs `program`, if this plugin may run it. If
+    /// the user hasn't decided yet, the editor asks them and sends it once
+    /// it's allowed; if it's denied, the callback is rejected.
+    fn send_process_command(
+        &self,
+        ctx: &rquickjs::Ctx<'_>,
+        program: &str,
+        callback_id: JsCallbackId,
+        command: PluginCommand,
+    ) -> rquickjs::Result<()> {
+        let request = PermissionRequest::Subprocess(program.to_string());
+        match self.services.check_permission(&self.plugin_name, &request) {
+            PermissionDecision::Allow => {
+                let _ = self.command_sender.send(command);
+                Ok(())
+            }
+            PermissionDecision::Deny => {
+                self.callback_contexts
+                    .borrow_mut()
+                    .remove(&callback_id.as_u64());
+                Err(rquickjs::Exception::throw_message(
+                    ctx,
+                    &format!(
+                        "Plugin '{}' is not allowed to run '{}'",
+                        self.plugin_name, program
+                    ),
+                ))
+            }
+            PermissionDecision::Ask => {
+                let _ = self.command_sender.send(PluginCommand::RequestPermission {
+                    plugin_name: self.plugin_name.clone(),
+                    request,
+                    callback_id: Some(callback_id),
+                    then: Some(Box::new(command)),
+                });
+                Ok(())
+            }
+        }
+    }
+}
+
 #[plugin_api_impl]
 #[rquickjs::methods(rename_all = "camelCase")]
 impl JsEditorApi {
@@ -717,15 +781,27 @@ impl JsEditorApi {
         false
     }
 
+    /// Get the revision of a buffer's contents, advanced by every change
+    pub fn get_buffer_revision(&self, buffer_id: u32) -> u64 {
+        if let Ok(s) = self.state_snapshot.read() {
+            if let Some(b) = s.buffers.get(&BufferId(buffer_id as usize)) {
+                return b.revision;
+            }
+        }
+        0
+    }
+
     /// Save a buffer to a specific file path
     /// Used by :w filename to save unnamed buffers or save-as
     pub fn save_buffer_to_path(&self, buffer_id: u32, path: String) -> bool {
-        self.command_sender
-            .send(PluginCommand::SaveBufferToPath {
-                buffer_id: BufferId(buffer_id as usize),
-                path: std::path::PathBuf::from(path),
-            })
-            .is_ok()
+        self.permitted(PermissionRequest::Write(PathBuf::from(&path)))
+            && self
+                .command_sender
+                .send(PluginCommand::SaveBufferToPath {
+                    buffer_id: BufferId(buffer_id as usize),
+                    path: PathBuf::from(path),
+                })
+                .is_ok()
     }
 
     /// Get buffer info by ID
@@ -897,25 +973,29 @@ impl JsEditorApi {
 
     /// Open a file, optionally at a specific line/column
     pub fn open_file(&self, path: String, line: Option<u32>, column: Option<u32>) -> bool {
-        self.command_sender
-            .send(PluginCommand::OpenFileAtLocation {
-                path: PathBuf::from(path),
-                line: line.map(|l| l as usize),
-                column: column.map(|c| c as usize),
-            })
-            .is_ok()
+        self.permitted(PermissionRequest::Read(PathBuf::from(&path)))
+            && self
+                .command_sender
+                .send(PluginCommand::OpenFileAtLocation {
+                    path: PathBuf::from(path),
+                    line: line.map(|l| l as usize),
+                    column: column.map(|c| c as usize),
+                })
+                .is_ok()
     }
 
     /// Open a file in a specific split
     pub fn open_file_in_split(&self, split_id: u32, path: String, line: u32, column: u32) -> bool {
-        self.command_sender
-            .send(PluginCommand::OpenFileInSplit {
-                split_id: split_id as usize,
-                path: PathBuf::from(path),
-                line: Some(line as usize),
-                column: Some(column as usize),
-            })
-            .is_ok()
+        self.permitted(PermissionRequest::Read(PathBuf::from(&path)))
+            && self
+                .command_sender
+                .send(PluginCommand::OpenFileInSplit {
+                    split_id: split_id as usize,
+                    path: PathBuf::from(path),
+                    line: Some(line as usize),
+                    column: Some(column as usize),
+                })
+                .is_ok()
     }
 
     /// Show a buffer in the current split
@@ -1056,17 +1136,21 @@ impl JsEditorApi {
 
     /// Check if file exists
     pub fn file_exists(&self, path: String) -> bool {
-        Path::new(&path).exists()
+        self.permitted(PermissionRequest::Read(PathBuf::from(&path))) && Path::new(&path).exists()
     }
 
     /// Read file contents
     pub fn read_file(&self, path: String) -> Option<String> {
+        if !self.permitted(PermissionRequest::Read(PathBuf::from(&path))) {
+            return None;
+        }
         std::fs::read_to_string(&path).ok()
     }
 
     /// Write file contents
     pub fn write_file(&self, path: String, content: String) -> bool {
-        std::fs::write(&path, content).is_ok()
+        self.permitted(PermissionRequest::Write(PathBuf::from(&path)))
+            && std::fs::write(&path, content).is_ok()
     }
 
     /// Read directory contents (returns array of {name, is_file, is_dir})
@@ -1078,23 +1162,28 @@ impl JsEditorApi {
     ) -> rquickjs::Result<Value<'js>> {
         use fresh_core::api::DirEntry;
 
-        let entries: Vec<DirEntry> = match std::fs::read_dir(&path) {
-            Ok(entries) => entries
-                .filter_map(|e| e.ok())
-                .map(|entry| {
-                    let file_type = entry.file_type().ok();
-                    DirEntry {
-                        name: entry.file_name().to_string_lossy().to_string(),
-                        is_file: file_type.map(|ft| ft.is_file()).unwrap_or(false),
-                        is_dir: file_type.map(|ft| ft.is_dir()).unwrap_or(false),
+        let entries: Vec<DirEntry> =
+            if self.permitted(PermissionRequest::Read(PathBuf::from(&path))) {
+                match std::fs::read_dir(&path) {
+                    Ok(entries) => entries
+                        .filter_map(|e| e.ok())
+                        .map(|entry| {
+                            let file_type = entry.file_type().ok();
+                            DirEntry {
+                                name: entry.file_name().to_string_lossy().to_string(),
+                                is_file: file_type.map(|ft| ft.is_file()).unwrap_or(false),
+                                is_dir: file_type.map(|ft| ft.is_dir()).unwrap_or(false),
+                            }
+                        })
+                        .collect(),
+                    Err(e) => {
+                        tracing::warn!("readDir failed for '{}': {}", path, e);
+                        Vec::new()
                     }
-                })
-                .collect(),
-            Err(e) => {
-                tracing::warn!("readDir failed for '{}': {}", path, e);
+                }
+            } else {
                 Vec::new()
-            }
-        };
+            };
 
         rquickjs_serde::to_value(ctx, &entries)
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
@@ -1240,7 +1329,11 @@ impl JsEditorApi {
         ctx: rquickjs::Ctx<'js>,
         path: String,
     ) -> rquickjs::Result<Value<'js>> {
-        let metadata = std::fs::metadata(&path).ok();
+        let metadata = if self.permitted(PermissionRequest::Read(PathBuf::from(&path))) {
+            std::fs::metadata(&path).ok()
+        } else {
+            None
+        };
         let stat = metadata.map(|m| {
             serde_json::json!({
                 "isFile": m.is_file(),
@@ -2314,11 +2407,11 @@ impl JsEditorApi {
     #[qjs(rename = "_spawnProcessStart")]
     pub fn spawn_process_start(
         &self,
-        _ctx: rquickjs::Ctx<'_>,
+        ctx: rquickjs::Ctx<'_>,
         command: String,
         args: Vec<String>,
         cwd: rquickjs::function::Opt<String>,
-    ) -> u64 {
+    ) -> rquickjs::Result<u64> {
         let id = {
             let mut id_ref = self.next_request_id.borrow_mut();
             let id = *id_ref;
@@ -2343,13 +2436,19 @@ impl JsEditorApi {
             effective_cwd,
             id
         );
-        let _ = self.command_sender.send(PluginCommand::SpawnProcess {
-            callback_id: JsCallbackId::new(id),
-            command,
-            args,
-            cwd: effective_cwd,
-        });
-        id
+        let program = command.clone();
+        self.send_process_command(
+            &ctx,
+            &program,
+            JsCallbackId::new(id),
+            PluginCommand::SpawnProcess {
+                callback_id: JsCallbackId::new(id),
+                command,
+                args,
+                cwd: effective_cwd,
+            },
+        )?;
+        Ok(id)
     }
 
     /// Wait for a process to complete and get its result (async)
@@ -2402,6 +2501,50 @@ impl JsEditorApi {
         id
     }
 
+    /// Apply text edits to several files, all or none (async, returns request_id)
+    ///
+    /// Each file's edits are a single undo step named `description`. Rejects
+    /// without changing anything if the plugin may not write a file, a file
+    /// is read-only, edits overlap, or an edit's `oldText` doesn't match.
+    #[plugin_api(async_promise, js_name = "applyEdits", ts_return = "number")]
+    #[qjs(rename = "_applyEditsStart")]
+    pub fn apply_edits_start(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        files: Vec<fresh_core::api::JsFileEdits>,
+        description: String,
+    ) -> rquickjs::Result<u64> {
+        if let Some(file) = files
+            .iter()
+            .find(|file| !self.permitted(PermissionRequest::Write(file.path.clone())))
+        {
+            return Err(rquickjs::Exception::throw_message(
+                &ctx,
+                &format!(
+                    "Plugin '{}' is not allowed to write '{}'",
+                    self.plugin_name,
+                    file.path.display()
+                ),
+            ));
+        }
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::ApplyEdits {
+            files,
+            description,
+            request_id: id,
+        });
+        Ok(id)
+    }
+
     /// Delay/sleep (async, returns request_id)
     #[plugin_api(async_promise, js_name = "delay", ts_return = "void")]
     #[qjs(rename = "_delayStart")]
@@ -2466,11 +2609,11 @@ impl JsEditorApi {
     #[qjs(rename = "_spawnBackgroundProcessStart")]
     pub fn spawn_background_process_start(
         &self,
-        _ctx: rquickjs::Ctx<'_>,
+        ctx: rquickjs::Ctx<'_>,
         command: String,
         args: Vec<String>,
         cwd: rquickjs::function::Opt<String>,
-    ) -> u64 {
+    ) -> rquickjs::Result<u64> {
         let id = {
             let mut id_ref = self.next_request_id.borrow_mut();
             let id = *id_ref;
@@ -2483,16 +2626,20 @@ impl JsEditorApi {
         };
         // Use id as process_id for simplicity
         let process_id = id;
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SpawnBackgroundProcess {
+        let program = command.clone();
+        self.send_process_command(
+            &ctx,
+            &program,
+            JsCallbackId::new(id),
+            PluginCommand::SpawnBackgroundProcess {
                 process_id,
                 command,
                 args,
                 cwd: cwd.0,
                 callback_id: JsCallbackId::new(id),
-            });
-        id
+            },
+        )?;
+        Ok(id)
     }
 
     /// Kill a background process
@@ -2773,6 +2920,72 @@ fn parse_view_token_style(
     }))
 }
 
+/// Interrupts plugins that run for longer than their time slice
+struct Watchdog {
+    time_slice: Option<Duration>,
+    /// When the running plugin's time slice ends
+    deadline: Mutex<Option<Instant>>,
+    /// Whether the running plugin was interrupted
+    expired: AtomicBool,
+}
+
+impl Watchdog {
+    fn new(time_slice: Option<Duration>) -> Self {
+        Self {
+            time_slice,
+            deadline: Mutex::new(None),
+            expired: AtomicBool::new(false),
+        }
+    }
+
+    /// Called by QuickJS every so often while JS runs: whether to stop it
+    fn should_interrupt(&self) -> bool {
+        let expired = self
+            .deadline
+            .lock()
+            .unwrap()
+            .is_some_and(|deadline| Instant::now() >= deadline);
+        if expired {
+            self.expired.store(true, Ordering::Relaxed);
+        }
+        expired
+    }
+}
+
+/// A plugin's time slice, started by [`QuickJsBackend::time_slice`] and
+/// ended when dropped
+struct TimeSlice {
+    watchdog: Arc<Watchdog>,
+    plugin_name: String,
+    command_sender: mpsc::Sender<PluginCommand>,
+    /// False for a slice started while another was running, which keeps
+    /// the outer deadline
+    started: bool,
+}
+
+impl Drop for TimeSlice {
+    fn drop(&mut self) {
+        if !self.started {
+            return;
+        }
+        *self.watchdog.deadline.lock().unwrap() = None;
+        if self.watchdog.expired.swap(false, Ordering::Relaxed) {
+            let limit = self.watchdog.time_slice.unwrap_or_default().as_millis();
+            tracing::warn!(
+                "Plugin '{}' ran for over {} ms and was interrupted",
+                self.plugin_name,
+                limit
+            );
+            let _ = self.command_sender.send(PluginCommand::SetStatus {
+                message: format!(
+                    "Plugin '{}' was stopped after running for {} ms",
+                    self.plugin_name, limit
+                ),
+            });
+        }
+    }
+}
+
 /// QuickJS-based JavaScript runtime for plugins
 pub struct QuickJsBackend {
     runtime: Runtime,
@@ -2797,6 +3010,8 @@ pub struct QuickJsBackend {
     callback_contexts: Rc<RefCell<HashMap<u64, String>>>,
     /// Bridge for editor services (i18n, theme, etc.)
     pub services: Arc<dyn fresh_core::services::PluginServiceBridge>,
+    /// Interrupts plugins that run too long
+    watchdog: Arc<Watchdog>,
 }
 
 impl QuickJsBackend {
@@ -2857,6 +3072,17 @@ impl QuickJsBackend {
             },
         )));
 
+        // Limit the heap shared by all plugins, and how long one may run
+        let limits = services.plugin_limits();
+        if let Some(memory_limit) = limits.memory_limit {
+            runtime.set_memory_limit(memory_limit);
+        }
+        let watchdog = Arc::new(Watchdog::new(limits.time_slice));
+        if limits.time_slice.is_some() {
+            let watchdog = Arc::clone(&watchdog);
+            runtime.set_interrupt_handler(Some(Box::new(move || watchdog.should_interrupt())));
+        }
+
         let main_context = Context::full(&runtime)
             .map_err(|e| anyhow!("Failed to create QuickJS context: {}", e))?;
 
@@ -2878,6 +3104,7 @@ impl QuickJsBackend {
             next_request_id,
             callback_contexts,
             services,
+            watchdog,
         };
 
         // Initialize main context (for internal utilities if needed)
@@ -2887,6 +3114,25 @@ impl QuickJsBackend {
         Ok(backend)
     }
 
+    /// Start `plugin_name`'s time slice: if it runs past it, QuickJS
+    /// interrupts it. The slice ends when the returned guard is dropped.
+    fn time_slice(&self, plugin_name: &str) -> TimeSlice {
+        let mut deadline = self.watchdog.deadline.lock().unwrap();
+        let started = deadline.is_none();
+        if started {
+            *deadline = self
+                .watchdog
+                .time_slice
+                .map(|time_slice| Instant::now() + time_slice);
+        }
+        TimeSlice {
+            watchdog: Arc::clone(&self.watchdog),
+            plugin_name: plugin_name.to_string(),
+            command_sender: self.command_sender.clone(),
+            started,
+        }
+    }
+
     /// Set up the editor API in a specific JavaScript context
     fn setup_context_api(&self, context: &Context, plugin_name: &str) -> Result<()> {
         let state_snapshot = Arc::clone(&self.state_snapshot);
@@ -3033,6 +3279,7 @@ impl QuickJsBackend {
                 editor.spawnBackgroundProcess = _wrapAsyncThenable("_spawnBackgroundProcessStart", "spawnBackgroundProcess");
                 editor.spawnProcessWait = _wrapAsync("_spawnProcessWaitStart", "spawnProcessWait");
                 editor.getBufferText = _wrapAsync("_getBufferTextStart", "getBufferText");
+                editor.applyEdits = _wrapAsync("_applyEditsStart", "applyEdits");
                 editor.createCompositeBuffer = _wrapAsync("_createCompositeBufferStart", "createCompositeBuffer");
                 editor.getHighlights = _wrapAsync("_getHighlightsStart", "getHighlights");
                 editor.loadPlugin = _wrapAsync("_loadPluginStart", "loadPlugin");
@@ -3151,6 +3398,7 @@ impl QuickJsBackend {
         let wrapped_code = format!("(function() {{ {} }})();", code);
         let wrapped = wrapped_code.as_str();
 
+        let _time_slice = self.time_slice(plugin_name);
         context.with(|ctx| {
             tracing::debug!("execute_js: executing plugin code for '{}'", plugin_name);
 
@@ -3226,6 +3474,7 @@ impl QuickJsBackend {
                         js_string_literal, handler_name, handler_name, handler_name, handler_name
                     );
 
+                    let _time_slice = self.time_slice(&handler.plugin_name);
                     context.with(|ctx| {
                         if let Err(e) = ctx.eval::<(), _>(code.as_bytes()) {
                             log_js_error(&ctx, e, &format!("handler {}", handler_name));
@@ -3298,6 +3547,7 @@ impl QuickJsBackend {
         );
 
         tracing::info!("start_action: evaluating JS code");
+        let _time_slice = self.time_slice(&plugin_name);
         context.with(|ctx| {
             if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
                 log_js_error(&ctx, e, &format!("action {}", action_name));
@@ -3360,6 +3610,7 @@ impl QuickJsBackend {
             action = action_name
         );
 
+        let _time_slice = self.time_slice(&plugin_name);
         context.with(|ctx| {
             // Eval returns a Promise for the async IIFE, which we need to drive
             match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
@@ -3403,6 +3654,7 @@ impl QuickJsBackend {
         // Poll all plugin contexts
         let contexts = self.plugin_contexts.borrow().clone();
         for (name, context) in contexts {
+            let _time_slice = self.time_slice(&name);
             context.with(|ctx| {
                 let count = run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name));
                 if count > 0 {
@@ -3449,6 +3701,7 @@ impl QuickJsBackend {
             return;
         };
 
+        let _time_slice = self.time_slice(&name);
         context.with(|ctx| {
             // Parse JSON string to serde_json::Value
             let json_value: serde_json::Value = match serde_json::from_str(result_json) {
@@ -3526,6 +3779,7 @@ impl QuickJsBackend {
             return;
         };
 
+        let _time_slice = self.time_slice(&name);
         context.with(|ctx| {
             // Get _rejectCallback function from globalThis
             let globals = ctx.globals();
@@ -3569,12 +3823,16 @@ mod tests {
 
     struct TestServiceBridge {
         en_strings: std::sync::Mutex<HashMap<String, String>>,
+        permission: PermissionDecision,
+        limits: fresh_core::services::PluginLimits,
     }
 
     impl TestServiceBridge {
         fn new() -> Self {
             Self {
                 en_strings: std::sync::Mutex::new(HashMap::new()),
+                permission: PermissionDecision::Allow,
+                limits: Default::default(),
             }
         }
     }
@@ -3617,6 +3875,25 @@ mod tests {
         fn config_dir(&self) -> std::path::PathBuf {
             std::path::PathBuf::from("/tmp/config")
         }
+        fn check_permission(
+            &self,
+            _plugin_name: &str,
+            _request: &PermissionRequest,
+        ) -> PermissionDecision {
+            self.permission
+        }
+        fn plugin_limits(&self) -> fresh_core::services::PluginLimits {
+            self.limits
+        }
+    }
+
+    fn create_backend_with_bridge(
+        bridge: TestServiceBridge,
+    ) -> (QuickJsBackend, mpsc::Receiver<PluginCommand>) {
+        let (tx, rx) = mpsc::channel();
+        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
+        let backend = QuickJsBackend::with_state(state_snapshot, tx, Arc::new(bridge)).unwrap();
+        (backend, rx)
     }
 
     #[test]
@@ -4855,6 +5132,7 @@ mod tests {
                     path: Some(PathBuf::from("/test1.txt")),
                     modified: false,
                     length: 100,
+                    revision: 0,
                 },
             );
             state.buffers.insert(
@@ -4864,6 +5142,7 @@ mod tests {
                     path: Some(PathBuf::from("/test2.txt")),
                     modified: true,
                     length: 200,
+                    revision: 0,
                 },
             );
         }
@@ -5506,4 +5785,185 @@ mod tests {
                 assert!(error.contains("nonexistent-plugin"));
             });
     }
+
+    #[test]
+    fn test_time_slice_interrupts_plugin() {
+        let mut bridge = TestServiceBridge::new();
+        bridge.limits.time_slice = Some(Duration::from_millis(50));
+        let (mut backend, rx) = create_backend_with_bridge(bridge);
+
+        assert!(backend.execute_js("while (true) {}", "spin.js").is_err());
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetStatus { message } => assert!(message.contains("'spin'")),
+            cmd => panic!("Expected SetStatus command, got {:?}", cmd),
+        }
+
+        // The next call gets a time slice of its own
+        assert!(backend.execute_js("let x = 1 + 2;", "spin.js").is_ok());
+    }
+
+    #[test]
+    fn test_permission_requests() {
+        let mut bridge = TestServiceBridge::new();
+        bridge.permission = PermissionDecision::Ask;
+        let (mut backend, rx) = create_backend_with_bridge(bridge);
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._content = editor.readFile("/etc/hosts");
+            editor.spawnProcess("git", ["status"]);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Reading fails until the user allows it
+        match rx.try_recv().unwrap() {
+            PluginCommand::RequestPermission {
+                plugin_name,
+                request,
+                callback_id,
+                then,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(request, PermissionRequest::Read("/etc/hosts".into()));
+                assert!(callback_id.is_none() && then.is_none());
+            }
+            cmd => panic!("Expected RequestPermission command, got {:?}", cmd),
+        }
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let content: Option<String> = ctx.globals().get("_content").unwrap();
+                assert_eq!(content, None);
+            });
+
+        // The process is started once the user allows it
+        match rx.try_recv().unwrap() {
+            PluginCommand::RequestPermission {
+                request,
+                callback_id,
+                then,
+                ..
+            } => {
+                assert_eq!(request, PermissionRequest::Subprocess("git".to_string()));
+                assert!(callback_id.is_some());
+                assert!(matches!(
+                    then.as_deref(),
+                    Some(PluginCommand::SpawnProcess { command, .. }) if command == "git"
+                ));
+            }
+            cmd => panic!("Expected RequestPermission command, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_denied_process_throws() {
+        let mut bridge = TestServiceBridge::new();
+        bridge.permission = PermissionDecision::Deny;
+        let (mut backend, rx) = create_backend_with_bridge(bridge);
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            try {
+                editor.spawnProcess("git", ["status"]);
+                globalThis._error = "";
+            } catch (e) {
+                globalThis._error = e.message;
+            }
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        assert!(rx.try_recv().is_err());
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let error: String = ctx.globals().get("_error").unwrap();
+                assert!(error.contains("not allowed to run 'git'"), "{}", error);
+            });
+    }
+
+    #[test]
+    fn test_denied_buffer_file_access() {
+        let mut bridge = TestServiceBridge::new();
+        bridge.permission = PermissionDecision::Deny;
+        let (mut backend, rx) = create_backend_with_bridge(bridge);
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._opened = editor.openFile("/etc/hosts", null, null)
+                || editor.openFileInSplit(1, "/etc/hosts", 1, 1);
+            globalThis._saved = editor.saveBufferToPath(1, "/etc/outside.txt");
+            try {
+                editor.applyEdits([{ path: "/etc/outside.txt", edits: [] }], "Edit");
+                globalThis._error = "";
+            } catch (e) {
+                globalThis._error = e.message;
+            }
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        assert!(rx.try_recv().is_err());
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let opened: bool = ctx.globals().get("_opened").unwrap();
+                let saved: bool = ctx.globals().get("_saved").unwrap();
+                let error: String = ctx.globals().get("_error").unwrap();
+                assert!(!opened && !saved);
+                assert!(
+                    error.contains("not allowed to write '/etc/outside.txt'"),
+                    "{}",
+                    error
+                );
+            });
+    }
+
+    #[test]
+    fn test_save_buffer_to_path_asks_permission() {
+        let mut bridge = TestServiceBridge::new();
+        bridge.permission = PermissionDecision::Ask;
+        let (mut backend, rx) = create_backend_with_bridge(bridge);
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.saveBufferToPath(1, "/etc/outside.txt");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Nothing is saved until the user allows it and the plugin retries
+        match rx.try_recv().unwrap() {
+            PluginCommand::RequestPermission { request, .. } => {
+                assert_eq!(request, PermissionRequest::Write("/etc/outside.txt".into()));
+            }
+            cmd => panic!("Expected RequestPermission command, got {:?}", cmd),
+        }
+        assert!(rx.try_recv().is_err());
+    }
 }
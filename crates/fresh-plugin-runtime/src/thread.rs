@@ -1130,6 +1130,7 @@ async fn load_plugins_from_dir_with_config_internal(
             PluginConfig {
                 enabled: existing_config.enabled,
                 path: Some(path.clone()),
+                permissions: existing_config.permissions.clone(),
             }
         } else {
             // Create new config with default enabled = true
@@ -19,10 +19,10 @@ use fresh_core::api::{
     BufferSavedDiff, CompositeHunk, CompositeLayoutConfig, CompositePaneStyle,
     CompositeSourceConfig, CreateCompositeBufferOptions, CreateVirtualBufferInExistingSplitOptions,
     CreateVirtualBufferInSplitOptions, CreateVirtualBufferOptions, CursorInfo, DirEntry,
-    FormatterPackConfig, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry,
-    LanguagePackConfig, LayoutHints, LspServerPackConfig, SpawnResult, TextPropertiesAtCursor,
-    TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult,
+    FormatterPackConfig, JsDiagnostic, JsFileEdits, JsPosition, JsRange, JsTextEdit,
+    JsTextPropertyEntry, LanguagePackConfig, LayoutHints, LspServerPackConfig, SpawnResult,
+    TextPropertiesAtCursor, TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind,
+    ViewportInfo, VirtualBufferResult,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
@@ -88,6 +88,10 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "JsRange" => Some(JsRange::decl()),
         "JsPosition" => Some(JsPosition::decl()),
 
+        // Edit types
+        "JsTextEdit" => Some(JsTextEdit::decl()),
+        "JsFileEdits" => Some(JsFileEdits::decl()),
+
         // Language pack types
         "LanguagePackConfig" => Some(LanguagePackConfig::decl()),
         "LspServerPackConfig" => Some(LspServerPackConfig::decl()),
@@ -118,6 +122,7 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "JsDiagnostic",                   // Used by getAllDiagnostics
     "JsRange",                        // Used by JsDiagnostic
     "JsPosition",                     // Used by JsRange
+    "JsTextEdit",                     // Used by JsFileEdits.edits
     "ActionSpec",                     // Used by executeActions
     "TsActionPopupAction",            // Used by ActionPopupOptions.actions
     "ActionPopupOptions",             // Used by showActionPopup
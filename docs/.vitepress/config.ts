@@ -37,6 +37,8 @@ export default defineConfig({
           { text: "File Explorer", link: "/features/file-explorer" },
           { text: "Search and Replace", link: "/features/search-replace" },
           { text: "Integrated Terminal", link: "/features/terminal" },
+          { text: "Tasks", link: "/features/tasks" },
+          { text: "Project Environment", link: "/features/project-environment" },
           { text: "LSP Integration", link: "/features/lsp" },
           { text: "Themes", link: "/features/themes" },
           { text: "Remote Editing (SSH)", link: "/features/ssh" },
@@ -90,6 +92,7 @@ export default defineConfig({
         text: "Developer Docs",
         items: [
           { text: "Architecture", link: "/architecture" },
+          { text: "Embedding the Editor", link: "/embedding" },
           { text: "WASM Compatibility", link: "/wasm" },
           { text: "QuickJS Migration", link: "/quickjs" },
           {